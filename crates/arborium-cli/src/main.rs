@@ -49,12 +49,13 @@ fn main() {
 
 fn run(args: Args) -> Result<(), String> {
     // Determine input source and read content
+    // Content is read as bytes: files may contain stray non-UTF-8 sequences
     let (content, filename) = match args.input.as_deref() {
         None | Some("-") => {
             // Read from stdin
-            let mut buffer = String::new();
+            let mut buffer = Vec::new();
             io::stdin()
-                .read_to_string(&mut buffer)
+                .read_to_end(&mut buffer)
                 .map_err(|e| format!("Failed to read stdin: {}", e))?;
             (buffer, None)
         }
@@ -62,24 +63,22 @@ fn run(args: Args) -> Result<(), String> {
             // Check if input is a file path
             let path = Path::new(input);
             if path.exists() && path.is_file() {
-                let content = std::fs::read_to_string(path)
+                let content = std::fs::read(path)
                     .map_err(|e| format!("Failed to read file '{}': {}", input, e))?;
                 (content, Some(input.to_string()))
             } else {
                 // Treat as literal code string
-                (input.to_string(), None)
+                (input.as_bytes().to_vec(), None)
             }
         }
     };
 
     // Detect language
     let detected_lang = if let Some(lang) = &args.lang {
-        Some(lang.as_str())
-    } else if let Some(filename) = &filename {
-        arborium::detect_language(filename)
+        Some(lang.clone())
     } else {
-        // Try to detect from content (shebang)
-        detect_from_content(&content)
+        // Extension, well-known filename, shebang, modeline, then heuristics
+        arborium::detect::detect(filename.as_deref(), &content).map(|found| found.language)
     };
 
     let lang = detected_lang.ok_or_else(|| {
@@ -87,7 +86,7 @@ fn run(args: Args) -> Result<(), String> {
             format!("Unknown language: {}", args.lang.as_ref().unwrap())
         } else if let Some(filename) = &filename {
             format!(
-                "Could not detect language from filename: {}. Use --lang to specify.",
+                "Could not detect language of {}. Use --lang to specify.",
                 filename
             )
         } else {
@@ -99,7 +98,7 @@ fn run(args: Args) -> Result<(), String> {
     if args.html {
         let mut highlighter = Highlighter::new();
         let html = highlighter
-            .highlight(lang, &content)
+            .highlight_bytes(&lang, &content)
             .map_err(|e| format!("Highlighting failed: {}", e))?;
         println!("{}", html);
     } else {
@@ -124,42 +123,12 @@ fn run(args: Args) -> Result<(), String> {
         };
 
         let mut highlighter = AnsiHighlighter::new(theme.clone());
+        let content = String::from_utf8_lossy(&content);
         let ansi = highlighter
-            .highlight(lang, &content)
+            .highlight(&lang, &content)
             .map_err(|e| format!("Highlighting failed: {}", e))?;
         println!("{}", ansi);
     }
 
     Ok(())
 }
-
-/// Detect language from content (e.g., shebang lines)
-fn detect_from_content(content: &str) -> Option<&'static str> {
-    let first_line = content.lines().next()?;
-
-    // Check for shebang
-    if let Some(shebang) = first_line.strip_prefix("#!") {
-        let shebang = shebang.trim();
-
-        // Common interpreters
-        if shebang.contains("python") {
-            return Some("python");
-        } else if shebang.contains("node") || shebang.contains("nodejs") {
-            return Some("javascript");
-        } else if shebang.contains("ruby") {
-            return Some("ruby");
-        } else if shebang.contains("perl") {
-            return Some("perl");
-        } else if shebang.contains("bash") || shebang.contains("/sh") {
-            return Some("bash");
-        } else if shebang.contains("zsh") {
-            return Some("zsh");
-        } else if shebang.contains("fish") {
-            return Some("fish");
-        } else if shebang.contains("php") {
-            return Some("php");
-        }
-    }
-
-    None
-}
@@ -0,0 +1,1024 @@
+//! Language detection from file names and contents.
+//!
+//! [`detect`] runs a fixed pipeline, stopping at the first step that yields a
+//! language:
+//!
+//! 1. the file extension (`main.rs`), checking the content for extensions
+//!    that unrelated languages share (`.v` is Verilog or Coq); a compound
+//!    extension (`view.blade.php`) wins over its last part,
+//! 2. well-known file names (`Dockerfile`, `CMakeLists.txt`, `.bashrc`),
+//! 3. the shebang line (`#!/usr/bin/env python3`),
+//! 4. a vim or emacs modeline (`# vim: set ft=ruby:`, `-*- mode: lua -*-`),
+//! 5. content heuristics (`<?php`, `package main`, ...).
+//!
+//! Mapping extensions and modeline names to language IDs depends on which
+//! grammars are available, so callers pass a `resolve` function for that.
+//! Everything else is self-contained, which lets the native crate and the
+//! browser host share one implementation.
+
+/// How many leading bytes to inspect when checking for binary content.
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// How many lines at the start and end of a file may hold a modeline.
+const MODELINE_LINES: usize = 5;
+
+/// The step of the [`detect`] pipeline that identified a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetectionMethod {
+    /// The file extension.
+    Extension,
+    /// A well-known file name, like `Dockerfile`.
+    Filename,
+    /// The `#!` line.
+    Shebang,
+    /// A vim or emacs modeline.
+    Modeline,
+    /// A guess based on the content.
+    Heuristic,
+}
+
+impl DetectionMethod {
+    /// A short lowercase name for the method (e.g. `"shebang"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetectionMethod::Extension => "extension",
+            DetectionMethod::Filename => "filename",
+            DetectionMethod::Shebang => "shebang",
+            DetectionMethod::Modeline => "modeline",
+            DetectionMethod::Heuristic => "heuristic",
+        }
+    }
+}
+
+/// A detected language and how it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    /// The language ID.
+    pub language: String,
+    /// Which step of the pipeline produced `language`.
+    pub method: DetectionMethod,
+}
+
+/// Detect the language of a file from its name and contents.
+///
+/// `resolve` maps a file extension or language name (e.g. `"rs"`, `"python"`)
+/// to a supported language ID, or `None`. Content that [`looks_binary`] is
+/// never detected, except by file name.
+///
+/// # Example
+///
+/// ```rust
+/// use arborium_highlight::detect::{DetectionMethod, detect};
+///
+/// let resolve = |name: &str| (name == "rs").then(|| "rust".to_string());
+///
+/// let found = detect(Some("src/main.rs"), b"fn main() {}", &resolve).unwrap();
+/// assert_eq!(found.language, "rust");
+/// assert_eq!(found.method, DetectionMethod::Extension);
+///
+/// let found = detect(None, b"#!/usr/bin/env python3\n", &resolve).unwrap();
+/// assert_eq!(found.method, DetectionMethod::Shebang);
+/// ```
+pub fn detect(
+    filename: Option<&str>,
+    content: &[u8],
+    resolve: &dyn Fn(&str) -> Option<String>,
+) -> Option<Detection> {
+    let found = |language: String, method| Some(Detection { language, method });
+
+    if let Some(filename) = filename {
+        let name = base_name(filename);
+        if let Some(language) =
+            compound_extension(name).and_then(|ext| resolve(&ext.to_lowercase()))
+        {
+            return found(language, DetectionMethod::Extension);
+        }
+        if let Some(ext) = extension(name).map(str::to_lowercase) {
+            match contested_extension(&ext, content) {
+                // Content that belongs to the other language never falls
+                // back to the extension, even if that language isn't available
+                Some(other) => {
+                    if let Some(language) = resolve(other) {
+                        return found(language, DetectionMethod::Heuristic);
+                    }
+                }
+                None => {
+                    if let Some(language) = resolve(&ext) {
+                        return found(language, DetectionMethod::Extension);
+                    }
+                }
+            }
+        }
+        match contested_filename(name, content) {
+            Some(other) => {
+                if let Some(language) = resolve(other) {
+                    return found(language, DetectionMethod::Heuristic);
+                }
+            }
+            None => {
+                if let Some(language) = detect_from_filename(filename) {
+                    return found(language.to_string(), DetectionMethod::Filename);
+                }
+            }
+        }
+    }
+
+    if looks_binary(content) {
+        return None;
+    }
+    let text = String::from_utf8_lossy(content);
+
+    if let Some(language) = text.lines().next().and_then(detect_from_shebang) {
+        return found(language.to_string(), DetectionMethod::Shebang);
+    }
+    if let Some(language) = detect_from_modeline(&text).and_then(|name| resolve(&name)) {
+        return found(language, DetectionMethod::Modeline);
+    }
+    if let Some(language) = detect_from_heuristics(&text) {
+        return found(language.to_string(), DetectionMethod::Heuristic);
+    }
+
+    None
+}
+
+/// Detect the language of a file from its path alone: the extension, then
+/// well-known file names.
+///
+/// This is the first two steps of [`detect`], for when there's no content
+/// to look at, like the `+++ b/src/main.rs` header of a diff.
+///
+/// # Example
+///
+/// ```rust
+/// use arborium_highlight::detect::detect_from_path;
+///
+/// let resolve = |name: &str| (name == "rs").then(|| "rust".to_string());
+///
+/// assert_eq!(detect_from_path("b/src/main.rs", &resolve), Some("rust".into()));
+/// assert_eq!(detect_from_path("a/Dockerfile", &resolve), Some("dockerfile".into()));
+/// assert_eq!(detect_from_path("rust", &resolve), None);
+/// ```
+pub fn detect_from_path(path: &str, resolve: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+    let name = base_name(path);
+    compound_extension(name)
+        .and_then(|ext| resolve(&ext.to_lowercase()))
+        .or_else(|| extension(name).and_then(|ext| resolve(&ext.to_lowercase())))
+        .or_else(|| detect_from_filename(path).map(str::to_string))
+}
+
+/// The language names an injection's captured `language` text may stand
+/// for, most literal first.
+///
+/// Documents write names the way people do, like Org's `#+begin_src C`,
+/// and some put the language among other attributes, like AsciiDoc's
+/// `[source,rust,linenums]`. This is `language` itself, then lowercased,
+/// then each of its comma-separated attributes, lowercased.
+///
+/// # Example
+///
+/// ```rust
+/// use arborium_highlight::detect::injection_language_candidates;
+///
+/// assert_eq!(injection_language_candidates("C"), ["C", "c"]);
+/// assert_eq!(
+///     injection_language_candidates("source,rust"),
+///     ["source,rust", "source", "rust"]
+/// );
+/// ```
+pub fn injection_language_candidates(language: &str) -> Vec<String> {
+    let mut candidates = vec![language.to_string()];
+    let mut push = |name: String| {
+        if !name.is_empty() && !candidates.contains(&name) {
+            candidates.push(name);
+        }
+    };
+    push(language.to_lowercase());
+    if language.contains(',') {
+        for attribute in language.split(',') {
+            push(attribute.trim().to_lowercase());
+        }
+    }
+    candidates
+}
+
+/// Whether `content` looks like binary data rather than text.
+///
+/// Uses the same heuristic as git: a NUL byte in the first few kilobytes.
+pub fn looks_binary(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_SNIFF_LEN)].contains(&0)
+}
+
+/// Detect a language from a shebang line (`#!/usr/bin/env python3`).
+///
+/// Returns `None` if `first_line` isn't a shebang or names an unknown
+/// interpreter.
+pub fn detect_from_shebang(first_line: &str) -> Option<&'static str> {
+    let shebang = first_line.strip_prefix("#!")?.trim();
+
+    // Common interpreters
+    if shebang.contains("python") {
+        Some("python")
+    } else if shebang.contains("node") || shebang.contains("nodejs") {
+        Some("javascript")
+    } else if shebang.contains("ruby") {
+        Some("ruby")
+    } else if shebang.contains("perl") {
+        Some("perl")
+    } else if shebang.contains("bash") || shebang.contains("/sh") {
+        Some("bash")
+    } else if shebang.contains("zsh") {
+        Some("zsh")
+    } else if shebang.contains("fish") {
+        Some("fish")
+    } else if shebang.contains("php") {
+        Some("php")
+    } else if shebang.contains("tclsh") || shebang.contains("expect") {
+        Some("tcl")
+    } else if shebang.contains("swipl") {
+        Some("prolog")
+    } else if shebang.contains("janet") {
+        Some("janet")
+    } else {
+        None
+    }
+}
+
+/// Detect a language from a well-known file name (`Dockerfile`, `.zshrc`).
+///
+/// A few of git's files have names too generic to go by alone, like
+/// `.git/config`, so those are matched on the end of the path.
+pub fn detect_from_filename(name: &str) -> Option<&'static str> {
+    if let Some(language) = git_path(name) {
+        return Some(language);
+    }
+    let name = base_name(name);
+    Some(match name {
+        "Dockerfile" | "Containerfile" | "dockerfile" => "dockerfile",
+        "CMakeLists.txt" => "cmake",
+        "Makefile" | "makefile" | "GNUmakefile" => "make",
+        "Gemfile" | "Rakefile" | "Vagrantfile" | "Podfile" | "Brewfile" => "ruby",
+        ".bashrc" | ".bash_profile" | ".bash_logout" | ".profile" | "PKGBUILD" => "bash",
+        ".zshrc" | ".zshenv" | ".zprofile" | ".zlogin" | ".zlogout" | "zshrc" | "zshenv" => "zsh",
+        "config.nu" | "env.nu" | "login.nu" => "nu",
+        ".vimrc" | ".gvimrc" | "_vimrc" | "_gvimrc" | "vimrc" | "gvimrc" | ".exrc" => "vim",
+        ".emacs" | "_emacs" => "elisp",
+        "Cargo.lock" | "Pipfile" | "poetry.lock" | "uv.lock" => "toml",
+        "BUILD" | "BUILD.bazel" | "WORKSPACE" | "WORKSPACE.bazel" | "Tiltfile" => "starlark",
+        "Jenkinsfile" => "groovy",
+        "meson.build" | "meson_options.txt" => "meson",
+        "nginx.conf" => "nginx",
+        "build.ninja" => "ninja",
+        "Caddyfile" => "caddy",
+        "ssh_config" | "sshd_config" => "ssh-config",
+        "COMMIT_EDITMSG" | "MERGE_MSG" | "SQUASH_MSG" | "TAG_EDITMSG" | "EDIT_DESCRIPTION" => {
+            "git-commit"
+        }
+        "git-rebase-todo" => "git-rebase",
+        ".gitconfig" | ".gitmodules" => "git-config",
+        ".gitignore" => "gitignore",
+        ".env" => "dotenv",
+        ".editorconfig" => "editorconfig",
+        "project.godot" => "godot-resource",
+        "flake.lock" | "tsconfig.json" => "json",
+        ".babelrc" | ".eslintrc" => "json5",
+        _ if name.starts_with("Dockerfile.") || name.ends_with(".Dockerfile") => "dockerfile",
+        // .env.local, .env.production
+        _ if name.starts_with(".env.") => "dotenv",
+        _ => return None,
+    })
+}
+
+/// Find a vim or emacs modeline and return the language name it sets.
+///
+/// Vim modelines (`vim: set ft=python:`, `vi: filetype=sh`) are looked for
+/// in the first and last few lines, emacs ones (`-*- mode: lua -*-`,
+/// `-*- lua -*-`) on the first line (or second, after a shebang). The name
+/// is returned lowercased, for the caller to resolve.
+pub fn detect_from_modeline(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+
+    for line in lines.iter().take(2) {
+        if let Some(mode) = emacs_mode(line) {
+            return Some(mode);
+        }
+    }
+
+    let head = lines.iter().take(MODELINE_LINES);
+    let tail = lines.iter().skip(MODELINE_LINES).rev().take(MODELINE_LINES);
+    head.chain(tail).find_map(|line| vim_filetype(line))
+}
+
+/// Guess a language from characteristic content.
+///
+/// Only distinctive, unambiguous markers are checked, so this returns `None`
+/// far more often than it guesses wrong.
+pub fn detect_from_heuristics(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim_start_matches('\u{feff}').trim_start();
+    let first_line = trimmed.lines().next().unwrap_or("");
+    let starts_with_ignore_case = |prefix: &str| {
+        trimmed
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    };
+
+    if trimmed.starts_with("<?php") {
+        return Some("php");
+    }
+    if trimmed.starts_with("<?xml") {
+        return Some("xml");
+    }
+    if starts_with_ignore_case("<!doctype html") || starts_with_ignore_case("<html") {
+        return Some("html");
+    }
+    if first_line.starts_with("diff --git ") || first_line.starts_with("--- a/") {
+        return Some("diff");
+    }
+    if first_line.starts_with("FROM ") && text.lines().any(is_dockerfile_instruction) {
+        return Some("dockerfile");
+    }
+
+    let has_line = |predicate: &dyn Fn(&str) -> bool| text.lines().any(|l| predicate(l.trim()));
+    if has_line(&|l| l.starts_with("package ")) && has_line(&|l| l.starts_with("func ")) {
+        return Some("go");
+    }
+    if has_line(&|l| l.starts_with("fn main(") || l.starts_with("use std::")) {
+        return Some("rust");
+    }
+    if has_line(&|l| l.starts_with("#include <") || l.starts_with("#include \"")) {
+        let cpp = has_line(&|l| {
+            l.starts_with("namespace ") || l.starts_with("template<") || l.contains("std::")
+        });
+        return Some(if cpp { "cpp" } else { "c" });
+    }
+    if has_line(&|l| l.starts_with("def ") && l.ends_with(':'))
+        || has_line(&|l| l.starts_with("from ") && l.contains(" import "))
+    {
+        return Some("python");
+    }
+
+    None
+}
+
+/// For an extension used by more than one language, the language `content`
+/// is written in if it isn't the one the extension usually means.
+///
+/// `.v` is Verilog, unless the file reads like a Coq proof script. `.ts` is
+/// TypeScript, unless it's a Qt Linguist translation file, which is XML.
+/// `.scm` is Scheme, unless it's a tree-sitter query file (`highlights.scm`).
+/// `.pl` is Perl, unless it reads like Prolog. `.m` is MATLAB, unless it
+/// reads like Objective-C; a file with nothing to go on, like an empty one,
+/// stays MATLAB, since a MATLAB script can be plain statements while an
+/// Objective-C file nearly always has an `#import` or `@implementation`.
+fn contested_extension(ext: &str, content: &[u8]) -> Option<&'static str> {
+    match ext {
+        "v" if !looks_binary(content) && looks_like_coq(&String::from_utf8_lossy(content)) => {
+            Some("coq")
+        }
+        "ts" if content.trim_ascii_start().starts_with(b"<") => Some("xml"),
+        "scm" if !looks_binary(content) && looks_like_query(&String::from_utf8_lossy(content)) => {
+            Some("query")
+        }
+        "pl" if !looks_binary(content) && looks_like_prolog(&String::from_utf8_lossy(content)) => {
+            Some("prolog")
+        }
+        "m" if !looks_binary(content) && looks_like_objc(&String::from_utf8_lossy(content)) => {
+            Some("objc")
+        }
+        _ => None,
+    }
+}
+
+/// Like [`contested_extension`], for well-known file names.
+///
+/// `.eslintrc` is JSON5, unless it's YAML, which ESLint reads it as too: a
+/// file that doesn't start with `{` or a comment is taken to be YAML.
+fn contested_filename(name: &str, content: &[u8]) -> Option<&'static str> {
+    match name {
+        ".eslintrc" => {
+            let content = content.trim_ascii_start();
+            let json5 = content.is_empty()
+                || [b"{".as_slice(), b"//", b"/*"]
+                    .iter()
+                    .any(|start| content.starts_with(start));
+            (!json5 && !looks_binary(content)).then_some("yaml")
+        }
+        _ => None,
+    }
+}
+
+/// Whether a line of `text` starts with a Coq vernacular command. These are
+/// capitalized, unlike any Verilog keyword.
+fn looks_like_coq(text: &str) -> bool {
+    const COMMANDS: &[&str] = &[
+        "Require ",
+        "From ",
+        "Theorem ",
+        "Lemma ",
+        "Definition ",
+        "Fixpoint ",
+        "Inductive ",
+        "Proof.",
+        "Qed.",
+    ];
+    text.lines()
+        .map(str::trim_start)
+        .any(|line| COMMANDS.iter().any(|c| line.starts_with(c)))
+}
+
+/// Whether `text` has Prolog clause syntax (a `:- module(...)` directive, a
+/// `:-` rule, a DCG `-->` rule) and no Perl declarations
+/// (`my $x`, `use strict;`, `sub name {`). Neither `:-` nor `-->` appears
+/// in Perl outside of strings and regexes.
+fn looks_like_prolog(text: &str) -> bool {
+    const PERL: &[&str] = &[
+        "my ",
+        "our ",
+        "use strict",
+        "use warnings",
+        "sub ",
+        "package ",
+    ];
+    let lines = || text.lines().map(str::trim);
+    let clauses = lines().any(|line| {
+        line.starts_with(":-")
+            || line.ends_with(":-")
+            || line.contains(" :- ")
+            || line.contains(" --> ")
+    });
+    clauses && !lines().any(|line| PERL.iter().any(|p| line.starts_with(p)))
+}
+
+/// Whether `text` has an Objective-C directive (`#import`, `@interface`,
+/// `@implementation`, ...), or a message send like `[center postNotification:n]`
+/// and no MATLAB structure (`%` comments, `function`, `classdef`, a lone
+/// `end`). MATLAB has `[a b:c]` too, so a message send alone isn't enough.
+fn looks_like_objc(text: &str) -> bool {
+    const DIRECTIVES: &[&str] = &[
+        "#import ",
+        "@interface ",
+        "@implementation ",
+        "@protocol ",
+        "@end",
+        "@property",
+        "@synthesize ",
+    ];
+    const MATLAB: &[&str] = &["%", "function ", "classdef "];
+    let lines = || text.lines().map(str::trim);
+    if lines().any(|line| DIRECTIVES.iter().any(|d| line.starts_with(d))) {
+        return true;
+    }
+    // `[receiver selector:...]`, or `] selector:...` when the receiver is
+    // itself a send
+    let is_selector = |word: Option<&str>| {
+        word.and_then(|w| w.split_once(':'))
+            .is_some_and(|(name, _)| is_identifier(name))
+    };
+    let message_send = lines().any(|line| {
+        line.match_indices(['[', ']']).any(|(i, bracket)| {
+            let mut words = line[i + 1..].split_whitespace();
+            match bracket {
+                "[" => words.next().is_some_and(is_identifier) && is_selector(words.next()),
+                _ => is_selector(words.next()),
+            }
+        })
+    });
+    message_send && !lines().any(|line| line == "end" || MATLAB.iter().any(|m| line.starts_with(m)))
+}
+
+/// Whether `word` is a C-style identifier.
+fn is_identifier(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether `text` has a tree-sitter query capture (`(identifier) @variable`)
+/// or predicate (`(#eq? @name "x")`). Scheme only uses `@` right after a
+/// comma (`,@`), and `#` followed by a name ending in `?` or `!` isn't
+/// Scheme syntax.
+fn looks_like_query(text: &str) -> bool {
+    let capture = text.match_indices(" @").any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + 2..].chars().next();
+        matches!(before, Some(')' | ']' | '"')) && after.is_some_and(|c| c.is_ascii_alphabetic())
+    });
+    let predicate = text.match_indices("(#").any(|(i, _)| {
+        let name: String = text[i + 2..]
+            .chars()
+            .take_while(|c| !c.is_whitespace())
+            .collect();
+        name.len() > 1 && (name.ends_with('?') || name.ends_with('!'))
+    });
+    capture || predicate
+}
+
+/// Git's config and exclude files that only their directory identifies,
+/// with the language of each.
+const GIT_PATHS: &[(&str, &str)] = &[
+    (".git/config", "git-config"),
+    (".config/git/config", "git-config"),
+    (".git/info/exclude", "gitignore"),
+    (".config/git/ignore", "gitignore"),
+];
+
+/// The language of `path` if it's one of [`GIT_PATHS`].
+fn git_path(path: &str) -> Option<&'static str> {
+    let path = path.replace('\\', "/");
+    GIT_PATHS
+        .iter()
+        .find(|(suffix, _)| {
+            path.strip_suffix(suffix)
+                .is_some_and(|parent| parent.is_empty() || parent.ends_with('/'))
+        })
+        .map(|&(_, language)| language)
+}
+
+/// Strip any directory components from a path.
+fn base_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// The extension of a file name, if it has one (`.bashrc` doesn't).
+fn extension(name: &str) -> Option<&str> {
+    let (stem, ext) = name.rsplit_once('.')?;
+    (!stem.is_empty() && !ext.is_empty()).then_some(ext)
+}
+
+/// The last two extensions of a file name, if it has a stem before them
+/// (`blade.php` for `welcome.blade.php`).
+fn compound_extension(name: &str) -> Option<&str> {
+    let (rest, _) = name.rsplit_once('.')?;
+    let (stem, _) = rest.rsplit_once('.')?;
+    (!stem.is_empty()).then(|| &name[stem.len() + 1..])
+}
+
+fn is_dockerfile_instruction(line: &str) -> bool {
+    const INSTRUCTIONS: &[&str] = &[
+        "RUN ",
+        "CMD ",
+        "COPY ",
+        "ADD ",
+        "ENTRYPOINT ",
+        "WORKDIR ",
+        "ENV ",
+        "EXPOSE ",
+    ];
+    INSTRUCTIONS.iter().any(|i| line.starts_with(i))
+}
+
+/// Parse `-*- mode: lua -*-` or `-*- lua -*-`.
+fn emacs_mode(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("-*-")?;
+    let (vars, _) = rest.split_once("-*-")?;
+    let vars = vars.trim();
+
+    if !vars.contains(':') {
+        return non_empty(vars);
+    }
+    vars.split(';').find_map(|var| {
+        let (key, value) = var.split_once(':')?;
+        if key.trim().eq_ignore_ascii_case("mode") {
+            non_empty(value.trim())
+        } else {
+            None
+        }
+    })
+}
+
+/// Parse `vim: set ft=python:` or `vi: filetype=sh`.
+fn vim_filetype(line: &str) -> Option<String> {
+    // Like vim, only accept markers at the start of the line or after a space
+    let start = ["vim:", "vi:", "ex:"]
+        .iter()
+        .flat_map(|marker| {
+            line.match_indices(marker)
+                .filter(|(i, _)| {
+                    line[..*i]
+                        .chars()
+                        .next_back()
+                        .is_none_or(char::is_whitespace)
+                })
+                .map(|(i, _)| i + marker.len())
+        })
+        .min()?;
+    line[start..]
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .find_map(|option| {
+            let value = option
+                .strip_prefix("filetype=")
+                .or_else(|| option.strip_prefix("ft="))
+                .or_else(|| option.strip_prefix("syntax="))
+                .or_else(|| option.strip_prefix("syn="))?;
+            non_empty(value)
+        })
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(name: &str) -> Option<String> {
+        let id = match name {
+            "rs" | "rust" => "rust",
+            "py" | "python" => "python",
+            "rb" | "ruby" => "ruby",
+            "lua" => "lua",
+            "sh" | "bash" => "bash",
+            "v" | "verilog" => "verilog",
+            "vim" => "vim",
+            "ts" | "mts" | "cts" | "typescript" => "typescript",
+            "php" => "php",
+            "blade.php" => "blade",
+            _ => return None,
+        };
+        Some(id.to_string())
+    }
+
+    fn method(filename: Option<&str>, content: &str) -> Option<(String, DetectionMethod)> {
+        detect(filename, content.as_bytes(), &resolve).map(|d| (d.language, d.method))
+    }
+
+    #[test]
+    fn test_pipeline_order() {
+        use DetectionMethod::*;
+
+        // The extension wins over the content
+        assert_eq!(
+            method(Some("a/b.rs"), "#!/usr/bin/env python\n"),
+            Some(("rust".into(), Extension))
+        );
+        assert_eq!(
+            method(Some("Dockerfile"), "FROM alpine\n"),
+            Some(("dockerfile".into(), Filename))
+        );
+        // Unknown extension falls through to the content
+        assert_eq!(
+            method(Some("script.tmp"), "#!/bin/sh\n"),
+            Some(("bash".into(), Shebang))
+        );
+        assert_eq!(
+            method(None, "local x = 1\n-- vim: set ft=lua:\n"),
+            Some(("lua".into(), Modeline))
+        );
+        assert_eq!(
+            method(None, "<?php echo 1;"),
+            Some(("php".into(), Heuristic))
+        );
+        assert_eq!(method(None, "hello world"), None);
+    }
+
+    #[test]
+    fn test_contested_extensions() {
+        use DetectionMethod::*;
+
+        let verilog = "module top(input clk);\nendmodule\n";
+        let coq = "Require Import List.\n\nLemma nil_app : forall l, [] ++ l = l.\nProof. reflexivity. Qed.\n";
+        assert_eq!(
+            method(Some("rtl/top.v"), verilog),
+            Some(("verilog".into(), Extension))
+        );
+        // Coq isn't available here, so nothing claims the file
+        assert_eq!(method(Some("theories/Lists.v"), coq), None);
+
+        let with_coq = |name: &str| match name {
+            "coq" => Some("coq".to_string()),
+            _ => resolve(name),
+        };
+        let found = detect(Some("theories/Lists.v"), coq.as_bytes(), &with_coq).unwrap();
+        assert_eq!(found.language, "coq");
+        assert_eq!(found.method, Heuristic);
+
+        let typescript = "import type { User } from \"./user\";\n";
+        let linguist =
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!DOCTYPE TS>\n<TS version=\"2.1\">\n";
+        assert_eq!(
+            method(Some("src/user.ts"), typescript),
+            Some(("typescript".into(), Extension))
+        );
+        assert_eq!(
+            method(Some("src/loader.mts"), typescript),
+            Some(("typescript".into(), Extension))
+        );
+        assert_eq!(method(Some("i18n/app_de.ts"), linguist), None);
+
+        // `.scm` is Scheme, which SICP-style material uses far more often
+        // than tree-sitter's query files do. Queries are recognized by their
+        // captures and predicates instead of claiming the extension.
+        let with_scheme = |name: &str| match name {
+            "scm" | "scheme" => Some("scheme".to_string()),
+            "query" => Some("query".to_string()),
+            _ => resolve(name),
+        };
+        let detect_scm = |content: &str| {
+            let found = detect(Some("src/file.scm"), content.as_bytes(), &with_scheme).unwrap();
+            (found.language, found.method)
+        };
+        assert_eq!(
+            detect_scm("(define (square x) (* x x))\n(display `(1 ,@(list 2 3)))\n"),
+            ("scheme".into(), Extension)
+        );
+        assert_eq!(
+            detect_scm("(function_item name: (identifier) @function)\n"),
+            ("query".into(), Heuristic)
+        );
+        assert_eq!(
+            detect_scm("((identifier) @constant\n  (#match? @constant \"^[A-Z]\"))\n"),
+            ("query".into(), Heuristic)
+        );
+        assert_eq!(
+            detect_scm("[\"if\" \"else\"] @keyword.conditional\n"),
+            ("query".into(), Heuristic)
+        );
+
+        // `.pl` is Perl unless the clauses say otherwise
+        let with_prolog = |name: &str| match name {
+            "pl" | "perl" => Some("perl".to_string()),
+            "pro" | "prolog" => Some("prolog".to_string()),
+            _ => resolve(name),
+        };
+        let detect_pl = |content: &str| {
+            let found = detect(Some("src/file.pl"), content.as_bytes(), &with_prolog).unwrap();
+            (found.language, found.method)
+        };
+        assert_eq!(
+            detect_pl("use strict;\nmy @files = glob('*.txt');\nprint \"$_\\n\" for @files;\n"),
+            ("perl".into(), Extension)
+        );
+        assert_eq!(
+            detect_pl(":- module(family, [parent/2]).\n\nparent(tom, bob).\n"),
+            ("prolog".into(), Heuristic)
+        );
+        assert_eq!(
+            detect_pl("ancestor(X, Y) :-\n    parent(X, Z),\n    ancestor(Z, Y).\n"),
+            ("prolog".into(), Heuristic)
+        );
+        assert_eq!(
+            detect_pl("greeting --> [hello], name.\n"),
+            ("prolog".into(), Heuristic)
+        );
+        // A heredoc line that happens to start with `:-` is still Perl
+        assert_eq!(
+            detect_pl("my $face = <<'END';\n:-)\nEND\nprint $face;\n"),
+            ("perl".into(), Extension)
+        );
+
+        // `.m` is MATLAB unless it reads like Objective-C
+        let with_objc = |name: &str| match name {
+            "m" | "matlab" => Some("matlab".to_string()),
+            "objc" => Some("objc".to_string()),
+            _ => resolve(name),
+        };
+        let detect_m = |content: &str| {
+            let found = detect(Some("src/file.m"), content.as_bytes(), &with_objc).unwrap();
+            (found.language, found.method)
+        };
+        assert_eq!(
+            detect_m(
+                "function y = smooth(x, n)\n% Moving average\ny = conv(x, ones(1, n) / n, 'same');\nend\n"
+            ),
+            ("matlab".into(), Extension)
+        );
+        assert_eq!(
+            detect_m("classdef Point\n    properties\n        X = 0\n    end\nend\n"),
+            ("matlab".into(), Extension)
+        );
+        assert_eq!(
+            detect_m("#import <Foundation/Foundation.h>\n\n@implementation Point\n@end\n"),
+            ("objc".into(), Heuristic)
+        );
+        assert_eq!(
+            detect_m(
+                "int main(void) {\n    [[NSApplication sharedApplication] setDelegate:self];\n}\n"
+            ),
+            ("objc".into(), Heuristic)
+        );
+        // A range in a MATLAB matrix looks like a message send
+        assert_eq!(
+            detect_m("for i = 1:n\n    v = [v i:n];\nend\n"),
+            ("matlab".into(), Extension)
+        );
+        // Nothing to go on keeps the extension's usual meaning
+        assert_eq!(detect_m(""), ("matlab".into(), Extension));
+    }
+
+    #[test]
+    fn test_well_known_filenames() {
+        assert_eq!(detect_from_filename("CMakeLists.txt"), Some("cmake"));
+        assert_eq!(detect_from_filename("src/GNUmakefile"), Some("make"));
+        assert_eq!(detect_from_filename("/home/me/.zshrc"), Some("zsh"));
+        assert_eq!(
+            detect_from_filename("/home/me/.config/nushell/env.nu"),
+            Some("nu")
+        );
+        assert_eq!(detect_from_filename("dotfiles/vim/vimrc"), Some("vim"));
+        assert_eq!(detect_from_filename("_vimrc"), Some("vim"));
+        assert_eq!(detect_from_filename("Dockerfile.dev"), Some("dockerfile"));
+        assert_eq!(
+            detect_from_filename("game/project.godot"),
+            Some("godot-resource")
+        );
+        assert_eq!(
+            detect_from_filename(".git/COMMIT_EDITMSG"),
+            Some("git-commit")
+        );
+        assert_eq!(
+            detect_from_filename(".git/rebase-merge/git-rebase-todo"),
+            Some("git-rebase")
+        );
+        assert_eq!(
+            detect_from_filename("/home/me/.gitconfig"),
+            Some("git-config")
+        );
+        assert_eq!(detect_from_filename("web/.gitignore"), Some("gitignore"));
+        assert_eq!(detect_from_filename("app/.env"), Some("dotenv"));
+        assert_eq!(detect_from_filename(".editorconfig"), Some("editorconfig"));
+        assert_eq!(detect_from_filename("app/.babelrc"), Some("json5"));
+        assert_eq!(detect_from_filename("README"), None);
+        assert_eq!(extension(".bashrc"), None);
+        assert_eq!(extension("archive.tar.gz"), Some("gz"));
+    }
+
+    #[test]
+    fn test_contested_filenames() {
+        use DetectionMethod::*;
+
+        let with_yaml = |name: &str| match name {
+            "yaml" => Some("yaml".to_string()),
+            _ => resolve(name),
+        };
+        let method = |content: &str| {
+            detect(Some("web/.eslintrc"), content.as_bytes(), &with_yaml)
+                .map(|d| (d.language, d.method))
+        };
+
+        assert_eq!(
+            method("{ root: true, }\n"),
+            Some(("json5".into(), Filename))
+        );
+        assert_eq!(
+            method("// legacy config\n{ \"root\": true }\n"),
+            Some(("json5".into(), Filename))
+        );
+        assert_eq!(method(""), Some(("json5".into(), Filename)));
+        assert_eq!(
+            method("root: true\nextends:\n  - eslint:recommended\n"),
+            Some(("yaml".into(), Heuristic))
+        );
+        // YAML never falls back to the file name
+        assert_eq!(detect(Some(".eslintrc"), b"root: true\n", &resolve), None);
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(
+            detect_from_path("b/src/lib.RS", &resolve),
+            Some("rust".into())
+        );
+        assert_eq!(detect_from_path("a/Gemfile", &resolve), Some("ruby".into()));
+        assert_eq!(detect_from_path("notes.txt", &resolve), None);
+        assert_eq!(detect_from_path("python", &resolve), None);
+    }
+
+    #[test]
+    fn test_env_files() {
+        // The extension step finds no language called "production", so the
+        // name decides
+        assert_eq!(
+            method(Some(".env.production"), "DATABASE_URL=postgres://db/app\n"),
+            Some(("dotenv".into(), DetectionMethod::Filename))
+        );
+        assert_eq!(detect_from_filename("deploy/.env.local"), Some("dotenv"));
+        // A real extension still wins
+        assert_eq!(
+            method(Some(".env.sh"), "export PORT=8080\n"),
+            Some(("bash".into(), DetectionMethod::Extension))
+        );
+        assert_eq!(detect_from_filename(".envrc"), None);
+    }
+
+    #[test]
+    fn test_git_paths() {
+        assert_eq!(detect_from_filename(".git/config"), Some("git-config"));
+        assert_eq!(
+            detect_from_filename("/home/me/.config/git/config"),
+            Some("git-config")
+        );
+        assert_eq!(
+            detect_from_filename("C:\\src\\app\\.git\\info\\exclude"),
+            Some("gitignore")
+        );
+        assert_eq!(detect_from_filename("/home/me/.ssh/config"), None);
+        assert_eq!(detect_from_filename("not.git/config"), None);
+        assert_eq!(
+            method(Some("repo/.git/config"), "[core]\n\tbare = false\n"),
+            Some(("git-config".into(), DetectionMethod::Filename))
+        );
+    }
+
+    #[test]
+    fn test_injection_language_candidates() {
+        assert_eq!(injection_language_candidates("rust"), ["rust"]);
+        assert_eq!(
+            injection_language_candidates("Python"),
+            ["Python", "python"]
+        );
+        assert_eq!(
+            injection_language_candidates("source%linenums, Ruby ,indent=0"),
+            [
+                "source%linenums, Ruby ,indent=0",
+                "source%linenums, ruby ,indent=0",
+                "source%linenums",
+                "ruby",
+                "indent=0"
+            ]
+        );
+        assert_eq!(injection_language_candidates(",rust"), [",rust", "rust"]);
+    }
+
+    #[test]
+    fn test_compound_extensions() {
+        assert_eq!(compound_extension("welcome.blade.php"), Some("blade.php"));
+        assert_eq!(compound_extension("jquery.min.js"), Some("min.js"));
+        assert_eq!(compound_extension("main.rs"), None);
+        assert_eq!(compound_extension(".blade.php"), None);
+
+        // The compound extension wins over its last part, which is still
+        // used when the compound one isn't known
+        assert_eq!(
+            method(Some("resources/views/welcome.blade.PHP"), "<div></div>"),
+            Some(("blade".into(), DetectionMethod::Extension))
+        );
+        assert_eq!(
+            method(Some("public/index.php"), "<?php echo 1;"),
+            Some(("php".into(), DetectionMethod::Extension))
+        );
+        assert_eq!(
+            method(Some("lib/tasks.rake.rb"), ""),
+            Some(("ruby".into(), DetectionMethod::Extension))
+        );
+        assert_eq!(
+            detect_from_path("b/views/welcome.blade.php", &resolve),
+            Some("blade".into())
+        );
+    }
+
+    #[test]
+    fn test_modelines() {
+        assert_eq!(
+            detect_from_modeline("# -*- mode: Ruby; coding: utf-8 -*-\n"),
+            Some("ruby".into())
+        );
+        assert_eq!(
+            detect_from_modeline("#!/bin/sh\n# -*- python -*-\n"),
+            Some("python".into())
+        );
+        assert_eq!(
+            detect_from_modeline("x\n\n\n\n\n\n\n\n/* vi: filetype=c */"),
+            Some("c".into())
+        );
+        assert_eq!(
+            method(None, "set number\n\" vim: set ft=vim:\n"),
+            Some(("vim".into(), DetectionMethod::Modeline))
+        );
+        assert_eq!(detect_from_modeline("no modeline here"), None);
+        assert_eq!(detect_from_modeline("index: ft=rust"), None);
+
+        // Unresolvable names don't count as a detection
+        assert_eq!(method(None, "# vim: ft=whatever\n"), None);
+    }
+
+    #[test]
+    fn test_heuristics() {
+        assert_eq!(
+            detect_from_heuristics("package main\n\nfunc main() {}\n"),
+            Some("go")
+        );
+        assert_eq!(
+            detect_from_heuristics("#include <stdio.h>\nint main() {}"),
+            Some("c")
+        );
+        assert_eq!(
+            detect_from_heuristics("#include <vector>\nstd::vector<int> v;"),
+            Some("cpp")
+        );
+        assert_eq!(
+            detect_from_heuristics("<!DOCTYPE html>\n<html></html>"),
+            Some("html")
+        );
+        assert_eq!(
+            detect_from_heuristics("FROM rust:1\nRUN cargo build\n"),
+            Some("dockerfile")
+        );
+        assert_eq!(detect_from_heuristics("SELECT 1;"), None);
+    }
+
+    #[test]
+    fn test_binary_content_is_not_detected() {
+        assert_eq!(method(None, "#!/bin/sh\n\0\0"), None);
+        // ...unless the file name alone is enough
+        assert!(method(Some("Gemfile"), "\0").is_some());
+    }
+}
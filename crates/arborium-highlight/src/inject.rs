@@ -0,0 +1,149 @@
+//! Turning a grammar's injections into the texts to parse.
+
+use std::borrow::Cow;
+
+use crate::types::Injection;
+
+/// Text injected into a document, ready to parse with its language's grammar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InjectedText<'a> {
+    /// Byte offset of `text` in the document.
+    pub start: u32,
+
+    /// The language to parse `text` with.
+    pub language: String,
+
+    /// The text to parse. Spans and injections it yields are relative to
+    /// `start`.
+    pub text: Cow<'a, [u8]>,
+}
+
+/// Collect the texts to parse for `injections` into `source`.
+///
+/// Each injection is its own text, except combined ones: all fragments with
+/// the same language become one text, running from the first fragment to the
+/// end of the last with everything in between blanked out. A jinja2 template
+/// thus reaches the HTML grammar as one document, with spaces where its
+/// `{% ... %}` tags were, and keeps its offsets into `source`. Line breaks
+/// are kept, so line comments end where they did.
+///
+/// Injections outside `source` are skipped.
+pub fn injected_texts(source: &[u8], injections: Vec<Injection>) -> Vec<InjectedText<'_>> {
+    let mut texts = Vec::new();
+    let mut combined: Vec<(String, Vec<(usize, usize)>, usize)> = Vec::new();
+
+    for injection in injections {
+        let (start, end) = (injection.start as usize, injection.end as usize);
+        if start >= end || end > source.len() {
+            continue;
+        }
+
+        if !injection.combined {
+            texts.push(InjectedText {
+                start: injection.start,
+                language: injection.language,
+                text: Cow::Borrowed(&source[start..end]),
+            });
+        } else if let Some((_, ranges, _)) = combined
+            .iter_mut()
+            .find(|(language, ..)| *language == injection.language)
+        {
+            ranges.push((start, end));
+        } else {
+            // Keep the combined text where its first fragment came
+            combined.push((injection.language, vec![(start, end)], texts.len()));
+        }
+    }
+
+    for (language, mut ranges, index) in combined.into_iter().rev() {
+        ranges.sort_unstable();
+        let start = ranges[0].0;
+        let end = ranges.iter().map(|&(_, end)| end).max().unwrap_or(start);
+
+        let mut text: Vec<u8> = source[start..end]
+            .iter()
+            .map(|&b| if b == b'\n' { b'\n' } else { b' ' })
+            .collect();
+        for (from, to) in ranges {
+            text[from - start..to - start].copy_from_slice(&source[from..to]);
+        }
+
+        texts.insert(
+            index,
+            InjectedText {
+                start: start as u32,
+                language,
+                text: Cow::Owned(text),
+            },
+        );
+    }
+
+    texts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn injection(start: u32, end: u32, language: &str, combined: bool) -> Injection {
+        Injection {
+            start,
+            end,
+            language: language.into(),
+            include_children: false,
+            combined,
+        }
+    }
+
+    #[test]
+    fn test_separate_injections() {
+        let source = b"<a>{{ x }}<b>";
+        let texts = injected_texts(
+            source,
+            vec![
+                injection(0, 3, "html", false),
+                injection(10, 13, "html", false),
+            ],
+        );
+        assert_eq!(texts.len(), 2);
+        assert_eq!(&*texts[1].text, b"<b>");
+        assert_eq!(texts[1].start, 10);
+    }
+
+    #[test]
+    fn test_combined_injections_are_stitched() {
+        let source = "{% x %}<div {% if y %}id=\"a\"{% endif %}>\n{{ z }}</div>";
+        let texts = injected_texts(
+            source.as_bytes(),
+            vec![
+                injection(7, 12, "html", true),
+                injection(22, 28, "html", true),
+                injection(39, 41, "html", true),
+                injection(48, 54, "html", true),
+            ],
+        );
+        assert_eq!(texts.len(), 1);
+        assert_eq!(texts[0].start, 7);
+        assert_eq!(
+            std::str::from_utf8(&texts[0].text).unwrap(),
+            "<div           id=\"a\"           >\n       </div>"
+        );
+    }
+
+    #[test]
+    fn test_combined_text_keeps_its_place() {
+        let source = b"abcdef";
+        let texts = injected_texts(
+            source,
+            vec![
+                injection(0, 1, "a", false),
+                injection(1, 2, "b", true),
+                injection(2, 3, "c", false),
+                injection(4, 5, "b", true),
+            ],
+        );
+        let languages: Vec<&str> = texts.iter().map(|t| t.language.as_str()).collect();
+        assert_eq!(languages, ["a", "b", "c"]);
+        assert_eq!(&*texts[1].text, b"b  e");
+    }
+}
@@ -99,15 +99,21 @@
 //!
 //! See [`HtmlFormat`] for examples and use cases.
 
+pub mod detect;
+mod inject;
 mod render;
 mod types;
 
 #[cfg(feature = "tree-sitter")]
 pub mod tree_sitter;
 
+pub use inject::{InjectedText, injected_texts};
 pub use render::{
-    AnsiOptions, ThemedSpan, html_escape, spans_to_ansi, spans_to_ansi_with_options, spans_to_html,
-    spans_to_themed, write_spans_as_ansi, write_spans_as_html,
+    AnsiOptions, ClassStyle, Decoration, DecorationContext, Decorator, Diagnostic, EscapeMode,
+    HtmlChunks, HtmlOptions, Severity, ThemedSpan, html_escape, html_escape_with_mode,
+    spans_to_ansi, spans_to_ansi_with_options, spans_to_html, spans_to_html_bytes,
+    spans_to_html_chunks, spans_to_html_with_options, spans_to_themed, write_spans_as_ansi,
+    write_spans_as_html,
 };
 pub use types::{HighlightError, Injection, ParseResult, Span};
 
@@ -184,6 +190,40 @@ pub trait GrammarProvider {
     fn get(&mut self, language: &str) -> impl Future<Output = Option<&mut Self::Grammar>>;
 }
 
+/// Maps the language an injection asks for to the one to highlight it with.
+///
+/// Injections usually name their language, but some queries can only capture
+/// something it follows from: the diff grammar captures the file path in a
+/// `+++ b/src/main.rs` header. A resolver sees the captured text of every
+/// injection before its grammar is looked up, and returns the language to
+/// use instead, or `None` to keep it.
+///
+/// Closures taking `&str` and returning `Option<String>` are resolvers.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use arborium_highlight::detect::detect_from_path;
+///
+/// highlighter.set_injection_resolver(|language: &str| {
+///     detect_from_path(language, &|ext| (ext == "rs").then(|| "rust".to_string()))
+/// });
+/// ```
+pub trait InjectionResolver {
+    /// The language to highlight an injection of `language` with, if it
+    /// isn't `language` itself.
+    fn resolve(&self, language: &str) -> Option<String>;
+}
+
+impl<F> InjectionResolver for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn resolve(&self, language: &str) -> Option<String> {
+        self(language)
+    }
+}
+
 /// HTML output format for syntax highlighting.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HtmlFormat {
@@ -248,6 +288,9 @@ pub struct HighlightConfig {
 
     /// HTML output format (custom elements vs class-based spans).
     pub html_format: HtmlFormat,
+
+    /// Additional HTML rendering options.
+    pub html_options: HtmlOptions,
 }
 
 impl Default for HighlightConfig {
@@ -255,6 +298,7 @@ impl Default for HighlightConfig {
         Self {
             max_injection_depth: 3,
             html_format: HtmlFormat::default(),
+            html_options: HtmlOptions::default(),
         }
     }
 }
@@ -267,18 +311,20 @@ impl Default for HighlightConfig {
 struct HighlighterCore<P: GrammarProvider> {
     provider: P,
     config: HighlightConfig,
+    resolver: Option<Box<dyn InjectionResolver + Send>>,
 }
 
 impl<P: GrammarProvider> HighlighterCore<P> {
     fn new(provider: P) -> Self {
-        Self {
-            provider,
-            config: HighlightConfig::default(),
-        }
+        Self::with_config(provider, HighlightConfig::default())
     }
 
     fn with_config(provider: P, config: HighlightConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            resolver: None,
+        }
     }
 
     /// Highlight and return raw spans for the full document,
@@ -319,7 +365,12 @@ impl<P: GrammarProvider> HighlighterCore<P> {
     /// The main highlight function - written once, used by both wrappers.
     async fn highlight(&mut self, language: &str, source: &str) -> Result<String, HighlightError> {
         let spans = self.highlight_spans(language, source).await?;
-        Ok(spans_to_html(source, spans, &self.config.html_format))
+        Ok(spans_to_html_with_options(
+            source,
+            spans,
+            &self.config.html_format,
+            &self.config.html_options,
+        ))
     }
 
     /// Process injections recursively.
@@ -335,43 +386,46 @@ impl<P: GrammarProvider> HighlighterCore<P> {
             return;
         }
 
-        for injection in injections {
-            let start = injection.start as usize;
-            let end = injection.end as usize;
-
-            if end <= source.len() && start < end {
-                // Try to get grammar for injected language
-                if let Some(inj_grammar) = self.provider.get(&injection.language).await {
-                    let injected_text = &source[start..end];
-                    let result = inj_grammar.parse(injected_text);
-
-                    // Adjust offsets and add spans
-                    let adjusted_spans: Vec<Span> = result
-                        .spans
-                        .into_iter()
-                        .map(|mut s| {
-                            s.start += base_offset + injection.start;
-                            s.end += base_offset + injection.start;
-                            s
-                        })
-                        .collect();
-                    all_spans.extend(adjusted_spans);
-
-                    // Recurse into nested injections
-                    if !result.injections.is_empty() {
-                        // Box the recursive call to avoid infinite type size
-                        Box::pin(self.process_injections(
-                            injected_text,
-                            result.injections,
-                            base_offset + injection.start,
-                            remaining_depth - 1,
-                            all_spans,
-                        ))
-                        .await;
-                    }
+        for injected in injected_texts(source.as_bytes(), injections) {
+            let Ok(injected_text) = std::str::from_utf8(&injected.text) else {
+                continue;
+            };
+            let language = self
+                .resolver
+                .as_ref()
+                .and_then(|resolver| resolver.resolve(&injected.language))
+                .unwrap_or(injected.language);
+
+            // Try to get grammar for injected language
+            if let Some(inj_grammar) = self.provider.get(&language).await {
+                let result = inj_grammar.parse(injected_text);
+
+                // Adjust offsets and add spans
+                let adjusted_spans: Vec<Span> = result
+                    .spans
+                    .into_iter()
+                    .map(|mut s| {
+                        s.start += base_offset + injected.start;
+                        s.end += base_offset + injected.start;
+                        s
+                    })
+                    .collect();
+                all_spans.extend(adjusted_spans);
+
+                // Recurse into nested injections
+                if !result.injections.is_empty() {
+                    // Box the recursive call to avoid infinite type size
+                    Box::pin(self.process_injections(
+                        injected_text,
+                        result.injections,
+                        base_offset + injected.start,
+                        remaining_depth - 1,
+                        all_spans,
+                    ))
+                    .await;
                 }
-                // If grammar not available, skip this injection silently
             }
+            // If grammar not available, skip this injection silently
         }
     }
 }
@@ -413,6 +467,12 @@ impl<P: GrammarProvider> SyncHighlighter<P> {
         &mut self.core.provider
     }
 
+    /// Resolve the languages injections ask for with `resolver` before
+    /// looking them up. See [`InjectionResolver`].
+    pub fn set_injection_resolver(&mut self, resolver: impl InjectionResolver + Send + 'static) {
+        self.core.resolver = Some(Box::new(resolver));
+    }
+
     /// Highlight source code synchronously and return HTML.
     ///
     /// # Panics
@@ -517,6 +577,17 @@ impl<P: GrammarProvider> AsyncHighlighter<P> {
         &mut self.core.provider
     }
 
+    /// Get the highlighting configuration.
+    pub fn config(&self) -> &HighlightConfig {
+        &self.core.config
+    }
+
+    /// Resolve the languages injections ask for with `resolver` before
+    /// looking them up. See [`InjectionResolver`].
+    pub fn set_injection_resolver(&mut self, resolver: impl InjectionResolver + Send + 'static) {
+        self.core.resolver = Some(Box::new(resolver));
+    }
+
     /// Highlight source code asynchronously.
     pub async fn highlight(
         &mut self,
@@ -525,6 +596,16 @@ impl<P: GrammarProvider> AsyncHighlighter<P> {
     ) -> Result<String, HighlightError> {
         self.core.highlight(language, source).await
     }
+
+    /// Highlight source code asynchronously and return raw spans
+    /// (including injections), for custom or incremental rendering.
+    pub async fn highlight_spans(
+        &mut self,
+        language: &str,
+        source: &str,
+    ) -> Result<Vec<Span>, HighlightError> {
+        self.core.highlight_spans(language, source).await
+    }
 }
 
 /// Create a no-op waker for sync polling.
@@ -613,6 +694,49 @@ mod tests {
                                 end: 5,
                                 language: "inner".into(),
                                 include_children: false,
+                                combined: false,
+                            }],
+                        },
+                    },
+                ),
+                (
+                    "inner",
+                    MockGrammar {
+                        result: ParseResult {
+                            spans: vec![Span {
+                                start: 0,
+                                end: 5,
+                                capture: "string".into(),
+                                pattern_index: 0,
+                            }],
+                            injections: vec![],
+                        },
+                    },
+                ),
+            ]
+            .into(),
+        };
+
+        let mut highlighter = SyncHighlighter::new(provider);
+        let html = highlighter.highlight("outer", "hello").unwrap();
+        assert_eq!(html, "<a-s>hello</a-s>");
+    }
+
+    #[test]
+    fn test_injection_resolver() {
+        let provider = MockProvider {
+            grammars: [
+                (
+                    "outer",
+                    MockGrammar {
+                        result: ParseResult {
+                            spans: vec![],
+                            injections: vec![Injection {
+                                start: 0,
+                                end: 5,
+                                language: "b/src/inner.in".into(),
+                                include_children: false,
+                                combined: false,
                             }],
                         },
                     },
@@ -636,10 +760,60 @@ mod tests {
         };
 
         let mut highlighter = SyncHighlighter::new(provider);
+        assert_eq!(highlighter.highlight("outer", "hello").unwrap(), "hello");
+
+        highlighter.set_injection_resolver(|language: &str| {
+            language.ends_with(".in").then(|| "inner".to_string())
+        });
         let html = highlighter.highlight("outer", "hello").unwrap();
         assert_eq!(html, "<a-s>hello</a-s>");
     }
 
+    #[test]
+    fn test_combined_injection() {
+        let fragment = |start, end| Injection {
+            start,
+            end,
+            language: "inner".into(),
+            include_children: false,
+            combined: true,
+        };
+        let span = |start, end| Span {
+            start,
+            end,
+            capture: "string".into(),
+            pattern_index: 0,
+        };
+        let provider = MockProvider {
+            grammars: [
+                (
+                    "outer",
+                    MockGrammar {
+                        result: ParseResult {
+                            spans: vec![],
+                            injections: vec![fragment(1, 3), fragment(6, 8)],
+                        },
+                    },
+                ),
+                (
+                    "inner",
+                    MockGrammar {
+                        // Offsets into the stitched text, which starts at 1
+                        result: ParseResult {
+                            spans: vec![span(0, 2), span(5, 7)],
+                            injections: vec![],
+                        },
+                    },
+                ),
+            ]
+            .into(),
+        };
+
+        let mut highlighter = SyncHighlighter::new(provider);
+        let html = highlighter.highlight("outer", "<ab{x}cd>").unwrap();
+        assert_eq!(html, "&lt;<a-s>ab</a-s>{x}<a-s>cd</a-s>&gt;");
+    }
+
     #[test]
     fn test_unsupported_language() {
         let provider = MockProvider {
@@ -17,7 +17,10 @@ use arborium_theme::{
     Theme, capture_to_slot, slot_to_highlight_index, tag_for_capture, tag_to_name,
 };
 use std::collections::HashMap;
+use std::fmt;
 use std::io::{self, Write};
+use std::ops::{Range, RangeInclusive};
+use std::sync::Arc;
 
 /// A span with a theme style index for rendering.
 ///
@@ -183,6 +186,433 @@ fn normalize_and_coalesce(spans: Vec<Span>) -> Vec<NormalizedSpan> {
     coalesced
 }
 
+/// Options controlling HTML rendering behavior.
+#[derive(Debug, Clone)]
+pub struct HtmlOptions {
+    /// If true, run an output optimization pass before emitting HTML:
+    /// adjacent runs with the same tag are merged into a single element and
+    /// zero-width runs are dropped. Pure-whitespace runs keep their wrapper,
+    /// since whether it shows (a background, an underline) is up to the
+    /// stylesheet.
+    ///
+    /// This never changes the rendered text content. Enabled by default.
+    pub optimize: bool,
+    /// The context the output will be embedded in, which decides how text
+    /// and attribute values are escaped. See [`EscapeMode`].
+    pub escape: EscapeMode,
+    /// Optional callback wrapping highlighted spans in links or elements
+    /// carrying extra attributes. See [`Decorator`].
+    pub decorator: Option<Decorator>,
+    /// If set, each line wrapper gets `id="{prefix}L{n}"` (with `n` starting
+    /// at 1), so pages can deep-link to `#{prefix}L{n}`. When a page holds
+    /// several code blocks, give each one a distinct prefix (e.g. derived
+    /// from a block id) to keep ids unique.
+    pub line_anchors: Option<String>,
+    /// If true and `line_anchors` is set, each line starts with an
+    /// `<a class="line-anchor" href="#{prefix}L{n}">` self-link the page
+    /// can style, e.g. as a clickable gutter.
+    pub line_self_links: bool,
+    /// If true, each line starts with its number. The number goes inside the
+    /// self-link if there is one, and in a `<span class="line-number">`
+    /// otherwise.
+    pub line_numbers: bool,
+    /// One-based line ranges whose wrappers get the extra `emphasized`
+    /// class.
+    pub emphasized_lines: Vec<RangeInclusive<usize>>,
+    /// Diagnostics to overlay on the highlighted code, e.g. syntax errors
+    /// reported by the parser. See [`Diagnostic`].
+    pub diagnostics: Vec<Diagnostic>,
+    /// Class naming scheme for highlighted spans. Compatibility styles
+    /// ignore the [`HtmlFormat`] and emit the class names of another
+    /// highlighter instead, so existing stylesheets keep working. See
+    /// [`ClassStyle`].
+    pub class_style: ClassStyle,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            optimize: true,
+            escape: EscapeMode::default(),
+            decorator: None,
+            line_anchors: None,
+            line_self_links: false,
+            line_numbers: false,
+            emphasized_lines: Vec::new(),
+            diagnostics: Vec::new(),
+            class_style: ClassStyle::default(),
+        }
+    }
+}
+
+impl HtmlOptions {
+    /// Give each line wrapper an `id="{prefix}L{n}"` anchor. See the
+    /// `line_anchors` field.
+    pub fn line_anchors(mut self, prefix: &str) -> Self {
+        self.line_anchors = Some(prefix.to_string());
+        self
+    }
+
+    /// Start each anchored line with a self-link.
+    pub fn line_self_links(mut self, enabled: bool) -> Self {
+        self.line_self_links = enabled;
+        self
+    }
+
+    /// Start each line with its number.
+    pub fn line_numbers(mut self, enabled: bool) -> Self {
+        self.line_numbers = enabled;
+        self
+    }
+
+    /// Mark the given one-based line ranges as emphasized.
+    pub fn emphasize_lines(
+        mut self,
+        lines: impl IntoIterator<Item = RangeInclusive<usize>>,
+    ) -> Self {
+        self.emphasized_lines.extend(lines);
+        self
+    }
+
+    /// Overlay the given diagnostics.
+    pub fn diagnostics(mut self, diagnostics: impl IntoIterator<Item = Diagnostic>) -> Self {
+        self.diagnostics.extend(diagnostics);
+        self
+    }
+
+    /// Emit the class names of the given highlighter.
+    pub fn class_style(mut self, style: ClassStyle) -> Self {
+        self.class_style = style;
+        self
+    }
+
+    /// Whether each line is wrapped in its own `<span class="line">`.
+    ///
+    /// Lines are wrapped as soon as any line-level option is used.
+    fn wraps_lines(&self) -> bool {
+        self.line_anchors.is_some() || self.line_numbers || !self.emphasized_lines.is_empty()
+    }
+}
+
+/// A message attached to a range of source, drawn as a squiggle in HTML
+/// output.
+///
+/// The range is wrapped in `<span class="diag diag-{severity}" title="...">`,
+/// split wherever it partially overlaps highlighted spans so that nesting
+/// stays well-formed. Theme CSS styles these as wavy underlines (see
+/// `Theme::to_css`). Empty ranges are ignored, and ranges that start or end
+/// inside a character are widened to cover all of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Byte range of the source the diagnostic applies to.
+    pub byte_range: Range<usize>,
+    /// How serious the diagnostic is.
+    pub severity: Severity,
+    /// Message shown as a tooltip. Omitted from the output if empty.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic.
+    pub fn new(byte_range: Range<usize>, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            byte_range,
+            severity,
+            message: message.into(),
+        }
+    }
+
+    /// Opening tag of the wrapper around the diagnostic's range.
+    fn html_tag(&self) -> String {
+        let mut tag = format!("<span class=\"diag {}\"", self.severity.class_name());
+        if !self.message.is_empty() {
+            tag.push_str(" title=\"");
+            push_attribute_value(&mut tag, &self.message);
+            tag.push('"');
+        }
+        tag.push('>');
+        tag
+    }
+}
+
+/// Severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// An error, e.g. a syntax error.
+    Error,
+    /// A warning.
+    Warning,
+    /// An informational message.
+    Info,
+    /// A hint or suggestion.
+    Hint,
+}
+
+impl Severity {
+    /// The CSS class for this severity (e.g. `"diag-error"`).
+    pub fn class_name(self) -> &'static str {
+        match self {
+            Severity::Error => "diag-error",
+            Severity::Warning => "diag-warning",
+            Severity::Info => "diag-info",
+            Severity::Hint => "diag-hint",
+        }
+    }
+}
+
+/// How HTML output is escaped, depending on where it will be embedded.
+///
+/// In every mode, U+0000 and other control characters (except tab, line feed
+/// and carriage return) are replaced with U+FFFD, since neither HTML nor XML
+/// allows them in documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EscapeMode {
+    /// Regular HTML element content. `&`, `<`, `>`, `"` and `'` are escaped
+    /// in text, and void elements are written as `<br>`.
+    #[default]
+    Html,
+    /// The output goes inside a quoted attribute value (e.g. a `title`
+    /// tooltip or a `data-` attribute). The markup is escaped once more, so
+    /// the attribute decodes to the [`EscapeMode::Html`] output, and tabs and
+    /// line breaks are written as character references so they survive
+    /// attribute-value normalization.
+    Attribute,
+    /// XHTML or other XML documents. Escaping matches [`EscapeMode::Html`],
+    /// using only entities predefined by XML, and void elements are written
+    /// in self-closing form (`<br/>`).
+    ///
+    /// The renderer writes no void elements of its own, so for now this
+    /// gives the same output as [`EscapeMode::Html`].
+    Xhtml,
+}
+
+impl EscapeMode {
+    /// Whether void elements are written in self-closing form (`<br/>`).
+    pub fn self_closing(self) -> bool {
+        self == EscapeMode::Xhtml
+    }
+}
+
+/// Class naming scheme for highlighted spans, for reusing stylesheets
+/// written for other highlighters.
+///
+/// Compatibility styles only rename slot markup; line, diagnostic and
+/// decoration wrappers keep their own classes, so leave those options off
+/// for output that must match the other highlighter exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClassStyle {
+    /// Arborium's own markup, as selected by [`HtmlFormat`].
+    #[default]
+    Arborium,
+    /// highlight.js classes: `<span class="hljs-keyword">`. Wrap the
+    /// output with [`ClassStyle::wrap_code_block`] to get the
+    /// `<pre><code class="hljs language-rust">` structure its themes target.
+    HighlightJs,
+    /// syntect's default (`ClassStyle::Spaced`) classes, one per TextMate
+    /// scope atom: `<span class="entity name function">`. These match
+    /// the stylesheets generated by syntect's `css_for_theme_with_class_style`.
+    Syntect,
+}
+
+impl ClassStyle {
+    /// The `class` attribute value for a theme slot tag (see
+    /// `ThemeSlot::tag`), or `None` if the slot is rendered as plain text in
+    /// this style.
+    ///
+    /// Always `None` for [`ClassStyle::Arborium`], whose markup depends on
+    /// the [`HtmlFormat`].
+    pub fn class_for_tag(self, tag: &str) -> Option<&'static str> {
+        match self {
+            ClassStyle::Arborium => None,
+            ClassStyle::HighlightJs => hljs_class(tag),
+            ClassStyle::Syntect => syntect_class(tag),
+        }
+    }
+
+    /// Wrap rendered HTML in the `<pre>`/`<code>` structure this style's
+    /// stylesheets expect, tagged with `language` if known.
+    pub fn wrap_code_block(self, language: Option<&str>, html: &str) -> String {
+        let language = language.map(language_class_token);
+        match (self, language) {
+            (ClassStyle::Arborium, Some(lang)) => {
+                format!("<pre><code class=\"language-{lang}\">{html}</code></pre>")
+            }
+            (ClassStyle::Arborium, None) => format!("<pre><code>{html}</code></pre>"),
+            (ClassStyle::HighlightJs, Some(lang)) => {
+                format!("<pre><code class=\"hljs language-{lang}\">{html}</code></pre>")
+            }
+            (ClassStyle::HighlightJs, None) => {
+                format!("<pre><code class=\"hljs\">{html}</code></pre>")
+            }
+            (ClassStyle::Syntect, Some(lang)) => format!(
+                "<pre class=\"code\"><code><span class=\"source {lang}\">{html}</span></code></pre>"
+            ),
+            (ClassStyle::Syntect, None) => {
+                format!("<pre class=\"code\"><code>{html}</code></pre>")
+            }
+        }
+    }
+}
+
+/// Lowercase `language`, replacing anything that can't appear in a class
+/// name with `-`.
+fn language_class_token(language: &str) -> String {
+    language
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' | '-' | '_' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '-',
+        })
+        .collect()
+}
+
+/// highlight.js class for a slot tag. Dotted highlight.js scopes such as
+/// `title.function` are written the way highlight.js writes them
+/// (`hljs-title function_`).
+fn hljs_class(tag: &str) -> Option<&'static str> {
+    Some(match tag {
+        "k" => "hljs-keyword",
+        "f" => "hljs-title function_",
+        "s" => "hljs-string",
+        "c" => "hljs-comment",
+        "t" => "hljs-type",
+        "v" => "hljs-variable",
+        "co" => "hljs-literal",
+        "n" => "hljs-number",
+        "o" => "hljs-operator",
+        "p" => "hljs-punctuation",
+        "pr" => "hljs-property",
+        "at" => "hljs-attr",
+        "tg" => "hljs-name",
+        "m" => "hljs-meta",
+        "l" => "hljs-symbol",
+        "ns" | "cr" => "hljs-title class_",
+        "tt" => "hljs-section",
+        "st" => "hljs-strong",
+        "em" => "hljs-emphasis",
+        "tu" => "hljs-link",
+        "tl" => "hljs-code",
+        "da" => "hljs-addition",
+        "dd" => "hljs-deletion",
+        "eb" => "hljs-subst",
+        _ => return None,
+    })
+}
+
+/// syntect class for a slot tag: the scope atoms of the closest TextMate
+/// scope, separated by spaces.
+fn syntect_class(tag: &str) -> Option<&'static str> {
+    Some(match tag {
+        "k" => "keyword",
+        "f" => "entity name function",
+        "s" => "string",
+        "c" => "comment",
+        "t" => "entity name type",
+        "v" => "variable",
+        "co" => "constant",
+        "n" => "constant numeric",
+        "o" => "keyword operator",
+        "p" => "punctuation",
+        "pr" => "variable other member",
+        "at" => "entity other attribute-name",
+        "tg" => "entity name tag",
+        "m" => "support macro",
+        "l" => "entity name label",
+        "ns" => "entity name namespace",
+        "cr" => "entity name type",
+        "tt" => "markup heading",
+        "st" => "markup bold",
+        "em" => "markup italic",
+        "tu" => "markup underline link",
+        "tl" => "markup raw",
+        "tx" => "markup strikethrough",
+        "da" => "markup inserted",
+        "dd" => "markup deleted",
+        "eb" => "meta embedded",
+        "er" => "invalid illegal",
+        _ => return None,
+    })
+}
+
+/// A contiguous run of source text styled by (at most) one tag.
+#[derive(Debug, Clone, Copy)]
+struct HtmlRun {
+    start: usize,
+    end: usize,
+    tag: Option<&'static str>,
+    /// Index of the decoration wrapping this run, if any.
+    decoration: Option<usize>,
+}
+
+/// Merge adjacent runs with the same tag and drop zero-width runs.
+fn optimize_html_runs(runs: Vec<HtmlRun>) -> Vec<HtmlRun> {
+    let mut optimized: Vec<HtmlRun> = Vec::with_capacity(runs.len());
+
+    for run in runs {
+        if run.start >= run.end {
+            continue;
+        }
+
+        match optimized.last_mut() {
+            Some(last)
+                if last.tag == run.tag
+                    && last.decoration == run.decoration
+                    && last.end == run.start =>
+            {
+                last.end = run.end
+            }
+            _ => optimized.push(run),
+        }
+    }
+
+    optimized
+}
+
+/// Strip trailing `\n` bytes, mirroring `str::trim_end_matches('\n')`.
+fn trim_trailing_newlines(source: &[u8]) -> &[u8] {
+    let mut end = source.len();
+    while end > 0 && source[end - 1] == b'\n' {
+        end -= 1;
+    }
+    &source[..end]
+}
+
+/// Whether `index` falls on a character boundary of `source`. Offsets past
+/// the end count as boundaries; invalid UTF-8 is treated byte by byte.
+fn is_char_boundary(source: &[u8], index: usize) -> bool {
+    // Any byte other than a continuation byte starts a character
+    index == 0 || index >= source.len() || (source[index] as i8) >= -0x40
+}
+
+/// Diagnostic ranges with their pre-rendered opening tags, outermost first.
+///
+/// Ranges are clamped to `source` and widened to character boundaries, so
+/// runs split at them never cut a character in half. Empty ranges are
+/// dropped.
+fn diagnostic_ranges(source: &[u8], diagnostics: &[Diagnostic]) -> Vec<(Range<usize>, String)> {
+    let mut ranges: Vec<(Range<usize>, String)> = diagnostics
+        .iter()
+        .filter_map(|d| {
+            let mut start = d.byte_range.start.min(source.len());
+            let mut end = d.byte_range.end.min(source.len());
+            while !is_char_boundary(source, start) {
+                start -= 1;
+            }
+            while !is_char_boundary(source, end) {
+                end += 1;
+            }
+            (start < end).then(|| (start..end, d.html_tag()))
+        })
+        .collect();
+    ranges.sort_by(|a, b| {
+        a.0.start
+            .cmp(&b.0.start)
+            .then_with(|| b.0.end.cmp(&a.0.end))
+    });
+    ranges
+}
+
 /// Deduplicate spans and convert to HTML.
 ///
 /// This handles:
@@ -195,11 +625,466 @@ fn normalize_and_coalesce(spans: Vec<Span>) -> Vec<NormalizedSpan> {
 /// Note: Trailing newlines are trimmed from the source to avoid extra whitespace
 /// when the output is embedded in `<pre><code>` tags.
 pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> String {
+    spans_to_html_with_options(source, spans, format, &HtmlOptions::default())
+}
+
+/// HTML rendering with additional configuration options.
+pub fn spans_to_html_with_options(
+    source: &str,
+    spans: Vec<Span>,
+    format: &HtmlFormat,
+    options: &HtmlOptions,
+) -> String {
+    // Trim trailing newlines from source to avoid extra whitespace in code blocks
+    let source = source.trim_end_matches('\n');
+
+    let diagnostics = diagnostic_ranges(source.as_bytes(), &options.diagnostics);
+    let (runs, decorations) = html_runs(source.as_bytes(), spans, options, &diagnostics);
+    let mut writer = HtmlWriter::new(format, options, &decorations, diagnostics);
+    let mut html = String::with_capacity(source.len() * 2);
+    for run in runs {
+        writer.write_run(&mut html, &source[run.start..run.end], run);
+    }
+    writer.finish(&mut html);
+    html
+}
+
+/// HTML rendering for source that may not be valid UTF-8.
+///
+/// Span offsets refer to bytes of the original `source`. Invalid UTF-8
+/// sequences are rendered as U+FFFD (`�`), while the text around them keeps
+/// its highlighting. Use this together with byte-oriented parsing (e.g.
+/// `CompiledGrammar::parse_bytes`) for files that may contain stray
+/// non-UTF-8 bytes, such as a latin-1 comment.
+pub fn spans_to_html_bytes(
+    source: &[u8],
+    spans: Vec<Span>,
+    format: &HtmlFormat,
+    options: &HtmlOptions,
+) -> String {
+    if let Ok(source) = std::str::from_utf8(source) {
+        return spans_to_html_with_options(source, spans, format, options);
+    }
+
     // Trim trailing newlines from source to avoid extra whitespace in code blocks
+    let source = trim_trailing_newlines(source);
+
+    let diagnostics = diagnostic_ranges(source, &options.diagnostics);
+    let (runs, decorations) = html_runs(source, spans, options, &diagnostics);
+    let mut writer = HtmlWriter::new(format, options, &decorations, diagnostics);
+    let mut html = String::with_capacity(source.len() * 2);
+    for run in runs {
+        let text = String::from_utf8_lossy(&source[run.start..run.end]);
+        writer.write_run(&mut html, &text, run);
+    }
+    writer.finish(&mut html);
+    html
+}
+
+/// Render spans as HTML in chunks of roughly `chunk_bytes` source bytes.
+///
+/// Concatenating every chunk yields exactly the output of
+/// [`spans_to_html_with_options`]. This lets callers interleave rendering of
+/// large documents with other work (e.g. yielding to the browser event loop
+/// between chunks) and stop early if the result is no longer needed.
+pub fn spans_to_html_chunks<'a>(
+    source: &'a str,
+    spans: Vec<Span>,
+    format: &HtmlFormat,
+    options: &HtmlOptions,
+    chunk_bytes: usize,
+) -> HtmlChunks<'a> {
     let source = source.trim_end_matches('\n');
+    let diagnostics = diagnostic_ranges(source.as_bytes(), &options.diagnostics);
+    let (runs, decorations) = html_runs(source.as_bytes(), spans, options, &diagnostics);
+    HtmlChunks {
+        source,
+        runs: runs.into_iter().peekable(),
+        writer: HtmlWriter::new(format, options, &decorations, diagnostics),
+        chunk_bytes: chunk_bytes.max(1),
+    }
+}
+
+/// Iterator over HTML chunks, returned by [`spans_to_html_chunks`].
+pub struct HtmlChunks<'a> {
+    source: &'a str,
+    runs: std::iter::Peekable<std::vec::IntoIter<HtmlRun>>,
+    writer: HtmlWriter,
+    chunk_bytes: usize,
+}
+
+impl Iterator for HtmlChunks<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.runs.peek()?;
+
+        let mut html = String::with_capacity(self.chunk_bytes * 2);
+        let mut consumed = 0;
+        while consumed < self.chunk_bytes {
+            let Some(run) = self.runs.next() else {
+                break;
+            };
+            consumed += run.end - run.start;
+            self.writer
+                .write_run(&mut html, &self.source[run.start..run.end], run);
+        }
+        if self.runs.peek().is_none() {
+            self.writer.finish(&mut html);
+        }
+        Some(html)
+    }
+}
+
+/// Writes runs as HTML, opening and closing line, diagnostic and decoration
+/// wrappers as needed.
+///
+/// Wrappers nest in that order: lines outermost, then diagnostics, then
+/// decorations, then slot tags.
+struct HtmlWriter {
+    format: HtmlFormat,
+    class_style: ClassStyle,
+    escape: EscapeMode,
+    /// Pre-rendered (opening, closing) tags for each decoration.
+    decoration_tags: Vec<(String, &'static str)>,
+    /// Index of the decoration whose wrapper is currently open.
+    open: Option<usize>,
+    /// Non-empty diagnostic ranges with their pre-rendered opening tags,
+    /// outermost first.
+    diagnostics: Vec<(Range<usize>, String)>,
+    /// Indices of the diagnostics whose wrappers are currently open,
+    /// outermost first.
+    open_diagnostics: Vec<usize>,
+    /// Line options, if lines are wrapped individually.
+    lines: Option<LineOptions>,
+    /// One-based number of the line whose wrapper is open, or 0 before the
+    /// first line.
+    line: usize,
+}
+
+/// The line-level subset of [`HtmlOptions`].
+struct LineOptions {
+    anchors: Option<String>,
+    self_links: bool,
+    numbers: bool,
+    emphasized: Vec<RangeInclusive<usize>>,
+}
+
+impl HtmlWriter {
+    fn new(
+        format: &HtmlFormat,
+        options: &HtmlOptions,
+        decorations: &[Decoration],
+        diagnostics: Vec<(Range<usize>, String)>,
+    ) -> Self {
+        Self {
+            format: format.clone(),
+            class_style: options.class_style,
+            escape: options.escape,
+            decoration_tags: decorations.iter().map(Decoration::html_tags).collect(),
+            open: None,
+            diagnostics,
+            open_diagnostics: Vec::new(),
+            lines: options.wraps_lines().then(|| LineOptions {
+                anchors: options.line_anchors.clone(),
+                self_links: options.line_self_links,
+                numbers: options.line_numbers,
+                emphasized: options.emphasized_lines.clone(),
+            }),
+            line: 0,
+        }
+    }
+
+    /// Append a single run with the given `text` to `html`, wrapped in its
+    /// tag if it has one.
+    fn write_run(&mut self, html: &mut String, text: &str, run: HtmlRun) {
+        if self.lines.is_none() {
+            self.write_text(html, text, run);
+            return;
+        }
+
+        // Split the run so that no element crosses a line wrapper
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 || self.line == 0 {
+                self.start_line(html);
+            }
+            if !segment.is_empty() {
+                self.write_text(html, segment, run);
+            }
+        }
+    }
+
+    /// Append `text` from `run`, switching diagnostic and decoration
+    /// wrappers if needed.
+    fn write_text(&mut self, html: &mut String, text: &str, run: HtmlRun) {
+        // Runs are split at diagnostic boundaries, so every diagnostic
+        // covering the start of the run covers all of it
+        let wanted: Vec<usize> = self
+            .diagnostics
+            .iter()
+            .enumerate()
+            .filter(|(_, (range, _))| range.contains(&run.start))
+            .map(|(idx, _)| idx)
+            .collect();
+        if wanted != self.open_diagnostics {
+            let mut markup = String::new();
+            if let Some(idx) = self.open.take() {
+                markup.push_str(self.decoration_tags[idx].1);
+            }
+            let common = self
+                .open_diagnostics
+                .iter()
+                .zip(&wanted)
+                .take_while(|(a, b)| a == b)
+                .count();
+            for _ in common..self.open_diagnostics.len() {
+                markup.push_str("</span>");
+            }
+            for &idx in &wanted[common..] {
+                markup.push_str(&self.diagnostics[idx].1);
+            }
+            self.open_diagnostics = wanted;
+            push_markup(html, &markup, self.escape);
+        }
+
+        if run.decoration != self.open {
+            let mut markup = String::new();
+            if let Some(idx) = self.open.take() {
+                markup.push_str(self.decoration_tags[idx].1);
+            }
+            if let Some(idx) = run.decoration {
+                markup.push_str(&self.decoration_tags[idx].0);
+                self.open = Some(idx);
+            }
+            push_markup(html, &markup, self.escape);
+        }
+
+        let tags = run.tag.and_then(|tag| match self.class_style {
+            ClassStyle::Arborium => Some(make_html_tags(tag, &self.format)),
+            style => style
+                .class_for_tag(tag)
+                .map(|class| (format!("<span class=\"{class}\">"), "</span>".to_string())),
+        });
+        // Markup is built as HTML, then escaped once more by `push_markup`
+        // in attribute mode.
+        let mut markup = String::with_capacity(text.len() + 16);
+        if let Some((open_tag, close_tag)) = tags {
+            markup.push_str(&open_tag);
+            push_escaped(&mut markup, text, EscapeMode::Html);
+            markup.push_str(&close_tag);
+        } else {
+            push_escaped(&mut markup, text, EscapeMode::Html);
+        }
+        push_markup(html, &markup, self.escape);
+    }
+
+    /// Close the open decoration and diagnostic wrappers, returning the
+    /// markup to do so.
+    fn close_inline(&mut self) -> String {
+        let mut markup = String::new();
+        if let Some(idx) = self.open.take() {
+            markup.push_str(self.decoration_tags[idx].1);
+        }
+        for _ in self.open_diagnostics.drain(..) {
+            markup.push_str("</span>");
+        }
+        markup
+    }
+
+    /// Close the current line wrapper (if any) and open the next one.
+    fn start_line(&mut self, html: &mut String) {
+        let mut markup = String::new();
+        if self.line > 0 {
+            markup.push_str(&self.close_inline());
+            markup.push_str("</span>\n");
+        }
+        let Some(options) = &self.lines else {
+            return;
+        };
+        self.line += 1;
+        let n = self.line;
+
+        markup.push_str("<span class=\"line");
+        if options.emphasized.iter().any(|lines| lines.contains(&n)) {
+            markup.push_str(" emphasized");
+        }
+        markup.push('"');
+        let id = options
+            .anchors
+            .as_ref()
+            .map(|prefix| format!("{prefix}L{n}"));
+        if let Some(id) = &id {
+            markup.push_str(" id=\"");
+            push_attribute_value(&mut markup, id);
+            markup.push('"');
+        }
+        markup.push('>');
+
+        match &id {
+            Some(id) if options.self_links => {
+                markup.push_str("<a class=\"line-anchor\" href=\"#");
+                push_attribute_value(&mut markup, id);
+                markup.push_str("\">");
+                if options.numbers {
+                    markup.push_str(&n.to_string());
+                }
+                markup.push_str("</a>");
+            }
+            _ if options.numbers => {
+                markup.push_str(&format!("<span class=\"line-number\">{n}</span>"));
+            }
+            _ => {}
+        }
+        push_markup(html, &markup, self.escape);
+    }
+
+    /// Close every open wrapper at the end of the document.
+    fn finish(&mut self, html: &mut String) {
+        let mut markup = self.close_inline();
+        if self.line > 0 {
+            markup.push_str("</span>");
+            self.line = 0;
+        }
+        push_markup(html, &markup, self.escape);
+    }
+}
+
+/// Resolve spans into contiguous runs of text, each styled by the innermost
+/// tag, along with the decorations the runs refer to. Runs are also split
+/// at the boundaries of `diagnostics`, as returned by [`diagnostic_ranges`].
+///
+/// `source` must already have its trailing newlines trimmed.
+fn html_runs(
+    source: &[u8],
+    spans: Vec<Span>,
+    options: &HtmlOptions,
+    diagnostics: &[(Range<usize>, String)],
+) -> (Vec<HtmlRun>, Vec<Decoration>) {
+    let decorations = collect_html_decorations(source, &spans, options);
+    let mut runs = styled_runs(source, spans);
+    if !decorations.is_empty() {
+        runs = split_at_decorations(runs, &decorations);
+    }
+    if options.optimize {
+        runs = optimize_html_runs(runs);
+    }
+    if !diagnostics.is_empty() {
+        let mut boundaries: Vec<usize> = diagnostics
+            .iter()
+            .flat_map(|(range, _)| [range.start, range.end])
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        runs = split_at_boundaries(runs, &boundaries);
+    }
+    let decorations = decorations.into_iter().map(|(_, _, d)| d).collect();
+    (runs, decorations)
+}
+
+/// Split runs at the given sorted byte offsets.
+fn split_at_boundaries(runs: Vec<HtmlRun>, boundaries: &[usize]) -> Vec<HtmlRun> {
+    let mut split = Vec::with_capacity(runs.len() + boundaries.len());
+    for mut run in runs {
+        let first = boundaries.partition_point(|&b| b <= run.start);
+        for &b in boundaries[first..].iter().take_while(|&&b| b < run.end) {
+            split.push(HtmlRun { end: b, ..run });
+            run.start = b;
+        }
+        split.push(run);
+    }
+    split
+}
+
+/// Collect non-overlapping decorations as `(start, end, decoration)`,
+/// sorted by start. When decorated spans overlap, the outermost one wins.
+fn collect_html_decorations(
+    source: &[u8],
+    spans: &[Span],
+    options: &HtmlOptions,
+) -> Vec<(usize, usize, Decoration)> {
+    let Some(decorator) = &options.decorator else {
+        return Vec::new();
+    };
+
+    let mut decorations = Vec::new();
+    for span in spans {
+        let start = span.start as usize;
+        let end = (span.end as usize).min(source.len());
+        if start >= end {
+            continue;
+        }
+        let Ok(text) = std::str::from_utf8(&source[start..end]) else {
+            continue;
+        };
+        let byte_range = start..end;
+        if let Some(decoration) = decorator.decorate(&DecorationContext {
+            capture: &span.capture,
+            text,
+            byte_range: &byte_range,
+        }) {
+            decorations.push((start, end, decoration));
+        }
+    }
+
+    // Outermost first, then drop anything overlapping an earlier decoration
+    decorations.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1)));
+    let mut result: Vec<(usize, usize, Decoration)> = Vec::with_capacity(decorations.len());
+    for decoration in decorations {
+        if result.last().is_none_or(|last| decoration.0 >= last.1) {
+            result.push(decoration);
+        }
+    }
+    result
+}
+
+/// Split runs at decoration boundaries and mark the runs each decoration
+/// covers, so that wrappers never cross tag boundaries.
+fn split_at_decorations(
+    runs: Vec<HtmlRun>,
+    decorations: &[(usize, usize, Decoration)],
+) -> Vec<HtmlRun> {
+    let mut split = Vec::with_capacity(runs.len() + decorations.len() * 2);
+    for mut run in runs {
+        while run.start < run.end {
+            let idx = decorations.partition_point(|d| d.1 <= run.start);
+            let end = match decorations.get(idx) {
+                Some(d) if d.0 <= run.start => {
+                    run.decoration = Some(idx);
+                    d.1.min(run.end)
+                }
+                Some(d) => {
+                    run.decoration = None;
+                    d.0.min(run.end)
+                }
+                None => {
+                    run.decoration = None;
+                    run.end
+                }
+            };
+            split.push(HtmlRun { end, ..run });
+            run.start = end;
+        }
+    }
+    split
+}
+
+/// Resolve spans into contiguous runs of text, each styled by the innermost tag.
+fn styled_runs(source: &[u8], spans: Vec<Span>) -> Vec<HtmlRun> {
+    let unstyled = || {
+        if source.is_empty() {
+            vec![]
+        } else {
+            vec![HtmlRun {
+                start: 0,
+                end: source.len(),
+                tag: None,
+                decoration: None,
+            }]
+        }
+    };
 
     if spans.is_empty() {
-        return html_escape(source);
+        return unstyled();
     }
 
     // Sort spans by (start, -end) so longer spans come first at same start
@@ -238,7 +1123,7 @@ pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> Str
     let spans = normalize_and_coalesce(spans);
 
     if spans.is_empty() {
-        return html_escape(source);
+        return unstyled();
     }
 
     // Re-sort after coalescing
@@ -257,26 +1142,26 @@ pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> Str
         a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)) // false (end) < true (start)
     });
 
-    // Process events with a stack
-    let mut html = String::with_capacity(source.len() * 2);
+    // Process events with a stack, collecting runs of text styled by the innermost span
+    let mut runs: Vec<HtmlRun> = Vec::with_capacity(events.len() + 1);
     let mut last_pos: usize = 0;
     let mut stack: Vec<usize> = Vec::new(); // indices into spans
 
     for (pos, is_start, span_idx) in events {
-        let pos = pos as usize;
+        // Spans ending inside a character cover all of it
+        let mut pos = pos as usize;
+        while !is_char_boundary(source, pos) {
+            pos += 1;
+        }
 
-        // Emit any source text before this position
+        // Record any source text before this position
         if pos > last_pos && pos <= source.len() {
-            let text = &source[last_pos..pos];
-            if let Some(&top_idx) = stack.last() {
-                let tag = spans[top_idx].tag;
-                let (open_tag, close_tag) = make_html_tags(tag, format);
-                html.push_str(&open_tag);
-                html.push_str(&html_escape(text));
-                html.push_str(&close_tag);
-            } else {
-                html.push_str(&html_escape(text));
-            }
+            runs.push(HtmlRun {
+                start: last_pos,
+                end: pos,
+                tag: stack.last().map(|&idx| spans[idx].tag),
+                decoration: None,
+            });
             last_pos = pos;
         }
 
@@ -291,21 +1176,17 @@ pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> Str
         }
     }
 
-    // Emit remaining text
+    // Record remaining text
     if last_pos < source.len() {
-        let text = &source[last_pos..];
-        if let Some(&top_idx) = stack.last() {
-            let tag = spans[top_idx].tag;
-            let (open_tag, close_tag) = make_html_tags(tag, format);
-            html.push_str(&open_tag);
-            html.push_str(&html_escape(text));
-            html.push_str(&close_tag);
-        } else {
-            html.push_str(&html_escape(text));
-        }
+        runs.push(HtmlRun {
+            start: last_pos,
+            end: source.len(),
+            tag: stack.last().map(|&idx| spans[idx].tag),
+            decoration: None,
+        });
     }
 
-    html
+    runs
 }
 
 /// Write spans as HTML to a writer.
@@ -322,33 +1203,174 @@ pub fn write_spans_as_html<W: Write>(
 }
 
 /// Escape HTML special characters.
+///
+/// Control characters other than tab, line feed and carriage return are
+/// replaced with U+FFFD.
 pub fn html_escape(text: &str) -> String {
+    html_escape_with_mode(text, EscapeMode::Html)
+}
+
+/// Escape text for the given [`EscapeMode`].
+pub fn html_escape_with_mode(text: &str, mode: EscapeMode) -> String {
     let mut result = String::with_capacity(text.len());
+    push_escaped(&mut result, text, mode);
+    result
+}
+
+/// Append `text` to `out`, escaped for `mode`.
+fn push_escaped(out: &mut String, text: &str, mode: EscapeMode) {
     for c in text.chars() {
         match c {
-            '<' => result.push_str("&lt;"),
-            '>' => result.push_str("&gt;"),
-            '&' => result.push_str("&amp;"),
-            '"' => result.push_str("&quot;"),
-            '\'' => result.push_str("&#39;"),
-            _ => result.push(c),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            '\t' if mode == EscapeMode::Attribute => out.push_str("&#9;"),
+            '\n' if mode == EscapeMode::Attribute => out.push_str("&#10;"),
+            '\r' if mode == EscapeMode::Attribute => out.push_str("&#13;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() => out.push('\u{FFFD}'),
+            _ => out.push(c),
         }
     }
-    result
 }
 
-/// Options controlling ANSI rendering behavior.
-#[derive(Debug, Clone)]
-pub struct AnsiOptions {
-    /// If true, apply the theme's foreground/background as a base style
-    /// for all text (including un-highlighted regions).
-    pub use_theme_base_style: bool,
-    /// Optional hard wrap width (in columns). When None, no wrapping is
-    /// performed and the original line structure is preserved.
-    pub width: Option<usize>,
-    /// If true and `width` is set, pad each visual line with spaces up
-    /// to exactly `width` columns.
-    pub pad_to_width: bool,
+/// Append an attribute value to `out`.
+///
+/// Tabs and line breaks are written as character references, since XML
+/// parsers would otherwise normalize them to spaces.
+fn push_attribute_value(out: &mut String, value: &str) {
+    push_escaped(out, value, EscapeMode::Attribute);
+}
+
+/// Append markup produced for [`EscapeMode::Html`] or [`EscapeMode::Xhtml`]
+/// to `out`, escaping it once more in [`EscapeMode::Attribute`].
+fn push_markup(out: &mut String, markup: &str, mode: EscapeMode) {
+    if mode == EscapeMode::Attribute {
+        push_escaped(out, markup, mode);
+    } else {
+        out.push_str(markup);
+    }
+}
+
+/// Whether `name` can be written as an attribute name without escaping.
+fn is_valid_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
+
+/// A highlighted span, as seen by a [`Decorator`].
+#[derive(Debug, Clone, Copy)]
+pub struct DecorationContext<'a> {
+    /// The capture name (e.g. `"function"`, `"string.special.url"`).
+    pub capture: &'a str,
+    /// The source text covered by the span.
+    pub text: &'a str,
+    /// Byte range of the span within the source.
+    pub byte_range: &'a Range<usize>,
+}
+
+/// Extra output a [`Decorator`] attaches to a span.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Decoration {
+    /// URI to link the span to.
+    pub link: Option<String>,
+    /// Extra attributes for the element wrapping the span in HTML output,
+    /// as `(name, value)` pairs. Values are escaped according to
+    /// [`HtmlOptions::escape`]; pairs with invalid names are skipped. Ignored
+    /// by the ANSI renderer.
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Decoration {
+    /// A decoration that links the span to `uri`.
+    pub fn link(uri: impl Into<String>) -> Self {
+        Self {
+            link: Some(uri.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Add an attribute to the element wrapping the span in HTML output.
+    pub fn with_attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.push((name.into(), value.into()));
+        self
+    }
+
+    /// Opening and closing tags wrapping a decorated span in HTML output.
+    ///
+    /// Links become `<a href="...">`, anything else a `<span>`.
+    fn html_tags(&self) -> (String, &'static str) {
+        let (mut open, close) = match &self.link {
+            Some(uri) => {
+                let mut open = String::from("<a href=\"");
+                push_attribute_value(&mut open, uri);
+                open.push('"');
+                (open, "</a>")
+            }
+            None => (String::from("<span"), "</span>"),
+        };
+        for (name, value) in &self.attributes {
+            if !is_valid_attribute_name(name) || (self.link.is_some() && name == "href") {
+                continue;
+            }
+            open.push(' ');
+            open.push_str(name);
+            open.push_str("=\"");
+            push_attribute_value(&mut open, value);
+            open.push('"');
+        }
+        open.push('>');
+        (open, close)
+    }
+}
+
+/// Callback that decorates highlighted spans, e.g. to turn function names
+/// into links to their definitions.
+///
+/// Called once per highlighted span; returning `None` leaves the span as is.
+/// When decorated spans overlap, the outermost one wins.
+#[derive(Clone)]
+pub struct Decorator(Arc<DecorateFn>);
+
+type DecorateFn = dyn Fn(&DecorationContext<'_>) -> Option<Decoration> + Send + Sync;
+
+impl Decorator {
+    /// Wrap a decoration callback.
+    pub fn new(
+        f: impl Fn(&DecorationContext<'_>) -> Option<Decoration> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Run the callback on a span.
+    pub fn decorate(&self, context: &DecorationContext<'_>) -> Option<Decoration> {
+        (self.0)(context)
+    }
+}
+
+impl fmt::Debug for Decorator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Decorator(..)")
+    }
+}
+
+/// Options controlling ANSI rendering behavior.
+#[derive(Debug, Clone)]
+pub struct AnsiOptions {
+    /// If true, apply the theme's foreground/background as a base style
+    /// for all text (including un-highlighted regions).
+    pub use_theme_base_style: bool,
+    /// Optional hard wrap width (in columns). When None, no wrapping is
+    /// performed and the original line structure is preserved.
+    pub width: Option<usize>,
+    /// If true and `width` is set, pad each visual line with spaces up
+    /// to exactly `width` columns.
+    pub pad_to_width: bool,
     /// Tab width (in columns) used when computing display width.
     pub tab_width: usize,
     /// Horizontal margin (in columns) outside the border/background.
@@ -365,6 +1387,21 @@ pub struct AnsiOptions {
     pub padding_y: usize,
     /// If true, draw a border around the code block using half-block characters.
     pub border: bool,
+    /// If true, avoid redundant escape sequences: pure-whitespace runs between
+    /// two styled runs keep the currently active style instead of switching,
+    /// as long as neither style has a background, underline or strikethrough
+    /// that would make the difference visible. Enabled by default.
+    pub optimize: bool,
+    /// Callback that can attach a link to highlighted spans. Links are
+    /// emitted as OSC 8 hyperlinks.
+    pub decorator: Option<Decorator>,
+    /// If true, link spans captured as URLs (`link_uri`, `markup.link.url`,
+    /// `string.special.url`) to themselves. Disabled by default.
+    pub auto_link: bool,
+    /// If false, never emit OSC 8 hyperlinks, whatever `decorator` and
+    /// `auto_link` say. Turn this off for terminals that print the escape
+    /// sequences literally. Enabled by default.
+    pub hyperlinks: bool,
 }
 
 /// Unicode block drawing characters used to create visual borders around ANSI output.
@@ -414,10 +1451,146 @@ impl Default for AnsiOptions {
             padding_x: 0,
             padding_y: 0,
             border: false,
+            optimize: true,
+            decorator: None,
+            auto_link: false,
+            hyperlinks: true,
         }
     }
 }
 
+/// Opens an OSC 8 hyperlink; followed by the URI and [`OSC8_END`].
+const OSC8_START: &str = "\x1b]8;;";
+/// String terminator ending an OSC 8 sequence.
+const OSC8_END: &str = "\x1b\\";
+
+/// Captures whose text is itself a URL, for [`AnsiOptions::auto_link`].
+fn is_url_capture(capture: &str) -> bool {
+    matches!(
+        capture,
+        "link_uri" | "markup.link.url" | "string.special.url" | "text.uri"
+    )
+}
+
+/// Make a URI safe to embed in an OSC 8 sequence.
+///
+/// The spec only allows printable ASCII, so everything else (including the
+/// ESC that would end the sequence early) is percent-encoded.
+fn osc8_uri(uri: &str) -> String {
+    let mut out = String::with_capacity(uri.len());
+    for byte in uri.bytes() {
+        if (0x21..0x7f).contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// Collect non-overlapping hyperlinks as `(start, end, uri)`, sorted by start.
+fn collect_ansi_links(
+    source: &str,
+    spans: &[Span],
+    options: &AnsiOptions,
+) -> Vec<(usize, usize, String)> {
+    if !options.hyperlinks || (options.decorator.is_none() && !options.auto_link) {
+        return Vec::new();
+    }
+
+    let mut links = Vec::new();
+    for span in spans {
+        let start = span.start as usize;
+        let end = (span.end as usize).min(source.len());
+        if start >= end || !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+            continue;
+        }
+        let text = &source[start..end];
+        let byte_range = start..end;
+        let decorated = options.decorator.as_ref().and_then(|decorator| {
+            decorator.decorate(&DecorationContext {
+                capture: &span.capture,
+                text,
+                byte_range: &byte_range,
+            })
+        });
+        let uri = match decorated.and_then(|d| d.link) {
+            Some(uri) => uri,
+            None if options.auto_link && is_url_capture(&span.capture) => text.to_string(),
+            None => continue,
+        };
+        links.push((start, end, osc8_uri(&uri)));
+    }
+
+    // Outermost first, then drop anything overlapping an earlier link
+    links.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1)));
+    let mut result: Vec<(usize, usize, String)> = Vec::with_capacity(links.len());
+    for link in links {
+        if result.last().is_none_or(|last| link.0 >= last.1) {
+            result.push(link);
+        }
+    }
+    result
+}
+
+/// Open, close or switch the OSC 8 hyperlink so that it matches the link
+/// covering `pos`.
+fn update_ansi_link(
+    out: &mut String,
+    links: &[(usize, usize, String)],
+    pos: usize,
+    active_link: &mut Option<usize>,
+) {
+    let idx = links.partition_point(|link| link.1 <= pos);
+    let desired = links.get(idx).filter(|link| link.0 <= pos).map(|_| idx);
+    if desired == *active_link {
+        return;
+    }
+    if active_link.is_some() {
+        out.push_str(OSC8_START);
+        out.push_str(OSC8_END);
+    }
+    if let Some(idx) = desired {
+        out.push_str(OSC8_START);
+        out.push_str(&links[idx].2);
+        out.push_str(OSC8_END);
+    }
+    *active_link = desired;
+}
+
+/// Event marker for hyperlink boundaries in the ANSI renderer.
+const LINK_BOUNDARY: usize = usize::MAX;
+
+/// Whether a style renders identically to plain text on whitespace.
+fn style_invisible_on_whitespace(theme: &Theme, index: usize) -> bool {
+    theme.style(index).is_none_or(|style| {
+        style.bg.is_none() && !style.modifiers.underline && !style.modifiers.strikethrough
+    })
+}
+
+/// Pick the style to render `text` with, keeping the active style for
+/// whitespace runs when switching would not be visible.
+fn resolve_ansi_style(
+    theme: &Theme,
+    options: &AnsiOptions,
+    text: &str,
+    active: Option<usize>,
+    desired: Option<usize>,
+) -> Option<usize> {
+    match (active, desired) {
+        (Some(a), Some(d))
+            if options.optimize
+                && a != d
+                && text.trim().is_empty()
+                && style_invisible_on_whitespace(theme, a)
+                && style_invisible_on_whitespace(theme, d) =>
+        {
+            active
+        }
+        _ => desired,
+    }
+}
+
 #[cfg(feature = "unicode-width")]
 fn char_display_width(c: char, col: usize, tab_width: usize) -> usize {
     if c == '\t' {
@@ -639,6 +1812,9 @@ pub fn spans_to_ansi_with_options(
     let mut spans = spans;
     spans.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| b.end.cmp(&a.end)));
 
+    // Hyperlinks are resolved before deduplication, so decorators see every capture
+    let links = collect_ansi_links(source, &spans, options);
+
     // Deduplicate ranges - prefer spans with higher pattern_index (later in highlights.scm wins)
     // This matches tree-sitter convention: later patterns override earlier ones
     let mut deduped: HashMap<(u32, u32), Span> = HashMap::new();
@@ -695,7 +1871,7 @@ pub fn spans_to_ansi_with_options(
         })
         .collect();
 
-    if normalized.is_empty() {
+    if normalized.is_empty() && links.is_empty() {
         return source.to_string();
     }
 
@@ -714,7 +1890,7 @@ pub fn spans_to_ansi_with_options(
         coalesced.push(span);
     }
 
-    if coalesced.is_empty() {
+    if coalesced.is_empty() && links.is_empty() {
         return source.to_string();
     }
 
@@ -724,6 +1900,11 @@ pub fn spans_to_ansi_with_options(
         events.push((span.start, true, i));
         events.push((span.end, false, i));
     }
+    // Link boundaries split text runs but don't touch the style stack
+    for link in &links {
+        events.push((link.0 as u32, false, LINK_BOUNDARY));
+        events.push((link.1 as u32, false, LINK_BOUNDARY));
+    }
 
     events.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
 
@@ -731,6 +1912,7 @@ pub fn spans_to_ansi_with_options(
     let mut last_pos: usize = 0;
     let mut stack: Vec<usize> = Vec::new();
     let mut active_style: Option<usize> = None;
+    let mut active_link: Option<usize> = None;
     let mut current_col: usize = 0;
 
     let base_ansi = if options.use_theme_base_style {
@@ -833,6 +2015,8 @@ pub fn spans_to_ansi_with_options(
         if pos > last_pos && pos <= source.len() {
             let text = &source[last_pos..pos];
             let desired = stack.last().copied().map(|idx| coalesced[idx].index);
+            let desired = resolve_ansi_style(theme, options, text, active_style, desired);
+            update_ansi_link(&mut out, &links, last_pos, &mut active_link);
 
             match (active_style, desired) {
                 (Some(a), Some(d)) if a == d => {
@@ -964,6 +2148,8 @@ pub fn spans_to_ansi_with_options(
     if last_pos < source.len() {
         let text = &source[last_pos..];
         let desired = stack.last().copied().map(|idx| coalesced[idx].index);
+        let desired = resolve_ansi_style(theme, options, text, active_style, desired);
+        update_ansi_link(&mut out, &links, last_pos, &mut active_link);
         match (active_style, desired) {
             (Some(a), Some(d)) if a == d => {
                 write_wrapped_text(
@@ -1071,6 +2257,9 @@ pub fn spans_to_ansi_with_options(
         }
     }
 
+    // Close any link still open at the end of the source
+    update_ansi_link(&mut out, &links, source.len(), &mut active_link);
+
     if let Some(width) = options.width {
         let padding_y = options.padding_y;
         let pad_to_width = options.pad_to_width;
@@ -1338,6 +2527,133 @@ mod tests {
         assert_eq!(ansi, expected);
     }
 
+    fn plain_ansi_options() -> AnsiOptions {
+        AnsiOptions {
+            width: None,
+            pad_to_width: false,
+            ..Default::default()
+        }
+    }
+
+    fn span(start: u32, end: u32, capture: &str) -> Span {
+        Span {
+            start,
+            end,
+            capture: capture.into(),
+            pattern_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_ansi_decorator_hyperlink() {
+        let theme = arborium_theme::theme::builtin::catppuccin_mocha();
+        let source = "fn main";
+        let spans = vec![span(0, 2, "keyword"), span(3, 7, "function")];
+        let options = AnsiOptions {
+            decorator: Some(Decorator::new(|ctx| {
+                (ctx.capture == "function")
+                    .then(|| Decoration::link(format!("file:///src/main.rs#{}", ctx.text)))
+            })),
+            ..plain_ansi_options()
+        };
+
+        let kw_idx = slot_to_highlight_index(capture_to_slot("keyword")).unwrap();
+        let fn_idx = slot_to_highlight_index(capture_to_slot("function")).unwrap();
+
+        // The link wraps the SGR-styled text: it opens before the style and
+        // closes before the final reset.
+        let expected = format!(
+            "{}fn{} \x1b]8;;file:///src/main.rs#main\x1b\\{}main\x1b]8;;\x1b\\{}",
+            theme.ansi_style(kw_idx),
+            Theme::ANSI_RESET,
+            theme.ansi_style(fn_idx),
+            Theme::ANSI_RESET
+        );
+        assert_eq!(
+            spans_to_ansi_with_options(source, spans.clone(), &theme, &options),
+            expected
+        );
+
+        // With hyperlinks disabled, the output is exactly the unlinked one
+        let disabled = AnsiOptions {
+            hyperlinks: false,
+            ..options
+        };
+        assert_eq!(
+            spans_to_ansi_with_options(source, spans.clone(), &theme, &disabled),
+            spans_to_ansi_with_options(source, spans, &theme, &plain_ansi_options())
+        );
+    }
+
+    #[test]
+    fn test_ansi_auto_link_inside_styled_span() {
+        let theme = arborium_theme::theme::builtin::catppuccin_mocha();
+        let source = "x = \"https://example.com\"";
+        let spans = vec![span(4, 25, "string"), span(5, 24, "string.special.url")];
+        let options = AnsiOptions {
+            auto_link: true,
+            ..plain_ansi_options()
+        };
+
+        let ansi = spans_to_ansi_with_options(source, spans.clone(), &theme, &options);
+        let open = "\x1b]8;;https://example.com\x1b\\";
+        let close = "\x1b]8;;\x1b\\";
+
+        // Exactly one link, around exactly the URL, properly terminated
+        assert_eq!(ansi.matches(open).count(), 1);
+        assert_eq!(ansi.matches(close).count(), 1);
+        let inner = &ansi[ansi.find(open).unwrap() + open.len()..ansi.find(close).unwrap()];
+        let visible: String = strip_sgr(inner);
+        assert_eq!(visible, "https://example.com");
+
+        // The link doesn't change what's visible
+        assert_eq!(strip_sgr(&strip_osc8(&ansi)), source);
+
+        // Auto-linking is off by default
+        let plain = spans_to_ansi_with_options(source, spans, &theme, &plain_ansi_options());
+        assert!(!plain.contains("\x1b]8;"));
+    }
+
+    #[test]
+    fn test_ansi_link_uri_is_sanitized() {
+        let theme = arborium_theme::theme::builtin::catppuccin_mocha();
+        let options = AnsiOptions {
+            decorator: Some(Decorator::new(|_| Some(Decoration::link("a b\x1b\\é")))),
+            ..plain_ansi_options()
+        };
+        let ansi = spans_to_ansi_with_options("x", vec![span(0, 1, "variable")], &theme, &options);
+        assert!(ansi.starts_with("\x1b]8;;a%20b%1B\\%C3%A9\x1b\\"));
+        assert!(ansi.contains("x\x1b]8;;\x1b\\"));
+    }
+
+    /// Remove SGR sequences (`ESC [ ... m`).
+    fn strip_sgr(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' && chars.peek() == Some(&'[') {
+                for c in chars.by_ref() {
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    /// Remove OSC 8 sequences (`ESC ] 8 ; ; URI ESC \`).
+    fn strip_osc8(s: &str) -> String {
+        let mut out = s.to_string();
+        while let Some(start) = out.find("\x1b]8;;") {
+            let end = start + out[start..].find("\x1b\\").unwrap() + 2;
+            out.replace_range(start..end, "");
+        }
+        out
+    }
+
     #[test]
     fn test_ansi_with_base_background() {
         let theme = arborium_theme::theme::builtin::tokyo_night();
@@ -1814,4 +3130,824 @@ mod html_tests {
         );
         assert_eq!(html, "let x = 1;");
     }
+
+    /// Strip tags and unescape entities to recover the rendered text.
+    fn html_text_content(html: &str) -> String {
+        let mut text = String::with_capacity(html.len());
+        let mut in_tag = false;
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&")
+    }
+
+    #[test]
+    fn test_optimize_merges_adjacent_same_tag() {
+        // Two keyword spans nested in a wider span aren't coalesced up front,
+        // so the renderer produces two adjacent runs with the same tag.
+        let source = "abcd";
+        let spans = vec![
+            Span {
+                start: 0,
+                end: 4,
+                capture: "variable".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 0,
+                end: 2,
+                capture: "keyword".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 2,
+                end: 4,
+                capture: "keyword.function".into(),
+                pattern_index: 0,
+            },
+        ];
+
+        let unoptimized = spans_to_html_with_options(
+            source,
+            spans.clone(),
+            &HtmlFormat::CustomElements,
+            &HtmlOptions {
+                optimize: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(unoptimized, "<a-k>ab</a-k><a-k>cd</a-k>");
+
+        let optimized = spans_to_html(source, spans, &HtmlFormat::CustomElements);
+        assert_eq!(optimized, "<a-k>abcd</a-k>");
+    }
+
+    #[test]
+    fn test_html_chunks_match_full_render() {
+        let source = "fn main() {\n    let x = \"<hi>\";\n}\n";
+        let spans = vec![
+            Span {
+                start: 0,
+                end: 2,
+                capture: "keyword".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 3,
+                end: 7,
+                capture: "function".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 16,
+                end: 19,
+                capture: "keyword".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 24,
+                end: 30,
+                capture: "string".into(),
+                pattern_index: 0,
+            },
+        ];
+
+        let full = spans_to_html(source, spans.clone(), &HtmlFormat::CustomElements);
+        for chunk_bytes in [1, 3, 8, 1024] {
+            let chunks: Vec<String> = spans_to_html_chunks(
+                source,
+                spans.clone(),
+                &HtmlFormat::CustomElements,
+                &HtmlOptions::default(),
+                chunk_bytes,
+            )
+            .collect();
+            assert!(chunks.iter().all(|c| !c.is_empty()));
+            assert_eq!(chunks.concat(), full);
+        }
+
+        assert_eq!(
+            spans_to_html_chunks(
+                "",
+                vec![],
+                &HtmlFormat::default(),
+                &HtmlOptions::default(),
+                8
+            )
+            .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_optimize_keeps_whitespace_wrappers() {
+        // Whether `  ` shows its keyword style is up to the stylesheet
+        let source = "a  b";
+        let spans = vec![
+            span(0, 4, "keyword"),
+            span(0, 1, "string"),
+            span(3, 4, "string"),
+        ];
+
+        let html = spans_to_html(source, spans, &HtmlFormat::CustomElements);
+        assert_eq!(html, "<a-s>a</a-s><a-k>  </a-k><a-s>b</a-s>");
+    }
+
+    #[test]
+    fn test_optimize_keeps_whitespace_with_visible_style() {
+        // Diff additions usually carry a background, so the wrapper must stay
+        let source = "+ ";
+        let spans = vec![
+            Span {
+                start: 0,
+                end: 1,
+                capture: "punctuation".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 1,
+                end: 2,
+                capture: "diff.plus".into(),
+                pattern_index: 0,
+            },
+        ];
+
+        let html = spans_to_html(source, spans, &HtmlFormat::CustomElements);
+        assert!(html.ends_with("<a-da> </a-da>"), "got: {html}");
+    }
+
+    #[test]
+    fn test_optimize_rust_fixture_size() {
+        let source = "fn main() {\n    let pairs = [(1, 2), (3, 4)];\n    for (a, b) in pairs {\n        println!(\"{a}: {b}\");\n    }\n}\n";
+
+        // Captures the way tree-sitter-rust reports them: one per bracket or
+        // delimiter, so runs of punctuation come out as adjacent spans, and
+        // the odd zero-width capture
+        let mut spans: Vec<Span> = source
+            .char_indices()
+            .filter_map(|(i, c)| {
+                let capture = match c {
+                    '(' | ')' | '[' | ']' | '{' | '}' => "punctuation.bracket",
+                    ',' | ';' | ':' => "punctuation.delimiter",
+                    _ => return None,
+                };
+                Some(span(i as u32, i as u32 + 1, capture))
+            })
+            .collect();
+        for keyword in ["fn ", "let ", "for ", " in "] {
+            for (i, _) in source.match_indices(keyword) {
+                let start = i + keyword.len() - keyword.trim_start().len();
+                let end = start + keyword.trim().len();
+                spans.push(span(start as u32, end as u32, "keyword"));
+            }
+        }
+        spans.push(span(3, 7, "function"));
+        spans.push(span(3, 3, "comment"));
+        spans.sort_by_key(|s| (s.start, s.end));
+
+        let unoptimized = spans_to_html_with_options(
+            source,
+            spans.clone(),
+            &HtmlFormat::ClassNames,
+            &HtmlOptions {
+                optimize: false,
+                ..Default::default()
+            },
+        );
+        let optimized = spans_to_html(source, spans, &HtmlFormat::ClassNames);
+
+        assert!(
+            optimized.len() < unoptimized.len(),
+            "optimizing didn't shrink the output ({} bytes)",
+            optimized.len()
+        );
+        assert_eq!(
+            html_text_content(&optimized),
+            html_text_content(&unoptimized)
+        );
+        assert_eq!(html_text_content(&optimized), source.trim_end_matches('\n'));
+    }
+
+    #[test]
+    fn test_ansi_optimize_skips_whitespace_switches() {
+        let theme = arborium_theme::theme::builtin::catppuccin_mocha();
+        let source = "fn main";
+        let spans = vec![
+            Span {
+                start: 0,
+                end: 2,
+                capture: "keyword".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 2,
+                end: 3,
+                capture: "variable".into(),
+                pattern_index: 0,
+            },
+            Span {
+                start: 3,
+                end: 7,
+                capture: "keyword".into(),
+                pattern_index: 0,
+            },
+        ];
+
+        let kw_idx = slot_to_highlight_index(capture_to_slot("keyword")).unwrap();
+
+        let options = AnsiOptions {
+            width: None,
+            pad_to_width: false,
+            ..Default::default()
+        };
+        let optimized = spans_to_ansi_with_options(source, spans.clone(), &theme, &options);
+        assert_eq!(
+            optimized,
+            format!("{}fn main{}", theme.ansi_style(kw_idx), Theme::ANSI_RESET)
+        );
+
+        let unoptimized = spans_to_ansi_with_options(
+            source,
+            spans,
+            &theme,
+            &AnsiOptions {
+                optimize: false,
+                ..options
+            },
+        );
+        assert!(optimized.len() < unoptimized.len());
+    }
+
+    #[test]
+    fn test_escape_modes() {
+        let text = "a\"'<>&\0\u{1b}\u{7f}\u{85}\tb\r\n";
+        assert_eq!(
+            html_escape_with_mode(text, EscapeMode::Html),
+            "a&quot;&#39;&lt;&gt;&amp;\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\tb\r\n"
+        );
+        assert_eq!(
+            html_escape_with_mode(text, EscapeMode::Xhtml),
+            html_escape_with_mode(text, EscapeMode::Html)
+        );
+        assert_eq!(
+            html_escape_with_mode(text, EscapeMode::Attribute),
+            "a&quot;&#39;&lt;&gt;&amp;\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}&#9;b&#13;&#10;"
+        );
+        assert_eq!(html_escape("nul\0"), "nul\u{FFFD}");
+        assert!(EscapeMode::Xhtml.self_closing());
+        assert!(!EscapeMode::Html.self_closing());
+    }
+
+    #[test]
+    fn test_decoration_attributes_are_escaped() {
+        let source = "fn main() {}";
+        let spans = vec![span(0, 2, "keyword"), span(3, 7, "function")];
+        let options = HtmlOptions {
+            decorator: Some(Decorator::new(|ctx| {
+                (ctx.capture == "function").then(|| {
+                    Decoration::link("/main?a=1&b=\"2\"")
+                        .with_attribute("title", format!("fn \"{}\" <'x'>\n", ctx.text))
+                        .with_attribute("bad name", "dropped")
+                        .with_attribute("href", "dropped")
+                })
+            })),
+            ..Default::default()
+        };
+        let html = spans_to_html_with_options(source, spans, &HtmlFormat::CustomElements, &options);
+        assert_eq!(
+            html,
+            "<a-k>fn</a-k> <a href=\"/main?a=1&amp;b=&quot;2&quot;\" \
+             title=\"fn &quot;main&quot; &lt;&#39;x&#39;&gt;&#10;\"><a-f>main</a-f></a>() {}"
+        );
+        check_well_formed(&html).unwrap();
+    }
+
+    #[test]
+    fn test_decoration_splits_runs() {
+        // The decorated span starts inside one styled run and ends inside another
+        let source = "abcdef";
+        let spans = vec![
+            span(0, 3, "keyword"),
+            span(3, 6, "string"),
+            span(1, 4, "label"),
+        ];
+        let options = HtmlOptions {
+            decorator: Some(Decorator::new(|ctx| {
+                (ctx.capture == "label").then(|| Decoration::default().with_attribute("class", "d"))
+            })),
+            ..Default::default()
+        };
+        let html = spans_to_html_with_options(
+            source,
+            spans.clone(),
+            &HtmlFormat::CustomElements,
+            &options,
+        );
+        check_well_formed(&html).unwrap();
+        assert!(html.contains("<span class=\"d\">"), "got: {html}");
+
+        let chunks: String =
+            spans_to_html_chunks(source, spans, &HtmlFormat::CustomElements, &options, 1).collect();
+        assert_eq!(chunks, html);
+    }
+
+    #[test]
+    fn test_escape_modes_produce_well_formed_output() {
+        const ALPHABET: &[&str] = &[
+            "a", "b", " ", "\"", "'", "<", ">", "&", "\0", "\u{1}", "\u{7f}", "\u{9f}", "\t", "\n",
+            "\r", "é", "&amp;", "]]>",
+        ];
+        const CAPTURES: &[&str] = &["keyword", "string", "comment", "function", "label", "spell"];
+
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        };
+
+        let decorator = Decorator::new(|ctx| {
+            (ctx.capture == "function" || ctx.capture == "label").then(|| {
+                Decoration::link(ctx.text.to_string()).with_attribute("data-text", ctx.text)
+            })
+        });
+
+        for _ in 0..200 {
+            let source: String = (0..next(24))
+                .map(|_| ALPHABET[next(ALPHABET.len())])
+                .collect();
+            let boundaries: Vec<usize> = (0..=source.len())
+                .filter(|&i| source.is_char_boundary(i))
+                .collect();
+            let spans: Vec<Span> = (0..next(6))
+                .map(|_| {
+                    let a = boundaries[next(boundaries.len())];
+                    let b = boundaries[next(boundaries.len())];
+                    span(
+                        a.min(b) as u32,
+                        a.max(b) as u32,
+                        CAPTURES[next(CAPTURES.len())],
+                    )
+                })
+                .collect();
+            let expected_text: String =
+                std::str::from_utf8(trim_trailing_newlines(source.as_bytes()))
+                    .unwrap()
+                    .chars()
+                    .map(|c| {
+                        if c.is_control() && !matches!(c, '\t' | '\n' | '\r') {
+                            '\u{FFFD}'
+                        } else {
+                            c
+                        }
+                    })
+                    .collect();
+
+            for format in [HtmlFormat::CustomElements, HtmlFormat::ClassNames] {
+                let render = |escape| {
+                    let options = HtmlOptions {
+                        escape,
+                        decorator: Some(decorator.clone()),
+                        ..Default::default()
+                    };
+                    spans_to_html_with_options(&source, spans.clone(), &format, &options)
+                };
+
+                let html = render(EscapeMode::Html);
+                let parsed = check_well_formed(&html)
+                    .unwrap_or_else(|e| panic!("{e}: {html:?} from {source:?}"));
+                assert_eq!(parsed.text, expected_text, "{html:?}");
+
+                // XHTML output is XML a real parser accepts
+                let xhtml = render(EscapeMode::Xhtml);
+                assert_eq!(xhtml, html);
+                let doc = format!("<code>{xhtml}</code>");
+                let xml = roxmltree::Document::parse(&doc)
+                    .unwrap_or_else(|e| panic!("{e}: {doc:?} from {source:?}"));
+                let xml_text: String = xml.descendants().filter_map(|node| node.text()).collect();
+                // XML parsers normalize line endings in text
+                let normalized = expected_text.replace("\r\n", "\n").replace('\r', "\n");
+                assert_eq!(xml_text, normalized, "{doc:?}");
+
+                // Attribute mode decodes back to the HTML mode output
+                let attr = render(EscapeMode::Attribute);
+                assert!(
+                    !attr.contains(['<', '>', '"', '\'', '\n', '\r', '\t']),
+                    "{attr:?}"
+                );
+                let doc = format!("<x title=\"{attr}\"/>");
+                let parsed = check_well_formed(&doc)
+                    .unwrap_or_else(|e| panic!("{e}: {doc:?} from {source:?}"));
+                assert_eq!(parsed.attributes[0], ("title".to_string(), html.clone()));
+                let xml = roxmltree::Document::parse(&doc)
+                    .unwrap_or_else(|e| panic!("{e}: {doc:?} from {source:?}"));
+                assert_eq!(xml.root_element().attribute("title"), Some(html.as_str()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_anchors() {
+        let source = "a\nb\n\nc\n";
+        let options = HtmlOptions::default().line_anchors("ex1-");
+        let html = spans_to_html_with_options(
+            source,
+            vec![span(0, 1, "keyword")],
+            &HtmlFormat::CustomElements,
+            &options,
+        );
+        assert_eq!(
+            html,
+            "<span class=\"line\" id=\"ex1-L1\"><a-k>a</a-k></span>\n\
+             <span class=\"line\" id=\"ex1-L2\">b</span>\n\
+             <span class=\"line\" id=\"ex1-L3\"></span>\n\
+             <span class=\"line\" id=\"ex1-L4\">c</span>"
+        );
+
+        // Prefixes are escaped, and distinguish blocks on the same page
+        let options = HtmlOptions::default().line_anchors("\"x\"");
+        let html = spans_to_html_with_options("a", vec![], &HtmlFormat::CustomElements, &options);
+        assert_eq!(html, "<span class=\"line\" id=\"&quot;x&quot;L1\">a</span>");
+    }
+
+    #[test]
+    fn test_line_options_compose() {
+        // A multi-line comment and a decorated span crossing a line break
+        let source = "/* a\nb */ fn\nx";
+        let spans = vec![
+            span(0, 9, "comment"),
+            span(10, 12, "keyword"),
+            span(3, 6, "spell"),
+        ];
+        let options = HtmlOptions {
+            decorator: Some(Decorator::new(|ctx| {
+                (ctx.capture == "spell").then(|| Decoration::default().with_attribute("class", "d"))
+            })),
+            ..HtmlOptions::default()
+        }
+        .line_anchors("b2-")
+        .line_self_links(true)
+        .line_numbers(true)
+        .emphasize_lines([2..=2]);
+        let html = spans_to_html_with_options(
+            source,
+            spans.clone(),
+            &HtmlFormat::CustomElements,
+            &options,
+        );
+        assert_eq!(
+            html,
+            "<span class=\"line\" id=\"b2-L1\"><a class=\"line-anchor\" href=\"#b2-L1\">1</a>\
+             <a-c>/* </a-c><span class=\"d\"><a-c>a</a-c></span></span>\n\
+             <span class=\"line emphasized\" id=\"b2-L2\"><a class=\"line-anchor\" href=\"#b2-L2\">2</a>\
+             <span class=\"d\"><a-c>b</a-c></span><a-c> */</a-c> <a-k>fn</a-k></span>\n\
+             <span class=\"line\" id=\"b2-L3\"><a class=\"line-anchor\" href=\"#b2-L3\">3</a>x</span>"
+        );
+        let parsed = check_well_formed(&html).unwrap();
+        assert_eq!(parsed.text, "1/* a\n2b */ fn\n3x");
+        let ids: Vec<&str> = parsed
+            .attributes
+            .iter()
+            .filter(|(name, _)| name == "id")
+            .map(|(_, value)| value.as_str())
+            .collect();
+        assert_eq!(ids, ["b2-L1", "b2-L2", "b2-L3"]);
+
+        let chunks: String =
+            spans_to_html_chunks(source, spans, &HtmlFormat::CustomElements, &options, 1).collect();
+        assert_eq!(chunks, html);
+
+        // Numbers without anchors, and emphasis alone
+        let options = HtmlOptions::default().line_numbers(true);
+        let html =
+            spans_to_html_with_options("x\ny", vec![], &HtmlFormat::CustomElements, &options);
+        assert_eq!(
+            html,
+            "<span class=\"line\"><span class=\"line-number\">1</span>x</span>\n\
+             <span class=\"line\"><span class=\"line-number\">2</span>y</span>"
+        );
+        let options = HtmlOptions::default().emphasize_lines([1..=1]);
+        let html =
+            spans_to_html_with_options("x\ny", vec![], &HtmlFormat::CustomElements, &options);
+        assert_eq!(
+            html,
+            "<span class=\"line emphasized\">x</span>\n<span class=\"line\">y</span>"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_overlay() {
+        // The error starts inside `let` and ends inside the string
+        let source = "let s = \"abc\";";
+        let spans = vec![span(0, 3, "keyword"), span(8, 13, "string")];
+        let options = HtmlOptions::default().diagnostics([
+            Diagnostic::new(1..10, Severity::Error, "expected `\"` or '<'\n& more"),
+            Diagnostic::new(4..5, Severity::Warning, ""),
+        ]);
+        let html = spans_to_html_with_options(source, spans, &HtmlFormat::CustomElements, &options);
+        assert_eq!(
+            html,
+            "<a-k>l</a-k>\
+             <span class=\"diag diag-error\" \
+             title=\"expected `&quot;` or &#39;&lt;&#39;&#10;&amp; more\">\
+             <a-k>et</a-k> <span class=\"diag diag-warning\">s</span> = <a-s>&quot;a</a-s></span>\
+             <a-s>bc&quot;</a-s>;"
+        );
+        let parsed = check_well_formed(&html).unwrap();
+        assert_eq!(parsed.text, source);
+        assert_eq!(
+            parsed.attributes[1],
+            (
+                "title".to_string(),
+                "expected `\"` or '<'\n& more".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_snap_to_char_boundaries() {
+        // `é` and `ö` are two bytes each, and every range below starts or
+        // ends inside one of them
+        let source = "héllo wörld";
+        let spans = vec![span(0, 2, "keyword"), span(7, 9, "string")];
+        let options = HtmlOptions::default().diagnostics([
+            Diagnostic::new(3..9, Severity::Error, ""),
+            Diagnostic::new(9..40, Severity::Hint, ""),
+        ]);
+        let html = spans_to_html_with_options(source, spans, &HtmlFormat::CustomElements, &options);
+        assert_eq!(
+            html,
+            "<a-k>hé</a-k><span class=\"diag diag-error\">llo <a-s>w</a-s>\
+             <span class=\"diag diag-hint\"><a-s>ö</a-s></span></span>\
+             <span class=\"diag diag-hint\">rld</span>"
+        );
+        assert!(!html.contains('\u{FFFD}'));
+        let parsed = check_well_formed(&html).unwrap();
+        assert_eq!(parsed.text, source);
+    }
+
+    #[test]
+    fn test_diagnostics_overlap_decorations_and_lines() {
+        let source = "/* a\nb */ fn x";
+        let spans = vec![
+            span(0, 9, "comment"),
+            span(10, 12, "keyword"),
+            span(13, 14, "function"),
+        ];
+        let options = HtmlOptions {
+            decorator: Some(Decorator::new(|ctx| {
+                (ctx.capture == "function").then(|| Decoration::link("#x"))
+            })),
+            ..HtmlOptions::default()
+        }
+        .line_numbers(true)
+        .diagnostics([
+            Diagnostic::new(3..11, Severity::Error, "a"),
+            Diagnostic::new(7..14, Severity::Hint, "b"),
+        ]);
+        let html = spans_to_html_with_options(
+            source,
+            spans.clone(),
+            &HtmlFormat::CustomElements,
+            &options,
+        );
+        let parsed = check_well_formed(&html).unwrap_or_else(|e| panic!("{e}: {html}"));
+        assert_eq!(parsed.text, "1/* a\n2b */ fn x");
+        assert!(
+            html.contains("<a href=\"#x\"><a-f>x</a-f></a></span></span>"),
+            "{html}"
+        );
+
+        let chunks: String =
+            spans_to_html_chunks(source, spans, &HtmlFormat::CustomElements, &options, 2).collect();
+        assert_eq!(chunks, html);
+    }
+
+    /// `fn main() { "hi" } // c` with a keyword, function, brackets, string
+    /// and comment.
+    fn class_style_sample() -> (&'static str, Vec<Span>) {
+        let source = "fn main() { \"hi\" } // c";
+        let spans = vec![
+            span(0, 2, "keyword.function"),
+            span(3, 7, "function"),
+            span(7, 9, "punctuation.bracket"),
+            span(10, 11, "punctuation.bracket"),
+            span(12, 16, "string"),
+            span(17, 18, "punctuation.bracket"),
+            span(19, 23, "comment"),
+        ];
+        (source, spans)
+    }
+
+    fn render_with_class_style(style: ClassStyle) -> String {
+        let (source, spans) = class_style_sample();
+        let options = HtmlOptions::default().class_style(style);
+        let html = spans_to_html_with_options(source, spans, &HtmlFormat::default(), &options);
+        style.wrap_code_block(Some("rust"), &html)
+    }
+
+    #[test]
+    fn test_class_style_golden() {
+        assert_eq!(
+            render_with_class_style(ClassStyle::Arborium),
+            "<pre><code class=\"language-rust\"><a-k>fn</a-k> <a-f>main</a-f><a-p>()</a-p> \
+             <a-p>{</a-p> <a-s>&quot;hi&quot;</a-s> <a-p>}</a-p> <a-c>// c</a-c></code></pre>"
+        );
+        assert_eq!(
+            render_with_class_style(ClassStyle::HighlightJs),
+            "<pre><code class=\"hljs language-rust\">\
+             <span class=\"hljs-keyword\">fn</span> \
+             <span class=\"hljs-title function_\">main</span>\
+             <span class=\"hljs-punctuation\">()</span> \
+             <span class=\"hljs-punctuation\">{</span> \
+             <span class=\"hljs-string\">&quot;hi&quot;</span> \
+             <span class=\"hljs-punctuation\">}</span> \
+             <span class=\"hljs-comment\">// c</span></code></pre>"
+        );
+        assert_eq!(
+            render_with_class_style(ClassStyle::Syntect),
+            "<pre class=\"code\"><code><span class=\"source rust\">\
+             <span class=\"keyword\">fn</span> \
+             <span class=\"entity name function\">main</span>\
+             <span class=\"punctuation\">()</span> \
+             <span class=\"punctuation\">{</span> \
+             <span class=\"string\">&quot;hi&quot;</span> \
+             <span class=\"punctuation\">}</span> \
+             <span class=\"comment\">// c</span></span></code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_class_styles_ignore_format_and_do_not_leak() {
+        let (source, spans) = class_style_sample();
+        for style in [ClassStyle::HighlightJs, ClassStyle::Syntect] {
+            let options = HtmlOptions::default().class_style(style);
+            let expected =
+                spans_to_html_with_options(source, spans.clone(), &HtmlFormat::default(), &options);
+            for format in [
+                HtmlFormat::CustomElementsWithPrefix("code".into()),
+                HtmlFormat::ClassNames,
+                HtmlFormat::ClassNamesWithPrefix("arb".into()),
+            ] {
+                let html = spans_to_html_with_options(source, spans.clone(), &format, &options);
+                assert_eq!(html, expected, "{style:?} with {format:?}");
+            }
+
+            let html = style.wrap_code_block(Some("rust"), &expected);
+            check_well_formed(&html).unwrap_or_else(|e| panic!("{e}: {html}"));
+            assert!(!html.contains("<a-"), "{html}");
+            assert!(!html.contains("arb"), "{html}");
+            assert!(!html.contains("a-"), "{html}");
+        }
+
+        assert_eq!(ClassStyle::Arborium.class_for_tag("k"), None);
+        assert_eq!(ClassStyle::HighlightJs.class_for_tag("zz"), None);
+        assert_eq!(
+            ClassStyle::HighlightJs.wrap_code_block(Some("C++"), "x"),
+            "<pre><code class=\"hljs language-c--\">x</code></pre>"
+        );
+    }
+
+    fn span(start: u32, end: u32, capture: &str) -> Span {
+        Span {
+            start,
+            end,
+            capture: capture.into(),
+            pattern_index: 0,
+        }
+    }
+
+    /// Text content and attributes (in document order) of a parsed document.
+    #[derive(Debug, Default)]
+    struct Parsed {
+        text: String,
+        attributes: Vec<(String, String)>,
+    }
+
+    /// A minimal, strict XML well-formedness checker: balanced tags, quoted
+    /// attributes, valid references and no raw markup or control characters
+    /// in text. Anything it accepts is also parsed as intended by HTML.
+    fn check_well_formed(doc: &str) -> Result<Parsed, String> {
+        fn is_name_char(c: char) -> bool {
+            c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')
+        }
+
+        fn check_char(c: char) -> Result<(), String> {
+            if c.is_control() && !matches!(c, '\t' | '\n' | '\r') {
+                return Err(format!("raw control character {c:?}"));
+            }
+            Ok(())
+        }
+
+        /// Decode a reference starting after `&`, returning the character.
+        fn reference(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Result<char, String> {
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some(';') => break,
+                    Some(c) if c.is_ascii_alphanumeric() || c == '#' => name.push(c),
+                    other => return Err(format!("bad reference &{name}{other:?}")),
+                }
+            }
+            let c = match name.as_str() {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => name
+                    .strip_prefix('#')
+                    .and_then(|n| n.parse::<u32>().ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("unknown entity &{name};"))?,
+            };
+            check_char(c)?;
+            Ok(c)
+        }
+
+        let mut parsed = Parsed::default();
+        let mut stack: Vec<String> = Vec::new();
+        let mut chars = doc.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '<' => {
+                    let closing = chars.next_if_eq(&'/').is_some();
+                    let mut name = String::new();
+                    while let Some(c) = chars.next_if(|&c| is_name_char(c)) {
+                        name.push(c);
+                    }
+                    if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                        return Err(format!("bad tag name {name:?}"));
+                    }
+                    if closing {
+                        if chars.next() != Some('>') {
+                            return Err(format!("bad closing tag {name}"));
+                        }
+                        if stack.pop().as_deref() != Some(name.as_str()) {
+                            return Err(format!("unbalanced closing tag {name}"));
+                        }
+                        continue;
+                    }
+                    loop {
+                        let had_space = chars.next_if_eq(&' ').is_some();
+                        match chars.next() {
+                            Some('>') => {
+                                stack.push(name);
+                                break;
+                            }
+                            Some('/') if chars.next() == Some('>') => break,
+                            Some(c) if had_space && c.is_ascii_alphabetic() => {
+                                let mut attr = String::from(c);
+                                while let Some(c) = chars.next_if(|&c| is_name_char(c)) {
+                                    attr.push(c);
+                                }
+                                if chars.next() != Some('=') || chars.next() != Some('"') {
+                                    return Err(format!("unquoted attribute {attr}"));
+                                }
+                                let mut value = String::new();
+                                loop {
+                                    match chars.next() {
+                                        Some('"') => break,
+                                        Some('&') => value.push(reference(&mut chars)?),
+                                        Some('<') | None => {
+                                            return Err(format!("bad value for {attr}"));
+                                        }
+                                        Some('\t' | '\n' | '\r') => {
+                                            return Err(format!("raw whitespace in {attr}"));
+                                        }
+                                        Some(c) => {
+                                            check_char(c)?;
+                                            value.push(c);
+                                        }
+                                    }
+                                }
+                                parsed.attributes.push((attr, value));
+                            }
+                            other => return Err(format!("bad tag {name}: {other:?}")),
+                        }
+                    }
+                }
+                '&' => parsed.text.push(reference(&mut chars)?),
+                '>' => return Err("raw '>' in text".to_string()),
+                c => {
+                    check_char(c)?;
+                    parsed.text.push(c);
+                }
+            }
+        }
+        if let Some(name) = stack.pop() {
+            return Err(format!("unclosed tag {name}"));
+        }
+        Ok(parsed)
+    }
 }
@@ -32,7 +32,9 @@
 //! ```
 
 use crate::types::{Injection, ParseResult, Span};
-use arborium_tree_sitter::{Language, Parser, Query, QueryCursor};
+use arborium_tree_sitter::{
+    Language, Node, Parser, Point, Query, QueryCursor, QueryPredicate, QueryPredicateArg,
+};
 use streaming_iterator::StreamingIterator;
 
 /// Configuration for creating a [`CompiledGrammar`].
@@ -153,6 +155,15 @@ impl CompiledGrammar {
     /// Requires a [`ParseContext`] which holds the mutable parser state.
     /// Each thread should have its own context.
     pub fn parse(&self, ctx: &mut ParseContext, text: &str) -> ParseResult {
+        self.parse_bytes(ctx, text.as_bytes())
+    }
+
+    /// Parse text that may not be valid UTF-8.
+    ///
+    /// Tree-sitter works on bytes, so invalid sequences simply become part of
+    /// whatever node surrounds them. All offsets in the result refer to bytes
+    /// of `text`.
+    pub fn parse_bytes(&self, ctx: &mut ParseContext, text: &[u8]) -> ParseResult {
         // Parse the text
         let tree = match ctx.parser.parse(text, None) {
             Some(tree) => tree,
@@ -160,7 +171,7 @@ impl CompiledGrammar {
         };
 
         let root_node = tree.root_node();
-        let source = text.as_bytes();
+        let source = text;
 
         // Collect highlight spans
         let mut spans = Vec::new();
@@ -203,6 +214,7 @@ impl CompiledGrammar {
                 let mut content_node = None;
                 let mut language_name = None;
                 let mut include_children = false;
+                let mut combined = false;
 
                 // Check for #set! injection.language property
                 for prop in injections_query.property_settings(m.pattern_index) {
@@ -215,6 +227,9 @@ impl CompiledGrammar {
                         "injection.include-children" => {
                             include_children = true;
                         }
+                        "injection.combined" => {
+                            combined = true;
+                        }
                         _ => {}
                     }
                 }
@@ -234,12 +249,37 @@ impl CompiledGrammar {
                 }
 
                 if let (Some(node), Some(lang)) = (content_node, language_name) {
-                    injections.push(Injection {
-                        start: node.start_byte() as u32,
-                        end: node.end_byte() as u32,
-                        language: lang,
-                        include_children,
-                    });
+                    let (mut start, mut end) = (node.start_byte(), node.end_byte());
+
+                    // Apply #offset! to the content, e.g. to skip a prefix
+                    for predicate in injections_query.general_predicates(m.pattern_index) {
+                        let Some(capture) = self.injection_content_idx else {
+                            continue;
+                        };
+                        if let Some(offsets) = injection_offsets(predicate, capture) {
+                            start = offset_byte(source, node.start_position(), start, offsets.0);
+                            end = offset_byte(source, node.end_position(), end, offsets.1);
+                        }
+                    }
+
+                    // Combined content is stitched back together by the
+                    // highlighter, so it can leave its children out
+                    let ranges = if combined && !include_children {
+                        exclude_children(node, start, end)
+                    } else {
+                        vec![(start, end)]
+                    };
+                    for (start, end) in ranges {
+                        if start < end {
+                            injections.push(Injection {
+                                start: start as u32,
+                                end: end as u32,
+                                language: lang.clone(),
+                                include_children,
+                                combined,
+                            });
+                        }
+                    }
                 }
             }
         }
@@ -248,6 +288,78 @@ impl CompiledGrammar {
     }
 }
 
+/// The `(rows, columns)` moves of the start and end of `capture` that an
+/// `(#offset! @capture start_row start_col end_row end_col)` predicate asks
+/// for, if `predicate` is one.
+///
+/// This matches the plugin runtime, so both produce the same injections.
+fn injection_offsets(
+    predicate: &QueryPredicate,
+    capture: u32,
+) -> Option<((isize, isize), (isize, isize))> {
+    if &*predicate.operator != "offset!" {
+        return None;
+    }
+    let [QueryPredicateArg::Capture(index), rest @ ..] = &*predicate.args else {
+        return None;
+    };
+    if *index != capture {
+        return None;
+    }
+    let mut numbers = rest.iter().map(|arg| match arg {
+        QueryPredicateArg::String(n) => n.parse::<isize>().ok(),
+        QueryPredicateArg::Capture(_) => None,
+    });
+    let mut next = || numbers.next().flatten();
+    Some(((next()?, next()?), (next()?, next()?)))
+}
+
+/// Move `byte`, at `point`, by `(rows, columns)` the way `#offset!` does:
+/// down or up a number of lines, keeping the column, then along the line.
+/// The result stays within the line it lands on.
+fn offset_byte(source: &[u8], point: Point, byte: usize, (rows, columns): (isize, isize)) -> usize {
+    let line_end = |start: usize| {
+        source[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(source.len(), |i| start + i)
+    };
+    let mut line_start = byte - point.column;
+    for _ in 0..rows.max(0) {
+        let end = line_end(line_start);
+        if end == source.len() {
+            return source.len();
+        }
+        line_start = end + 1;
+    }
+    for _ in 0..(-rows).max(0) {
+        line_start = source[..line_start.saturating_sub(1)]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+    }
+    let column = (point.column as isize + columns).max(0) as usize;
+    (line_start + column).min(line_end(line_start))
+}
+
+/// Split `start..end`, a range within `node`, around the node's children,
+/// the way tree-sitter injections without `injection.include-children` do.
+fn exclude_children(node: Node, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut from = start;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.end_byte() <= from || child.start_byte() >= end {
+            continue;
+        }
+        ranges.push((from, child.start_byte().max(from)));
+        from = child.end_byte().min(end);
+    }
+    ranges.push((from, end));
+    ranges.retain(|(start, end)| start < end);
+    ranges
+}
+
 /// Per-thread parsing context.
 ///
 /// This holds the mutable state needed for parsing: a [`Parser`] and [`QueryCursor`].
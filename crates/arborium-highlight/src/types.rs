@@ -46,6 +46,10 @@ pub struct Injection {
 
     /// Whether to include the node's children in the injection range.
     pub include_children: bool,
+
+    /// Whether this is one fragment of a combined injection. All fragments
+    /// with the same language are parsed together as one document.
+    pub combined: bool,
 }
 
 /// Result of parsing a document with a grammar.
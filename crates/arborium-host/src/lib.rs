@@ -21,17 +21,42 @@
 //!
 //!     // Parse text using a grammar handle (sync).
 //!     parse(handle, text) { ... },
+//!
+//!     // Return the syntax tree for text (sync, optional: only needed
+//!     // for `parseTree`).
+//!     parseTree(handle, text, maxDepth, maxNodes) { ... },
+//!
+//!     // Return the nodes covering a byte offset (sync, optional: only
+//!     // needed for `nodeAt`).
+//!     nodeAt(handle, text, offset) { ... },
+//!
+//!     // Map a file extension or alias to a language id, or null (sync,
+//!     // optional: without it, only exact language ids are recognized).
+//!     languageForExtension(extension) { ... },
 //! };
 //! ```
+//!
+//! Grammar plugins can also be handed to the host by URL with
+//! `registerGrammarModule`, in which case the host fetches and drives them
+//! itself, without their JS glue; see [`register_grammar_module`].
+//! Registered modules take precedence over `arboriumHost`, which is then
+//! only needed for languages that aren't registered.
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 
 use wasm_bindgen::prelude::*;
 
 use arborium_highlight::{
-    AsyncHighlighter, Grammar, GrammarProvider, HighlightConfig as CoreConfig,
-    HtmlFormat as CoreHtmlFormat, Injection, ParseResult, Span,
+    AsyncHighlighter, Grammar, GrammarProvider, HighlightConfig as CoreConfig, HighlightError,
+    HtmlFormat as CoreHtmlFormat, Injection, ParseResult, Span, detect, html_escape,
+    spans_to_html_chunks,
 };
+use web_sys::AbortSignal;
+
+mod modules;
+
+pub use modules::{preload_grammar_module, register_grammar_module};
 
 /// Grammar handle type (matches JS side)
 type GrammarHandle = u32;
@@ -52,6 +77,29 @@ extern "C" {
     /// Returns { spans: [...], injections: [...] }
     #[wasm_bindgen(js_namespace = arboriumHost, js_name = parse)]
     fn js_parse(handle: GrammarHandle, text: &str) -> JsValue;
+
+    /// Return the syntax tree for text using a grammar handle.
+    /// Returns { root: {...}, node_count, truncated }
+    #[wasm_bindgen(js_namespace = arboriumHost, js_name = parseTree, catch)]
+    fn js_parse_tree(
+        handle: GrammarHandle,
+        text: &str,
+        max_depth: u32,
+        max_nodes: u32,
+    ) -> Result<JsValue, JsValue>;
+
+    /// Return the nodes covering a byte offset, from the root down.
+    #[wasm_bindgen(js_namespace = arboriumHost, js_name = nodeAt, catch)]
+    fn js_node_at(handle: GrammarHandle, text: &str, offset: u32) -> Result<JsValue, JsValue>;
+
+    /// Map a file extension or alias to a language id.
+    /// Returns a string, or null/undefined if unknown.
+    #[wasm_bindgen(js_namespace = arboriumHost, js_name = languageForExtension, catch)]
+    fn js_language_for_extension(extension: &str) -> Result<JsValue, JsValue>;
+
+    /// Schedule a callback on the event loop (works in windows and workers).
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn js_set_timeout(callback: &js_sys::Function, delay: i32) -> JsValue;
 }
 
 /// Parse the JS result object into our ParseResult.
@@ -130,35 +178,50 @@ fn parse_js_result(value: JsValue) -> ParseResult {
             .ok()
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
+        let combined = Reflect::get(&inj_obj, &"combined".into())
+            .ok()
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
         injections.push(Injection {
             start,
             end,
             language,
             include_children,
+            combined,
         });
     }
 
     ParseResult { spans, injections }
 }
 
-/// A grammar that wraps a JS grammar handle.
+/// A grammar that wraps a JS grammar handle, or a session in a grammar
+/// module the host loaded itself.
 ///
 /// When `parse()` is called, it calls into JS synchronously.
 pub struct JsGrammar {
-    handle: GrammarHandle,
+    backend: Backend,
+}
+
+enum Backend {
+    Js(GrammarHandle),
+    Module(modules::ModuleGrammar),
 }
 
 impl JsGrammar {
     fn new(handle: GrammarHandle) -> Self {
-        Self { handle }
+        Self {
+            backend: Backend::Js(handle),
+        }
     }
 }
 
 impl Grammar for JsGrammar {
     fn parse(&mut self, text: &str) -> ParseResult {
-        let result = js_parse(self.handle, text);
-        parse_js_result(result)
+        match &mut self.backend {
+            Backend::Js(handle) => parse_js_result(js_parse(*handle, text)),
+            Backend::Module(grammar) => grammar.parse(text),
+        }
     }
 }
 
@@ -169,12 +232,24 @@ impl Grammar for JsGrammar {
 pub struct JsGrammarProvider {
     /// Cached grammars by language name
     grammars: HashMap<String, JsGrammar>,
+    /// Whether to wait for grammar modules that are still loading
+    wait_for_modules: bool,
 }
 
 impl JsGrammarProvider {
     pub fn new() -> Self {
         Self {
             grammars: HashMap::new(),
+            wait_for_modules: true,
+        }
+    }
+
+    /// A provider that treats grammar modules still loading as unavailable,
+    /// starting their load.
+    pub fn without_waiting() -> Self {
+        Self {
+            wait_for_modules: false,
+            ..Self::new()
         }
     }
 }
@@ -191,16 +266,28 @@ impl GrammarProvider for JsGrammarProvider {
     // This crate is only compiled for wasm32, so we use the non-Send version
     #[cfg(target_arch = "wasm32")]
     async fn get(&mut self, language: &str) -> Option<&mut Self::Grammar> {
-        // Check if language is available (fast sync check)
-        if !js_is_language_available(language) {
-            return None;
-        }
-
         // Check if we already have this grammar cached
         if self.grammars.contains_key(language) {
             return self.grammars.get_mut(language);
         }
 
+        // Modules the host loads itself come first
+        if modules::is_registered(language) {
+            let grammar = modules::open(language, self.wait_for_modules).await?;
+            self.grammars.insert(
+                language.to_string(),
+                JsGrammar {
+                    backend: Backend::Module(grammar),
+                },
+            );
+            return self.grammars.get_mut(language);
+        }
+
+        // Check if language is available (fast sync check)
+        if !js_is_language_available(language) {
+            return None;
+        }
+
         // Load the grammar from JS (async)
         let handle = match js_load_grammar(language).await {
             Ok(val) => val.as_f64().unwrap_or(0.0) as GrammarHandle,
@@ -297,10 +384,11 @@ pub async fn highlight_with_config(
     let core_config = CoreConfig {
         max_injection_depth: config.max_injection_depth,
         html_format: config.html_format.clone(),
+        ..Default::default()
     };
 
     let provider = JsGrammarProvider::new();
-    let mut highlighter = AsyncHighlighter::with_config(provider, core_config);
+    let mut highlighter = new_highlighter(provider, core_config);
 
     highlighter
         .highlight(language, source)
@@ -308,8 +396,344 @@ pub async fn highlight_with_config(
         .map_err(|e| JsValue::from_str(&format!("{}", e)))
 }
 
+/// Highlight with the grammars loaded so far, without waiting for any.
+///
+/// Grammar modules registered with [`register_grammar_module`] that aren't
+/// loaded yet start loading, and meanwhile their text renders as plain,
+/// HTML-escaped text; this applies to injected languages too. Call again
+/// once they're loaded (see [`preload_grammar_module`]) to get the full
+/// highlighting. Grammars served through `arboriumHost` are awaited as in
+/// [`highlight`].
+#[wasm_bindgen(js_name = highlightNow)]
+pub async fn highlight_now(language: &str, source: &str) -> Result<String, JsValue> {
+    let provider = JsGrammarProvider::without_waiting();
+    let mut highlighter = new_highlighter(provider, CoreConfig::default());
+
+    match highlighter.highlight(language, source).await {
+        Err(HighlightError::UnsupportedLanguage(_)) if modules::is_registered(language) => {
+            Ok(html_escape(source))
+        }
+        result => result.map_err(|e| JsValue::from_str(&format!("{}", e))),
+    }
+}
+
 /// Check if a language is available for highlighting.
 #[wasm_bindgen(js_name = isLanguageAvailable)]
 pub fn is_language_available(language: &str) -> bool {
-    js_is_language_available(language)
+    modules::is_registered(language) || js_is_language_available(language)
+}
+
+/// Number of source bytes rendered per slice by [`highlight_async`].
+const ASYNC_CHUNK_BYTES: usize = 16 * 1024;
+
+/// Number of source bytes parsed per step by [`highlight_async`].
+const ASYNC_PARSE_BYTES: u32 = 16 * 1024;
+
+/// Build the error `highlight_async` rejects with when cancelled.
+///
+/// Its `name` is `"AbortError"`, matching what `fetch()` and friends reject
+/// with, so callers can use the same check for both.
+fn abort_error() -> JsValue {
+    let error = js_sys::Error::new("highlighting was cancelled");
+    error.set_name("AbortError");
+    error.into()
+}
+
+fn check_aborted(signal: Option<&AbortSignal>) -> Result<(), JsValue> {
+    match signal {
+        Some(signal) if signal.aborted() => Err(abort_error()),
+        _ => Ok(()),
+    }
+}
+
+/// Yield to the event loop so the page stays responsive.
+async fn yield_to_event_loop() {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        js_set_timeout(&resolve, 0);
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Highlight source code without blocking the main thread for long.
+///
+/// Parsing and HTML rendering are done in slices of roughly 16KiB of
+/// source, yielding to the event loop between slices. Two parts still run
+/// in one go: matching the highlight queries against the parsed tree, and
+/// everything about injected languages, which tend to be small. Grammars
+/// served through `arboriumHost` rather than [`register_grammar_module`]
+/// also parse in one go, as do modules built before parsing in steps.
+///
+/// If `signal` (an `AbortSignal`, e.g. from an `AbortController`) is aborted,
+/// work stops at the next slice boundary and the returned promise rejects
+/// with an error whose `name` is `"AbortError"`. This lets a newer request
+/// cancel an in-flight older one.
+#[wasm_bindgen(js_name = highlightAsync)]
+pub async fn highlight_async(
+    language: &str,
+    source: &str,
+    signal: Option<AbortSignal>,
+) -> Result<String, JsValue> {
+    highlight_async_with_config(language, source, HighlightConfig::default(), signal).await
+}
+
+/// Cancellable, chunked highlighting with custom configuration.
+///
+/// See [`highlight_async`].
+#[wasm_bindgen(js_name = highlightAsyncWithConfig)]
+pub async fn highlight_async_with_config(
+    language: &str,
+    source: &str,
+    config: HighlightConfig,
+    signal: Option<AbortSignal>,
+) -> Result<String, JsValue> {
+    let signal = signal.as_ref();
+    check_aborted(signal)?;
+
+    let core_config = CoreConfig {
+        max_injection_depth: config.max_injection_depth,
+        html_format: config.html_format.clone(),
+        ..Default::default()
+    };
+
+    let mut provider = JsGrammarProvider::new();
+    parse_in_steps(&mut provider, language, source, signal).await?;
+    let mut highlighter = new_highlighter(provider, core_config);
+
+    let spans = highlighter
+        .highlight_spans(language, source)
+        .await
+        .map_err(|e| JsValue::from_str(&format!("{}", e)))?;
+
+    let core_config = highlighter.config();
+    let mut html = String::with_capacity(source.len() * 2);
+    for chunk in spans_to_html_chunks(
+        source,
+        spans,
+        &core_config.html_format,
+        &core_config.html_options,
+        ASYNC_CHUNK_BYTES,
+    ) {
+        check_aborted(signal)?;
+        html.push_str(&chunk);
+        yield_to_event_loop().await;
+    }
+    check_aborted(signal)?;
+
+    Ok(html)
+}
+
+/// Parse `source` ahead of highlighting it, a slice at a time, if its
+/// grammar is a module that can do that. The highlighter then picks up the
+/// parsed tree instead of parsing again.
+async fn parse_in_steps(
+    provider: &mut JsGrammarProvider,
+    language: &str,
+    source: &str,
+    signal: Option<&AbortSignal>,
+) -> Result<(), JsValue> {
+    // Unsupported languages are reported by the highlighter
+    let Some(JsGrammar {
+        backend: Backend::Module(grammar),
+    }) = provider.get(language).await
+    else {
+        return Ok(());
+    };
+    if !grammar.load(source)? {
+        return Ok(());
+    }
+    loop {
+        check_aborted(signal)?;
+        if grammar.step(ASYNC_PARSE_BYTES)? {
+            return Ok(());
+        }
+        yield_to_event_loop().await;
+    }
+}
+
+/// Default depth limit for [`parse_tree`].
+const DEFAULT_TREE_MAX_DEPTH: u32 = 64;
+
+/// Default node-count cap for [`parse_tree`].
+const DEFAULT_TREE_MAX_NODES: u32 = 10_000;
+
+thread_local! {
+    /// Grammar handles used for tree inspection, by language.
+    ///
+    /// Each handle owns a parser session in its plugin, so they're reused
+    /// across calls rather than loaded per request.
+    static TREE_HANDLES: RefCell<HashMap<String, GrammarHandle>> = RefCell::new(HashMap::new());
+}
+
+/// Get (loading it on first use) the grammar handle for tree inspection.
+async fn tree_handle(language: &str) -> Result<GrammarHandle, JsValue> {
+    if let Some(handle) = TREE_HANDLES.with(|h| h.borrow().get(language).copied()) {
+        return Ok(handle);
+    }
+
+    let unsupported = || JsValue::from_str(&format!("Unsupported language: {}", language));
+    if !js_is_language_available(language) {
+        return Err(unsupported());
+    }
+    let handle = js_load_grammar(language).await?.as_f64().unwrap_or(0.0) as GrammarHandle;
+    if handle == 0 {
+        return Err(unsupported());
+    }
+
+    TREE_HANDLES.with(|h| h.borrow_mut().insert(language.to_string(), handle));
+    Ok(handle)
+}
+
+/// Parse source code and return its syntax tree, for tree explorers.
+///
+/// Resolves to `{ root, node_count, truncated }`, where each node is
+/// `{ kind, start, end, start_row, start_col, end_row, end_col, is_error,
+/// is_missing, children }`. Only named nodes (and missing nodes inserted by
+/// error recovery) are included. Offsets and columns are UTF-8 byte offsets.
+///
+/// At most `max_nodes` nodes (default 10000) are returned, down to
+/// `max_depth` levels below the root (default 64); `truncated` tells whether
+/// anything was left out.
+#[wasm_bindgen(js_name = parseTree)]
+pub async fn parse_tree(
+    language: &str,
+    source: &str,
+    max_depth: Option<u32>,
+    max_nodes: Option<u32>,
+) -> Result<JsValue, JsValue> {
+    let handle = tree_handle(language).await?;
+    js_parse_tree(
+        handle,
+        source,
+        max_depth.unwrap_or(DEFAULT_TREE_MAX_DEPTH),
+        max_nodes.unwrap_or(DEFAULT_TREE_MAX_NODES),
+    )
+}
+
+/// Return the nodes covering a UTF-8 byte offset, from the root down.
+///
+/// Nodes have the same shape as in [`parse_tree`], without children. The
+/// last node is the innermost one containing `offset`.
+#[wasm_bindgen(js_name = nodeAt)]
+pub async fn node_at(language: &str, source: &str, offset: u32) -> Result<JsValue, JsValue> {
+    let handle = tree_handle(language).await?;
+    js_node_at(handle, source, offset)
+}
+
+/// Resolve a file extension or language name to an available language id.
+fn resolve_language(name: &str) -> Option<String> {
+    // `languageForExtension` is optional, so a missing hook just falls through
+    if let Some(language) = js_language_for_extension(name)
+        .ok()
+        .and_then(|v| v.as_string())
+    {
+        return Some(language);
+    }
+    is_language_available(name).then(|| name.to_string())
+}
+
+/// Resolve an injection whose language isn't an id as written: names
+/// written loosely, like Org's `#+begin_src C` or AsciiDoc's
+/// `[source,rust]`, and files rather than languages, like the diff
+/// grammar's `+++ b/src/main.rs` headers, which are detected from the path.
+fn resolve_injection(language: &str) -> Option<String> {
+    if is_language_available(language) {
+        return None;
+    }
+    detect::injection_language_candidates(language)
+        .into_iter()
+        .find(|name| is_language_available(name))
+        .or_else(|| detect::detect_from_path(language, &resolve_language))
+}
+
+fn new_highlighter(
+    provider: JsGrammarProvider,
+    config: CoreConfig,
+) -> AsyncHighlighter<JsGrammarProvider> {
+    let mut highlighter = AsyncHighlighter::with_config(provider, config);
+    highlighter.set_injection_resolver(resolve_injection);
+    highlighter
+}
+
+fn detect_source(filename: Option<&str>, source: &str) -> Option<detect::Detection> {
+    detect::detect(filename, source.as_bytes(), &resolve_language)
+}
+
+/// Build a plain JS object from string properties, skipping `None`s.
+fn js_object(properties: &[(&str, Option<&str>)]) -> JsValue {
+    let obj = js_sys::Object::new();
+    for (key, value) in properties {
+        let value = value.map_or(JsValue::NULL, JsValue::from_str);
+        let _ = js_sys::Reflect::set(&obj, &(*key).into(), &value);
+    }
+    obj.into()
+}
+
+/// Detect the language of a file from its name (if known) and contents.
+///
+/// Tries, in order: the file extension, well-known file names
+/// (`Dockerfile`, `CMakeLists.txt`), the shebang line, a vim or emacs
+/// modeline, then content heuristics. Returns `undefined` if nothing matched.
+#[wasm_bindgen(js_name = detectLanguage)]
+pub fn detect_language(filename: Option<String>, source: &str) -> Option<String> {
+    detect_source(filename.as_deref(), source).map(|d| d.language)
+}
+
+/// Like [`detect_language`], but also reports how the language was found.
+///
+/// Returns `{ language, method }`, where `method` is one of `"extension"`,
+/// `"filename"`, `"shebang"`, `"modeline"` or `"heuristic"`, or `null` if
+/// nothing matched.
+#[wasm_bindgen(js_name = detectLanguageWithMethod)]
+pub fn detect_language_with_method(filename: Option<String>, source: &str) -> JsValue {
+    match detect_source(filename.as_deref(), source) {
+        Some(found) => js_object(&[
+            ("language", Some(found.language.as_str())),
+            ("method", Some(found.method.as_str())),
+        ]),
+        None => JsValue::NULL,
+    }
+}
+
+/// Detect the language of a file and highlight it in one call.
+///
+/// Resolves to `{ html, language, method }`. If no language is detected, or
+/// the detected one can't be loaded, `html` is the HTML-escaped source and
+/// `language` and `method` are `null`. Styling comes from CSS, as with
+/// [`highlight`]; pass a `config` to pick the HTML format.
+#[wasm_bindgen(js_name = highlightAuto)]
+pub async fn highlight_auto(
+    filename: Option<String>,
+    source: &str,
+    config: Option<HighlightConfig>,
+) -> Result<JsValue, JsValue> {
+    let plain = || {
+        js_object(&[
+            ("html", Some(html_escape(source).as_str())),
+            ("language", None),
+            ("method", None),
+        ])
+    };
+
+    let Some(found) = detect_source(filename.as_deref(), source) else {
+        return Ok(plain());
+    };
+
+    let config = config.unwrap_or_default();
+    let core_config = CoreConfig {
+        max_injection_depth: config.max_injection_depth,
+        html_format: config.html_format.clone(),
+        ..Default::default()
+    };
+    let provider = JsGrammarProvider::new();
+    let mut highlighter = new_highlighter(provider, core_config);
+
+    match highlighter.highlight(&found.language, source).await {
+        Ok(html) => Ok(js_object(&[
+            ("html", Some(html.as_str())),
+            ("language", Some(found.language.as_str())),
+            ("method", Some(found.method.as_str())),
+        ])),
+        Err(HighlightError::UnsupportedLanguage(_)) => Ok(plain()),
+        Err(e) => Err(JsValue::from_str(&format!("{}", e))),
+    }
 }
@@ -0,0 +1,355 @@
+//! Grammar plugins fetched and instantiated by the host itself.
+//!
+//! Rather than importing each plugin's wasm-bindgen JS glue, the host can
+//! fetch a plugin's `.wasm` file and drive it through the raw ABI described
+//! in [`arborium_wire::abi`]: a handful of exports taking and returning
+//! integers, with requests and results postcard-encoded in guest memory.
+//! The wasm-bindgen imports plugins carry are never called through that
+//! ABI, so they are stubbed out.
+//!
+//! Modules are registered by URL with [`register_grammar_module`], fetched
+//! the first time their language is highlighted, and kept for the lifetime
+//! of the page.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use arborium_highlight::{Injection, ParseResult, Span};
+use arborium_wire::abi;
+use arborium_wire::{ParseError, Utf8ParseResult, WIRE_VERSION, is_version_compatible};
+use js_sys::{Array, Function, Object, Reflect, Uint8Array, WebAssembly};
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Fetch a resource (works in windows and workers).
+    #[wasm_bindgen(js_name = fetch)]
+    fn js_fetch(url: &str) -> js_sys::Promise;
+}
+
+/// A loaded plugin module.
+pub(crate) struct PluginModule {
+    exports: Object,
+    memory: WebAssembly::Memory,
+}
+
+impl PluginModule {
+    /// Fetch, compile and instantiate the plugin at `url`.
+    async fn load(url: &str) -> Result<Self, JsValue> {
+        let response: web_sys::Response = JsFuture::from(js_fetch(url)).await?.dyn_into()?;
+        if !response.ok() {
+            return Err(JsValue::from_str(&format!(
+                "fetching {} failed with status {}",
+                url,
+                response.status()
+            )));
+        }
+        let bytes = JsFuture::from(response.array_buffer()?).await?;
+        let module: WebAssembly::Module = JsFuture::from(WebAssembly::compile(&bytes))
+            .await?
+            .dyn_into()?;
+        let instance: WebAssembly::Instance = JsFuture::from(WebAssembly::instantiate_module(
+            &module,
+            &stub_imports(&module)?,
+        ))
+        .await?
+        .dyn_into()?;
+
+        let exports = instance.exports();
+        let memory = Reflect::get(&exports, &"memory".into())?.dyn_into()?;
+        let plugin = Self { exports, memory };
+
+        let version = plugin.call(abi::WIRE_VERSION, &[])?.as_f64().unwrap_or(0.0) as u32;
+        if !is_version_compatible(version) {
+            return Err(JsValue::from_str(&format!(
+                "{} speaks wire version {}, the host speaks {}",
+                url, version, WIRE_VERSION
+            )));
+        }
+        Ok(plugin)
+    }
+
+    fn call(&self, name: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+        let func: Function = Reflect::get(&self.exports, &name.into())?
+            .dyn_into()
+            .map_err(|_| JsValue::from_str(&format!("plugin does not export {}", name)))?;
+        func.apply(&JsValue::UNDEFINED, &args.iter().collect::<Array>())
+    }
+
+    fn call_u32(&self, name: &str, args: &[JsValue]) -> Result<u32, JsValue> {
+        // i32 results come back signed
+        Ok(self.call(name, args)?.as_f64().unwrap_or(0.0) as i32 as u32)
+    }
+
+    fn create_session(&self) -> Result<u32, JsValue> {
+        self.call_u32(abi::CREATE_SESSION, &[])
+    }
+
+    fn free_session(&self, session: u32) -> Result<(), JsValue> {
+        self.call(abi::FREE_SESSION, &[session.into()]).map(drop)
+    }
+
+    fn has_export(&self, name: &str) -> bool {
+        Reflect::get(&self.exports, &name.into()).is_ok_and(|f| f.is_function())
+    }
+
+    /// Copy `text` into guest memory and pass it to the text-setting
+    /// export `name`.
+    fn send_text(&self, name: &str, session: u32, text: &str) -> Result<(), JsValue> {
+        let len = text.len() as u32;
+        let ptr = self.call_u32(abi::ALLOC, &[len.into()])?;
+        Uint8Array::new(&self.memory.buffer()).set(&Uint8Array::from(text.as_bytes()), ptr);
+        let packed = self.call(name, &[session.into(), ptr.into(), len.into()]);
+        self.call(abi::DEALLOC, &[ptr.into(), len.into()])?;
+        self.read_result::<()>(packed?)
+    }
+
+    /// Set the text of a session and parse it.
+    fn parse(&self, session: u32, text: &str) -> Result<Utf8ParseResult, JsValue> {
+        self.send_text(abi::SET_TEXT, session, text)?;
+        self.parse_loaded(session)
+    }
+
+    /// Highlight the text already in a session.
+    fn parse_loaded(&self, session: u32) -> Result<Utf8ParseResult, JsValue> {
+        let packed = self.call(abi::PARSE, &[session.into()])?;
+        self.read_result(packed)
+    }
+
+    /// Copy out, free and decode a `Result<T, ParseError>` returned by the
+    /// guest.
+    fn read_result<T: DeserializeOwned>(&self, packed: JsValue) -> Result<T, JsValue> {
+        let packed = u64::try_from(packed)
+            .map_err(|_| JsValue::from_str("plugin returned a non-integer result"))?;
+        let (ptr, len) = abi::unpack(packed);
+        // Views go stale when memory grows, so take a fresh one
+        let bytes = Uint8Array::new(&self.memory.buffer())
+            .subarray(ptr, ptr + len)
+            .to_vec();
+        self.call(abi::DEALLOC, &[ptr.into(), len.into()])?;
+
+        let result: Result<T, ParseError> = postcard::from_bytes(&bytes)
+            .map_err(|e| JsValue::from_str(&format!("failed to decode result: {}", e)))?;
+        result.map_err(|e| JsValue::from_str(&format!("parse error: {}", e.message)))
+    }
+}
+
+/// Build an import object stubbing every function `module` imports.
+///
+/// Plugins import wasm-bindgen glue for their JS API; the raw ABI never
+/// reaches it, so the stubs just throw.
+fn stub_imports(module: &WebAssembly::Module) -> Result<Object, JsValue> {
+    let imports = Object::new();
+    for import in WebAssembly::Module::imports(module).iter() {
+        let namespace = Reflect::get(&import, &"module".into())?;
+        let name = Reflect::get(&import, &"name".into())?;
+        let kind = Reflect::get(&import, &"kind".into())?;
+        if kind.as_string().as_deref() != Some("function") {
+            continue;
+        }
+
+        let mut target = Reflect::get(&imports, &namespace)?;
+        if target.is_undefined() {
+            target = Object::new().into();
+            Reflect::set(&imports, &namespace, &target)?;
+        }
+        let message = format!(
+            "grammar plugin called `{}`, which the arborium host does not provide",
+            name.as_string().unwrap_or_default()
+        );
+        let stub = Function::new_no_args(&format!("throw new Error({:?});", message));
+        Reflect::set(&target, &name, &stub)?;
+    }
+    Ok(imports)
+}
+
+/// A session in a [`PluginModule`], freed on drop.
+pub(crate) struct ModuleGrammar {
+    module: Rc<PluginModule>,
+    session: u32,
+    /// Text loaded with [`load`](Self::load), which `parse` doesn't need to
+    /// send again.
+    loaded: Option<String>,
+}
+
+impl ModuleGrammar {
+    fn new(module: Rc<PluginModule>) -> Result<Self, JsValue> {
+        let session = module.create_session()?;
+        Ok(Self {
+            module,
+            session,
+            loaded: None,
+        })
+    }
+
+    /// Load `text` into the session without parsing it, for
+    /// [`step`](Self::step). Returns `false` if the plugin predates
+    /// parsing in steps.
+    pub(crate) fn load(&mut self, text: &str) -> Result<bool, JsValue> {
+        self.loaded = None;
+        if !self.module.has_export(abi::LOAD_TEXT) || !self.module.has_export(abi::PARSE_STEP) {
+            return Ok(false);
+        }
+        self.module.send_text(abi::LOAD_TEXT, self.session, text)?;
+        self.loaded = Some(text.to_string());
+        Ok(true)
+    }
+
+    /// Parse the loaded text up to about `max_bytes` further. Returns
+    /// whether the parse is complete.
+    pub(crate) fn step(&mut self, max_bytes: u32) -> Result<bool, JsValue> {
+        let packed = self
+            .module
+            .call(abi::PARSE_STEP, &[self.session.into(), max_bytes.into()])?;
+        self.module.read_result(packed)
+    }
+
+    pub(crate) fn parse(&mut self, text: &str) -> ParseResult {
+        let result = if self.loaded.take().as_deref() == Some(text) {
+            self.module.parse_loaded(self.session)
+        } else {
+            self.module.parse(self.session, text)
+        };
+        match result {
+            Ok(result) => ParseResult {
+                spans: result
+                    .spans
+                    .into_iter()
+                    .map(|s| Span {
+                        start: s.start,
+                        end: s.end,
+                        capture: s.capture,
+                        pattern_index: s.pattern_index,
+                    })
+                    .collect(),
+                injections: result
+                    .injections
+                    .into_iter()
+                    .map(|i| Injection {
+                        start: i.start,
+                        end: i.end,
+                        language: i.language,
+                        include_children: i.include_children,
+                        combined: i.combined,
+                    })
+                    .collect(),
+            },
+            Err(e) => {
+                web_sys::console::error_1(&e);
+                ParseResult::default()
+            }
+        }
+    }
+}
+
+impl Drop for ModuleGrammar {
+    fn drop(&mut self) {
+        let _ = self.module.free_session(self.session);
+    }
+}
+
+enum ModuleState {
+    /// Registered, not requested yet.
+    Registered(String),
+    /// Being fetched; the promise settles once it's `Ready` or `Failed`.
+    Loading(js_sys::Promise),
+    Ready(Rc<PluginModule>),
+    Failed,
+}
+
+thread_local! {
+    /// Registered grammar modules, by language.
+    static MODULES: RefCell<HashMap<String, ModuleState>> = RefCell::new(HashMap::new());
+}
+
+/// Whether a grammar module is registered for `language`.
+pub(crate) fn is_registered(language: &str) -> bool {
+    MODULES.with(|m| m.borrow().contains_key(language))
+}
+
+/// Start loading the module for `language` if it isn't already, returning
+/// the promise of the load, or `None` if there's nothing to wait for.
+fn start_loading(language: &str) -> Option<js_sys::Promise> {
+    MODULES.with(|m| {
+        let mut modules = m.borrow_mut();
+        let url = match modules.get(language)? {
+            ModuleState::Registered(url) => url.clone(),
+            ModuleState::Loading(promise) => return Some(promise.clone()),
+            ModuleState::Ready(_) | ModuleState::Failed => return None,
+        };
+
+        let key = language.to_string();
+        let promise = wasm_bindgen_futures::future_to_promise(async move {
+            let state = match PluginModule::load(&url).await {
+                Ok(module) => ModuleState::Ready(Rc::new(module)),
+                Err(e) => {
+                    web_sys::console::error_2(
+                        &JsValue::from_str(&format!("failed to load grammar module {}:", url)),
+                        &e,
+                    );
+                    ModuleState::Failed
+                }
+            };
+            MODULES.with(|m| {
+                let mut modules = m.borrow_mut();
+                // Unless it was registered again in the meantime
+                if matches!(modules.get(&key), Some(ModuleState::Loading(_))) {
+                    modules.insert(key, state);
+                }
+            });
+            Ok(JsValue::UNDEFINED)
+        });
+        modules.insert(language.to_string(), ModuleState::Loading(promise.clone()));
+        Some(promise)
+    })
+}
+
+/// Open a session in the module for `language`.
+///
+/// With `wait`, fetches the module first if needed. Without, returns `None`
+/// while it's loading, kicking off the load.
+pub(crate) async fn open(language: &str, wait: bool) -> Option<ModuleGrammar> {
+    if let Some(promise) = start_loading(language) {
+        if !wait {
+            return None;
+        }
+        let _ = JsFuture::from(promise).await;
+    }
+
+    let module = MODULES.with(|m| match m.borrow().get(language) {
+        Some(ModuleState::Ready(module)) => Some(module.clone()),
+        _ => None,
+    })?;
+    match ModuleGrammar::new(module) {
+        Ok(grammar) => Some(grammar),
+        Err(e) => {
+            web_sys::console::error_1(&e);
+            None
+        }
+    }
+}
+
+/// Serve `language` from the grammar plugin at `url`.
+///
+/// The plugin is fetched the first time `language` is highlighted, directly
+/// or through an injection, and its instance is kept for later calls.
+/// Registering a language again with another URL replaces the module.
+#[wasm_bindgen(js_name = registerGrammarModule)]
+pub fn register_grammar_module(language: &str, url: &str) {
+    MODULES.with(|m| {
+        m.borrow_mut().insert(
+            language.to_string(),
+            ModuleState::Registered(url.to_string()),
+        )
+    });
+}
+
+/// Fetch the grammar module registered for `language` now, rather than on
+/// first use. Resolves to whether it loaded.
+#[wasm_bindgen(js_name = preloadGrammarModule)]
+pub async fn preload_grammar_module(language: &str) -> bool {
+    open(language, true).await.is_some()
+}
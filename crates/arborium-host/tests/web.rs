@@ -0,0 +1,416 @@
+//! Browser tests for the wasm host.
+//!
+//! Run with `wasm-pack test --headless --firefox crates/arborium-host`.
+
+#![cfg(target_arch = "wasm32")]
+
+use arborium_host::{
+    detect_language, detect_language_with_method, highlight, highlight_async, highlight_auto,
+    highlight_now, is_language_available, node_at, parse_tree, preload_grammar_module,
+    register_grammar_module,
+};
+use js_sys::{Array, Function, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+use web_sys::AbortController;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Install a fake `globalThis.arboriumHost` whose grammar marks every
+/// `x` as a keyword, and reset its call counters.
+fn install_fake_host() {
+    Function::new_no_args(
+        r#"
+        globalThis.fakeHostCalls = { load: 0, parse: 0 };
+        globalThis.arboriumHost = {
+            isLanguageAvailable(language) { return language === "fake"; },
+            async loadGrammar(language) {
+                globalThis.fakeHostCalls.load++;
+                return 1;
+            },
+            parse(handle, text) {
+                globalThis.fakeHostCalls.parse++;
+                const spans = [];
+                for (let i = 0; i < text.length; i++) {
+                    if (text[i] === "x") {
+                        spans.push({ start: i, end: i + 1, capture: "keyword", pattern_index: 0 });
+                    }
+                }
+                return { spans, injections: [] };
+            },
+            parseTree(handle, text, maxDepth, maxNodes) {
+                globalThis.fakeHostCalls.parse++;
+                return {
+                    root: { kind: "source_file", start: 0, end: text.length, children: [] },
+                    node_count: 1,
+                    truncated: maxDepth === 0,
+                };
+            },
+            nodeAt(handle, text, offset) {
+                return [{ kind: "source_file", start: 0, end: text.length, children: [] }];
+            },
+        };
+        "#,
+    )
+    .call0(&JsValue::NULL)
+    .unwrap();
+}
+
+fn host_calls(kind: &str) -> u32 {
+    let calls = Reflect::get(&js_sys::global(), &"fakeHostCalls".into()).unwrap();
+    Reflect::get(&calls, &kind.into())
+        .unwrap()
+        .as_f64()
+        .unwrap() as u32
+}
+
+fn error_name(error: &JsValue) -> String {
+    Reflect::get(error, &"name".into())
+        .unwrap()
+        .as_string()
+        .unwrap_or_default()
+}
+
+/// Source large enough to be rendered in several slices.
+fn large_source() -> String {
+    "x = y;\n".repeat(20_000)
+}
+
+/// Let timers and other tasks queued so far run.
+async fn next_tick() {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn highlight_async_renders() {
+    install_fake_host();
+
+    let html = highlight_async("fake", "x y x", None).await.unwrap();
+    assert_eq!(html, "<a-k>x</a-k> y <a-k>x</a-k>");
+}
+
+#[wasm_bindgen_test]
+async fn highlight_async_already_aborted() {
+    install_fake_host();
+
+    let controller = AbortController::new().unwrap();
+    controller.abort();
+
+    let error = highlight_async("fake", "x", Some(controller.signal()))
+        .await
+        .unwrap_err();
+    assert_eq!(error_name(&error), "AbortError");
+
+    // No work was started at all
+    assert_eq!(host_calls("load"), 0);
+    assert_eq!(host_calls("parse"), 0);
+}
+
+#[wasm_bindgen_test]
+async fn highlight_async_cancelled_in_flight() {
+    install_fake_host();
+
+    let source = large_source();
+    let controller = AbortController::new().unwrap();
+
+    // Abort from a timer, which fires while the render is yielding
+    // between slices.
+    let abort = Closure::once_into_js({
+        let controller = controller.clone();
+        move || controller.abort()
+    });
+    web_sys::window()
+        .unwrap()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(abort.unchecked_ref(), 0)
+        .unwrap();
+
+    let error = highlight_async("fake", &source, Some(controller.signal()))
+        .await
+        .unwrap_err();
+    assert_eq!(error_name(&error), "AbortError");
+    assert_eq!(host_calls("parse"), 1);
+}
+
+#[wasm_bindgen_test]
+async fn highlight_async_newer_request_cancels_older() {
+    install_fake_host();
+
+    let source = large_source();
+    let older = AbortController::new().unwrap();
+    let older_signal = older.signal();
+
+    let first = wasm_bindgen_futures::future_to_promise({
+        let source = source.clone();
+        async move {
+            highlight_async("fake", &source, Some(older_signal))
+                .await
+                .map(JsValue::from)
+        }
+    });
+
+    // Let the older request get going. It parses and renders its first
+    // slice in one go before yielding, so once it has parsed, it is in
+    // flight between slices.
+    while host_calls("parse") == 0 {
+        next_tick().await;
+    }
+
+    // A newer request comes in: cancel the older one and render this one
+    older.abort();
+    let html = highlight_async("fake", "x", None).await.unwrap();
+    assert_eq!(html, "<a-k>x</a-k>");
+
+    let error = wasm_bindgen_futures::JsFuture::from(first)
+        .await
+        .unwrap_err();
+    assert_eq!(error_name(&error), "AbortError");
+}
+
+fn get(value: &JsValue, path: &[&str]) -> JsValue {
+    path.iter().fold(value.clone(), |v, key| {
+        Reflect::get(&v, &(*key).into()).unwrap()
+    })
+}
+
+#[wasm_bindgen_test]
+async fn parse_tree_reuses_grammar_handle() {
+    install_fake_host();
+
+    let tree = parse_tree("fake", "x", None, None).await.unwrap();
+    assert_eq!(
+        get(&tree, &["root", "kind"]).as_string().unwrap(),
+        "source_file"
+    );
+    assert_eq!(get(&tree, &["truncated"]).as_bool(), Some(false));
+
+    let tree = parse_tree("fake", "x", Some(0), None).await.unwrap();
+    assert_eq!(get(&tree, &["truncated"]).as_bool(), Some(true));
+
+    let spine = node_at("fake", "x", 0).await.unwrap();
+    assert_eq!(
+        get(&spine, &["0", "kind"]).as_string().unwrap(),
+        "source_file"
+    );
+
+    // The grammar was loaded once and then reused
+    assert!(host_calls("load") <= 1);
+    assert_eq!(host_calls("parse"), 2);
+}
+
+#[wasm_bindgen_test]
+async fn parse_tree_unsupported_language() {
+    install_fake_host();
+
+    assert!(parse_tree("nope", "x", None, None).await.is_err());
+    assert_eq!(host_calls("load"), 0);
+}
+
+#[wasm_bindgen_test]
+fn detect_python_by_shebang() {
+    install_fake_host();
+
+    let source = "#!/usr/bin/env python3\nprint('hi')\n";
+    assert_eq!(detect_language(None, source).as_deref(), Some("python"));
+
+    let found = detect_language_with_method(None, source);
+    assert_eq!(get(&found, &["language"]).as_string().unwrap(), "python");
+    assert_eq!(get(&found, &["method"]).as_string().unwrap(), "shebang");
+}
+
+#[wasm_bindgen_test]
+fn detect_dockerfile_by_name() {
+    install_fake_host();
+
+    let found = detect_language_with_method(
+        Some("docker/Dockerfile".to_string()),
+        "FROM alpine\nRUN true\n",
+    );
+    assert_eq!(
+        get(&found, &["language"]).as_string().unwrap(),
+        "dockerfile"
+    );
+    assert_eq!(get(&found, &["method"]).as_string().unwrap(), "filename");
+
+    assert!(detect_language_with_method(None, "just words").is_null());
+}
+
+#[wasm_bindgen_test]
+async fn highlight_auto_detects_and_falls_back() {
+    install_fake_host();
+
+    let result = highlight_auto(Some("main.fake".to_string()), "x y", None)
+        .await
+        .unwrap();
+    assert_eq!(
+        get(&result, &["html"]).as_string().unwrap(),
+        "<a-k>x</a-k> y"
+    );
+    assert_eq!(get(&result, &["language"]).as_string().unwrap(), "fake");
+    assert_eq!(get(&result, &["method"]).as_string().unwrap(), "extension");
+
+    // Nothing detected: escaped plain text
+    let result = highlight_auto(None, "a < b", None).await.unwrap();
+    assert_eq!(get(&result, &["html"]).as_string().unwrap(), "a &lt; b");
+    assert!(get(&result, &["language"]).is_null());
+
+    // Detected, but no grammar for it: also plain text
+    let result = highlight_auto(None, "#!/bin/sh\n<x>", None).await.unwrap();
+    assert_eq!(
+        get(&result, &["html"]).as_string().unwrap(),
+        "#!/bin/sh\n&lt;x&gt;"
+    );
+}
+
+/// A grammar plugin in WAT implementing the raw ABI, whose parse always
+/// returns a `keyword` span over bytes 0..2. It imports wasm-bindgen glue
+/// like real plugins do.
+fn fake_plugin_url() -> String {
+    let wasm = wat::parse_str(format!(
+        r#"(module
+          (import "__wbindgen_placeholder__" "__wbindgen_describe" (func (param i32)))
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (data (i32.const 16) "\00")
+          (data (i32.const 32) "\00\01\00\02\07keyword\00\00")
+          (func (export "arborium_wire_version") (result i32) (i32.const {version}))
+          (func (export "arborium_alloc") (param i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get 0)))
+            (local.get $ptr))
+          (func (export "arborium_dealloc") (param i32 i32))
+          (func (export "arborium_create_session") (result i32) (i32.const 1))
+          (func (export "arborium_free_session") (param i32))
+          (func (export "arborium_set_text") (param i32 i32 i32) (result i64)
+            (i64.const {ok_unit}))
+          (func (export "arborium_parse") (param i32) (result i64) (i64.const {ok_parse})))"#,
+        version = arborium_wire::WIRE_VERSION,
+        ok_unit = arborium_wire::abi::pack(16, 1),
+        ok_parse = arborium_wire::abi::pack(32, 14),
+    ))
+    .unwrap();
+    blob_url(&wasm)
+}
+
+/// Like [`fake_plugin_url`], but also exporting the calls to parse in
+/// steps. The parse completes after `steps` steps, or never with
+/// `u32::MAX`.
+fn fake_stepping_plugin_url(steps: u32) -> String {
+    let wasm = wat::parse_str(format!(
+        r#"(module
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (global $steps (mut i32) (i32.const 0))
+          (data (i32.const 16) "\00")
+          (data (i32.const 32) "\00\01\00\02\07keyword\00\00")
+          (data (i32.const 48) "\00\00")
+          (data (i32.const 52) "\00\01")
+          (func (export "arborium_wire_version") (result i32) (i32.const {version}))
+          (func (export "arborium_alloc") (param i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get 0)))
+            (local.get $ptr))
+          (func (export "arborium_dealloc") (param i32 i32))
+          (func (export "arborium_create_session") (result i32) (i32.const 1))
+          (func (export "arborium_free_session") (param i32))
+          (func (export "arborium_set_text") (param i32 i32 i32) (result i64)
+            (i64.const {ok_unit}))
+          (func (export "arborium_load_text") (param i32 i32 i32) (result i64)
+            (global.set $steps (i32.const 0))
+            (i64.const {ok_unit}))
+          (func (export "arborium_parse_step") (param i32 i32) (result i64)
+            (global.set $steps (i32.add (global.get $steps) (i32.const 1)))
+            (if (result i64) (i32.ge_u (global.get $steps) (i32.const {steps}))
+              (then (i64.const {ok_done}))
+              (else (i64.const {ok_pending}))))
+          (func (export "arborium_parse") (param i32) (result i64) (i64.const {ok_parse})))"#,
+        version = arborium_wire::WIRE_VERSION,
+        steps = steps as i32,
+        ok_unit = arborium_wire::abi::pack(16, 1),
+        ok_parse = arborium_wire::abi::pack(32, 14),
+        ok_pending = arborium_wire::abi::pack(48, 2),
+        ok_done = arborium_wire::abi::pack(52, 2),
+    ))
+    .unwrap();
+    blob_url(&wasm)
+}
+
+fn blob_url(bytes: &[u8]) -> String {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("application/wasm");
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(
+        &Array::of1(&Uint8Array::from(bytes)),
+        &options,
+    )
+    .unwrap();
+    web_sys::Url::create_object_url_with_blob(&blob).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn grammar_module_loads_on_first_use() {
+    register_grammar_module("modfake", &fake_plugin_url());
+    assert!(is_language_available("modfake"));
+
+    // Not loaded yet: plain text, and the load starts
+    let html = highlight_now("modfake", "fn <x>").await.unwrap();
+    assert_eq!(html, "fn &lt;x&gt;");
+
+    // `highlight` waits for it
+    let html = highlight("modfake", "fn <x>").await.unwrap();
+    assert_eq!(html, "<a-k>fn</a-k> &lt;x&gt;");
+
+    // From then on the cached instance serves it right away
+    let html = highlight_now("modfake", "fn <x>").await.unwrap();
+    assert_eq!(html, "<a-k>fn</a-k> &lt;x&gt;");
+}
+
+#[wasm_bindgen_test]
+async fn grammar_module_preload() {
+    register_grammar_module("modpreload", &fake_plugin_url());
+    assert!(preload_grammar_module("modpreload").await);
+    let html = highlight_now("modpreload", "fn").await.unwrap();
+    assert_eq!(html, "<a-k>fn</a-k>");
+
+    // Not a wasm module
+    register_grammar_module("modbroken", &blob_url(b"not wasm"));
+    assert!(!preload_grammar_module("modbroken").await);
+    assert!(highlight("modbroken", "fn").await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn highlight_async_parses_modules_in_steps() {
+    register_grammar_module("modsteps", &fake_stepping_plugin_url(5));
+
+    let html = highlight_async("modsteps", "fn <x>", None).await.unwrap();
+    assert_eq!(html, "<a-k>fn</a-k> &lt;x&gt;");
+}
+
+#[wasm_bindgen_test]
+async fn highlight_async_cancelled_while_parsing() {
+    // A parse that never completes: only yielding between steps lets the
+    // abort below run at all
+    register_grammar_module("modendless", &fake_stepping_plugin_url(u32::MAX));
+    assert!(preload_grammar_module("modendless").await);
+
+    let controller = AbortController::new().unwrap();
+    let abort = Closure::once_into_js({
+        let controller = controller.clone();
+        move || controller.abort()
+    });
+    web_sys::window()
+        .unwrap()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(abort.unchecked_ref(), 0)
+        .unwrap();
+
+    let error = highlight_async("modendless", "fn <x>", Some(controller.signal()))
+        .await
+        .unwrap_err();
+    assert_eq!(error_name(&error), "AbortError");
+}
@@ -4,8 +4,10 @@ use std::io::{self, Read};
 use std::process;
 
 use anyhow::{Context, Result};
-use arborium::Highlighter;
-use html_escape::{encode_double_quoted_attribute, encode_safe};
+use arborium::advanced::spans_to_html_with_options;
+use arborium::cache::{self, CacheKey, FsStore, RenderCache};
+use arborium::{Config, Highlighter, HtmlFormat, HtmlOptions};
+use html_escape::encode_double_quoted_attribute;
 use mdbook::book::{Book, BookItem};
 use mdbook::errors::Error as MdError;
 use mdbook::preprocess::{Preprocessor, PreprocessorContext};
@@ -48,7 +50,8 @@ struct ArboriumPreprocessor;
 
 impl ArboriumPreprocessor {
     fn apply(&self, ctx: &PreprocessorContext, book: &mut Book) -> Result<()> {
-        let _ = ctx;
+        let settings = Settings::from_context(ctx);
+        let mut cache = render_cache(ctx);
         let mut highlighter = Highlighter::new();
 
         book.for_each_mut(|item| {
@@ -57,7 +60,7 @@ impl ArboriumPreprocessor {
                     return;
                 }
 
-                match transform_markdown(&chapter.content, &mut highlighter) {
+                match transform_markdown(&chapter.content, &mut highlighter, &mut cache, settings) {
                     Ok(transformed) => {
                         chapter.content = transformed;
                     }
@@ -91,13 +94,72 @@ impl Preprocessor for ArboriumPreprocessor {
     }
 }
 
+/// Settings read from `[preprocessor.arborium]` in `book.toml`.
+#[derive(Debug, Default, Clone, Copy)]
+struct Settings {
+    /// `line-anchors = true`: give every line an `id` to deep-link to.
+    line_anchors: bool,
+    /// `line-numbers = true`: number every line.
+    line_numbers: bool,
+}
+
+impl Settings {
+    fn from_context(ctx: &PreprocessorContext) -> Self {
+        let Some(table) = ctx.config.get_preprocessor("arborium") else {
+            return Self::default();
+        };
+        let flag = |key: &str| table.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        Self {
+            line_anchors: flag("line-anchors"),
+            line_numbers: flag("line-numbers"),
+        }
+    }
+
+    /// HTML options for the `index`-th code block of a chapter.
+    ///
+    /// Anchor prefixes are derived from the block index (`b1-L3` is line 3
+    /// of the first block), so ids stay unique across a chapter's blocks.
+    fn html_options(&self, index: usize) -> HtmlOptions {
+        let mut options = HtmlOptions::default().line_numbers(self.line_numbers);
+        if self.line_anchors {
+            options = options
+                .line_anchors(&format!("b{}-", index + 1))
+                .line_self_links(true);
+        }
+        options
+    }
+}
+
+/// Cache for rendered code blocks.
+///
+/// With `cache-dir = "..."` in `[preprocessor.arborium]`, entries are kept
+/// on disk (relative paths are resolved against the book root) so
+/// unchanged blocks aren't re-highlighted across builds. Otherwise identical
+/// blocks are only shared within a build.
+fn render_cache(ctx: &PreprocessorContext) -> RenderCache {
+    let cache_dir = ctx
+        .config
+        .get_preprocessor("arborium")
+        .and_then(|table| table.get("cache-dir"))
+        .and_then(|v| v.as_str());
+    match cache_dir {
+        Some(dir) => RenderCache::new(FsStore::new(ctx.root.join(dir))),
+        None => RenderCache::in_memory(1024),
+    }
+}
+
 #[derive(Deserialize)]
 struct PreprocessorRequest {
     context: PreprocessorContext,
     book: Book,
 }
 
-fn transform_markdown(content: &str, highlighter: &mut Highlighter) -> Result<String> {
+fn transform_markdown(
+    content: &str,
+    highlighter: &mut Highlighter,
+    cache: &mut RenderCache,
+    settings: Settings,
+) -> Result<String> {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_FOOTNOTES);
@@ -107,6 +169,7 @@ fn transform_markdown(content: &str, highlighter: &mut Highlighter) -> Result<St
     let parser = Parser::new_ext(content, options);
     let mut events: Vec<Event> = Vec::new();
     let mut active = ActiveFence::default();
+    let mut block_index = 0;
 
     for event in parser {
         let mut handled = false;
@@ -136,7 +199,13 @@ fn transform_markdown(content: &str, highlighter: &mut Highlighter) -> Result<St
             }
             Event::End(TagEnd::CodeBlock) => {
                 if let Some(block) = active.take() {
-                    events.push(Event::Html(CowStr::from(block.render(highlighter))));
+                    let options = settings.html_options(block_index);
+                    block_index += 1;
+                    events.push(Event::Html(CowStr::from(block.render(
+                        highlighter,
+                        cache,
+                        &options,
+                    ))));
                     handled = true;
                 }
             }
@@ -149,7 +218,12 @@ fn transform_markdown(content: &str, highlighter: &mut Highlighter) -> Result<St
     }
 
     if let Some(block) = active.take() {
-        events.push(Event::Html(CowStr::from(block.render(highlighter))));
+        let options = settings.html_options(block_index);
+        events.push(Event::Html(CowStr::from(block.render(
+            highlighter,
+            cache,
+            &options,
+        ))));
     }
 
     let mut output = String::new();
@@ -194,7 +268,12 @@ impl FencedBlock {
         self.code.push_str(text);
     }
 
-    fn render(mut self, highlighter: &mut Highlighter) -> String {
+    fn render(
+        mut self,
+        highlighter: &mut Highlighter,
+        cache: &mut RenderCache,
+        options: &HtmlOptions,
+    ) -> String {
         // Trim trailing newline inserted by parser to avoid double spacing.
         if self.code.ends_with('\n') {
             self.code.pop();
@@ -204,20 +283,36 @@ impl FencedBlock {
         }
 
         let lang = parse_language(&self.info);
-        let highlighted =
-            lang.as_deref()
-                .and_then(|lang| match highlighter.highlight(lang, &self.code) {
-                    Ok(html) => Some(html),
-                    Err(err) => {
-                        eprintln!("[arborium-mdbook] unsupported language '{lang}': {err}");
-                        None
-                    }
-                });
+        let config = Config {
+            html_options: options.clone(),
+            ..Config::default()
+        };
+        let highlighted = lang.as_deref().and_then(|lang| {
+            let key = CacheKey::new(lang, "", cache::html_fingerprint(&config), &self.code);
+            let rendered = cache.get_or_render(key, || {
+                let spans = highlighter.highlight_spans(lang, &self.code)?;
+                Ok::<_, arborium::Error>(spans_to_html_with_options(
+                    &self.code,
+                    spans,
+                    &config.html_format,
+                    options,
+                ))
+            });
+            match rendered {
+                Ok(html) => Some(html),
+                Err(err) => {
+                    eprintln!("[arborium-mdbook] unsupported language '{lang}': {err}");
+                    None
+                }
+            }
+        });
 
-        build_code_block_html(
-            lang.as_deref(),
-            highlighted.unwrap_or_else(|| encode_safe(&self.code).to_string()),
-        )
+        // Unhighlighted blocks still go through the renderer so they get the
+        // same line wrappers as highlighted ones
+        let body = highlighted.unwrap_or_else(|| {
+            spans_to_html_with_options(&self.code, Vec::new(), &HtmlFormat::default(), options)
+        });
+        build_code_block_html(lang.as_deref(), body)
     }
 }
 
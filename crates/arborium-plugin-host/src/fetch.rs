@@ -0,0 +1,554 @@
+//! Downloading plugins on demand, with integrity checks and a local cache.
+//!
+//! A plugin release is a directory of `{language_id}-{wire_version}.wasm`
+//! files next to an index, [`INDEX_FILE`], listing their SHA-256 digests in
+//! `sha256sum` format. The index is signed with ed25519; the detached
+//! signature is in [`SIGNATURE_FILE`]. Plugins are only used if their
+//! digest matches a correctly signed index.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use arborium_wire::WIRE_VERSION;
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+/// Name of the index listing plugin digests.
+pub const INDEX_FILE: &str = "plugins.sha256";
+
+/// Name of the ed25519 signature of [`INDEX_FILE`], as 64 raw bytes.
+pub const SIGNATURE_FILE: &str = "plugins.sha256.sig";
+
+/// Largest download [`HttpTransport`] accepts, in bytes. The biggest
+/// plugins are a few megabytes; this leaves plenty of headroom.
+pub const MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Fetches bytes by URL.
+///
+/// Implement this to use the host application's HTTP stack, or a browser's
+/// `fetch` when the host itself runs in WASM. With the `fetch-http`
+/// feature, [`HttpTransport`] is a blocking implementation.
+pub trait Transport: Send + Sync {
+    /// Fetch the body of `url`.
+    fn get(&self, url: &str) -> io::Result<Vec<u8>>;
+}
+
+/// A blocking HTTP [`Transport`] using `ureq`.
+#[cfg(feature = "fetch-http")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HttpTransport;
+
+#[cfg(feature = "fetch-http")]
+impl Transport for HttpTransport {
+    fn get(&self, url: &str) -> io::Result<Vec<u8>> {
+        let mut response = ureq::get(url).call().map_err(io::Error::other)?;
+        response
+            .body_mut()
+            .with_config()
+            .limit(MAX_DOWNLOAD_BYTES)
+            .read_to_vec()
+            .map_err(io::Error::other)
+    }
+}
+
+/// Error type for [`PluginFetcher`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FetchError {
+    /// The language id contains characters other than lowercase ASCII
+    /// letters, digits, `_` and `-`, so it can't name a plugin.
+    InvalidLanguageId(String),
+
+    /// Downloading a file failed, and no cached copy was available.
+    Transport {
+        /// The URL requested.
+        url: String,
+        /// What went wrong.
+        error: io::Error,
+    },
+
+    /// Reading or writing the cache failed.
+    Cache(io::Error),
+
+    /// The index signature is missing or doesn't verify with the trusted
+    /// key.
+    BadSignature,
+
+    /// The index couldn't be parsed.
+    InvalidIndex {
+        /// What was wrong with it.
+        message: String,
+    },
+
+    /// The index doesn't list the plugin.
+    NotInIndex(String),
+
+    /// A downloaded plugin doesn't match its digest in the index.
+    ChecksumMismatch {
+        /// The plugin's file name.
+        file: String,
+        /// The digest from the index, in hex.
+        expected: String,
+        /// The digest of the download, in hex.
+        actual: String,
+    },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::InvalidLanguageId(id) => write!(f, "invalid language id `{}`", id),
+            FetchError::Transport { url, error } => {
+                write!(f, "failed to download {}: {}", url, error)
+            }
+            FetchError::Cache(e) => write!(f, "plugin cache error: {}", e),
+            FetchError::BadSignature => {
+                write!(f, "plugin index signature doesn't match the trusted key")
+            }
+            FetchError::InvalidIndex { message } => {
+                write!(f, "invalid plugin index: {}", message)
+            }
+            FetchError::NotInIndex(file) => write!(f, "{} isn't in the plugin index", file),
+            FetchError::ChecksumMismatch {
+                file,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "checksum mismatch for {}: expected {}, got {}",
+                file, expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Transport { error, .. } => Some(error),
+            FetchError::Cache(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Downloads plugins from a release and keeps verified copies on disk.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use arborium_plugin_host::fetch::PluginFetcher;
+///
+/// let mut fetcher = PluginFetcher::new(
+///     "https://example.com/arborium/plugins/2.4.1",
+///     cache_dir.join("plugins"),
+///     RELEASE_KEY,
+/// )?;
+/// let path = fetcher.ensure("zig")?;
+/// let plugin = host.load(&path)?;
+/// ```
+pub struct PluginFetcher {
+    base_url: String,
+    cache_dir: PathBuf,
+    key: VerifyingKey,
+    transport: Box<dyn Transport>,
+    /// Digests from the index, once fetched and verified.
+    index: Option<HashMap<String, [u8; 32]>>,
+}
+
+impl fmt::Debug for PluginFetcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginFetcher")
+            .field("base_url", &self.base_url)
+            .field("cache_dir", &self.cache_dir)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PluginFetcher {
+    /// Create a fetcher downloading over HTTP from `base_url`, trusting
+    /// indexes signed with the ed25519 public key `trusted_key`.
+    #[cfg(feature = "fetch-http")]
+    pub fn new(
+        base_url: &str,
+        cache_dir: impl Into<PathBuf>,
+        trusted_key: [u8; 32],
+    ) -> Result<Self, FetchError> {
+        Self::with_transport(base_url, cache_dir, trusted_key, HttpTransport)
+    }
+
+    /// Create a fetcher downloading through `transport`.
+    pub fn with_transport(
+        base_url: &str,
+        cache_dir: impl Into<PathBuf>,
+        trusted_key: [u8; 32],
+        transport: impl Transport + 'static,
+    ) -> Result<Self, FetchError> {
+        let key = VerifyingKey::from_bytes(&trusted_key).map_err(|_| FetchError::BadSignature)?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            cache_dir: cache_dir.into(),
+            key,
+            transport: Box::new(transport),
+            index: None,
+        })
+    }
+
+    /// Make sure a verified plugin for `language_id` is in the cache,
+    /// returning its path.
+    ///
+    /// A cached copy matching the index is used as is. Without network, a
+    /// cached copy is used even if it can't be checked against the index;
+    /// it was verified when it was downloaded.
+    ///
+    /// Language ids may only contain lowercase ASCII letters, digits, `_`
+    /// and `-`; anything else is rejected before touching the cache or the
+    /// network.
+    pub fn ensure(&mut self, language_id: &str) -> Result<PathBuf, FetchError> {
+        let valid = !language_id.is_empty()
+            && language_id
+                .bytes()
+                .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-'));
+        if !valid {
+            return Err(FetchError::InvalidLanguageId(language_id.to_string()));
+        }
+
+        let file = format!("{}-{}.wasm", language_id, WIRE_VERSION);
+        let path = self.cache_dir.join(&file);
+        let cached = path.is_file();
+
+        let expected = match self.digest(&file) {
+            Ok(expected) => expected,
+            Err(FetchError::Transport { .. }) if cached => return Ok(path),
+            Err(e) => return Err(e),
+        };
+        if cached && sha256(&std::fs::read(&path).map_err(FetchError::Cache)?) == expected {
+            return Ok(path);
+        }
+
+        let bytes = match self.get(&file) {
+            Ok(bytes) => bytes,
+            // Stale, but verified when downloaded
+            Err(FetchError::Transport { .. }) if cached => return Ok(path),
+            Err(e) => return Err(e),
+        };
+        let actual = sha256(&bytes);
+        if actual != expected {
+            return Err(FetchError::ChecksumMismatch {
+                file,
+                expected: hex(&expected),
+                actual: hex(&actual),
+            });
+        }
+
+        write_atomically(&self.cache_dir, &file, &bytes).map_err(FetchError::Cache)?;
+        Ok(path)
+    }
+
+    /// The digest of `file` from the verified index.
+    fn digest(&mut self, file: &str) -> Result<[u8; 32], FetchError> {
+        if self.index.is_none() {
+            let index = self.get(INDEX_FILE)?;
+            let signature = self.get(SIGNATURE_FILE)?;
+            let signature =
+                Signature::from_slice(&signature).map_err(|_| FetchError::BadSignature)?;
+            self.key
+                .verify_strict(&index, &signature)
+                .map_err(|_| FetchError::BadSignature)?;
+            self.index = Some(parse_index(&index)?);
+        }
+        let index = self.index.as_ref().expect("just fetched");
+        index
+            .get(file)
+            .copied()
+            .ok_or_else(|| FetchError::NotInIndex(file.to_string()))
+    }
+
+    fn get(&self, file: &str) -> Result<Vec<u8>, FetchError> {
+        let url = format!("{}/{}", self.base_url, file);
+        self.transport
+            .get(&url)
+            .map_err(|error| FetchError::Transport { url, error })
+    }
+}
+
+/// Parse `sha256sum` output: a hex digest, whitespace, and a file name per
+/// line (a leading `*` marks binary mode and is ignored).
+fn parse_index(index: &[u8]) -> Result<HashMap<String, [u8; 32]>, FetchError> {
+    let invalid = |message: String| FetchError::InvalidIndex { message };
+    let index = std::str::from_utf8(index).map_err(|e| invalid(e.to_string()))?;
+
+    let mut digests = HashMap::new();
+    for line in index.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (digest, file) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| invalid(format!("malformed line `{}`", line)))?;
+        let file = file.trim_start().trim_start_matches('*');
+        let digest =
+            parse_hex(digest).ok_or_else(|| invalid(format!("bad digest for {}", file)))?;
+        digests.insert(file.to_string(), digest);
+    }
+    Ok(digests)
+}
+
+fn sha256(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn parse_hex(s: &str) -> Option<[u8; 32]> {
+    if s.len() != 64 || !s.is_ascii() {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+/// Write `dir/file` through a temporary file and a rename, so readers never
+/// see a partial plugin.
+fn write_atomically(dir: &Path, file: &str, bytes: &[u8]) -> io::Result<()> {
+    // Unique per process and per call, so concurrent writers of the same
+    // plugin never share a temporary file
+    static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+    std::fs::create_dir_all(dir)?;
+    let temp = dir.join(format!(
+        ".{}.{}.{}.tmp",
+        file,
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = std::fs::File::create(&temp).and_then(|mut out| {
+        out.write_all(bytes)?;
+        out.sync_all()
+    });
+    match result.and_then(|()| std::fs::rename(&temp, dir.join(file))) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::sync::{Arc, Mutex};
+
+    const BASE: &str = "https://plugins.test/v1";
+
+    /// Serves files from memory, or nothing while offline.
+    #[derive(Clone, Default)]
+    struct FakeTransport {
+        files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+        offline: Arc<Mutex<bool>>,
+    }
+
+    impl FakeTransport {
+        fn put(&self, file: &str, bytes: &[u8]) {
+            let url = format!("{}/{}", BASE, file);
+            self.files.lock().unwrap().insert(url, bytes.to_vec());
+        }
+
+        fn set_offline(&self, offline: bool) {
+            *self.offline.lock().unwrap() = offline;
+        }
+    }
+
+    impl Transport for FakeTransport {
+        fn get(&self, url: &str) -> io::Result<Vec<u8>> {
+            if *self.offline.lock().unwrap() {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "offline"));
+            }
+            self.files
+                .lock()
+                .unwrap()
+                .get(url)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, url.to_string()))
+        }
+    }
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    /// Publish `plugins` with an index signed by `key`.
+    fn publish(transport: &FakeTransport, key: &SigningKey, plugins: &[(&str, &[u8])]) {
+        let mut index = String::new();
+        for (id, bytes) in plugins {
+            let file = format!("{}-{}.wasm", id, WIRE_VERSION);
+            index.push_str(&format!("{}  {}\n", hex(&sha256(bytes)), file));
+            transport.put(&file, bytes);
+        }
+        transport.put(INDEX_FILE, index.as_bytes());
+        transport.put(SIGNATURE_FILE, &key.sign(index.as_bytes()).to_bytes());
+    }
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "arborium-fetch-test-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn fetcher(transport: &FakeTransport, cache: &TempDir) -> PluginFetcher {
+        let key = signing_key().verifying_key().to_bytes();
+        PluginFetcher::with_transport(BASE, &cache.0, key, transport.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_download_then_serve_cached_offline() {
+        let transport = FakeTransport::default();
+        publish(&transport, &signing_key(), &[("zig", b"zig plugin")]);
+        let cache = TempDir::new("offline");
+
+        let path = fetcher(&transport, &cache).ensure("zig").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"zig plugin");
+        assert_eq!(
+            path.file_name().unwrap().to_str().unwrap(),
+            format!("zig-{}.wasm", WIRE_VERSION)
+        );
+
+        // A new fetcher has no index yet and can't get one
+        transport.set_offline(true);
+        assert_eq!(fetcher(&transport, &cache).ensure("zig").unwrap(), path);
+        assert!(matches!(
+            fetcher(&transport, &cache).ensure("odin"),
+            Err(FetchError::Transport { .. })
+        ));
+    }
+
+    #[test]
+    fn test_stale_cache_is_replaced() {
+        let transport = FakeTransport::default();
+        publish(&transport, &signing_key(), &[("zig", b"zig 1")]);
+        let cache = TempDir::new("stale");
+        let path = fetcher(&transport, &cache).ensure("zig").unwrap();
+
+        publish(&transport, &signing_key(), &[("zig", b"zig 2")]);
+        assert_eq!(fetcher(&transport, &cache).ensure("zig").unwrap(), path);
+        assert_eq!(std::fs::read(&path).unwrap(), b"zig 2");
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let transport = FakeTransport::default();
+        publish(&transport, &signing_key(), &[("zig", b"zig plugin")]);
+        transport.put(&format!("zig-{}.wasm", WIRE_VERSION), b"tampered");
+        let cache = TempDir::new("mismatch");
+
+        let err = fetcher(&transport, &cache).ensure("zig").unwrap_err();
+        assert!(matches!(err, FetchError::ChecksumMismatch { .. }), "{err}");
+        assert!(!cache.0.join(format!("zig-{}.wasm", WIRE_VERSION)).exists());
+    }
+
+    #[test]
+    fn test_untrusted_index() {
+        let transport = FakeTransport::default();
+        publish(
+            &transport,
+            &SigningKey::from_bytes(&[8; 32]),
+            &[("zig", b"zig plugin")],
+        );
+        let cache = TempDir::new("untrusted");
+
+        let err = fetcher(&transport, &cache).ensure("zig").unwrap_err();
+        assert!(matches!(err, FetchError::BadSignature), "{err}");
+
+        publish(&transport, &signing_key(), &[("zig", b"zig plugin")]);
+        assert!(matches!(
+            fetcher(&transport, &cache).ensure("odin"),
+            Err(FetchError::NotInIndex(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_language_ids() {
+        let transport = FakeTransport::default();
+        publish(&transport, &signing_key(), &[("zig", b"zig plugin")]);
+        let cache = TempDir::new("invalid-id");
+
+        // A file outside the cache that a traversing id would point at
+        let outside = cache.0.join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join(format!("x-{}.wasm", WIRE_VERSION)), b"x").unwrap();
+        let cached = cache.0.join("plugins");
+
+        transport.set_offline(true);
+        let key = signing_key().verifying_key().to_bytes();
+        let mut fetcher =
+            PluginFetcher::with_transport(BASE, &cached, key, transport.clone()).unwrap();
+        for id in ["../outside/x", "", "Zig", "zig.wasm", "a/b", "a\\b", "zig "] {
+            assert!(
+                matches!(fetcher.ensure(id), Err(FetchError::InvalidLanguageId(_))),
+                "{id:?}"
+            );
+        }
+
+        transport.set_offline(false);
+        assert!(fetcher.ensure("zig").is_ok());
+        assert!(matches!(
+            fetcher.ensure("c-sharp_2"),
+            Err(FetchError::NotInIndex(_))
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_writes_use_distinct_temp_files() {
+        let cache = TempDir::new("concurrent");
+        std::thread::scope(|scope| {
+            for i in 0..8u8 {
+                let dir = &cache.0;
+                scope.spawn(move || {
+                    write_atomically(dir, "zig.wasm", &[i; 4096]).unwrap();
+                });
+            }
+        });
+
+        // One complete write won, and no temporary files are left behind
+        let bytes = std::fs::read(cache.0.join("zig.wasm")).unwrap();
+        assert_eq!(bytes.len(), 4096);
+        assert!(bytes.iter().all(|&b| b == bytes[0]));
+        assert_eq!(std::fs::read_dir(&cache.0).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_parse_index() {
+        let digest = hex(&sha256(b"x"));
+        let index = format!("# release 2.4.1\n{digest}  zig-2.wasm\n\n{digest} *odin-2.wasm\n");
+        let parsed = parse_index(index.as_bytes()).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed["odin-2.wasm"], sha256(b"x"));
+
+        assert!(parse_index(b"nothex  zig-2.wasm").is_err());
+        assert!(parse_index(b"justonefield").is_err());
+    }
+}
@@ -0,0 +1,1032 @@
+//! Native host for arborium grammar plugins.
+//!
+//! Grammar plugins are WASM modules built by `cargo xtask build`. In the
+//! browser they are driven through their wasm-bindgen exports; this crate
+//! drives them from native Rust through the raw ABI described in
+//! [`arborium_wire::abi`], using wasmtime.
+//!
+//! Each session gets its own instance of the plugin, so a guest that traps
+//! only takes down the session it was working on.
+//!
+//! [`read_manifest`] tells what language a `.wasm` file provides, and
+//! whether it speaks this host's wire version, without compiling it.
+//! [`PluginRegistry`] builds on it to serve grammars from plugin directories
+//! by language name.
+//!
+//! [`ProviderRegistry`] goes one step further and puts plugins and grammars
+//! compiled into the host behind one [`SyntaxProvider`] interface.
+//!
+//! With the `fetch` feature, [`fetch`] downloads plugins from a release on
+//! demand and verifies them against a signed index.
+//!
+//! Calls into plugins are bounded in time and memory by [`PluginLimits`].
+//! Rebuilt plugins can be swapped in without losing sessions, see
+//! [`PluginRegistry::reload`]; with the `watch` feature, `PluginWatcher`
+//! notices rebuilds on its own.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use arborium_plugin_host::PluginHost;
+//! use std::path::Path;
+//!
+//! let host = PluginHost::new();
+//! let mut plugin = host.load(Path::new("rust/grammar_bg.wasm"))?;
+//! let session = plugin.create_session()?;
+//! plugin.set_text(session, "fn main() {}")?;
+//! let result = plugin.parse(session)?;
+//! plugin.free_session(session)?;
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use arborium_wire::abi::{self, EditRequest};
+use arborium_wire::{Edit, ParseError, Utf8ParseResult, WIRE_VERSION, is_version_compatible};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use wasmtime::{
+    Engine, Instance, Linker, Memory, Module, Store, Trap, TypedFunc, WasmParams, WasmResults,
+};
+
+#[cfg(feature = "fetch")]
+pub mod fetch;
+mod limits;
+mod manifest;
+mod provider;
+mod registry;
+#[cfg(feature = "watch")]
+mod watch;
+
+use limits::StoreState;
+
+pub use arborium_wire::{PluginManifest, QueryKind};
+pub use limits::{Limit, PluginLimits, ViolationPolicy};
+pub use manifest::read_manifest;
+#[cfg(feature = "native")]
+pub use provider::NativeProvider;
+pub use provider::{ProviderRegistry, SyntaxProvider};
+pub use registry::{GrammarHandle, PluginRegistry, RegistryWarning};
+#[cfg(feature = "watch")]
+pub use watch::PluginWatcher;
+
+/// Error type for plugin host operations.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HostError {
+    /// Reading the plugin file failed.
+    Io(io::Error),
+
+    /// The plugin couldn't be compiled or instantiated.
+    Wasm(wasmtime::Error),
+
+    /// The plugin lacks one of the raw ABI exports, or exports it with the
+    /// wrong signature.
+    ///
+    /// This happens for modules that aren't arborium plugins, and for
+    /// plugins built before the raw ABI existed.
+    MissingExport {
+        /// Name of the export.
+        name: &'static str,
+    },
+
+    /// The plugin speaks a different wire protocol version.
+    WireVersionMismatch {
+        /// The plugin's version.
+        plugin: u32,
+        /// The host's version ([`WIRE_VERSION`]).
+        host: u32,
+    },
+
+    /// The plugin has no [`MANIFEST_SECTION`](arborium_wire::MANIFEST_SECTION).
+    MissingManifest,
+
+    /// The plugin's manifest couldn't be decoded.
+    InvalidManifest {
+        /// What was wrong with it.
+        message: String,
+    },
+
+    /// No registered plugin or grammar provides the language.
+    UnknownLanguage(String),
+
+    /// A grammar compiled into the host couldn't be set up, e.g. because
+    /// it was generated for an incompatible tree-sitter version.
+    Native(String),
+
+    /// The session doesn't exist, was freed, or was dropped after a trap.
+    UnknownSession(u32),
+
+    /// The guest trapped. The session's instance is dropped, since its
+    /// state can't be trusted anymore; other sessions are unaffected.
+    Trap {
+        /// The session the guest was working on.
+        session: u32,
+        /// The trap message, with a guest backtrace if available.
+        message: String,
+    },
+
+    /// A call exceeded one of the host's [`PluginLimits`]. What happened
+    /// to the plugin depends on its [`ViolationPolicy`].
+    LimitExceeded {
+        /// The session the guest was working on.
+        session: u32,
+        /// The limit exceeded.
+        limit: Limit,
+    },
+
+    /// The plugin exceeded a limit earlier and was disabled by
+    /// [`ViolationPolicy::MarkUnhealthy`].
+    Unhealthy,
+
+    /// The guest returned a payload that couldn't be decoded.
+    Malformed {
+        /// The session the payload was for.
+        session: u32,
+        /// What was wrong with it.
+        message: String,
+    },
+
+    /// The guest reported an error, e.g. a failed parse.
+    ///
+    /// A guest panic that the plugin caught comes back with
+    /// [`ParseErrorKind::Internal`](arborium_wire::ParseErrorKind::Internal);
+    /// the session is then unusable and should be freed.
+    Guest {
+        /// The session the error is for.
+        session: u32,
+        /// The guest's error.
+        error: ParseError,
+    },
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostError::Io(e) => write!(f, "I/O error: {}", e),
+            HostError::Wasm(e) => write!(f, "failed to load plugin: {}", e),
+            HostError::MissingExport { name } => write!(
+                f,
+                "missing or mistyped export `{}` (not an arborium plugin, or built without the raw ABI)",
+                name
+            ),
+            HostError::WireVersionMismatch { plugin, host } => write!(
+                f,
+                "plugin uses wire protocol version {}, but this host speaks version {}",
+                plugin, host
+            ),
+            HostError::MissingManifest => write!(
+                f,
+                "plugin has no `{}` section (built before manifests existed?)",
+                arborium_wire::MANIFEST_SECTION
+            ),
+            HostError::InvalidManifest { message } => {
+                write!(f, "invalid plugin manifest: {}", message)
+            }
+            HostError::UnknownLanguage(name) => write!(f, "unknown language `{}`", name),
+            HostError::Native(message) => write!(f, "failed to set up grammar: {}", message),
+            HostError::UnknownSession(session) => write!(f, "unknown session {}", session),
+            HostError::Trap { session, message } => {
+                write!(f, "plugin trapped in session {}: {}", session, message)
+            }
+            HostError::LimitExceeded { session, limit } => {
+                write!(f, "plugin {} in session {}", limit, session)
+            }
+            HostError::Unhealthy => {
+                write!(f, "plugin disabled after exceeding its resource limits")
+            }
+            HostError::Malformed { session, message } => write!(
+                f,
+                "malformed payload from plugin in session {}: {}",
+                session, message
+            ),
+            HostError::Guest { session, error } => {
+                write!(f, "plugin error in session {}: {}", session, error.message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HostError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HostError::Io(e) => Some(e),
+            HostError::Wasm(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for HostError {
+    fn from(e: io::Error) -> Self {
+        HostError::Io(e)
+    }
+}
+
+/// Where to load a plugin from: a `.wasm` file or its bytes.
+#[derive(Debug, Clone, Copy)]
+pub enum PluginSource<'a> {
+    /// Path to a `.wasm` file.
+    Path(&'a Path),
+    /// The contents of a `.wasm` file.
+    Bytes(&'a [u8]),
+}
+
+impl<'a> From<&'a Path> for PluginSource<'a> {
+    fn from(path: &'a Path) -> Self {
+        PluginSource::Path(path)
+    }
+}
+
+impl<'a> From<&'a PathBuf> for PluginSource<'a> {
+    fn from(path: &'a PathBuf) -> Self {
+        PluginSource::Path(path)
+    }
+}
+
+impl<'a> From<&'a [u8]> for PluginSource<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        PluginSource::Bytes(bytes)
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for PluginSource<'a> {
+    fn from(bytes: &'a Vec<u8>) -> Self {
+        PluginSource::Bytes(bytes)
+    }
+}
+
+/// Loads grammar plugins.
+///
+/// Plugins loaded by the same host share a compilation engine and
+/// [`PluginLimits`].
+#[derive(Clone)]
+pub struct PluginHost {
+    engine: Engine,
+    limits: PluginLimits,
+}
+
+impl Default for PluginHost {
+    fn default() -> Self {
+        Self::with_limits(PluginLimits::default())
+    }
+}
+
+impl fmt::Debug for PluginHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginHost")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PluginHost {
+    /// Create a host with the default [`PluginLimits`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a host enforcing `limits` on every plugin it loads.
+    pub fn with_limits(limits: PluginLimits) -> Self {
+        Self {
+            engine: limits::engine_for(&limits),
+            limits,
+        }
+    }
+
+    /// The limits enforced on plugins.
+    pub fn limits(&self) -> &PluginLimits {
+        &self.limits
+    }
+
+    /// Compile a plugin and check its wire protocol version.
+    pub fn load<'a>(&self, source: impl Into<PluginSource<'a>>) -> Result<LoadedPlugin, HostError> {
+        let module = match source.into() {
+            PluginSource::Path(path) => Module::new(&self.engine, std::fs::read(path)?),
+            PluginSource::Bytes(bytes) => Module::new(&self.engine, bytes),
+        }
+        .map_err(HostError::Wasm)?;
+
+        // Plugins built with wasm-bindgen import JS glue they never call on
+        // the raw ABI path
+        let mut linker = Linker::new(&self.engine);
+        linker
+            .define_unknown_imports_as_traps(&module)
+            .map_err(HostError::Wasm)?;
+
+        let mut probe = GuestInstance::new(&self.engine, &linker, &module, &self.limits)?;
+        let wire_version = probe.call::<(), u32>(0, abi::WIRE_VERSION, ())?;
+        if !is_version_compatible(wire_version) {
+            return Err(HostError::WireVersionMismatch {
+                plugin: wire_version,
+                host: WIRE_VERSION,
+            });
+        }
+
+        Ok(LoadedPlugin {
+            engine: self.engine.clone(),
+            module,
+            linker,
+            limits: self.limits,
+            wire_version,
+            healthy: true,
+            sessions: HashMap::new(),
+            next_session: 1,
+        })
+    }
+}
+
+/// A compiled plugin and its sessions.
+///
+/// Methods mirror `PluginRuntime` in `arborium-plugin-runtime`.
+pub struct LoadedPlugin {
+    engine: Engine,
+    module: Module,
+    linker: Linker<StoreState>,
+    limits: PluginLimits,
+    wire_version: u32,
+    /// Cleared by [`ViolationPolicy::MarkUnhealthy`].
+    healthy: bool,
+    sessions: HashMap<u32, GuestSession>,
+    next_session: u32,
+}
+
+impl fmt::Debug for LoadedPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoadedPlugin")
+            .field("wire_version", &self.wire_version)
+            .field("healthy", &self.healthy)
+            .field("sessions", &self.sessions.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A session, backed by its own instance of the plugin.
+struct GuestSession {
+    instance: GuestInstance,
+    /// The session id inside the guest.
+    guest_id: u32,
+    /// The text last set, replayed into the new build on reload.
+    text: String,
+}
+
+impl LoadedPlugin {
+    /// The plugin's wire protocol version.
+    pub fn wire_version(&self) -> u32 {
+        self.wire_version
+    }
+
+    /// Whether the plugin is still usable, i.e. wasn't disabled by
+    /// [`ViolationPolicy::MarkUnhealthy`].
+    pub fn is_healthy(&self) -> bool {
+        self.healthy
+    }
+
+    /// The capture names of the plugin's queries, e.g. for theming.
+    ///
+    /// Like [`query_source`](Self::query_source) and
+    /// [`query_hash`](Self::query_hash), this runs in a fresh instance of
+    /// the plugin; keep the result rather than asking again.
+    pub fn capture_names(&self) -> Result<Vec<String>, HostError> {
+        self.introspect(abi::CAPTURE_NAMES, ())
+    }
+
+    /// The source of one of the queries the plugin was built with.
+    pub fn query_source(&self, kind: QueryKind) -> Result<String, HostError> {
+        self.introspect(abi::QUERY_SOURCE, kind.to_u32())
+    }
+
+    /// The hash of the plugin's queries, the same as in its manifest.
+    ///
+    /// Include it in cache keys for rendered output, so entries go stale
+    /// when the plugin is rebuilt with other queries.
+    pub fn query_hash(&self) -> Result<String, HostError> {
+        self.introspect(abi::QUERY_HASH, ())
+    }
+
+    /// Call a session-less export returning a result, in a fresh instance.
+    fn introspect<P: WasmParams, T: DeserializeOwned>(
+        &self,
+        name: &'static str,
+        params: P,
+    ) -> Result<T, HostError> {
+        if !self.healthy {
+            return Err(HostError::Unhealthy);
+        }
+        let mut instance =
+            GuestInstance::new(&self.engine, &self.linker, &self.module, &self.limits)?;
+        let packed = instance.call::<P, u64>(0, name, params)?;
+        instance.read_result(0, packed)
+    }
+
+    /// Create a parsing session.
+    pub fn create_session(&mut self) -> Result<u32, HostError> {
+        if !self.healthy {
+            return Err(HostError::Unhealthy);
+        }
+        let mut instance =
+            GuestInstance::new(&self.engine, &self.linker, &self.module, &self.limits)?;
+        let session = self.next_session;
+        let guest_id = instance
+            .call::<(), u32>(session, abi::CREATE_SESSION, ())
+            .inspect_err(|e| self.on_error(e))?;
+
+        self.next_session += 1;
+        self.sessions.insert(
+            session,
+            GuestSession {
+                instance,
+                guest_id,
+                text: String::new(),
+            },
+        );
+        Ok(session)
+    }
+
+    /// Free a session and its instance.
+    pub fn free_session(&mut self, session: u32) -> Result<(), HostError> {
+        let mut guest = self
+            .sessions
+            .remove(&session)
+            .ok_or(HostError::UnknownSession(session))?;
+        // The instance is dropped either way; only report traps
+        guest
+            .instance
+            .call::<u32, ()>(session, abi::FREE_SESSION, guest.guest_id)
+    }
+
+    /// Set the full text of a session.
+    pub fn set_text(&mut self, session: u32, text: &str) -> Result<(), HostError> {
+        self.call(session, |guest, guest_id| {
+            guest.set_text(session, guest_id, text)
+        })?;
+        self.remember_text(session, text);
+        Ok(())
+    }
+
+    /// Apply an incremental edit, `new_text` being the full text after it.
+    pub fn apply_edit(
+        &mut self,
+        session: u32,
+        new_text: &str,
+        edit: &Edit,
+    ) -> Result<(), HostError> {
+        let request = EditRequest {
+            new_text: new_text.to_string(),
+            edit: edit.clone(),
+        };
+        self.call(session, |guest, guest_id| {
+            let (ptr, len) = guest.write_encoded(session, &request)?;
+            let packed = guest.call::<(u32, u32, u32), u64>(
+                session,
+                abi::APPLY_EDIT,
+                (guest_id, ptr, len),
+            )?;
+            guest.free(session, ptr, len)?;
+            guest.read_result::<()>(session, packed)
+        })?;
+        self.remember_text(session, new_text);
+        Ok(())
+    }
+
+    /// Parse the session's text, returning spans and injections with UTF-8
+    /// byte offsets.
+    pub fn parse(&mut self, session: u32) -> Result<Utf8ParseResult, HostError> {
+        self.call(session, |guest, guest_id| {
+            let packed = guest.call::<u32, u64>(session, abi::PARSE, guest_id)?;
+            guest.read_result(session, packed)
+        })
+    }
+
+    /// Make the plugin panic while working on the session, for testing
+    /// panic handling. Only plugins built with the `test-hooks` feature
+    /// export this; others fail with [`HostError::MissingExport`].
+    #[doc(hidden)]
+    pub fn test_panic(&mut self, session: u32) -> Result<(), HostError> {
+        self.call(session, |guest, guest_id| {
+            let packed = guest.call::<u32, u64>(session, abi::TEST_PANIC, guest_id)?;
+            guest.read_result(session, packed)
+        })
+    }
+
+    /// Request cancellation of the session's parse.
+    pub fn cancel(&mut self, session: u32) -> Result<(), HostError> {
+        self.call(session, |guest, guest_id| {
+            guest.call::<u32, ()>(session, abi::CANCEL, guest_id)
+        })
+    }
+
+    /// Swap in a rebuilt plugin, keeping all sessions.
+    ///
+    /// Every session gets an instance of `replacement` with its current text
+    /// set again, so the next parse reflects the new build without the
+    /// caller recreating anything; session ids stay the same. If any session
+    /// fails to migrate, the error is returned and the old build stays in
+    /// use. Otherwise the old instances are dropped.
+    ///
+    /// Calls take `&mut self`, so none can be in flight against the old
+    /// build while it is replaced.
+    pub fn reload(&mut self, replacement: LoadedPlugin) -> Result<(), HostError> {
+        let mut sessions = HashMap::with_capacity(self.sessions.len());
+        for (&session, old) in &self.sessions {
+            let mut instance = GuestInstance::new(
+                &replacement.engine,
+                &replacement.linker,
+                &replacement.module,
+                &replacement.limits,
+            )?;
+            let guest_id = instance.call::<(), u32>(session, abi::CREATE_SESSION, ())?;
+            if !old.text.is_empty() {
+                instance.set_text(session, guest_id, &old.text)?;
+            }
+            sessions.insert(
+                session,
+                GuestSession {
+                    instance,
+                    guest_id,
+                    text: old.text.clone(),
+                },
+            );
+        }
+
+        *self = LoadedPlugin {
+            sessions,
+            next_session: self.next_session,
+            ..replacement
+        };
+        Ok(())
+    }
+
+    fn remember_text(&mut self, session: u32, text: &str) {
+        if let Some(guest) = self.sessions.get_mut(&session) {
+            guest.text.clear();
+            guest.text.push_str(text);
+        }
+    }
+
+    /// Run `f` against a session's instance, dropping the instance if the
+    /// guest traps or exceeds a limit.
+    fn call<R>(
+        &mut self,
+        session: u32,
+        f: impl FnOnce(&mut GuestInstance, u32) -> Result<R, HostError>,
+    ) -> Result<R, HostError> {
+        if !self.healthy {
+            return Err(HostError::Unhealthy);
+        }
+        let guest = self
+            .sessions
+            .get_mut(&session)
+            .ok_or(HostError::UnknownSession(session))?;
+        let result = f(&mut guest.instance, guest.guest_id);
+        if let Err(e) = &result {
+            if matches!(e, HostError::Trap { .. } | HostError::LimitExceeded { .. }) {
+                self.sessions.remove(&session);
+            }
+            self.on_error(e);
+        }
+        result
+    }
+
+    /// Apply the [`ViolationPolicy`] after a failed call.
+    fn on_error(&mut self, error: &HostError) {
+        if matches!(error, HostError::LimitExceeded { .. })
+            && self.limits.on_violation == ViolationPolicy::MarkUnhealthy
+        {
+            self.healthy = false;
+            self.sessions.clear();
+        }
+    }
+}
+
+/// An instance of a plugin with its store and memory.
+struct GuestInstance {
+    store: Store<StoreState>,
+    instance: Instance,
+    memory: Memory,
+    limits: PluginLimits,
+}
+
+impl GuestInstance {
+    fn new(
+        engine: &Engine,
+        linker: &Linker<StoreState>,
+        module: &Module,
+        limits: &PluginLimits,
+    ) -> Result<Self, HostError> {
+        let mut store = Store::new(engine, StoreState::new(limits));
+        store.limiter(|state| state);
+        if let Some(ticks) = limits.deadline_ticks() {
+            // Covers the start function, if any
+            store.set_epoch_deadline(ticks);
+        }
+        let instance = linker
+            .instantiate(&mut store, module)
+            .map_err(HostError::Wasm)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or(HostError::MissingExport { name: "memory" })?;
+        Ok(Self {
+            store,
+            instance,
+            memory,
+            limits: *limits,
+        })
+    }
+
+    /// Call an export within the time budget.
+    fn call<P: WasmParams, R: WasmResults>(
+        &mut self,
+        session: u32,
+        name: &'static str,
+        params: P,
+    ) -> Result<R, HostError> {
+        let func = self.func::<P, R>(name)?;
+        if let Some(ticks) = self.limits.deadline_ticks() {
+            self.store.set_epoch_deadline(ticks);
+        }
+        func.call(&mut self.store, params)
+            .map_err(|error| self.classify(session, error))
+    }
+
+    /// Tell limit violations apart from other traps.
+    fn classify(&mut self, session: u32, error: wasmtime::Error) -> HostError {
+        let limit = if std::mem::take(&mut self.store.data_mut().memory_exceeded) {
+            self.limits.max_memory.map(Limit::Memory)
+        } else if error.downcast_ref::<Trap>() == Some(&Trap::Interrupt) {
+            self.limits.deadline.map(Limit::Deadline)
+        } else {
+            None
+        };
+        match limit {
+            Some(limit) => HostError::LimitExceeded { session, limit },
+            None => HostError::Trap {
+                session,
+                message: format!("{:?}", error),
+            },
+        }
+    }
+
+    fn func<P: WasmParams, R: WasmResults>(
+        &mut self,
+        name: &'static str,
+    ) -> Result<TypedFunc<P, R>, HostError> {
+        self.instance
+            .get_typed_func(&mut self.store, name)
+            .map_err(|_| HostError::MissingExport { name })
+    }
+
+    /// Set the full text of the guest session `guest_id`.
+    fn set_text(&mut self, session: u32, guest_id: u32, text: &str) -> Result<(), HostError> {
+        let (ptr, len) = self.write(session, text.as_bytes())?;
+        let packed =
+            self.call::<(u32, u32, u32), u64>(session, abi::SET_TEXT, (guest_id, ptr, len))?;
+        self.free(session, ptr, len)?;
+        self.read_result::<()>(session, packed)
+    }
+
+    /// Copy `bytes` into a fresh guest buffer.
+    fn write(&mut self, session: u32, bytes: &[u8]) -> Result<(u32, u32), HostError> {
+        let len = u32::try_from(bytes.len()).map_err(|_| HostError::Malformed {
+            session,
+            message: format!("{} byte argument doesn't fit in guest memory", bytes.len()),
+        })?;
+        let ptr = self.call::<u32, u32>(session, abi::ALLOC, len)?;
+        self.memory
+            .write(&mut self.store, ptr as usize, bytes)
+            .map_err(|e| HostError::Malformed {
+                session,
+                message: format!("guest allocation out of bounds: {}", e),
+            })?;
+        Ok((ptr, len))
+    }
+
+    /// Encode `value` into a fresh guest buffer.
+    fn write_encoded<T: Serialize>(
+        &mut self,
+        session: u32,
+        value: &T,
+    ) -> Result<(u32, u32), HostError> {
+        let bytes = postcard::to_allocvec(value).map_err(|e| HostError::Malformed {
+            session,
+            message: format!("failed to encode request: {}", e),
+        })?;
+        self.write(session, &bytes)
+    }
+
+    fn free(&mut self, session: u32, ptr: u32, len: u32) -> Result<(), HostError> {
+        self.call::<(u32, u32), ()>(session, abi::DEALLOC, (ptr, len))
+    }
+
+    /// Copy out, free and decode a `Result<T, ParseError>` returned by the
+    /// guest.
+    fn read_result<T: DeserializeOwned>(
+        &mut self,
+        session: u32,
+        packed: u64,
+    ) -> Result<T, HostError> {
+        let (ptr, len) = abi::unpack(packed);
+        let mut bytes = vec![0; len as usize];
+        self.memory
+            .read(&self.store, ptr as usize, &mut bytes)
+            .map_err(|e| HostError::Malformed {
+                session,
+                message: format!("result out of bounds: {}", e),
+            })?;
+        self.free(session, ptr, len)?;
+
+        let result: Result<T, ParseError> =
+            postcard::from_bytes(&bytes).map_err(|e| HostError::Malformed {
+                session,
+                message: format!("failed to decode result: {}", e),
+            })?;
+        result.map_err(|error| HostError::Guest { session, error })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    /// A fake plugin in WAT implementing the raw ABI with canned results.
+    ///
+    /// `set_text` traps on the text `"trap"`, returns an undecodable
+    /// payload for `"junk"`, spins forever on `"loop"` and grows memory by
+    /// 64 MiB on `"grow"`; `parse` always returns a single `keyword` span
+    /// over bytes 0..2. It has no other captures, an empty source for every
+    /// query and `0123456789abcdef` as query hash.
+    pub(crate) fn fake_plugin(wire_version: u32) -> Vec<u8> {
+        fake_plugin_capturing(wire_version, "keyword")
+    }
+
+    /// [`fake_plugin`], with `capture` as the span's capture name, like a
+    /// build with other highlight queries.
+    pub(crate) fn fake_plugin_capturing(wire_version: u32, capture: &str) -> Vec<u8> {
+        assert!(capture.len() <= 25, "capture name overlaps other data");
+        let ok_unit = abi::pack(16, 1);
+        let ok_parse = abi::pack(32, 7 + capture.len() as u32);
+        let capture_len = capture.len();
+        let ok_capture_names = abi::pack(96, 3 + capture.len() as u32);
+        let ok_query_hash = abi::pack(128, 18);
+        let ok_query_source = abi::pack(160, 2);
+        let junk = abi::pack(64, 1);
+        format!(
+            r#"(module
+              (memory (export "memory") 1)
+              (global $next (mut i32) (i32.const 1024))
+              (data (i32.const 16) "\00")
+              (data (i32.const 32) "\00\01\00\02\{capture_len:02x}{capture}\00\00")
+              (data (i32.const 64) "\05")
+              (data (i32.const 96) "\00\01\{capture_len:02x}{capture}")
+              (data (i32.const 128) "\00\100123456789abcdef")
+              (data (i32.const 160) "\00\00")
+              (func (export "arborium_wire_version") (result i32) (i32.const {wire_version}))
+              (func (export "arborium_alloc") (param i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get 0)))
+                (local.get $ptr))
+              (func (export "arborium_dealloc") (param i32 i32))
+              (func (export "arborium_create_session") (result i32) (i32.const 1))
+              (func (export "arborium_free_session") (param i32))
+              (func (export "arborium_set_text") (param i32 i32 i32) (result i64)
+                (if (i32.eq (i32.load (local.get 1)) (i32.const 0x70617274))
+                  (then unreachable))
+                (if (i32.eq (i32.load (local.get 1)) (i32.const 0x6b6e756a))
+                  (then (return (i64.const {junk}))))
+                (if (i32.eq (i32.load (local.get 1)) (i32.const 0x706f6f6c))
+                  (then (loop $spin (br $spin))))
+                (if (i32.eq (i32.load (local.get 1)) (i32.const 0x776f7267))
+                  (then (drop (memory.grow (i32.const 1024)))))
+                (i64.const {ok_unit}))
+              (func (export "arborium_apply_edit") (param i32 i32 i32) (result i64)
+                (i64.const {ok_unit}))
+              (func (export "arborium_parse") (param i32) (result i64) (i64.const {ok_parse}))
+              (func (export "arborium_cancel") (param i32))
+              (func (export "arborium_capture_names") (result i64) (i64.const {ok_capture_names}))
+              (func (export "arborium_query_source") (param i32) (result i64)
+                (i64.const {ok_query_source}))
+              (func (export "arborium_query_hash") (result i64) (i64.const {ok_query_hash})))"#
+        )
+        .into_bytes()
+    }
+
+    fn edit() -> Edit {
+        Edit {
+            start_byte: 0,
+            old_end_byte: 0,
+            new_end_byte: 1,
+            start_row: 0,
+            start_col: 0,
+            old_end_row: 0,
+            old_end_col: 0,
+            new_end_row: 0,
+            new_end_col: 1,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut plugin = PluginHost::new().load(&fake_plugin(WIRE_VERSION)).unwrap();
+        assert_eq!(plugin.wire_version(), WIRE_VERSION);
+
+        let session = plugin.create_session().unwrap();
+        plugin.set_text(session, "fn x").unwrap();
+        plugin.apply_edit(session, " fn x", &edit()).unwrap();
+        plugin.cancel(session).unwrap();
+
+        let result = plugin.parse(session).unwrap();
+        assert_eq!(result.spans.len(), 1);
+        assert_eq!(result.spans[0].capture, "keyword");
+        assert_eq!((result.spans[0].start, result.spans[0].end), (0, 2));
+        assert!(result.injections.is_empty());
+
+        plugin.free_session(session).unwrap();
+        assert!(matches!(
+            plugin.parse(session),
+            Err(HostError::UnknownSession(s)) if s == session
+        ));
+    }
+
+    #[test]
+    fn test_wire_version_mismatch() {
+        let err = PluginHost::new().load(&fake_plugin(999)).unwrap_err();
+        assert!(
+            matches!(err, HostError::WireVersionMismatch { plugin: 999, host } if host == WIRE_VERSION),
+            "{err}"
+        );
+        assert!(err.to_string().contains("version 999"), "{err}");
+    }
+
+    #[test]
+    fn test_missing_export() {
+        let module = br#"(module (memory (export "memory") 1))"#;
+        let err = PluginHost::new().load(&module[..]).unwrap_err();
+        assert!(
+            matches!(err, HostError::MissingExport { name } if name == abi::WIRE_VERSION),
+            "{err}"
+        );
+
+        let err = PluginHost::new().load(&b"not wasm"[..]).unwrap_err();
+        assert!(matches!(err, HostError::Wasm(_)), "{err}");
+    }
+
+    #[test]
+    fn test_trap_only_drops_its_session() {
+        let mut plugin = PluginHost::new().load(&fake_plugin(WIRE_VERSION)).unwrap();
+        let doomed = plugin.create_session().unwrap();
+        let healthy = plugin.create_session().unwrap();
+
+        let err = plugin.set_text(doomed, "trap").unwrap_err();
+        assert!(
+            matches!(err, HostError::Trap { session, .. } if session == doomed),
+            "{err}"
+        );
+        assert!(matches!(
+            plugin.parse(doomed),
+            Err(HostError::UnknownSession(_))
+        ));
+
+        plugin.set_text(healthy, "fn x").unwrap();
+        assert_eq!(plugin.parse(healthy).unwrap().spans.len(), 1);
+    }
+
+    #[test]
+    fn test_malformed_payload_keeps_session() {
+        let mut plugin = PluginHost::new().load(&fake_plugin(WIRE_VERSION)).unwrap();
+        let session = plugin.create_session().unwrap();
+
+        let err = plugin.set_text(session, "junk").unwrap_err();
+        assert!(matches!(err, HostError::Malformed { .. }), "{err}");
+        assert!(plugin.parse(session).is_ok());
+    }
+
+    fn limited(deadline_ms: u64, on_violation: ViolationPolicy) -> LoadedPlugin {
+        let host = PluginHost::with_limits(PluginLimits {
+            deadline: Some(Duration::from_millis(deadline_ms)),
+            max_memory: Some(16 * 1024 * 1024),
+            on_violation,
+        });
+        host.load(&fake_plugin(WIRE_VERSION)).unwrap()
+    }
+
+    #[test]
+    fn test_deadline_interrupts_looping_plugin() {
+        let mut plugin = limited(50, ViolationPolicy::Recreate);
+        let looping = plugin.create_session().unwrap();
+        let other = plugin.create_session().unwrap();
+
+        let start = Instant::now();
+        let err = plugin.set_text(looping, "loop").unwrap_err();
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "{:?}",
+            start.elapsed()
+        );
+        assert!(
+            matches!(
+                err,
+                HostError::LimitExceeded { session, limit: Limit::Deadline(d) }
+                    if session == looping && d == Duration::from_millis(50)
+            ),
+            "{err}"
+        );
+        assert!(err.to_string().contains("timed out"), "{err}");
+
+        // The looping session is gone; the rest of the plugin is fine
+        assert!(matches!(
+            plugin.parse(looping),
+            Err(HostError::UnknownSession(_))
+        ));
+        assert!(plugin.is_healthy());
+        plugin.set_text(other, "fn x").unwrap();
+        let fresh = plugin.create_session().unwrap();
+        assert_eq!(plugin.parse(fresh).unwrap().spans.len(), 1);
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let mut plugin = limited(1000, ViolationPolicy::Recreate);
+        let session = plugin.create_session().unwrap();
+        let err = plugin.set_text(session, "grow").unwrap_err();
+        assert!(
+            matches!(
+                err,
+                HostError::LimitExceeded { limit: Limit::Memory(max), .. } if max == 16 * 1024 * 1024
+            ),
+            "{err}"
+        );
+
+        let host = PluginHost::with_limits(PluginLimits::unlimited());
+        let mut plugin = host.load(&fake_plugin(WIRE_VERSION)).unwrap();
+        let session = plugin.create_session().unwrap();
+        plugin.set_text(session, "grow").unwrap();
+    }
+
+    #[test]
+    fn test_unhealthy_policy() {
+        let mut plugin = limited(50, ViolationPolicy::MarkUnhealthy);
+        let looping = plugin.create_session().unwrap();
+        let other = plugin.create_session().unwrap();
+
+        assert!(matches!(
+            plugin.set_text(looping, "loop"),
+            Err(HostError::LimitExceeded { .. })
+        ));
+        assert!(!plugin.is_healthy());
+        assert!(matches!(plugin.parse(other), Err(HostError::Unhealthy)));
+        assert!(matches!(plugin.create_session(), Err(HostError::Unhealthy)));
+    }
+
+    #[test]
+    fn test_reload_keeps_sessions() {
+        let host = PluginHost::with_limits(PluginLimits::unlimited());
+        let mut plugin = host.load(&fake_plugin(WIRE_VERSION)).unwrap();
+        let session = plugin.create_session().unwrap();
+        plugin.set_text(session, "fn x").unwrap();
+        assert_eq!(plugin.parse(session).unwrap().spans[0].capture, "keyword");
+
+        let rebuilt = host
+            .load(&fake_plugin_capturing(WIRE_VERSION, "function"))
+            .unwrap();
+        plugin.reload(rebuilt).unwrap();
+        assert_eq!(plugin.parse(session).unwrap().spans[0].capture, "function");
+        assert_ne!(plugin.create_session().unwrap(), session);
+    }
+
+    #[test]
+    fn test_failed_reload_keeps_old_build() {
+        let mut plugin = PluginHost::with_limits(PluginLimits::unlimited())
+            .load(&fake_plugin(WIRE_VERSION))
+            .unwrap();
+        let session = plugin.create_session().unwrap();
+        plugin.set_text(session, "grow").unwrap();
+
+        // Replaying the text into the new build exceeds its memory limit
+        let rebuilt = limited(1000, ViolationPolicy::Recreate);
+        assert!(matches!(
+            plugin.reload(rebuilt),
+            Err(HostError::LimitExceeded { .. })
+        ));
+        assert_eq!(plugin.parse(session).unwrap().spans[0].capture, "keyword");
+    }
+
+    #[test]
+    fn test_introspection() {
+        let mut plugin = PluginHost::new().load(&fake_plugin(WIRE_VERSION)).unwrap();
+        let names = plugin.capture_names().unwrap();
+        assert_eq!(names, ["keyword"]);
+
+        let session = plugin.create_session().unwrap();
+        plugin.set_text(session, "fn x").unwrap();
+        for span in plugin.parse(session).unwrap().spans {
+            assert!(names.contains(&span.capture), "{}", span.capture);
+        }
+
+        assert_eq!(plugin.query_hash().unwrap(), "0123456789abcdef");
+        assert_eq!(plugin.query_source(QueryKind::Locals).unwrap(), "");
+    }
+}
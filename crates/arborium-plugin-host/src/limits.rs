@@ -0,0 +1,179 @@
+//! Resource limits for plugin execution.
+
+use std::fmt;
+use std::time::Duration;
+
+use wasmtime::{Engine, ResourceLimiter};
+
+/// How often the engine's epoch advances; deadlines are rounded up to it.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Limits on what a plugin may do in a single call.
+///
+/// Plugins run parsers and scanners compiled from third-party C code; a
+/// pathological input can make them loop forever or grow memory without
+/// bound. Calls exceeding a limit fail with [`HostError::LimitExceeded`],
+/// and [`on_violation`](Self::on_violation) decides what happens to the
+/// plugin afterwards.
+///
+/// [`HostError::LimitExceeded`]: crate::HostError::LimitExceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginLimits {
+    /// Wall-clock budget per call into the plugin. `None` disables it.
+    pub deadline: Option<Duration>,
+    /// Maximum linear memory per instance, in bytes. `None` disables it.
+    pub max_memory: Option<usize>,
+    /// What to do with a plugin that exceeded a limit.
+    pub on_violation: ViolationPolicy,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            deadline: Some(Duration::from_secs(1)),
+            max_memory: Some(256 * 1024 * 1024),
+            on_violation: ViolationPolicy::default(),
+        }
+    }
+}
+
+impl PluginLimits {
+    /// No limits at all.
+    pub fn unlimited() -> Self {
+        Self {
+            deadline: None,
+            max_memory: None,
+            on_violation: ViolationPolicy::default(),
+        }
+    }
+
+    /// The deadline in epoch ticks, if any.
+    pub(crate) fn deadline_ticks(&self) -> Option<u64> {
+        self.deadline.map(|deadline| {
+            let ticks = deadline.as_nanos().div_ceil(EPOCH_TICK.as_nanos());
+            u64::try_from(ticks).unwrap_or(u64::MAX).max(1)
+        })
+    }
+}
+
+/// What happens to a plugin after a call exceeded a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViolationPolicy {
+    /// Drop the session's instance, like after a trap. The plugin stays
+    /// usable; new sessions get fresh instances.
+    #[default]
+    Recreate,
+    /// Drop all sessions and refuse further calls with
+    /// [`HostError::Unhealthy`](crate::HostError::Unhealthy), so callers
+    /// fall back to plain text.
+    MarkUnhealthy,
+}
+
+/// The limit a call exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// The call ran past [`PluginLimits::deadline`].
+    Deadline(Duration),
+    /// The instance tried to grow past [`PluginLimits::max_memory`].
+    Memory(usize),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Deadline(deadline) => write!(f, "timed out after {:?}", deadline),
+            Limit::Memory(max) => write!(f, "exceeded its memory limit of {} bytes", max),
+        }
+    }
+}
+
+/// Per-store state enforcing the memory limit.
+pub(crate) struct StoreState {
+    max_memory: Option<usize>,
+    /// Set when a memory grow was refused, to tell the resulting trap apart
+    /// from ordinary ones.
+    pub(crate) memory_exceeded: bool,
+}
+
+impl StoreState {
+    pub(crate) fn new(limits: &PluginLimits) -> Self {
+        Self {
+            max_memory: limits.max_memory,
+            memory_exceeded: false,
+        }
+    }
+}
+
+impl ResourceLimiter for StoreState {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        match self.max_memory {
+            Some(max) if desired > max => {
+                // Trap rather than fail the grow: plugins abort on allocation
+                // failure anyway, and this way the call fails right here
+                self.memory_exceeded = true;
+                Err(wasmtime::Error::msg("plugin memory limit exceeded"))
+            }
+            _ => Ok(true),
+        }
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Create an engine enforcing `limits`.
+///
+/// With a deadline, a background thread advances the engine's epoch every
+/// [`EPOCH_TICK`]; it exits once the engine is dropped.
+pub(crate) fn engine_for(limits: &PluginLimits) -> Engine {
+    let mut config = wasmtime::Config::new();
+    config.epoch_interruption(limits.deadline.is_some());
+    // Plugins unwind panics with wasm exceptions
+    config.wasm_exceptions(true);
+    let engine = Engine::new(&config).expect("engine config is valid");
+
+    if limits.deadline.is_some() {
+        let weak = engine.weak();
+        std::thread::Builder::new()
+            .name("arborium-plugin-epoch".to_string())
+            .spawn(move || {
+                loop {
+                    std::thread::sleep(EPOCH_TICK);
+                    match weak.upgrade() {
+                        Some(engine) => engine.increment_epoch(),
+                        None => break,
+                    }
+                }
+            })
+            .expect("failed to spawn epoch thread");
+    }
+    engine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline_ticks_round_up() {
+        let limits = |deadline| PluginLimits {
+            deadline: Some(deadline),
+            ..PluginLimits::default()
+        };
+        assert_eq!(limits(Duration::from_millis(50)).deadline_ticks(), Some(5));
+        assert_eq!(limits(Duration::from_millis(51)).deadline_ticks(), Some(6));
+        assert_eq!(limits(Duration::ZERO).deadline_ticks(), Some(1));
+        assert_eq!(PluginLimits::unlimited().deadline_ticks(), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Reading plugin manifests without instantiating plugins.
+
+use arborium_wire::{MANIFEST_SECTION, PluginManifest, WIRE_VERSION, is_version_compatible};
+
+use crate::HostError;
+
+/// Read the manifest embedded in a plugin's `.wasm` bytes.
+///
+/// Only walks the module's section headers; nothing is compiled. Plugins
+/// speaking another wire version are rejected with
+/// [`HostError::WireVersionMismatch`].
+pub fn read_manifest(wasm: &[u8]) -> Result<PluginManifest, HostError> {
+    let section = find_custom_section(wasm, MANIFEST_SECTION)?.ok_or(HostError::MissingManifest)?;
+
+    // The version comes first so it can be checked even if the rest of the
+    // manifest changed shape between versions
+    let (wire_version, _) =
+        postcard::take_from_bytes::<u32>(section).map_err(|e| invalid(format!("{}", e)))?;
+    if !is_version_compatible(wire_version) {
+        return Err(HostError::WireVersionMismatch {
+            plugin: wire_version,
+            host: WIRE_VERSION,
+        });
+    }
+
+    postcard::from_bytes(section).map_err(|e| invalid(format!("{}", e)))
+}
+
+fn invalid(message: String) -> HostError {
+    HostError::InvalidManifest { message }
+}
+
+/// Find the payload of the first custom section called `name`.
+fn find_custom_section<'a>(wasm: &'a [u8], name: &str) -> Result<Option<&'a [u8]>, HostError> {
+    let mut rest = wasm
+        .strip_prefix(b"\0asm\x01\0\0\0")
+        .ok_or_else(|| invalid("not a wasm module".to_string()))?;
+
+    while let Some((&id, after_id)) = rest.split_first() {
+        let (size, after_size) = read_u32(after_id)?;
+        let size = size as usize;
+        if after_size.len() < size {
+            return Err(invalid("truncated section".to_string()));
+        }
+        let (payload, next) = after_size.split_at(size);
+        rest = next;
+
+        if id == 0 {
+            let (name_len, after_len) = read_u32(payload)?;
+            let name_len = name_len as usize;
+            if after_len.len() < name_len {
+                return Err(invalid("truncated section name".to_string()));
+            }
+            let (section_name, data) = after_len.split_at(name_len);
+            if section_name == name.as_bytes() {
+                return Ok(Some(data));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Read an unsigned LEB128 `u32`.
+fn read_u32(bytes: &[u8]) -> Result<(u32, &[u8]), HostError> {
+    let mut value = 0u32;
+    for (i, &byte) in bytes.iter().enumerate().take(5) {
+        value |= u32::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+    Err(invalid("bad LEB128 integer".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(wire_version: u32) -> PluginManifest {
+        PluginManifest {
+            wire_version,
+            language_id: "rust".to_string(),
+            aliases: vec!["rs".to_string()],
+            extensions: vec!["rust".to_string(), "rs".to_string()],
+            provides_injections: true,
+            provides_locals: true,
+            query_hash: "0123456789abcdef".to_string(),
+            version: "2.4.1".to_string(),
+        }
+    }
+
+    fn leb(mut value: usize, out: &mut Vec<u8>) {
+        while value >= 0x80 {
+            out.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    /// A module with a type section, then a custom section per entry.
+    fn module(customs: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // Type section with one `() -> ()` function type
+        wasm.extend_from_slice(&[1, 4, 1, 0x60, 0, 0]);
+        for (name, data) in customs {
+            let mut payload = Vec::new();
+            leb(name.len(), &mut payload);
+            payload.extend_from_slice(name.as_bytes());
+            payload.extend_from_slice(data);
+            wasm.push(0);
+            leb(payload.len(), &mut wasm);
+            wasm.extend(payload);
+        }
+        wasm
+    }
+
+    #[test]
+    fn test_read_manifest() {
+        let encoded = postcard::to_allocvec(&manifest(WIRE_VERSION)).unwrap();
+        let wasm = module(&[("name", b"\0"), (MANIFEST_SECTION, &encoded)]);
+        assert_eq!(read_manifest(&wasm).unwrap(), manifest(WIRE_VERSION));
+    }
+
+    #[test]
+    fn test_wire_version_mismatch() {
+        let encoded = postcard::to_allocvec(&manifest(999)).unwrap();
+        let wasm = module(&[(MANIFEST_SECTION, &encoded)]);
+        let err = read_manifest(&wasm).unwrap_err();
+        assert!(
+            matches!(err, HostError::WireVersionMismatch { plugin: 999, host } if host == WIRE_VERSION),
+            "{err}"
+        );
+
+        // Only the version has to decode for the mismatch to be reported
+        let mut future = Vec::new();
+        leb(999, &mut future);
+        future.push(0xff);
+        let wasm = module(&[(MANIFEST_SECTION, &future)]);
+        assert!(matches!(
+            read_manifest(&wasm),
+            Err(HostError::WireVersionMismatch { plugin: 999, .. })
+        ));
+    }
+
+    #[test]
+    fn test_missing_or_invalid() {
+        assert!(matches!(
+            read_manifest(&module(&[])),
+            Err(HostError::MissingManifest)
+        ));
+        assert!(matches!(
+            read_manifest(b"not wasm"),
+            Err(HostError::InvalidManifest { .. })
+        ));
+
+        let mut truncated = module(&[]);
+        truncated.extend_from_slice(&[0, 50, 1]);
+        assert!(matches!(
+            read_manifest(&truncated),
+            Err(HostError::InvalidManifest { .. })
+        ));
+
+        let mut garbage = Vec::new();
+        leb(WIRE_VERSION as usize, &mut garbage);
+        garbage.push(0xff);
+        let wasm = module(&[(MANIFEST_SECTION, &garbage)]);
+        assert!(matches!(
+            read_manifest(&wasm),
+            Err(HostError::InvalidManifest { .. })
+        ));
+    }
+}
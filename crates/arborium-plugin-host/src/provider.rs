@@ -0,0 +1,331 @@
+//! One interface over grammar plugins and natively compiled grammars.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use arborium_wire::{Edit, Utf8ParseResult};
+
+use crate::{HostError, LoadedPlugin, PluginRegistry};
+
+/// Parses text in sessions, whether the grammar is a plugin or compiled in.
+///
+/// Implemented by [`LoadedPlugin`] and, with the `native` feature, by
+/// [`NativeProvider`]. Both behave the same: unknown sessions fail with
+/// [`HostError::UnknownSession`], parsing a session without text fails with
+/// [`HostError::Guest`], spans are sorted by start then end, and a
+/// cancelled session parses to an empty result until its text changes.
+///
+/// Pattern indices are only comparable between spans of the same provider.
+pub trait SyntaxProvider: Send {
+    /// Create a parsing session.
+    fn create_session(&mut self) -> Result<u32, HostError>;
+
+    /// Free a session.
+    fn free_session(&mut self, session: u32) -> Result<(), HostError>;
+
+    /// Set the full text of a session.
+    fn set_text(&mut self, session: u32, text: &str) -> Result<(), HostError>;
+
+    /// Apply an incremental edit, `new_text` being the full text after it.
+    fn apply_edit(&mut self, session: u32, new_text: &str, edit: &Edit) -> Result<(), HostError>;
+
+    /// Parse the session's text, returning spans and injections with UTF-8
+    /// byte offsets.
+    fn parse(&mut self, session: u32) -> Result<Utf8ParseResult, HostError>;
+
+    /// Request cancellation of the session's parse.
+    fn cancel(&mut self, session: u32) -> Result<(), HostError>;
+
+    /// Whether a plugin backs this provider.
+    fn is_plugin(&self) -> bool;
+}
+
+impl SyntaxProvider for LoadedPlugin {
+    fn create_session(&mut self) -> Result<u32, HostError> {
+        LoadedPlugin::create_session(self)
+    }
+
+    fn free_session(&mut self, session: u32) -> Result<(), HostError> {
+        LoadedPlugin::free_session(self, session)
+    }
+
+    fn set_text(&mut self, session: u32, text: &str) -> Result<(), HostError> {
+        LoadedPlugin::set_text(self, session, text)
+    }
+
+    fn apply_edit(&mut self, session: u32, new_text: &str, edit: &Edit) -> Result<(), HostError> {
+        LoadedPlugin::apply_edit(self, session, new_text, edit)
+    }
+
+    fn parse(&mut self, session: u32) -> Result<Utf8ParseResult, HostError> {
+        LoadedPlugin::parse(self, session)
+    }
+
+    fn cancel(&mut self, session: u32) -> Result<(), HostError> {
+        LoadedPlugin::cancel(self, session)
+    }
+
+    fn is_plugin(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "native")]
+pub use native::NativeProvider;
+
+#[cfg(feature = "native")]
+mod native {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use arborium_highlight::tree_sitter::{CompiledGrammar, ParseContext};
+    use arborium_wire::{Edit, ParseError, Utf8Injection, Utf8ParseResult, Utf8Span};
+
+    use super::SyntaxProvider;
+    use crate::HostError;
+
+    /// A [`SyntaxProvider`] over a grammar compiled into the host.
+    ///
+    /// Edits reparse the whole text; natively that is fast enough not to
+    /// bother with tree-sitter's incremental parsing.
+    pub struct NativeProvider {
+        grammar: Arc<CompiledGrammar>,
+        context: ParseContext,
+        sessions: HashMap<u32, NativeSession>,
+        next_session: u32,
+    }
+
+    #[derive(Default)]
+    struct NativeSession {
+        text: Option<String>,
+        cancelled: bool,
+    }
+
+    impl NativeProvider {
+        /// Create a provider for `grammar`.
+        pub fn new(grammar: Arc<CompiledGrammar>) -> Result<Self, HostError> {
+            let context = ParseContext::for_grammar(&grammar)
+                .map_err(|e| HostError::Native(e.to_string()))?;
+            Ok(Self {
+                grammar,
+                context,
+                sessions: HashMap::new(),
+                next_session: 1,
+            })
+        }
+
+        fn session(&mut self, session: u32) -> Result<&mut NativeSession, HostError> {
+            self.sessions
+                .get_mut(&session)
+                .ok_or(HostError::UnknownSession(session))
+        }
+    }
+
+    impl SyntaxProvider for NativeProvider {
+        fn create_session(&mut self) -> Result<u32, HostError> {
+            let session = self.next_session;
+            self.next_session += 1;
+            self.sessions.insert(session, NativeSession::default());
+            Ok(session)
+        }
+
+        fn free_session(&mut self, session: u32) -> Result<(), HostError> {
+            self.sessions
+                .remove(&session)
+                .map(drop)
+                .ok_or(HostError::UnknownSession(session))
+        }
+
+        fn set_text(&mut self, session: u32, text: &str) -> Result<(), HostError> {
+            let state = self.session(session)?;
+            state.text = Some(text.to_string());
+            state.cancelled = false;
+            Ok(())
+        }
+
+        fn apply_edit(
+            &mut self,
+            session: u32,
+            new_text: &str,
+            _edit: &Edit,
+        ) -> Result<(), HostError> {
+            self.set_text(session, new_text)
+        }
+
+        fn parse(&mut self, session: u32) -> Result<Utf8ParseResult, HostError> {
+            let state = self
+                .sessions
+                .get(&session)
+                .ok_or(HostError::UnknownSession(session))?;
+            if state.cancelled {
+                return Ok(Utf8ParseResult::empty());
+            }
+            // Same error as the plugin runtime's
+            let text = state.text.as_deref().ok_or_else(|| HostError::Guest {
+                session,
+                error: ParseError::new("no text set for session"),
+            })?;
+
+            let result = self.grammar.parse(&mut self.context, text);
+            let mut spans: Vec<Utf8Span> = result
+                .spans
+                .into_iter()
+                .map(|s| Utf8Span {
+                    start: s.start,
+                    end: s.end,
+                    capture: s.capture,
+                    pattern_index: s.pattern_index,
+                })
+                .collect();
+            spans.sort_by_key(|s| (s.start, s.end));
+            let injections = result
+                .injections
+                .into_iter()
+                .map(|i| Utf8Injection {
+                    start: i.start,
+                    end: i.end,
+                    language: i.language,
+                    include_children: i.include_children,
+                    combined: i.combined,
+                })
+                .collect();
+            Ok(Utf8ParseResult { spans, injections })
+        }
+
+        fn cancel(&mut self, session: u32) -> Result<(), HostError> {
+            self.session(session)?.cancelled = true;
+            Ok(())
+        }
+
+        fn is_plugin(&self) -> bool {
+            false
+        }
+    }
+}
+
+/// Creates a provider for a grammar compiled into the host.
+type ProviderFactory = Box<dyn Fn() -> Result<Box<dyn SyntaxProvider>, HostError> + Send>;
+
+/// Serves a [`SyntaxProvider`] per language, preferring grammars compiled
+/// into the host and falling back to plugins.
+///
+/// This lets a binary compile in a few languages and load the rest as
+/// plugins on demand, without callers caring which is which. Providers are
+/// kept alive once created, since callers hold sessions in them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use arborium_plugin_host::{NativeProvider, ProviderRegistry};
+///
+/// let mut registry = ProviderRegistry::scan(&[config_dir.join("grammars")]);
+/// registry.add_native("rust", &["rs"], move || {
+///     Ok(Box::new(NativeProvider::new(rust_grammar.clone())?))
+/// });
+///
+/// let provider = registry.provider_for("zig")?;
+/// let session = provider.create_session()?;
+/// provider.set_text(session, "const x = 1;")?;
+/// let result = provider.parse(session)?;
+/// ```
+pub struct ProviderRegistry {
+    plugins: PluginRegistry,
+    natives: HashMap<String, ProviderFactory>,
+    /// Lowercased IDs and aliases of native grammars to language IDs.
+    native_names: HashMap<String, String>,
+    live: HashMap<String, Box<dyn SyntaxProvider>>,
+}
+
+impl fmt::Debug for ProviderRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProviderRegistry")
+            .field("plugins", &self.plugins)
+            .field("natives", &self.natives.len())
+            .field("live", &self.live.len())
+            .finish()
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::new(PluginRegistry::default())
+    }
+}
+
+impl ProviderRegistry {
+    /// Create a registry falling back to the plugins of `plugins`.
+    pub fn new(plugins: PluginRegistry) -> Self {
+        Self {
+            plugins,
+            natives: HashMap::new(),
+            native_names: HashMap::new(),
+            live: HashMap::new(),
+        }
+    }
+
+    /// Create a registry falling back to the plugins found in `dirs`.
+    pub fn scan(dirs: &[PathBuf]) -> Self {
+        Self::new(PluginRegistry::scan(dirs))
+    }
+
+    /// The plugins used as fallback.
+    pub fn plugins(&self) -> &PluginRegistry {
+        &self.plugins
+    }
+
+    /// Add a grammar compiled into the host, answering to `aliases` too.
+    pub fn add_native(
+        &mut self,
+        language_id: &str,
+        aliases: &[&str],
+        factory: impl Fn() -> Result<Box<dyn SyntaxProvider>, HostError> + Send + 'static,
+    ) {
+        for name in aliases.iter().chain([&language_id]) {
+            self.native_names
+                .insert(name.to_ascii_lowercase(), language_id.to_string());
+        }
+        self.natives
+            .insert(language_id.to_string(), Box::new(factory));
+        // A plugin may have been serving it so far
+        self.live.remove(language_id);
+    }
+
+    /// Resolve a name to a language ID, and whether a native grammar
+    /// provides it.
+    fn resolve(&self, name: &str) -> Option<(String, bool)> {
+        let lowered = name.trim_start_matches('.').to_ascii_lowercase();
+        if let Some(id) = self.native_names.get(&lowered) {
+            return Some((id.clone(), true));
+        }
+        self.plugins.resolve(name).map(|id| (id.to_string(), false))
+    }
+
+    /// Whether `name` is provided by a native grammar, or `None` if nothing
+    /// provides it.
+    pub fn is_native(&self, name: &str) -> Option<bool> {
+        self.resolve(name).map(|(_, native)| native)
+    }
+
+    /// Get the provider for a language ID, alias or file extension,
+    /// creating it if needed.
+    pub fn provider_for(&mut self, name: &str) -> Result<&mut dyn SyntaxProvider, HostError> {
+        let (language_id, native) = self
+            .resolve(name)
+            .ok_or_else(|| HostError::UnknownLanguage(name.to_string()))?;
+
+        if !self.live.contains_key(&language_id) {
+            let provider: Box<dyn SyntaxProvider> = if native {
+                (self.natives[&language_id])()?
+            } else {
+                Box::new(self.plugins.load_plugin(&language_id)?)
+            };
+            self.live.insert(language_id.clone(), provider);
+        }
+        Ok(self
+            .live
+            .get_mut(&language_id)
+            .expect("just inserted")
+            .as_mut())
+    }
+}
@@ -0,0 +1,949 @@
+//! Discovering plugins in directories and serving grammars by name.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use arborium_highlight::{Grammar, GrammarProvider, Injection, ParseResult, Span};
+use arborium_wire::PluginManifest;
+
+use crate::{HostError, LoadedPlugin, PluginHost, read_manifest};
+
+/// Creates instances of a built-in grammar.
+type NativeFactory = Box<dyn Fn() -> Box<dyn Grammar + Send> + Send>;
+
+/// Default for [`PluginRegistry::with_max_live_plugins`].
+const DEFAULT_MAX_LIVE_PLUGINS: usize = 16;
+
+/// Something [`PluginRegistry::scan`] skipped or had to choose.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RegistryWarning {
+    /// A `.wasm` file couldn't be used, e.g. it has no manifest or speaks
+    /// another wire version.
+    Skipped {
+        /// The file.
+        path: PathBuf,
+        /// Why it was skipped.
+        error: HostError,
+    },
+
+    /// Two plugins provide the same language with the same version; the
+    /// first one found is used.
+    Conflict {
+        /// The language both plugins provide.
+        language_id: String,
+        /// The plugin in use.
+        kept: PathBuf,
+        /// The plugin ignored.
+        ignored: PathBuf,
+    },
+}
+
+impl fmt::Display for RegistryWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryWarning::Skipped { path, error } => {
+                write!(f, "skipping {}: {}", path.display(), error)
+            }
+            RegistryWarning::Conflict {
+                language_id,
+                kept,
+                ignored,
+            } => write!(
+                f,
+                "{} and {} both provide `{}` at the same version; using the former",
+                kept.display(),
+                ignored.display(),
+                language_id
+            ),
+        }
+    }
+}
+
+/// How a language is provided.
+#[derive(Default)]
+struct Entry {
+    plugin: Option<PluginEntry>,
+    native: Option<NativeFactory>,
+}
+
+struct PluginEntry {
+    path: PathBuf,
+    manifest: PluginManifest,
+}
+
+/// Grammars from plugin directories and built-in native grammars, looked up
+/// by language ID, alias or file extension.
+///
+/// Plugins are only compiled on first use, and at most
+/// [`with_max_live_plugins`](Self::with_max_live_plugins) of them are kept
+/// alive, least recently used first out. A plugin providing the same
+/// language as a native grammar takes precedence over it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use arborium_plugin_host::PluginRegistry;
+///
+/// let mut registry = PluginRegistry::scan(&[config_dir.join("grammars")]);
+/// for warning in registry.warnings() {
+///     eprintln!("warning: {warning}");
+/// }
+/// let grammar = registry.highlighter_for("zig")?;
+/// let result = grammar.try_parse("const x = 1;")?;
+/// ```
+pub struct PluginRegistry {
+    host: PluginHost,
+    entries: HashMap<String, Entry>,
+    /// Lowercased IDs, aliases and extensions to language IDs.
+    names: HashMap<String, String>,
+    live: HashMap<String, GrammarHandle>,
+    /// Languages with a live plugin instance, least recently used first.
+    lru: VecDeque<String>,
+    max_live_plugins: usize,
+    warnings: Vec<RegistryWarning>,
+}
+
+impl fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginRegistry")
+            .field("languages", &self.entries.len())
+            .field("live", &self.live.len())
+            .field("warnings", &self.warnings)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        Self::new(PluginHost::new())
+    }
+}
+
+impl PluginRegistry {
+    /// Create an empty registry loading plugins with `host`.
+    pub fn new(host: PluginHost) -> Self {
+        Self {
+            host,
+            entries: HashMap::new(),
+            names: HashMap::new(),
+            live: HashMap::new(),
+            lru: VecDeque::new(),
+            max_live_plugins: DEFAULT_MAX_LIVE_PLUGINS,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Create a registry with the plugins found in `dirs`.
+    ///
+    /// Directories that don't exist are ignored.
+    pub fn scan(dirs: &[PathBuf]) -> Self {
+        let mut registry = Self::default();
+        for dir in dirs {
+            registry.scan_dir(dir);
+        }
+        registry
+    }
+
+    /// Set how many plugin instances are kept alive at once.
+    pub fn with_max_live_plugins(mut self, max: usize) -> Self {
+        self.max_live_plugins = max.max(1);
+        self
+    }
+
+    /// Add the `.wasm` files in `dir`, in file name order.
+    ///
+    /// When two plugins provide the same language, the one with the higher
+    /// manifest version wins; on a tie the first one stays, and a
+    /// [`RegistryWarning::Conflict`] is recorded.
+    pub fn scan_dir(&mut self, dir: &Path) {
+        let Ok(read_dir) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = read_dir
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            match std::fs::read(&path)
+                .map_err(HostError::from)
+                .and_then(|bytes| read_manifest(&bytes))
+            {
+                Ok(manifest) => self.add_plugin(path, manifest),
+                Err(error) => self.warnings.push(RegistryWarning::Skipped { path, error }),
+            }
+        }
+    }
+
+    fn add_plugin(&mut self, path: PathBuf, manifest: PluginManifest) {
+        let language_id = manifest.language_id.clone();
+        let existing = self
+            .entries
+            .get(&language_id)
+            .and_then(|entry| entry.plugin.as_ref());
+        if let Some(existing) = existing {
+            match compare_versions(&manifest.version, &existing.manifest.version) {
+                Ordering::Greater => {}
+                Ordering::Less => return,
+                Ordering::Equal => {
+                    let kept = existing.path.clone();
+                    self.warnings.push(RegistryWarning::Conflict {
+                        language_id,
+                        kept,
+                        ignored: path,
+                    });
+                    return;
+                }
+            }
+        }
+
+        self.index(&language_id, &manifest.aliases, &manifest.extensions);
+        self.evict(&language_id);
+        let entry = self.entries.entry(language_id).or_default();
+        entry.plugin = Some(PluginEntry { path, manifest });
+    }
+
+    /// Add a built-in grammar, answering to `aliases` as names and file
+    /// extensions like generated grammar crates do.
+    pub fn add_native(
+        &mut self,
+        language_id: &str,
+        aliases: &[&str],
+        factory: impl Fn() -> Box<dyn Grammar + Send> + Send + 'static,
+    ) {
+        let aliases: Vec<String> = aliases.iter().map(|a| a.to_string()).collect();
+        self.index(language_id, &aliases, &aliases);
+        let entry = self.entries.entry(language_id.to_string()).or_default();
+        entry.native = Some(Box::new(factory));
+    }
+
+    fn index(&mut self, language_id: &str, aliases: &[String], extensions: &[String]) {
+        // Canonical IDs beat aliases of other languages
+        for name in aliases.iter().chain(extensions) {
+            let name = name.to_ascii_lowercase();
+            if !self.entries.contains_key(&name) {
+                self.names.insert(name, language_id.to_string());
+            }
+        }
+        self.names
+            .insert(language_id.to_ascii_lowercase(), language_id.to_string());
+    }
+
+    /// Resolve a language ID, alias or file extension to a language ID.
+    pub fn resolve(&self, name: &str) -> Option<&str> {
+        let name = name.trim_start_matches('.').to_ascii_lowercase();
+        self.names.get(&name).map(String::as_str)
+    }
+
+    /// All known language IDs, sorted.
+    pub fn languages(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.entries.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// The manifest of the plugin providing `name`, if a plugin provides it.
+    pub fn manifest(&self, name: &str) -> Option<&PluginManifest> {
+        let entry = self.entries.get(self.resolve(name)?)?;
+        entry.plugin.as_ref().map(|plugin| &plugin.manifest)
+    }
+
+    /// What [`scan`](Self::scan) skipped or had to choose.
+    pub fn warnings(&self) -> &[RegistryWarning] {
+        &self.warnings
+    }
+
+    /// How many plugin instances are alive.
+    pub fn live_plugins(&self) -> usize {
+        self.lru.len()
+    }
+
+    /// Get the grammar for a language ID, alias or file extension,
+    /// instantiating its plugin if needed.
+    pub fn highlighter_for(&mut self, name: &str) -> Result<&mut GrammarHandle, HostError> {
+        let language_id = self
+            .resolve(name)
+            .ok_or_else(|| HostError::UnknownLanguage(name.to_string()))?
+            .to_string();
+
+        if !self.live.contains_key(&language_id) {
+            let handle = self.instantiate(&language_id)?;
+            self.live.insert(language_id.clone(), handle);
+        }
+        if self.live[&language_id].is_plugin() {
+            self.touch(&language_id);
+        }
+        Ok(self.live.get_mut(&language_id).expect("just inserted"))
+    }
+
+    /// Reload the plugin providing `name` from its file, e.g. after
+    /// rebuilding it.
+    ///
+    /// A live instance is migrated with [`LoadedPlugin::reload`], so its
+    /// session survives and the next parse uses the new build. If the new
+    /// build can't be loaded, the old one stays in use and the error is
+    /// returned. Languages only provided natively can't be reloaded and
+    /// report [`HostError::UnknownLanguage`].
+    pub fn reload(&mut self, name: &str) -> Result<(), HostError> {
+        let unknown = || HostError::UnknownLanguage(name.to_string());
+        let language_id = self.resolve(name).ok_or_else(unknown)?.to_string();
+        let path = match &self.entries[&language_id].plugin {
+            Some(plugin) => plugin.path.clone(),
+            None => return Err(unknown()),
+        };
+
+        let bytes = std::fs::read(&path)?;
+        let manifest = read_manifest(&bytes)?;
+        if manifest.language_id != language_id {
+            return Err(HostError::InvalidManifest {
+                message: format!(
+                    "{} now provides `{}` instead of `{}`",
+                    path.display(),
+                    manifest.language_id,
+                    language_id
+                ),
+            });
+        }
+        let replacement = self.host.load(&bytes)?;
+        match self.live.get_mut(&language_id) {
+            Some(GrammarHandle(Backend::Plugin { plugin, .. })) => plugin.reload(replacement)?,
+            _ => {
+                self.live.insert(
+                    language_id.clone(),
+                    GrammarHandle(Backend::Plugin {
+                        plugin: Box::new(replacement),
+                        session: None,
+                    }),
+                );
+                self.touch(&language_id);
+            }
+        }
+
+        self.index(&language_id, &manifest.aliases, &manifest.extensions);
+        let entry = self.entries.get_mut(&language_id).expect("resolved");
+        entry.plugin = Some(PluginEntry { path, manifest });
+        Ok(())
+    }
+
+    /// Reload the plugins whose files `watcher` saw change since the last
+    /// call, returning the outcome per language.
+    ///
+    /// New files aren't picked up; use [`scan_dir`](Self::scan_dir) for
+    /// those.
+    #[cfg(feature = "watch")]
+    pub fn reload_changed(
+        &mut self,
+        watcher: &crate::PluginWatcher,
+    ) -> Vec<(String, Result<(), HostError>)> {
+        let changed = watcher.changed_paths();
+        let mut language_ids: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| {
+                entry.plugin.as_ref().is_some_and(|plugin| {
+                    let path =
+                        std::fs::canonicalize(&plugin.path).unwrap_or_else(|_| plugin.path.clone());
+                    changed.contains(&path)
+                })
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        language_ids.sort_unstable();
+
+        language_ids
+            .into_iter()
+            .map(|id| {
+                let result = self.reload(&id);
+                (id, result)
+            })
+            .collect()
+    }
+
+    /// Highlight a whole document, following injections into whichever
+    /// grammars provide the injected languages.
+    ///
+    /// Each injected range is parsed on its own by the grammar its language
+    /// resolves to, and the resulting spans are offset into document
+    /// coordinates. Injections nest at most `max_depth` levels deep. Unknown
+    /// injected languages and injected grammars that fail leave their range
+    /// as the enclosing grammar highlighted it; only a failure of the root
+    /// grammar is an error.
+    pub fn highlight_document(
+        &mut self,
+        text: &str,
+        root_language: &str,
+        max_depth: u32,
+    ) -> Result<Vec<Span>, HostError> {
+        let result = self.highlighter_for(root_language)?.try_parse(text)?;
+        let mut spans = result.spans;
+        self.inject(text, 0, result.injections, max_depth, &mut spans);
+        Ok(spans)
+    }
+
+    /// Highlight `injections` into `text`, which starts at `base` in the
+    /// document.
+    fn inject(
+        &mut self,
+        text: &str,
+        base: u32,
+        injections: Vec<Injection>,
+        depth: u32,
+        spans: &mut Vec<Span>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+        for injection in injections {
+            let Some(content) = text.get(injection.start as usize..injection.end as usize) else {
+                continue;
+            };
+            if content.is_empty() {
+                continue;
+            }
+            let Ok(grammar) = self.highlighter_for(&injection.language) else {
+                continue;
+            };
+            let Ok(result) = grammar.try_parse(content) else {
+                continue;
+            };
+
+            let offset = base + injection.start;
+            spans.extend(result.spans.into_iter().map(|span| Span {
+                start: span.start + offset,
+                end: span.end + offset,
+                ..span
+            }));
+            self.inject(content, offset, result.injections, depth - 1, spans);
+        }
+    }
+
+    /// The host plugins are loaded with.
+    pub fn host(&self) -> &PluginHost {
+        &self.host
+    }
+
+    /// Load the plugin providing `language_id`, outside of the registry's
+    /// own instances.
+    pub(crate) fn load_plugin(&self, language_id: &str) -> Result<LoadedPlugin, HostError> {
+        let plugin = self
+            .entries
+            .get(language_id)
+            .and_then(|entry| entry.plugin.as_ref())
+            .ok_or_else(|| HostError::UnknownLanguage(language_id.to_string()))?;
+        self.host.load(&plugin.path)
+    }
+
+    fn instantiate(&self, language_id: &str) -> Result<GrammarHandle, HostError> {
+        let entry = &self.entries[language_id];
+        if let Some(plugin) = &entry.plugin {
+            let plugin = self.host.load(&plugin.path)?;
+            return Ok(GrammarHandle(Backend::Plugin {
+                plugin: Box::new(plugin),
+                session: None,
+            }));
+        }
+        let factory = entry.native.as_ref().expect("entries have a backing");
+        Ok(GrammarHandle(Backend::Native(factory())))
+    }
+
+    /// Mark a live plugin as most recently used, evicting the least recently
+    /// used ones over the limit.
+    fn touch(&mut self, language_id: &str) {
+        self.lru.retain(|id| id != language_id);
+        self.lru.push_back(language_id.to_string());
+        while self.lru.len() > self.max_live_plugins {
+            let oldest = self.lru.pop_front().expect("non-empty");
+            self.live.remove(&oldest);
+        }
+    }
+
+    /// Drop a live instance, e.g. because its backing changed.
+    fn evict(&mut self, language_id: &str) {
+        self.lru.retain(|id| id != language_id);
+        self.live.remove(language_id);
+    }
+}
+
+impl GrammarProvider for PluginRegistry {
+    type Grammar = GrammarHandle;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get(&mut self, language: &str) -> impl Future<Output = Option<&mut GrammarHandle>> + Send {
+        std::future::ready(self.highlighter_for(language).ok())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn get(&mut self, language: &str) -> impl Future<Output = Option<&mut GrammarHandle>> {
+        std::future::ready(self.highlighter_for(language).ok())
+    }
+}
+
+/// A grammar from a [`PluginRegistry`], backed by a plugin or a native
+/// grammar.
+pub struct GrammarHandle(Backend);
+
+enum Backend {
+    Native(Box<dyn Grammar + Send>),
+    Plugin {
+        plugin: Box<LoadedPlugin>,
+        /// Created on first parse, and again after a trap dropped it.
+        session: Option<u32>,
+    },
+}
+
+impl fmt::Debug for GrammarHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Backend::Native(_) => f.write_str("GrammarHandle::Native"),
+            Backend::Plugin { plugin, .. } => f
+                .debug_tuple("GrammarHandle::Plugin")
+                .field(plugin)
+                .finish(),
+        }
+    }
+}
+
+impl GrammarHandle {
+    /// Whether a plugin provides this grammar.
+    pub fn is_plugin(&self) -> bool {
+        matches!(self.0, Backend::Plugin { .. })
+    }
+
+    /// Parse `text`, reporting plugin failures.
+    ///
+    /// [`Grammar::parse`] returns an empty result instead, i.e. plain text,
+    /// e.g. when the plugin timed out.
+    pub fn try_parse(&mut self, text: &str) -> Result<ParseResult, HostError> {
+        let (plugin, session) = match &mut self.0 {
+            Backend::Native(grammar) => return Ok(grammar.parse(text)),
+            Backend::Plugin { plugin, session } => (plugin, session),
+        };
+
+        let id = match *session {
+            Some(id) => id,
+            None => *session.insert(plugin.create_session()?),
+        };
+        let result = plugin
+            .set_text(id, text)
+            .and_then(|()| plugin.parse(id))
+            .inspect_err(|e| {
+                if let HostError::Trap { .. } | HostError::LimitExceeded { .. } = e {
+                    *session = None;
+                }
+            })?;
+
+        Ok(ParseResult {
+            spans: result
+                .spans
+                .into_iter()
+                .map(|s| Span {
+                    start: s.start,
+                    end: s.end,
+                    capture: s.capture,
+                    pattern_index: s.pattern_index,
+                })
+                .collect(),
+            injections: result
+                .injections
+                .into_iter()
+                .map(|i| Injection {
+                    start: i.start,
+                    end: i.end,
+                    language: i.language,
+                    include_children: i.include_children,
+                    combined: i.combined,
+                })
+                .collect(),
+        })
+    }
+}
+
+impl Grammar for GrammarHandle {
+    fn parse(&mut self, text: &str) -> ParseResult {
+        self.try_parse(text).unwrap_or_default()
+    }
+}
+
+/// Compare versions like semver: numeric components first, then a
+/// pre-release (`-rc1`) sorts before the release. Build metadata is ignored.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn parts(v: &str) -> (Vec<u64>, Option<&str>) {
+        let v = v.split('+').next().unwrap_or_default();
+        let (core, pre) = match v.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (v, None),
+        };
+        let core = core.split('.').map(|n| n.parse().unwrap_or(0)).collect();
+        (core, pre)
+    }
+
+    let (a_core, a_pre) = parts(a);
+    let (b_core, b_pre) = parts(b);
+    a_core.cmp(&b_core).then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(b),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PluginLimits;
+    use crate::tests::{fake_plugin, fake_plugin_capturing};
+    use arborium_wire::{MANIFEST_SECTION, WIRE_VERSION};
+
+    /// A fake plugin binary with a manifest for `language_id`.
+    fn plugin_file(language_id: &str, aliases: &[&str], version: &str) -> Vec<u8> {
+        with_manifest(fake_plugin(WIRE_VERSION), language_id, aliases, version)
+    }
+
+    /// Compile a fake plugin and append a manifest to it.
+    fn with_manifest(wat: Vec<u8>, language_id: &str, aliases: &[&str], version: &str) -> Vec<u8> {
+        let mut wasm = wat::parse_bytes(&wat).unwrap().into_owned();
+        let manifest = PluginManifest {
+            wire_version: WIRE_VERSION,
+            language_id: language_id.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            extensions: aliases.iter().map(|a| a.to_string()).collect(),
+            provides_injections: false,
+            provides_locals: false,
+            query_hash: "0000000000000000".to_string(),
+            grammar_commit: "src-0000000000000000".to_string(),
+            version: version.to_string(),
+        };
+        let mut payload = vec![MANIFEST_SECTION.len() as u8];
+        payload.extend_from_slice(MANIFEST_SECTION.as_bytes());
+        payload.extend(postcard::to_allocvec(&manifest).unwrap());
+        wasm.push(0);
+        wasm.push(payload.len() as u8);
+        wasm.extend(payload);
+        wasm
+    }
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "arborium-registry-test-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, name: &str, bytes: &[u8]) {
+            std::fs::write(self.0.join(name), bytes).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    struct FixedGrammar;
+
+    impl Grammar for FixedGrammar {
+        fn parse(&mut self, _text: &str) -> ParseResult {
+            ParseResult {
+                spans: vec![Span {
+                    start: 0,
+                    end: 1,
+                    capture: "string".to_string(),
+                    pattern_index: 0,
+                }],
+                injections: vec![],
+            }
+        }
+    }
+
+    #[test]
+    fn test_scan_and_lookup() {
+        let dir = TempDir::new("lookup");
+        dir.write("zig.wasm", &plugin_file("zig", &["zon"], "1.0.0"));
+        dir.write("odin.wasm", &plugin_file("odin", &[], "1.0.0"));
+        dir.write("notes.txt", b"not a plugin");
+        dir.write("broken.wasm", b"not wasm either");
+
+        let mut registry = PluginRegistry::scan(&[dir.0.clone(), dir.0.join("missing")]);
+        registry.add_native("json", &["jsonc"], || Box::new(FixedGrammar));
+
+        assert_eq!(registry.languages(), ["json", "odin", "zig"]);
+        assert_eq!(registry.resolve("ZON"), Some("zig"));
+        assert_eq!(registry.resolve(".jsonc"), Some("json"));
+        assert_eq!(registry.resolve("cobol"), None);
+        assert_eq!(registry.manifest("zon").unwrap().version, "1.0.0");
+        assert!(registry.manifest("json").is_none());
+
+        assert_eq!(registry.warnings().len(), 1);
+        assert!(matches!(
+            &registry.warnings()[0],
+            RegistryWarning::Skipped { path, .. } if path.ends_with("broken.wasm")
+        ));
+
+        // Nothing is compiled until first use
+        assert_eq!(registry.live_plugins(), 0);
+        let zig = registry.highlighter_for("zig").unwrap();
+        assert!(zig.is_plugin());
+        assert_eq!(zig.try_parse("fn x").unwrap().spans[0].capture, "keyword");
+        assert_eq!(registry.live_plugins(), 1);
+
+        let json = registry.highlighter_for("jsonc").unwrap();
+        assert!(!json.is_plugin());
+        assert_eq!(json.parse("\"\"").spans[0].capture, "string");
+        assert_eq!(registry.live_plugins(), 1);
+
+        assert!(matches!(
+            registry.highlighter_for("cobol"),
+            Err(HostError::UnknownLanguage(name)) if name == "cobol"
+        ));
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let first = TempDir::new("conflict-a");
+        let second = TempDir::new("conflict-b");
+        first.write("zig.wasm", &plugin_file("zig", &[], "1.2.0"));
+        second.write("zig-new.wasm", &plugin_file("zig", &[], "1.10.0"));
+        second.write("zig-older.wasm", &plugin_file("zig", &[], "0.9.0"));
+        second.write("zig-rc.wasm", &plugin_file("zig", &[], "1.10.0-rc1"));
+        second.write("zig-same.wasm", &plugin_file("zig", &[], "1.10.0"));
+
+        let registry = PluginRegistry::scan(&[first.0.clone(), second.0.clone()]);
+        // 1.10.0 beats 1.2.0, older versions lose quietly, the tie warns
+        assert_eq!(registry.manifest("zig").unwrap().version, "1.10.0");
+        assert_eq!(registry.warnings().len(), 1);
+        assert!(matches!(
+            &registry.warnings()[0],
+            RegistryWarning::Conflict { language_id, kept, ignored }
+                if language_id == "zig"
+                    && kept.ends_with("zig-new.wasm")
+                    && ignored.ends_with("zig-same.wasm")
+        ));
+    }
+
+    #[test]
+    fn test_plugins_shadow_native_grammars() {
+        let dir = TempDir::new("shadow");
+        dir.write("json.wasm", &plugin_file("json", &[], "1.0.0"));
+
+        let mut registry = PluginRegistry::scan(&[dir.0.clone()]);
+        registry.add_native("json", &[], || Box::new(FixedGrammar));
+        assert!(registry.highlighter_for("json").unwrap().is_plugin());
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let dir = TempDir::new("lru");
+        for id in ["a", "b", "c"] {
+            dir.write(&format!("{id}.wasm"), &plugin_file(id, &[], "1.0.0"));
+        }
+
+        let mut registry = PluginRegistry::scan(&[dir.0.clone()]).with_max_live_plugins(2);
+        registry.highlighter_for("a").unwrap();
+        registry.highlighter_for("b").unwrap();
+        registry.highlighter_for("a").unwrap();
+        registry.highlighter_for("c").unwrap();
+        assert_eq!(registry.live_plugins(), 2);
+        assert!(registry.live.contains_key("a"));
+        assert!(!registry.live.contains_key("b"));
+
+        // Evicted plugins come back on demand
+        let b = registry.highlighter_for("b").unwrap();
+        assert_eq!(b.try_parse("fn x").unwrap().spans.len(), 1);
+    }
+
+    #[test]
+    fn test_trap_recreates_session() {
+        let dir = TempDir::new("trap");
+        dir.write("zig.wasm", &plugin_file("zig", &[], "1.0.0"));
+
+        let mut registry = PluginRegistry::scan(&[dir.0.clone()]);
+        let zig = registry.highlighter_for("zig").unwrap();
+        assert!(matches!(zig.try_parse("trap"), Err(HostError::Trap { .. })));
+        assert!(zig.parse("trap").spans.is_empty());
+        assert_eq!(zig.try_parse("fn x").unwrap().spans.len(), 1);
+    }
+
+    #[test]
+    fn test_timeout_falls_back_to_plain_text() {
+        let dir = TempDir::new("timeout");
+        dir.write("zig.wasm", &plugin_file("zig", &[], "1.0.0"));
+
+        let host = PluginHost::with_limits(PluginLimits {
+            deadline: Some(std::time::Duration::from_millis(50)),
+            ..PluginLimits::default()
+        });
+        let mut registry = PluginRegistry::new(host);
+        registry.scan_dir(&dir.0);
+
+        let zig = registry.highlighter_for("zig").unwrap();
+        assert!(zig.parse("loop").spans.is_empty());
+        assert_eq!(zig.parse("fn x").spans.len(), 1);
+    }
+
+    /// A native grammar highlighting its first byte and injecting fixed
+    /// ranges, clamped to the text.
+    struct InjectingGrammar(Vec<(u32, u32, &'static str)>);
+
+    impl Grammar for InjectingGrammar {
+        fn parse(&mut self, text: &str) -> ParseResult {
+            let len = text.len() as u32;
+            ParseResult {
+                spans: vec![Span {
+                    start: 0,
+                    end: 1,
+                    capture: "markup".to_string(),
+                    pattern_index: 0,
+                }],
+                injections: self
+                    .0
+                    .iter()
+                    .filter(|&&(_, end, _)| end <= len)
+                    .map(|&(start, end, language)| Injection {
+                        start,
+                        end,
+                        language: language.to_string(),
+                        include_children: false,
+                        combined: false,
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    fn ranges(spans: &[Span]) -> Vec<(u32, u32, &str)> {
+        let mut ranges: Vec<_> = spans
+            .iter()
+            .map(|s| (s.start, s.end, s.capture.as_str()))
+            .collect();
+        ranges.sort_unstable();
+        ranges
+    }
+
+    #[test]
+    fn test_highlight_document_across_backends() {
+        let dir = TempDir::new("document");
+        dir.write("zig.wasm", &plugin_file("zig", &["zon"], "1.0.0"));
+
+        let mut registry = PluginRegistry::scan(&[dir.0.clone()]);
+        registry.add_native("doc", &[], || {
+            Box::new(InjectingGrammar(vec![
+                (4, 8, "ZON"),
+                (10, 20, "json"),
+                (20, 24, "cobol"),
+            ]))
+        });
+        registry.add_native("json", &[], || {
+            Box::new(InjectingGrammar(vec![(2, 6, "zig")]))
+        });
+
+        let text = "doc zig!  json zig! cobol";
+        let spans = registry.highlight_document(text, "doc", 8).unwrap();
+        assert_eq!(
+            ranges(&spans),
+            [
+                (0, 1, "markup"),
+                (4, 6, "keyword"),
+                (10, 11, "markup"),
+                (12, 14, "keyword"),
+            ]
+        );
+
+        assert!(matches!(
+            registry.highlight_document(text, "cobol", 8),
+            Err(HostError::UnknownLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_highlight_document_depth() {
+        let mut registry = PluginRegistry::default();
+        // Injects itself over its whole text, forever
+        registry.add_native("doc", &[], || {
+            Box::new(InjectingGrammar(vec![(0, 4, "doc")]))
+        });
+
+        for depth in [0, 1, 3] {
+            let spans = registry.highlight_document("text", "doc", depth).unwrap();
+            assert_eq!(spans.len(), depth as usize + 1);
+        }
+    }
+
+    #[test]
+    fn test_highlight_document_skips_failing_injections() {
+        let dir = TempDir::new("document-trap");
+        dir.write("zig.wasm", &plugin_file("zig", &[], "1.0.0"));
+
+        let mut registry = PluginRegistry::scan(&[dir.0.clone()]);
+        registry.add_native("doc", &[], || {
+            Box::new(InjectingGrammar(vec![(4, 8, "zig")]))
+        });
+
+        let spans = registry.highlight_document("doc trap", "doc", 8).unwrap();
+        assert_eq!(ranges(&spans), [(0, 1, "markup")]);
+    }
+
+    #[test]
+    fn test_reload_keeps_session() {
+        let dir = TempDir::new("reload");
+        dir.write("zig.wasm", &plugin_file("zig", &[], "1.0.0"));
+
+        let mut registry = PluginRegistry::scan(&[dir.0.clone()]);
+        let zig = registry.highlighter_for("zig").unwrap();
+        assert_eq!(zig.try_parse("fn x").unwrap().spans[0].capture, "keyword");
+
+        dir.write(
+            "zig.wasm",
+            &with_manifest(
+                fake_plugin_capturing(WIRE_VERSION, "function"),
+                "zig",
+                &[],
+                "1.0.1",
+            ),
+        );
+        registry.reload("zig").unwrap();
+        assert_eq!(registry.manifest("zig").unwrap().version, "1.0.1");
+
+        // Same session, new queries, without setting the text again
+        let Some(GrammarHandle(Backend::Plugin {
+            plugin,
+            session: Some(session),
+        })) = registry.live.get_mut("zig")
+        else {
+            panic!("zig should be live with a session");
+        };
+        assert_eq!(plugin.parse(*session).unwrap().spans[0].capture, "function");
+
+        // A broken build leaves the working one in place
+        dir.write("zig.wasm", b"not wasm");
+        assert!(registry.reload("zig").is_err());
+        let zig = registry.highlighter_for("zig").unwrap();
+        assert_eq!(zig.try_parse("fn x").unwrap().spans[0].capture, "function");
+
+        registry.add_native("json", &[], || Box::new(FixedGrammar));
+        assert!(matches!(
+            registry.reload("json"),
+            Err(HostError::UnknownLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.10.0", "1.9.3"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0.0", "2.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("0.3", "0.3.1"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-rc1", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0+abc", "1.0.0"), Ordering::Equal);
+    }
+}
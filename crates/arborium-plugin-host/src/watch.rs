@@ -0,0 +1,72 @@
+//! Watching plugin directories for rebuilt plugins.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, channel};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::HostError;
+
+/// Watches plugin directories for changed `.wasm` files.
+///
+/// Pass it to [`PluginRegistry::reload_changed`] to pick up the changes,
+/// e.g. once per frame or before each highlight.
+///
+/// [`PluginRegistry::reload_changed`]: crate::PluginRegistry::reload_changed
+pub struct PluginWatcher {
+    // Stops watching when dropped
+    _watcher: RecommendedWatcher,
+    changes: Receiver<PathBuf>,
+}
+
+impl std::fmt::Debug for PluginWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginWatcher").finish_non_exhaustive()
+    }
+}
+
+impl PluginWatcher {
+    /// Start watching `dirs`. Directories that don't exist are ignored.
+    pub fn new(dirs: &[PathBuf]) -> Result<Self, HostError> {
+        let (sender, changes) = channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+                if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    return;
+                }
+                for path in event.paths {
+                    if path.extension().is_some_and(|ext| ext == "wasm") {
+                        // The receiver only goes away with the watcher
+                        let _ = sender.send(path);
+                    }
+                }
+            })
+            .map_err(io::Error::other)?;
+
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(io::Error::other)?;
+        }
+        Ok(Self {
+            _watcher: watcher,
+            changes,
+        })
+    }
+
+    /// The canonical paths changed since the last call.
+    ///
+    /// A rebuild usually writes a file in several steps; they collapse into
+    /// one entry here.
+    pub(crate) fn changed_paths(&self) -> HashSet<PathBuf> {
+        self.changes
+            .try_iter()
+            .map(|path| std::fs::canonicalize(&path).unwrap_or(path))
+            .collect()
+    }
+}
@@ -0,0 +1,108 @@
+//! Building grammar plugins for integration tests.
+
+// Each test crate uses a different part of this
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Build the plugin for `lang` in `langs/<group>`, returning the path of the
+/// `.wasm` file.
+///
+/// Needs the plugin crate generated by `cargo xtask gen` and the
+/// `wasm32-unknown-unknown` target; returns `None` with a message otherwise.
+/// All plugins share a target directory, so they end up next to each other.
+pub fn build_plugin(group: &str, lang: &str) -> Option<PathBuf> {
+    build_plugin_with_features(group, lang, &[])
+}
+
+/// [`build_plugin`], enabling `features` of the plugin crate.
+///
+/// Each set of features gets its own target directory, so builds with
+/// different features don't overwrite each other.
+pub fn build_plugin_with_features(group: &str, lang: &str, features: &[&str]) -> Option<PathBuf> {
+    build(group, lang, features, false)
+}
+
+/// [`build_plugin_with_features`], built like `cargo xtask build` does so
+/// that panics unwind instead of trapping.
+///
+/// Needs a nightly toolchain with `rust-src` besides what [`build_plugin`]
+/// needs; returns `None` with a message otherwise.
+pub fn build_unwinding_plugin(group: &str, lang: &str, features: &[&str]) -> Option<PathBuf> {
+    let rust_src = Command::new("rustup")
+        .args(["component", "list", "--installed", "--toolchain", "nightly"])
+        .output()
+        .map(|out| {
+            out.status.success() && String::from_utf8_lossy(&out.stdout).contains("rust-src")
+        })
+        .unwrap_or(false);
+    if !rust_src {
+        eprintln!("skipping: nightly toolchain with rust-src not installed");
+        return None;
+    }
+    build(group, lang, features, true)
+}
+
+fn build(group: &str, lang: &str, features: &[&str], unwind: bool) -> Option<PathBuf> {
+    let plugin_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../langs")
+        .join(group)
+        .join(lang)
+        .join("npm");
+    if !plugin_dir.join("Cargo.toml").exists() {
+        eprintln!("skipping: {lang} plugin crate not generated, run `cargo xtask gen` first");
+        return None;
+    }
+
+    let installed = Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).contains("wasm32-unknown-unknown"))
+        .unwrap_or(false);
+    if !installed {
+        eprintln!("skipping: wasm32-unknown-unknown target not installed");
+        return None;
+    }
+
+    let mut target_dir = String::from(if unwind { "plugins-unwind" } else { "plugins" });
+    for feature in features {
+        target_dir.push('-');
+        target_dir.push_str(feature);
+    }
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(target_dir);
+    let mut cargo = if unwind {
+        // Same flags as `cargo xtask build`, minus the size optimizations
+        let mut cargo = Command::new("rustup");
+        cargo
+            .args(["run", "nightly", "cargo", "build", "-Zbuild-std=std,panic_unwind"])
+            .env(
+                "RUSTFLAGS",
+                "-Cpanic=unwind -Ctarget-feature=+exception-handling -Cllvm-args=-wasm-use-legacy-eh=false",
+            );
+        cargo
+    } else {
+        let mut cargo = Command::new(env!("CARGO"));
+        cargo.arg("build");
+        cargo
+    };
+    let status = cargo
+        .args(["--lib", "--release", "--target", "wasm32-unknown-unknown"])
+        .args(["--features", &features.join(",")])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        // Same as `cargo xtask build`: host CFLAGS don't apply to wasm32
+        .env("CFLAGS", "")
+        .env("CXXFLAGS", "")
+        .env("CFLAGS_wasm32_unknown_unknown", "")
+        .env("CXXFLAGS_wasm32_unknown_unknown", "")
+        .current_dir(&plugin_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "building the {lang} plugin failed");
+
+    Some(target_dir.join(format!(
+        "wasm32-unknown-unknown/release/arborium_{}_plugin.wasm",
+        lang.replace('-', "_")
+    )))
+}
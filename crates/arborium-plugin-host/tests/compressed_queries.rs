@@ -0,0 +1,87 @@
+//! Compares the Rust grammar plugin built with and without the
+//! `compressed-queries` feature. Run with `--nocapture` to see the numbers.
+//!
+//! Needs the plugin crate generated by `cargo xtask gen` and the
+//! `wasm32-unknown-unknown` target; skips with a message otherwise.
+
+mod common;
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use arborium_plugin_host::{PluginHost, QueryKind};
+use arborium_wire::Utf8ParseResult;
+
+use common::{build_plugin, build_plugin_with_features};
+
+const SOURCE: &str = "pub fn main() -> u32 {\n    let x = \"hi\";\n    42\n}\n";
+
+struct Startup {
+    /// Until the first session has its text
+    ready: Duration,
+    /// Until the first parse is done, which compiles the queries
+    parsed: Duration,
+    result: Utf8ParseResult,
+}
+
+fn startup(wasm: &Path) -> Startup {
+    let host = PluginHost::new();
+    let mut plugin = host.load(wasm).unwrap();
+    let start = Instant::now();
+    let session = plugin.create_session().unwrap();
+    plugin.set_text(session, SOURCE).unwrap();
+    let ready = start.elapsed();
+    let result = plugin.parse(session).unwrap();
+    Startup {
+        ready,
+        parsed: start.elapsed(),
+        result,
+    }
+}
+
+#[test]
+fn test_compressed_queries() {
+    let Some(plain) = build_plugin("group-birch", "rust") else {
+        return;
+    };
+    let Some(compressed) =
+        build_plugin_with_features("group-birch", "rust", &["compressed-queries"])
+    else {
+        return;
+    };
+
+    let plain_size = std::fs::metadata(&plain).unwrap().len();
+    let compressed_size = std::fs::metadata(&compressed).unwrap().len();
+    eprintln!(
+        "plain: {plain_size} bytes, compressed queries: {compressed_size} bytes ({:+} bytes)",
+        compressed_size as i64 - plain_size as i64
+    );
+
+    let plain_startup = startup(&plain);
+    let compressed_startup = startup(&compressed);
+    for (name, s) in [
+        ("plain", &plain_startup),
+        ("compressed", &compressed_startup),
+    ] {
+        eprintln!(
+            "{name}: text set after {:?}, first parse after {:?}",
+            s.ready, s.parsed
+        );
+    }
+
+    // Same plugin otherwise
+    assert_eq!(plain_startup.result, compressed_startup.result);
+    let host = PluginHost::new();
+    let plain = host.load(&plain).unwrap();
+    let compressed = host.load(&compressed).unwrap();
+    for kind in QueryKind::ALL {
+        assert_eq!(
+            plain.query_source(kind).unwrap(),
+            compressed.query_source(kind).unwrap()
+        );
+    }
+    assert!(
+        compressed_size < plain_size,
+        "compressing queries didn't shrink the plugin"
+    );
+}
@@ -0,0 +1,158 @@
+//! Runs the same checks against a native grammar and a plugin, so both
+//! sides of `SyntaxProvider` behave the same.
+//!
+//! The plugin half needs the plugin crate generated by `cargo xtask gen`
+//! and the `wasm32-unknown-unknown` target; it skips with a message
+//! otherwise.
+
+#![cfg(feature = "native")]
+
+mod common;
+
+use std::sync::Arc;
+
+use arborium_highlight::tree_sitter::{CompiledGrammar, GrammarConfig};
+use arborium_plugin_host::{
+    HostError, NativeProvider, PluginRegistry, ProviderRegistry, SyntaxProvider,
+};
+use arborium_wire::Edit;
+
+use common::build_plugin;
+
+const SOURCE: &str = "/// Docs\nfn main() -> u32 {\n    let x = \"hi\";\n    42\n}\n";
+
+fn rust_grammar() -> Arc<CompiledGrammar> {
+    let config = GrammarConfig {
+        language: arborium_rust::language().into(),
+        highlights_query: &arborium_rust::HIGHLIGHTS_QUERY,
+        injections_query: arborium_rust::INJECTIONS_QUERY,
+        locals_query: arborium_rust::LOCALS_QUERY,
+    };
+    Arc::new(CompiledGrammar::new(config).expect("rust grammar compiles"))
+}
+
+fn native_provider() -> Box<dyn SyntaxProvider> {
+    Box::new(NativeProvider::new(rust_grammar()).unwrap())
+}
+
+/// Drive `provider` through a session's life, returning the spans of
+/// [`SOURCE`] as `(start, end, capture)`.
+fn check_conformance(provider: &mut dyn SyntaxProvider) -> Vec<(u32, u32, String)> {
+    assert!(matches!(
+        provider.parse(999),
+        Err(HostError::UnknownSession(999))
+    ));
+    assert!(matches!(
+        provider.set_text(999, "fn x"),
+        Err(HostError::UnknownSession(999))
+    ));
+
+    let session = provider.create_session().unwrap();
+    assert!(matches!(
+        provider.parse(session),
+        Err(HostError::Guest { session: s, .. }) if s == session
+    ));
+
+    provider.set_text(session, SOURCE).unwrap();
+    let spans = provider.parse(session).unwrap().spans;
+    assert!(!spans.is_empty());
+    assert!(
+        spans
+            .iter()
+            .all(|s| s.start <= s.end && s.end as usize <= SOURCE.len())
+    );
+    assert!(
+        spans
+            .windows(2)
+            .all(|w| (w[0].start, w[0].end) <= (w[1].start, w[1].end)),
+        "spans not sorted: {spans:?}"
+    );
+    assert!(
+        spans
+            .iter()
+            .any(|s| &SOURCE[s.start as usize..s.end as usize] == "fn"),
+        "no span for `fn`: {spans:?}"
+    );
+
+    // Insert "pub " before `fn`
+    let offset = SOURCE.find("fn").unwrap();
+    let edited = format!("{}pub {}", &SOURCE[..offset], &SOURCE[offset..]);
+    let edit = Edit {
+        start_byte: offset as u32,
+        old_end_byte: offset as u32,
+        new_end_byte: offset as u32 + 4,
+        start_row: 1,
+        start_col: 0,
+        old_end_row: 1,
+        old_end_col: 0,
+        new_end_row: 1,
+        new_end_col: 4,
+    };
+    provider.apply_edit(session, &edited, &edit).unwrap();
+    let after_edit = provider.parse(session).unwrap().spans;
+    assert!(
+        after_edit
+            .iter()
+            .any(|s| &edited[s.start as usize..s.end as usize] == "pub"),
+        "no span for `pub` after edit: {after_edit:?}"
+    );
+
+    // Cancelled sessions parse to nothing until the text changes
+    provider.cancel(session).unwrap();
+    assert!(provider.parse(session).unwrap().spans.is_empty());
+    provider.set_text(session, SOURCE).unwrap();
+    assert_eq!(provider.parse(session).unwrap().spans.len(), spans.len());
+
+    provider.free_session(session).unwrap();
+    assert!(matches!(
+        provider.parse(session),
+        Err(HostError::UnknownSession(_))
+    ));
+
+    spans
+        .into_iter()
+        .map(|s| (s.start, s.end, s.capture))
+        .collect()
+}
+
+#[test]
+fn test_native_conformance() {
+    let mut provider = native_provider();
+    assert!(!provider.is_plugin());
+    check_conformance(provider.as_mut());
+}
+
+#[test]
+fn test_plugin_conformance() {
+    let Some(wasm) = build_plugin("group-birch", "rust") else {
+        return;
+    };
+
+    let mut plugin = PluginRegistry::default().host().load(&wasm).unwrap();
+    let from_plugin = check_conformance(&mut plugin);
+    let from_native = check_conformance(native_provider().as_mut());
+    assert_eq!(from_plugin, from_native);
+}
+
+#[test]
+fn test_prefers_native_over_plugins() {
+    let Some(wasm) = build_plugin("group-birch", "rust") else {
+        return;
+    };
+
+    let dir = wasm.parent().unwrap().to_path_buf();
+    let mut registry = ProviderRegistry::scan(&[dir]);
+    assert_eq!(registry.is_native("rs"), Some(false));
+    assert!(registry.provider_for("rs").unwrap().is_plugin());
+
+    registry.add_native("rust", &["rs"], || Ok(native_provider()));
+    assert_eq!(registry.is_native("rs"), Some(true));
+    let provider = registry.provider_for("rs").unwrap();
+    assert!(!provider.is_plugin());
+    check_conformance(provider);
+
+    assert!(matches!(
+        registry.provider_for("cobol"),
+        Err(HostError::UnknownLanguage(_))
+    ));
+}
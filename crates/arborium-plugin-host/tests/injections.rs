@@ -0,0 +1,47 @@
+//! Highlights a Markdown document with Rust and HTML injected into it, each
+//! language served by its own plugin.
+//!
+//! Needs the plugin crates generated by `cargo xtask gen` and the
+//! `wasm32-unknown-unknown` target; skips with a message otherwise.
+
+mod common;
+
+use arborium_plugin_host::PluginRegistry;
+
+use common::build_plugin;
+
+#[test]
+fn test_markdown_with_rust_and_html() {
+    let mut plugins = Vec::new();
+    for (group, lang) in [
+        ("group-willow", "markdown"),
+        ("group-birch", "rust"),
+        ("group-acorn", "html"),
+    ] {
+        let Some(wasm) = build_plugin(group, lang) else {
+            return;
+        };
+        plugins.push(wasm);
+    }
+
+    let dir = plugins[0].parent().unwrap().to_path_buf();
+    let mut registry = PluginRegistry::scan(&[dir]);
+    for lang in ["markdown", "rust", "html"] {
+        assert!(registry.manifest(lang).is_some(), "{lang} plugin not found");
+    }
+
+    let document = "# Title\n\n```rust\nfn main() {}\n```\n\n<div>hi</div>\n";
+    let spans = registry.highlight_document(document, "md", 4).unwrap();
+
+    let covers = |text: &str, capture: &str| {
+        let start = document.find(text).unwrap() as u32;
+        let end = start + text.len() as u32;
+        spans
+            .iter()
+            .any(|s| s.start == start && s.end == end && s.capture.starts_with(capture))
+    };
+    // One span from each grammar, in document coordinates
+    assert!(covers("Title", "text.title"), "markdown: {spans:?}");
+    assert!(covers("fn", "keyword"), "rust: {spans:?}");
+    assert!(covers("div", "tag"), "html: {spans:?}");
+}
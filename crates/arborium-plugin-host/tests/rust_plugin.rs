@@ -0,0 +1,150 @@
+//! Builds the Rust grammar plugin and drives it through the native host.
+//!
+//! Needs the plugin crate generated by `cargo xtask gen` and the
+//! `wasm32-unknown-unknown` target; skips with a message otherwise.
+
+mod common;
+
+use arborium_plugin_host::{HostError, PluginHost, QueryKind, read_manifest};
+use arborium_wire::{Edit, ParseErrorKind, WIRE_VERSION};
+
+use common::{build_plugin, build_unwinding_plugin};
+
+#[test]
+fn test_rust_plugin_round_trip() {
+    let Some(wasm) = build_plugin("group-birch", "rust") else {
+        return;
+    };
+
+    let host = PluginHost::new();
+    let mut plugin = host.load(&wasm).unwrap();
+    let session = plugin.create_session().unwrap();
+
+    let source = "fn main() {}";
+    plugin.set_text(session, source).unwrap();
+    let result = plugin.parse(session).unwrap();
+    assert!(
+        result
+            .spans
+            .iter()
+            .any(|s| s.capture == "keyword" && &source[s.start as usize..s.end as usize] == "fn"),
+        "no keyword span for `fn`: {:?}",
+        result.spans
+    );
+
+    // Insert "pub " at the start
+    let edited = "pub fn main() {}";
+    let edit = Edit {
+        start_byte: 0,
+        old_end_byte: 0,
+        new_end_byte: 4,
+        start_row: 0,
+        start_col: 0,
+        old_end_row: 0,
+        old_end_col: 0,
+        new_end_row: 0,
+        new_end_col: 4,
+    };
+    plugin.apply_edit(session, edited, &edit).unwrap();
+    let result = plugin.parse(session).unwrap();
+    assert!(
+        result
+            .spans
+            .iter()
+            .any(|s| &edited[s.start as usize..s.end as usize] == "pub"),
+        "no span for `pub` after edit: {:?}",
+        result.spans
+    );
+
+    plugin.free_session(session).unwrap();
+    assert!(matches!(
+        plugin.parse(session),
+        Err(HostError::UnknownSession(_))
+    ));
+}
+
+#[test]
+fn test_rust_plugin_panic_poisons_only_its_session() {
+    let Some(wasm) = build_unwinding_plugin("group-birch", "rust", &["test-hooks"]) else {
+        return;
+    };
+
+    let host = PluginHost::new();
+    let mut plugin = host.load(&wasm).unwrap();
+    let broken = plugin.create_session().unwrap();
+    let healthy = plugin.create_session().unwrap();
+    plugin.set_text(broken, "fn main() {}").unwrap();
+    plugin.set_text(healthy, "fn main() {}").unwrap();
+
+    // The panic is caught in the guest and reported, not trapped on
+    match plugin.test_panic(broken) {
+        Err(HostError::Guest { session, error }) => {
+            assert_eq!(session, broken);
+            assert_eq!(error.kind, ParseErrorKind::Internal);
+            assert!(error.message.contains("test panic"), "{}", error.message);
+        }
+        other => panic!("expected an internal error, got {other:?}"),
+    }
+
+    // The session survives, but stays poisoned
+    match plugin.parse(broken) {
+        Err(HostError::Guest { error, .. }) => assert_eq!(error.kind, ParseErrorKind::Poisoned),
+        other => panic!("expected a poisoned session, got {other:?}"),
+    }
+
+    // Others keep working
+    let result = plugin.parse(healthy).unwrap();
+    assert!(result.spans.iter().any(|s| s.capture == "keyword"));
+    plugin.free_session(broken).unwrap();
+    plugin.free_session(healthy).unwrap();
+}
+
+#[test]
+fn test_rust_plugin_manifest() {
+    let Some(wasm) = build_plugin("group-birch", "rust") else {
+        return;
+    };
+
+    let manifest = read_manifest(&std::fs::read(&wasm).unwrap()).unwrap();
+    assert_eq!(manifest.wire_version, WIRE_VERSION);
+    assert_eq!(manifest.language_id, "rust");
+    assert_eq!(manifest.aliases, ["rs"]);
+    assert_eq!(manifest.extensions, ["rust", "rs"]);
+    assert_eq!(manifest.query_hash.len(), 16);
+}
+
+#[test]
+fn test_rust_plugin_queries() {
+    let Some(wasm) = build_plugin("group-birch", "rust") else {
+        return;
+    };
+
+    let mut plugin = PluginHost::new().load(&wasm).unwrap();
+    let names = plugin.capture_names().unwrap();
+    let session = plugin.create_session().unwrap();
+    plugin
+        .set_text(
+            session,
+            "/// Docs\npub fn main() -> u32 {\n    let x = \"hi\";\n    42\n}\n",
+        )
+        .unwrap();
+    let spans = plugin.parse(session).unwrap().spans;
+    assert!(!spans.is_empty());
+    for span in &spans {
+        assert!(
+            names.contains(&span.capture),
+            "`{}` not in {:?}",
+            span.capture,
+            names
+        );
+    }
+
+    let manifest = read_manifest(&std::fs::read(&wasm).unwrap()).unwrap();
+    assert_eq!(plugin.query_hash().unwrap(), manifest.query_hash);
+    assert!(
+        plugin
+            .query_source(QueryKind::Highlights)
+            .unwrap()
+            .contains("@keyword")
+    );
+}
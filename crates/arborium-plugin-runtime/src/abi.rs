@@ -0,0 +1,308 @@
+//! Raw ABI exports for native hosts.
+//!
+//! Plugins call [`export_abi!`](crate::export_abi) to export the functions
+//! described in [`arborium_wire::abi`] next to their wasm-bindgen exports.
+//! The functions here implement them; they are only meaningful on wasm32,
+//! where guest pointers are 32 bits wide.
+//!
+//! Session calls run through [`PluginRuntime::guarded`], so a panic comes
+//! back as an error and poisons only the session it happened in.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub use arborium_wire::WIRE_VERSION;
+use arborium_wire::abi::{EditRequest, pack};
+use arborium_wire::{ParseError, QueryKind};
+use serde::Serialize;
+
+use crate::PluginRuntime;
+
+/// Allocate a zeroed buffer of `len` bytes for the host to write into.
+pub fn alloc(len: u32) -> u32 {
+    let buf = vec![0u8; len as usize].into_boxed_slice();
+    Box::into_raw(buf) as *mut u8 as usize as u32
+}
+
+/// Free a buffer returned by [`alloc`] or by one of the exports.
+///
+/// # Safety
+///
+/// `ptr` and `len` must describe a buffer allocated by this module that
+/// hasn't been freed yet.
+pub unsafe fn dealloc(ptr: u32, len: u32) {
+    let slice = core::ptr::slice_from_raw_parts_mut(ptr as usize as *mut u8, len as usize);
+    drop(unsafe { Box::from_raw(slice) });
+}
+
+/// Borrow a buffer written by the host.
+///
+/// # Safety
+///
+/// `ptr` and `len` must describe a live buffer from [`alloc`].
+unsafe fn input<'a>(ptr: u32, len: u32) -> &'a [u8] {
+    unsafe { core::slice::from_raw_parts(ptr as usize as *const u8, len as usize) }
+}
+
+/// Encode `value` into a fresh buffer and return it packed for the host.
+pub fn output<T: Serialize>(value: &T) -> u64 {
+    // Every wire type is encodable; an empty buffer decodes as an error on
+    // the host side if that ever changes
+    let bytes = postcard::to_allocvec(value).unwrap_or_default();
+    let len = bytes.len() as u32;
+    let ptr = Box::into_raw(bytes.into_boxed_slice()) as *mut u8 as usize as u32;
+    pack(ptr, len)
+}
+
+/// Implementation of [`arborium_wire::abi::SET_TEXT`].
+///
+/// # Safety
+///
+/// `ptr` and `len` must describe a live buffer from [`alloc`].
+pub unsafe fn set_text(runtime: &mut PluginRuntime, session: u32, ptr: u32, len: u32) -> u64 {
+    let result = match core::str::from_utf8(unsafe { input(ptr, len) }) {
+        Ok(text) => runtime.guarded(session, |runtime| {
+            runtime.set_text(session, text);
+            Ok(())
+        }),
+        Err(e) => Err(ParseError::new(alloc::format!("text is not UTF-8: {e}"))),
+    };
+    output(&result)
+}
+
+/// Implementation of [`arborium_wire::abi::LOAD_TEXT`].
+///
+/// # Safety
+///
+/// `ptr` and `len` must describe a live buffer from [`alloc`].
+pub unsafe fn load_text(runtime: &mut PluginRuntime, session: u32, ptr: u32, len: u32) -> u64 {
+    let result = match core::str::from_utf8(unsafe { input(ptr, len) }) {
+        Ok(text) => runtime.guarded(session, |runtime| {
+            runtime.load_text(session, text);
+            Ok(())
+        }),
+        Err(e) => Err(ParseError::new(alloc::format!("text is not UTF-8: {e}"))),
+    };
+    output(&result)
+}
+
+/// Implementation of [`arborium_wire::abi::APPLY_EDIT`].
+///
+/// # Safety
+///
+/// `ptr` and `len` must describe a live buffer from [`alloc`].
+pub unsafe fn apply_edit(runtime: &mut PluginRuntime, session: u32, ptr: u32, len: u32) -> u64 {
+    let result = match postcard::from_bytes::<EditRequest>(unsafe { input(ptr, len) }) {
+        Ok(request) => runtime.guarded(session, |runtime| {
+            runtime.apply_edit(session, &request.new_text, &request.edit);
+            Ok(())
+        }),
+        Err(e) => Err(ParseError::new(alloc::format!("malformed edit: {e}"))),
+    };
+    output(&result)
+}
+
+/// Implementation of [`arborium_wire::abi::PARSE`].
+pub fn parse(runtime: &mut PluginRuntime, session: u32) -> u64 {
+    output(&runtime.guarded(session, |runtime| runtime.parse(session)))
+}
+
+/// Implementation of [`arborium_wire::abi::PARSE_STEP`].
+pub fn parse_step(runtime: &mut PluginRuntime, session: u32, max_bytes: u32) -> u64 {
+    output(&runtime.guarded(session, |runtime| runtime.parse_step(session, max_bytes)))
+}
+
+/// Implementation of [`arborium_wire::abi::TEST_PANIC`].
+#[cfg(feature = "test-hooks")]
+pub fn test_panic(runtime: &mut PluginRuntime, session: u32) -> u64 {
+    output(&runtime.guarded::<()>(session, |runtime| {
+        runtime.parse(session)?;
+        panic!("test panic in session {session}");
+    }))
+}
+
+/// Implementation of [`arborium_wire::abi::CAPTURE_NAMES`].
+pub fn capture_names(runtime: &PluginRuntime) -> u64 {
+    let config = runtime.config();
+    let result = config.precompile().map(|()| {
+        config
+            .capture_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>()
+    });
+    output(&result)
+}
+
+/// Implementation of [`arborium_wire::abi::QUERY_SOURCE`].
+pub fn query_source(runtime: &PluginRuntime, kind: u32) -> u64 {
+    let result = match QueryKind::from_u32(kind) {
+        Some(kind) => runtime
+            .config()
+            .query_source(kind)
+            .map(|source| source.to_string()),
+        None => Err(ParseError::new(alloc::format!("unknown query kind {kind}"))),
+    };
+    output(&result)
+}
+
+/// Implementation of [`arborium_wire::abi::QUERY_HASH`].
+pub fn query_hash(runtime: &PluginRuntime) -> u64 {
+    output(&Ok::<_, ParseError>(
+        runtime.config().query_hash().to_string(),
+    ))
+}
+
+/// Export the raw ABI from a plugin crate.
+///
+/// Takes an expression evaluating to the plugin's
+/// `&RefCell<Option<PluginRuntime>>`, initialized on access, as generated
+/// plugins keep it.
+///
+/// ```ignore
+/// arborium_plugin_runtime::export_abi!(get_or_init_runtime());
+/// ```
+#[macro_export]
+macro_rules! export_abi {
+    ($runtime:expr) => {
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_wire_version() -> u32 {
+            $crate::abi::WIRE_VERSION
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_alloc(len: u32) -> u32 {
+            $crate::abi::alloc(len)
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn arborium_dealloc(ptr: u32, len: u32) {
+            unsafe { $crate::abi::dealloc(ptr, len) }
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_create_session() -> u32 {
+            $runtime
+                .borrow_mut()
+                .as_mut()
+                .expect("runtime not initialized")
+                .create_session()
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_free_session(session: u32) {
+            $runtime
+                .borrow_mut()
+                .as_mut()
+                .expect("runtime not initialized")
+                .free_session(session)
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn arborium_set_text(session: u32, ptr: u32, len: u32) -> u64 {
+            let mut runtime = $runtime.borrow_mut();
+            let runtime = runtime.as_mut().expect("runtime not initialized");
+            unsafe { $crate::abi::set_text(runtime, session, ptr, len) }
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn arborium_apply_edit(session: u32, ptr: u32, len: u32) -> u64 {
+            let mut runtime = $runtime.borrow_mut();
+            let runtime = runtime.as_mut().expect("runtime not initialized");
+            unsafe { $crate::abi::apply_edit(runtime, session, ptr, len) }
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_parse(session: u32) -> u64 {
+            let mut runtime = $runtime.borrow_mut();
+            let runtime = runtime.as_mut().expect("runtime not initialized");
+            $crate::abi::parse(runtime, session)
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn arborium_load_text(session: u32, ptr: u32, len: u32) -> u64 {
+            let mut runtime = $runtime.borrow_mut();
+            let runtime = runtime.as_mut().expect("runtime not initialized");
+            unsafe { $crate::abi::load_text(runtime, session, ptr, len) }
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_parse_step(session: u32, max_bytes: u32) -> u64 {
+            let mut runtime = $runtime.borrow_mut();
+            let runtime = runtime.as_mut().expect("runtime not initialized");
+            $crate::abi::parse_step(runtime, session, max_bytes)
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_cancel(session: u32) {
+            $runtime
+                .borrow_mut()
+                .as_mut()
+                .expect("runtime not initialized")
+                .cancel(session)
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_capture_names() -> u64 {
+            let runtime = $runtime.borrow();
+            let runtime = runtime.as_ref().expect("runtime not initialized");
+            $crate::abi::capture_names(runtime)
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_query_source(kind: u32) -> u64 {
+            let runtime = $runtime.borrow();
+            let runtime = runtime.as_ref().expect("runtime not initialized");
+            $crate::abi::query_source(runtime, kind)
+        }
+
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_query_hash() -> u64 {
+            let runtime = $runtime.borrow();
+            let runtime = runtime.as_ref().expect("runtime not initialized");
+            $crate::abi::query_hash(runtime)
+        }
+
+        $crate::__export_test_hooks!($runtime);
+    };
+}
+
+/// Export the test-only functions of the raw ABI, if this crate was built
+/// with the `test-hooks` feature.
+#[cfg(feature = "test-hooks")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __export_test_hooks {
+    ($runtime:expr) => {
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn arborium_test_panic(session: u32) -> u64 {
+            let mut runtime = $runtime.borrow_mut();
+            let runtime = runtime.as_mut().expect("runtime not initialized");
+            $crate::abi::test_panic(runtime, session)
+        }
+    };
+}
+
+#[cfg(not(feature = "test-hooks"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __export_test_hooks {
+    ($runtime:expr) => {};
+}
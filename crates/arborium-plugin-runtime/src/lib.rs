@@ -7,7 +7,13 @@
 //! - Parser state and tree storage
 //! - Query execution to produce Span and Injection records
 //! - Incremental parsing via edit application
+//! - Parsing in steps, see [`PluginRuntime::parse_step`]
 //! - Cancellation support
+//! - Panic isolation, see [`PluginRuntime::guarded`]
+//! - Lazy query compilation, see [`HighlightConfig::lazy`]
+//! - Syntax tree inspection (for tree explorers and query debugging)
+//! - Query introspection (capture names, query sources and hash)
+//! - Symbol tagging for outlines, see [`PluginRuntime::tags`]
 //!
 //! # Offset Encoding
 //!
@@ -45,19 +51,26 @@ extern crate alloc;
 #[cfg(target_family = "wasm")]
 use arborium_sysroot as _;
 
-use alloc::collections::BTreeMap;
+pub mod abi;
+pub mod manifest;
+
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::any::Any;
+use core::cell::OnceCell;
+use core::ops::ControlFlow;
+use core::panic::AssertUnwindSafe;
 use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
 use arborium_tree_sitter::{
-    InputEdit, Language, Parser, Point, Query, QueryCursor, QueryError, StreamingIterator, Tree,
+    InputEdit, Language, Node, ParseOptions, ParseState, Parser, Point, Query, QueryCursor,
+    QueryError, QueryPredicate, QueryPredicateArg, StreamingIterator, Tree,
 };
 use arborium_wire::{
-    Edit, ParseError, Utf8Injection, Utf8ParseResult, Utf8Span, Utf16Injection, Utf16ParseResult,
-    Utf16Span,
+    Edit, ParseError, QueryKind, SyntaxNode, SyntaxTree, Utf8Injection, Utf8ParseResult, Utf8Span,
+    Utf8Tag, Utf16Injection, Utf16ParseResult, Utf16Span,
 };
-use tree_sitter_language::LanguageFn;
 
 /// Batch convert UTF-8 byte offsets to UTF-16 code unit indices in a single pass.
 ///
@@ -102,9 +115,28 @@ fn batch_utf8_to_utf16(text: &str, offsets: &[usize]) -> Vec<u32> {
 
 /// Configuration for syntax highlighting.
 ///
-/// Contains the compiled queries for highlights, injections, and locals.
+/// Holds the highlights, injections and locals queries. Configs made with
+/// [`new`](Self::new) compile them right away; [`lazy`](Self::lazy) and
+/// [`deflated`](Self::deflated) configs compile them on first use, so
+/// creating sessions and setting text stays cheap.
 pub struct HighlightConfig {
     language: Language,
+    /// Query sources, highlights then injections then locals. Deflated
+    /// configs fill this in on first use, with an error if they don't
+    /// decompress.
+    sources: OnceCell<Result<[String; 3], ParseError>>,
+    #[cfg(feature = "compressed-queries")]
+    deflated: Option<[&'static [u8]; 3]>,
+    compiled: OnceCell<Result<CompiledQuery, ParseError>>,
+    /// Tags query, see [`with_tags`](Self::with_tags). Always compiled on
+    /// first use.
+    tags: String,
+    compiled_tags: OnceCell<Result<TagsQuery, ParseError>>,
+    query_hash: String,
+}
+
+/// The queries of a [`HighlightConfig`], compiled into one.
+struct CompiledQuery {
     query: Query,
     injection_content_capture_index: Option<u32>,
     injection_language_capture_index: Option<u32>,
@@ -112,21 +144,13 @@ pub struct HighlightConfig {
     highlights_pattern_index: usize,
 }
 
-impl HighlightConfig {
-    /// Create a new highlight configuration.
-    ///
-    /// # Arguments
-    /// * `language` - The tree-sitter language
-    /// * `highlights_query` - Query for syntax highlighting captures
-    /// * `injections_query` - Query for language injections
-    /// * `locals_query` - Query for local variable tracking
-    pub fn new(
-        language: LanguageFn,
+impl CompiledQuery {
+    fn new(
+        language: &Language,
         highlights_query: &str,
         injections_query: &str,
         locals_query: &str,
     ) -> Result<Self, QueryError> {
-        let language: Language = language.into();
         // Concatenate queries: injections, then locals, then highlights
         // Add newline separators to ensure queries don't merge incorrectly
         // if they don't end with newlines
@@ -143,7 +167,7 @@ impl HighlightConfig {
         let highlights_query_offset = query_source.len();
         query_source.push_str(highlights_query);
 
-        let query = Query::new(&language, &query_source)?;
+        let query = Query::new(language, &query_source)?;
 
         // Find pattern indices for each section
         let mut locals_pattern_index = 0;
@@ -170,7 +194,6 @@ impl HighlightConfig {
         }
 
         Ok(Self {
-            language,
             query,
             injection_content_capture_index,
             injection_language_capture_index,
@@ -178,18 +201,333 @@ impl HighlightConfig {
             highlights_pattern_index,
         })
     }
+}
+
+/// A compiled tags query, with what each capture stands for.
+struct TagsQuery {
+    query: Query,
+    name_capture_index: Option<u32>,
+    /// For each capture, its kind and whether it's a definition, if it's
+    /// a `@definition.*` or `@reference.*` capture.
+    kinds: Vec<Option<(String, bool)>>,
+}
+
+impl TagsQuery {
+    fn new(language: &Language, source: &str) -> Result<Self, QueryError> {
+        let query = Query::new(language, source)?;
+        let mut name_capture_index = None;
+        let mut kinds = Vec::new();
+        for (i, name) in query.capture_names().iter().enumerate() {
+            if *name == "name" {
+                name_capture_index = Some(i as u32);
+            }
+            kinds.push(if let Some(kind) = name.strip_prefix("definition.") {
+                Some((String::from(kind), true))
+            } else {
+                name.strip_prefix("reference.")
+                    .map(|kind| (String::from(kind), false))
+            });
+        }
+        Ok(Self {
+            query,
+            name_capture_index,
+            kinds,
+        })
+    }
+}
+
+impl HighlightConfig {
+    /// Create a new highlight configuration, compiling the queries now.
+    ///
+    /// # Arguments
+    /// * `language` - The tree-sitter language
+    /// * `highlights_query` - Query for syntax highlighting captures
+    /// * `injections_query` - Query for language injections
+    /// * `locals_query` - Query for local variable tracking
+    pub fn new(
+        language: impl Into<Language>,
+        highlights_query: &str,
+        injections_query: &str,
+        locals_query: &str,
+    ) -> Result<Self, QueryError> {
+        let config = Self::lazy(language, highlights_query, injections_query, locals_query);
+        let compiled = CompiledQuery::new(
+            &config.language,
+            highlights_query,
+            injections_query,
+            locals_query,
+        )?;
+        let _ = config.compiled.set(Ok(compiled));
+        Ok(config)
+    }
+
+    /// Create a highlight configuration that compiles the queries on first
+    /// use, see [`precompile`](Self::precompile).
+    ///
+    /// Errors in the queries then come back from parsing, as
+    /// [`ParseErrorKind::Query`](arborium_wire::ParseErrorKind::Query).
+    pub fn lazy(
+        language: impl Into<Language>,
+        highlights_query: &str,
+        injections_query: &str,
+        locals_query: &str,
+    ) -> Self {
+        Self {
+            language: language.into(),
+            sources: OnceCell::from(Ok([
+                highlights_query.into(),
+                injections_query.into(),
+                locals_query.into(),
+            ])),
+            #[cfg(feature = "compressed-queries")]
+            deflated: None,
+            compiled: OnceCell::new(),
+            tags: String::new(),
+            compiled_tags: OnceCell::new(),
+            query_hash: String::new(),
+        }
+    }
+
+    /// Create a [`lazy`](Self::lazy) highlight configuration from
+    /// deflate-compressed queries, decompressed on first use.
+    ///
+    /// Generated plugins built with the `compressed-queries` feature embed
+    /// their queries this way, which makes the `.wasm` noticeably smaller
+    /// for grammars with large queries.
+    ///
+    /// Queries that aren't valid deflate streams of UTF-8 text fail like
+    /// queries that don't compile, with
+    /// [`ParseErrorKind::Query`](arborium_wire::ParseErrorKind::Query).
+    #[cfg(feature = "compressed-queries")]
+    pub fn deflated(
+        language: impl Into<Language>,
+        highlights_query: &'static [u8],
+        injections_query: &'static [u8],
+        locals_query: &'static [u8],
+    ) -> Self {
+        Self {
+            language: language.into(),
+            sources: OnceCell::new(),
+            deflated: Some([highlights_query, injections_query, locals_query]),
+            compiled: OnceCell::new(),
+            tags: String::new(),
+            compiled_tags: OnceCell::new(),
+            query_hash: String::new(),
+        }
+    }
+
+    /// Set the hash identifying the queries, as computed at build time.
+    ///
+    /// Generated plugins use the `query_hash` of their manifest, so hosts
+    /// can invalidate caches when a grammar's queries change.
+    pub fn with_query_hash(mut self, query_hash: &str) -> Self {
+        self.query_hash = query_hash.into();
+        self
+    }
+
+    /// Set the tags query, which [`PluginRuntime::tags`] runs.
+    ///
+    /// Unlike the other queries it is kept apart and only compiled the
+    /// first time it's used. Without one, there are no tags.
+    pub fn with_tags(mut self, tags_query: &str) -> Self {
+        self.tags = tags_query.into();
+        self.compiled_tags = OnceCell::new();
+        self
+    }
+
+    /// Compile the queries now rather than on first use.
+    ///
+    /// Does nothing if they're already compiled. Errors are of kind
+    /// [`ParseErrorKind::Query`](arborium_wire::ParseErrorKind::Query), and
+    /// are returned again on every later use.
+    pub fn precompile(&self) -> Result<(), ParseError> {
+        self.compiled()?;
+        self.compiled_tags().map(|_| ())
+    }
+
+    /// Whether the queries have been compiled, successfully or not.
+    pub fn is_compiled(&self) -> bool {
+        self.compiled.get().is_some()
+    }
+
+    fn compiled(&self) -> Result<&CompiledQuery, ParseError> {
+        self.compiled
+            .get_or_init(|| {
+                let [highlights, injections, locals] = self.sources()?;
+                CompiledQuery::new(&self.language, highlights, injections, locals)
+                    .map_err(|e| ParseError::query(alloc::format!("invalid query: {e}")))
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
 
-    /// Get the capture names from the query.
+    /// The compiled tags query, or `None` if there is none.
+    fn compiled_tags(&self) -> Result<Option<&TagsQuery>, ParseError> {
+        if self.tags.is_empty() {
+            return Ok(None);
+        }
+        self.compiled_tags
+            .get_or_init(|| {
+                TagsQuery::new(&self.language, &self.tags)
+                    .map_err(|e| ParseError::query(alloc::format!("invalid tags query: {e}")))
+            })
+            .as_ref()
+            .map(Some)
+            .map_err(Clone::clone)
+    }
+
+    fn sources(&self) -> Result<&[String; 3], ParseError> {
+        self.sources
+            .get_or_init(|| {
+                #[cfg(feature = "compressed-queries")]
+                if let Some(deflated) = self.deflated {
+                    return inflate_sources(deflated);
+                }
+                // Plain configs are created with their sources
+                Err(ParseError::internal("query sources missing"))
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Get the capture names from the query, compiling it if needed.
+    ///
+    /// Empty if the query failed to compile; [`precompile`](Self::precompile)
+    /// tells why.
     pub fn capture_names(&self) -> &[&str] {
-        self.query.capture_names()
+        match self.compiled() {
+            Ok(compiled) => compiled.query.capture_names(),
+            Err(_) => &[],
+        }
+    }
+
+    /// Get the source of one of the queries, as passed to [`new`](Self::new).
+    ///
+    /// Fails only for [`deflated`](Self::deflated) queries that don't
+    /// decompress.
+    pub fn query_source(&self, kind: QueryKind) -> Result<&str, ParseError> {
+        let [highlights, injections, locals] = self.sources()?;
+        Ok(match kind {
+            QueryKind::Highlights => highlights,
+            QueryKind::Injections => injections,
+            QueryKind::Locals => locals,
+        })
+    }
+
+    /// Get the tags query set with [`with_tags`](Self::with_tags), empty if
+    /// none was set.
+    pub fn tags_query(&self) -> &str {
+        &self.tags
+    }
+
+    /// Get the hash set with [`with_query_hash`](Self::with_query_hash),
+    /// empty if none was set.
+    pub fn query_hash(&self) -> &str {
+        &self.query_hash
+    }
+}
+
+/// Decompress the queries of a [`HighlightConfig::deflated`] config.
+#[cfg(feature = "compressed-queries")]
+fn inflate_sources(deflated: [&'static [u8]; 3]) -> Result<[String; 3], ParseError> {
+    let mut sources: [String; 3] = Default::default();
+    for (source, bytes) in sources.iter_mut().zip(deflated) {
+        let text = miniz_oxide::inflate::decompress_to_vec(bytes)
+            .map_err(|e| ParseError::query(alloc::format!("corrupt compressed query: {e:?}")))?;
+        *source = String::from_utf8(text)
+            .map_err(|e| ParseError::query(alloc::format!("compressed query is not UTF-8: {e}")))?;
+    }
+    Ok(sources)
+}
+
+/// Limits on how much of a syntax tree [`PluginRuntime::syntax_tree`] returns.
+///
+/// Trees for large files have hundreds of thousands of nodes; these keep
+/// payloads small enough to ship to a browser and render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeLimits {
+    /// Maximum depth to descend to. The root is at depth 0.
+    pub max_depth: u32,
+    /// Maximum number of nodes to return, the root included.
+    pub max_nodes: u32,
+}
+
+impl Default for TreeLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 64,
+            max_nodes: 10_000,
+        }
+    }
+}
+
+/// Whether a node shows up in syntax trees.
+///
+/// Anonymous nodes are punctuation and keywords; missing nodes are kept
+/// even when anonymous (e.g. a missing `;`) so error recovery is visible.
+fn is_visible(node: &Node) -> bool {
+    node.is_named() || node.is_missing()
+}
+
+/// Convert a node to its wire form, without children.
+fn syntax_node(node: &Node) -> SyntaxNode {
+    let start = node.start_position();
+    let end = node.end_position();
+    SyntaxNode {
+        kind: String::from(node.kind()),
+        start: node.start_byte() as u32,
+        end: node.end_byte() as u32,
+        start_row: start.row as u32,
+        start_col: start.column as u32,
+        end_row: end.row as u32,
+        end_col: end.column as u32,
+        is_error: node.is_error(),
+        is_missing: node.is_missing(),
+        children: Vec::new(),
     }
 }
 
+/// Build the wire form of `node` and its visible descendants, within `limits`.
+fn build_syntax_node(
+    node: Node,
+    depth: u32,
+    limits: &TreeLimits,
+    node_count: &mut u32,
+    truncated: &mut bool,
+) -> SyntaxNode {
+    let mut result = syntax_node(&node);
+    *node_count += 1;
+
+    for i in 0..node.child_count() as u32 {
+        let Some(child) = node.child(i) else {
+            continue;
+        };
+        if !is_visible(&child) {
+            continue;
+        }
+        if depth + 1 > limits.max_depth || *node_count >= limits.max_nodes {
+            *truncated = true;
+            break;
+        }
+        result.children.push(build_syntax_node(
+            child,
+            depth + 1,
+            limits,
+            node_count,
+            truncated,
+        ));
+    }
+
+    result
+}
+
 /// A parsing session that maintains parser state.
 struct Session {
     parser: Parser,
     tree: Option<Tree>,
     text: String,
+    /// Whether `text` came from `load_text` and isn't fully parsed yet.
+    pending: bool,
     cursor: QueryCursor,
     cancelled: AtomicBool,
 }
@@ -204,10 +542,45 @@ impl Session {
             parser,
             tree: None,
             text: String::new(),
+            pending: false,
             cursor: QueryCursor::new(),
             cancelled: AtomicBool::new(false),
         }
     }
+
+    /// Continue parsing text from `load_text` where the last step stopped,
+    /// stopping again once the parser got `max_bytes` further, if given.
+    fn advance(&mut self, max_bytes: Option<usize>) {
+        if !self.pending {
+            return;
+        }
+        // Tree-sitter picks a halted parse back up on the next call
+        let tree = match max_bytes {
+            None => self.parser.parse(&self.text, None),
+            Some(max_bytes) => {
+                let bytes = self.text.as_bytes();
+                let mut start = None;
+                let mut progress = |state: &ParseState| {
+                    let offset = state.current_byte_offset();
+                    let start = *start.get_or_insert(offset);
+                    if offset.saturating_sub(start) >= max_bytes {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                };
+                self.parser.parse_with_options(
+                    &mut |i, _| bytes.get(i..).unwrap_or_default(),
+                    None,
+                    Some(ParseOptions::new().progress_callback(&mut progress)),
+                )
+            }
+        };
+        if tree.is_some() {
+            self.tree = tree;
+            self.pending = false;
+        }
+    }
 }
 
 // Internal structs to hold raw byte offsets during parsing
@@ -223,6 +596,7 @@ struct RawInjection {
     end: usize,
     language: String,
     include_children: bool,
+    combined: bool,
 }
 
 /// Runtime for a grammar plugin.
@@ -232,6 +606,8 @@ struct RawInjection {
 pub struct PluginRuntime {
     config: HighlightConfig,
     sessions: BTreeMap<u32, Session>,
+    /// Sessions dropped after a panic, see [`poison`](Self::poison).
+    poisoned: BTreeSet<u32>,
     next_session_id: AtomicU32,
 }
 
@@ -241,6 +617,7 @@ impl PluginRuntime {
         Self {
             config,
             sessions: BTreeMap::new(),
+            poisoned: BTreeSet::new(),
             next_session_id: AtomicU32::new(1),
         }
     }
@@ -258,6 +635,53 @@ impl PluginRuntime {
     /// Free a parsing session and its resources.
     pub fn free_session(&mut self, session_id: u32) {
         self.sessions.remove(&session_id);
+        self.poisoned.remove(&session_id);
+    }
+
+    /// Drop a session's state after a panic while working on it.
+    ///
+    /// The parser and tree may have been left half-updated, so they are
+    /// thrown away. The id stays reserved until
+    /// [`free_session`](Self::free_session), and
+    /// [`is_poisoned`](Self::is_poisoned) reports it.
+    pub fn poison(&mut self, session_id: u32) {
+        if self.sessions.remove(&session_id).is_some() {
+            self.poisoned.insert(session_id);
+        }
+    }
+
+    /// Whether a session was poisoned by [`poison`](Self::poison).
+    pub fn is_poisoned(&self, session_id: u32) -> bool {
+        self.poisoned.contains(&session_id)
+    }
+
+    /// Run `f` on behalf of a session, turning a panic into an error.
+    ///
+    /// A panic poisons the session and is reported as a
+    /// [`ParseErrorKind::Internal`](arborium_wire::ParseErrorKind::Internal)
+    /// error carrying the panic message; calls on a poisoned session fail
+    /// with [`ParseErrorKind::Poisoned`](arborium_wire::ParseErrorKind::Poisoned)
+    /// without running `f`. Other sessions are unaffected.
+    ///
+    /// Plugin exports go through this. Panics can only be caught when the
+    /// plugin is built with `panic = "unwind"`, as `cargo xtask build` does
+    /// using wasm exception handling; with wasm32's default of aborting, a
+    /// panic still traps the instance.
+    pub fn guarded<T>(
+        &mut self,
+        session_id: u32,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if self.is_poisoned(session_id) {
+            return Err(ParseError::poisoned(session_id));
+        }
+        match std::panic::catch_unwind(AssertUnwindSafe(|| f(self))) {
+            Ok(result) => result,
+            Err(payload) => {
+                self.poison(session_id);
+                Err(ParseError::internal(panic_message(payload.as_ref())))
+            }
+        }
     }
 
     /// Set the full text content for a session.
@@ -266,16 +690,55 @@ impl PluginRuntime {
     pub fn set_text(&mut self, session_id: u32, text: &str) {
         if let Some(session) = self.sessions.get_mut(&session_id) {
             session.text = String::from(text);
+            // Drop what's left of a parse from `load_text`
+            session.parser.reset();
+            session.pending = false;
             session.tree = session.parser.parse(text, None);
             session.cancelled.store(false, Ordering::Relaxed);
         }
     }
 
+    /// Set the full text content for a session without parsing it yet.
+    ///
+    /// Parse it with [`parse_step`](Self::parse_step) to get control back
+    /// every so often, e.g. to keep a page responsive while parsing a large
+    /// file. [`parse`](Self::parse) and the other calls needing a tree
+    /// parse whatever is left first; [`syntax_tree`](Self::syntax_tree)
+    /// and [`node_at`](Self::node_at) fail until the parse is complete.
+    pub fn load_text(&mut self, session_id: u32, text: &str) {
+        if let Some(session) = self.sessions.get_mut(&session_id) {
+            session.text = String::from(text);
+            session.parser.reset();
+            session.pending = true;
+            session.tree = None;
+            session.cancelled.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Continue parsing the text from [`load_text`](Self::load_text),
+    /// stopping once the parser got about `max_bytes` further into it.
+    ///
+    /// Returns whether the parse is complete, which it also is for text
+    /// from [`set_text`](Self::set_text). Error recovery can make the
+    /// parser go over the same bytes more than once, so the number of
+    /// steps a text takes is only roughly its length over `max_bytes`.
+    pub fn parse_step(&mut self, session_id: u32, max_bytes: u32) -> Result<bool, ParseError> {
+        let session = self
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| missing_session(&self.poisoned, session_id))?;
+        session.advance(Some(max_bytes.max(1) as usize));
+        Ok(!session.pending)
+    }
+
     /// Apply an incremental edit to the session's text.
     ///
     /// The session must have had `set_text` called previously.
     pub fn apply_edit(&mut self, session_id: u32, new_text: &str, edit: &Edit) {
         if let Some(session) = self.sessions.get_mut(&session_id) {
+            // The edit applies to the old text's tree
+            session.advance(None);
+
             // Update the text
             session.text = String::from(new_text);
 
@@ -319,18 +782,22 @@ impl PluginRuntime {
         let session = self
             .sessions
             .get_mut(&session_id)
-            .ok_or_else(|| ParseError::new("invalid session id"))?;
+            .ok_or_else(|| missing_session(&self.poisoned, session_id))?;
 
         // Check for cancellation
         if session.cancelled.load(Ordering::Relaxed) {
             return Ok((String::new(), Vec::new(), Vec::new()));
         }
 
+        session.advance(None);
+
         let tree = session
             .tree
             .as_ref()
             .ok_or_else(|| ParseError::new("no text set for session"))?;
 
+        let compiled = self.config.compiled()?;
+
         let mut raw_spans: Vec<RawSpan> = Vec::new();
         let mut raw_injections: Vec<RawInjection> = Vec::new();
 
@@ -339,7 +806,7 @@ impl PluginRuntime {
         let root = tree.root_node();
 
         // Execute the query using streaming iterator
-        let mut matches = session.cursor.matches(&self.config.query, root, source);
+        let mut matches = session.cursor.matches(&compiled.query, root, source);
 
         let mut check_count = 0;
         const CANCELLATION_CHECK_INTERVAL: usize = 100;
@@ -355,23 +822,24 @@ impl PluginRuntime {
             }
 
             // Process injections (patterns before locals_pattern_index)
-            if m.pattern_index < self.config.locals_pattern_index {
+            if m.pattern_index < compiled.locals_pattern_index {
                 let mut language_name: Option<&str> = None;
                 let mut content_node = None;
                 let mut include_children = false;
+                let mut combined = false;
 
                 for capture in m.captures {
-                    if Some(capture.index) == self.config.injection_language_capture_index {
+                    if Some(capture.index) == compiled.injection_language_capture_index {
                         if let Ok(name) = capture.node.utf8_text(source) {
                             language_name = Some(name);
                         }
-                    } else if Some(capture.index) == self.config.injection_content_capture_index {
+                    } else if Some(capture.index) == compiled.injection_content_capture_index {
                         content_node = Some(capture.node);
                     }
                 }
 
                 // Check for #set! predicates
-                for prop in self.config.query.property_settings(m.pattern_index) {
+                for prop in compiled.query.property_settings(m.pattern_index) {
                     match prop.key.as_ref() {
                         "injection.language" => {
                             if language_name.is_none() {
@@ -381,30 +849,58 @@ impl PluginRuntime {
                         "injection.include-children" => {
                             include_children = true;
                         }
+                        "injection.combined" => {
+                            combined = true;
+                        }
                         _ => {}
                     }
                 }
 
                 if let (Some(lang), Some(node)) = (language_name, content_node) {
-                    raw_injections.push(RawInjection {
-                        start: node.start_byte(),
-                        end: node.end_byte(),
-                        language: String::from(lang),
-                        include_children,
-                    });
+                    let (mut start, mut end) = (node.start_byte(), node.end_byte());
+
+                    // Apply #offset! to the content, e.g. to skip a prefix
+                    for predicate in compiled.query.general_predicates(m.pattern_index) {
+                        let Some(capture) = compiled.injection_content_capture_index else {
+                            continue;
+                        };
+                        if let Some(offsets) = injection_offsets(predicate, capture) {
+                            start = offset_byte(source, node.start_position(), start, offsets.0);
+                            end = offset_byte(source, node.end_position(), end, offsets.1);
+                        }
+                    }
+
+                    // Combined content is stitched back together by the
+                    // host, so it can leave its children out
+                    let ranges = if combined && !include_children {
+                        exclude_children(node, start, end)
+                    } else {
+                        alloc::vec![(start, end)]
+                    };
+                    for (start, end) in ranges {
+                        if start < end {
+                            raw_injections.push(RawInjection {
+                                start,
+                                end,
+                                language: String::from(lang),
+                                include_children,
+                                combined,
+                            });
+                        }
+                    }
                 }
 
                 continue;
             }
 
             // Skip locals patterns (between locals_pattern_index and highlights_pattern_index)
-            if m.pattern_index < self.config.highlights_pattern_index {
+            if m.pattern_index < compiled.highlights_pattern_index {
                 continue;
             }
 
             // Process highlights
             for capture in m.captures {
-                let capture_name = self.config.query.capture_names()[capture.index as usize];
+                let capture_name = compiled.query.capture_names()[capture.index as usize];
 
                 // Skip internal captures (starting with underscore)
                 if capture_name.starts_with('_') {
@@ -465,6 +961,7 @@ impl PluginRuntime {
                 end: i.end as u32,
                 language: i.language,
                 include_children: i.include_children,
+                combined: i.combined,
             })
             .collect();
 
@@ -530,22 +1027,267 @@ impl PluginRuntime {
                 end: lookup(i.end),
                 language: i.language,
                 include_children: i.include_children,
+                combined: i.combined,
             })
             .collect();
 
         Ok(Utf16ParseResult { spans, injections })
     }
 
+    /// Run the tags query over the session's text, finding the definitions
+    /// and references of symbols.
+    ///
+    /// Each match of a `@definition.*` or `@reference.*` capture along with
+    /// a `@name` makes a tag. A name gets a single tag, from the earliest
+    /// pattern matching it, so a query can list a special case (methods,
+    /// say) before the general one (functions). Tags come in the order of
+    /// the items they tag, and offsets are UTF-8 byte offsets.
+    ///
+    /// Empty if the config has no tags query, see
+    /// [`HighlightConfig::with_tags`].
+    pub fn tags(&mut self, session_id: u32) -> Result<Vec<Utf8Tag>, ParseError> {
+        let session = self
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| missing_session(&self.poisoned, session_id))?;
+        session.advance(None);
+        let tree = session
+            .tree
+            .as_ref()
+            .ok_or_else(|| ParseError::new("no text set for session"))?;
+        let Some(compiled) = self.config.compiled_tags()? else {
+            return Ok(Vec::new());
+        };
+
+        let source = session.text.as_bytes();
+        let mut tags: Vec<(usize, Utf8Tag)> = Vec::new();
+        let mut matches = session
+            .cursor
+            .matches(&compiled.query, tree.root_node(), source);
+        while let Some(m) = matches.next() {
+            let mut name = None;
+            let mut item = None;
+            for capture in m.captures {
+                if Some(capture.index) == compiled.name_capture_index {
+                    name = Some(capture.node);
+                } else if let Some(kind) = &compiled.kinds[capture.index as usize] {
+                    item = Some((capture.node, kind));
+                }
+            }
+            let (Some(name), Some((node, (kind, is_definition)))) = (name, item) else {
+                continue;
+            };
+            let Ok(text) = name.utf8_text(source) else {
+                continue;
+            };
+            tags.push((
+                m.pattern_index,
+                Utf8Tag {
+                    start: node.start_byte() as u32,
+                    end: node.end_byte() as u32,
+                    name_start: name.start_byte() as u32,
+                    name_end: name.end_byte() as u32,
+                    name: String::from(text),
+                    kind: kind.clone(),
+                    is_definition: *is_definition,
+                },
+            ));
+        }
+
+        tags.sort_by_key(|(pattern_index, tag)| (tag.name_start, tag.name_end, *pattern_index));
+        tags.dedup_by_key(|(_, tag)| (tag.name_start, tag.name_end));
+        let mut tags: Vec<Utf8Tag> = tags.into_iter().map(|(_, tag)| tag).collect();
+        tags.sort_by_key(|tag| (tag.start, tag.name_start));
+        Ok(tags)
+    }
+
+    /// Return the session's syntax tree, limited to visible nodes.
+    ///
+    /// Only named nodes (and missing nodes, which are inserted by error
+    /// recovery) are included. Nodes deeper than `limits.max_depth` or past
+    /// `limits.max_nodes` are left out and `truncated` is set.
+    pub fn syntax_tree(
+        &self,
+        session_id: u32,
+        limits: &TreeLimits,
+    ) -> Result<SyntaxTree, ParseError> {
+        let tree = self.session_tree(session_id)?;
+
+        let mut node_count = 0;
+        let mut truncated = false;
+        let root = build_syntax_node(tree.root_node(), 0, limits, &mut node_count, &mut truncated);
+
+        Ok(SyntaxTree {
+            root,
+            node_count,
+            truncated,
+        })
+    }
+
+    /// Return the spine of visible nodes covering a UTF-8 byte offset.
+    ///
+    /// The result starts at the root and ends at the innermost node that
+    /// contains `offset`. Nodes are returned without their children.
+    pub fn node_at(&self, session_id: u32, offset: u32) -> Result<Vec<SyntaxNode>, ParseError> {
+        let tree = self.session_tree(session_id)?;
+        let root = tree.root_node();
+        let offset = (offset as usize).min(root.end_byte());
+
+        let mut spine = Vec::new();
+        let Some(mut node) = root.descendant_for_byte_range(offset, offset) else {
+            return Ok(spine);
+        };
+        loop {
+            if is_visible(&node) {
+                spine.push(syntax_node(&node));
+            }
+            match node.parent() {
+                Some(parent) => node = parent,
+                None => break,
+            }
+        }
+        spine.reverse();
+
+        Ok(spine)
+    }
+
+    fn session_tree(&self, session_id: u32) -> Result<&Tree, ParseError> {
+        let session = self
+            .sessions
+            .get(&session_id)
+            .ok_or_else(|| missing_session(&self.poisoned, session_id))?;
+        if session.pending {
+            return Err(ParseError::new("text is still being parsed"));
+        }
+        session
+            .tree
+            .as_ref()
+            .ok_or_else(|| ParseError::new("no text set for session"))
+    }
+
     /// Get the language provided by this plugin.
     pub fn language(&self) -> &Language {
         &self.config.language
     }
+
+    /// Get the highlight configuration the runtime was created with.
+    pub fn config(&self) -> &HighlightConfig {
+        &self.config
+    }
+}
+
+/// The `(rows, columns)` moves of the start and end of `capture` that an
+/// `(#offset! @capture start_row start_col end_row end_col)` predicate asks
+/// for, if `predicate` is one.
+fn injection_offsets(
+    predicate: &QueryPredicate,
+    capture: u32,
+) -> Option<((isize, isize), (isize, isize))> {
+    if &*predicate.operator != "offset!" {
+        return None;
+    }
+    let [QueryPredicateArg::Capture(index), rest @ ..] = &*predicate.args else {
+        return None;
+    };
+    if *index != capture {
+        return None;
+    }
+    let mut numbers = rest.iter().map(|arg| match arg {
+        QueryPredicateArg::String(n) => n.parse::<isize>().ok(),
+        QueryPredicateArg::Capture(_) => None,
+    });
+    let mut next = || numbers.next().flatten();
+    Some(((next()?, next()?), (next()?, next()?)))
+}
+
+/// Move `byte`, at `point`, by `(rows, columns)` the way `#offset!` does:
+/// down or up a number of lines, keeping the column, then along the line.
+/// The result stays within the line it lands on.
+fn offset_byte(source: &[u8], point: Point, byte: usize, (rows, columns): (isize, isize)) -> usize {
+    let line_end = |start: usize| {
+        source[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(source.len(), |i| start + i)
+    };
+    let mut line_start = byte - point.column;
+    for _ in 0..rows.max(0) {
+        let end = line_end(line_start);
+        if end == source.len() {
+            return source.len();
+        }
+        line_start = end + 1;
+    }
+    for _ in 0..(-rows).max(0) {
+        line_start = source[..line_start.saturating_sub(1)]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+    }
+    let column = (point.column as isize + columns).max(0) as usize;
+    (line_start + column).min(line_end(line_start))
+}
+
+/// Split `start..end`, a range within `node`, around the node's children,
+/// the way tree-sitter injections without `injection.include-children` do.
+fn exclude_children(node: Node, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut from = start;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.end_byte() <= from || child.start_byte() >= end {
+            continue;
+        }
+        ranges.push((from, child.start_byte().max(from)));
+        from = child.end_byte().min(end);
+    }
+    ranges.push((from, end));
+    ranges.retain(|(start, end)| start < end);
+    ranges
+}
+
+/// Error for a session that isn't in the session map.
+fn missing_session(poisoned: &BTreeSet<u32>, session_id: u32) -> ParseError {
+    if poisoned.contains(&session_id) {
+        ParseError::poisoned(session_id)
+    } else {
+        ParseError::new("invalid session id")
+    }
+}
+
+/// Extract the message of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("plugin panicked")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_offset_byte_within_a_line() {
+        let source = b"x = 1  # type: int\n";
+        let start = Point::new(0, 7);
+        assert_eq!(offset_byte(source, start, 7, (0, 8)), 15);
+        assert_eq!(offset_byte(source, start, 7, (0, -20)), 0);
+        assert_eq!(offset_byte(source, start, 7, (0, 40)), 18);
+    }
+
+    #[test]
+    fn test_offset_byte_across_lines() {
+        let source = b"ab\ncdef\ng";
+        assert_eq!(offset_byte(source, Point::new(0, 1), 1, (1, 0)), 4);
+        assert_eq!(offset_byte(source, Point::new(1, 3), 6, (-1, 0)), 2);
+        assert_eq!(offset_byte(source, Point::new(1, 3), 6, (1, 0)), 9);
+        assert_eq!(offset_byte(source, Point::new(2, 1), 9, (1, 0)), 9);
+    }
+
     #[test]
     fn test_batch_utf8_to_utf16_ascii() {
         // ASCII: 1 byte UTF-8 = 1 UTF-16 code unit
@@ -717,6 +1459,103 @@ mod tests {
 
             runtime.free_session(session);
         }
+
+        #[test]
+        fn test_parse_in_steps() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let source = "fn main() {\n    let x = 1;\n}\n".repeat(500);
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+
+            runtime.set_text(session, &source);
+            let expected = runtime.parse(session).expect("parse failed");
+
+            runtime.load_text(session, &source);
+            assert!(
+                runtime
+                    .syntax_tree(session, &TreeLimits::default())
+                    .is_err()
+            );
+            let mut steps = 0;
+            while !runtime.parse_step(session, 1024).expect("step failed") {
+                steps += 1;
+                assert!(steps < 10_000, "parse never completed");
+            }
+            assert!(
+                steps > 1,
+                "{} bytes parsed in {} steps",
+                source.len(),
+                steps
+            );
+            assert_eq!(runtime.parse(session).expect("parse failed"), expected);
+
+            // Parsing finishes whatever the steps left
+            runtime.load_text(session, &source);
+            assert!(!runtime.parse_step(session, 1024).expect("step failed"));
+            assert_eq!(runtime.parse(session).expect("parse failed"), expected);
+
+            runtime.free_session(session);
+        }
+
+        #[test]
+        fn test_panic_poisons_only_its_session() {
+            use arborium_wire::ParseErrorKind;
+
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let broken = runtime.create_session();
+            let healthy = runtime.create_session();
+            runtime.set_text(broken, "fn main() {}");
+            runtime.set_text(healthy, "fn main() {}");
+
+            // Panic halfway through a parse, as a query processing bug would
+            let err = runtime
+                .guarded::<()>(broken, |runtime| {
+                    runtime.parse(broken)?;
+                    panic!("bad node text");
+                })
+                .unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::Internal);
+            assert_eq!(err.message, "bad node text");
+            assert!(runtime.is_poisoned(broken));
+
+            // The broken session stays poisoned, whichever way it's called
+            let err = runtime
+                .guarded(broken, |runtime| runtime.parse(broken))
+                .unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::Poisoned);
+            let err = runtime.parse(broken).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::Poisoned);
+
+            // Others keep working
+            let result = runtime
+                .guarded(healthy, |runtime| runtime.parse(healthy))
+                .expect("parse failed");
+            assert!(result.spans.iter().any(|s| s.capture == "keyword"));
+            let fresh = runtime.create_session();
+            runtime.set_text(fresh, "fn main() {}");
+            assert!(runtime.parse(fresh).is_ok());
+
+            // Freeing the session clears the mark
+            runtime.free_session(broken);
+            assert!(!runtime.is_poisoned(broken));
+            let err = runtime.parse(broken).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::Request);
+        }
     }
 
     /// Test Styx grammar - verifies pattern_index is correct for deduplication
@@ -0,0 +1,210 @@
+//! Compile-time encoding of a plugin's manifest.
+//!
+//! Plugins call [`export_manifest!`](crate::export_manifest) to embed an
+//! [`arborium_wire::PluginManifest`] in the
+//! [`MANIFEST_SECTION`](arborium_wire::MANIFEST_SECTION) custom section.
+//! The bytes are produced by `const fn`s here, matching postcard's encoding
+//! of the manifest, so the section exists in any build of the plugin without
+//! a post-processing step.
+
+/// Borrowed manifest fields, buildable in a `const`.
+///
+/// Mirrors [`arborium_wire::PluginManifest`] field by field.
+#[derive(Debug, Clone, Copy)]
+pub struct ManifestFields<'a> {
+    /// See [`arborium_wire::PluginManifest::wire_version`].
+    pub wire_version: u32,
+    /// See [`arborium_wire::PluginManifest::language_id`].
+    pub language_id: &'a str,
+    /// See [`arborium_wire::PluginManifest::aliases`].
+    pub aliases: &'a [&'a str],
+    /// See [`arborium_wire::PluginManifest::extensions`].
+    pub extensions: &'a [&'a str],
+    /// See [`arborium_wire::PluginManifest::provides_injections`].
+    pub provides_injections: bool,
+    /// See [`arborium_wire::PluginManifest::provides_locals`].
+    pub provides_locals: bool,
+    /// See [`arborium_wire::PluginManifest::query_hash`].
+    pub query_hash: &'a str,
+    /// See [`arborium_wire::PluginManifest::grammar_commit`].
+    pub grammar_commit: &'a str,
+    /// See [`arborium_wire::PluginManifest::version`].
+    pub version: &'a str,
+}
+
+/// Length of the encoded manifest.
+pub const fn encoded_len(m: &ManifestFields<'_>) -> usize {
+    varint_len(m.wire_version as usize)
+        + str_len(m.language_id)
+        + strs_len(m.aliases)
+        + strs_len(m.extensions)
+        + 2
+        + str_len(m.query_hash)
+        + str_len(m.grammar_commit)
+        + str_len(m.version)
+}
+
+/// Encode the manifest. `N` must be [`encoded_len`] of it.
+pub const fn encode<const N: usize>(m: &ManifestFields<'_>) -> [u8; N] {
+    let mut out = Writer {
+        buf: [0; N],
+        pos: 0,
+    };
+    out.varint(m.wire_version as usize);
+    out.str(m.language_id);
+    out.strs(m.aliases);
+    out.strs(m.extensions);
+    out.byte(m.provides_injections as u8);
+    out.byte(m.provides_locals as u8);
+    out.str(m.query_hash);
+    out.str(m.grammar_commit);
+    out.str(m.version);
+    assert!(out.pos == N, "manifest length mismatch");
+    out.buf
+}
+
+const fn varint_len(mut value: usize) -> usize {
+    let mut len = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        len += 1;
+    }
+    len
+}
+
+const fn str_len(s: &str) -> usize {
+    varint_len(s.len()) + s.len()
+}
+
+const fn strs_len(strs: &[&str]) -> usize {
+    let mut len = varint_len(strs.len());
+    let mut i = 0;
+    while i < strs.len() {
+        len += str_len(strs[i]);
+        i += 1;
+    }
+    len
+}
+
+struct Writer<const N: usize> {
+    buf: [u8; N],
+    pos: usize,
+}
+
+impl<const N: usize> Writer<N> {
+    const fn byte(&mut self, b: u8) {
+        self.buf[self.pos] = b;
+        self.pos += 1;
+    }
+
+    const fn varint(&mut self, mut value: usize) {
+        while value >= 0x80 {
+            self.byte((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.byte(value as u8);
+    }
+
+    const fn str(&mut self, s: &str) {
+        self.varint(s.len());
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            self.byte(bytes[i]);
+            i += 1;
+        }
+    }
+
+    const fn strs(&mut self, strs: &[&str]) {
+        self.varint(strs.len());
+        let mut i = 0;
+        while i < strs.len() {
+            self.str(strs[i]);
+            i += 1;
+        }
+    }
+}
+
+/// Embed a plugin's manifest in its custom section.
+///
+/// Takes a `const` [`ManifestFields`] expression.
+///
+/// ```ignore
+/// arborium_plugin_runtime::export_manifest!(ManifestFields {
+///     wire_version: arborium_wire::WIRE_VERSION,
+///     language_id: "rust",
+///     aliases: &["rs"],
+///     extensions: &["rust", "rs"],
+///     provides_injections: true,
+///     provides_locals: true,
+///     query_hash: "0123456789abcdef",
+///     grammar_commit: "f4be518f20f71ee62eaff4f3f1de07254c240704",
+///     version: "2.4.1",
+/// });
+/// ```
+#[macro_export]
+macro_rules! export_manifest {
+    ($fields:expr) => {
+        const _: () = {
+            const FIELDS: $crate::manifest::ManifestFields<'static> = $fields;
+            const LEN: usize = $crate::manifest::encoded_len(&FIELDS);
+
+            // Must match `arborium_wire::MANIFEST_SECTION`
+            #[used]
+            #[unsafe(link_section = "arborium_manifest")]
+            static MANIFEST: [u8; LEN] = $crate::manifest::encode::<LEN>(&FIELDS);
+        };
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use arborium_wire::PluginManifest;
+
+    const FIELDS: ManifestFields<'static> = ManifestFields {
+        wire_version: 300,
+        language_id: "rust",
+        aliases: &["rs"],
+        extensions: &["rust", "rs"],
+        provides_injections: true,
+        provides_locals: false,
+        query_hash: "0123456789abcdef",
+        grammar_commit: "src-fedcba9876543210",
+        version: "2.4.1",
+    };
+    const BYTES: [u8; encoded_len(&FIELDS)] = encode(&FIELDS);
+
+    #[test]
+    fn test_encoding_matches_postcard() {
+        let decoded: PluginManifest = postcard::from_bytes(&BYTES).unwrap();
+        let expected = PluginManifest {
+            wire_version: 300,
+            language_id: "rust".to_string(),
+            aliases: vec!["rs".to_string()],
+            extensions: vec!["rust".to_string(), "rs".to_string()],
+            provides_injections: true,
+            provides_locals: false,
+            query_hash: "0123456789abcdef".to_string(),
+            grammar_commit: "src-fedcba9876543210".to_string(),
+            version: "2.4.1".to_string(),
+        };
+        assert_eq!(decoded, expected);
+        assert_eq!(&BYTES[..], &postcard::to_allocvec(&expected).unwrap()[..]);
+    }
+
+    #[test]
+    fn test_long_strings_use_multibyte_lengths() {
+        let long = "x".repeat(200);
+        let fields = ManifestFields {
+            language_id: &long,
+            ..FIELDS
+        };
+        assert_eq!(encoded_len(&fields), encoded_len(&FIELDS) + 200 - 4 + 1);
+        let bytes = encode::<{ encoded_len(&FIELDS) + 200 - 4 + 1 }>(&fields);
+        let decoded: PluginManifest = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.language_id, long);
+    }
+}
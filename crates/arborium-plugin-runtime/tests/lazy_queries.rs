@@ -0,0 +1,163 @@
+//! Lazy query compilation tests.
+//!
+//! These run natively with `cargo test`, and in a browser with
+//! `wasm-pack test --headless --firefox crates/arborium-plugin-runtime`.
+
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
+use arborium_wire::{ParseErrorKind, QueryKind};
+use wasm_bindgen_test::*;
+
+const SOURCE: &str = "/// Docs\npub fn main() -> u32 {\n    let x = \"hi\";\n    42\n}\n";
+
+fn eager_config() -> HighlightConfig {
+    HighlightConfig::new(
+        arborium_rust::language(),
+        &arborium_rust::HIGHLIGHTS_QUERY,
+        arborium_rust::INJECTIONS_QUERY,
+        arborium_rust::LOCALS_QUERY,
+    )
+    .expect("failed to create config")
+}
+
+fn lazy_config() -> HighlightConfig {
+    HighlightConfig::lazy(
+        arborium_rust::language(),
+        &arborium_rust::HIGHLIGHTS_QUERY,
+        arborium_rust::INJECTIONS_QUERY,
+        arborium_rust::LOCALS_QUERY,
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_lazy_compiles_on_first_parse() {
+    let mut runtime = PluginRuntime::new(lazy_config());
+    let session = runtime.create_session();
+    runtime.set_text(session, SOURCE);
+    assert!(!runtime.config().is_compiled());
+
+    let lazy = runtime.parse(session).expect("parse");
+    assert!(runtime.config().is_compiled());
+
+    let mut eager = PluginRuntime::new(eager_config());
+    let eager_session = eager.create_session();
+    eager.set_text(eager_session, SOURCE);
+    assert_eq!(lazy, eager.parse(eager_session).expect("parse"));
+}
+
+#[wasm_bindgen_test]
+fn test_precompile() {
+    let config = lazy_config();
+    assert!(!config.is_compiled());
+    config.precompile().expect("precompile");
+    assert!(config.is_compiled());
+    assert_eq!(config.capture_names(), eager_config().capture_names());
+    assert!(eager_config().is_compiled());
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_query_fails_at_parse() {
+    let config =
+        HighlightConfig::lazy(arborium_rust::language(), "(no_such_node) @keyword", "", "");
+    let mut runtime = PluginRuntime::new(config);
+    let session = runtime.create_session();
+    runtime.set_text(session, SOURCE);
+
+    let err = runtime.parse(session).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::Query);
+    // The error sticks rather than recompiling every time
+    assert_eq!(runtime.parse_utf16(session).unwrap_err(), err);
+    assert_eq!(runtime.config().precompile().unwrap_err(), err);
+    assert!(runtime.config().capture_names().is_empty());
+    // The sources are still there to look at
+    assert_eq!(
+        runtime
+            .config()
+            .query_source(QueryKind::Highlights)
+            .unwrap(),
+        "(no_such_node) @keyword"
+    );
+}
+
+#[cfg(feature = "compressed-queries")]
+#[wasm_bindgen_test]
+fn test_deflated_queries() {
+    use miniz_oxide::deflate::compress_to_vec;
+
+    let highlights = compress_to_vec(arborium_rust::HIGHLIGHTS_QUERY.as_bytes(), 10);
+    let injections = compress_to_vec(arborium_rust::INJECTIONS_QUERY.as_bytes(), 10);
+    let locals = compress_to_vec(arborium_rust::LOCALS_QUERY.as_bytes(), 10);
+    let config = HighlightConfig::deflated(
+        arborium_rust::language(),
+        highlights.leak(),
+        injections.leak(),
+        locals.leak(),
+    );
+    assert_eq!(
+        config.query_source(QueryKind::Highlights).unwrap(),
+        &*arborium_rust::HIGHLIGHTS_QUERY
+    );
+
+    let mut runtime = PluginRuntime::new(config);
+    let session = runtime.create_session();
+    runtime.set_text(session, SOURCE);
+    let deflated = runtime.parse(session).expect("parse");
+
+    let mut eager = PluginRuntime::new(eager_config());
+    let eager_session = eager.create_session();
+    eager.set_text(eager_session, SOURCE);
+    assert_eq!(deflated, eager.parse(eager_session).expect("parse"));
+}
+
+#[cfg(feature = "compressed-queries")]
+#[wasm_bindgen_test]
+fn test_corrupt_deflated_queries() {
+    use miniz_oxide::deflate::compress_to_vec;
+
+    let empty: &'static [u8] = compress_to_vec(b"", 10).leak();
+    let not_utf8 = compress_to_vec(b"(identifier) @\xff", 10);
+    let cases: [(&'static [u8], &str); 2] = [
+        (b"not a deflate stream", "corrupt compressed query"),
+        (not_utf8.leak(), "compressed query is not UTF-8"),
+    ];
+    for (highlights, message) in cases {
+        let config = HighlightConfig::deflated(arborium_rust::language(), highlights, empty, empty);
+        let err = config.precompile().unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Query);
+        assert!(err.message.starts_with(message), "{}", err.message);
+        assert!(config.is_compiled());
+        assert!(config.capture_names().is_empty());
+        assert_eq!(config.query_source(QueryKind::Locals).unwrap_err(), err);
+
+        // Sessions get the error instead of a trap
+        let mut runtime = PluginRuntime::new(config);
+        let session = runtime.create_session();
+        runtime.set_text(session, SOURCE);
+        assert_eq!(runtime.parse(session).unwrap_err(), err);
+    }
+}
+
+/// Reports how long it takes until the first session has its text, and
+/// until the first parse, with eager and lazy configs. Run with
+/// `--nocapture` to see the numbers.
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_startup_time() {
+    use std::time::{Duration, Instant};
+
+    fn measure(config: impl FnOnce() -> HighlightConfig) -> (Duration, Duration) {
+        let start = Instant::now();
+        let mut runtime = PluginRuntime::new(config());
+        let session = runtime.create_session();
+        runtime.set_text(session, SOURCE);
+        let ready = start.elapsed();
+        runtime.parse(session).expect("parse");
+        (ready, start.elapsed())
+    }
+
+    let (eager_ready, eager_parsed) = measure(eager_config);
+    let (lazy_ready, lazy_parsed) = measure(lazy_config);
+    eprintln!("eager: text set after {eager_ready:?}, first parse after {eager_parsed:?}");
+    eprintln!("lazy:  text set after {lazy_ready:?}, first parse after {lazy_parsed:?}");
+    // Compiling the rust queries takes milliseconds, setting text doesn't
+    assert!(lazy_ready < eager_ready);
+}
@@ -0,0 +1,71 @@
+//! Query introspection tests.
+//!
+//! These run natively with `cargo test`, and in a browser with
+//! `wasm-pack test --headless --firefox crates/arborium-plugin-runtime`.
+
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
+use arborium_wire::QueryKind;
+use wasm_bindgen_test::*;
+
+fn rust_config() -> HighlightConfig {
+    HighlightConfig::new(
+        arborium_rust::language(),
+        &arborium_rust::HIGHLIGHTS_QUERY,
+        arborium_rust::INJECTIONS_QUERY,
+        arborium_rust::LOCALS_QUERY,
+    )
+    .expect("failed to create config")
+}
+
+#[wasm_bindgen_test]
+fn test_spans_use_listed_capture_names() {
+    let mut runtime = PluginRuntime::new(rust_config());
+    let session = runtime.create_session();
+    runtime.set_text(
+        session,
+        "/// Docs\npub fn main() -> u32 {\n    let x = \"hi\";\n    42\n}\n",
+    );
+
+    let result = runtime.parse(session).expect("parse");
+    assert!(!result.spans.is_empty());
+    let names = runtime.config().capture_names();
+    for span in &result.spans {
+        assert!(
+            names.contains(&span.capture.as_str()),
+            "`{}` not in {:?}",
+            span.capture,
+            names
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_query_sources_and_hash() {
+    let config = rust_config();
+    assert_eq!(
+        config.query_source(QueryKind::Highlights).unwrap(),
+        &*arborium_rust::HIGHLIGHTS_QUERY
+    );
+    assert_eq!(
+        config.query_source(QueryKind::Injections).unwrap(),
+        arborium_rust::INJECTIONS_QUERY
+    );
+    assert_eq!(
+        config.query_source(QueryKind::Locals).unwrap(),
+        arborium_rust::LOCALS_QUERY
+    );
+    assert_eq!(config.query_hash(), "");
+
+    let config = config.with_query_hash("0123456789abcdef");
+    assert_eq!(config.query_hash(), "0123456789abcdef");
+}
+
+#[wasm_bindgen_test]
+fn test_query_kind_names() {
+    for kind in QueryKind::ALL {
+        assert_eq!(QueryKind::from_name(kind.name()), Some(kind));
+        assert_eq!(QueryKind::from_u32(kind.to_u32()), Some(kind));
+    }
+    assert_eq!(QueryKind::from_name("folds"), None);
+    assert_eq!(QueryKind::from_u32(3), None);
+}
@@ -0,0 +1,118 @@
+//! Syntax tree inspection tests.
+//!
+//! These run natively with `cargo test`, and in a browser with
+//! `wasm-pack test --headless --firefox crates/arborium-plugin-runtime`.
+
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime, TreeLimits};
+use arborium_wire::SyntaxNode;
+use wasm_bindgen_test::*;
+
+fn rust_runtime() -> PluginRuntime {
+    let config = HighlightConfig::new(
+        arborium_rust::language(),
+        &arborium_rust::HIGHLIGHTS_QUERY,
+        arborium_rust::INJECTIONS_QUERY,
+        arborium_rust::LOCALS_QUERY,
+    )
+    .expect("failed to create config");
+    PluginRuntime::new(config)
+}
+
+fn any_node(node: &SyntaxNode, predicate: &dyn Fn(&SyntaxNode) -> bool) -> bool {
+    predicate(node) || node.children.iter().any(|c| any_node(c, predicate))
+}
+
+#[wasm_bindgen_test]
+fn test_parse_tree_root_kind() {
+    let mut runtime = rust_runtime();
+    let session = runtime.create_session();
+    runtime.set_text(session, "fn main() {\n    let x = 1;\n}\n");
+
+    let tree = runtime
+        .syntax_tree(session, &TreeLimits::default())
+        .expect("syntax tree");
+    assert_eq!(tree.root.kind, "source_file");
+    assert!(!tree.truncated);
+
+    let function = &tree.root.children[0];
+    assert_eq!(function.kind, "function_item");
+    assert_eq!((function.start_row, function.end_row), (0, 2));
+    assert!(any_node(&tree.root, &|n| n.kind == "let_declaration"));
+    assert!(!any_node(&tree.root, &|n| n.is_error || n.is_missing));
+
+    // Anonymous nodes (`fn`, `{`, ...) are left out
+    assert!(!any_node(&tree.root, &|n| n.kind == "fn"));
+}
+
+#[wasm_bindgen_test]
+fn test_parse_tree_error_flags() {
+    let mut runtime = rust_runtime();
+    let session = runtime.create_session();
+    runtime.set_text(session, "fn main() { let x = ; }\nfn f( {}\n");
+
+    let tree = runtime
+        .syntax_tree(session, &TreeLimits::default())
+        .expect("syntax tree");
+    assert!(any_node(&tree.root, &|n| n.is_error || n.is_missing));
+}
+
+#[wasm_bindgen_test]
+fn test_parse_tree_limits() {
+    let mut runtime = rust_runtime();
+    let session = runtime.create_session();
+    runtime.set_text(session, "fn a() {}\nfn b() {}\nfn c() {}\n");
+
+    let shallow = runtime
+        .syntax_tree(
+            session,
+            &TreeLimits {
+                max_depth: 1,
+                max_nodes: 1000,
+            },
+        )
+        .unwrap();
+    assert!(shallow.truncated);
+    assert_eq!(shallow.root.children.len(), 3);
+    assert!(shallow.root.children.iter().all(|c| c.children.is_empty()));
+
+    let capped = runtime
+        .syntax_tree(
+            session,
+            &TreeLimits {
+                max_depth: 64,
+                max_nodes: 3,
+            },
+        )
+        .unwrap();
+    assert!(capped.truncated);
+    assert_eq!(capped.node_count, 3);
+}
+
+#[wasm_bindgen_test]
+fn test_node_at_spine() {
+    let mut runtime = rust_runtime();
+    let session = runtime.create_session();
+    let source = "fn main() { let answer = 42; }";
+    runtime.set_text(session, source);
+
+    let offset = source.find("42").unwrap() as u32;
+    let spine = runtime.node_at(session, offset).unwrap();
+    let kinds: Vec<_> = spine.iter().map(|n| n.kind.as_str()).collect();
+
+    assert_eq!(kinds.first(), Some(&"source_file"));
+    assert_eq!(kinds.last(), Some(&"integer_literal"));
+    assert!(kinds.contains(&"let_declaration"));
+    assert!(spine.iter().all(|n| n.start <= offset && offset <= n.end));
+}
+
+#[wasm_bindgen_test]
+fn test_tree_requires_text() {
+    let mut runtime = rust_runtime();
+    let session = runtime.create_session();
+    assert!(
+        runtime
+            .syntax_tree(session, &TreeLimits::default())
+            .is_err()
+    );
+    assert!(runtime.node_at(999, 0).is_err());
+}
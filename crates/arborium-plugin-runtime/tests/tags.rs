@@ -0,0 +1,192 @@
+//! Symbol tagging tests.
+//!
+//! These run natively with `cargo test`, and in a browser with
+//! `wasm-pack test --headless --firefox crates/arborium-plugin-runtime`.
+
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
+use arborium_wire::Utf8Tag;
+use wasm_bindgen_test::*;
+
+fn tags(config: HighlightConfig, text: &str) -> Vec<Utf8Tag> {
+    let mut runtime = PluginRuntime::new(config);
+    let session = runtime.create_session();
+    runtime.set_text(session, text);
+    runtime.tags(session).expect("tags")
+}
+
+/// The definitions among `tags` as `kind name` lines, indented by how many
+/// other definitions contain them.
+fn outline(tags: &[Utf8Tag]) -> Vec<String> {
+    let definitions: Vec<_> = tags.iter().filter(|tag| tag.is_definition).collect();
+    definitions
+        .iter()
+        .map(|tag| {
+            let depth = definitions
+                .iter()
+                .filter(|outer| {
+                    outer.start <= tag.start
+                        && tag.end <= outer.end
+                        && outer.name_start != tag.name_start
+                })
+                .count();
+            format!("{}{} {}", "  ".repeat(depth), tag.kind, tag.name)
+        })
+        .collect()
+}
+
+const RUST: &str = r#"mod geometry {
+    pub struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    impl Point {
+        pub fn origin() -> Self {
+            Point { x: 0.0, y: 0.0 }
+        }
+
+        pub fn distance(&self, other: &Point) -> f64 {
+            ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+        }
+    }
+}
+
+enum Shape {
+    Circle(f64),
+}
+
+fn main() {
+    let p = geometry::Point::origin();
+    println!("{}", p.distance(&p));
+}
+"#;
+
+const PYTHON: &str = r#"import math
+
+TAU = 2 * math.pi
+
+
+class Circle:
+    def __init__(self, radius):
+        self.radius = radius
+
+    @property
+    def area(self):
+        return math.pi * self.radius ** 2
+
+
+class Square:
+    def area(self):
+        return self.side * self.side
+
+
+def describe(shape):
+    print(shape.area)
+"#;
+
+fn rust_config() -> HighlightConfig {
+    HighlightConfig::new(
+        arborium_rust::language(),
+        &arborium_rust::HIGHLIGHTS_QUERY,
+        arborium_rust::INJECTIONS_QUERY,
+        arborium_rust::LOCALS_QUERY,
+    )
+    .expect("failed to create config")
+    .with_tags(arborium_rust::TAGS_QUERY)
+}
+
+fn python_config() -> HighlightConfig {
+    HighlightConfig::new(
+        arborium_python::language(),
+        &arborium_python::HIGHLIGHTS_QUERY,
+        arborium_python::INJECTIONS_QUERY,
+        arborium_python::LOCALS_QUERY,
+    )
+    .expect("failed to create config")
+    .with_tags(arborium_python::TAGS_QUERY)
+}
+
+#[wasm_bindgen_test]
+fn test_rust_outline() {
+    let tags = tags(rust_config(), RUST);
+    // Methods sit in the `impl`, which is a reference rather than part of
+    // the struct
+    assert_eq!(
+        outline(&tags),
+        [
+            "module geometry",
+            "  class Point",
+            "  method origin",
+            "  method distance",
+            "class Shape",
+            "function main",
+        ]
+    );
+
+    // Names point into the text
+    let main = tags.iter().find(|tag| tag.name == "main").unwrap();
+    assert_eq!(
+        &RUST[main.name_start as usize..main.name_end as usize],
+        "main"
+    );
+    assert!(RUST[main.start as usize..main.end as usize].starts_with("fn main()"));
+}
+
+#[wasm_bindgen_test]
+fn test_rust_references() {
+    let tags = tags(rust_config(), RUST);
+    let calls: Vec<_> = tags
+        .iter()
+        .filter(|tag| !tag.is_definition && tag.kind == "call")
+        .map(|tag| tag.name.as_str())
+        .collect();
+    assert!(calls.contains(&"println"), "calls: {calls:?}");
+    assert!(calls.contains(&"distance"), "calls: {calls:?}");
+    assert!(
+        tags.iter()
+            .any(|tag| tag.kind == "implementation" && tag.name == "Point")
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_python_outline() {
+    let tags = tags(python_config(), PYTHON);
+    assert_eq!(
+        outline(&tags),
+        [
+            "constant TAU",
+            "class Circle",
+            "  function __init__",
+            "  function area",
+            "class Square",
+            "  function area",
+            "function describe",
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_no_tags_query() {
+    let config = HighlightConfig::new(
+        arborium_rust::language(),
+        &arborium_rust::HIGHLIGHTS_QUERY,
+        arborium_rust::INJECTIONS_QUERY,
+        arborium_rust::LOCALS_QUERY,
+    )
+    .expect("failed to create config");
+    assert_eq!(config.tags_query(), "");
+    assert!(tags(config, RUST).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_tags_query() {
+    let config = rust_config().with_tags("(no_such_node) @definition.function");
+    let mut runtime = PluginRuntime::new(config);
+    let session = runtime.create_session();
+    runtime.set_text(session, RUST);
+
+    let error = runtime.tags(session).unwrap_err();
+    assert!(error.message.contains("tags query"), "{}", error.message);
+    // Highlighting is unaffected
+    assert!(!runtime.parse(session).expect("parse").spans.is_empty());
+}
@@ -0,0 +1,273 @@
+//! Highlight assertion files.
+//!
+//! The format is tree-sitter's own: source files where comments point at
+//! the line above them and say which capture it should get there.
+//!
+//! ```text
+//! pub fn main() {
+//! // <- keyword
+//! //     ^ function
+//! //  ^ !function
+//! }
+//! ```
+//!
+//! `<-` points at the column the comment starts in, each `^` at its own
+//! column, both on the closest line above that isn't an assertion. Columns
+//! are counted in chars. The position must be covered by a span whose
+//! capture is the expected one, or starts with it followed by a `.`; with a
+//! `!`, by no such span. Injected languages get no spans, so assertions
+//! only work on the grammar's own text.
+//!
+//! Grammars opt in by putting such files in `def/test/highlight/`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
+use arborium_tree_sitter::Language;
+use arborium_wire::Utf8ParseResult;
+
+use crate::snapshot::checked_in_dir;
+use crate::{HarnessError, HarnessResult};
+
+/// Line comment openers recognized by default. Block comment openers work
+/// too, since anything after the capture name is ignored.
+pub const DEFAULT_COMMENT_PREFIXES: &[&str] = &[
+    "//", "#", "--", ";", "%", "/*", "<!--", "{-", "(*", "\"", "'", "!",
+];
+
+/// One assertion from an assertion file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assertion {
+    /// 1-based line of the assertion comment.
+    pub line: usize,
+    /// Byte offset in the source the assertion points at.
+    pub offset: usize,
+    /// Expected capture, without `@`.
+    pub capture: String,
+    /// Whether the capture must not be there.
+    pub negated: bool,
+}
+
+/// Directory holding a grammar's highlight assertion files, if any.
+pub fn assertion_dir(crate_dir: &Path) -> Option<PathBuf> {
+    checked_in_dir(crate_dir, "test/highlight")
+}
+
+/// Parse the assertions out of `source`, whose comments start with one of
+/// `comment_prefixes`.
+pub fn parse_assertions(source: &str, comment_prefixes: &[&str]) -> Result<Vec<Assertion>, String> {
+    let mut assertions = Vec::new();
+    // Start offset and text of the last line that isn't an assertion
+    let mut target: Option<(usize, &str)> = None;
+    let mut line_start = 0;
+
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        let start = line_start;
+        line_start += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+        let Some(pointers) = assertion_pointers(line, comment_prefixes) else {
+            target = Some((start, line));
+            continue;
+        };
+
+        let line_number = index + 1;
+        let (target_start, target_line) = target.ok_or_else(|| {
+            format!(
+                "line {}: assertion with no line above it to point at",
+                line_number
+            )
+        })?;
+        for (column, capture, negated) in pointers {
+            let offset = target_line
+                .char_indices()
+                .nth(column)
+                .map(|(i, _)| target_start + i)
+                .ok_or_else(|| {
+                    format!(
+                        "line {}: assertion points at column {}, past the end of the line above",
+                        line_number,
+                        column + 1
+                    )
+                })?;
+            assertions.push(Assertion {
+                line: line_number,
+                offset,
+                capture: capture.to_string(),
+                negated,
+            });
+        }
+    }
+    Ok(assertions)
+}
+
+/// If `line` is an assertion, the char columns it points at, with the
+/// expected capture and whether it's negated.
+fn assertion_pointers<'a>(
+    line: &'a str,
+    comment_prefixes: &[&str],
+) -> Option<Vec<(usize, &'a str, bool)>> {
+    let indent = line.len() - line.trim_start().len();
+    let comment = &line[indent..];
+    let prefix = comment_prefixes
+        .iter()
+        .filter(|p| comment.starts_with(**p))
+        .max_by_key(|p| p.len())?;
+    // Skip doubled openers like `///` or `;;`
+    let body = comment[prefix.len()..].trim_start_matches(|c| prefix.contains(c));
+    let arrow = body.trim_start();
+    let arrow_start = line.len() - arrow.len();
+
+    let (columns, rest) = if let Some(rest) = arrow.strip_prefix("<-") {
+        let column = line[..indent].chars().count();
+        (vec![column], rest)
+    } else if arrow.starts_with('^') {
+        let carets = arrow.len() - arrow.trim_start_matches('^').len();
+        let first = line[..arrow_start].chars().count();
+        ((first..first + carets).collect(), &arrow[carets..])
+    } else {
+        return None;
+    };
+
+    let name = rest.split_whitespace().next()?;
+    let (name, negated) = match name.strip_prefix('!') {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    let name = name.strip_prefix('@').unwrap_or(name);
+    if name.is_empty() {
+        return None;
+    }
+    Some(columns.into_iter().map(|c| (c, name, negated)).collect())
+}
+
+/// Whether `capture` satisfies an assertion expecting `expected`.
+pub fn capture_matches(capture: &str, expected: &str) -> bool {
+    capture
+        .strip_prefix(expected)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Check `assertions` against the result of highlighting `source`,
+/// returning a description of each one that fails.
+pub fn failed_assertions(
+    source: &str,
+    result: &Utf8ParseResult,
+    assertions: &[Assertion],
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    for assertion in assertions {
+        let covering: Vec<&str> = result
+            .spans
+            .iter()
+            .filter(|s| (s.start as usize) <= assertion.offset && assertion.offset < s.end as usize)
+            .map(|s| s.capture.as_str())
+            .collect();
+        let found = covering
+            .iter()
+            .any(|&capture| capture_matches(capture, &assertion.capture));
+        if found != assertion.negated {
+            continue;
+        }
+
+        let at = source[assertion.offset..].chars().next().unwrap_or(' ');
+        let found = if covering.is_empty() {
+            "no highlight".to_string()
+        } else {
+            covering
+                .iter()
+                .map(|c| format!("@{}", c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        failures.push(format!(
+            "line {}: expected {}@{} at {:?} (byte {}), found {}",
+            assertion.line,
+            if assertion.negated { "no " } else { "" },
+            assertion.capture,
+            at,
+            assertion.offset,
+            found
+        ));
+    }
+    failures
+}
+
+/// Run every assertion file of the `name` grammar through `runtime`.
+pub fn check_highlight_assertions(
+    runtime: &mut PluginRuntime,
+    name: &str,
+    crate_dir: &Path,
+    comment_prefixes: &[&str],
+) -> HarnessResult {
+    let Some(dir) = assertion_dir(crate_dir) else {
+        return Ok(());
+    };
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(|e| HarnessError::new(format!("Failed to read {}: {}", dir.display(), e)))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+
+    let session = runtime.create_session();
+    let mut failures = Vec::new();
+    for path in &files {
+        let source = fs::read_to_string(path)
+            .map_err(|e| HarnessError::new(format!("Failed to read {}: {}", path.display(), e)))?;
+        let checked = parse_assertions(&source, comment_prefixes).and_then(|assertions| {
+            runtime.set_text(session, &source);
+            let result = runtime.parse(session).map_err(|e| e.message)?;
+            Ok(failed_assertions(&source, &result, &assertions))
+        });
+        match checked {
+            Ok(failed) => failures.extend(
+                failed
+                    .into_iter()
+                    .map(|f| format!("{}: {}", path.display(), f)),
+            ),
+            Err(e) => failures.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    runtime.free_session(session);
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+    Err(HarnessError::new(format!(
+        "Highlight assertions failed for {}:\n  {}",
+        name,
+        failures.join("\n  ")
+    )))
+}
+
+/// Runs [`check_highlight_assertions`] for a grammar whose comments don't
+/// start with one of the [`DEFAULT_COMMENT_PREFIXES`].
+///
+/// [`test_grammar`](crate::test_grammar) already runs the assertion files
+/// with the default prefixes.
+///
+/// # Panics
+///
+/// Panics if the queries don't compile, or an assertion fails.
+pub fn test_highlight_assertions(
+    language: impl Into<Language>,
+    name: &str,
+    highlights_query: &str,
+    injections_query: &str,
+    locals_query: &str,
+    crate_dir: &str,
+    comment_prefixes: &[&str],
+) {
+    let config = HighlightConfig::new(language, highlights_query, injections_query, locals_query)
+        .unwrap_or_else(|e| {
+            panic!("Failed to create plugin config for {}: {:?}", name, e);
+        });
+    let mut runtime = PluginRuntime::new(config);
+    if let Err(e) =
+        check_highlight_assertions(&mut runtime, name, Path::new(crate_dir), comment_prefixes)
+    {
+        panic!("{}", e);
+    }
+}
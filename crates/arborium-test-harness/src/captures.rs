@@ -0,0 +1,88 @@
+//! Capture-name validation.
+//!
+//! A capture that maps to no theme slot and isn't one of the
+//! [`HIGHLIGHT_NAMES`](crate::HIGHLIGHT_NAMES) renders unstyled, which is
+//! almost always a typo or a name from another editor's vocabulary.
+//! Grammars can list captures that are unstyled on purpose in
+//! `capture-allowlist.txt`, one per line; those are reported as warnings.
+
+use std::path::Path;
+
+use arborium_theme::{CAPTURE_NAMES, ThemeSlot, capture_to_slot};
+use arborium_tree_sitter::{Language, Query};
+
+use crate::{HarnessError, HarnessResult, mentions_token, pattern_location, read_allowlist};
+
+/// Name of the per-grammar allowlist of intentionally unstyled captures.
+pub const CAPTURE_ALLOWLIST: &str = "capture-allowlist.txt";
+
+/// Whether a capture gets styled, or is deliberately left alone.
+///
+/// Captures resolve if they map to a theme slot, or if they match one of the
+/// highlight names, exactly or as a dotted prefix (`@none`, `@spell`, ...).
+/// Captures starting with `_`, `injection.` or `local.` never reach themes
+/// and are ignored.
+pub fn capture_resolves(capture: &str) -> bool {
+    if capture.starts_with('_')
+        || capture.starts_with("injection.")
+        || capture.starts_with("local.")
+    {
+        return true;
+    }
+    if capture_to_slot(capture) != ThemeSlot::None {
+        return true;
+    }
+    CAPTURE_NAMES.iter().any(|name| {
+        capture
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+/// Check that every capture in `highlights_query` resolves, see
+/// [`capture_resolves`].
+///
+/// Captures in the grammar's allowlist are only warned about.
+pub fn check_capture_names(
+    language: &Language,
+    name: &str,
+    highlights_query: &str,
+    crate_dir: &Path,
+) -> HarnessResult {
+    if highlights_query.is_empty() {
+        return Ok(());
+    }
+    let query = Query::new(language, highlights_query).map_err(|e| {
+        HarnessError::new(format!(
+            "Failed to compile highlights query for {}: {:?}",
+            name, e
+        ))
+    })?;
+    let allowlist = read_allowlist(crate_dir, CAPTURE_ALLOWLIST);
+
+    let mut unresolved = Vec::new();
+    for capture in query.capture_names() {
+        if capture_resolves(capture) {
+            continue;
+        }
+        let needle = format!("@{}", capture);
+        let location = pattern_location(&query, highlights_query, |p| mentions_token(p, &needle));
+        let report = format!("@{} (highlights.scm {})", capture, location);
+        if allowlist.contains(*capture) {
+            eprintln!("warning: {} leaves {} unstyled", name, report);
+        } else {
+            unresolved.push(report);
+        }
+    }
+
+    if unresolved.is_empty() {
+        return Ok(());
+    }
+    Err(HarnessError::new(format!(
+        "Captures in the {} highlights query map to no highlight, so they render unstyled:\n  {}\n\
+         Rename them to one of arborium_theme::CAPTURE_NAMES, or list them in {} if that's intended.",
+        name,
+        unresolved.join("\n  "),
+        CAPTURE_ALLOWLIST
+    )))
+}
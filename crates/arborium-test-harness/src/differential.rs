@@ -0,0 +1,192 @@
+//! Differential checks between the two highlighting pipelines.
+//!
+//! The HTML renderer highlights through [`CompiledGrammar`], plugins through
+//! [`PluginRuntime`](arborium_plugin_runtime::PluginRuntime), and the two handle queries separately: the runtime
+//! runs injections, locals and highlights as one query, and they disagree
+//! on which of a captured and a `#set!` injection language wins. Each
+//! sample is highlighted both ways, both results are reduced to the
+//! highlights that would render (range and theme slot) and the injections
+//! (range and language), and any difference is reported.
+//!
+//! Known differences go in `differential-expectations.txt`, one per line:
+//! `runtime-only <highlight>` or `renderer-only <highlight>`, where the
+//! highlight is a slot name like `keyword`, or `injection(<language>)`.
+//! Those are left out of the comparison.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use arborium_highlight::{CompiledGrammar, ParseContext, ParseResult};
+use arborium_theme::highlights::{tag_for_capture, tag_to_name};
+use arborium_wire::Utf8ParseResult;
+
+use crate::invariants::context;
+use crate::{HarnessError, HarnessResult, read_allowlist};
+
+/// Name of the per-grammar file of known differences between the
+/// pipelines.
+pub const DIFFERENTIAL_EXPECTATIONS: &str = "differential-expectations.txt";
+
+/// Most discrepancies shown per sample.
+const MAX_REPORTED: usize = 20;
+
+/// Which pipeline produced a highlight the other didn't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Side {
+    /// [`PluginRuntime`](arborium_plugin_runtime::PluginRuntime)
+    Runtime,
+    /// [`CompiledGrammar`], used by the HTML renderer
+    Renderer,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Runtime => write!(f, "runtime-only"),
+            Side::Renderer => write!(f, "renderer-only"),
+        }
+    }
+}
+
+/// A highlight, as both pipelines can be compared on: its range, and the
+/// slot name it renders as or `injection(<language>)`.
+pub type Highlight = (u32, u32, String);
+
+/// A highlight only one of the pipelines produced.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Discrepancy {
+    pub side: Side,
+    pub highlight: Highlight,
+}
+
+/// Reduce highlights to the ones that render, keyed by slot name rather
+/// than capture, plus injections.
+fn normalize<'a>(
+    spans: impl Iterator<Item = (u32, u32, &'a str)>,
+    injections: impl Iterator<Item = (u32, u32, &'a str)>,
+) -> BTreeSet<Highlight> {
+    let spans = spans.filter_map(|(start, end, capture)| {
+        let name = tag_for_capture(capture).and_then(tag_to_name)?;
+        (start < end).then(|| (start, end, name.to_string()))
+    });
+    let injections =
+        injections.map(|(start, end, language)| (start, end, format!("injection({})", language)));
+    spans.chain(injections).collect()
+}
+
+/// Normalize a [`PluginRuntime`](arborium_plugin_runtime::PluginRuntime)
+/// result.
+pub fn normalize_runtime(result: &Utf8ParseResult) -> BTreeSet<Highlight> {
+    normalize(
+        result
+            .spans
+            .iter()
+            .map(|s| (s.start, s.end, s.capture.as_str())),
+        result
+            .injections
+            .iter()
+            .map(|i| (i.start, i.end, i.language.as_str())),
+    )
+}
+
+/// Normalize a [`CompiledGrammar`] result.
+pub fn normalize_renderer(result: &ParseResult) -> BTreeSet<Highlight> {
+    normalize(
+        result
+            .spans
+            .iter()
+            .map(|s| (s.start, s.end, s.capture.as_str())),
+        result
+            .injections
+            .iter()
+            .map(|i| (i.start, i.end, i.language.as_str())),
+    )
+}
+
+/// Highlights only one of the pipelines produced, in source order.
+pub fn discrepancies(
+    runtime: &BTreeSet<Highlight>,
+    renderer: &BTreeSet<Highlight>,
+) -> Vec<Discrepancy> {
+    let only = |side, ours: &BTreeSet<Highlight>, theirs: &BTreeSet<Highlight>| {
+        ours.difference(theirs)
+            .map(|highlight| Discrepancy {
+                side,
+                highlight: highlight.clone(),
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut found = only(Side::Runtime, runtime, renderer);
+    found.extend(only(Side::Renderer, renderer, runtime));
+    found.sort_by(|a, b| (&a.highlight, a.side).cmp(&(&b.highlight, b.side)));
+    found
+}
+
+/// Read a grammar's known differences, as `(side, highlight name)` pairs.
+fn read_expectations(crate_dir: &Path) -> HashSet<(Side, String)> {
+    read_allowlist(crate_dir, DIFFERENTIAL_EXPECTATIONS)
+        .iter()
+        .filter_map(|line| {
+            let (side, name) = line.split_once(char::is_whitespace)?;
+            let side = match side {
+                "runtime-only" => Side::Runtime,
+                "renderer-only" => Side::Renderer,
+                _ => return None,
+            };
+            Some((side, name.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Highlight `source` with `grammar` and compare the result against the
+/// runtime's `runtime_result` for the same text.
+pub fn check_differential(
+    grammar: &CompiledGrammar,
+    parse_context: &mut ParseContext,
+    name: &str,
+    sample: &Path,
+    source: &str,
+    runtime_result: &Utf8ParseResult,
+    crate_dir: &Path,
+) -> HarnessResult {
+    let renderer_result = grammar.parse(parse_context, source);
+    let expected = read_expectations(crate_dir);
+    let found: Vec<Discrepancy> = discrepancies(
+        &normalize_runtime(runtime_result),
+        &normalize_renderer(&renderer_result),
+    )
+    .into_iter()
+    .filter(|d| !expected.contains(&(d.side, d.highlight.2.clone())))
+    .collect();
+    if found.is_empty() {
+        return Ok(());
+    }
+
+    let mut report: Vec<String> = found
+        .iter()
+        .take(MAX_REPORTED)
+        .map(|d| {
+            let (start, end, highlight) = &d.highlight;
+            format!(
+                "{} {} {}..{}\n{}",
+                d.side,
+                highlight,
+                start,
+                end,
+                context(source, *start as usize, *end as usize)
+            )
+        })
+        .collect();
+    if found.len() > MAX_REPORTED {
+        report.push(format!("... and {} more", found.len() - MAX_REPORTED));
+    }
+    Err(HarnessError::new(format!(
+        "The plugin runtime and the renderer highlight {} differently for {}:\n{}\n\
+         Fix whichever is wrong, or document the difference in {}.",
+        sample.display(),
+        name,
+        report.join("\n"),
+        DIFFERENTIAL_EXPECTATIONS
+    )))
+}
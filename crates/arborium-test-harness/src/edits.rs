@@ -0,0 +1,370 @@
+//! Incremental edit checks.
+//!
+//! Applies random edit sequences to seed documents through
+//! [`PluginRuntime::apply_edit`], and after every edit compares the result
+//! with a fresh parse of the same text and checks its
+//! [span invariants](crate::invariants). Edits land on random char
+//! boundaries, insert multi-line and multibyte text, and delete across
+//! lines.
+//!
+//! Runs are deterministic for a given seed. Set `ARBORIUM_EDIT_SEED` to try
+//! another one and `ARBORIUM_EDIT_STEPS` to change how many edits are
+//! applied to each document.
+
+use std::fmt;
+
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
+use arborium_tree_sitter::Language;
+use arborium_wire::{Edit, Utf8ParseResult};
+
+use crate::invariants::span_violations;
+use crate::{HarnessError, HarnessResult};
+
+/// Environment variable overriding the random seed.
+pub const EDIT_SEED_ENV: &str = "ARBORIUM_EDIT_SEED";
+
+/// Environment variable overriding the number of edits per document.
+pub const EDIT_STEPS_ENV: &str = "ARBORIUM_EDIT_STEPS";
+
+const DEFAULT_SEED: u64 = 0x5eed_a4b0_41e5;
+const DEFAULT_STEPS: usize = 100;
+
+/// Text inserted by edits, on top of slices of the document itself.
+const FRAGMENTS: &[&str] = &[
+    "x",
+    " ",
+    "\n",
+    "\n\n",
+    "    ",
+    "\t",
+    "(",
+    ")",
+    "{",
+    "}",
+    "[",
+    "]",
+    "<",
+    ">",
+    "\"",
+    "'",
+    "//",
+    "/*",
+    "*/",
+    "#",
+    ";",
+    ",",
+    ".",
+    ":",
+    "=",
+    "é",
+    "日本語",
+    "🦀",
+    "λ\n",
+    "\r\n",
+    "a\nb\nc",
+    "}\n\n{",
+];
+
+/// One edit: replace `deleted` bytes at `start` with `inserted`.
+///
+/// Offsets get clamped to the text and rounded down to char boundaries when
+/// the edit is applied, so edits stay valid when earlier ones are dropped
+/// while minimizing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditStep {
+    pub start: usize,
+    pub deleted: usize,
+    pub inserted: String,
+}
+
+impl fmt::Display for EditStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at byte {}", self.start)?;
+        if self.deleted > 0 {
+            write!(f, ", delete {} bytes", self.deleted)?;
+        }
+        if !self.inserted.is_empty() {
+            write!(f, ", insert {:?}", self.inserted)?;
+        }
+        Ok(())
+    }
+}
+
+impl EditStep {
+    /// Apply to `text`, returning the new text and the tree-sitter edit.
+    pub fn apply(&self, text: &str) -> (String, Edit) {
+        let start = floor_char_boundary(text, self.start);
+        let old_end = floor_char_boundary(text, start.saturating_add(self.deleted));
+        let new_text = format!("{}{}{}", &text[..start], self.inserted, &text[old_end..]);
+        let new_end = start + self.inserted.len();
+
+        let (start_row, start_col) = position(text, start);
+        let (old_end_row, old_end_col) = position(text, old_end);
+        let (new_end_row, new_end_col) = position(&new_text, new_end);
+        let edit = Edit {
+            start_byte: start as u32,
+            old_end_byte: old_end as u32,
+            new_end_byte: new_end as u32,
+            start_row,
+            start_col,
+            old_end_row,
+            old_end_col,
+            new_end_row,
+            new_end_col,
+        };
+        (new_text, edit)
+    }
+}
+
+fn floor_char_boundary(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// Row and byte column of `offset`, as tree-sitter counts them.
+fn position(text: &str, offset: usize) -> (u32, u32) {
+    let before = &text[..offset];
+    let row = before.matches('\n').count();
+    let col = before.rfind('\n').map_or(offset, |nl| offset - nl - 1);
+    (row as u32, col as u32)
+}
+
+/// A small xorshift generator, so runs are reproducible from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`, or 0 if `n` is 0.
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            (self.next_u64() % n as u64) as usize
+        }
+    }
+}
+
+/// Generate a random edit to `text`.
+fn random_step(rng: &mut Rng, text: &str) -> EditStep {
+    let start = floor_char_boundary(text, rng.below(text.len() + 1));
+    // Mostly small edits, sometimes spanning several lines
+    let max_len = if rng.below(8) == 0 { 200 } else { 12 };
+    let (deleted, inserted) = match rng.below(3) {
+        // Insertion
+        0 => (0, random_insert(rng, text, max_len)),
+        // Deletion
+        1 => (1 + rng.below(max_len), String::new()),
+        // Replacement
+        _ => (1 + rng.below(max_len), random_insert(rng, text, max_len)),
+    };
+    EditStep {
+        start,
+        deleted,
+        inserted,
+    }
+}
+
+fn random_insert(rng: &mut Rng, text: &str, max_len: usize) -> String {
+    if text.is_empty() || rng.below(2) == 0 {
+        return FRAGMENTS[rng.below(FRAGMENTS.len())].to_string();
+    }
+    // A slice of the document, so edits produce plausible code
+    let start = floor_char_boundary(text, rng.below(text.len()));
+    let end = floor_char_boundary(text, start + 1 + rng.below(max_len));
+    text[start..end].to_string()
+}
+
+fn parse(runtime: &mut PluginRuntime, session: u32) -> Result<Utf8ParseResult, String> {
+    runtime.parse(session).map_err(|e| e.message)
+}
+
+/// Replay `steps` on `seed`, returning the index of the first step after
+/// which the incremental parse differs from a fresh one or breaks the span
+/// invariants, with both results.
+fn first_mismatch(
+    runtime: &mut PluginRuntime,
+    seed: &str,
+    steps: &[EditStep],
+) -> Option<(usize, String, String)> {
+    let incremental = runtime.create_session();
+    let fresh = runtime.create_session();
+    runtime.set_text(incremental, seed);
+
+    let mut text = seed.to_string();
+    let mut mismatch = None;
+    for (i, step) in steps.iter().enumerate() {
+        let (new_text, edit) = step.apply(&text);
+        text = new_text;
+        runtime.apply_edit(incremental, &text, &edit);
+        runtime.set_text(fresh, &text);
+
+        let actual = parse(runtime, incremental);
+        let expected = parse(runtime, fresh);
+        let violations = match &actual {
+            Ok(result) => span_violations(&text, result, true),
+            Err(_) => Vec::new(),
+        };
+        if actual != expected || !violations.is_empty() {
+            let mut described = describe(&text, &actual);
+            for violation in violations {
+                described.push_str(&violation);
+                described.push('\n');
+            }
+            mismatch = Some((i, described, describe(&text, &expected)));
+            break;
+        }
+    }
+
+    runtime.free_session(incremental);
+    runtime.free_session(fresh);
+    mismatch
+}
+
+fn describe(text: &str, result: &Result<Utf8ParseResult, String>) -> String {
+    match result {
+        Ok(result) => crate::snapshot::render_snapshot(text, result),
+        Err(e) => format!("error: {}\n", e),
+    }
+}
+
+/// Shrink a failing edit sequence: drop steps, then shorten the remaining
+/// ones, as long as the mismatch still reproduces.
+fn minimize(runtime: &mut PluginRuntime, seed: &str, mut steps: Vec<EditStep>) -> Vec<EditStep> {
+    if let Some((last, _, _)) = first_mismatch(runtime, seed, &steps) {
+        steps.truncate(last + 1);
+    }
+
+    let mut i = 0;
+    while i < steps.len() {
+        let mut candidate = steps.clone();
+        candidate.remove(i);
+        if first_mismatch(runtime, seed, &candidate).is_some() {
+            steps = candidate;
+        } else {
+            i += 1;
+        }
+    }
+
+    for i in 0..steps.len() {
+        loop {
+            let step = &steps[i];
+            let shorter = [
+                EditStep {
+                    deleted: step.deleted / 2,
+                    ..step.clone()
+                },
+                EditStep {
+                    inserted: step
+                        .inserted
+                        .chars()
+                        .take(step.inserted.chars().count() / 2)
+                        .collect(),
+                    ..step.clone()
+                },
+            ];
+            let reproduces = shorter.into_iter().filter(|s| s != step).find(|s| {
+                let mut candidate = steps.clone();
+                candidate[i] = s.clone();
+                first_mismatch(runtime, seed, &candidate).is_some()
+            });
+            match reproduces {
+                Some(s) => steps[i] = s,
+                None => break,
+            }
+        }
+    }
+    steps
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Check that incremental parses match fresh ones over random edit
+/// sequences applied to each of `seeds`, given as `(name, text)` pairs.
+///
+/// On failure, the error shows the minimized edit sequence.
+pub fn check_incremental_edits(
+    config: HighlightConfig,
+    name: &str,
+    seeds: &[(&str, &str)],
+) -> HarnessResult {
+    let rng_seed = env_or(EDIT_SEED_ENV, DEFAULT_SEED);
+    let step_count = env_or(EDIT_STEPS_ENV, DEFAULT_STEPS);
+    let mut runtime = PluginRuntime::new(config);
+
+    for (index, &(seed_name, seed)) in seeds.iter().enumerate() {
+        let mut rng = Rng::new(rng_seed.wrapping_add(index as u64));
+        let mut text = seed.to_string();
+        let mut steps = Vec::with_capacity(step_count);
+        for _ in 0..step_count {
+            let step = random_step(&mut rng, &text);
+            text = step.apply(&text).0;
+            steps.push(step);
+        }
+
+        if first_mismatch(&mut runtime, seed, &steps).is_none() {
+            continue;
+        }
+        let steps = minimize(&mut runtime, seed, steps);
+        let (_, actual, expected) = first_mismatch(&mut runtime, seed, &steps)
+            .expect("minimized edits no longer reproduce the mismatch");
+        let listed: Vec<String> = steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("  {}. {}", i + 1, s))
+            .collect();
+        return Err(HarnessError::new(format!(
+            "Incremental parse of {} / {} is wrong or differs from a fresh parse ({}={})\n\
+             --- edits ---\n{}\n--- after edits ---\n{}--- fresh parse ---\n{}\n{}",
+            name,
+            seed_name,
+            EDIT_SEED_ENV,
+            rng_seed,
+            listed.join("\n"),
+            actual,
+            expected,
+            crate::snapshot::diff_snapshots(&expected, &actual)
+        )));
+    }
+    Ok(())
+}
+
+/// Runs [`check_incremental_edits`] for a grammar.
+///
+/// # Panics
+///
+/// Panics if the queries don't compile, or an incremental parse differs
+/// from a fresh one.
+pub fn test_incremental_edits(
+    language: impl Into<Language>,
+    name: &str,
+    highlights_query: &str,
+    injections_query: &str,
+    locals_query: &str,
+    seeds: &[(&str, &str)],
+) {
+    let config = HighlightConfig::new(language, highlights_query, injections_query, locals_query)
+        .unwrap_or_else(|e| {
+            panic!("Failed to create plugin config for {}: {:?}", name, e);
+        });
+    if let Err(e) = check_incremental_edits(config, name, seeds) {
+        panic!("{}", e);
+    }
+}
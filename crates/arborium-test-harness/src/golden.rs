@@ -0,0 +1,197 @@
+//! Golden HTML.
+//!
+//! Span [snapshots](crate::snapshot) catch query regressions; golden files
+//! catch renderer ones. Grammars with a `def/golden/` directory get each
+//! sample rendered through the HTML renderer, wrapped in a page styled
+//! with a fixed theme, and compared against `golden/<sample>.html`.
+//!
+//! The renderer output is checked to be well-formed, and HTML comments are
+//! left out of the comparison so generator notes don't churn the files.
+//! Updates go through [`UPDATE_SNAPSHOTS_ENV`]; an update growing a golden
+//! file by more than [`GROWTH_BUDGET`] needs `ARBORIUM_UPDATE_SNAPSHOTS=force`.
+//!
+//! Only the grammar's own spans are rendered: injected languages show up
+//! as plain text.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use arborium_highlight::{HtmlFormat, Span, spans_to_html};
+use arborium_theme::builtin;
+use arborium_wire::Utf8ParseResult;
+
+use crate::snapshot::{
+    UPDATE_SNAPSHOTS_ENV, checked_in_dir, diff_lines, missing_is_error, update_forced,
+    update_requested, write_snapshot,
+};
+use crate::{HarnessError, HarnessResult};
+
+/// How much larger, as a fraction, an update may make a golden file
+/// without being forced.
+pub const GROWTH_BUDGET: f64 = 0.10;
+
+/// Selector the theme's CSS is scoped to.
+const CODE_SELECTOR: &str = "pre.arborium";
+
+/// Directory holding a grammar's golden HTML, if it has adopted it.
+pub fn golden_dir(crate_dir: &Path) -> Option<PathBuf> {
+    checked_in_dir(crate_dir, "golden")
+}
+
+/// Path of the golden HTML for `sample` in `dir`.
+pub fn golden_path(dir: &Path, sample: &Path) -> PathBuf {
+    let file_name = sample
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    dir.join(format!("{}.html", file_name))
+}
+
+/// Render a parse result of `source` as a golden page.
+///
+/// Fails if the renderer produces malformed HTML.
+pub fn render_golden(source: &str, result: &Utf8ParseResult) -> Result<String, String> {
+    let spans = result
+        .spans
+        .iter()
+        .map(|s| Span {
+            start: s.start,
+            end: s.end,
+            capture: s.capture.clone(),
+            pattern_index: s.pattern_index,
+        })
+        .collect();
+    let code = spans_to_html(source, spans, &HtmlFormat::CustomElements);
+    check_well_formed(&code)?;
+
+    let css = builtin::catppuccin_mocha().to_css(CODE_SELECTOR);
+    Ok(format!(
+        "<!DOCTYPE html>\n<style>\n{}</style>\n<pre class=\"arborium\"><code>{}</code></pre>\n",
+        css, code
+    ))
+}
+
+/// Check that `html` only has balanced elements, quoted attributes and
+/// known character references, with no stray `<` in text.
+pub fn check_well_formed(html: &str) -> Result<(), String> {
+    let mut open: Vec<&str> = Vec::new();
+    let mut rest = html;
+    while let Some(i) = rest.find(['<', '&']) {
+        let offset = html.len() - rest.len() + i;
+        rest = &rest[i..];
+        if rest.starts_with('&') {
+            let entity = rest[1..]
+                .find(';')
+                .map(|end| &rest[1..=end])
+                .filter(|name| is_entity(name))
+                .ok_or_else(|| format!("bare `&` at byte {}", offset))?;
+            // Skip `&`, the name and `;`
+            rest = &rest[entity.len() + 2..];
+            continue;
+        }
+
+        let end = rest
+            .find('>')
+            .ok_or_else(|| format!("unterminated tag at byte {}", offset))?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if let Some(name) = tag.strip_prefix('/') {
+            match open.pop() {
+                Some(expected) if expected == name => {}
+                Some(expected) => {
+                    return Err(format!(
+                        "`</{}>` at byte {} closes `<{}>`",
+                        name, offset, expected
+                    ));
+                }
+                None => return Err(format!("`</{}>` at byte {} closes nothing", name, offset)),
+            }
+            continue;
+        }
+        let name = tag.split_whitespace().next().unwrap_or("");
+        let valid_name =
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid_name || tag.matches('"').count() % 2 != 0 {
+            return Err(format!("malformed tag `<{}>` at byte {}", tag, offset));
+        }
+        if !tag.ends_with('/') {
+            open.push(name);
+        }
+    }
+
+    match open.pop() {
+        Some(name) => Err(format!("`<{}>` is never closed", name)),
+        None => Ok(()),
+    }
+}
+
+fn is_entity(name: &str) -> bool {
+    if let Some(code) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        return !code.is_empty() && code.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    if let Some(code) = name.strip_prefix('#') {
+        return !code.is_empty() && code.chars().all(|c| c.is_ascii_digit());
+    }
+    matches!(name, "amp" | "lt" | "gt" | "quot" | "apos")
+}
+
+/// Drop HTML comments and normalize line endings.
+fn normalize(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => rest = "",
+        }
+    }
+    out.push_str(rest);
+    out.replace("\r\n", "\n")
+}
+
+/// Compare `actual` against the golden file at `path`.
+///
+/// Works like [`check_snapshot`](crate::snapshot::check_snapshot), except
+/// that updates growing the file beyond [`GROWTH_BUDGET`] must be forced.
+pub fn check_golden(path: &Path, actual: &str) -> HarnessResult {
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => normalize(&expected),
+        Err(_) if missing_is_error() => {
+            return Err(HarnessError::new(format!(
+                "Missing golden HTML {}; run the tests with {}=1 to record it",
+                path.display(),
+                UPDATE_SNAPSHOTS_ENV
+            )));
+        }
+        Err(_) => return write_snapshot(path, actual),
+    };
+    let normalized = normalize(actual);
+    if expected == normalized {
+        return Ok(());
+    }
+
+    if !update_requested() {
+        return Err(HarnessError::new(format!(
+            "Golden HTML {} doesn't match; run the tests with {}=1 to accept the changes\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS_ENV,
+            diff_lines(&expected, &normalized)
+        )));
+    }
+
+    let growth = normalized.len() as f64 / expected.len().max(1) as f64 - 1.0;
+    if growth > GROWTH_BUDGET && !update_forced() {
+        return Err(HarnessError::new(format!(
+            "Golden HTML {} would grow by {:.0}% ({} -> {} bytes), more than the {:.0}% budget; \
+             run the tests with {}=force if that's intended",
+            path.display(),
+            growth * 100.0,
+            expected.len(),
+            normalized.len(),
+            GROWTH_BUDGET * 100.0,
+            UPDATE_SNAPSHOTS_ENV
+        )));
+    }
+    write_snapshot(path, actual)
+}
@@ -0,0 +1,166 @@
+//! Helpers for the hand-written tests in grammar crates: a highlighter over
+//! compiled grammars, the plugin runtime's results for a source, and the
+//! nodes of a parse tree.
+
+use std::collections::HashMap;
+
+use arborium_highlight::tree_sitter::{CompiledGrammar, GrammarConfig, ParseContext};
+use arborium_highlight::{Grammar, GrammarProvider, ParseResult, SyncHighlighter};
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
+use arborium_tree_sitter::{Language, Node, Parser, Tree};
+use arborium_wire::Utf8ParseResult;
+
+/// A compiled grammar with its own parse context.
+pub struct TestGrammar {
+    grammar: CompiledGrammar,
+    ctx: ParseContext,
+}
+
+impl TestGrammar {
+    pub fn new(language: impl Into<Language>, highlights: &str, injections: &str) -> Self {
+        let grammar = CompiledGrammar::new(GrammarConfig {
+            language: language.into(),
+            highlights_query: highlights,
+            injections_query: injections,
+            locals_query: "",
+        })
+        .expect("Failed to compile grammar");
+        let ctx = ParseContext::for_grammar(&grammar).expect("Failed to create parse context");
+        Self { grammar, ctx }
+    }
+}
+
+impl Grammar for TestGrammar {
+    fn parse(&mut self, text: &str) -> ParseResult {
+        self.grammar.parse(&mut self.ctx, text)
+    }
+}
+
+/// Provides the grammars it was made with, by name.
+pub struct TestProvider {
+    grammars: HashMap<&'static str, TestGrammar>,
+}
+
+impl GrammarProvider for TestProvider {
+    type Grammar = TestGrammar;
+
+    async fn get(&mut self, language: &str) -> Option<&mut Self::Grammar> {
+        self.grammars.get_mut(language)
+    }
+}
+
+/// A highlighter that knows only `grammars`.
+pub fn highlighter(
+    grammars: impl IntoIterator<Item = (&'static str, TestGrammar)>,
+) -> SyncHighlighter<TestProvider> {
+    SyncHighlighter::new(TestProvider {
+        grammars: grammars.into_iter().collect(),
+    })
+}
+
+/// The queries the plugin runtime highlights a grammar with.
+#[derive(Clone, Copy)]
+pub struct Queries<'a> {
+    pub highlights: &'a str,
+    pub injections: &'a str,
+    pub locals: &'a str,
+}
+
+/// The [`Queries`] a grammar crate exports, e.g. `queries!(arborium_vue)`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __queries {
+    ($krate:ident) => {
+        $crate::helpers::Queries {
+            highlights: &$krate::HIGHLIGHTS_QUERY,
+            injections: $krate::INJECTIONS_QUERY,
+            locals: $krate::LOCALS_QUERY,
+        }
+    };
+}
+#[doc(inline)]
+pub use __queries as queries;
+
+/// Highlight `source` with the plugin runtime.
+pub fn plugin_parse(
+    language: impl Into<Language>,
+    queries: Queries<'_>,
+    source: &str,
+) -> Utf8ParseResult {
+    let config = HighlightConfig::new(
+        language,
+        queries.highlights,
+        queries.injections,
+        queries.locals,
+    )
+    .unwrap_or_else(|e| panic!("Failed to create plugin config: {:?}", e));
+    let mut runtime = PluginRuntime::new(config);
+    let session = runtime.create_session();
+    runtime.set_text(session, source);
+    let result = runtime.parse(session).expect("Failed to highlight");
+    runtime.free_session(session);
+    result
+}
+
+/// The text and language of each injection the plugin runtime finds in
+/// `source`.
+pub fn injected<'a>(
+    language: impl Into<Language>,
+    queries: Queries<'_>,
+    source: &'a str,
+) -> Vec<(&'a str, String)> {
+    injections(&plugin_parse(language, queries, source), source)
+}
+
+/// The text and language of each injection in `result`, a parse of `source`.
+pub fn injections<'a>(result: &Utf8ParseResult, source: &'a str) -> Vec<(&'a str, String)> {
+    result
+        .injections
+        .iter()
+        .map(|i| {
+            (
+                &source[i.start as usize..i.end as usize],
+                i.language.clone(),
+            )
+        })
+        .collect()
+}
+
+/// The text injected as `language` in `result`, a parse of `source`.
+pub fn injected_as<'a>(result: &Utf8ParseResult, source: &'a str, language: &str) -> Vec<&'a str> {
+    injections(result, source)
+        .into_iter()
+        .filter(|(_, l)| l == language)
+        .map(|(text, _)| text)
+        .collect()
+}
+
+/// Parse `source` into a tree.
+pub fn parse_tree(language: impl Into<Language>, source: &str) -> Tree {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language.into())
+        .expect("Failed to set language");
+    parser.parse(source, None).expect("Parser returned no tree")
+}
+
+/// The text of every node of `kind` in `source`, which has to parse
+/// without errors.
+pub fn nodes<'a>(language: impl Into<Language>, source: &'a str, kind: &str) -> Vec<&'a str> {
+    let tree = parse_tree(language, source);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+    let mut out = Vec::new();
+    collect(root, kind, source, &mut out);
+    out
+}
+
+fn collect<'a>(node: Node, kind: &str, source: &'a str, out: &mut Vec<&'a str>) {
+    if node.kind() == kind {
+        out.push(&source[node.byte_range()]);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect(child, kind, source, out);
+    }
+}
@@ -0,0 +1,251 @@
+//! Injection target validation.
+//!
+//! An injection into a language the central registry doesn't know silently
+//! falls back to plain text, so a misspelled `#set! injection.language` or a
+//! grammar that was never added goes unnoticed. The harness collects the
+//! languages a grammar's injections query names statically, plus the ones
+//! its samples actually inject, and checks that each is a grammar id or
+//! alias, resolved the same way as the umbrella crate's `normalize_language`.
+//! Captured file paths, like a diff's, resolve by extension or file name,
+//! and are left as plain text when neither names a grammar.
+//!
+//! The registry is read from the `arborium.yaml` files next to the grammar
+//! in the repository, so the check is skipped outside of it. Grammars can
+//! list targets that are expected not to resolve in
+//! `injection-allowlist.txt`; those are reported as warnings.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use arborium_highlight::detect::{detect_from_path, injection_language_candidates};
+
+use crate::{HarnessError, HarnessResult, read_allowlist};
+
+/// Name of the per-grammar allowlist of injection targets that may not
+/// resolve to a grammar.
+pub const INJECTION_ALLOWLIST: &str = "injection-allowlist.txt";
+
+/// Language names injections can resolve to: the ids and aliases of every
+/// grammar, and the ids of internal ones, which only exist to be injected.
+pub struct LanguageRegistry {
+    names: BTreeMap<String, String>,
+}
+
+impl LanguageRegistry {
+    /// Read the registry from the `langs/` tree `crate_dir` lives in.
+    ///
+    /// Returns `None` when the crate isn't inside the repository, e.g. when
+    /// it was installed from crates.io.
+    pub fn load(crate_dir: &Path) -> Option<Self> {
+        let langs_dir = crate_dir
+            .ancestors()
+            .find(|dir| dir.file_name().is_some_and(|name| name == "langs"))?;
+        let mut names = BTreeMap::new();
+        for path in definition_files(langs_dir) {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            for (id, aliases) in parse_definition(&content) {
+                for alias in aliases {
+                    names.insert(alias, id.clone());
+                }
+                names.insert(id.clone(), id);
+            }
+        }
+        (!names.is_empty()).then_some(Self { names })
+    }
+
+    /// The grammar id `language` resolves to, if any.
+    ///
+    /// Names are also tried lowercased and attribute by attribute, since
+    /// documents write them loosely, like Org's `#+begin_src C` or
+    /// AsciiDoc's `[source,rust]`. Injections that capture a file path rather than a language, like the
+    /// diff grammar's, resolve from the path the way the highlighters do.
+    pub fn resolve(&self, language: &str) -> Option<&str> {
+        if let Some(id) = injection_language_candidates(language)
+            .iter()
+            .find_map(|name| self.names.get(name))
+        {
+            return Some(id);
+        }
+        let id = detect_from_path(language, &|ext| self.names.get(ext).cloned())?;
+        self.names.get(&id).map(String::as_str)
+    }
+}
+
+/// All `langs/group-*/<lang>/def/arborium.yaml` files.
+fn definition_files(langs_dir: &Path) -> Vec<PathBuf> {
+    let subdirs = |dir: &Path| -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut files: Vec<PathBuf> = subdirs(langs_dir)
+        .iter()
+        .flat_map(|group| subdirs(group))
+        .map(|lang| lang.join("def").join("arborium.yaml"))
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Pick the grammar ids and aliases out of an `arborium.yaml`. Internal
+/// grammars are registered by id alone, so their aliases are left out.
+///
+/// Only the few keys needed here are read, so the harness doesn't need a
+/// YAML parser: `- id:`, `internal:` and `aliases:`, as a block list or a
+/// flow list.
+fn parse_definition(content: &str) -> Vec<(String, Vec<String>)> {
+    let unquote = |s: &str| s.trim().trim_matches(['"', '\'']).to_string();
+    let mut grammars: Vec<(String, Vec<String>, bool)> = Vec::new();
+    // Indentation of the `aliases:` key while reading its block list
+    let mut aliases_indent: Option<usize> = None;
+
+    for line in content.lines() {
+        let line = line.split(" #").next().unwrap_or("");
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+
+        if let Some(key_indent) = aliases_indent {
+            if let Some(alias) = trimmed.strip_prefix("- ").filter(|_| indent >= key_indent) {
+                if let Some(grammar) = grammars.last_mut() {
+                    grammar.1.push(unquote(alias));
+                }
+                continue;
+            }
+            aliases_indent = None;
+        }
+
+        if let Some(id) = trimmed.strip_prefix("- id:") {
+            grammars.push((unquote(id), Vec::new(), false));
+        } else if let Some(internal) = trimmed.strip_prefix("internal:") {
+            if let Some(grammar) = grammars.last_mut() {
+                grammar.2 = internal.trim() == "true";
+            }
+        } else if let Some(aliases) = trimmed.strip_prefix("aliases:") {
+            let aliases = aliases.trim();
+            match aliases.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+                Some(list) => {
+                    if let Some(grammar) = grammars.last_mut() {
+                        grammar
+                            .1
+                            .extend(list.split(',').map(unquote).filter(|a| !a.is_empty()));
+                    }
+                }
+                None if aliases.is_empty() => aliases_indent = Some(indent),
+                None => {}
+            }
+        }
+    }
+
+    grammars
+        .into_iter()
+        .filter(|(id, _, _)| !id.ends_with("_inline"))
+        .map(|(id, aliases, internal)| (id, if internal { Vec::new() } else { aliases }))
+        .collect()
+}
+
+/// List the languages `injections_query` sets with
+/// `(#set! injection.language "...")`, with their byte offsets.
+///
+/// Languages captured with `@injection.language` depend on the text being
+/// highlighted and only show up in the injections of sample results.
+pub fn static_targets(injections_query: &str) -> Vec<(String, usize)> {
+    let mut targets = Vec::new();
+    for (offset, _) in injections_query.match_indices("#set!") {
+        let line_start = injections_query[..offset].rfind('\n').map_or(0, |i| i + 1);
+        if injections_query[line_start..offset].contains(';') {
+            continue;
+        }
+        let rest = injections_query[offset + "#set!".len()..].trim_start();
+        let Some(rest) = rest.strip_prefix("injection.language") else {
+            continue;
+        };
+        let Some(rest) = rest.trim_start().strip_prefix('"') else {
+            continue;
+        };
+        if let Some(end) = rest.find('"') {
+            targets.push((rest[..end].to_string(), offset));
+        }
+    }
+    targets
+}
+
+/// Check that every language the `name` grammar injects resolves in the
+/// central registry.
+///
+/// `injected` maps each language the samples injected to the samples that
+/// injected it. Targets in the grammar's allowlist are only warned about.
+pub fn check_injection_targets(
+    name: &str,
+    injections_query: &str,
+    injected: &BTreeMap<String, BTreeSet<String>>,
+    crate_dir: &Path,
+) -> HarnessResult {
+    let mut seen: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (language, offset) in static_targets(injections_query) {
+        let line = injections_query[..offset].matches('\n').count() + 1;
+        seen.entry(language)
+            .or_default()
+            .push(format!("injections.scm line {}", line));
+    }
+    for (language, samples) in injected {
+        seen.entry(language.clone())
+            .or_default()
+            .extend(samples.iter().cloned());
+    }
+    if seen.is_empty() {
+        return Ok(());
+    }
+
+    let Some(registry) = LanguageRegistry::load(crate_dir) else {
+        eprintln!(
+            "warning: no arborium.yaml files found around {}, not checking {} injection targets",
+            crate_dir.display(),
+            name
+        );
+        return Ok(());
+    };
+    let allowlist = read_allowlist(crate_dir, INJECTION_ALLOWLIST);
+
+    let mut unresolved = Vec::new();
+    for (language, places) in &seen {
+        if registry.resolve(language).is_some() {
+            continue;
+        }
+        // A captured path names a file, not a language, and files like
+        // `b/README.txt` have no grammar on purpose
+        if language.contains(['/', '.']) {
+            continue;
+        }
+        let report = format!("{:?} ({})", language, places.join(", "));
+        if allowlist.contains(language) {
+            eprintln!("warning: {} injects an unknown language: {}", name, report);
+        } else {
+            unresolved.push(report);
+        }
+    }
+
+    if unresolved.is_empty() {
+        return Ok(());
+    }
+    Err(HarnessError::new(format!(
+        "{} injects languages that aren't a grammar id or alias, so they render as plain text:\n  {}\n\
+         Use a registered name, add it to the target's aliases in arborium.yaml, \
+         or list it in {} if that's intended.",
+        name,
+        unresolved.join("\n  "),
+        INJECTION_ALLOWLIST
+    )))
+}
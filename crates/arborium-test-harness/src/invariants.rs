@@ -0,0 +1,162 @@
+//! Span invariants.
+//!
+//! Every parse result the harness produces is checked for:
+//!
+//! - spans and injections with `start <= end <= text.len()`, on UTF-8 char
+//!   boundaries
+//! - spans sorted by `(start, end)`
+//! - spans with a capture name, injections with a language
+//! - optionally, no two spans with the same range and capture
+//!
+//! Zero-width spans are allowed. Queries capturing zero-width nodes
+//! (`MISSING` nodes, empty tokens from external scanners) produce them, and
+//! the renderers skip them, so there's nothing to gain from rejecting them.
+//! Duplicate spans are allowed by default too, since the renderers
+//! deduplicate ranges.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use arborium_wire::Utf8ParseResult;
+
+use crate::{HarnessError, HarnessResult};
+
+/// Bytes of source shown on each side of an offending span.
+const CONTEXT_BYTES: usize = 30;
+
+/// List the ways `result` breaks the span invariants for `source`, each with
+/// the offending span and its surroundings.
+pub fn span_violations(
+    source: &str,
+    result: &Utf8ParseResult,
+    allow_duplicates: bool,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+    let mut check_range = |what: String, start: u32, end: u32| {
+        let (start, end) = (start as usize, end as usize);
+        let problem = if start > end {
+            "starts after it ends"
+        } else if end > source.len() {
+            "ends past the end of the text"
+        } else if !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+            "isn't on char boundaries"
+        } else {
+            return;
+        };
+        violations.push(format!(
+            "{} {}\n{}",
+            what,
+            problem,
+            context(source, start, end)
+        ));
+    };
+
+    for (i, span) in result.spans.iter().enumerate() {
+        let what = format!("span #{} {}..{} @{}", i, span.start, span.end, span.capture);
+        check_range(what, span.start, span.end);
+    }
+    for (i, injection) in result.injections.iter().enumerate() {
+        let what = format!(
+            "injection #{} {}..{} ({})",
+            i, injection.start, injection.end, injection.language
+        );
+        check_range(what, injection.start, injection.end);
+    }
+
+    for (i, pair) in result.spans.windows(2).enumerate() {
+        let (a, b) = (&pair[0], &pair[1]);
+        if (a.start, a.end) > (b.start, b.end) {
+            violations.push(format!(
+                "spans #{} {}..{} and #{} {}..{} are out of order\n{}",
+                i,
+                a.start,
+                a.end,
+                i + 1,
+                b.start,
+                b.end,
+                context(source, b.start as usize, a.end as usize)
+            ));
+        }
+    }
+
+    if !allow_duplicates {
+        let mut seen = HashSet::new();
+        for (i, span) in result.spans.iter().enumerate() {
+            if !seen.insert((span.start, span.end, &span.capture)) {
+                violations.push(format!(
+                    "span #{} {}..{} @{} is a duplicate\n{}",
+                    i,
+                    span.start,
+                    span.end,
+                    span.capture,
+                    context(source, span.start as usize, span.end as usize)
+                ));
+            }
+        }
+    }
+
+    for (i, span) in result.spans.iter().enumerate() {
+        if span.capture.is_empty() {
+            violations.push(format!(
+                "span #{} {}..{} has an empty capture name\n{}",
+                i,
+                span.start,
+                span.end,
+                context(source, span.start as usize, span.end as usize)
+            ));
+        }
+    }
+    for (i, injection) in result.injections.iter().enumerate() {
+        if injection.language.is_empty() {
+            violations.push(format!(
+                "injection #{} {}..{} has an empty language\n{}",
+                i,
+                injection.start,
+                injection.end,
+                context(source, injection.start as usize, injection.end as usize)
+            ));
+        }
+    }
+    violations
+}
+
+/// Show `start..end` in `source` with some text around it, the range
+/// itself between `⟦` and `⟧`.
+pub(crate) fn context(source: &str, start: usize, end: usize) -> String {
+    let floor = |mut i: usize| {
+        i = i.min(source.len());
+        while !source.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    };
+    let (start, end) = (floor(start.min(end)), floor(start.max(end)));
+    let before = floor(start.saturating_sub(CONTEXT_BYTES));
+    let after = floor(end + CONTEXT_BYTES);
+    format!(
+        "    {:?} ⟦{:?}⟧ {:?}",
+        &source[before..start],
+        &source[start..end],
+        &source[end..after]
+    )
+}
+
+/// Check the span invariants of the result of highlighting `sample` with
+/// the `name` grammar.
+pub fn check_span_invariants(
+    name: &str,
+    sample: &Path,
+    source: &str,
+    result: &Utf8ParseResult,
+) -> HarnessResult {
+    let violations = span_violations(source, result, true);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(HarnessError::new(format!(
+        "Highlighting {} with {} broke span invariants:\n{}",
+        sample.display(),
+        name,
+        violations.join("\n")
+    )))
+}
@@ -24,17 +24,62 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Snapshots
+//!
+//! Grammars with a `def/snapshots/` directory get their samples' highlights
+//! compared against checked-in snapshots, see [`snapshot`]. Set
+//! `ARBORIUM_UPDATE_SNAPSHOTS=1` to accept changes. Grammars with a
+//! `def/golden/` directory get their samples' rendered HTML compared the
+//! same way, see [`golden`].
+//!
+//! # Highlight assertions
+//!
+//! Files in `def/test/highlight/` are tree-sitter style highlight tests,
+//! with comments asserting which capture the line above gets, see
+//! [`assertions`].
+//!
+//! # Timing
+//!
+//! With `ARBORIUM_TIMING=1`, samples are also timed against the grammar's
+//! `def/timing-budget.txt`, see [`timing`].
+//!
+//! # Incremental edits
+//!
+//! [`edits::test_incremental_edits`] checks that parsing after random edits
+//! gives the same highlights as parsing the edited text from scratch.
+//!
+//! # Hand-written tests
+//!
+//! Tests a grammar needs beyond these go in its `def/tests/`, which is
+//! copied to the crate's `tests/`, and use the [`helpers`]. The other
+//! grammars they need are listed under `test_dependencies` in its
+//! `arborium.yaml`.
 
 pub use arborium_highlight;
 pub use arborium_tree_sitter as tree_sitter;
-
-use std::collections::HashSet;
+pub use arborium_wire;
+
+pub mod assertions;
+pub mod captures;
+pub mod differential;
+pub mod edits;
+pub mod golden;
+pub mod helpers;
+pub mod injections;
+pub mod invariants;
+pub mod node_kinds;
+pub mod snapshot;
+pub mod timing;
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use arborium_highlight::{CompiledGrammar, GrammarConfig, ParseContext};
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
 use arborium_tree_sitter::Language;
-use arborium_tree_sitter::{Node, Parser, Tree};
+use arborium_tree_sitter::{CaptureQuantifier, Node, Parser, Query, Tree};
 use tree_sitter_language::LanguageFn;
 
 // Re-export CAPTURE_NAMES from arborium-theme as HIGHLIGHT_NAMES for convenience
@@ -83,9 +128,23 @@ type HarnessResult<T = ()> = Result<T, HarnessError>;
 /// Tests a grammar by validating its queries and highlighting all samples.
 ///
 /// This function:
-/// 1. Validates that the queries compile correctly
-/// 2. Finds sample files in the samples/ directory
-/// 3. Highlights each sample file and verifies we get highlights
+/// 1. Validates that the queries only refer to node kinds and fields the
+///    grammar has (see [`node_kinds`]), that they compile correctly, and that
+///    every highlight capture gets styled (see [`captures`])
+/// 2. If the grammar has highlight assertion files, checks them (see
+///    [`assertions`])
+/// 3. Finds sample files in the samples/ directory
+/// 4. Highlights each sample through the plugin runtime and verifies we get
+///    highlights that satisfy the span invariants (see [`invariants`])
+/// 5. Checks that every language the injections query or the samples
+///    inject resolves to a registered grammar (see [`injections`])
+/// 6. Compares each sample's highlights against the ones the renderer's
+///    pipeline produces (see [`differential`])
+/// 7. If the grammar has snapshots, compares each sample's highlights against
+///    its snapshot (see [`snapshot`]), and if it has golden HTML, each
+///    sample's rendered HTML against its golden file (see [`golden`])
+/// 8. With `ARBORIUM_TIMING=1`, checks how long each sample takes to
+///    highlight against the grammar's budget (see [`timing`])
 ///
 /// # Arguments
 ///
@@ -93,31 +152,49 @@ type HarnessResult<T = ()> = Result<T, HarnessError>;
 /// * `name` - The grammar name (e.g., "rust")
 /// * `highlights_query` - The highlights.scm content
 /// * `injections_query` - The injections.scm content
-/// * `locals_query` - The locals.scm content (currently unused by arborium-highlight)
+/// * `locals_query` - The locals.scm content
 /// * `crate_dir` - Path to the crate directory (use `env!("CARGO_MANIFEST_DIR")`)
 ///
 /// # Panics
 ///
-/// Panics if query validation fails, highlighting produces errors, or no highlights are found.
+/// Panics if a query refers to unknown nodes, query validation fails, a
+/// capture maps to no highlight, a highlight assertion fails, highlighting
+/// produces errors, no highlights are found, spans break the invariants, an
+/// injection target doesn't resolve, the two highlighting pipelines
+/// disagree, a snapshot or golden file doesn't match, or, when timing, a
+/// sample is over budget.
 pub fn test_grammar(
     language: impl Into<Language>,
     name: &str,
     highlights_query: &str,
     injections_query: &str,
-    _locals_query: &str,
+    locals_query: &str,
     crate_dir: &str,
 ) {
     let language: Language = language.into();
+    let crate_path = Path::new(crate_dir);
+
+    // Check the queries only refer to nodes the grammar has, reporting all
+    // the ones it doesn't before tree-sitter rejects the first
+    let queries = [
+        ("highlights.scm", highlights_query),
+        ("injections.scm", injections_query),
+        ("locals.scm", locals_query),
+    ];
+    if let Err(e) = node_kinds::check_node_kinds(&language, name, &queries, crate_path) {
+        panic!("{}", e);
+    }
+
     // Create grammar config
     let config = GrammarConfig {
-        language,
+        language: language.clone(),
         highlights_query,
         injections_query,
         locals_query: "", // Not used by arborium-highlight yet
     };
 
     // Validate queries compile by creating the grammar
-    let grammar = CompiledGrammar::new(config).unwrap_or_else(|e| {
+    let compiled = CompiledGrammar::new(config).unwrap_or_else(|e| {
         panic!(
             "Query validation failed for {}: {:?}\n\
              This usually means highlights.scm references a node type that doesn't exist in the grammar.\n\
@@ -126,28 +203,52 @@ pub fn test_grammar(
         );
     });
 
-    // Create a parse context for this grammar
-    let mut ctx = ParseContext::for_grammar(&grammar).unwrap_or_else(|e| {
-        panic!("Failed to create parse context for {}: {:?}", name, e);
-    });
+    if let Err(e) = captures::check_capture_names(&language, name, highlights_query, crate_path) {
+        panic!("{}", e);
+    }
 
-    // Find samples from arborium.kdl
-    let crate_path = Path::new(crate_dir);
-    let kdl_path = crate_path.join("arborium.kdl");
-    let samples: Vec<_> = if kdl_path.exists() {
-        parse_samples_from_kdl(&kdl_path)
-            .into_iter()
-            .map(|p| crate_path.join(p))
-            .collect()
-    } else {
-        vec![]
-    };
+    // Highlight the way plugins do
+    let config = HighlightConfig::new(language, highlights_query, injections_query, locals_query)
+        .unwrap_or_else(|e| {
+            panic!("Failed to create plugin config for {}: {:?}", name, e);
+        });
+    let mut runtime = PluginRuntime::new(config);
+
+    if let Err(e) = assertions::check_highlight_assertions(
+        &mut runtime,
+        name,
+        crate_path,
+        assertions::DEFAULT_COMMENT_PREFIXES,
+    ) {
+        panic!("{}", e);
+    }
 
+    let samples = grammar_sample_files(crate_dir, name);
     if samples.is_empty() {
-        // No samples - just verify query compiles (already done above)
+        // No samples - just verify queries compile (already done above) and
+        // the languages they name statically resolve
+        let injected = BTreeMap::new();
+        if let Err(e) =
+            injections::check_injection_targets(name, injections_query, &injected, crate_path)
+        {
+            panic!("{}", e);
+        }
         return;
     }
 
+    let session = runtime.create_session();
+    let mut parse_context = ParseContext::for_grammar(&compiled).unwrap_or_else(|e| {
+        panic!("Failed to create parse context for {}: {:?}", name, e);
+    });
+
+    let snapshot_dir = snapshot::snapshot_dir(crate_path);
+    let golden_dir = golden::golden_dir(crate_path);
+    let mut sample_failures = Vec::new();
+    // Languages injected by the samples, with the samples injecting them
+    let mut injected: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    // Samples to time afterwards, if timing was asked for
+    let mut timed = Vec::new();
+
     // Test each sample - must produce at least one highlight
     for sample_path in &samples {
         let sample_code = fs::read_to_string(sample_path).unwrap_or_else(|e| {
@@ -159,14 +260,18 @@ pub fn test_grammar(
             );
         });
 
-        // Parse with the grammar
-        let result = grammar.parse(&mut ctx, &sample_code);
-
-        // Count highlight spans
-        let highlight_count = result.spans.len();
+        runtime.set_text(session, &sample_code);
+        let result = runtime.parse(session).unwrap_or_else(|e| {
+            panic!(
+                "Highlighting {} failed for {}: {}",
+                sample_path.display(),
+                name,
+                e.message
+            );
+        });
 
         // Verify we got highlights
-        if highlight_count == 0 {
+        if result.spans.is_empty() {
             panic!(
                 "No highlights produced for {} in {}.\n\
                  Sample has {} bytes.\n\
@@ -176,9 +281,243 @@ pub fn test_grammar(
                 sample_code.len()
             );
         }
+
+        if let Err(e) = invariants::check_span_invariants(name, sample_path, &sample_code, &result)
+        {
+            panic!("{}", e);
+        }
+
+        if let Err(e) = differential::check_differential(
+            &compiled,
+            &mut parse_context,
+            name,
+            sample_path,
+            &sample_code,
+            &result,
+            crate_path,
+        ) {
+            sample_failures.push(e.to_string());
+        }
+
+        if timing::enabled() {
+            timed.push((sample_path.clone(), sample_code.clone()));
+        }
+
+        for injection in &result.injections {
+            injected
+                .entry(injection.language.clone())
+                .or_default()
+                .insert(sample_path.display().to_string());
+        }
+
+        if let Some(dir) = &snapshot_dir {
+            let rendered = snapshot::render_snapshot(&sample_code, &result);
+            let path = snapshot::snapshot_path(dir, sample_path);
+            if let Err(e) = snapshot::check_snapshot(&path, &rendered) {
+                sample_failures.push(format!("{}: {}", sample_path.display(), e));
+            }
+        }
+
+        if let Some(dir) = &golden_dir {
+            let path = golden::golden_path(dir, sample_path);
+            let checked = golden::render_golden(&sample_code, &result)
+                .map_err(|e| format!("the HTML renderer produced malformed HTML: {}", e))
+                .and_then(|html| golden::check_golden(&path, &html).map_err(|e| e.to_string()));
+            if let Err(e) = checked {
+                sample_failures.push(format!("{}: {}", sample_path.display(), e));
+            }
+        }
+    }
+
+    if let Err(e) =
+        injections::check_injection_targets(name, injections_query, &injected, crate_path)
+    {
+        panic!("{}", e);
+    }
+
+    if !timed.is_empty() {
+        if let Err(e) = timing::check_parse_times(&mut runtime, name, &timed, crate_path) {
+            panic!("{}", e);
+        }
+    }
+
+    if !sample_failures.is_empty() {
+        panic!(
+            "Samples don't highlight as expected for {}:\n\n{}",
+            name,
+            sample_failures.join("\n\n")
+        );
+    }
+}
+
+/// Tests a grammar's folds query, if it has one.
+///
+/// The query has to refer only to node kinds and fields the grammar has
+/// (see [`node_kinds`]), compile, and capture nothing but `@fold`. An empty
+/// query passes, so every grammar crate can call this.
+///
+/// # Panics
+///
+/// Panics if any of those checks fail.
+pub fn test_folds(language: impl Into<Language>, name: &str, folds_query: &str, crate_dir: &str) {
+    if folds_query.is_empty() {
+        return;
+    }
+    let language: Language = language.into();
+
+    let queries = [("folds.scm", folds_query)];
+    if let Err(e) = node_kinds::check_node_kinds(&language, name, &queries, Path::new(crate_dir)) {
+        panic!("{}", e);
+    }
+
+    let query = Query::new(&language, folds_query).unwrap_or_else(|e| {
+        panic!("Folds query validation failed for {}: {:?}", name, e);
+    });
+    let others: Vec<_> = query
+        .capture_names()
+        .iter()
+        .filter(|capture| **capture != "fold")
+        .collect();
+    assert!(
+        others.is_empty(),
+        "Folds query for {} captures {:?}, but only @fold is used",
+        name,
+        others
+    );
+}
+
+/// Tests a grammar's tags query, if it has one.
+///
+/// The query has to refer only to node kinds and fields the grammar has,
+/// and compile. Its captures are the ones tree-sitter's tagging uses: a
+/// pattern capturing a `@definition.*` or `@reference.*` kind must also
+/// capture the `@name`, and besides those only `@doc`, `@ignore` and
+/// `@local.*` are allowed. An empty query passes.
+///
+/// # Panics
+///
+/// Panics if any of those checks fail.
+pub fn test_tags(language: impl Into<Language>, name: &str, tags_query: &str, crate_dir: &str) {
+    if tags_query.is_empty() {
+        return;
+    }
+    let language: Language = language.into();
+
+    let queries = [("tags.scm", tags_query)];
+    if let Err(e) = node_kinds::check_node_kinds(&language, name, &queries, Path::new(crate_dir)) {
+        panic!("{}", e);
+    }
+
+    let query = Query::new(&language, tags_query).unwrap_or_else(|e| {
+        panic!("Tags query validation failed for {}: {:?}", name, e);
+    });
+    let capture_names = query.capture_names();
+    let is_kind =
+        |capture: &str| capture.starts_with("definition.") || capture.starts_with("reference.");
+    let unknown: Vec<_> = capture_names
+        .iter()
+        .filter(|capture| {
+            !is_kind(**capture)
+                && !matches!(**capture, "name" | "doc" | "ignore")
+                && !capture.starts_with("local.")
+        })
+        .collect();
+    assert!(
+        unknown.is_empty(),
+        "Tags query for {} captures {:?}, which tagging doesn't use",
+        name,
+        unknown
+    );
+
+    for pattern in 0..query.pattern_count() {
+        let captured = |wanted: &dyn Fn(&str) -> bool| {
+            query
+                .capture_quantifiers(pattern)
+                .iter()
+                .zip(capture_names)
+                .any(|(quantifier, capture)| {
+                    *quantifier != CaptureQuantifier::Zero && wanted(capture)
+                })
+        };
+        if captured(&is_kind) {
+            assert!(
+                captured(&|capture| capture == "name"),
+                "Tags query for {} has a pattern at byte {} without a @name",
+                name,
+                query.start_byte_for_pattern(pattern)
+            );
+        }
+    }
+}
+
+/// Tests a grammar crate's metadata.
+///
+/// `abi_version` has to be the language's own, and one arborium's
+/// tree-sitter can load. `grammar_commit` can't be empty.
+///
+/// # Panics
+///
+/// Panics if any of those checks fail.
+pub fn test_metadata(
+    language: impl Into<Language>,
+    name: &str,
+    abi_version: usize,
+    grammar_commit: &str,
+) {
+    let language: Language = language.into();
+    assert_eq!(
+        abi_version,
+        language.abi_version(),
+        "abi_version() for {} disagrees with the language",
+        name
+    );
+    assert!(
+        (arborium_tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION
+            ..=arborium_tree_sitter::LANGUAGE_VERSION)
+            .contains(&abi_version),
+        "{} was generated for ABI {}, which arborium can't load",
+        name,
+        abi_version
+    );
+    assert!(
+        !grammar_commit.is_empty(),
+        "GRAMMAR_COMMIT for {} is empty",
+        name
+    );
+}
+
+/// Return all sample files for a grammar crate, from its `samples/` directory.
+pub fn sample_files(crate_dir: &str) -> Vec<PathBuf> {
+    files_in(&Path::new(crate_dir).join("samples"))
+}
+
+/// Return the sample files the `name` grammar is tested with.
+///
+/// Grammars sharing a crate, like `erb` and `ejs`, keep their samples apart
+/// in `samples/<name>/`; otherwise they're all of [`sample_files`].
+pub fn grammar_sample_files(crate_dir: &str, name: &str) -> Vec<PathBuf> {
+    let own_dir = Path::new(crate_dir).join("samples").join(name);
+    if own_dir.is_dir() {
+        files_in(&own_dir)
+    } else {
+        sample_files(crate_dir)
     }
 }
 
+/// The files directly in `dir`, sorted.
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<_> = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    entries.sort();
+    entries
+}
+
 /// Runs corpus-style parsing tests for a grammar.
 ///
 /// The harness looks for a `corpus/` directory at the crate root and reads all
@@ -509,53 +848,53 @@ fn parse_corpus(content: &str) -> HarnessResult<Vec<CorpusTest>> {
     Ok(tests)
 }
 
-/// Parse sample paths from arborium.kdl
-///
-/// Looks for `sample { path "..." }` blocks and extracts the path values.
-fn parse_samples_from_kdl(path: &Path) -> Vec<String> {
-    let content = match fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(_) => return vec![],
+/// Read a per-grammar allowlist from the crate directory: one entry per
+/// line, with `#` comments. A missing file is an empty list.
+pub(crate) fn read_allowlist(crate_dir: &Path, file_name: &str) -> BTreeSet<String> {
+    let Ok(content) = fs::read_to_string(crate_dir.join(file_name)) else {
+        return BTreeSet::new();
     };
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
 
-    let mut samples = Vec::new();
-    let mut in_sample_block = false;
-    let mut brace_depth = 0;
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        // Track sample blocks
-        if trimmed.starts_with("sample") && trimmed.contains('{') {
-            in_sample_block = true;
-            brace_depth = 1;
-            continue;
-        }
-
-        if in_sample_block {
-            // Track brace depth
-            brace_depth += trimmed.matches('{').count();
-            brace_depth = brace_depth.saturating_sub(trimmed.matches('}').count());
-
-            if brace_depth == 0 {
-                in_sample_block = false;
-                continue;
-            }
+/// Whether `pattern` contains `token` as a whole word, rather than as part
+/// of a longer name.
+pub(crate) fn mentions_token(pattern: &str, token: &str) -> bool {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.');
+    pattern.match_indices(token).any(|(i, _)| {
+        let before = pattern[..i].chars().next_back();
+        let after = pattern[i + token.len()..].chars().next();
+        !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+    })
+}
 
-            // Look for path "..."
-            if trimmed.starts_with("path")
-                && let Some(start) = trimmed.find('"')
-                && let Some(end) = trimmed[start + 1..].find('"')
-            {
-                let path_value = &trimmed[start + 1..start + 1 + end];
-                if !path_value.is_empty() {
-                    samples.push(path_value.to_string());
-                }
-            }
-        }
+/// Describe where the patterns of `query` satisfying `mentions` start in
+/// `source`, as 1-based line numbers.
+pub(crate) fn pattern_location(
+    query: &Query,
+    source: &str,
+    mentions: impl Fn(&str) -> bool,
+) -> String {
+    let lines: Vec<String> = (0..query.pattern_count())
+        .map(|i| {
+            (
+                query.start_byte_for_pattern(i),
+                query.end_byte_for_pattern(i),
+            )
+        })
+        .filter(|&(start, end)| source.get(start..end).is_some_and(&mentions))
+        .map(|(start, _)| (source[..start].matches('\n').count() + 1).to_string())
+        .collect();
+    match lines.len() {
+        0 => "unknown line".to_string(),
+        1 => format!("line {}", lines[0]),
+        _ => format!("lines {}", lines.join(", ")),
     }
-
-    samples
 }
 
 /// Standard highlight names used by arborium.
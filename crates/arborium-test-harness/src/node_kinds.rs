@@ -0,0 +1,218 @@
+//! Node-kind validation.
+//!
+//! Checks that the node kinds and fields a grammar's queries refer to exist
+//! in the grammar, which is what usually goes wrong after bumping one. All
+//! unknown references are reported at once, with their line, before
+//! tree-sitter gets to reject the first of them.
+//!
+//! Grammars can list references that are expected to be missing in
+//! `node-kind-allowlist.txt`, written the way they're reported: `kind`,
+//! `"literal"` or `field:`. Those are reported as warnings.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+use arborium_tree_sitter::Language;
+
+use crate::{HarnessError, HarnessResult, read_allowlist};
+
+/// Name of the per-grammar allowlist of node kinds and fields that queries
+/// may refer to without the grammar having them.
+pub const NODE_KIND_ALLOWLIST: &str = "node-kind-allowlist.txt";
+
+/// A node kind or field referred to by a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryReference {
+    /// A named node, `(identifier)`.
+    Named(String),
+    /// An anonymous node, `"fn"`.
+    Anonymous(String),
+    /// A field, `name:` or `!name`.
+    Field(String),
+}
+
+impl fmt::Display for QueryReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryReference::Named(kind) => write!(f, "{}", kind),
+            QueryReference::Anonymous(kind) => write!(f, "{:?}", kind),
+            QueryReference::Field(field) => write!(f, "{}:", field),
+        }
+    }
+}
+
+/// The node kinds and fields of a grammar.
+pub struct NodeInventory {
+    language: Language,
+    named: BTreeSet<&'static str>,
+    fields: BTreeSet<&'static str>,
+}
+
+impl NodeInventory {
+    pub fn new(language: &Language) -> Self {
+        let named = (0..language.node_kind_count() as u16)
+            .filter(|&id| language.node_kind_is_named(id))
+            .filter_map(|id| language.node_kind_for_id(id))
+            .collect();
+        let fields = (1..=language.field_count() as u16)
+            .filter_map(|id| language.field_name_for_id(id))
+            .collect();
+        Self {
+            language: language.clone(),
+            named,
+            fields,
+        }
+    }
+
+    /// Whether the grammar has what `reference` refers to.
+    pub fn contains(&self, reference: &QueryReference) -> bool {
+        match reference {
+            QueryReference::Named(kind) => self.named.contains(kind.as_str()),
+            QueryReference::Anonymous(kind) => self.language.id_for_node_kind(kind, false) != 0,
+            QueryReference::Field(field) => self.fields.contains(field.as_str()),
+        }
+    }
+}
+
+/// List the node kinds and fields `source` refers to, with their byte
+/// offsets.
+///
+/// This is a scanner rather than a parser: it skips comments and
+/// predicates, and picks out `(kind`, `"literal"`, `field:` and `!field`.
+/// The wildcard, `ERROR` and `MISSING` aren't node kinds and are left out.
+pub fn query_references(source: &str) -> Vec<(QueryReference, usize)> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/');
+    // For each open paren, whether it's a predicate like `(#eq? ...)`
+    let mut parens: Vec<bool> = Vec::new();
+    let mut references = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let in_predicate = parens.iter().any(|&p| p);
+        match c {
+            ';' => {
+                while i < chars.len() && chars[i].1 != '\n' {
+                    i += 1;
+                }
+            }
+            '"' => {
+                let mut literal = String::new();
+                i += 1;
+                while i < chars.len() && chars[i].1 != '"' {
+                    if chars[i].1 == '\\' && i + 1 < chars.len() {
+                        i += 1;
+                        literal.push(match chars[i].1 {
+                            'n' => '\n',
+                            'r' => '\r',
+                            't' => '\t',
+                            '0' => '\0',
+                            other => other,
+                        });
+                    } else {
+                        literal.push(chars[i].1);
+                    }
+                    i += 1;
+                }
+                if !in_predicate {
+                    references.push((QueryReference::Anonymous(literal), offset));
+                }
+            }
+            '(' => {
+                let mut j = i + 1;
+                while j < chars.len() && chars[j].1.is_whitespace() {
+                    j += 1;
+                }
+                let predicate = chars.get(j).is_some_and(|&(_, c)| c == '#');
+                let start = j;
+                while j < chars.len() && is_name_char(chars[j].1) {
+                    j += 1;
+                }
+                if !predicate && !in_predicate && j > start {
+                    let name: String = chars[start..j].iter().map(|&(_, c)| c).collect();
+                    if !matches!(name.as_str(), "_" | "ERROR" | "MISSING") {
+                        // `supertype/subtype` refers to both
+                        for kind in name.split('/') {
+                            references.push((QueryReference::Named(kind.to_string()), offset));
+                        }
+                    }
+                }
+                parens.push(predicate);
+                i = j;
+                continue;
+            }
+            ')' => {
+                parens.pop();
+            }
+            '@' => {
+                i += 1;
+                while i < chars.len() && is_name_char(chars[i].1) {
+                    i += 1;
+                }
+                continue;
+            }
+            c if c == '!' || c.is_alphanumeric() || c == '_' => {
+                let negated = c == '!';
+                let start = if negated { i + 1 } else { i };
+                let mut j = start;
+                while j < chars.len() && is_name_char(chars[j].1) {
+                    j += 1;
+                }
+                let name: String = chars[start..j].iter().map(|&(_, c)| c).collect();
+                let is_field = negated || chars.get(j).is_some_and(|&(_, c)| c == ':');
+                if is_field && !in_predicate && !name.is_empty() {
+                    references.push((QueryReference::Field(name), offset));
+                }
+                i = j.max(i + 1);
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    references
+}
+
+/// Check that every node kind and field the queries refer to exists in
+/// `language`.
+///
+/// `queries` pairs each query's file name with its source. References in
+/// the grammar's allowlist are only warned about.
+pub fn check_node_kinds(
+    language: &Language,
+    name: &str,
+    queries: &[(&str, &str)],
+    crate_dir: &Path,
+) -> HarnessResult {
+    let inventory = NodeInventory::new(language);
+    let allowlist = read_allowlist(crate_dir, NODE_KIND_ALLOWLIST);
+
+    let mut unknown = Vec::new();
+    for &(file, source) in queries {
+        for (reference, offset) in query_references(source) {
+            if inventory.contains(&reference) {
+                continue;
+            }
+            let line = source[..offset].matches('\n').count() + 1;
+            let report = format!("{} ({} line {})", reference, file, line);
+            if allowlist.contains(&reference.to_string()) {
+                eprintln!("warning: {} refers to a missing node: {}", name, report);
+            } else {
+                unknown.push(report);
+            }
+        }
+    }
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(HarnessError::new(format!(
+        "Queries for {} refer to node kinds or fields the grammar doesn't have:\n  {}\n\
+         Check the grammar's node-types.json, or list them in {} if that's intended.",
+        name,
+        unknown.join("\n  "),
+        NODE_KIND_ALLOWLIST
+    )))
+}
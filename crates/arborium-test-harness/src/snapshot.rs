@@ -0,0 +1,286 @@
+//! Highlight snapshots.
+//!
+//! A snapshot lists the spans and injections the plugin runtime produces for
+//! a sample, one per line, in a format meant to be read in diffs:
+//!
+//! ```text
+//! 0..3 keyword "pub"
+//! 4..6 keyword "fn"
+//! 7..11 function "main"
+//! 40..92 injection(sql)
+//! ```
+//!
+//! Spans are listed by `(start, end, capture)`. Pattern indices are left out,
+//! so reordering a query doesn't churn every snapshot.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use arborium_wire::Utf8ParseResult;
+
+use crate::{HarnessError, HarnessResult};
+
+/// Set to `1` to (re)write snapshots instead of comparing against them.
+///
+/// This covers [golden HTML](crate::golden) too.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "ARBORIUM_UPDATE_SNAPSHOTS";
+
+/// Longest token text shown in a snapshot line, in chars.
+const MAX_TEXT_CHARS: usize = 40;
+
+/// How many differences to list before summarizing the rest.
+const MAX_REPORTED_CHANGES: usize = 50;
+
+/// Directory holding a grammar's snapshots, if it has adopted them.
+///
+/// Snapshots are checked in next to the samples, in `def/snapshots/`, and
+/// that's where they're updated when the crate is built from the repository.
+/// Published crates carry a copy in `snapshots/`.
+pub fn snapshot_dir(crate_dir: &Path) -> Option<PathBuf> {
+    checked_in_dir(crate_dir, "snapshots")
+}
+
+/// Find the directory `name` under the grammar's `def/`, or failing that,
+/// in the crate itself.
+pub(crate) fn checked_in_dir(crate_dir: &Path, name: &str) -> Option<PathBuf> {
+    let def_dir = crate_dir.parent().map(|lang| lang.join("def").join(name));
+    if let Some(dir) = def_dir.filter(|d| d.is_dir()) {
+        return Some(dir);
+    }
+    let crate_dir = crate_dir.join(name);
+    crate_dir.is_dir().then_some(crate_dir)
+}
+
+/// Whether [`UPDATE_SNAPSHOTS_ENV`] asks for snapshots to be rewritten.
+///
+/// `1` rewrites them; `force` also accepts changes beyond size budgets.
+pub(crate) fn update_requested() -> bool {
+    matches!(
+        std::env::var(UPDATE_SNAPSHOTS_ENV).as_deref(),
+        Ok("1") | Ok("force")
+    )
+}
+
+/// Whether [`UPDATE_SNAPSHOTS_ENV`] is `force`.
+pub(crate) fn update_forced() -> bool {
+    std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok_and(|v| v == "force")
+}
+
+/// Whether a missing snapshot should be an error rather than recorded.
+pub(crate) fn missing_is_error() -> bool {
+    !update_requested() && std::env::var_os("CI").is_some()
+}
+
+/// Path of the snapshot for `sample` in `dir`.
+pub fn snapshot_path(dir: &Path, sample: &Path) -> PathBuf {
+    let file_name = sample
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    dir.join(format!("{}.snap", file_name))
+}
+
+/// Render a parse result of `source` as a snapshot.
+pub fn render_snapshot(source: &str, result: &Utf8ParseResult) -> String {
+    let mut spans: Vec<_> = result.spans.iter().collect();
+    spans.sort_by(|a, b| (a.start, a.end, &a.capture).cmp(&(b.start, b.end, &b.capture)));
+
+    let mut out = String::new();
+    for span in spans {
+        let text = source
+            .get(span.start as usize..span.end as usize)
+            .map(snapshot_text)
+            .unwrap_or_else(|| "<invalid range>".to_string());
+        let _ = writeln!(
+            out,
+            "{}..{} {} {}",
+            span.start, span.end, span.capture, text
+        );
+    }
+
+    let mut injections: Vec<_> = result.injections.iter().collect();
+    injections.sort_by(|a, b| (a.start, a.end, &a.language).cmp(&(b.start, b.end, &b.language)));
+    for injection in injections {
+        let _ = writeln!(
+            out,
+            "{}..{} injection({})",
+            injection.start, injection.end, injection.language
+        );
+    }
+    out
+}
+
+/// Quote a token's text, shortening long tokens.
+fn snapshot_text(text: &str) -> String {
+    if text.chars().count() <= MAX_TEXT_CHARS {
+        return format!("{:?}", text);
+    }
+    let head: String = text.chars().take(MAX_TEXT_CHARS).collect();
+    format!("{:?}...", head)
+}
+
+/// Compare `actual` against the snapshot at `path`.
+///
+/// With [`UPDATE_SNAPSHOTS_ENV`] set, the snapshot is written instead. A
+/// missing snapshot is recorded too, except on CI, where it's an error.
+pub fn check_snapshot(path: &Path, actual: &str) -> HarnessResult {
+    let update = update_requested();
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) if !update => expected.replace("\r\n", "\n"),
+        Err(_) if missing_is_error() => {
+            return Err(HarnessError::new(format!(
+                "Missing snapshot {}; run the tests with {}=1 to record it",
+                path.display(),
+                UPDATE_SNAPSHOTS_ENV
+            )));
+        }
+        _ => {
+            return write_snapshot(path, actual);
+        }
+    };
+
+    if expected == actual {
+        return Ok(());
+    }
+    Err(HarnessError::new(format!(
+        "Snapshot {} doesn't match; run the tests with {}=1 to accept the changes\n{}",
+        path.display(),
+        UPDATE_SNAPSHOTS_ENV,
+        diff_snapshots(&expected, actual)
+    )))
+}
+
+pub(crate) fn write_snapshot(path: &Path, contents: &str) -> HarnessResult {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| HarnessError::new(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    fs::write(path, contents).map_err(|e| {
+        HarnessError::new(format!(
+            "Failed to write snapshot {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    eprintln!("Recorded snapshot {}", path.display());
+    Ok(())
+}
+
+/// Labels and token text found at a range in a snapshot.
+#[derive(Default)]
+struct SnapshotEntry<'a> {
+    labels: BTreeSet<&'a str>,
+    text: &'a str,
+}
+
+fn parse_snapshot(snapshot: &str) -> BTreeMap<(u32, u32), SnapshotEntry<'_>> {
+    let mut entries: BTreeMap<(u32, u32), SnapshotEntry<'_>> = BTreeMap::new();
+    for line in snapshot.lines() {
+        let Some((range, rest)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some((start, end)) = range.split_once("..") else {
+            continue;
+        };
+        let (Ok(start), Ok(end)) = (start.parse(), end.parse()) else {
+            continue;
+        };
+        let (label, text) = rest.split_once(' ').unwrap_or((rest, ""));
+        let entry = entries.entry((start, end)).or_default();
+        entry.labels.insert(label);
+        if !text.is_empty() {
+            entry.text = text;
+        }
+    }
+    entries
+}
+
+/// Describe how two snapshots differ, by range: captures that changed,
+/// and tokens that appeared or disappeared.
+pub fn diff_snapshots(expected: &str, actual: &str) -> String {
+    let expected = parse_snapshot(expected);
+    let actual = parse_snapshot(actual);
+    let ranges: BTreeSet<_> = expected.keys().chain(actual.keys()).collect();
+
+    let join = |labels: &BTreeSet<&str>| labels.iter().copied().collect::<Vec<_>>().join(", ");
+    let mut changes = Vec::new();
+    for range in ranges {
+        let line = match (expected.get(range), actual.get(range)) {
+            (Some(old), Some(new)) if old.labels != new.labels => format!(
+                "~ {}..{} {}: {} -> {}",
+                range.0,
+                range.1,
+                new.text,
+                join(&old.labels),
+                join(&new.labels)
+            ),
+            (Some(old), None) => format!(
+                "- {}..{} {}: {}",
+                range.0,
+                range.1,
+                old.text,
+                join(&old.labels)
+            ),
+            (None, Some(new)) => format!(
+                "+ {}..{} {}: {}",
+                range.0,
+                range.1,
+                new.text,
+                join(&new.labels)
+            ),
+            _ => continue,
+        };
+        changes.push(line);
+    }
+
+    let mut out = format!(
+        "{} token(s) changed (~ changed capture, - removed, + added):\n",
+        changes.len()
+    );
+    for line in changes.iter().take(MAX_REPORTED_CHANGES) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if changes.len() > MAX_REPORTED_CHANGES {
+        let _ = writeln!(out, "... and {} more", changes.len() - MAX_REPORTED_CHANGES);
+    }
+    out
+}
+
+/// Show where two texts that aren't snapshots differ: the lines between
+/// their common beginning and end.
+pub(crate) fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut out = format!("first difference on line {}:\n", prefix + 1);
+    let removed = &expected[prefix..expected.len() - suffix];
+    let added = &actual[prefix..actual.len() - suffix];
+    for (sign, lines) in [('-', removed), ('+', added)] {
+        for line in lines.iter().take(MAX_REPORTED_CHANGES) {
+            let _ = writeln!(out, "{} {}", sign, line);
+        }
+        if lines.len() > MAX_REPORTED_CHANGES {
+            let _ = writeln!(
+                out,
+                "{} ... and {} more",
+                sign,
+                lines.len() - MAX_REPORTED_CHANGES
+            );
+        }
+    }
+    out
+}
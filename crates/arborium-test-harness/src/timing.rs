@@ -0,0 +1,277 @@
+//! Parse-time budgets.
+//!
+//! Timing is opt-in: with `ARBORIUM_TIMING=1`, each sample is highlighted
+//! [`DEFAULT_RUNS`] times (`ARBORIUM_TIMING_RUNS` overrides it) and the
+//! median wall time is compared against the grammar's `timing-budget.txt`.
+//! A sample fails when its median exceeds its budget by more than
+//! [`DEFAULT_FACTOR`] (`ARBORIUM_TIMING_FACTOR` overrides it); budgets are
+//! generous and medians ignore outliers, so this only catches real
+//! regressions on a noisy CI machine.
+//!
+//! Set `ARBORIUM_UPDATE_TIMING=1` to re-baseline the budgets from the
+//! current medians. Samples without a budget are only reported.
+//!
+//! Set `ARBORIUM_TIMING_REPORT` to a directory to get a `<grammar>.json`
+//! report there, for charting trends.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use arborium_plugin_runtime::PluginRuntime;
+
+use crate::{HarnessError, HarnessResult};
+
+/// Environment variable enabling timing.
+pub const TIMING_ENV: &str = "ARBORIUM_TIMING";
+
+/// Environment variable overriding how many times each sample is parsed.
+pub const TIMING_RUNS_ENV: &str = "ARBORIUM_TIMING_RUNS";
+
+/// Environment variable overriding how far over budget a sample may go.
+pub const TIMING_FACTOR_ENV: &str = "ARBORIUM_TIMING_FACTOR";
+
+/// Environment variable asking for the budgets to be rewritten.
+pub const UPDATE_TIMING_ENV: &str = "ARBORIUM_UPDATE_TIMING";
+
+/// Environment variable naming the directory JSON reports are written to.
+pub const TIMING_REPORT_ENV: &str = "ARBORIUM_TIMING_REPORT";
+
+/// Name of the per-grammar budget file.
+pub const TIMING_BUDGET: &str = "timing-budget.txt";
+
+/// How many times each sample is parsed by default.
+pub const DEFAULT_RUNS: usize = 11;
+
+/// How many times its budget a sample may take by default.
+pub const DEFAULT_FACTOR: f64 = 3.0;
+
+/// Whether [`TIMING_ENV`] asks for timing.
+pub fn enabled() -> bool {
+    matches!(std::env::var(TIMING_ENV).as_deref(), Ok("1"))
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Path of a grammar's budget file, preferring the checked-in one in
+/// `def/`.
+pub fn budget_path(crate_dir: &Path) -> PathBuf {
+    let def_path = crate_dir
+        .parent()
+        .map(|lang| lang.join("def"))
+        .filter(|def| def.is_dir())
+        .map(|def| def.join(TIMING_BUDGET));
+    def_path.unwrap_or_else(|| crate_dir.join(TIMING_BUDGET))
+}
+
+/// Read a budget file: one `<sample file name> <microseconds>` per line,
+/// `#` starting comments.
+pub fn read_budgets(path: &Path) -> BTreeMap<String, u64> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return BTreeMap::new();
+    };
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter_map(|line| {
+            let (sample, micros) = line.rsplit_once(char::is_whitespace)?;
+            Some((sample.trim().to_string(), micros.parse().ok()?))
+        })
+        .collect()
+}
+
+fn write_budgets(path: &Path, medians: &BTreeMap<String, u64>) -> HarnessResult {
+    let mut content = String::from(
+        "# Median microseconds to highlight each sample, recorded with\n\
+         # ARBORIUM_UPDATE_TIMING=1. The test harness fails when a sample takes\n\
+         # several times longer, see ARBORIUM_TIMING_FACTOR.\n",
+    );
+    for (sample, micros) in medians {
+        let _ = writeln!(content, "{} {}", sample, micros);
+    }
+    fs::write(path, content).map_err(|e| {
+        HarnessError::new(format!(
+            "Failed to write timing budgets {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Timing of one sample.
+#[derive(Debug, Clone)]
+pub struct SampleTiming {
+    pub sample: String,
+    pub bytes: usize,
+    pub median: Duration,
+    pub budget: Option<Duration>,
+}
+
+impl SampleTiming {
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.bytes as f64 / self.median.as_secs_f64().max(1e-9)
+    }
+}
+
+/// Median time to highlight `source` from scratch, over `runs` runs.
+pub fn median_parse_time(
+    runtime: &mut PluginRuntime,
+    session: u32,
+    source: &str,
+    runs: usize,
+) -> Duration {
+    let mut times: Vec<Duration> = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            runtime.set_text(session, source);
+            let _ = runtime.parse(session);
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[times.len() / 2]
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Render a machine-readable report of `timings`.
+pub fn render_report(name: &str, runs: usize, timings: &[SampleTiming]) -> String {
+    let samples: Vec<String> = timings
+        .iter()
+        .map(|t| {
+            let budget = t
+                .budget
+                .map_or("null".to_string(), |b| b.as_micros().to_string());
+            format!(
+                "    {{\"sample\": {}, \"bytes\": {}, \"median_us\": {}, \"bytes_per_sec\": {:.0}, \"budget_us\": {}}}",
+                json_string(&t.sample),
+                t.bytes,
+                t.median.as_micros(),
+                t.bytes_per_sec(),
+                budget
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"grammar\": {},\n  \"runs\": {},\n  \"samples\": [\n{}\n  ]\n}}\n",
+        json_string(name),
+        runs,
+        samples.join(",\n")
+    )
+}
+
+/// Time highlighting each of `samples`, given as `(path, source)` pairs,
+/// and compare the medians against the grammar's budgets.
+pub fn check_parse_times(
+    runtime: &mut PluginRuntime,
+    name: &str,
+    samples: &[(PathBuf, String)],
+    crate_dir: &Path,
+) -> HarnessResult {
+    let runs = env_or(TIMING_RUNS_ENV, DEFAULT_RUNS);
+    let factor = env_or(TIMING_FACTOR_ENV, DEFAULT_FACTOR);
+    let path = budget_path(crate_dir);
+    let budgets = read_budgets(&path);
+
+    let session = runtime.create_session();
+    let timings: Vec<SampleTiming> = samples
+        .iter()
+        .map(|(sample_path, source)| {
+            let sample = sample_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let budget = budgets.get(&sample).copied().map(Duration::from_micros);
+            SampleTiming {
+                median: median_parse_time(runtime, session, source, runs),
+                bytes: source.len(),
+                budget,
+                sample,
+            }
+        })
+        .collect();
+    runtime.free_session(session);
+
+    for t in &timings {
+        eprintln!(
+            "timing: {} / {}: {:?} median, {:.1} MB/s{}",
+            name,
+            t.sample,
+            t.median,
+            t.bytes_per_sec() / 1e6,
+            t.budget
+                .map_or(" (no budget)".to_string(), |b| format!(", budget {:?}", b))
+        );
+    }
+
+    if let Ok(dir) = std::env::var(TIMING_REPORT_ENV) {
+        let dir = PathBuf::from(dir);
+        let report = dir.join(format!("{}.json", name));
+        fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(&report, render_report(name, runs, &timings)))
+            .map_err(|e| {
+                HarnessError::new(format!(
+                    "Failed to write timing report {}: {}",
+                    report.display(),
+                    e
+                ))
+            })?;
+    }
+
+    if matches!(std::env::var(UPDATE_TIMING_ENV).as_deref(), Ok("1")) {
+        let medians = timings
+            .iter()
+            .map(|t| (t.sample.clone(), t.median.as_micros() as u64))
+            .collect();
+        return write_budgets(&path, &medians);
+    }
+
+    let over: Vec<String> = timings
+        .iter()
+        .filter_map(|t| {
+            let budget = t.budget?;
+            (t.median.as_secs_f64() > budget.as_secs_f64() * factor).then(|| {
+                format!(
+                    "{}: {:?} median, budget {:?} (x{:.1})",
+                    t.sample,
+                    t.median,
+                    budget,
+                    t.median.as_secs_f64() / budget.as_secs_f64().max(1e-9)
+                )
+            })
+        })
+        .collect();
+    if over.is_empty() {
+        return Ok(());
+    }
+    Err(HarnessError::new(format!(
+        "Highlighting got slower for {}, beyond {}x the budget in {}:\n  {}\n\
+         Run the tests with {}=1 to re-baseline if that's expected.",
+        name,
+        factor,
+        path.display(),
+        over.join("\n  "),
+        UPDATE_TIMING_ENV
+    )))
+}
@@ -232,11 +232,16 @@ pub fn capture_to_slot(capture: &str) -> ThemeSlot {
         | "character" | "character.special" | "escape" => ThemeSlot::String,
 
         // Comments
-        "comment" | "comment.documentation" | "comment.line" | "comment.block"
-        | "comment.error" | "comment.warning" | "comment.note" | "comment.todo" => {
+        "comment" | "comment.documentation" | "comment.line" | "comment.block" => {
             ThemeSlot::Comment
         }
 
+        // Tags inside comments (TODO:, NOTE:, FIXME:) borrow slots every
+        // theme already colors, so they stand out from the comment around them
+        "comment.todo" | "comment.warning" => ThemeSlot::Keyword,
+        "comment.note" => ThemeSlot::Label,
+        "comment.error" => ThemeSlot::Error,
+
         // Types
         "type" | "type.builtin" | "type.qualifier" | "type.definition" | "type.enum"
         | "type.enum.variant" | "type.parameter" => ThemeSlot::Type,
@@ -302,7 +307,7 @@ pub fn capture_to_slot(capture: &str) -> ThemeSlot {
 
         // Markup - literal/raw/code
         "text.literal" | "markup.raw" | "markup.raw.block" | "markup.raw.inline"
-        | "markup.inline" => ThemeSlot::Literal,
+        | "markup.inline" | "markup.math" => ThemeSlot::Literal,
 
         // Markup - strikethrough
         "text.strikethrough" | "markup.strikethrough" => ThemeSlot::Strikethrough,
@@ -313,6 +318,15 @@ pub fn capture_to_slot(capture: &str) -> ThemeSlot {
             ThemeSlot::Punctuation
         }
 
+        // Table columns (CSV, TSV), rotating through slots with distinct
+        // colors so neighbouring columns never look alike
+        "column.1" => ThemeSlot::Keyword,
+        "column.2" => ThemeSlot::String,
+        "column.3" => ThemeSlot::Function,
+        "column.4" => ThemeSlot::Type,
+        "column.5" => ThemeSlot::Number,
+        "column.6" => ThemeSlot::Tag,
+
         // Diff
         "diff.addition" | "diff.plus" | "diff.delta" => ThemeSlot::DiffAdd,
         "diff.deletion" | "diff.minus" => ThemeSlot::DiffDelete,
@@ -1110,6 +1124,7 @@ pub const CAPTURE_NAMES: &[&str] = &[
     "markup.raw.block",
     "markup.raw.inline",
     "markup.inline",
+    "markup.math",
     // Markup - strikethrough
     "text.strikethrough",
     "markup.strikethrough",
@@ -1123,6 +1138,13 @@ pub const CAPTURE_NAMES: &[&str] = &[
     // Markup - generic
     "text",
     "markup",
+    // Table columns
+    "column.1",
+    "column.2",
+    "column.3",
+    "column.4",
+    "column.5",
+    "column.6",
     // Diff
     "diff.addition",
     "diff.plus",
@@ -1196,6 +1218,7 @@ mod tests {
         assert_eq!(capture_to_slot("text.title"), ThemeSlot::Title);
         assert_eq!(capture_to_slot("markup.bold"), ThemeSlot::Strong);
         assert_eq!(capture_to_slot("markup.italic"), ThemeSlot::Emphasis);
+        assert_eq!(capture_to_slot("markup.math"), ThemeSlot::Literal);
     }
 
     #[test]
@@ -1221,6 +1244,18 @@ mod tests {
         assert_eq!(tag_for_capture("comment"), Some("c"));
         assert_eq!(tag_for_capture("comment.documentation"), Some("c"));
 
+        // Comment tags stand out from the comment they're in
+        assert_eq!(tag_for_capture("comment.todo"), Some("k"));
+        assert_eq!(tag_for_capture("comment.warning"), Some("k"));
+        assert_eq!(tag_for_capture("comment.note"), Some("l"));
+        assert_eq!(tag_for_capture("comment.error"), Some("er"));
+
+        // Each rotating table column gets its own color
+        let columns: Vec<_> = (1..=6)
+            .map(|n| tag_for_capture(&format!("column.{n}")).unwrap())
+            .collect();
+        assert_eq!(columns, ["k", "s", "f", "t", "n", "tg"]);
+
         // No tag for special captures
         assert_eq!(tag_for_capture("spell"), None);
         assert_eq!(tag_for_capture("none"), None);
@@ -432,6 +432,34 @@ impl Theme {
             writeln!(css, " }}").unwrap();
         }
 
+        // Diagnostic overlays from the HTML renderer, drawn as squiggles
+        let error_idx = HIGHLIGHTS.iter().position(|h| h.name == "error");
+        let error_color = error_idx
+            .and_then(|i| self.styles[i].fg.as_ref())
+            .map(|c| c.to_hex())
+            .unwrap_or_else(|| "#e51400".to_string());
+        writeln!(
+            css,
+            "  .diag {{ text-decoration: wavy underline; text-decoration-skip-ink: none; }}"
+        )
+        .unwrap();
+        writeln!(
+            css,
+            "  .diag-error {{ text-decoration-color: {error_color}; }}"
+        )
+        .unwrap();
+        writeln!(css, "  .diag-warning {{ text-decoration-color: #e5a000; }}").unwrap();
+        writeln!(
+            css,
+            "  .diag-info {{ text-decoration-color: var(--accent); }}"
+        )
+        .unwrap();
+        writeln!(
+            css,
+            "  .diag-hint {{ text-decoration-color: var(--muted); }}"
+        )
+        .unwrap();
+
         writeln!(css, "}}").unwrap();
 
         css
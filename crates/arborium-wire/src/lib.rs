@@ -31,7 +31,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Bump this when making breaking changes to the protocol.
 /// Host and plugins must agree on this version.
-pub const WIRE_VERSION: u32 = 2;
+pub const WIRE_VERSION: u32 = 6;
 
 // ============================================================================
 // UTF-8 types (native tree-sitter offsets, for Rust string slicing)
@@ -67,6 +67,10 @@ pub struct Utf8Injection {
     pub language: String,
     /// Whether to include the node children in the injection.
     pub include_children: bool,
+    /// Whether this is one fragment of a combined injection. Fragments
+    /// with the same language are parsed together as one document.
+    #[serde(default)]
+    pub combined: bool,
 }
 
 /// Result of parsing text, with UTF-8 byte offsets.
@@ -91,6 +95,30 @@ impl Utf8ParseResult {
     }
 }
 
+/// A definition or reference of a symbol, found by a grammar's tags query,
+/// with UTF-8 byte offsets.
+///
+/// Definitions make up a file's outline: functions, classes, methods and
+/// the like. Kinds are named after the query's captures, so
+/// `@definition.function` is a definition of kind `"function"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Utf8Tag {
+    /// UTF-8 byte offset where the tagged item starts.
+    pub start: u32,
+    /// UTF-8 byte offset where the tagged item ends (exclusive).
+    pub end: u32,
+    /// UTF-8 byte offset where the item's name starts.
+    pub name_start: u32,
+    /// UTF-8 byte offset where the item's name ends (exclusive).
+    pub name_end: u32,
+    /// The item's name, e.g. the name of a function.
+    pub name: String,
+    /// The kind of item (e.g., "function", "class", "call").
+    pub kind: String,
+    /// Whether this defines the symbol, rather than referring to it.
+    pub is_definition: bool,
+}
+
 // ============================================================================
 // UTF-16 types (for JavaScript interop)
 // ============================================================================
@@ -125,6 +153,10 @@ pub struct Utf16Injection {
     pub language: String,
     /// Whether to include the node children in the injection.
     pub include_children: bool,
+    /// Whether this is one fragment of a combined injection. Fragments
+    /// with the same language are parsed together as one document.
+    #[serde(default)]
+    pub combined: bool,
 }
 
 /// Result of parsing text, with UTF-16 code unit indices.
@@ -172,7 +204,7 @@ pub type Injection = Utf8Injection;
 pub type ParseResult = Utf8ParseResult;
 
 // ============================================================================
-// Other types (not offset-dependent)
+// Other types
 // ============================================================================
 
 /// An edit to apply to the text (for incremental parsing).
@@ -198,20 +230,104 @@ pub struct Edit {
     pub new_end_col: u32,
 }
 
+/// A node of a syntax tree, for tree explorers and query debugging.
+///
+/// Offsets are UTF-8 byte offsets; rows and columns are zero-based, with
+/// columns counted in bytes (as tree-sitter does).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyntaxNode {
+    /// The node kind (e.g., "function_item", "identifier", "ERROR").
+    pub kind: String,
+    /// UTF-8 byte offset where the node starts.
+    pub start: u32,
+    /// UTF-8 byte offset where the node ends (exclusive).
+    pub end: u32,
+    /// Row where the node starts.
+    pub start_row: u32,
+    /// Column where the node starts.
+    pub start_col: u32,
+    /// Row where the node ends.
+    pub end_row: u32,
+    /// Column where the node ends.
+    pub end_col: u32,
+    /// Whether this is an `ERROR` node inserted by error recovery.
+    pub is_error: bool,
+    /// Whether this node was inserted by error recovery to stand in for
+    /// something the parser expected but didn't find.
+    pub is_missing: bool,
+    /// Named child nodes. Empty for leaves, and for nodes below the depth
+    /// limit or past the node-count cap.
+    pub children: Vec<SyntaxNode>,
+}
+
+/// A (possibly truncated) syntax tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyntaxTree {
+    /// The root node.
+    pub root: SyntaxNode,
+    /// Number of nodes included in `root`, the root itself included.
+    pub node_count: u32,
+    /// Whether nodes were left out because of the depth limit or node cap.
+    pub truncated: bool,
+}
+
 /// Error that can occur during parsing.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParseError {
+    /// What kind of failure this is.
+    pub kind: ParseErrorKind,
     /// Error message.
     pub message: String,
 }
 
+/// Kinds of [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseErrorKind {
+    /// The request couldn't be served, e.g. the session has no text yet.
+    Request,
+    /// The plugin's queries failed to compile. Plugins may compile them on
+    /// first use, so this can come back from any parse.
+    Query,
+    /// The plugin panicked; the message is the panic payload. The session
+    /// is [`Poisoned`](Self::Poisoned) from then on.
+    Internal,
+    /// The session was poisoned by an earlier panic. Free it and create a
+    /// new one.
+    Poisoned,
+}
+
 impl ParseError {
-    /// Create a new parse error.
+    /// Create a new parse error of kind [`ParseErrorKind::Request`].
     pub fn new(message: impl Into<String>) -> Self {
         Self {
+            kind: ParseErrorKind::Request,
             message: message.into(),
         }
     }
+
+    /// Create an error of kind [`ParseErrorKind::Query`].
+    pub fn query(message: impl Into<String>) -> Self {
+        Self {
+            kind: ParseErrorKind::Query,
+            message: message.into(),
+        }
+    }
+
+    /// Create an error of kind [`ParseErrorKind::Internal`].
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self {
+            kind: ParseErrorKind::Internal,
+            message: message.into(),
+        }
+    }
+
+    /// Create an error of kind [`ParseErrorKind::Poisoned`].
+    pub fn poisoned(session: u32) -> Self {
+        Self {
+            kind: ParseErrorKind::Poisoned,
+            message: alloc::format!("session {} was poisoned by a panic", session),
+        }
+    }
 }
 
 /// Check if a wire version is compatible with the current version.
@@ -221,3 +337,171 @@ impl ParseError {
 pub fn is_version_compatible(version: u32) -> bool {
     version == WIRE_VERSION
 }
+
+// ============================================================================
+// Plugin manifest
+// ============================================================================
+
+/// Name of the wasm custom section holding a plugin's [`PluginManifest`].
+///
+/// The section is postcard-encoded, so hosts can tell what a `.wasm` file
+/// provides without instantiating it.
+pub const MANIFEST_SECTION: &str = "arborium_manifest";
+
+/// What a grammar plugin provides, embedded in its [`MANIFEST_SECTION`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// The wire protocol version the plugin speaks.
+    ///
+    /// Kept first, so it stays decodable if later fields change.
+    pub wire_version: u32,
+    /// The canonical language ID (e.g., "rust").
+    pub language_id: String,
+    /// Alternative names the language answers to (e.g., "rs").
+    pub aliases: Vec<String>,
+    /// File extensions the language is detected from, without the dot.
+    pub extensions: Vec<String>,
+    /// Whether the plugin has an injections query.
+    pub provides_injections: bool,
+    /// Whether the plugin has a locals query.
+    pub provides_locals: bool,
+    /// Hash of the plugin's query files, for cache invalidation.
+    pub query_hash: String,
+    /// The upstream revision the grammar was vendored from, or `src-` and
+    /// a hash of its sources, for cache invalidation.
+    pub grammar_commit: String,
+    /// The arborium release the plugin was built from (e.g., "2.4.1").
+    ///
+    /// Hosts finding two plugins for the same language prefer the newer one.
+    pub version: String,
+}
+
+/// One of the queries a plugin was built with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QueryKind {
+    /// `highlights.scm`.
+    Highlights,
+    /// `injections.scm`.
+    Injections,
+    /// `locals.scm`.
+    Locals,
+}
+
+impl QueryKind {
+    /// All kinds, in the order plugins combine them.
+    pub const ALL: [QueryKind; 3] = [
+        QueryKind::Injections,
+        QueryKind::Locals,
+        QueryKind::Highlights,
+    ];
+
+    /// The query file name without extension, e.g. `"highlights"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            QueryKind::Highlights => "highlights",
+            QueryKind::Injections => "injections",
+            QueryKind::Locals => "locals",
+        }
+    }
+
+    /// Parse a name from [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        QueryKind::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// The number identifying the kind in the raw ABI.
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    /// Parse a number from [`to_u32`](Self::to_u32).
+    pub fn from_u32(value: u32) -> Option<Self> {
+        QueryKind::ALL
+            .into_iter()
+            .find(|kind| kind.to_u32() == value)
+    }
+}
+
+/// Raw ABI for driving plugins from native hosts.
+///
+/// The wasm-bindgen exports of a plugin need JS glue to be called. Plugins
+/// also export the functions named here, which only take and return
+/// integers, so any wasm runtime can drive them (see `arborium-plugin-host`).
+///
+/// Byte buffers cross the boundary in guest memory:
+///
+/// - Arguments are written by the host into a buffer it allocated with
+///   [`ALLOC`], and freed with [`DEALLOC`] after the call.
+/// - Results are postcard-encoded and returned as a [`pack`]ed pointer and
+///   length. The host copies them out and frees them with [`DEALLOC`].
+pub mod abi {
+    use alloc::string::String;
+    use serde::{Deserialize, Serialize};
+
+    use crate::Edit;
+
+    /// `() -> u32`: the plugin's [`WIRE_VERSION`](crate::WIRE_VERSION).
+    pub const WIRE_VERSION: &str = "arborium_wire_version";
+    /// `(len: u32) -> u32`: allocate `len` bytes in guest memory.
+    pub const ALLOC: &str = "arborium_alloc";
+    /// `(ptr: u32, len: u32)`: free a buffer from [`ALLOC`] or a result.
+    pub const DEALLOC: &str = "arborium_dealloc";
+    /// `() -> u32`: create a session.
+    pub const CREATE_SESSION: &str = "arborium_create_session";
+    /// `(session: u32)`: free a session.
+    pub const FREE_SESSION: &str = "arborium_free_session";
+    /// `(session: u32, ptr: u32, len: u32) -> u64`: set the session's text
+    /// from UTF-8 bytes. Returns `Result<(), ParseError>`.
+    pub const SET_TEXT: &str = "arborium_set_text";
+    /// `(session: u32, ptr: u32, len: u32) -> u64`: apply an
+    /// [`EditRequest`]. Returns `Result<(), ParseError>`.
+    pub const APPLY_EDIT: &str = "arborium_apply_edit";
+    /// `(session: u32, ptr: u32, len: u32) -> u64`: set the session's text
+    /// from UTF-8 bytes without parsing it, for [`PARSE_STEP`]. Returns
+    /// `Result<(), ParseError>`. Optional: older plugins don't export it.
+    pub const LOAD_TEXT: &str = "arborium_load_text";
+    /// `(session: u32, max_bytes: u32) -> u64`: continue parsing text from
+    /// [`LOAD_TEXT`], stopping once the parser got about `max_bytes`
+    /// further. Returns `Result<bool, ParseError>`, `true` once the parse is
+    /// complete. [`PARSE`] finishes an incomplete parse. Optional, like
+    /// [`LOAD_TEXT`].
+    pub const PARSE_STEP: &str = "arborium_parse_step";
+    /// `(session: u32) -> u64`: parse the session's text. Returns
+    /// `Result<Utf8ParseResult, ParseError>`.
+    pub const PARSE: &str = "arborium_parse";
+    /// `(session: u32)`: cancel an in-progress parse.
+    pub const CANCEL: &str = "arborium_cancel";
+    /// `() -> u64`: the capture names of the plugin's queries. Returns
+    /// `Result<Vec<String>, ParseError>`.
+    pub const CAPTURE_NAMES: &str = "arborium_capture_names";
+    /// `(kind: u32) -> u64`: the source of a query, `kind` being a
+    /// [`QueryKind::to_u32`](crate::QueryKind::to_u32). Returns
+    /// `Result<String, ParseError>`.
+    pub const QUERY_SOURCE: &str = "arborium_query_source";
+    /// `() -> u64`: the hash of the plugin's queries, as in its manifest.
+    /// Returns `Result<String, ParseError>`.
+    pub const QUERY_HASH: &str = "arborium_query_hash";
+    /// `(session: u32) -> u64`: panic while working on the session, for
+    /// testing how hosts handle it. Returns `Result<(), ParseError>`. Only
+    /// exported by plugins built with the `test-hooks` feature.
+    pub const TEST_PANIC: &str = "arborium_test_panic";
+
+    /// Arguments of [`APPLY_EDIT`].
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct EditRequest {
+        /// The full text after the edit.
+        pub new_text: String,
+        /// The edit that turned the old text into `new_text`.
+        pub edit: Edit,
+    }
+
+    /// Pack a guest pointer and length into a single return value.
+    pub fn pack(ptr: u32, len: u32) -> u64 {
+        ((ptr as u64) << 32) | len as u64
+    }
+
+    /// Split a value from [`pack`] back into pointer and length.
+    pub fn unpack(packed: u64) -> (u32, u32) {
+        ((packed >> 32) as u32, packed as u32)
+    }
+}
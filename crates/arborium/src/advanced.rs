@@ -46,8 +46,15 @@ pub use arborium_highlight::{Injection, ParseResult, Span};
 
 // Low-level rendering utilities
 pub use arborium_highlight::{
-    html_escape, spans_to_ansi, spans_to_ansi_with_options, spans_to_html, write_spans_as_html,
+    html_escape, html_escape_with_mode, spans_to_ansi, spans_to_ansi_with_options, spans_to_html,
+    spans_to_html_with_options, write_spans_as_html,
 };
 
 // ANSI rendering options
 pub use arborium_highlight::AnsiOptions;
+
+// Span decorations (e.g. hyperlinks)
+pub use arborium_highlight::{Decoration, DecorationContext, Decorator};
+
+// Diagnostic overlays for HTML output
+pub use arborium_highlight::{Diagnostic, Severity};
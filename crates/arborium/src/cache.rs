@@ -0,0 +1,409 @@
+//! Content-hash based render cache.
+//!
+//! Static site builds tend to highlight the same snippets over and over, both
+//! within a build and across incremental rebuilds. A [`RenderCache`] stores
+//! rendered output keyed by everything that can influence it (see
+//! [`CacheKey`]), so a cache hit skips parsing entirely.
+//!
+//! Storage is pluggable through [`CacheStore`]. [`LruStore`] keeps entries in
+//! memory; with the `cache-fs` feature, [`FsStore`] keeps them on disk so
+//! they survive between runs.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use arborium::Highlighter;
+//! use arborium::cache::RenderCache;
+//!
+//! let mut hl = Highlighter::new();
+//! let mut cache = RenderCache::in_memory(1024);
+//! let html = hl.highlight_cached(&mut cache, "rust", "fn main() {}")?;
+//! let again = hl.highlight_cached(&mut cache, "rust", "fn main() {}")?;
+//! assert_eq!(html, again);
+//! assert_eq!((cache.hits(), cache.misses()), (1, 1));
+//! ```
+
+use std::collections::HashMap;
+
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::Config;
+use arborium_highlight::AnsiOptions;
+
+/// Everything that can influence a rendered snippet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Language the snippet was highlighted as.
+    pub language: String,
+    /// Name of the theme used for rendering, or empty if the output doesn't
+    /// depend on a theme (as with HTML, which is themed by CSS).
+    pub theme: String,
+    /// Fingerprint of the rendering options and of the crate's queries, from
+    /// [`html_fingerprint`] or [`ansi_fingerprint`].
+    pub options: u64,
+    /// Fingerprint of the language's grammar, from [`grammar_fingerprint`].
+    pub grammar: u64,
+    /// xxh3 hash of the source text.
+    pub source: u64,
+}
+
+impl CacheKey {
+    /// Build a key, hashing `source` and fingerprinting the grammar of
+    /// `language`.
+    pub fn new(language: &str, theme: &str, options: u64, source: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            theme: theme.to_string(),
+            options,
+            grammar: grammar_fingerprint(language),
+            source: xxh3_64(source.as_bytes()),
+        }
+    }
+
+    /// A single hash of the whole key, e.g. for use as a file name.
+    pub fn digest(&self) -> u64 {
+        let mut bytes = Vec::with_capacity(self.language.len() + self.theme.len() + 26);
+        bytes.extend_from_slice(self.language.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(self.theme.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&self.options.to_le_bytes());
+        bytes.extend_from_slice(&self.grammar.to_le_bytes());
+        bytes.extend_from_slice(&self.source.to_le_bytes());
+        xxh3_64(&bytes)
+    }
+}
+
+/// Fingerprint of a highlighting configuration for HTML output.
+///
+/// Changes whenever the configuration, the crate version or any of the
+/// bundled queries change, so stale entries are never served after an
+/// upgrade.
+///
+/// Decorators are closures and can't be fingerprinted: if a
+/// [`HtmlOptions::decorator`](crate::HtmlOptions::decorator) is set, its
+/// behavior must not change while the cache is in use.
+pub fn html_fingerprint(config: &Config) -> u64 {
+    fingerprint(&format!(
+        "html|{}|{:?}|{:?}",
+        config.max_injection_depth, config.html_format, config.html_options
+    ))
+}
+
+/// Fingerprint of a highlighting configuration and ANSI options for ANSI
+/// output. See [`html_fingerprint`].
+pub fn ansi_fingerprint(config: &Config, options: &AnsiOptions) -> u64 {
+    fingerprint(&format!(
+        "ansi|{}|{:?}",
+        config.max_injection_depth, options
+    ))
+}
+
+/// Fingerprint of the grammar a language is highlighted with: its upstream
+/// revision, parser ABI version and queries.
+///
+/// Picks up a grammar bump that leaves the queries alone, which the
+/// fingerprints of the options don't. Zero for languages that aren't
+/// enabled; `language` can be an ID or an alias.
+pub fn grammar_fingerprint(language: &str) -> u64 {
+    let Some(info) = crate::supported_languages_detailed()
+        .iter()
+        .find(|info| info.id == language || info.aliases.contains(&language))
+    else {
+        return 0;
+    };
+    xxh3_64(
+        format!(
+            "{}|{}|{}",
+            info.grammar_commit,
+            info.abi_version(),
+            info.queries_hash()
+        )
+        .as_bytes(),
+    )
+}
+
+fn fingerprint(options: &str) -> u64 {
+    let salted = format!(
+        "{}|{}|{options}",
+        env!("CARGO_PKG_VERSION"),
+        crate::QUERY_HASH
+    );
+    xxh3_64(salted.as_bytes())
+}
+
+/// Backing storage for a [`RenderCache`].
+pub trait CacheStore: Send {
+    /// Look up a rendered snippet.
+    fn get(&mut self, key: &CacheKey) -> Option<String>;
+
+    /// Store a rendered snippet.
+    fn insert(&mut self, key: CacheKey, rendered: String);
+}
+
+/// In-memory store that evicts the least recently used entry once it holds
+/// `capacity` entries.
+#[derive(Debug, Clone)]
+pub struct LruStore {
+    capacity: usize,
+    /// Entries with the tick of their last use.
+    entries: HashMap<CacheKey, (String, u64)>,
+    tick: u64,
+}
+
+impl LruStore {
+    /// Create a store holding at most `capacity` entries (at least one).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Number of entries currently stored.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl CacheStore for LruStore {
+    fn get(&mut self, key: &CacheKey) -> Option<String> {
+        self.tick += 1;
+        let (rendered, used) = self.entries.get_mut(key)?;
+        *used = self.tick;
+        Some(rendered.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, rendered: String) {
+        self.tick += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (rendered, self.tick));
+    }
+}
+
+/// On-disk store keeping one file per entry in a directory.
+///
+/// Files are named after [`CacheKey::digest`]. I/O errors are treated as
+/// cache misses, so a broken cache directory only costs performance.
+#[cfg(feature = "cache-fs")]
+#[derive(Debug, Clone)]
+pub struct FsStore {
+    dir: std::path::PathBuf,
+}
+
+#[cfg(feature = "cache-fs")]
+impl FsStore {
+    /// Use `dir` for cache entries, creating it on first insert.
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &CacheKey) -> std::path::PathBuf {
+        self.dir.join(format!("{:016x}.html", key.digest()))
+    }
+}
+
+#[cfg(feature = "cache-fs")]
+impl CacheStore for FsStore {
+    fn get(&mut self, key: &CacheKey) -> Option<String> {
+        std::fs::read_to_string(self.path(key)).ok()
+    }
+
+    fn insert(&mut self, key: CacheKey, rendered: String) {
+        // Write to a temporary file first so readers never see partial entries
+        let path = self.path(&key);
+        let tmp = path.with_extension("tmp");
+        let _ = std::fs::create_dir_all(&self.dir)
+            .and_then(|()| std::fs::write(&tmp, rendered))
+            .and_then(|()| std::fs::rename(&tmp, &path));
+    }
+}
+
+/// Cache of rendered snippets, counting hits and misses.
+pub struct RenderCache {
+    store: Box<dyn CacheStore>,
+    hits: u64,
+    misses: u64,
+}
+
+impl RenderCache {
+    /// Create a cache backed by `store`.
+    pub fn new(store: impl CacheStore + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Create a cache backed by an [`LruStore`] holding `capacity` entries.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self::new(LruStore::new(capacity))
+    }
+
+    /// Return the cached output for `key`, or render, store and return it.
+    ///
+    /// Errors from `render` are returned as is and nothing is stored.
+    pub fn get_or_render<E>(
+        &mut self,
+        key: CacheKey,
+        render: impl FnOnce() -> Result<String, E>,
+    ) -> Result<String, E> {
+        if let Some(rendered) = self.store.get(&key) {
+            self.hits += 1;
+            return Ok(rendered);
+        }
+        self.misses += 1;
+        let rendered = render()?;
+        self.store.insert(key, rendered.clone());
+        Ok(rendered)
+    }
+
+    /// Number of lookups answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of lookups that had to render.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+impl std::fmt::Debug for RenderCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderCache")
+            .field("hits", &self.hits)
+            .field("misses", &self.misses)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_counting<'a>(
+        calls: &'a mut u32,
+        output: &'a str,
+    ) -> impl FnOnce() -> Result<String, ()> + 'a {
+        move || {
+            *calls += 1;
+            Ok(output.to_string())
+        }
+    }
+
+    #[test]
+    fn test_hits_and_misses() {
+        let mut cache = RenderCache::in_memory(16);
+        let options = html_fingerprint(&Config::default());
+        let mut calls = 0;
+
+        let key = CacheKey::new("rust", "", options, "fn main() {}");
+        let first = cache
+            .get_or_render(key.clone(), render_counting(&mut calls, "<a-k>fn</a-k>"))
+            .unwrap();
+        let second = cache
+            .get_or_render(key, render_counting(&mut calls, "unused"))
+            .unwrap();
+        assert_eq!(first, second);
+
+        // A different source is a different entry
+        let key = CacheKey::new("rust", "", options, "fn other() {}");
+        cache
+            .get_or_render(key, render_counting(&mut calls, "other"))
+            .unwrap();
+
+        assert_eq!(calls, 2);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+    }
+
+    #[test]
+    fn test_changed_theme_or_options_miss() {
+        let mut cache = RenderCache::in_memory(16);
+        let config = Config::default();
+        let options = ansi_fingerprint(&config, &AnsiOptions::default());
+        let mut calls = 0;
+
+        for theme in ["mocha", "mocha", "latte"] {
+            let key = CacheKey::new("rust", theme, options, "x");
+            cache
+                .get_or_render(key, render_counting(&mut calls, theme))
+                .unwrap();
+        }
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        let wide = AnsiOptions {
+            width: Some(40),
+            ..AnsiOptions::default()
+        };
+        assert_ne!(ansi_fingerprint(&config, &wide), options);
+        let mut shallow = config.clone();
+        shallow.max_injection_depth = 0;
+        assert_ne!(html_fingerprint(&shallow), html_fingerprint(&config));
+        assert_ne!(html_fingerprint(&config), options);
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let mut cache = RenderCache::in_memory(16);
+        let key = CacheKey::new("nope", "", 0, "x");
+        assert!(
+            cache
+                .get_or_render(key.clone(), || Err::<String, _>("unsupported"))
+                .is_err()
+        );
+        assert_eq!(
+            cache.get_or_render(key, || Ok::<_, ()>("ok".to_string())),
+            Ok("ok".to_string())
+        );
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut store = LruStore::new(2);
+        let key = |source: &str| CacheKey::new("rust", "", 0, source);
+        store.insert(key("a"), "a".into());
+        store.insert(key("b"), "b".into());
+        // Touch "a" so that "b" is the least recently used
+        assert_eq!(store.get(&key("a")), Some("a".into()));
+        store.insert(key("c"), "c".into());
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(&key("b")), None);
+        assert_eq!(store.get(&key("a")), Some("a".into()));
+        assert_eq!(store.get(&key("c")), Some("c".into()));
+    }
+
+    #[cfg(feature = "cache-fs")]
+    #[test]
+    fn test_fs_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("arborium-cache-test-{}", std::process::id()));
+        let key = CacheKey::new("rust", "", 0, "fn main() {}");
+
+        let mut store = FsStore::new(&dir);
+        assert_eq!(store.get(&key), None);
+        store.insert(key.clone(), "<a-k>fn</a-k>".into());
+
+        // A fresh store over the same directory sees the entry
+        let mut store = FsStore::new(&dir);
+        assert_eq!(store.get(&key), Some("<a-k>fn</a-k>".into()));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,124 @@
+//! Language detection from file names and contents.
+//!
+//! [`detect_language`](crate::detect_language) works from a file extension.
+//! The functions here also look at the contents, which is useful for stdin or
+//! extension-less scripts. [`detect`] combines everything: extension,
+//! well-known file names, shebang, modeline, then content heuristics.
+//!
+//! Detection typically has to run before we know whether a file is text at
+//! all, so these functions accept arbitrary bytes: they reject content that
+//! looks binary and tolerate invalid UTF-8 elsewhere.
+
+pub use arborium_highlight::detect::{Detection, DetectionMethod, looks_binary};
+
+use arborium_highlight::detect::detect_from_shebang;
+
+/// Detect the language of a file from its name (if known) and contents.
+///
+/// Returns the language along with the [`DetectionMethod`] that found it,
+/// which helps when debugging a surprising result. Only languages enabled
+/// via feature flags are returned by the extension and modeline steps.
+///
+/// # Example
+///
+/// ```rust
+/// use arborium::detect::{DetectionMethod, detect};
+///
+/// let found = detect(Some("Dockerfile"), b"FROM alpine").unwrap();
+/// assert_eq!(found.language, "dockerfile");
+/// assert_eq!(found.method, DetectionMethod::Filename);
+///
+/// let found = detect(None, b"#!/usr/bin/env python3\nprint(1)").unwrap();
+/// assert_eq!(found.method, DetectionMethod::Shebang);
+/// ```
+pub fn detect(filename: Option<&str>, content: &[u8]) -> Option<Detection> {
+    arborium_highlight::detect::detect(filename, content, &|name| {
+        crate::language_for_extension(name).map(str::to_string)
+    })
+}
+
+/// Detect the language of some source text from its contents.
+///
+/// Currently this recognizes shebang lines (`#!/usr/bin/env python3`, ...).
+/// Returns `None` if nothing matched.
+///
+/// # Example
+///
+/// ```rust
+/// use arborium::detect::detect_from_content;
+///
+/// assert_eq!(detect_from_content("#!/usr/bin/env python3\nprint(1)"), Some("python"));
+/// assert_eq!(detect_from_content("print(1)"), None);
+/// ```
+pub fn detect_from_content(content: &str) -> Option<&'static str> {
+    detect_from_shebang(content.lines().next()?)
+}
+
+/// Detect the language of some content that may not be valid UTF-8.
+///
+/// Returns `None` for content that looks binary (contains NUL bytes near the
+/// start). Otherwise behaves like [`detect_from_content`]; invalid UTF-8
+/// sequences don't prevent detection.
+///
+/// # Example
+///
+/// ```rust
+/// use arborium::detect::detect_from_bytes;
+///
+/// assert_eq!(detect_from_bytes(b"#!/bin/bash\necho caf\xe9"), Some("bash"));
+/// assert_eq!(detect_from_bytes(b"#!/bin/bash\0\0\0"), None);
+/// ```
+pub fn detect_from_bytes(content: &[u8]) -> Option<&'static str> {
+    if looks_binary(content) {
+        return None;
+    }
+
+    let first_line = content.split(|&b| b == b'\n').next()?;
+    detect_from_shebang(&String::from_utf8_lossy(first_line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_shebang() {
+        assert_eq!(
+            detect_from_content("#!/usr/bin/env node\n"),
+            Some("javascript")
+        );
+        assert_eq!(detect_from_content("#!/bin/sh\nset -e\n"), Some("bash"));
+        assert_eq!(detect_from_content("#!/usr/bin/env zsh"), Some("zsh"));
+        assert_eq!(detect_from_content("fn main() {}"), None);
+        assert_eq!(detect_from_content(""), None);
+    }
+
+    #[test]
+    fn test_detect_bytes_with_invalid_utf8() {
+        assert_eq!(
+            detect_from_bytes(b"#!/usr/bin/perl\n# caf\xe9\n"),
+            Some("perl")
+        );
+        assert_eq!(detect_from_bytes(b"#!/usr/bin/\xffruby\n"), Some("ruby"));
+    }
+
+    #[test]
+    fn test_detect_bytes_rejects_binary() {
+        assert!(looks_binary(b"\x7fELF\x02\x01\x01\0\0\0"));
+        assert!(!looks_binary(b"plain text"));
+        assert_eq!(detect_from_bytes(b"#!/bin/sh\n\0binary"), None);
+    }
+
+    #[test]
+    fn test_detect_reports_method() {
+        let found = detect(None, b"#!/usr/bin/env python3\nimport sys\n").unwrap();
+        assert_eq!(found.language, "python");
+        assert_eq!(found.method, DetectionMethod::Shebang);
+
+        let found = detect(Some("/src/CMakeLists.txt"), b"project(x)").unwrap();
+        assert_eq!(found.language, "cmake");
+        assert_eq!(found.method, DetectionMethod::Filename);
+
+        assert_eq!(detect(Some("notes"), b"just some words"), None);
+    }
+}
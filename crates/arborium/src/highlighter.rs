@@ -30,11 +30,16 @@
 use std::io::Write;
 use std::sync::Arc;
 
+use arborium_highlight::detect::{detect_from_path, injection_language_candidates};
 use arborium_highlight::tree_sitter::{CompiledGrammar, ParseContext};
-use arborium_highlight::{AnsiOptions, Span, spans_to_ansi_with_options, spans_to_html};
+use arborium_highlight::{
+    AnsiOptions, InjectionResolver, Span, injected_texts, spans_to_ansi_with_options,
+    spans_to_html_bytes, spans_to_html_with_options,
+};
 use arborium_theme::Theme;
 
 use crate::Config;
+use crate::cache::{self, CacheKey, RenderCache};
 use crate::error::Error;
 use crate::store::GrammarStore;
 
@@ -63,6 +68,7 @@ pub struct Highlighter {
     store: Arc<GrammarStore>,
     ctx: Option<ParseContext>,
     config: Config,
+    resolver: Option<Arc<dyn InjectionResolver + Send + Sync>>,
 }
 
 impl Default for Highlighter {
@@ -80,6 +86,7 @@ impl Clone for Highlighter {
             store: self.store.clone(),
             ctx: None, // New context will be created on first use
             config: self.config.clone(),
+            resolver: self.resolver.clone(),
         }
     }
 }
@@ -93,6 +100,7 @@ impl Highlighter {
             store: Arc::new(GrammarStore::new()),
             ctx: None,
             config: Config::default(),
+            resolver: None,
         }
     }
 
@@ -102,6 +110,7 @@ impl Highlighter {
             store: Arc::new(GrammarStore::new()),
             ctx: None,
             config,
+            resolver: None,
         }
     }
 
@@ -113,6 +122,7 @@ impl Highlighter {
             store,
             ctx: None,
             config: Config::default(),
+            resolver: None,
         }
     }
 
@@ -122,6 +132,7 @@ impl Highlighter {
             store,
             ctx: None,
             config,
+            resolver: None,
         }
     }
 
@@ -134,6 +145,7 @@ impl Highlighter {
             store: self.store.clone(),
             ctx: None,
             config: self.config.clone(),
+            resolver: self.resolver.clone(),
         }
     }
 
@@ -144,13 +156,46 @@ impl Highlighter {
         &self.store
     }
 
+    /// Resolve the languages injections ask for with `resolver` before
+    /// looking them up. See [`InjectionResolver`].
+    ///
+    /// Forks share the resolver. [`highlight_cached`](Self::highlight_cached)
+    /// doesn't know about it, so don't share a [`RenderCache`] between
+    /// highlighters with different resolvers.
+    pub fn set_injection_resolver(
+        &mut self,
+        resolver: impl InjectionResolver + Send + Sync + 'static,
+    ) {
+        self.resolver = Some(Arc::new(resolver));
+    }
+
     /// Highlight source code and return HTML string.
     ///
     /// This automatically handles language injections (e.g., CSS/JS in HTML,
     /// SQL in Python strings, etc.).
     pub fn highlight(&mut self, language: &str, source: &str) -> Result<String, Error> {
         let spans = self.highlight_spans(language, source)?;
-        Ok(spans_to_html(source, spans, &self.config.html_format))
+        Ok(spans_to_html_with_options(
+            source,
+            spans,
+            &self.config.html_format,
+            &self.config.html_options,
+        ))
+    }
+
+    /// Highlight source code to HTML, reusing output from `cache` when the
+    /// same source was already highlighted with the same configuration.
+    ///
+    /// Cache hits skip parsing entirely. See [`crate::cache`].
+    pub fn highlight_cached(
+        &mut self,
+        cache: &mut RenderCache,
+        language: &str,
+        source: &str,
+    ) -> Result<String, Error> {
+        let options = cache::html_fingerprint(&self.config);
+        let key = CacheKey::new(language, "", options, source);
+        cache.get_or_render(key, || self.highlight(language, source))
     }
 
     /// Highlight source code and write HTML directly to a writer.
@@ -168,8 +213,36 @@ impl Highlighter {
         Ok(())
     }
 
+    /// Highlight source that may not be valid UTF-8 and return HTML.
+    ///
+    /// Parsing works directly on the bytes, so a stray invalid sequence (for
+    /// example a latin-1 comment in an otherwise UTF-8 file) doesn't prevent
+    /// the rest of the file from being highlighted. Invalid sequences are
+    /// rendered as U+FFFD (`�`).
+    pub fn highlight_bytes(&mut self, language: &str, source: &[u8]) -> Result<String, Error> {
+        let spans = self.highlight_spans_bytes(language, source)?;
+        Ok(spans_to_html_bytes(
+            source,
+            spans,
+            &self.config.html_format,
+            &self.config.html_options,
+        ))
+    }
+
     /// Highlight and return raw spans (for custom rendering).
     pub fn highlight_spans(&mut self, language: &str, source: &str) -> Result<Vec<Span>, Error> {
+        self.highlight_spans_bytes(language, source.as_bytes())
+    }
+
+    /// Highlight source that may not be valid UTF-8 and return raw spans.
+    ///
+    /// Span offsets refer to bytes of the original `source`, even when it
+    /// contains invalid UTF-8 sequences.
+    pub fn highlight_spans_bytes(
+        &mut self,
+        language: &str,
+        source: &[u8],
+    ) -> Result<Vec<Span>, Error> {
         // Get the primary grammar
         let grammar = self
             .store
@@ -190,7 +263,7 @@ impl Highlighter {
             })?;
 
         // Parse the primary language
-        let result = grammar.parse(ctx, source);
+        let result = grammar.parse_bytes(ctx, source);
 
         // Collect all spans (including from injections)
         let mut all_spans = result.spans;
@@ -225,7 +298,7 @@ impl Highlighter {
     /// Process injections recursively.
     fn process_injections(
         &mut self,
-        source: &str,
+        source: &[u8],
         injections: Vec<arborium_highlight::Injection>,
         base_offset: u32,
         remaining_depth: u32,
@@ -235,18 +308,27 @@ impl Highlighter {
             return Ok(());
         }
 
-        for injection in injections {
-            let start = injection.start as usize;
-            let end = injection.end as usize;
-
-            if start >= source.len() || end > source.len() || start >= end {
-                continue;
-            }
-
-            let injected_source = &source[start..end];
-
-            // Try to get grammar for injected language
-            let Some(grammar) = self.store.get(&injection.language) else {
+        for injected in injected_texts(source, injections) {
+            let language = self
+                .resolver
+                .as_ref()
+                .and_then(|resolver| resolver.resolve(&injected.language))
+                .unwrap_or(injected.language);
+
+            // Try to get grammar for injected language. Documents write names
+            // loosely, like Org's `#+begin_src C` or AsciiDoc's
+            // `[source,rust]`, and some queries capture a file path instead,
+            // like the diff grammar's `+++ b/src/main.rs`
+            let grammar = injection_language_candidates(&language)
+                .iter()
+                .find_map(|name| self.store.get(name))
+                .or_else(|| {
+                    detect_from_path(&language, &|ext| {
+                        crate::language_for_extension(ext).map(str::to_string)
+                    })
+                    .and_then(|language| self.store.get(&language))
+                });
+            let Some(grammar) = grammar else {
                 continue;
             };
 
@@ -257,10 +339,10 @@ impl Highlighter {
             }
 
             // Parse injected content
-            let result = grammar.parse(ctx, injected_source);
+            let result = grammar.parse_bytes(ctx, &injected.text);
 
             // Offset spans to document coordinates
-            let offset = base_offset + injection.start;
+            let offset = base_offset + injected.start;
             for mut span in result.spans {
                 span.start += offset;
                 span.end += offset;
@@ -269,7 +351,7 @@ impl Highlighter {
 
             // Recurse into nested injections
             self.process_injections(
-                injected_source,
+                &injected.text,
                 result.injections,
                 offset,
                 remaining_depth - 1,
@@ -389,6 +471,22 @@ impl AnsiHighlighter {
         ))
     }
 
+    /// Highlight source code to ANSI, reusing output from `cache` when the
+    /// same source was already highlighted with the same theme, configuration
+    /// and options.
+    ///
+    /// Cache hits skip parsing entirely. See [`crate::cache`].
+    pub fn highlight_cached(
+        &mut self,
+        cache: &mut RenderCache,
+        language: &str,
+        source: &str,
+    ) -> Result<String, Error> {
+        let options = cache::ansi_fingerprint(&self.inner.config, &self.options);
+        let key = CacheKey::new(language, &self.theme.name, options, source);
+        cache.get_or_render(key, || self.highlight(language, source))
+    }
+
     /// Highlight source code and write ANSI output directly to a writer.
     pub fn highlight_to_writer<W: Write>(
         &mut self,
@@ -0,0 +1,280 @@
+//! Per-line span slicing for line-oriented renderers.
+//!
+//! Terminal UIs and virtualized views render one line at a time, so they need
+//! spans pre-split at line boundaries. [`split_spans`] does that eagerly;
+//! [`iter_spans`] does it lazily, one line per iteration, without
+//! materializing every line up front.
+//!
+//! Lines follow [`str::lines`] semantics: both `\n` and `\r\n` end a line,
+//! the terminator is not part of the line, and a final line without a
+//! trailing newline is still yielded.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use arborium::Highlighter;
+//!
+//! let mut hl = Highlighter::new();
+//! let source = "/* a\n   b */\nfn main() {}";
+//! let spans = hl.highlight_spans("rust", source)?;
+//! for line in arborium::lines::iter_spans(source, &spans) {
+//!     for span in &line.spans {
+//!         println!("{}: {:?} {}", line.index, span.column_range, span.span.capture);
+//!     }
+//! }
+//! ```
+
+use std::ops::Range;
+
+use arborium_highlight::Span;
+
+/// A single line of source with the spans that overlap it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineSpans<'a, 's> {
+    /// Zero-based line index.
+    pub index: usize,
+    /// The line's text, without its line terminator.
+    pub text: &'a str,
+    /// Byte range of `text` within the source.
+    pub byte_range: Range<usize>,
+    /// Spans clipped to this line, ordered by start.
+    pub spans: Vec<LineSpan<'s>>,
+}
+
+/// A span clipped to a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineSpan<'s> {
+    /// The original (unclipped) span.
+    pub span: &'s Span,
+    /// Clipped byte range, relative to the start of the source.
+    pub byte_range: Range<usize>,
+    /// Clipped byte range, relative to the start of the line.
+    pub column_range: Range<usize>,
+}
+
+/// Split spans at line boundaries, returning one entry per line.
+///
+/// Spans that cover several lines (block comments, multi-line strings)
+/// appear once on every line they touch, clipped to that line. Spans that
+/// only cover a line terminator are dropped.
+pub fn split_spans<'a, 's>(source: &'a str, spans: &'s [Span]) -> Vec<LineSpans<'a, 's>> {
+    iter_spans(source, spans).collect()
+}
+
+/// Lazily split spans at line boundaries.
+///
+/// Like [`split_spans`], but yields lines one at a time.
+pub fn iter_spans<'a, 's>(source: &'a str, spans: &'s [Span]) -> LineSpansIter<'a, 's> {
+    let mut sorted: Vec<&'s Span> = spans.iter().filter(|s| s.start < s.end).collect();
+    sorted.sort_by_key(|s| (s.start, s.end));
+
+    LineSpansIter {
+        source,
+        pos: 0,
+        index: 0,
+        sorted,
+        next_span: 0,
+        active: Vec::new(),
+    }
+}
+
+/// Iterator over [`LineSpans`], returned by [`iter_spans`].
+#[derive(Debug, Clone)]
+pub struct LineSpansIter<'a, 's> {
+    source: &'a str,
+    /// Byte offset where the next line starts.
+    pos: usize,
+    /// Index of the next line.
+    index: usize,
+    /// Non-empty spans sorted by start.
+    sorted: Vec<&'s Span>,
+    /// Index into `sorted` of the first span not yet activated.
+    next_span: usize,
+    /// Spans that started on an earlier line and may still continue.
+    active: Vec<&'s Span>,
+}
+
+impl<'a, 's> Iterator for LineSpansIter<'a, 's> {
+    type Item = LineSpans<'a, 's>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.source.len() {
+            return None;
+        }
+
+        let rest = &self.source[self.pos..];
+        let (content_len, terminator_len) = match rest.find('\n') {
+            Some(nl) if nl > 0 && rest.as_bytes()[nl - 1] == b'\r' => (nl - 1, 2),
+            Some(nl) => (nl, 1),
+            None => (rest.len(), 0),
+        };
+
+        let start = self.pos;
+        let end = start + content_len;
+        self.pos = end + terminator_len;
+
+        // Activate spans starting on this line (including its terminator)
+        while let Some(span) = self.sorted.get(self.next_span) {
+            if span.start as usize >= self.pos {
+                break;
+            }
+            self.active.push(span);
+            self.next_span += 1;
+        }
+
+        let mut spans = Vec::new();
+        for span in &self.active {
+            let clipped_start = (span.start as usize).max(start);
+            let clipped_end = (span.end as usize).min(end);
+            if clipped_start < clipped_end {
+                spans.push(LineSpan {
+                    span,
+                    byte_range: clipped_start..clipped_end,
+                    column_range: clipped_start - start..clipped_end - start,
+                });
+            }
+        }
+
+        // Drop spans that don't reach the next line
+        let next_line = self.pos;
+        self.active.retain(|span| span.end as usize > next_line);
+
+        let line = LineSpans {
+            index: self.index,
+            text: &self.source[start..end],
+            byte_range: start..end,
+            spans,
+        };
+        self.index += 1;
+        Some(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32, capture: &str) -> Span {
+        Span {
+            start,
+            end,
+            capture: capture.to_string(),
+            pattern_index: 0,
+        }
+    }
+
+    /// `(index, text, [(capture, column_range)])` for each line.
+    type Summary<'a> = Vec<(usize, &'a str, Vec<(String, Range<usize>)>)>;
+
+    fn summarize<'a>(lines: &[LineSpans<'a, '_>]) -> Summary<'a> {
+        lines
+            .iter()
+            .map(|line| {
+                (
+                    line.index,
+                    line.text,
+                    line.spans
+                        .iter()
+                        .map(|s| (s.span.capture.clone(), s.column_range.clone()))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_multiline_comment() {
+        let source = "/* one\n   two\n   three */ x";
+        let spans = vec![span(0, 25, "comment"), span(26, 27, "variable")];
+        let lines = split_spans(source, &spans);
+
+        assert_eq!(
+            summarize(&lines),
+            vec![
+                (0, "/* one", vec![("comment".to_string(), 0..6)]),
+                (1, "   two", vec![("comment".to_string(), 0..6)]),
+                (
+                    2,
+                    "   three */ x",
+                    vec![
+                        ("comment".to_string(), 0..11),
+                        ("variable".to_string(), 12..13)
+                    ]
+                ),
+            ]
+        );
+
+        // Absolute offsets point back into the source
+        for line in &lines {
+            for s in &line.spans {
+                assert_eq!(
+                    &source[s.byte_range.clone()],
+                    &line.text[s.column_range.clone()]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        let source = "let s = \"a\r\nb\r\nc\";\r\nx\r\n";
+        let spans = vec![span(0, 3, "keyword"), span(8, 17, "string")];
+        let lines = split_spans(source, &spans);
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].text, "let s = \"a");
+        assert_eq!(lines[1].text, "b");
+        assert_eq!(lines[2].text, "c\";");
+        assert_eq!(lines[3].text, "x");
+        assert!(lines.iter().all(|l| !l.text.contains('\r')));
+
+        assert_eq!(
+            summarize(&lines)[..3],
+            vec![
+                (
+                    0,
+                    "let s = \"a",
+                    vec![("keyword".to_string(), 0..3), ("string".to_string(), 8..10)]
+                ),
+                (1, "b", vec![("string".to_string(), 0..1)]),
+                (2, "c\";", vec![("string".to_string(), 0..2)]),
+            ]
+        );
+        assert!(lines[3].spans.is_empty());
+    }
+
+    #[test]
+    fn test_final_line_without_newline() {
+        let source = "a\nb";
+        let spans = vec![span(2, 3, "keyword")];
+        let lines = split_spans(source, &spans);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].byte_range, 2..3);
+        assert_eq!(lines[1].spans[0].column_range, 0..1);
+    }
+
+    #[test]
+    fn test_terminator_only_spans_and_empty_lines() {
+        let source = "a\n\nb\n";
+        // A span covering just the first newline, and one across the empty line
+        let spans = vec![span(1, 2, "punctuation"), span(0, 4, "string")];
+        let lines = split_spans(source, &spans);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].spans.len(), 1);
+        assert_eq!(lines[0].spans[0].span.capture, "string");
+        assert_eq!(lines[1].text, "");
+        assert!(lines[1].spans.is_empty());
+        assert_eq!(lines[2].spans[0].column_range, 0..1);
+    }
+
+    #[test]
+    fn test_iterator_matches_eager() {
+        let source = "/* x\ny */\nfn f() {}\n";
+        let spans = vec![span(0, 9, "comment"), span(10, 12, "keyword")];
+        let lazy: Vec<_> = iter_spans(source, &spans).collect();
+        assert_eq!(lazy, split_spans(source, &spans));
+        assert!(split_spans("", &spans).is_empty());
+    }
+}
@@ -0,0 +1,408 @@
+//! Semantic tokens for language servers.
+//!
+//! [`semantic_tokens`] highlights a document and encodes the result as the
+//! `data` array of an LSP `SemanticTokens` response: five integers per token
+//! (`deltaLine`, `deltaStart`, `length`, `tokenType`, `tokenModifiers`),
+//! each position relative to the previous token. Columns and lengths are
+//! counted in UTF-16 code units, the LSP default position encoding.
+//!
+//! Token types and modifiers are indices into a [`Legend`], which the server
+//! advertises in its `semanticTokensProvider` capability. [`legend`] builds
+//! one covering every highlight arborium produces.
+//!
+//! Nothing here depends on a particular `lsp-types` version: legends are
+//! plain strings and the encoded data is a plain `Vec<u32>`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use arborium::{Highlighter, lsp};
+//!
+//! let legend = lsp::legend();
+//! // Advertise `legend.token_types` / `legend.token_modifiers` to the client,
+//! // then answer `textDocument/semanticTokens/full` with:
+//! let mut hl = Highlighter::new();
+//! let data = lsp::semantic_tokens(&mut hl, "rust", "fn main() {}", &legend)?;
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use arborium_highlight::Span;
+use arborium_theme::{ThemeSlot, capture_to_slot};
+
+use crate::error::Error;
+use crate::highlighter::Highlighter;
+use crate::tokens::flatten_with;
+
+/// Token types and modifiers a server supports, in index order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Legend {
+    /// Token type names (e.g. `"keyword"`); a token's type is an index into
+    /// this list.
+    pub token_types: Vec<&'static str>,
+    /// Token modifier names (e.g. `"readonly"`); a token's modifiers are a
+    /// bit set over this list.
+    pub token_modifiers: Vec<&'static str>,
+}
+
+/// Build the legend for every highlight name in [`HIGHLIGHT_NAMES`], using
+/// only types and modifiers predefined by the LSP specification.
+///
+/// Highlights without a sensible LSP counterpart (punctuation, markup, and
+/// so on) are left out and never produce tokens.
+///
+/// [`HIGHLIGHT_NAMES`]: crate::HIGHLIGHT_NAMES
+pub fn legend() -> Legend {
+    let mut legend = Legend {
+        token_types: Vec::new(),
+        token_modifiers: Vec::new(),
+    };
+    for name in crate::HIGHLIGHT_NAMES {
+        let Some((token_type, modifiers)) = classify(name) else {
+            continue;
+        };
+        if !legend.token_types.contains(&token_type) {
+            legend.token_types.push(token_type);
+        }
+        for modifier in modifiers {
+            if !legend.token_modifiers.contains(modifier) {
+                legend.token_modifiers.push(modifier);
+            }
+        }
+    }
+    legend
+}
+
+/// A position in a document, as in LSP: a zero-based line and a UTF-16
+/// column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// Zero-based line number.
+    pub line: u32,
+    /// Zero-based column, in UTF-16 code units.
+    pub character: u32,
+}
+
+impl Position {
+    /// Create a position.
+    pub fn new(line: u32, character: u32) -> Self {
+        Self { line, character }
+    }
+}
+
+/// Highlight `source` and encode it as semantic tokens for
+/// `textDocument/semanticTokens/full`.
+pub fn semantic_tokens(
+    highlighter: &mut Highlighter,
+    language: &str,
+    source: &str,
+    legend: &Legend,
+) -> Result<Vec<u32>, Error> {
+    let spans = highlighter.highlight_spans(language, source)?;
+    Ok(encode(source, spans, legend))
+}
+
+/// Like [`semantic_tokens`], but only returns tokens overlapping `range`,
+/// for `textDocument/semanticTokens/range`.
+///
+/// The whole document is still parsed, since highlighting depends on
+/// context outside the range.
+pub fn semantic_tokens_range(
+    highlighter: &mut Highlighter,
+    language: &str,
+    source: &str,
+    range: Range<Position>,
+    legend: &Legend,
+) -> Result<Vec<u32>, Error> {
+    let spans = highlighter.highlight_spans(language, source)?;
+    Ok(encode_range(source, spans, range, legend))
+}
+
+/// Encode raw spans (as returned by [`Highlighter::highlight_spans`]) as
+/// semantic tokens.
+///
+/// Overlapping spans are flattened so the innermost one wins, tokens
+/// spanning several lines are split at line breaks, and captures whose type
+/// isn't in `legend` are skipped.
+pub fn encode(source: &str, spans: Vec<Span>, legend: &Legend) -> Vec<u32> {
+    encode_tokens(absolute_tokens(source, spans, legend))
+}
+
+/// Like [`encode`], but only keeps tokens overlapping `range`.
+pub fn encode_range(
+    source: &str,
+    spans: Vec<Span>,
+    range: Range<Position>,
+    legend: &Legend,
+) -> Vec<u32> {
+    let tokens = absolute_tokens(source, spans, legend)
+        .into_iter()
+        .filter(|token| {
+            let start = Position::new(token.line, token.start);
+            let end = Position::new(token.line, token.start + token.length);
+            end > range.start && start < range.end
+        });
+    encode_tokens(tokens)
+}
+
+/// A token with an absolute position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AbsoluteToken {
+    line: u32,
+    start: u32,
+    length: u32,
+    token_type: u32,
+    modifiers: u32,
+}
+
+/// Resolve spans to single-line tokens with absolute UTF-16 positions, in
+/// document order.
+fn absolute_tokens(source: &str, spans: Vec<Span>, legend: &Legend) -> Vec<AbsoluteToken> {
+    let mut cache: HashMap<String, Option<(u32, u32)>> = HashMap::new();
+    let runs = flatten_with(source, spans, |capture| {
+        *cache
+            .entry(capture.to_string())
+            .or_insert_with(|| resolve(capture, legend))
+    });
+
+    let mut tokens = Vec::new();
+    let mut line = 0;
+    let mut column = 0;
+    for run in runs {
+        let text = &source[run.byte_range];
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 {
+                line += 1;
+                column = 0;
+            }
+            let content = segment.strip_suffix('\r').unwrap_or(segment);
+            let length = utf16_len(content);
+            match run.value {
+                Some((token_type, modifiers)) if length > 0 => tokens.push(AbsoluteToken {
+                    line,
+                    start: column,
+                    length,
+                    token_type,
+                    modifiers,
+                }),
+                _ => {}
+            }
+            column += utf16_len(segment);
+        }
+    }
+    tokens
+}
+
+/// Apply the LSP relative encoding.
+fn encode_tokens(tokens: impl IntoIterator<Item = AbsoluteToken>) -> Vec<u32> {
+    let mut data = Vec::new();
+    let (mut prev_line, mut prev_start) = (0, 0);
+    for token in tokens {
+        let delta_line = token.line - prev_line;
+        let delta_start = if delta_line == 0 {
+            token.start - prev_start
+        } else {
+            token.start
+        };
+        data.extend([
+            delta_line,
+            delta_start,
+            token.length,
+            token.token_type,
+            token.modifiers,
+        ]);
+        prev_line = token.line;
+        prev_start = token.start;
+    }
+    data
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.chars().map(|c| c.len_utf16() as u32).sum()
+}
+
+/// Resolve a capture to a (type index, modifier bits) pair in `legend`.
+fn resolve(capture: &str, legend: &Legend) -> Option<(u32, u32)> {
+    let (token_type, modifiers) = classify(capture)?;
+    let token_type = legend.token_types.iter().position(|&t| t == token_type)? as u32;
+    let modifiers = modifiers
+        .iter()
+        .filter_map(|m| legend.token_modifiers.iter().position(|lm| lm == m))
+        .fold(0, |bits, index| bits | 1 << index);
+    Some((token_type, modifiers))
+}
+
+/// Map a capture name to an LSP token type and modifiers.
+fn classify(capture: &str) -> Option<(&'static str, &'static [&'static str])> {
+    let capture = capture.strip_prefix('@').unwrap_or(capture);
+
+    // Sub-captures with a more specific LSP type than their theme slot
+    let token_type = match capture {
+        "function.method" | "function.method.call" | "method" | "method.call" => "method",
+        "variable.parameter" | "parameter" => "parameter",
+        "variable.member" | "variable.other.member" | "field" => "property",
+        "type.parameter" => "typeParameter",
+        "type.enum.variant" => "enumMember",
+        "string.regexp" | "string.regex" => "regexp",
+        // Comment tags are still comments to an editor
+        "comment.todo" | "comment.note" | "comment.warning" | "comment.error" => "comment",
+        // Table column colors only say where a field is, which has no token type
+        _ if capture.starts_with("column.") => return None,
+        _ => match capture_to_slot(capture) {
+            ThemeSlot::Keyword => "keyword",
+            ThemeSlot::Function => "function",
+            ThemeSlot::String => "string",
+            ThemeSlot::Comment => "comment",
+            ThemeSlot::Type => "type",
+            ThemeSlot::Variable | ThemeSlot::Constant => "variable",
+            ThemeSlot::Number => "number",
+            ThemeSlot::Operator => "operator",
+            ThemeSlot::Property => "property",
+            ThemeSlot::Attribute => "decorator",
+            ThemeSlot::Macro => "macro",
+            ThemeSlot::Namespace => "namespace",
+            ThemeSlot::Constructor => "class",
+            _ => return None,
+        },
+    };
+
+    let constant = capture_to_slot(capture) == ThemeSlot::Constant;
+    let builtin = capture.contains(".builtin");
+    let modifiers: &'static [&'static str] = match (constant, builtin) {
+        (true, true) => &["readonly", "defaultLibrary"],
+        (true, false) => &["readonly"],
+        (false, true) => &["defaultLibrary"],
+        (false, false) if capture.ends_with(".documentation") => &["documentation"],
+        (false, false) if capture.ends_with(".definition") => &["definition"],
+        (false, false) => &[],
+    };
+    Some((token_type, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32, capture: &str) -> Span {
+        Span {
+            start,
+            end,
+            capture: capture.to_string(),
+            pattern_index: 0,
+        }
+    }
+
+    fn test_legend() -> Legend {
+        Legend {
+            token_types: vec!["keyword", "function", "string", "comment", "variable"],
+            token_modifiers: vec!["readonly", "defaultLibrary"],
+        }
+    }
+
+    #[test]
+    fn test_two_lines_with_multibyte_character() {
+        // "é" is two bytes in UTF-8 but one UTF-16 unit; "😀" is four bytes
+        // and two UTF-16 units
+        let source = "fn é() {}\nlet s = \"😀\"; // x";
+        let spans = vec![
+            span(0, 2, "keyword"),            // fn
+            span(3, 5, "function"),           // é
+            span(11, 14, "keyword"),          // let
+            span(19, 25, "string"),           // "😀"
+            span(27, 31, "comment"),          // // x
+            span(15, 16, "constant.builtin"), // s
+        ];
+
+        let data = encode(source, spans, &test_legend());
+        #[rustfmt::skip]
+        assert_eq!(
+            data,
+            vec![
+                0, 0, 2, 0, 0, // fn: line 0, col 0
+                0, 3, 1, 1, 0, // é: col 3, one UTF-16 unit
+                1, 0, 3, 0, 0, // let: next line, col 0
+                0, 4, 1, 4, 3, // s: col 4, readonly | defaultLibrary
+                0, 4, 4, 2, 0, // "😀": col 8, quotes plus a surrogate pair
+                0, 6, 4, 3, 0, // // x: col 14, after 6 units
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiline_tokens_are_split() {
+        let source = "/* a\r\n   b */ x";
+        let spans = vec![span(0, 13, "comment")];
+        let data = encode(source, spans, &test_legend());
+        #[rustfmt::skip]
+        assert_eq!(data, vec![
+            0, 0, 4, 3, 0,
+            1, 0, 7, 3, 0,
+        ]);
+    }
+
+    #[test]
+    fn test_range_and_unknown_types() {
+        let source = "a\nb\nc";
+        let spans = vec![
+            span(0, 1, "keyword"),
+            span(2, 3, "punctuation"),
+            span(4, 5, "keyword"),
+        ];
+        let legend = test_legend();
+
+        // Punctuation has no LSP type
+        assert_eq!(
+            encode(source, spans.clone(), &legend),
+            vec![0, 0, 1, 0, 0, 2, 0, 1, 0, 0]
+        );
+
+        // Relative encoding still starts from the document start
+        let range = Position::new(1, 0)..Position::new(2, 1);
+        assert_eq!(
+            encode_range(source, spans, range, &legend),
+            vec![2, 0, 1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_comment_tags_stay_comments() {
+        for capture in [
+            "comment.todo",
+            "comment.note",
+            "comment.warning",
+            "comment.error",
+        ] {
+            assert_eq!(classify(capture), Some(("comment", &[][..])), "{capture}");
+        }
+    }
+
+    #[test]
+    fn test_table_columns_are_not_tokens() {
+        assert_eq!(classify("column.1"), None);
+        assert_eq!(classify("column.6"), None);
+    }
+
+    #[test]
+    fn test_legend_uses_standard_names() {
+        let legend = legend();
+        for token_type in [
+            "keyword",
+            "function",
+            "method",
+            "string",
+            "comment",
+            "parameter",
+        ] {
+            assert!(legend.token_types.contains(&token_type), "{token_type}");
+        }
+        assert!(legend.token_modifiers.contains(&"defaultLibrary"));
+        assert!(!legend.token_types.contains(&"punctuation"));
+
+        // Every type and modifier is unique
+        let mut types = legend.token_types.clone();
+        types.sort();
+        types.dedup();
+        assert_eq!(types.len(), legend.token_types.len());
+    }
+}
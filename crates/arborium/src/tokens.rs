@@ -0,0 +1,277 @@
+//! Token stream API for custom renderers.
+//!
+//! [`tokens`] flattens the (possibly overlapping, possibly injected) spans
+//! produced by a [`Highlighter`] into contiguous, non-overlapping runs of
+//! source text. Each run carries the innermost highlight that applies to it,
+//! so GUI text widgets and other non-HTML consumers don't have to reconstruct
+//! the nesting themselves.
+//!
+//! Concatenating the `text` of every token yields the original source,
+//! byte-for-byte.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use arborium::Highlighter;
+//!
+//! let mut hl = Highlighter::new();
+//! for token in arborium::tokens(&mut hl, "rust", "fn main() {}")? {
+//!     println!("{:?} {:?}", token.highlight, token.text);
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use arborium_highlight::Span;
+use arborium_theme::capture_to_slot;
+
+use crate::error::Error;
+use crate::highlighter::Highlighter;
+
+/// A contiguous run of source text with a single resolved highlight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// The source text covered by this token.
+    pub text: &'a str,
+    /// Byte range of `text` within the original source.
+    pub byte_range: Range<usize>,
+    /// Innermost highlight name (e.g. `"keyword"`, `"string"`), or `None`
+    /// for unhighlighted text.
+    pub highlight: Option<&'static str>,
+    /// Number of highlights enclosing this run, innermost included.
+    ///
+    /// `0` for unhighlighted text. Callers that only care about the innermost
+    /// style can ignore this.
+    pub depth: usize,
+}
+
+/// Iterator over [`Token`]s, returned by [`tokens`].
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    inner: std::vec::IntoIter<Token<'a>>,
+}
+
+impl<'a> Tokens<'a> {
+    /// Flatten raw spans (as returned by [`Highlighter::highlight_spans`])
+    /// into a token stream over `source`.
+    pub fn from_spans(source: &'a str, spans: Vec<Span>) -> Self {
+        Self {
+            inner: flatten(source, spans).into_iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Tokens<'_> {}
+
+/// Highlight `source` and return it as a stream of non-overlapping tokens.
+///
+/// Injections are resolved through the highlighter's grammar store and
+/// configuration, exactly as for [`Highlighter::highlight`], so embedded
+/// languages show up transparently in the token stream. The highlighter
+/// stands in for the config and injection resolver: set a resolver with
+/// [`Highlighter::set_injection_resolver`] and the injection depth with
+/// [`Highlighter::with_config`].
+///
+/// This is eager: the whole source, injections included, is highlighted
+/// and flattened before the first token is returned, and iterating only
+/// walks the result.
+pub fn tokens<'a>(
+    highlighter: &mut Highlighter,
+    language: &str,
+    source: &'a str,
+) -> Result<Tokens<'a>, Error> {
+    let spans = highlighter.highlight_spans(language, source)?;
+    Ok(Tokens::from_spans(source, spans))
+}
+
+/// A span resolved to a caller-defined value.
+struct ResolvedSpan<T> {
+    start: usize,
+    end: usize,
+    value: T,
+}
+
+/// A contiguous, non-overlapping run produced by [`flatten_with`].
+pub(crate) struct Run<T> {
+    pub(crate) byte_range: Range<usize>,
+    /// Value of the innermost resolved span covering the run, if any.
+    pub(crate) value: Option<T>,
+    /// Number of resolved spans covering the run.
+    pub(crate) depth: usize,
+}
+
+fn flatten(source: &str, spans: Vec<Span>) -> Vec<Token<'_>> {
+    flatten_with(source, spans, |capture| capture_to_slot(capture).name())
+        .into_iter()
+        .map(|run| Token {
+            text: &source[run.byte_range.clone()],
+            byte_range: run.byte_range,
+            highlight: run.value,
+            depth: run.depth,
+        })
+        .collect()
+}
+
+/// Flatten spans into contiguous runs covering all of `source`, each
+/// carrying the innermost span's value.
+///
+/// `resolve` maps a capture name to a value; captures it maps to `None` are
+/// treated as unstyled and ignored.
+pub(crate) fn flatten_with<T: Copy>(
+    source: &str,
+    spans: Vec<Span>,
+    mut resolve: impl FnMut(&str) -> Option<T>,
+) -> Vec<Run<T>> {
+    // Deduplicate identical ranges the same way the HTML renderer does:
+    // styled spans win over unstyled ones, then later patterns win.
+    let mut deduped: HashMap<(u32, u32), (Span, Option<T>)> = HashMap::new();
+    for span in spans {
+        let key = (span.start, span.end);
+        let value = resolve(&span.capture);
+
+        if let Some((existing, existing_value)) = deduped.get(&key) {
+            let should_replace = match (value.is_some(), existing_value.is_some()) {
+                (true, false) => true,
+                (false, true) => false,
+                _ => span.pattern_index >= existing.pattern_index,
+            };
+            if should_replace {
+                deduped.insert(key, (span, value));
+            }
+        } else {
+            deduped.insert(key, (span, value));
+        }
+    }
+
+    let mut resolved: Vec<ResolvedSpan<T>> = deduped
+        .into_values()
+        .filter_map(|(span, value)| {
+            let start = span.start as usize;
+            let end = (span.end as usize).min(source.len());
+            if start >= end || !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+                return None;
+            }
+            Some(ResolvedSpan {
+                start,
+                end,
+                value: value?,
+            })
+        })
+        .collect();
+
+    // Sort by (start, -end) so enclosing spans are pushed before nested ones
+    resolved.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| b.end.cmp(&a.end)));
+
+    // (pos, is_start, span_index); ends sort before starts at the same position
+    let mut events: Vec<(usize, bool, usize)> = Vec::with_capacity(resolved.len() * 2);
+    for (i, span) in resolved.iter().enumerate() {
+        events.push((span.start, true, i));
+        events.push((span.end, false, i));
+    }
+    events.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut runs = Vec::with_capacity(events.len() + 1);
+    let mut stack: Vec<usize> = Vec::new();
+    let mut last_pos = 0;
+
+    for (pos, is_start, span_idx) in events {
+        if pos > last_pos {
+            push_run(&mut runs, &resolved, &stack, last_pos..pos);
+            last_pos = pos;
+        }
+
+        if is_start {
+            stack.push(span_idx);
+        } else if let Some(idx) = stack.iter().rposition(|&x| x == span_idx) {
+            stack.remove(idx);
+        }
+    }
+
+    push_run(&mut runs, &resolved, &stack, last_pos..source.len());
+
+    runs
+}
+
+/// Push the run `range` styled by the innermost span on `stack`, if non-empty.
+fn push_run<T: Copy>(
+    runs: &mut Vec<Run<T>>,
+    resolved: &[ResolvedSpan<T>],
+    stack: &[usize],
+    range: Range<usize>,
+) {
+    if range.is_empty() {
+        return;
+    }
+    runs.push(Run {
+        byte_range: range,
+        value: stack.last().map(|&i| resolved[i].value),
+        depth: stack.len(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32, capture: &str) -> Span {
+        Span {
+            start,
+            end,
+            capture: capture.to_string(),
+            pattern_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_tokens_cover_source() {
+        let source = "let x = \"hi\";";
+        let spans = vec![span(0, 3, "keyword"), span(8, 12, "string")];
+        let tokens: Vec<_> = Tokens::from_spans(source, spans).collect();
+
+        let joined: String = tokens.iter().map(|t| t.text).collect();
+        assert_eq!(joined, source);
+        assert_eq!(tokens[0].text, "let");
+        assert_eq!(tokens[0].highlight, Some("keyword"));
+        assert_eq!(tokens[1].highlight, None);
+        assert_eq!(tokens[1].depth, 0);
+    }
+
+    #[test]
+    fn test_tokens_innermost_wins() {
+        // A string containing an escape sequence
+        let source = "\"a\\nb\"";
+        let spans = vec![span(0, 6, "string"), span(2, 4, "string.escape")];
+        let tokens: Vec<_> = Tokens::from_spans(source, spans).collect();
+
+        let texts: Vec<_> = tokens.iter().map(|t| t.text).collect();
+        assert_eq!(texts, vec!["\"a", "\\n", "b\""]);
+        assert_eq!(tokens[0].depth, 1);
+        assert_eq!(tokens[1].depth, 2);
+        assert_eq!(tokens[2].depth, 1);
+    }
+
+    #[test]
+    fn test_tokens_skip_unstyled_and_out_of_bounds() {
+        let source = "abc";
+        let spans = vec![span(0, 1, "spell"), span(1, 10, "comment")];
+        let tokens: Vec<_> = Tokens::from_spans(source, spans).collect();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].highlight, None);
+        assert_eq!(tokens[1].text, "bc");
+        assert_eq!(tokens[1].highlight, Some("comment"));
+    }
+}
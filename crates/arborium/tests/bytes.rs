@@ -0,0 +1,73 @@
+//! Tests for highlighting source that isn't valid UTF-8.
+
+#![cfg(feature = "lang-rust")]
+
+use arborium::Highlighter;
+
+/// Rust code with a latin-1 `é` (0xE9) in a comment, and a proper UTF-8
+/// `é` in a string literal.
+const SOURCE: &[u8] =
+    b"fn main() {\n    // caf\xe9 au lait\n    let s = \"\xc3\xa9t\xc3\xa9\";\n}\n";
+
+#[test]
+fn highlight_bytes_replaces_invalid_sequences() {
+    assert!(std::str::from_utf8(SOURCE).is_err());
+
+    let mut hl = Highlighter::new();
+    let html = hl.highlight_bytes("rust", SOURCE).unwrap();
+
+    // Invalid byte becomes U+FFFD inside the (still highlighted) comment
+    assert!(
+        html.contains("<a-c>// caf\u{FFFD} au lait</a-c>"),
+        "got: {html}"
+    );
+    // Surrounding tokens keep their highlighting
+    assert!(html.contains("<a-k>fn</a-k>"), "got: {html}");
+    assert!(html.contains("<a-k>let</a-k>"), "got: {html}");
+    assert!(html.contains("<a-s>&quot;été&quot;</a-s>"), "got: {html}");
+}
+
+#[test]
+fn highlight_spans_bytes_offsets_refer_to_original_bytes() {
+    let mut hl = Highlighter::new();
+    let spans = hl.highlight_spans_bytes("rust", SOURCE).unwrap();
+
+    let comment_start = SOURCE.windows(2).position(|w| w == b"//").unwrap();
+    let comment_end = comment_start
+        + SOURCE[comment_start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap();
+    assert!(
+        spans.iter().any(|s| s.capture.starts_with("comment")
+            && s.start as usize == comment_start
+            && s.end as usize == comment_end),
+        "no comment span at {comment_start}..{comment_end}: {spans:#?}"
+    );
+
+    let string_start = SOURCE.iter().position(|&b| b == b'"').unwrap();
+    let string_span = spans
+        .iter()
+        .find(|s| s.capture == "string" && s.start as usize == string_start)
+        .expect("string span");
+    assert_eq!(
+        &SOURCE[string_span.start as usize..string_span.end as usize],
+        "\"été\"".as_bytes()
+    );
+}
+
+#[test]
+fn highlight_bytes_matches_highlight_for_valid_utf8() {
+    let source = "fn main() {\n    let x = \"hi\"; // done\n}\n";
+
+    let mut hl = Highlighter::new();
+    let from_str = hl.highlight("rust", source).unwrap();
+    let from_bytes = hl.highlight_bytes("rust", source.as_bytes()).unwrap();
+    assert_eq!(from_str, from_bytes);
+}
+
+#[test]
+fn detect_from_bytes_before_decoding() {
+    let script = b"#!/usr/bin/env python3\n# r\xe9sum\xe9\nprint('hi')\n";
+    assert_eq!(arborium::detect::detect_from_bytes(script), Some("python"));
+}
@@ -0,0 +1,91 @@
+//! Tests for the render cache with real grammars.
+
+#![cfg(feature = "lang-rust")]
+
+use arborium::cache::RenderCache;
+use arborium::theme::builtin;
+use arborium::{AnsiHighlighter, Config, Highlighter, HtmlFormat};
+
+#[test]
+fn html_hits_on_identical_input() {
+    let mut hl = Highlighter::new();
+    let mut cache = RenderCache::in_memory(16);
+
+    let first = hl
+        .highlight_cached(&mut cache, "rust", "fn main() {}")
+        .unwrap();
+    let second = hl
+        .highlight_cached(&mut cache, "rust", "fn main() {}")
+        .unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first, hl.highlight("rust", "fn main() {}").unwrap());
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+    hl.highlight_cached(&mut cache, "rust", "fn other() {}")
+        .unwrap();
+    assert_eq!(cache.misses(), 2);
+}
+
+#[test]
+fn html_format_is_part_of_the_key() {
+    let mut cache = RenderCache::in_memory(16);
+    let mut elements = Highlighter::new();
+    let mut classes = Highlighter::with_config(Config {
+        html_format: HtmlFormat::ClassNames,
+        ..Default::default()
+    });
+
+    let a = elements
+        .highlight_cached(&mut cache, "rust", "fn main() {}")
+        .unwrap();
+    let b = classes
+        .highlight_cached(&mut cache, "rust", "fn main() {}")
+        .unwrap();
+    assert_ne!(a, b);
+    assert_eq!(cache.misses(), 2);
+}
+
+#[test]
+fn ansi_theme_is_part_of_the_key() {
+    let mut cache = RenderCache::in_memory(16);
+    let mut mocha = AnsiHighlighter::new(builtin::catppuccin_mocha().clone());
+    let mut latte = AnsiHighlighter::new(builtin::catppuccin_latte().clone());
+
+    mocha
+        .highlight_cached(&mut cache, "rust", "fn main() {}")
+        .unwrap();
+    latte
+        .highlight_cached(&mut cache, "rust", "fn main() {}")
+        .unwrap();
+    mocha
+        .highlight_cached(&mut cache, "rust", "fn main() {}")
+        .unwrap();
+    assert_eq!((cache.hits(), cache.misses()), (1, 2));
+}
+
+#[test]
+fn errors_are_not_cached() {
+    let mut hl = Highlighter::new();
+    let mut cache = RenderCache::in_memory(16);
+
+    assert!(
+        hl.highlight_cached(&mut cache, "no-such-lang", "x")
+            .is_err()
+    );
+    assert!(
+        hl.highlight_cached(&mut cache, "no-such-lang", "x")
+            .is_err()
+    );
+    assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn grammar_is_part_of_the_key() {
+    use arborium::cache::{CacheKey, grammar_fingerprint};
+
+    let rust = grammar_fingerprint("rust");
+    assert_ne!(rust, 0);
+    assert_eq!(grammar_fingerprint("rs"), rust);
+    assert_eq!(grammar_fingerprint("bartholomew"), 0);
+    assert_eq!(CacheKey::new("rust", "", 0, "x").grammar, rust);
+}
@@ -1,4 +1,4 @@
-//! Tests for the `get_language` function.
+//! Tests for `get_language` and the supported language list.
 
 #[test]
 #[cfg(feature = "lang-rust")]
@@ -12,3 +12,1170 @@ fn get_unsupported() {
     let lang = arborium::get_language("bartholomew");
     assert!(lang.is_none(), "unknown language should return None");
 }
+
+#[test]
+fn every_supported_language_builds() {
+    let store = arborium::GrammarStore::new();
+    for lang in arborium::supported_languages_detailed() {
+        assert!(
+            store.get(lang.id).is_some(),
+            "{} is listed as supported but has no grammar",
+            lang.id
+        );
+        assert!(arborium::get_language(lang.id).is_some());
+        for alias in lang.aliases {
+            assert!(store.get(alias).is_some(), "alias {alias} of {}", lang.id);
+        }
+    }
+}
+
+#[test]
+fn supported_languages_matches_detailed() {
+    let ids: Vec<_> = arborium::supported_languages_detailed()
+        .iter()
+        .map(|lang| lang.id)
+        .collect();
+    assert_eq!(arborium::supported_languages(), ids);
+}
+
+#[test]
+#[cfg(feature = "lang-rust")]
+fn rust_language_info() {
+    let rust = arborium::supported_languages_detailed()
+        .iter()
+        .find(|lang| lang.id == "rust")
+        .expect("rust should be listed");
+    assert_eq!(rust.name, "Rust");
+    assert_eq!(rust.category, "code");
+    assert!(rust.aliases.contains(&"rs"));
+    for ext in rust.extensions {
+        assert_eq!(
+            arborium::detect_language(&format!("main.{ext}")),
+            Some("rust")
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "lang-zsh")]
+fn zsh_dotfiles_are_highlighted() {
+    let mut hl = arborium::Highlighter::new();
+    let html = hl.highlight("zsh", "typeset -U path\n").unwrap();
+    assert!(html.contains("<a-k>typeset</a-k>"), "got: {html}");
+
+    assert_eq!(arborium::detect_language("plugins/git.zsh"), Some("zsh"));
+    assert_eq!(arborium::detect_language("/home/me/.zshrc"), Some("zsh"));
+    assert_eq!(arborium::detect_language("/home/me/.zshenv"), Some("zsh"));
+}
+
+#[test]
+#[cfg(feature = "lang-protobuf")]
+fn protobuf_schemas_are_highlighted() {
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight("proto", "message Book {\n  string title = 1;\n}\n")
+        .unwrap();
+    assert!(html.contains("<a-k>message</a-k>"), "got: {html}");
+    assert!(html.contains("<a-t>string</a-t>"), "got: {html}");
+
+    assert_eq!(
+        arborium::detect_language("api/v1/library.proto"),
+        Some("protobuf")
+    );
+}
+
+#[test]
+#[cfg(all(feature = "lang-markdown", feature = "lang-protobuf"))]
+fn markdown_proto_fences_resolve_to_protobuf() {
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight("markdown", "```proto\nsyntax = \"proto3\";\n```\n")
+        .unwrap();
+    assert!(html.contains("<a-k>syntax</a-k>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-make")]
+fn makefiles_are_detected() {
+    assert_eq!(arborium::detect_language("Makefile"), Some("make"));
+    assert_eq!(arborium::detect_language("src/makefile"), Some("make"));
+    assert_eq!(arborium::detect_language("rules/common.mk"), Some("make"));
+
+    let found = arborium::detect::detect(Some("GNUmakefile"), b"all:\n").unwrap();
+    assert_eq!(found.language, "make");
+}
+
+#[test]
+#[cfg(feature = "lang-jsonnet")]
+fn jsonnet_libraries_are_detected() {
+    assert_eq!(arborium::detect_language("main.jsonnet"), Some("jsonnet"));
+    assert_eq!(
+        arborium::detect_language("lib/k8s.libsonnet"),
+        Some("jsonnet")
+    );
+}
+
+#[test]
+#[cfg(all(feature = "lang-cue", feature = "lang-dhall"))]
+fn cue_and_dhall_are_detected() {
+    assert_eq!(arborium::detect_language("schema.cue"), Some("cue"));
+    assert_eq!(arborium::detect_language("package.dhall"), Some("dhall"));
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter.highlight("dhall", "let x = 1 in x").unwrap();
+    assert!(html.contains("<a-k>let</a-k>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(feature = "lang-rego", feature = "lang-nickel"))]
+fn rego_and_nickel_are_detected() {
+    assert_eq!(arborium::detect_language("policy/authz.rego"), Some("rego"));
+    assert_eq!(arborium::detect_language("server.ncl"), Some("nickel"));
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight("rego", "package authz\n\ndefault allow := false\n")
+        .unwrap();
+    assert!(html.contains("<a-k>default</a-k>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(feature = "lang-cuda", feature = "lang-wgsl"))]
+fn gpu_languages_are_detected() {
+    assert_eq!(arborium::detect_language("kernels/reduce.cu"), Some("cuda"));
+    assert_eq!(
+        arborium::detect_language("include/reduce.cuh"),
+        Some("cuda")
+    );
+    assert_eq!(arborium::detect_language("shaders/blur.wgsl"), Some("wgsl"));
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight(
+            "cuda",
+            "__global__ void k(int* x) { x[threadIdx.x] = 0; }\nvoid run(int* x) { k<<<1, 32>>>(x); }\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>__global__</a-k>"), "{}", html);
+    assert!(html.contains("<a-p>&lt;&lt;&lt;</a-p>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(feature = "lang-solidity", feature = "lang-move"))]
+fn smart_contract_languages_are_detected() {
+    assert_eq!(
+        arborium::detect_language("contracts/Token.sol"),
+        Some("solidity")
+    );
+    assert_eq!(arborium::detect_language("sources/coin.move"), Some("move"));
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight(
+            "move",
+            "module 0x1::coin {\n    struct Coin has store { value: u64 }\n}\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>module</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>has</a-k>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(feature = "lang-gdscript", feature = "lang-godot-resource"))]
+fn godot_files_are_detected() {
+    assert_eq!(
+        arborium::detect_language("player/player.gd"),
+        Some("gdscript")
+    );
+    assert_eq!(
+        arborium::detect_language("player/player.tscn"),
+        Some("godot-resource")
+    );
+    assert_eq!(
+        arborium::detect_language("themes/dark.tres"),
+        Some("godot-resource")
+    );
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight("gdscript", "@export var speed := 200.0\n")
+        .unwrap();
+    assert!(html.contains("<a-at>@export</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>var</a-k>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(feature = "lang-crystal", feature = "lang-nim", feature = "lang-odin"))]
+fn crystal_nim_and_odin_are_detected() {
+    assert_eq!(
+        arborium::detect_language("src/inventory.cr"),
+        Some("crystal")
+    );
+    assert_eq!(arborium::detect_language("src/inventory.nim"), Some("nim"));
+    assert_eq!(arborium::detect_language("config.nims"), Some("nim"));
+    assert_eq!(arborium::detect_language("src/main.odin"), Some("odin"));
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight("crystal", "struct Point\n  getter x : Int32\nend\n")
+        .unwrap();
+    assert!(html.contains("<a-k>struct</a-k>"), "{}", html);
+
+    let html = highlighter
+        .highlight("nim", "proc double(x: int): int {.inline.} = x * 2\n")
+        .unwrap();
+    assert!(html.contains("<a-k>proc</a-k>"), "{}", html);
+    assert!(html.contains("<a-at>inline</a-at>"), "{}", html);
+
+    let html = highlighter
+        .highlight("odin", "package main\n\nN :: #config(N, 4)\n")
+        .unwrap();
+    assert!(html.contains("<a-k>package</a-k>"), "{}", html);
+    assert!(html.contains("<a-at>#config</a-at>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(feature = "lang-fortran", feature = "lang-pascal"))]
+fn fortran_and_pascal_are_detected() {
+    for path in ["solver.f90", "legacy.f95", "types.f03", "LINPACK.f"] {
+        assert_eq!(arborium::detect_language(path), Some("fortran"), "{}", path);
+    }
+    for path in ["Inventory.pas", "units/stock.pp", "Project1.dpr"] {
+        assert_eq!(arborium::detect_language(path), Some("pascal"), "{}", path);
+    }
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight(
+            "fortran",
+            "MODULE geometry\n  IMPLICIT NONE\nEND MODULE geometry\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>MODULE</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>IMPLICIT</a-k>"), "{}", html);
+
+    let html = highlighter
+        .highlight(
+            "pascal",
+            "{$IFDEF DEBUG}\nconst Verbose = True;\n{$ENDIF}\n{ a comment }\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-at>{$IFDEF DEBUG}</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+    assert!(html.contains("<a-c>{ a comment }</a-c>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-haxe",
+    feature = "lang-purescript",
+    feature = "lang-rescript"
+))]
+fn haxe_purescript_and_rescript_are_detected() {
+    assert_eq!(arborium::detect_language("src/Main.hx"), Some("haxe"));
+    assert_eq!(
+        arborium::detect_language("src/Data/Inventory.purs"),
+        Some("purescript")
+    );
+    assert_eq!(arborium::detect_language("src/App.res"), Some("rescript"));
+    assert_eq!(arborium::detect_language("src/App.resi"), Some("rescript"));
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight(
+            "haxe",
+            "@:keep\nclass Main {\n\t#if js\n\tvar name = 'web';\n\t#end\n}\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-at>@:keep</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>#if js</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>class</a-k>"), "{}", html);
+
+    let html = highlighter
+        .highlight(
+            "purescript",
+            "module Main where\n\nclass Show a where\n  show :: a -> String\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>class</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>where</a-k>"), "{}", html);
+
+    let html = highlighter
+        .highlight(
+            "rescript",
+            "@react.component\nlet make = () => <div className=\"app\" />\n",
+        )
+        .unwrap();
+    assert!(html.contains("react.component</a-at>"), "{}", html);
+    assert!(html.contains("<a-tg>div</a-tg>"), "{}", html);
+    assert!(html.contains("<a-at>className</a-at>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-query",
+    feature = "lang-racket",
+    feature = "lang-scheme"
+))]
+fn scheme_owns_scm_and_racket_owns_rkt() {
+    use arborium::detect::{DetectionMethod, detect};
+
+    // `.scm` means Scheme; tree-sitter queries are told apart by content.
+    assert_eq!(
+        arborium::detect_language("sicp/streams.scm"),
+        Some("scheme")
+    );
+    assert_eq!(arborium::detect_language("queries/foo.tsq"), Some("query"));
+    assert_eq!(arborium::detect_language("list.rkt"), Some("racket"));
+
+    let found = detect(Some("streams.scm"), b"(define (square x) (* x x))\n").unwrap();
+    assert_eq!(found.language, "scheme");
+    let found = detect(Some("highlights.scm"), b"(comment) @comment\n").unwrap();
+    assert_eq!(found.language, "query");
+    assert_eq!(found.method, DetectionMethod::Heuristic);
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight("scheme", "(define (square x) (* x x))\n")
+        .unwrap();
+    assert!(html.contains("<a-k>define</a-k>"), "{}", html);
+
+    let html = highlighter
+        .highlight("racket", "#lang racket\n(define (f #:x [x 1]) x)\n")
+        .unwrap();
+    assert!(html.contains("<a-k>define</a-k>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(feature = "lang-groovy", feature = "lang-kotlin"))]
+fn gradle_scripts_are_detected() {
+    assert_eq!(
+        arborium::detect_language("app/build.gradle"),
+        Some("groovy")
+    );
+    assert_eq!(arborium::detect_language("settings.gradle"), Some("groovy"));
+    assert_eq!(
+        arborium::detect_language("scripts/release.gvy"),
+        Some("groovy")
+    );
+    assert_eq!(
+        arborium::detect_language("app/build.gradle.kts"),
+        Some("kotlin")
+    );
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight("gradle", "@CompileStatic\nclass Versions {}\n")
+        .unwrap();
+    assert!(html.contains("CompileStatic</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>class</a-k>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-astro",
+    feature = "lang-html",
+    feature = "lang-typescript",
+    feature = "lang-tsx"
+))]
+fn astro_components_are_detected() {
+    assert_eq!(
+        arborium::detect_language("src/pages/index.astro"),
+        Some("astro")
+    );
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight(
+            "astro",
+            "---\nconst { title } = Astro.props;\n---\n<h1>{title}</h1>\n<Counter client:load />\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-p>---</a-p>"), "{}", html);
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+    assert!(html.contains("<a-at>client:load</a-at>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-html",
+    feature = "lang-javascript",
+    feature = "lang-liquid",
+    feature = "lang-twig"
+))]
+fn twig_and_liquid_templates_are_detected() {
+    assert_eq!(
+        arborium::detect_language("templates/base.html.twig"),
+        Some("twig")
+    );
+    assert_eq!(arborium::detect_language("macros.twig"), Some("twig"));
+    assert_eq!(
+        arborium::detect_language("sections/collection.liquid"),
+        Some("liquid")
+    );
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight(
+            "twig",
+            "{% for user in users %}<li>{{ user.name|title }}</li>{% endfor %}\n<script>const n = 1;</script>\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>for</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+
+    let html = highlighter
+        .highlight(
+            "liquid",
+            "{% if product.available %}<p>{{ product.price | money }}</p>{% endif %}\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>if</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>p</a-tg>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-handlebars",
+    feature = "lang-html",
+    feature = "lang-javascript"
+))]
+fn handlebars_and_mustache_templates_are_detected() {
+    assert_eq!(
+        arborium::detect_language("views/layout.hbs"),
+        Some("handlebars")
+    );
+    assert_eq!(
+        arborium::detect_language("emails/welcome.handlebars"),
+        Some("handlebars")
+    );
+    assert_eq!(
+        arborium::detect_language("templates/list.mustache"),
+        Some("handlebars")
+    );
+
+    let mut highlighter = arborium::Highlighter::new();
+    for language in ["handlebars", "hbs", "mustache"] {
+        let html = highlighter
+            .highlight(
+                language,
+                "{{#each people}}<li>{{name}}</li>{{/each}}\n<script>const n = 1;</script>\n",
+            )
+            .unwrap();
+        assert!(html.contains("<a-k>each</a-k>"), "{}", html);
+        assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+        assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-erb",
+    feature = "lang-ejs",
+    feature = "lang-html",
+    feature = "lang-javascript",
+    feature = "lang-ruby"
+))]
+fn erb_and_ejs_templates_are_detected() {
+    assert_eq!(
+        arborium::detect_language("app/views/orders/show.html.erb"),
+        Some("erb")
+    );
+    assert_eq!(
+        arborium::detect_language("config/database.yml.erb"),
+        Some("erb")
+    );
+    assert_eq!(
+        arborium::detect_language("views/dashboard.ejs"),
+        Some("ejs")
+    );
+
+    // Same grammar, code injected as the id's own language
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight(
+            "erb",
+            "<% items.each do |item| %><li><%= item %></li><% end %>\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>do</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+
+    let html = highlighter
+        .highlight(
+            "ejs",
+            "<% items.forEach(function (item) { %><li><%= item %></li><% }) %>\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>function</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-blade",
+    feature = "lang-html",
+    feature = "lang-php",
+    feature = "lang-php-only"
+))]
+fn blade_compound_extension_wins_over_php() {
+    assert_eq!(
+        arborium::detect_language("resources/views/orders/index.blade.php"),
+        Some("blade")
+    );
+    assert_eq!(
+        arborium::detect_language("Welcome.Blade.PHP"),
+        Some("blade")
+    );
+    assert_eq!(arborium::detect_language("public/index.php"), Some("php"));
+
+    let found = arborium::detect::detect(Some("views/welcome.blade.php"), b"<div></div>").unwrap();
+    assert_eq!(found.language, "blade");
+    assert_eq!(found.method, arborium::detect::DetectionMethod::Extension);
+
+    let mut highlighter = arborium::Highlighter::new();
+    let html = highlighter
+        .highlight("blade", "@if ($user)\n<p>{{ $user->name }}</p>\n@endif\n")
+        .unwrap();
+    assert!(html.contains("<a-k>@if</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>p</a-tg>"), "{}", html);
+    assert!(html.contains("<a-v>$user</a-v>"), "{}", html);
+}
+
+#[test]
+#[cfg(feature = "lang-nu")]
+fn nushell_scripts_are_detected() {
+    assert_eq!(arborium::detect_language("scripts/backup.nu"), Some("nu"));
+    assert_eq!(
+        arborium::detect_language("~/.config/nushell/config.nu"),
+        Some("nu")
+    );
+
+    let found = arborium::detect::detect(Some("env.nu"), b"$env.EDITOR = \"hx\"\n").unwrap();
+    assert_eq!(found.language, "nu");
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "nushell",
+            "def greet [name: string] {\n  $\"Hello, ($name | str upcase)\"\n}\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>def</a-k>"), "got: {html}");
+    assert!(html.contains("<a-f>greet</a-f>"), "got: {html}");
+    assert!(html.contains("<a-f>str</a-f>"), "got: {html}");
+    assert!(html.contains("<a-t>string</a-t>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-tcl")]
+fn tcl_and_expect_scripts_are_detected() {
+    assert_eq!(arborium::detect_language("scripts/build.tcl"), Some("tcl"));
+    assert_eq!(arborium::detect_language("login.exp"), Some("tcl"));
+
+    let found = arborium::detect::detect(None, b"#!/usr/bin/env tclsh\nputs hi\n").unwrap();
+    assert_eq!(found.language, "tcl");
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "tcl",
+            "proc greet {name} {\n    puts \"Hello, $name\"\n    puts {$name}\n}\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>proc</a-k>"), "got: {html}");
+    assert!(html.contains("<a-f>greet</a-f>"), "got: {html}");
+    assert!(html.contains("<a-v>name</a-v>"), "got: {html}");
+    // Braces suppress substitution, so `$name` stays part of the string
+    assert!(html.contains("<a-s>{$name}</a-s>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(feature = "lang-perl", feature = "lang-prolog"))]
+fn pl_files_are_perl_unless_they_read_like_prolog() {
+    assert_eq!(arborium::detect_language("scripts/backup.pl"), Some("perl"));
+    assert_eq!(arborium::detect_language("family.pro"), Some("prolog"));
+    assert_eq!(arborium::detect_language("family.prolog"), Some("prolog"));
+
+    let perl = b"use strict;\nmy $dir = shift // '.';\nprint \"$dir\\n\";\n";
+    let found = arborium::detect::detect(Some("backup.pl"), perl).unwrap();
+    assert_eq!(found.language, "perl");
+
+    let prolog = b":- module(family, [ancestor/2]).\n\nancestor(X, Y) :- parent(X, Y).\n";
+    let found = arborium::detect::detect(Some("family.pl"), prolog).unwrap();
+    assert_eq!(found.language, "prolog");
+    assert_eq!(found.method, arborium::detect::DetectionMethod::Heuristic);
+}
+
+#[test]
+#[cfg(all(feature = "lang-matlab", feature = "lang-objc"))]
+fn m_files_are_matlab_unless_they_read_like_objective_c() {
+    use arborium::detect::{DetectionMethod, detect};
+
+    assert_eq!(
+        arborium::detect_language("signal/fft_example.m"),
+        Some("matlab")
+    );
+    let objc = arborium::supported_languages_detailed()
+        .iter()
+        .find(|lang| lang.id == "objc")
+        .unwrap();
+    assert!(objc.extensions.contains(&"m"));
+
+    let matlab = include_bytes!("../../../langs/group-moss/matlab/def/samples/fft_example.m");
+    let found = detect(Some("fft_example.m"), matlab).unwrap();
+    assert_eq!(found.language, "matlab");
+    assert_eq!(found.method, DetectionMethod::Extension);
+
+    let objc = include_bytes!("../../../langs/group-birch/objc/def/samples/ARBRingBuffer.m");
+    let found = detect(Some("ARBRingBuffer.m"), objc).unwrap();
+    assert_eq!(found.language, "objc");
+    assert_eq!(found.method, DetectionMethod::Heuristic);
+
+    // An empty file has nothing to go on, so it's MATLAB like the extension says
+    let found = detect(Some("untitled.m"), b"").unwrap();
+    assert_eq!(found.language, "matlab");
+}
+
+#[test]
+#[cfg(feature = "lang-ini")]
+fn ini_dialects_are_highlighted() {
+    assert_eq!(arborium::detect_language("wg0.conf"), Some("ini"));
+    assert_eq!(
+        arborium::detect_language("/usr/share/applications/firefox.desktop"),
+        Some("ini")
+    );
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight("ini", "memory_limit = 256M\n\n[[servers]]\nenabled = yes\n")
+        .unwrap();
+    assert!(html.contains("<a-pr>memory_limit</a-pr>"), "got: {html}");
+    assert!(html.contains("<a-n>256M</a-n>"), "got: {html}");
+    assert!(html.contains("<a-t>servers</a-t>"), "got: {html}");
+    assert!(html.contains("<a-co>yes</a-co>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-graphql")]
+fn graphql_schemas_are_highlighted() {
+    assert_eq!(
+        arborium::detect_language("schema.graphqls"),
+        Some("graphql")
+    );
+    assert_eq!(
+        arborium::detect_language("queries/cart.gql"),
+        Some("graphql")
+    );
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "graphql",
+            "type Cart {\n  items(first: Int): [ID!]! @deprecated(reason: \"Use lines\")\n}\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-t>Cart</a-t>"), "got: {html}");
+    assert!(html.contains("<a-pr>items</a-pr>"), "got: {html}");
+    assert!(html.contains("<a-v>first</a-v>"), "got: {html}");
+    assert!(html.contains("<a-t>Int</a-t>"), "got: {html}");
+    assert!(html.contains("<a-at>deprecated</a-at>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-janet")]
+fn janet_files_are_detected() {
+    assert_eq!(arborium::detect_language("src/main.janet"), Some("janet"));
+    assert_eq!(arborium::detect_language("project.jdn"), Some("janet"));
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight("janet", "(defn greet [name] (print \"hi \" name))\n")
+        .unwrap();
+    assert!(html.contains("<a-k>defn</a-k>"), "got: {html}");
+    assert!(html.contains("<a-f>greet</a-f>"), "got: {html}");
+    assert!(html.contains("<a-v>name</a-v>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-vhdl")]
+fn vhdl_files_are_detected() {
+    assert_eq!(arborium::detect_language("rtl/uart_tx.vhd"), Some("vhdl"));
+    assert_eq!(arborium::detect_language("rtl/uart_tx.vhdl"), Some("vhdl"));
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "vhd",
+            "entity blink is\n  port (clk : in std_logic);\nend entity blink;\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-t>blink</a-t>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-org")]
+fn org_files_are_detected() {
+    assert_eq!(arborium::detect_language("notes/todo.org"), Some("org"));
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl.highlight("org", "* TODO Ship it\n").unwrap();
+    assert!(html.contains("<a-k>TODO </a-k>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(feature = "lang-org", feature = "lang-c"))]
+fn org_source_blocks_resolve_capitalised_languages() {
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "org",
+            "#+begin_src C\nint main(void) { return 0; }\n#+end_src\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>return</a-k>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-rst")]
+fn rst_files_are_detected() {
+    assert_eq!(arborium::detect_language("docs/index.rst"), Some("rst"));
+    assert_eq!(arborium::detect_language("README.rest"), Some("rst"));
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl.highlight("rst", "Title\n=====\n").unwrap();
+    assert!(html.contains("<a-tt>Title</a-tt>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-asciidoc")]
+fn asciidoc_files_are_detected() {
+    assert_eq!(
+        arborium::detect_language("docs/guide.adoc"),
+        Some("asciidoc")
+    );
+    assert_eq!(
+        arborium::detect_language("book/chapter.asciidoc"),
+        Some("asciidoc")
+    );
+}
+
+#[test]
+#[cfg(all(feature = "lang-asciidoc", feature = "lang-rust"))]
+fn asciidoc_source_listings_resolve_their_style_language() {
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight("asciidoc", "[source,rust]\n----\nfn main() {}\n----\n")
+        .unwrap();
+    assert!(html.contains("<a-k>fn</a-k>"), "got: {html}");
+}
+
+#[test]
+fn git_files_are_detected_by_name() {
+    let detect = |path: &str| arborium::detect::detect(Some(path), b"").map(|d| d.language);
+    assert_eq!(detect(".git/COMMIT_EDITMSG").as_deref(), Some("git-commit"));
+    assert_eq!(
+        detect(".git/rebase-merge/git-rebase-todo").as_deref(),
+        Some("git-rebase")
+    );
+    assert_eq!(detect("/home/me/.gitconfig").as_deref(), Some("git-config"));
+    assert_eq!(detect("repo/.git/config").as_deref(), Some("git-config"));
+    assert_eq!(
+        detect("repo/.git/info/exclude").as_deref(),
+        Some("gitignore")
+    );
+    assert_eq!(detect("web/.gitignore").as_deref(), Some("gitignore"));
+}
+
+#[test]
+#[cfg(all(feature = "lang-git-commit", feature = "lang-diff"))]
+fn verbose_commit_messages_highlight_their_diff() {
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "git-commit",
+            "Add a greeting\n\nSigned-off-by: Ada <ada@example.com>\n\
+             # ------------------------ >8 ------------------------\n\
+             diff --git a/README b/README\n--- a/README\n+++ b/README\n@@ -1 +1 @@\n-Hi\n+Hello\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-tt>Add a greeting</a-tt>"), "got: {html}");
+    assert!(html.contains("<a-pr>Signed-off-by:</a-pr>"), "got: {html}");
+    // Lines the diff adds and removes
+    assert!(html.contains("Hello</a-s>"), "got: {html}");
+    assert!(html.contains("Hi</a-k>"), "got: {html}");
+}
+
+#[test]
+fn env_and_editorconfig_files_are_detected_by_name() {
+    let detect = |path: &str| arborium::detect::detect(Some(path), b"").map(|d| d.language);
+    assert_eq!(detect(".env").as_deref(), Some("dotenv"));
+    // "production" isn't a language, so the name decides
+    assert_eq!(detect("deploy/.env.production").as_deref(), Some("dotenv"));
+    assert_eq!(detect(".editorconfig").as_deref(), Some("editorconfig"));
+}
+
+#[test]
+#[cfg(feature = "lang-dotenv")]
+fn dotenv_interpolation_highlights() {
+    assert_eq!(arborium::detect_language("settings.env"), Some("dotenv"));
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "dotenv",
+            "export URL=\"http://${HOST:-localhost}:$PORT\"\nRAW='$PORT'\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>export</a-k>"), "got: {html}");
+    assert!(html.contains("<a-v>HOST</a-v>"), "got: {html}");
+    assert!(html.contains("<a-v>$PORT</a-v>"), "got: {html}");
+    assert!(html.contains("<a-s>&#39;$PORT&#39;</a-s>"), "got: {html}");
+}
+
+#[test]
+#[cfg(feature = "lang-systemd")]
+fn systemd_units_are_detected() {
+    for path in [
+        "/etc/systemd/system/backup@.service",
+        "backup@.timer",
+        "sshd.socket",
+        "home.mount",
+        "multi-user.target",
+    ] {
+        assert_eq!(arborium::detect_language(path), Some("systemd"), "{path}");
+    }
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "service",
+            "[Service]\nUser=%i\nRestart=on-failure\nPrivateTmp=yes\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-t>Service</a-t>"), "got: {html}");
+    assert!(html.contains("<a-k>Restart</a-k>"), "got: {html}");
+    assert!(html.contains("<a-v>%i</a-v>"), "got: {html}");
+    assert!(html.contains("<a-co>yes</a-co>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-markdown",
+    feature = "lang-promql",
+    feature = "lang-logfmt"
+))]
+fn promql_and_logfmt_fences_are_highlighted() {
+    assert_eq!(
+        arborium::detect_language("alerts/error_ratio.promql"),
+        Some("promql")
+    );
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "markdown",
+            "```promql\nsum by (job) (rate(http_requests_total{code=~\"5..\"}[5m]))\n```\n\n\
+             ```logfmt\nlevel=error msg=\"upstream failed\" duration=1.2s\n```\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>by</a-k>"), "got: {html}");
+    assert!(
+        html.contains("<a-v>http_requests_total</a-v>"),
+        "got: {html}"
+    );
+    assert!(html.contains("<a-n>5m</a-n>"), "got: {html}");
+    assert!(html.contains("<a-er>error</a-er>"), "got: {html}");
+    assert!(html.contains("<a-n>1.2s</a-n>"), "got: {html}");
+
+    // The `prometheus` alias names the same grammar
+    let html = hl.highlight("prometheus", "up{job=\"api\"} == 0").unwrap();
+    assert!(html.contains("<a-pr>job</a-pr>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(feature = "lang-markdown", feature = "lang-mermaid"))]
+fn mermaid_fences_are_highlighted() {
+    assert_eq!(
+        arborium::detect_language("docs/architecture.mmd"),
+        Some("mermaid")
+    );
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "markdown",
+            "```mermaid\ngraph LR\n  api[API] -->|calls| db[(Postgres)]\n```\n\n\
+             ```mermaid\nclassDiagram\n  Animal <|-- Duck\n```\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>graph</a-k>"), "got: {html}");
+    assert!(html.contains("<a-v>api</a-v>"), "got: {html}");
+    assert!(html.contains("<a-o>--&gt;</a-o>"), "got: {html}");
+    assert!(html.contains("<a-s>Postgres</a-s>"), "got: {html}");
+    // Dialects without a dedicated grammar still get their keyword
+    assert!(html.contains("<a-k>classDiagram</a-k>"), "got: {html}");
+    assert!(html.contains("<a-o>&lt;|--</a-o>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(feature = "lang-json5", feature = "lang-csv", feature = "lang-tsv"))]
+fn json5_csv_and_tsv_are_detected() {
+    assert_eq!(arborium::detect_language("renovate.json5"), Some("json5"));
+    assert_eq!(arborium::detect_language("exports/orders.csv"), Some("csv"));
+    assert_eq!(arborium::detect_language("genes.tsv"), Some("tsv"));
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl.highlight("csv", "id,name\n1,\"Doe, Jane\"\n").unwrap();
+    assert!(
+        html.contains("<a-k>id</a-k>,<a-s>name</a-s>"),
+        "got: {html}"
+    );
+    assert!(
+        html.contains("<a-s>&quot;Doe, Jane&quot;</a-s>"),
+        "got: {html}"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "lang-json", feature = "lang-json5"))]
+fn jsonc_resolves_to_json5() {
+    assert_eq!(
+        arborium::detect_language(".vscode/settings.jsonc"),
+        Some("json5")
+    );
+    assert_eq!(arborium::detect_language("package.json"), Some("json"));
+
+    // JSONC's trailing commas are JSON5, so the alias resolves there
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight("jsonc", "{\n  // editor\n  \"tabSize\": 2,\n}\n")
+        .unwrap();
+    assert!(html.contains("<a-c>// editor</a-c>"), "got: {html}");
+    assert!(html.contains("<a-n>2</a-n>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(feature = "lang-json5", feature = "lang-yaml"))]
+fn eslintrc_is_json5_or_yaml() {
+    use arborium::detect::{DetectionMethod, detect};
+
+    assert_eq!(arborium::detect_language("app/.babelrc"), Some("json5"));
+    assert_eq!(arborium::detect_language("app/.eslintrc"), Some("json5"));
+
+    let found = detect(Some("app/.eslintrc"), b"{\n  root: true,\n}\n").unwrap();
+    assert_eq!(found.language, "json5");
+    assert_eq!(found.method, DetectionMethod::Filename);
+
+    // ESLint reads the extensionless file as YAML too
+    let found = detect(
+        Some("app/.eslintrc"),
+        b"root: true\nextends: eslint:recommended\n",
+    )
+    .unwrap();
+    assert_eq!(found.language, "yaml");
+    assert_eq!(found.method, DetectionMethod::Heuristic);
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-sql",
+    feature = "lang-postgresql",
+    feature = "lang-mysql",
+    feature = "lang-sqlite"
+))]
+fn sql_dialects_are_detected() {
+    assert_eq!(arborium::detect_language("db/schema.sql"), Some("sql"));
+    assert_eq!(
+        arborium::detect_language("migrations/0001_init.psql"),
+        Some("postgresql")
+    );
+
+    // Same grammar, with each dialect's highlights on top
+    let source = "SELECT price::numeric FROM items;\n";
+    let mut hl = arborium::Highlighter::new();
+    let html = hl.highlight("postgres", source).unwrap();
+    assert!(html.contains("<a-o>::</a-o>"), "got: {html}");
+    let html = hl.highlight("sql", source).unwrap();
+    assert!(!html.contains("<a-o>::</a-o>"), "got: {html}");
+
+    let html = hl
+        .highlight("mysql", "CREATE TABLE `users` (`id` int) ENGINE=InnoDB;\n")
+        .unwrap();
+    assert!(html.contains("<a-at>ENGINE</a-at>"), "got: {html}");
+    assert!(html.contains("<a-co>InnoDB</a-co>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-markdown",
+    feature = "lang-smithy",
+    feature = "lang-prisma"
+))]
+fn smithy_and_prisma_fences_are_highlighted() {
+    assert_eq!(
+        arborium::detect_language("model/weather.smithy"),
+        Some("smithy")
+    );
+    assert_eq!(
+        arborium::detect_language("prisma/schema.prisma"),
+        Some("prisma")
+    );
+
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "markdown",
+            "```smithy\n@readonly\noperation GetCity {\n    input := { @required cityId: CityId }\n}\n```\n\n\
+             ```prisma\nmodel User {\n  id Int @id @default(autoincrement())\n}\n```\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-at>@readonly</a-at>"), "got: {html}");
+    assert!(html.contains("<a-k>operation</a-k>"), "got: {html}");
+    assert!(html.contains("<a-pr>cityId</a-pr>"), "got: {html}");
+    assert!(html.contains("<a-k>model</a-k>"), "got: {html}");
+    assert!(html.contains("<a-at>@default</a-at>"), "got: {html}");
+    assert!(html.contains("<a-f>autoincrement</a-f>"), "got: {html}");
+}
+
+#[test]
+#[cfg(all(
+    feature = "lang-c",
+    feature = "lang-comment",
+    feature = "lang-doxygen",
+    feature = "lang-x86asm"
+))]
+fn c_doc_comments_macros_and_asm_are_highlighted() {
+    let mut hl = arborium::Highlighter::new();
+    let html = hl
+        .highlight(
+            "c",
+            "/** @brief Swaps bytes. @param v The word */\n\
+             #define FOREVER for (;;)\n\
+             unsigned swap(unsigned v) { __asm__(\"bswap %0\" : \"+r\"(v)); return v; }\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>@brief</a-k>"), "got: {html}");
+    assert!(html.contains("<a-k>@param</a-k>"), "got: {html}");
+    assert!(html.contains("<a-k>for</a-k>"), "got: {html}");
+    assert!(html.contains("<a-f>bswap</a-f>"), "got: {html}");
+}
+
+#[test]
+fn every_supported_language_has_a_folds_query() {
+    for lang in arborium::supported_languages_detailed() {
+        assert!(
+            arborium::get_folds_query(lang.id).is_some(),
+            "{} has no folds query entry",
+            lang.id
+        );
+    }
+    assert_eq!(arborium::get_folds_query("bartholomew"), None);
+}
+
+#[test]
+fn every_supported_language_has_a_tags_query() {
+    for lang in arborium::supported_languages_detailed() {
+        assert!(
+            arborium::get_tags_query(lang.id).is_some(),
+            "{} has no tags query entry",
+            lang.id
+        );
+    }
+    assert_eq!(arborium::get_tags_query("bartholomew"), None);
+}
+
+#[test]
+#[cfg(feature = "lang-rust")]
+fn rust_tags_query_is_vendored() {
+    let tags = arborium::get_tags_query("rust").unwrap();
+    assert!(tags.contains("@definition.function"), "got: {tags}");
+}
+
+#[test]
+fn grammar_metadata_is_filled_in() {
+    use arborium::tree_sitter::{LANGUAGE_VERSION, MIN_COMPATIBLE_LANGUAGE_VERSION};
+
+    let mut placeholders = std::collections::HashMap::new();
+    for lang in arborium::supported_languages_detailed() {
+        assert!(
+            !lang.grammar_commit.is_empty() && lang.grammar_commit != "n/a",
+            "{} has no grammar commit",
+            lang.id
+        );
+        assert!(
+            (MIN_COMPATIBLE_LANGUAGE_VERSION..=LANGUAGE_VERSION).contains(&lang.abi_version()),
+            "{} has ABI version {}",
+            lang.id,
+            lang.abi_version()
+        );
+        assert_ne!(lang.queries_hash(), 0, "{}", lang.id);
+        assert!(
+            !lang.node_kinds().is_empty(),
+            "{} has no node kinds",
+            lang.id
+        );
+
+        // Upstream revisions can be shared by grammars from one repository,
+        // but source hashes stand in for a single crate's grammar. Grammars
+        // sharing a crate have the same queries too.
+        if lang.grammar_commit.starts_with("src-") {
+            if let Some(other) = placeholders.insert(lang.grammar_commit, *lang) {
+                assert_eq!(
+                    other.queries_hash(),
+                    lang.queries_hash(),
+                    "{} and {} share grammar commit {}",
+                    other.id,
+                    lang.id,
+                    lang.grammar_commit
+                );
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "lang-rust", feature = "lang-csv", feature = "lang-tsv"))]
+fn grammar_metadata_matches_the_crates() {
+    let info = |id: &str| {
+        *arborium::supported_languages_detailed()
+            .iter()
+            .find(|lang| lang.id == id)
+            .unwrap()
+    };
+
+    let rust = info("rust");
+    assert_eq!(rust.grammar_commit, arborium::lang_rust::GRAMMAR_COMMIT);
+    assert_eq!(
+        rust.grammar_commit,
+        "261b20226c04ef601adbdf185a800512a5f66291"
+    );
+    assert_eq!(rust.abi_version(), arborium::lang_rust::abi_version());
+    assert_eq!(
+        rust.abi_version(),
+        arborium::get_language("rust").unwrap().abi_version()
+    );
+    assert_eq!(rust.queries_hash(), arborium::lang_rust::queries_hash());
+
+    let kinds = rust.node_kinds();
+    assert!(kinds.contains(&"function_item"), "{kinds:?}");
+    assert!(!kinds.iter().any(|kind| kind.starts_with('_')), "{kinds:?}");
+    assert!(kinds.is_sorted());
+    let fields = rust.field_names();
+    assert!(
+        fields.contains(&"name") && fields.contains(&"body"),
+        "{fields:?}"
+    );
+    assert!(fields.is_sorted());
+
+    // Maintained here, so named after its sources
+    let csv = info("csv");
+    assert!(
+        csv.grammar_commit.starts_with("src-"),
+        "{}",
+        csv.grammar_commit
+    );
+    assert_ne!(csv.grammar_commit, info("tsv").grammar_commit);
+    assert_ne!(csv.queries_hash(), rust.queries_hash());
+}
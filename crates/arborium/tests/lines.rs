@@ -0,0 +1,64 @@
+//! Tests for per-line span slicing with real grammars.
+
+#![cfg(feature = "lang-rust")]
+
+use arborium::Highlighter;
+use arborium::lines::split_spans;
+use indoc::indoc;
+
+#[test]
+fn block_comment_spans_every_line() {
+    let source = indoc! {"
+        /* first
+           second
+           third */
+        fn main() {}
+    "};
+
+    let mut hl = Highlighter::new();
+    let spans = hl.highlight_spans("rust", source).unwrap();
+    let lines = split_spans(source, &spans);
+
+    assert_eq!(lines.len(), 4);
+    for line in &lines[..3] {
+        let comment = line
+            .spans
+            .iter()
+            .find(|s| s.span.capture.starts_with("comment"))
+            .unwrap_or_else(|| panic!("no comment on line {}: {:#?}", line.index, line));
+        assert_eq!(comment.column_range, 0..line.text.len());
+    }
+
+    let keyword = lines[3]
+        .spans
+        .iter()
+        .find(|s| s.span.capture.starts_with("keyword"))
+        .expect("keyword on last line");
+    assert_eq!(&lines[3].text[keyword.column_range.clone()], "fn");
+}
+
+#[test]
+fn crlf_string_literal() {
+    let source = "let s = \"one\r\ntwo\r\nthree\";\r\n";
+
+    let mut hl = Highlighter::new();
+    let spans = hl.highlight_spans("rust", source).unwrap();
+    let lines = split_spans(source, &spans);
+
+    let texts: Vec<_> = lines.iter().map(|l| l.text).collect();
+    assert_eq!(texts, vec!["let s = \"one", "two", "three\";"]);
+
+    for line in &lines {
+        assert!(
+            line.spans.iter().any(|s| s.span.capture == "string"),
+            "line {} should carry the string span",
+            line.index
+        );
+        for s in &line.spans {
+            assert_eq!(
+                &source[s.byte_range.clone()],
+                &line.text[s.column_range.clone()]
+            );
+        }
+    }
+}
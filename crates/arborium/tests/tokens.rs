@@ -0,0 +1,99 @@
+//! Tests for the token stream API.
+
+#![cfg(feature = "lang-rust")]
+
+use arborium::{Highlighter, Token};
+use indoc::indoc;
+
+fn collect<'a>(hl: &mut Highlighter, language: &str, source: &'a str) -> Vec<Token<'a>> {
+    arborium::tokens(hl, language, source).unwrap().collect()
+}
+
+#[test]
+fn tokens_roundtrip_source() {
+    let source = indoc! {r#"
+        // A comment
+        fn main() {
+            let message = "Hello, world!";
+            println!("{}", message);
+        }
+    "#};
+
+    let mut hl = Highlighter::new();
+    let tokens = collect(&mut hl, "rust", source);
+
+    let joined: String = tokens.iter().map(|t| t.text).collect();
+    assert_eq!(joined.as_bytes(), source.as_bytes());
+
+    // Ranges are contiguous and non-overlapping
+    let mut pos = 0;
+    for token in &tokens {
+        assert_eq!(token.byte_range.start, pos);
+        assert_eq!(&source[token.byte_range.clone()], token.text);
+        pos = token.byte_range.end;
+    }
+    assert_eq!(pos, source.len());
+}
+
+#[test]
+fn tokens_known_highlights() {
+    let source = "fn main() { let x = \"hi\"; }";
+
+    let mut hl = Highlighter::new();
+    let tokens = collect(&mut hl, "rust", source);
+
+    let find = |text: &str| {
+        tokens
+            .iter()
+            .find(|t| t.text == text)
+            .unwrap_or_else(|| panic!("no token for {text:?}: {tokens:#?}"))
+    };
+
+    assert_eq!(find("fn").highlight, Some("keyword"));
+    assert_eq!(find("let").highlight, Some("keyword"));
+    assert_eq!(find("main").highlight, Some("function"));
+    assert_eq!(find("\"hi\"").highlight, Some("string"));
+    assert!(
+        tokens
+            .iter()
+            .all(|t| t.highlight.is_some() == (t.depth > 0))
+    );
+}
+
+#[test]
+fn tokens_unsupported_language() {
+    let mut hl = Highlighter::new();
+    assert!(arborium::tokens(&mut hl, "bartholomew", "hello").is_err());
+}
+
+#[test]
+#[cfg(all(feature = "lang-html", feature = "lang-css"))]
+fn tokens_include_injections() {
+    let source = "<style>a { color: red; }</style>";
+
+    let mut hl = Highlighter::new();
+    let tokens = collect(&mut hl, "html", source);
+
+    let joined: String = tokens.iter().map(|t| t.text).collect();
+    assert_eq!(joined, source);
+
+    // `color` only gets highlighted through the CSS injection
+    let color = tokens.iter().find(|t| t.text == "color").unwrap();
+    assert_eq!(color.highlight, Some("property"));
+}
+
+#[test]
+#[cfg(all(feature = "lang-html", feature = "lang-css"))]
+fn tokens_use_the_injection_resolver() {
+    let source = "<style>a { color: red; }</style>";
+
+    let mut hl = Highlighter::new();
+    hl.set_injection_resolver(|language: &str| {
+        (language == "css").then(|| "bartholomew".to_string())
+    });
+    let tokens = collect(&mut hl, "html", source);
+
+    // The CSS went to a language nobody knows, so it stays plain
+    let color = tokens.iter().find(|t| t.text.contains("color")).unwrap();
+    assert_eq!(color.highlight, None);
+}
@@ -0,0 +1,72 @@
+// Schema and defaults for the services deployed by this repository.
+package deploy
+
+import (
+	"strings"
+	"list"
+	"net"
+)
+
+#Port: int & >0 & <=65535
+
+#Service: {
+	name!:      string & =~"^[a-z][a-z0-9-]*$" & strings.MaxRunes(63)
+	image:      string | *"registry.example.com/\(name):latest"
+	replicas:   uint | *2
+	ports:      [...#Port] & list.MinItems(1)
+	env?:       {[string]: string}
+	tier:       "frontend" | "backend" | *"internal"
+	host?:      string & net.FQDN
+	resources?: #Resources
+
+	_labels: {
+		app:  name
+		tier: tier
+	}
+}
+
+#Resources: {
+	cpu:    *"250m" | string
+	memory: *256Mi | number
+}
+
+services: [Name=string]: #Service & {name: Name}
+
+services: {
+	web: {
+		ports: [80, 443]
+		tier:  "frontend"
+		host:  "www.example.com"
+		env: LOG_LEVEL: "info"
+	}
+	api: {
+		replicas: 3
+		ports: [8080]
+		tier: "backend"
+		env: {
+			DATABASE_URL: "postgres://api@db:5432/app"
+			FEATURES:     strings.Join(["search", "billing"], ",")
+		}
+	}
+	worker: ports: [9000] @deprecated(use="jobs")
+}
+
+let allPorts = [for _, s in services for p in s.ports {p}]
+
+summary: {
+	count: len(services)
+	ports: list.Sort(allPorts, list.Ascending)
+	motd: """
+		Deployed \(count) services.
+		Raw strings look like #"C:\path"#.
+		"""
+	for name, s in services if s.tier == "frontend" {
+		"public-\(name)": s.host
+	}
+	broken?: _|_
+	extra:   _
+	empty:   null
+	ok:      true && !false
+	ratio:   0.75 * 1e2
+	...
+}
@@ -0,0 +1,124 @@
+! Shapes with type-bound procedures, a user-defined operator and a small
+! driver program.
+
+module shapes
+  use, intrinsic :: iso_fortran_env, only: dp => real64
+  implicit none
+  private
+  public :: shape, circle, rectangle, vec2, operator(+), describe
+
+  real(dp), parameter :: PI = 4.0_dp * atan(1.0_dp)
+
+  type :: vec2
+    real(dp) :: x = 0.0_dp, y = 0.0_dp
+  end type vec2
+
+  type, abstract :: shape
+    character(len=:), allocatable :: name
+  contains
+    procedure(area_iface), deferred :: area
+    procedure :: describe
+  end type shape
+
+  type, extends(shape) :: circle
+    real(dp) :: radius
+  contains
+    procedure :: area => circle_area
+  end type circle
+
+  type, extends(shape) :: rectangle
+    real(dp) :: width, height
+  contains
+    procedure :: area => rectangle_area
+  end type rectangle
+
+  abstract interface
+    pure function area_iface(self) result(a)
+      import :: shape, dp
+      class(shape), intent(in) :: self
+      real(dp) :: a
+    end function area_iface
+  end interface
+
+  interface operator(+)
+    module procedure add_vec
+  end interface
+
+contains
+
+  pure function circle_area(self) result(a)
+    class(circle), intent(in) :: self
+    real(dp) :: a
+    a = PI * self%radius**2
+  end function circle_area
+
+  pure function rectangle_area(self) result(a)
+    class(rectangle), intent(in) :: self
+    real(dp) :: a
+    a = self%width * self%height
+  end function rectangle_area
+
+  elemental function add_vec(a, b) result(c)
+    type(vec2), intent(in) :: a, b
+    type(vec2) :: c
+    c = vec2(a%x + b%x, a%y + b%y)
+  end function add_vec
+
+  subroutine describe(self)
+    class(shape), intent(in) :: self
+    write (*, '(a, ": ", f10.4)') self%name, self%area()
+  end subroutine describe
+
+end module shapes
+
+program main
+  use shapes
+  implicit none
+  integer, parameter :: n = 5
+  integer :: i, grade
+  real(8) :: squares(n), total
+  type(circle) :: c
+  type(vec2) :: p
+  logical :: verbose = .true.
+
+#ifdef DEBUG
+  print *, "debug build"
+#endif
+
+  c = circle(name="unit circle", radius=1.0d0)
+  call c%describe()
+
+  p = vec2(1.0d0, 2.0d0) + vec2(0.5d0, -1.0d0)
+  print '(2f6.2)', p%x, p%y
+
+  squares = [(dble(i)**2, i = 1, n)]
+  total = 0
+  do i = 1, n
+    if (mod(i, 2) == 0) cycle
+    total = total + squares(i)
+  end do
+
+  outer: do while (total > 1.0d0 .and. verbose)
+    total = total / 2
+    if (total < 10) exit outer
+  end do outer
+
+  grade = 87
+  select case (grade)
+  case (90:)
+    print *, "A"
+  case (80:89)
+    print *, "B"
+  case default
+    print *, "below B"
+  end select
+
+  if (size(squares) /= n) then
+    error stop "size mismatch"
+  else if (any(squares < 0)) then
+    stop 1
+  else
+    print *, "sum of odd squares:", &
+             total, sum(squares(1:n:2))
+  end if
+end program main
@@ -0,0 +1,81 @@
+class_name Player
+extends CharacterBody2D
+## A platformer player controller with a double jump and a health bar.
+
+signal health_changed(old_value: int, new_value: int)
+signal died
+
+enum State { IDLE, RUNNING, JUMPING, FALLING }
+
+const GRAVITY := 980.0
+const MAX_JUMPS = 2
+
+@export var speed: float = 240.0
+@export_range(100, 800, 10) var jump_velocity := 420.0
+@export var hurt_sound: AudioStream = preload("res://audio/hurt.wav")
+
+@onready var sprite: AnimatedSprite2D = $Sprite
+@onready var health_bar := $UI/HealthBar as ProgressBar
+@onready var camera = %Camera
+
+var state := State.IDLE
+var jumps_left := MAX_JUMPS
+var inventory: Array[String] = []
+
+var health: int = 100:
+	set(value):
+		var old := health
+		health = clampi(value, 0, 100)
+		health_changed.emit(old, health)
+		if health == 0:
+			died.emit()
+	get:
+		return health
+
+
+func _ready() -> void:
+	health_changed.connect(_on_health_changed)
+	died.connect(func(): set_physics_process(false))
+	sprite.play(&"idle")
+
+
+func _physics_process(delta: float) -> void:
+	if not is_on_floor():
+		velocity.y += GRAVITY * delta
+	else:
+		jumps_left = MAX_JUMPS
+
+	if Input.is_action_just_pressed("jump") and jumps_left > 0:
+		velocity.y = -jump_velocity
+		jumps_left -= 1
+
+	var direction := Input.get_axis(&"move_left", &"move_right")
+	velocity.x = direction * speed if direction else move_toward(velocity.x, 0, speed)
+	move_and_slide()
+	_update_state()
+
+
+func _update_state() -> void:
+	match state:
+		State.IDLE, State.RUNNING:
+			if velocity.y < 0:
+				state = State.JUMPING
+		State.JUMPING when velocity.y >= 0:
+			state = State.FALLING
+		_:
+			state = State.RUNNING if absf(velocity.x) > 1.0 else State.IDLE
+
+
+func take_damage(amount: int, source: Node = null) -> void:
+	health -= amount
+	$HurtPlayer.stream = hurt_sound
+	$HurtPlayer.play()
+	if source is Enemy and not source.is_queued_for_deletion():
+		print("Hit by %s for %d" % [source.name, amount])
+	await get_tree().create_timer(0.5).timeout
+	sprite.modulate = Color(1, 1, 1)
+
+
+func _on_health_changed(_old_value: int, new_value: int) -> void:
+	health_bar.value = new_value
+	get_node(^"UI/HealthBar").visible = new_value < 100
@@ -0,0 +1,92 @@
+package shop;
+
+import haxe.ds.StringMap;
+import haxe.Json;
+using StringTools;
+
+/**
+ * Items a shop can hold, with per-kind payloads.
+ */
+enum Item {
+	Weapon(name:String, damage:Int);
+	Potion(name:String, ?heal:Int);
+	Gold(amount:Int);
+	Nothing;
+}
+
+abstract Coins(Int) from Int to Int {
+	public inline function new(value:Int) {
+		this = value;
+	}
+
+	@:op(A + B) static function add(a:Coins, b:Coins):Coins;
+
+	public function toString():String {
+		return '${this}g';
+	}
+}
+
+typedef Slot = {
+	var item:Item;
+	var count:Int;
+	@:optional var note:String;
+}
+
+@:keep
+class Inventory {
+	public static inline var MAX_SLOTS = 24;
+
+	public var gold(default, null):Coins = new Coins(0);
+
+	final slots:StringMap<Slot> = new StringMap();
+	var onChange:Null<(key:String) -> Void>;
+
+	public function new() {}
+
+	public function add(key:String, item:Item, count = 1):Bool {
+		if (Lambda.count(slots) >= MAX_SLOTS) return false;
+
+		switch (item) {
+			case Gold(amount):
+				gold += amount;
+			case Nothing:
+				return false;
+			case Weapon(name, damage) if (damage > 100):
+				trace('Legendary $name deals ${damage * 2} damage!');
+				slots.set(key, {item: item, count: 1});
+			case _:
+				var slot = slots.get(key);
+				if (slot != null) slot.count += count; else slots.set(key, {item: item, count: count});
+		}
+
+		if (onChange != null) onChange(key);
+		return true;
+	}
+
+	public function describe():String {
+		var lines = [for (key => slot in slots) '$key x${slot.count}'];
+		lines.sort((a, b) -> Reflect.compare(a, b));
+		return lines.join(", ").trim();
+	}
+
+	public function save():String {
+		#if js
+		var storage = js.Browser.getLocalStorage();
+		storage.setItem("inventory", Json.stringify(describe()));
+		#elseif sys
+		sys.io.File.saveContent("inventory.json", Json.stringify(describe()));
+		#else
+		#error "Unsupported target"
+		#end
+
+		var pattern = ~/^[a-z_]+$/i;
+		try {
+			for (i in 0...MAX_SLOTS) {
+				if (!pattern.match(Std.string(i))) continue;
+			}
+		} catch (e:haxe.Exception) {
+			throw 'Could not save: ${e.message}';
+		}
+		return cast(gold, Int) > 0 ? "saved" : "empty";
+	}
+}
@@ -0,0 +1,27 @@
+-- Shop schema for MySQL 8
+
+CREATE TABLE `customers` (
+  `id` int unsigned NOT NULL AUTO_INCREMENT,
+  `email` varchar(255) NOT NULL,
+  `display_name` varchar(100) DEFAULT NULL COMMENT 'shown on invoices',
+  `created_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB AUTO_INCREMENT=1000 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE `orders` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `customer_id` int unsigned NOT NULL REFERENCES `customers` (`id`) ON DELETE CASCADE,
+  `total` decimal(10,2) NOT NULL DEFAULT '0.00',
+  `updated_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+INSERT INTO `customers` (`email`, `display_name`)
+VALUES ('ada@example.com', 'Ada'), ('grace@example.com', NULL);
+
+SELECT c.`display_name`, count(o.`id`) AS `orders`, sum(o.`total`) AS `spent`
+FROM `customers` AS c
+LEFT JOIN `orders` AS o ON o.`customer_id` = c.`id`
+GROUP BY c.`id`
+HAVING `spent` > 100
+ORDER BY `spent` DESC;
@@ -0,0 +1,109 @@
+package particles
+
+import "core:fmt"
+import "core:math"
+import rl "vendor:raylib"
+
+MAX_PARTICLES :: 256
+GRAVITY : f32 : 9.81
+
+Vec2 :: [2]f32
+
+Kind :: enum u8 {
+	Spark,
+	Smoke,
+	Ember = 4,
+}
+
+Particle :: struct {
+	using pos: Vec2,
+	vel:       Vec2,
+	life:      f32,
+	kind:      Kind,
+	name:      string `json:"name"`,
+}
+
+Event :: union {
+	Spawned,
+	Expired,
+}
+
+Spawned :: struct { count: int }
+Expired :: struct { index: int }
+
+Pool :: struct($N: int) {
+	items: [N]Particle,
+	alive: bit_set[0..<64],
+	len:   int,
+}
+
+@(private)
+spawn :: proc(pool: ^Pool($N), kind: Kind, at: Vec2) -> (index: int, ok: bool) {
+	if pool.len >= N {
+		return -1, false
+	}
+	pool.items[pool.len] = Particle{pos = at, vel = {0, -1}, life = 1.0, kind = kind}
+	pool.len += 1
+	return pool.len - 1, true
+}
+
+step :: proc(pool: ^Pool($N), dt: f32) #no_bounds_check {
+	for i in 0..<pool.len {
+		p := &pool.items[i]
+		p.vel.y += GRAVITY * dt
+		p.pos += p.vel * dt
+		p.life -= dt if p.kind != .Smoke else dt / 2
+	}
+}
+
+describe :: proc(e: Event) -> string {
+	switch v in e {
+	case Spawned:
+		return fmt.tprintf("spawned %d", v.count)
+	case Expired:
+		return fmt.tprintf("expired #%d", v.index)
+	}
+	return "unknown"
+}
+
+lerp :: proc "contextless" (a, b: f32, t: f32) -> f32 {
+	return a + (b - a) * t
+}
+
+foreign import libc "system:c"
+
+foreign libc {
+	rand :: proc() -> i32 ---
+}
+
+main :: proc() {
+	pool: Pool(MAX_PARTICLES)
+	defer fmt.println("done")
+
+	when ODIN_OS == .Windows {
+		fmt.println("running on Windows")
+	} else {
+		fmt.println("running elsewhere")
+	}
+
+	for i := 0; i < 8; i += 1 {
+		idx, ok := spawn(&pool, .Spark, {f32(i), 0})
+		if !ok do break
+		fmt.println(idx, math.sqrt(f32(idx)))
+	}
+
+	colors := map[Kind]rl.Color{
+		.Spark = rl.ORANGE,
+		.Smoke = rl.GRAY,
+	}
+	defer delete(colors)
+
+	#partial switch pool.items[0].kind {
+	case .Spark, .Ember:
+		fmt.println("hot")
+	}
+
+	seed := cast(u32)rand()
+	value := transmute(f32)seed
+	fmt.printf("%v %v %c\n", value, lerp(0, 1, 0.5), 'x')
+}
@@ -0,0 +1,45 @@
+-- Row-level audit trail for the orders table
+
+CREATE TABLE order_audit (
+    id bigserial PRIMARY KEY,
+    order_id bigint NOT NULL,
+    operation text NOT NULL,
+    changed_at timestamptz NOT NULL DEFAULT now(),
+    old_row jsonb,
+    new_row jsonb
+);
+
+CREATE OR REPLACE FUNCTION order_total(p_order_id bigint)
+RETURNS numeric
+LANGUAGE sql
+STABLE
+AS $$
+    SELECT coalesce(sum(quantity * unit_price), 0)::numeric(12, 2)
+    FROM order_lines
+    WHERE order_id = p_order_id;
+$$;
+
+CREATE OR REPLACE FUNCTION audit_order() RETURNS trigger
+AS $body$
+DECLARE
+    total numeric;
+BEGIN
+    total := order_total(NEW.id);
+    IF total > 10000 THEN
+        RAISE NOTICE 'large order % (% EUR)', NEW.id, total;
+    END IF;
+    INSERT INTO order_audit (order_id, operation, old_row, new_row)
+    VALUES (NEW.id, TG_OP, to_jsonb(OLD), to_jsonb(NEW));
+    RETURN NEW;
+END;
+$body$ LANGUAGE plpgsql;
+
+CREATE TRIGGER orders_audit
+    AFTER INSERT OR UPDATE ON orders
+    FOR EACH ROW EXECUTE FUNCTION audit_order();
+
+SELECT id, created_at::date AS day, E'status:\t' || status AS label
+FROM orders
+WHERE created_at > now() - interval '7 days'
+ORDER BY created_at DESC
+LIMIT 20;
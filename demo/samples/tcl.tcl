@@ -0,0 +1,88 @@
+# build.tcl -- non-project Vivado build for the UART demo board
+#
+#   vivado -mode batch -source build.tcl -tclargs ?top? ?part?
+
+package require Tcl 8.6
+
+namespace eval ::build {
+    variable top  [expr {[llength $::argv] > 0 ? [lindex $::argv 0] : "uart_top"}]
+    variable part xc7a35ticsg324-1L
+    variable out  [file normalize [file join [pwd] out]]
+    variable sources {
+        rtl/uart_tx.vhd
+        rtl/uart_rx.vhd
+        rtl/uart_top.vhd
+    }
+
+    namespace export run
+}
+
+proc ::build::log {msg {level INFO}} {
+    set stamp [clock format [clock seconds] -format %H:%M:%S]
+    puts "\[$stamp\] $level: $msg"
+}
+
+proc ::build::read_sources {} {
+    variable sources
+    foreach src $sources {
+        if {![file exists $src]} {
+            error "missing source: $src"
+        }
+        read_vhdl -vhdl2008 $src
+    }
+    log "read [llength $sources] VHDL files"
+}
+
+# Pins and clocks for the board; braces keep $ and [] literal until
+# read_xdc hands them to the constraint parser.
+proc ::build::constrain {} {
+    set xdc {
+        set_property -dict {PACKAGE_PIN E3 IOSTANDARD LVCMOS33} [get_ports clk]
+        set_property -dict {PACKAGE_PIN D10 IOSTANDARD LVCMOS33} [get_ports tx]
+        create_clock -period 10.000 -name sys_clk [get_ports clk]
+    }
+    set path [file join $::build::out board.xdc]
+    set fh [open $path w]
+    puts $fh $xdc
+    close $fh
+    read_xdc $path
+}
+
+proc ::build::run {} {
+    variable top
+    variable part
+    variable out
+
+    file mkdir $out
+    read_sources
+    constrain
+
+    set steps {synth_design opt_design place_design route_design}
+    for {set i 0} {$i < [llength $steps]} {incr i} {
+        set step [lindex $steps $i]
+        log "step [expr {$i + 1}]/[llength $steps]: $step"
+        if {$step eq "synth_design"} {
+            synth_design -top $top -part $part
+        } else {
+            $step
+        }
+    }
+
+    set wns [get_property SLACK [get_timing_paths -max_paths 1]]
+    if {$wns < 0.0} {
+        log "timing failed, WNS = ${wns}ns" ERROR
+        return -code error $wns
+    }
+
+    report_utilization -file $out/${top}_util.rpt
+    write_bitstream -force $out/$top.bit
+}
+
+try {
+    ::build::run
+} on error {msg opts} {
+    ::build::log $msg ERROR
+    exit 1
+} finally {
+    ::build::log "done in $::build::out"
+}
@@ -0,0 +1,105 @@
+// GPU particle simulation: a compute pass integrates positions, then a
+// render pass draws each particle as a camera-facing quad.
+enable f16;
+
+const PARTICLES_PER_GROUP: u32 = 64u;
+override gravity: f32 = -9.81;
+
+alias Color = vec4<f32>;
+
+struct Particle {
+  position: vec3f,
+  @align(16) velocity: vec3<f32>,
+  life: f32,
+  tint: Color,
+}
+
+struct Camera {
+  view_proj: mat4x4<f32>,
+  right: vec3f,
+  up: vec3f,
+}
+
+struct VertexOutput {
+  @builtin(position) clip_position: vec4f,
+  @location(0) uv: vec2<f32>,
+  @location(1) @interpolate(flat) tint: Color,
+}
+
+@group(0) @binding(0) var<uniform> camera: Camera;
+@group(0) @binding(1) var<storage, read_write> particles: array<Particle>;
+@group(0) @binding(2) var<storage, read_write> alive: atomic<u32>;
+@group(1) @binding(0) var sprite: texture_2d<f32>;
+@group(1) @binding(1) var sprite_sampler: sampler;
+
+var<workgroup> tile: array<vec3f, PARTICLES_PER_GROUP>;
+
+fn integrate(p: ptr<function, Particle>, dt: f32) {
+  (*p).velocity.y += gravity * dt;
+  (*p).position += (*p).velocity * dt;
+  (*p).life = max((*p).life - dt, 0.0);
+}
+
+@compute @workgroup_size(PARTICLES_PER_GROUP)
+fn simulate(
+  @builtin(global_invocation_id) id: vec3<u32>,
+  @builtin(local_invocation_index) local: u32,
+) {
+  let index = id.x;
+  if index >= arrayLength(&particles) {
+    return;
+  }
+
+  var particle = particles[index];
+  integrate(&particle, 1.0 / 60.0);
+  tile[local] = particle.position;
+  workgroupBarrier();
+
+  if particle.life > 0.0 {
+    atomicAdd(&alive, 1u);
+  } else if particle.position.y < -10.0 {
+    particle.tint = Color(0.0);
+  }
+  particles[index] = particle;
+}
+
+@vertex
+fn vs_main(
+  @builtin(vertex_index) vertex: u32,
+  @builtin(instance_index) instance: u32,
+) -> VertexOutput {
+  var corners = array<vec2f, 4>(
+    vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(-1.0, 1.0), vec2(1.0, 1.0),
+  );
+  let corner = corners[vertex];
+  let particle = particles[instance];
+  let size = 0.05h;
+  let world = particle.position
+    + camera.right * corner.x * f32(size)
+    + camera.up * corner.y * f32(size);
+
+  var out: VertexOutput;
+  out.clip_position = camera.view_proj * vec4<f32>(world, 1.0);
+  out.uv = corner * 0.5 + 0.5;
+  out.tint = particle.tint;
+  return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+  let texel = textureSample(sprite, sprite_sampler, in.uv);
+  if texel.a < 0.01 {
+    discard;
+  }
+  var color = texel * in.tint;
+  for (var i = 0; i < 3; i++) {
+    color[i] = pow(color[i], 1.0 / 2.2);
+  }
+  switch u32(color.a * 4.0) {
+    case 0u, 1u: {
+      color.a = 0.5;
+    }
+    default: {}
+  }
+  return color;
+}
@@ -0,0 +1,59 @@
+/**
+ * @file Tree-sitter grammar definition for DTD
+ * @author ObserverOfTime
+ * @license MIT
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+import * as c from './common/common.mjs';
+
+const O = optional;
+
+export default grammar({
+  name: 'dtd',
+
+  externals: $ => [
+    $.PITarget,
+    $._pi_content,
+    $.Comment,
+  ],
+
+  extras: _ => [],
+
+  supertypes: $ => [
+    $._markupdecl,
+    $._AttType,
+    $._EnumeratedType,
+    $._EntityDecl,
+    $._Reference,
+  ],
+
+  conflicts: $ => [
+    [$.AttlistDecl, $.AttDef]
+  ],
+
+  word: $ => $.Name,
+
+  rules: {
+    document: $ => seq(
+      O($.TextDecl),
+      repeat(choice(
+        $._markupdecl,
+        $._DeclSep,
+      )),
+    ),
+
+    TextDecl: $ => seq(
+      '<?',
+      'xml',
+      O($._VersionInfo),
+      $._EncodingDecl,
+      O($._S),
+      '?>'
+    ),
+
+    ...c.rules
+  }
+});
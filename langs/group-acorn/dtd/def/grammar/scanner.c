@@ -0,0 +1,33 @@
+#include "common/scanner.h"
+
+bool tree_sitter_dtd_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+    if (valid_symbols[PI_TARGET] && valid_symbols[PI_CONTENT] && valid_symbols[COMMENT]) {
+        return false;
+    }
+
+    if (valid_symbols[PI_TARGET]) {
+        return scan_pi_target(lexer, valid_symbols);
+    }
+
+    if (valid_symbols[PI_CONTENT]) {
+        return scan_pi_content(lexer);
+    }
+
+    if (valid_symbols[COMMENT] && lexer->lookahead == '<') {
+        advance(lexer);
+        if (lexer->lookahead == '!') {
+            advance(lexer);
+            return scan_comment(lexer);
+        }
+    }
+
+    return false;
+}
+
+void *tree_sitter_dtd_external_scanner_create() { return NULL; }
+
+void tree_sitter_dtd_external_scanner_destroy(void *payload) {}
+
+unsigned tree_sitter_dtd_external_scanner_serialize(void *payload, char *buffer) { return 0; }
+
+void tree_sitter_dtd_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {}
@@ -0,0 +1,24 @@
+//! Incremental parses must match fresh parses, whatever the edits.
+//!
+//! The demo sample plus a short document heavy on multibyte text. Set
+//! `ARBORIUM_EDIT_STEPS` for longer runs.
+
+use arborium_test_harness::edits::test_incremental_edits;
+
+#[test]
+fn test_html_edits() {
+    test_incremental_edits(
+        arborium_html::language(),
+        "html",
+        &arborium_html::HIGHLIGHTS_QUERY,
+        arborium_html::INJECTIONS_QUERY,
+        arborium_html::LOCALS_QUERY,
+        &[
+            ("sample.html", include_str!("../sample.html")),
+            (
+                "multibyte",
+                "<p title=\"Größe\">日本語 &amp; 🦀</p>\n<!-- ünïcödé -->\n<script>let λ = \"é\";</script>\n<style>p::after { content: \"→\"; }</style>\n",
+            ),
+        ],
+    );
+}
@@ -1,15 +1,47 @@
-; Parse the contents of tagged template literals using
-; a language inferred from the tag.
+; Parse the contents of tagged template literals using a language inferred
+; from the tag. Only the string fragments are injected, so ${} holes are left
+; out, and the fragments of all templates with the same tag are combined.
 
+; gql`query { ... }`, graphql`...`
+(call_expression
+  function: (identifier) @_tag
+  arguments: (template_string (string_fragment) @injection.content)
+  (#any-of? @_tag "gql" "graphql")
+  (#set! injection.language "graphql")
+  (#set! injection.combined))
+
+; css`...` and the helpers of CSS-in-JS libraries
+(call_expression
+  function: (identifier) @_tag
+  arguments: (template_string (string_fragment) @injection.content)
+  (#any-of? @_tag "css" "keyframes" "injectGlobal" "createGlobalStyle")
+  (#set! injection.language "css")
+  (#set! injection.combined))
+
+; styled.div`...`, styled(Button)`...`, styled.a.attrs({ ... })`...`
 (call_expression
   function: [
-    (identifier) @injection.language
     (member_expression
-      property: (property_identifier) @injection.language)
+      object: (identifier) @_styled)
+    (call_expression
+      function: (identifier) @_styled)
+    (call_expression
+      function: (member_expression
+        object: (member_expression
+          object: (identifier) @_styled)))
   ]
   arguments: (template_string (string_fragment) @injection.content)
-  (#set! injection.combined)
-  (#set! injection.include-children))
+  (#eq? @_styled "styled")
+  (#set! injection.language "css")
+  (#set! injection.combined))
+
+; Any other tag names its language: sql`...`, html`...`
+(call_expression
+  function: (identifier) @injection.language
+  arguments: (template_string (string_fragment) @injection.content)
+  (#not-any-of? @injection.language
+    "gql" "graphql" "css" "keyframes" "injectGlobal" "createGlobalStyle" "hbs")
+  (#set! injection.combined))
 
 
 ; Parse regex syntax within regex literals
@@ -17,11 +49,17 @@
 ((regex_pattern) @injection.content
  (#set! injection.language "regex"))
 
- ; Parse JSDoc annotations in comments
+ ; Parse JSDoc annotations in /** */ comments, and tags like TODO: in the rest
 
 ((comment) @injection.content
+ (#match? @injection.content "^/\\*\\*[^/]")
  (#set! injection.language "jsdoc"))
 
+((comment) @injection.content
+ (#not-match? @injection.content "^/\\*\\*[^/]")
+ (#set! injection.language "comment")
+ (#set! injection.include-children))
+
 ; Parse Ember/Glimmer/Handlebars/HTMLBars/etc. template literals
 ; e.g.: await render(hbs`<SomeComponent />`)
 (call_expression
@@ -0,0 +1,56 @@
+import { gql } from "@apollo/client";
+import { html } from "lit";
+import styled, { css, keyframes } from "styled-components";
+import sql from "./db.js";
+
+export const GET_ORDERS = gql`
+  query GetOrders($customerId: ID!, $first: Int = 20) {
+    customer(id: $customerId) {
+      name
+      orders(first: $first) {
+        edges {
+          node { id total createdAt }
+        }
+      }
+    }
+  }
+`;
+
+export async function recentOrders(customerId, limit) {
+  return sql`
+    SELECT id, total, created_at
+    FROM orders
+    WHERE customer_id = ${customerId}
+    ORDER BY created_at DESC
+    LIMIT ${limit}
+  `;
+}
+
+const pulse = keyframes`
+  from { opacity: 1; }
+  to { opacity: 0.4; }
+`;
+
+const highlighted = css`
+  border-left: 3px solid ${(props) => props.theme.accent};
+`;
+
+export const OrderRow = styled.li`
+  display: flex;
+  justify-content: space-between;
+  padding: 0.5rem 1rem;
+  animation: ${pulse} 1s ease-in-out infinite alternate;
+  ${(props) => props.selected && highlighted}
+`;
+
+export const Total = styled(OrderRow)`
+  font-weight: bold;
+`;
+
+export function orderList(orders) {
+  return html`
+    <ul class="orders">
+      ${orders.map((order) => html`<li data-id=${order.id}>${order.total}</li>`)}
+    </ul>
+  `;
+}
@@ -0,0 +1,30 @@
+// Form validators built on regex literals.
+
+const EMAIL = /^[^\s@]+@[^\s@]+\.[a-z]{2,}$/i;
+const SLUG = /^[a-z0-9]+(?:-[a-z0-9]+)*$/;
+const HEX_COLOR = /^#(?:[0-9a-f]{3}){1,2}$/i;
+const SEMVER =
+  /^(?<major>0|[1-9]\d*)\.(?<minor>0|[1-9]\d*)\.(?<patch>0|[1-9]\d*)(?:-(?<pre>[\w.-]+))?$/;
+const REPEATED_WORD = /\b(\w+)\s+\1\b/gi;
+const PRICE = /(?<=\$)\d+(?:\.\d\d)?(?!\d)/u;
+
+export function validate(field, value) {
+  switch (field) {
+    case "email":
+      return EMAIL.test(value);
+    case "slug":
+      return SLUG.test(value);
+    case "color":
+      return HEX_COLOR.test(value);
+    case "version":
+      return SEMVER.exec(value)?.groups ?? null;
+    default:
+      return !REPEATED_WORD.test(value);
+  }
+}
+
+export function prices(text) {
+  return [...text.matchAll(new RegExp(PRICE, "gu"))].map((m) => Number(m[0]));
+}
+
+export const stripTags = (html) => html.replace(/<\/?[a-z][^>]*>/gi, "");
@@ -0,0 +1,36 @@
+function add(a, b) {
+// <- keyword
+//       ^ function
+  return a + b;
+  // <- keyword
+}
+
+const MAX_SIZE = 10;
+// <- keyword
+//    ^ constant
+const double = (n) => n * 2;
+//    ^ function
+let total = add(MAX_SIZE, double(4));
+// <- keyword
+//          ^ function
+//                               ^ number
+
+class Counter extends Base {
+// <- keyword
+//    ^ constructor
+//            ^ keyword
+  increment() {
+  // <- function.method
+    this.count += 1;
+    // <- variable.builtin
+    //   ^ property
+  }
+}
+
+const label = `total: ${total}`;
+//            ^ string
+const found = /ab+c/.test(label);
+//                   ^ function.method
+// A plain comment
+// ^ comment
+// ^ !keyword
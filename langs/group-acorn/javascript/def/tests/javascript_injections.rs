@@ -0,0 +1,103 @@
+//! Tagged template literals in JavaScript inject the language their tag
+//! names, leaving `${}` holes out.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_javascript::language(),
+        helpers::queries!(arborium_javascript),
+        source,
+    )
+    .into_iter()
+    .filter(|(_, language)| !["jsdoc", "regex", "comment"].contains(&language.as_str()))
+    .collect()
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                arborium_javascript::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "graphql",
+            TestGrammar::new(
+                arborium_graphql::language(),
+                arborium_graphql::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "sql",
+            TestGrammar::new(arborium_sql::language(), arborium_sql::HIGHLIGHTS_QUERY, ""),
+        ),
+        (
+            "css",
+            TestGrammar::new(arborium_css::language(), arborium_css::HIGHLIGHTS_QUERY, ""),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+#[test]
+fn test_tags_name_the_language() {
+    let source = "\
+gql`query { me { id } }`;
+graphql`{ users { name } }`;
+sql`SELECT 1`;
+css`color: red;`;
+keyframes`from { opacity: 0; }`;
+styled.div`margin: 0;`;
+styled(Button)`padding: 0;`;
+styled.a.attrs({ href: \"#\" })`color: blue;`;
+html`<p>hi</p>`;
+";
+    let languages: Vec<String> = injected(source).into_iter().map(|(_, l)| l).collect();
+    assert_eq!(
+        languages,
+        [
+            "graphql", "graphql", "sql", "css", "css", "css", "css", "css", "html"
+        ]
+    );
+}
+
+#[test]
+fn test_holes_are_left_out() {
+    let source = "sql`SELECT * FROM users WHERE id = ${id} AND active`;";
+    let injections = injected(source);
+    let fragments: Vec<&str> = injections.iter().map(|(text, _)| *text).collect();
+    assert_eq!(
+        fragments,
+        ["SELECT * FROM users WHERE id = ", " AND active"]
+    );
+    assert!(injections.iter().all(|(_, language)| language == "sql"));
+}
+
+#[test]
+fn test_template_contents_highlight() {
+    let source = "\
+const q = gql`query Orders { orders { id } }`;
+const rows = sql`SELECT id FROM orders WHERE total > ${min}`;
+const Row = styled.li`display: flex; color: ${(p) => p.color};`;
+";
+    let html = highlighter().highlight("javascript", source).unwrap();
+    assert!(html.contains("<a-k>query</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>SELECT</a-k>"), "{}", html);
+    assert!(html.contains("<a-pr>display</a-pr>"), "{}", html);
+    // The hole is still JavaScript
+    assert!(html.contains("<a-v>min</a-v>"), "{}", html);
+}
+
+#[test]
+fn test_unknown_tags_stay_plain_strings() {
+    let source = "const s = shout`SELECT loudly`;";
+    let html = highlighter().highlight("javascript", source).unwrap();
+    assert!(!html.contains("<a-k>SELECT</a-k>"), "{}", html);
+    assert!(html.contains("<a-s>`SELECT loudly`</a-s>"), "{}", html);
+}
@@ -0,0 +1,134 @@
+/**
+ * @file JSON5 grammar for tree-sitter
+ * @license MIT
+ *
+ * JSON5 extends JSON with comments, trailing commas, unquoted keys,
+ * single-quoted strings, hexadecimal numbers, `Infinity` and `NaN`, and
+ * explicit `+` signs. Node names follow tree-sitter-json, so queries
+ * written for one carry over to the other.
+ * https://spec.json5.org/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'json5',
+
+  extras: $ => [
+    /\s/,
+    $.comment,
+  ],
+
+  supertypes: $ => [
+    $._value,
+  ],
+
+  word: $ => $.identifier,
+
+  rules: {
+    document: $ => repeat($._value),
+
+    _value: $ => choice(
+      $.object,
+      $.array,
+      $.number,
+      $.string,
+      $.true,
+      $.false,
+      $.null,
+    ),
+
+    object: $ => seq(
+      '{', commaSep($.pair), '}',
+    ),
+
+    pair: $ => seq(
+      field('key', choice($.string, $.identifier)),
+      ':',
+      field('value', $._value),
+    ),
+
+    array: $ => seq(
+      '[', commaSep($._value), ']',
+    ),
+
+    string: $ => choice(
+      seq('"', repeat(choice(
+        alias($._double_quoted_content, $.string_content),
+        $.escape_sequence,
+      )), '"'),
+      seq('\'', repeat(choice(
+        alias($._single_quoted_content, $.string_content),
+        $.escape_sequence,
+      )), '\''),
+    ),
+
+    _double_quoted_content: _ => token.immediate(prec(1, /[^\\"\r\n]+/)),
+
+    _single_quoted_content: _ => token.immediate(prec(1, /[^\\'\r\n]+/)),
+
+    // Any character can be escaped; a backslash before a line break
+    // continues the string on the next line
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /x[0-9a-fA-F]{2}/,
+        /u[0-9a-fA-F]{4}/,
+        /\r?\n/,
+        /[^xu\r\n]/,
+      ),
+    )),
+
+    number: _ => {
+      const decimalDigits = /\d+/;
+      const exponentPart = seq(choice('e', 'E'), optional(choice('+', '-')), decimalDigits);
+
+      const decimalLiteral = choice(
+        seq(decimalDigits, optional(seq('.', optional(decimalDigits))), optional(exponentPart)),
+        seq('.', decimalDigits, optional(exponentPart)),
+      );
+
+      // Outranks `identifier`, which `Infinity` and `NaN` also match
+      return token(prec(1, seq(
+        optional(choice('+', '-')),
+        choice(
+          decimalLiteral,
+          /0[xX][0-9a-fA-F]+/,
+          'Infinity',
+          'NaN',
+        ),
+      )));
+    },
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    null: _ => 'null',
+
+    // Reserved words are valid keys too, so this is only ever a key
+    identifier: _ => /[\p{L}\p{Nl}$_][\p{L}\p{Nl}\p{Mn}\p{Mc}\p{Nd}\p{Pc}$\u200C\u200D]*/,
+
+    comment: _ => token(choice(
+      seq('//', /.*/),
+      seq(
+        '/*',
+        /[^*]*\*+([^/*][^*]*\*+)*/,
+        '/',
+      ),
+    )),
+  },
+});
+
+/**
+ * Creates a rule to optionally match one or more of the rules separated by
+ * a comma, with an optional trailing comma
+ *
+ * @param {RuleOrLiteral} rule
+ *
+ * @returns {ChoiceRule}
+ */
+function commaSep(rule) {
+  return optional(seq(rule, repeat(seq(',', rule)), optional(',')));
+}
@@ -0,0 +1,102 @@
+//! JSON5 keeps tree-sitter-json's node names, adding unquoted keys,
+//! single-quoted strings, trailing commas and the extra number forms.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/renovate.json5");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_json5::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_json5::language(), source, kind)
+}
+
+fn highlight(source: &str) -> String {
+    let grammars = [(
+        "json5",
+        TestGrammar::new(
+            arborium_json5::language(),
+            arborium_json5::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    helpers::highlighter(grammars)
+        .highlight("json5", source)
+        .unwrap()
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_keys_strings_and_trailing_commas() {
+    let source = "{\n  name: 'arborium',\n  \"null\": [1, 2,],\n  true: \"it's\",\n}\n";
+    assert_eq!(nodes(source, "identifier"), ["name", "true"]);
+    assert_eq!(
+        nodes(source, "string"),
+        ["'arborium'", "\"null\"", "\"it's\""]
+    );
+    assert_eq!(nodes(source, "pair").len(), 3);
+    assert!(nodes(source, "null").is_empty());
+    assert!(nodes(source, "true").is_empty());
+}
+
+#[test]
+fn test_numbers() {
+    let source = "[0x1F, +1, -.5, 2., 1e-3, Infinity, -Infinity, NaN]";
+    assert_eq!(
+        nodes(source, "number"),
+        [
+            "0x1F",
+            "+1",
+            "-.5",
+            "2.",
+            "1e-3",
+            "Infinity",
+            "-Infinity",
+            "NaN"
+        ]
+    );
+}
+
+#[test]
+fn test_escapes_and_continuations() {
+    let source = "'tab\\there \\x41 \\u00e9 \\\nnext line'";
+    assert_eq!(
+        nodes(source, "escape_sequence"),
+        ["\\t", "\\x41", "\\u00e9", "\\\n"]
+    );
+}
+
+#[test]
+fn test_highlights() {
+    let html = highlight(SAMPLE);
+    assert!(
+        html.contains(
+            "<a-c>// Renovate configuration for a small Rust and TypeScript monorepo.</a-c>"
+        ),
+        "{}",
+        html
+    );
+    assert!(html.contains("<a-pr>timezone</a-pr>"), "{}", html);
+    assert!(
+        html.contains("<a-pr>&#39;assignees&#39;</a-pr>"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("<a-s>&#39;Europe/London&#39;</a-s>"),
+        "{}",
+        html
+    );
+    assert!(html.contains("<a-n>0x5A0</a-n>"), "{}", html);
+    assert!(html.contains("<a-n>Infinity</a-n>"), "{}", html);
+    assert!(html.contains("<a-co>null</a-co>"), "{}", html);
+}
@@ -0,0 +1,98 @@
+import { useCallback, useEffect, useMemo, useReducer, useRef } from "react";
+import type { FormEvent, ReactNode } from "react";
+
+export interface Todo {
+  id: number;
+  title: string;
+  done: boolean;
+}
+
+type Action =
+  | { type: "add"; title: string }
+  | { type: "toggle"; id: number }
+  | { type: "clear" };
+
+function reducer(todos: Todo[], action: Action): Todo[] {
+  switch (action.type) {
+    case "add":
+      return [...todos, { id: Date.now(), title: action.title, done: false }];
+    case "toggle":
+      return todos.map((t) => (t.id === action.id ? { ...t, done: !t.done } : t));
+    case "clear":
+      return todos.filter((t) => !t.done);
+  }
+}
+
+function useLocalStorage<T>(key: string, value: T) {
+  useEffect(() => {
+    localStorage.setItem(key, JSON.stringify(value));
+  }, [key, value]);
+}
+
+interface CardProps {
+  title: string;
+  children?: ReactNode;
+}
+
+const Card = ({ title, children }: CardProps) => (
+  <section className="card" aria-label={title}>
+    <h2>{title}</h2>
+    {children}
+  </section>
+);
+
+export default function TodoList({ initial = [] }: { initial?: Todo[] }) {
+  const [todos, dispatch] = useReducer(reducer, initial);
+  const input = useRef<HTMLInputElement>(null);
+  const remaining = useMemo(() => todos.filter((t) => !t.done).length, [todos]);
+
+  useLocalStorage("todos", todos);
+
+  const submit = useCallback((event: FormEvent<HTMLFormElement>) => {
+    event.preventDefault();
+    const title = input.current?.value.trim();
+    if (title) {
+      dispatch({ type: "add", title });
+      input.current!.value = "";
+    }
+  }, []);
+
+  return (
+    <Card title={`Todos (${remaining} left)`}>
+      <form onSubmit={submit}>
+        <input ref={input} placeholder="What needs doing?" autoFocus />
+        <button type="submit" disabled={remaining > 99}>
+          Add
+        </button>
+      </form>
+      {todos.length === 0 ? (
+        <p className="empty">Nothing to do.</p>
+      ) : (
+        <ul>
+          {todos.map((todo) => (
+            <li key={todo.id} data-done={todo.done}>
+              <label>
+                <input
+                  type="checkbox"
+                  checked={todo.done}
+                  onChange={() => dispatch({ type: "toggle", id: todo.id })}
+                />
+                {todo.done ? <s>{todo.title}</s> : todo.title}
+              </label>
+            </li>
+          ))}
+        </ul>
+      )}
+      <button onClick={() => dispatch({ type: "clear" })}>Clear completed</button>
+      <style jsx>{`
+        .empty {
+          color: #888;
+          font-style: italic;
+        }
+        li[data-done="true"] {
+          opacity: 0.6;
+        }
+      `}</style>
+    </Card>
+  );
+}
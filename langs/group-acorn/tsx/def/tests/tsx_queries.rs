@@ -0,0 +1,75 @@
+//! TSX layers JSX captures on top of the TypeScript and JavaScript
+//! highlights, and injects CSS into styled-jsx blocks.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_tsx::language(),
+        helpers::queries!(arborium_tsx),
+        source,
+    )
+    .into_iter()
+    .filter(|(_, language)| language != "jsdoc" && language != "regex")
+    .collect()
+}
+
+#[test]
+fn test_styled_jsx_and_tagged_templates_inject() {
+    let source = "\
+const Title = styled.h1`font-size: 2em;`;
+
+export const Page = () => (
+  <main>
+    <style jsx>{`main { padding: 1rem; }`}</style>
+    <pre>{`not css`}</pre>
+  </main>
+);
+";
+    let injections = injected(source);
+    assert_eq!(
+        injections,
+        [
+            ("font-size: 2em;", "css".to_string()),
+            ("main { padding: 1rem; }", "css".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_jsx_captures_layer_on_typescript() {
+    let mut highlighter = helpers::highlighter([(
+        "tsx",
+        TestGrammar::new(
+            arborium_tsx::language(),
+            &arborium_tsx::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )]);
+    let source = "\
+interface Props {
+  label: string;
+}
+
+export function Button({ label }: Props) {
+  const [count, setCount] = useState<number>(0);
+  return (
+    <Layout.Row>
+      <button className=\"primary\" onClick={() => setCount(count + 1)}>
+        {label}
+      </button>
+    </Layout.Row>
+  );
+}
+";
+    let html = highlighter.highlight("tsx", source).unwrap();
+    // JavaScript and TypeScript captures still apply
+    assert!(html.contains("<a-k>interface</a-k>"), "{}", html);
+    assert!(html.contains("<a-t>number</a-t>"), "{}", html);
+    assert!(html.contains("<a-k>return</a-k>"), "{}", html);
+    // JSX: intrinsic elements are tags, components constructors
+    assert!(html.contains("<a-tg>button</a-tg>"), "{}", html);
+    assert!(html.contains("<a-cr>Layout</a-cr>"), "{}", html);
+    assert!(html.contains("<a-at>className</a-at>"), "{}", html);
+    assert!(html.contains("<a-at>onClick</a-at>"), "{}", html);
+}
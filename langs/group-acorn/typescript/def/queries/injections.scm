@@ -0,0 +1,63 @@
+; Parse the contents of tagged template literals using a language inferred
+; from the tag. Only the string fragments are injected, so ${} holes are left
+; out, and the fragments of all templates with the same tag are combined.
+
+; gql`query { ... }`, graphql`...`
+(call_expression
+  function: (identifier) @_tag
+  arguments: (template_string (string_fragment) @injection.content)
+  (#any-of? @_tag "gql" "graphql")
+  (#set! injection.language "graphql")
+  (#set! injection.combined))
+
+; css`...` and the helpers of CSS-in-JS libraries
+(call_expression
+  function: (identifier) @_tag
+  arguments: (template_string (string_fragment) @injection.content)
+  (#any-of? @_tag "css" "keyframes" "injectGlobal" "createGlobalStyle")
+  (#set! injection.language "css")
+  (#set! injection.combined))
+
+; styled.div`...`, styled(Button)`...`, styled.a.attrs({ ... })`...`
+(call_expression
+  function: [
+    (member_expression
+      object: (identifier) @_styled)
+    (call_expression
+      function: (identifier) @_styled)
+    (call_expression
+      function: (member_expression
+        object: (member_expression
+          object: (identifier) @_styled)))
+  ]
+  arguments: (template_string (string_fragment) @injection.content)
+  (#eq? @_styled "styled")
+  (#set! injection.language "css")
+  (#set! injection.combined))
+
+; Any other tag names its language: sql`...`, html`...`
+(call_expression
+  function: (identifier) @injection.language
+  arguments: (template_string (string_fragment) @injection.content)
+  (#not-any-of? @injection.language
+    "gql" "graphql" "css" "keyframes" "injectGlobal" "createGlobalStyle" "hbs")
+  (#set! injection.combined))
+
+
+; Parse regex syntax within regex literals
+
+((regex_pattern) @injection.content
+ (#set! injection.language "regex"))
+
+ ; Parse JSDoc annotations in comments
+
+((comment) @injection.content
+ (#set! injection.language "jsdoc"))
+
+; Parse Ember/Glimmer/Handlebars/HTMLBars/etc. template literals
+; e.g.: await render(hbs`<SomeComponent />`)
+(call_expression
+  function: ((identifier) @_name
+             (#eq? @_name "hbs"))
+  arguments: ((template_string) @glimmer
+              (#offset! @glimmer 0 1 0 -1)))
@@ -0,0 +1,78 @@
+import type { Request, Response } from "express";
+import { type Logger, createLogger } from "./logging";
+
+export interface Repository<T extends { id: string }> {
+  find(id: string): Promise<T | undefined>;
+  save(entity: T): Promise<void>;
+}
+
+export type Partialize<T, K extends keyof T> = Omit<T, K> & {
+  [P in K]+?: T[P];
+};
+
+type Unwrap<T> = T extends Promise<infer U> ? U : T;
+
+function isDefined<T>(value: T | null | undefined): value is T {
+  return value !== null && value !== undefined;
+}
+
+enum Status {
+  Active = "active",
+  Suspended = "suspended",
+}
+
+interface User {
+  readonly id: string;
+  name: string;
+  status: Status;
+}
+
+function Injectable(): ClassDecorator {
+  return () => {};
+}
+
+function Route(path: string): MethodDecorator {
+  return (_target, _key, descriptor) => descriptor;
+}
+
+@Injectable()
+export class UserService {
+  private readonly cache = new Map<string, User>();
+  private log: Logger = createLogger("users");
+
+  constructor(private readonly repo: Repository<User>) {}
+
+  @Route("/users/:id")
+  async show(req: Request, res: Response): Promise<void> {
+    const user = await this.load(req.params.id);
+    if (!isDefined(user)) {
+      res.status(404).end();
+      return;
+    }
+    res.json(user satisfies User);
+  }
+
+  protected async load(id: string): Promise<Unwrap<ReturnType<Repository<User>["find"]>>> {
+    const cached = this.cache.get(id);
+    if (cached) return cached;
+
+    const user = await this.repo.find(id);
+    if (user) this.cache.set(id, user);
+    this.log.debug(`loaded ${id}`);
+    return user;
+  }
+}
+
+export abstract class Shape {
+  abstract area(): number;
+
+  describe(this: Shape): string {
+    return `${this.constructor.name} with area ${this.area().toFixed(2)}`;
+  }
+}
+
+declare global {
+  interface Window {
+    services?: Record<string, unknown>;
+  }
+}
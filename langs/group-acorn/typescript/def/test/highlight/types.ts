@@ -0,0 +1,40 @@
+import type { Config } from "./config";
+// <- keyword
+//     ^ keyword
+//            ^ type
+
+interface Box<T> {
+// <- keyword
+//        ^ type
+  readonly value: T;
+  // <- keyword
+  //              ^ type
+}
+
+function first<T>(items: T[]): T | undefined {
+// <- keyword
+//       ^ function
+//                ^ variable.parameter
+  return items[0];
+}
+
+function isString(value: unknown): value is string {
+//                       ^ type.builtin
+//                                       ^ keyword
+//                                          ^ type.builtin
+  return typeof value === "string";
+}
+
+@Component({ selector: "app-root" })
+// <- attribute
+//^ attribute
+class App implements Box<number> {
+//        ^ keyword
+  @Input() value = 0;
+  //^ attribute
+  private config?: Config;
+  // <- keyword
+}
+
+enum Color { Red, Green }
+// <- keyword
@@ -0,0 +1,63 @@
+//! TypeScript layers its own highlights on top of JavaScript's and injects
+//! tagged templates the same way.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_typescript::language(),
+        helpers::queries!(arborium_typescript),
+        source,
+    )
+    .into_iter()
+    .filter(|(_, language)| language != "jsdoc" && language != "regex")
+    .collect()
+}
+
+#[test]
+fn test_tagged_templates_inject_like_javascript() {
+    let source = "\
+const query = gql`query User($id: ID!) { user(id: $id) { name } }`;
+const rows = await sql<User[]>`SELECT * FROM users WHERE id = ${id}`;
+";
+    let injections = injected(source);
+    let languages: Vec<&str> = injections.iter().map(|(_, l)| l.as_str()).collect();
+    assert_eq!(languages, ["graphql", "sql"]);
+    assert!(injections[1].0.starts_with("SELECT"), "{:?}", injections);
+    assert!(!injections[1].0.contains("${"), "{:?}", injections);
+}
+
+#[test]
+fn test_typescript_and_javascript_captures_combine() {
+    let mut highlighter = helpers::highlighter([(
+        "typescript",
+        TestGrammar::new(
+            arborium_typescript::language(),
+            &arborium_typescript::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )]);
+    let source = "\
+import type { User } from \"./user\";
+
+@Injectable()
+export class Users implements Repository<User> {
+  private readonly byId = new Map<string, User>();
+
+  async find(id: string): Promise<User | undefined> {
+    return this.byId.get(id);
+  }
+}
+";
+    let html = highlighter.highlight("typescript", source).unwrap();
+    // From the JavaScript highlights
+    assert!(html.contains("<a-k>async</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>return</a-k>"), "{}", html);
+    assert!(html.contains("<a-v>this</a-v>"), "{}", html);
+    // From the TypeScript ones
+    assert!(html.contains("<a-k>type</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>implements</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>private</a-k>"), "{}", html);
+    assert!(html.contains("<a-t>string</a-t>"), "{}", html);
+    assert!(html.contains("<a-at>@Injectable</a-at>"), "{}", html);
+}
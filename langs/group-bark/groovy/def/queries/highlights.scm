@@ -31,7 +31,7 @@
   "false"
 ] @boolean
 
-(null) @constant
+(null) @constant.builtin
 "this" @variable.builtin
 
 [ 
@@ -52,14 +52,18 @@
   "public"
   "static"
   "synchronized"
-] @type.qualifier
+] @keyword.modifier
 
 (comment) @comment
 (shebang) @comment
 
 (string) @string
-(string (escape_sequence) @operator)
-(string (interpolation ([ "$" ]) @operator))
+(escape_sequence) @string.escape
+
+; GString interpolation: "${name}" and "$name"
+(interpolation "$" @punctuation.special)
+(interpolation ["{" "}"] @punctuation.special)
+(interpolation (identifier) @variable)
 
 ("(") @punctuation.bracket
 (")") @punctuation.bracket
@@ -89,7 +93,8 @@
 
 (ternary_op ([ "?" ":" ]) @operator)
 
-(map (map_item key: (identifier) @variable.parameter))
+(map (map_item key: (identifier) @property))
+(map (map_item key: (string) @property))
 
 (parameter type: (identifier) @type name: (identifier) @variable.parameter)
 (generic_param name: (identifier) @variable.parameter)
@@ -131,9 +136,17 @@
 (function_declaration 
   function: (identifier) @function)
 
-(annotation) @function.macro
-(annotation (identifier) @function.macro)
-"@interface" @function.macro
+(annotation) @attribute
+(annotation (identifier) @attribute)
+"@interface" @attribute
+
+; Closures
+(closure "->" @operator)
+(closure
+  (parameter_list
+    (parameter name: (identifier) @variable.parameter)))
+((identifier) @variable.builtin
+  (#eq? @variable.builtin "it"))
 
 "pipeline" @keyword
 
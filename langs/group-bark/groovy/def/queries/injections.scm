@@ -3,3 +3,28 @@
 
 ((groovy_doc) @injection.content
   (#set! injection.language "comment"))
+
+; commandLine 'sh', '-c', 'echo hi' in Gradle Exec tasks
+(juxt_function_call
+  function: (identifier) @_command
+  args: (argument_list
+    (string
+      (string_content) @_flag)
+    .
+    (string
+      (string_content) @injection.content))
+  (#any-of? @_command "commandLine" "exec")
+  (#eq? @_flag "-c")
+  (#set! injection.language "bash"))
+
+(function_call
+  function: (identifier) @_command
+  args: (argument_list
+    (string
+      (string_content) @_flag)
+    .
+    (string
+      (string_content) @injection.content))
+  (#any-of? @_command "commandLine" "exec")
+  (#eq? @_flag "-c")
+  (#set! injection.language "bash"))
@@ -0,0 +1,25 @@
+//! A Gradle `commandLine 'sh', '-c', ...` script is highlighted as shell.
+
+use arborium_test_harness::helpers;
+
+const GRADLE: &str = r#"tasks.register('gitDescribe', Exec) {
+    commandLine 'sh', '-c', 'git describe --tags > out.txt'
+}
+
+tasks.register('listFiles', Exec) {
+    commandLine 'ls', '-la'
+}
+"#;
+
+#[test]
+fn test_command_line_script_injects_bash() {
+    let result = helpers::plugin_parse(
+        arborium_groovy::language(),
+        helpers::queries!(arborium_groovy),
+        GRADLE,
+    );
+
+    let injected = helpers::injected_as(&result, GRADLE, "bash");
+
+    assert_eq!(injected, ["git describe --tags > out.txt"]);
+}
@@ -0,0 +1,8 @@
+; TODO:, FIXME(name):, @mentions, #123 and URLs inside comments
+
+([
+  (line_comment)
+  (block_comment)
+] @injection.content
+  (#set! injection.language "comment")
+  (#set! injection.include-children))
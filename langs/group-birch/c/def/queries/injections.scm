@@ -0,0 +1,34 @@
+; Doxygen commands in /** and /*! doc comments
+
+((comment) @injection.content
+  (#match? @injection.content "^/\\*[*!][^*/]")
+  (#set! injection.language "doxygen"))
+
+; TODO:, FIXME(name):, @mentions, #123 and URLs inside other comments
+
+((comment) @injection.content
+  (#not-match? @injection.content "^/\\*[*!][^*/]")
+  (#set! injection.language "comment")
+  (#set! injection.include-children))
+
+; Macro bodies, highlighted as code: #define MAX(a, b) ((a) > (b) ? (a) : (b))
+
+(preproc_def
+  value: (preproc_arg) @injection.content
+  (#set! injection.language "c"))
+
+(preproc_function_def
+  value: (preproc_arg) @injection.content
+  (#set! injection.language "c"))
+
+; asm("movl %1, %0" : "=r" (out) : "r" (in))
+
+(gnu_asm_expression
+  assembly_code: [
+    (string_literal
+      (string_content) @injection.content)
+    (concatenated_string
+      (string_literal
+        (string_content) @injection.content))
+  ]
+  (#set! injection.language "x86asm"))
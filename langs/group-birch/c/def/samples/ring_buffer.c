@@ -0,0 +1,66 @@
+/*!
+ * @file ring_buffer.c
+ * @brief Lock-free single-producer ring buffer for interrupt handlers.
+ */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define RING_CAPACITY 64u
+#define RING_MASK (RING_CAPACITY - 1u)
+#define RING_NEXT(i) (((i) + 1u) & RING_MASK)
+#define barrier() __asm__ __volatile__("" ::: "memory")
+
+/* Indices only ever grow, wrapping through RING_MASK */
+struct ring {
+    volatile uint32_t head; /**< Next slot to write */
+    volatile uint32_t tail; /**< Next slot to read */
+    uint8_t items[RING_CAPACITY];
+};
+
+/**
+ * @brief Pushes an item, overwriting the oldest one when full.
+ *
+ * @param[in,out] ring The buffer
+ * @param[in] item Byte to store
+ * @return \c true if an item was overwritten
+ * @sa ring_pop
+ */
+bool ring_push(struct ring *ring, uint8_t item)
+{
+    uint32_t head = ring->head;
+    bool overwrote = RING_NEXT(head) == ring->tail;
+
+    ring->items[head] = item;
+    barrier();
+    ring->head = RING_NEXT(head);
+    if (overwrote) {
+        ring->tail = RING_NEXT(ring->tail);
+    }
+    return overwrote;
+}
+
+/**
+ * @brief Pops the oldest item.
+ *
+ * @param ring The buffer
+ * @param[out] item Where to store the byte
+ * @retval false The buffer was empty
+ */
+bool ring_pop(struct ring *ring, uint8_t *item)
+{
+    if (ring->tail == ring->head) {
+        return false;
+    }
+    *item = ring->items[ring->tail];
+    ring->tail = RING_NEXT(ring->tail);
+    return true;
+}
+
+// TODO(sev): use the CPU's count-leading-zeros instead
+static inline uint32_t ring_swap(uint32_t value)
+{
+    __asm__("bswap %0" : "+r"(value));
+    return value;
+}
@@ -0,0 +1,131 @@
+//! C, C++ and Objective-C inject Doxygen into `/**` and `/*!` doc comments,
+//! the comment grammar into other comments, themselves into macro bodies,
+//! and x86 assembly into `asm` strings.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers;
+use arborium_test_harness::tree_sitter::Language;
+
+const SOURCE: &str = "\
+/**
+ * @brief Swaps the bytes of a word.
+ * @param value The word
+ */
+static unsigned swap(unsigned value)
+{
+    /* not documentation */
+    __asm__(\"bswap %0\" : \"+r\"(value));
+    return value; // TODO: use the builtin
+}
+
+/*! @brief The larger of two values */
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+#define LIMIT 64
+";
+
+fn parse(
+    language: impl Into<Language>,
+    highlights: &str,
+    injections: &str,
+    source: &str,
+) -> Utf8ParseResult {
+    let queries = helpers::Queries {
+        highlights,
+        injections,
+        locals: "",
+    };
+    helpers::plugin_parse(language, queries, source)
+}
+
+/// The text of every injection into `language`.
+fn injected<'a>(result: &Utf8ParseResult, source: &'a str, language: &str) -> Vec<&'a str> {
+    helpers::injected_as(result, source, language)
+        .into_iter()
+        .map(str::trim)
+        .collect()
+}
+
+fn check_injections(name: &str, result: Utf8ParseResult) {
+    let doxygen = injected(&result, SOURCE, "doxygen");
+    assert_eq!(doxygen.len(), 2, "{name}: {doxygen:?}");
+    assert!(doxygen[0].contains("@param value"), "{name}: {doxygen:?}");
+    assert!(doxygen[1].starts_with("/*!"), "{name}: {doxygen:?}");
+
+    let comments = injected(&result, SOURCE, "comment");
+    assert_eq!(
+        comments,
+        ["/* not documentation */", "// TODO: use the builtin"],
+        "{name}"
+    );
+
+    assert_eq!(
+        injected(&result, SOURCE, name),
+        ["((a) > (b) ? (a) : (b))", "64"],
+        "{name}"
+    );
+    assert_eq!(injected(&result, SOURCE, "x86asm"), ["bswap %0"], "{name}");
+}
+
+#[test]
+fn test_c_injections() {
+    check_injections(
+        "c",
+        parse(
+            arborium_c::language(),
+            arborium_c::HIGHLIGHTS_QUERY,
+            arborium_c::INJECTIONS_QUERY,
+            SOURCE,
+        ),
+    );
+}
+
+#[test]
+fn test_cpp_injections() {
+    check_injections(
+        "cpp",
+        parse(
+            arborium_cpp::language(),
+            &arborium_cpp::HIGHLIGHTS_QUERY,
+            arborium_cpp::INJECTIONS_QUERY,
+            SOURCE,
+        ),
+    );
+}
+
+#[test]
+fn test_objc_injections() {
+    check_injections(
+        "objc",
+        parse(
+            arborium_objc::language(),
+            &arborium_objc::HIGHLIGHTS_QUERY,
+            arborium_objc::INJECTIONS_QUERY,
+            SOURCE,
+        ),
+    );
+}
+
+#[test]
+fn test_doxygen_commands() {
+    let source =
+        "/**\n * @param[in,out] ring The buffer, see \\ref ring_pop\n * \\return Nothing\n */";
+    let result = parse(
+        arborium_doxygen::language(),
+        arborium_doxygen::HIGHLIGHTS_QUERY,
+        "",
+        source,
+    );
+    let captured = |capture: &str| -> Vec<&str> {
+        result
+            .spans
+            .iter()
+            .filter(|s| s.capture == capture)
+            .map(|s| &source[s.start as usize..s.end as usize])
+            .collect()
+    };
+
+    assert_eq!(captured("keyword"), ["@param", "\\ref", "\\return"]);
+    assert_eq!(captured("attribute"), ["[in,out]"]);
+    assert_eq!(captured("variable.parameter"), ["ring"]);
+    assert_eq!(captured("function"), ["ring_pop"]);
+}
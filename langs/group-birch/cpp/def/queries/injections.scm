@@ -1,3 +1,38 @@
 (raw_string_literal
   delimiter: (raw_string_delimiter) @injection.language
   (raw_string_content) @injection.content)
+
+; Doxygen commands in /** and /*! doc comments
+
+((comment) @injection.content
+  (#match? @injection.content "^/\\*[*!][^*/]")
+  (#set! injection.language "doxygen"))
+
+; TODO:, FIXME(name):, @mentions, #123 and URLs inside other comments
+
+((comment) @injection.content
+  (#not-match? @injection.content "^/\\*[*!][^*/]")
+  (#set! injection.language "comment")
+  (#set! injection.include-children))
+
+; Macro bodies, highlighted as code: #define MAX(a, b) ((a) > (b) ? (a) : (b))
+
+(preproc_def
+  value: (preproc_arg) @injection.content
+  (#set! injection.language "cpp"))
+
+(preproc_function_def
+  value: (preproc_arg) @injection.content
+  (#set! injection.language "cpp"))
+
+; asm("movl %1, %0" : "=r" (out) : "r" (in))
+
+(gnu_asm_expression
+  assembly_code: [
+    (string_literal
+      (string_content) @injection.content)
+    (concatenated_string
+      (string_literal
+        (string_content) @injection.content))
+  ]
+  (#set! injection.language "x86asm"))
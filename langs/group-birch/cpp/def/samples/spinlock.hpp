@@ -0,0 +1,49 @@
+/**
+ * @file spinlock.hpp
+ * @brief A test-and-set spinlock that backs off with the pause instruction.
+ */
+
+#pragma once
+
+#include <atomic>
+
+#define SPIN_LIKELY(x) __builtin_expect(!!(x), 1)
+#define SPIN_NO_COPY(T) \
+    T(const T&) = delete; \
+    T& operator=(const T&) = delete
+
+namespace sync {
+
+/**
+ * @brief Guards a short critical section without a syscall.
+ *
+ * @tparam Backoff Called between failed attempts, see \ref pause
+ */
+template <typename Backoff>
+class spinlock {
+public:
+    spinlock() = default;
+    SPIN_NO_COPY(spinlock);
+
+    /**
+     * @brief Spins until the lock is taken.
+     * @note Never sleeps, so don't hold it across I/O.
+     */
+    void lock() noexcept {
+        while (!SPIN_LIKELY(!flag_.test_and_set(std::memory_order_acquire))) {
+            Backoff{}();
+        }
+    }
+
+    void unlock() noexcept { flag_.clear(std::memory_order_release); }
+
+private:
+    std::atomic_flag flag_ = ATOMIC_FLAG_INIT;
+};
+
+/// Tells the CPU we're spinning, which frees the core's sibling thread
+struct pause {
+    void operator()() const noexcept { asm volatile("pause" ::: "memory"); }
+};
+
+} // namespace sync
@@ -0,0 +1,49 @@
+/**
+ * @file Doxygen grammar for tree-sitter
+ * @license MIT
+ *
+ * Parses the text of a C, C++ or Objective-C doc comment, `/** … *\/` or
+ * `/*! … *\/`, for its commands, written `@param` or `\param` alike. The
+ * comment delimiters and leading `*`s are skipped.
+ * https://www.doxygen.nl/manual/commands.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'doxygen',
+
+  extras: $ => [/\s/, $._delimiter],
+
+  rules: {
+    document: $ => repeat(choice($.tag, $._word)),
+
+    tag: $ => choice(
+      // @param[in] count Number of items, \tparam T Element type
+      seq(
+        field('name', alias(choice('@param', '\\param', '@tparam', '\\tparam'), $.tag_name)),
+        optional(field('direction', $.direction)),
+        field('parameter', $.identifier),
+      ),
+      // \ref ring_buffer, @sa ring_push
+      seq(
+        field('name', alias(choice('@ref', '\\ref', '@sa', '\\sa', '@see', '\\see'), $.tag_name)),
+        field('reference', $.identifier),
+      ),
+      field('name', $.tag_name),
+    ),
+
+    // @brief, \return, @deprecated, ...
+    tag_name: _ => /[@\\][a-zA-Z][a-zA-Z0-9_]*/,
+
+    // [in], [out], [in,out]
+    direction: _ => token.immediate(/\[\s*(in|out)(\s*,\s*(in|out))?\s*\]/),
+
+    identifier: _ => /[a-zA-Z_][a-zA-Z0-9_]*(::[a-zA-Z_][a-zA-Z0-9_]*)*/,
+
+    _word: _ => /[^\s@\\][^\s]*|[@\\]/,
+
+    _delimiter: _ => token(choice('/**', '/*!', '*/', '*')),
+  },
+});
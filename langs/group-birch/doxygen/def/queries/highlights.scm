@@ -0,0 +1,9 @@
+(tag_name) @keyword
+
+(direction) @attribute
+
+(tag
+  parameter: (identifier) @variable.parameter)
+
+(tag
+  reference: (identifier) @function)
@@ -0,0 +1,49 @@
+; SQL passed to database/sql and sqlx, as a literal string that starts like
+; a statement
+; e.g.: db.QueryRow("SELECT name FROM users WHERE id = $1", id)
+
+(call_expression
+  function: (selector_expression
+    field: (field_identifier) @_method)
+  arguments: (argument_list
+    .
+    [
+      (interpreted_string_literal
+        (interpreted_string_literal_content) @injection.content)
+      (raw_string_literal
+        (raw_string_literal_content) @injection.content)
+    ])
+  (#any-of? @_method
+    "Query" "QueryRow" "Exec" "Prepare"
+    "Queryx" "QueryRowx" "MustExec" "Preparex" "NamedExec" "NamedQuery")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with)\\s")
+  (#set! injection.language "sql"))
+
+; The context variants take the query after the context
+; e.g.: db.QueryContext(ctx, `SELECT ...`, args...)
+
+(call_expression
+  function: (selector_expression
+    field: (field_identifier) @_method)
+  arguments: (argument_list
+    .
+    (_)
+    .
+    [
+      (interpreted_string_literal
+        (interpreted_string_literal_content) @injection.content)
+      (raw_string_literal
+        (raw_string_literal_content) @injection.content)
+    ])
+  (#any-of? @_method
+    "QueryContext" "QueryRowContext" "ExecContext" "PrepareContext"
+    "QueryxContext" "QueryRowxContext" "MustExecContext" "PreparexContext"
+    "NamedExecContext" "NamedQueryContext")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with)\\s")
+  (#set! injection.language "sql"))
+
+; TODO:, FIXME(name):, @mentions, #123 and URLs inside comments
+
+((comment) @injection.content
+  (#set! injection.language "comment")
+  (#set! injection.include-children))
@@ -0,0 +1,53 @@
+package store
+
+import (
+	"context"
+	"database/sql"
+	"time"
+)
+
+type Order struct {
+	ID        int64
+	Email     string
+	Total     float64
+	CreatedAt time.Time
+}
+
+type Orders struct {
+	db *sql.DB
+}
+
+func (s *Orders) Recent(ctx context.Context, since time.Time) ([]Order, error) {
+	rows, err := s.db.QueryContext(ctx, `
+		SELECT o.id, c.email, o.total, o.created_at
+		FROM orders o
+		JOIN customers c ON c.id = o.customer_id
+		WHERE o.created_at >= $1
+		ORDER BY o.created_at DESC
+	`, since)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	var orders []Order
+	for rows.Next() {
+		var o Order
+		if err := rows.Scan(&o.ID, &o.Email, &o.Total, &o.CreatedAt); err != nil {
+			return nil, err
+		}
+		orders = append(orders, o)
+	}
+	return orders, rows.Err()
+}
+
+func (s *Orders) Total(id int64) (float64, error) {
+	var total float64
+	err := s.db.QueryRow("SELECT total FROM orders WHERE id = $1", id).Scan(&total)
+	return total, err
+}
+
+func (s *Orders) Cancel(id int64) error {
+	_, err := s.db.Exec("UPDATE orders SET status = 'cancelled' WHERE id = $1", id)
+	return err
+}
@@ -0,0 +1,48 @@
+/*!
+ * @file ARBRingBuffer.m
+ * @brief Fixed-size buffer of recent log lines, for crash reports.
+ */
+
+#import <Foundation/Foundation.h>
+
+#define ARB_CAPACITY 128
+#define ARB_WRAP(i) ((i) % ARB_CAPACITY)
+#define ARB_FENCE() __asm__ __volatile__("mfence" ::: "memory")
+
+@interface ARBRingBuffer : NSObject
+
+/**
+ * @brief Appends a line, dropping the oldest one when full.
+ * @param line The line to keep, copied
+ * @sa lines
+ */
+- (void)appendLine:(NSString *)line;
+
+/// Every kept line, oldest first
+- (NSArray<NSString *> *)lines;
+
+@end
+
+@implementation ARBRingBuffer {
+    NSString *_lines[ARB_CAPACITY];
+    NSUInteger _next;
+    NSUInteger _count;
+}
+
+- (void)appendLine:(NSString *)line {
+    _lines[_next] = [line copy];
+    ARB_FENCE();
+    _next = ARB_WRAP(_next + 1);
+    _count = MIN(_count + 1, ARB_CAPACITY);
+}
+
+- (NSArray<NSString *> *)lines {
+    NSMutableArray *lines = [NSMutableArray arrayWithCapacity:_count];
+    NSUInteger start = ARB_WRAP(_next + ARB_CAPACITY - _count);
+    for (NSUInteger i = 0; i < _count; i++) {
+        [lines addObject:_lines[ARB_WRAP(start + i)]];
+    }
+    return lines; // FIXME: return an immutable copy
+}
+
+@end
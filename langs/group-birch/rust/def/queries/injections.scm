@@ -7,3 +7,73 @@
   (token_tree) @injection.content)
  (#set! injection.language "rust")
  (#set! injection.include-children))
+
+; Doc comments are markdown. Each `///` or `//!` line is its own node;
+; injection.combined asks for the lines to be parsed as one document, which
+; fenced code blocks need. Until that's honored, each line is parsed alone.
+
+((line_outer_doc_comment
+  doc: (doc_comment) @injection.content)
+ (#set! injection.language "markdown")
+ (#set! injection.combined))
+
+((line_comment
+  inner: (inner_doc_comment_marker)
+  doc: (doc_comment) @injection.content)
+ (#set! injection.language "markdown")
+ (#set! injection.combined))
+
+((block_outer_doc_comment
+  doc: (doc_comment) @injection.content)
+ (#set! injection.language "markdown"))
+
+((block_comment
+  inner: (inner_doc_comment_marker)
+  doc: (doc_comment) @injection.content)
+ (#set! injection.language "markdown"))
+
+; #[doc = "..."] and #![doc = "..."]
+
+((attribute
+  (identifier) @_doc
+  value: (string_literal
+    (string_content) @injection.content))
+ (#eq? @_doc "doc")
+ (#set! injection.language "markdown"))
+
+; Patterns passed to the regex crate
+; e.g.: Regex::new(r"^\d{4}-\d{2}$"), regex::bytes::RegexBuilder::new("[a-z]+")
+; Plain strings with escapes are left alone: the regex would see `\\d`
+; where the reader sees `\d`.
+
+(call_expression
+  function: (scoped_identifier
+    path: [
+      (identifier) @_type
+      (scoped_identifier
+        name: (identifier) @_type)
+    ]
+    name: (identifier) @_new)
+  arguments: (arguments
+    .
+    [
+      (raw_string_literal
+        (string_content) @injection.content)
+      (string_literal
+        .
+        (string_content) @injection.content
+        .)
+    ])
+  (#any-of? @_type "Regex" "RegexBuilder")
+  (#eq? @_new "new")
+  (#set! injection.language "regex"))
+
+; TODO:, FIXME(name):, @mentions, #123 and URLs inside comments
+; Doc comments are markdown instead, so only plain comments get this.
+
+([
+  (line_comment !inner)
+  (block_comment !inner)
+] @injection.content
+  (#set! injection.language "comment")
+  (#set! injection.include-children))
@@ -0,0 +1,87 @@
+; Scopes
+;-------
+
+[
+  (function_item)
+  (closure_expression)
+  (block)
+  (for_expression)
+  (while_expression)
+  (if_expression)
+  (match_arm)
+  (last_match_arm)
+] @local.scope
+
+; Definitions
+;------------
+
+; Parameters
+
+(parameter
+  pattern: (identifier) @local.definition.variable.parameter)
+
+(parameter
+  pattern: (mut_pattern
+    (identifier) @local.definition.variable.parameter))
+
+(parameter
+  pattern: (tuple_pattern
+    (identifier) @local.definition.variable.parameter))
+
+(closure_parameters
+  (identifier) @local.definition.variable.parameter)
+
+(closure_parameters
+  (mut_pattern
+    (identifier) @local.definition.variable.parameter))
+
+; Bindings
+
+(let_declaration
+  pattern: (identifier) @local.definition.var)
+
+(let_declaration
+  pattern: (mut_pattern
+    (identifier) @local.definition.var))
+
+(let_declaration
+  pattern: (tuple_pattern
+    (identifier) @local.definition.var))
+
+(let_condition
+  pattern: (identifier) @local.definition.var)
+
+(for_expression
+  pattern: (identifier) @local.definition.var)
+
+(for_expression
+  pattern: (tuple_pattern
+    (identifier) @local.definition.var))
+
+(match_pattern
+  (identifier) @local.definition.var)
+
+(tuple_struct_pattern
+  type: (_)
+  (identifier) @local.definition.var)
+
+(captured_pattern
+  .
+  (identifier) @local.definition.var)
+
+; Items are defined in the scope around them
+
+((function_item
+  name: (identifier) @local.definition.function)
+ (#set! definition.function.scope "parent"))
+
+(const_item
+  name: (identifier) @local.definition.constant)
+
+(static_item
+  name: (identifier) @local.definition.constant)
+
+; References
+;-----------
+
+(identifier) @local.reference
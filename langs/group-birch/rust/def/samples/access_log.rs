@@ -0,0 +1,38 @@
+//! Parses combined-format access log lines.
+
+use std::sync::LazyLock;
+
+use regex::{Regex, RegexBuilder};
+
+static LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^(?P<ip>\S+) \S+ (?P<user>\S+) \[(?P<time>[^\]]+)\] "(?P<method>[A-Z]+) (?P<path>\S+) HTTP/\d\.\d" (?P<status>\d{3}) (?P<bytes>\d+|-)"#,
+    )
+    .expect("valid access log pattern")
+});
+
+static BOT: LazyLock<Regex> = LazyLock::new(|| {
+    RegexBuilder::new(r"\b(?:bot|crawler|spider)\b")
+        .case_insensitive(true)
+        .build()
+        .expect("valid bot pattern")
+});
+
+pub struct Entry<'a> {
+    pub ip: &'a str,
+    pub path: &'a str,
+    pub status: u16,
+}
+
+pub fn parse(line: &str) -> Option<Entry<'_>> {
+    let caps = LINE.captures(line)?;
+    Some(Entry {
+        ip: caps.name("ip")?.as_str(),
+        path: caps.name("path")?.as_str(),
+        status: caps["status"].parse().ok()?,
+    })
+}
+
+pub fn is_bot(user_agent: &str) -> bool {
+    BOT.is_match(user_agent)
+}
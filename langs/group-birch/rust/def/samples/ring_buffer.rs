@@ -0,0 +1,81 @@
+//! A fixed-capacity ring buffer.
+//!
+//! Pushing onto a full buffer **overwrites** the oldest element:
+//!
+//! ```
+//! let mut ring = RingBuffer::new(2);
+//! ring.push(1);
+//! ring.push(2);
+//! ring.push(3);
+//! assert_eq!(ring.iter().collect::<Vec<_>>(), [&2, &3]);
+//! ```
+
+#![doc = "See [`RingBuffer`] for the details."]
+
+/// A ring buffer holding at most `capacity` elements.
+///
+/// # Panics
+///
+/// [`RingBuffer::new`] panics when `capacity` is zero.
+pub struct RingBuffer<T> {
+    items: Vec<Option<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty buffer.
+    ///
+    /// ```should_panic
+    /// let ring: RingBuffer<u8> = RingBuffer::new(0);
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        let items = (0..capacity).map(|_| None).collect();
+        Self { items, head: 0, len: 0 }
+    }
+
+    /// Pushes `item`, returning the element it overwrote, if any.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let capacity = self.items.len();
+        let slot = (self.head + self.len) % capacity;
+        let evicted = self.items[slot].replace(item);
+        if self.len == capacity {
+            self.head = (self.head + 1) % capacity;
+        } else {
+            self.len += 1;
+        }
+        evicted
+    }
+
+    /** Iterates from the oldest element to the newest. */
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let capacity = self.items.len();
+        (0..self.len).filter_map(move |offset| self.items[(self.head + offset) % capacity].as_ref())
+    }
+
+    #[doc = "Removes every element, keeping the capacity."]
+    pub fn clear(&mut self) {
+        for slot in &mut self.items {
+            *slot = None;
+        }
+        let (head, len) = (0, 0);
+        self.head = head;
+        self.len = len;
+    }
+}
+
+macro_rules! ring {
+    ($capacity:expr; $($item:expr),* $(,)?) => {{
+        let mut ring = RingBuffer::new($capacity);
+        $(ring.push($item);)*
+        ring
+    }};
+}
+
+fn main() {
+    let ring = ring![3; "a", "b", "c", "d"];
+    if let Some(oldest) = ring.iter().next() {
+        println!("oldest: {oldest}");
+    }
+}
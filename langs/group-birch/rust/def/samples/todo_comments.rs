@@ -0,0 +1,47 @@
+use std::collections::VecDeque;
+
+/// A bounded queue that drops the oldest item when full.
+pub struct Bounded<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> Bounded<T> {
+    pub fn new(capacity: usize) -> Self {
+        // TODO(sev): fix #42
+        // A capacity of zero should be an error, not a queue that drops
+        // everything it's given.
+        Self {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let dropped = if self.items.len() == self.capacity {
+            self.items.pop_front()
+        } else {
+            None
+        };
+        self.items.push_back(item);
+        dropped
+    }
+
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        // NOTE: callers rely on the front slice holding the oldest items
+        self.items.as_slices()
+    }
+
+    /* FIXME: iterating while full allocates; see
+       https://github.com/bearcove/arborium/issues/42 */
+    pub fn drain(&mut self) -> Vec<T> {
+        // HACK(@bearcove/runtime): collect first, the borrow checker
+        // can't see that `items` and `capacity` don't alias here
+        self.items.drain(..).collect()
+    }
+
+    pub unsafe fn first_unchecked(&self) -> &T {
+        // SAFETY: the caller guarantees the queue isn't empty
+        unsafe { self.items.get(0).unwrap_unchecked() }
+    }
+}
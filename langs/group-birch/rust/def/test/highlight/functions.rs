@@ -0,0 +1,28 @@
+fn greet(name: &str) -> usize {
+// <- keyword
+// ^ function
+//       ^ variable.parameter
+//              ^ type.builtin
+//                      ^ type.builtin
+    let count = name.len();
+    // <- keyword
+    //  ^ !keyword
+    //               ^ function.method
+    println!("{}", count);
+    // <- function.macro
+    //     ^ function.macro
+    count
+}
+
+pub fn main() {
+// <- keyword
+//  ^ keyword
+//     ^ function
+    let total = greet("arborium") + helpers::offset();
+    //          ^ function
+    //                ^ string
+    //                              ^ !function
+    //                                       ^ function
+    // A plain comment
+    // ^ comment
+}
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+// <- keyword
+
+struct Point {
+// <- keyword
+//     ^ type
+    x: f64,
+    // <- property
+    // ^ type.builtin
+}
+
+enum Shape {
+// <- keyword
+//   ^ type
+    Circle(Point, f64),
+}
+
+impl Point {
+// <- keyword
+//   ^ type
+    const ORIGIN: Point = Point { x: 0.0 };
+    // <- keyword
+    //            ^ type
+
+    fn norm(&self) -> f64 {
+    //       ^ variable.builtin
+        self.x.abs()
+        // <- variable.builtin
+        //   ^ property
+        //     ^ function.method
+    }
+}
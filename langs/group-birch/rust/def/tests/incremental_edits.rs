@@ -0,0 +1,24 @@
+//! Incremental parses must match fresh parses, whatever the edits.
+//!
+//! The demo sample plus a short document heavy on multibyte text. Set
+//! `ARBORIUM_EDIT_STEPS` for longer runs.
+
+use arborium_test_harness::edits::test_incremental_edits;
+
+#[test]
+fn test_rust_edits() {
+    test_incremental_edits(
+        arborium_rust::language(),
+        "rust",
+        &arborium_rust::HIGHLIGHTS_QUERY,
+        arborium_rust::INJECTIONS_QUERY,
+        arborium_rust::LOCALS_QUERY,
+        &[
+            ("sample.rs", include_str!("../sample.rs")),
+            (
+                "multibyte",
+                "/// Größe in 日本語 🦀\nfn größe(λ: &str) -> usize {\n    let s = \"é\\u{1F980}\";\n    λ.len() + s.len() // ünïcödé\n}\n",
+            ),
+        ],
+    );
+}
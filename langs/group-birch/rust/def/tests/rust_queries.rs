@@ -0,0 +1,80 @@
+//! Rust's doc comment and regex injections and locals query.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::assertions::{failed_assertions, parse_assertions};
+use arborium_test_harness::helpers;
+
+fn highlight(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_rust::language(),
+        helpers::queries!(arborium_rust),
+        source,
+    )
+}
+
+fn injected<'a>(source: &'a str, language: &str) -> Vec<&'a str> {
+    helpers::injected_as(&highlight(source), source, language)
+}
+
+#[test]
+fn test_doc_comments_inject_markdown() {
+    let source = "\
+//! Crate docs with `code`.
+/// Outer docs.
+/** Block docs. */
+// Not docs.
+/* Not docs either. */
+fn main() {}
+";
+    let injected = injected(source, "markdown");
+    assert_eq!(injected.len(), 3, "{:?}", injected);
+    assert!(injected[0].contains("Crate docs"));
+    assert!(injected[1].contains("Outer docs."));
+    assert!(injected[2].contains("Block docs."));
+}
+
+#[test]
+fn test_doc_attributes_inject_markdown() {
+    let source =
+        "#![doc = \"Crate *docs*.\"]\n#[doc = \"Item docs.\"]\n#[cfg(test)]\nfn main() {}\n";
+    assert_eq!(
+        injected(source, "markdown"),
+        ["Crate *docs*.", "Item docs."]
+    );
+}
+
+#[test]
+fn test_regex_constructors_inject_regex() {
+    let source = r##"
+fn main() -> Result<(), regex::Error> {
+    let date = Regex::new(r"^\d{4}-\d{2}$")?;
+    let word = regex::bytes::RegexBuilder::new("[a-z]+").build()?;
+    let hash = Regex::new(r#"#[0-9a-f]{6}"#)?;
+    let escaped = Regex::new("\\d+")?;
+    let other = Vec::new("not a regex");
+    Ok(())
+}
+"##;
+    assert_eq!(
+        injected(source, "regex"),
+        [r"^\d{4}-\d{2}$", "[a-z]+", "#[0-9a-f]{6}"]
+    );
+}
+
+#[test]
+#[ignore = "the plugin runtime doesn't resolve locals yet"]
+fn test_parameters_highlight_at_use_sites() {
+    let source = "\
+fn scale(factor: u32, values: &[u32]) -> u32 {
+    let total: u32 = values.iter().map(|v| v * factor).sum();
+//                   ^ variable.parameter
+//                                         ^ variable.parameter
+//                                             ^ variable.parameter
+    total
+//  ^ !variable.parameter
+}
+";
+    let assertions = parse_assertions(source, &["//"]).expect("Invalid assertions");
+    let failures = failed_assertions(source, &highlight(source), &assertions);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,105 @@
+/**
+ * @file Janet grammar for tree-sitter
+ * @license MIT
+ *
+ * Janet source is data: literals, tuples, arrays, structs and tables, and
+ * the reader macros that quote, quasiquote, unquote and splice them. Like
+ * tree-sitter-janet-simple, this grammar leaves special forms and macros
+ * to the highlight queries, which look at the first symbol of a tuple.
+ * https://janet-lang.org/docs/syntax.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const SYMBOL_HEAD = /[A-Za-z!$%&*+\-./<?=>^_\u00C0-\uFFFF]/;
+const SYMBOL_BODY = /[A-Za-z0-9!$%&*+\-./:<?=>@^_\u00C0-\uFFFF]/;
+
+module.exports = grammar({
+  name: 'janet',
+
+  extras: $ => [/\s/, $.comment],
+
+  externals: $ => [
+    $.long_str_lit,
+    $.long_buf_lit,
+    $._error_sentinel,
+  ],
+
+  rules: {
+    source: $ => repeat($._lit),
+
+    _lit: $ => choice(
+      $.bool_lit,
+      $.nil_lit,
+      $.num_lit,
+      $.kwd_lit,
+      $.sym_lit,
+      $.str_lit,
+      $.buf_lit,
+      $.long_str_lit,
+      $.long_buf_lit,
+      $.par_tup_lit,
+      $.sqr_tup_lit,
+      $.struct_lit,
+      $.par_arr_lit,
+      $.sqr_arr_lit,
+      $.tbl_lit,
+      $.quote_lit,
+      $.qq_lit,
+      $.unquote_lit,
+      $.splice_lit,
+      $.short_fn_lit,
+    ),
+
+    comment: _ => /#.*/,
+
+    bool_lit: _ => choice('true', 'false'),
+
+    nil_lit: _ => 'nil',
+
+    // 42, -1.5e3, 0xff, 2r1011, 1_000_000, 1.5&3
+    num_lit: _ => token(prec(1, seq(
+      optional(/[+-]/),
+      choice(
+        /0x[0-9A-Fa-f_]+(\.[0-9A-Fa-f_]*)?/,
+        /[0-9]+r[0-9A-Za-z_]+(\.[0-9A-Za-z_]*)?(&[+-]?[0-9A-Za-z]+)?/,
+        /([0-9][0-9_]*(\.[0-9_]*)?|\.[0-9][0-9_]*)([eE&][+-]?[0-9]+)?/,
+      ),
+    ))),
+
+    // :name, :a/b, :
+    kwd_lit: _ => token(seq(':', repeat(SYMBOL_BODY))),
+
+    sym_lit: _ => token(seq(SYMBOL_HEAD, repeat(SYMBOL_BODY))),
+
+    str_lit: _ => token(seq('"', repeat(choice(/[^"\\]/, /\\(.|\r?\n)/)), '"')),
+
+    buf_lit: _ => token(seq('@"', repeat(choice(/[^"\\]/, /\\(.|\r?\n)/)), '"')),
+
+    // (f x), [a b], {:k v}
+    par_tup_lit: $ => seq('(', repeat($._lit), ')'),
+
+    sqr_tup_lit: $ => seq('[', repeat($._lit), ']'),
+
+    struct_lit: $ => seq('{', repeat($._lit), '}'),
+
+    // @(x y), @[a b], @{:k v}
+    par_arr_lit: $ => seq('@(', repeat($._lit), ')'),
+
+    sqr_arr_lit: $ => seq('@[', repeat($._lit), ']'),
+
+    tbl_lit: $ => seq('@{', repeat($._lit), '}'),
+
+    // 'x ~x ,x ;x |(f $)
+    quote_lit: $ => seq('\'', $._lit),
+
+    qq_lit: $ => seq('~', $._lit),
+
+    unquote_lit: $ => seq(',', $._lit),
+
+    splice_lit: $ => seq(';', $._lit),
+
+    short_fn_lit: $ => seq('|', $._lit),
+  },
+});
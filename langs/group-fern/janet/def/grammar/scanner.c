@@ -0,0 +1,72 @@
+#include "tree_sitter/parser.h"
+
+#include <stdbool.h>
+#include <wctype.h>
+
+// Long strings and buffers open with a run of backticks and close at the
+// next run of the same length, which a regex can't count.
+enum TokenType {
+  LONG_STR_LIT,
+  LONG_BUF_LIT,
+  ERROR_SENTINEL,
+};
+
+void *tree_sitter_janet_external_scanner_create() { return NULL; }
+void tree_sitter_janet_external_scanner_destroy(void *payload) {}
+unsigned tree_sitter_janet_external_scanner_serialize(void *payload, char *buffer) { return 0; }
+void tree_sitter_janet_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {}
+
+static inline void advance(TSLexer *lexer) { lexer->advance(lexer, false); }
+
+static bool scan_long_string(TSLexer *lexer) {
+  unsigned open = 0;
+  while (lexer->lookahead == '`') {
+    advance(lexer);
+    open++;
+  }
+  if (open == 0) {
+    return false;
+  }
+
+  while (!lexer->eof(lexer)) {
+    if (lexer->lookahead != '`') {
+      advance(lexer);
+      continue;
+    }
+    unsigned close = 0;
+    while (lexer->lookahead == '`' && close < open) {
+      advance(lexer);
+      close++;
+    }
+    if (close == open) {
+      lexer->mark_end(lexer);
+      return true;
+    }
+  }
+
+  // Never closed
+  return false;
+}
+
+bool tree_sitter_janet_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+  if (valid_symbols[ERROR_SENTINEL]) {
+    return false;
+  }
+
+  while (iswspace(lexer->lookahead)) {
+    lexer->advance(lexer, true);
+  }
+
+  if (lexer->lookahead == '@' && valid_symbols[LONG_BUF_LIT]) {
+    advance(lexer);
+    lexer->result_symbol = LONG_BUF_LIT;
+    return scan_long_string(lexer);
+  }
+
+  if (lexer->lookahead == '`' && valid_symbols[LONG_STR_LIT]) {
+    lexer->result_symbol = LONG_STR_LIT;
+    return scan_long_string(lexer);
+  }
+
+  return false;
+}
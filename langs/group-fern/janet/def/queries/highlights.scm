@@ -0,0 +1,151 @@
+; Literals
+
+(comment) @comment
+
+[
+  (str_lit)
+  (long_str_lit)
+] @string
+
+[
+  (buf_lit)
+  (long_buf_lit)
+] @string.special
+
+(kwd_lit) @string.special.symbol
+
+(num_lit) @number
+
+(bool_lit) @boolean
+
+(nil_lit) @constant.builtin
+
+; Variables
+
+(sym_lit) @variable
+
+((sym_lit) @variable.builtin
+  (#any-of? @variable.builtin "$" "$0" "$1" "$2" "$3" "$&"))
+
+((sym_lit) @operator
+  (#any-of? @operator
+    "+" "-" "*" "/" "%" "=" "<" ">" "<=" ">=" "not=" "+=" "-=" "*=" "/="
+    "++" "--" "->" "->>" "-?>" "-?>>"))
+
+; Calls
+
+(par_tup_lit
+  .
+  (sym_lit) @function.call)
+
+(par_tup_lit
+  .
+  (sym_lit) @function.builtin
+  (#any-of? @function.builtin
+    "print" "printf" "prin" "pp" "eprint" "eprintf" "string" "string/format"
+    "length" "get" "put" "in" "keys" "values" "pairs" "map" "filter" "reduce"
+    "mapcat" "find" "sort" "sort-by" "reverse" "range" "array/push"
+    "array/concat" "table" "struct" "tuple" "error" "type" "describe"
+    "peg/match" "peg/compile" "slurp" "spit" "os/time" "ev/go" "ev/sleep"))
+
+; Special forms and core macros
+
+(par_tup_lit
+  .
+  (sym_lit) @keyword
+  (#any-of? @keyword
+    "def" "def-" "var" "var-" "set" "quote" "quasiquote" "unquote" "splice"
+    "upscope" "let" "do" "with" "with-dyns" "defer" "edefer" "compwhen"
+    "comptime" "varglobal" "defglobal"))
+
+(par_tup_lit
+  .
+  (sym_lit) @keyword.conditional
+  (#any-of? @keyword.conditional
+    "if" "if-let" "if-not" "when" "when-let" "unless" "cond" "case" "match"
+    "and" "or"))
+
+(par_tup_lit
+  .
+  (sym_lit) @keyword.repeat
+  (#any-of? @keyword.repeat
+    "while" "break" "loop" "seq" "for" "forv" "each" "eachk" "eachp" "eachy"
+    "repeat" "generate"))
+
+(par_tup_lit
+  .
+  (sym_lit) @keyword.exception
+  (#any-of? @keyword.exception "try" "protect" "assert" "errorf"))
+
+(par_tup_lit
+  .
+  (sym_lit) @keyword.function
+  (#any-of? @keyword.function
+    "fn" "defn" "defn-" "defmacro" "defmacro-" "short-fn"))
+
+(par_tup_lit
+  .
+  (sym_lit) @keyword.import
+  (#any-of? @keyword.import "import" "use" "require"))
+
+; Definitions
+
+; (defn name "docstring" [arg ...] body ...)
+(par_tup_lit
+  .
+  (sym_lit) @_defn
+  .
+  (sym_lit) @function
+  (#any-of? @_defn "defn" "defn-" "defmacro" "defmacro-" "fn"))
+
+(par_tup_lit
+  .
+  (sym_lit) @_defn
+  (sqr_tup_lit
+    (sym_lit) @variable.parameter)
+  (#any-of? @_defn "defn" "defn-" "defmacro" "defmacro-" "fn"))
+
+; (def name value)
+(par_tup_lit
+  .
+  (sym_lit) @_def
+  .
+  (sym_lit) @variable
+  (#any-of? @_def "def" "def-" "var" "var-"))
+
+; (import spork/json :as json)
+(par_tup_lit
+  .
+  (sym_lit) @_import
+  .
+  (sym_lit) @module
+  (#any-of? @_import "import" "use"))
+
+; Quoting
+
+[
+  "'"
+  "~"
+] @punctuation.special
+
+[
+  ","
+  ";"
+] @operator
+
+(short_fn_lit
+  "|" @keyword.function)
+
+; Punctuation
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+  "@("
+  "@["
+  "@{"
+] @punctuation.bracket
@@ -0,0 +1,62 @@
+//! Janet long strings close at a backtick run as long as the opening one,
+//! and special forms are recognized by the head of a tuple.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/todo.janet");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_janet::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_janet::language(), source, kind)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_long_strings_match_their_delimiters() {
+    let source = "(def doc ``a `quoted` word``)\n(def raw @`C:\\temp`)\n";
+    assert_eq!(nodes(source, "long_str_lit"), ["``a `quoted` word``"]);
+    assert_eq!(nodes(source, "long_buf_lit"), ["@`C:\\temp`"]);
+}
+
+#[test]
+fn test_quasiquote_and_splices() {
+    let source = "~(,f ,;args @[1 2] @{:k v})\n";
+    assert_eq!(nodes(source, "qq_lit").len(), 1);
+    assert_eq!(nodes(source, "unquote_lit"), [",f", ",;args"]);
+    assert_eq!(nodes(source, "splice_lit"), [";args"]);
+    assert_eq!(nodes(source, "sqr_arr_lit"), ["@[1 2]"]);
+    assert_eq!(nodes(source, "tbl_lit"), ["@{:k v}"]);
+}
+
+#[test]
+fn test_sample_captures() {
+    let grammars = [(
+        "janet",
+        TestGrammar::new(
+            arborium_janet::language(),
+            arborium_janet::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    let html = helpers::highlighter(grammars)
+        .highlight("janet", SAMPLE)
+        .unwrap();
+    assert!(html.contains("<a-k>defn</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>load-tasks</a-f>"), "{}", html);
+    assert!(html.contains("<a-k>if-let</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>eachp</a-k>"), "{}", html);
+    assert!(html.contains("<a-s>:text</a-s>"), "{}", html);
+    assert!(html.contains("<a-s>@&quot;&quot;</a-s>"), "{}", html);
+    assert!(html.contains("<a-ns>spork/json</a-ns>"), "{}", html);
+    assert!(html.contains("<a-co>false</a-co>"), "{}", html);
+}
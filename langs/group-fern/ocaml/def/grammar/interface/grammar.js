@@ -0,0 +1,18 @@
+/**
+ * @file OCaml interface grammar for tree-sitter
+ * @license MIT
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar(require('../grammar'), {
+  name: 'ocaml_interface',
+
+  rules: {
+    compilation_unit: $ => seq(
+      optional($.shebang),
+      optional($._signature),
+    ),
+  },
+});
@@ -65,7 +65,9 @@
 
 [(number) (signed_number)] @number
 
-[(string) (character)] @string
+(string) @string
+
+(character) @character
 
 (quoted_string "{" @string "}" @string) @string
 
@@ -140,9 +142,12 @@
 ; Attributes
 ;-----------
 
-(attribute_id) @tag
+(attribute_id) @attribute
 
 ; Comments
 ;---------
 
 [(comment) (line_number_directive) (directive) (shebang)] @comment
+
+((comment) @comment.documentation
+ (#match? @comment.documentation "^\\(\\*\\*[^*)]"))
@@ -0,0 +1,13 @@
+; Quoted extensions whose extension name is a language, as used by ppxs
+; embedding other languages: {%sql| SELECT 1 |}
+
+([
+  (quoted_extension
+    (attribute_id) @injection.language
+    (quoted_string_content) @injection.content)
+  (quoted_item_extension
+    (attribute_id) @injection.language
+    (quoted_string_content) @injection.content)
+ ]
+ (#any-of? @injection.language
+   "sql" "graphql" "html" "css" "json" "javascript" "xml" "yaml"))
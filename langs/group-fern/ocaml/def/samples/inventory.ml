@@ -0,0 +1,49 @@
+(** An in-memory inventory with a SQL export, showing modules, variants,
+    records, attributes and quoted extensions. *)
+
+module Item = struct
+  type category = Tool | Part of string | Bundle of t list
+  and t = { name : string; category : category; mutable stock : int }
+
+  let make ?(stock = 0) ~name category = { name; category; stock }
+
+  let rec weight = function
+    | { category = Bundle items; _ } -> List.fold_left (fun acc i -> acc + weight i) 0 items
+    | { category = Tool | Part _; _ } -> 1
+  [@@ocaml.warning "-4"]
+end
+
+module Inventory : sig
+  type t
+  val empty : t
+  val add : Item.t -> t -> t
+  val find : string -> t -> Item.t option
+end = struct
+  module Names = Map.Make (String)
+
+  type t = Item.t Names.t
+
+  let empty = Names.empty
+  let add (item : Item.t) inv = Names.add item.name item inv
+  let find name inv = Names.find_opt name inv
+end
+
+let separator = ','
+let escaped = "tab:\t quote:\" newline:\n"
+
+let restock inv name amount =
+  match Inventory.find name inv with
+  | Some item when amount > 0 ->
+      item.stock <- item.stock + amount;
+      Ok item
+  | Some _ -> Error (`Invalid_amount amount)
+  | None -> failwith (Printf.sprintf "unknown item %S" name)
+
+let export_query = {%sql| SELECT name, stock FROM items WHERE stock > 0 |}
+
+let () =
+  let hammer = Item.make ~name:"hammer" ~stock:3 Item.Tool in
+  let inv = Inventory.(empty |> add hammer) in
+  match restock inv "hammer" 2 with
+  | Ok item -> Printf.printf "%s%c%d\n" item.name separator item.stock
+  | Error (`Invalid_amount n) -> prerr_endline (string_of_int n)
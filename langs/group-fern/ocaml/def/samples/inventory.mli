@@ -0,0 +1,33 @@
+(** Interface of an in-memory inventory, matching [inventory.ml]. *)
+
+module Item : sig
+  type category = Tool | Part of string | Bundle of t list
+  and t = { name : string; category : category; mutable stock : int }
+
+  val make : ?stock:int -> name:string -> category -> t
+  (** [make ~name category] is an item with no stock unless [stock] is
+      given. *)
+
+  val weight : t -> int [@@ocaml.warning "-4"]
+end
+
+module type INVENTORY = sig
+  type t
+
+  val empty : t
+  val add : Item.t -> t -> t
+  val find : string -> t -> Item.t option
+end
+
+module Inventory : INVENTORY
+
+exception Unknown_item of string
+
+external unsafe_stock : Item.t -> int = "%field2"
+
+val separator : char
+
+val restock :
+  Inventory.t -> string -> int -> (Item.t, [> `Invalid_amount of int ]) result
+
+val export_query : string
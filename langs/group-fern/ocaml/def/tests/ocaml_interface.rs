@@ -0,0 +1,54 @@
+//! `.mli` files only parse with the interface grammar.
+//!
+//! The implementation grammar reads a signature as a structure, so an
+//! interface highlighted with it comes out full of errors.
+
+use std::path::Path;
+
+use arborium_test_harness::helpers;
+use arborium_test_harness::tree_sitter::{Language, Tree};
+
+const INTERFACE: &str = "\
+type t
+val empty : t
+val add : string -> t -> t
+module type S = sig
+  val size : t -> int
+end
+";
+
+fn parse(language: impl Into<Language>) -> Tree {
+    helpers::parse_tree(language, INTERFACE)
+}
+
+#[test]
+fn test_interface_grammar_parses_signatures() {
+    let tree = parse(arborium_ocaml::interface_language());
+    let root = tree.root_node();
+    assert_eq!(root.kind(), "compilation_unit");
+    assert!(!root.has_error(), "{}", root.to_sexp());
+    assert!(root.to_sexp().contains("value_specification"));
+}
+
+#[test]
+fn test_implementation_grammar_rejects_signatures() {
+    let tree = parse(arborium_ocaml::language());
+    assert!(
+        tree.root_node().has_error(),
+        "{}",
+        tree.root_node().to_sexp()
+    );
+}
+
+#[test]
+fn test_interface_corpus() {
+    // Next to the crate's corpus rather than in it, which is run with the
+    // implementation grammar
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/interface/signatures.txt");
+    arborium_test_harness::run_corpus_file(
+        arborium_ocaml::interface_language(),
+        "ocaml-interface",
+        &path,
+    )
+    .unwrap_or_else(|e| panic!("{e}"));
+}
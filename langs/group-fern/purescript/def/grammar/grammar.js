@@ -0,0 +1,713 @@
+/**
+ * @file PureScript grammar for tree-sitter
+ * @license MIT
+ *
+ * PureScript modules: imports, signatures and equations with guards, data
+ * types, newtypes, type synonyms, rows and records, type classes and
+ * instances, foreign imports, and `do`/`ado` blocks. Layout (indentation
+ * sensitive blocks after `where`, `let`, `do`, `ado` and `of`) is handled by
+ * the external scanner. Node names follow tree-sitter-haskell where the two
+ * languages overlap.
+ * https://github.com/purescript/documentation/tree/master/language
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  typed: 1,
+  infix: 2,
+  negate: 3,
+  apply: 4,
+  access: 5,
+};
+
+const OPERATOR = /[!#$%&*+/<=>?@\\^|~:-]+/;
+
+module.exports = grammar({
+  name: 'purescript',
+
+  externals: $ => [
+    $._layout_start,
+    $._expression_layout_start,
+    $._layout_semicolon,
+    $._layout_end,
+  ],
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $._lower,
+
+  conflicts: $ => [
+    // `class Eq a <= Ord a` and `instance Show a => Show (Box a)`: the head
+    // and the superclass constraint look alike until `<=` or `=>`.
+    [$.class_head, $.constraint],
+    [$.instance_head, $.constraint],
+    // `f { a: 1 }` applies `f` to a record, `r { a = 1 }` updates `r`.
+    [$._fexpression, $.record_update],
+  ],
+
+  rules: {
+    purescript: $ => seq(
+      $.header,
+      $._layout_start,
+      optional(sepBy1($._layout_semicolon, $._top_declaration)),
+      $._layout_end,
+    ),
+
+    // module Data.Inventory (Item(..), addItem) where
+    header: $ => seq(
+      'module',
+      field('module', $.module),
+      optional(field('exports', $.exports)),
+      'where',
+    ),
+
+    module: _ => /[A-Z][\w']*(\.[A-Z][\w']*)*/,
+
+    exports: $ => seq('(', optional(commaSep1($._export)), ')'),
+
+    _export: $ => choice(
+      $._import_item,
+      seq('module', $.module),
+    ),
+
+    // import Data.Maybe (Maybe(..), fromMaybe) as M
+    import: $ => seq(
+      'import',
+      field('module', $.module),
+      optional(seq(optional('hiding'), field('names', $.import_list))),
+      optional(seq('as', field('alias', $.module))),
+    ),
+
+    import_list: $ => seq('(', optional(commaSep1($._import_item)), ')'),
+
+    _import_item: $ => choice(
+      $.variable,
+      seq($.name, optional(choice(
+        alias('(..)', $.all_names),
+        seq('(', optional(commaSep1($.constructor)), ')'),
+      ))),
+      seq('class', $.name),
+      seq('type', $._operator_name),
+      $._operator_name,
+    ),
+
+    _operator_name: $ => seq('(', $.operator, ')'),
+
+    // Declarations
+
+    _top_declaration: $ => choice(
+      $.import,
+      $._declaration,
+      $.data_type,
+      $.newtype,
+      $.type_synonym,
+      $.kind_signature,
+      $.class_declaration,
+      $.instance_declaration,
+      $.deriving_instance,
+      $.foreign_import,
+      $.fixity,
+    ),
+
+    _declaration: $ => choice(
+      $.signature,
+      $.function,
+      $.bind,
+    ),
+
+    signature: $ => seq(
+      field('name', $.variable),
+      '::',
+      field('type', $._type),
+    ),
+
+    // add x y = x + y
+    function: $ => seq(
+      field('name', $.variable),
+      field('patterns', $.patterns),
+      $._right_hand_side,
+    ),
+
+    // total = 0
+    bind: $ => seq(
+      field('name', $.variable),
+      $._right_hand_side,
+    ),
+
+    _right_hand_side: $ => seq(
+      choice(
+        seq('=', field('match', $.match)),
+        repeat1($.guarded_match),
+      ),
+      optional(field('binds', $.local_binds)),
+    ),
+
+    match: $ => $._expression,
+
+    // | n < 0 = "negative"
+    guarded_match: $ => seq(
+      '|',
+      field('guards', $.guards),
+      '=',
+      field('match', $.match),
+    ),
+
+    guards: $ => commaSep1($._guard),
+
+    _guard: $ => choice(
+      $._expression,
+      alias($._bind_statement, $.pattern_guard),
+    ),
+
+    local_binds: $ => seq(
+      'where',
+      layout($, sepBy1($._layout_semicolon, $._declaration)),
+    ),
+
+    // data Item = Sword Int | Potion { heal :: Int } | Empty
+    data_type: $ => seq(
+      'data',
+      field('name', $.name),
+      repeat($.type_variable),
+      optional(seq(
+        '=',
+        sepBy1('|', $.data_constructor),
+      )),
+    ),
+
+    data_constructor: $ => seq(
+      field('constructor', $.constructor),
+      repeat($._atype),
+    ),
+
+    newtype: $ => seq(
+      'newtype',
+      field('name', $.name),
+      repeat($.type_variable),
+      '=',
+      field('constructor', $.constructor),
+      $._atype,
+    ),
+
+    type_synonym: $ => seq(
+      'type',
+      field('name', $.name),
+      repeat($.type_variable),
+      '=',
+      field('type', $._type),
+    ),
+
+    // data Proxy :: forall k. k -> Type
+    kind_signature: $ => seq(
+      choice('data', 'newtype', 'type', 'class'),
+      field('name', $.name),
+      '::',
+      field('kind', $._type),
+    ),
+
+    // class (Eq a) <= Ord a where
+    class_declaration: $ => seq(
+      'class',
+      optional(seq(field('context', $._superclasses), '<=')),
+      $.class_head,
+      optional(seq(
+        'where',
+        layout($, sepBy1($._layout_semicolon, $.signature)),
+      )),
+    ),
+
+    _superclasses: $ => choice(
+      $.constraint,
+      alias(seq('(', commaSep1($.constraint), ')'), $.context),
+    ),
+
+    class_head: $ => seq(
+      field('name', $.name),
+      repeat($.type_variable),
+      optional(seq('|', commaSep1($.functional_dependency))),
+    ),
+
+    // | a -> b
+    functional_dependency: $ => seq(
+      repeat($.type_variable),
+      '->',
+      repeat($.type_variable),
+    ),
+
+    constraint: $ => seq(
+      field('class', $._qualified_name),
+      repeat($._atype),
+    ),
+
+    // instance showItem :: Show Item where
+    instance_declaration: $ => seq(
+      'instance',
+      $._instance_signature,
+      optional(seq(
+        'where',
+        layout($, sepBy1($._layout_semicolon, $._declaration)),
+      )),
+    ),
+
+    // derive instance eqItem :: Eq Item, derive newtype instance ...
+    deriving_instance: $ => seq(
+      'derive',
+      optional('newtype'),
+      'instance',
+      $._instance_signature,
+    ),
+
+    _instance_signature: $ => seq(
+      optional(seq(field('name', $.variable), '::')),
+      optional(seq(field('context', $._superclasses), '=>')),
+      $.instance_head,
+    ),
+
+    instance_head: $ => seq(
+      field('class', $._qualified_name),
+      repeat($._atype),
+    ),
+
+    // foreign import now :: Effect Instant
+    foreign_import: $ => seq(
+      'foreign',
+      'import',
+      choice(
+        seq(field('name', $.variable), '::', field('type', $._type)),
+        seq('data', field('name', $.name), '::', field('kind', $._type)),
+      ),
+    ),
+
+    // infixl 6 add as +
+    fixity: $ => seq(
+      choice('infix', 'infixl', 'infixr'),
+      field('precedence', $.integer),
+      field('name', choice(
+        $._qualified_variable,
+        $._qualified_constructor,
+        seq('type', $._qualified_name),
+      )),
+      'as',
+      field('operator', $.operator),
+    ),
+
+    // Types
+
+    _type: $ => choice(
+      $._btype,
+      $.function_type,
+      $.forall,
+      $.constrained_type,
+    ),
+
+    function_type: $ => prec.right(seq(
+      field('parameter', $._btype),
+      '->',
+      field('result', $._type),
+    )),
+
+    forall: $ => prec.right(seq(
+      'forall',
+      repeat1($.type_variable),
+      '.',
+      $._type,
+    )),
+
+    // Show a => a -> String, (Eq a, Show a) => ...
+    constrained_type: $ => prec.right(seq(
+      field('context', choice($._btype, $.context)),
+      '=>',
+      $._type,
+    )),
+
+    context: $ => seq('(', $._btype, ',', commaSep1($._btype), ')'),
+
+    _btype: $ => choice($._atype, $.type_apply),
+
+    type_apply: $ => prec.left(seq($._btype, $._atype)),
+
+    _atype: $ => choice(
+      $._qualified_name,
+      $.type_variable,
+      $.record_type,
+      $.row_type,
+      $.wildcard,
+      $.string,
+      $.integer,
+      seq('(', $._type, ')'),
+    ),
+
+    // { name :: String, count :: Int | r }
+    record_type: $ => seq(
+      '{',
+      optional($._row),
+      '}',
+    ),
+
+    // ( name :: String | r )
+    row_type: $ => seq(
+      '(',
+      $._row,
+      ')',
+    ),
+
+    _row: $ => choice(
+      seq(commaSep1($.row_field), optional(seq('|', $._type))),
+      seq('|', $._type),
+    ),
+
+    row_field: $ => seq(
+      field('name', $._label),
+      '::',
+      field('type', $._type),
+    ),
+
+    type_variable: $ => $._lower,
+
+    // Patterns
+
+    patterns: $ => repeat1($._apattern),
+
+    _pattern: $ => choice(
+      $._apattern,
+      alias($.constructor_pattern, $.apply),
+    ),
+
+    constructor_pattern: $ => seq(
+      $._qualified_constructor,
+      repeat1($._apattern),
+    ),
+
+    _apattern: $ => choice(
+      $.variable,
+      $.wildcard,
+      $._qualified_constructor,
+      $._literal,
+      $.as_pattern,
+      alias($.array_pattern, $.array),
+      alias($.record_pattern, $.record),
+      seq('(', $._pattern, ')'),
+    ),
+
+    // all@(first : rest)
+    as_pattern: $ => seq(
+      field('name', $.variable),
+      token.immediate('@'),
+      field('pattern', $._apattern),
+    ),
+
+    array_pattern: $ => seq('[', optional(commaSep1($._pattern)), ']'),
+
+    // { name, count: n }
+    record_pattern: $ => seq(
+      '{',
+      optional(commaSep1(choice(
+        alias($._label, $.field_pun),
+        seq(field('name', $._label), ':', field('pattern', $._pattern)),
+      ))),
+      '}',
+    ),
+
+    // Expressions
+
+    _expression: $ => choice(
+      $._infix_operand,
+      $.infix,
+      $.typed_expression,
+    ),
+
+    typed_expression: $ => prec.right(PREC.typed, seq(
+      field('expression', $._expression),
+      '::',
+      field('type', $._type),
+    )),
+
+    // xs <> ys, a `div` b
+    infix: $ => prec.left(PREC.infix, seq(
+      field('left_operand', $._expression),
+      field('operator', choice($.operator, $.infix_id)),
+      field('right_operand', $._infix_operand),
+    )),
+
+    infix_id: $ => seq(
+      '`',
+      choice($._qualified_variable, $._qualified_constructor),
+      '`',
+    ),
+
+    _infix_operand: $ => choice(
+      $._fexpression,
+      $.negation,
+      $.lambda,
+      $.let_in,
+      $.conditional,
+      $.case,
+      $.do,
+    ),
+
+    negation: $ => prec(PREC.negate, seq('-', $._fexpression)),
+
+    _fexpression: $ => choice($._aexpression, $.apply),
+
+    // `launchAff_ do ...` and `traverse_ xs \x -> ...` take a block last.
+    apply: $ => prec.left(PREC.apply, seq(
+      field('function', $._fexpression),
+      field('argument', choice($._aexpression, $.do, $.lambda, $.case)),
+    )),
+
+    _aexpression: $ => choice(
+      $._qualified_variable,
+      $._qualified_constructor,
+      $._literal,
+      $.wildcard,
+      $.hole,
+      $.array,
+      $.record,
+      $.record_access,
+      $.record_update,
+      $.parens,
+      $._operator_name,
+    ),
+
+    parens: $ => seq('(', $._expression, ')'),
+
+    array: $ => seq('[', optional(commaSep1($._expression)), ']'),
+
+    // { name: "Potion", heal }
+    record: $ => seq(
+      '{',
+      optional(commaSep1(choice(
+        alias($._label, $.field_pun),
+        $.record_field,
+      ))),
+      '}',
+    ),
+
+    record_field: $ => seq(
+      field('name', $._label),
+      ':',
+      field('value', $._expression),
+    ),
+
+    // item.stats.heal
+    record_access: $ => prec.left(PREC.access, seq(
+      field('record', $._aexpression),
+      token.immediate('.'),
+      field('field', alias(token.immediate(/[a-z_][\w']*/), $.variable)),
+    )),
+
+    // item { count = item.count + 1 }
+    record_update: $ => prec.dynamic(1, seq(
+      field('record', $._aexpression),
+      '{',
+      commaSep1($.field_update),
+      '}',
+    )),
+
+    field_update: $ => seq(
+      field('name', $._label),
+      '=',
+      field('value', $._expression),
+    ),
+
+    // \x y -> x + y
+    lambda: $ => prec.right(seq(
+      '\\',
+      field('patterns', $.patterns),
+      '->',
+      field('expression', $._expression),
+    )),
+
+    let_in: $ => prec.right(seq(
+      'let',
+      field('binds', $._local_declarations),
+      'in',
+      field('expression', $._expression),
+    )),
+
+    _local_declarations: $ => alias(
+      expressionLayout($, sepBy1($._layout_semicolon, $._declaration)),
+      $.local_binds,
+    ),
+
+    conditional: $ => prec.right(seq(
+      'if',
+      field('if', $._expression),
+      'then',
+      field('then', $._expression),
+      'else',
+      field('else', $._expression),
+    )),
+
+    // case item of Sword n -> n
+    case: $ => seq(
+      'case',
+      commaSep1($._expression),
+      'of',
+      expressionLayout($, sepBy1($._layout_semicolon, $.alternative)),
+    ),
+
+    alternative: $ => seq(
+      commaSep1(field('pattern', $._pattern)),
+      choice(
+        seq('->', field('match', $.match)),
+        repeat1(alias($._guarded_alternative, $.guarded_match)),
+      ),
+    ),
+
+    _guarded_alternative: $ => seq(
+      '|',
+      field('guards', $.guards),
+      '->',
+      field('match', $.match),
+    ),
+
+    // do
+    //   item <- fetch
+    //   let count = 1
+    //   pure item
+    do: $ => choice(
+      seq('do', expressionLayout($, sepBy1($._layout_semicolon, $._statement))),
+      prec.right(seq(
+        'ado',
+        expressionLayout($, sepBy1($._layout_semicolon, $._statement)),
+        'in',
+        field('result', $._expression),
+      )),
+    ),
+
+    _statement: $ => choice(
+      alias($._bind_statement, $.bind_statement),
+      alias(seq('let', $._local_declarations), $.let),
+      alias($._expression, $.exp),
+    ),
+
+    _bind_statement: $ => seq(
+      field('pattern', $._expression),
+      '<-',
+      field('expression', $._expression),
+    ),
+
+    // Names and literals
+
+    _qualified_variable: $ => choice(
+      $.variable,
+      alias($.qualified_variable, $.qualified),
+    ),
+
+    qualified_variable: $ => seq(
+      field('module', alias($._qualifier, $.module)),
+      field('id', alias(token.immediate(/[a-z_][\w']*/), $.variable)),
+    ),
+
+    _qualified_constructor: $ => choice(
+      $.constructor,
+      alias($.qualified_constructor, $.qualified),
+    ),
+
+    qualified_constructor: $ => seq(
+      field('module', alias($._qualifier, $.module)),
+      field('id', alias(token.immediate(/[A-Z][\w']*/), $.constructor)),
+    ),
+
+    _qualified_name: $ => choice(
+      $.name,
+      alias($.qualified_name, $.qualified),
+    ),
+
+    qualified_name: $ => seq(
+      field('module', alias($._qualifier, $.module)),
+      field('id', alias(token.immediate(/[A-Z][\w']*/), $.name)),
+    ),
+
+    // `Map.` in `Map.lookup`
+    _qualifier: _ => /([A-Z][\w']*\.)+/,
+
+    variable: $ => $._lower,
+
+    constructor: $ => $._upper,
+
+    name: $ => $._upper,
+
+    _label: $ => choice(alias($._lower, $.variable), alias($.string, $.variable)),
+
+    _lower: _ => /[a-z][\w']*|_[\w']+/,
+
+    _upper: _ => /[A-Z][\w']*/,
+
+    operator: _ => OPERATOR,
+
+    wildcard: _ => '_',
+
+    // ?todo
+    hole: _ => /\?[a-z_][\w']*/,
+
+    _literal: $ => choice(
+      $.integer,
+      $.float,
+      $.char,
+      $.string,
+    ),
+
+    integer: _ => token(choice(/\d[\d_]*/, /0x[0-9a-fA-F]+/)),
+
+    float: _ => token(/\d[\d_]*\.\d[\d_]*([eE][+-]?\d+)?|\d[\d_]*[eE][+-]?\d+/),
+
+    char: _ => token(seq('\'', choice(/[^'\\]/, /\\[^']+/, /\\'/), '\'')),
+
+    string: _ => token(choice(
+      seq('"""', repeat(choice(/[^"]/, /"[^"]/, /""[^"]/)), '"""'),
+      seq('"', repeat(choice(/[^"\\\n]/, /\\(.|\n)/)), '"'),
+    )),
+
+    comment: _ => token(choice(
+      seq('--', /[^\n]*/),
+      seq('{-', /[^-]*-+([^}-][^-]*-+)*/, '}'),
+    )),
+  },
+});
+
+/**
+ * A block opened by `where`: its items are separated and closed by layout.
+ *
+ * @param {GrammarSymbols<string>} $
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function layout($, rule) {
+  return seq($._layout_start, optional(rule), $._layout_end);
+}
+
+/**
+ * A block opened by `let`, `do`, `ado` or `of`, which a `where` also closes.
+ *
+ * @param {GrammarSymbols<string>} $
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function expressionLayout($, rule) {
+  return seq($._expression_layout_start, optional(rule), $._layout_end);
+}
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return sepBy1(',', rule);
+}
+
+/**
+ * One or more `rule`s separated by `separator`.
+ *
+ * @param {RuleOrLiteral} separator
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function sepBy1(separator, rule) {
+  return seq(rule, repeat(seq(separator, rule)));
+}
@@ -0,0 +1,192 @@
+#include "tree_sitter/array.h"
+#include "tree_sitter/parser.h"
+
+#include <stdint.h>
+#include <string.h>
+
+// The layout rule for PureScript. `where`, `let`, `do`, `ado` and `of` open a
+// block whose column is that of the next token; a later line starting at that
+// column separates items, and a line starting left of it (or a token that
+// can't continue the block, such as `in` or a closing bracket) ends it.
+
+enum TokenType {
+    LAYOUT_START,
+    EXPRESSION_LAYOUT_START,
+    LAYOUT_SEMICOLON,
+    LAYOUT_END,
+};
+
+// Blocks opened by `let`, `do`, `ado` and `of` are also closed by a `where`
+// belonging to the enclosing declaration.
+#define EXPRESSION_BLOCK 0x8000
+#define COLUMN_MASK 0x7FFF
+
+typedef struct {
+    Array(uint16_t) blocks;
+} Scanner;
+
+static inline void skip(TSLexer *lexer) { lexer->advance(lexer, true); }
+
+static inline bool is_identifier_char(int32_t c) {
+    return (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || (c >= '0' && c <= '9') || c == '_' ||
+           c == '\'';
+}
+
+// Consumes `word` and reports whether it stands alone rather than starting a
+// longer identifier.
+static bool scan_word(TSLexer *lexer, const char *word) {
+    for (const char *c = word; *c; c++) {
+        if (lexer->lookahead != *c) {
+            return false;
+        }
+        skip(lexer);
+    }
+    return !is_identifier_char(lexer->lookahead);
+}
+
+// Tokens that can't continue the innermost block: `in` closes `let` and `ado`,
+// `then`, `else` and `of` close a `do` nested in an `if` or `case` head,
+// brackets and commas close a block nested in them, and `where` closes the
+// expression blocks of the declaration it belongs to.
+static bool ends_block(TSLexer *lexer, uint16_t block) {
+    switch (lexer->lookahead) {
+    case ')':
+    case ']':
+    case '}':
+    case ',':
+        return true;
+    case 'i':
+        return scan_word(lexer, "in");
+    case 't':
+        return scan_word(lexer, "then");
+    case 'e':
+        return scan_word(lexer, "else");
+    case 'o':
+        return scan_word(lexer, "of");
+    case 'w':
+        return (block & EXPRESSION_BLOCK) && scan_word(lexer, "where");
+    default:
+        return false;
+    }
+}
+
+bool tree_sitter_purescript_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+    Scanner *scanner = (Scanner *)payload;
+
+    // During error recovery every symbol is valid; let the parser resync.
+    if (valid_symbols[LAYOUT_START] && valid_symbols[LAYOUT_SEMICOLON] && valid_symbols[LAYOUT_END]) {
+        return false;
+    }
+
+    // Layout tokens are zero-width.
+    lexer->mark_end(lexer);
+
+    bool found_end_of_line = false;
+    while (lexer->lookahead == ' ' || lexer->lookahead == '\t' || lexer->lookahead == '\r' ||
+           lexer->lookahead == '\n' || lexer->lookahead == '\f') {
+        if (lexer->lookahead == '\n') {
+            found_end_of_line = true;
+        }
+        skip(lexer);
+    }
+
+    bool start = valid_symbols[LAYOUT_START] || valid_symbols[EXPRESSION_LAYOUT_START];
+
+    if (lexer->eof(lexer)) {
+        if (start) {
+            // An empty block at the end of the file; closed on the next scan.
+            array_push(&scanner->blocks, COLUMN_MASK);
+            lexer->result_symbol =
+                valid_symbols[LAYOUT_START] ? LAYOUT_START : EXPRESSION_LAYOUT_START;
+            return true;
+        }
+        if (valid_symbols[LAYOUT_END] && scanner->blocks.size > 0) {
+            array_pop(&scanner->blocks);
+            lexer->result_symbol = LAYOUT_END;
+            return true;
+        }
+        return false;
+    }
+
+    uint16_t column = (uint16_t)lexer->get_column(lexer) & COLUMN_MASK;
+
+    // Comments are lexed by the grammar; the scanner runs again after them.
+    bool consumed = false;
+    if (lexer->lookahead == '-' || lexer->lookahead == '{') {
+        skip(lexer);
+        if (lexer->lookahead == '-') {
+            return false;
+        }
+        consumed = true;
+    }
+
+    if (start) {
+        uint16_t flag = valid_symbols[LAYOUT_START] ? 0 : EXPRESSION_BLOCK;
+        // A block that isn't indented past its parent is empty: a column past
+        // the next token makes it close straight away.
+        if (scanner->blocks.size > 0 && column <= (*array_back(&scanner->blocks) & COLUMN_MASK)) {
+            column = (*array_back(&scanner->blocks) & COLUMN_MASK) + 1;
+        }
+        array_push(&scanner->blocks, column | flag);
+        lexer->result_symbol = flag ? EXPRESSION_LAYOUT_START : LAYOUT_START;
+        return true;
+    }
+
+    if (scanner->blocks.size == 0) {
+        return false;
+    }
+
+    uint16_t block = *array_back(&scanner->blocks);
+    uint16_t current = block & COLUMN_MASK;
+
+    if (valid_symbols[LAYOUT_END] &&
+        ((found_end_of_line && column < current) || (!consumed && ends_block(lexer, block)))) {
+        array_pop(&scanner->blocks);
+        lexer->result_symbol = LAYOUT_END;
+        return true;
+    }
+
+    if (valid_symbols[LAYOUT_SEMICOLON] && found_end_of_line && column == current) {
+        lexer->result_symbol = LAYOUT_SEMICOLON;
+        return true;
+    }
+
+    return false;
+}
+
+unsigned tree_sitter_purescript_external_scanner_serialize(void *payload, char *buffer) {
+    Scanner *scanner = (Scanner *)payload;
+
+    size_t size = 0;
+    for (uint32_t iter = 0; iter < scanner->blocks.size && size + 1 < TREE_SITTER_SERIALIZATION_BUFFER_SIZE;
+         ++iter) {
+        uint16_t block = *array_get(&scanner->blocks, iter);
+        buffer[size++] = (char)(block & 0xFF);
+        buffer[size++] = (char)((block >> 8) & 0xFF);
+    }
+
+    return size;
+}
+
+void tree_sitter_purescript_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {
+    Scanner *scanner = (Scanner *)payload;
+
+    array_clear(&scanner->blocks);
+
+    for (size_t size = 0; size + 1 < length; size += 2) {
+        uint16_t block = (unsigned char)buffer[size] | ((unsigned char)buffer[size + 1] << 8);
+        array_push(&scanner->blocks, block);
+    }
+}
+
+void *tree_sitter_purescript_external_scanner_create() {
+    Scanner *scanner = calloc(1, sizeof(Scanner));
+    array_init(&scanner->blocks);
+    return scanner;
+}
+
+void tree_sitter_purescript_external_scanner_destroy(void *payload) {
+    Scanner *scanner = (Scanner *)payload;
+    array_delete(&scanner->blocks);
+    free(scanner);
+}
@@ -0,0 +1,223 @@
+; ----------------------------------------------------------------------------
+; Parameters and variables
+; NOTE: These are at the top, so that they have low priority,
+; and don't override destructured parameters
+(variable) @variable
+
+(function
+  patterns: (patterns
+    (variable) @variable.parameter))
+
+(lambda
+  patterns: (patterns
+    (variable) @variable.parameter))
+
+(as_pattern
+  name: (variable) @variable.parameter)
+
+; ----------------------------------------------------------------------------
+; Literals and comments
+(integer) @number
+
+(float) @number.float
+
+(char) @character
+
+(string) @string
+
+(comment) @comment
+
+((comment) @comment.documentation
+  (#match? @comment.documentation "^-- \\|"))
+
+((variable) @boolean
+  (#any-of? @boolean "true" "false"))
+
+((variable) @boolean
+  (#eq? @boolean "otherwise"))
+
+; ----------------------------------------------------------------------------
+; Punctuation
+[
+  "("
+  ")"
+  "{"
+  "}"
+  "["
+  "]"
+] @punctuation.bracket
+
+"," @punctuation.delimiter
+
+; ----------------------------------------------------------------------------
+; Keywords, operators, includes
+"forall" @keyword.repeat
+
+[
+  "if"
+  "then"
+  "else"
+  "case"
+  "of"
+] @keyword.conditional
+
+[
+  "import"
+  "module"
+  "foreign"
+] @keyword.import
+
+[
+  (operator)
+  (all_names)
+  (wildcard)
+  "."
+  "="
+  "|"
+  "::"
+  "=>"
+  "<="
+  "->"
+  "<-"
+  "\\"
+  "`"
+  "-"
+  "@"
+] @operator
+
+(module) @module
+
+(qualified
+  module: (module) @module)
+
+[
+  "where"
+  "let"
+  "in"
+  "class"
+  "instance"
+  "derive"
+  "data"
+  "newtype"
+  "type"
+  "as"
+  "hiding"
+  "do"
+  "ado"
+  "infix"
+  "infixl"
+  "infixr"
+] @keyword
+
+; ----------------------------------------------------------------------------
+; Functions and variables
+(signature
+  name: (variable) @function)
+
+(function
+  name: (variable) @function)
+
+(bind
+  name: (variable) @variable)
+
+; signatures with a function type, and the binds that follow them
+((signature
+  name: (variable) @_name
+  type: [
+    (function_type)
+    (forall)
+    (constrained_type)
+  ])
+  .
+  (bind
+    name: (variable) @function)
+  (#eq? @function @_name))
+
+; main is always a function
+(bind
+  name: (variable) @function
+  (#eq? @function "main"))
+
+(bind
+  name: (variable) @function
+  match: (match
+    (lambda)))
+
+(foreign_import
+  name: (variable) @function)
+
+(fixity
+  name: (variable) @function)
+
+(instance_declaration
+  name: (variable) @function)
+
+(deriving_instance
+  name: (variable) @function)
+
+(apply
+  function: [
+    (variable) @function.call
+    (qualified
+      id: (variable) @function.call)
+  ])
+
+(infix_id
+  [
+    (variable) @operator
+    (qualified
+      (variable) @operator)
+  ])
+
+; variables being passed to a function call
+(apply
+  argument: [
+    (variable) @variable
+    (qualified
+      id: (variable) @variable)
+  ])
+
+((variable) @keyword.debug
+  (#any-of? @keyword.debug "spy" "spyWith" "trace" "traceM" "unsafeCrashWith"))
+
+; ----------------------------------------------------------------------------
+; Types
+(name) @type
+
+(type_variable) @type
+
+(class_head
+  name: (name) @type.definition)
+
+(data_type
+  name: (name) @type.definition)
+
+(newtype
+  name: (name) @type.definition)
+
+(type_synonym
+  name: (name) @type.definition)
+
+(constructor) @constructor
+
+(hole) @label
+
+; ----------------------------------------------------------------------------
+; Fields
+(row_field
+  name: (variable) @variable.member)
+
+(record_field
+  name: (variable) @variable.member)
+
+(field_update
+  name: (variable) @variable.member)
+
+(record
+  name: (variable) @variable.member)
+
+(field_pun
+  (variable) @variable.member)
+
+(record_access
+  field: (variable) @variable.member)
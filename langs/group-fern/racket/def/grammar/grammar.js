@@ -0,0 +1,211 @@
+/**
+ * @file Racket grammar for tree-sitter
+ * @license MIT
+ *
+ * Racket's default reader: `#lang` lines, keywords (`#:key`), syntax
+ * objects (`#'`, `#``, `#,`, `#,@`), regexp and byte-string literals, hash
+ * and prefab structure literals and boxes.
+ * https://docs.racket-lang.org/reference/reader.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const SYMBOL_START = /[^\s"',`;()\[\]{}#|\\]/;
+const SYMBOL_REST = /[^\s"',`;()\[\]{}|\\]/;
+const QUOTED_SYMBOL = /\|[^|]*\|/;
+
+module.exports = grammar({
+  name: 'racket',
+
+  extras: $ => [
+    /\s/,
+    $.comment,
+    $.block_comment,
+    $.datum_comment,
+  ],
+
+  rules: {
+    program: $ => seq(
+      optional($.lang_line),
+      repeat($._datum),
+    ),
+
+    // #lang racket/base
+    lang_line: $ => seq(
+      token(prec(1, choice('#lang', '#!'))),
+      field('language', $.language_name),
+    ),
+
+    language_name: _ => /[a-zA-Z0-9_+\-/.]+/,
+
+    _datum: $ => choice(
+      $.boolean,
+      $.number,
+      $.character,
+      $.string,
+      $.byte_string,
+      $.regex,
+      $.symbol,
+      $.keyword,
+      $.list,
+      $.vector,
+      $.hash,
+      $.structure,
+      $.box,
+      $.quote,
+      $.quasiquote,
+      $.unquote,
+      $.unquote_splicing,
+      $.syntax,
+      $.quasisyntax,
+      $.unsyntax,
+      $.unsyntax_splicing,
+      $.reader_extension,
+    ),
+
+    // Atoms
+
+    boolean: _ => token(prec(1, choice('#t', '#f', '#true', '#false', '#T', '#F'))),
+
+    // 42, -1/2, 3.14, 6.02e23, +inf.0, #x1F, #e1.5, 1+2i
+    number: _ => token(prec(1, choice(
+      /[+-]?(\d+(\/\d+)?|\d*\.\d+|\d+\.\d*)([eE][+-]?\d+)?/,
+      /[+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?[+-](\d+(\.\d*)?|\.\d+)?i/,
+      /[+-](inf|nan)\.[0ft]/,
+      /#[eEiI]([+-]?\d+(\/\d+)?|[+-]?\d*\.\d+|[+-]?\d+\.\d*)([eE][+-]?\d+)?/,
+      /#[xX][+-]?[0-9a-fA-F]+(\/[0-9a-fA-F]+)?/,
+      /#[oO][+-]?[0-7]+/,
+      /#[bB][+-]?[01]+/,
+    ))),
+
+    // #\a, #\space, #\λ, #\u03BB
+    character: _ => token(seq(
+      '#\\',
+      choice(
+        /[a-zA-Z]+/,
+        /u[0-9a-fA-F]{1,6}/,
+        /[0-7]{3}/,
+        /./,
+      ),
+    )),
+
+    string: $ => seq(
+      '"',
+      repeat(choice($.escape_sequence, token.immediate(prec(1, /[^"\\]+/)))),
+      token.immediate('"'),
+    ),
+
+    // #"bytes"
+    byte_string: $ => seq(
+      '#"',
+      repeat(choice($.escape_sequence, token.immediate(prec(1, /[^"\\]+/)))),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /[abtnvfre"'\\\n]/,
+        /[0-7]{1,3}/,
+        /x[0-9a-fA-F]{1,2}/,
+        /u[0-9a-fA-F]{1,4}/,
+        /U[0-9a-fA-F]{1,8}/,
+      ),
+    )),
+
+    // #rx"a+", #px"\\d+", #rx#"bytes"
+    regex: $ => seq(
+      field('kind', alias(token(choice('#rx', '#px', '#rx#', '#px#')), $.regex_prefix)),
+      field('pattern', alias(token.immediate(seq('"', repeat(choice(/[^"\\]/, /\\./)), '"')), $.regex_pattern)),
+    ),
+
+    // foo, list->vector, set-car!, #%app, |two words|
+    symbol: _ => token(choice(
+      seq(SYMBOL_START, repeat(SYMBOL_REST)),
+      seq('#%', repeat(SYMBOL_REST)),
+      QUOTED_SYMBOL,
+    )),
+
+    // #:key
+    keyword: _ => token(seq('#:', repeat1(SYMBOL_REST))),
+
+    // Compound data
+
+    list: $ => choice(
+      seq('(', repeat($._datum), optional($._dotted_tail), ')'),
+      seq('[', repeat($._datum), optional($._dotted_tail), ']'),
+      seq('{', repeat($._datum), optional($._dotted_tail), '}'),
+    ),
+
+    _dotted_tail: $ => seq('.', $._datum),
+
+    vector: $ => choice(
+      seq(/#\d*\(/, repeat($._datum), ')'),
+      seq(/#\d*\[/, repeat($._datum), ']'),
+      seq(/#fl\(|#fx\(/, repeat($._datum), ')'),
+    ),
+
+    // #hash((a . 1) (b . 2))
+    hash: $ => seq(
+      token(choice('#hash(', '#hasheq(', '#hasheqv(', '#hashalw(')),
+      repeat($._datum),
+      ')',
+    ),
+
+    // #s(point 1 2)
+    structure: $ => seq(
+      token(choice('#s(', '#s[')),
+      repeat($._datum),
+      choice(')', ']'),
+    ),
+
+    box: $ => seq('#&', $._datum),
+
+    // Quoting
+
+    quote: $ => seq("'", $._datum),
+
+    quasiquote: $ => seq('`', $._datum),
+
+    unquote: $ => seq(',', $._datum),
+
+    unquote_splicing: $ => seq(',@', $._datum),
+
+    syntax: $ => seq("#'", $._datum),
+
+    quasisyntax: $ => seq('#`', $._datum),
+
+    unsyntax: $ => seq('#,', $._datum),
+
+    unsyntax_splicing: $ => seq('#,@', $._datum),
+
+    // #reader, #ci and friends
+    reader_extension: $ => seq(
+      alias(token(choice('#reader', '#ci', '#cs')), $.reader_prefix),
+      $._datum,
+    ),
+
+    // Comments
+
+    comment: _ => token(choice(
+      seq(';', /[^\n]*/),
+      seq('#!/', /[^\n]*/),
+    )),
+
+    block_comment: $ => seq(
+      '#|',
+      repeat(choice(
+        $.block_comment,
+        /[^|#]+/,
+        /\|[^#]/,
+        /#[^|]/,
+        /[|#]/,
+      )),
+      '|#',
+    ),
+
+    // #;(ignored datum)
+    datum_comment: $ => seq('#;', $._datum),
+  },
+});
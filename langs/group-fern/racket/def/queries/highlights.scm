@@ -0,0 +1,188 @@
+; Variables
+
+(symbol) @variable
+
+((symbol) @operator
+  (#any-of? @operator "+" "-" "*" "/" "=" "<" ">" "<=" ">="))
+
+; Calls
+
+(list
+  .
+  (symbol) @function.call)
+
+(list
+  .
+  (symbol) @function.builtin
+  (#any-of? @function.builtin
+    "append" "apply" "assoc" "build-list" "car" "cdr" "cons" "display"
+    "displayln" "eq?" "equal?" "error" "filter" "first" "foldl" "foldr"
+    "for-each" "format" "hash-ref" "hash-set" "hash-set!" "length" "list"
+    "list-ref" "map" "member" "newline" "not" "null?" "printf" "raise"
+    "raise-argument-error" "rest" "reverse" "second" "sort" "string-append"
+    "vector" "vector-ref" "vector-set!" "void"))
+
+; Special forms
+
+(list
+  .
+  (symbol) @keyword
+  (#any-of? @keyword
+    "define" "define-values" "define-syntax" "define-syntax-rule"
+    "define-for-syntax" "define-struct" "struct" "set!" "let" "let*" "letrec"
+    "let-values" "let*-values" "letrec-values" "let-syntax" "letrec-syntax"
+    "syntax-rules" "syntax-case" "syntax-parse" "with-syntax" "begin"
+    "begin0" "begin-for-syntax" "do" "delay" "parameterize" "with-handlers"
+    "quote" "quasiquote" "unquote" "unquote-splicing" "syntax" "quasisyntax"
+    "unsyntax" "unsyntax-splicing" "and" "or" "match" "match-define"
+    "match-lambda" "match-let"))
+
+(list
+  .
+  (symbol) @keyword.conditional
+  (#any-of? @keyword.conditional "if" "cond" "case" "when" "unless"))
+
+((symbol) @keyword.conditional
+  (#any-of? @keyword.conditional "else" "=>"))
+
+(list
+  .
+  (symbol) @keyword.repeat
+  (#match? @keyword.repeat "^for\\*?(/[a-z-]+)?$"))
+
+(list
+  .
+  (symbol) @keyword.function
+  (#any-of? @keyword.function "lambda" "λ" "case-lambda"))
+
+(list
+  .
+  (symbol) @keyword.import
+  (#any-of? @keyword.import
+    "require" "provide" "module" "module+" "module*" "for-syntax"
+    "only-in" "except-in" "prefix-in" "rename-in" "all-defined-out"
+    "all-from-out" "contract-out"))
+
+; Definitions
+
+; (define (name arg ...) body ...)
+(list
+  .
+  (symbol) @_define
+  .
+  (list
+    .
+    (symbol) @function
+    (symbol)* @variable.parameter)
+  (#any-of? @_define "define" "define-syntax" "define-syntax-rule" "define-for-syntax"))
+
+; (define name value)
+(list
+  .
+  (symbol) @_define
+  .
+  (symbol) @variable
+  (#any-of? @_define "define" "define-values"))
+
+; (struct point (x y))
+(list
+  .
+  (symbol) @_struct
+  .
+  (symbol) @type
+  (#any-of? @_struct "struct" "define-struct"))
+
+(list
+  .
+  (symbol) @_lambda
+  .
+  (list
+    (symbol) @variable.parameter)
+  (#any-of? @_lambda "lambda" "λ"))
+
+; (let loop ([i 0]) body ...)
+(list
+  .
+  (symbol) @_let
+  .
+  (symbol) @function
+  (#eq? @_let "let"))
+
+; Keyword arguments
+
+(keyword) @variable.parameter
+
+; Quoting and syntax objects
+
+(quote
+  (symbol) @string.special.symbol)
+
+(quote
+  (list
+    (symbol) @string.special.symbol))
+
+(quasiquote
+  (symbol) @string.special.symbol)
+
+(unquote
+  (symbol) @variable)
+
+(unquote_splicing
+  (symbol) @variable)
+
+[
+  "'"
+  "`"
+  ","
+  ",@"
+  "#'"
+  "#`"
+  "#,"
+  "#,@"
+  "#&"
+] @punctuation.special
+
+; Literals
+
+(number) @number
+
+(character) @character
+
+(boolean) @boolean
+
+[
+  (string)
+  (byte_string)
+] @string
+
+(escape_sequence) @string.escape
+
+(regex
+  (regex_prefix) @string.special
+  (regex_pattern) @string.regexp)
+
+; Module language
+
+(lang_line) @keyword.directive
+
+(lang_line
+  language: (language_name) @module)
+
+(reader_prefix) @keyword.directive
+
+; Comments
+
+[
+  (comment)
+  (block_comment)
+  (datum_comment)
+] @comment
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
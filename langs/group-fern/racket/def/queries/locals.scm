@@ -0,0 +1,57 @@
+; Scopes
+
+(program) @local.scope
+
+((list
+  .
+  (symbol) @_form) @local.scope
+  (#any-of? @_form
+    "define" "lambda" "λ" "case-lambda" "let" "let*" "letrec" "let-values"
+    "let*-values" "for" "for*" "for/list" "for/fold" "match-lambda"))
+
+; Definitions
+
+; (define (name arg ...) ...)
+(list
+  .
+  (symbol) @_define
+  .
+  (list
+    .
+    (symbol) @local.definition
+    (symbol)* @local.definition)
+  (#any-of? @_define "define" "define-syntax-rule"))
+
+; (define name ...), (struct name ...)
+(list
+  .
+  (symbol) @_define
+  .
+  (symbol) @local.definition
+  (#any-of? @_define "define" "define-syntax" "struct"))
+
+; (lambda (arg ...) ...)
+(list
+  .
+  (symbol) @_lambda
+  .
+  (list
+    (symbol) @local.definition)
+  (#any-of? @_lambda "lambda" "λ"))
+
+; (let ([name value] ...) ...), (for ([x xs]) ...)
+(list
+  .
+  (symbol) @_let
+  .
+  (symbol)? @local.definition
+  .
+  (list
+    (list
+      .
+      (symbol) @local.definition))
+  (#any-of? @_let "let" "let*" "letrec" "for" "for*" "for/list" "for/fold"))
+
+; References
+
+(symbol) @local.reference
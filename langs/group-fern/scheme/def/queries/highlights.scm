@@ -1,84 +1,190 @@
-["(" ")" "[" "]" "{" "}"] @punctuation.bracket
+; Variables
 
-(number) @number
-(character) @constant.builtin
-(boolean) @constant.builtin
 (symbol) @variable
 
-(string) @string
+((symbol) @operator
+  (#any-of? @operator "+" "-" "*" "/" "=" "<" ">" "<=" ">="))
 
-(escape_sequence) @escape
+; Calls
 
 (list
   .
-  (symbol) @function)
+  (symbol) @function.call)
 
 (list
   .
-  "["
+  (symbol) @function.builtin
+  (#any-of? @function.builtin
+    "abs" "append" "apply" "assoc" "assq" "assv" "boolean?" "caar" "cadr"
+    "caddr" "call-with-current-continuation" "call/cc" "call-with-values"
+    "car" "cdar" "cddr" "cdr" "ceiling" "char->integer" "char?" "cons"
+    "display" "dynamic-wind" "eq?" "equal?" "eqv?" "error" "eval" "even?"
+    "exact->inexact" "exact?" "exp" "expt" "floor" "for-each" "force" "gcd"
+    "inexact->exact" "integer?" "lcm" "length" "list" "list->string"
+    "list->vector" "list-ref" "list-tail" "list?" "load" "log" "make-string"
+    "make-vector" "map" "max" "member" "memq" "memv" "min" "modulo"
+    "negative?" "newline" "not" "null?" "number->string" "number?" "odd?"
+    "pair?" "positive?" "procedure?" "quotient" "read" "remainder" "reverse"
+    "round" "set-car!" "set-cdr!" "sqrt" "string" "string->list"
+    "string->number" "string->symbol" "string-append" "string-length"
+    "string-ref" "string?" "substring" "symbol->string" "symbol?" "truncate"
+    "values" "vector" "vector->list" "vector-length" "vector-ref"
+    "vector-set!" "vector?" "write" "zero?"))
+
+; Special forms
+
+(list
   .
-  (symbol)+ @variable
+  (symbol) @keyword
+  (#any-of? @keyword
+    "define" "define-record-type" "define-syntax" "define-values" "set!"
+    "let" "let*" "letrec" "letrec*" "let-values" "let*-values" "let-syntax"
+    "letrec-syntax" "syntax-rules" "syntax-case" "begin" "do" "delay"
+    "delay-force" "make-promise" "parameterize" "guard" "quote" "quasiquote"
+    "unquote" "unquote-splicing" "and" "or" "assert"))
+
+(list
   .
-  "]")
+  (symbol) @keyword.conditional
+  (#any-of? @keyword.conditional "if" "cond" "case" "when" "unless"))
 
-((symbol) @operator
- (#match? @operator "^(\\+|-|\\*|/|=|>|<|>=|<=)$"))
+((symbol) @keyword.conditional
+  (#any-of? @keyword.conditional "else" "=>"))
 
 (list
   .
-  (symbol) @keyword
-  (#match? @keyword
-   "^(define-syntax|let\\*|lambda|λ|case|=>|quote-splicing|unquote-splicing|set!|let|letrec|letrec-syntax|let-values|let\\*-values|do|else|define|cond|syntax-rules|unquote|begin|quote|let-syntax|and|if|quasiquote|letrec|delay|or|when|unless|identifier-syntax|assert|library|export|import|rename|only|except|prefix)$"
-   ))
+  (symbol) @keyword.function
+  (#any-of? @keyword.function "lambda" "λ" "case-lambda"))
 
 (list
   .
-  (symbol) @function.builtin
-  (#match? @function.builtin
-   "^(caar|cadr|call-with-input-file|call-with-output-file|cdar|cddr|list|open-input-file|open-output-file|with-input-from-file|with-output-to-file|\\*|\\+|-|/|<|<=|=|>|>=|abs|acos|angle|append|apply|asin|assoc|assq|assv|atan|boolean\\?|caaaar|caaadr|caaar|caadar|caaddr|caadr|cadaar|cadadr|cadar|caddar|cadddr|caddr|call-with-current-continuation|call-with-values|car|cdaaar|cdaadr|cdaar|cdadar|cdaddr|cdadr|cddaar|cddadr|cddar|cdddar|cddddr|cdddr|cdr|ceiling|char->integer|char-alphabetic\\?|char-ci<=\\?|char-ci<\\?|char-ci=\\?|char-ci>=\\?|char-ci>\\?|char-downcase|char-lower-case\\?|char-numeric\\?|char-ready\\?|char-upcase|char-upper-case\\?|char-whitespace\\?|char<=\\?|char<\\?|char=\\?|char>=\\?|char>\\?|char\\?|close-input-port|close-output-port|complex\\?|cons|cos|current-error-port|current-input-port|current-output-port|denominator|display|dynamic-wind|eof-object\\?|eq\\?|equal\\?|eqv\\?|eval|even\\?|exact->inexact|exact\\?|exp|expt|floor|flush-output|for-each|force|gcd|imag-part|inexact->exact|inexact\\?|input-port\\?|integer->char|integer\\?|interaction-environment|lcm|length|list->string|list->vector|list-ref|list-tail|list\\?|load|log|magnitude|make-polar|make-rectangular|make-string|make-vector|map|max|member|memq|memv|min|modulo|negative\\?|newline|not|null-environment|null\\?|number->string|number\\?|numerator|odd\\?|output-port\\?|pair\\?|peek-char|positive\\?|procedure\\?|quotient|rational\\?|rationalize|read|read-char|real-part|real\\?|remainder|reverse|round|scheme-report-environment|set-car!|set-cdr!|sin|sqrt|string|string->list|string->number|string->symbol|string-append|string-ci<=\\?|string-ci<\\?|string-ci=\\?|string-ci>=\\?|string-ci>\\?|string-copy|string-fill!|string-length|string-ref|string-set!|string<=\\?|string<\\?|string=\\?|string>=\\?|string>\\?|string\\?|substring|symbol->string|symbol\\?|tan|transcript-off|transcript-on|truncate|values|vector|vector->list|vector-fill!|vector-length|vector-ref|vector-set!|vector\\?|write|write-char|zero\\?)$"
-   ))
+  (symbol) @keyword.import
+  (#any-of? @keyword.import
+    "import" "export" "library" "define-library" "include" "only" "except"
+    "prefix" "rename"))
 
-;; quote ;;
+; Definitions
 
-;; hardcoded highlight four levels of nested structure
+; (define (name arg ...) body ...)
+(list
+  .
+  (symbol) @_define
+  .
+  (list
+    .
+    (symbol) @function
+    (symbol)* @variable.parameter)
+  (#any-of? @_define "define" "define-syntax"))
 
-; 'atom
-(quote
-  _ @constant)
+; (define name value)
+(list
+  .
+  (symbol) @_define
+  .
+  (symbol) @variable
+  (#any-of? @_define "define" "define-values"))
+
+; (define name (lambda (arg ...) body ...))
+(list
+  .
+  (symbol) @_define
+  .
+  (symbol) @function
+  .
+  (list
+    .
+    (symbol) @_lambda
+    .
+    (list
+      (symbol) @variable.parameter))
+  (#eq? @_define "define")
+  (#any-of? @_lambda "lambda" "λ"))
+
+(list
+  .
+  (symbol) @_lambda
+  .
+  (list
+    (symbol) @variable.parameter)
+  (#any-of? @_lambda "lambda" "λ"))
+
+; (let loop ((i 0)) body ...)
+(list
+  .
+  (symbol) @_let
+  .
+  (symbol) @function
+  (#eq? @_let "let"))
+
+; Quoting
 
-; '()
 (quote
-  (_ _* @constant))
+  (symbol) @string.special.symbol)
 
-; '(())
 (quote
-  (_ (_ _* @constant)))
+  (list
+    (symbol) @string.special.symbol))
 
-; '((()))
 (quote
-  (_ (_ (_ _* @constant))))
+  (list
+    (list
+      (symbol) @string.special.symbol)))
 
-;; sexp comment ;;
+(quasiquote
+  (symbol) @string.special.symbol)
 
-;; hardcoded highlight four levels of nested structure
+(quasiquote
+  (list
+    (symbol) @string.special.symbol))
 
-; #;atom
-(comment
-  _ @comment)
+; Unquoted expressions are evaluated again
+(unquote
+  (symbol) @variable)
 
-; #;(list)
-(comment
-  (_ _* @comment))
+(unquote_splicing
+  (symbol) @variable)
 
-; #;(list (list))
-(comment
-  (_ (_ _* @comment)))
+[
+  "'"
+  "`"
+  ","
+  ",@"
+  "#'"
+  "#`"
+  "#,"
+  "#,@"
+] @punctuation.special
 
-; #;(list (list (list)))
-(comment
-  (_ (_ (_ _ @comment))))
+; Literals
 
-[(comment)
- (block_comment)
- (directive)] @comment
+(number) @number
+
+(character) @character
+
+(boolean) @boolean
+
+(string) @string
+
+(escape_sequence) @string.escape
 
+(keyword) @string.special.symbol
+
+; Comments
+
+[
+  (comment)
+  (block_comment)
+] @comment
+
+(directive) @keyword.directive
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+  "#("
+  "#vu8("
+] @punctuation.bracket
@@ -0,0 +1,57 @@
+; Scopes
+
+(program) @local.scope
+
+((list
+  .
+  (symbol) @_form) @local.scope
+  (#any-of? @_form
+    "define" "lambda" "λ" "case-lambda" "let" "let*" "letrec" "letrec*"
+    "let-values" "let*-values" "do"))
+
+; Definitions
+
+; (define (name arg ...) ...)
+(list
+  .
+  (symbol) @_define
+  .
+  (list
+    .
+    (symbol) @local.definition
+    (symbol)* @local.definition)
+  (#eq? @_define "define"))
+
+; (define name ...)
+(list
+  .
+  (symbol) @_define
+  .
+  (symbol) @local.definition
+  (#any-of? @_define "define" "define-syntax"))
+
+; (lambda (arg ...) ...)
+(list
+  .
+  (symbol) @_lambda
+  .
+  (list
+    (symbol) @local.definition)
+  (#any-of? @_lambda "lambda" "λ"))
+
+; (let ((name value) ...) ...), (let loop ((name value) ...) ...)
+(list
+  .
+  (symbol) @_let
+  .
+  (symbol)? @local.definition
+  .
+  (list
+    (list
+      .
+      (symbol) @local.definition))
+  (#any-of? @_let "let" "let*" "letrec" "letrec*" "do"))
+
+; References
+
+(symbol) @local.reference
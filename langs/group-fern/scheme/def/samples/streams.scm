@@ -0,0 +1,65 @@
+;;; Lazy streams in the style of SICP section 3.5.
+
+(define-syntax cons-stream
+  (syntax-rules ()
+    ((_ head tail) (cons head (delay tail)))))
+
+(define the-empty-stream '())
+(define stream-null? null?)
+(define (stream-car s) (car s))
+(define (stream-cdr s) (force (cdr s)))
+
+(define (stream-ref s n)
+  (if (= n 0)
+      (stream-car s)
+      (stream-ref (stream-cdr s) (- n 1))))
+
+(define (stream-map proc . streams)
+  (if (stream-null? (car streams))
+      the-empty-stream
+      (cons-stream
+       (apply proc (map stream-car streams))
+       (apply stream-map (cons proc (map stream-cdr streams))))))
+
+(define (stream-filter keep? s)
+  (cond ((stream-null? s) the-empty-stream)
+        ((keep? (stream-car s))
+         (cons-stream (stream-car s) (stream-filter keep? (stream-cdr s))))
+        (else (stream-filter keep? (stream-cdr s)))))
+
+(define (integers-from n)
+  (cons-stream n (integers-from (+ n 1))))
+
+;; The sieve of Eratosthenes over an infinite stream.
+(define (sieve s)
+  (cons-stream
+   (stream-car s)
+   (sieve (stream-filter
+           (lambda (x) (not (= 0 (remainder x (stream-car s)))))
+           (stream-cdr s)))))
+
+(define primes (sieve (integers-from 2)))
+
+(define (stream-take s n)
+  (let loop ((s s) (n n) (acc '()))
+    (if (or (= n 0) (stream-null? s))
+        (reverse acc)
+        (loop (stream-cdr s) (- n 1) (cons (stream-car s) acc)))))
+
+(define fibs
+  (cons-stream 0 (cons-stream 1 (stream-map + fibs (stream-cdr fibs)))))
+
+#| Quasiquote builds a report with the computed values spliced in. |#
+(define (report name values)
+  `(,name count: ,(length values) values: ,@values))
+
+(for-each
+ (lambda (line)
+   (display line)
+   (newline))
+ (list (report 'primes (stream-take primes 10))
+       (report 'fibonacci (stream-take fibs 12))
+       (string-append "sqrt(2) ~ " (number->string (sqrt 2.0)))
+       #\λ
+       #;(this datum is commented out)
+       (vector 'done #t)))
@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+# Creates the reporting schema and writes a connection config for the
+# dashboard service.
+set -euo pipefail
+
+DB_NAME="${DB_NAME:-reports}"
+CONFIG_DIR="${CONFIG_DIR:-/etc/dashboard}"
+
+psql --dbname "$DB_NAME" --set ON_ERROR_STOP=1 <<'EOF_SQL'
+CREATE TABLE IF NOT EXISTS daily_totals (
+    day date PRIMARY KEY,
+    orders integer NOT NULL DEFAULT 0,
+    revenue numeric(12, 2) NOT NULL DEFAULT 0
+);
+
+INSERT INTO daily_totals (day, orders, revenue)
+SELECT created_at::date, count(*), sum(total)
+FROM orders
+GROUP BY created_at::date
+ON CONFLICT (day) DO NOTHING;
+EOF_SQL
+
+mkdir -p "$CONFIG_DIR"
+cat > "$CONFIG_DIR/database.json" <<-JSON
+	{
+	  "database": "$DB_NAME",
+	  "pool": { "min": 1, "max": 8 },
+	  "readOnly": true
+	}
+	JSON
+
+echo "Schema ready in $DB_NAME" >&2
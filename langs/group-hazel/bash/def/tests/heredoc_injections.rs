@@ -0,0 +1,123 @@
+//! Heredocs whose delimiter names a language inject that language, in
+//! bash, ruby, crystal and perl.
+
+use arborium_test_harness::helpers;
+
+fn bash(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_bash::language(),
+        helpers::queries!(arborium_bash),
+        source,
+    )
+}
+
+fn ruby(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_ruby::language(),
+        helpers::queries!(arborium_ruby),
+        source,
+    )
+}
+
+fn crystal(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_crystal::language(),
+        helpers::queries!(arborium_crystal),
+        source,
+    )
+}
+
+fn perl(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_perl::language(),
+        helpers::queries!(arborium_perl),
+        source,
+    )
+}
+
+fn languages(injections: &[(&str, String)]) -> Vec<String> {
+    injections.iter().map(|(_, l)| l.clone()).collect()
+}
+
+#[test]
+fn test_bash_heredoc_tags_name_the_language() {
+    let source = "\
+psql <<'EOF_SQL'
+SELECT 1;
+EOF_SQL
+cat <<json
+{ \"a\": 1 }
+json
+cat <<EOF
+plain text
+EOF
+";
+    let injections = bash(source);
+    assert_eq!(languages(&injections), ["sql", "json"]);
+    assert!(injections[0].0.contains("SELECT 1;"));
+    assert!(!injections[0].0.contains("EOF_SQL"));
+    assert!(injections[1].0.contains("\"a\": 1"));
+}
+
+#[test]
+fn test_ruby_heredoc_tags_name_the_language() {
+    let source = "\
+db.exec(<<~SQL)
+  SELECT * FROM orders
+SQL
+JSON.parse(<<-END_JSON)
+  { \"b\": 2 }
+  END_JSON
+puts <<~TEXT
+  not injected
+TEXT
+";
+    let injections = ruby(source);
+    assert_eq!(languages(&injections), ["sql", "json"]);
+    assert!(injections[0].0.contains("SELECT * FROM orders"));
+    assert!(injections[1].0.contains("\"b\": 2"));
+}
+
+#[test]
+fn test_crystal_heredoc_tags_name_the_language() {
+    let source = "\
+db.exec <<-SQL
+  SELECT id FROM items WHERE qty > 0
+  SQL
+config = <<-YAML
+  retries: 3
+  YAML
+puts <<-TEXT
+  not injected
+  TEXT
+";
+    let injections = crystal(source);
+    assert_eq!(languages(&injections), ["sql", "yaml"]);
+    assert!(injections[0].0.contains("SELECT id FROM items"));
+    assert!(injections[1].0.contains("retries: 3"));
+}
+
+#[test]
+fn test_perl_heredoc_tags_name_the_language() {
+    let source = "\
+my $sql = <<'SQL';
+SELECT name FROM customers
+SQL
+my $json = <<~Json;
+    { \"c\": 3 }
+    Json
+my $text = <<EOT;
+not injected
+EOT
+";
+    let injections = perl(source);
+    assert_eq!(languages(&injections), ["sql", "json"]);
+    assert!(injections[0].0.contains("SELECT name FROM customers"));
+    assert!(injections[1].0.contains("\"c\": 3"));
+}
+
+#[test]
+fn test_words_merely_containing_a_tag_dont_inject() {
+    let source = "cat <<MYSQL_NOTES\nnotes\nMYSQL_NOTES\ncat <<FINISH\ndone\nFINISH\n";
+    assert!(bash(source).is_empty());
+}
@@ -0,0 +1,470 @@
+/**
+ * @file Nushell grammar for tree-sitter
+ * @license MIT
+ *
+ * Nushell scripts and configs: pipelines of commands and expressions,
+ * custom commands (`def`) with typed signatures, `let`/`mut`/`const`,
+ * closures, records, lists and tables, cell paths (`$env.PATH`), ranges,
+ * and string interpolation (`$"($name)"`), whose holes are parsed as Nu
+ * code in place.
+ *
+ * Like a shell, Nushell ends a statement at a newline, except inside
+ * brackets and before a `|` that continues the pipeline on the next line.
+ * Newlines are extras everywhere else, so they only terminate a statement
+ * where a terminator is valid.
+ * https://www.nushell.sh/book/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  range: 1,
+  or: 2,
+  xor: 3,
+  and: 4,
+  not: 5,
+  bit_or: 6,
+  bit_xor: 7,
+  bit_and: 8,
+  compare: 9,
+  shift: 10,
+  additive: 11,
+  multiplicative: 12,
+  power: 13,
+  unary: 14,
+};
+
+const BINARY_OPERATORS = [
+  [PREC.or, 'or'],
+  [PREC.xor, 'xor'],
+  [PREC.and, 'and'],
+  [PREC.bit_or, 'bit-or'],
+  [PREC.bit_xor, 'bit-xor'],
+  [PREC.bit_and, 'bit-and'],
+  [PREC.compare, choice(
+    '==', '!=', '<', '<=', '>', '>=', '=~', '!~',
+    'in', 'not-in', 'starts-with', 'ends-with',
+    'like', 'not-like', 'has', 'not-has',
+  )],
+  [PREC.shift, choice('bit-shl', 'bit-shr')],
+  [PREC.additive, choice('+', '-', '++')],
+  [PREC.multiplicative, choice('*', '/', '//', 'mod')],
+];
+
+module.exports = grammar({
+  name: 'nu',
+
+  extras: $ => [/\s/, $.comment],
+
+  rules: {
+    source_file: $ => optional($._statements),
+
+    _statements: $ => prec(1, seq(
+      repeat(seq($._statement, $._terminator)),
+      $._statement,
+      optional($._terminator),
+    )),
+
+    _terminator: _ => choice(';', /\r?\n/),
+
+    _statement: $ => choice(
+      $.pipeline,
+      $.let_statement,
+      $.assignment,
+      $.function_definition,
+      $.extern_definition,
+      $.alias_definition,
+      $.module_definition,
+      $.export,
+      $.for_statement,
+      $.while_statement,
+      $.loop_statement,
+      $.return_statement,
+      $.break_statement,
+      $.continue_statement,
+    ),
+
+    // ls | where size > 1mb | sort-by modified
+    pipeline: $ => seq(
+      $._pipe_element,
+      repeat(seq(alias($._pipe, '|'), $._pipe_element)),
+    ),
+
+    // A `|` continues the pipeline even at the start of the next line
+    _pipe: _ => token(seq(repeat(/\r?\n[ \t]*/), '|')),
+
+    _pipe_element: $ => choice(
+      $.command,
+      $._expression,
+      $.if_expression,
+      $.match_expression,
+      $.try_expression,
+    ),
+
+    // git log --oneline -n 5, ^ls -la
+    command: $ => prec.right(seq(
+      field('head', choice($.cmd_identifier, $.external)),
+      repeat(field('argument', $._argument)),
+    )),
+
+    cmd_identifier: _ => /[A-Za-z_][\w-]*/,
+
+    // ^git: run the external command, not a Nu one of the same name
+    external: _ => /\^[^\s\[\](){}|;,"'`]+/,
+
+    _argument: $ => choice(
+      $._value,
+      $.long_flag,
+      $.short_flag,
+      $.unquoted,
+    ),
+
+    long_flag: _ => /--[A-Za-z_][\w-]*/,
+
+    short_flag: _ => /-[A-Za-z_]\w*/,
+
+    // A bare word argument, like a path or a subcommand: `~/dev`, `*.rs`,
+    // the `trim` of `str trim`
+    unquoted: _ => token(prec(-1, /[^\s\d\-$#"'`\[\](){}|;,][^\s\[\](){}|;,"'`]*/)),
+
+    // let name: string = $in | str trim
+    let_statement: $ => seq(
+      field('keyword', choice('let', 'mut', 'const')),
+      field('name', $._identifier),
+      optional(seq(':', field('type', $.type))),
+      '=',
+      field('value', $.pipeline),
+    ),
+
+    // $env.PATH = ($env.PATH | prepend ~/.cargo/bin), $count += 1
+    assignment: $ => seq(
+      field('left', choice($.variable, $.cell_path)),
+      field('operator', choice('=', '+=', '-=', '*=', '/=', '++=')),
+      field('right', $.pipeline),
+    ),
+
+    // def --env goto [dir: path] { cd $dir }
+    function_definition: $ => seq(
+      choice('def', 'def-env'),
+      repeat($.long_flag),
+      field('name', choice($.cmd_identifier, $.string)),
+      field('parameters', $.parameters),
+      optional(field('return_type', $.return_type)),
+      field('body', $.block),
+    ),
+
+    // extern "git push" [remote?: string, --force(-f)]
+    extern_definition: $ => seq(
+      'extern',
+      field('name', choice($.cmd_identifier, $.string)),
+      field('parameters', $.parameters),
+    ),
+
+    // alias ll = ls -l
+    alias_definition: $ => seq(
+      'alias',
+      field('name', choice($.cmd_identifier, $.string)),
+      '=',
+      field('value', $.pipeline),
+    ),
+
+    module_definition: $ => seq(
+      'module',
+      field('name', choice($._identifier, $.string)),
+      field('body', $.block),
+    ),
+
+    // export def, export const, export use std/log
+    export: $ => seq(
+      'export',
+      choice(
+        $.function_definition,
+        $.extern_definition,
+        $.alias_definition,
+        $.module_definition,
+        $.let_statement,
+        $.command,
+      ),
+    ),
+
+    parameters: $ => seq(
+      '[',
+      repeat(choice($.parameter, ',')),
+      ']',
+    ),
+
+    // name, name?: string, count: int = 1, --verbose(-v), ...rest: path
+    parameter: $ => seq(
+      choice(
+        seq(field('name', $._identifier), optional(token.immediate('?'))),
+        seq(
+          field('name', $.long_flag),
+          optional(seq('(', field('short', $.short_flag), ')')),
+        ),
+        seq('...', field('name', $._identifier)),
+      ),
+      optional(seq(':', field('type', $.type))),
+      optional(seq('=', field('default', $._expression))),
+    ),
+
+    // -> string, : nothing -> record, : [string -> int, list -> int]
+    return_type: $ => choice(
+      seq('->', $.type),
+      seq(':', $.type, '->', $.type),
+      seq(':', '[', repeat(choice($.type, '->', ',')), ']'),
+    ),
+
+    // string, list<string>, record<name: string, size: filesize>
+    type: $ => seq(
+      field('name', alias($.cmd_identifier, $.type_identifier)),
+      optional(seq('<', repeat(choice($.type, ',', ':')), '>')),
+    ),
+
+    if_expression: $ => prec.right(seq(
+      'if',
+      field('condition', $._expression),
+      field('consequence', $.block),
+      optional(seq(
+        'else',
+        field('alternative', choice($.block, $.if_expression)),
+      )),
+    )),
+
+    // match $status { 200 => "ok", 404 | 410 => "gone", _ => "error" }
+    match_expression: $ => seq(
+      'match',
+      field('value', $._expression),
+      '{',
+      repeat(choice($.match_arm, ',')),
+      '}',
+    ),
+
+    match_arm: $ => seq(
+      field('pattern', $._pattern),
+      repeat(seq('|', field('pattern', $._pattern))),
+      optional(seq('if', field('guard', $._expression))),
+      '=>',
+      field('body', $._expression),
+    ),
+
+    _pattern: $ => choice(
+      $._value,
+      $.unquoted,
+      $.wildcard,
+    ),
+
+    wildcard: _ => '_',
+
+    try_expression: $ => prec.right(seq(
+      'try',
+      field('body', $.block),
+      optional(seq('catch', field('handler', choice($.block, $.closure)))),
+    )),
+
+    for_statement: $ => seq(
+      'for',
+      field('variable', $._identifier),
+      'in',
+      field('iterable', $._expression),
+      field('body', $.block),
+    ),
+
+    while_statement: $ => seq(
+      'while',
+      field('condition', $._expression),
+      field('body', $.block),
+    ),
+
+    loop_statement: $ => seq('loop', field('body', $.block)),
+
+    return_statement: $ => prec.right(seq('return', optional($._expression))),
+
+    break_statement: _ => 'break',
+
+    continue_statement: _ => 'continue',
+
+    _identifier: $ => alias($.cmd_identifier, $.identifier),
+
+    _expression: $ => choice(
+      $._value,
+      $.binary_expression,
+      $.unary_expression,
+    ),
+
+    binary_expression: $ => choice(
+      ...BINARY_OPERATORS.map(([precedence, operator]) => prec.left(precedence, seq(
+        field('left', $._expression),
+        field('operator', operator),
+        field('right', $._expression),
+      ))),
+      prec.right(PREC.power, seq(
+        field('left', $._expression),
+        field('operator', '**'),
+        field('right', $._expression),
+      )),
+    ),
+
+    unary_expression: $ => choice(
+      prec(PREC.not, seq('not', $._expression)),
+      prec(PREC.unary, seq('-', $._expression)),
+    ),
+
+    _value: $ => choice(
+      $.variable,
+      $.cell_path,
+      $.number,
+      $.filesize,
+      $.duration,
+      $.date,
+      $.boolean,
+      $.null,
+      $.string,
+      $.raw_string,
+      $.string_interpolation,
+      $.list,
+      $.table,
+      $.record,
+      $.block,
+      $.closure,
+      $.subexpression,
+      $.range,
+    ),
+
+    // $name, $env, $in, $nu
+    variable: _ => /\$[A-Za-z_]\w*/,
+
+    // $env.PATH, $row.size?, (ls).name, $matrix.0.1
+    cell_path: $ => seq(
+      field('head', choice($.variable, $.subexpression)),
+      repeat1(seq(token.immediate('.'), field('member', $.member))),
+    ),
+
+    member: _ => token.immediate(/[\w-]+[?!]?/),
+
+    // 1..10, 0..<$n, $start..
+    range: $ => prec.right(PREC.range, seq(
+      field('start', $._range_bound),
+      choice('..', '..<', '..='),
+      optional(field('end', $._range_bound)),
+    )),
+
+    _range_bound: $ => choice(
+      $.number,
+      $.variable,
+      $.cell_path,
+      $.subexpression,
+    ),
+
+    number: _ => token(choice(
+      /-?\d[\d_]*(\.\d+)?([eE][+-]?\d+)?/,
+      /0x[0-9a-fA-F_]+/,
+      /0o[0-7_]+/,
+      /0b[01_]+/,
+    )),
+
+    // 512b, 1.5kb, 2GiB
+    filesize: _ => /-?\d+(\.\d+)?([kKmMgGtTpPeE][iI]?)?[bB]/,
+
+    // 500ms, 1.5sec, 3day
+    duration: _ => /-?\d+(\.\d+)?(ns|us|µs|ms|sec|min|hr|day|wk)/,
+
+    // 2024-03-01, 2024-03-01T09:30:00+02:00
+    date: _ => /\d{4}-\d{2}-\d{2}(T\d{2}:\d{2}(:\d{2}(\.\d+)?)?(Z|[+-]\d{2}:\d{2})?)?/,
+
+    boolean: _ => choice('true', 'false'),
+
+    null: _ => 'null',
+
+    // "escaped\n", 'literal', `backtick`
+    string: $ => choice(
+      seq(
+        '"',
+        repeat(choice($._string_fragment, $.escape_sequence)),
+        '"',
+      ),
+      /'[^']*'/,
+      /`[^`]*`/,
+    ),
+
+    _string_fragment: _ => token.immediate(prec(1, /[^"\\]+/)),
+
+    escape_sequence: _ => token.immediate(/\\(u\{[0-9a-fA-F]+\}|.)/),
+
+    // r#'C:\Users\'#
+    raw_string: _ => /r#+'([^']|'[^#])*'#+/,
+
+    // $"($name) has (ls | length) files", $'($a)\($b)'
+    string_interpolation: $ => choice(
+      seq(
+        '$"',
+        repeat(choice(
+          alias(token.immediate(prec(1, /[^"\\(]+/)), $.string_content),
+          $.escape_sequence,
+          $.interpolation,
+        )),
+        '"',
+      ),
+      seq(
+        '$\'',
+        repeat(choice(
+          alias(token.immediate(prec(1, /[^'(]+/)), $.string_content),
+          $.interpolation,
+        )),
+        '\'',
+      ),
+    ),
+
+    interpolation: $ => seq('(', optional($._statements), ')'),
+
+    // [1 2 3], [a, b, c]
+    list: $ => seq(
+      '[',
+      repeat(choice($._value, $.unquoted, ',')),
+      ']',
+    ),
+
+    // [[name size]; [Cargo.toml 1kb] [README.md 4kb]]
+    table: $ => seq(
+      '[',
+      field('header', $.list),
+      ';',
+      repeat(choice(field('row', $.list), ',')),
+      ']',
+    ),
+
+    // {name: "nu", version: 0.9}; `{}` is an empty record, not a block
+    record: $ => prec(1, seq(
+      '{',
+      repeat(choice($.record_entry, ',')),
+      '}',
+    )),
+
+    record_entry: $ => seq(
+      field('key', choice($._identifier, $.string)),
+      ':',
+      field('value', choice($._expression, $.unquoted)),
+    ),
+
+    block: $ => seq('{', optional($._statements), '}'),
+
+    // {|row| $row.size > 1mb}, {|| date now}
+    closure: $ => seq(
+      '{',
+      field('parameters', $.closure_parameters),
+      optional($._statements),
+      '}',
+    ),
+
+    closure_parameters: $ => seq(
+      '|',
+      repeat(choice($.parameter, ',')),
+      '|',
+    ),
+
+    // (ls | length)
+    subexpression: $ => seq('(', optional($._statements), ')'),
+
+    comment: _ => /#[^\r\n]*/,
+  },
+});
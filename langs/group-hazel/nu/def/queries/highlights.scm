@@ -0,0 +1,207 @@
+(comment) @comment
+
+; Literals
+
+[
+  (string)
+  (raw_string)
+  (string_content)
+] @string
+
+(string_interpolation
+  [
+    "$\""
+    "$'"
+    "\""
+    "'"
+  ] @string)
+
+(escape_sequence) @string.escape
+
+[
+  (number)
+  (filesize)
+  (duration)
+] @number
+
+(date) @string.special
+
+(boolean) @boolean
+
+(null) @constant.builtin
+
+; Commands
+
+(command
+  head: (cmd_identifier) @function.call)
+
+(command
+  head: (external) @function.builtin)
+
+((command
+  head: (cmd_identifier) @keyword.import)
+  (#any-of? @keyword.import
+    "use" "source" "source-env" "hide" "hide-env" "overlay" "export-env"))
+
+(function_definition
+  name: [
+    (cmd_identifier)
+    (string)
+  ] @function)
+
+(extern_definition
+  name: [
+    (cmd_identifier)
+    (string)
+  ] @function)
+
+(alias_definition
+  name: [
+    (cmd_identifier)
+    (string)
+  ] @function)
+
+(module_definition
+  name: (identifier) @module)
+
+[
+  (long_flag)
+  (short_flag)
+] @variable.parameter
+
+; Variables
+
+(variable) @variable
+
+((variable) @variable.builtin
+  (#any-of? @variable.builtin "$env" "$nu" "$in" "$it"))
+
+(let_statement
+  name: (identifier) @variable)
+
+(for_statement
+  variable: (identifier) @variable)
+
+(parameter
+  name: (identifier) @variable.parameter)
+
+(member) @property
+
+(record_entry
+  key: [
+    (identifier)
+    (string)
+  ] @property)
+
+(type
+  name: (type_identifier) @type)
+
+(wildcard) @variable.builtin
+
+; Keywords
+
+[
+  "def"
+  "def-env"
+  "extern"
+  "alias"
+  "module"
+  "export"
+] @keyword.function
+
+(let_statement
+  keyword: _ @keyword)
+
+[
+  "if"
+  "else"
+  "match"
+] @keyword.conditional
+
+[
+  "for"
+  "in"
+  "while"
+  "loop"
+  (break_statement)
+  (continue_statement)
+] @keyword.repeat
+
+[
+  "try"
+  "catch"
+] @keyword.exception
+
+"return" @keyword.return
+
+; Operators
+
+(binary_expression
+  operator: _ @operator)
+
+(binary_expression
+  operator: [
+    "and"
+    "or"
+    "xor"
+    "in"
+    "not-in"
+    "starts-with"
+    "ends-with"
+    "like"
+    "not-like"
+    "has"
+    "not-has"
+    "mod"
+    "bit-and"
+    "bit-or"
+    "bit-xor"
+    "bit-shl"
+    "bit-shr"
+  ] @keyword.operator)
+
+(unary_expression
+  "not" @keyword.operator)
+
+(unary_expression
+  "-" @operator)
+
+(assignment
+  operator: _ @operator)
+
+[
+  "|"
+  "="
+  ".."
+  "..<"
+  "..="
+  "..."
+  "=>"
+  "->"
+] @operator
+
+; Punctuation
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ":"
+  ";"
+] @punctuation.delimiter
+
+(cell_path
+  "." @punctuation.delimiter)
+
+(interpolation
+  [
+    "("
+    ")"
+  ] @punctuation.special)
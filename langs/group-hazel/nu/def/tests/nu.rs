@@ -0,0 +1,91 @@
+//! Nushell ends statements at newlines, except inside brackets and before a
+//! `|` on the next line, and parses the holes of interpolated strings as Nu
+//! code in place.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/config.nu");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_nu::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_nu::language(), source, kind)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_newlines_end_statements_outside_brackets() {
+    let source = "\
+let files = ls
+  | where size > 1mb
+  | get name
+let config = {
+  name: nu
+  tags: [shell, data]
+}
+print $files
+";
+    let heads: Vec<_> = nodes(source, "command")
+        .iter()
+        .map(|command| command.split_whitespace().next().unwrap())
+        .collect();
+    assert_eq!(heads, ["ls", "where", "get", "print"]);
+    assert_eq!(nodes(source, "pipeline").len(), 3);
+    assert_eq!(
+        nodes(source, "record_entry"),
+        ["name: nu", "tags: [shell, data]"]
+    );
+}
+
+#[test]
+fn test_interpolation_holes_are_nu_code() {
+    let source = "$\"($user.name) has (ls | length) files\"\n";
+    assert_eq!(
+        nodes(source, "interpolation"),
+        ["($user.name)", "(ls | length)"]
+    );
+    assert_eq!(nodes(source, "cell_path"), ["$user.name"]);
+    assert_eq!(nodes(source, "command"), ["ls", "length"]);
+    assert_eq!(nodes(source, "string_content"), [" has ", " files"]);
+}
+
+#[test]
+fn test_cell_paths_and_flags() {
+    let source = "$env.PATH = ($env.PATH | split row (char esep) | uniq --count)\n";
+    assert_eq!(nodes(source, "cell_path"), ["$env.PATH", "$env.PATH"]);
+    assert_eq!(nodes(source, "member"), ["PATH", "PATH"]);
+    assert_eq!(nodes(source, "long_flag"), ["--count"]);
+}
+
+#[test]
+fn test_sample_captures() {
+    let grammars = [(
+        "nu",
+        TestGrammar::new(arborium_nu::language(), arborium_nu::HIGHLIGHTS_QUERY, ""),
+    )];
+    let html = helpers::highlighter(grammars)
+        .highlight("nu", SAMPLE)
+        .unwrap();
+    assert!(html.contains("<a-k>def</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>mkcd</a-f>"), "{}", html);
+    assert!(
+        html.contains("<a-f>&quot;git recent&quot;</a-f>"),
+        "{}",
+        html
+    );
+    assert!(html.contains("<a-v>--reverse</a-v>"), "{}", html);
+    assert!(html.contains("<a-pr>show_banner</a-pr>"), "{}", html);
+    assert!(html.contains("<a-t>list</a-t>"), "{}", html);
+    // The hole of an interpolated string is highlighted as Nu code
+    assert!(html.contains("<a-f>date</a-f>"), "{}", html);
+    assert!(html.contains("<a-k>match</a-k>"), "{}", html);
+}
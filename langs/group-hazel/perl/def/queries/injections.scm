@@ -12,3 +12,64 @@
   (#match? @_modifiers "e")
   (#not-match? @_modifiers "e.*e")
   (#set! injection.language "perl"))
+
+; Heredocs whose delimiter names a language: <<~SQL ... SQL
+;
+; The tag may have other words around it, separated by underscores, and
+; matches in any case. The body's node ends with the delimiter line, so
+; that line is injected too.
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?sql(_\\w+)?\\s*$")
+ (#set! injection.language "sql"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?json(_\\w+)?\\s*$")
+ (#set! injection.language "json"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?ya?ml(_\\w+)?\\s*$")
+ (#set! injection.language "yaml"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?html(_\\w+)?\\s*$")
+ (#set! injection.language "html"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?xml(_\\w+)?\\s*$")
+ (#set! injection.language "xml"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?css(_\\w+)?\\s*$")
+ (#set! injection.language "css"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(js|javascript)(_\\w+)?\\s*$")
+ (#set! injection.language "javascript"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(graphql|gql)(_\\w+)?\\s*$")
+ (#set! injection.language "graphql"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(py|python)(_\\w+)?\\s*$")
+ (#set! injection.language "python"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(rb|ruby)(_\\w+)?\\s*$")
+ (#set! injection.language "ruby"))
+
+((heredoc_content
+  (heredoc_end) @_tag) @injection.content
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(sh|bash|shell)(_\\w+)?\\s*$")
+ (#set! injection.language "bash"))
@@ -0,0 +1,31 @@
+#!/usr/bin/env perl
+# Prints last week's top customers, and the settings it ran with, as JSON.
+use strict;
+use warnings;
+
+use DBI;
+use JSON::PP;
+
+my $dbh = DBI->connect('dbi:Pg:dbname=shop', undef, undef, { RaiseError => 1 });
+
+my $sth = $dbh->prepare(<<~'SQL');
+    SELECT c.name, sum(o.total) AS spent
+    FROM customers c
+    JOIN orders o ON o.customer_id = c.id
+    WHERE o.created_at > now() - interval '7 days'
+    GROUP BY c.name
+    ORDER BY spent DESC
+    LIMIT ?
+    SQL
+
+my $defaults = decode_json(<<'END_JSON');
+{
+  "limit": 10,
+  "currency": "EUR"
+}
+END_JSON
+
+$sth->execute($defaults->{limit});
+while (my ($name, $spent) = $sth->fetchrow_array) {
+    printf "%-30s %10.2f %s\n", $name, $spent, $defaults->{currency};
+}
@@ -0,0 +1,2 @@
+; php_only parses the same statements as php, so the highlights prepended
+; from arborium-php cover it. Add patterns for code without a php tag here.
@@ -0,0 +1,29 @@
+// Snippets like this one show up in docs without an opening tag.
+
+$prices = ['apple' => 1.25, 'pear' => 0.9, 'plum' => 2.0];
+
+/**
+ * Apply a discount to every price.
+ *
+ * @param array<string, float> $prices
+ * @return array<string, float>
+ */
+function discount(array $prices, float $rate = 0.1): array
+{
+    return array_map(fn (float $price): float => round($price * (1 - $rate), 2), $prices);
+}
+
+foreach (discount($prices) as $fruit => $price) {
+    printf("%-6s %5.2f\n", $fruit, $price);
+}
+
+$report = <<<SQL
+    SELECT fruit, price
+    FROM prices
+    WHERE price > {$prices['pear']}
+    SQL;
+
+echo match (true) {
+    count($prices) > 2 => "many fruits",
+    default => "few fruits",
+};
@@ -0,0 +1,19 @@
+<?php
+
+declare(strict_types=1);
+
+enum Suit: string
+{
+    case Hearts = 'H';
+    case Spades = 'S';
+
+    public function color(): string
+    {
+        return match ($this) {
+            self::Hearts => 'Red',
+            self::Spades => 'Black',
+        };
+    }
+}
+
+echo Suit::from('H')->color(), PHP_EOL;
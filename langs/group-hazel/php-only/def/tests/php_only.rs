@@ -0,0 +1,61 @@
+//! Code without a `<?php` tag only parses as PHP with the php_only grammar.
+//!
+//! The php grammar reads everything before the first tag as template text,
+//! and hands that text to html.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers;
+use arborium_test_harness::tree_sitter::{Language, Tree};
+
+const SNIPPET: &str = "$total = array_sum([1, 2, 3]);\necho \"<b>{$total}</b>\";\n";
+
+const TEMPLATE: &str = "<ul>\n<?php foreach ($items as $item): ?>\n  <li><?= $item ?></li>\n<?php endforeach; ?>\n</ul>\n";
+
+fn parse(language: impl Into<Language>, source: &str) -> Tree {
+    helpers::parse_tree(language, source)
+}
+
+fn highlight_php(source: &str) -> Utf8ParseResult {
+    let result = helpers::plugin_parse(
+        arborium_php::language(),
+        helpers::queries!(arborium_php),
+        source,
+    );
+    result
+}
+
+#[test]
+fn test_php_only_parses_untagged_code() {
+    let tree = parse(arborium_php_only::language(), SNIPPET);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+    assert!(root.to_sexp().contains("echo_statement"));
+}
+
+#[test]
+fn test_php_only_accepts_a_php_tag() {
+    let source = format!("<?php\n{}", SNIPPET);
+    let tree = parse(arborium_php_only::language(), &source);
+    assert!(
+        !tree.root_node().has_error(),
+        "{}",
+        tree.root_node().to_sexp()
+    );
+}
+
+#[test]
+fn test_php_reads_untagged_code_as_text() {
+    let tree = parse(arborium_php::language(), SNIPPET);
+    let sexp = tree.root_node().to_sexp();
+    assert!(sexp.contains("text"), "{}", sexp);
+    assert!(!sexp.contains("echo_statement"), "{}", sexp);
+}
+
+#[test]
+fn test_php_injects_html_into_template_text() {
+    let result = highlight_php(TEMPLATE);
+    let html = helpers::injected_as(&result, TEMPLATE, "html");
+    assert!(html.iter().any(|t| t.contains("<ul>")), "{:?}", html);
+    assert!(html.iter().any(|t| t.contains("<li>")), "{:?}", html);
+    assert!(html.iter().all(|t| !t.contains("foreach")), "{:?}", html);
+}
@@ -8,3 +8,49 @@
 (nowdoc
   (nowdoc_body) @injection.content
   (heredoc_end) @injection.language)
+
+; SQL passed to PDO, mysqli and Laravel's DB facade, as a literal first
+; argument that starts like a statement
+; e.g.: $pdo->prepare('SELECT * FROM users WHERE id = ?'), DB::select("...")
+
+(member_call_expression
+  name: (name) @_method
+  arguments: (arguments
+    .
+    (argument
+      [
+        (string
+          .
+          (string_content) @injection.content .)
+        (encapsed_string
+          .
+          (string_content) @injection.content .)
+      ]))
+  (#any-of? @_method "prepare" "query" "exec")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with|replace)\\s")
+  (#set! injection.language "sql"))
+
+(scoped_call_expression
+  scope: (name) @_scope
+  name: (name) @_method
+  arguments: (arguments
+    .
+    (argument
+      [
+        (string
+          .
+          (string_content) @injection.content .)
+        (encapsed_string
+          .
+          (string_content) @injection.content .)
+      ]))
+  (#eq? @_scope "DB")
+  (#any-of? @_method "select" "insert" "update" "delete" "statement" "unprepared" "raw")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with|replace)\\s")
+  (#set! injection.language "sql"))
+
+; Everything outside the php tags is the HTML template
+
+((text) @injection.content
+  (#set! injection.language "html")
+  (#set! injection.combined))
@@ -0,0 +1,42 @@
+<?php
+
+namespace App\Repositories;
+
+use Illuminate\Support\Facades\DB;
+use PDO;
+
+final class OrderRepository
+{
+    public function __construct(private PDO $pdo)
+    {
+    }
+
+    public function find(int $id): ?array
+    {
+        $statement = $this->pdo->prepare('
+            SELECT o.id, o.total, c.email
+            FROM orders o
+            JOIN customers c ON c.id = o.customer_id
+            WHERE o.id = :id
+        ');
+        $statement->execute(['id' => $id]);
+
+        return $statement->fetch(PDO::FETCH_ASSOC) ?: null;
+    }
+
+    public function archiveOlderThan(string $date): int
+    {
+        return $this->pdo->exec("DELETE FROM orders WHERE status = 'archived'");
+    }
+
+    public function revenueByRegion(): array
+    {
+        return DB::select('
+            SELECT c.region, sum(o.total) AS revenue
+            FROM orders o
+            JOIN customers c ON c.id = o.customer_id
+            GROUP BY c.region
+            ORDER BY revenue DESC
+        ');
+    }
+}
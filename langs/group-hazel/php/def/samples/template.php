@@ -0,0 +1,24 @@
+<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <title><?= htmlspecialchars($title) ?></title>
+    <style>
+        .done { text-decoration: line-through; }
+    </style>
+</head>
+<body>
+<?php if (empty($tasks)): ?>
+    <p class="empty">Nothing to do.</p>
+<?php else: ?>
+    <ul>
+    <?php foreach ($tasks as $task): ?>
+        <li class="<?= $task->done ? 'done' : '' ?>"><?= htmlspecialchars($task->title) ?></li>
+    <?php endforeach; ?>
+    </ul>
+<?php endif; ?>
+<script>
+    document.querySelectorAll("li").forEach((li) => li.addEventListener("click", () => li.classList.toggle("done")));
+</script>
+</body>
+</html>
@@ -0,0 +1,88 @@
+; Regular expressions passed to the re module
+; e.g.: re.compile(r"^\d+$"), re.sub(r"\s+", " ", text)
+
+(call
+  function: (attribute
+    object: (identifier) @_re
+    attribute: (identifier) @_fn)
+  arguments: (argument_list
+    .
+    (string
+      (string_content) @injection.content))
+  (#eq? @_re "re")
+  (#any-of? @_fn "compile" "search" "match" "fullmatch" "split" "findall" "finditer" "sub" "subn")
+  (#set! injection.language "regex"))
+
+; SQL passed to DB-API cursors
+; e.g.: cursor.execute("SELECT name FROM users WHERE id = ?", (id,))
+
+(call
+  function: (attribute
+    attribute: (identifier) @_fn)
+  arguments: (argument_list
+    .
+    (string
+      (string_content) @injection.content))
+  (#any-of? @_fn "execute" "executemany" "executescript")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with|pragma)\\s")
+  (#set! injection.language "sql"))
+
+; SQL for SQLAlchemy and pandas
+; e.g.: session.execute(text("SELECT ...")), pd.read_sql("SELECT ...", conn)
+
+(call
+  function: [
+    (identifier) @_fn
+    (attribute
+      attribute: (identifier) @_fn)
+  ]
+  arguments: (argument_list
+    .
+    (string
+      (string_content) @injection.content))
+  (#any-of? @_fn "text" "read_sql" "read_sql_query")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with|pragma)\\s")
+  (#set! injection.language "sql"))
+
+; PEP 484 type comments
+; e.g.: x = []  # type: List[int]
+; The annotation only highlights once `#offset!` skips the `# type: ` prefix.
+
+((comment) @injection.content
+  (#match? @injection.content "^# type: ")
+  (#offset! @injection.content 0 8 0 0)
+  (#set! injection.language "python"))
+
+; TODO:, FIXME(name):, @mentions, #123 and URLs in every other comment
+
+((comment) @injection.content
+  (#not-match? @injection.content "^# type: ")
+  (#set! injection.language "comment")
+  (#set! injection.include-children))
+
+; Interactive sessions in docstrings, as run by doctest
+
+((module
+  .
+  (expression_statement
+    (string
+      (string_content) @injection.content)))
+  (#match? @injection.content "(?m)^\\s*>>> ")
+  (#set! injection.language "pycon"))
+
+([
+  (function_definition
+    body: (block
+      .
+      (expression_statement
+        (string
+          (string_content) @injection.content))))
+  (class_definition
+    body: (block
+      .
+      (expression_statement
+        (string
+          (string_content) @injection.content))))
+]
+  (#match? @injection.content "(?m)^\\s*>>> ")
+  (#set! injection.language "pycon"))
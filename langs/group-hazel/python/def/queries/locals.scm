@@ -0,0 +1,130 @@
+; Scopes
+;-------
+
+[
+  (module)
+  (function_definition)
+  (lambda)
+  (class_definition)
+  (list_comprehension)
+  (set_comprehension)
+  (dictionary_comprehension)
+  (generator_expression)
+] @local.scope
+
+; Definitions
+;------------
+
+; Parameters
+
+(parameters
+  (identifier) @local.definition.variable.parameter)
+
+(lambda_parameters
+  (identifier) @local.definition.variable.parameter)
+
+(default_parameter
+  name: (identifier) @local.definition.variable.parameter)
+
+(typed_parameter
+  .
+  (identifier) @local.definition.variable.parameter)
+
+(typed_default_parameter
+  name: (identifier) @local.definition.variable.parameter)
+
+(parameters
+  [
+    (list_splat_pattern
+      (identifier) @local.definition.variable.parameter)
+    (dictionary_splat_pattern
+      (identifier) @local.definition.variable.parameter)
+  ])
+
+(lambda_parameters
+  [
+    (list_splat_pattern
+      (identifier) @local.definition.variable.parameter)
+    (dictionary_splat_pattern
+      (identifier) @local.definition.variable.parameter)
+  ])
+
+(typed_parameter
+  [
+    (list_splat_pattern
+      (identifier) @local.definition.variable.parameter)
+    (dictionary_splat_pattern
+      (identifier) @local.definition.variable.parameter)
+  ])
+
+; Functions and classes are defined in the scope around them
+
+((function_definition
+  name: (identifier) @local.definition.function)
+ (#set! definition.function.scope "parent"))
+
+((class_definition
+  name: (identifier) @local.definition.type)
+ (#set! definition.type.scope "parent"))
+
+; Variables
+
+(assignment
+  left: (identifier) @local.definition.var)
+
+(assignment
+  left: (pattern_list
+    (identifier) @local.definition.var))
+
+(assignment
+  left: (tuple_pattern
+    (identifier) @local.definition.var))
+
+(augmented_assignment
+  left: (identifier) @local.definition.var)
+
+(named_expression
+  name: (identifier) @local.definition.var)
+
+(for_statement
+  left: (identifier) @local.definition.var)
+
+(for_statement
+  left: (pattern_list
+    (identifier) @local.definition.var))
+
+(for_in_clause
+  left: (identifier) @local.definition.var)
+
+(for_in_clause
+  left: (pattern_list
+    (identifier) @local.definition.var))
+
+(as_pattern
+  alias: (as_pattern_target
+    (identifier) @local.definition.var))
+
+(global_statement
+  (identifier) @local.definition.var)
+
+(nonlocal_statement
+  (identifier) @local.definition.var)
+
+; Imports
+
+(import_statement
+  name: (dotted_name
+    .
+    (identifier) @local.definition.import))
+
+(aliased_import
+  alias: (identifier) @local.definition.import)
+
+(import_from_statement
+  name: (dotted_name
+    (identifier) @local.definition.import))
+
+; References
+;-----------
+
+(identifier) @local.reference
@@ -0,0 +1,59 @@
+"""Keep track of items in a small SQLite-backed inventory.
+
+>>> inv = Inventory(":memory:")
+>>> inv.add("widget", 3)
+>>> inv.count("widget")
+3
+"""
+
+import re
+import sqlite3
+from typing import Dict, List
+
+SKU = re.compile(r"^[A-Z]{3}-\d{4}$")
+
+
+class Inventory:
+    """An inventory stored in a SQLite database.
+
+    >>> Inventory(":memory:").names()
+    []
+    """
+
+    def __init__(self, path: str = "inventory.db") -> None:
+        self.db = sqlite3.connect(path)
+        cursor = self.db.cursor()
+        cursor.execute("CREATE TABLE IF NOT EXISTS items (name TEXT PRIMARY KEY, count INTEGER)")
+
+    def add(self, name, count=1, *tags, **extra):
+        cursor = self.db.cursor()
+        cursor.execute(
+            "INSERT INTO items (name, count) VALUES (?, ?) "
+            "ON CONFLICT(name) DO UPDATE SET count = count + excluded.count",
+            (name, count),
+        )
+        return name
+
+    def count(self, name: str) -> int:
+        row = self.db.execute("SELECT count FROM items WHERE name = ?", (name,)).fetchone()
+        return row[0] if row else 0
+
+    def names(self):
+        rows = self.db.execute("SELECT name FROM items ORDER BY name")
+        return [name for (name,) in rows]
+
+
+def parse_skus(text):
+    # type: (str) -> List[str]
+    """Find every SKU-looking token in ``text``."""
+    words = re.split(r"\s+", text.strip())
+    return [word for word in words if SKU.match(word)]
+
+
+def totals(lines):
+    counts = {}  # type: Dict[str, int]
+    for line in lines:
+        if (match := re.match(r"(\w+)\s*=\s*(\d+)", line)) is not None:
+            name, count = match.groups()
+            counts[name] = counts.get(name, 0) + int(count)
+    return counts
@@ -0,0 +1,36 @@
+"""Monthly sales reports."""
+
+import pandas as pd
+from sqlalchemy import create_engine, text
+
+engine = create_engine("postgresql://reports@localhost/shop")
+
+MONTHLY_TOTALS = text("""
+    SELECT date_trunc('month', o.created_at) AS month,
+           c.region,
+           sum(o.total) AS revenue,
+           count(*) AS orders
+    FROM orders o
+    JOIN customers c ON c.id = o.customer_id
+    WHERE o.created_at >= :since
+    GROUP BY 1, 2
+    ORDER BY 1, 2
+""")
+
+
+def monthly_totals(since):
+    with engine.connect() as conn:
+        return conn.execute(MONTHLY_TOTALS, {"since": since}).fetchall()
+
+
+def top_products(limit=10):
+    return pd.read_sql(
+        """
+        SELECT p.name, sum(i.quantity) AS sold
+        FROM order_items i
+        JOIN products p ON p.id = i.product_id
+        GROUP BY p.name
+        ORDER BY sold DESC
+        """,
+        engine,
+    ).head(limit)
@@ -0,0 +1,24 @@
+//! Incremental parses must match fresh parses, whatever the edits.
+//!
+//! The demo sample plus a short document heavy on multibyte text. Set
+//! `ARBORIUM_EDIT_STEPS` for longer runs.
+
+use arborium_test_harness::edits::test_incremental_edits;
+
+#[test]
+fn test_python_edits() {
+    test_incremental_edits(
+        arborium_python::language(),
+        "python",
+        &arborium_python::HIGHLIGHTS_QUERY,
+        arborium_python::INJECTIONS_QUERY,
+        arborium_python::LOCALS_QUERY,
+        &[
+            ("sample.py", include_str!("../sample.py")),
+            (
+                "multibyte",
+                "def größe(λ):\n    \"\"\"Größe in 日本語 🐍\"\"\"\n    s = f\"é{λ!r}\"\n    return len(s)  # ünïcödé\n",
+            ),
+        ],
+    );
+}
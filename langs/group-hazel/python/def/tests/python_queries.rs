@@ -0,0 +1,89 @@
+//! Python's injections and locals queries.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::assertions::{failed_assertions, parse_assertions};
+use arborium_test_harness::helpers;
+
+fn highlight(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_python::language(),
+        helpers::queries!(arborium_python),
+        source,
+    )
+}
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injections(&highlight(source), source)
+}
+
+#[test]
+fn test_re_calls_inject_regex() {
+    let source = "import re\nDIGITS = re.compile(r\"\\d+\")\nre.sub(\"a\", \"b\", s)\n";
+    assert_eq!(
+        injected(source),
+        [("\\d+", "regex".to_string()), ("a", "regex".to_string())]
+    );
+}
+
+#[test]
+fn test_other_calls_dont_inject_regex() {
+    assert!(injected("regex.compile(r\"\\d+\")\nre.escape(\"a.b\")\n").is_empty());
+}
+
+#[test]
+fn test_cursor_queries_inject_sql() {
+    let source = "cursor.execute(\"SELECT * FROM items\")\ncursor.execute(\"hello\")\n";
+    assert_eq!(
+        injected(source),
+        [("SELECT * FROM items", "sql".to_string())]
+    );
+}
+
+#[test]
+fn test_type_comments_inject_python() {
+    let source = "x = []  # type: List[int]\ny = 1  # not a type\n";
+    let injections = injected(source);
+    assert_eq!(injections.len(), 2);
+    assert_eq!(injections[0].1, "python");
+    assert!(injections[0].0.starts_with("# type: "));
+    // Other comments get the comment grammar instead
+    assert_eq!(injections[1], ("# not a type", "comment".to_string()));
+}
+
+#[test]
+fn test_docstrings_with_examples_inject_pycon() {
+    let source = "\
+def double(x):
+    \"\"\"Double x.
+
+    >>> double(2)
+    4
+    \"\"\"
+    return x * 2
+
+def half(x):
+    \"\"\"Halve x.\"\"\"
+    return x / 2
+";
+    let injections = injected(source);
+    assert_eq!(injections.len(), 1);
+    assert_eq!(injections[0].1, "pycon");
+    assert!(injections[0].0.contains(">>> double(2)"));
+}
+
+#[test]
+#[ignore = "the plugin runtime doesn't resolve locals yet"]
+fn test_parameters_highlight_at_use_sites() {
+    let source = "\
+def scale(factor, *values, **options):
+    total = factor * sum(values)
+#           ^ variable.parameter
+#                        ^ variable.parameter
+    return options.get(\"round\", total)
+#          ^ variable.parameter
+#                               ^ !variable.parameter
+";
+    let assertions = parse_assertions(source, &["#"]).expect("Invalid assertions");
+    let failures = failed_assertions(source, &highlight(source), &assertions);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
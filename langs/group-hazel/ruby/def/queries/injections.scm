@@ -0,0 +1,71 @@
+; Heredocs whose delimiter names a language: <<~SQL ... SQL
+;
+; The tag may have other words around it, separated by underscores, and
+; matches in any case. Interpolations split the body into several pieces,
+; each injected on its own.
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?sql(_\\w+)?\\s*$")
+ (#set! injection.language "sql"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?json(_\\w+)?\\s*$")
+ (#set! injection.language "json"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?ya?ml(_\\w+)?\\s*$")
+ (#set! injection.language "yaml"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?html(_\\w+)?\\s*$")
+ (#set! injection.language "html"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?xml(_\\w+)?\\s*$")
+ (#set! injection.language "xml"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?css(_\\w+)?\\s*$")
+ (#set! injection.language "css"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(js|javascript)(_\\w+)?\\s*$")
+ (#set! injection.language "javascript"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(graphql|gql)(_\\w+)?\\s*$")
+ (#set! injection.language "graphql"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(py|python)(_\\w+)?\\s*$")
+ (#set! injection.language "python"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(rb|ruby)(_\\w+)?\\s*$")
+ (#set! injection.language "ruby"))
+
+((heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @_tag)
+ (#match? @_tag "(?i)^\\s*(\\w+_)?(sh|bash|shell)(_\\w+)?\\s*$")
+ (#set! injection.language "bash"))
@@ -0,0 +1,38 @@
+# frozen_string_literal: true
+
+require "json"
+
+# Exports the monthly sales report, both as rows from the database and as
+# a JSON document for the dashboard.
+class ReportExport
+  def initialize(connection, month)
+    @connection = connection
+    @month = month
+  end
+
+  def rows
+    @connection.exec_params(<<~SQL, [@month])
+      SELECT region, sum(total) AS revenue, count(*) AS orders
+      FROM orders
+      WHERE date_trunc('month', created_at) = $1
+      GROUP BY region
+      ORDER BY revenue DESC
+    SQL
+  end
+
+  def manifest
+    JSON.parse(<<~JSON)
+      {
+        "report": "monthly-sales",
+        "columns": ["region", "revenue", "orders"],
+        "cache": { "ttl": 3600 }
+      }
+    JSON
+  end
+
+  def summary
+    <<~TEXT
+      Sales report for #{@month}: #{rows.count} regions.
+    TEXT
+  end
+end
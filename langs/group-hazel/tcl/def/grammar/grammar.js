@@ -0,0 +1,328 @@
+/**
+ * @file Tcl grammar for tree-sitter
+ * @license MIT
+ *
+ * Tcl scripts, and Expect scripts built on them: commands made of words,
+ * with `$var` and `[command]` substitution in bare and double-quoted
+ * words, and none in braced ones. Braces only hold code by convention, so
+ * the bodies and conditions of the core control commands (`proc`, `if`,
+ * `foreach`, `while`, `for`, `try`, `catch`, `namespace eval`, `expr`,
+ * and Expect's `expect`) are parsed as scripts and expressions, and any
+ * other braced word stays a literal.
+ *
+ * A newline or `;` ends a command where one can end; inside braces and
+ * brackets newlines are plain whitespace.
+ * https://www.tcl-lang.org/man/tcl/TclCmd/Tcl.htm
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'tcl',
+
+  extras: _ => [/\s/, /\\\r?\n/],
+
+  rules: {
+    source_file: $ => optional($._commands),
+
+    _commands: $ => prec(1, seq(
+      repeat(seq($._command, $._terminator)),
+      $._command,
+      optional($._terminator),
+    )),
+
+    _terminator: _ => choice(';', /\r?\n/),
+
+    _command: $ => choice(
+      $.comment,
+      $.procedure,
+      $.if,
+      $.foreach,
+      $.while,
+      $.for,
+      $.try,
+      $.catch,
+      $.namespace,
+      $.set,
+      $.global,
+      $.expr_command,
+      $.expect,
+      $.command,
+    ),
+
+    // A comment is a command that starts with `#`
+    comment: _ => token(prec(1, /#[^\r\n]*/)),
+
+    command: $ => seq(
+      field('name', $._word),
+      repeat(field('argument', $._word)),
+    ),
+
+    _word: $ => choice(
+      $.simple_word,
+      $.quoted_word,
+      $.braced_word,
+      $.variable_substitution,
+      $.command_substitution,
+      $.escaped_character,
+      $.argument_expansion,
+    ),
+
+    // A run of characters with no substitution in it. A word can't start
+    // with `(` here, so `$array(key)` reads as one variable; a lone
+    // parenthesised word still parses, at a lower precedence.
+    simple_word: $ => choice(
+      /[^\s\\\[\]{}$";(][^\s\\\[\]{}$";]*/,
+      token(prec(-1, /\([^\s\\\[\]{}$";]*/)),
+    ),
+
+    escaped_character: _ => /\\([^\r\n]|x[0-9a-fA-F]{1,2}|u[0-9a-fA-F]{1,4})/,
+
+    // "Hello, $name: [clock format $now]"
+    quoted_word: $ => seq(
+      '"',
+      repeat(choice(
+        token.immediate(prec(1, /([^"\\$\[]|\$[^A-Za-z0-9_:{("])+/)),
+        $.escaped_character,
+        $.variable_substitution,
+        $.command_substitution,
+      )),
+      '"',
+    ),
+
+    // {no $substitution [here]}
+    braced_word: $ => seq(
+      '{',
+      repeat(choice(
+        /([^{}\\]|\\(.|\r?\n))+/,
+        $.braced_word,
+      )),
+      '}',
+    ),
+
+    // {*}$args
+    argument_expansion: $ => seq(
+      '{*}',
+      choice(
+        $.simple_word,
+        $.quoted_word,
+        $.braced_word,
+        $.variable_substitution,
+        $.command_substitution,
+      ),
+    ),
+
+    // $name, $::env(HOME), $opts($key), ${name with spaces}
+    variable_substitution: $ => choice(
+      seq(
+        '$',
+        field('name', alias(
+          token.immediate(/(::)?[A-Za-z0-9_]+(::+[A-Za-z0-9_]+)*/),
+          $.variable_name,
+        )),
+        optional(field('index', $.array_index)),
+      ),
+      seq('${', field('name', alias(/[^}]*/, $.variable_name)), '}'),
+    ),
+
+    array_index: $ => seq(
+      token.immediate('('),
+      repeat(choice(
+        /[^)$\[\\]+/,
+        $.escaped_character,
+        $.variable_substitution,
+        $.command_substitution,
+      )),
+      ')',
+    ),
+
+    // [llength $items]
+    command_substitution: $ => seq('[', optional($._commands), ']'),
+
+    // A braced script: a procedure or loop body
+    body: $ => seq('{', optional($._commands), '}'),
+
+    // proc ::app::greet {name {greeting Hello}} { ... }
+    procedure: $ => seq(
+      'proc',
+      field('name', $._word),
+      field('arguments', choice($.arguments, $.simple_word)),
+      field('body', $.body),
+    ),
+
+    arguments: $ => seq(
+      '{',
+      repeat(choice(
+        alias($.simple_word, $.argument),
+        $.argument_with_default,
+      )),
+      '}',
+    ),
+
+    argument_with_default: $ => seq(
+      '{',
+      field('name', alias($.simple_word, $.argument)),
+      optional(field('default', $._word)),
+      '}',
+    ),
+
+    if: $ => seq(
+      'if',
+      field('condition', $._expression_word),
+      optional('then'),
+      field('consequence', $.body),
+      repeat($.elseif),
+      optional($.else),
+    ),
+
+    elseif: $ => seq(
+      'elseif',
+      field('condition', $._expression_word),
+      optional('then'),
+      field('consequence', $.body),
+    ),
+
+    else: $ => seq('else', field('alternative', $.body)),
+
+    // foreach {key value} $pairs { ... }
+    foreach: $ => seq(
+      'foreach',
+      field('variables', $._word),
+      field('list', $._word),
+      field('body', $.body),
+    ),
+
+    while: $ => seq(
+      'while',
+      field('condition', $._expression_word),
+      field('body', $.body),
+    ),
+
+    for: $ => seq(
+      'for',
+      field('start', $.body),
+      field('condition', $._expression_word),
+      field('next', $.body),
+      field('body', $.body),
+    ),
+
+    // try { ... } on error {msg opts} { ... } finally { ... }
+    try: $ => seq(
+      'try',
+      field('body', $.body),
+      repeat($.handler),
+      optional($.finally),
+    ),
+
+    handler: $ => seq(
+      choice('on', 'trap'),
+      field('code', $._word),
+      field('variables', $._word),
+      field('body', $.body),
+    ),
+
+    finally: $ => seq('finally', field('body', $.body)),
+
+    // catch { ... } result options
+    catch: $ => seq(
+      'catch',
+      field('body', $.body),
+      optional(field('result', $._word)),
+      optional(field('options', $._word)),
+    ),
+
+    // namespace eval ::app { ... }, namespace export greet
+    namespace: $ => seq(
+      'namespace',
+      choice(
+        seq('eval', field('name', $._word), field('body', $.body)),
+        seq(
+          field('subcommand', $.simple_word),
+          repeat(field('argument', $._word)),
+        ),
+      ),
+    ),
+
+    set: $ => seq(
+      'set',
+      field('name', $._word),
+      optional(field('value', $._word)),
+    ),
+
+    global: $ => seq('global', repeat1(field('name', $._word))),
+
+    expr_command: $ => seq('expr', repeat1($._expression_word)),
+
+    _expression_word: $ => choice(
+      $.expr,
+      $.simple_word,
+      $.quoted_word,
+      $.variable_substitution,
+      $.command_substitution,
+    ),
+
+    // {$count > 0 && [llength $queue] < $max}
+    expr: $ => seq(
+      '{',
+      repeat(choice(
+        $.number,
+        $.operator,
+        alias(/[A-Za-z_][\w:]*/, $.simple_word),
+        '(',
+        ')',
+        ',',
+        $.quoted_word,
+        $.braced_word,
+        $.escaped_character,
+        $.variable_substitution,
+        $.command_substitution,
+      )),
+      '}',
+    ),
+
+    number: _ => /(0[xX][0-9a-fA-F]+|0[bB][01]+|0[oO][0-7]+|\d+(\.\d*)?([eE][+-]?\d+)?|\.\d+([eE][+-]?\d+)?)/,
+
+    operator: _ => choice(
+      '**', '*', '/', '%', '+', '-', '~', '!',
+      '<<', '>>', '<', '>', '<=', '>=', '==', '!=',
+      '&', '^', '|', '&&', '||', '?', ':',
+    ),
+
+    // expect {
+    //   -re {password:} { send "$pass\r"; exp_continue }
+    //   timeout { exit 1 }
+    // }
+    //
+    // A brace straight after `expect` always opens a block of clauses;
+    // patterns can only be braced inside one.
+    expect: $ => seq(
+      choice('expect', 'expect_before', 'expect_after'),
+      choice(
+        seq('{', repeat($.expect_clause), '}'),
+        alias($._inline_expect_clause, $.expect_clause),
+      ),
+    ),
+
+    expect_clause: $ => prec.right(seq(
+      repeat(field('flag', $.flag)),
+      field('pattern', choice($._pattern, $.braced_word)),
+      optional(field('body', $.body)),
+    )),
+
+    _inline_expect_clause: $ => prec.right(seq(
+      repeat(field('flag', $.flag)),
+      field('pattern', $._pattern),
+      optional(field('body', $.body)),
+    )),
+
+    _pattern: $ => choice(
+      $.simple_word,
+      $.quoted_word,
+      $.variable_substitution,
+      $.command_substitution,
+    ),
+
+    flag: _ => token(prec(1, /-[A-Za-z]+/)),
+  },
+});
@@ -0,0 +1,183 @@
+(comment) @comment
+
+; Words
+
+[
+  (quoted_word)
+  (braced_word)
+] @string
+
+(escaped_character) @string.escape
+
+(number) @number
+
+; Commands
+
+(command
+  name: (simple_word) @function.call)
+
+((command
+  name: (simple_word) @keyword)
+  (#any-of? @keyword "uplevel" "upvar" "variable" "error" "rename" "apply"))
+
+((command
+  name: (simple_word) @keyword.return)
+  (#eq? @keyword.return "return"))
+
+((command
+  name: (simple_word) @keyword.repeat)
+  (#any-of? @keyword.repeat "break" "continue"))
+
+((command
+  name: (simple_word) @keyword.import)
+  (#any-of? @keyword.import "package" "source"))
+
+((command
+  argument: (simple_word) @variable.parameter)
+  (#match? @variable.parameter "^-[A-Za-z]"))
+
+(procedure
+  name: (simple_word) @function)
+
+(argument) @variable.parameter
+
+[
+  "set"
+  "global"
+  "expr"
+] @function.builtin
+
+(namespace
+  name: (simple_word) @module)
+
+(namespace
+  subcommand: (simple_word) @function.method)
+
+; Variables
+
+(variable_substitution) @variable
+
+(variable_substitution
+  [
+    "$"
+    "${"
+  ] @punctuation.special)
+
+(set
+  name: (simple_word) @variable)
+
+(global
+  name: (simple_word) @variable)
+
+(foreach
+  variables: [
+    (simple_word)
+    (braced_word)
+  ] @variable)
+
+; Expressions
+
+(expr
+  (simple_word) @function.call
+  .
+  "(")
+
+((expr
+  (simple_word) @keyword.operator)
+  (#any-of? @keyword.operator "eq" "ne" "in" "ni"))
+
+((expr
+  (simple_word) @boolean)
+  (#any-of? @boolean "true" "false" "yes" "no" "on" "off"))
+
+(operator) @operator
+
+; Expect
+
+(expect_clause
+  pattern: (simple_word) @constant.builtin)
+
+(flag) @variable.parameter
+
+; Keywords
+
+"proc" @keyword.function
+
+[
+  "if"
+  "then"
+  "elseif"
+  "else"
+] @keyword.conditional
+
+[
+  "foreach"
+  "while"
+  "for"
+] @keyword.repeat
+
+[
+  "try"
+  "on"
+  "trap"
+  "finally"
+  "catch"
+] @keyword.exception
+
+[
+  "namespace"
+  "eval"
+  "expect"
+  "expect_before"
+  "expect_after"
+] @keyword
+
+"{*}" @operator
+
+; Punctuation
+
+(command_substitution
+  [
+    "["
+    "]"
+  ] @punctuation.special)
+
+(body
+  [
+    "{"
+    "}"
+  ] @punctuation.bracket)
+
+(arguments
+  [
+    "{"
+    "}"
+  ] @punctuation.bracket)
+
+(argument_with_default
+  [
+    "{"
+    "}"
+  ] @punctuation.bracket)
+
+(expr
+  [
+    "{"
+    "}"
+  ] @punctuation.bracket)
+
+(expect
+  [
+    "{"
+    "}"
+  ] @punctuation.bracket)
+
+[
+  "("
+  ")"
+] @punctuation.bracket
+
+[
+  ","
+  ";"
+] @punctuation.delimiter
@@ -0,0 +1,98 @@
+//! Tcl substitutes `$var` and `[command]` in bare and quoted words but not
+//! in braced ones, except where a core command treats the braces as a
+//! script or an expression.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/build.tcl");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_tcl::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_tcl::language(), source, kind)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_braces_suppress_substitution() {
+    let source = "puts \"$name: [llength $items]\"\nputs {$name: [llength $items]}\n";
+    assert_eq!(nodes(source, "variable_substitution"), ["$name", "$items"]);
+    assert_eq!(nodes(source, "command_substitution"), ["[llength $items]"]);
+    assert_eq!(nodes(source, "braced_word"), ["{$name: [llength $items]}"]);
+}
+
+#[test]
+fn test_control_bodies_are_scripts() {
+    let source = "\
+foreach item $items {
+    if {$item ne {}} { lappend seen $opts($item) }
+}
+";
+    assert_eq!(nodes(source, "braced_word"), ["{}"]);
+    assert_eq!(nodes(source, "expr").len(), 1);
+    assert_eq!(nodes(source, "command"), ["lappend seen $opts($item)"]);
+    assert_eq!(nodes(source, "array_index"), ["($item)"]);
+}
+
+#[test]
+fn test_namespaces_and_procedures() {
+    let source = "\
+namespace eval ::app {
+    variable count 0
+    namespace export greet
+}
+proc ::app::greet {name {greeting Hello}} {
+    incr ::app::count
+    return \"$greeting, $name\"
+}
+";
+    assert_eq!(nodes(source, "namespace").len(), 2);
+    assert_eq!(nodes(source, "argument"), ["name", "greeting"]);
+    assert_eq!(nodes(source, "variable_name"), ["greeting", "name"]);
+}
+
+#[test]
+fn test_expect_clauses() {
+    let source = "\
+spawn ssh $host
+expect {
+    -re {[Pp]assword:} { send \"$pass\\r\"; exp_continue }
+    timeout { exit 1 }
+    eof
+}
+";
+    assert_eq!(nodes(source, "expect_clause").len(), 3);
+    assert_eq!(nodes(source, "flag"), ["-re"]);
+    assert_eq!(nodes(source, "escaped_character"), ["\\r"]);
+}
+
+#[test]
+fn test_sample_captures() {
+    let grammars = [(
+        "tcl",
+        TestGrammar::new(arborium_tcl::language(), arborium_tcl::HIGHLIGHTS_QUERY, ""),
+    )];
+    let html = helpers::highlighter(grammars)
+        .highlight("tcl", SAMPLE)
+        .unwrap();
+    assert!(html.contains("<a-k>proc</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>::build::log</a-f>"), "{}", html);
+    assert!(html.contains("<a-v>stamp</a-v>"), "{}", html);
+    assert!(html.contains("<a-v>-vhdl2008</a-v>"), "{}", html);
+    assert!(html.contains("<a-k>eq</a-k>"), "{}", html);
+    // Braced constraints stay a literal string, commands and all
+    assert!(
+        html.contains("[get_ports clk]\n        create_clock"),
+        "{}",
+        html
+    );
+}
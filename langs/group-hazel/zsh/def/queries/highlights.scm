@@ -1,19 +1,37 @@
-; Comments
-(comment) @comment
+; Zsh highlights
+;
+; The bash highlights are prepended from arborium-bash, and the grammar is
+; bash's, so this only adds what zsh has on top: its builtins and the
+; declaration keywords bash leaves unhighlighted. Best-effort; see
+; grammar/grammar.js for the syntax that doesn't parse.
 
-; Strings
-(string) @string
-(raw_string) @string
-(escape_sequence) @string.escape
+((command_name
+  (word) @function.builtin)
+ (#any-of? @function.builtin
+  "autoload" "bindkey" "builtin" "compdef" "compinit" "compctl" "disable" "emulate" "enable"
+  "fc" "functions" "hash" "history" "print" "printf" "promptinit" "rehash" "setopt" "source"
+  "typeset" "unfunction" "unhash" "unsetopt" "whence" "where" "which" "zcompile" "zformat"
+  "zle" "zmodload" "zparseopts" "zstyle" "add-zsh-hook" "vcs_info" "alias" "unalias" "eval"
+  "exec" "cd" "pushd" "popd" "echo" "read" "shift" "trap" "wait" "test" "true" "false"))
 
-; Variables
-(expansion) @variable
+[
+  "declare"
+  "local"
+  "readonly"
+  "typeset"
+] @keyword
 
-; Command substitution
-(command_substitution) @embedded
+[
+  "[["
+  "]]"
+  "(("
+  "))"
+] @punctuation.bracket
 
-; Numbers
-(number) @number
+(special_variable_name) @variable.builtin
 
-; Words (commands, arguments, etc) - low priority
-(word) @string.special
+((variable_name) @variable.builtin
+ (#any-of? @variable.builtin
+  "PROMPT" "RPROMPT" "PS1" "RPS1" "PS2" "PATH" "path" "fpath" "FPATH" "HISTFILE" "HISTSIZE"
+  "SAVEHIST" "ZDOTDIR" "ZSH" "ZSH_THEME" "plugins" "precmd_functions" "preexec_functions"
+  "WORDCHARS" "LS_COLORS" "EDITOR" "VISUAL" "PAGER"))
@@ -0,0 +1,61 @@
+//! Zsh is parsed with the bash grammar. A typical zshrc should come out
+//! clean, and the zsh-only syntax bash can't parse should only break the
+//! word it's in.
+
+use arborium_test_harness::helpers;
+use arborium_test_harness::tree_sitter::{Node, Tree};
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_zsh::language(), source)
+}
+
+fn commands<'a>(node: Node, source: &'a str, out: &mut Vec<&'a str>) {
+    if node.kind() == "command_name" {
+        out.push(&source[node.byte_range()]);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        commands(child, source, out);
+    }
+}
+
+#[test]
+fn test_zshrc_parses_cleanly() {
+    let source = "\
+HISTFILE=\"$HOME/.zsh_history\"
+setopt SHARE_HISTORY AUTO_CD
+typeset -U path fpath
+path=(\"$HOME/.local/bin\" $path)
+
+autoload -Uz compinit vcs_info add-zsh-hook
+compinit -C
+zstyle ':completion:*' matcher-list 'm:{a-z}={A-Za-z}'
+zstyle ':vcs_info:git:*' formats ' %F{magenta}(%b)%f'
+
+PROMPT='%F{cyan}%~%f${vcs_info_msg_0_} %# '
+_prompt_precmd() {
+  vcs_info
+}
+add-zsh-hook precmd _prompt_precmd
+bindkey -M menuselect '^[[Z' reverse-menu-complete
+alias -g G='| grep -i'
+[[ -r ~/.zshrc.local ]] && source ~/.zshrc.local
+";
+    let tree = parse(source);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_unsupported_syntax_stays_local() {
+    let source = "\
+echo before
+print -l ${(s:,:)list}
+echo after
+";
+    let tree = parse(source);
+    let mut names = Vec::new();
+    commands(tree.root_node(), source, &mut names);
+    let echoes = names.iter().filter(|name| **name == "echo").count();
+    assert_eq!(echoes, 2, "{}", tree.root_node().to_sexp());
+}
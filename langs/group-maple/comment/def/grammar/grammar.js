@@ -0,0 +1,52 @@
+/**
+ * @file Comment grammar for tree-sitter
+ * @license MIT
+ *
+ * The text of a comment, as injected by host languages: `TODO:`-style tags
+ * (optionally naming someone, as in `TODO(sev):`), `@user` mentions,
+ * `#123` issue references and URLs. Everything else is plain text, so any
+ * comment parses, comment markers included.
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'comment',
+
+  extras: _ => [/\s/],
+
+  rules: {
+    source: $ => repeat(choice(
+      $.tag,
+      alias($.name, $.text),
+      $.user,
+      $.issue,
+      $.uri,
+      $._word,
+    )),
+
+    // TODO:, FIXME(sev):, SAFETY:
+    tag: $ => seq(
+      $.name,
+      optional(seq(
+        token.immediate('('),
+        alias(/[^()\s]+/, $.user),
+        ')',
+      )),
+      token.immediate(':'),
+    ),
+
+    name: _ => token(prec(1, /[A-Z][A-Z0-9_-]*[A-Z0-9]/)),
+
+    // @sev, @bearcove/reviewers
+    user: _ => /@[A-Za-z0-9_][A-Za-z0-9_\/-]*/,
+
+    // #42
+    issue: _ => /#[0-9]+/,
+
+    uri: _ => /https?:\/\/[^\s<>"]*[^\s<>".,;:!?'")\]]/,
+
+    _word: _ => choice(/[^\s#@:()]+/, /[#@:()]/),
+  },
+});
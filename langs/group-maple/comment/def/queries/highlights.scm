@@ -0,0 +1,33 @@
+; Tags are only highlighted when they're ones editors know; `Example:` or
+; `HTTP:` stay plain text.
+
+((tag
+  (name) @comment.todo)
+  (#any-of? @comment.todo "TODO" "WIP"))
+
+((tag
+  (name) @comment.note)
+  (#any-of? @comment.note "NOTE" "INFO" "DOCS" "PERF" "TEST" "OPTIMIZE"))
+
+((tag
+  (name) @comment.warning)
+  (#any-of? @comment.warning "HACK" "WARNING" "WARN" "XXX" "SAFETY" "DEPRECATED"))
+
+((tag
+  (name) @comment.error)
+  (#any-of? @comment.error "FIXME" "FIX" "BUG" "ERROR"))
+
+(tag
+  [
+    "("
+    ")"
+  ] @punctuation.bracket)
+
+(tag
+  ":" @punctuation.delimiter)
+
+(user) @constant
+
+(issue) @number
+
+(uri) @markup.link.url
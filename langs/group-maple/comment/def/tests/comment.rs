@@ -0,0 +1,116 @@
+//! Tags, mentions, issue references and URLs in comments, on their own and
+//! injected into the comments of other languages.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/tags.txt");
+const RUST: &str = include_str!("../../../../group-birch/rust/def/samples/todo_comments.rs");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_comment::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_comment::language(), source, kind)
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "comment",
+            TestGrammar::new(
+                arborium_comment::language(),
+                arborium_comment::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "python",
+            TestGrammar::new(
+                arborium_python::language(),
+                &arborium_python::HIGHLIGHTS_QUERY,
+                arborium_python::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                arborium_javascript::INJECTIONS_QUERY,
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_tags_need_a_colon() {
+    let source = "// TODO(sev): fix #42, TODO later, NOTE : spaced, HTTP/2 FIXME:";
+    assert_eq!(nodes(source, "tag"), ["TODO(sev):", "FIXME:"]);
+    assert_eq!(nodes(source, "name"), ["TODO", "FIXME"]);
+    assert_eq!(nodes(source, "user"), ["sev"]);
+    assert_eq!(nodes(source, "issue"), ["#42"]);
+    // Capitalised words that aren't tags are plain text
+    assert_eq!(nodes(source, "text"), ["TODO", "NOTE"]);
+}
+
+#[test]
+fn test_mentions_and_urls() {
+    let source = "# see https://example.com/a?b=1. Ask @sev or (@bearcove/runtime)";
+    assert_eq!(nodes(source, "uri"), ["https://example.com/a?b=1"]);
+    assert_eq!(nodes(source, "user"), ["@sev", "@bearcove/runtime"]);
+}
+
+#[test]
+fn test_rust_comments_nest_tag_captures() {
+    let html = highlighter().highlight("rust", RUST).unwrap();
+    assert!(html.contains("<a-k>TODO</a-k>"), "{}", html);
+    assert!(html.contains("<a-co>sev</a-co>"), "{}", html);
+    assert!(html.contains("<a-n>#42</a-n>"), "{}", html);
+    assert!(html.contains("<a-l>NOTE</a-l>"), "{}", html);
+    assert!(html.contains("<a-er>FIXME</a-er>"), "{}", html);
+    assert!(html.contains("<a-k>SAFETY</a-k>"), "{}", html);
+    assert!(
+        html.contains("<a-tu>https://github.com/bearcove/arborium/issues/42</a-tu>"),
+        "{}",
+        html
+    );
+}
+
+#[test]
+fn test_other_hosts() {
+    let mut hl = highlighter();
+
+    let html = hl
+        .highlight(
+            "python",
+            "x = 1  # FIXME: off by one\ny = []  # type: List[int]\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-er>FIXME</a-er>"), "{}", html);
+
+    let html = hl
+        .highlight("javascript", "// WIP(@ana): #7\n/** TODO: stays jsdoc */\n")
+        .unwrap();
+    assert!(html.contains("<a-k>WIP</a-k>"), "{}", html);
+    assert!(html.contains("<a-co>@ana</a-co>"), "{}", html);
+    assert!(!html.contains("<a-k>TODO</a-k>"), "{}", html);
+}
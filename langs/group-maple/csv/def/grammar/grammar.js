@@ -0,0 +1,72 @@
+/**
+ * @file CSV grammar for tree-sitter
+ * @license MIT
+ *
+ * Comma-separated values as described by RFC 4180: one record per line,
+ * fields optionally double-quoted, and a quoted field may hold commas,
+ * line breaks and doubled `""` quotes. Each field is tagged with its
+ * column number modulo six (`column1` to `column6`), so highlight queries
+ * can give neighbouring columns different colors.
+ * https://www.rfc-editor.org/rfc/rfc4180
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const COLUMNS = 6;
+
+module.exports = grammar({
+  name: 'csv',
+
+  extras: _ => [],
+
+  rules: {
+    document: $ => seq(
+      repeat(seq(optional($.row), $._newline)),
+      optional($.row),
+    ),
+
+    row: $ => $._column1,
+
+    ...columns(),
+
+    field: $ => choice(
+      /[^,"\r\n][^,\r\n]*/,
+      $.string,
+    ),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        /[^"]+/,
+        alias('""', $.escape_sequence),
+      )),
+      '"',
+    ),
+
+    _newline: _ => /\r?\n/,
+  },
+});
+
+/**
+ * Creates the `_column1` to `_column6` rules, each matching a possibly
+ * empty field and the rest of the row, whose first field is the next
+ * column around
+ *
+ * @returns {Record<string, ($: GrammarSymbols<string>) => RuleOrLiteral>}
+ */
+function columns() {
+  /** @type {Record<string, ($: GrammarSymbols<string>) => RuleOrLiteral>} */
+  const rules = {};
+  for (let n = 1; n <= COLUMNS; n++) {
+    const next = `_column${n % COLUMNS + 1}`;
+    rules[`_column${n}`] = $ => {
+      const rest = seq(',', optional($[next]));
+      return choice(
+        seq(field(`column${n}`, $.field), optional(rest)),
+        rest,
+      );
+    };
+  }
+  return rules;
+}
@@ -0,0 +1,93 @@
+//! CSV and TSV rows tag each field with its column modulo six, which the
+//! highlight queries turn into rotating column colors.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Language;
+
+const CSV: &str = include_str!("../samples/orders.csv");
+const TSV: &str = include_str!("../../../../group-maple/tsv/def/samples/genes.tsv");
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_csv::language(), source, kind)
+}
+
+fn highlight(language: &str, source: &str) -> String {
+    let grammars = [
+        (
+            "csv",
+            TestGrammar::new(arborium_csv::language(), arborium_csv::HIGHLIGHTS_QUERY, ""),
+        ),
+        (
+            "tsv",
+            TestGrammar::new(arborium_tsv::language(), arborium_tsv::HIGHLIGHTS_QUERY, ""),
+        ),
+    ];
+    helpers::highlighter(grammars)
+        .highlight(language, source)
+        .unwrap()
+}
+
+#[test]
+fn test_samples_parse_cleanly() {
+    for (language, sample) in [
+        (Language::from(arborium_csv::language()), CSV),
+        (arborium_tsv::language().into(), TSV),
+    ] {
+        let tree = helpers::parse_tree(language, sample);
+        let root = tree.root_node();
+        assert!(!root.has_error(), "{}", root.to_sexp());
+    }
+}
+
+#[test]
+fn test_quoted_fields() {
+    // Quoted fields hold commas, line breaks and doubled quotes
+    assert_eq!(nodes(CSV, "row").len(), 6);
+    let source = "id,\"Okafor, Chidi\",\"12 Harbour Road\nLagos\",\"say \"\"hi\"\"\"\n";
+    assert_eq!(
+        nodes(source, "string"),
+        [
+            "\"Okafor, Chidi\"",
+            "\"12 Harbour Road\nLagos\"",
+            "\"say \"\"hi\"\"\""
+        ]
+    );
+    assert_eq!(nodes(source, "escape_sequence"), ["\"\"", "\"\""]);
+    assert_eq!(nodes(source, "field").len(), 4);
+}
+
+#[test]
+fn test_columns_rotate() {
+    let html = highlight("csv", "a,,c,d,e,f,g,h\n");
+    assert_eq!(
+        html.trim_end(),
+        "<a-k>a</a-k>,,<a-f>c</a-f>,<a-t>d</a-t>,<a-n>e</a-n>,<a-tg>f</a-tg>,<a-k>g</a-k>,<a-s>h</a-s>"
+    );
+
+    let html = highlight("tsv", "TP53\t17\t-\n");
+    assert_eq!(
+        html.trim_end(),
+        "<a-k>TP53</a-k>\t<a-s>17</a-s>\t<a-f>-</a-f>"
+    );
+}
+
+#[test]
+fn test_highlights() {
+    let html = highlight("csv", CSV);
+    assert!(html.contains("<a-k>order_id</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>total</a-tg>"), "{}", html);
+    assert!(
+        html.contains("<a-f>&quot;Okafor, Chidi&quot;</a-f>"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("<a-t>&quot;Storgatan 8, 411 38 Göteborg&quot;</a-t>"),
+        "{}",
+        html
+    );
+
+    let html = highlight("tsv", TSV);
+    assert!(html.contains("<a-s>BRCA1</a-s>"), "{}", html);
+    assert!(html.contains("<a-tg>-</a-tg>"), "{}", html);
+}
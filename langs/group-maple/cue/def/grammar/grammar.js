@@ -0,0 +1,297 @@
+/**
+ * @file CUE grammar for tree-sitter
+ * @license MIT
+ *
+ * Commas between declarations are optional: CUE inserts them at line
+ * breaks, and nothing else in the language can follow an expression on
+ * the next line.
+ * https://cuelang.org/docs/reference/spec/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  disjunction: 1,
+  unification: 2,
+  or: 3,
+  and: 4,
+  comparison: 5,
+  additive: 6,
+  multiplicative: 7,
+  unary: 8,
+  primary: 9,
+};
+
+module.exports = grammar({
+  name: 'cue',
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.identifier,
+
+  conflicts: $ => [
+    // `[x]: y` is a pattern constraint, `[x]` alone an embedded list
+    [$._label, $.list_lit],
+    // `(x): y` is a dynamic field, `(x)` alone an embedded expression
+    [$._label, $.parenthesized_expression],
+    [$._label, $._operand],
+    [$._label, $.string],
+  ],
+
+  rules: {
+    source_file: $ => seq(
+      repeat($.attribute),
+      optional(seq($.package_clause, optional(','))),
+      repeat(seq($.import_declaration, optional(','))),
+      repeat(seq($._declaration, optional(','))),
+    ),
+
+    package_clause: $ => seq('package', field('name', $.identifier)),
+
+    import_declaration: $ => seq(
+      'import',
+      choice(
+        $.import_spec,
+        seq('(', repeat(seq($.import_spec, optional(','))), ')'),
+      ),
+    ),
+
+    import_spec: $ => seq(optional(field('name', $.identifier)), field('path', $.simple_string_lit)),
+
+    // Declarations
+
+    _declaration: $ => choice(
+      $.field,
+      $.ellipsis,
+      $.embedding,
+      $.let_clause,
+      $.attribute,
+    ),
+
+    field: $ => seq(
+      repeat1(seq($.label, choice(':', '::'))),
+      field('value', $._expression),
+      repeat($.attribute),
+    ),
+
+    label: $ => seq(
+      optional(seq(field('alias', $.identifier), '=')),
+      $._label,
+      optional(choice('?', '!')),
+    ),
+
+    _label: $ => choice(
+      $.identifier,
+      $.simple_string_lit,
+      $.parenthesized_expression,
+      seq('[', optional(seq(field('alias', $.identifier), '=')), $._expression, ']'),
+    ),
+
+    embedding: $ => choice($.comprehension, $._expression),
+
+    let_clause: $ => seq('let', field('name', $.identifier), '=', field('value', $._expression)),
+
+    ellipsis: $ => prec.right(seq('...', optional($._expression))),
+
+    attribute: _ => token(seq('@', /[A-Za-z_$][A-Za-z0-9_$]*/, '(', /([^()]|\([^()]*\))*/, ')')),
+
+    // Comprehensions
+
+    comprehension: $ => seq(
+      choice($.for_clause, $.guard_clause),
+      repeat(seq(optional(','), $._clause)),
+      $.struct_lit,
+    ),
+
+    _clause: $ => choice($.for_clause, $.guard_clause, $.let_clause),
+
+    for_clause: $ => seq(
+      'for',
+      optional(seq(field('key', $.identifier), ',')),
+      field('value', $.identifier),
+      'in',
+      $._expression,
+    ),
+
+    guard_clause: $ => seq('if', $._expression),
+
+    // Expressions
+
+    _expression: $ => choice($.unary_expression, $.binary_expression, $._primary_expression),
+
+    _primary_expression: $ => choice(
+      $._operand,
+      $.selector_expression,
+      $.index_expression,
+      $.slice_expression,
+      $.call_expression,
+    ),
+
+    _operand: $ => choice(
+      $._literal,
+      $.identifier,
+      $.parenthesized_expression,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    selector_expression: $ => prec(PREC.primary, seq(
+      field('operand', $._primary_expression),
+      '.',
+      field('field', choice($.identifier, $.simple_string_lit)),
+    )),
+
+    index_expression: $ => prec(PREC.primary, seq(
+      field('operand', $._primary_expression),
+      '[',
+      $._expression,
+      ']',
+    )),
+
+    slice_expression: $ => prec(PREC.primary, seq(
+      field('operand', $._primary_expression),
+      '[',
+      optional($._expression),
+      ':',
+      optional($._expression),
+      ']',
+    )),
+
+    call_expression: $ => prec(PREC.primary, seq(
+      field('function', $._primary_expression),
+      $.arguments,
+    )),
+
+    arguments: $ => seq(
+      '(',
+      optional(seq($._expression, repeat(seq(',', $._expression)), optional(','))),
+      ')',
+    ),
+
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice('+', '-', '!', '*', '!=', '<', '<=', '>', '>=', '=~', '!~')),
+      field('operand', $._expression),
+    )),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.disjunction, '|'],
+        [PREC.unification, '&'],
+        [PREC.or, '||'],
+        [PREC.and, '&&'],
+        [PREC.comparison, choice('==', '!=', '<', '<=', '>', '>=', '=~', '!~')],
+        [PREC.additive, choice('+', '-')],
+        [PREC.multiplicative, choice('*', '/')],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
+        ),
+      )));
+    },
+
+    // Literals
+
+    _literal: $ => choice(
+      $.number,
+      $.string,
+      $.boolean,
+      $.null,
+      $.top,
+      $.bottom,
+      $.struct_lit,
+      $.list_lit,
+    ),
+
+    struct_lit: $ => seq('{', repeat(seq($._declaration, optional(','))), '}'),
+
+    list_lit: $ => seq(
+      '[',
+      optional(seq(
+        choice($._expression, $.ellipsis, $.comprehension),
+        repeat(seq(',', choice($._expression, $.ellipsis, $.comprehension))),
+        optional(','),
+      )),
+      ']',
+    ),
+
+    boolean: _ => choice('true', 'false'),
+
+    null: _ => 'null',
+
+    top: _ => '_',
+
+    bottom: _ => '_|_',
+
+    number: _ => token(choice(
+      /0[xX][0-9a-fA-F](_?[0-9a-fA-F])*/,
+      /0[oO][0-7](_?[0-7])*/,
+      /0[bB][01](_?[01])*/,
+      /(\d(_?\d)*)?\.?\d(_?\d)*([eE][+-]?\d+)?([KMGTP]i?)?/,
+    )),
+
+    identifier: _ => /[#_]*[A-Za-z$][A-Za-z0-9_$]*|_[A-Za-z0-9_$]+/,
+
+    // Strings
+
+    string: $ => choice(
+      $.simple_string_lit,
+      $.multiline_string_lit,
+      $.bytes_lit,
+      $.raw_string_lit,
+    ),
+
+    simple_string_lit: $ => seq(
+      '"',
+      repeat(choice(
+        token.immediate(prec(1, /[^"\\\n]+/)),
+        $.escape_sequence,
+        $.interpolation,
+      )),
+      '"',
+    ),
+
+    multiline_string_lit: $ => seq(
+      '"""',
+      repeat(choice(
+        token.immediate(prec(1, /[^"\\]+/)),
+        token.immediate('"'),
+        $.escape_sequence,
+        $.interpolation,
+      )),
+      '"""',
+    ),
+
+    bytes_lit: $ => seq(
+      '\'',
+      repeat(choice(
+        token.immediate(prec(1, /[^'\\\n]+/)),
+        $.escape_sequence,
+        $.interpolation,
+      )),
+      '\'',
+    ),
+
+    // #"no \escapes or \(interpolation) here"#
+    raw_string_lit: _ => token(seq('#"', /([^"]|"[^#])*/, '"#')),
+
+    interpolation: $ => seq(token.immediate('\\('), $._expression, ')'),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /[abfnrtv\\'"\/]/,
+        /u[0-9a-fA-F]{4}/,
+        /U[0-9a-fA-F]{8}/,
+        /x[0-9a-fA-F]{2}/,
+        /[0-7]{3}/,
+      ),
+    )),
+
+    comment: _ => token(seq('//', /.*/)),
+  },
+});
@@ -0,0 +1,134 @@
+; Keywords
+
+[
+  "package"
+  "import"
+  "let"
+  "for"
+  "in"
+  "if"
+] @keyword
+
+; Literals
+
+[
+  (simple_string_lit)
+  (multiline_string_lit)
+  (bytes_lit)
+  (raw_string_lit)
+] @string
+
+(escape_sequence) @string.escape
+
+(number) @number
+
+(boolean) @boolean
+
+[
+  (null)
+  (top)
+  (bottom)
+] @constant.builtin
+
+(comment) @comment
+
+; Identifiers
+
+(identifier) @variable
+
+((identifier) @type.definition
+  (#match? @type.definition "^_?#"))
+
+((identifier) @type.builtin
+  (#match? @type.builtin "^(bool|string|bytes|number|int|float|u?int(8|16|32|64|128)?|float(32|64)|rune)$"))
+
+(package_clause
+  name: (identifier) @module)
+
+(import_spec
+  name: (identifier) @module)
+
+(import_spec
+  path: (simple_string_lit) @string.special.path)
+
+; Fields
+
+(label
+  (identifier) @property)
+
+(label
+  (simple_string_lit) @property)
+
+((label
+  (identifier) @type.definition)
+  (#match? @type.definition "^_?#"))
+
+(label
+  alias: (identifier) @variable)
+
+(label
+  [
+    "?"
+    "!"
+  ] @operator)
+
+(selector_expression
+  field: (identifier) @property)
+
+(let_clause
+  name: (identifier) @variable)
+
+(for_clause
+  key: (identifier) @variable)
+
+(for_clause
+  value: (identifier) @variable)
+
+; Calls
+
+(call_expression
+  function: (identifier) @function.builtin
+  (#match? @function.builtin "^(len|close|and|or|div|mod|quo|rem)$"))
+
+(call_expression
+  function: (selector_expression
+    field: (identifier) @function.call))
+
+(call_expression
+  function: (selector_expression
+    operand: (identifier) @module))
+
+(attribute) @attribute
+
+; Operators
+
+(unary_expression
+  operator: _ @operator)
+
+(binary_expression
+  operator: _ @operator)
+
+[
+  "="
+  "..."
+] @operator
+
+[
+  ":"
+  "::"
+  ","
+  "."
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(interpolation
+  "\\(" @punctuation.special
+  ")" @punctuation.special)
@@ -0,0 +1,443 @@
+/**
+ * @file Dhall grammar for tree-sitter
+ * @license MIT
+ *
+ * Follows the operator precedence of the standard's ABNF, lowest first.
+ * https://github.com/dhall-lang/dhall-lang/blob/master/standard/dhall.abnf
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  import_alt: 1,
+  with: 2,
+  or: 3,
+  plus: 4,
+  text_append: 5,
+  list_append: 6,
+  and: 7,
+  combine: 8,
+  prefer: 9,
+  combine_types: 10,
+  times: 11,
+  equal: 12,
+  not_equal: 13,
+  equivalent: 14,
+  application: 15,
+  completion: 16,
+  selector: 17,
+};
+
+const BUILTIN_TYPES = [
+  'Bool',
+  'Natural',
+  'Integer',
+  'Double',
+  'Text',
+  'Bytes',
+  'List',
+  'Optional',
+  'Date',
+  'Time',
+  'TimeZone',
+  'Type',
+  'Kind',
+  'Sort',
+];
+
+const BUILTIN_FUNCTIONS = [
+  'None',
+  'Natural/fold',
+  'Natural/build',
+  'Natural/isZero',
+  'Natural/even',
+  'Natural/odd',
+  'Natural/toInteger',
+  'Natural/show',
+  'Natural/subtract',
+  'Integer/toDouble',
+  'Integer/show',
+  'Integer/negate',
+  'Integer/clamp',
+  'Double/show',
+  'List/build',
+  'List/fold',
+  'List/length',
+  'List/head',
+  'List/last',
+  'List/indexed',
+  'List/reverse',
+  'Text/show',
+  'Text/replace',
+  'Date/show',
+  'Time/show',
+  'TimeZone/show',
+];
+
+// Characters that end an import path
+const PATH_COMPONENT = /[^\s\/\\()\[\]{}<>,#"]+/;
+
+module.exports = grammar({
+  name: 'dhall',
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.label,
+
+  rules: {
+    source_file: $ => $._expression,
+
+    _expression: $ => choice(
+      $.lambda_expression,
+      $.if_expression,
+      $.let_expression,
+      $.forall_expression,
+      $.arrow_expression,
+      $.assert_expression,
+      $.annotated_expression,
+      $.empty_list_literal,
+      $._operator_expression,
+    ),
+
+    lambda_expression: $ => prec.right(seq(
+      choice('\\', 'λ'),
+      '(',
+      field('parameter', $.label),
+      ':',
+      field('type', $._expression),
+      ')',
+      choice('->', '→'),
+      field('body', $._expression),
+    )),
+
+    if_expression: $ => prec.right(seq(
+      'if',
+      field('condition', $._expression),
+      'then',
+      field('consequence', $._expression),
+      'else',
+      field('alternative', $._expression),
+    )),
+
+    let_expression: $ => prec.right(seq(
+      repeat1($.let_binding),
+      'in',
+      field('body', $._expression),
+    )),
+
+    let_binding: $ => seq(
+      'let',
+      field('name', $.label),
+      optional(seq(':', field('type', $._expression))),
+      '=',
+      field('value', $._expression),
+    ),
+
+    forall_expression: $ => prec.right(seq(
+      choice('forall', '∀'),
+      '(',
+      field('parameter', $.label),
+      ':',
+      field('type', $._expression),
+      ')',
+      choice('->', '→'),
+      field('body', $._expression),
+    )),
+
+    arrow_expression: $ => prec.right(seq(
+      field('parameter', $._operator_expression),
+      choice('->', '→'),
+      field('result', $._expression),
+    )),
+
+    assert_expression: $ => prec.right(seq('assert', ':', $._expression)),
+
+    annotated_expression: $ => prec.right(seq(
+      field('expression', $._operator_expression),
+      ':',
+      field('type', $._expression),
+    )),
+
+    // `[] : List Natural`
+    empty_list_literal: $ => prec.right(seq(
+      '[',
+      optional(','),
+      ']',
+      ':',
+      field('type', $._operator_expression),
+    )),
+
+    // Operators
+
+    _operator_expression: $ => choice(
+      $.binary_expression,
+      $.with_expression,
+      $._application_expression,
+    ),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.import_alt, '?'],
+        [PREC.or, '||'],
+        [PREC.plus, '+'],
+        [PREC.text_append, '++'],
+        [PREC.list_append, '#'],
+        [PREC.and, '&&'],
+        [PREC.combine, choice('/\\', '∧')],
+        [PREC.prefer, choice('//', '⫽')],
+        [PREC.combine_types, choice('//\\\\', '⩓')],
+        [PREC.times, '*'],
+        [PREC.equal, '=='],
+        [PREC.not_equal, '!='],
+        [PREC.equivalent, choice('===', '≡')],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._operator_expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._operator_expression),
+        ),
+      )));
+    },
+
+    // `config with server.port = 8080`
+    with_expression: $ => prec.left(PREC.with, seq(
+      field('record', $._operator_expression),
+      'with',
+      field('path', $.label_path),
+      '=',
+      field('value', $._operator_expression),
+    )),
+
+    label_path: $ => seq($.label, repeat(seq('.', $.label))),
+
+    // Application
+
+    _application_expression: $ => choice(
+      $.application_expression,
+      $.merge_expression,
+      $.some_expression,
+      $.to_map_expression,
+      $.show_constructor_expression,
+      $._import_expression,
+    ),
+
+    application_expression: $ => prec.left(PREC.application, seq(
+      field('function', $._application_expression),
+      field('argument', $._import_expression),
+    )),
+
+    merge_expression: $ => prec.left(PREC.application, seq(
+      'merge',
+      field('handlers', $._import_expression),
+      field('union', $._import_expression),
+    )),
+
+    some_expression: $ => prec.left(PREC.application, seq('Some', $._import_expression)),
+
+    to_map_expression: $ => prec.left(PREC.application, seq('toMap', $._import_expression)),
+
+    show_constructor_expression: $ => prec.left(PREC.application, seq(
+      'showConstructor',
+      $._import_expression,
+    )),
+
+    _import_expression: $ => choice(
+      $.import,
+      $.completion_expression,
+      $._selector_expression,
+    ),
+
+    // `Config::{ port = 80 }`
+    completion_expression: $ => prec.left(PREC.completion, seq(
+      field('type', $._selector_expression),
+      '::',
+      field('record', $._selector_expression),
+    )),
+
+    _selector_expression: $ => choice($.selector_expression, $._primitive_expression),
+
+    selector_expression: $ => prec.left(PREC.selector, seq(
+      field('record', $._selector_expression),
+      '.',
+      field('selector', choice(
+        $.label,
+        // Projection by labels or by type: `r.{ a, b }`, `r.(T)`
+        seq('{', optional(','), optional(seq($.label, repeat(seq(',', $.label)))), optional(','), '}'),
+        seq('(', $._expression, ')'),
+      )),
+    )),
+
+    // Imports
+
+    import: $ => prec.right(seq(
+      choice($.missing, $.local_import, $.http_import, $.env_import),
+      optional($.hash),
+      optional(seq('as', field('as', choice('Text', 'Location', 'Bytes')))),
+    )),
+
+    missing: _ => 'missing',
+
+    local_import: _ => token(seq(
+      choice('/', './', '../', '~/'),
+      PATH_COMPONENT,
+      repeat(seq('/', PATH_COMPONENT)),
+    )),
+
+    http_import: _ => token(seq(/https?:\/\//, /[^\s()\[\]{}<>,"]+/)),
+
+    env_import: _ => token(seq('env:', choice(/[A-Za-z_][A-Za-z0-9_]*/, /"[^"]*"/))),
+
+    hash: _ => token(seq('sha256:', /[0-9a-fA-F]{64}/)),
+
+    // Primitives
+
+    _primitive_expression: $ => choice(
+      $.double_literal,
+      $.natural_literal,
+      $.integer_literal,
+      $.text_literal,
+      $.bytes_literal,
+      $.boolean_literal,
+      $.record_type,
+      $.record_literal,
+      $.union_type,
+      $.list_literal,
+      $.builtin,
+      $.builtin_type,
+      $.label,
+      $.parenthesized_expression,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    record_type: $ => seq(
+      '{',
+      optional(','),
+      optional(seq(
+        $.record_type_field,
+        repeat(seq(',', $.record_type_field)),
+        optional(','),
+      )),
+      '}',
+    ),
+
+    record_type_field: $ => seq(field('name', $.label), ':', field('type', $._expression)),
+
+    // `{=}`, `{ a = 1, b.c = 2 }`, or punned `{ a, b }`
+    record_literal: $ => seq(
+      '{',
+      optional(','),
+      choice(
+        '=',
+        seq(
+          $.record_literal_field,
+          repeat(seq(',', $.record_literal_field)),
+          optional(','),
+        ),
+      ),
+      '}',
+    ),
+
+    record_literal_field: $ => seq(
+      field('name', $.label_path),
+      optional(seq('=', field('value', $._expression))),
+    ),
+
+    union_type: $ => seq(
+      '<',
+      optional('|'),
+      optional(seq(
+        $.union_type_field,
+        repeat(seq('|', $.union_type_field)),
+        optional('|'),
+      )),
+      '>',
+    ),
+
+    union_type_field: $ => seq(
+      field('name', $.label),
+      optional(seq(':', field('type', $._expression))),
+    ),
+
+    list_literal: $ => seq(
+      '[',
+      optional(','),
+      $._expression,
+      repeat(seq(',', $._expression)),
+      optional(','),
+      ']',
+    ),
+
+    boolean_literal: _ => choice('True', 'False'),
+
+    builtin_type: _ => choice(...BUILTIN_TYPES),
+
+    builtin: _ => choice(...BUILTIN_FUNCTIONS),
+
+    natural_literal: _ => token(choice(/\d+/, /0x[0-9a-fA-F]+/, /0b[01]+/)),
+
+    integer_literal: _ => token(seq(/[+-]/, choice(/\d+/, /0x[0-9a-fA-F]+/, /0b[01]+/))),
+
+    double_literal: _ => choice(
+      token(seq(
+        optional(/[+-]/),
+        /\d+/,
+        choice(seq('.', /\d+/, optional(/[eE][+-]?\d+/)), /[eE][+-]?\d+/),
+      )),
+      'Infinity',
+      '-Infinity',
+      'NaN',
+    ),
+
+    bytes_literal: _ => token(seq('0x"', /[0-9a-fA-F]*/, '"')),
+
+    // Labels may contain `-` and `/`; anything else goes in backticks
+    label: _ => token(choice(
+      /[A-Za-z_][A-Za-z0-9_\-\/]*/,
+      seq('`', /[^`]*/, '`'),
+    )),
+
+    // Text
+
+    text_literal: $ => choice(
+      seq(
+        '"',
+        repeat(choice($._double_quote_chunk, $.escape_sequence, $.interpolation)),
+        '"',
+      ),
+      seq(
+        '\'\'',
+        repeat(choice(
+          $._single_quote_chunk,
+          // `'''` for a literal `''`, `''${` for a literal `${`
+          alias(token.immediate(choice('\'\'\'', '\'\'${')), $.escape_sequence),
+          $.interpolation,
+        )),
+        '\'\'',
+      ),
+    ),
+
+    _double_quote_chunk: _ => token.immediate(prec(1, /[^"\\$]+|\$/)),
+
+    _single_quote_chunk: _ => token.immediate(prec(1, /([^'$]|'[^'$])+|\$|'/)),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /["$\\\/bfnrt]/,
+        /u[0-9a-fA-F]{4}/,
+        /u\{[0-9a-fA-F]+\}/,
+      ),
+    )),
+
+    interpolation: $ => seq(token.immediate('${'), $._expression, '}'),
+
+    comment: _ => token(choice(
+      seq('--', /.*/),
+      seq('{-', /[^-]*-+([^}-][^-]*-+)*/, '}'),
+    )),
+  },
+});
@@ -0,0 +1,144 @@
+; Keywords
+
+[
+  "as"
+  "assert"
+  "else"
+  "forall"
+  "if"
+  "in"
+  "let"
+  "merge"
+  "showConstructor"
+  "Some"
+  "then"
+  "toMap"
+  "with"
+  "∀"
+] @keyword
+
+[
+  "\\"
+  "λ"
+] @keyword.function
+
+(missing) @constant.builtin
+
+; Literals
+
+(text_literal) @string
+
+(escape_sequence) @string.escape
+
+(bytes_literal) @string.special
+
+[
+  (natural_literal)
+  (integer_literal)
+  (double_literal)
+] @number
+
+(boolean_literal) @boolean
+
+; Identifiers
+
+(label) @variable
+
+(builtin) @function.builtin
+
+((builtin) @constant.builtin
+  (#eq? @constant.builtin "None"))
+
+(builtin_type) @type.builtin
+
+(lambda_expression
+  parameter: (label) @variable.parameter)
+
+(forall_expression
+  parameter: (label) @variable.parameter)
+
+(let_binding
+  name: (label) @variable)
+
+(application_expression
+  function: (label) @function.call)
+
+(application_expression
+  function: (selector_expression
+    selector: (label) @function.call))
+
+; Fields and types
+
+(record_type_field
+  name: (label) @property)
+
+(record_literal_field
+  name: (label_path
+    (label) @property))
+
+(with_expression
+  path: (label_path
+    (label) @property))
+
+(selector_expression
+  selector: (label) @property)
+
+(union_type_field
+  name: (label) @constructor)
+
+(annotated_expression
+  type: (label) @type)
+
+(record_type_field
+  type: (label) @type)
+
+; Imports
+
+[
+  (local_import)
+  (http_import)
+  (env_import)
+] @string.special.path
+
+(hash) @string.special
+
+(import
+  as: _ @type.builtin)
+
+; Operators and punctuation
+
+(binary_expression
+  operator: _ @operator)
+
+[
+  "="
+  ":"
+  "::"
+  "->"
+  "→"
+] @operator
+
+[
+  "."
+  ","
+  "|"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+  "<"
+  ">"
+] @punctuation.bracket
+
+(interpolation
+  [
+    "${"
+    "}"
+  ] @punctuation.special)
+
+(comment) @comment
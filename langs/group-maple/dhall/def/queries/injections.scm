@@ -0,0 +1,45 @@
+; Text blocks tagged with the language they hold: `{- sql -} ''…''`
+
+((comment) @_language
+  .
+  (text_literal) @injection.content
+  (#match? @_language "^\\{-\\s*(bash|sh)\\s*-\\}$")
+  (#set! injection.language "bash")
+  (#set! injection.combined))
+
+((comment) @_language
+  .
+  (text_literal) @injection.content
+  (#match? @_language "^\\{-\\s*sql\\s*-\\}$")
+  (#set! injection.language "sql")
+  (#set! injection.combined))
+
+((comment) @_language
+  .
+  (text_literal) @injection.content
+  (#match? @_language "^\\{-\\s*json\\s*-\\}$")
+  (#set! injection.language "json")
+  (#set! injection.combined))
+
+((comment) @_language
+  .
+  (text_literal) @injection.content
+  (#match? @_language "^\\{-\\s*ya?ml\\s*-\\}$")
+  (#set! injection.language "yaml")
+  (#set! injection.combined))
+
+((comment) @_language
+  .
+  (text_literal) @injection.content
+  (#match? @_language "^\\{-\\s*html\\s*-\\}$")
+  (#set! injection.language "html")
+  (#set! injection.combined))
+
+; Shell scripts bound by name, as in nix: `let buildScript = ''…''`
+
+((let_binding
+  name: (label) @_name
+  value: (text_literal) @injection.content)
+  (#match? @_name "([sS]cript|[hH]ook)$")
+  (#set! injection.language "bash")
+  (#set! injection.combined))
@@ -0,0 +1,59 @@
+//! Dhall text literals inject the language named by a `{- lang -}` comment in
+//! front of them, and let bindings named like scripts inject bash. The
+//! literal's delimiters and interpolations are left out of the injection.
+
+use arborium_test_harness::helpers;
+
+const DHALL: &str = "\
+let table = \"invoices\"
+
+let schema = {- sql -} ''
+      SELECT id FROM ${table};
+      ''
+
+let startScript = ''
+      exec billing --port ''${PORT}
+      ''
+
+let motd = ''
+      welcome
+      ''
+
+in  { schema, startScript, motd }
+";
+
+#[test]
+fn test_tagged_and_script_text_inject() {
+    let result = helpers::plugin_parse(
+        arborium_dhall::language(),
+        helpers::queries!(arborium_dhall),
+        DHALL,
+    );
+
+    let text = |language: &str| -> String {
+        result
+            .injections
+            .iter()
+            .filter(|injection| injection.language == language)
+            .map(|injection| &DHALL[injection.start as usize..injection.end as usize])
+            .collect()
+    };
+
+    let sql = text("sql");
+    assert!(sql.contains("SELECT id FROM "), "{:?}", sql);
+    assert!(!sql.contains("${"), "{:?}", sql);
+    assert!(!sql.contains("''"), "{:?}", sql);
+
+    let bash = text("bash");
+    assert!(bash.contains("exec billing --port "), "{:?}", bash);
+    assert!(!bash.contains("welcome"), "{:?}", bash);
+
+    assert!(
+        result
+            .injections
+            .iter()
+            .all(|injection| injection.language == "sql" || injection.language == "bash"),
+        "{:?}",
+        result.injections
+    );
+}
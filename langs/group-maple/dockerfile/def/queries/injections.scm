@@ -0,0 +1,34 @@
+; Shell form instructions run their arguments with /bin/sh -c. The exec
+; form is a JSON array and isn't injected.
+;
+; A shell command spans its line continuations, so multiline commands are
+; injected whole. Comment lines between continuations are dropped by
+; Docker, but bash reads them as ending the command.
+
+((run_instruction
+  (shell_command) @injection.content)
+ (#not-match? @injection.content "^<<")
+ (#set! injection.language "bash"))
+
+(cmd_instruction
+  (shell_command) @injection.content
+  (#set! injection.language "bash"))
+
+(entrypoint_instruction
+  (shell_command) @injection.content
+  (#set! injection.language "bash"))
+
+; RUN <<EOF runs the heredoc as a script. The heredoc is injected with its
+; closing delimiter, which bash reads as a command. Heredocs fed to another
+; command, as in RUN python3 <<EOF or RUN cat <<EOF > file, aren't scripts.
+
+((run_instruction
+  (shell_command
+    .
+    (shell_fragment
+      .
+      (heredoc_marker)
+      .)
+    .)
+  (heredoc_block) @injection.content)
+ (#set! injection.language "bash"))
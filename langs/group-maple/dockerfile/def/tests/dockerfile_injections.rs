@@ -0,0 +1,73 @@
+//! Dockerfile hands its shell form instructions to bash.
+
+use arborium_test_harness::helpers;
+
+const DOCKERFILE: &str = "\
+FROM debian:bookworm-slim
+RUN apt-get update \\
+    && apt-get install -y curl
+RUN <<EOF
+for dir in data logs; do
+  mkdir -p \"/srv/$dir\"
+done
+EOF
+RUN python3 <<EOF
+print(\"not shell\")
+EOF
+ENTRYPOINT [\"/usr/local/bin/api\"]
+CMD exec api --verbose
+";
+
+fn bash_injections(source: &str) -> Vec<&str> {
+    let result = helpers::plugin_parse(
+        arborium_dockerfile::language(),
+        helpers::queries!(arborium_dockerfile),
+        source,
+    );
+    helpers::injected_as(&result, source, "bash")
+}
+
+/// The text bash highlights as `capture` in `source`.
+fn bash_captures<'a>(source: &'a str, capture: &str) -> Vec<&'a str> {
+    helpers::plugin_parse(
+        arborium_bash::language(),
+        helpers::queries!(arborium_bash),
+        source,
+    )
+    .spans
+    .into_iter()
+    .filter(|s| s.capture == capture)
+    .map(|s| &source[s.start as usize..s.end as usize])
+    .collect()
+}
+
+#[test]
+fn test_shell_form_instructions_inject_bash() {
+    let scripts = bash_injections(DOCKERFILE);
+    assert_eq!(scripts.len(), 4, "{:?}", scripts);
+    assert_eq!(
+        scripts[0],
+        "apt-get update \\\n    && apt-get install -y curl"
+    );
+    assert!(scripts[1].contains("mkdir -p"));
+    assert_eq!(scripts[2], "python3 <<EOF");
+    assert_eq!(scripts[3], "exec api --verbose");
+}
+
+#[test]
+fn test_only_script_heredocs_inject_bash() {
+    let scripts = bash_injections(DOCKERFILE);
+    assert!(scripts.iter().all(|s| !s.contains("not shell")));
+    assert!(scripts.iter().all(|s| !s.starts_with("<<")));
+}
+
+#[test]
+fn test_injected_commands_highlight_as_bash() {
+    let scripts = bash_injections(DOCKERFILE);
+    let commands = bash_captures(scripts[0], "function");
+    assert_eq!(commands, ["apt-get", "apt-get"]);
+    let keywords = bash_captures(scripts[1], "keyword");
+    for keyword in ["for", "in", "do", "done"] {
+        assert!(keywords.contains(&keyword), "{:?}", keywords);
+    }
+}
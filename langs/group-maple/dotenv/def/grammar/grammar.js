@@ -0,0 +1,102 @@
+/**
+ * @file dotenv grammar for tree-sitter
+ * @license MIT
+ *
+ * `KEY=value` lines, optionally prefixed with `export`, as read by the
+ * dotenv libraries and Docker Compose. Values are unquoted, double-quoted
+ * (with escapes, spanning lines if need be) or single-quoted (taken
+ * literally). `$VAR` and `${VAR:-default}` expand in all but single
+ * quotes. A `#` starts a comment at the start of a line, or after
+ * whitespace following a value.
+ * https://hexdocs.pm/dotenvy/dotenv-file-format.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'dotenv',
+
+  extras: _ => [],
+
+  rules: {
+    source: $ => seq(
+      repeat(choice($._newline, seq($._line, $._newline))),
+      optional($._line),
+    ),
+
+    _newline: _ => /\r?\n/,
+
+    _space: _ => /[ \t]+/,
+
+    _line: $ => choice(
+      $._space,
+      seq(optional($._space), choice($.comment, $.variable)),
+    ),
+
+    comment: _ => /#[^\r\n]*/,
+
+    // export DATABASE_URL="postgres://${DB_HOST:-localhost}/app" # local
+    variable: $ => seq(
+      optional(seq('export', $._space)),
+      field('name', $.variable_name),
+      optional($._space),
+      '=',
+      optional($._space),
+      optional(field('value', choice($.string, $.raw_string, $.value))),
+      optional($._space),
+      optional(alias($._inline_comment, $.comment)),
+    ),
+
+    variable_name: _ => /[A-Za-z_][A-Za-z0-9_.-]*/,
+
+    // The whitespace before the `#` is part of the token, so it's never
+    // mistaken for more of an unquoted value
+    _inline_comment: _ => /[ \t]+#[^\r\n]*/,
+
+    value: $ => prec.right(seq(
+      $._value_part,
+      repeat(choice($._value_part, $._space)),
+    )),
+
+    _value_part: $ => choice(
+      $._text,
+      $.expansion,
+      $.simple_expansion,
+      $.escape_sequence,
+      '$',
+    ),
+
+    _text: _ => /[^\s$\\"'][^\s$\\]*/,
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        /[^"\\$]+/,
+        $.expansion,
+        $.simple_expansion,
+        $.escape_sequence,
+        '$',
+      )),
+      '"',
+    ),
+
+    raw_string: _ => /'[^']*'/,
+
+    escape_sequence: _ => /\\(.|\r?\n)/,
+
+    // $HOME
+    simple_expansion: _ => /\$[A-Za-z_][A-Za-z0-9_]*/,
+
+    // ${PORT}, ${PORT:-8080}, ${API_KEY:?must be set}
+    expansion: $ => seq(
+      '${',
+      field('name', alias(/[A-Za-z_][A-Za-z0-9_]*/, $.variable_name)),
+      optional(seq(
+        field('operator', alias(/:?[-=?+]/, $.operator)),
+        optional(field('default', alias(/[^}\r\n]+/, $.default))),
+      )),
+      '}',
+    ),
+  },
+});
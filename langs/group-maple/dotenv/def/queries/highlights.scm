@@ -0,0 +1,29 @@
+(comment) @comment
+
+"export" @keyword
+
+(variable_name) @variable
+
+"=" @operator
+
+(value) @string
+
+(string) @string
+
+(raw_string) @string
+
+(escape_sequence) @string.escape
+
+[
+  "${"
+  "}"
+] @punctuation.special
+
+(simple_expansion) @variable
+
+(expansion
+  (variable_name) @variable)
+
+(operator) @operator
+
+(default) @string
@@ -0,0 +1,71 @@
+//! dotenv values expand `$VAR` and `${VAR}` unless single-quoted, and a `#`
+//! after whitespace ends an unquoted value.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/.env");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_dotenv::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_dotenv::language(), source, kind)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_quoting_controls_expansion() {
+    let source = "\
+URL=\"postgres://${USER}@$HOST/app\"
+RAW='${USER}@$HOST'
+BARE=redis://$HOST:6379
+";
+    assert_eq!(nodes(source, "expansion"), ["${USER}"]);
+    assert_eq!(nodes(source, "simple_expansion"), ["$HOST", "$HOST"]);
+    assert_eq!(nodes(source, "raw_string"), ["'${USER}@$HOST'"]);
+}
+
+#[test]
+fn test_expansion_defaults() {
+    let source = "PORT=${PORT:-8080}\nKEY=${API_KEY:?must be set}\n";
+    assert_eq!(nodes(source, "operator"), [":-", ":?"]);
+    assert_eq!(nodes(source, "default"), ["8080", "must be set"]);
+}
+
+#[test]
+fn test_inline_comments_end_unquoted_values() {
+    let source = "export LEVEL=info # or debug\nCOLOR=#ff0000\nGREETING=hello world\n";
+    assert_eq!(nodes(source, "value"), ["info", "#ff0000", "hello world"]);
+    assert_eq!(nodes(source, "comment"), [" # or debug"]);
+}
+
+#[test]
+fn test_sample_captures() {
+    let grammars = [(
+        "dotenv",
+        TestGrammar::new(
+            arborium_dotenv::language(),
+            arborium_dotenv::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    let html = helpers::highlighter(grammars)
+        .highlight("dotenv", SAMPLE)
+        .unwrap();
+    assert!(html.contains("<a-k>export</a-k>"), "{}", html);
+    assert!(html.contains("<a-v>DATABASE_URL</a-v>"), "{}", html);
+    assert!(html.contains("<a-v>$HOST</a-v>"), "{}", html);
+    assert!(
+        html.contains("<a-s>&#39;p@ss$word#1&#39;</a-s>"),
+        "{}",
+        html
+    );
+}
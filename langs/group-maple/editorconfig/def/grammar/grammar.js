@@ -0,0 +1,93 @@
+/**
+ * @file EditorConfig grammar for tree-sitter
+ * @license MIT
+ *
+ * `.editorconfig` files: `key = value` pairs, a preamble that may set
+ * `root`, then sections whose header is a glob like `[*.{js,ts}]` or
+ * `[lib/**.py]`. Comments start with `#` or `;` and take a whole line.
+ * https://spec.editorconfig.org/#file-format
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'editorconfig',
+
+  extras: $ => [/[ \t]/, $.comment],
+
+  rules: {
+    document: $ => seq(
+      repeat(choice($._newline, $.pair)),
+      repeat($.section),
+    ),
+
+    _newline: _ => /\r?\n/,
+
+    comment: _ => /[#;][^\r\n]*/,
+
+    section: $ => prec.right(seq(
+      field('header', $.section_header),
+      repeat(choice($._newline, $.pair)),
+    )),
+
+    section_header: $ => seq(
+      '[',
+      field('glob', $.glob),
+      ']',
+      $._newline,
+    ),
+
+    // *, **, ?, [a-z], [!_], {js,ts}, {1..3} and literal text
+    glob: $ => repeat1($._glob_part),
+
+    _glob_part: $ => choice(
+      $.wildcard,
+      $.character_class,
+      $.brace_expansion,
+      $.escape_sequence,
+      $.path_separator,
+      $._glob_text,
+    ),
+
+    wildcard: _ => choice('**', '*', '?'),
+
+    character_class: _ => /\[!?\]?[^\]\r\n]*\]/,
+
+    brace_expansion: $ => seq(
+      '{',
+      repeat(choice(
+        ',',
+        $.range,
+        $.wildcard,
+        $.character_class,
+        $.brace_expansion,
+        $.escape_sequence,
+        $.path_separator,
+        alias(/[^\r\n\[\]{}*?\\\/,]+/, $.text),
+      )),
+      '}',
+    ),
+
+    range: _ => token(prec(1, /[+-]?[0-9]+\.\.[+-]?[0-9]+/)),
+
+    escape_sequence: _ => /\\[^\r\n]/,
+
+    path_separator: _ => '/',
+
+    _glob_text: _ => /[^\r\n\[\]{}*?\\\/]+/,
+
+    // indent_style = space
+    pair: $ => seq(
+      field('key', $.key),
+      '=',
+      optional(field('value', $.value)),
+      $._newline,
+    ),
+
+    key: _ => /[^\s=#;\[][^\s=]*/,
+
+    // Everything to the end of the line but trailing whitespace
+    value: _ => /[^\s]([^\r\n]*[^\s])?/,
+  },
+});
@@ -0,0 +1,44 @@
+(comment) @comment
+
+[
+  "["
+  "]"
+] @punctuation.bracket
+
+"=" @operator
+
+(glob) @string.special.path
+
+(wildcard) @operator
+
+(character_class) @string.regexp
+
+(brace_expansion
+  [
+    "{"
+    "}"
+    ","
+  ] @punctuation.delimiter)
+
+(range) @number
+
+(escape_sequence) @string.escape
+
+(path_separator) @punctuation.delimiter
+
+(key) @property
+
+; The properties the spec defines
+((key) @keyword
+  (#match? @keyword "(?i)^(root|indent_style|indent_size|tab_width|end_of_line|charset|spelling_language|trim_trailing_whitespace|insert_final_newline|max_line_length)$"))
+
+(value) @string
+
+((value) @number
+  (#match? @number "^[0-9]+$"))
+
+((value) @constant.builtin.boolean
+  (#match? @constant.builtin.boolean "(?i)^(true|false)$"))
+
+((value) @constant.builtin
+  (#match? @constant.builtin "(?i)^(space|tab|lf|crlf|cr|latin1|utf-8|utf-8-bom|utf-16be|utf-16le|off|unset)$"))
@@ -0,0 +1,66 @@
+//! EditorConfig section headers parse as globs, and the properties the spec
+//! defines stand out from editor-specific ones.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/.editorconfig");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_editorconfig::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_editorconfig::language(), source, kind)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_section_globs() {
+    let source = "[*.{js,ts}]\nindent_size = 2\n[lib/**/[!_]*.py]\nindent_size = 4\n[{1..3}.txt]\n";
+    assert_eq!(
+        nodes(source, "glob"),
+        ["*.{js,ts}", "lib/**/[!_]*.py", "{1..3}.txt"]
+    );
+    assert_eq!(nodes(source, "wildcard"), ["*", "**", "*"]);
+    assert_eq!(nodes(source, "brace_expansion"), ["{js,ts}", "{1..3}"]);
+    assert_eq!(nodes(source, "character_class"), ["[!_]"]);
+    assert_eq!(nodes(source, "range"), ["1..3"]);
+}
+
+#[test]
+fn test_preamble_and_sections() {
+    let source =
+        "# top\nroot = true\n\n[*]\ncharset = utf-8\n; no more\n[Makefile]\nindent_style = tab\n";
+    assert_eq!(nodes(source, "section").len(), 2);
+    assert_eq!(nodes(source, "key"), ["root", "charset", "indent_style"]);
+    assert_eq!(nodes(source, "comment"), ["# top", "; no more"]);
+}
+
+#[test]
+fn test_sample_captures() {
+    let grammars = [(
+        "editorconfig",
+        TestGrammar::new(
+            arborium_editorconfig::language(),
+            arborium_editorconfig::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    let html = helpers::highlighter(grammars)
+        .highlight("editorconfig", SAMPLE)
+        .unwrap();
+    assert!(html.contains("<a-k>indent_style</a-k>"), "{}", html);
+    // Only the spec's own properties are keywords
+    assert!(html.contains("<a-pr>shell_variant</a-pr>"), "{}", html);
+    assert!(html.contains("<a-co>true</a-co>"), "{}", html);
+    assert!(html.contains("<a-co>crlf</a-co>"), "{}", html);
+    assert!(html.contains("<a-n>100</a-n>"), "{}", html);
+    assert!(html.contains("<a-o>**</a-o>"), "{}", html);
+}
@@ -0,0 +1,114 @@
+/**
+ * @file Git commit message grammar for tree-sitter
+ * @license MIT
+ *
+ * The message git opens in an editor (`COMMIT_EDITMSG`, `MERGE_MSG`,
+ * `TAG_EDITMSG`): a subject line, a body, trailers like `Signed-off-by:`,
+ * and the comments git writes, including the status of the changes.
+ *
+ * The subject is cut at 50 characters and the rest kept as `overflow`.
+ * `git commit --verbose` appends the diff under a scissors line; it's kept
+ * whole so it can be handed to the diff grammar.
+ * https://git-scm.com/docs/git-commit#_discussion
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'git_commit',
+
+  extras: _ => [],
+
+  rules: {
+    source: $ => seq(
+      repeat(choice($._blank_line, $.comment)),
+      optional($.message),
+      optional($._verbose),
+    ),
+
+    _blank_line: _ => /[ \t]*\r?\n/,
+
+    _newline: _ => /\r?\n/,
+
+    _space: _ => /[ \t]+/,
+
+    message: $ => prec.right(seq(
+      field('subject', $.subject),
+      optional(field('overflow', $.overflow)),
+      $._newline,
+      repeat(choice($._blank_line, $.comment, $.trailer, $._line)),
+    )),
+
+    subject: _ => /[^#\s][^\n]{0,49}/,
+
+    overflow: _ => token.immediate(/[^\n]+/),
+
+    // A body line may be indented, but only a `#` in the first column
+    // starts a comment.
+    _line: _ => /([^#\s]|[ \t]+[^\s])[^\n]*\r?\n/,
+
+    // Signed-off-by: Ada <ada@example.com>
+    //
+    // Hyphenated keys, and the few single words that are common, take
+    // precedence over a body line.
+    trailer: $ => seq(
+      field('key', $.trailer_key),
+      optional($._space),
+      optional(field('value', $.trailer_value)),
+      $._newline,
+    ),
+
+    trailer_key: _ => token(prec(1, choice(
+      /[A-Za-z0-9]+(-[A-Za-z0-9]+)+:/,
+      /(Fixes|Closes|Resolves|Refs|Link|Bug|Cc|BREAKING CHANGE):/,
+    ))),
+
+    trailer_value: _ => /[^\s][^\n]*/,
+
+    // # On branch main
+    // #	modified:   src/lib.rs
+    comment: $ => seq(
+      '#',
+      optional(choice(
+        seq(
+          alias(token(prec(1, / On branch /)), $.branch_prefix),
+          field('branch', $.branch),
+        ),
+        seq(
+          field('change', $.change_kind),
+          optional($._space),
+          optional(field('path', $.path)),
+        ),
+        $._comment_text,
+      )),
+      $._newline,
+    ),
+
+    _comment_text: _ => /[^\n]+/,
+
+    branch: _ => /[^\s]+/,
+
+    change_kind: _ => token(prec(1,
+      /\t(new file|modified|deleted|renamed|copied|typechange|both modified|both added|both deleted|added by us|added by them|deleted by us|deleted by them):/,
+    )),
+
+    path: _ => /[^\s][^\n]*/,
+
+    // # ------------------------ >8 ------------------------
+    _verbose: $ => seq(
+      $.scissors,
+      repeat(choice($._blank_line, $.comment)),
+      optional($.diff),
+    ),
+
+    scissors: _ => token(prec(2, /#[ \t]*-+ >8 -+[ \t]*\r?\n/)),
+
+    diff: $ => seq(
+      token(prec(1, /diff [^\n]*\r?\n/)),
+      repeat($._diff_line),
+    ),
+
+    _diff_line: _ => /[^\n]*\r?\n|[^\n]+/,
+  },
+});
@@ -0,0 +1,18 @@
+(subject) @markup.heading
+
+; Past the 50 characters a subject should fit in
+(overflow) @error
+
+(trailer_key) @property
+
+(trailer_value) @string
+
+(comment) @comment
+
+(branch) @label
+
+(change_kind) @keyword
+
+(path) @string.special.path
+
+(scissors) @punctuation.special
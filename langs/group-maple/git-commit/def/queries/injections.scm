@@ -0,0 +1,3 @@
+; The diff `git commit --verbose` adds under the scissors line
+((diff) @injection.content
+  (#set! injection.language "diff"))
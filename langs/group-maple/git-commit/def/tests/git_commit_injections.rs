@@ -0,0 +1,112 @@
+//! `git commit --verbose` messages inject the diff under the scissors line,
+//! which in turn injects each changed line into the language of its file.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::arborium_highlight::detect::detect_from_path;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const COMMIT: &str = "\
+Retry transient failures in the HTTP client before giving up
+
+Requests that fail with a connection reset are retried.
+
+Signed-off-by: Ada Lovelace <ada@example.com>
+# Please enter the commit message for your changes.
+#
+# On branch retry
+# Changes to be committed:
+#\tmodified:   client/retry.py
+#
+# ------------------------ >8 ------------------------
+# Do not modify or remove the line above.
+diff --git a/client/retry.py b/client/retry.py
+index 3b18e51..a4c2d9f 100644
+--- a/client/retry.py
++++ b/client/retry.py
+@@ -1,2 +1,3 @@
+ import time
++def backoff(attempt):
++    return 2**attempt
+";
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "git-commit",
+            TestGrammar::new(
+                arborium_git_commit::language(),
+                arborium_git_commit::HIGHLIGHTS_QUERY,
+                arborium_git_commit::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "diff",
+            TestGrammar::new(
+                arborium_diff::language(),
+                arborium_diff::HIGHLIGHTS_QUERY,
+                arborium_diff::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "python",
+            TestGrammar::new(
+                arborium_python::language(),
+                &arborium_python::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    let mut highlighter = helpers::highlighter(grammars);
+    highlighter.set_injection_resolver(|language: &str| {
+        detect_from_path(language, &|ext| (ext == "py").then(|| "python".to_string()))
+    });
+    highlighter
+}
+
+#[test]
+fn test_verbose_diff_is_injected() {
+    let result = helpers::plugin_parse(
+        arborium_git_commit::language(),
+        helpers::queries!(arborium_git_commit),
+        COMMIT,
+    );
+
+    let injections: Vec<(&str, &str)> = result
+        .injections
+        .iter()
+        .map(|i| {
+            (
+                &COMMIT[i.start as usize..i.end as usize],
+                i.language.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(injections.len(), 1, "{:?}", injections);
+    let (diff, language) = injections[0];
+    assert_eq!(language, "diff");
+    assert!(diff.starts_with("diff --git a/client/retry.py"), "{}", diff);
+    assert!(diff.ends_with("    return 2**attempt\n"), "{}", diff);
+}
+
+#[test]
+fn test_message_parts_highlight() {
+    let html = highlighter().highlight("git-commit", COMMIT).unwrap();
+    // The subject is cut at 50 characters
+    assert!(
+        html.contains("<a-tt>Retry transient failures in the HTTP client before</a-tt>"),
+        "{}",
+        html
+    );
+    assert!(html.contains("<a-er> giving up</a-er>"), "{}", html);
+    assert!(html.contains("<a-pr>Signed-off-by:</a-pr>"), "{}", html);
+    assert!(html.contains("<a-l>retry</a-l>"), "{}", html);
+}
+
+#[test]
+fn test_diff_changes_highlight_as_their_language() {
+    let html = highlighter().highlight("git-commit", COMMIT).unwrap();
+    assert!(html.contains("<a-k>def</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>return</a-k>"), "{}", html);
+    // The message body above the scissors line isn't part of the diff
+    assert!(!html.contains("<a-da>Requests"), "{}", html);
+}
@@ -0,0 +1,74 @@
+/**
+ * @file Git config grammar for tree-sitter
+ * @license MIT
+ *
+ * Git's ini-like configuration files (`.gitconfig`, `.git/config`,
+ * `.gitmodules`): sections with an optional quoted subsection, like
+ * `[remote "origin"]` or `[includeIf "gitdir:~/work/"]`, and the variables
+ * set under them. Comments start with `#` or `;`, outside of quotes.
+ * https://git-scm.com/docs/git-config#_syntax
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'git_config',
+
+  extras: $ => [/[ \t]/, /\\\r?\n/, $.comment],
+
+  rules: {
+    config: $ => repeat(choice($._newline, $.section)),
+
+    _newline: _ => /\r?\n/,
+
+    comment: _ => /[#;][^\n]*/,
+
+    section: $ => prec.right(seq(
+      field('header', $.section_header),
+      repeat(choice($._newline, $.variable)),
+    )),
+
+    // [core], [remote "origin"], [branch.main] (the older subsection form)
+    section_header: $ => seq(
+      '[',
+      field('name', $.section_name),
+      optional(seq('"', optional(field('subsection', $.subsection_name)), '"')),
+      ']',
+      $._newline,
+    ),
+
+    section_name: _ => /[A-Za-z0-9.-]+/,
+
+    subsection_name: _ => token.immediate(/([^"\\\n]|\\.)+/),
+
+    // editor = vim, or just a name, which sets it to true
+    variable: $ => seq(
+      field('name', $.name),
+      optional(seq('=', optional(field('value', $.value)))),
+      $._newline,
+    ),
+
+    name: _ => /[A-Za-z][A-Za-z0-9-]*/,
+
+    value: $ => prec.right(repeat1(choice(
+      $.string,
+      $.boolean,
+      $.escape_sequence,
+      /[^\s"#;\\]+/,
+    ))),
+
+    boolean: _ => choice('true', 'false', 'yes', 'no', 'on', 'off'),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        token.immediate(/[^"\\\n]+/),
+        alias(token.immediate(/\\[\\"ntb]/), $.escape_sequence),
+      )),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: _ => /\\[\\"ntb]/,
+  },
+});
@@ -0,0 +1,24 @@
+(comment) @comment
+
+[
+  "["
+  "]"
+] @punctuation.bracket
+
+"=" @operator
+
+(section_name) @type
+
+; [include] and [includeIf "gitdir:~/work/"] pull in other files
+((section_name) @keyword
+  (#match? @keyword "^[iI][nN][cC][lL][uU][dD][eE]([iI][fF])?$"))
+
+(subsection_name) @string.special
+
+(name) @property
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(boolean) @constant.builtin.boolean
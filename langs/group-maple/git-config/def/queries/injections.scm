@@ -0,0 +1,21 @@
+; Aliases starting with `!` run in the shell, quoted or not
+(section
+  header: (section_header
+    name: (section_name) @_section)
+  (variable
+    value: (value) @injection.content)
+  (#match? @_section "^[aA][lL][iI][aA][sS]$")
+  (#match? @injection.content "^!")
+  (#offset! @injection.content 0 1 0 0)
+  (#set! injection.language "bash"))
+
+(section
+  header: (section_header
+    name: (section_name) @_section)
+  (variable
+    value: (value
+      (string) @injection.content))
+  (#match? @_section "^[aA][lL][iI][aA][sS]$")
+  (#match? @injection.content "^\"!")
+  (#offset! @injection.content 0 2 0 -1)
+  (#set! injection.language "bash"))
@@ -0,0 +1,99 @@
+//! Git's config, rebase todo and ignore files.
+//!
+//! Shell aliases in a config and `exec` lines in a todo list inject bash.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+fn bash() -> (&'static str, TestGrammar) {
+    (
+        "bash",
+        TestGrammar::new(
+            arborium_bash::language(),
+            arborium_bash::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )
+}
+
+#[test]
+fn test_config_shell_aliases_highlight_as_bash() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "git-config",
+            TestGrammar::new(
+                arborium_git_config::language(),
+                arborium_git_config::HIGHLIGHTS_QUERY,
+                arborium_git_config::INJECTIONS_QUERY,
+            ),
+        ),
+        bash(),
+    ]);
+    let html = highlighter
+        .highlight(
+            "git-config",
+            "[alias]\n\tst = status --short\n\troot = !pwd\n\tnuke = \"!if true; then git clean -fdx; fi\"\n\
+             [includeIf \"gitdir:~/work/\"]\n\tpath = ~/work.gitconfig\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-t>alias</a-t>"), "{}", html);
+    assert!(html.contains("<a-pr>st</a-pr>"), "{}", html);
+    assert!(html.contains("<a-k>if</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>fi</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>includeIf</a-k>"), "{}", html);
+    // Only shell aliases are bash
+    assert!(!html.contains("<a-f>status</a-f>"), "{}", html);
+}
+
+#[test]
+fn test_rebase_todo_commands() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "git-rebase",
+            TestGrammar::new(
+                arborium_git_rebase::language(),
+                arborium_git_rebase::HIGHLIGHTS_QUERY,
+                arborium_git_rebase::INJECTIONS_QUERY,
+            ),
+        ),
+        bash(),
+    ]);
+    let html = highlighter
+        .highlight(
+            "git-rebase",
+            "pick 3b18e51 Add a parser\nfixup -C a4c2d9f Tidy up\nexec if true; then cargo test; fi\n\
+             # Rebase 0c1d2e3..a4c2d9f onto 0c1d2e3\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-k>pick</a-k>"), "{}", html);
+    assert!(html.contains("<a-co>3b18e51</a-co>"), "{}", html);
+    assert!(html.contains("<a-at>-C</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>fi</a-k>"), "{}", html);
+    assert!(
+        html.contains("<a-c># Rebase 0c1d2e3..a4c2d9f onto 0c1d2e3</a-c>"),
+        "{}",
+        html
+    );
+}
+
+#[test]
+fn test_gitignore_patterns() {
+    let mut highlighter = helpers::highlighter([(
+        "gitignore",
+        TestGrammar::new(
+            arborium_gitignore::language(),
+            arborium_gitignore::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )]);
+    let html = highlighter
+        .highlight(
+            "gitignore",
+            "# Logs\n*.log\n!keep.log\n/target/\n\\#notes\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-c># Logs</a-c>"), "{}", html);
+    assert!(html.contains("<a-o>*</a-o>"), "{}", html);
+    assert!(html.contains("<a-o>!</a-o>"), "{}", html);
+    // An escaped `#` starts a pattern, not a comment
+    assert!(!html.contains("<a-c>#notes"), "{}", html);
+}
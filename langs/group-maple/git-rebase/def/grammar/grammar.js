@@ -0,0 +1,98 @@
+/**
+ * @file Git rebase todo grammar for tree-sitter
+ * @license MIT
+ *
+ * The `git-rebase-todo` file `git rebase --interactive` opens in an
+ * editor. Each line is a command and its arguments, like
+ * `pick 3b18e51 Add a parser`; `exec` runs the rest of its line in the
+ * shell, and `label`, `reset` and `merge` rebuild merges under
+ * `--rebase-merges`. Every command has a one-letter abbreviation.
+ * https://git-scm.com/docs/git-rebase#_interactive_mode
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'git_rebase',
+
+  extras: _ => [],
+
+  rules: {
+    source: $ => seq(
+      repeat(choice($._newline, seq($._line, $._newline))),
+      optional($._line),
+    ),
+
+    _newline: _ => /\r?\n/,
+
+    _space: _ => /[ \t]+/,
+
+    _line: $ => seq(
+      optional($._space),
+      choice($.comment, $.operation),
+      optional($._space),
+    ),
+
+    comment: _ => /#[^\r\n]*/,
+
+    operation: $ => choice(
+      // pick 3b18e51 Add a parser
+      seq(
+        field('command', alias(choice(
+          'pick', 'p', 'reword', 'r', 'edit', 'e', 'squash', 's', 'drop', 'd',
+        ), $.command)),
+        $._space,
+        field('commit', $.commit),
+        optional(seq($._space, field('message', $.message))),
+      ),
+      // fixup -C 3b18e51 Add a parser
+      seq(
+        field('command', alias(choice('fixup', 'f'), $.command)),
+        optional(seq($._space, field('option', $.option))),
+        $._space,
+        field('commit', $.commit),
+        optional(seq($._space, field('message', $.message))),
+      ),
+      // exec cargo test
+      seq(
+        field('command', alias(choice('exec', 'x'), $.command)),
+        $._space,
+        field('script', $.script),
+      ),
+      field('command', alias(choice('break', 'b', 'noop'), $.command)),
+      // label onto, reset onto, update-ref refs/heads/feature
+      seq(
+        field('command', alias(choice(
+          'label', 'l', 'reset', 't', 'update-ref', 'u',
+        ), $.command)),
+        $._space,
+        field('label', $.label),
+        optional(seq($._space, field('message', $.message))),
+      ),
+      // merge -C 5d6e7f8 feature # Merge branch 'feature'
+      seq(
+        field('command', alias(choice('merge', 'm'), $.command)),
+        optional(seq(
+          $._space,
+          field('option', $.option),
+          $._space,
+          field('commit', $.commit),
+        )),
+        $._space,
+        field('label', $.label),
+        optional(seq($._space, field('message', $.message))),
+      ),
+    ),
+
+    option: _ => choice('-C', '-c'),
+
+    commit: _ => /[0-9a-fA-F]{4,64}/,
+
+    label: _ => /[^\s#]+/,
+
+    message: _ => /[^\s][^\r\n]*/,
+
+    script: _ => /[^\s][^\r\n]*/,
+  },
+});
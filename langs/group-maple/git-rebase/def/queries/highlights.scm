@@ -0,0 +1,11 @@
+(comment) @comment
+
+(command) @keyword
+
+(option) @attribute
+
+(commit) @constant
+
+(label) @label
+
+(message) @string
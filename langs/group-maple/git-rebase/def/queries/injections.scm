@@ -0,0 +1,3 @@
+; exec runs the rest of its line in the shell
+((script) @injection.content
+  (#set! injection.language "bash"))
@@ -0,0 +1,58 @@
+/**
+ * @file Gitignore grammar for tree-sitter
+ * @license MIT
+ *
+ * One pattern per line, as read from `.gitignore`, `.git/info/exclude` and
+ * `core.excludesFile`. A `#` starts a comment only at the start of a line,
+ * a leading `!` negates the pattern, and a backslash escapes the next
+ * character, like `\#` or `\!` for a literal one.
+ * https://git-scm.com/docs/gitignore#_pattern_format
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'gitignore',
+
+  extras: _ => [],
+
+  rules: {
+    document: $ => seq(
+      repeat(choice($._newline, seq($._line, $._newline))),
+      optional($._line),
+    ),
+
+    _newline: _ => /\r?\n/,
+
+    _line: $ => choice($.comment, $.pattern),
+
+    comment: _ => token(prec(1, /#[^\r\n]*/)),
+
+    // !/target/**/*.rs
+    pattern: $ => seq(
+      optional($.negation),
+      repeat1(choice(
+        $.directory_separator,
+        $.wildcard,
+        $.bracket_expression,
+        $.escape_sequence,
+        $._text,
+      )),
+    ),
+
+    negation: _ => '!',
+
+    directory_separator: _ => '/',
+
+    wildcard: _ => choice('**', '*', '?'),
+
+    // [Dd]ebug, [!.]*, []a]
+    bracket_expression: _ => /\[[!^]?\]?[^\]\r\n]*\]/,
+
+    escape_sequence: _ => /\\[^\r\n]/,
+
+    // A `!` anywhere but the start is literal
+    _text: _ => /[^\r\n\\\[*?\/!][^\r\n\\\[*?\/]*/,
+  },
+});
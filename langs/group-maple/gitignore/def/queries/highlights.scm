@@ -0,0 +1,13 @@
+(comment) @comment
+
+(negation) @operator
+
+(directory_separator) @punctuation.delimiter
+
+(wildcard) @operator
+
+(bracket_expression) @string.regexp
+
+(escape_sequence) @string.escape
+
+(pattern) @string.special.path
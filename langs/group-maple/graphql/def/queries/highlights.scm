@@ -1,4 +1,10 @@
 ; GraphQL highlights query
+;
+; Later patterns win, so the fallback comes first and the most specific
+; patterns last.
+
+; Name fallback
+(name) @variable
 
 ; Comments
 (comment) @comment
@@ -37,18 +43,46 @@
 (union_type_definition
   (name) @type.definition)
 
+(object_type_extension
+  (name) @type.definition)
+
+(interface_type_extension
+  (name) @type.definition)
+
+(input_object_type_extension
+  (name) @type.definition)
+
+(enum_type_extension
+  (name) @type.definition)
+
+(scalar_type_extension
+  (name) @type.definition)
+
+(union_type_extension
+  (name) @type.definition)
+
 ; Type references
 (named_type
   (name) @type)
 
+; The scalars every schema has
+((named_type
+  (name) @type.builtin)
+  (#any-of? @type.builtin "Int" "Float" "String" "Boolean" "ID"))
+
 ; Field definitions
 (field_definition
   (name) @property)
 
-; Input value definitions (arguments in schema)
+; Input value definitions: arguments of fields and directives...
 (input_value_definition
   (name) @variable.parameter)
 
+; ...and the fields of input types
+(input_fields_definition
+  (input_value_definition
+    (name) @property))
+
 ; Operations
 (operation_definition
   (name) @function)
@@ -84,8 +118,27 @@
   (name) @attribute)
 
 (directive_definition
+  "@" @punctuation.special
   (name) @attribute)
 
+; The directives the spec defines
+(directive
+  (name) @attribute.builtin
+  (#any-of? @attribute.builtin "deprecated" "include" "skip" "specifiedBy" "oneOf"))
+
+(directive_location) @constant.builtin
+
+; Why a field or enum value is deprecated reads as documentation
+(directive
+  (name) @_directive
+  (arguments
+    (argument
+      (name) @_argument
+      (value
+        (string_value) @comment.documentation)))
+  (#eq? @_directive "deprecated")
+  (#eq? @_argument "reason"))
+
 ; Keywords
 [
   "query"
@@ -131,6 +184,3 @@
 (variable
   "$" @punctuation.special
   (name) @variable)
-
-; Name fallback
-(name) @variable
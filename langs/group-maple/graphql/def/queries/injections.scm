@@ -0,0 +1,11 @@
+; Descriptions are CommonMark, so block string descriptions are markdown,
+; without their """ delimiters. Indentation the spec strips from them still
+; counts here, so a deeply indented one may read as a code block.
+((description
+  (string_value) @injection.content)
+  (#match? @injection.content "^\"\"\"")
+  (#offset! @injection.content 0 3 0 -3)
+  (#set! injection.language "markdown"))
+
+; Other strings stay plain, including directive arguments like
+; `@constraint(pattern: "^[a-z]+$")`: only the directive knows what they are
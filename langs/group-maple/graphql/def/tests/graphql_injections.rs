@@ -0,0 +1,99 @@
+//! GraphQL block string descriptions inject markdown, and schema files tell
+//! types, fields, arguments and directives apart.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SCHEMA: &str = include_str!("../samples/catalog.graphqls");
+const OPERATIONS: &str = include_str!("../samples/storefront.graphql");
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_graphql::language(),
+        helpers::queries!(arborium_graphql),
+        source,
+    )
+}
+
+#[test]
+fn test_samples_parse_cleanly() {
+    for sample in [SCHEMA, OPERATIONS] {
+        let tree = helpers::parse_tree(arborium_graphql::language(), sample);
+        let root = tree.root_node();
+        assert!(!root.has_error(), "{}", root.to_sexp());
+    }
+}
+
+#[test]
+fn test_block_descriptions_inject_markdown() {
+    let source = "\
+\"\"\"
+A **product**.
+\"\"\"
+type Product {
+  \"The display name.\"
+  name: String @constraint(pattern: \"^[a-z]+$\")
+}
+";
+    let injections = injected(source);
+    assert_eq!(injections, [("\nA **product**.\n", "markdown".to_string())]);
+}
+
+#[test]
+fn test_samples_only_inject_descriptions() {
+    let injections = injected(SCHEMA);
+    assert_eq!(injections.len(), 4, "{:?}", injections);
+    assert!(
+        injections
+            .iter()
+            .all(|(_, language)| language == "markdown")
+    );
+    assert!(injections.iter().all(|(text, _)| !text.contains("\"\"\"")));
+    assert!(injected(OPERATIONS).is_empty());
+}
+
+#[test]
+fn test_sample_captures() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "graphql",
+            TestGrammar::new(
+                arborium_graphql::language(),
+                arborium_graphql::HIGHLIGHTS_QUERY,
+                arborium_graphql::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "markdown",
+            TestGrammar::new(
+                arborium_markdown::language(),
+                arborium_markdown::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+
+    let html = highlighter.highlight("graphql", SCHEMA).unwrap();
+    assert!(html.contains("<a-t>Product</a-t>"), "{}", html);
+    assert!(html.contains("<a-t>String</a-t>"), "{}", html);
+    assert!(html.contains("<a-pr>legacyCode</a-pr>"), "{}", html);
+    assert!(html.contains("<a-v>first</a-v>"), "{}", html);
+    assert!(html.contains("<a-at>constraint</a-at>"), "{}", html);
+    assert!(html.contains("<a-at>deprecated</a-at>"), "{}", html);
+    assert!(
+        html.contains("<a-co>ARGUMENT_DEFINITION</a-co>"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("<a-c>&quot;No longer sold in GBP.&quot;</a-c>"),
+        "{}",
+        html
+    );
+
+    let html = highlighter.highlight("graphql", OPERATIONS).unwrap();
+    assert!(html.contains("<a-f>ProductPage</a-f>"), "{}", html);
+    assert!(html.contains("<a-f>ProductCard</a-f>"), "{}", html);
+    assert!(html.contains("<a-pr>reviews</a-pr>"), "{}", html);
+    assert!(html.contains("<a-v>withReviews</a-v>"), "{}", html);
+    assert!(html.contains("<a-at>include</a-at>"), "{}", html);
+}
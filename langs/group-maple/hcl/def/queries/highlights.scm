@@ -4,9 +4,12 @@
 (comment) @comment
 
 ; Strings and templates
+;
+; Only the literal parts of templates are strings, so interpolations and
+; directives highlight as the expressions they contain.
 (string_lit) @string
-(quoted_template) @string
-(heredoc_template) @string
+(quoted_template_start) @string
+(quoted_template_end) @string
 (template_literal) @string
 
 ; Numbers
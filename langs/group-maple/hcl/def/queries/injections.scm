@@ -0,0 +1,77 @@
+; Heredocs whose delimiter names a language: <<-EOF_JSON ... EOF_JSON
+;
+; The tag may have other words around it, separated by underscores, and
+; matches in any case. Only the literal text is injected; interpolations
+; and directives stay HCL.
+
+((heredoc_template
+  (heredoc_identifier) @_tag
+  (template_literal) @injection.content)
+ (#match? @_tag "(?i)^(\\w+_)?json(_\\w+)?$")
+ (#set! injection.language "json")
+ (#set! injection.combined))
+
+((heredoc_template
+  (heredoc_identifier) @_tag
+  (template_literal) @injection.content)
+ (#match? @_tag "(?i)^(\\w+_)?ya?ml(_\\w+)?$")
+ (#set! injection.language "yaml")
+ (#set! injection.combined))
+
+((heredoc_template
+  (heredoc_identifier) @_tag
+  (template_literal) @injection.content)
+ (#match? @_tag "(?i)^(\\w+_)?(sh|bash|shell)(_\\w+)?$")
+ (#set! injection.language "bash")
+ (#set! injection.combined))
+
+((heredoc_template
+  (heredoc_identifier) @_tag
+  (template_literal) @injection.content)
+ (#match? @_tag "(?i)^(\\w+_)?(ps1|powershell)(_\\w+)?$")
+ (#set! injection.language "powershell")
+ (#set! injection.combined))
+
+((heredoc_template
+  (heredoc_identifier) @_tag
+  (template_literal) @injection.content)
+ (#match? @_tag "(?i)^(\\w+_)?sql(_\\w+)?$")
+ (#set! injection.language "sql")
+ (#set! injection.combined))
+
+; Heredocs with a generic tag (EOF, EOT, ...) are recognised by how they
+; start: a shebang naming a shell, or cloud-init's #cloud-config header.
+
+((heredoc_template
+  (heredoc_identifier) @_tag
+  (template_literal) @injection.content) @_heredoc
+ (#not-match? @_tag "(?i)(^|_)(json|ya?ml|sh|bash|shell|ps1|powershell|sql)(_|$)")
+ (#match? @_heredoc "^<<-?\\w+\\s*#![^\\n]*\\b(ba|da|k|z)?sh\\b")
+ (#set! injection.language "bash")
+ (#set! injection.combined))
+
+((heredoc_template
+  (heredoc_identifier) @_tag
+  (template_literal) @injection.content) @_heredoc
+ (#not-match? @_tag "(?i)(^|_)(json|ya?ml|sh|bash|shell|ps1|powershell|sql)(_|$)")
+ (#match? @_heredoc "^<<-?\\w+\\s*#cloud-config\\b")
+ (#set! injection.language "yaml")
+ (#set! injection.combined))
+
+; Policy documents and other JSON-valued attributes: policy = <<EOF { ... }
+;
+; Only attributes conventionally holding JSON are considered, and only when
+; the body opens with an object or array.
+
+((attribute
+  (identifier) @_name
+  (expression
+    (template_expr
+      (heredoc_template
+        (heredoc_identifier) @_tag
+        (template_literal) @injection.content) @_heredoc)))
+ (#match? @_name "(policy|policies|document|definition|definitions|json)$")
+ (#not-match? @_tag "(?i)(^|_)(json|ya?ml|sh|bash|shell|ps1|powershell|sql)(_|$)")
+ (#match? @_heredoc "^<<-?\\w+\\s*[\\[{]")
+ (#set! injection.language "json")
+ (#set! injection.combined))
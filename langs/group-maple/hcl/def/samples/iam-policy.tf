@@ -0,0 +1,48 @@
+resource "aws_s3_bucket" "artifacts" {
+  bucket = "${var.project}-artifacts"
+}
+
+resource "aws_iam_role" "ci" {
+  name = "${var.project}-ci"
+
+  assume_role_policy = <<EOF
+{
+  "Version": "2012-10-17",
+  "Statement": [
+    {
+      "Effect": "Allow",
+      "Principal": { "Service": "codebuild.amazonaws.com" },
+      "Action": "sts:AssumeRole"
+    }
+  ]
+}
+EOF
+}
+
+resource "aws_iam_policy" "artifacts_rw" {
+  name        = "${var.project}-artifacts-rw"
+  description = "Read and write build artifacts"
+
+  policy = <<-POLICY
+    {
+      "Version": "2012-10-17",
+      "Statement": [
+        {
+          "Effect": "Allow",
+          "Action": ["s3:GetObject", "s3:PutObject"],
+          "Resource": "${aws_s3_bucket.artifacts.arn}/*"
+        },
+        {
+          "Effect": "Allow",
+          "Action": "s3:ListBucket",
+          "Resource": "${aws_s3_bucket.artifacts.arn}"
+        }
+      ]
+    }
+  POLICY
+}
+
+resource "aws_iam_role_policy_attachment" "ci_artifacts" {
+  role       = aws_iam_role.ci.name
+  policy_arn = aws_iam_policy.artifacts_rw.arn
+}
@@ -0,0 +1,42 @@
+resource "aws_instance" "web" {
+  ami           = data.aws_ami.ubuntu.id
+  instance_type = var.instance_type
+  subnet_id     = module.vpc.public_subnets[0]
+
+  user_data = <<-EOF
+    #!/bin/bash
+    set -euo pipefail
+
+    apt-get update -y
+    apt-get install -y nginx
+
+    echo "<h1>${var.project} (${terraform.workspace})</h1>" > /var/www/html/index.html
+
+    %{ for port in var.extra_ports ~}
+    ufw allow ${port}/tcp
+    %{ endfor ~}
+
+    systemctl enable --now nginx
+  EOF
+
+  tags = {
+    Name = "${var.project}-web"
+  }
+}
+
+resource "aws_launch_template" "workers" {
+  name_prefix   = "${var.project}-workers-"
+  image_id      = data.aws_ami.ubuntu.id
+  instance_type = "t3.small"
+
+  user_data = base64encode(<<-CLOUD
+    #cloud-config
+    package_update: true
+    packages:
+      - docker.io
+    runcmd:
+      - systemctl enable --now docker
+      - docker run -d --restart always ${var.worker_image}
+  CLOUD
+  )
+}
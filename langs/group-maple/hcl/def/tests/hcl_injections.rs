@@ -0,0 +1,107 @@
+//! Terraform heredocs inject the language their delimiter names, or that
+//! their contents clearly start with, leaving `${}` and `%{}` out.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_hcl::language(),
+        helpers::queries!(arborium_hcl),
+        source,
+    )
+}
+
+fn languages(injections: &[(&str, String)]) -> Vec<String> {
+    injections.iter().map(|(_, l)| l.clone()).collect()
+}
+
+#[test]
+fn test_delimiters_name_the_language() {
+    let source = "\
+a = <<EOF_JSON
+{ \"a\": 1 }
+EOF_JSON
+b = <<-YAML
+  key: value
+  YAML
+c = <<SHELL
+echo hi
+SHELL
+d = <<EOT
+plain text
+EOT
+";
+    let injections = injected(source);
+    assert_eq!(languages(&injections), ["json", "yaml", "bash"]);
+    assert!(injections[0].0.contains("\"a\": 1"));
+    assert!(!injections[0].0.contains("EOF_JSON"));
+    assert!(injections[1].0.contains("key: value"));
+}
+
+#[test]
+fn test_generic_delimiters_are_recognised_by_content() {
+    let source = "\
+user_data = <<-EOF
+  #!/bin/bash
+  apt-get install -y nginx
+EOF
+cloud = <<-EOF
+  #cloud-config
+  packages: [docker.io]
+EOF
+notes = <<-EOF
+  # just a comment
+EOF
+";
+    let injections = injected(source);
+    assert_eq!(languages(&injections), ["bash", "yaml"]);
+    assert!(injections[0].0.contains("apt-get install"));
+    assert!(injections[1].0.contains("packages:"));
+}
+
+#[test]
+fn test_policy_attributes_starting_with_json_inject_json() {
+    let source = "\
+policy = <<EOF
+{
+  \"Resource\": \"${aws_s3_bucket.b.arn}/*\"
+}
+EOF
+assume_role_policy = <<EOF
+[1, 2]
+EOF
+description = <<EOF
+{ not a policy }
+EOF
+policy_name = <<EOF
+{ nor this }
+EOF
+";
+    let injections = injected(source);
+    assert_eq!(languages(&injections), ["json", "json", "json"]);
+    let fragments: Vec<&str> = injections.iter().map(|(text, _)| *text).collect();
+    assert!(
+        fragments[0].ends_with("\"Resource\": \""),
+        "{:?}",
+        fragments
+    );
+    assert!(fragments[1].starts_with("/*\""), "{:?}", fragments);
+    assert!(fragments[2].contains("[1, 2]"), "{:?}", fragments);
+}
+
+#[test]
+fn test_interpolations_highlight_as_expressions() {
+    let mut highlighter = helpers::highlighter([(
+        "hcl",
+        TestGrammar::new(
+            arborium_hcl::language(),
+            arborium_hcl::HIGHLIGHTS_QUERY,
+            arborium_hcl::INJECTIONS_QUERY,
+        ),
+    )]);
+    let source = "name = \"${var.project}-ci\"\n";
+    let html = highlighter.highlight("hcl", source).unwrap();
+    assert!(html.contains("<a-v>var</a-v>"), "{}", html);
+    assert!(html.contains("<a-pr>project</a-pr>"), "{}", html);
+    assert!(!html.contains("<a-s>\"${"), "{}", html);
+}
@@ -1,15 +1,25 @@
+// Beyond upstream, this tolerates the dialects `conf` and `cfg` files are
+// written in: settings before the first section (php.ini, my.cnf),
+// `[[double bracket]]` sections, keys without a value
+// (`skip-name-resolve`), `\` line continuations, quoted values with escapes,
+// and `${VAR}` and `%(name)s` interpolation.
+
+// A `$` or `%` that doesn't start an interpolation
+const LITERAL_SIGIL = /\$[^{\s"]|%[^(\s"]/;
+
 module.exports = grammar({
   name: 'ini',
 
   extras: $ => [
     $.comment,
     $._blank,
-    /[\t ]/
+    /[\t\r ]/
   ],
 
   rules: {
     document: $ => seq(
       repeat($._blank),  // Eat blank lines at top of file.
+      repeat($.setting),
       repeat($.section),
     ),
 
@@ -24,22 +34,59 @@ module.exports = grammar({
     )),
 
     section_name: $ => seq(
-      '[',
-      alias(/[^\[\]\n]+/, $.text),
-      ']',
+      choice(
+        seq('[', alias(/[^\[\]\n]+/, $.text), ']'),
+        seq('[[', alias(/[^\[\]\n]+/, $.text), ']]'),
+      ),
       '\n',
     ),
 
+    // Keys may have spaces and brackets inside (`Name[de]`), and a key
+    // alone on its line is a flag
     setting: $ => seq(
-      alias(/[^;#=\s\[]+/, $.setting_name),
-      '=',
-      alias(/.+/, $.setting_value),
+      alias(/[^;#=\s\[]([^;#=\n]*[^;#=\s])?/, $.setting_name),
+      optional(seq('=', optional($.setting_value))),
       '\n',
     ),
 
+    setting_value: $ => repeat1(choice(
+      $.string,
+      $.interpolation,
+      $.line_continuation,
+      $.text,
+      // A lone `$` or `%`, like `100%`, interpolates nothing
+      alias(/[$%]/, $.text),
+    )),
+
+    // A word of a value. `;` and `#` only start a comment at the start of
+    // one, so `Categories=Utility;Development;` is all value.
+    text: _ => token(seq(
+      choice(/[^\s"$%\\;#]/, /\\\S/, LITERAL_SIGIL),
+      repeat(choice(/[^\s"$%\\]/, /\\\S/, LITERAL_SIGIL)),
+    )),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        alias(token.immediate(prec(1, /[^"\\\n]+/)), $.string_content),
+        $.escape_sequence,
+      )),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: _ => token.immediate(/\\[^\n]/),
+
+    // ${VAR}, ${section:key}, %(name)s
+    interpolation: $ => choice(
+      seq('${', alias(/[^}\n]+/, $.variable), '}'),
+      seq('%(', alias(/[^)\n]+/, $.variable), ')', token.immediate(/[a-z]/)),
+    ),
+
+    line_continuation: _ => /\\\r?\n/,
+
     // setting_name: () => /[^#=\s\[]+/,
     // setting_value: () => /[^#\n]+/,
-    comment: $ => seq(/[;#]/, alias(/.*/, $.text), '\n'),
+    comment: $ => seq(/[;#]/, alias(/.*/, $.text)),
 
     _blank: () => field('blank', '\n'),
   }
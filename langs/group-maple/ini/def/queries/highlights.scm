@@ -6,6 +6,8 @@
 [
   "["
   "]"
+  "[["
+  "]]"
 ] @punctuation.bracket
 
 "=" @operator
@@ -13,4 +15,33 @@
 (setting
   (setting_name) @property)
 
-; (setting_value) @none ; grammar does not support subtypes
+; (setting_value) @none ; plain words stay unhighlighted
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(interpolation) @punctuation.special
+
+(interpolation
+  (variable) @variable)
+
+(line_continuation) @punctuation.delimiter
+
+; A value that's a single word can be a boolean or a number
+(setting_value
+  .
+  (text) @boolean
+  .
+  (#match? @boolean "(?i)^(true|false|yes|no|on|off)$"))
+
+(setting_value
+  .
+  (text) @number
+  .
+  (#match? @number "^[+-]?([0-9]+(\\.[0-9]+)?[KMGkmg]?|0[xX][0-9a-fA-F]+)$"))
+
+; /usr/bin/env, ~/.config, ./run.sh, C:\php\ext
+(setting_value
+  (text) @string.special.path
+  (#match? @string.special.path "^(~|\\.\\.?)?/|^[A-Za-z]:\\\\"))
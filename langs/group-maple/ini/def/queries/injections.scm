@@ -0,0 +1,8 @@
+; Best effort: keys naming a command, like a desktop entry's `Exec` or
+; `post_command`, usually hold a shell command line
+((setting
+  (setting_name) @_name
+  (setting_value) @injection.content)
+  (#match? @_name "(?i)(command|exec)$")
+  (#set! injection.include-children)
+  (#set! injection.language "bash"))
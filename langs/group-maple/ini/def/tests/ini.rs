@@ -0,0 +1,127 @@
+//! INI tolerates the dialects real `conf` and `cfg` files use, and keys
+//! naming a command inject bash.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const WIREGUARD: &str = include_str!("../samples/wg0.conf");
+const DESKTOP_ENTRY: &str = include_str!("../samples/org.example.Notes.desktop");
+const PHP_INI: &str = include_str!("../samples/php.ini");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_ini::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_ini::language(), source, kind)
+}
+
+fn highlight(source: &str) -> String {
+    let mut highlighter = helpers::highlighter([
+        (
+            "ini",
+            TestGrammar::new(
+                arborium_ini::language(),
+                arborium_ini::HIGHLIGHTS_QUERY,
+                arborium_ini::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "bash",
+            TestGrammar::new(
+                arborium_bash::language(),
+                arborium_bash::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    highlighter.highlight("ini", source).unwrap()
+}
+
+#[test]
+fn test_wireguard_config_parses_cleanly() {
+    let root = parse(WIREGUARD).root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_desktop_entry_parses_cleanly() {
+    let root = parse(DESKTOP_ENTRY).root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_php_ini_parses_cleanly() {
+    let root = parse(PHP_INI).root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_dialect_constructs() {
+    let source = "\
+log = /var/log/app.log
+skip-name-resolve
+
+[[servers]]
+Name[de]=Notizen
+cmd = run --all \\
+    --quiet
+greeting = \"say \\\"hi\\\"\"
+home = ${HOME}/app
+dir = %(base)s/cache
+";
+    assert_eq!(
+        nodes(source, "setting_name"),
+        [
+            "log",
+            "skip-name-resolve",
+            "Name[de]",
+            "cmd",
+            "greeting",
+            "home",
+            "dir"
+        ]
+    );
+    assert_eq!(nodes(source, "section").len(), 1);
+    assert_eq!(nodes(source, "line_continuation").len(), 1);
+    assert_eq!(nodes(source, "string"), ["\"say \\\"hi\\\"\""]);
+    assert_eq!(nodes(source, "escape_sequence"), ["\\\"", "\\\""]);
+    assert_eq!(nodes(source, "interpolation"), ["${HOME}", "%(base)s"]);
+    assert_eq!(nodes(source, "variable"), ["HOME", "base"]);
+}
+
+#[test]
+fn test_comments_start_words_only() {
+    let source = "[Desktop Entry]\nCategories=Office;Utility;\nlevel = 3 ; the default\n";
+    assert_eq!(nodes(source, "setting_value"), ["Office;Utility;", "3"]);
+    assert_eq!(nodes(source, "comment"), ["; the default"]);
+}
+
+#[test]
+fn test_sample_captures() {
+    let html = highlight(PHP_INI);
+    assert!(html.contains("<a-t>PHP</a-t>"), "{}", html);
+    assert!(html.contains("<a-pr>memory_limit</a-pr>"), "{}", html);
+    assert!(html.contains("<a-co>On</a-co>"), "{}", html);
+    assert!(html.contains("<a-n>256M</a-n>"), "{}", html);
+    assert!(
+        html.contains("<a-s>/var/log/php/error.log</a-s>"),
+        "{}",
+        html
+    );
+    assert!(html.contains("<a-v>TMPDIR</a-v>"), "{}", html);
+
+    let html = highlight(WIREGUARD);
+    assert!(html.contains("<a-t>Peer</a-t>"), "{}", html);
+    assert!(html.contains("<a-n>51820</a-n>"), "{}", html);
+    assert!(html.contains("<a-co>false</a-co>"), "{}", html);
+}
+
+#[test]
+fn test_command_keys_inject_bash() {
+    let html = highlight(DESKTOP_ENTRY);
+    // The bash grammar takes over `Exec`, but not `Name`
+    assert!(html.contains("<a-f>notes</a-f>"), "{}", html);
+    assert!(html.contains("<a-pr>Exec</a-pr>"), "{}", html);
+    assert!(!html.contains("<a-f>Notes</a-f>"), "{}", html);
+}
@@ -0,0 +1,304 @@
+/**
+ * @file Jsonnet grammar for tree-sitter
+ * @license MIT
+ *
+ * https://jsonnet.org/ref/spec.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Lowest to highest, per the spec's operator table
+const PREC = {
+  local: 1,
+  or: 2,
+  and: 3,
+  bitor: 4,
+  bitxor: 5,
+  bitand: 6,
+  equality: 7,
+  comparison: 8,
+  shift: 9,
+  additive: 10,
+  multiplicative: 11,
+  unary: 12,
+  apply: 13,
+};
+
+module.exports = grammar({
+  name: 'jsonnet',
+
+  extras: $ => [/\s/, $.comment],
+
+  externals: $ => [$.text_block],
+
+  word: $ => $.id,
+
+  rules: {
+    document: $ => $._expr,
+
+    _expr: $ => choice(
+      $.null,
+      $.true,
+      $.false,
+      $.self,
+      $.dollar,
+      $.super,
+      $.string,
+      $.number,
+      $.object,
+      $.array,
+      $.forloop,
+      $.field_access,
+      $.index,
+      $.slice,
+      $.functioncall,
+      $.id,
+      $.local_bind,
+      $.conditional,
+      $.binary,
+      $.unary,
+      $.object_apply,
+      $.anonymous_function,
+      $.assert_expr,
+      $.import,
+      $.importstr,
+      $.importbin,
+      $.error_expr,
+      $.parenthesis,
+    ),
+
+    null: _ => 'null',
+    true: _ => 'true',
+    false: _ => 'false',
+    self: _ => 'self',
+    dollar: _ => '$',
+    super: _ => 'super',
+
+    parenthesis: $ => seq('(', $._expr, ')'),
+
+    // Objects
+
+    object: $ => seq('{', optional($._objinside), '}'),
+
+    _objinside: $ => choice(
+      seq(commaSep1($.member), optional(',')),
+      $.objforloop,
+    ),
+
+    member: $ => choice($.objlocal, $.assert, $.field),
+
+    objlocal: $ => seq('local', $.bind),
+
+    field: $ => choice(
+      seq(
+        field('name', $.fieldname),
+        optional('+'),
+        field('visibility', $._visibility),
+        field('value', $._expr),
+      ),
+      seq(
+        field('name', $.fieldname),
+        '(',
+        optional(field('params', $.params)),
+        ')',
+        field('visibility', $._visibility),
+        field('value', $._expr),
+      ),
+    ),
+
+    _visibility: _ => choice(':', '::', ':::'),
+
+    fieldname: $ => choice($.id, $.string, seq('[', $._expr, ']')),
+
+    assert: $ => seq('assert', $._expr, optional(seq(':', $._expr))),
+
+    // { [name]: value for name in names }
+    objforloop: $ => seq(
+      field('body', $.field),
+      $.forspec,
+      repeat($._compspec),
+    ),
+
+    // Arrays
+
+    array: $ => seq('[', optional(seq(commaSep1($._expr), optional(','))), ']'),
+
+    forloop: $ => seq('[', $._expr, optional(','), $.forspec, repeat($._compspec), ']'),
+
+    _compspec: $ => choice($.forspec, $.ifspec),
+
+    forspec: $ => seq('for', field('name', $.id), 'in', $._expr),
+
+    ifspec: $ => seq('if', $._expr),
+
+    // Access and calls
+
+    field_access: $ => prec(PREC.apply, seq($._expr, '.', field('field', $.id))),
+
+    index: $ => prec(PREC.apply, seq($._expr, '[', $._expr, ']')),
+
+    slice: $ => prec(PREC.apply, seq(
+      $._expr,
+      '[',
+      optional($._expr),
+      ':',
+      optional($._expr),
+      optional(seq(':', optional($._expr))),
+      ']',
+    )),
+
+    functioncall: $ => prec(PREC.apply, seq(
+      field('function', $._expr),
+      '(',
+      optional(field('args', $.args)),
+      ')',
+      optional('tailstrict'),
+    )),
+
+    args: $ => seq(commaSep1(choice($._expr, $.named_argument)), optional(',')),
+
+    named_argument: $ => seq(field('name', $.id), '=', field('value', $._expr)),
+
+    object_apply: $ => prec(PREC.apply, seq($._expr, $.object)),
+
+    // Bindings and functions
+
+    local_bind: $ => prec.right(PREC.local, seq(
+      'local',
+      commaSep1($.bind),
+      ';',
+      field('body', $._expr),
+    )),
+
+    bind: $ => choice(
+      seq(field('name', $.id), '=', field('value', $._expr)),
+      seq(
+        field('function', $.id),
+        '(',
+        optional(field('params', $.params)),
+        ')',
+        '=',
+        field('body', $._expr),
+      ),
+    ),
+
+    params: $ => seq(commaSep1($.param), optional(',')),
+
+    param: $ => seq(field('name', $.id), optional(seq('=', field('default', $._expr)))),
+
+    anonymous_function: $ => prec.right(PREC.local, seq(
+      'function',
+      '(',
+      optional(field('params', $.params)),
+      ')',
+      field('body', $._expr),
+    )),
+
+    conditional: $ => prec.right(PREC.local, seq(
+      'if',
+      field('condition', $._expr),
+      'then',
+      field('consequence', $._expr),
+      optional(seq('else', field('alternative', $._expr))),
+    )),
+
+    assert_expr: $ => prec.right(PREC.local, seq($.assert, ';', $._expr)),
+
+    error_expr: $ => prec.right(PREC.local, seq('error', $._expr)),
+
+    import: $ => seq('import', field('path', $.string)),
+
+    importstr: $ => seq('importstr', field('path', $.string)),
+
+    importbin: $ => seq('importbin', field('path', $.string)),
+
+    // Operators
+
+    unary: $ => prec(PREC.unary, seq(
+      field('operator', choice('-', '+', '!', '~')),
+      field('argument', $._expr),
+    )),
+
+    binary: $ => {
+      const table = [
+        [PREC.multiplicative, choice('*', '/', '%')],
+        [PREC.additive, choice('+', '-')],
+        [PREC.shift, choice('<<', '>>')],
+        [PREC.comparison, choice('<', '<=', '>', '>=', 'in')],
+        [PREC.equality, choice('==', '!=')],
+        [PREC.bitand, '&'],
+        [PREC.bitxor, '^'],
+        [PREC.bitor, '|'],
+        [PREC.and, '&&'],
+        [PREC.or, '||'],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._expr),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expr),
+        ),
+      )));
+    },
+
+    // Literals
+
+    id: _ => /[_a-zA-Z][_a-zA-Z0-9]*/,
+
+    number: _ => /\d+(\.\d+)?([eE][+-]?\d+)?/,
+
+    string: $ => choice(
+      seq(
+        '"',
+        repeat(choice($._string_content_double, $.escape_sequence, $.format_specifier)),
+        '"',
+      ),
+      seq(
+        '\'',
+        repeat(choice($._string_content_single, $.escape_sequence, $.format_specifier)),
+        '\'',
+      ),
+      seq('@"', repeat(token.immediate(choice(/[^"]+/, '""'))), '"'),
+      seq('@\'', repeat(token.immediate(choice(/[^']+/, '\'\''))), '\''),
+      $.text_block,
+    ),
+
+    _string_content_double: _ => token.immediate(prec(1, /[^"\\%]+|%/)),
+
+    _string_content_single: _ => token.immediate(prec(1, /[^'\\%]+|%/)),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(/["'\\/bfnrt]/, /u[0-9a-fA-F]{4}/),
+    )),
+
+    // Python-style `%` formatting: "%s", "%(name)05d", "%%"
+    format_specifier: _ => token.immediate(prec(2, seq(
+      '%',
+      optional(/\([^)]*\)/),
+      optional(/[#0\- +]+/),
+      optional(choice('*', /\d+/)),
+      optional(seq('.', choice('*', /\d+/))),
+      optional(/[hlL]/),
+      /[diouxXeEfFgGcrs%]/,
+    ))),
+
+    comment: _ => token(choice(
+      seq('//', /.*/),
+      seq('#', /.*/),
+      seq('/*', /[^*]*\*+([^/*][^*]*\*+)*/, '/'),
+    )),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
@@ -0,0 +1,130 @@
+#include "tree_sitter/parser.h"
+#include <stdbool.h>
+#include <stdint.h>
+
+enum TokenType {
+    TEXT_BLOCK,
+};
+
+// Indentation of a text block longer than this isn't compared in full
+#define MAX_INDENT 256
+
+static void advance(TSLexer *lexer) { lexer->advance(lexer, false); }
+
+static void skip(TSLexer *lexer) { lexer->advance(lexer, true); }
+
+static bool is_blank(int32_t c) { return c == ' ' || c == '\t'; }
+
+// Consume the rest of the line, including its line break.
+static void consume_line(TSLexer *lexer) {
+    while (lexer->lookahead != '\n' && !lexer->eof(lexer)) {
+        advance(lexer);
+    }
+    if (lexer->lookahead == '\n') {
+        advance(lexer);
+    }
+}
+
+static bool consume_bars(TSLexer *lexer) {
+    for (int i = 0; i < 3; i++) {
+        if (lexer->lookahead != '|') {
+            return false;
+        }
+        advance(lexer);
+    }
+    return true;
+}
+
+// A text block: `|||` (or `|||-`), a line break, then lines that all start
+// with the whitespace the first one starts with, up to a line with less
+// indentation holding the closing `|||`. Blank lines don't count.
+//
+// https://jsonnet.org/ref/spec.html#lexing
+static bool scan_text_block(TSLexer *lexer) {
+    while (lexer->lookahead == ' ' || lexer->lookahead == '\t' ||
+           lexer->lookahead == '\r' || lexer->lookahead == '\n') {
+        skip(lexer);
+    }
+
+    if (!consume_bars(lexer)) {
+        return false;
+    }
+    if (lexer->lookahead == '-') {
+        advance(lexer);
+    }
+    while (is_blank(lexer->lookahead) || lexer->lookahead == '\r') {
+        advance(lexer);
+    }
+    if (lexer->lookahead != '\n') {
+        return false;
+    }
+    advance(lexer);
+
+    // Blank lines before the first one are part of the block
+    while (lexer->lookahead == '\n' || lexer->lookahead == '\r') {
+        advance(lexer);
+    }
+
+    int32_t indent[MAX_INDENT];
+    unsigned indent_len = 0;
+    while (is_blank(lexer->lookahead)) {
+        if (indent_len < MAX_INDENT) {
+            indent[indent_len++] = lexer->lookahead;
+        }
+        advance(lexer);
+    }
+    if (indent_len == 0) {
+        return false;
+    }
+
+    for (;;) {
+        consume_line(lexer);
+        if (lexer->eof(lexer)) {
+            return false;
+        }
+
+        unsigned matched = 0;
+        while (matched < indent_len && lexer->lookahead == indent[matched]) {
+            advance(lexer);
+            matched++;
+        }
+        if (matched == indent_len) {
+            continue;
+        }
+        if (lexer->lookahead == '\n' || lexer->lookahead == '\r') {
+            continue;
+        }
+
+        // Less indented: only the closing bars may follow
+        while (is_blank(lexer->lookahead)) {
+            advance(lexer);
+        }
+        if (!consume_bars(lexer)) {
+            return false;
+        }
+        lexer->mark_end(lexer);
+        lexer->result_symbol = TEXT_BLOCK;
+        return true;
+    }
+}
+
+void *tree_sitter_jsonnet_external_scanner_create(void) { return NULL; }
+
+void tree_sitter_jsonnet_external_scanner_destroy(void *payload) {}
+
+unsigned tree_sitter_jsonnet_external_scanner_serialize(void *payload,
+                                                        char *buffer) {
+    return 0;
+}
+
+void tree_sitter_jsonnet_external_scanner_deserialize(void *payload,
+                                                      const char *buffer,
+                                                      unsigned length) {}
+
+bool tree_sitter_jsonnet_external_scanner_scan(void *payload, TSLexer *lexer,
+                                               const bool *valid_symbols) {
+    if (valid_symbols[TEXT_BLOCK] && lexer->lookahead != 0) {
+        return scan_text_block(lexer);
+    }
+    return false;
+}
@@ -0,0 +1,123 @@
+; Keywords
+
+[
+  "assert"
+  "else"
+  "error"
+  "for"
+  "function"
+  "if"
+  "import"
+  "importbin"
+  "importstr"
+  "in"
+  "local"
+  "tailstrict"
+  "then"
+] @keyword
+
+[
+  (self)
+  (dollar)
+  (super)
+] @variable.builtin
+
+[
+  (true)
+  (false)
+] @boolean
+
+(null) @constant.builtin
+
+; Variables and fields
+
+(id) @variable
+
+(param
+  name: (id) @variable.parameter)
+
+(bind
+  function: (id) @function)
+
+(field
+  name: (fieldname
+    (id) @property))
+
+(field
+  name: (fieldname
+    (string) @property))
+
+(field_access
+  field: (id) @property)
+
+(named_argument
+  name: (id) @variable.parameter)
+
+(forspec
+  name: (id) @variable)
+
+; Calls
+
+(functioncall
+  function: (id) @function.call)
+
+(functioncall
+  function: (field_access
+    field: (id) @function.call))
+
+((functioncall
+  function: (field_access
+    (id) @module))
+  (#eq? @module "std"))
+
+; Literals
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(format_specifier) @string.special
+
+(import
+  path: (string) @string.special.path)
+
+(importstr
+  path: (string) @string.special.path)
+
+(importbin
+  path: (string) @string.special.path)
+
+(number) @number
+
+(comment) @comment
+
+; Operators and punctuation
+
+(binary
+  operator: _ @operator)
+
+(unary
+  operator: _ @operator)
+
+[
+  "="
+  ":"
+  "::"
+  ":::"
+  "+"
+] @operator
+
+[
+  "."
+  ","
+  ";"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
@@ -0,0 +1,28 @@
+; Text blocks parsed by the standard library's parsers, without their
+; ||| delimiters
+
+((functioncall
+  function: (field_access
+    (id) @_std
+    field: (id) @_fn)
+  args: (args
+    .
+    (string
+      (text_block) @injection.content)))
+  (#eq? @_std "std")
+  (#eq? @_fn "parseYaml")
+  (#offset! @injection.content 0 3 0 -3)
+  (#set! injection.language "yaml"))
+
+((functioncall
+  function: (field_access
+    (id) @_std
+    field: (id) @_fn)
+  args: (args
+    .
+    (string
+      (text_block) @injection.content)))
+  (#eq? @_std "std")
+  (#eq? @_fn "parseJson")
+  (#offset! @injection.content 0 3 0 -3)
+  (#set! injection.language "json"))
@@ -0,0 +1,47 @@
+//! Text blocks handed to `std.parseYaml` and `std.parseJson` inject the
+//! matching language, without their `|||` delimiters.
+
+use arborium_test_harness::helpers;
+
+const JSONNET: &str = "\
+local defaults = std.parseYaml(|||
+  replicas: 2
+  image: nginx
+|||);
+local extra = std.parseJson(|||
+  {\"debug\": true}
+|||);
+{
+  motd: |||
+    not parsed
+  |||,
+  config: defaults + extra,
+}
+";
+
+#[test]
+fn test_parsed_text_blocks_inject_their_format() {
+    let result = helpers::plugin_parse(
+        arborium_jsonnet::language(),
+        helpers::queries!(arborium_jsonnet),
+        JSONNET,
+    );
+
+    let injections: Vec<(&str, &str)> = result
+        .injections
+        .iter()
+        .map(|injection| {
+            (
+                injection.language.as_str(),
+                &JSONNET[injection.start as usize..injection.end as usize],
+            )
+        })
+        .collect();
+    assert_eq!(
+        injections,
+        [
+            ("yaml", "\n  replicas: 2\n  image: nginx\n"),
+            ("json", "\n  {\"debug\": true}\n"),
+        ]
+    );
+}
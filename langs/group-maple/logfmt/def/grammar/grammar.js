@@ -0,0 +1,64 @@
+/**
+ * @file logfmt grammar for tree-sitter
+ * @license MIT
+ *
+ * Log records, one per line, of space-separated `key=value` pairs. Values
+ * with spaces are double-quoted, with backslash escapes; a key on its own
+ * is a flag. Timestamps, numbers and levels are told apart by the
+ * highlight queries, so every bare value is one token here.
+ * https://brandur.org/logfmt
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'logfmt',
+
+  extras: _ => [],
+
+  rules: {
+    source: $ => seq(
+      repeat(choice($._newline, seq($.record, $._newline))),
+      optional($.record),
+    ),
+
+    _newline: _ => /\r?\n/,
+
+    _space: _ => /[ \t]+/,
+
+    // level=info msg="request done" duration=12ms
+    //
+    // Pairs are always separated by whitespace, so a value never runs
+    // into the next key.
+    record: $ => seq(
+      optional($._space),
+      $.pair,
+      repeat(seq($._space, $.pair)),
+      optional($._space),
+    ),
+
+    pair: $ => seq(
+      field('key', $.key),
+      optional(seq(
+        '=',
+        optional(field('value', choice($.string, $.value))),
+      )),
+    ),
+
+    key: _ => /[^\s="]+/,
+
+    value: _ => /[^\s"]+/,
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        token.immediate(/[^"\\\r\n]+/),
+        $.escape_sequence,
+      )),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: _ => token.immediate(/\\[^\r\n]/),
+  },
+});
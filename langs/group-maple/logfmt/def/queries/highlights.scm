@@ -0,0 +1,65 @@
+(key) @property
+
+"=" @operator
+
+(value) @string
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+((value) @number
+  (#match? @number "^-?[0-9]+(\\.[0-9]+)?([eE][+-]?[0-9]+)?$"))
+
+; 12ms, 1.5s, 2h45m
+((value) @number
+  (#match? @number "^([0-9]+(\\.[0-9]+)?(ns|us|µs|ms|s|m|h))+$"))
+
+((value) @constant.builtin.boolean
+  (#any-of? @constant.builtin.boolean "true" "false"))
+
+; RFC 3339 timestamps, whatever the key
+((value) @constant
+  (#match? @constant "^[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}"))
+
+(pair
+  key: (key) @_key
+  value: [
+    (value)
+    (string)
+  ] @constant
+  (#any-of? @_key "ts" "time" "timestamp" "t"))
+
+; Levels, so themes can set errors and warnings apart
+(pair
+  key: (key) @_key
+  value: [
+    (value)
+    (string)
+  ] @error
+  (#any-of? @_key "level" "lvl" "severity")
+  (#match? @error "(?i)^\"?(error|err|fatal|panic|crit|critical|alert|emerg)\"?$"))
+
+(pair
+  key: (key) @_key
+  value: [
+    (value)
+    (string)
+  ] @keyword
+  (#any-of? @_key "level" "lvl" "severity")
+  (#match? @keyword "(?i)^\"?(warn|warning)\"?$"))
+
+(pair
+  key: (key) @_key
+  value: [
+    (value)
+    (string)
+  ] @comment
+  (#any-of? @_key "level" "lvl" "severity")
+  (#match? @comment "(?i)^\"?(debug|trace)\"?$"))
+
+; A record's message
+(pair
+  key: (key) @_key
+  value: (string) @string.special
+  (#any-of? @_key "msg" "message"))
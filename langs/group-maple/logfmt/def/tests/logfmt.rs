@@ -0,0 +1,73 @@
+//! logfmt records are `key=value` pairs; levels and timestamps get their
+//! own captures so themes can pick out errors and warnings.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/app.logfmt");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_logfmt::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_logfmt::language(), source, kind)
+}
+
+fn highlight(source: &str) -> String {
+    let grammars = [(
+        "logfmt",
+        TestGrammar::new(
+            arborium_logfmt::language(),
+            arborium_logfmt::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    helpers::highlighter(grammars)
+        .highlight("logfmt", source)
+        .unwrap()
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_pairs_strings_and_flags() {
+    let source = "level=info msg=\"say \\\"hi\\\"\" retry  path=/a=b\nnext=1\n";
+    assert_eq!(nodes(source, "record").len(), 2);
+    assert_eq!(
+        nodes(source, "key"),
+        ["level", "msg", "retry", "path", "next"]
+    );
+    assert_eq!(nodes(source, "value"), ["info", "/a=b", "1"]);
+    assert_eq!(nodes(source, "string"), ["\"say \\\"hi\\\"\""]);
+    assert_eq!(nodes(source, "escape_sequence"), ["\\\"", "\\\""]);
+}
+
+#[test]
+fn test_levels_and_timestamps() {
+    let html = highlight(SAMPLE);
+    assert!(html.contains("<a-er>error</a-er>"), "{}", html);
+    assert!(html.contains("<a-er>ERROR</a-er>"), "{}", html);
+    assert!(html.contains("<a-k>warn</a-k>"), "{}", html);
+    assert!(html.contains("<a-c>debug</a-c>"), "{}", html);
+    assert!(
+        html.contains("<a-co>2024-05-14T09:12:03.481Z</a-co>"),
+        "{}",
+        html
+    );
+    // A timestamp key makes any value a timestamp
+    assert!(
+        html.contains("<a-co>&quot;2024-05-14 09:12:11&quot;</a-co>"),
+        "{}",
+        html
+    );
+    assert!(html.contains("<a-n>12.4ms</a-n>"), "{}", html);
+    assert!(html.contains("<a-pr>duration</a-pr>"), "{}", html);
+    // `info` is an ordinary value
+    assert!(html.contains("<a-s>info</a-s>"), "{}", html);
+}
@@ -0,0 +1,306 @@
+/**
+ * @file GNU Make grammar for tree-sitter
+ * @license MIT
+ *
+ * Newlines are significant: every rule, assignment and directive ends at
+ * one, and a line starting with a tab after a rule is a recipe line.
+ * https://www.gnu.org/software/make/manual/make.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const NL = /\r?\n/;
+const WS = /[\t ]+/;
+const SPLIT = token(seq('\\', /\r?\n/));
+
+const FUNCTIONS = [
+  'subst',
+  'patsubst',
+  'strip',
+  'findstring',
+  'filter',
+  'filter-out',
+  'sort',
+  'word',
+  'words',
+  'wordlist',
+  'firstword',
+  'lastword',
+  'dir',
+  'notdir',
+  'suffix',
+  'basename',
+  'addsuffix',
+  'addprefix',
+  'join',
+  'wildcard',
+  'realpath',
+  'abspath',
+  'error',
+  'warning',
+  'info',
+  'origin',
+  'flavor',
+  'foreach',
+  'if',
+  'or',
+  'and',
+  'intcmp',
+  'call',
+  'eval',
+  'file',
+  'value',
+  'let',
+];
+
+const ASSIGNMENT_OPERATORS = ['=', ':=', '::=', ':::=', '?=', '+=', '!='];
+
+module.exports = grammar({
+  name: 'make',
+
+  extras: $ => [WS, SPLIT, $.comment],
+
+  word: $ => $.word,
+
+  rules: {
+    makefile: $ => repeat($._thing),
+
+    _thing: $ => choice(
+      $.rule,
+      $.variable_assignment,
+      $.define_directive,
+      $._directive,
+      $.conditional,
+      seq($._function, NL),
+      NL,
+    ),
+
+    word: _ => /([^\s:=$#(){}|;\\,?+!"']|\\[^\r\n])+/,
+
+    // Rules
+
+    rule: $ => seq(
+      field('targets', $.targets),
+      choice(':', '::', '&:'),
+      choice(
+        // foo: CFLAGS += -g
+        $.variable_assignment,
+        seq(
+          optional(field('normal', $.prerequisites)),
+          optional(seq('|', field('order_only', $.prerequisites))),
+          optional(seq(';', optional($.recipe_line))),
+          NL,
+          optional($.recipe),
+        ),
+      ),
+    ),
+
+    targets: $ => repeat1($._primary),
+
+    prerequisites: $ => repeat1($._primary),
+
+    // Trailing blank and comment lines are kept in the recipe rather than
+    // ending it, as make does
+    recipe: $ => prec.right(seq(
+      $._prefixed_recipe_line,
+      repeat(choice($._prefixed_recipe_line, NL)),
+    )),
+
+    _prefixed_recipe_line: $ => seq(
+      $._recipeprefix,
+      optional($.recipe_line),
+      NL,
+    ),
+
+    // A tab at the start of a line wins over whitespace wherever a recipe
+    // line can start
+    _recipeprefix: _ => token(prec(1, '\t')),
+
+    recipe_line: $ => choice(
+      seq(
+        field('modifier', $.recipe_modifier),
+        repeat($._recipe_item),
+      ),
+      repeat1($._recipe_item),
+    ),
+
+    recipe_modifier: _ => token(prec(1, /[@+\-]+/)),
+
+    _recipe_item: $ => choice($.shell_text, $._variable, $._function),
+
+    // `$$` is a literal dollar sign for the shell
+    shell_text: _ => token(repeat1(choice(
+      /[^$\r\n\\]/,
+      /\\[^\r\n]/,
+      '$$',
+    ))),
+
+    // Variables
+
+    variable_assignment: $ => seq(
+      repeat(field('modifier', choice('export', 'override', 'private'))),
+      field('name', $.word),
+      field('operator', choice(...ASSIGNMENT_OPERATORS)),
+      optional(field('value', $.text)),
+      NL,
+    ),
+
+    define_directive: $ => seq(
+      repeat(field('modifier', choice('export', 'override', 'private'))),
+      'define',
+      field('name', $.word),
+      optional(field('operator', choice(...ASSIGNMENT_OPERATORS))),
+      NL,
+      repeat(choice(seq(field('value', $.text), NL), NL)),
+      'endef',
+      NL,
+    ),
+
+    text: $ => repeat1(choice($._text_chunk, $._variable, $._function)),
+
+    _text_chunk: _ => token(repeat1(choice(/[^$#\r\n\\]/, /\\[^\r\n]/, '$$'))),
+
+    _variable: $ => choice(
+      $.variable_reference,
+      $.substitution_reference,
+      $.automatic_variable,
+    ),
+
+    variable_reference: $ => choice(
+      seq('$(', $.word, ')'),
+      seq('${', $.word, '}'),
+      // $X for single-character names
+      seq('$', token.immediate(/[A-Za-z0-9_]/)),
+    ),
+
+    // $(SRCS:.c=.o)
+    substitution_reference: $ => seq(
+      '$(',
+      field('text', $.word),
+      ':',
+      field('pattern', $.word),
+      '=',
+      field('replacement', $.word),
+      ')',
+    ),
+
+    automatic_variable: _ => token(seq('$', /[@%<?^+|*]/)),
+
+    // Functions
+
+    _function: $ => choice($.function_call, $.shell_function),
+
+    function_call: $ => seq(
+      '$(',
+      field('function', choice(...FUNCTIONS)),
+      optional($.arguments),
+      ')',
+    ),
+
+    shell_function: $ => seq(
+      '$(',
+      field('function', 'shell'),
+      optional($.shell_command),
+      ')',
+    ),
+
+    shell_command: $ => repeat1(choice(
+      alias(token(repeat1(/[^$()\r\n\\]|\\[^\r\n]|\$\$/)), $.shell_text),
+      $._variable,
+      $._function,
+    )),
+
+    arguments: $ => choice(
+      seq($.argument, repeat(seq(',', optional($.argument)))),
+      repeat1(seq(',', optional($.argument))),
+    ),
+
+    argument: $ => repeat1(choice(
+      token(repeat1(/[^$(),\r\n\\]|\\[^\r\n]|\$\$/)),
+      $._variable,
+      $._function,
+    )),
+
+    _primary: $ => choice($.word, $._variable, $._function),
+
+    list: $ => repeat1($._primary),
+
+    // Directives
+
+    _directive: $ => choice(
+      $.include_directive,
+      $.export_directive,
+      $.unexport_directive,
+      $.vpath_directive,
+    ),
+
+    include_directive: $ => seq(
+      choice('include', '-include', 'sinclude'),
+      field('filenames', $.list),
+      NL,
+    ),
+
+    export_directive: $ => seq('export', optional(field('variables', $.list)), NL),
+
+    unexport_directive: $ => seq('unexport', optional(field('variables', $.list)), NL),
+
+    vpath_directive: $ => seq('vpath', optional($.list), NL),
+
+    // Conditionals
+
+    conditional: $ => seq(
+      field('condition', $._conditional_directive),
+      optional(field('consequence', $._conditional_consequence)),
+      repeat(field('alternative', $.elsif_directive)),
+      optional(field('alternative', $.else_directive)),
+      'endif',
+      NL,
+    ),
+
+    _conditional_directive: $ => choice(
+      $.ifeq_directive,
+      $.ifneq_directive,
+      $.ifdef_directive,
+      $.ifndef_directive,
+    ),
+
+    _conditional_consequence: $ => repeat1($._thing),
+
+    elsif_directive: $ => seq(
+      'else',
+      field('condition', $._conditional_directive),
+      optional(field('consequence', $._conditional_consequence)),
+    ),
+
+    else_directive: $ => seq(
+      'else',
+      NL,
+      optional(field('consequence', $._conditional_consequence)),
+    ),
+
+    ifeq_directive: $ => seq('ifeq', $._comparison, NL),
+
+    ifneq_directive: $ => seq('ifneq', $._comparison, NL),
+
+    ifdef_directive: $ => seq('ifdef', field('variable', $._primary), NL),
+
+    ifndef_directive: $ => seq('ifndef', field('variable', $._primary), NL),
+
+    // ifeq (a,b) or ifeq "a" "b"
+    _comparison: $ => choice(
+      seq(
+        '(',
+        optional(field('arg0', $.argument)),
+        ',',
+        optional(field('arg1', $.argument)),
+        ')',
+      ),
+      seq(field('arg0', $.string), field('arg1', $.string)),
+    ),
+
+    string: _ => token(choice(/"[^"\r\n]*"/, /'[^'\r\n]*'/)),
+
+    comment: _ => token(prec(-1, /#.*/)),
+  },
+});
@@ -0,0 +1,115 @@
+; Directives
+
+[
+  "include"
+  "-include"
+  "sinclude"
+  "export"
+  "unexport"
+  "override"
+  "private"
+  "vpath"
+  "define"
+  "endef"
+] @keyword
+
+[
+  "ifeq"
+  "ifneq"
+  "ifdef"
+  "ifndef"
+  "else"
+  "endif"
+] @keyword.conditional
+
+; Rules
+
+(targets
+  (word) @function)
+
+((targets
+  (word) @function.builtin)
+  (#match? @function.builtin "^\\.[A-Z_]+$"))
+
+(prerequisites
+  (word) @string.special.path)
+
+(include_directive
+  filenames: (list
+    (word) @string.special.path))
+
+(recipe_modifier) @operator
+
+; Variables
+
+(variable_assignment
+  name: (word) @variable)
+
+(define_directive
+  name: (word) @variable)
+
+(variable_reference
+  (word) @variable)
+
+(substitution_reference
+  text: (word) @variable)
+
+(automatic_variable) @variable.builtin
+
+(ifdef_directive
+  variable: (word) @variable)
+
+(ifndef_directive
+  variable: (word) @variable)
+
+(export_directive
+  variables: (list
+    (word) @variable))
+
+(unexport_directive
+  variables: (list
+    (word) @variable))
+
+; Functions
+
+(function_call
+  function: _ @function.builtin)
+
+(shell_function
+  function: _ @function.builtin)
+
+; Literals
+
+(string) @string
+
+(comment) @comment
+
+; Punctuation and operators
+
+[
+  "="
+  ":="
+  "::="
+  ":::="
+  "?="
+  "+="
+  "!="
+] @operator
+
+[
+  ":"
+  "::"
+  "&:"
+  "|"
+  ";"
+  ","
+] @punctuation.delimiter
+
+[
+  "$("
+  "${"
+  "$"
+  "("
+  ")"
+  "}"
+] @punctuation.special
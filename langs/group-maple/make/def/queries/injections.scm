@@ -0,0 +1,10 @@
+; Recipe lines are shell. They are stitched into one bash document with the
+; make references blanked out, so a command continued over several lines
+; parses as one.
+((recipe_line
+  (shell_text) @injection.content)
+  (#set! injection.language "bash")
+  (#set! injection.combined))
+
+((shell_command) @injection.content
+  (#set! injection.language "bash"))
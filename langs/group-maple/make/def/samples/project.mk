@@ -0,0 +1,60 @@
+# Build, test and package a small C project.
+
+PROJECT   := tidepool
+VERSION   ?= $(shell git describe --tags --always 2>/dev/null || echo dev)
+PREFIX    ?= /usr/local
+BUILD_DIR := build
+
+CC      ?= cc
+CFLAGS  += -std=c11 -Wall -Wextra -O2
+LDLIBS  += -lm
+
+SRCS := $(wildcard src/*.c)
+OBJS := $(patsubst src/%.c,$(BUILD_DIR)/%.o,$(SRCS))
+DEPS := $(OBJS:.o=.d)
+
+ifeq ($(OS),Windows_NT)
+    EXE := .exe
+else ifeq ($(shell uname -s),Darwin)
+    LDLIBS += -framework CoreFoundation
+else
+    EXE :=
+endif
+
+ifndef NO_COLOR
+export TERM_COLORS = 1
+endif
+
+define banner
+@printf '%s %s\n' "$(1)" "$(PROJECT)"
+endef
+
+.PHONY: all clean install test
+
+all: $(BUILD_DIR)/$(PROJECT)$(EXE)
+
+$(BUILD_DIR)/$(PROJECT)$(EXE): $(OBJS) | $(BUILD_DIR)
+	$(call banner,Linking)
+	$(CC) $(LDFLAGS) -o $@ $^ $(LDLIBS)
+
+$(BUILD_DIR)/%.o: src/%.c | $(BUILD_DIR)
+	@echo "  CC  $<"
+	$(CC) $(CFLAGS) -MMD -MP -c $< -o $@
+
+$(BUILD_DIR):
+	mkdir -p $@
+
+test: all
+	@for t in tests/*.sh; do \
+	  if ! sh "$$t"; then \
+	    echo "FAIL: $$t"; exit 1; \
+	  fi; \
+	done
+
+install: all
+	install -d $(DESTDIR)$(PREFIX)/bin
+	install -m 755 $(BUILD_DIR)/$(PROJECT)$(EXE) $(DESTDIR)$(PREFIX)/bin/
+
+clean: ; rm -rf $(BUILD_DIR)
+
+-include $(DEPS)
@@ -0,0 +1,26 @@
+OBJS := $(patsubst %.c,%.o,$(SRCS))
+# <- variable
+#    ^ operator
+#         ^ function.builtin
+
+ifeq ($(CC),gcc)
+# <- keyword.conditional
+CFLAGS += -fanalyzer
+endif
+# <- keyword.conditional
+
+.PHONY: all
+# <- function.builtin
+
+all: $(OBJS)
+	@echo "linking $@"
+	# <- operator
+	#              ^ variable.builtin
+
+include config.mk
+# <- keyword
+#       ^ string.special.path
+
+check: all
+# <- function
+#      ^ string.special.path
@@ -0,0 +1,79 @@
+//! Makefile recipes inject their shell text as one combined bash document,
+//! so commands continued over several lines still parse as one.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const MAKEFILE: &str = "\
+SRCS := $(wildcard src/*.c)
+VERSION := $(shell git describe --tags)
+
+test: $(SRCS)
+\t@echo \"testing $(VERSION)\"
+\tfor t in tests/*.sh; do \\
+\t  sh \"$$t\" || exit 1; \\
+\tdone
+";
+
+#[test]
+fn test_recipe_lines_are_combined_bash() {
+    let result = helpers::plugin_parse(
+        arborium_make::language(),
+        helpers::queries!(arborium_make),
+        MAKEFILE,
+    );
+
+    let recipe: Vec<&str> = result
+        .injections
+        .iter()
+        .filter(|injection| injection.combined)
+        .map(|injection| {
+            assert_eq!(injection.language, "bash");
+            &MAKEFILE[injection.start as usize..injection.end as usize]
+        })
+        .collect();
+    assert!(
+        recipe.iter().any(|text| text.contains("for t in")),
+        "{:?}",
+        recipe
+    );
+    // Make's own references are left out of the shell text
+    assert!(
+        recipe.iter().all(|text| !text.contains("$(")),
+        "{:?}",
+        recipe
+    );
+
+    let shell: Vec<&str> = result
+        .injections
+        .iter()
+        .filter(|injection| !injection.combined)
+        .map(|injection| &MAKEFILE[injection.start as usize..injection.end as usize])
+        .collect();
+    assert_eq!(shell, ["git describe --tags"]);
+}
+
+#[test]
+fn test_recipes_highlight_as_bash() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "make",
+            TestGrammar::new(
+                arborium_make::language(),
+                arborium_make::HIGHLIGHTS_QUERY,
+                arborium_make::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "bash",
+            TestGrammar::new(
+                arborium_bash::language(),
+                arborium_bash::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    let html = highlighter.highlight("make", MAKEFILE).unwrap();
+    assert!(html.contains("<a-k>for</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>done</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>test</a-f>"), "{}", html);
+}
@@ -0,0 +1,290 @@
+/**
+ * @file Nickel grammar for tree-sitter
+ * @license MIT
+ *
+ * Multiline strings are only recognized with a single `%` in their
+ * delimiters (`m%"…"%`), which is what nearly all code uses.
+ * https://nickel-lang.org/user-manual/syntax
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  arrow: 1,
+  pipe: 2,
+  or: 3,
+  and: 4,
+  equality: 5,
+  comparison: 6,
+  merge: 7,
+  concat: 8,
+  additive: 9,
+  multiplicative: 10,
+  unary: 11,
+  application: 12,
+  field: 13,
+};
+
+module.exports = grammar({
+  name: 'nickel',
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.ident,
+
+  rules: {
+    source_file: $ => $._term,
+
+    _term: $ => choice(
+      $.let_expression,
+      $.fun_expression,
+      $.if_expression,
+      $.forall_type,
+      $.annotated_expression,
+      $._infix_expression,
+    ),
+
+    let_expression: $ => prec.right(seq(
+      'let',
+      optional('rec'),
+      commaSep1($.let_binding),
+      'in',
+      field('body', $._term),
+    )),
+
+    let_binding: $ => seq(
+      field('pattern', $._pattern),
+      repeat($.annotation),
+      '=',
+      field('value', $._term),
+    ),
+
+    fun_expression: $ => prec.right(seq(
+      'fun',
+      repeat1(field('parameter', $._pattern)),
+      '=>',
+      field('body', $._term),
+    )),
+
+    if_expression: $ => prec.right(seq(
+      'if',
+      field('condition', $._term),
+      'then',
+      field('consequence', $._term),
+      'else',
+      field('alternative', $._term),
+    )),
+
+    // forall a. Array a -> a
+    forall_type: $ => prec.right(seq(
+      'forall',
+      repeat1($.ident),
+      '.',
+      $._term,
+    )),
+
+    annotated_expression: $ => prec.right(seq(
+      field('expression', $._infix_expression),
+      repeat1($.annotation),
+    )),
+
+    // `| Contract`, `: Type` and the field metadata keywords
+    annotation: $ => prec.right(choice(
+      seq(':', field('type', $._infix_expression)),
+      seq('|', field('contract', $._infix_expression)),
+      seq('|', 'default'),
+      seq('|', 'optional'),
+      seq('|', 'force'),
+      seq('|', 'not_exported'),
+      seq('|', 'doc', $._string),
+      seq('|', 'priority', $.number),
+    )),
+
+    // Infix operators
+
+    _infix_expression: $ => choice(
+      $.binary_expression,
+      $.arrow_type,
+      $.unary_expression,
+      $._application,
+    ),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.pipe, '|>'],
+        [PREC.or, '||'],
+        [PREC.and, '&&'],
+        [PREC.equality, choice('==', '!=')],
+        [PREC.comparison, choice('<', '<=', '>', '>=')],
+        [PREC.merge, '&'],
+        [PREC.concat, choice('++', '@')],
+        [PREC.additive, choice('+', '-')],
+        [PREC.multiplicative, choice('*', '/', '%')],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._infix_expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._infix_expression),
+        ),
+      )));
+    },
+
+    arrow_type: $ => prec.right(PREC.arrow, seq(
+      field('parameter', $._infix_expression),
+      '->',
+      field('result', $._infix_expression),
+    )),
+
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice('!', '-')),
+      field('operand', $._infix_expression),
+    )),
+
+    _application: $ => choice($.application, $._atom),
+
+    application: $ => prec.left(PREC.application, seq(
+      field('function', $._application),
+      field('argument', $._atom),
+    )),
+
+    // Atoms
+
+    _atom: $ => choice(
+      $.ident,
+      $.number,
+      $.boolean,
+      $.null,
+      $.builtin_type,
+      $.enum_tag,
+      $._string,
+      $.record,
+      $.dictionary_type,
+      $.enum_type,
+      $.array,
+      $.match_expression,
+      $.import,
+      $.field_access,
+      $.parenthesized_expression,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._term, ')'),
+
+    field_access: $ => prec.left(PREC.field, seq(
+      field('record', $._atom),
+      '.',
+      field('field', $._field_name),
+    )),
+
+    import: $ => seq('import', field('path', $.string)),
+
+    record: $ => seq(
+      '{',
+      optional(seq(
+        commaSep1($.record_field),
+        optional(seq(',', '..')),
+        optional(','),
+      )),
+      '}',
+    ),
+
+    record_field: $ => seq(
+      field('path', $.field_path),
+      repeat($.annotation),
+      optional(seq('=', field('value', $._term))),
+    ),
+
+    field_path: $ => seq($._field_name, repeat(seq('.', $._field_name))),
+
+    _field_name: $ => choice($.ident, $.string),
+
+    // {_ : Number}
+    dictionary_type: $ => seq('{', '_', ':', $._term, '}'),
+
+    // [| 'Foo, 'Bar |]
+    enum_type: $ => seq('[|', commaSep1($.enum_tag), optional(','), '|]'),
+
+    array: $ => seq('[', optional(seq(commaSep1($._term), optional(','))), ']'),
+
+    match_expression: $ => seq(
+      'match',
+      '{',
+      optional(seq(commaSep1($.match_branch), optional(','))),
+      '}',
+    ),
+
+    match_branch: $ => seq(
+      field('pattern', $._pattern),
+      optional(seq('if', field('guard', $._term))),
+      '=>',
+      field('body', $._term),
+    ),
+
+    _pattern: $ => choice(
+      $.ident,
+      '_',
+      $.number,
+      $.boolean,
+      $.null,
+      $.string,
+      $.enum_pattern,
+    ),
+
+    enum_pattern: $ => prec.right(seq($.enum_tag, optional(choice($.ident, '_')))),
+
+    // Literals
+
+    boolean: _ => choice('true', 'false'),
+
+    null: _ => 'null',
+
+    builtin_type: _ => choice('Number', 'String', 'Bool', 'Dyn', 'Array'),
+
+    number: _ => /\d+(\.\d+)?([eE][+-]?\d+)?/,
+
+    enum_tag: _ => token(seq('\'', choice(/[_a-zA-Z][_a-zA-Z0-9]*/, /"[^"]*"/))),
+
+    ident: _ => /_?[a-zA-Z][_a-zA-Z0-9\-']*/,
+
+    // Strings
+
+    _string: $ => choice($.string, $.multiline_string),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        token.immediate(prec(1, /[^"\\%]+|%/)),
+        $.escape_sequence,
+        $.interpolation,
+      )),
+      '"',
+    ),
+
+    multiline_string: $ => seq(
+      'm%"',
+      repeat(choice(
+        token.immediate(prec(1, /([^"%]|"[^%]|%[^{])+|"|%/)),
+        $.interpolation,
+      )),
+      token.immediate('"%'),
+    ),
+
+    escape_sequence: _ => token.immediate(seq('\\', /["\\%nrt]/)),
+
+    interpolation: $ => seq(token.immediate('%{'), $._term, '}'),
+
+    comment: _ => token(seq('#', /.*/)),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
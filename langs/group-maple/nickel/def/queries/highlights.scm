@@ -0,0 +1,134 @@
+; Keywords
+
+[
+  "else"
+  "forall"
+  "fun"
+  "if"
+  "import"
+  "in"
+  "let"
+  "match"
+  "rec"
+  "then"
+] @keyword
+
+[
+  "default"
+  "doc"
+  "force"
+  "not_exported"
+  "optional"
+  "priority"
+] @keyword.modifier
+
+(boolean) @boolean
+
+(null) @constant.builtin
+
+; Literals
+
+[
+  (string)
+  (multiline_string)
+] @string
+
+(escape_sequence) @string.escape
+
+(number) @number
+
+(enum_tag) @constant
+
+; Identifiers
+
+(ident) @variable
+
+((ident) @module
+  (#eq? @module "std"))
+
+(builtin_type) @type.builtin
+
+(let_binding
+  pattern: (ident) @variable)
+
+(fun_expression
+  parameter: (ident) @variable.parameter)
+
+(forall_type
+  (ident) @type)
+
+(application
+  function: (ident) @function.call)
+
+(application
+  function: (field_access
+    field: (ident) @function.call))
+
+; Fields
+
+(record_field
+  path: (field_path
+    (ident) @property))
+
+(record_field
+  path: (field_path
+    (string) @property))
+
+(field_access
+  field: (ident) @property)
+
+; Types and contracts
+
+(annotation
+  type: (ident) @type)
+
+(annotation
+  contract: (ident) @type)
+
+(annotation
+  contract: (field_access
+    field: (ident) @type))
+
+(import
+  path: (string) @string.special.path)
+
+; Operators and punctuation
+
+(binary_expression
+  operator: _ @operator)
+
+(unary_expression
+  operator: _ @operator)
+
+[
+  "="
+  "=>"
+  "->"
+  "|"
+  ":"
+  ".."
+] @operator
+
+[
+  "."
+  ","
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+  "[|"
+  "|]"
+] @punctuation.bracket
+
+(interpolation
+  [
+    "%{"
+    "}"
+  ] @punctuation.special)
+
+(comment) @comment
@@ -0,0 +1,235 @@
+/**
+ * @file PromQL grammar for tree-sitter
+ * @license MIT
+ *
+ * One Prometheus query expression: vector selectors with label matchers,
+ * `[5m]` ranges and `[1h:1m]` subqueries, `offset` and `@` modifiers,
+ * function calls, aggregations with `by`/`without`, and binary operators
+ * with `bool` and `on`/`ignoring`/`group_left` vector matching.
+ * https://prometheus.io/docs/prometheus/latest/querying/basics/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Binary operators bind from `or` up to `^`; unary minus binds tighter
+// than everything but `^`, and modifiers tighter still.
+const PREC = {
+  or: 1,
+  and: 2,
+  comparison: 3,
+  additive: 4,
+  multiplicative: 5,
+  unary: 6,
+  power: 7,
+  modifier: 8,
+};
+
+const AGGREGATIONS = [
+  'avg',
+  'bottomk',
+  'count',
+  'count_values',
+  'group',
+  'limit_ratio',
+  'limitk',
+  'max',
+  'min',
+  'quantile',
+  'stddev',
+  'stdvar',
+  'sum',
+  'topk',
+];
+
+/**
+ * @param {Rule} rule
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
+
+module.exports = grammar({
+  name: 'promql',
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    query: $ => optional($._expression),
+
+    comment: _ => token(seq('#', /.*/)),
+
+    _expression: $ => choice(
+      $.number_literal,
+      $.string_literal,
+      $.instant_vector_selector,
+      $.range_selector,
+      $.subquery,
+      $.offset_expression,
+      $.at_expression,
+      $.function_call,
+      $.aggregation,
+      $.unary_expression,
+      $.binary_expression,
+      $.parenthesized_expression,
+    ),
+
+    // http_requests_total{job="api", status=~"5.."}
+    instant_vector_selector: $ => choice(
+      seq(
+        field('metric', alias($.identifier, $.metric_name)),
+        optional(field('labels', $.label_selectors)),
+      ),
+      field('labels', $.label_selectors),
+    ),
+
+    label_selectors: $ => seq(
+      '{',
+      optional(seq(commaSep1($.label_matcher), optional(','))),
+      '}',
+    ),
+
+    label_matcher: $ => seq(
+      field('label', $._label_name),
+      field('operator', choice('=', '!=', '=~', '!~')),
+      field('value', $.string_literal),
+    ),
+
+    _label_name: $ => alias($.identifier, $.label_name),
+
+    // http_requests_total[5m]; a subquery reads the same up to its `:`
+    range_selector: $ => prec(PREC.modifier, seq(
+      field('vector', $._expression),
+      '[',
+      field('range', $.duration),
+      ']',
+    )),
+
+    subquery: $ => prec(PREC.modifier, seq(
+      field('vector', $._expression),
+      '[',
+      field('range', $.duration),
+      ':',
+      optional(field('resolution', $.duration)),
+      ']',
+    )),
+
+    offset_expression: $ => prec(PREC.modifier, seq(
+      field('vector', $._expression),
+      'offset',
+      optional('-'),
+      field('offset', $.duration),
+    )),
+
+    at_expression: $ => prec(PREC.modifier, seq(
+      field('vector', $._expression),
+      '@',
+      field('time', choice(
+        $.number_literal,
+        seq(choice('start', 'end'), '(', ')'),
+      )),
+    )),
+
+    // histogram_quantile(0.99, rate(...))
+    function_call: $ => seq(
+      field('function', alias($.identifier, $.function_name)),
+      '(',
+      optional(commaSep1($._expression)),
+      ')',
+    ),
+
+    // sum by (job) (rate(...)), or with the grouping after the arguments
+    aggregation: $ => seq(
+      field('operator', $.aggregation_operator),
+      optional(field('grouping', $.grouping)),
+      '(',
+      commaSep1($._expression),
+      ')',
+      optional(field('grouping', $.grouping)),
+    ),
+
+    aggregation_operator: _ => choice(...AGGREGATIONS),
+
+    grouping: $ => seq(choice('by', 'without'), $.label_list),
+
+    label_list: $ => seq(
+      '(',
+      optional(seq(commaSep1($._label_name), optional(','))),
+      ')',
+    ),
+
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice('-', '+')),
+      field('operand', $._expression),
+    )),
+
+    binary_expression: $ => choice(
+      ...[
+        [PREC.or, choice('or')],
+        [PREC.and, choice('and', 'unless')],
+        [PREC.comparison, choice('==', '!=', '>', '<', '>=', '<=')],
+        [PREC.additive, choice('+', '-')],
+        [PREC.multiplicative, choice('*', '/', '%', 'atan2')],
+      ].map(([precedence, operator]) => prec.left(precedence, seq(
+        field('left', $._expression),
+        field('operator', operator),
+        optional('bool'),
+        optional(field('matching', $.vector_matching)),
+        field('right', $._expression),
+      ))),
+      prec.right(PREC.power, seq(
+        field('left', $._expression),
+        field('operator', '^'),
+        optional(field('matching', $.vector_matching)),
+        field('right', $._expression),
+      )),
+    ),
+
+    // on (job) group_left (team)
+    vector_matching: $ => seq(
+      choice('on', 'ignoring'),
+      $.label_list,
+      optional($.group_modifier),
+    ),
+
+    // A `(` after group_left is always its label list
+    group_modifier: $ => prec.right(seq(
+      choice('group_left', 'group_right'),
+      optional($.label_list),
+    )),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    identifier: _ => /[a-zA-Z_:][a-zA-Z0-9_:]*/,
+
+    duration: _ => /([0-9]+(ms|[smhdwy]))+/,
+
+    number_literal: _ => choice(
+      /0[xX][0-9a-fA-F]+/,
+      /[0-9]+(\.[0-9]*)?([eE][+-]?[0-9]+)?/,
+      /\.[0-9]+([eE][+-]?[0-9]+)?/,
+      'Inf',
+      'NaN',
+    ),
+
+    string_literal: $ => choice(
+      seq(
+        '"',
+        repeat(choice(token.immediate(/[^"\\\n]+/), $.escape_sequence)),
+        token.immediate('"'),
+      ),
+      seq(
+        '\'',
+        repeat(choice(token.immediate(/[^'\\\n]+/), $.escape_sequence)),
+        token.immediate('\''),
+      ),
+      /`[^`]*`/,
+    ),
+
+    escape_sequence: _ => token.immediate(
+      /\\(["'\\abfnrtv]|x[0-9a-fA-F]{2}|u[0-9a-fA-F]{4}|[0-7]{3})/,
+    ),
+  },
+});
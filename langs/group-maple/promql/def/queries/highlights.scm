@@ -0,0 +1,75 @@
+(comment) @comment
+
+(metric_name) @variable
+
+(label_name) @property
+
+(label_matcher
+  operator: _ @operator)
+
+(function_name) @function
+
+(aggregation_operator) @function.builtin
+
+; Aggregation and vector matching modifiers
+[
+  "by"
+  "without"
+  "on"
+  "ignoring"
+  "group_left"
+  "group_right"
+  "bool"
+] @keyword.modifier
+
+"offset" @keyword
+
+[
+  "start"
+  "end"
+] @function.builtin
+
+[
+  "and"
+  "or"
+  "unless"
+  "atan2"
+] @keyword.operator
+
+[
+  "+"
+  "-"
+  "*"
+  "/"
+  "%"
+  "^"
+  "=="
+  "!="
+  ">"
+  "<"
+  ">="
+  "<="
+  "@"
+] @operator
+
+(duration) @number
+
+(number_literal) @number
+
+(string_literal) @string
+
+(escape_sequence) @string.escape
+
+[
+  "("
+  ")"
+  "{"
+  "}"
+  "["
+  "]"
+] @punctuation.bracket
+
+[
+  ","
+  ":"
+] @punctuation.delimiter
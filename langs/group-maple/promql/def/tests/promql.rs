@@ -0,0 +1,104 @@
+//! PromQL modifiers bind tighter than any operator, and aggregations take
+//! their grouping before or after the arguments.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/error_ratio.promql");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_promql::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_promql::language(), source, kind)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_selectors_and_modifiers() {
+    let source = "a + rate(b{job=\"api\", code!~\"2..\"}[5m] offset 1w) @ 1700000000";
+    assert_eq!(nodes(source, "metric_name"), ["a", "b"]);
+    assert_eq!(nodes(source, "label_name"), ["job", "code"]);
+    assert_eq!(
+        nodes(source, "range_selector"),
+        ["b{job=\"api\", code!~\"2..\"}[5m]"]
+    );
+    assert_eq!(
+        nodes(source, "offset_expression"),
+        ["b{job=\"api\", code!~\"2..\"}[5m] offset 1w"]
+    );
+    // `@` applies to the call, not to the sum
+    assert_eq!(
+        nodes(source, "at_expression"),
+        ["rate(b{job=\"api\", code!~\"2..\"}[5m] offset 1w) @ 1700000000"]
+    );
+    assert_eq!(nodes(source, "duration"), ["5m", "1w"]);
+}
+
+#[test]
+fn test_subqueries() {
+    let source = "max_over_time(deriv(x[10m])[1h:5m])";
+    assert_eq!(nodes(source, "subquery"), ["deriv(x[10m])[1h:5m]"]);
+    assert_eq!(nodes(source, "range_selector"), ["x[10m]"]);
+    assert_eq!(nodes(source, "function_name"), ["max_over_time", "deriv"]);
+}
+
+#[test]
+fn test_aggregation_grouping_either_side() {
+    let source = "sum by (job) (x) / on (job) group_left (team) count(y) without (pod)";
+    assert_eq!(nodes(source, "aggregation_operator"), ["sum", "count"]);
+    assert_eq!(nodes(source, "grouping"), ["by (job)", "without (pod)"]);
+    assert_eq!(
+        nodes(source, "vector_matching"),
+        ["on (job) group_left (team)"]
+    );
+}
+
+#[test]
+fn test_operator_precedence() {
+    let source = "a or b and c > bool d + e * f ^ g ^ h";
+    let tree = parse(source);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+    let top = root.named_child(0).unwrap();
+    assert_eq!(
+        &source[top.child_by_field_name("left").unwrap().byte_range()],
+        "a"
+    );
+    let power = nodes(source, "binary_expression")
+        .into_iter()
+        .filter(|text| text.starts_with("f ^"))
+        .collect::<Vec<_>>();
+    // `^` is right-associative
+    assert_eq!(power, ["f ^ g ^ h"]);
+}
+
+#[test]
+fn test_sample_captures() {
+    let grammars = [(
+        "promql",
+        TestGrammar::new(
+            arborium_promql::language(),
+            arborium_promql::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    let html = helpers::highlighter(grammars)
+        .highlight("promql", SAMPLE)
+        .unwrap();
+    assert!(html.contains("<a-v>http_requests_total</a-v>"), "{}", html);
+    assert!(html.contains("<a-pr>status</a-pr>"), "{}", html);
+    assert!(html.contains("<a-o>=~</a-o>"), "{}", html);
+    assert!(html.contains("<a-f>histogram_quantile</a-f>"), "{}", html);
+    assert!(html.contains("<a-f>sum</a-f>"), "{}", html);
+    assert!(html.contains("<a-k>by</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>offset</a-k>"), "{}", html);
+    assert!(html.contains("<a-n>5m</a-n>"), "{}", html);
+}
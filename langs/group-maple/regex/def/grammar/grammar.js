@@ -0,0 +1,188 @@
+/**
+ * @file Regular expression grammar for tree-sitter
+ * @license MIT
+ *
+ * The pattern inside a regex literal or string, as injected by host
+ * languages. Node names follow tree-sitter-regex so queries written for
+ * it carry over. ECMAScript syntax is the baseline; the common additions
+ * of Python, Rust, .NET and PCRE (`(?P<name>)`, inline flags, `\A`, `\z`,
+ * POSIX classes) are accepted too, so one grammar serves every host.
+ * https://tc39.es/ecma262/#sec-patterns
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'regex',
+
+  extras: _ => [],
+
+  rules: {
+    pattern: $ => choice($.alternation, $.term),
+
+    alternation: $ => seq(
+      optional($.term),
+      repeat1(seq('|', optional($.term))),
+    ),
+
+    term: $ => repeat1(seq(
+      choice(
+        $.start_assertion,
+        $.end_assertion,
+        $.boundary_assertion,
+        $.non_boundary_assertion,
+        $.lookaround_assertion,
+        $.pattern_character,
+        $.character_class,
+        $.any_character,
+        $.decimal_escape,
+        $.character_class_escape,
+        $._character_escape,
+        $.backreference_escape,
+        $.anonymous_capturing_group,
+        $.named_capturing_group,
+        $.non_capturing_group,
+        $.flags_group,
+      ),
+      optional($._quantifier),
+    )),
+
+    // Anchors
+
+    start_assertion: _ => choice('^', '\\A'),
+
+    end_assertion: _ => choice('$', '\\z', '\\Z'),
+
+    boundary_assertion: _ => '\\b',
+
+    non_boundary_assertion: _ => '\\B',
+
+    lookaround_assertion: $ => seq(
+      choice('(?=', '(?!', '(?<=', '(?<!'),
+      optional($.pattern),
+      ')',
+    ),
+
+    // Groups
+
+    anonymous_capturing_group: $ => seq('(', optional($.pattern), ')'),
+
+    named_capturing_group: $ => seq(
+      choice('(?<', '(?P<'),
+      $.group_name,
+      '>',
+      optional($.pattern),
+      ')',
+    ),
+
+    non_capturing_group: $ => seq('(?:', optional($.pattern), ')'),
+
+    // (?i), (?x-s), (?i:...)
+    flags_group: $ => seq(
+      '(?',
+      choice(
+        seq($.flags, optional(seq('-', $.flags))),
+        seq('-', $.flags),
+      ),
+      choice(')', seq(':', optional($.pattern), ')')),
+    ),
+
+    flags: _ => /[a-zA-Z]+/,
+
+    group_name: _ => /[A-Za-z_][A-Za-z0-9_]*/,
+
+    // Quantifiers
+
+    _quantifier: $ => choice(
+      $.zero_or_more,
+      $.one_or_more,
+      $.optional,
+      $.count_quantifier,
+    ),
+
+    zero_or_more: $ => seq('*', optional($.lazy)),
+
+    one_or_more: $ => seq('+', optional($.lazy)),
+
+    optional: $ => seq('?', optional($.lazy)),
+
+    // {3}, {2,}, {2,5}; a brace that isn't one is a literal character
+    count_quantifier: $ => seq(
+      token(/\{[0-9]+(,[0-9]*)?\}/),
+      optional($.lazy),
+    ),
+
+    lazy: _ => '?',
+
+    // Characters
+
+    pattern_character: _ => /[^\^$\\.*+?()\[|]/,
+
+    any_character: _ => '.',
+
+    character_class: $ => seq(
+      '[',
+      optional(alias('^', $.negation)),
+      repeat(choice(
+        $.class_range,
+        $.class_character,
+        $.posix_character_class,
+        $.character_class_escape,
+        $._character_escape,
+      )),
+      ']',
+    ),
+
+    // a-z, 0-9, \x00-\x1f
+    class_range: _ => token(prec(1, seq(
+      choice(/[^\\\]\n^]/, /\\[^\n]/, /\\x[0-9a-fA-F]{2}/, /\\u[0-9a-fA-F]{4}/),
+      '-',
+      choice(/[^\\\]\n]/, /\\[^\n]/, /\\x[0-9a-fA-F]{2}/, /\\u[0-9a-fA-F]{4}/),
+    ))),
+
+    class_character: _ => /[^\\\]]/,
+
+    // [:alpha:], [:^space:]
+    posix_character_class: _ => /\[:\^?[a-z]+:\]/,
+
+    // \d, \W, \p{Letter}, \P{Script=Greek}, \pL
+    character_class_escape: $ => choice(
+      /\\[dDsSwWhHvV]/,
+      seq(/\\[pP]/, '{', $.unicode_property_value_expression, '}'),
+      /\\[pP][A-Z]/,
+    ),
+
+    unicode_property_value_expression: _ => /[A-Za-z_]+(=[A-Za-z_]+)?/,
+
+    // Escapes
+
+    _character_escape: $ => choice(
+      $.control_escape,
+      $.control_letter_escape,
+      $.hex_escape,
+      $.unicode_character_escape,
+      $.identity_escape,
+    ),
+
+    control_escape: _ => /\\[fnrte0]/,
+
+    control_letter_escape: _ => /\\c[a-zA-Z]/,
+
+    hex_escape: _ => /\\x([0-9a-fA-F]{2}|\{[0-9a-fA-F]+\})/,
+
+    unicode_character_escape: _ => /\\u([0-9a-fA-F]{4}|\{[0-9a-fA-F]+\})|\\U[0-9a-fA-F]{8}/,
+
+    // \., \/, \\ and any other escaped character
+    identity_escape: _ => token(prec(-1, /\\[^\n]/)),
+
+    // Backreferences
+
+    decimal_escape: _ => /\\[1-9][0-9]*/,
+
+    backreference_escape: $ => choice(
+      seq('\\k<', $.group_name, '>'),
+      seq('(?P=', $.group_name, ')'),
+    ),
+  },
+});
@@ -0,0 +1,106 @@
+(pattern_character) @string.regexp
+
+; Character classes
+
+(character_class
+  [
+    "["
+    "]"
+  ] @punctuation.bracket)
+
+(negation) @operator
+
+[
+  (class_character)
+  (class_range)
+] @constant
+
+[
+  (any_character)
+  (character_class_escape)
+  (posix_character_class)
+] @constant.builtin
+
+(unicode_property_value_expression) @property
+
+; Quantifiers
+
+[
+  "*"
+  "+"
+  "?"
+  (lazy)
+] @operator
+
+(count_quantifier) @operator
+
+"|" @operator
+
+; Groups
+
+(anonymous_capturing_group
+  [
+    "("
+    ")"
+  ] @punctuation.bracket)
+
+(non_capturing_group
+  [
+    "(?:"
+    ")"
+  ] @punctuation.special)
+
+(named_capturing_group
+  [
+    "(?<"
+    "(?P<"
+    ">"
+    ")"
+  ] @punctuation.special)
+
+(group_name) @label
+
+(flags_group
+  [
+    "(?"
+    ":"
+    ")"
+  ] @punctuation.special)
+
+(flags) @attribute
+
+; Anchors and lookarounds
+
+[
+  (start_assertion)
+  (end_assertion)
+  (boundary_assertion)
+  (non_boundary_assertion)
+] @keyword.operator
+
+(lookaround_assertion
+  [
+    "(?="
+    "(?!"
+    "(?<="
+    "(?<!"
+    ")"
+  ] @keyword.operator)
+
+; Escapes
+
+[
+  (control_escape)
+  (control_letter_escape)
+  (hex_escape)
+  (unicode_character_escape)
+  (identity_escape)
+] @string.escape
+
+[
+  (decimal_escape)
+  (backreference_escape)
+] @variable.builtin
+
+(backreference_escape
+  (group_name) @label)
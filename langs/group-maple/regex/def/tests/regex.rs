@@ -0,0 +1,122 @@
+//! Regex patterns keep classes, quantifiers, groups, anchors and escapes
+//! apart, on their own and injected into the languages that write them.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/timestamp.regex");
+const JAVASCRIPT: &str =
+    include_str!("../../../../group-acorn/javascript/def/samples/validators.js");
+const PYTHON: &str = include_str!("../../../../group-hazel/python/def/samples/inventory.py");
+const RUST: &str = include_str!("../../../../group-birch/rust/def/samples/access_log.rs");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_regex::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_regex::language(), source, kind)
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "regex",
+            TestGrammar::new(
+                arborium_regex::language(),
+                arborium_regex::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                arborium_javascript::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "python",
+            TestGrammar::new(
+                arborium_python::language(),
+                &arborium_python::HIGHLIGHTS_QUERY,
+                arborium_python::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_groups() {
+    let source = r"(a)(?:b)(?<c>d)(?P<e>f)(?=g)(?<!h)(?i)(?s-m:j)";
+    assert_eq!(nodes(source, "anonymous_capturing_group"), ["(a)"]);
+    assert_eq!(nodes(source, "non_capturing_group"), ["(?:b)"]);
+    assert_eq!(
+        nodes(source, "named_capturing_group"),
+        ["(?<c>d)", "(?P<e>f)"]
+    );
+    assert_eq!(nodes(source, "group_name"), ["c", "e"]);
+    assert_eq!(nodes(source, "lookaround_assertion"), ["(?=g)", "(?<!h)"]);
+    assert_eq!(nodes(source, "flags"), ["i", "s", "m"]);
+}
+
+#[test]
+fn test_classes_quantifiers_and_escapes() {
+    let source = r"^[^a-z\d_-]+?\.\w{2,}x{3}{$\b\1\k<c>\x41\u00e9\t";
+    assert_eq!(nodes(source, "negation"), ["^"]);
+    assert_eq!(nodes(source, "class_range"), ["a-z"]);
+    assert_eq!(nodes(source, "class_character"), ["_", "-"]);
+    assert_eq!(nodes(source, "character_class_escape"), [r"\d", r"\w"]);
+    assert_eq!(nodes(source, "lazy"), ["?"]);
+    assert_eq!(nodes(source, "count_quantifier"), ["{2,}", "{3}"]);
+    // A brace that doesn't quantify is just a character
+    assert_eq!(nodes(source, "pattern_character"), ["x", "{"]);
+    assert_eq!(nodes(source, "identity_escape"), [r"\."]);
+    assert_eq!(nodes(source, "start_assertion"), ["^"]);
+    assert_eq!(nodes(source, "end_assertion"), ["$"]);
+    assert_eq!(nodes(source, "boundary_assertion"), [r"\b"]);
+    assert_eq!(nodes(source, "decimal_escape"), [r"\1"]);
+    assert_eq!(nodes(source, "backreference_escape"), [r"\k<c>"]);
+    assert_eq!(nodes(source, "hex_escape"), [r"\x41"]);
+    assert_eq!(nodes(source, "unicode_character_escape"), [r"\u00e9"]);
+    assert_eq!(nodes(source, "control_escape"), [r"\t"]);
+}
+
+#[test]
+fn test_highlights_inside_host_languages() {
+    let mut hl = highlighter();
+
+    let html = hl.highlight("javascript", JAVASCRIPT).unwrap();
+    assert!(html.contains("<a-co>\\s@</a-co>"), "{}", html);
+    assert!(html.contains("<a-o>{2,}</a-o>"), "{}", html);
+    assert!(html.contains("<a-l>major</a-l>"), "{}", html);
+    assert!(html.contains("<a-k>(?&lt;=</a-k>"), "{}", html);
+    assert!(html.contains("<a-v>\\1</a-v>"), "{}", html);
+
+    let html = hl.highlight("python", PYTHON).unwrap();
+    assert!(html.contains("<a-co>A-Z</a-co>"), "{}", html);
+    assert!(html.contains("<a-o>{4}</a-o>"), "{}", html);
+
+    let html = hl.highlight("rust", RUST).unwrap();
+    assert!(html.contains("<a-l>status</a-l>"), "{}", html);
+    assert!(html.contains("<a-p>(?:</a-p>"), "{}", html);
+    assert!(html.contains("<a-co>\\S</a-co>"), "{}", html);
+}
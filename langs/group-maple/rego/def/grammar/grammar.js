@@ -1,419 +1,304 @@
+/**
+ * @file Rego grammar for tree-sitter
+ * @license MIT
+ *
+ * Covers both the v0 (`deny[msg] { … }`) and the v1 (`deny contains msg
+ * if { … }`) rule syntax. Newlines are whitespace: nothing in Rego can
+ * continue an expression by juxtaposition, so a body literal simply ends
+ * where the next one starts.
+ * https://www.openpolicyagent.org/docs/latest/policy-reference/#grammar
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  assignment: 1,
+  membership: 2,
+  union: 3,
+  intersection: 4,
+  relation: 5,
+  additive: 6,
+  multiplicative: 7,
+  unary: 8,
+  comprehension: 9,
+  call: 10,
+  ref: 11,
+};
+
 module.exports = grammar({
-  name: "rego",
+  name: 'rego',
 
-  extras: ($) => [$.comment, /[\s\p{Zs}\uFEFF\u2060\u200B]/],
+  extras: $ => [/\s/, $.comment],
 
-  word: ($) => $.keyword,
+  word: $ => $.var,
 
-  conflicts: ($) => [[$.membership], [$.rule_body, $.assignment_operator]],
+  conflicts: $ => [
+    // `allow if { x }` is a rule body, not a set
+    [$.literal, $.set],
+  ],
 
   rules: {
-    source_file: ($) => optional($.module),
-
-    // module          = package { import } policy
-    module: ($) => seq($._package, repeat($._import), optional($.policy)),
-
-    // package         = "package" ref
-    _package: ($) => seq($.package, $.ref),
-
-    // import          = "import" ref [ "as" var ]
-    _import: ($) => seq($.import, $.ref, optional(seq($.as, $.var))),
-
-    // policy          = { rule }
-    policy: ($) => repeat1($.rule),
-
-    // rule            = [ "default" ] rule-head { rule-body }
-    rule: ($) =>
-      seq(optional($.default), $.rule_head, prec.left(repeat1($.rule_body))),
-
-    // rule-head       = var ( rule-head-set | rule-head-obj | rule-head-func | rule-head-comp | "if" )
-    rule_head: ($) =>
-      prec.right(
-        seq(
-          $.var,
-          optional(
-            choice(
-              // rule-head-set   = ( "contains" term [ "if" ] ) | ( "[" term "]" )
-              seq($.contains, $.term, optional($.if)),
-              // rule-head-obj   = "[" term "]" [ rule-head-comp ] [ "if" ]
-              seq(
-                $.open_bracket,
-                $.term,
-                $.close_bracket,
-                optional($.rule_head_comp),
-                optional($.if),
-              ),
-              // rule-head-func  = "(" rule-args ")" [ rule-head-comp ]
-              seq(
-                seq($.open_paren, $.rule_args, $.close_paren),
-                optional($.rule_head_comp),
-                optional($.if),
-              ),
-              // if
-              $.if,
-            ),
-          ),
-        ),
-      ),
-
-    // rule-head-comp  = ( ":=" | "=" ) term
-    rule_head_comp: ($) => seq($.assignment_operator, $.term),
-
-    // rule-args       = term { "," term }
-    rule_args: ($) => seq($.term, repeat(seq(",", $.term))),
-
-    // rule-body       = [ "else" [ ( ":=" | "=" ) term ] ] ( "{" query "}" ) | literal
-    rule_body: ($) =>
-      seq(
-        optional(seq($.else, optional(seq($.assignment_operator, $.term)))),
-        choice(
-          seq($.open_curly, $.query, $.close_curly),
-          $.literal,
-          seq($.assignment, $.term),
-        ),
-      ),
-
-    // query           = literal { ( ";" | ( [CR] LF ) ) literal }
-    query: ($) =>
-      seq(
-        $.literal,
-        repeat(
-          choice(
-            seq(choice(";", seq(optional("\r"), "\n")), optional($.literal)),
-            seq(
-              choice(";", seq(optional("\r"), "\n")),
-              optional($.with_modifier),
-            ),
-          ),
-        ),
+    source_file: $ => seq(
+      optional($.package),
+      repeat(choice($.import, $.rule)),
+    ),
+
+    package: $ => seq('package', field('path', $.path)),
+
+    import: $ => seq(
+      'import',
+      field('path', $.path),
+      optional(seq('as', field('alias', $.var))),
+    ),
+
+    // data.authz["team-a"].roles
+    path: $ => seq(
+      $.var,
+      repeat(choice(seq('.', $.var), seq('[', $.string, ']'))),
+    ),
+
+    // Rules
+
+    rule: $ => seq(
+      optional('default'),
+      $.rule_head,
+      optional($.rule_body),
+    ),
+
+    rule_head: $ => seq(
+      field('name', $.var),
+      repeat(choice(
+        seq('.', field('name', $.var)),
+        seq('[', field('key', $._expression), ']'),
+      )),
+      optional(field('arguments', $.rule_arguments)),
+      optional(choice(
+        seq('contains', field('value', $._expression)),
+        seq(choice('=', ':='), field('value', $._expression)),
+      )),
+    ),
+
+    rule_arguments: $ => seq('(', optional(commaSep1($._term)), ')'),
+
+    rule_body: $ => prec.right(seq(
+      choice(
+        $.query_block,
+        seq('if', choice($.query_block, $.literal)),
       ),
-
-    // literal         = ( some-decl | expr | "not" expr ) { with-modifier }
-    literal: ($) =>
+      repeat($.else_clause),
+    )),
+
+    else_clause: $ => prec.right(seq(
+      'else',
+      optional(seq(choice('=', ':='), field('value', $._expression))),
+      optional(choice(
+        $.query_block,
+        seq('if', choice($.query_block, $.literal)),
+      )),
+    )),
+
+    query_block: $ => prec.dynamic(1, seq('{', $.query, '}')),
+
+    query: $ => prec.left(seq(
+      $.literal,
+      repeat(seq(optional(';'), $.literal)),
+    )),
+
+    literal: $ => choice(
+      $.some_declaration,
+      $.every_expression,
       seq(
-        choice($.some_decl, $.expr, seq($.not, $.expr)),
+        optional('not'),
+        $._expression,
         repeat($.with_modifier),
       ),
+    ),
 
-    // with-modifier   = "with" term "as" term
-    with_modifier: ($) => seq($.with, $.term, $.as, $.term),
-
-    // some-decl       = "some" term { "," term } { "in" expr }
-    some_decl: ($) =>
-      seq($.some, $.term, repeat(seq(",", $.term)), repeat(seq($.in, $.expr))),
-
-    // expr            = term | expr-call | expr-infix | expr-every | expr-unary
-    expr: ($) =>
-      prec.left(
-        1,
-        choice(
-          $.term,
-          $.expr_call,
-          $.expr_infix,
-          $.expr_every,
-          $.expr_parens,
-          $.expr_unary,
-        ),
-      ),
-
-    // expr-parens     = "(" expr ")"
-    expr_parens: ($) => prec(-1, seq($.open_paren, $.expr, $.close_paren)),
-
-    // expr-call       = var [ "." var ] "(" [ expr { "," expr } ] ")"
-    expr_call: ($) =>
-      seq(
-        field("func_name", $.fn_name),
-        $.open_paren,
-        field("func_arguments", optional($.fn_args)),
-        $.close_paren,
-      ),
-
-    fn_name: ($) => seq($.var, optional(seq(".", $.var))),
-    fn_args: ($) => seq($.expr, repeat(seq(",", $.expr))),
-
-    // expr-infix = expr infix-operator expr
-    expr_infix: ($) => prec.left(1, seq($.expr, $.infix_operator, $.expr)),
-
-    // expr-every      = "every" var { "," var } "in" ( term | expr-call | expr-infix ) "{" query "}"
-    expr_every: ($) =>
-      seq(
-        $.every,
-        $.var,
-        repeat(seq(",", $.var)),
-        $.in,
-        choice($.term, $.expr_call, $.expr_infix),
-        $.open_curly,
-        $.query,
-        $.close_curly,
-      ),
-
-    // expr-unary      = "-" expr
-    expr_unary: ($) => prec.left(-3, seq("-", $.expr)),
-
-    // term            = ref | var | scalar | array | object | set | array-compr | object-compr | set-compr | membership
-    term: ($) =>
-      choice(
-        $.ref,
-        $.var,
-        $.scalar,
-        $.array,
-        $.object,
-        $.set,
-        $.array_compr,
-        $.object_compr,
-        $.set_compr,
-        $.membership,
-      ),
-
-    // array-compr     = "[" term "|" rule-body "]"
-    array_compr: ($) =>
-      seq($.open_bracket, $.term, "|", $.query, $.close_bracket),
-
-    // set-compr       = "{" term "|" rule-body "}"
-    set_compr: ($) => seq($.open_curly, $.term, "|", $.query, $.close_curly),
-
-    // object-compr    = "{" object-item "|" rule-body "}"
-    object_compr: ($) =>
-      seq($.open_curly, $.object_item, "|", $.query, $.close_curly),
-
-    // infix-operator  = bool-operator | arith-operator | bin-operator
-    infix_operator: ($) =>
+    some_declaration: $ => prec.right(seq(
+      'some',
       choice(
-        prec.left(2, $.assignment_operator),
-        $.bool_operator,
-        $.arith_operator,
-        $.bin_operator,
-      ),
-
-    // assignment-operator = ":=" | "="
-    assignment_operator: ($) => choice($.assignment, $.unification),
-
-    // assignment operator
-    assignment: ($) => ":=",
-
-    // unification operator
-    unification: ($) => "=",
-
-    // bool-operator   = "==" | "\!=" | "<" | ">" | ">=" | "<="
-    bool_operator: ($) => choice("==", "\!=", "<", ">", ">=", "<="),
-
-    // arith-operator  = "+" | "-" | "*" | "/" | "%"
-    arith_operator: ($) => choice("+", "-", "*", "/", "%"),
-
-    // bin-operator    = "&" | "|"
-    bin_operator: ($) => choice("&", "|"),
-
-    // ref             = ( var | array | object | set | array-compr | object-compr | set-compr | expr-call ) { ref-arg }
-    ref: ($) =>
-      prec.left(
-        2,
+        commaSep1($.var),
         seq(
-          choice(
-            $.var,
-            $.array,
-            $.object,
-            $.set,
-            $.array_compr,
-            $.object_compr,
-            $.set_compr,
-            $.expr_call,
-          ),
-          repeat($.ref_arg),
-        ),
-      ),
-
-    // ref-arg         = ref-arg-dot | ref-arg-brack
-    ref_arg: ($) => choice($.ref_arg_dot, $.ref_arg_brack),
-
-    // ref-arg-brack   = "[" ( scalar | var | array | object | set | "_" ) "]"
-    ref_arg_brack: ($) =>
-      seq(
-        $.open_bracket,
-        choice($.scalar, $.var, $.array, $.object, $.set, "_"),
-        $.close_bracket,
-      ),
-
-    // ref-arg-dot     = "." var
-    ref_arg_dot: ($) => prec.left(2, seq(".", $.var)),
-
-    // var             = ( ALPHA | "_" ) { ALPHA | DIGIT | "_" }
-    var: ($) => /[A-Za-z_]+\w*/,
-
-    // scalar          = string | NUMBER | TRUE | FALSE | NULL
-    scalar: ($) => choice($.string, $.number, $.boolean, "null"),
-
-    // string          = STRING | raw-string | interpolated-string-double | interpolated-string-raw
-    string: ($) =>
-      choice(
-        $.interpolated_string_double,
-        $.interpolated_string_raw,
-        $.quoted_string,
-        $.raw_string,
-      ),
-
-    // quoted-string   = '"' { CHAR } '"'
-    quoted_string: ($) =>
-      seq(
-        '"',
-        optional(
-          alias(
-            token.immediate(/([^\\"\n]|\\["\\/bfnrt]|\\u[0-9a-fA-F]{4})+/),
-            "string_content",
-          ),
+          field('key', $.var),
+          optional(seq(',', field('value', $.var))),
+          'in',
+          field('collection', $._expression),
         ),
-        '"',
       ),
-
-    // raw-string      = "`" { CHAR-"`" } "`"
-    raw_string: ($) => seq("`", repeat(token.immediate(/[^`]+/)), "`"),
-
-    // interpolated-string-double = '$"' { string-content | interpolation } '"'
-    interpolated_string_double: ($) =>
-      seq(
-        '$"',
-        repeat(
-          choice(
-            $.interpolation,
-            $.interpolation_escape,
-            $.string_escape,
-            token.immediate(prec(1, /[^\\"\n{]+/)),
-          ),
-        ),
-        '"',
-      ),
-
-    // interpolated-string-raw = '$`' { string-content | interpolation } '`'
-    interpolated_string_raw: ($) =>
-      seq(
-        "$`",
-        repeat(
-          choice(
-            $.interpolation,
-            $.interpolation_escape,
-            token.immediate(prec(1, /[^`{]+/)),
-          ),
+    )),
+
+    every_expression: $ => seq(
+      'every',
+      field('key', $.var),
+      optional(seq(',', field('value', $.var))),
+      'in',
+      field('collection', $._expression),
+      $.query_block,
+    ),
+
+    with_modifier: $ => seq(
+      'with',
+      field('target', $._term),
+      'as',
+      field('value', $._term),
+    ),
+
+    // Expressions
+
+    _expression: $ => choice($.binary_expression, $.unary_expression, $._term),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.assignment, choice(':=', '=')],
+        [PREC.membership, 'in'],
+        [PREC.union, '|'],
+        [PREC.intersection, '&'],
+        [PREC.relation, choice('==', '!=', '<', '<=', '>', '>=')],
+        [PREC.additive, choice('+', '-')],
+        [PREC.multiplicative, choice('*', '/', '%')],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
         ),
-        "`",
-      ),
-
-    // interpolation = '{' expr '}'
-    interpolation: ($) => seq("{", $.expr, "}"),
-
-    // string-escape = backslash escape sequences
-    string_escape: ($) =>
-      token.immediate(
-        seq("\\", choice(/["\\/bfnrt]/, seq("u", /[0-9a-fA-F]{4}/))),
-      ),
-
-    // interpolation-escape = '\{' | '\}'
-    interpolation_escape: ($) => token.immediate(/\\[{}]/),
-
-    // array           = "[" term { "," term } "]"
-    array: ($) =>
-      seq(
-        $.open_bracket,
-        $.term,
-        repeat(seq(",", $.term)),
-        optional(","),
-        $.close_bracket,
-      ),
-
-    // object          = "{" object-item { "," object-item } "}"
-    object: ($) =>
-      seq(
-        $.open_curly,
-        $.object_item,
-        repeat(seq(",", $.object_item)),
-        optional(","),
-        $.close_curly,
-      ),
-
-    // object-item     = ( scalar | ref | var ) ":" term
-    object_item: ($) =>
-      seq(
-        field("key", choice($.scalar, $.ref, $.var)),
-        ":",
-        field("value", $.term),
-      ),
-
-    // set             = empty-set | non-empty-set
-    set: ($) => choice($.empty_set, $.non_empty_set),
-
-    // non-empty-set   = "{" term { "," term } "}"
-    non_empty_set: ($) =>
-      seq(
-        $.open_curly,
-        $.term,
-        repeat(seq(",", $.term)),
-        optional(","),
-        $.close_curly,
+      )));
+    },
+
+    unary_expression: $ => prec(PREC.unary, seq('-', $._expression)),
+
+    _term: $ => choice(
+      $.var,
+      $.ref,
+      $.call,
+      $._scalar,
+      $.array,
+      $.object,
+      $.set,
+      $.array_comprehension,
+      $.object_comprehension,
+      $.set_comprehension,
+      $.parenthesized_expression,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    ref: $ => prec.left(PREC.ref, seq(
+      field('operand', $._term),
+      choice(
+        seq('.', field('field', $.var)),
+        seq('[', field('index', $._expression), ']'),
       ),
-
-    // empty-set       = "set(" ")"
-    empty_set: ($) => seq("set(", ")"),
-
-    // comment
-    comment: ($) => token(seq("#", /.*/)),
-
-    // boolean
-    boolean: ($) => choice("true", "false"),
-
-    // membership      = term [ "," term ] "in" term
-    membership: ($) =>
-      prec.left(-1, seq($.term, optional(seq(",", $.term)), $.in, $.term)),
-
-    // parenthesis
-    open_paren: ($) => "(",
-    close_paren: ($) => ")",
-
-    // brackets
-    open_bracket: ($) => "[",
-    close_bracket: ($) => "]",
-
-    // curly bracket
-    open_curly: ($) => "{",
-    close_curly: ($) => "}",
-
-    // number
-    number: ($) => /([0-9]*[.])?[0-9]+/,
-
-    // not keyword
-    not: ($) => "not",
-
-    // with keyword
-    with: ($) => "with",
-
-    // as keyword
-    as: ($) => "as",
-
-    // in keyword
-    in: ($) => "in",
-
-    // if keyword
-    if: ($) => "if",
-
-    // every keyword
-    every: ($) => "every",
-
-    // else keyword
-    else: ($) => "else",
-
-    // package keyword
-    package: ($) => "package",
-
-    // import keyword
-    import: ($) => "import",
-
-    // contains keyword
-    contains: ($) => "contains",
-
-    // some keyword
-    some: ($) => "some",
-
-    // default keyword
-    default: ($) => "default",
-
-    // match whole words
-    keyword: ($) => /[a-z]+/,
+    )),
+
+    call: $ => prec(PREC.call, seq(
+      field('function', choice($.var, $.ref)),
+      '(',
+      optional(commaSep1($._expression)),
+      ')',
+    )),
+
+    // Collections
+
+    array: $ => seq('[', optional(seq(commaSep1($._expression), optional(','))), ']'),
+
+    object: $ => seq(
+      '{',
+      optional(seq(commaSep1($.object_item), optional(','))),
+      '}',
+    ),
+
+    object_item: $ => seq(field('key', $._term), ':', field('value', $._expression)),
+
+    set: $ => seq('{', commaSep1($._expression), optional(','), '}'),
+
+    array_comprehension: $ => prec(PREC.comprehension, seq(
+      '[',
+      field('head', $._term),
+      '|',
+      field('body', $.query),
+      ']',
+    )),
+
+    set_comprehension: $ => prec(PREC.comprehension, seq(
+      '{',
+      field('head', $._term),
+      '|',
+      field('body', $.query),
+      '}',
+    )),
+
+    object_comprehension: $ => prec(PREC.comprehension, seq(
+      '{',
+      field('key', $._term),
+      ':',
+      field('value', $._term),
+      '|',
+      field('body', $.query),
+      '}',
+    )),
+
+    // Scalars
+
+    _scalar: $ => choice(
+      $.number,
+      $.string,
+      $.raw_string,
+      $.template_string,
+      $.true,
+      $.false,
+      $.null,
+    ),
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    null: _ => 'null',
+
+    number: _ => /\d+(\.\d+)?([eE][+-]?\d+)?/,
+
+    string: $ => seq(
+      '"',
+      repeat(choice(token.immediate(prec(1, /[^"\\\n]+/)), $.escape_sequence)),
+      '"',
+    ),
+
+    raw_string: _ => token(seq('`', /[^`]*/, '`')),
+
+    // $"Hello, {input.user}!"
+    template_string: $ => seq(
+      '$"',
+      repeat(choice(
+        token.immediate(prec(1, /[^"\\{\n]+/)),
+        $.escape_sequence,
+        $.template_expression,
+      )),
+      '"',
+    ),
+
+    template_expression: $ => seq(token.immediate('{'), $._expression, '}'),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(/["\\\/bfnrt{]/, /u[0-9a-fA-F]{4}/),
+    )),
+
+    var: _ => /[A-Za-z_][A-Za-z0-9_]*/,
+
+    comment: _ => token(seq('#', /.*/)),
   },
 });
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
@@ -1,64 +1,124 @@
-; highlights.scm
-[
-  (import) 
-  (package)
-] @module
+; Keywords
 
 [
-  (with)
-  (as)
-  (every)
-  (some)
-  (in)
-  (not)
-  (if)
-  (contains)
-  (else)
-  (default)
-  "null"
+  "as"
+  "contains"
+  "default"
+  "else"
+  "every"
+  "if"
+  "import"
+  "in"
+  "not"
+  "package"
+  "some"
+  "with"
 ] @keyword
 
 [
-  "true"
-  "false"
+  (true)
+  (false)
 ] @boolean
 
-[
-  (assignment_operator)
-  (bool_operator)
-  (arith_operator)
-  (bin_operator)
-] @operator
+(null) @constant.builtin
+
+; Literals
 
 [
   (string)
   (raw_string)
+  (template_string)
 ] @string
 
-(term (ref (var))) @variable
-
-(comment) @comment
+(escape_sequence) @string.escape
 
 (number) @number
 
-(expr_call func_name: (fn_name (var) @function .))
+; Variables
+
+(var) @variable
+
+((var) @variable.builtin
+  (#match? @variable.builtin "^(input|data)$"))
+
+(package
+  path: (path
+    (var) @module))
+
+(import
+  path: (path
+    (var) @module))
+
+(import
+  alias: (var) @module)
+
+(ref
+  field: (var) @property)
+
+(object_item
+  key: (string) @property)
+
+; Rules
 
-(expr_call func_arguments: (fn_args (expr) @variable.parameter))
+(rule_head
+  name: (var) @function)
 
-(rule_args (term) @variable.parameter)
+(rule_arguments
+  (var) @variable.parameter)
+
+; Calls
+
+(call
+  function: (var) @function.call)
+
+(call
+  function: (ref
+    field: (var) @function.call))
+
+((call
+  function: (var) @function.builtin)
+  (#match? @function.builtin "^(abs|all|any|array|base64|ceil|concat|count|endswith|floor|format_int|indexof|intersection|is_array|is_boolean|is_null|is_number|is_object|is_set|is_string|lower|max|min|numbers|object|print|product|replace|round|sort|split|sprintf|startswith|strings|substring|sum|to_number|trim|trim_left|trim_prefix|trim_right|trim_space|trim_suffix|type_name|union|upper)$"))
+
+; Namespaced builtins like `time.now_ns` or `io.jwt.decode`
+((call
+  function: (ref
+    field: (var) @function.builtin) @_ref)
+  (#match? @_ref "^(array|base64|crypto|glob|graph|http|io\\.jwt|json|net|object|regex|semver|strings|time|units|urlquery|uuid|yaml)\\."))
+
+; Operators and punctuation
+
+(binary_expression
+  operator: _ @operator)
+
+(unary_expression
+  "-" @operator)
+
+[
+  ":="
+  "="
+  "|"
+] @operator
 
 [
-  (open_paren)
-  (close_paren)
-  (open_bracket)
-  (close_bracket)
-  (open_curly)
-  (close_curly)
+  "."
+  ","
+  ";"
+  ":"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
 ] @punctuation.bracket
 
-(rule (rule_head (var) @attribute))
+(template_expression
+  [
+    "{"
+    "}"
+  ] @punctuation.special)
 
-(rule 
-  (rule_head (term (ref (var) @head-var)))
-  (rule_body (query (literal (expr (expr_infix (expr (term (ref (var)) @output-var)))))) (#eq? @output-var @head-var))
-)
\ No newline at end of file
+(comment) @comment
@@ -0,0 +1,84 @@
+# HTTP API authorization for the billing service.
+#
+# Input looks like:
+#   {"method": "GET", "path": ["invoices", "42"], "token": "<jwt>"}
+package httpapi.authz
+
+import rego.v1
+
+import data.roles as role_grants
+
+default allow := false
+
+# Decode once; every rule below reads the claims from here.
+claims := payload if {
+	[_, payload, _] := io.jwt.decode(input.token)
+}
+
+user := claims.sub
+
+token_valid if {
+	now := time.now_ns() / 1000000000
+	claims.exp > now
+	claims.iss == "https://auth.example.com"
+}
+
+# Admins can do anything with a valid token.
+allow if {
+	token_valid
+	"admin" in user_roles
+}
+
+# Everyone may read their own invoices.
+allow if {
+	token_valid
+	input.method == "GET"
+	input.path = ["invoices", invoice_id]
+	data.invoices[invoice_id].owner == user
+}
+
+# Finance may read and export any invoice.
+allow if {
+	token_valid
+	some grant in grants
+	grant.action == input.method
+	glob.match(grant.path, ["/"], concat("/", input.path))
+}
+
+user_roles contains role if {
+	some role in role_grants[user]
+}
+
+grants := [grant |
+	some role in user_roles
+	some grant in data.permissions[role]
+]
+
+deny contains msg if {
+	not token_valid
+	msg := sprintf("token for %q is expired or untrusted", [user])
+}
+
+deny contains msg if {
+	count(input.path) > 4
+	msg := $"path too deep: {count(input.path)} segments"
+}
+
+every_segment_safe if {
+	every segment in input.path {
+		not contains(segment, "..")
+		not startswith(segment, `\`)
+	}
+}
+
+reasons := {r: true | some r in deny}
+
+status := 200 if allow
+else := 403 if token_valid
+else := 401
+
+test_admin_allowed if {
+	allow with input as {"method": "DELETE", "path": ["invoices"], "token": "t"}
+		with token_valid as true
+		with user_roles as {"admin"}
+}
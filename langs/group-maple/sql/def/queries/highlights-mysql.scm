@@ -0,0 +1,32 @@
+; MySQL and MariaDB additions to highlights.scm
+;
+; Backtick-quoted identifiers are identifiers to the grammar, so they're
+; highlighted by what they name like any other.
+
+; ) ENGINE=InnoDB AUTO_INCREMENT=1000 DEFAULT CHARSET=utf8mb4
+(table_option
+  name: [
+    (keyword_engine)
+    (identifier)
+  ] @attribute)
+
+(table_option
+  value: (identifier) @constant)
+
+; DEFAULT CHARACTER SET utf8mb4, COLLATE utf8mb4_unicode_ci
+(table_option
+  (keyword_set)
+  (identifier) @constant)
+
+(table_option
+  (keyword_collate)
+  (identifier) @constant)
+
+; Column attributes the grammar doesn't know, like ON UPDATE
+; CURRENT_TIMESTAMP, and `#` comments come out as ERROR nodes. Nothing
+; captures ERROR itself, and keywords and literals are matched on their own
+; rather than by their parents, so the rest of the statement keeps its
+; highlighting.
+((ERROR
+  (identifier) @attribute)
+  (#match? @attribute "^(?i)(current_timestamp|now)$"))
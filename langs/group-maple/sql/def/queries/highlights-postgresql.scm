@@ -0,0 +1,25 @@
+; PostgreSQL additions to highlights.scm
+
+; CREATE FUNCTION ... AS $$ ... $$ LANGUAGE plpgsql
+(dollar_quote) @punctuation.special
+
+(function_language
+  (identifier) @label)
+
+; value::type
+(implicit_cast
+  "::" @operator)
+
+; The grammar parses SQL statements in a dollar-quoted body, but not
+; PL/pgSQL's own: IF ... THEN, RAISE, PERFORM, := assignments and the like
+; come out as ERROR nodes. Nothing here or in highlights.scm captures ERROR
+; itself, and keywords, literals and parameters are matched on their own
+; rather than by their parents, so the statements around it, and what the
+; parser still recognizes within it, keep their highlighting.
+((ERROR
+  (identifier) @keyword)
+  (#match? @keyword "^(?i)(raise|notice|warning|exception|perform|elsif|loop|exit|continue|foreach)$"))
+
+((ERROR
+  (identifier) @variable.builtin)
+  (#match? @variable.builtin "^(?i)(found|tg_op|tg_table_name|sqlstate|sqlerrm)$"))
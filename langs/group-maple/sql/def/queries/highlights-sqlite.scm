@@ -0,0 +1,14 @@
+; SQLite additions to highlights.scm
+
+; Every table without WITHOUT ROWID has these, whatever its columns
+((identifier) @variable.builtin
+  (#match? @variable.builtin "^(?i)(rowid|_rowid_|oid)$"))
+
+; The grammar has no PRAGMA statement, nor AUTOINCREMENT or a table's
+; STRICT and WITHOUT ROWID options: those come out as ERROR nodes. Nothing
+; captures ERROR itself, and keywords and literals are matched on their own
+; rather than by their parents, so the statements around them keep their
+; highlighting.
+((ERROR
+  (identifier) @keyword)
+  (#match? @keyword "^(?i)(pragma|autoincrement|rowid|strict)$"))
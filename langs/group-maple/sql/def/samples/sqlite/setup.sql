@@ -0,0 +1,30 @@
+-- Connection setup and schema for an app's local database
+
+PRAGMA foreign_keys = ON;
+PRAGMA journal_mode = WAL;
+PRAGMA synchronous = NORMAL;
+PRAGMA user_version = 3;
+
+CREATE TABLE IF NOT EXISTS notes (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    body TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS tags (
+    note_id INTEGER NOT NULL REFERENCES notes (id) ON DELETE CASCADE,
+    name TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS tags_by_name ON tags (name);
+
+INSERT INTO notes (title, body) VALUES ('groceries', 'milk, eggs');
+
+SELECT rowid, title, strftime('%Y-%m-%d', created_at) AS day
+FROM notes
+WHERE title LIKE '%gro%'
+ORDER BY rowid DESC;
+
+PRAGMA integrity_check;
+VACUUM;
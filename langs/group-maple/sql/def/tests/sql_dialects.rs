@@ -0,0 +1,93 @@
+//! PostgreSQL, MySQL and SQLite share the SQL grammar, each with its own
+//! highlights appended to the SQL ones.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers;
+
+fn parse(highlights_query: &str, source: &str) -> Utf8ParseResult {
+    let queries = helpers::Queries {
+        highlights: highlights_query,
+        ..helpers::queries!(arborium_sql)
+    };
+    helpers::plugin_parse(arborium_sql::language(), queries, source)
+}
+
+/// The capture that wins for the first occurrence of `text` in `source`,
+/// if one spans exactly that.
+fn capture_of<'a>(result: &'a Utf8ParseResult, source: &str, text: &str) -> Option<&'a str> {
+    let start = source.find(text).expect("text not in source") as u32;
+    let end = start + text.len() as u32;
+    result
+        .spans
+        .iter()
+        .filter(|s| s.start == start && s.end == end)
+        .max_by_key(|s| s.pattern_index)
+        .map(|s| s.capture.as_str())
+}
+
+#[test]
+fn test_dialects_extend_the_sql_highlights() {
+    for query in [
+        &*arborium_sql::POSTGRESQL_HIGHLIGHTS_QUERY,
+        &*arborium_sql::MYSQL_HIGHLIGHTS_QUERY,
+        &*arborium_sql::SQLITE_HIGHLIGHTS_QUERY,
+    ] {
+        assert!(query.starts_with(arborium_sql::HIGHLIGHTS_QUERY));
+        assert!(query.len() > arborium_sql::HIGHLIGHTS_QUERY.len());
+    }
+}
+
+#[test]
+fn test_postgresql_dollar_quotes_and_casts() {
+    let source = "\
+CREATE FUNCTION total() RETURNS numeric AS $$
+    SELECT sum(price)::numeric FROM items;
+$$ LANGUAGE sql;
+";
+    let result = parse(&arborium_sql::POSTGRESQL_HIGHLIGHTS_QUERY, source);
+    assert_eq!(
+        capture_of(&result, source, "$$"),
+        Some("punctuation.special")
+    );
+    assert_eq!(capture_of(&result, source, "::"), Some("operator"));
+    assert_eq!(capture_of(&result, source, "sql"), Some("label"));
+
+    // Plain SQL leaves them alone
+    let result = parse(arborium_sql::HIGHLIGHTS_QUERY, source);
+    assert_eq!(capture_of(&result, source, "$$"), None);
+    assert_eq!(capture_of(&result, source, "::"), None);
+}
+
+#[test]
+fn test_mysql_table_options() {
+    let source = "CREATE TABLE `users` (`id` int) ENGINE=InnoDB;\n";
+    let result = parse(&arborium_sql::MYSQL_HIGHLIGHTS_QUERY, source);
+    assert_eq!(capture_of(&result, source, "ENGINE"), Some("attribute"));
+    assert_eq!(capture_of(&result, source, "InnoDB"), Some("constant"));
+    assert_eq!(capture_of(&result, source, "`users`"), Some("type"));
+}
+
+#[test]
+fn test_sqlite_rowid() {
+    let source = "SELECT rowid, title FROM notes;\n";
+    let result = parse(&arborium_sql::SQLITE_HIGHLIGHTS_QUERY, source);
+    assert_eq!(
+        capture_of(&result, source, "rowid"),
+        Some("variable.builtin")
+    );
+    assert_eq!(capture_of(&result, source, "title"), Some("field"));
+}
+
+#[test]
+fn test_unparsed_statements_leave_the_rest_highlighted() {
+    // SQLite pragmas aren't in the grammar
+    let source = "PRAGMA foreign_keys = ON;\nSELECT title FROM notes;\n";
+    let result = parse(&arborium_sql::SQLITE_HIGHLIGHTS_QUERY, source);
+    assert_eq!(capture_of(&result, source, "SELECT"), Some("keyword"));
+    assert_eq!(capture_of(&result, source, "notes"), Some("type"));
+    assert!(
+        result.spans.iter().all(|s| s.end - s.start < 20),
+        "{:?}",
+        result.spans
+    );
+}
@@ -0,0 +1,103 @@
+//! SQL in string literals passed to well-known database calls, in python,
+//! php and go.
+
+use arborium_test_harness::helpers;
+use arborium_test_harness::tree_sitter::Language;
+
+fn sql<'a>(
+    language: impl Into<Language>,
+    queries: helpers::Queries<'_>,
+    source: &'a str,
+) -> Vec<&'a str> {
+    let result = helpers::plugin_parse(language, queries, source);
+    helpers::injected_as(&result, source, "sql")
+        .into_iter()
+        .map(str::trim)
+        .collect()
+}
+
+fn python(source: &str) -> Vec<&str> {
+    sql(
+        arborium_python::language(),
+        helpers::queries!(arborium_python),
+        source,
+    )
+}
+
+fn php(source: &str) -> Vec<&str> {
+    sql(
+        arborium_php::language(),
+        helpers::queries!(arborium_php),
+        source,
+    )
+}
+
+fn go(source: &str) -> Vec<&str> {
+    sql(
+        arborium_go::language(),
+        helpers::queries!(arborium_go),
+        source,
+    )
+}
+
+#[test]
+fn test_python_sql_calls() {
+    let source = "\
+cursor.execute(\"SELECT 1\")
+conn.execute(text(\"\"\"
+    UPDATE users SET active = 0
+\"\"\"))
+pd.read_sql(\"select * from t\", engine)
+text(\"hello there\")
+cursor.execute(query)
+";
+    assert_eq!(
+        python(source),
+        ["SELECT 1", "UPDATE users SET active = 0", "select * from t"]
+    );
+}
+
+#[test]
+fn test_php_sql_calls() {
+    let source = "\
+<?php
+$pdo->prepare('SELECT * FROM users WHERE id = ?');
+$db->query(\"DELETE FROM sessions\");
+DB::select('select name from users');
+$db->query(\"SELECT * FROM users WHERE id = $id\");
+$logger->exec('rotate logs');
+Cache::select('SELECT 1');
+";
+    assert_eq!(
+        php(source),
+        [
+            "SELECT * FROM users WHERE id = ?",
+            "DELETE FROM sessions",
+            "select name from users",
+        ]
+    );
+}
+
+#[test]
+fn test_go_sql_calls() {
+    let source = "\
+package main
+
+func main() {
+	db.QueryRow(\"SELECT name FROM users WHERE id = $1\", id)
+	db.ExecContext(ctx, `
+		DELETE FROM sessions
+	`)
+	db.Query(query, id)
+	db.Exec(\"not a statement\")
+	db.QueryContext(\"SELECT 1\", ctx)
+}
+";
+    assert_eq!(
+        go(source),
+        [
+            "SELECT name FROM users WHERE id = $1",
+            "DELETE FROM sessions"
+        ]
+    );
+}
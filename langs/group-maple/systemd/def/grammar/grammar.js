@@ -0,0 +1,85 @@
+/**
+ * @file systemd unit file grammar for tree-sitter
+ * @license MIT
+ *
+ * `.service`, `.timer`, `.socket`, `.mount`, `.target` and the other unit
+ * files: `[Section]` headers and `Key=value` directives. Values may use
+ * `%` specifiers like `%i` and continue onto the next line after a
+ * backslash. Comments start with `#` or `;` and take a whole line.
+ *
+ * The command line of the `Exec*=` directives is kept apart from the
+ * prefixes that change how it runs (`-`, `@`, `:`, `+`, `!`, `!!`), so it
+ * can be handed to the bash grammar.
+ * https://www.freedesktop.org/software/systemd/man/latest/systemd.syntax.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const EXEC_KEYS = [
+  'ExecCondition',
+  'ExecReload',
+  'ExecStart',
+  'ExecStartPost',
+  'ExecStartPre',
+  'ExecStop',
+  'ExecStopPost',
+];
+
+module.exports = grammar({
+  name: 'systemd',
+
+  extras: _ => [/[ \t]/, /\\\r?\n/],
+
+  rules: {
+    unit_file: $ => repeat(choice($._newline, $.comment, $.section)),
+
+    _newline: _ => /\r?\n/,
+
+    comment: _ => /[#;][^\r\n]*/,
+
+    section: $ => prec.right(seq(
+      field('header', $.section_header),
+      repeat(choice($._newline, $.comment, $.directive)),
+    )),
+
+    // [Service]
+    section_header: $ => seq(
+      '[',
+      field('name', $.section_name),
+      ']',
+      $._newline,
+    ),
+
+    section_name: _ => /[^\]\r\n]+/,
+
+    directive: $ => choice(
+      // ExecStart=-/usr/bin/restic backup %h
+      seq(
+        field('key', alias(choice(...EXEC_KEYS), $.key)),
+        '=',
+        optional(field('prefix', $.exec_prefix)),
+        optional(field('command', alias($.value, $.command_line))),
+        $._newline,
+      ),
+      // Restart=on-failure
+      seq(
+        field('key', $.key),
+        '=',
+        optional(field('value', $.value)),
+        $._newline,
+      ),
+    ),
+
+    key: _ => /[A-Za-z0-9_.-]+/,
+
+    exec_prefix: _ => token(prec(1, /[-@:+!|]+/)),
+
+    value: $ => prec.right(repeat1(choice($.specifier, $._text))),
+
+    // %i, %H, %%
+    specifier: _ => /%[A-Za-z%]/,
+
+    _text: _ => /([^%\r\n\\]|\\[^\r\n])+|%/,
+  },
+});
@@ -0,0 +1,66 @@
+(comment) @comment
+
+[
+  "["
+  "]"
+] @punctuation.bracket
+
+"=" @operator
+
+(section_name) @type
+
+(key) @property
+
+; Directives systemd.unit(5), .service(5), .exec(5), .timer(5), .socket(5)
+; and .mount(5) document
+((key) @keyword
+  (#any-of? @keyword
+    ; [Unit]
+    "Description" "Documentation" "Requires" "Requisite" "Wants" "BindsTo"
+    "PartOf" "Upholds" "Conflicts" "Before" "After" "OnFailure" "OnSuccess"
+    "PropagatesReloadTo" "ReloadPropagatedFrom" "JoinsNamespaceOf"
+    "RequiresMountsFor" "DefaultDependencies" "StopWhenUnneeded"
+    "RefuseManualStart" "RefuseManualStop" "AllowIsolate" "JobTimeoutSec"
+    "StartLimitIntervalSec" "StartLimitBurst" "StartLimitAction"
+    "ConditionPathExists" "ConditionPathIsDirectory" "ConditionHost"
+    "ConditionVirtualization" "ConditionACPower" "AssertPathExists"
+    ; [Install]
+    "WantedBy" "RequiredBy" "UpheldBy" "Also" "Alias" "DefaultInstance"
+    ; [Service]
+    "Type" "RemainAfterExit" "PIDFile" "BusName" "ExecCondition" "ExecStart"
+    "ExecStartPre" "ExecStartPost" "ExecReload" "ExecStop" "ExecStopPost"
+    "Restart" "RestartSec" "TimeoutSec" "TimeoutStartSec" "TimeoutStopSec"
+    "WatchdogSec" "SuccessExitStatus" "KillMode" "KillSignal" "NotifyAccess"
+    ; Execution environment
+    "User" "Group" "DynamicUser" "SupplementaryGroups" "WorkingDirectory"
+    "RootDirectory" "Environment" "EnvironmentFile" "PassEnvironment"
+    "StandardInput" "StandardOutput" "StandardError" "SyslogIdentifier"
+    "Nice" "UMask" "LimitNOFILE" "MemoryMax" "CPUQuota" "Slice"
+    "StateDirectory" "CacheDirectory" "LogsDirectory" "RuntimeDirectory"
+    "ConfigurationDirectory" "NoNewPrivileges" "PrivateTmp" "PrivateDevices"
+    "PrivateNetwork" "ProtectSystem" "ProtectHome" "ProtectKernelModules"
+    "ProtectKernelTunables" "ProtectControlGroups" "ReadOnlyPaths"
+    "ReadWritePaths" "InaccessiblePaths" "CapabilityBoundingSet"
+    "AmbientCapabilities" "SystemCallFilter" "RestrictAddressFamilies"
+    "LockPersonality" "MemoryDenyWriteExecute"
+    ; [Timer]
+    "OnActiveSec" "OnBootSec" "OnStartupSec" "OnUnitActiveSec"
+    "OnUnitInactiveSec" "OnCalendar" "AccuracySec" "RandomizedDelaySec"
+    "FixedRandomDelay" "Persistent" "WakeSystem" "RemainAfterElapse" "Unit"
+    ; [Socket]
+    "ListenStream" "ListenDatagram" "ListenSequentialPacket" "ListenFIFO"
+    "Accept" "SocketUser" "SocketGroup" "SocketMode" "Service"
+    ; [Mount]
+    "What" "Where" "Options" "LazyUnmount" "DirectoryMode"))
+
+(exec_prefix) @operator
+
+(value) @string
+
+((value) @constant.builtin.boolean
+  (#match? @constant.builtin.boolean "^(yes|no|true|false|on|off)\\s*$"))
+
+((value) @number
+  (#match? @number "^[0-9]+\\s*$"))
+
+(specifier) @variable.builtin
@@ -0,0 +1,4 @@
+; Exec command lines aren't quite shell, but bash gets most of them right
+((command_line) @injection.content
+  (#set! injection.include-children)
+  (#set! injection.language "bash"))
@@ -0,0 +1,79 @@
+//! The command lines of systemd's `Exec*=` directives inject bash, without
+//! the prefixes that change how systemd runs them.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SERVICE: &str = include_str!("../samples/backup@.service");
+const TIMER: &str = include_str!("../samples/backup@.timer");
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_systemd::language(),
+        helpers::queries!(arborium_systemd),
+        source,
+    )
+}
+
+#[test]
+fn test_samples_parse_cleanly() {
+    for sample in [SERVICE, TIMER] {
+        let tree = helpers::parse_tree(arborium_systemd::language(), sample);
+        let root = tree.root_node();
+        assert!(!root.has_error(), "{}", root.to_sexp());
+    }
+}
+
+#[test]
+fn test_exec_command_lines_inject_bash() {
+    let source = "\
+[Service]
+Environment=PATH=/usr/bin
+ExecStartPre=-/usr/bin/restic unlock
+ExecStart=/usr/bin/restic backup \\
+    --tag %i %h
+ExecStopPost=+/bin/sh -c 'logger done'
+";
+    let injections = injected(source);
+    assert!(injections.iter().all(|(_, language)| language == "bash"));
+    let commands: Vec<&str> = injections.iter().map(|(text, _)| text.trim_end()).collect();
+    assert_eq!(
+        commands,
+        [
+            "/usr/bin/restic unlock",
+            "/usr/bin/restic backup \\\n    --tag %i %h",
+            "/bin/sh -c 'logger done'",
+        ]
+    );
+}
+
+#[test]
+fn test_sample_captures() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "systemd",
+            TestGrammar::new(
+                arborium_systemd::language(),
+                arborium_systemd::HIGHLIGHTS_QUERY,
+                arborium_systemd::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "bash",
+            TestGrammar::new(
+                arborium_bash::language(),
+                arborium_bash::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    let html = highlighter.highlight("systemd", SERVICE).unwrap();
+    assert!(html.contains("<a-t>Service</a-t>"), "{}", html);
+    assert!(html.contains("<a-k>ExecStart</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>WantedBy</a-k>"), "{}", html);
+    // Not a directive systemd documents
+    assert!(html.contains("<a-pr>IOSchedulingClass</a-pr>"), "{}", html);
+    assert!(html.contains("<a-v>%i</a-v>"), "{}", html);
+    assert!(html.contains("<a-o>-</a-o>"), "{}", html);
+    assert!(html.contains("<a-co>yes</a-co>"), "{}", html);
+    assert!(html.contains("<a-c>; A stale lock"), "{}", html);
+}
@@ -0,0 +1,59 @@
+/**
+ * @file TSV grammar for tree-sitter
+ * @license MIT
+ *
+ * Tab-separated values as registered with IANA: one record per line and
+ * fields that can't contain tabs or line breaks, so unlike CSV there is no
+ * quoting. Rows are built like the CSV grammar's, with each field tagged
+ * with its column number modulo six (`column1` to `column6`).
+ * https://www.iana.org/assignments/media-types/text/tab-separated-values
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const COLUMNS = 6;
+
+module.exports = grammar({
+  name: 'tsv',
+
+  extras: _ => [],
+
+  rules: {
+    document: $ => seq(
+      repeat(seq(optional($.row), $._newline)),
+      optional($.row),
+    ),
+
+    row: $ => $._column1,
+
+    ...columns(),
+
+    field: _ => /[^\t\r\n]+/,
+
+    _newline: _ => /\r?\n/,
+  },
+});
+
+/**
+ * Creates the `_column1` to `_column6` rules, each matching a possibly
+ * empty field and the rest of the row, whose first field is the next
+ * column around
+ *
+ * @returns {Record<string, ($: GrammarSymbols<string>) => RuleOrLiteral>}
+ */
+function columns() {
+  /** @type {Record<string, ($: GrammarSymbols<string>) => RuleOrLiteral>} */
+  const rules = {};
+  for (let n = 1; n <= COLUMNS; n++) {
+    const next = `_column${n % COLUMNS + 1}`;
+    rules[`_column${n}`] = $ => {
+      const rest = seq('\t', optional($[next]));
+      return choice(
+        seq(field(`column${n}`, $.field), optional(rest)),
+        rest,
+      );
+    };
+  }
+  return rules;
+}
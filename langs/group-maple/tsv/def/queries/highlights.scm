@@ -0,0 +1,13 @@
+; Columns rotate through six colors, repeating from the seventh
+
+(row column1: (field) @column.1)
+
+(row column2: (field) @column.2)
+
+(row column3: (field) @column.3)
+
+(row column4: (field) @column.4)
+
+(row column5: (field) @column.5)
+
+(row column6: (field) @column.6)
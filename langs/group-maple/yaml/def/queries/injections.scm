@@ -0,0 +1,51 @@
+; Shell in CI files: GitHub Actions' run steps, GitLab CI's scripts and
+; container entrypoints.
+;
+; A block scalar is injected whole, from its `|` or `>` indicator on, with
+; each line's indentation. bash reads the indentation as whitespace, but
+; the indicator and folding aren't stripped: a folded (`>`) script is
+; highlighted line by line, as if it were literal.
+
+((block_mapping_pair
+  key: (flow_node
+    (plain_scalar
+      (string_scalar) @_key))
+  value: (block_node
+    (block_scalar) @injection.content))
+ (#any-of? @_key "run" "script" "before_script" "after_script" "entrypoint")
+ (#set! injection.language "bash"))
+
+((block_mapping_pair
+  key: (flow_node
+    (plain_scalar
+      (string_scalar) @_key))
+  value: (flow_node
+    (plain_scalar) @injection.content))
+ (#any-of? @_key "run" "script" "before_script" "after_script" "entrypoint")
+ (#set! injection.language "bash"))
+
+; GitLab CI scripts are usually lists of commands
+
+((block_mapping_pair
+  key: (flow_node
+    (plain_scalar
+      (string_scalar) @_key))
+  value: (block_node
+    (block_sequence
+      (block_sequence_item
+        (block_node
+          (block_scalar) @injection.content)))))
+ (#any-of? @_key "script" "before_script" "after_script")
+ (#set! injection.language "bash"))
+
+((block_mapping_pair
+  key: (flow_node
+    (plain_scalar
+      (string_scalar) @_key))
+  value: (block_node
+    (block_sequence
+      (block_sequence_item
+        (flow_node
+          (plain_scalar) @injection.content)))))
+ (#any-of? @_key "script" "before_script" "after_script")
+ (#set! injection.language "bash"))
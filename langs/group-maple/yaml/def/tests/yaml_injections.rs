@@ -0,0 +1,83 @@
+//! YAML hands the scripts in CI files to bash.
+
+use arborium_test_harness::helpers;
+
+const WORKFLOW: &str = "\
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo test
+      - name: Lint
+        run: |
+          for f in *.sh; do
+            shellcheck \"$f\"
+          done
+      - name: Notes
+        with:
+          body: |
+            for everyone
+";
+
+const GITLAB: &str = "\
+test:
+  before_script:
+    - apt-get update
+  script:
+    - |
+      if [ -n \"$CI\" ]; then
+        make test
+      fi
+  description: |
+    if only this were shell
+";
+
+fn bash_injections(source: &str) -> Vec<&str> {
+    let result = helpers::plugin_parse(
+        arborium_yaml::language(),
+        helpers::queries!(arborium_yaml),
+        source,
+    );
+    helpers::injected_as(&result, source, "bash")
+}
+
+/// The text bash highlights as `capture` in `source`.
+fn bash_captures<'a>(source: &'a str, capture: &str) -> Vec<&'a str> {
+    helpers::plugin_parse(
+        arborium_bash::language(),
+        helpers::queries!(arborium_bash),
+        source,
+    )
+    .spans
+    .into_iter()
+    .filter(|s| s.capture == capture)
+    .map(|s| &source[s.start as usize..s.end as usize])
+    .collect()
+}
+
+#[test]
+fn test_run_steps_inject_bash() {
+    let scripts = bash_injections(WORKFLOW);
+    assert_eq!(scripts.len(), 2, "{:?}", scripts);
+    assert_eq!(scripts[0], "cargo test");
+    assert!(scripts[1].contains("shellcheck"));
+    assert!(scripts.iter().all(|s| !s.contains("for everyone")));
+}
+
+#[test]
+fn test_gitlab_script_lists_inject_bash() {
+    let scripts = bash_injections(GITLAB);
+    assert_eq!(scripts.len(), 2, "{:?}", scripts);
+    assert_eq!(scripts[0], "apt-get update");
+    assert!(scripts[1].contains("make test"));
+}
+
+#[test]
+fn test_injected_scripts_highlight_as_bash() {
+    let scripts = bash_injections(WORKFLOW);
+    let keywords = bash_captures(scripts[1], "keyword");
+    for keyword in ["for", "in", "do", "done"] {
+        assert!(keywords.contains(&keyword), "{:?}", keywords);
+    }
+    assert!(bash_captures(scripts[1], "string").contains(&"\"$f\""));
+}
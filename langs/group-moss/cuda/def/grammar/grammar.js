@@ -0,0 +1,71 @@
+/**
+ * @file CUDA grammar for tree-sitter, extending C++
+ * @license MIT
+ *
+ * Adds the execution and memory space qualifiers, `__launch_bounds__`,
+ * and kernel launches: `kernel<<<blocks, threads, shared, stream>>>(args)`.
+ * https://docs.nvidia.com/cuda/cuda-c-programming-guide/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const CPP = require('tree-sitter-cpp/grammar');
+const { PREC } = require('tree-sitter-c/grammar');
+
+module.exports = grammar(CPP, {
+  name: 'cuda',
+
+  rules: {
+    _declaration_modifiers: ($, original) => choice(
+      original,
+      $.execution_space_qualifier,
+      $.memory_space_qualifier,
+      $.launch_bounds,
+    ),
+
+    execution_space_qualifier: _ => choice(
+      '__global__',
+      '__device__',
+      '__host__',
+      '__forceinline__',
+      '__noinline__',
+    ),
+
+    memory_space_qualifier: _ => choice(
+      '__shared__',
+      '__constant__',
+      '__managed__',
+      '__grid_constant__',
+    ),
+
+    // __launch_bounds__(maxThreadsPerBlock, minBlocksPerMultiprocessor)
+    launch_bounds: $ => seq(
+      '__launch_bounds__',
+      '(',
+      commaSep1($.expression),
+      ')',
+    ),
+
+    call_expression: ($, original) => choice(
+      original,
+      prec(PREC.CALL, seq(
+        field('function', $.expression),
+        field('launch', $.kernel_call_syntax),
+        field('arguments', $.argument_list),
+      )),
+    ),
+
+    kernel_call_syntax: $ => seq('<<<', commaSep1($.expression), '>>>'),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
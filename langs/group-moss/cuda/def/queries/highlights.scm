@@ -0,0 +1,31 @@
+; CUDA additions on top of the C++ highlights
+
+[
+  (execution_space_qualifier)
+  (memory_space_qualifier)
+  "__launch_bounds__"
+] @keyword.modifier
+
+(kernel_call_syntax
+  [
+    "<<<"
+    ">>>"
+  ] @punctuation.bracket)
+
+(call_expression
+  function: (identifier) @function.call
+  launch: (kernel_call_syntax))
+
+(call_expression
+  function: (template_function
+    name: (identifier) @function.call)
+  launch: (kernel_call_syntax))
+
+; Built-in variables of a kernel's launch configuration
+((identifier) @variable.builtin
+  (#match? @variable.builtin "^(threadIdx|blockIdx|blockDim|gridDim|warpSize)$"))
+
+; Synchronization, atomics, warp intrinsics and fast math
+((call_expression
+  function: (identifier) @function.builtin)
+  (#match? @function.builtin "^(__syncthreads(_count|_and|_or)?|__syncwarp|__threadfence(_block|_system)?|atomic(Add|Sub|Exch|Min|Max|Inc|Dec|CAS|And|Or|Xor)(_block|_system)?|__(shfl|shfl_up|shfl_down|shfl_xor)_sync|__(all|any|ballot|activemask)(_sync)?|__ldg|__(expf|logf|sinf|cosf|powf|fdividef)|__(float2half|half2float)|__(popc|clz|ffs|brev)(ll)?)$"))
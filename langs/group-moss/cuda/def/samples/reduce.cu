@@ -0,0 +1,86 @@
+// Block-wise sum reduction: each block reduces its slice in shared memory,
+// finishes with warp shuffles, and adds its partial sum to the result.
+#include <cstdio>
+#include <vector>
+#include <cuda_runtime.h>
+
+#define CHECK(call)                                                        \
+    do {                                                                   \
+        cudaError_t err = (call);                                          \
+        if (err != cudaSuccess) {                                          \
+            std::fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__,        \
+                         cudaGetErrorString(err));                         \
+            std::exit(1);                                                  \
+        }                                                                  \
+    } while (0)
+
+constexpr int kBlockSize = 256;
+
+__constant__ float scale;
+
+__device__ __forceinline__ float warp_sum(float value) {
+    for (int offset = warpSize / 2; offset > 0; offset /= 2) {
+        value += __shfl_down_sync(0xffffffff, value, offset);
+    }
+    return value;
+}
+
+template <typename T>
+__global__ void __launch_bounds__(kBlockSize)
+reduce(const T* __restrict__ input, T* output, size_t n) {
+    __shared__ T partial[kBlockSize / 32];
+
+    const unsigned lane = threadIdx.x % warpSize;
+    const unsigned warp = threadIdx.x / warpSize;
+
+    T sum = 0;
+    for (size_t i = blockIdx.x * blockDim.x + threadIdx.x; i < n;
+         i += static_cast<size_t>(blockDim.x) * gridDim.x) {
+        sum += input[i] * scale;
+    }
+
+    sum = warp_sum(sum);
+    if (lane == 0) {
+        partial[warp] = sum;
+    }
+    __syncthreads();
+
+    if (warp == 0) {
+        sum = lane < blockDim.x / warpSize ? partial[lane] : 0;
+        sum = warp_sum(sum);
+        if (lane == 0) {
+            atomicAdd(output, sum);
+        }
+    }
+}
+
+int main() {
+    const size_t n = 1 << 24;
+    std::vector<float> host(n, 1.0f);
+
+    float *input = nullptr, *output = nullptr;
+    CHECK(cudaMalloc(&input, n * sizeof(float)));
+    CHECK(cudaMalloc(&output, sizeof(float)));
+    CHECK(cudaMemcpy(input, host.data(), n * sizeof(float), cudaMemcpyHostToDevice));
+    CHECK(cudaMemset(output, 0, sizeof(float)));
+
+    const float one = 1.0f;
+    CHECK(cudaMemcpyToSymbol(scale, &one, sizeof(float)));
+
+    cudaStream_t stream;
+    CHECK(cudaStreamCreate(&stream));
+
+    const int blocks = 1024;
+    reduce<float><<<blocks, kBlockSize, 0, stream>>>(input, output, n);
+    CHECK(cudaGetLastError());
+
+    float result = 0;
+    CHECK(cudaMemcpyAsync(&result, output, sizeof(float), cudaMemcpyDeviceToHost, stream));
+    CHECK(cudaStreamSynchronize(stream));
+    std::printf("sum = %.0f (expected %zu)\n", result, n);
+
+    cudaFree(input);
+    cudaFree(output);
+    cudaStreamDestroy(stream);
+    return 0;
+}
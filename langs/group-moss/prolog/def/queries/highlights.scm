@@ -8,9 +8,52 @@
 ((atom) @boolean
   (#eq? @boolean "false"))
 
+((atom) @string
+  (#match? @string "^'"))
+
+((atom) @keyword
+  (#eq? @keyword "!"))
+
 (functional_notation
   function: (atom) @function.call)
 
+((functional_notation
+  function: (atom) @function.builtin)
+  (#any-of? @function.builtin
+    "call" "findall" "bagof" "setof" "forall" "aggregate_all" "phrase"
+    "format" "write" "writeln" "print" "nl" "read_term" "assert" "asserta"
+    "assertz" "retract" "atom" "number" "var" "nonvar" "is_list" "length"
+    "member" "append" "msort" "sort" "succ" "throw" "catch"))
+
+; Facts and the heads of rules and DCG rules
+(clause_term
+  (functional_notation
+    function: (atom) @function))
+
+(clause_term
+  (atom) @function)
+
+(clause_term
+  (operator_notation
+    .
+    (functional_notation
+      function: (atom) @function)
+    operator: (binary_operator) @_neck
+    (#any-of? @_neck ":-" "-->")))
+
+(clause_term
+  (operator_notation
+    .
+    (atom) @function
+    operator: (binary_operator) @_neck
+    (#any-of? @_neck ":-" "-->")))
+
+(directive_term
+  (functional_notation
+    function: (atom) @keyword.import)
+  (#any-of? @keyword.import
+    "module" "use_module" "ensure_loaded" "include" "consult"))
+
 (integer) @number
 
 (float_number) @number.float
@@ -20,6 +63,12 @@
 (operator_notation
   operator: _ @operator)
 
+((binary_operator) @keyword.operator
+  (#any-of? @keyword.operator "is" "rem" "mod"))
+
+((binary_operator) @keyword.function
+  (#any-of? @keyword.function ":-" "-->"))
+
 [
  (open)
  (open_ct)
@@ -44,3 +93,6 @@
 (double_quoted_list_notation) @string
 
 (variable_term) @variable
+
+((variable_term) @variable.builtin
+  (#eq? @variable.builtin "_"))
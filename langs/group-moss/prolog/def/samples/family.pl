@@ -0,0 +1,62 @@
+% family.pl -- facts, rules and a small DCG over a family tree.
+
+:- module(family, [ancestor/2, sentence//1]).
+:- use_module(library(lists)).
+
+% Facts: parent(Parent, Child).
+parent(tom, bob).
+parent(tom, liz).
+parent(bob, ann).
+parent(bob, pat).
+parent(pat, jim).
+
+female(liz).
+female(ann).
+female(pat).
+male(X) :- \+ female(X).
+
+% Rules. Capitalized names are variables; lowercase names are atoms.
+mother(Mother, Child) :-
+    parent(Mother, Child),
+    female(Mother).
+
+ancestor(X, Y) :- parent(X, Y).
+ancestor(X, Y) :-
+    parent(X, Z),
+    ancestor(Z, Y).
+
+siblings(X, Y) :-
+    parent(P, X),
+    parent(P, Y),
+    X \== Y.
+
+generations(X, X, 0) :- !.
+generations(X, Y, N) :-
+    parent(X, Z),
+    generations(Z, Y, N0),
+    N is N0 + 1.
+
+descendants(X, Sorted) :-
+    findall(D, ancestor(X, D), Ds),
+    sort(Ds, Sorted).
+
+% A DCG for questions like "who is the mother of ann".
+sentence(query(Rel, Who)) --> [who, is, the], relation(Rel), [of], name(Who).
+
+relation(mother) --> [mother].
+relation(parent) --> [parent].
+relation(ancestor) --> [ancestor].
+
+name(Who) --> [Who], { atom(Who), parent(_, Who) }.
+
+answer(Words, Answer) :-
+    phrase(sentence(query(Rel, Who)), Words),
+    Goal =.. [Rel, Answer, Who],
+    (   call(Goal)
+    ->  true
+    ;   Answer = 'nobody we know'
+    ).
+
+main :-
+    answer([who, is, the, mother, of, ann], A),
+    format("~w~n", [A]).
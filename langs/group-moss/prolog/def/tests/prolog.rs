@@ -0,0 +1,60 @@
+//! Prolog tells variables from atoms by their first letter, and marks the
+//! heads of facts, rules and DCG rules as the predicates they define.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SAMPLE: &str = include_str!("../samples/family.pl");
+
+fn highlight(source: &str) -> String {
+    let grammars = [(
+        "prolog",
+        TestGrammar::new(
+            arborium_prolog::language(),
+            arborium_prolog::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    helpers::highlighter(grammars)
+        .highlight("prolog", source)
+        .unwrap()
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = helpers::parse_tree(arborium_prolog::language(), SAMPLE);
+    assert!(
+        !tree.root_node().has_error(),
+        "{}",
+        tree.root_node().to_sexp()
+    );
+}
+
+#[test]
+fn test_variables_and_atoms() {
+    let html = highlight("likes(Person, Thing) :- friend(Person, bob), _ = Thing.\n");
+    assert!(html.contains("<a-v>Person</a-v>"), "{}", html);
+    assert!(html.contains("<a-v>Thing</a-v>"), "{}", html);
+    assert!(html.contains("<a-v>_</a-v>"), "{}", html);
+    assert!(html.contains("<a-co>bob</a-co>"), "{}", html);
+}
+
+#[test]
+fn test_sample_captures() {
+    let html = highlight(SAMPLE);
+    // Heads of facts, rules and DCG rules
+    assert!(html.contains("<a-f>female</a-f>"), "{}", html);
+    assert!(html.contains("<a-f>ancestor</a-f>"), "{}", html);
+    assert!(html.contains("<a-f>relation</a-f>"), "{}", html);
+    assert!(html.contains("<a-f>main</a-f>"), "{}", html);
+    assert!(html.contains("<a-k>:-</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>--&gt;</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>use_module</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>findall</a-f>"), "{}", html);
+    assert!(html.contains("<a-k>is</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>!</a-k>"), "{}", html);
+    assert!(
+        html.contains("<a-s>&#39;nobody we know&#39;</a-s>"),
+        "{}",
+        html
+    );
+}
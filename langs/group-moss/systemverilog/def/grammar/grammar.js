@@ -0,0 +1,14 @@
+/**
+ * @file SystemVerilog grammar for tree-sitter
+ * @license MIT
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// tree-sitter-verilog already implements IEEE 1800, so SystemVerilog is the
+// same grammar under its own name. Keeping it separate lets `.sv` files and
+// plain Verilog get their own highlights.
+module.exports = grammar(require('tree-sitter-verilog/grammar'), {
+  name: 'systemverilog',
+});
@@ -0,0 +1,127 @@
+; SystemVerilog highlights
+;
+; The Verilog highlights are prepended from arborium-verilog. These patterns
+; come later, so they win over the generic identifier and keyword captures
+; there.
+
+; Classes
+(class_declaration
+  (class_identifier
+    (simple_identifier) @type.definition))
+
+(interface_class_declaration
+  (class_identifier
+    (simple_identifier) @type.definition))
+
+(ps_class_identifier
+  (class_identifier
+    (simple_identifier) @type))
+
+(class_constructor_declaration
+  "new" @constructor)
+
+(class_new
+  "new" @constructor)
+
+(implicit_class_handle) @variable.builtin
+
+(method_identifier
+  (simple_identifier) @function.method)
+
+(random_qualifier) @keyword.modifier
+(class_item_qualifier) @keyword.modifier
+(lifetime) @keyword.modifier
+
+; Interfaces, packages and modports
+(interface_identifier
+  (simple_identifier) @type)
+
+(package_identifier
+  (simple_identifier) @module)
+
+(modport_identifier
+  (simple_identifier) @type)
+
+; Constraints, coverage and assertions
+(constraint_identifier
+  (simple_identifier) @function)
+
+(covergroup_identifier
+  (simple_identifier) @type)
+
+(cover_point_identifier
+  (simple_identifier) @label)
+
+(property_identifier
+  (simple_identifier) @function)
+
+; Data types
+(integer_atom_type) @type.builtin
+(integer_vector_type) @type.builtin
+(non_integer_type) @type.builtin
+
+[
+  "string"
+  "chandle"
+  "event"
+  "void"
+] @type.builtin
+
+"null" @constant.builtin
+
+; Keywords
+[
+  "class"
+  "endclass"
+  "extends"
+  "implements"
+  "virtual"
+  "pure"
+  "extern"
+  "interface"
+  "endinterface"
+  "modport"
+  "package"
+  "endpackage"
+  "import"
+  "export"
+  "program"
+  "endprogram"
+  "clocking"
+  "endclocking"
+  "typedef"
+  "struct"
+  "union"
+  "enum"
+  "packed"
+  "constraint"
+  "solve"
+  "before"
+  "inside"
+  "covergroup"
+  "endgroup"
+  "coverpoint"
+  "cross"
+  "bins"
+  "property"
+  "endproperty"
+  "sequence"
+  "endsequence"
+  "assert"
+  "assume"
+  "cover"
+  "iff"
+  "disable"
+  "fork"
+  "join"
+  "join_any"
+  "join_none"
+  "wait"
+  "final"
+  "foreach"
+  "do"
+  "return"
+  "unique"
+  "priority"
+  "with"
+] @keyword
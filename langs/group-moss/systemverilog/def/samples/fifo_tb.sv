@@ -0,0 +1,105 @@
+// Class-based testbench for a synchronous FIFO: a virtual interface, a
+// randomized transaction, a driver/monitor pair and functional coverage.
+
+interface fifo_if (input logic clk);
+  logic       rst_n;
+  logic       push, pop;
+  logic [7:0] wdata, rdata;
+  logic       full, empty;
+
+  modport dut (input clk, rst_n, push, pop, wdata, output rdata, full, empty);
+endinterface : fifo_if
+
+package fifo_tb_pkg;
+
+  class fifo_txn;
+    rand bit       push;
+    rand bit       pop;
+    rand bit [7:0] data;
+
+    constraint no_idle_c { push || pop; }
+    constraint data_c { data inside {[8'h00:8'h7f]}; }
+
+    function string to_string();
+      return $sformatf("push=%0b pop=%0b data=%02h", push, pop, data);
+    endfunction
+  endclass : fifo_txn
+
+  class fifo_driver;
+    virtual fifo_if vif;
+    protected int unsigned sent;
+
+    function new(virtual fifo_if vif);
+      this.vif = vif;
+    endfunction
+
+    task automatic drive(fifo_txn txn);
+      @(posedge vif.clk);
+      vif.push  <= txn.push && !vif.full;
+      vif.pop   <= txn.pop && !vif.empty;
+      vif.wdata <= txn.data;
+      sent++;
+    endtask
+  endclass : fifo_driver
+
+  class fifo_scoreboard;
+    bit [7:0] expected[$];
+    int errors;
+
+    covergroup fifo_cg with function sample(bit full, bit empty);
+      full_cp  : coverpoint full;
+      empty_cp : coverpoint empty;
+      state_x  : cross full_cp, empty_cp;
+    endgroup
+
+    function new();
+      fifo_cg = new();
+    endfunction
+
+    function void check(bit [7:0] actual);
+      bit [7:0] want = expected.pop_front();
+      if (actual !== want) begin
+        errors++;
+        $error("expected %02h, got %02h", want, actual);
+      end
+    endfunction
+  endclass : fifo_scoreboard
+
+endpackage : fifo_tb_pkg
+
+module fifo_tb;
+  import fifo_tb_pkg::*;
+
+  logic clk = 0;
+  always #5 clk = ~clk;
+
+  fifo_if bus (clk);
+  sync_fifo #(.DEPTH(16)) dut (.bus(bus.dut));
+
+  property no_push_when_full;
+    @(posedge clk) disable iff (!bus.rst_n) bus.full |-> !bus.push;
+  endproperty
+
+  assert property (no_push_when_full) else $error("push while full");
+
+  initial begin
+    fifo_driver     drv = new(bus);
+    fifo_scoreboard sb  = new();
+    fifo_txn        txn;
+
+    bus.rst_n = 0;
+    repeat (2) @(posedge clk);
+    bus.rst_n = 1;
+
+    repeat (200) begin
+      txn = new();
+      if (!txn.randomize()) $fatal(1, "randomize failed");
+      if (txn.push) sb.expected.push_back(txn.data);
+      drv.drive(txn);
+      sb.fifo_cg.sample(bus.full, bus.empty);
+    end
+
+    $display("done: %0d errors", sb.errors);
+    $finish;
+  end
+endmodule
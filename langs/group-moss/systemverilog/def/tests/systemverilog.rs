@@ -0,0 +1,53 @@
+//! SystemVerilog shares Verilog's parser but layers its own highlights on
+//! top, so classes, qualifiers and assertions stop looking like plain
+//! identifiers.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const TESTBENCH: &str = "\
+class packet extends base_packet;
+  rand bit [7:0] data;
+  constraint small_c { data < 8'h10; }
+
+  function new();
+    super.new();
+  endfunction
+endclass
+";
+
+fn highlight(language: &'static str, grammar: TestGrammar) -> String {
+    helpers::highlighter([(language, grammar)])
+        .highlight(language, TESTBENCH)
+        .unwrap()
+}
+
+#[test]
+fn test_systemverilog_highlights_classes() {
+    let html = highlight(
+        "systemverilog",
+        TestGrammar::new(
+            arborium_systemverilog::language(),
+            &arborium_systemverilog::HIGHLIGHTS_QUERY,
+            arborium_systemverilog::INJECTIONS_QUERY,
+        ),
+    );
+    assert!(html.contains("<a-k>class</a-k>"), "{}", html);
+    assert!(html.contains("<a-t>packet</a-t>"), "{}", html);
+    assert!(html.contains("<a-t>base_packet</a-t>"), "{}", html);
+    assert!(html.contains("<a-k>rand</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>endclass</a-k>"), "{}", html);
+}
+
+#[test]
+fn test_verilog_keeps_its_own_highlights() {
+    let html = highlight(
+        "verilog",
+        TestGrammar::new(
+            arborium_verilog::language(),
+            arborium_verilog::HIGHLIGHTS_QUERY,
+            arborium_verilog::INJECTIONS_QUERY,
+        ),
+    );
+    assert!(html.contains("<a-v>packet</a-v>"), "{}", html);
+    assert!(!html.contains("<a-k>class</a-k>"), "{}", html);
+}
@@ -0,0 +1,83 @@
+// 8N1 UART transmitter in plain Verilog-2001.
+
+module uart_tx #(
+    parameter CLKS_PER_BIT = 868
+) (
+    input  wire       clk,
+    input  wire       rst,
+    input  wire       start,
+    input  wire [7:0] data,
+    output reg        tx,
+    output wire       busy
+);
+
+    localparam IDLE  = 2'd0;
+    localparam START = 2'd1;
+    localparam DATA  = 2'd2;
+    localparam STOP  = 2'd3;
+
+    reg [1:0]  state;
+    reg [9:0]  clk_count;
+    reg [2:0]  bit_index;
+    reg [7:0]  shift;
+
+    assign busy = (state != IDLE);
+
+    always @(posedge clk) begin
+        if (rst) begin
+            state     <= IDLE;
+            tx        <= 1'b1;
+            clk_count <= 0;
+            bit_index <= 0;
+        end else begin
+            case (state)
+                IDLE: begin
+                    tx <= 1'b1;
+                    if (start) begin
+                        shift     <= data;
+                        clk_count <= 0;
+                        state     <= START;
+                    end
+                end
+
+                START: begin
+                    tx <= 1'b0;
+                    if (clk_count == CLKS_PER_BIT - 1) begin
+                        clk_count <= 0;
+                        state     <= DATA;
+                    end else begin
+                        clk_count <= clk_count + 1;
+                    end
+                end
+
+                DATA: begin
+                    tx <= shift[bit_index];
+                    if (clk_count == CLKS_PER_BIT - 1) begin
+                        clk_count <= 0;
+                        if (bit_index == 3'd7) begin
+                            bit_index <= 0;
+                            state     <= STOP;
+                        end else begin
+                            bit_index <= bit_index + 1;
+                        end
+                    end else begin
+                        clk_count <= clk_count + 1;
+                    end
+                end
+
+                STOP: begin
+                    tx <= 1'b1;
+                    if (clk_count == CLKS_PER_BIT - 1) begin
+                        clk_count <= 0;
+                        state     <= IDLE;
+                    end else begin
+                        clk_count <= clk_count + 1;
+                    end
+                end
+
+                default: state <= IDLE;
+            endcase
+        end
+    end
+
+endmodule
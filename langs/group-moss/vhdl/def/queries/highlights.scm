@@ -3,6 +3,9 @@
 ; Comments
 (comment) @comment
 
+; Identifiers (fallback; the more specific patterns below win)
+(identifier) @variable
+
 ; Strings
 (string_literal) @string
 (character_literal) @character
@@ -127,7 +130,9 @@
   "="
   "/="
   "<"
+  "<="
   ">"
+  ">="
   ":="
   "=>"
 ] @operator
@@ -147,5 +152,92 @@
   "."
 ] @punctuation.delimiter
 
-; Identifiers (fallback)
-(identifier) @variable
+(boolean) @boolean
+
+; Design units
+(entity_declaration
+  name: (identifier) @type)
+
+(architecture_body
+  name: (identifier) @type
+  entity: (simple_name) @type)
+
+(component_declaration
+  name: (identifier) @type)
+
+(entity_instantiation
+  entity: (simple_name) @type)
+
+(entity_instantiation
+  entity: (selected_name
+    suffix: (simple_name) @type))
+
+(entity_instantiation
+  architecture: (simple_name) @type)
+
+(entity_declaration
+  at_end: (simple_name) @type)
+
+(architecture_body
+  at_end: (simple_name) @type)
+
+(component_declaration
+  at_end: (simple_name) @type)
+
+(library_clause
+  (logical_name_list
+    library: (simple_name) @module))
+
+; Types
+(full_type_declaration
+  name: (identifier) @type.definition)
+
+(subtype_declaration
+  name: (identifier) @type.definition)
+
+(type_mark
+  (simple_name) @type)
+
+; Subprograms
+(function_body
+  designator: (identifier) @function)
+
+(procedure_body
+  designator: (identifier) @function)
+
+(function_declaration
+  designator: (identifier) @function)
+
+(procedure_declaration
+  designator: (identifier) @function)
+
+(function_call
+  function: (simple_name) @function.call)
+
+(procedure_call_statement
+  procedure: (simple_name) @function.call)
+
+; Calls and indexing share a syntax, so only well-known functions are marked
+((ambiguous_name
+  prefix: (simple_name) @function.builtin)
+  (#any-of? @function.builtin
+    "rising_edge" "falling_edge" "to_integer" "to_unsigned" "to_signed"
+    "resize" "shift_left" "shift_right" "rotate_left" "rotate_right"
+    "to_01" "std_match" "to_string" "to_hstring" "minimum" "maximum"))
+
+; Attributes: clk'event, data'length, my_attr
+(attribute_name
+  designator: (predefined_designator) @attribute.builtin)
+
+(attribute_name
+  designator: (simple_name) @attribute)
+
+(range_attribute_name
+  designator: (predefined_designator) @attribute.builtin)
+
+(attribute_declaration
+  name: (identifier) @attribute)
+
+; Labels of processes, instances and generate statements
+(label
+  (identifier) @label)
@@ -0,0 +1,88 @@
+-- UART transmitter: 8 data bits, no parity, one stop bit.
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+
+entity uart_tx is
+  generic (
+    CLK_HZ : positive := 50_000_000;
+    BAUD   : positive := 115_200
+  );
+  port (
+    clk     : in  std_logic;
+    rst_n   : in  std_logic;
+    data    : in  std_logic_vector(7 downto 0);
+    start   : in  std_logic;
+    tx      : out std_logic;
+    busy    : out std_logic
+  );
+end entity uart_tx;
+
+architecture rtl of uart_tx is
+  constant TICKS_PER_BIT : natural := CLK_HZ / BAUD;
+
+  type state_t is (IDLE, START_BIT, DATA_BITS, STOP_BIT);
+  signal state    : state_t := IDLE;
+  signal tick_cnt : unsigned(15 downto 0) := (others => '0');
+  signal bit_idx  : integer range 0 to 7 := 0;
+  signal shreg    : std_logic_vector(data'range);
+
+  attribute fsm_encoding : string;
+  attribute fsm_encoding of state : signal is "one_hot";
+begin
+  busy <= '0' when state = IDLE else '1';
+
+  transmit : process (clk, rst_n)
+  begin
+    if rst_n = '0' then
+      state    <= IDLE;
+      tx       <= '1';
+      tick_cnt <= (others => '0');
+    elsif rising_edge(clk) then
+      case state is
+        when IDLE =>
+          tx <= '1';
+          if start = '1' then
+            shreg    <= data;
+            tick_cnt <= (others => '0');
+            state    <= START_BIT;
+          end if;
+
+        when START_BIT =>
+          tx <= '0';
+          if tick_cnt = TICKS_PER_BIT - 1 then
+            tick_cnt <= (others => '0');
+            bit_idx  <= 0;
+            state    <= DATA_BITS;
+          else
+            tick_cnt <= tick_cnt + 1;
+          end if;
+
+        when DATA_BITS =>
+          tx <= shreg(bit_idx);
+          if tick_cnt = TICKS_PER_BIT - 1 then
+            tick_cnt <= (others => '0');
+            if bit_idx = shreg'high then
+              state <= STOP_BIT;
+            else
+              bit_idx <= bit_idx + 1;
+            end if;
+          else
+            tick_cnt <= tick_cnt + 1;
+          end if;
+
+        when STOP_BIT =>
+          tx <= '1';
+          if tick_cnt = TICKS_PER_BIT - 1 then
+            state <= IDLE;
+          else
+            tick_cnt <= tick_cnt + 1;
+          end if;
+      end case;
+    end if;
+  end process transmit;
+
+  assert BAUD < CLK_HZ / 4
+    report "baud rate too high for the clock"
+    severity failure;
+end architecture rtl;
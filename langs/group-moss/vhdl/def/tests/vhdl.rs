@@ -0,0 +1,44 @@
+//! VHDL design units, subprograms and attributes get their own captures
+//! instead of falling back to plain identifiers.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SAMPLE: &str = include_str!("../samples/uart_tx.vhd");
+
+fn highlight() -> String {
+    let grammars = [(
+        "vhdl",
+        TestGrammar::new(
+            arborium_vhdl::language(),
+            arborium_vhdl::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    helpers::highlighter(grammars)
+        .highlight("vhdl", SAMPLE)
+        .unwrap()
+}
+
+#[test]
+fn test_design_units_are_types() {
+    let html = highlight();
+    assert!(
+        html.contains("<a-k>entity</a-k> <a-t>uart_tx</a-t>"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("<a-k>architecture</a-k> <a-t>rtl</a-t>"),
+        "{}",
+        html
+    );
+    assert!(html.contains("<a-t>state_t</a-t>"), "{}", html);
+}
+
+#[test]
+fn test_attributes_and_calls() {
+    let html = highlight();
+    assert!(html.contains("<a-at>high</a-at>"), "{}", html);
+    assert!(html.contains("<a-f>rising_edge</a-f>"), "{}", html);
+    assert!(html.contains("<a-l>transmit</a-l>"), "{}", html);
+}
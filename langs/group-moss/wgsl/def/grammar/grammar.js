@@ -0,0 +1,431 @@
+/**
+ * @file WGSL grammar for tree-sitter
+ * @license MIT
+ *
+ * WGSL disambiguates `vec4<f32>` from comparisons in its lexer. Here only
+ * the predeclared generic types take template lists, so an identifier
+ * followed by `<` is always a comparison.
+ * https://www.w3.org/TR/WGSL/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  or: 1,
+  and: 2,
+  bitor: 3,
+  bitxor: 4,
+  bitand: 5,
+  equality: 6,
+  relational: 7,
+  shift: 8,
+  additive: 9,
+  multiplicative: 10,
+  unary: 11,
+  postfix: 12,
+};
+
+const GENERIC_TYPES = [
+  'array',
+  'atomic',
+  'ptr',
+  'vec2',
+  'vec3',
+  'vec4',
+  'mat2x2',
+  'mat2x3',
+  'mat2x4',
+  'mat3x2',
+  'mat3x3',
+  'mat3x4',
+  'mat4x2',
+  'mat4x3',
+  'mat4x4',
+  'texture_1d',
+  'texture_2d',
+  'texture_2d_array',
+  'texture_3d',
+  'texture_cube',
+  'texture_cube_array',
+  'texture_multisampled_2d',
+  'texture_storage_1d',
+  'texture_storage_2d',
+  'texture_storage_2d_array',
+  'texture_storage_3d',
+];
+
+const ASSIGNMENT_OPERATORS = ['=', '+=', '-=', '*=', '/=', '%=', '&=', '|=', '^=', '>>=', '<<='];
+
+module.exports = grammar({
+  name: 'wgsl',
+
+  extras: $ => [/\s/, $.line_comment, $.block_comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    translation_unit: $ => seq(
+      repeat($.global_directive),
+      repeat($._global_declaration),
+    ),
+
+    global_directive: $ => seq(
+      choice('enable', 'requires'),
+      commaSep1($.identifier),
+      optional(','),
+      ';',
+    ),
+
+    _global_declaration: $ => choice(
+      ';',
+      $.global_variable_declaration,
+      $.global_constant_declaration,
+      $.type_alias_declaration,
+      $.struct_declaration,
+      $.function_declaration,
+      $.const_assert_statement,
+    ),
+
+    // Attributes
+
+    _attributes: $ => repeat1($.attribute),
+
+    attribute: $ => seq(
+      '@',
+      field('name', $.identifier),
+      optional(field('arguments', $.attribute_arguments)),
+    ),
+
+    attribute_arguments: $ => seq('(', commaSep1($._expression), optional(','), ')'),
+
+    // Declarations
+
+    global_variable_declaration: $ => seq(
+      optional($._attributes),
+      $._variable_declaration,
+      ';',
+    ),
+
+    _variable_declaration: $ => seq(
+      'var',
+      optional($.variable_qualifier),
+      field('name', $.identifier),
+      optional(seq(':', field('type', $._type))),
+      optional(seq('=', field('value', $._expression))),
+    ),
+
+    // var<storage, read_write>
+    variable_qualifier: $ => seq(
+      '<',
+      field('address_space', $.identifier),
+      optional(seq(',', field('access_mode', $.identifier))),
+      '>',
+    ),
+
+    global_constant_declaration: $ => seq(
+      optional($._attributes),
+      choice('const', 'override'),
+      field('name', $.identifier),
+      optional(seq(':', field('type', $._type))),
+      optional(seq('=', field('value', $._expression))),
+      ';',
+    ),
+
+    type_alias_declaration: $ => seq(
+      'alias',
+      field('name', $.identifier),
+      '=',
+      field('type', $._type),
+      ';',
+    ),
+
+    struct_declaration: $ => seq(
+      'struct',
+      field('name', $.identifier),
+      '{',
+      commaSep1($.struct_member),
+      optional(','),
+      '}',
+      optional(';'),
+    ),
+
+    struct_member: $ => seq(
+      optional($._attributes),
+      field('name', $.identifier),
+      ':',
+      field('type', $._type),
+    ),
+
+    function_declaration: $ => seq(
+      optional($._attributes),
+      'fn',
+      field('name', $.identifier),
+      field('parameters', $.parameter_list),
+      optional(seq('->', optional($._attributes), field('return_type', $._type))),
+      field('body', $.compound_statement),
+    ),
+
+    parameter_list: $ => seq(
+      '(',
+      optional(seq(commaSep1($.parameter), optional(','))),
+      ')',
+    ),
+
+    parameter: $ => seq(
+      optional($._attributes),
+      field('name', $.identifier),
+      ':',
+      field('type', $._type),
+    ),
+
+    // Types
+
+    _type: $ => choice($.identifier, $.type_declaration),
+
+    // vec4<f32>, array<Light, 16>, ptr<function, i32>
+    type_declaration: $ => prec.right(seq(
+      field('name', $.generic_type),
+      optional(field('arguments', $.template_list)),
+    )),
+
+    generic_type: _ => choice(...GENERIC_TYPES),
+
+    template_list: $ => seq(
+      '<',
+      commaSep1(choice($._type, $.int_literal)),
+      optional(','),
+      '>',
+    ),
+
+    // Statements
+
+    compound_statement: $ => seq(optional($._attributes), '{', repeat($._statement), '}'),
+
+    _statement: $ => choice(
+      ';',
+      $.compound_statement,
+      $.return_statement,
+      $.if_statement,
+      $.switch_statement,
+      $.loop_statement,
+      $.for_statement,
+      $.while_statement,
+      $.break_statement,
+      $.continue_statement,
+      $.discard_statement,
+      $.const_assert_statement,
+      seq($.variable_statement, ';'),
+      seq($.assignment_statement, ';'),
+      seq($.increment_statement, ';'),
+      seq(alias($.call_expression, $.call_statement), ';'),
+    ),
+
+    variable_statement: $ => choice(
+      $._variable_declaration,
+      seq(
+        choice('let', 'const'),
+        field('name', $.identifier),
+        optional(seq(':', field('type', $._type))),
+        '=',
+        field('value', $._expression),
+      ),
+    ),
+
+    assignment_statement: $ => seq(
+      field('left', choice($._expression, '_')),
+      field('operator', choice(...ASSIGNMENT_OPERATORS)),
+      field('right', $._expression),
+    ),
+
+    increment_statement: $ => seq($._expression, choice('++', '--')),
+
+    return_statement: $ => seq('return', optional($._expression), ';'),
+
+    break_statement: _ => seq('break', ';'),
+
+    continue_statement: _ => seq('continue', ';'),
+
+    discard_statement: _ => seq('discard', ';'),
+
+    const_assert_statement: $ => seq('const_assert', $._expression, ';'),
+
+    if_statement: $ => seq(
+      optional($._attributes),
+      'if',
+      field('condition', $._expression),
+      field('consequence', $.compound_statement),
+      optional(seq('else', field('alternative', choice($.if_statement, $.compound_statement)))),
+    ),
+
+    switch_statement: $ => seq(
+      optional($._attributes),
+      'switch',
+      field('value', $._expression),
+      optional($._attributes),
+      '{',
+      repeat1($.switch_clause),
+      '}',
+    ),
+
+    switch_clause: $ => seq(
+      choice(
+        seq('case', commaSep1(choice($._expression, 'default')), optional(',')),
+        'default',
+      ),
+      optional(':'),
+      field('body', $.compound_statement),
+    ),
+
+    loop_statement: $ => seq(
+      optional($._attributes),
+      'loop',
+      optional($._attributes),
+      '{',
+      repeat($._statement),
+      optional($.continuing_statement),
+      '}',
+    ),
+
+    continuing_statement: $ => seq(
+      'continuing',
+      optional($._attributes),
+      '{',
+      repeat($._statement),
+      optional($.break_if_statement),
+      '}',
+    ),
+
+    break_if_statement: $ => seq('break', 'if', $._expression, ';'),
+
+    for_statement: $ => seq(
+      optional($._attributes),
+      'for',
+      '(',
+      optional(field('initializer', choice(
+        $.variable_statement,
+        $.assignment_statement,
+        $.increment_statement,
+        alias($.call_expression, $.call_statement),
+      ))),
+      ';',
+      optional(field('condition', $._expression)),
+      ';',
+      optional(field('update', choice(
+        $.assignment_statement,
+        $.increment_statement,
+        alias($.call_expression, $.call_statement),
+      ))),
+      ')',
+      field('body', $.compound_statement),
+    ),
+
+    while_statement: $ => seq(
+      optional($._attributes),
+      'while',
+      field('condition', $._expression),
+      field('body', $.compound_statement),
+    ),
+
+    // Expressions
+
+    _expression: $ => choice(
+      $.binary_expression,
+      $.unary_expression,
+      $._primary_expression,
+    ),
+
+    _primary_expression: $ => choice(
+      $.identifier,
+      $.bool_literal,
+      $.int_literal,
+      $.float_literal,
+      $.call_expression,
+      $.member_expression,
+      $.index_expression,
+      $.parenthesized_expression,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    call_expression: $ => prec(PREC.postfix, seq(
+      field('function', choice($.identifier, $.type_declaration)),
+      field('arguments', $.argument_list),
+    )),
+
+    argument_list: $ => seq(
+      '(',
+      optional(seq(commaSep1($._expression), optional(','))),
+      ')',
+    ),
+
+    member_expression: $ => prec(PREC.postfix, seq(
+      field('value', $._primary_expression),
+      '.',
+      field('member', $.identifier),
+    )),
+
+    index_expression: $ => prec(PREC.postfix, seq(
+      field('value', $._primary_expression),
+      '[',
+      field('index', $._expression),
+      ']',
+    )),
+
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice('-', '!', '~', '*', '&')),
+      field('operand', $._expression),
+    )),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.or, '||'],
+        [PREC.and, '&&'],
+        [PREC.bitor, '|'],
+        [PREC.bitxor, '^'],
+        [PREC.bitand, '&'],
+        [PREC.equality, choice('==', '!=')],
+        [PREC.relational, choice('<', '<=', '>', '>=')],
+        [PREC.shift, choice('<<', '>>')],
+        [PREC.additive, choice('+', '-')],
+        [PREC.multiplicative, choice('*', '/', '%')],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
+        ),
+      )));
+    },
+
+    // Literals
+
+    bool_literal: _ => choice('true', 'false'),
+
+    int_literal: _ => /(0[xX][0-9a-fA-F]+|0|[1-9][0-9]*)[iu]?/,
+
+    float_literal: _ => token(choice(
+      /((\d+\.\d*|\.\d+)([eE][+-]?\d+)?|\d+[eE][+-]?\d+)[fh]?/,
+      /(0|[1-9]\d*)[fh]/,
+      /0[xX]([0-9a-fA-F]+\.[0-9a-fA-F]*|\.[0-9a-fA-F]+|[0-9a-fA-F]+)[pP][+-]?\d+[fh]?/,
+    )),
+
+    identifier: _ => /[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    line_comment: _ => token(seq('//', /.*/)),
+
+    block_comment: _ => token(seq('/*', /[^*]*\*+([^/*][^*]*\*+)*/, '/')),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
@@ -0,0 +1,165 @@
+; Keywords
+
+[
+  "alias"
+  "break"
+  "case"
+  "const"
+  "const_assert"
+  "continue"
+  "continuing"
+  "default"
+  "discard"
+  "else"
+  "enable"
+  "fn"
+  "for"
+  "if"
+  "let"
+  "loop"
+  "override"
+  "requires"
+  "return"
+  "struct"
+  "switch"
+  "var"
+  "while"
+] @keyword
+
+(bool_literal) @boolean
+
+[
+  (int_literal)
+  (float_literal)
+] @number
+
+; Identifiers
+
+(identifier) @variable
+
+(parameter
+  name: (identifier) @variable.parameter)
+
+(struct_member
+  name: (identifier) @property)
+
+(member_expression
+  member: (identifier) @property)
+
+; Types
+
+(generic_type) @type.builtin
+
+(type_alias_declaration
+  name: (identifier) @type)
+
+(struct_declaration
+  name: (identifier) @type)
+
+(struct_member
+  type: (identifier) @type)
+
+(parameter
+  type: (identifier) @type)
+
+(function_declaration
+  return_type: (identifier) @type)
+
+(global_variable_declaration
+  type: (identifier) @type)
+
+(global_constant_declaration
+  type: (identifier) @type)
+
+(variable_statement
+  type: (identifier) @type)
+
+(template_list
+  (identifier) @type)
+
+; Scalars and the vector and matrix shorthands like vec4f or mat4x4h
+((identifier) @type.builtin
+  (#match? @type.builtin "^(bool|f16|f32|i32|u32|vec[234][fhiu]|mat[234]x[234][fh]|sampler|sampler_comparison|texture_depth_(2d|2d_array|cube|cube_array|multisampled_2d)|texture_external)$"))
+
+; Address spaces and access modes: var<storage, read_write>, ptr<function, f32>
+(variable_qualifier
+  (identifier) @keyword.storage)
+
+((template_list
+  (identifier) @keyword.storage)
+  (#match? @keyword.storage "^(function|private|workgroup|uniform|storage|handle|read|write|read_write)$"))
+
+; Functions
+
+(function_declaration
+  name: (identifier) @function)
+
+(call_expression
+  function: (identifier) @function.call)
+
+((call_expression
+  function: (identifier) @function.builtin)
+  (#match? @function.builtin "^(abs|acos|acosh|all|any|arrayLength|asin|asinh|atan|atan2|atanh|atomic(Load|Store|Add|Sub|Max|Min|And|Or|Xor|Exchange|CompareExchangeWeak)|bitcast|ceil|clamp|cos|cosh|count(LeadingZeros|OneBits|TrailingZeros)|cross|degrees|determinant|distance|dot|dot4[IU]8Packed|dpdx|dpdxCoarse|dpdxFine|dpdy|dpdyCoarse|dpdyFine|exp|exp2|extractBits|faceForward|firstLeadingBit|firstTrailingBit|floor|fma|fract|frexp|fwidth|fwidthCoarse|fwidthFine|insertBits|inverseSqrt|ldexp|length|log|log2|max|min|mix|modf|normalize|pack.*|pow|quantizeToF16|radians|reflect|refract|reverseBits|round|saturate|select|sign|sin|sinh|smoothstep|sqrt|step|storageBarrier|subgroup.*|tan|tanh|texture(Dimensions|Gather|GatherCompare|Load|NumLayers|NumLevels|NumSamples|Sample|SampleBaseClampToEdge|SampleBias|SampleCompare|SampleCompareLevel|SampleGrad|SampleLevel|Store|Barrier)|transpose|trunc|unpack.*|workgroupBarrier|workgroupUniformLoad)$"))
+
+; Attributes
+
+(attribute
+  "@" @attribute
+  name: (identifier) @attribute)
+
+; Built-in values: @builtin(position)
+((attribute
+  name: (identifier) @_name
+  arguments: (attribute_arguments
+    (identifier) @variable.builtin))
+  (#eq? @_name "builtin"))
+
+; Operators and punctuation
+
+(binary_expression
+  operator: _ @operator)
+
+(unary_expression
+  operator: _ @operator)
+
+(assignment_statement
+  operator: _ @operator)
+
+[
+  "->"
+  "++"
+  "--"
+] @operator
+
+[
+  "."
+  ","
+  ":"
+  ";"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(template_list
+  [
+    "<"
+    ">"
+  ] @punctuation.bracket)
+
+(variable_qualifier
+  [
+    "<"
+    ">"
+  ] @punctuation.bracket)
+
+[
+  (line_comment)
+  (block_comment)
+] @comment
@@ -0,0 +1,694 @@
+/**
+ * @file Haxe grammar for tree-sitter
+ * @license MIT
+ *
+ * Haxe 4: modules with classes, interfaces, enums, abstracts and typedefs,
+ * metadata (`@:keep`), conditional compilation (`#if js ... #end`), and an
+ * expression-oriented body language with string interpolation in
+ * single-quoted strings.
+ * https://haxe.org/manual/introduction.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  assign: 1,
+  pair: 2,
+  ternary: 3,
+  coalesce: 4,
+  or: 5,
+  and: 6,
+  interval: 7,
+  compare: 8,
+  bitwise: 9,
+  shift: 10,
+  additive: 11,
+  multiplicative: 12,
+  modulo: 13,
+  unary: 14,
+  postfix: 15,
+  call: 16,
+};
+
+const MODIFIERS = [
+  'public',
+  'private',
+  'static',
+  'inline',
+  'override',
+  'dynamic',
+  'extern',
+  'macro',
+  'overload',
+  'abstract',
+  'final',
+];
+
+module.exports = grammar({
+  name: 'haxe',
+
+  extras: $ => [/\s/, $.comment, $.conditional_compilation],
+
+  word: $ => $.identifier,
+
+  conflicts: $ => [
+    // `(a)` and `(a:Int)` start both an arrow function and an expression.
+    [$._expression, $.parameter],
+    // `{ final x:Int }` is a structure field, `{ final x:Int; }` a member.
+    [$.modifier, $.variable_declaration],
+  ],
+
+  rules: {
+    source_file: $ => repeat(choice(
+      $.package_declaration,
+      $.import_declaration,
+      $.using_declaration,
+      $._type_declaration,
+      $.function_declaration,
+      $.variable_declaration,
+    )),
+
+    // #if (js && !debug), #elseif sys, #else, #end, #error "message"
+    conditional_compilation: _ => token(choice(
+      seq(/#(if|elseif)[ \t]+/, choice(/!?[a-zA-Z_][\w.]*/, /!?\([^)\n]*\)/)),
+      '#else',
+      '#end',
+      seq('#error', /[^\n]*/),
+    )),
+
+    // Modules
+
+    package_declaration: $ => seq(
+      'package',
+      optional(field('name', $._dotted_name)),
+      ';',
+    ),
+
+    // import haxe.ds.StringMap; import haxe.macro.*; import Lambda.map as lmap;
+    import_declaration: $ => seq(
+      'import',
+      field('path', $.import_path),
+      optional(seq(choice('as', 'in'), field('alias', $.identifier))),
+      ';',
+    ),
+
+    import_path: $ => seq(
+      $.identifier,
+      repeat(seq('.', choice($.identifier, '*'))),
+    ),
+
+    using_declaration: $ => seq('using', field('path', $._dotted_name), ';'),
+
+    _dotted_name: $ => alias(
+      seq($.identifier, repeat(seq('.', $.identifier))),
+      $.dotted_name,
+    ),
+
+    // Metadata and modifiers
+
+    // @:keep, @:native("Foo"), @author("me")
+    metadata: $ => prec.right(seq(
+      field('name', alias(/@:?[a-zA-Z_][\w.]*/, $.metadata_name)),
+      optional(seq(
+        token.immediate('('),
+        optional(commaSep1($._expression)),
+        ')',
+      )),
+    )),
+
+    modifier: _ => choice(...MODIFIERS),
+
+    _decorations: $ => repeat1(choice($.metadata, $.modifier)),
+
+    // Types
+
+    _type_declaration: $ => choice(
+      $.class_declaration,
+      $.interface_declaration,
+      $.enum_declaration,
+      $.abstract_declaration,
+      $.typedef_declaration,
+    ),
+
+    class_declaration: $ => seq(
+      optional($._decorations),
+      'class',
+      field('name', $.identifier),
+      optional(field('type_parameters', $.type_parameters)),
+      repeat(choice(
+        seq('extends', field('superclass', $.type)),
+        seq('implements', field('interface', $.type)),
+      )),
+      field('body', $.class_body),
+    ),
+
+    interface_declaration: $ => seq(
+      optional($._decorations),
+      'interface',
+      field('name', $.identifier),
+      optional(field('type_parameters', $.type_parameters)),
+      repeat(seq('extends', field('interface', $.type))),
+      field('body', $.class_body),
+    ),
+
+    class_body: $ => seq(
+      '{',
+      repeat(choice($.variable_declaration, $.function_declaration)),
+      '}',
+    ),
+
+    // enum Shape { Circle(radius:Float); Square(side:Float); Empty; }
+    enum_declaration: $ => seq(
+      optional($._decorations),
+      'enum',
+      field('name', $.identifier),
+      optional(field('type_parameters', $.type_parameters)),
+      '{',
+      repeat($.enum_constructor),
+      '}',
+    ),
+
+    enum_constructor: $ => seq(
+      repeat($.metadata),
+      field('name', $.identifier),
+      optional(field('parameters', $.parameters)),
+      optional($.type_annotation),
+      ';',
+    ),
+
+    // abstract Meters(Float) from Float to Float { ... }
+    // enum abstract Color(Int) { var Red = 0; }
+    abstract_declaration: $ => seq(
+      optional($._decorations),
+      optional('enum'),
+      'abstract',
+      field('name', $.identifier),
+      optional(field('type_parameters', $.type_parameters)),
+      optional(seq('(', field('underlying', $._type), ')')),
+      repeat(seq(choice('from', 'to'), $._type)),
+      field('body', $.class_body),
+    ),
+
+    typedef_declaration: $ => seq(
+      optional($._decorations),
+      'typedef',
+      field('name', $.identifier),
+      optional(field('type_parameters', $.type_parameters)),
+      '=',
+      field('type', $._type),
+      optional(';'),
+    ),
+
+    // <T>, <K:haxe.Constraints.IMap<String, Int>, V = Int>
+    type_parameters: $ => seq(
+      '<',
+      commaSep1($.type_parameter),
+      '>',
+    ),
+
+    type_parameter: $ => seq(
+      field('name', $.identifier),
+      optional(seq(':', $._type)),
+      optional(seq('=', $._type)),
+    ),
+
+    _type: $ => choice(
+      $.type,
+      $.function_type,
+      $.structure_type,
+      $.parenthesized_type,
+    ),
+
+    // Int, haxe.ds.Map<String, Array<Int>>
+    type: $ => prec.right(seq(
+      $.identifier,
+      repeat(seq('.', $.identifier)),
+      optional($.type_arguments),
+    )),
+
+    type_arguments: $ => seq('<', commaSep1($._type), '>'),
+
+    // Int -> String -> Void, (name:String, ?age:Int) -> Void
+    function_type: $ => prec.right(seq($._type, '->', $._type)),
+
+    parenthesized_type: $ => seq(
+      '(',
+      optional(commaSep1(choice(
+        $._type,
+        seq(optional('?'), $.identifier, ':', $._type),
+      ))),
+      ')',
+    ),
+
+    // { x:Int, ?y:Int }, {> Base, z:Int }, { var x:Int; function f():Void; }
+    structure_type: $ => choice(
+      seq(
+        '{',
+        optional(seq('>', $.type, ',')),
+        optional(commaSep1($.structure_field)),
+        optional(','),
+        '}',
+      ),
+      seq(
+        '{',
+        repeat1(choice($.variable_declaration, $.function_declaration)),
+        '}',
+      ),
+    ),
+
+    structure_field: $ => seq(
+      optional($._decorations),
+      optional('?'),
+      field('name', $.identifier),
+      ':',
+      field('type', $._type),
+    ),
+
+    type_annotation: $ => seq(':', $._type),
+
+    // Declarations
+
+    // var count(default, null):Int = 0;
+    variable_declaration: $ => seq(
+      optional($._decorations),
+      choice('var', 'final'),
+      commaSep1($.variable_declarator),
+      ';',
+    ),
+
+    variable_declarator: $ => seq(
+      field('name', $.identifier),
+      optional(field('accessors', $.property_accessors)),
+      optional($.type_annotation),
+      optional(seq('=', field('value', $._expression))),
+    ),
+
+    // (get, set), (default, null), (default, never)
+    property_accessors: $ => seq(
+      '(',
+      $._accessor,
+      ',',
+      $._accessor,
+      ')',
+    ),
+
+    _accessor: $ => choice(
+      $.identifier,
+      alias(choice('default', 'null', 'dynamic'), $.identifier),
+    ),
+
+    function_declaration: $ => seq(
+      optional($._decorations),
+      'function',
+      field('name', choice($.identifier, alias('new', $.identifier))),
+      optional(field('type_parameters', $.type_parameters)),
+      field('parameters', $.parameters),
+      optional(field('return_type', $.type_annotation)),
+      choice(';', seq(field('body', $._expression), optional(';'))),
+    ),
+
+    parameters: $ => seq(
+      '(',
+      optional(seq(commaSep1($.parameter), optional(','))),
+      ')',
+    ),
+
+    // ?name:String = "anonymous"
+    parameter: $ => seq(
+      repeat($.metadata),
+      optional('?'),
+      field('name', $.identifier),
+      optional($.type_annotation),
+      optional(seq('=', field('default', $._expression))),
+    ),
+
+    // Statements
+
+    block: $ => seq('{', repeat($._statement), '}'),
+
+    _statement: $ => choice(
+      $.variable_declaration,
+      $.expression_statement,
+    ),
+
+    // Blocks and other expressions ending in `}` don't need a semicolon.
+    expression_statement: $ => prec.right(-1, seq($._expression, optional(';'))),
+
+    // Expressions
+
+    _expression: $ => choice(
+      $.identifier,
+      $.integer,
+      $.float,
+      $.string,
+      $.regex,
+      $.boolean,
+      $.null,
+      $.this,
+      $.super,
+      $.block,
+      $.object,
+      $.array,
+      $.parenthesized_expression,
+      $.type_check,
+      $.call_expression,
+      $.member_expression,
+      $.index_expression,
+      $.new_expression,
+      $.cast_expression,
+      $.unary_expression,
+      $.update_expression,
+      $.binary_expression,
+      $.ternary_expression,
+      $.assignment_expression,
+      $.function_expression,
+      $.arrow_function,
+      $.if_expression,
+      $.switch_expression,
+      $.for_expression,
+      $.while_expression,
+      $.do_while_expression,
+      $.try_expression,
+      $.return_expression,
+      $.throw_expression,
+      $.break,
+      $.continue,
+      $.untyped_expression,
+      $.macro_expression,
+      $.metadata_expression,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    // (value : Float)
+    type_check: $ => seq('(', $._expression, ':', $._type, ')'),
+
+    // { name: "Ada", "year": 1815 }
+    object: $ => seq(
+      '{',
+      commaSep1($.object_field),
+      optional(','),
+      '}',
+    ),
+
+    object_field: $ => seq(
+      field('name', choice($.identifier, $.string)),
+      ':',
+      field('value', $._expression),
+    ),
+
+    // [1, 2], ["a" => 1], [for (i in 0...3) i * i]
+    array: $ => seq(
+      '[',
+      optional(seq(commaSep1($._expression), optional(','))),
+      ']',
+    ),
+
+    call_expression: $ => prec(PREC.call, seq(
+      field('function', $._expression),
+      field('arguments', $.arguments),
+    )),
+
+    arguments: $ => seq(
+      '(',
+      optional(seq(commaSep1($._expression), optional(','))),
+      ')',
+    ),
+
+    member_expression: $ => prec(PREC.call, seq(
+      field('object', $._expression),
+      choice('.', '?.'),
+      field('member', $.identifier),
+    )),
+
+    index_expression: $ => prec(PREC.call, seq(
+      field('object', $._expression),
+      '[',
+      field('index', $._expression),
+      ']',
+    )),
+
+    new_expression: $ => prec(PREC.call, seq(
+      'new',
+      field('type', $.type),
+      field('arguments', $.arguments),
+    )),
+
+    // cast value, cast(value, Float)
+    cast_expression: $ => choice(
+      prec(PREC.unary, seq('cast', $._expression)),
+      seq('cast', '(', $._expression, ',', $._type, ')'),
+    ),
+
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice('!', '-', '~')),
+      field('argument', $._expression),
+    )),
+
+    update_expression: $ => choice(
+      prec(PREC.unary, seq(field('operator', choice('++', '--')), $._expression)),
+      prec(PREC.postfix, seq($._expression, field('operator', choice('++', '--')))),
+    ),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.pair, '=>', prec.right],
+        [PREC.coalesce, '??', prec.right],
+        [PREC.or, '||', prec.left],
+        [PREC.and, '&&', prec.left],
+        [PREC.interval, '...', prec.left],
+        [PREC.compare, choice('==', '!=', '<', '<=', '>', '>='), prec.left],
+        [PREC.bitwise, choice('|', '&', '^'), prec.left],
+        [PREC.shift, choice('<<', '>>', '>>>'), prec.left],
+        [PREC.additive, choice('+', '-'), prec.left],
+        [PREC.multiplicative, choice('*', '/'), prec.left],
+        [PREC.modulo, '%', prec.left],
+      ];
+      return choice(
+        ...table.map(([precedence, operator, associativity]) =>
+          /** @type {typeof prec.left} */ (associativity)(
+            /** @type {number} */ (precedence),
+            seq(
+              field('left', $._expression),
+              field('operator', /** @type {RuleOrLiteral} */ (operator)),
+              field('right', $._expression),
+            ),
+          )),
+        // value is String
+        prec.left(PREC.compare, seq(
+          field('left', $._expression),
+          field('operator', 'is'),
+          field('right', $.type),
+        )),
+      );
+    },
+
+    ternary_expression: $ => prec.right(PREC.ternary, seq(
+      field('condition', $._expression),
+      '?',
+      field('consequence', $._expression),
+      ':',
+      field('alternative', $._expression),
+    )),
+
+    assignment_expression: $ => prec.right(PREC.assign, seq(
+      field('left', $._expression),
+      field('operator', choice(
+        '=', '+=', '-=', '*=', '/=', '%=', '<<=', '>>=', '>>>=', '|=', '&=', '^=', '??=',
+      )),
+      field('right', $._expression),
+    )),
+
+    // function(x:Int):Int return x * 2, function named() {}
+    function_expression: $ => prec.right(seq(
+      'function',
+      optional(field('name', $.identifier)),
+      optional(field('type_parameters', $.type_parameters)),
+      field('parameters', $.parameters),
+      optional(field('return_type', $.type_annotation)),
+      field('body', $._expression),
+    )),
+
+    // x -> x * 2, (a, b) -> a + b
+    arrow_function: $ => prec.right(PREC.assign, seq(
+      field('parameters', choice(
+        alias($.identifier, $.parameter),
+        $.parameters,
+      )),
+      '->',
+      field('body', $._expression),
+    )),
+
+    // `a; else b` is allowed: the semicolon belongs to the branch.
+    if_expression: $ => prec.right(seq(
+      'if',
+      '(',
+      field('condition', $._expression),
+      ')',
+      field('consequence', $._expression),
+      optional(seq(
+        choice('else', alias(token(seq(';', /\s*/, 'else')), 'else')),
+        field('alternative', $._expression),
+      )),
+    )),
+
+    switch_expression: $ => seq(
+      'switch',
+      field('value', $._expression),
+      '{',
+      repeat(choice($.switch_case, $.switch_default)),
+      '}',
+    ),
+
+    // case Circle(r) | Ring(r, _) if (r > 0):
+    switch_case: $ => seq(
+      'case',
+      commaSep1(field('pattern', $._expression)),
+      optional(seq('if', field('guard', $._expression))),
+      ':',
+      repeat($._statement),
+    ),
+
+    switch_default: $ => seq('default', ':', repeat($._statement)),
+
+    // for (i in 0...10), for (key => value in map)
+    for_expression: $ => prec.right(seq(
+      'for',
+      '(',
+      field('variable', $.identifier),
+      optional(seq('=>', field('value_variable', $.identifier))),
+      'in',
+      field('iterable', $._expression),
+      ')',
+      field('body', $._expression),
+    )),
+
+    while_expression: $ => prec.right(seq(
+      'while',
+      '(',
+      field('condition', $._expression),
+      ')',
+      field('body', $._expression),
+    )),
+
+    do_while_expression: $ => seq(
+      'do',
+      field('body', $._expression),
+      'while',
+      '(',
+      field('condition', $._expression),
+      ')',
+    ),
+
+    try_expression: $ => prec.right(seq(
+      'try',
+      field('body', $._expression),
+      repeat1($.catch_clause),
+    )),
+
+    catch_clause: $ => prec.right(seq(
+      'catch',
+      '(',
+      field('name', $.identifier),
+      optional($.type_annotation),
+      ')',
+      field('body', $._expression),
+    )),
+
+    return_expression: $ => prec.right(seq('return', optional($._expression))),
+
+    throw_expression: $ => prec.right(seq('throw', $._expression)),
+
+    break: _ => 'break',
+
+    continue: _ => 'continue',
+
+    untyped_expression: $ => prec(PREC.unary, seq('untyped', $._expression)),
+
+    macro_expression: $ => prec(PREC.unary, seq('macro', $._expression)),
+
+    // @:privateAccess obj.secret
+    metadata_expression: $ => prec(PREC.unary, seq($.metadata, $._expression)),
+
+    // Literals
+
+    identifier: _ => /[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    integer: _ => token(choice(
+      /0x[0-9a-fA-F_]+(i32|i64|u32)?/,
+      /\d[\d_]*(i32|i64|u32)?/,
+    )),
+
+    float: _ => token(choice(
+      /\d[\d_]*\.\d[\d_]*([eE][+-]?\d+)?(f64)?/,
+      /\.\d[\d_]*([eE][+-]?\d+)?(f64)?/,
+      /\d[\d_]*[eE][+-]?\d+(f64)?/,
+    )),
+
+    boolean: _ => choice('true', 'false'),
+
+    null: _ => 'null',
+
+    this: _ => 'this',
+
+    super: _ => 'super',
+
+    string: $ => choice(
+      seq(
+        '"',
+        repeat(choice(
+          alias(token.immediate(prec(1, /[^"\\]+/)), $.string_content),
+          $.escape_sequence,
+        )),
+        '"',
+      ),
+      // Single-quoted strings interpolate `$name` and `${expression}`.
+      seq(
+        '\'',
+        repeat(choice(
+          alias(token.immediate(prec(1, /[^'\\$]+/)), $.string_content),
+          alias(token.immediate('$'), $.string_content),
+          alias(token.immediate('$$'), $.escape_sequence),
+          $.escape_sequence,
+          $.interpolation,
+        )),
+        '\'',
+      ),
+    ),
+
+    interpolation: $ => choice(
+      seq(token.immediate('${'), $._expression, '}'),
+      seq(token.immediate('$'), alias(token.immediate(/[a-zA-Z_][a-zA-Z0-9_]*/), $.identifier)),
+    ),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /[nrt"'\\$]/,
+        /x[0-9a-fA-F]{2}/,
+        /u[0-9a-fA-F]{4}/,
+        /u\{[0-9a-fA-F]+\}/,
+        /[0-7]{1,3}/,
+      ),
+    )),
+
+    // ~/^[a-z]+$/i
+    regex: _ => token(seq('~/', /([^\/\\\n]|\\.)*/, '/', /[gimsu]*/)),
+
+    comment: _ => token(choice(
+      seq('//', /[^\n]*/),
+      seq('/*', /[^*]*\*+([^/*][^*]*\*+)*/, '/'),
+    )),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
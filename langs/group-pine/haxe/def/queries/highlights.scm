@@ -0,0 +1,262 @@
+; Identifiers
+
+(identifier) @variable
+
+((identifier) @type
+  (#match? @type "^[A-Z][a-zA-Z0-9_]*[a-z][a-zA-Z0-9_]*$"))
+
+((identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+((identifier) @function.builtin
+  (#eq? @function.builtin "trace"))
+
+; Keywords
+
+[
+  "class"
+  "interface"
+  "enum"
+  "abstract"
+  "typedef"
+  "var"
+  "final"
+  "extends"
+  "implements"
+  "from"
+  "to"
+  "new"
+  "cast"
+  "untyped"
+  "macro"
+] @keyword
+
+"function" @keyword.function
+
+[
+  "package"
+  "import"
+  "using"
+  "as"
+] @keyword.import
+
+[
+  "if"
+  "else"
+  "switch"
+  "case"
+  "default"
+] @keyword.conditional
+
+[
+  "for"
+  "while"
+  "do"
+  "in"
+  (break)
+  (continue)
+] @keyword.repeat
+
+"return" @keyword.return
+
+[
+  "try"
+  "catch"
+  "throw"
+] @keyword.exception
+
+"is" @keyword.operator
+
+(modifier) @keyword.modifier
+
+(conditional_compilation) @keyword.directive
+
+(metadata
+  name: (metadata_name) @attribute)
+
+; Types
+
+(type
+  (identifier) @type)
+
+((type
+  (identifier) @type.builtin)
+  (#any-of? @type.builtin
+    "Any" "Array" "Bool" "Dynamic" "Float" "Int" "Map" "Null" "String" "UInt"
+    "Void"))
+
+(class_declaration
+  name: (identifier) @type.definition)
+
+(interface_declaration
+  name: (identifier) @type.definition)
+
+(enum_declaration
+  name: (identifier) @type.definition)
+
+(abstract_declaration
+  name: (identifier) @type.definition)
+
+(typedef_declaration
+  name: (identifier) @type.definition)
+
+(type_parameter
+  name: (identifier) @type)
+
+(enum_constructor
+  name: (identifier) @constructor)
+
+; Declarations
+
+(import_path
+  (identifier) @module)
+
+(dotted_name
+  (identifier) @module)
+
+(function_declaration
+  name: (identifier) @function)
+
+(function_expression
+  name: (identifier) @function)
+
+((function_declaration
+  name: (identifier) @constructor)
+  (#eq? @constructor "new"))
+
+(parameter
+  name: (identifier) @variable.parameter)
+
+(arrow_function
+  parameters: (parameter) @variable.parameter)
+
+(catch_clause
+  name: (identifier) @variable.parameter)
+
+(class_body
+  (variable_declaration
+    (variable_declarator
+      name: (identifier) @property)))
+
+(property_accessors
+  (identifier) @keyword)
+
+(structure_field
+  name: (identifier) @property)
+
+(object_field
+  name: (_) @property)
+
+(member_expression
+  member: (identifier) @property)
+
+; Calls
+
+(call_expression
+  function: (identifier) @function.call)
+
+(call_expression
+  function: (member_expression
+    member: (identifier) @function.method.call))
+
+(new_expression
+  type: (type
+    (identifier) @constructor))
+
+; Literals
+
+(integer) @number
+
+(float) @number.float
+
+(boolean) @boolean
+
+(null) @constant.builtin
+
+[
+  (this)
+  (super)
+] @variable.builtin
+
+(string) @string
+
+(string_content) @string
+
+(escape_sequence) @string.escape
+
+(regex) @string.regexp
+
+(interpolation
+  [
+    "${"
+    "$"
+    "}"
+  ] @punctuation.special)
+
+(interpolation
+  (identifier) @variable)
+
+(comment) @comment
+
+; Operators and punctuation
+
+[
+  "="
+  "+="
+  "-="
+  "*="
+  "/="
+  "%="
+  "<<="
+  ">>="
+  ">>>="
+  "|="
+  "&="
+  "^="
+  "??="
+  "=>"
+  "??"
+  "||"
+  "&&"
+  "..."
+  "=="
+  "!="
+  "<"
+  "<="
+  ">"
+  ">="
+  "|"
+  "&"
+  "^"
+  "<<"
+  ">>"
+  ">>>"
+  "+"
+  "-"
+  "*"
+  "/"
+  "%"
+  "!"
+  "~"
+  "++"
+  "--"
+  "->"
+  "?"
+  "?."
+] @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ":"
+  ";"
+  "."
+] @punctuation.delimiter
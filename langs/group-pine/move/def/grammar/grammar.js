@@ -0,0 +1,494 @@
+/**
+ * @file Move grammar for tree-sitter
+ * @license Apache-2.0
+ *
+ * Covers the Move dialects of Aptos and Sui, including Sui's file-level
+ * `module a::b;` form. Specification blocks are not parsed.
+ * https://aptos.dev/en/build/smart-contracts/book
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  assign: 1,
+  or: 2,
+  and: 3,
+  comparison: 4,
+  bitor: 5,
+  bitxor: 6,
+  bitand: 7,
+  shift: 8,
+  range: 9,
+  additive: 10,
+  multiplicative: 11,
+  unary: 12,
+  field: 13,
+  call: 14,
+};
+
+const PRIMITIVE_TYPES = [
+  'u8',
+  'u16',
+  'u32',
+  'u64',
+  'u128',
+  'u256',
+  'bool',
+  'address',
+  'signer',
+  'vector',
+];
+
+module.exports = grammar({
+  name: 'move',
+
+  extras: $ => [/\s/, $.line_comment, $.block_comment],
+
+  word: $ => $.identifier,
+
+  conflicts: $ => [
+    // `borrow_global<T>(addr)` against `a < b`
+    [$.name_expression],
+  ],
+
+  rules: {
+    source_file: $ => repeat(choice($.module_definition, $.script_definition)),
+
+    // Modules
+
+    module_definition: $ => seq(
+      repeat($.attributes),
+      'module',
+      field('name', $.module_identity),
+      choice(
+        seq('{', repeat($._item), '}'),
+        seq(';', repeat($._item)),
+      ),
+    ),
+
+    // 0x1::coin, aptos_framework::coin, or just coin in an address block
+    module_identity: $ => seq(
+      optional(seq(field('address', choice($.num_literal, $.identifier)), '::')),
+      field('module', $.identifier),
+    ),
+
+    script_definition: $ => seq(
+      'script',
+      '{',
+      repeat($._item),
+      '}',
+    ),
+
+    _item: $ => seq(
+      repeat($.attributes),
+      optional($._modifiers),
+      choice(
+        $.use_declaration,
+        $.friend_declaration,
+        $.constant,
+        $.struct_definition,
+        $.function_definition,
+      ),
+    ),
+
+    _modifiers: $ => repeat1(choice(
+      $.visibility,
+      'entry',
+      'native',
+      'inline',
+    )),
+
+    visibility: _ => seq(
+      'public',
+      optional(seq('(', choice('friend', 'package', 'script'), ')')),
+    ),
+
+    // #[test(account = @0x1)], #[test_only]
+    attributes: $ => seq('#', '[', commaSep1($.attribute), optional(','), ']'),
+
+    attribute: $ => seq(
+      field('name', $.identifier),
+      optional(choice(
+        seq('=', field('value', $._literal)),
+        seq('(', optional(seq(commaSep1($.attribute), optional(','))), ')'),
+      )),
+    ),
+
+    use_declaration: $ => seq(
+      'use',
+      field('path', $.use_path),
+      ';',
+    ),
+
+    // std::vector, aptos_framework::coin::{Self, Coin as C}
+    use_path: $ => seq(
+      choice($.num_literal, $.identifier),
+      repeat1(seq('::', choice($.identifier, $.use_group))),
+      optional(seq('as', field('alias', $.identifier))),
+    ),
+
+    use_group: $ => seq(
+      '{',
+      commaSep1(seq(
+        $.identifier,
+        optional(seq('as', field('alias', $.identifier))),
+      )),
+      optional(','),
+      '}',
+    ),
+
+    friend_declaration: $ => seq('friend', field('module', $.module_access), ';'),
+
+    constant: $ => seq(
+      'const',
+      field('name', $.identifier),
+      ':',
+      field('type', $._type),
+      '=',
+      field('value', $._expression),
+      ';',
+    ),
+
+    // Structs
+
+    struct_definition: $ => seq(
+      'struct',
+      field('name', $.identifier),
+      optional(field('type_parameters', $.type_parameters)),
+      optional(field('abilities', $.ability_declarations)),
+      choice(
+        field('fields', $.field_declarations),
+        seq(field('fields', $.positional_fields), optional(field('abilities', $.ability_declarations)), ';'),
+        ';',
+      ),
+    ),
+
+    ability_declarations: $ => seq('has', commaSep1($.ability)),
+
+    ability: _ => choice('copy', 'drop', 'store', 'key'),
+
+    field_declarations: $ => seq(
+      '{',
+      optional(seq(commaSep1($.field_annotation), optional(','))),
+      '}',
+    ),
+
+    field_annotation: $ => seq(field('name', $.identifier), ':', field('type', $._type)),
+
+    positional_fields: $ => seq('(', optional(seq(commaSep1($._type), optional(','))), ')'),
+
+    type_parameters: $ => seq('<', commaSep1($.type_parameter), optional(','), '>'),
+
+    type_parameter: $ => seq(
+      optional('phantom'),
+      field('name', $.identifier),
+      optional(seq(':', $.ability, repeat(seq('+', $.ability)))),
+    ),
+
+    // Functions
+
+    function_definition: $ => seq(
+      'fun',
+      field('name', $.identifier),
+      optional(field('type_parameters', $.type_parameters)),
+      field('parameters', $.function_parameters),
+      optional(seq(':', field('return_type', $._type))),
+      optional(seq('acquires', commaSep1($.module_access))),
+      choice(field('body', $.block), ';'),
+    ),
+
+    function_parameters: $ => seq(
+      '(',
+      optional(seq(commaSep1($.function_parameter), optional(','))),
+      ')',
+    ),
+
+    function_parameter: $ => seq(
+      optional('mut'),
+      field('name', $.identifier),
+      ':',
+      field('type', $._type),
+    ),
+
+    // Types
+
+    _type: $ => choice(
+      $.primitive_type,
+      $.apply_type,
+      $.ref_type,
+      $.tuple_type,
+    ),
+
+    primitive_type: $ => prec.right(seq(
+      choice(...PRIMITIVE_TYPES),
+      optional($.type_arguments),
+    )),
+
+    apply_type: $ => prec.right(seq(
+      $.module_access,
+      optional($.type_arguments),
+    )),
+
+    ref_type: $ => seq('&', optional('mut'), $._type),
+
+    tuple_type: $ => seq('(', optional(seq(commaSep1($._type), optional(','))), ')'),
+
+    type_arguments: $ => seq('<', commaSep1($._type), optional(','), '>'),
+
+    // coin::Coin, 0x1::coin::Coin, @std::string
+    module_access: $ => prec.right(seq(
+      optional('@'),
+      choice($.identifier, $.num_literal),
+      repeat(seq('::', $.identifier)),
+    )),
+
+    // Statements and blocks
+
+    block: $ => seq(
+      '{',
+      repeat(choice($.use_declaration, $.let_statement, seq($._expression, ';'))),
+      optional($._expression),
+      '}',
+    ),
+
+    let_statement: $ => seq(
+      'let',
+      field('binding', $._bind),
+      optional(seq(':', field('type', $._type))),
+      optional(seq('=', field('value', $._expression))),
+      ';',
+    ),
+
+    _bind: $ => choice(
+      $.bind_variable,
+      $.bind_unpack,
+      $.bind_tuple,
+    ),
+
+    bind_variable: $ => seq(optional('mut'), $.identifier),
+
+    // let Coin { value } = coin;
+    bind_unpack: $ => seq(
+      $.module_access,
+      optional($.type_arguments),
+      '{',
+      optional(seq(commaSep1($.bind_field), optional(','))),
+      '}',
+    ),
+
+    bind_field: $ => seq(
+      field('field', $.identifier),
+      optional(seq(':', field('binding', $._bind))),
+    ),
+
+    bind_tuple: $ => seq('(', optional(seq(commaSep1($._bind), optional(','))), ')'),
+
+    // Expressions
+
+    _expression: $ => choice(
+      $.assign_expression,
+      $.if_expression,
+      $.while_expression,
+      $.loop_expression,
+      $.return_expression,
+      $.abort_expression,
+      $.break_expression,
+      $.continue_expression,
+      $.binary_expression,
+      $._unary_expression,
+    ),
+
+    assign_expression: $ => prec.right(PREC.assign, seq(
+      field('left', $._unary_expression),
+      field('operator', choice('=', '+=', '-=', '*=', '/=', '%=', '&=', '|=', '^=', '<<=', '>>=')),
+      field('right', $._expression),
+    )),
+
+    if_expression: $ => prec.right(seq(
+      'if',
+      '(',
+      field('condition', $._expression),
+      ')',
+      field('consequence', $._expression),
+      optional(seq('else', field('alternative', $._expression))),
+    )),
+
+    while_expression: $ => prec.right(seq(
+      'while',
+      '(',
+      field('condition', $._expression),
+      ')',
+      field('body', $._expression),
+    )),
+
+    loop_expression: $ => prec.right(seq('loop', field('body', $._expression))),
+
+    return_expression: $ => prec.right(seq('return', optional($._expression))),
+
+    abort_expression: $ => prec.right(seq('abort', $._expression)),
+
+    break_expression: _ => 'break',
+
+    continue_expression: _ => 'continue',
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.or, '||'],
+        [PREC.and, '&&'],
+        [PREC.comparison, choice('==', '!=', '<', '>', '<=', '>=')],
+        [PREC.bitor, '|'],
+        [PREC.bitxor, '^'],
+        [PREC.bitand, '&'],
+        [PREC.shift, choice('<<', '>>')],
+        [PREC.range, '..'],
+        [PREC.additive, choice('+', '-')],
+        [PREC.multiplicative, choice('*', '/', '%')],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
+        ),
+      )));
+    },
+
+    _unary_expression: $ => choice(
+      $.unary_expression,
+      $.borrow_expression,
+      $.dereference_expression,
+      $.move_or_copy_expression,
+      $._primary_expression,
+    ),
+
+    unary_expression: $ => prec(PREC.unary, seq('!', $._unary_expression)),
+
+    borrow_expression: $ => prec(PREC.unary, seq('&', optional('mut'), $._unary_expression)),
+
+    dereference_expression: $ => prec(PREC.unary, seq('*', $._unary_expression)),
+
+    move_or_copy_expression: $ => prec(PREC.unary, seq(choice('move', 'copy'), $.identifier)),
+
+    _primary_expression: $ => choice(
+      $._literal,
+      $.name_expression,
+      $.call_expression,
+      $.macro_call_expression,
+      $.pack_expression,
+      $.dot_expression,
+      $.index_expression,
+      $.vector_expression,
+      $.cast_expression,
+      $.parenthesized_expression,
+      $.tuple_expression,
+      $.block,
+    ),
+
+    name_expression: $ => seq(
+      field('access', $.module_access),
+      optional(field('type_arguments', $.type_arguments)),
+    ),
+
+    call_expression: $ => prec(PREC.call, seq(
+      field('function', $.name_expression),
+      field('arguments', $.arguments),
+    )),
+
+    // assert!(cond, E_CODE)
+    macro_call_expression: $ => seq(
+      field('macro', $.identifier),
+      token.immediate('!'),
+      field('arguments', $.arguments),
+    ),
+
+    arguments: $ => seq('(', optional(seq(commaSep1($._expression), optional(','))), ')'),
+
+    // Coin { value: 0 }
+    pack_expression: $ => seq(
+      field('type', $.name_expression),
+      '{',
+      optional(seq(commaSep1($.field_initializer), optional(','))),
+      '}',
+    ),
+
+    field_initializer: $ => seq(
+      field('field', $.identifier),
+      optional(seq(':', field('value', $._expression))),
+    ),
+
+    // coin.value, or a method call: coin.split(10)
+    dot_expression: $ => prec(PREC.field, seq(
+      field('object', $._primary_expression),
+      '.',
+      field('field', $.identifier),
+      optional(field('type_arguments', $.type_arguments)),
+      optional(field('arguments', $.arguments)),
+    )),
+
+    index_expression: $ => prec(PREC.field, seq(
+      field('object', $._primary_expression),
+      '[',
+      field('index', $._expression),
+      ']',
+    )),
+
+    vector_expression: $ => seq(
+      'vector',
+      optional($.type_arguments),
+      '[',
+      optional(seq(commaSep1($._expression), optional(','))),
+      ']',
+    ),
+
+    cast_expression: $ => seq('(', $._expression, 'as', field('type', $._type), ')'),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    tuple_expression: $ => choice(
+      seq('(', ')'),
+      seq('(', $._expression, ',', optional(seq(commaSep1($._expression), optional(','))), ')'),
+    ),
+
+    // Literals
+
+    _literal: $ => choice(
+      $.bool_literal,
+      $.num_literal,
+      $.address_literal,
+      $.byte_string_literal,
+      $.hex_string_literal,
+    ),
+
+    bool_literal: _ => choice('true', 'false'),
+
+    num_literal: _ => token(seq(
+      choice(/0[xX][0-9a-fA-F_]+/, /\d[\d_]*/),
+      optional(choice('u8', 'u16', 'u32', 'u64', 'u128', 'u256')),
+    )),
+
+    // @0x1, @std
+    address_literal: _ => token(seq('@', choice(/0[xX][0-9a-fA-F_]+/, /\d[\d_]*/, /[a-zA-Z_][a-zA-Z0-9_]*/))),
+
+    byte_string_literal: _ => token(seq('b"', repeat(choice(/[^"\\]/, /\\./)), '"')),
+
+    hex_string_literal: _ => token(seq('x"', /[0-9a-fA-F]*/, '"')),
+
+    identifier: _ => /[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    line_comment: _ => token(seq('//', /.*/)),
+
+    block_comment: _ => token(seq('/*', /[^*]*\*+([^/*][^*]*\*+)*/, '/')),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
@@ -0,0 +1,200 @@
+; Keywords
+
+[
+  "abort"
+  "acquires"
+  "as"
+  "break"
+  "const"
+  "continue"
+  "else"
+  "entry"
+  "friend"
+  "fun"
+  "has"
+  "if"
+  "inline"
+  "let"
+  "loop"
+  "module"
+  "mut"
+  "native"
+  "phantom"
+  "public"
+  "return"
+  "script"
+  "struct"
+  "use"
+  "while"
+] @keyword
+
+[
+  "copy"
+  "move"
+] @keyword.operator
+
+(visibility
+  [
+    "package"
+    "script"
+  ] @keyword.modifier)
+
+(ability) @type.builtin
+
+; Types
+
+(primitive_type) @type.builtin
+
+(apply_type
+  (module_access
+    (identifier) @type .))
+
+(struct_definition
+  name: (identifier) @type)
+
+(type_parameter
+  name: (identifier) @type)
+
+(pack_expression
+  type: (name_expression
+    access: (module_access
+      (identifier) @type .)))
+
+(bind_unpack
+  (module_access
+    (identifier) @type .))
+
+; Modules
+
+(module_identity
+  module: (identifier) @module)
+
+(module_identity
+  address: (_) @constant)
+
+(use_path
+  .
+  (identifier) @module)
+
+; Functions
+
+(function_definition
+  name: (identifier) @function)
+
+(call_expression
+  function: (name_expression
+    access: (module_access
+      (identifier) @function.call .)))
+
+(dot_expression
+  field: (identifier) @function.method.call
+  arguments: (arguments))
+
+(macro_call_expression
+  macro: (identifier) @function.macro
+  "!" @function.macro)
+
+; Variables and fields
+
+(function_parameter
+  name: (identifier) @variable.parameter)
+
+(field_annotation
+  name: (identifier) @property)
+
+(field_initializer
+  field: (identifier) @property)
+
+(bind_field
+  field: (identifier) @property)
+
+(dot_expression
+  field: (identifier) @property)
+
+(constant
+  name: (identifier) @constant)
+
+((identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+; Attributes
+
+(attributes) @attribute
+
+(attribute
+  name: (identifier) @attribute)
+
+; Literals
+
+(bool_literal) @boolean
+
+(num_literal) @number
+
+(address_literal) @constant
+
+[
+  (byte_string_literal)
+  (hex_string_literal)
+] @string
+
+[
+  (line_comment)
+  (block_comment)
+] @comment
+
+((line_comment) @comment.documentation
+  (#match? @comment.documentation "^///"))
+
+; Operators and punctuation
+
+(binary_expression
+  operator: _ @operator)
+
+(assign_expression
+  operator: _ @operator)
+
+(unary_expression
+  "!" @operator)
+
+(borrow_expression
+  "&" @operator)
+
+(dereference_expression
+  "*" @operator)
+
+(ref_type
+  "&" @operator)
+
+[
+  "::"
+  "."
+  ","
+  ";"
+  ":"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(type_arguments
+  [
+    "<"
+    ">"
+  ] @punctuation.bracket)
+
+(type_parameters
+  [
+    "<"
+    ">"
+  ] @punctuation.bracket)
+
+[
+  "#"
+  "@"
+] @punctuation.special
@@ -0,0 +1,84 @@
+/// A minimal fungible coin, in the style of the Aptos framework.
+module 0xc0ffee::coin {
+    use std::signer;
+    use std::vector;
+    use aptos_framework::event::{Self, EventHandle};
+
+    friend 0xc0ffee::exchange;
+
+    /// The account has no `Balance` resource.
+    const E_NO_BALANCE: u64 = 1;
+    /// Not enough coins to complete the transfer.
+    const E_INSUFFICIENT: u64 = 2;
+    const SYMBOL: vector<u8> = b"CAFE";
+    const ADMIN_KEY: vector<u8> = x"c0ffee";
+
+    struct Coin<phantom T> has store {
+        value: u64,
+    }
+
+    struct Balance<phantom T> has key {
+        coin: Coin<T>,
+        deposits: EventHandle<DepositEvent>,
+    }
+
+    struct DepositEvent has drop, store {
+        amount: u64,
+    }
+
+    public fun value<T>(coin: &Coin<T>): u64 {
+        coin.value
+    }
+
+    public(friend) fun mint<T>(amount: u64): Coin<T> {
+        Coin<T> { value: amount }
+    }
+
+    public entry fun register<T>(account: &signer) {
+        let balance = Balance<T> {
+            coin: Coin { value: 0 },
+            deposits: event::new_event_handle<DepositEvent>(account),
+        };
+        move_to(account, balance);
+    }
+
+    public fun balance_of<T>(owner: address): u64 acquires Balance {
+        assert!(exists<Balance<T>>(owner), E_NO_BALANCE);
+        borrow_global<Balance<T>>(owner).coin.value
+    }
+
+    public fun deposit<T>(owner: address, coin: Coin<T>) acquires Balance {
+        let Coin { value } = coin;
+        let balance = borrow_global_mut<Balance<T>>(owner);
+        let stored = &mut balance.coin.value;
+        *stored = *stored + value;
+        event::emit_event(&mut balance.deposits, DepositEvent { amount: value });
+    }
+
+    public fun withdraw<T>(account: &signer, amount: u64): Coin<T> acquires Balance {
+        let owner = signer::address_of(account);
+        let balance = borrow_global_mut<Balance<T>>(owner);
+        if (balance.coin.value < amount) abort E_INSUFFICIENT;
+        balance.coin.value = balance.coin.value - amount;
+        Coin { value: amount }
+    }
+
+    public fun sum(values: &vector<u64>): u128 {
+        let (total, i) = (0u128, 0);
+        while (i < vector::length(values)) {
+            total = total + (*vector::borrow(values, i) as u128);
+            i = i + 1;
+        };
+        total
+    }
+
+    #[test(account = @0xc0ffee)]
+    fun test_mint_and_deposit(account: signer) acquires Balance {
+        register<u8>(&account);
+        let addr = signer::address_of(&account);
+        deposit(addr, mint<u8>(100));
+        assert!(balance_of<u8>(addr) == 100, 0);
+        let amounts = vector[1, 2, 3];
+        assert!(sum(&amounts) == 6, 1);
+    }
+}
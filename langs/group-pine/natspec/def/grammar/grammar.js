@@ -0,0 +1,38 @@
+/**
+ * @file NatSpec grammar for tree-sitter
+ * @license MIT
+ *
+ * Parses the text of a Solidity doc comment, `/// …` or `/** … *\/`, for
+ * its tags. The comment delimiters and leading `*`s are skipped.
+ * https://docs.soliditylang.org/en/latest/natspec-format.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'natspec',
+
+  extras: $ => [/\s/, $._delimiter],
+
+  rules: {
+    document: $ => repeat(choice($.tag, $._word)),
+
+    tag: $ => choice(
+      // @param amount The amount to send
+      seq(field('name', alias('@param', $.tag_name)), field('parameter', $.identifier)),
+      // @inheritdoc IERC20
+      seq(field('name', alias('@inheritdoc', $.tag_name)), field('contract', $.identifier)),
+      field('name', $.tag_name),
+    ),
+
+    // @notice, @dev, @custom:security-contact, ...
+    tag_name: _ => /@[a-zA-Z][a-zA-Z0-9_:\-]*/,
+
+    identifier: _ => /[a-zA-Z_$][a-zA-Z0-9_$]*/,
+
+    _word: _ => /[^\s@][^\s]*/,
+
+    _delimiter: _ => token(choice('///', '/**', '*/', '*')),
+  },
+});
@@ -0,0 +1,7 @@
+(tag_name) @keyword
+
+(tag
+  parameter: (identifier) @variable.parameter)
+
+(tag
+  contract: (identifier) @type)
@@ -0,0 +1,175 @@
+/**
+ * @file Prisma schema grammar for tree-sitter
+ * @license MIT
+ *
+ * The Prisma Schema Language: `datasource` and `generator` blocks of
+ * `key = value` settings, and `model`, `view`, `type` and `enum` blocks
+ * whose fields carry a type (`String?`, `Post[]`) and `@attributes`.
+ * Block-level attributes are written `@@index([...])`.
+ * https://www.prisma.io/docs/orm/reference/prisma-schema-reference
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const IDENTIFIER = /[A-Za-z_][A-Za-z0-9_]*/;
+
+module.exports = grammar({
+  name: 'prisma',
+
+  extras: $ => [
+    /\s/,
+    $.comment,
+  ],
+
+  rules: {
+    schema: $ => repeat(choice(
+      $.config_block,
+      $.model_block,
+      $.enum_block,
+    )),
+
+    // datasource db { ... }, generator client { ... }
+    config_block: $ => seq(
+      field('kind', choice('datasource', 'generator')),
+      field('name', $.identifier),
+      '{',
+      repeat($.assignment),
+      '}',
+    ),
+
+    assignment: $ => seq(
+      field('key', $.identifier),
+      '=',
+      field('value', $._expression),
+    ),
+
+    model_block: $ => seq(
+      field('kind', choice('model', 'view', 'type')),
+      field('name', $.identifier),
+      '{',
+      repeat(choice($.field_declaration, $.block_attribute)),
+      '}',
+    ),
+
+    field_declaration: $ => seq(
+      field('name', $.identifier),
+      field('type', $.field_type),
+      repeat($.attribute),
+    ),
+
+    // String, Int?, Post[], Unsupported("circle")
+    field_type: $ => seq(
+      choice(
+        $.identifier,
+        $.call_expression,
+      ),
+      optional(choice(
+        alias(token.immediate('?'), $.optional),
+        alias(token.immediate('[]'), $.list),
+      )),
+    ),
+
+    enum_block: $ => seq(
+      'enum',
+      field('name', $.identifier),
+      '{',
+      repeat(choice($.enum_value, $.block_attribute)),
+      '}',
+    ),
+
+    enum_value: $ => seq(
+      field('name', $.identifier),
+      repeat($.attribute),
+    ),
+
+    // @id, @default(now()), @db.VarChar(255)
+    attribute: $ => seq(
+      '@',
+      field('name', $._attribute_name),
+      optional(field('arguments', $.arguments)),
+    ),
+
+    block_attribute: $ => seq(
+      '@@',
+      field('name', $._attribute_name),
+      optional(field('arguments', $.arguments)),
+    ),
+
+    _attribute_name: $ => alias(
+      token.immediate(seq(IDENTIFIER, optional(seq('.', IDENTIFIER)))),
+      $.identifier,
+    ),
+
+    arguments: $ => seq(
+      '(',
+      optional(seq(
+        $._argument,
+        repeat(seq(',', $._argument)),
+        optional(','),
+      )),
+      ')',
+    ),
+
+    _argument: $ => choice(
+      $.named_argument,
+      $._expression,
+    ),
+
+    named_argument: $ => seq(
+      field('name', $.identifier),
+      ':',
+      field('value', $._expression),
+    ),
+
+    _expression: $ => choice(
+      $.call_expression,
+      $.array,
+      $.string,
+      $.number,
+      $.true,
+      $.false,
+      $.null,
+      $.identifier,
+    ),
+
+    // env("DATABASE_URL"), now(), name(sort: Desc)
+    call_expression: $ => seq(
+      field('function', $.identifier),
+      field('arguments', $.arguments),
+    ),
+
+    array: $ => seq(
+      '[',
+      optional(seq(
+        $._expression,
+        repeat(seq(',', $._expression)),
+        optional(','),
+      )),
+      ']',
+    ),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        alias(token.immediate(prec(1, /[^"\\\r\n]+/)), $.string_content),
+        $.escape_sequence,
+      )),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: _ => token.immediate(/\\(u[0-9a-fA-F]{4}|[^u\r\n])/),
+
+    number: _ => /-?[0-9]+(\.[0-9]+)?/,
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    null: _ => 'null',
+
+    identifier: _ => IDENTIFIER,
+
+    comment: _ => token(seq('//', /.*/)),
+  },
+});
@@ -0,0 +1,118 @@
+; Blocks
+
+[
+  "datasource"
+  "generator"
+  "model"
+  "view"
+  "type"
+  "enum"
+] @keyword
+
+[
+  (model_block
+    name: (identifier) @type.definition)
+  (enum_block
+    name: (identifier) @type.definition)
+]
+
+(config_block
+  name: (identifier) @variable)
+
+(assignment
+  key: (identifier) @property)
+
+; Fields
+
+(field_declaration
+  name: (identifier) @property)
+
+(field_type
+  (identifier) @type)
+
+((field_type
+  (identifier) @type.builtin)
+  (#any-of? @type.builtin
+    "String" "Boolean" "Int" "BigInt" "Float" "Decimal" "DateTime" "Json" "Bytes"))
+
+[
+  (optional)
+  (list)
+] @operator
+
+(enum_value
+  name: (identifier) @constant)
+
+; Attributes
+
+(attribute
+  "@" @attribute
+  name: (identifier) @attribute)
+
+(block_attribute
+  "@@" @attribute
+  name: (identifier) @attribute)
+
+; Arguments
+
+(call_expression
+  function: (identifier) @function)
+
+((call_expression
+  function: (identifier) @function.builtin)
+  (#any-of? @function.builtin
+    "env" "now" "autoincrement" "uuid" "cuid" "ulid" "nanoid" "dbgenerated" "sequence" "auto"))
+
+; Unsupported("circle")
+(field_type
+  (call_expression
+    function: (identifier) @type.builtin))
+
+(named_argument
+  name: (identifier) @variable.parameter)
+
+; @default(USER), onDelete: Cascade
+(arguments
+  (identifier) @constant)
+
+(named_argument
+  value: (identifier) @constant)
+
+; Field lists, as in @@index([email]) and @relation(fields: [authorId])
+(array
+  (identifier) @variable.member)
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(number) @number
+
+[
+  (true)
+  (false)
+  (null)
+] @constant.builtin
+
+; Punctuation
+
+"=" @operator
+
+[
+  ":"
+  ","
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(comment) @comment
+
+((comment) @comment.documentation
+  (#match? @comment.documentation "^///"))
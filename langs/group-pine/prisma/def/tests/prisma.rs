@@ -0,0 +1,78 @@
+//! Prisma models, enums and config blocks; attribute arguments and config
+//! values share one expression rule.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/blog.prisma");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_prisma::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_prisma::language(), source, kind)
+}
+
+fn highlight(source: &str) -> String {
+    let grammars = [(
+        "prisma",
+        TestGrammar::new(
+            arborium_prisma::language(),
+            arborium_prisma::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    helpers::highlighter(grammars)
+        .highlight("prisma", source)
+        .unwrap()
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_fields_and_attributes() {
+    let source = "model Post {\n  id     Int     @id @default(autoincrement())\n  tags   Tag[]\n  type   String? @db.VarChar(20)\n  @@index([id(sort: Desc)])\n}\n";
+    assert_eq!(nodes(source, "field_declaration").len(), 3);
+    assert_eq!(nodes(source, "field_type"), ["Int", "Tag[]", "String?"]);
+    assert_eq!(nodes(source, "list"), ["[]"]);
+    assert_eq!(nodes(source, "optional"), ["?"]);
+    assert_eq!(
+        nodes(source, "attribute"),
+        ["@id", "@default(autoincrement())", "@db.VarChar(20)"]
+    );
+    assert_eq!(
+        nodes(source, "block_attribute"),
+        ["@@index([id(sort: Desc)])"]
+    );
+    assert_eq!(nodes(source, "named_argument"), ["sort: Desc"]);
+}
+
+#[test]
+fn test_config_blocks() {
+    let source =
+        "datasource db {\n  provider = \"sqlite\"\n  url      = env(\"DATABASE_URL\")\n}\n";
+    assert_eq!(nodes(source, "assignment").len(), 2);
+    assert_eq!(nodes(source, "call_expression"), ["env(\"DATABASE_URL\")"]);
+}
+
+#[test]
+fn test_highlights() {
+    let html = highlight(SAMPLE);
+    assert!(html.contains("<a-k>model</a-k>"), "{}", html);
+    assert!(html.contains("<a-t>User</a-t>"), "{}", html);
+    assert!(html.contains("<a-pr>createdAt</a-pr>"), "{}", html);
+    assert!(html.contains("<a-t>DateTime</a-t>"), "{}", html);
+    assert!(html.contains("<a-at>@default</a-at>"), "{}", html);
+    assert!(html.contains("<a-at>@@map</a-at>"), "{}", html);
+    assert!(html.contains("<a-f>autoincrement</a-f>"), "{}", html);
+    assert!(html.contains("<a-f>env</a-f>"), "{}", html);
+    assert!(html.contains("<a-co>Cascade</a-co>"), "{}", html);
+    assert!(html.contains("<a-co>READER</a-co>"), "{}", html);
+    assert!(html.contains("<a-pr>provider</a-pr>"), "{}", html);
+}
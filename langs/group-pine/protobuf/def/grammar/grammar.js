@@ -0,0 +1,355 @@
+/**
+ * @file Protocol Buffers grammar for tree-sitter
+ * @license MIT
+ *
+ * Covers proto2, proto3 and editions schema files.
+ * https://protobuf.dev/reference/protobuf/proto3-spec/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PRIMITIVE_TYPES = [
+  'double',
+  'float',
+  'int32',
+  'int64',
+  'uint32',
+  'uint64',
+  'sint32',
+  'sint64',
+  'fixed32',
+  'fixed64',
+  'sfixed32',
+  'sfixed64',
+  'bool',
+  'string',
+  'bytes',
+];
+
+const KEY_TYPES = PRIMITIVE_TYPES.filter(
+  (type) => !['double', 'float', 'bytes'].includes(type),
+);
+
+module.exports = grammar({
+  name: 'protobuf',
+
+  extras: $ => [$.comment, /\s/],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source_file: $ => seq(
+      optional(choice($.syntax, $.edition)),
+      repeat(choice(
+        $.import,
+        $.package,
+        $.option,
+        $.enum,
+        $.message,
+        $.extend,
+        $.service,
+        $.empty_statement,
+      )),
+    ),
+
+    empty_statement: _ => ';',
+
+    syntax: $ => seq('syntax', '=', field('version', $.string), ';'),
+
+    edition: $ => seq('edition', '=', field('version', $.string), ';'),
+
+    import: $ => seq(
+      'import',
+      optional(field('modifier', choice('weak', 'public'))),
+      field('path', $.string),
+      ';',
+    ),
+
+    package: $ => seq('package', field('name', $.full_ident), ';'),
+
+    // option java_package = "com.example";
+    // option (my_extension).field = true;
+    option: $ => seq('option', $._option_assignment, ';'),
+
+    _option_assignment: $ => seq(
+      field('name', $.option_name),
+      '=',
+      field('value', $.constant),
+    ),
+
+    option_name: $ => seq(
+      choice($.identifier, seq('(', $.full_ident, ')')),
+      repeat(seq('.', choice($.identifier, seq('(', $.full_ident, ')')))),
+    ),
+
+    field_options: $ => seq(
+      '[',
+      commaSep1(alias($._option_assignment, $.field_option)),
+      ']',
+    ),
+
+    // Messages
+
+    message: $ => seq('message', field('name', $.message_name), $.message_body),
+
+    message_name: $ => $.identifier,
+
+    message_body: $ => seq(
+      '{',
+      repeat(choice(
+        $.field,
+        $.enum,
+        $.message,
+        $.extend,
+        $.extensions,
+        $.option,
+        $.oneof,
+        $.map_field,
+        $.group,
+        $.reserved,
+        $.empty_statement,
+      )),
+      '}',
+    ),
+
+    field: $ => seq(
+      optional(field('label', choice('repeated', 'optional', 'required'))),
+      field('type', $.type),
+      field('name', $.identifier),
+      '=',
+      field('number', $.int_lit),
+      optional($.field_options),
+      ';',
+    ),
+
+    // proto2 only, but still found in the wild
+    group: $ => seq(
+      optional(field('label', choice('repeated', 'optional', 'required'))),
+      'group',
+      field('name', $.identifier),
+      '=',
+      field('number', $.int_lit),
+      optional($.field_options),
+      $.message_body,
+    ),
+
+    oneof: $ => seq(
+      'oneof',
+      field('name', $.identifier),
+      '{',
+      repeat(choice($.option, $.oneof_field, $.empty_statement)),
+      '}',
+    ),
+
+    oneof_field: $ => seq(
+      field('type', $.type),
+      field('name', $.identifier),
+      '=',
+      field('number', $.int_lit),
+      optional($.field_options),
+      ';',
+    ),
+
+    map_field: $ => seq(
+      'map',
+      '<',
+      field('key', $.key_type),
+      ',',
+      field('value', $.type),
+      '>',
+      field('name', $.identifier),
+      '=',
+      field('number', $.int_lit),
+      optional($.field_options),
+      ';',
+    ),
+
+    key_type: _ => choice(...KEY_TYPES),
+
+    type: $ => choice(...PRIMITIVE_TYPES, $.message_or_enum_type),
+
+    message_or_enum_type: $ => seq(
+      optional('.'),
+      repeat(seq($.identifier, '.')),
+      $.identifier,
+    ),
+
+    extensions: $ => seq('extensions', $.ranges, optional($.field_options), ';'),
+
+    reserved: $ => seq('reserved', choice($.ranges, $.field_names), ';'),
+
+    ranges: $ => commaSep1($.range),
+
+    range: $ => seq(
+      field('start', $.int_lit),
+      optional(seq('to', field('end', choice($.int_lit, 'max')))),
+    ),
+
+    // Strings in proto2/proto3, bare identifiers in editions
+    field_names: $ => choice(commaSep1($.string), commaSep1($.identifier)),
+
+    // Enums
+
+    enum: $ => seq('enum', field('name', $.enum_name), $.enum_body),
+
+    enum_name: $ => $.identifier,
+
+    enum_body: $ => seq(
+      '{',
+      repeat(choice($.option, $.enum_field, $.reserved, $.empty_statement)),
+      '}',
+    ),
+
+    enum_field: $ => seq(
+      field('name', $.identifier),
+      '=',
+      optional('-'),
+      field('number', $.int_lit),
+      optional($.field_options),
+      ';',
+    ),
+
+    extend: $ => seq(
+      'extend',
+      field('extendee', $.message_or_enum_type),
+      '{',
+      repeat(choice($.field, $.group, $.empty_statement)),
+      '}',
+    ),
+
+    // Services
+
+    service: $ => seq(
+      'service',
+      field('name', $.service_name),
+      '{',
+      repeat(choice($.option, $.rpc, $.empty_statement)),
+      '}',
+    ),
+
+    service_name: $ => $.identifier,
+
+    rpc: $ => seq(
+      'rpc',
+      field('name', $.rpc_name),
+      '(',
+      optional('stream'),
+      field('request', $.message_or_enum_type),
+      ')',
+      'returns',
+      '(',
+      optional('stream'),
+      field('response', $.message_or_enum_type),
+      ')',
+      choice(
+        seq('{', repeat(choice($.option, $.empty_statement)), '}'),
+        ';',
+      ),
+    ),
+
+    rpc_name: $ => $.identifier,
+
+    // Constants
+
+    constant: $ => choice(
+      $.full_ident,
+      seq(optional(choice('-', '+')), $.int_lit),
+      seq(optional(choice('-', '+')), $.float_lit),
+      repeat1($.string),
+      $.true,
+      $.false,
+      $.block_lit,
+    ),
+
+    // Aggregate option values use the text format:
+    // option (http) = { get: "/v1/books/{id}" };
+    block_lit: $ => seq(
+      '{',
+      repeat(seq(
+        field('name', choice($.identifier, seq('[', $.full_ident, ']'))),
+        optional(':'),
+        field('value', choice($.constant, $.block_list)),
+        optional(choice(',', ';')),
+      )),
+      '}',
+    ),
+
+    block_list: $ => seq('[', commaSep($.constant), ']'),
+
+    full_ident: $ => seq($.identifier, repeat(seq('.', $.identifier))),
+
+    identifier: _ => /[A-Za-z_][A-Za-z0-9_]*/,
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    int_lit: _ => token(choice(
+      /[1-9][0-9]*/,
+      /0[0-7]*/,
+      /0[xX][0-9a-fA-F]+/,
+    )),
+
+    float_lit: _ => token(choice(
+      /[0-9]+\.[0-9]*([eE][+-]?[0-9]+)?/,
+      /[0-9]+[eE][+-]?[0-9]+/,
+      /\.[0-9]+([eE][+-]?[0-9]+)?/,
+    )),
+
+    string: $ => choice(
+      seq(
+        '"',
+        repeat(choice(
+          token.immediate(prec(1, /[^"\\\n]+/)),
+          $.escape_sequence,
+        )),
+        '"',
+      ),
+      seq(
+        '\'',
+        repeat(choice(
+          token.immediate(prec(1, /[^'\\\n]+/)),
+          $.escape_sequence,
+        )),
+        '\'',
+      ),
+    ),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /[xX][0-9a-fA-F]{1,2}/,
+        /[0-7]{1,3}/,
+        /u[0-9a-fA-F]{4}/,
+        /U[0-9a-fA-F]{8}/,
+        /[abfnrtv\\'"?]/,
+      ),
+    )),
+
+    comment: _ => token(choice(
+      seq('//', /.*/),
+      seq('/*', /[^*]*\*+([^/*][^*]*\*+)*/, '/'),
+    )),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
+
+/**
+ * Zero or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {ChoiceRule}
+ */
+function commaSep(rule) {
+  return optional(commaSep1(rule));
+}
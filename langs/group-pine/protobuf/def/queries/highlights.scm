@@ -0,0 +1,151 @@
+; Keywords
+
+[
+  "syntax"
+  "edition"
+  "package"
+  "option"
+  "message"
+  "enum"
+  "service"
+  "rpc"
+  "returns"
+  "stream"
+  "oneof"
+  "map"
+  "group"
+  "extend"
+  "extensions"
+  "reserved"
+  "to"
+] @keyword
+
+"import" @keyword.import
+
+[
+  "weak"
+  "public"
+  "repeated"
+  "optional"
+  "required"
+] @keyword.modifier
+
+"max" @constant.builtin
+
+; Types
+
+[
+  "double"
+  "float"
+  "int32"
+  "int64"
+  "uint32"
+  "uint64"
+  "sint32"
+  "sint64"
+  "fixed32"
+  "fixed64"
+  "sfixed32"
+  "sfixed64"
+  "bool"
+  "string"
+  "bytes"
+] @type.builtin
+
+(message_or_enum_type
+  (identifier) @type)
+
+[
+  (message_name)
+  (enum_name)
+  (service_name)
+] @type.definition
+
+; Messages and fields
+
+(field
+  name: (identifier) @property)
+
+(group
+  name: (identifier) @type.definition)
+
+(oneof
+  name: (identifier) @property)
+
+(oneof_field
+  name: (identifier) @property)
+
+(map_field
+  name: (identifier) @property)
+
+(field_names
+  (identifier) @property)
+
+(enum_field
+  name: (identifier) @constant)
+
+; Services
+
+(rpc_name) @function
+
+; Options
+
+(option_name
+  (identifier) @attribute)
+
+(option_name
+  (full_ident
+    (identifier) @attribute))
+
+(block_lit
+  name: (identifier) @property)
+
+(package
+  name: (full_ident) @module)
+
+; Literals
+
+(string) @string
+
+(import
+  path: (string) @string.special.path)
+
+(escape_sequence) @string.escape
+
+[
+  (int_lit)
+  (float_lit)
+] @number
+
+[
+  (true)
+  (false)
+] @boolean
+
+(comment) @comment
+
+; Punctuation
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+  "<"
+  ">"
+] @punctuation.bracket
+
+[
+  ";"
+  ","
+  "."
+  ":"
+] @punctuation.delimiter
+
+[
+  "="
+  "-"
+  "+"
+] @operator
@@ -281,7 +281,7 @@
   close_tag: (jsx_closing_element ["<" "/" ">"] @tag.delimiter))
 (jsx_self_closing_element ["/" ">" "<"] @tag.delimiter)
 (jsx_fragment [">" "<" "/"] @tag.delimiter)
-(jsx_attribute (property_identifier) @tag.attribute)
+(jsx_attribute (property_identifier) @attribute)
 
 ; Error
 ;----------
@@ -1 +1,98 @@
-404: Not Found
\ No newline at end of file
+// A small todo list component: variants, pattern matching and JSX.
+
+type filter = All | Active | Completed
+
+type todo = {
+  id: int,
+  title: string,
+  completed: bool,
+}
+
+type action =
+  | Add(string)
+  | Toggle(int)
+  | Remove(int)
+  | SetFilter(filter)
+
+type state = {
+  todos: array<todo>,
+  filter: filter,
+  nextId: int,
+}
+
+let reducer = (state, action) =>
+  switch action {
+  | Add(title) if String.trim(title) == "" => state
+  | Add(title) => {
+      ...state,
+      todos: state.todos->Array.concat([{id: state.nextId, title, completed: false}]),
+      nextId: state.nextId + 1,
+    }
+  | Toggle(id) => {
+      ...state,
+      todos: state.todos->Array.map(todo =>
+        todo.id == id ? {...todo, completed: !todo.completed} : todo
+      ),
+    }
+  | Remove(id) => {...state, todos: state.todos->Array.filter(todo => todo.id != id)}
+  | SetFilter(filter) => {...state, filter}
+  }
+
+let visible = (todos, filter) =>
+  todos->Array.filter(todo =>
+    switch (filter, todo.completed) {
+    | (All, _) => true
+    | (Active, completed) => !completed
+    | (Completed, completed) => completed
+    }
+  )
+
+module TodoItem = {
+  @react.component
+  let make = (~todo: todo, ~onToggle, ~onRemove) =>
+    <li className={todo.completed ? "done" : ""}>
+      <input type_="checkbox" checked={todo.completed} onChange={_ => onToggle(todo.id)} />
+      <span> {React.string(todo.title)} </span>
+      <button onClick={_ => onRemove(todo.id)}> {React.string("x")} </button>
+    </li>
+}
+
+@react.component
+let make = () => {
+  let (state, dispatch) = React.useReducer(reducer, {todos: [], filter: All, nextId: 1})
+  let (draft, setDraft) = React.useState(() => "")
+
+  let onSubmit = event => {
+    ReactEvent.Form.preventDefault(event)
+    dispatch(Add(draft))
+    setDraft(_ => "")
+  }
+
+  let remaining = state.todos->Array.filter(todo => !todo.completed)->Array.length
+
+  <>
+    <form onSubmit>
+      <input
+        value=draft
+        placeholder="What needs doing?"
+        onChange={event => setDraft(_ => ReactEvent.Form.target(event)["value"])}
+      />
+    </form>
+    <ul>
+      {visible(state.todos, state.filter)
+      ->Array.map(todo =>
+        <TodoItem
+          key={Int.toString(todo.id)}
+          todo
+          onToggle={id => dispatch(Toggle(id))}
+          onRemove={id => dispatch(Remove(id))}
+        />
+      )
+      ->React.array}
+    </ul>
+    <footer>
+      {React.string(`${Int.toString(remaining)} left`)}
+      <button onClick={_ => dispatch(SetFilter(Active))}> {React.string("Active")} </button>
+    </footer>
+  </>
+}
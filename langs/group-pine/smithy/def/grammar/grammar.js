@@ -0,0 +1,271 @@
+/**
+ * @file Smithy IDL grammar for tree-sitter
+ * @license MIT
+ *
+ * Smithy 2.0's interface definition language: a control section
+ * (`$version: "2"`), metadata, then a namespace with `use` statements and
+ * shapes. Traits (`@http(method: "GET")`) and trait values are JSON-like
+ * node values in which a bare shape ID stands for a string. Commas are
+ * whitespace.
+ * https://smithy.io/2.0/spec/idl.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const IDENTIFIER = /[A-Za-z_][A-Za-z0-9_]*/;
+
+module.exports = grammar({
+  name: 'smithy',
+
+  extras: $ => [
+    /\s/,
+    ',',
+    $.comment,
+  ],
+
+  rules: {
+    source_file: $ => repeat(choice(
+      $.control_statement,
+      $.metadata_statement,
+      $.namespace_statement,
+      $.use_statement,
+      $.apply_statement,
+      $._shape_statement,
+    )),
+
+    // $version: "2"
+    control_statement: $ => seq(
+      '$',
+      field('name', alias($._immediate_identifier, $.identifier)),
+      ':',
+      field('value', $._node_value),
+    ),
+
+    metadata_statement: $ => seq(
+      'metadata',
+      field('key', $._node_key),
+      '=',
+      field('value', $._node_value),
+    ),
+
+    namespace_statement: $ => seq(
+      'namespace',
+      field('name', $.namespace),
+    ),
+
+    use_statement: $ => seq(
+      'use',
+      field('name', $.shape_id),
+    ),
+
+    // apply MyString @documentation("...")
+    apply_statement: $ => seq(
+      'apply',
+      field('target', $.shape_id),
+      choice(
+        $.trait,
+        seq('{', repeat($.trait), '}'),
+      ),
+    ),
+
+    _shape_statement: $ => choice(
+      $.simple_shape,
+      $.enum_shape,
+      $.aggregate_shape,
+      $.entity_shape,
+      $.operation_shape,
+    ),
+
+    simple_shape: $ => seq(
+      repeat($.trait),
+      field('type', choice(
+        'blob', 'boolean', 'document', 'string', 'byte', 'short', 'integer',
+        'long', 'float', 'double', 'bigInteger', 'bigDecimal', 'timestamp',
+      )),
+      field('name', $.identifier),
+      optional($.mixins),
+    ),
+
+    enum_shape: $ => seq(
+      repeat($.trait),
+      field('type', choice('enum', 'intEnum')),
+      field('name', $.identifier),
+      optional($.mixins),
+      '{',
+      repeat($.enum_member),
+      '}',
+    ),
+
+    enum_member: $ => seq(
+      repeat($.trait),
+      field('name', $.identifier),
+      optional(seq('=', field('value', $._node_value))),
+    ),
+
+    aggregate_shape: $ => seq(
+      repeat($.trait),
+      field('type', choice('list', 'map', 'structure', 'union')),
+      field('name', $.identifier),
+      optional($.for_resource),
+      optional($.mixins),
+      field('body', $.shape_members),
+    ),
+
+    // Services and resources describe themselves with a node object
+    entity_shape: $ => seq(
+      repeat($.trait),
+      field('type', choice('service', 'resource')),
+      field('name', $.identifier),
+      optional($.mixins),
+      field('body', $.node_object),
+    ),
+
+    operation_shape: $ => seq(
+      repeat($.trait),
+      field('type', 'operation'),
+      field('name', $.identifier),
+      optional($.mixins),
+      field('body', $.operation_body),
+    ),
+
+    operation_body: $ => seq(
+      '{',
+      repeat(choice($.operation_property, $.inline_structure)),
+      '}',
+    ),
+
+    operation_property: $ => seq(
+      field('name', $.identifier),
+      ':',
+      field('value', $._node_value),
+    ),
+
+    // input := { ... }
+    inline_structure: $ => seq(
+      field('name', $.identifier),
+      ':=',
+      repeat($.trait),
+      optional($.for_resource),
+      optional($.mixins),
+      field('body', $.shape_members),
+    ),
+
+    for_resource: $ => seq('for', $.shape_id),
+
+    mixins: $ => seq('with', '[', repeat($.shape_id), ']'),
+
+    shape_members: $ => seq(
+      '{',
+      repeat(choice($.member, $.elided_member)),
+      '}',
+    ),
+
+    member: $ => seq(
+      repeat($.trait),
+      field('name', $.identifier),
+      ':',
+      field('type', $.shape_id),
+      optional(seq('=', field('default', $._node_value))),
+    ),
+
+    // $id, taking its target from the resource or mixin
+    elided_member: $ => seq(
+      repeat($.trait),
+      '$',
+      field('name', alias($._immediate_identifier, $.identifier)),
+    ),
+
+    trait: $ => seq(
+      '@',
+      field('name', $.shape_id),
+      optional($.trait_body),
+    ),
+
+    trait_body: $ => seq(
+      token.immediate('('),
+      optional(choice(
+        repeat1($.node_object_member),
+        $._node_value,
+      )),
+      ')',
+    ),
+
+    _node_value: $ => choice(
+      $.node_array,
+      $.node_object,
+      $.number,
+      $.string,
+      $.text_block,
+      $.true,
+      $.false,
+      $.null,
+      $.shape_id,
+    ),
+
+    node_array: $ => seq('[', repeat($._node_value), ']'),
+
+    node_object: $ => seq('{', repeat($.node_object_member), '}'),
+
+    node_object_member: $ => seq(
+      field('key', $._node_key),
+      ':',
+      field('value', $._node_value),
+    ),
+
+    // Keys are lexed as shape IDs, which a bare value in the same place
+    // could also be
+    _node_key: $ => choice(
+      $.string,
+      alias($.shape_id, $.identifier),
+    ),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        alias(token.immediate(prec(1, /[^"\\]+/)), $.string_content),
+        $.escape_sequence,
+      )),
+      token.immediate('"'),
+    ),
+
+    // Lone and doubled quotes are content; three end the block
+    text_block: $ => seq(
+      '"""',
+      repeat(choice(
+        alias(token.immediate(prec(1, /[^"\\]+/)), $.string_content),
+        alias(token.immediate(/""?/), $.string_content),
+        $.escape_sequence,
+      )),
+      token.immediate('"""'),
+    ),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(/u[0-9a-fA-F]{4}/, /\r?\n/, /[^u\r\n]/),
+    )),
+
+    number: _ => /-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?/,
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    null: _ => 'null',
+
+    namespace: _ => token(seq(IDENTIFIER, repeat(seq('.', IDENTIFIER)))),
+
+    // smithy.api#String, Foo, Foo$bar
+    shape_id: _ => token(seq(
+      optional(seq(IDENTIFIER, repeat(seq('.', IDENTIFIER)), '#')),
+      IDENTIFIER,
+      optional(seq('$', IDENTIFIER)),
+    )),
+
+    identifier: _ => IDENTIFIER,
+
+    _immediate_identifier: _ => token.immediate(IDENTIFIER),
+
+    comment: _ => token(seq('//', /.*/)),
+  },
+});
@@ -0,0 +1,124 @@
+; Statements
+
+[
+  "metadata"
+  "namespace"
+  "use"
+  "apply"
+  "for"
+  "with"
+] @keyword
+
+(control_statement
+  "$" @keyword.directive
+  name: (identifier) @keyword.directive)
+
+; Shapes
+
+[
+  "blob"
+  "boolean"
+  "document"
+  "string"
+  "byte"
+  "short"
+  "integer"
+  "long"
+  "float"
+  "double"
+  "bigInteger"
+  "bigDecimal"
+  "timestamp"
+  "enum"
+  "intEnum"
+  "list"
+  "map"
+  "structure"
+  "union"
+  "service"
+  "resource"
+  "operation"
+] @keyword.type
+
+(_
+  type: _
+  name: (identifier) @type.definition)
+
+(namespace) @module
+
+(shape_id) @type
+
+((shape_id) @type.builtin
+  (#any-of? @type.builtin
+    "Blob" "Boolean" "Document" "String" "Byte" "Short" "Integer" "Long"
+    "Float" "Double" "BigInteger" "BigDecimal" "Timestamp" "Unit"
+    "PrimitiveBoolean" "PrimitiveByte" "PrimitiveShort" "PrimitiveInteger"
+    "PrimitiveLong" "PrimitiveFloat" "PrimitiveDouble"))
+
+; Members
+
+(member
+  name: (identifier) @property)
+
+(elided_member
+  "$" @property
+  name: (identifier) @property)
+
+(inline_structure
+  name: (identifier) @property)
+
+(operation_property
+  name: (identifier) @property)
+
+(enum_member
+  name: (identifier) @constant)
+
+; Traits
+
+(trait
+  "@" @attribute
+  name: (shape_id) @attribute)
+
+; Node values
+
+(string) @string
+
+(text_block) @string
+
+(escape_sequence) @string.escape
+
+(number) @number
+
+[
+  (true)
+  (false)
+  (null)
+] @constant.builtin
+
+(node_object_member
+  key: (_) @property)
+
+; Punctuation
+
+[
+  "="
+  ":="
+] @operator
+
+":" @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+; Comments
+
+(comment) @comment
+
+((comment) @comment.documentation
+  (#match? @comment.documentation "^///"))
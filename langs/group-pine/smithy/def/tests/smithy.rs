@@ -0,0 +1,82 @@
+//! Smithy shapes, members and traits, with trait values parsed as node
+//! values whose bare shape IDs are strings.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const SAMPLE: &str = include_str!("../samples/weather.smithy");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_smithy::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_smithy::language(), source, kind)
+}
+
+fn highlight(source: &str) -> String {
+    let grammars = [(
+        "smithy",
+        TestGrammar::new(
+            arborium_smithy::language(),
+            arborium_smithy::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    helpers::highlighter(grammars)
+        .highlight("smithy", source)
+        .unwrap()
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = parse(SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_shapes_members_and_traits() {
+    let source = "namespace a.b\n\n@http(method: \"GET\", uri: \"/\")\noperation Get {\n    input := {\n        @required\n        id: smithy.api#String = \"x\"\n    }\n    errors: [Missing]\n}\n";
+    assert_eq!(nodes(source, "trait").len(), 2);
+    assert_eq!(
+        nodes(source, "shape_id"),
+        ["http", "required", "smithy.api#String", "Missing"]
+    );
+    assert_eq!(
+        nodes(source, "identifier"),
+        ["method", "uri", "Get", "input", "id", "errors"]
+    );
+    assert_eq!(nodes(source, "inline_structure").len(), 1);
+
+    let source = "structure Foo for Bar with [Baz] {\n    $id\n    list: Ids\n}\n";
+    assert_eq!(nodes(source, "elided_member"), ["$id"]);
+    assert_eq!(nodes(source, "member"), ["list: Ids"]);
+}
+
+#[test]
+fn test_text_blocks() {
+    let source =
+        "@documentation(\"\"\"\n    Say \"hi\" twice: \"\"hi\"\"\n    \"\"\")\nstring Greeting\n";
+    assert_eq!(nodes(source, "text_block").len(), 1);
+    assert_eq!(nodes(source, "simple_shape").len(), 1);
+}
+
+#[test]
+fn test_highlights() {
+    let html = highlight(SAMPLE);
+    assert!(html.contains("<a-k>$version</a-k>"), "{}", html);
+    assert!(html.contains("<a-ns>example.weather</a-ns>"), "{}", html);
+    assert!(html.contains("<a-k>structure</a-k>"), "{}", html);
+    assert!(html.contains("<a-t>CityCoordinates</a-t>"), "{}", html);
+    assert!(html.contains("<a-at>@http</a-at>"), "{}", html);
+    assert!(html.contains("<a-pr>method</a-pr>"), "{}", html);
+    assert!(html.contains("<a-pr>latitude</a-pr>"), "{}", html);
+    assert!(html.contains("<a-pr>$cityId</a-pr>"), "{}", html);
+    assert!(html.contains("<a-co>CLEAR</a-co>"), "{}", html);
+    assert!(
+        html.contains("<a-c>/// Provides weather forecasts.</a-c>"),
+        "{}",
+        html
+    );
+}
@@ -31,6 +31,10 @@
 
 (comment) @comment
 
+; NatSpec doc comments
+((comment) @comment.documentation
+  (#match? @comment.documentation "^(///|/\\*\\*)"))
+
 ; Definitions and references
 ; -----------
 (type_name) @type
@@ -0,0 +1,4 @@
+; NatSpec tags in doc comments
+((comment) @injection.content
+  (#match? @injection.content "^(///|/\\*\\*)")
+  (#set! injection.language "natspec"))
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title A minimal ERC-20 token
+/// @author Example Labs
+/// @notice Fixed-supply token with allowances and an owner-controlled pause.
+interface IERC20 {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    function totalSupply() external view returns (uint256);
+    function balanceOf(address account) external view returns (uint256);
+    function transfer(address to, uint256 value) external returns (bool);
+    function allowance(address owner, address spender) external view returns (uint256);
+    function approve(address spender, uint256 value) external returns (bool);
+    function transferFrom(address from, address to, uint256 value) external returns (bool);
+}
+
+library SafeCast {
+    error Overflow(uint256 value);
+
+    function toUint96(uint256 value) internal pure returns (uint96) {
+        if (value > type(uint96).max) revert Overflow(value);
+        return uint96(value);
+    }
+}
+
+contract Token is IERC20 {
+    using SafeCast for uint256;
+
+    string public constant name = "Example";
+    string public constant symbol = "EXM";
+    uint8 public constant decimals = 18;
+
+    uint256 public immutable override totalSupply;
+    address public owner;
+    bool public paused;
+
+    mapping(address => uint256) private _balances;
+    mapping(address => mapping(address => uint256)) private _allowances;
+
+    error InsufficientBalance(address account, uint256 needed, uint256 available);
+    error Paused();
+
+    event PauseChanged(bool paused);
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "Token: caller is not the owner");
+        _;
+    }
+
+    modifier whenNotPaused() {
+        if (paused) revert Paused();
+        _;
+    }
+
+    /// @param supply Number of whole tokens minted to the deployer
+    constructor(uint256 supply) {
+        owner = msg.sender;
+        totalSupply = supply * 10 ** decimals;
+        _balances[msg.sender] = totalSupply;
+        emit Transfer(address(0), msg.sender, totalSupply);
+    }
+
+    function balanceOf(address account) external view returns (uint256) {
+        return _balances[account];
+    }
+
+    /**
+     * @notice Moves `value` tokens from the caller to `to`.
+     * @dev Emits a {Transfer} event.
+     * @param to Recipient of the tokens
+     * @param value Amount in the smallest unit
+     * @return Always true; failures revert
+     */
+    function transfer(address to, uint256 value) external whenNotPaused returns (bool) {
+        _transfer(msg.sender, to, value);
+        return true;
+    }
+
+    function allowance(address holder, address spender) external view returns (uint256) {
+        return _allowances[holder][spender];
+    }
+
+    /// @inheritdoc IERC20
+    function approve(address spender, uint256 value) external returns (bool) {
+        _allowances[msg.sender][spender] = value;
+        emit Approval(msg.sender, spender, value);
+        return true;
+    }
+
+    function transferFrom(address from, address to, uint256 value)
+        external
+        whenNotPaused
+        returns (bool)
+    {
+        uint256 allowed = _allowances[from][msg.sender];
+        if (allowed != type(uint256).max) {
+            require(allowed >= value, "Token: insufficient allowance");
+            unchecked {
+                _allowances[from][msg.sender] = allowed - value;
+            }
+        }
+        _transfer(from, to, value);
+        return true;
+    }
+
+    function setPaused(bool value) external onlyOwner {
+        paused = value;
+        emit PauseChanged(value);
+    }
+
+    function _transfer(address from, address to, uint256 value) private {
+        uint256 available = _balances[from];
+        if (available < value) {
+            revert InsufficientBalance({account: from, needed: value, available: available});
+        }
+        unchecked {
+            _balances[from] = available - value;
+            _balances[to] += value;
+        }
+        emit Transfer(from, to, value);
+    }
+}
@@ -0,0 +1,54 @@
+//! Solidity doc comments (`///` and `/** */`) inject NatSpec; ordinary
+//! comments are left alone.
+
+use arborium_test_harness::helpers;
+
+const SOLIDITY: &str = "\
+contract Vault {
+    // not documentation
+    uint256 private total;
+
+    /// @notice Deposits `amount` tokens.
+    /// @param amount The amount to deposit.
+    function deposit(uint256 amount) external {
+        /* also not documentation */
+        total += amount;
+    }
+
+    /**
+     * @return The current total.
+     */
+    function balance() external view returns (uint256) {
+        return total;
+    }
+}
+";
+
+#[test]
+fn test_doc_comments_inject_natspec() {
+    let result = helpers::plugin_parse(
+        arborium_solidity::language(),
+        helpers::queries!(arborium_solidity),
+        SOLIDITY,
+    );
+
+    let injected = helpers::injected_as(&result, SOLIDITY, "natspec");
+
+    assert!(
+        injected.iter().any(|text| text.contains("@param amount")),
+        "{:?}",
+        injected
+    );
+    assert!(
+        injected.iter().any(|text| text.contains("@return")),
+        "{:?}",
+        injected
+    );
+    assert!(
+        injected
+            .iter()
+            .all(|text| !text.contains("not documentation")),
+        "{:?}",
+        injected
+    );
+}
@@ -0,0 +1,341 @@
+/**
+ * @file Crystal grammar for tree-sitter, extending Ruby
+ * @license MIT
+ *
+ * Adds type restrictions and declarations, return types, `struct`, `lib`,
+ * `fun`, `enum` and `annotation` definitions, `@[Annotations]`, `of`
+ * literals and the `{{ }}` / `{% %}` macro language. Character literals
+ * (`'a'`) are parsed as Ruby's single-quoted strings.
+ * https://crystal-lang.org/reference/latest/syntax_and_semantics/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const RUBY = require('tree-sitter-ruby/grammar');
+
+// The Ruby grammar's precedences that these rules need to fit between
+const PREC = {
+  ASSIGN: 15,
+  BITWISE_OR: 50,
+  CALL: 56,
+};
+
+module.exports = grammar(RUBY, {
+  name: 'crystal',
+
+  rules: {
+    _statement: ($, original) => choice(
+      original,
+      $.type_declaration,
+      $.annotation,
+      $.abstract_method,
+      $.fun_declaration,
+      $.type_alias,
+      $.macro_control,
+    ),
+
+    _primary: ($, original) => choice(
+      original,
+      $.struct,
+      $.lib,
+      $.enum,
+      $.annotation_definition,
+      $.macro,
+      $.macro_expression,
+    ),
+
+    _argument: ($, original) => choice(
+      original,
+      $.type_declaration,
+      $.short_block_argument,
+    ),
+
+    // names.map(&.upcase)
+    short_block_argument: $ => seq(
+      '&.',
+      field('method', choice($.identifier, $._function_identifier, $.operator)),
+      optional(field('arguments', $.argument_list)),
+    ),
+
+    _method_name: ($, original) => choice(
+      original,
+      $.macro_expression,
+    ),
+
+    // item.{{field.id}}
+    _call: ($, original) => choice(
+      original,
+      prec.left(PREC.CALL, seq(
+        field('receiver', $._primary),
+        field('operator', $._call_operator),
+        field('method', $.macro_expression),
+      )),
+    ),
+
+    // Definitions
+
+    _method_rest: $ => seq(
+      field('name', $._method_name),
+      optional(field('parameters', alias($.parameters, $.method_parameters))),
+      optional(seq(':', field('return_type', $._type))),
+      optional(seq('forall', commaSep1($.constant))),
+      optional($._terminator),
+      optional(field('body', $.body_statement)),
+      'end',
+    ),
+
+    abstract_method: $ => seq(
+      'abstract',
+      'def',
+      field('name', $._method_name),
+      optional(field('parameters', alias($.parameters, $.method_parameters))),
+      optional(seq(':', field('return_type', $._type))),
+    ),
+
+    parameters: $ => seq(
+      '(',
+      optional(seq(
+        commaSep1(choice($._formal_parameter, $.typed_parameter, $.instance_variable)),
+        optional(','),
+      )),
+      ')',
+    ),
+
+    // x : Int32, @name : String = "", *args : Int32, &block : String -> Nil
+    typed_parameter: $ => prec(PREC.BITWISE_OR + 1, seq(
+      optional(choice('*', '**', '&')),
+      field('name', choice($.identifier, $.instance_variable)),
+      ':',
+      field('type', $._type),
+      optional(seq('=', field('value', $._arg))),
+    )),
+
+    class: $ => seq(
+      optional('abstract'),
+      'class',
+      field('name', $._definition_name),
+      choice(
+        seq(field('superclass', $.superclass), $._terminator),
+        optional($._terminator),
+      ),
+      optional(field('body', $.body_statement)),
+      'end',
+    ),
+
+    struct: $ => seq(
+      optional('abstract'),
+      'struct',
+      field('name', $._definition_name),
+      choice(
+        seq(field('superclass', $.superclass), $._terminator),
+        optional($._terminator),
+      ),
+      optional(field('body', $.body_statement)),
+      'end',
+    ),
+
+    module: $ => seq(
+      'module',
+      field('name', $._definition_name),
+      optional($._terminator),
+      optional(field('body', $.body_statement)),
+      'end',
+    ),
+
+    // Box(T), Container::Pair(K, V)
+    _definition_name: $ => choice(
+      $.constant,
+      $.scope_resolution,
+      alias($._generic_definition_name, $.generic_type),
+    ),
+
+    _generic_definition_name: $ => seq(
+      field('name', choice($.constant, $.scope_resolution)),
+      token.immediate('('),
+      commaSep1(seq(optional('*'), $.constant)),
+      ')',
+    ),
+
+    enum: $ => seq(
+      'enum',
+      field('name', choice($.constant, $.scope_resolution)),
+      optional(seq(':', field('type', $._type))),
+      optional($._terminator),
+      optional(field('body', $.body_statement)),
+      'end',
+    ),
+
+    annotation_definition: $ => seq(
+      'annotation',
+      field('name', choice($.constant, $.scope_resolution)),
+      optional($._terminator),
+      'end',
+    ),
+
+    // @[Link("z")], @[JSON::Field(key: "id")]
+    annotation: $ => seq(
+      '@[',
+      field('name', choice($.constant, alias($._type_scope, $.scope_resolution))),
+      optional(field('arguments', $.argument_list)),
+      ']',
+    ),
+
+    lib: $ => seq(
+      'lib',
+      field('name', $.constant),
+      optional($._terminator),
+      optional(field('body', $.body_statement)),
+      'end',
+    ),
+
+    // fun compress = compress2(dest : UInt8*, len : ULong*) : Int32
+    fun_declaration: $ => seq(
+      'fun',
+      field('name', $.identifier),
+      optional(seq('=', field('real_name', choice($.identifier, $.string)))),
+      optional(field('parameters', alias($.parameters, $.method_parameters))),
+      optional(seq(':', field('return_type', $._type))),
+    ),
+
+    type_alias: $ => seq(
+      'alias',
+      field('name', $.constant),
+      '=',
+      field('type', $._type),
+    ),
+
+    // @name : String, property age : Int32 = 0
+    type_declaration: $ => prec.right(PREC.ASSIGN, seq(
+      field('name', choice(
+        $.identifier,
+        $.instance_variable,
+        $.class_variable,
+        $.global_variable,
+      )),
+      ':',
+      field('type', $._type),
+      optional(seq('=', field('value', $._arg))),
+    )),
+
+    rescue: ($, original) => choice(
+      original,
+      seq(
+        'rescue',
+        field('variable', alias($.identifier, $.exception_variable)),
+        ':',
+        field('exceptions', alias($._type, $.exceptions)),
+        choice($._terminator, field('body', $.then)),
+      ),
+    ),
+
+    // Types
+
+    _type: $ => choice(
+      $._simple_type,
+      $.union_type,
+      $.proc_type,
+    ),
+
+    _simple_type: $ => choice(
+      $.constant,
+      alias($._type_scope, $.scope_resolution),
+      $.generic_type,
+      $.nilable_type,
+      $.pointer_type,
+      $.tuple_type,
+      $.self,
+      seq('(', $._type, ')'),
+    ),
+
+    _type_scope: $ => seq(
+      optional(field('scope', choice($.constant, alias($._type_scope, $.scope_resolution)))),
+      '::',
+      field('name', $.constant),
+    ),
+
+    // Array(Int32), Hash(String, Array(Int32)), StaticArray(UInt8, 16)
+    generic_type: $ => seq(
+      field('name', choice($.constant, alias($._type_scope, $.scope_resolution))),
+      token.immediate('('),
+      commaSep1(choice($._type, $.integer)),
+      ')',
+    ),
+
+    nilable_type: $ => seq($._simple_type, token.immediate('?')),
+
+    pointer_type: $ => seq($._simple_type, token.immediate('*')),
+
+    tuple_type: $ => seq('{', commaSep1($._type), '}'),
+
+    union_type: $ => seq($._simple_type, repeat1(seq('|', $._simple_type))),
+
+    // String -> Nil, -> Int32
+    proc_type: $ => prec.right(seq(
+      optional($._simple_type),
+      '->',
+      optional($._simple_type),
+    )),
+
+    // `[] of Int32` and `{} of String => Int32`
+    array: ($, original) => prec.right(seq(
+      original,
+      optional(seq('of', field('type', $._type))),
+    )),
+
+    hash: ($, original) => prec.right(seq(
+      original,
+      optional(seq('of', field('key_type', $._type), '=>', field('value_type', $._type))),
+    )),
+
+    // Literals
+
+    integer: (_, original) => token(seq(original, optional(/_?[iu](8|16|32|64|128)/))),
+
+    float: (_, original) => token(seq(original, optional(/_?f(32|64)/))),
+
+    // Macros
+
+    macro: $ => seq(
+      'macro',
+      field('name', $._method_name),
+      optional(field('parameters', alias($.parameters, $.method_parameters))),
+      optional($._terminator),
+      optional(field('body', $.body_statement)),
+      'end',
+    ),
+
+    // {{ name.id }}
+    macro_expression: $ => seq('{{', $._arg, '}}'),
+
+    // {% if flag?(:linux) %}, {% for name in names %}, {% end %}
+    macro_control: $ => seq(
+      '{%',
+      optional(field('keyword', choice(
+        'if',
+        'unless',
+        'elsif',
+        'else',
+        'for',
+        'begin',
+        'end',
+        'verbatim',
+      ))),
+      optional(seq(
+        commaSep1($._arg),
+        optional(seq('in', $._arg)),
+      )),
+      '%}',
+    ),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
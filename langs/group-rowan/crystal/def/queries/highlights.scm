@@ -0,0 +1,136 @@
+; Crystal additions on top of the Ruby highlights
+
+[
+  "abstract"
+  "annotation"
+  "enum"
+  "forall"
+  "fun"
+  "lib"
+  "macro"
+  "of"
+  "struct"
+] @keyword
+
+((identifier) @keyword
+  (#any-of? @keyword "uninitialized" "pointerof" "sizeof" "instance_sizeof" "offsetof" "typeof" "out" "select"))
+
+((identifier) @function.macro
+  (#any-of? @function.macro
+    "getter" "getter?" "getter!" "setter" "property" "property?" "property!"
+    "record" "delegate" "def_equals" "def_hash" "def_equals_and_hash" "spawn"))
+
+; Definitions
+
+(struct
+  name: (constant) @type)
+
+(class
+  name: (constant) @type)
+
+(enum
+  name: (constant) @type)
+
+(lib
+  name: (constant) @module)
+
+(annotation_definition
+  name: (constant) @type)
+
+(generic_type
+  name: (constant) @type)
+
+(type_alias
+  name: (constant) @type.definition)
+
+(macro
+  name: (identifier) @function.macro)
+
+(fun_declaration
+  name: (identifier) @function)
+
+(abstract_method
+  name: (identifier) @function.method)
+
+; Types
+
+(typed_parameter
+  name: (identifier) @variable.parameter)
+
+(type_declaration
+  name: (identifier) @variable)
+
+(typed_parameter
+  type: (constant) @type)
+
+(type_declaration
+  type: (constant) @type)
+
+(method
+  return_type: (constant) @type)
+
+(abstract_method
+  return_type: (constant) @type)
+
+(fun_declaration
+  return_type: (constant) @type)
+
+(generic_type
+  (constant) @type)
+
+(nilable_type
+  (constant) @type)
+
+(pointer_type
+  (constant) @type)
+
+(union_type
+  (constant) @type)
+
+(tuple_type
+  (constant) @type)
+
+(proc_type
+  (constant) @type)
+
+(nilable_type
+  "?" @operator)
+
+(pointer_type
+  "*" @operator)
+
+(union_type
+  "|" @operator)
+
+(proc_type
+  "->" @operator)
+
+; Annotations
+
+(annotation
+  "@[" @attribute
+  name: (_) @attribute
+  "]" @attribute)
+
+; Macros
+
+(macro_expression
+  "{{" @punctuation.special
+  "}}" @punctuation.special)
+
+(macro_control
+  "{%" @punctuation.special
+  "%}" @punctuation.special)
+
+(macro_control
+  keyword: _ @keyword)
+
+(short_block_argument
+  "&." @operator
+  method: (identifier) @function.method)
+
+(typed_parameter
+  ":" @punctuation.delimiter)
+
+(type_declaration
+  ":" @punctuation.delimiter)
@@ -0,0 +1,858 @@
+/**
+ * @file Fortran grammar for tree-sitter
+ * @license MIT
+ *
+ * Free-form Fortran 90 through 2018: programs, modules, procedures,
+ * interfaces and derived types. Keywords are case-insensitive and may be
+ * written `end do` or `enddo`. Lines are continued with a trailing `&`.
+ * Fixed-form sources parse on a best-effort basis only.
+ * https://j3-fortran.org/doc/year/18/18-007r1.pdf
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  eqv: 1,
+  or: 2,
+  and: 3,
+  not: 4,
+  comparison: 5,
+  concat: 6,
+  additive: 7,
+  multiplicative: 8,
+  unary: 9,
+  power: 10,
+  call: 11,
+};
+
+const INTRINSIC_TYPES = ['integer', 'real', 'complex', 'character', 'logical'];
+
+const ATTRIBUTES = [
+  'allocatable',
+  'asynchronous',
+  'contiguous',
+  'external',
+  'intrinsic',
+  'optional',
+  'parameter',
+  'pointer',
+  'private',
+  'protected',
+  'public',
+  'save',
+  'target',
+  'value',
+  'volatile',
+];
+
+module.exports = grammar({
+  name: 'fortran',
+
+  extras: $ => [
+    /[ \t\f\r]/,
+    // A trailing & continues the statement on the next line, which may
+    // itself start with an &.
+    /&[ \t]*(![^\n]*)?\r?\n([ \t]*&)?/,
+    $.comment,
+  ],
+
+  word: $ => $.identifier,
+
+  rules: {
+    translation_unit: $ => repeat(choice(
+      $.program,
+      $.module,
+      $.submodule,
+      $.subroutine,
+      $.function,
+      $.preproc_directive,
+      $._end_of_statement,
+    )),
+
+    _end_of_statement: _ => choice(';', /\n/),
+
+    preproc_directive: _ => token(seq('#', /[^\n]*/)),
+
+    // Program units
+
+    program: $ => seq(
+      kw('program'),
+      field('name', $._name),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+      optional($.internal_procedures),
+      endOf($, 'program'),
+    ),
+
+    module: $ => seq(
+      kw('module'),
+      field('name', $._name),
+      $._end_of_statement,
+      repeat($._specification),
+      optional($.internal_procedures),
+      endOf($, 'module'),
+    ),
+
+    // submodule (parent) child
+    submodule: $ => seq(
+      kw('submodule'),
+      '(',
+      field('parent', $._name),
+      optional(seq(':', $._name)),
+      ')',
+      field('name', $._name),
+      $._end_of_statement,
+      repeat($._specification),
+      optional($.internal_procedures),
+      endOf($, 'submodule'),
+    ),
+
+    internal_procedures: $ => seq(
+      kw('contains'),
+      $._end_of_statement,
+      repeat(choice(
+        $.subroutine,
+        $.function,
+        $.module_procedure,
+        $.preproc_directive,
+        $._end_of_statement,
+      )),
+    ),
+
+    subroutine: $ => seq(
+      optional($._procedure_prefix),
+      kw('subroutine'),
+      field('name', $._name),
+      optional(field('parameters', $.parameters)),
+      optional($.language_binding),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+      optional($.internal_procedures),
+      endOf($, 'subroutine'),
+    ),
+
+    function: $ => seq(
+      optional($._procedure_prefix),
+      kw('function'),
+      field('name', $._name),
+      field('parameters', $.parameters),
+      repeat(choice(
+        seq(kw('result'), '(', field('result', $.identifier), ')'),
+        $.language_binding,
+      )),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+      optional($.internal_procedures),
+      endOf($, 'function'),
+    ),
+
+    // module procedure name ... end procedure, inside a submodule
+    module_procedure: $ => seq(
+      kw('module'),
+      kw('procedure'),
+      field('name', $._name),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+      optional($.internal_procedures),
+      endOf($, 'procedure'),
+    ),
+
+    // pure elemental real(dp) function ...
+    _procedure_prefix: $ => repeat1(choice($.procedure_qualifier, $._type_specifier)),
+
+    procedure_qualifier: _ => choice(
+      kw('pure'),
+      kw('impure'),
+      kw('elemental'),
+      kw('recursive'),
+      kw('non_recursive'),
+      kw('module'),
+    ),
+
+    parameters: $ => seq('(', optional(commaSep1($.identifier)), ')'),
+
+    // bind(c, name="c_name")
+    language_binding: $ => seq(
+      kw('bind'),
+      '(',
+      $.identifier,
+      optional(seq(',', $.keyword_argument)),
+      ')',
+    ),
+
+    // Specifications
+
+    _specification_or_statement: $ => choice(
+      $._specification,
+      $._statement,
+    ),
+
+    _specification: $ => choice(
+      $.use_statement,
+      $.import_statement,
+      $.implicit_statement,
+      $.variable_declaration,
+      $.derived_type_definition,
+      $.interface,
+      $.access_statement,
+      $.include_statement,
+      $.preproc_directive,
+      $._end_of_statement,
+    ),
+
+    // use, intrinsic :: iso_fortran_env, only: dp => real64
+    use_statement: $ => seq(
+      kw('use'),
+      optional(seq(',', choice(kw('intrinsic'), kw('non_intrinsic')), '::')),
+      field('module', $._name),
+      optional(seq(
+        ',',
+        choice(
+          seq(kw('only'), ':', optional(commaSep1($._use_item))),
+          commaSep1($.use_rename),
+        ),
+      )),
+      $._end_of_statement,
+    ),
+
+    _use_item: $ => choice(
+      $.identifier,
+      $.use_rename,
+      $.operator_name,
+    ),
+
+    use_rename: $ => seq(
+      field('local', $.identifier),
+      '=>',
+      field('remote', $.identifier),
+    ),
+
+    // import :: shape, dp, inside interface bodies
+    import_statement: $ => seq(
+      kw('import'),
+      optional(seq(optional('::'), commaSep1($.identifier))),
+      $._end_of_statement,
+    ),
+
+    // implicit none, implicit none (type, external)
+    implicit_statement: $ => seq(
+      kw('implicit'),
+      kw('none'),
+      optional(seq('(', optional(commaSep1(choice(kw('type'), kw('external')))), ')')),
+      $._end_of_statement,
+    ),
+
+    access_statement: $ => seq(
+      choice(kw('public'), kw('private')),
+      optional(seq(optional('::'), commaSep1(choice($.identifier, $.operator_name)))),
+      $._end_of_statement,
+    ),
+
+    include_statement: $ => seq(
+      kw('include'),
+      field('path', $.string_literal),
+      $._end_of_statement,
+    ),
+
+    // real(dp), allocatable, intent(in) :: x(:), y = 1.0
+    variable_declaration: $ => seq(
+      $._type_specifier,
+      repeat(seq(',', $.type_attribute)),
+      optional('::'),
+      commaSep1($._declarator),
+      $._end_of_statement,
+    ),
+
+    _type_specifier: $ => choice(
+      $.intrinsic_type,
+      $.derived_type,
+    ),
+
+    intrinsic_type: $ => seq(
+      choice(
+        ...INTRINSIC_TYPES.map(kw),
+        seq(kw('double'), kw('precision')),
+        kw('doubleprecision'),
+      ),
+      optional(field('kind', $.kind_selector)),
+    ),
+
+    // (dp), (kind=int64), (len=*), *8
+    kind_selector: $ => choice(
+      seq('(', commaSep1(choice($._expression, $.keyword_argument, '*', ':')), ')'),
+      seq('*', choice($.integer_literal, seq('(', '*', ')'))),
+    ),
+
+    // type(point), class(shape), class(*)
+    derived_type: $ => seq(
+      choice(kw('type'), kw('class')),
+      '(',
+      choice($.identifier, '*', $.intrinsic_type),
+      ')',
+    ),
+
+    type_attribute: $ => choice(
+      ...ATTRIBUTES.map(kw),
+      seq(kw('dimension'), $.argument_list),
+      seq(kw('intent'), '(', choice(kw('in'), kw('out'), kw('inout'), seq(kw('in'), kw('out'))), ')'),
+      $.language_binding,
+      seq(kw('codimension'), '[', commaSep1(choice($._expression, '*', ':')), ']'),
+    ),
+
+    _declarator: $ => choice(
+      field('declarator', $.identifier),
+      $.sized_declarator,
+      $.init_declarator,
+    ),
+
+    // x(:, :), name*20
+    sized_declarator: $ => seq(
+      field('declarator', $.identifier),
+      choice(
+        $.argument_list,
+        seq('*', choice($.integer_literal, seq('(', choice($._expression, '*'), ')'))),
+      ),
+    ),
+
+    init_declarator: $ => seq(
+      choice(field('declarator', $.identifier), $.sized_declarator),
+      choice('=', '=>'),
+      field('value', $._expression),
+    ),
+
+    // type, extends(shape), public :: circle ... end type circle
+    derived_type_definition: $ => seq(
+      kw('type'),
+      optional(seq(
+        repeat1(seq(',', choice(
+          kw('public'),
+          kw('private'),
+          kw('abstract'),
+          seq(kw('extends'), '(', field('parent', $.identifier), ')'),
+          $.language_binding,
+        ))),
+        '::',
+      )),
+      optional('::'),
+      field('name', $._name),
+      optional($.parameters),
+      $._end_of_statement,
+      repeat(choice(
+        $.variable_declaration,
+        $.access_statement,
+        seq(kw('sequence'), $._end_of_statement),
+        $.preproc_directive,
+        $._end_of_statement,
+      )),
+      optional($.type_bound_procedures),
+      endOf($, 'type'),
+    ),
+
+    type_bound_procedures: $ => seq(
+      kw('contains'),
+      $._end_of_statement,
+      repeat(choice(
+        $.procedure_binding,
+        $.access_statement,
+        $._end_of_statement,
+      )),
+    ),
+
+    // procedure, pass(self) :: area => circle_area
+    // generic :: operator(+) => add
+    procedure_binding: $ => seq(
+      choice(kw('procedure'), kw('generic'), kw('final')),
+      optional(seq('(', $.identifier, ')')),
+      repeat(seq(',', choice(
+        kw('public'),
+        kw('private'),
+        kw('deferred'),
+        kw('non_overridable'),
+        kw('nopass'),
+        seq(kw('pass'), optional(seq('(', $.identifier, ')'))),
+      ))),
+      optional('::'),
+      commaSep1(seq(
+        field('name', choice($.identifier, $.operator_name)),
+        optional(seq('=>', commaSep1(field('implementation', $.identifier)))),
+      )),
+      $._end_of_statement,
+    ),
+
+    // interface, interface name, interface operator(+), abstract interface
+    interface: $ => seq(
+      optional(kw('abstract')),
+      kw('interface'),
+      optional(field('name', choice($._name, $.operator_name))),
+      $._end_of_statement,
+      repeat(choice(
+        $.subroutine,
+        $.function,
+        seq(optional(kw('module')), kw('procedure'), optional('::'), commaSep1($.identifier), $._end_of_statement),
+        $.preproc_directive,
+        $._end_of_statement,
+      )),
+      endOf($, 'interface'),
+    ),
+
+    // operator(.cross.), assignment(=)
+    operator_name: $ => seq(
+      choice(kw('operator'), kw('assignment')),
+      '(',
+      choice($.user_operator, /[^)\s]+/),
+      ')',
+    ),
+
+    // Statements
+
+    _statement: $ => seq(
+      optional(field('label', $.statement_label)),
+      choice(
+        $.assignment_statement,
+        $.pointer_assignment_statement,
+        $.call_statement,
+        $.if_statement,
+        $.do_loop,
+        $.select_case_statement,
+        $.select_type_statement,
+        $.block_construct,
+        $.associate_statement,
+        $.where_statement,
+        $.print_statement,
+        $.io_statement,
+        $.allocate_statement,
+        $.return_statement,
+        $.stop_statement,
+        $.exit_statement,
+        $.cycle_statement,
+        $.continue_statement,
+        $.goto_statement,
+      ),
+    ),
+
+    statement_label: _ => token(prec(1, /\d{1,5}/)),
+
+    // Constructs can be named: `outer: do i = 1, n`
+    _construct_name: $ => seq(field('label', alias($.identifier, $.block_label)), ':'),
+
+    assignment_statement: $ => seq(
+      field('left', $._expression),
+      '=',
+      field('right', $._expression),
+      $._end_of_statement,
+    ),
+
+    pointer_assignment_statement: $ => seq(
+      field('left', $._expression),
+      '=>',
+      field('right', $._expression),
+      $._end_of_statement,
+    ),
+
+    call_statement: $ => seq(
+      kw('call'),
+      field('subroutine', $._expression),
+      $._end_of_statement,
+    ),
+
+    if_statement: $ => choice(
+      // if (x > 0) y = 1
+      seq(
+        kw('if'),
+        field('condition', $.parenthesized_expression),
+        field('consequence', choice(
+          $.assignment_statement,
+          $.pointer_assignment_statement,
+          $.call_statement,
+          $.print_statement,
+          $.io_statement,
+          $.return_statement,
+          $.stop_statement,
+          $.exit_statement,
+          $.cycle_statement,
+          $.goto_statement,
+        )),
+      ),
+      seq(
+        optional($._construct_name),
+        kw('if'),
+        field('condition', $.parenthesized_expression),
+        kw('then'),
+        $._end_of_statement,
+        repeat($._specification_or_statement),
+        repeat($.elseif_clause),
+        optional($.else_clause),
+        endOf($, 'if'),
+      ),
+    ),
+
+    elseif_clause: $ => seq(
+      choice(seq(kw('else'), kw('if')), kw('elseif')),
+      field('condition', $.parenthesized_expression),
+      kw('then'),
+      optional($.identifier),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+    ),
+
+    else_clause: $ => seq(
+      kw('else'),
+      optional($.identifier),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+    ),
+
+    // do i = 1, n, 2 / do while (x) / do concurrent (i = 1:n) / do
+    do_loop: $ => seq(
+      optional($._construct_name),
+      kw('do'),
+      optional(choice(
+        $.loop_control,
+        seq(kw('while'), field('condition', $.parenthesized_expression)),
+        seq(kw('concurrent'), $.argument_list),
+      )),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+      endOf($, 'do'),
+    ),
+
+    loop_control: $ => seq(
+      field('variable', $.identifier),
+      '=',
+      commaSep1($._expression),
+    ),
+
+    select_case_statement: $ => seq(
+      optional($._construct_name),
+      choice(seq(kw('select'), kw('case')), kw('selectcase')),
+      field('value', $.parenthesized_expression),
+      $._end_of_statement,
+      repeat(choice($.case_clause, $._end_of_statement)),
+      endOf($, 'select'),
+    ),
+
+    // case (1, 3:5) / case default
+    case_clause: $ => seq(
+      kw('case'),
+      choice(
+        kw('default'),
+        seq('(', commaSep1(choice($._expression, $.range)), ')'),
+      ),
+      optional($.identifier),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+    ),
+
+    select_type_statement: $ => seq(
+      optional($._construct_name),
+      choice(seq(kw('select'), kw('type')), kw('selecttype')),
+      '(',
+      optional(seq(field('associate', $.identifier), '=>')),
+      field('value', $._expression),
+      ')',
+      $._end_of_statement,
+      repeat(choice($.type_guard, $._end_of_statement)),
+      endOf($, 'select'),
+    ),
+
+    // type is (integer) / class is (shape) / class default
+    type_guard: $ => seq(
+      choice(
+        seq(kw('type'), kw('is'), '(', choice($.intrinsic_type, $.identifier), ')'),
+        seq(kw('class'), kw('is'), '(', $.identifier, ')'),
+        seq(kw('class'), kw('default')),
+      ),
+      $._end_of_statement,
+      // Declarations would start with `type`/`class` too; they aren't
+      // allowed here anyway.
+      repeat(choice($._statement, $._end_of_statement)),
+    ),
+
+    block_construct: $ => seq(
+      optional($._construct_name),
+      kw('block'),
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+      endOf($, 'block'),
+    ),
+
+    // associate (r => self%radius)
+    associate_statement: $ => seq(
+      optional($._construct_name),
+      kw('associate'),
+      '(',
+      commaSep1(seq($.identifier, '=>', $._expression)),
+      ')',
+      $._end_of_statement,
+      repeat($._specification_or_statement),
+      endOf($, 'associate'),
+    ),
+
+    where_statement: $ => seq(
+      kw('where'),
+      field('mask', $.parenthesized_expression),
+      choice(
+        $.assignment_statement,
+        seq(
+          $._end_of_statement,
+          repeat(choice($.assignment_statement, $._end_of_statement)),
+          optional(seq(
+            kw('elsewhere'),
+            optional($.parenthesized_expression),
+            $._end_of_statement,
+            repeat(choice($.assignment_statement, $._end_of_statement)),
+          )),
+          endOf($, 'where'),
+        ),
+      ),
+    ),
+
+    // print *, x / print '(a, i0)', "n = ", n
+    print_statement: $ => seq(
+      kw('print'),
+      field('format', choice('*', $._expression)),
+      optional(seq(',', commaSep1($._expression))),
+      $._end_of_statement,
+    ),
+
+    // write (*, '(a)') msg / read (unit, *, iostat=ios) x / open (...)
+    io_statement: $ => seq(
+      choice(
+        kw('write'),
+        kw('read'),
+        kw('open'),
+        kw('close'),
+        kw('inquire'),
+        kw('rewind'),
+        kw('backspace'),
+        kw('flush'),
+      ),
+      $.io_control,
+      optional(commaSep1($._expression)),
+      $._end_of_statement,
+    ),
+
+    io_control: $ => seq(
+      '(',
+      commaSep1(choice($._expression, $.keyword_argument, '*')),
+      ')',
+    ),
+
+    allocate_statement: $ => seq(
+      choice(kw('allocate'), kw('deallocate'), kw('nullify')),
+      $.argument_list,
+      $._end_of_statement,
+    ),
+
+    return_statement: $ => seq(kw('return'), $._end_of_statement),
+
+    // stop, error stop "message", stop 1
+    stop_statement: $ => seq(
+      optional(kw('error')),
+      kw('stop'),
+      optional($._expression),
+      $._end_of_statement,
+    ),
+
+    exit_statement: $ => seq(kw('exit'), optional($.identifier), $._end_of_statement),
+
+    cycle_statement: $ => seq(kw('cycle'), optional($.identifier), $._end_of_statement),
+
+    continue_statement: $ => seq(kw('continue'), $._end_of_statement),
+
+    goto_statement: $ => seq(
+      choice(seq(kw('go'), kw('to')), kw('goto')),
+      $.statement_label,
+      $._end_of_statement,
+    ),
+
+    // Expressions
+
+    _expression: $ => choice(
+      $.identifier,
+      $.integer_literal,
+      $.real_literal,
+      $.complex_literal,
+      $.string_literal,
+      $.boolean_literal,
+      $.array_constructor,
+      $.parenthesized_expression,
+      $.call_expression,
+      $.derived_type_member_expression,
+      $.unary_expression,
+      $.binary_expression,
+      $.logical_expression,
+      $.comparison_expression,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    // Function calls and array references look the same: f(x), a(1:n, j)
+    call_expression: $ => prec(PREC.call, seq(
+      field('function', $._expression),
+      field('arguments', $.argument_list),
+    )),
+
+    argument_list: $ => seq(
+      '(',
+      optional(commaSep1(choice($._expression, $.keyword_argument, $.range, '*'))),
+      ')',
+    ),
+
+    keyword_argument: $ => seq(
+      field('name', $.identifier),
+      '=',
+      field('value', choice($._expression, $.range, '*')),
+    ),
+
+    // a:b, :b, a:, :, a:b:stride
+    range: $ => prec.right(seq(
+      optional(field('start', $._expression)),
+      ':',
+      optional(field('end', $._expression)),
+      optional(seq(':', field('stride', $._expression))),
+    )),
+
+    derived_type_member_expression: $ => prec(PREC.call, seq(
+      field('object', $._expression),
+      '%',
+      field('member', $.identifier),
+    )),
+
+    unary_expression: $ => choice(
+      prec(PREC.unary, seq(field('operator', choice('-', '+')), field('argument', $._expression))),
+      prec(PREC.not, seq(field('operator', kw('.not.')), field('argument', $._expression))),
+    ),
+
+    binary_expression: $ => choice(
+      prec.right(PREC.power, seq(field('left', $._expression), field('operator', '**'), field('right', $._expression))),
+      prec.left(PREC.multiplicative, seq(field('left', $._expression), field('operator', choice('*', '/')), field('right', $._expression))),
+      prec.left(PREC.additive, seq(field('left', $._expression), field('operator', choice('+', '-')), field('right', $._expression))),
+      prec.left(PREC.concat, seq(field('left', $._expression), field('operator', '//'), field('right', $._expression))),
+      prec.left(PREC.eqv, seq(field('left', $._expression), field('operator', $.user_operator), field('right', $._expression))),
+    ),
+
+    comparison_expression: $ => prec.left(PREC.comparison, seq(
+      field('left', $._expression),
+      field('operator', choice(
+        '==', '/=', '<', '<=', '>', '>=',
+        kw('.eq.'), kw('.ne.'), kw('.lt.'), kw('.le.'), kw('.gt.'), kw('.ge.'),
+      )),
+      field('right', $._expression),
+    )),
+
+    logical_expression: $ => choice(
+      prec.left(PREC.and, seq(field('left', $._expression), field('operator', kw('.and.')), field('right', $._expression))),
+      prec.left(PREC.or, seq(field('left', $._expression), field('operator', kw('.or.')), field('right', $._expression))),
+      prec.left(PREC.eqv, seq(field('left', $._expression), field('operator', choice(kw('.eqv.'), kw('.neqv.'))), field('right', $._expression))),
+    ),
+
+    // .cross., .dot.
+    user_operator: _ => /\.[a-zA-Z]+\./,
+
+    // [1, 2, 3], (/ 1, 2, 3 /), [real(dp) :: 1, 2]
+    array_constructor: $ => choice(
+      seq('[', optional(seq($._type_specifier, '::')), optional(commaSep1($._array_element)), ']'),
+      seq('(/', optional(commaSep1($._array_element)), '/)'),
+    ),
+
+    _array_element: $ => choice(
+      $._expression,
+      $.implied_do_loop,
+    ),
+
+    // (i**2, i = 1, n)
+    implied_do_loop: $ => seq(
+      '(',
+      commaSep1($._expression),
+      ',',
+      $.loop_control,
+      ')',
+    ),
+
+    complex_literal: $ => seq(
+      '(',
+      field('real', $._expression),
+      ',',
+      field('imaginary', $._expression),
+      ')',
+    ),
+
+    _name: $ => alias($.identifier, $.name),
+
+    identifier: _ => /[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    // 42, 1_int64, b'1010', z'ff'
+    integer_literal: _ => token(choice(
+      seq(/\d+/, optional(seq('_', /[a-zA-Z0-9_]+/))),
+      /[bBoOzZ]('[0-9a-fA-F]+'|"[0-9a-fA-F]+")/,
+    )),
+
+    // 1.0, .5, 1e-3, 1.0d0, 2.5_dp
+    real_literal: _ => token(seq(
+      choice(
+        seq(choice(/\d+\.\d*/, /\.\d+/), optional(/[eEdDqQ][+-]?\d+/)),
+        seq(/\d+/, /[eEdDqQ][+-]?\d+/),
+      ),
+      optional(seq('_', /[a-zA-Z0-9_]+/)),
+    )),
+
+    boolean_literal: _ => token(prec(1, seq(
+      choice(/\.[tT][rR][uU][eE]\./, /\.[fF][aA][lL][sS][eE]\./),
+      optional(seq('_', /[a-zA-Z0-9_]+/)),
+    ))),
+
+    // Quotes are escaped by doubling them.
+    string_literal: _ => token(choice(
+      seq('"', /([^"\n]|"")*/, '"'),
+      seq('\'', /([^'\n]|'')*/, '\''),
+    )),
+
+    comment: _ => token(seq('!', /[^\n]*/)),
+  },
+});
+
+/**
+ * The closing statement of a construct: `end`, `end do`, `enddo` or
+ * `end do outer`.
+ *
+ * @param {GrammarSymbols<string>} $
+ * @param {string} keyword
+ * @returns {SeqRule}
+ */
+function endOf($, keyword) {
+  return seq(
+    choice(
+      seq(kw('end'), optional(seq(kw(keyword), optional($.identifier)))),
+      seq(kw(`end${keyword}`), optional($.identifier)),
+    ),
+    $._end_of_statement,
+  );
+}
+
+/**
+ * A case-insensitive keyword, named after its lowercase spelling.
+ *
+ * @param {string} word
+ * @returns {AliasRule}
+ */
+function kw(word) {
+  const pattern = word.split('').map(ch => {
+    if (/[a-z]/.test(ch)) {
+      return `[${ch}${ch.toUpperCase()}]`;
+    }
+    return ch.replace(/[.*+?^$()|[\]\\]/g, '\\$&');
+  }).join('');
+  return alias(token(prec(1, new RegExp(pattern))), word);
+}
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
@@ -0,0 +1,310 @@
+; Identifiers
+
+((identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+; Keywords
+
+[
+  "program"
+  "module"
+  "submodule"
+  "contains"
+  "subroutine"
+  "function"
+  "procedure"
+  "result"
+  "interface"
+  "abstract"
+  "operator"
+  "assignment"
+  "generic"
+  "final"
+  "end"
+  "endprogram"
+  "endmodule"
+  "endsubmodule"
+  "endsubroutine"
+  "endfunction"
+  "endprocedure"
+  "endinterface"
+  "endtype"
+  "endif"
+  "enddo"
+  "endselect"
+  "endblock"
+  "endassociate"
+  "endwhere"
+  "implicit"
+  "none"
+  "type"
+  "class"
+  "extends"
+  "sequence"
+  "bind"
+  "dimension"
+  "codimension"
+  "intent"
+  "in"
+  "out"
+  "inout"
+  "pass"
+  "nopass"
+  "deferred"
+  "non_overridable"
+  "pure"
+  "impure"
+  "elemental"
+  "recursive"
+  "non_recursive"
+  "double"
+  "precision"
+  "doubleprecision"
+] @keyword
+
+[
+  "allocatable"
+  "asynchronous"
+  "contiguous"
+  "external"
+  "intrinsic"
+  "non_intrinsic"
+  "optional"
+  "parameter"
+  "pointer"
+  "private"
+  "protected"
+  "public"
+  "save"
+  "target"
+  "value"
+  "volatile"
+] @keyword.modifier
+
+[
+  "use"
+  "only"
+  "import"
+  "include"
+] @keyword.import
+
+[
+  "if"
+  "then"
+  "else"
+  "elseif"
+  "select"
+  "selectcase"
+  "selecttype"
+  "case"
+  "default"
+  "is"
+  "where"
+  "elsewhere"
+] @keyword.conditional
+
+[
+  "do"
+  "while"
+  "concurrent"
+  "exit"
+  "cycle"
+  "continue"
+] @keyword.repeat
+
+[
+  "return"
+  "stop"
+  "error"
+  "go"
+  "to"
+  "goto"
+] @keyword.return
+
+[
+  "call"
+  "block"
+  "associate"
+  "allocate"
+  "deallocate"
+  "nullify"
+] @keyword
+
+[
+  "print"
+  "write"
+  "read"
+  "open"
+  "close"
+  "inquire"
+  "rewind"
+  "backspace"
+  "flush"
+] @function.builtin
+
+; Types
+
+(intrinsic_type) @type.builtin
+
+(derived_type
+  (identifier) @type)
+
+(derived_type_definition
+  name: (name) @type)
+
+(derived_type_definition
+  parent: (identifier) @type)
+
+(type_guard
+  (identifier) @type)
+
+; Declarations
+
+(program
+  name: (name) @module)
+
+(module
+  name: (name) @module)
+
+(submodule
+  name: (name) @module)
+
+(submodule
+  parent: (name) @module)
+
+(use_statement
+  module: (name) @module)
+
+(subroutine
+  name: (name) @function)
+
+(function
+  name: (name) @function)
+
+(module_procedure
+  name: (name) @function)
+
+(interface
+  name: (name) @function)
+
+(procedure_binding
+  name: (identifier) @function.method)
+
+(procedure_binding
+  implementation: (identifier) @function)
+
+(parameters
+  (identifier) @variable.parameter)
+
+(function
+  result: (identifier) @variable)
+
+(keyword_argument
+  name: (identifier) @variable.parameter)
+
+(derived_type_member_expression
+  member: (identifier) @property)
+
+(block_label) @label
+
+(statement_label) @label
+
+; Calls
+
+(call_statement
+  subroutine: (identifier) @function.call)
+
+(call_statement
+  subroutine: (call_expression
+    function: (identifier) @function.call))
+
+(call_statement
+  subroutine: (derived_type_member_expression
+    member: (identifier) @function.method.call))
+
+(call_statement
+  subroutine: (call_expression
+    function: (derived_type_member_expression
+      member: (identifier) @function.method.call)))
+
+((call_expression
+  function: (identifier) @function.builtin)
+  (#any-of? @function.builtin
+    "abs" "achar" "acos" "adjustl" "adjustr" "aimag" "aint" "all" "allocated"
+    "anint" "any" "asin" "associated" "atan" "atan2" "bit_size" "ceiling"
+    "char" "cmplx" "conjg" "cos" "cosh" "count" "cpu_time" "cshift"
+    "date_and_time" "dble" "digits" "dot_product" "epsilon" "exp" "floor"
+    "huge" "iachar" "ichar" "index" "int" "kind" "lbound" "len" "len_trim"
+    "log" "log10" "matmul" "max" "maxloc" "maxval" "merge" "min" "minloc"
+    "minval" "mod" "modulo" "move_alloc" "nint" "norm2" "pack" "present"
+    "product" "random_number" "random_seed" "real" "repeat" "reshape"
+    "selected_int_kind" "selected_real_kind" "shape" "sign" "sin" "sinh"
+    "size" "spread" "sqrt" "sum" "system_clock" "tan" "tanh" "tiny"
+    "transpose" "trim" "ubound" "unpack"))
+
+; Literals
+
+[
+  (integer_literal)
+  (real_literal)
+] @number
+
+(boolean_literal) @boolean
+
+(string_literal) @string
+
+(comment) @comment
+
+(preproc_directive) @keyword.directive
+
+; Operators and punctuation
+
+(unary_expression
+  operator: _ @operator)
+
+(binary_expression
+  operator: _ @operator)
+
+(comparison_expression
+  operator: _ @operator)
+
+(logical_expression
+  operator: _ @operator)
+
+(user_operator) @operator
+
+[
+  ".not."
+  ".and."
+  ".or."
+  ".eqv."
+  ".neqv."
+  ".eq."
+  ".ne."
+  ".lt."
+  ".le."
+  ".gt."
+  ".ge."
+] @keyword.operator
+
+[
+  "="
+  "=>"
+  "%"
+] @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "(/"
+  "/)"
+] @punctuation.bracket
+
+[
+  ","
+  ":"
+  "::"
+  ";"
+] @punctuation.delimiter
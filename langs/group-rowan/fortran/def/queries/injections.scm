@@ -0,0 +1,4 @@
+; #include, #ifdef and friends are C preprocessor lines
+
+((preproc_directive) @injection.content
+  (#set! injection.language "c"))
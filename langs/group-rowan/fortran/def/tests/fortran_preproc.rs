@@ -0,0 +1,25 @@
+//! Fortran preprocessor lines inject C; the Fortran around them stays put.
+
+use arborium_test_harness::helpers;
+
+const FORTRAN: &str = "program main
+#ifdef USE_MPI
+  use mpi
+#endif
+  implicit none
+  print *, 'hello'
+end program main
+";
+
+#[test]
+fn test_preprocessor_lines_inject_c() {
+    let result = helpers::plugin_parse(
+        arborium_fortran::language(),
+        helpers::queries!(arborium_fortran),
+        FORTRAN,
+    );
+
+    let injected = helpers::injected_as(&result, FORTRAN, "c");
+
+    assert_eq!(injected, ["#ifdef USE_MPI", "#endif"]);
+}
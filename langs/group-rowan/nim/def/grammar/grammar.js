@@ -0,0 +1,729 @@
+/**
+ * @file Nim grammar for tree-sitter
+ * @license MIT
+ *
+ * Covers routines, type/var/let/const sections, objects, enums, pragmas,
+ * command-call syntax (`echo x, y`) and `fmt"..."` / `&"..."` interpolation.
+ * Operator precedence follows the first character of the operator, as in
+ * the manual. Indentation is tracked by the external scanner.
+ * https://nim-lang.org/docs/manual.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  command: -1,
+  arrow: 1,
+  or: 3,
+  and: 4,
+  comparison: 5,
+  range: 6,
+  concat: 7,
+  additive: 8,
+  multiplicative: 9,
+  power: 10,
+  unary: 11,
+  call: 12,
+};
+
+const ROUTINE_KEYWORDS = ['proc', 'func', 'method', 'iterator', 'converter', 'template', 'macro'];
+
+module.exports = grammar({
+  name: 'nim',
+
+  extras: $ => [/\s/, $.comment],
+
+  externals: $ => [
+    $._newline,
+    $._indent,
+    $._dedent,
+
+    // Comments are external so that the scanner runs on every token and can
+    // keep emitting dedents while recovering from errors.
+    $.comment,
+
+    // Lets the scanner avoid dedents between brackets.
+    ')',
+    ']',
+    '}',
+  ],
+
+  word: $ => $.identifier,
+
+  conflicts: $ => [
+    // `a -b` is a command call, `a - b` a subtraction; prefer the latter.
+    [$._primary_expression, $.command_call],
+  ],
+
+  rules: {
+    source_file: $ => repeat($._statement),
+
+    _statement: $ => choice(
+      $._simple_statements,
+      $._compound_statement,
+    ),
+
+    _simple_statements: $ => seq(
+      $._simple_statement,
+      repeat(seq(';', $._simple_statement)),
+      optional(';'),
+      $._newline,
+    ),
+
+    _simple_statement: $ => choice(
+      $.import_statement,
+      $.from_import_statement,
+      $.include_statement,
+      $.export_statement,
+      $.var_section,
+      $.let_section,
+      $.const_section,
+      $.pragma_statement,
+      $.expression_statement,
+      $.command_call,
+      $.assignment,
+      $.return_statement,
+      $.discard_statement,
+      $.yield_statement,
+      $.raise_statement,
+      $.break_statement,
+      $.continue_statement,
+    ),
+
+    _compound_statement: $ => choice(
+      $.routine_declaration,
+      $.type_section,
+      alias($._var_block, $.var_section),
+      alias($._let_block, $.let_section),
+      alias($._const_block, $.const_section),
+      $.if_statement,
+      $.when_statement,
+      $.case_statement,
+      $.while_statement,
+      $.for_statement,
+      $.try_statement,
+      $.block_statement,
+      $.defer_statement,
+      $.static_statement,
+    ),
+
+    // Modules
+
+    // import std/[strutils, tables], os except getEnv
+    import_statement: $ => seq(
+      'import',
+      commaSep1($._expression),
+      optional(seq('except', commaSep1($.identifier))),
+    ),
+
+    from_import_statement: $ => seq(
+      'from',
+      field('module', $._expression),
+      'import',
+      commaSep1($._expression),
+    ),
+
+    include_statement: $ => seq('include', commaSep1($._expression)),
+
+    export_statement: $ => seq('export', commaSep1($._expression)),
+
+    // Declarations
+
+    routine_declaration: $ => seq(
+      field('kind', choice(...ROUTINE_KEYWORDS)),
+      field('name', $._symbol),
+      optional(field('generic_parameters', $.generic_parameter_list)),
+      optional(field('parameters', $.parameter_list)),
+      optional(seq(':', field('return_type', $._type))),
+      optional(field('pragmas', $.pragma_list)),
+      choice(
+        seq('=', field('body', $.statement_list)),
+        $._newline,
+      ),
+    ),
+
+    // foo, foo*, `+`, `==`*
+    _symbol: $ => choice(
+      $.identifier,
+      $.accent_quoted,
+      $.exported_symbol,
+    ),
+
+    exported_symbol: $ => seq(
+      choice($.identifier, $.accent_quoted),
+      token.immediate('*'),
+    ),
+
+    accent_quoted: _ => token(seq('`', /[^`\n]+/, '`')),
+
+    // [T], [K, V: SomeOrdinal; U]
+    generic_parameter_list: $ => seq(
+      '[',
+      sep1($.generic_parameter, choice(',', ';')),
+      ']',
+    ),
+
+    generic_parameter: $ => seq(
+      field('name', $.identifier),
+      optional(seq(':', field('type', $._type))),
+      optional(seq('=', field('default', $._type))),
+    ),
+
+    // (a, b: int; c = 1.0)
+    parameter_list: $ => seq(
+      '(',
+      optional(seq(sep1($.parameter_declaration, choice(',', ';')), optional(choice(',', ';')))),
+      ')',
+    ),
+
+    parameter_declaration: $ => seq(
+      field('name', $._symbol),
+      optional(seq(':', field('type', $._type))),
+      optional(seq('=', field('default', $._expression))),
+    ),
+
+    // {.inline, raises: [IOError].}, {.push checks: off.}
+    pragma_list: $ => seq(
+      '{.',
+      repeat(seq($.pragma, optional(','))),
+      choice('.}', '}'),
+    ),
+
+    pragma: $ => prec.right(seq(
+      field('name', $.identifier),
+      optional(choice(
+        seq(':', field('value', $._expression)),
+        field('arguments', $.argument_list),
+      )),
+    )),
+
+    pragma_statement: $ => $.pragma_list,
+
+    var_section: $ => seq('var', $.variable_declaration),
+    let_section: $ => seq('let', $.variable_declaration),
+    const_section: $ => seq('const', $.variable_declaration),
+
+    _var_block: $ => seq('var', $._variable_block),
+    _let_block: $ => seq('let', $._variable_block),
+    _const_block: $ => seq('const', $._variable_block),
+
+    _variable_block: $ => seq(
+      $._indent,
+      repeat1(seq($.variable_declaration, $._newline)),
+      $._dedent,
+    ),
+
+    // x, y*: int = 0, (a, b) = pair
+    variable_declaration: $ => seq(
+      commaSep1(field('name', choice($.symbol_declaration, $.tuple_deconstruct))),
+      optional(seq(':', field('type', $._type))),
+      optional(seq('=', field('value', $._expression))),
+    ),
+
+    symbol_declaration: $ => seq(
+      $._symbol,
+      optional(field('pragmas', $.pragma_list)),
+    ),
+
+    tuple_deconstruct: $ => seq('(', commaSep1($.symbol_declaration), ')'),
+
+    type_section: $ => seq(
+      'type',
+      choice(
+        seq($.type_declaration, $._newline),
+        seq($._indent, repeat1(seq($.type_declaration, $._newline)), $._dedent),
+      ),
+    ),
+
+    type_declaration: $ => seq(
+      field('name', $._symbol),
+      optional(field('generic_parameters', $.generic_parameter_list)),
+      optional(field('pragmas', $.pragma_list)),
+      '=',
+      field('value', choice(
+        $.object_declaration,
+        $.enum_declaration,
+        $.distinct_type,
+        $.concept_declaration,
+        $._type,
+      )),
+    ),
+
+    // ref object of RootObj, with an indented block of fields
+    object_declaration: $ => seq(
+      optional(choice('ref', 'ptr')),
+      'object',
+      optional(seq('of', field('parent', $._type))),
+      optional(field('pragmas', $.pragma_list)),
+      optional(seq(
+        $._indent,
+        repeat1(seq($.field_declaration, $._newline)),
+        $._dedent,
+      )),
+    ),
+
+    field_declaration: $ => seq(
+      commaSep1(field('name', $.symbol_declaration)),
+      ':',
+      field('type', $._type),
+      optional(seq('=', field('default', $._expression))),
+    ),
+
+    // enum red, green, blue; or one or more fields per indented line
+    enum_declaration: $ => seq(
+      'enum',
+      choice(
+        seq(commaSep1($.enum_field), optional(',')),
+        seq(
+          $._indent,
+          repeat1(seq(commaSep1($.enum_field), optional(','), $._newline)),
+          $._dedent,
+        ),
+      ),
+    ),
+
+    enum_field: $ => seq(
+      field('name', $._symbol),
+      optional(seq('=', field('value', $._expression))),
+    ),
+
+    distinct_type: $ => seq('distinct', $._type),
+
+    // Concept bodies are ordinary statements over the placeholder names.
+    concept_declaration: $ => seq(
+      'concept',
+      optional(commaSep1($.identifier)),
+      field('body', $.statement_list),
+    ),
+
+    // Types
+
+    _type: $ => choice(
+      $.pointer_type,
+      $.proc_type,
+      $.tuple_type,
+      $._primary_expression,
+    ),
+
+    // ref T, ptr T, var T, sink T, lent T, static T
+    pointer_type: $ => prec.right(seq(
+      choice('ref', 'ptr', 'var', 'out', 'sink', 'lent', 'static'),
+      $._type,
+    )),
+
+    proc_type: $ => prec.right(seq(
+      'proc',
+      optional(field('parameters', $.parameter_list)),
+      optional(seq(':', field('return_type', $._type))),
+      optional(field('pragmas', $.pragma_list)),
+    )),
+
+    // tuple[name: string, age: int]
+    tuple_type: $ => prec.right(seq(
+      'tuple',
+      optional(seq(
+        '[',
+        sep1($.field_declaration, choice(',', ';')),
+        ']',
+      )),
+    )),
+
+    // Statements
+
+    statement_list: $ => choice(
+      $._simple_statements,
+      seq($._indent, repeat1($._statement), $._dedent),
+    ),
+
+    if_statement: $ => seq(
+      'if',
+      field('condition', $._expression),
+      ':',
+      field('consequence', $.statement_list),
+      repeat(field('alternative', $.elif_branch)),
+      optional(field('alternative', $.else_branch)),
+    ),
+
+    when_statement: $ => seq(
+      'when',
+      field('condition', $._expression),
+      ':',
+      field('consequence', $.statement_list),
+      repeat(field('alternative', $.elif_branch)),
+      optional(field('alternative', $.else_branch)),
+    ),
+
+    elif_branch: $ => seq(
+      'elif',
+      field('condition', $._expression),
+      ':',
+      field('consequence', $.statement_list),
+    ),
+
+    else_branch: $ => seq('else', ':', field('consequence', $.statement_list)),
+
+    // The `of` branches may be indented under `case` or line up with it.
+    case_statement: $ => seq(
+      'case',
+      field('value', $._expression),
+      optional(':'),
+      choice(
+        seq($._newline, repeat1($.of_branch), optional($.else_branch)),
+        seq($._indent, repeat1($.of_branch), optional($.else_branch), $._dedent),
+      ),
+    ),
+
+    of_branch: $ => seq(
+      'of',
+      commaSep1(field('value', $._expression)),
+      ':',
+      field('consequence', $.statement_list),
+    ),
+
+    while_statement: $ => seq(
+      'while',
+      field('condition', $._expression),
+      ':',
+      field('body', $.statement_list),
+    ),
+
+    for_statement: $ => seq(
+      'for',
+      commaSep1(field('left', choice($.symbol_declaration, $.tuple_deconstruct))),
+      'in',
+      field('right', $._expression),
+      ':',
+      field('body', $.statement_list),
+    ),
+
+    try_statement: $ => seq(
+      'try',
+      ':',
+      field('body', $.statement_list),
+      repeat($.except_branch),
+      optional($.finally_branch),
+    ),
+
+    // except IOError, OSError as e:
+    except_branch: $ => seq(
+      'except',
+      optional(commaSep1($._expression)),
+      ':',
+      field('body', $.statement_list),
+    ),
+
+    finally_branch: $ => seq('finally', ':', field('body', $.statement_list)),
+
+    block_statement: $ => seq(
+      'block',
+      optional(field('label', $.identifier)),
+      ':',
+      field('body', $.statement_list),
+    ),
+
+    defer_statement: $ => seq('defer', ':', field('body', $.statement_list)),
+
+    static_statement: $ => seq('static', ':', field('body', $.statement_list)),
+
+    expression_statement: $ => $._expression,
+
+    // echo "total: ", total  --  a call without parentheses
+    command_call: $ => prec.dynamic(PREC.command, seq(
+      field('function', choice($.identifier, $.dot_expression)),
+      field('arguments', alias($._command_arguments, $.argument_list)),
+    )),
+
+    _command_arguments: $ => commaSep1($._argument),
+
+    assignment: $ => seq(
+      field('left', $._expression),
+      field('operator', choice('=', '+=', '-=', '*=', '/=', '&=', '|=')),
+      field('right', $._expression),
+    ),
+
+    return_statement: $ => prec.right(seq('return', optional($._expression))),
+
+    discard_statement: $ => prec.right(seq('discard', optional($._expression))),
+
+    yield_statement: $ => prec.right(seq('yield', optional($._expression))),
+
+    raise_statement: $ => prec.right(seq('raise', optional($._expression))),
+
+    break_statement: $ => prec.right(seq('break', optional($.identifier))),
+
+    continue_statement: _ => 'continue',
+
+    // Expressions
+
+    _expression: $ => choice(
+      $._primary_expression,
+      $.unary_expression,
+      $.binary_expression,
+      $.proc_expression,
+    ),
+
+    _primary_expression: $ => choice(
+      $.identifier,
+      $.accent_quoted,
+      $.integer_literal,
+      $.float_literal,
+      $.char_literal,
+      $.string_literal,
+      $.raw_string_literal,
+      $.long_string_literal,
+      $.generalized_string,
+      $.interpolated_string,
+      $.nil_literal,
+      $.array_construction,
+      $.curly_construction,
+      $.tuple_construction,
+      $.parenthesized_expression,
+      $.dot_expression,
+      $.bracket_expression,
+      $.call,
+      $.cast_expression,
+    ),
+
+    // -x, not done, $value, @[1, 2]
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice('-', '+', 'not', '$', '@')),
+      field('argument', $._expression),
+    )),
+
+    binary_expression: $ => {
+      const table = [
+        [prec.right, PREC.arrow, choice('->', '=>')],
+        [prec.left, PREC.or, choice('or', 'xor')],
+        [prec.left, PREC.and, 'and'],
+        [prec.left, PREC.comparison, choice(
+          '==', '!=', '<', '<=', '>', '>=',
+          'in', 'notin', 'is', 'isnot', 'of', 'as',
+        )],
+        [prec.left, PREC.range, choice('..', '..<', '..^')],
+        [prec.left, PREC.concat, '&'],
+        [prec.left, PREC.additive, choice('+', '-', '|')],
+        [prec.left, PREC.multiplicative, choice('*', '/', '%', 'div', 'mod', 'shl', 'shr')],
+        [prec.right, PREC.power, '^'],
+      ];
+      return choice(...table.map(([fn, precedence, operator]) => /** @type {Function} */ (fn)(
+        precedence,
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
+        ),
+      )));
+    },
+
+    // proc (x: int): int = x * 2
+    proc_expression: $ => prec.right(seq(
+      'proc',
+      field('parameters', $.parameter_list),
+      optional(seq(':', field('return_type', $._type))),
+      optional(field('pragmas', $.pragma_list)),
+      '=',
+      field('body', $.statement_list),
+    )),
+
+    dot_expression: $ => prec(PREC.call, seq(
+      field('left', $._primary_expression),
+      '.',
+      field('right', choice($.identifier, $.accent_quoted)),
+    )),
+
+    // a[i], seq[int], Table[string, int]
+    bracket_expression: $ => prec(PREC.call, seq(
+      field('left', $._primary_expression),
+      token.immediate('['),
+      commaSep1(choice($._expression, $.pointer_type, $.proc_type, $.tuple_type)),
+      ']',
+    )),
+
+    call: $ => prec(PREC.call, seq(
+      field('function', $._primary_expression),
+      field('arguments', $.argument_list),
+    )),
+
+    argument_list: $ => seq(
+      token.immediate('('),
+      optional(seq(commaSep1($._argument), optional(','))),
+      ')',
+    ),
+
+    _argument: $ => choice(
+      $._expression,
+      $.named_argument,
+    ),
+
+    // Foo(x: 1), open(path, mode = fmRead)
+    named_argument: $ => seq(
+      field('name', $.identifier),
+      choice(':', '='),
+      field('value', $._expression),
+    ),
+
+    cast_expression: $ => seq(
+      'cast',
+      '[',
+      field('type', $._type),
+      ']',
+      '(',
+      field('value', $._expression),
+      ')',
+    ),
+
+    array_construction: $ => seq(
+      '[',
+      optional(seq(commaSep1($._expression), optional(','))),
+      ']',
+    ),
+
+    // {'a'..'z'} sets and {"key": value} table constructors
+    curly_construction: $ => seq(
+      '{',
+      optional(choice(
+        ':',
+        seq(commaSep1(choice($._expression, $.pair)), optional(',')),
+      )),
+      '}',
+    ),
+
+    pair: $ => seq(
+      field('key', $._expression),
+      ':',
+      field('value', $._expression),
+    ),
+
+    tuple_construction: $ => seq(
+      '(',
+      choice(
+        seq($._expression, ','),
+        seq($._expression, repeat1(seq(',', $._expression)), optional(',')),
+        seq(commaSep1($.named_argument), optional(',')),
+      ),
+      ')',
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    // Literals
+
+    identifier: _ => /[a-zA-Z_\u00C0-\uFFFF][a-zA-Z0-9_\u00C0-\uFFFF]*/,
+
+    nil_literal: _ => 'nil',
+
+    integer_literal: _ => token(seq(
+      choice(
+        /0[xX][0-9a-fA-F_]+/,
+        /0[bB][01_]+/,
+        /0o[0-7_]+/,
+        /\d[\d_]*/,
+      ),
+      optional(seq(optional('\''), /[iIuU](8|16|32|64)?/)),
+    )),
+
+    float_literal: _ => token(choice(
+      seq(
+        /\d[\d_]*/,
+        choice(
+          /\.\d[\d_]*([eE][+-]?\d+)?/,
+          /[eE][+-]?\d+/,
+        ),
+        optional(seq(optional('\''), /[fFdD](32|64|128)?/)),
+      ),
+      seq(/\d[\d_]*/, optional('\''), /[fFdD](32|64|128)?/),
+    )),
+
+    char_literal: _ => token(seq(
+      '\'',
+      choice(/[^'\\\n]/, /\\([^xu\n]|x[0-9a-fA-F]{2}|u[0-9a-fA-F]{4})/),
+      '\'',
+    )),
+
+    string_literal: $ => seq(
+      '"',
+      repeat(choice($.string_content, $.escape_sequence)),
+      token.immediate('"'),
+    ),
+
+    string_content: _ => token.immediate(prec(1, /[^"\\\n]+/)),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /x[0-9a-fA-F]{2}/,
+        /u[0-9a-fA-F]{4}/,
+        /u\{[0-9a-fA-F]+\}/,
+        /\d+/,
+        /[^xu\d]/,
+      ),
+    )),
+
+    // r"C:\path", where "" stands for a quote
+    raw_string_literal: _ => token(seq(/[rR]"/, /([^"\n]|"")*/, '"')),
+
+    long_string_literal: _ => token(seq('"""', /([^"]|"[^"]|""[^"])*/, '"""')),
+
+    // re"\d+", sql"""select 1""": a call with a raw string argument
+    generalized_string: $ => seq(
+      field('function', $.identifier),
+      field('argument', alias(
+        token.immediate(choice(
+          seq('"""', /([^"]|"[^"]|""[^"])*/, '"""'),
+          seq('"', /([^"\n]|"")*/, '"'),
+        )),
+        $.raw_string_literal,
+      )),
+    ),
+
+    // fmt"Hello {name}!" and &"{count:>4} items"
+    interpolated_string: $ => seq(
+      choice('fmt"', '&"'),
+      repeat(choice(
+        alias(token.immediate(prec(1, /[^"\\\n{}]+/)), $.string_content),
+        $.escape_sequence,
+        alias(token.immediate(choice('{{', '}}')), $.escape_sequence),
+        $.interpolation,
+      )),
+      token.immediate('"'),
+    ),
+
+    interpolation: $ => seq(
+      token.immediate('{'),
+      field('expression', $._expression),
+      optional(seq(
+        token.immediate(choice(':', '=')),
+        optional(field('format', alias(token.immediate(/[^}"\n]+/), $.format_specifier))),
+      )),
+      '}',
+    ),
+
+    // # line, ## doc, #[ block ]#, ##[ doc block ]##
+    comment: _ => token(choice(
+      seq('#', /.*/),
+      seq('#', optional('#'), '[', /([^\]]|\][^#])*/, ']', optional('#'), '#'),
+    )),
+  },
+});
+
+/**
+ * One or more `rule`s separated by `separator`.
+ *
+ * @param {RuleOrLiteral} rule
+ * @param {RuleOrLiteral} separator
+ * @returns {SeqRule}
+ */
+function sep1(rule, separator) {
+  return seq(rule, repeat(seq(separator, rule)));
+}
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return sep1(rule, ',');
+}
@@ -0,0 +1,186 @@
+#include "tree_sitter/array.h"
+#include "tree_sitter/parser.h"
+
+#include <stdint.h>
+#include <string.h>
+
+// Indentation tracking for Nim, derived from the Python scanner. Block comments
+// (`#[ ... ]#`, which nest) are skipped like line comments while measuring the
+// indentation of the next line.
+
+enum TokenType {
+    NEWLINE,
+    INDENT,
+    DEDENT,
+    COMMENT,
+    CLOSE_PAREN,
+    CLOSE_BRACKET,
+    CLOSE_BRACE,
+};
+
+typedef struct {
+    Array(uint16_t) indents;
+} Scanner;
+
+static inline void skip(TSLexer *lexer) { lexer->advance(lexer, true); }
+
+// Skips the rest of a block comment whose opening `#[` has been consumed up to
+// the `[`, leaving the lexer after the matching `]#`.
+static void skip_block_comment(TSLexer *lexer) {
+    unsigned depth = 0;
+    while (!lexer->eof(lexer)) {
+        if (lexer->lookahead == '#') {
+            skip(lexer);
+            if (lexer->lookahead == '[') {
+                depth++;
+                skip(lexer);
+            }
+        } else if (lexer->lookahead == ']') {
+            skip(lexer);
+            if (lexer->lookahead == '#') {
+                skip(lexer);
+                if (--depth == 0) {
+                    return;
+                }
+            }
+        } else {
+            skip(lexer);
+        }
+    }
+}
+
+bool tree_sitter_nim_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+    Scanner *scanner = (Scanner *)payload;
+
+    bool error_recovery_mode = valid_symbols[INDENT] && valid_symbols[CLOSE_PAREN] && valid_symbols[CLOSE_BRACE];
+    bool within_brackets = valid_symbols[CLOSE_BRACE] || valid_symbols[CLOSE_PAREN] || valid_symbols[CLOSE_BRACKET];
+
+    lexer->mark_end(lexer);
+
+    bool found_end_of_line = false;
+    uint16_t indent_length = 0;
+    int32_t first_comment_indent_length = -1;
+    for (;;) {
+        if (lexer->lookahead == '\n') {
+            found_end_of_line = true;
+            indent_length = 0;
+            skip(lexer);
+        } else if (lexer->lookahead == ' ') {
+            indent_length++;
+            skip(lexer);
+        } else if (lexer->lookahead == '\r' || lexer->lookahead == '\f') {
+            indent_length = 0;
+            skip(lexer);
+        } else if (lexer->lookahead == '\t') {
+            // Tabs aren't allowed in Nim code, count them like Python does
+            indent_length += 8;
+            skip(lexer);
+        } else if (lexer->lookahead == '#' &&
+                   (valid_symbols[INDENT] || valid_symbols[DEDENT] || valid_symbols[NEWLINE])) {
+            // A comment after an expression on the same line never changes
+            // the indentation.
+            if (!found_end_of_line) {
+                return false;
+            }
+            if (first_comment_indent_length == -1) {
+                first_comment_indent_length = (int32_t)indent_length;
+            }
+            skip(lexer);
+            if (lexer->lookahead == '#') {
+                skip(lexer);
+            }
+            if (lexer->lookahead == '[') {
+                skip_block_comment(lexer);
+            }
+            while (lexer->lookahead && lexer->lookahead != '\n') {
+                skip(lexer);
+            }
+            skip(lexer);
+            indent_length = 0;
+        } else if (lexer->lookahead == '\\') {
+            skip(lexer);
+            if (lexer->lookahead == '\r') {
+                skip(lexer);
+            }
+            if (lexer->lookahead == '\n' || lexer->eof(lexer)) {
+                skip(lexer);
+            } else {
+                return false;
+            }
+        } else if (lexer->eof(lexer)) {
+            indent_length = 0;
+            found_end_of_line = true;
+            break;
+        } else {
+            break;
+        }
+    }
+
+    if (found_end_of_line) {
+        if (scanner->indents.size > 0) {
+            uint16_t current_indent_length = *array_back(&scanner->indents);
+
+            if (valid_symbols[INDENT] && indent_length > current_indent_length) {
+                array_push(&scanner->indents, indent_length);
+                lexer->result_symbol = INDENT;
+                return true;
+            }
+
+            if ((valid_symbols[DEDENT] || (!valid_symbols[NEWLINE] && !within_brackets)) &&
+                indent_length < current_indent_length &&
+                // Wait to create a dedent token until we've consumed any
+                // comments whose indentation matches the current block.
+                first_comment_indent_length < (int32_t)current_indent_length) {
+                array_pop(&scanner->indents);
+                lexer->result_symbol = DEDENT;
+                return true;
+            }
+        }
+
+        if (valid_symbols[NEWLINE] && !error_recovery_mode) {
+            lexer->result_symbol = NEWLINE;
+            return true;
+        }
+    }
+
+    return false;
+}
+
+unsigned tree_sitter_nim_external_scanner_serialize(void *payload, char *buffer) {
+    Scanner *scanner = (Scanner *)payload;
+
+    size_t size = 0;
+    uint32_t iter = 1;
+    for (; iter < scanner->indents.size && size + 1 < TREE_SITTER_SERIALIZATION_BUFFER_SIZE; ++iter) {
+        uint16_t indent_value = *array_get(&scanner->indents, iter);
+        buffer[size++] = (char)(indent_value & 0xFF);
+        buffer[size++] = (char)((indent_value >> 8) & 0xFF);
+    }
+
+    return size;
+}
+
+void tree_sitter_nim_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {
+    Scanner *scanner = (Scanner *)payload;
+
+    array_delete(&scanner->indents);
+    array_push(&scanner->indents, 0);
+
+    for (size_t size = 0; size + 1 < length; size += 2) {
+        uint16_t indent_value = (unsigned char)buffer[size] | ((unsigned char)buffer[size + 1] << 8);
+        array_push(&scanner->indents, indent_value);
+    }
+}
+
+void *tree_sitter_nim_external_scanner_create() {
+    Scanner *scanner = calloc(1, sizeof(Scanner));
+    array_init(&scanner->indents);
+    tree_sitter_nim_external_scanner_deserialize(scanner, NULL, 0);
+    return scanner;
+}
+
+void tree_sitter_nim_external_scanner_destroy(void *payload) {
+    Scanner *scanner = (Scanner *)payload;
+    array_delete(&scanner->indents);
+    free(scanner);
+}
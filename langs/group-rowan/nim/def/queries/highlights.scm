@@ -0,0 +1,239 @@
+; Identifiers
+
+((identifier) @type
+  (#match? @type "^[A-Z][a-zA-Z0-9]*[a-z][a-zA-Z0-9]*$"))
+
+((identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+((identifier) @variable.builtin
+  (#eq? @variable.builtin "result"))
+
+((identifier) @boolean
+  (#any-of? @boolean "true" "false"))
+
+((identifier) @type.builtin
+  (#any-of? @type.builtin
+    "int" "int8" "int16" "int32" "int64"
+    "uint" "uint8" "uint16" "uint32" "uint64"
+    "float" "float32" "float64" "bool" "char" "string" "cstring"
+    "pointer" "byte" "void" "auto" "untyped" "typed" "typedesc"
+    "seq" "array" "openArray" "varargs" "set" "range" "Natural" "Positive"))
+
+; Keywords
+
+[
+  "and"
+  "as"
+  "block"
+  "break"
+  "case"
+  "cast"
+  "concept"
+  "const"
+  "continue"
+  "defer"
+  "discard"
+  "distinct"
+  "div"
+  "elif"
+  "else"
+  "enum"
+  "except"
+  "export"
+  "finally"
+  "for"
+  "from"
+  "if"
+  "import"
+  "in"
+  "include"
+  "is"
+  "isnot"
+  "let"
+  "lent"
+  "mod"
+  "not"
+  "notin"
+  "object"
+  "of"
+  "or"
+  "out"
+  "ptr"
+  "raise"
+  "ref"
+  "return"
+  "shl"
+  "shr"
+  "sink"
+  "static"
+  "try"
+  "tuple"
+  "type"
+  "var"
+  "when"
+  "while"
+  "xor"
+  "yield"
+  "proc"
+  "func"
+  "method"
+  "iterator"
+  "converter"
+  "template"
+  "macro"
+] @keyword
+
+(continue_statement) @keyword
+
+(nil_literal) @constant.builtin
+
+; Declarations
+
+(routine_declaration
+  name: [
+    (identifier) @function
+    (accent_quoted) @function
+    (exported_symbol [(identifier) (accent_quoted)] @function)
+  ])
+
+(type_declaration
+  name: [
+    (identifier) @type
+    (exported_symbol (identifier) @type)
+  ])
+
+(exported_symbol "*" @operator)
+
+(generic_parameter
+  name: (identifier) @type)
+
+(parameter_declaration
+  name: [
+    (identifier) @variable.parameter
+    (exported_symbol (identifier) @variable.parameter)
+  ])
+
+(field_declaration
+  name: (symbol_declaration [
+    (identifier) @property
+    (exported_symbol (identifier) @property)
+  ]))
+
+(enum_field
+  name: [
+    (identifier) @constant
+    (exported_symbol (identifier) @constant)
+  ])
+
+(named_argument
+  name: (identifier) @property)
+
+; Types
+
+(parameter_declaration
+  type: (identifier) @type)
+
+(field_declaration
+  type: (identifier) @type)
+
+(variable_declaration
+  type: (identifier) @type)
+
+(routine_declaration
+  return_type: (identifier) @type)
+
+(object_declaration
+  parent: (identifier) @type)
+
+; Calls
+
+(dot_expression
+  right: (identifier) @property)
+
+(call
+  function: (identifier) @function.call)
+
+(call
+  function: (dot_expression
+    right: (identifier) @function.method.call))
+
+(command_call
+  function: (identifier) @function.call)
+
+(command_call
+  function: (dot_expression
+    right: (identifier) @function.method.call))
+
+(generalized_string
+  function: (identifier) @function.call)
+
+; Pragmas
+
+(pragma_list
+  [
+    "{."
+    ".}"
+  ] @punctuation.special)
+
+(pragma
+  name: (identifier) @attribute)
+
+; Literals
+
+(integer_literal) @number
+
+(float_literal) @number.float
+
+(char_literal) @character
+
+[
+  (string_literal)
+  (raw_string_literal)
+  (long_string_literal)
+  (interpolated_string)
+] @string
+
+(escape_sequence) @string.escape
+
+(format_specifier) @string.special
+
+(comment) @comment
+
+((comment) @comment.documentation
+  (#match? @comment.documentation "^##"))
+
+; Operators and punctuation
+
+(unary_expression
+  operator: _ @operator)
+
+(binary_expression
+  operator: _ @operator)
+
+(assignment
+  operator: _ @operator)
+
+[
+  "="
+  "."
+] @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ";"
+  ":"
+] @punctuation.delimiter
+
+(interpolation
+  "{" @punctuation.special
+  "}" @punctuation.special)
@@ -0,0 +1,15 @@
+; {.emit: """...""".} splices C into the generated code
+
+(pragma
+  name: (identifier) @_name
+  value: (string_literal
+    (string_content) @injection.content)
+  (#eq? @_name "emit")
+  (#set! injection.language "c"))
+
+(pragma
+  name: (identifier) @_name
+  value: (long_string_literal) @injection.content
+  (#eq? @_name "emit")
+  (#offset! @injection.content 0 3 0 -3)
+  (#set! injection.language "c"))
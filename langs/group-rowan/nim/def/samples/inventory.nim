@@ -0,0 +1,90 @@
+## A small inventory tracker with a C-backed checksum.
+
+import std/[strutils, tables, strformat]
+
+{.emit: """
+#include <stdint.h>
+
+static uint32_t fnv1a(const char *s) {
+  uint32_t h = 2166136261u;
+  while (*s) { h ^= (uint8_t)*s++; h *= 16777619u; }
+  return h;
+}
+""".}
+
+proc fnv1a(s: cstring): uint32 {.importc, nodecl.}
+
+type
+  Category* = enum
+    tools, food,
+    books
+
+  Item* = ref object of RootObj
+    name*: string
+    category*: Category
+    quantity: int = 0
+
+  Inventory = object
+    items: Table[string, Item]
+
+const
+  MaxQuantity = 1_000
+  Version = "1.2.0"
+
+#[ Block comments can span
+   several lines. ]#
+
+proc newItem*(name: string; category: Category, quantity = 1): Item =
+  result = Item(name: name, category: category, quantity: quantity)
+
+proc `$`*(item: Item): string =
+  fmt"{item.name:<12} {item.quantity:>4} ({item.category})"
+
+proc add*(inv: var Inventory, item: Item) {.raises: [ValueError].} =
+  if item.quantity > MaxQuantity:
+    raise newException(ValueError, &"too many {item.name}: {item.quantity}")
+  elif inv.items.hasKey(item.name):
+    inv.items[item.name].quantity += item.quantity
+  else:
+    inv.items[item.name] = item
+
+iterator byCategory(inv: Inventory, cat: Category): Item =
+  for name, item in inv.items:
+    if item.category == cat:
+      yield item
+
+func total(inv: Inventory): int =
+  for item in inv.items.values:
+    result += item.quantity
+
+template withLabel(label: string, body: untyped) =
+  echo "== ", label, " =="
+  body
+
+when isMainModule:
+  var inv = Inventory(items: initTable[string, Item]())
+  inv.add newItem("hammer", tools, 3)
+  inv.add newItem("apple", food, 12)
+  inv.add newItem("manual", books)
+
+  let (low, high) = (0, MaxQuantity)
+  echo "range: ", low, "..", high, " v", Version
+
+  case inv.total
+  of 0:
+    echo "empty"
+  of 1..10:
+    echo "a few items"
+  else:
+    echo "plenty: ", $inv.total
+
+  block listing:
+    for item in inv.byCategory(tools):
+      echo item
+
+  try:
+    inv.add newItem("nails", tools, 5_000)
+  except ValueError as e:
+    echo "rejected: ", e.msg
+  finally:
+    echo "checksum: ", fnv1a(Version.cstring).toHex
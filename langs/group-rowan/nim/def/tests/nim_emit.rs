@@ -0,0 +1,28 @@
+//! Nim's `emit` pragma injects C; other pragma strings are left alone.
+
+use arborium_test_harness::helpers;
+
+const NIM: &str = r#"{.emit: """
+static int twice(int x) { return x * 2; }
+""".}
+
+{.emit: "/* inline */ int answer = 42;".}
+
+proc twice(x: cint): cint {.importc, header: "not_c.h".}
+"#;
+
+#[test]
+fn test_emit_pragma_injects_c() {
+    let result = helpers::plugin_parse(
+        arborium_nim::language(),
+        helpers::queries!(arborium_nim),
+        NIM,
+    );
+
+    let injected = helpers::injected_as(&result, NIM, "c");
+
+    assert_eq!(injected.len(), 2, "{:?}", injected);
+    assert!(injected[0].contains("static int twice"), "{:?}", injected);
+    assert!(!injected[0].contains("\"\"\""), "{:?}", injected);
+    assert_eq!(injected[1], "/* inline */ int answer = 42;");
+}
@@ -0,0 +1,665 @@
+/**
+ * @file Odin grammar for tree-sitter
+ * @license MIT
+ *
+ * Newlines are treated as whitespace; semicolons are optional everywhere.
+ * `Foo{...}` after an `if`/`for`/`switch` header is ambiguous with the
+ * statement's block, so both readings are kept until one fails to parse.
+ * https://odin-lang.org/docs/overview/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  ternary: 1,
+  or_else: 2,
+  range: 3,
+  or: 4,
+  and: 5,
+  comparison: 6,
+  in: 7,
+  additive: 8,
+  multiplicative: 9,
+  unary: 10,
+  postfix: 11,
+};
+
+const ASSIGNMENT_OPERATORS = [
+  '=', '+=', '-=', '*=', '/=', '%=', '%%=', '&=', '|=', '~=', '&~=', '<<=', '>>=', '&&=', '||=',
+];
+
+module.exports = grammar({
+  name: 'odin',
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.identifier,
+
+  conflicts: $ => [
+    // `if x == Foo {`: a compound literal, or the start of the block
+    [$._expression, $.compound_literal],
+    // `loop: for ...` versus `name: Type`
+    [$._primary_expression, $.labeled_statement],
+  ],
+
+  rules: {
+    source_file: $ => repeat($._top_level),
+
+    _top_level: $ => seq(
+      choice(
+        $.package_declaration,
+        $.import_declaration,
+        $.foreign_import_declaration,
+        $._statement,
+      ),
+      optional(';'),
+    ),
+
+    package_declaration: $ => seq('package', field('name', $.identifier)),
+
+    // import "core:fmt", import rl "vendor:raylib"
+    import_declaration: $ => seq(
+      'import',
+      optional(field('alias', $.identifier)),
+      field('path', $.string_literal),
+    ),
+
+    foreign_import_declaration: $ => seq(
+      repeat($.attribute),
+      'foreign',
+      'import',
+      optional(field('name', $.identifier)),
+      choice(
+        field('path', $.string_literal),
+        seq('{', commaSep1(field('path', $.string_literal)), optional(','), '}'),
+      ),
+    ),
+
+    // Statements
+
+    _statement: $ => choice(
+      $.const_declaration,
+      $.var_declaration,
+      $.attributed_declaration,
+      $.foreign_block,
+      $.assignment_statement,
+      $.expression_statement,
+      $.block,
+      $.if_statement,
+      $.when_statement,
+      $.for_statement,
+      $.for_in_statement,
+      $.switch_statement,
+      $.labeled_statement,
+      $.directive_statement,
+      $.return_statement,
+      $.defer_statement,
+      $.break_statement,
+      $.continue_statement,
+      $.fallthrough_statement,
+      $.using_statement,
+    ),
+
+    // Foo :: struct {...}, N :: 10, x : int : 5
+    const_declaration: $ => seq(
+      commaSep1(field('name', $._primary_expression)),
+      choice(
+        '::',
+        seq(':', field('type', $._expression), ':'),
+      ),
+      commaSep1(field('value', $._expression)),
+    ),
+
+    // x := 1, x: int, x: int = ---
+    var_declaration: $ => prec.right(seq(
+      commaSep1(field('name', $._primary_expression)),
+      choice(
+        seq(':=', commaSep1(field('value', $._expression))),
+        seq(
+          ':',
+          field('type', $._expression),
+          optional(seq('=', commaSep1(field('value', $._expression)))),
+        ),
+      ),
+    )),
+
+    attributed_declaration: $ => seq(
+      repeat1($.attribute),
+      choice($.const_declaration, $.var_declaration, $.foreign_block),
+    ),
+
+    // @(private), @(link_name = "foo", require), @private
+    attribute: $ => seq(
+      '@',
+      choice(
+        field('name', $.identifier),
+        seq(
+          '(',
+          commaSep1(choice(
+            field('name', $.identifier),
+            seq(field('name', $.identifier), '=', field('value', $._expression)),
+          )),
+          optional(','),
+          ')',
+        ),
+      ),
+    ),
+
+    foreign_block: $ => seq(
+      'foreign',
+      optional(field('library', $.identifier)),
+      field('body', $.block),
+    ),
+
+    assignment_statement: $ => seq(
+      commaSep1(field('left', $._primary_expression)),
+      field('operator', choice(...ASSIGNMENT_OPERATORS)),
+      commaSep1(field('right', $._expression)),
+    ),
+
+    // Only calls (and `or_return` chains) may stand alone.
+    expression_statement: $ => choice(
+      $.call_expression,
+      $.or_return_expression,
+    ),
+
+    block: $ => seq(
+      '{',
+      repeat(seq($._statement, optional(';'))),
+      '}',
+    ),
+
+    _body: $ => choice(
+      $.block,
+      seq('do', $._statement),
+    ),
+
+    _simple_statement: $ => choice(
+      $.var_declaration,
+      $.assignment_statement,
+      $.expression_statement,
+    ),
+
+    if_statement: $ => seq(
+      'if',
+      optional(seq(field('initializer', $._simple_statement), ';')),
+      field('condition', $._expression),
+      field('consequence', $._body),
+      optional(seq('else', field('alternative', choice($.if_statement, $._body)))),
+    ),
+
+    when_statement: $ => seq(
+      'when',
+      field('condition', $._expression),
+      field('consequence', $._body),
+      optional(seq('else', field('alternative', choice($.when_statement, $._body)))),
+    ),
+
+    // for, for cond, for i := 0; i < n; i += 1
+    for_statement: $ => seq(
+      'for',
+      optional(choice(
+        field('condition', $._expression),
+        seq(
+          optional(field('initializer', $._simple_statement)),
+          ';',
+          optional(field('condition', $._expression)),
+          ';',
+          optional(field('update', $._simple_statement)),
+        ),
+      )),
+      field('body', $._body),
+    ),
+
+    // for value, index in values
+    for_in_statement: $ => prec(1, seq(
+      'for',
+      commaSep1(field('left', $._primary_expression)),
+      'in',
+      field('right', $._expression),
+      field('body', $._body),
+    )),
+
+    switch_statement: $ => seq(
+      'switch',
+      optional(seq(field('initializer', $._simple_statement), ';')),
+      optional(field('value', $._expression)),
+      '{',
+      repeat($.case_clause),
+      '}',
+    ),
+
+    case_clause: $ => seq(
+      'case',
+      optional(commaSep1(field('value', $._expression))),
+      ':',
+      repeat(seq($._statement, optional(';'))),
+    ),
+
+    labeled_statement: $ => seq(
+      field('label', $.identifier),
+      ':',
+      choice($.block, $.for_statement, $.for_in_statement, $.switch_statement, $.if_statement),
+    ),
+
+    // #partial switch, #no_bounds_check for, #unroll for
+    directive_statement: $ => seq(
+      $.directive,
+      choice($.for_statement, $.for_in_statement, $.switch_statement, $.if_statement),
+    ),
+
+    return_statement: $ => prec.right(seq('return', optional(commaSep1($._expression)))),
+
+    defer_statement: $ => seq('defer', $._statement),
+
+    break_statement: $ => prec.right(seq('break', optional(field('label', $.identifier)))),
+
+    continue_statement: $ => prec.right(seq('continue', optional(field('label', $.identifier)))),
+
+    fallthrough_statement: _ => 'fallthrough',
+
+    using_statement: $ => seq('using', $._expression),
+
+    // Expressions
+
+    _expression: $ => choice(
+      $._primary_expression,
+      $.unary_expression,
+      $.binary_expression,
+      $.ternary_expression,
+      $.or_else_expression,
+      $.or_return_expression,
+      $.compound_literal,
+      $.cast_expression,
+      $.proc_literal,
+      $.proc_group,
+      $._type,
+      $.uninitialized,
+    ),
+
+    _primary_expression: $ => choice(
+      $.identifier,
+      $.polymorphic_identifier,
+      $.implicit_selector,
+      $.directive,
+      $.integer_literal,
+      $.float_literal,
+      $.rune_literal,
+      $.string_literal,
+      $.raw_string_literal,
+      $.true,
+      $.false,
+      $.nil,
+      $.parenthesized_expression,
+      $.selector_expression,
+      $.index_expression,
+      $.slice_expression,
+      $.dereference_expression,
+      $.type_assertion,
+      $.call_expression,
+    ),
+
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice('-', '+', '!', '~', '&')),
+      field('argument', $._expression),
+    )),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.range, choice('..<', '..=')],
+        [PREC.or, '||'],
+        [PREC.and, '&&'],
+        [PREC.comparison, choice('==', '!=', '<', '<=', '>', '>=')],
+        [PREC.in, choice('in', 'not_in')],
+        [PREC.additive, choice('+', '-', '|', '~')],
+        [PREC.multiplicative, choice('*', '/', '%', '%%', '&', '&~', '<<', '>>')],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
+        ),
+      )));
+    },
+
+    // a if cond else b, cond ? a : b
+    ternary_expression: $ => prec.right(PREC.ternary, choice(
+      seq(
+        field('consequence', $._expression),
+        choice('if', 'when'),
+        field('condition', $._expression),
+        'else',
+        field('alternative', $._expression),
+      ),
+      seq(
+        field('condition', $._expression),
+        '?',
+        field('consequence', $._expression),
+        ':',
+        field('alternative', $._expression),
+      ),
+    )),
+
+    or_else_expression: $ => prec.left(PREC.or_else, seq(
+      field('left', $._expression),
+      'or_else',
+      field('right', $._expression),
+    )),
+
+    or_return_expression: $ => prec(PREC.postfix, seq($._primary_expression, 'or_return')),
+
+    // cast(f32)x, transmute(u32)f, auto_cast x
+    cast_expression: $ => prec(PREC.unary, seq(
+      choice(
+        seq(choice('cast', 'transmute'), '(', field('type', $._expression), ')'),
+        'auto_cast',
+      ),
+      field('value', $._expression),
+    )),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    // .Red
+    implicit_selector: $ => seq('.', field('field', $.identifier)),
+
+    selector_expression: $ => prec(PREC.postfix, seq(
+      field('operand', $._primary_expression),
+      choice('.', '->'),
+      field('field', $.identifier),
+    )),
+
+    index_expression: $ => prec(PREC.postfix, seq(
+      field('operand', $._primary_expression),
+      '[',
+      field('index', $._expression),
+      optional(seq(',', field('index', $._expression))),
+      ']',
+    )),
+
+    slice_expression: $ => prec(PREC.postfix, seq(
+      field('operand', $._primary_expression),
+      '[',
+      optional(field('start', $._expression)),
+      ':',
+      optional(field('end', $._expression)),
+      ']',
+    )),
+
+    dereference_expression: $ => prec(PREC.postfix, seq(
+      field('operand', $._primary_expression),
+      '^',
+    )),
+
+    // value.(T), maybe.?
+    type_assertion: $ => prec(PREC.postfix, seq(
+      field('operand', $._primary_expression),
+      '.',
+      choice(
+        seq('(', field('type', $._expression), ')'),
+        '?',
+      ),
+    )),
+
+    call_expression: $ => prec(PREC.postfix, seq(
+      field('function', $._primary_expression),
+      field('arguments', $.argument_list),
+    )),
+
+    argument_list: $ => seq(
+      '(',
+      optional(seq(commaSep1(choice($._expression, $.named_argument)), optional(','))),
+      ')',
+    ),
+
+    named_argument: $ => seq(
+      field('name', $.identifier),
+      '=',
+      field('value', $._expression),
+    ),
+
+    // Vec2{1, 2}, [3]int{1, 2, 3}, {x = 1, y = 2}
+    compound_literal: $ => seq(
+      optional(field('type', choice(
+        $._primary_expression,
+        $.array_type,
+        $.map_type,
+        $.matrix_type,
+        $.bit_set_type,
+      ))),
+      '{',
+      optional(seq(commaSep1(choice($._expression, $.field_value)), optional(','))),
+      '}',
+    ),
+
+    field_value: $ => seq(
+      field('field', $._expression),
+      '=',
+      field('value', $._expression),
+    ),
+
+    // proc(a, b: int) -> int { ... }, or a foreign `---` body
+    proc_literal: $ => prec(1, seq(
+      $.proc_type,
+      repeat($.directive),
+      choice(field('body', $.block), '---'),
+    )),
+
+    proc_group: $ => seq(
+      'proc',
+      '{',
+      commaSep1($._expression),
+      optional(','),
+      '}',
+    ),
+
+    // Types
+
+    _type: $ => choice(
+      $.pointer_type,
+      $.array_type,
+      $.map_type,
+      $.matrix_type,
+      $.bit_set_type,
+      $.distinct_type,
+      $.proc_type,
+      $.struct_type,
+      $.enum_type,
+      $.union_type,
+    ),
+
+    pointer_type: $ => prec(PREC.unary, seq('^', $._expression)),
+
+    // [4]f32, []u8, [dynamic]string, [?]int, [^]byte
+    array_type: $ => prec(PREC.unary, seq(
+      '[',
+      optional(field('length', choice($._expression, '?', 'dynamic', '^'))),
+      ']',
+      field('element', $._expression),
+    )),
+
+    map_type: $ => prec(PREC.unary, seq(
+      'map',
+      '[',
+      field('key', $._expression),
+      ']',
+      field('value', $._expression),
+    )),
+
+    matrix_type: $ => prec(PREC.unary, seq(
+      'matrix',
+      '[',
+      field('rows', $._expression),
+      ',',
+      field('columns', $._expression),
+      ']',
+      field('element', $._expression),
+    )),
+
+    bit_set_type: $ => seq(
+      'bit_set',
+      '[',
+      field('element', $._expression),
+      optional(seq(';', field('underlying', $._expression))),
+      ']',
+    ),
+
+    distinct_type: $ => prec(PREC.unary, seq('distinct', $._expression)),
+
+    proc_type: $ => prec.right(seq(
+      'proc',
+      optional(field('calling_convention', $.string_literal)),
+      field('parameters', $.parameter_list),
+      optional(seq('->', field('result', choice($._expression, $.parameter_list, '!')))),
+    )),
+
+    parameter_list: $ => seq(
+      '(',
+      optional(seq(commaSep1($.parameter), optional(','))),
+      ')',
+    ),
+
+    // a, b: int, using e: ^Entity, $T: typeid, args: ..any, x := 0
+    parameter: $ => choice(
+      seq(
+        repeat(choice($.directive, 'using')),
+        commaSep1(field('name', choice($.identifier, $.polymorphic_identifier))),
+        choice(
+          seq(':', field('type', $._parameter_type), optional(seq('=', field('default', $._expression)))),
+          seq(':=', field('default', $._expression)),
+        ),
+      ),
+      field('type', $._parameter_type),
+    ),
+
+    _parameter_type: $ => choice(
+      $._expression,
+      $.variadic_type,
+    ),
+
+    variadic_type: $ => seq('..', $._expression),
+
+    struct_type: $ => seq(
+      'struct',
+      optional(field('parameters', $.parameter_list)),
+      repeat($.directive),
+      '{',
+      optional(seq(commaSep1($.field_declaration), optional(','))),
+      '}',
+    ),
+
+    field_declaration: $ => seq(
+      optional('using'),
+      commaSep1(field('name', $.identifier)),
+      ':',
+      field('type', $._expression),
+      optional(field('tag', choice($.string_literal, $.raw_string_literal))),
+    ),
+
+    enum_type: $ => seq(
+      'enum',
+      optional(field('backing', $._expression)),
+      '{',
+      optional(seq(commaSep1($.enum_field), optional(','))),
+      '}',
+    ),
+
+    enum_field: $ => seq(
+      field('name', $.identifier),
+      optional(seq('=', field('value', $._expression))),
+    ),
+
+    union_type: $ => seq(
+      'union',
+      optional(field('parameters', $.parameter_list)),
+      repeat($.directive),
+      '{',
+      optional(seq(commaSep1($._expression), optional(','))),
+      '}',
+    ),
+
+    // Literals
+
+    identifier: _ => /[a-zA-Z_\u00C0-\uFFFF][a-zA-Z0-9_\u00C0-\uFFFF]*/,
+
+    // $T in polymorphic parameters
+    polymorphic_identifier: _ => /\$[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    // #partial, #load, #assert, #caller_location, #packed
+    directive: _ => /#[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    uninitialized: _ => '---',
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    nil: _ => 'nil',
+
+    integer_literal: _ => token(seq(
+      choice(
+        /0[xX][0-9a-fA-F_]+/,
+        /0[bB][01_]+/,
+        /0[oO][0-7_]+/,
+        /0[zZ][0-9abAB_]+/,
+        /\d[\d_]*/,
+      ),
+      optional(/[ijk]/),
+    )),
+
+    float_literal: _ => token(seq(
+      choice(
+        /\d[\d_]*\.\d[\d_]*([eE][+-]?\d+)?/,
+        /\d[\d_]*[eE][+-]?\d+/,
+        /0h[0-9a-fA-F]+/,
+      ),
+      optional(/[ijk]/),
+    )),
+
+    rune_literal: _ => token(seq(
+      '\'',
+      choice(/[^'\\\n]/, /\\(x[0-9a-fA-F]{2}|u[0-9a-fA-F]{4}|U[0-9a-fA-F]{8}|[0-7]{3}|.)/),
+      '\'',
+    )),
+
+    string_literal: $ => seq(
+      '"',
+      repeat(choice(
+        token.immediate(prec(1, /[^"\\\n]+/)),
+        $.escape_sequence,
+      )),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /x[0-9a-fA-F]{2}/,
+        /u[0-9a-fA-F]{4}/,
+        /U[0-9a-fA-F]{8}/,
+        /[0-7]{3}/,
+        /[^xuU0-7]/,
+      ),
+    )),
+
+    raw_string_literal: _ => token(seq('`', /[^`]*/, '`')),
+
+    // Block comments nest in Odin; nesting isn't tracked here.
+    comment: _ => token(choice(
+      seq('//', /.*/),
+      seq('/*', /[^*]*\*+([^/*][^*]*\*+)*/, '/'),
+    )),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
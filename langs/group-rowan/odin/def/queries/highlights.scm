@@ -0,0 +1,241 @@
+; Identifiers
+
+((identifier) @type
+  (#match? @type "^[A-Z][a-zA-Z0-9]*[a-z][a-zA-Z0-9]*$"))
+
+((identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+((identifier) @type.builtin
+  (#any-of? @type.builtin
+    "int" "i8" "i16" "i32" "i64" "i128"
+    "uint" "u8" "u16" "u32" "u64" "u128" "uintptr"
+    "f16" "f32" "f64" "complex64" "complex128" "quaternion256"
+    "bool" "b8" "b16" "b32" "b64" "byte" "rune" "string" "cstring"
+    "rawptr" "typeid" "any"))
+
+((identifier) @variable.builtin
+  (#eq? @variable.builtin "context"))
+
+((identifier) @function.builtin
+  (#any-of? @function.builtin
+    "len" "cap" "size_of" "align_of" "offset_of" "type_of" "type_info_of" "typeid_of"
+    "make" "new" "free" "delete" "append" "clear" "min" "max" "abs" "clamp"
+    "assert" "panic" "swizzle"))
+
+; Keywords
+
+[
+  "auto_cast"
+  "bit_set"
+  "break"
+  "case"
+  "cast"
+  "continue"
+  "defer"
+  "distinct"
+  "do"
+  "dynamic"
+  "else"
+  "enum"
+  "fallthrough"
+  "for"
+  "foreign"
+  "if"
+  "import"
+  "in"
+  "map"
+  "matrix"
+  "not_in"
+  "or_else"
+  "or_return"
+  "package"
+  "proc"
+  "return"
+  "struct"
+  "switch"
+  "transmute"
+  "union"
+  "using"
+  "when"
+] @keyword
+
+(fallthrough_statement) @keyword
+
+[
+  (true)
+  (false)
+] @boolean
+
+(nil) @constant.builtin
+
+(uninitialized) @constant.builtin
+
+; Declarations
+
+(const_declaration
+  name: (identifier) @function
+  value: [
+    (proc_literal)
+    (proc_group)
+  ])
+
+(const_declaration
+  name: (identifier) @type
+  value: [
+    (struct_type)
+    (enum_type)
+    (union_type)
+    (distinct_type)
+    (bit_set_type)
+  ])
+
+(package_declaration
+  name: (identifier) @module)
+
+(import_declaration
+  alias: (identifier) @module)
+
+(foreign_import_declaration
+  name: (identifier) @module)
+
+[
+  (import_declaration
+    path: (string_literal) @string.special.path)
+  (foreign_import_declaration
+    path: (string_literal) @string.special.path)
+]
+
+(parameter
+  name: (identifier) @variable.parameter)
+
+(polymorphic_identifier) @type
+
+(field_declaration
+  name: (identifier) @property)
+
+(field_declaration
+  tag: _ @string.special)
+
+(enum_field
+  name: (identifier) @constant)
+
+(implicit_selector
+  field: (identifier) @constant)
+
+(field_value
+  field: (identifier) @property)
+
+(named_argument
+  name: (identifier) @variable.parameter)
+
+(labeled_statement
+  label: (identifier) @label)
+
+(break_statement
+  label: (identifier) @label)
+
+(continue_statement
+  label: (identifier) @label)
+
+; Types
+
+(parameter
+  type: (identifier) @type)
+
+(var_declaration
+  type: (identifier) @type)
+
+(field_declaration
+  type: (identifier) @type)
+
+(proc_type
+  result: (identifier) @type)
+
+(compound_literal
+  type: (identifier) @type)
+
+(cast_expression
+  type: (identifier) @type)
+
+(type_assertion
+  type: (identifier) @type)
+
+(proc_type
+  calling_convention: (string_literal) @string.special)
+
+; Calls
+
+(selector_expression
+  field: (identifier) @property)
+
+(call_expression
+  function: (identifier) @function.call)
+
+(call_expression
+  function: (selector_expression
+    field: (identifier) @function.method.call))
+
+; Attributes and directives
+
+(attribute
+  "@" @attribute
+  name: (identifier) @attribute)
+
+(directive) @attribute
+
+; Literals
+
+(integer_literal) @number
+
+(float_literal) @number.float
+
+(rune_literal) @character
+
+[
+  (string_literal)
+  (raw_string_literal)
+] @string
+
+(escape_sequence) @string.escape
+
+(comment) @comment
+
+; Operators and punctuation
+
+(unary_expression
+  operator: _ @operator)
+
+(binary_expression
+  operator: _ @operator)
+
+(assignment_statement
+  operator: _ @operator)
+
+(pointer_type "^" @operator)
+
+(dereference_expression "^" @operator)
+
+[
+  "::"
+  ":="
+  "->"
+  "?"
+  ".."
+] @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ";"
+  ":"
+  "."
+] @punctuation.delimiter
@@ -0,0 +1,742 @@
+/**
+ * @file Pascal grammar for tree-sitter
+ * @license MIT
+ *
+ * Object Pascal as accepted by Delphi and Free Pascal: programs, units and
+ * libraries, classes, records, interfaces, properties, generics and
+ * exception handling. Keywords are case-insensitive. Compiler directives
+ * (`{$IFDEF DEBUG}`) are kept apart from ordinary comments.
+ * https://docwiki.embarcadero.com/RADStudio/en/Delphi_Language_Guide_Index
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  relational: 1,
+  additive: 2,
+  multiplicative: 3,
+  unary: 4,
+  postfix: 5,
+};
+
+const ROUTINE_DIRECTIVES = [
+  'abstract',
+  'assembler',
+  'cdecl',
+  'deprecated',
+  'dynamic',
+  'experimental',
+  'final',
+  'inline',
+  'overload',
+  'override',
+  'platform',
+  'pascal',
+  'register',
+  'reintroduce',
+  'safecall',
+  'static',
+  'stdcall',
+  'varargs',
+  'virtual',
+  'winapi',
+];
+
+module.exports = grammar({
+  name: 'pascal',
+
+  extras: $ => [/\s/, $.comment, $.compiler_directive],
+
+  word: $ => $.identifier,
+
+  rules: {
+    root: $ => choice(
+      $.program,
+      $.unit,
+      $.library,
+      repeat1($._declaration),
+    ),
+
+    // Program structure
+
+    program: $ => seq(
+      kw('program'),
+      field('name', $._module_name),
+      optional(seq('(', commaSep1($.identifier), ')')),
+      ';',
+      optional($.uses_clause),
+      repeat($._declaration),
+      field('body', $.block),
+      '.',
+    ),
+
+    library: $ => seq(
+      kw('library'),
+      field('name', $._module_name),
+      ';',
+      optional($.uses_clause),
+      repeat(choice($._declaration, $.exports_clause)),
+      field('body', $.block),
+      '.',
+    ),
+
+    unit: $ => seq(
+      kw('unit'),
+      field('name', $._module_name),
+      ';',
+      $.interface_section,
+      $.implementation_section,
+      optional(choice(
+        seq($.initialization_section, optional($.finalization_section)),
+        seq(kw('begin'), optional($._statements)),
+      )),
+      kw('end'),
+      '.',
+    ),
+
+    interface_section: $ => seq(
+      kw('interface'),
+      optional($.uses_clause),
+      repeat(choice(
+        $.const_section,
+        $.type_section,
+        $.var_section,
+        $.resourcestring_section,
+        $.routine_declaration,
+      )),
+    ),
+
+    implementation_section: $ => seq(
+      kw('implementation'),
+      optional($.uses_clause),
+      repeat($._declaration),
+    ),
+
+    initialization_section: $ => seq(kw('initialization'), optional($._statements)),
+
+    finalization_section: $ => seq(kw('finalization'), optional($._statements)),
+
+    // System.SysUtils, Vcl.Forms
+    _module_name: $ => alias($.qualified_identifier, $.module_name),
+
+    qualified_identifier: $ => seq($.identifier, repeat(seq('.', $.identifier))),
+
+    // uses SysUtils, Classes, MyUnit in 'src/MyUnit.pas';
+    uses_clause: $ => seq(
+      kw('uses'),
+      commaSep1(seq(
+        $._module_name,
+        optional(seq(kw('in'), field('path', $.string))),
+      )),
+      ';',
+    ),
+
+    exports_clause: $ => seq(kw('exports'), commaSep1($.identifier), ';'),
+
+    // Declarations
+
+    _declaration: $ => choice(
+      $.const_section,
+      $.type_section,
+      $.var_section,
+      $.resourcestring_section,
+      $.label_section,
+      $.routine_definition,
+      $.routine_forward_declaration,
+    ),
+
+    const_section: $ => prec.right(seq(
+      kw('const'),
+      repeat1($.const_declaration),
+    )),
+
+    const_declaration: $ => seq(
+      field('name', $.identifier),
+      optional(seq(':', field('type', $._type))),
+      '=',
+      field('value', choice($._expression, $.array_constant)),
+      ';',
+    ),
+
+    // ('tool', 'part', 'consumable')
+    array_constant: $ => seq(
+      '(',
+      choice($._expression, $.array_constant),
+      repeat1(seq(',', choice($._expression, $.array_constant))),
+      ')',
+    ),
+
+    resourcestring_section: $ => prec.right(seq(
+      kw('resourcestring'),
+      repeat1($.const_declaration),
+    )),
+
+    var_section: $ => prec.right(seq(
+      choice(kw('var'), kw('threadvar')),
+      repeat1($.var_declaration),
+    )),
+
+    var_declaration: $ => seq(
+      commaSep1(field('name', $.identifier)),
+      ':',
+      field('type', $._type),
+      optional(seq(choice('=', kw('absolute')), field('value', $._expression))),
+      ';',
+    ),
+
+    label_section: $ => seq(kw('label'), commaSep1(choice($.identifier, $.number)), ';'),
+
+    // Inside a class, a nested section runs until the next visibility
+    // specifier or `var`, as in Delphi.
+    type_section: $ => prec.right(seq(
+      kw('type'),
+      repeat1($.type_declaration),
+    )),
+
+    // TList<T> = class(TObject) ... end;
+    type_declaration: $ => seq(
+      field('name', $.identifier),
+      optional(field('type_parameters', $.generic_parameters)),
+      '=',
+      optional(kw('type')),
+      field('type', choice(
+        $._type,
+        $.class_type,
+        $.record_type,
+        $.interface_type,
+        $.forward_class,
+      )),
+      ';',
+    ),
+
+    // <T>, <K, V: class>
+    generic_parameters: $ => seq(
+      '<',
+      sep1(seq(
+        commaSep1($.identifier),
+        optional(seq(':', commaSep1(choice($.type_reference, kw('class'), kw('record'), kw('constructor'))))),
+      ), ';'),
+      '>',
+    ),
+
+    // Routines
+
+    // function TStack<T>.Pop: T;
+    _routine_header: $ => seq(
+      optional(kw('class')),
+      field('kind', choice(
+        kw('procedure'),
+        kw('function'),
+        kw('constructor'),
+        kw('destructor'),
+        kw('operator'),
+      )),
+      field('name', $.routine_name),
+      optional(field('parameters', $.parameter_list)),
+      optional(seq(':', field('return_type', $._type))),
+      ';',
+    ),
+
+    routine_name: $ => seq(
+      $.identifier,
+      optional($.generic_parameters),
+      repeat(seq('.', $.identifier, optional($.generic_parameters))),
+    ),
+
+    routine_directive: $ => seq(
+      choice(
+        ...ROUTINE_DIRECTIVES.map(kw),
+        seq(kw('message'), $._expression),
+        seq(kw('dispid'), $._expression),
+      ),
+      optional($.string),
+      ';',
+    ),
+
+    // external 'user32.dll' name 'MessageBoxW';
+    external_directive: $ => seq(
+      kw('external'),
+      optional(field('library', $._expression)),
+      optional(seq(kw('name'), field('external_name', $._expression))),
+      optional(seq(kw('index'), $._expression)),
+      optional(kw('delayed')),
+      ';',
+    ),
+
+    // A routine header on its own: in an interface section or a class.
+    routine_declaration: $ => seq(
+      $._routine_header,
+      repeat(choice($.routine_directive, $.external_directive)),
+    ),
+
+    routine_forward_declaration: $ => seq(
+      $._routine_header,
+      repeat($.routine_directive),
+      choice(seq(kw('forward'), ';'), $.external_directive),
+    ),
+
+    routine_definition: $ => seq(
+      $._routine_header,
+      repeat($.routine_directive),
+      repeat($._declaration),
+      field('body', $.block),
+      ';',
+    ),
+
+    // (const AName: string; var Count: Integer = 0; out Items: array of T)
+    parameter_list: $ => seq(
+      '(',
+      optional(sep1($.parameter, ';')),
+      ')',
+    ),
+
+    parameter: $ => seq(
+      optional(choice(kw('const'), kw('var'), kw('out'), kw('constref'))),
+      commaSep1(field('name', $.identifier)),
+      optional(seq(':', field('type', $._type))),
+      optional(seq('=', field('default', $._expression))),
+    ),
+
+    // Types
+
+    _type: $ => choice(
+      $.type_reference,
+      $.pointer_type,
+      $.array_type,
+      $.set_type,
+      $.file_type,
+      $.string_type,
+      $.subrange_type,
+      $.enum_type,
+      $.procedural_type,
+      $.class_reference_type,
+    ),
+
+    // Integer, System.TObject, TList<string>
+    type_reference: $ => prec(1, seq(
+      $.identifier,
+      repeat(seq('.', $.identifier)),
+      optional(seq('<', commaSep1($._type), '>')),
+    )),
+
+    pointer_type: $ => seq('^', $._type),
+
+    // array[0..9, Boolean] of Real, array of const
+    array_type: $ => seq(
+      optional(kw('packed')),
+      kw('array'),
+      optional(seq('[', commaSep1($._type), ']')),
+      kw('of'),
+      choice($._type, kw('const')),
+    ),
+
+    set_type: $ => seq(kw('set'), kw('of'), $._type),
+
+    file_type: $ => prec.right(seq(kw('file'), optional(seq(kw('of'), $._type)))),
+
+    // string, string[80]
+    string_type: $ => prec.right(seq(
+      kw('string'),
+      optional(seq('[', $._expression, ']')),
+    )),
+
+    // 1..10, 'a'..'z', Low(Byte)..High(Byte)
+    subrange_type: $ => seq(
+      field('low', $._subrange_bound),
+      '..',
+      field('high', $._subrange_bound),
+    ),
+
+    _subrange_bound: $ => choice(
+      $.number,
+      $.string,
+      $.identifier,
+      $.call_expression,
+      $.unary_expression,
+    ),
+
+    // (Red, Green, Blue = 4)
+    enum_type: $ => seq(
+      '(',
+      commaSep1(seq(
+        field('member', $.identifier),
+        optional(seq('=', $._expression)),
+      )),
+      ')',
+    ),
+
+    // procedure(Sender: TObject) of object, reference to function: Integer
+    procedural_type: $ => prec.right(seq(
+      optional(seq(kw('reference'), kw('to'))),
+      choice(kw('procedure'), kw('function')),
+      optional(field('parameters', $.parameter_list)),
+      optional(seq(':', field('return_type', $._type))),
+      optional(seq(kw('of'), kw('object'))),
+    )),
+
+    class_reference_type: $ => seq(kw('class'), kw('of'), $.type_reference),
+
+    forward_class: _ => choice(kw('class'), kw('interface'), kw('dispinterface')),
+
+    class_type: $ => seq(
+      optional(choice(kw('sealed'), kw('abstract'))),
+      choice(kw('class'), kw('object')),
+      optional(seq('(', commaSep1($.type_reference), ')')),
+      optional(seq(repeat($._class_member), kw('end'))),
+    ),
+
+    record_type: $ => seq(
+      optional(kw('packed')),
+      kw('record'),
+      repeat($._class_member),
+      kw('end'),
+    ),
+
+    // IShape = interface(IInterface) ['{GUID}'] ... end
+    interface_type: $ => seq(
+      choice(kw('interface'), kw('dispinterface')),
+      optional(seq('(', commaSep1($.type_reference), ')')),
+      optional(seq('[', $.string, ']')),
+      repeat($._class_member),
+      kw('end'),
+    ),
+
+    _class_member: $ => choice(
+      $.visibility,
+      $.field_declaration,
+      $.routine_declaration,
+      $.property_declaration,
+      $.const_section,
+      $.type_section,
+      seq(optional(kw('class')), kw('var')),
+    ),
+
+    // strict private, protected, public, published
+    visibility: _ => choice(
+      seq(optional(kw('strict')), choice(kw('private'), kw('protected'))),
+      kw('public'),
+      kw('published'),
+      kw('automated'),
+    ),
+
+    // The last field of a record may leave out its semicolon.
+    field_declaration: $ => seq(
+      commaSep1(field('name', $.identifier)),
+      ':',
+      field('type', $._type),
+      optional(';'),
+    ),
+
+    // property Items[Index: Integer]: T read GetItem write SetItem; default;
+    property_declaration: $ => seq(
+      optional(kw('class')),
+      kw('property'),
+      field('name', $.identifier),
+      optional(seq('[', sep1($.parameter, ';'), ']')),
+      optional(seq(':', field('type', $._type))),
+      repeat($.property_specifier),
+      ';',
+      optional(seq(kw('default'), ';')),
+    ),
+
+    property_specifier: $ => choice(
+      seq(kw('read'), field('reader', $._expression)),
+      seq(kw('write'), field('writer', $._expression)),
+      seq(choice(kw('stored'), kw('default'), kw('index'), kw('implements')), $._expression),
+      kw('nodefault'),
+    ),
+
+    // Statements
+
+    block: $ => seq(
+      kw('begin'),
+      optional($._statements),
+      kw('end'),
+    ),
+
+    // Statements are separated, not terminated, by semicolons, so empty
+    // statements are allowed anywhere.
+    _statements: $ => repeat1(choice($._statement, ';')),
+
+    _statement: $ => choice(
+      $.assignment,
+      $.expression_statement,
+      $.block,
+      $.if_statement,
+      $.case_statement,
+      $.for_statement,
+      $.for_in_statement,
+      $.while_statement,
+      $.repeat_statement,
+      $.with_statement,
+      $.try_statement,
+      $.raise_statement,
+      $.goto_statement,
+      $.asm_statement,
+    ),
+
+    assignment: $ => seq(
+      field('left', $._expression),
+      field('operator', choice(':=', '+=', '-=', '*=', '/=')),
+      field('right', $._expression),
+    ),
+
+    expression_statement: $ => $._expression,
+
+    if_statement: $ => prec.right(seq(
+      kw('if'),
+      field('condition', $._expression),
+      kw('then'),
+      optional(field('consequence', $._statement)),
+      optional(seq(kw('else'), optional(field('alternative', $._statement)))),
+    )),
+
+    case_statement: $ => seq(
+      kw('case'),
+      field('value', $._expression),
+      kw('of'),
+      repeat(choice($.case_branch, ';')),
+      optional(seq(choice(kw('else'), kw('otherwise')), optional($._statements))),
+      kw('end'),
+    ),
+
+    // 1, 3..5: WriteLn('small');
+    case_branch: $ => seq(
+      commaSep1(field('label', choice($._expression, $.range))),
+      ':',
+      optional(field('body', $._statement)),
+    ),
+
+    range: $ => seq($._expression, '..', $._expression),
+
+    // for I := 0 to Count - 1 do, for var I := High(A) downto 0 do
+    for_statement: $ => seq(
+      kw('for'),
+      optional(kw('var')),
+      field('variable', $.identifier),
+      optional(seq(':', field('type', $._type))),
+      ':=',
+      field('start', $._expression),
+      choice(kw('to'), kw('downto')),
+      field('end', $._expression),
+      kw('do'),
+      optional(field('body', $._statement)),
+    ),
+
+    for_in_statement: $ => seq(
+      kw('for'),
+      optional(kw('var')),
+      field('variable', $.identifier),
+      kw('in'),
+      field('collection', $._expression),
+      kw('do'),
+      optional(field('body', $._statement)),
+    ),
+
+    while_statement: $ => seq(
+      kw('while'),
+      field('condition', $._expression),
+      kw('do'),
+      optional(field('body', $._statement)),
+    ),
+
+    repeat_statement: $ => seq(
+      kw('repeat'),
+      optional($._statements),
+      kw('until'),
+      field('condition', $._expression),
+    ),
+
+    with_statement: $ => seq(
+      kw('with'),
+      commaSep1($._expression),
+      kw('do'),
+      optional(field('body', $._statement)),
+    ),
+
+    try_statement: $ => seq(
+      kw('try'),
+      optional($._statements),
+      choice(
+        seq(kw('except'), optional(choice($._exception_handlers, $._statements))),
+        seq(kw('finally'), optional($._statements)),
+      ),
+      kw('end'),
+    ),
+
+    _exception_handlers: $ => seq(
+      repeat1(seq($.exception_handler, optional(';'))),
+      optional(seq(kw('else'), optional($._statements))),
+    ),
+
+    // on E: EConvertError do ShowMessage(E.Message);
+    exception_handler: $ => seq(
+      kw('on'),
+      optional(seq(field('variable', $.identifier), ':')),
+      field('type', $.type_reference),
+      kw('do'),
+      optional(field('body', $._statement)),
+    ),
+
+    raise_statement: $ => prec.right(seq(
+      kw('raise'),
+      optional(seq($._expression, optional(seq(kw('at'), $._expression)))),
+    )),
+
+    goto_statement: $ => seq(kw('goto'), choice($.identifier, $.number)),
+
+    asm_statement: _ => seq(
+      kw('asm'),
+      token(/([^eE]|[eE][^nN]|[eE][nN][^dD])*/),
+      kw('end'),
+    ),
+
+    // Expressions
+
+    _expression: $ => choice(
+      $.identifier,
+      $.number,
+      $.string,
+      $.nil,
+      $.set_constructor,
+      $.parenthesized_expression,
+      $.call_expression,
+      $.index_expression,
+      $.member_expression,
+      $.dereference_expression,
+      $.inherited_expression,
+      $.unary_expression,
+      $.binary_expression,
+    ),
+
+    nil: _ => kw('nil'),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    call_expression: $ => prec(PREC.postfix, seq(
+      field('function', $._expression),
+      field('arguments', $.argument_list),
+    )),
+
+    // Format('%d%%', [Percent]), Str(X:8:2, S)
+    argument_list: $ => seq(
+      '(',
+      optional(commaSep1(seq($._expression, repeat(seq(':', $._expression))))),
+      ')',
+    ),
+
+    index_expression: $ => prec(PREC.postfix, seq(
+      field('object', $._expression),
+      '[',
+      commaSep1(field('index', $._expression)),
+      ']',
+    )),
+
+    member_expression: $ => prec(PREC.postfix, seq(
+      field('object', $._expression),
+      '.',
+      field('member', $.identifier),
+    )),
+
+    dereference_expression: $ => prec(PREC.postfix, seq($._expression, '^')),
+
+    // inherited; inherited Create(AOwner);
+    inherited_expression: $ => prec.right(seq(
+      kw('inherited'),
+      optional(field('member', $.identifier)),
+    )),
+
+    // [1, 3..5, Ord('a')]
+    set_constructor: $ => seq(
+      '[',
+      optional(commaSep1(choice($._expression, $.range))),
+      ']',
+    ),
+
+    unary_expression: $ => prec(PREC.unary, seq(
+      field('operator', choice(kw('not'), '-', '+', '@')),
+      field('argument', $._expression),
+    )),
+
+    binary_expression: $ => {
+      const table = [
+        [PREC.relational, choice('=', '<>', '<', '<=', '>', '>=', kw('in'), kw('is'))],
+        [PREC.additive, choice('+', '-', kw('or'), kw('xor'))],
+        [PREC.multiplicative, choice('*', '/', kw('div'), kw('mod'), kw('and'), kw('shl'), kw('shr'), kw('as'))],
+      ];
+      return choice(...table.map(([precedence, operator]) => prec.left(
+        /** @type {number} */ (precedence),
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
+        ),
+      )));
+    },
+
+    // Literals
+
+    identifier: _ => /&?[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    // 42, $FF, %1010, &17, 3.14, 1e-9
+    number: _ => token(choice(
+      /\d+/,
+      /\d+\.\d+([eE][+-]?\d+)?/,
+      /\d+[eE][+-]?\d+/,
+      /\$[0-9a-fA-F]+/,
+      /%[01]+/,
+      /&[0-7]+/,
+    )),
+
+    // 'It''s'#13#10'done', #$41
+    string: _ => token(repeat1(choice(
+      /'([^'\n]|'')*'/,
+      /#(\d+|\$[0-9a-fA-F]+)/,
+    ))),
+
+    // {$IFDEF DEBUG}, (*$R+*)
+    compiler_directive: _ => token(prec(1, choice(
+      seq('{$', /[^}]*/, '}'),
+      seq('(*$', /([^*]|\*+[^*)])*/, /\*+\)/),
+    ))),
+
+    comment: _ => token(choice(
+      seq('//', /[^\n]*/),
+      seq('{', /[^}]*/, '}'),
+      seq('(*', /([^*]|\*+[^*)])*/, /\*+\)/),
+    )),
+  },
+});
+
+/**
+ * A case-insensitive keyword, named after its lowercase spelling.
+ *
+ * @param {string} word
+ * @returns {AliasRule}
+ */
+function kw(word) {
+  const pattern = word.split('').map(ch => /[a-z]/.test(ch) ? `[${ch}${ch.toUpperCase()}]` : ch).join('');
+  return alias(token(prec(1, new RegExp(pattern))), word);
+}
+
+/**
+ * One or more `rule`s separated by `separator`.
+ *
+ * @param {RuleOrLiteral} rule
+ * @param {RuleOrLiteral} separator
+ * @returns {SeqRule}
+ */
+function sep1(rule, separator) {
+  return seq(rule, repeat(seq(separator, rule)));
+}
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return sep1(rule, ',');
+}
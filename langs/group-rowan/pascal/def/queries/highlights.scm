@@ -0,0 +1,278 @@
+; Identifiers
+
+(identifier) @variable
+
+((identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+((identifier) @variable.builtin
+  (#any-of? @variable.builtin "Self" "self" "Result" "result"))
+
+; Keywords
+
+[
+  "program"
+  "unit"
+  "library"
+  "interface"
+  "implementation"
+  "initialization"
+  "finalization"
+  "begin"
+  "end"
+  "const"
+  "resourcestring"
+  "type"
+  "var"
+  "threadvar"
+  "label"
+  "procedure"
+  "function"
+  "constructor"
+  "destructor"
+  "operator"
+  "class"
+  "object"
+  "record"
+  "dispinterface"
+  "property"
+  "array"
+  "set"
+  "file"
+  "of"
+  "reference"
+  "packed"
+  "with"
+  "asm"
+  "inherited"
+  "absolute"
+  "out"
+  "constref"
+  "read"
+  "write"
+  "stored"
+  "default"
+  "nodefault"
+  "index"
+  "implements"
+  "name"
+  "message"
+  "dispid"
+  "delayed"
+] @keyword
+
+[
+  "uses"
+  "exports"
+  "in"
+] @keyword.import
+
+[
+  "strict"
+  "private"
+  "protected"
+  "public"
+  "published"
+  "automated"
+  "sealed"
+  "abstract"
+  "assembler"
+  "cdecl"
+  "deprecated"
+  "dynamic"
+  "experimental"
+  "external"
+  "final"
+  "forward"
+  "inline"
+  "overload"
+  "override"
+  "platform"
+  "pascal"
+  "register"
+  "reintroduce"
+  "safecall"
+  "static"
+  "stdcall"
+  "varargs"
+  "virtual"
+  "winapi"
+] @keyword.modifier
+
+[
+  "if"
+  "then"
+  "else"
+  "case"
+  "otherwise"
+] @keyword.conditional
+
+[
+  "for"
+  "to"
+  "downto"
+  "do"
+  "while"
+  "repeat"
+  "until"
+] @keyword.repeat
+
+[
+  "try"
+  "except"
+  "finally"
+  "raise"
+  "on"
+  "at"
+] @keyword.exception
+
+"goto" @keyword.return
+
+[
+  "and"
+  "or"
+  "xor"
+  "not"
+  "div"
+  "mod"
+  "shl"
+  "shr"
+  "is"
+  "as"
+] @keyword.operator
+
+; Types
+
+(type_reference
+  (identifier) @type)
+
+(string_type
+  "string" @type.builtin)
+
+((type_reference
+  (identifier) @type.builtin)
+  (#any-of? @type.builtin
+    "Boolean" "Byte" "Cardinal" "Char" "Currency" "Double" "Extended"
+    "Int64" "Integer" "LongInt" "NativeInt" "Pointer" "Real" "ShortInt"
+    "Single" "SmallInt" "UInt64" "Variant" "WideChar" "Word"))
+
+(type_declaration
+  name: (identifier) @type.definition)
+
+(generic_parameters
+  (identifier) @type)
+
+(enum_type
+  member: (identifier) @constant)
+
+; Declarations
+
+(module_name
+  (identifier) @module)
+
+(routine_name
+  (identifier) @function)
+
+(routine_name
+  (identifier) @type
+  .
+  "."
+  (identifier))
+
+(parameter
+  name: (identifier) @variable.parameter)
+
+(field_declaration
+  name: (identifier) @property)
+
+(property_declaration
+  name: (identifier) @property)
+
+(const_declaration
+  name: (identifier) @constant)
+
+(member_expression
+  member: (identifier) @property)
+
+(exception_handler
+  variable: (identifier) @variable)
+
+(visibility) @keyword.modifier
+
+; Calls
+
+(call_expression
+  function: (identifier) @function.call)
+
+(call_expression
+  function: (member_expression
+    member: (identifier) @function.method.call))
+
+(inherited_expression
+  member: (identifier) @function.method.call)
+
+((call_expression
+  function: (identifier) @function.builtin)
+  (#any-of? @function.builtin
+    "Abs" "Assert" "Assign" "Assigned" "Break" "Chr" "Concat" "Continue"
+    "Copy" "Dec" "Delete" "Dispose" "Exclude" "Exit" "FillChar" "Format"
+    "FreeAndNil" "GetMem" "Halt" "High" "Inc" "Include" "Insert" "IntToStr"
+    "Length" "Low" "New" "Ord" "Pos" "Pred" "Read" "ReadLn" "Round"
+    "SetLength" "SizeOf" "Sqr" "Sqrt" "StrToInt" "Succ" "Trunc" "Write"
+    "WriteLn"))
+
+((expression_statement
+  (identifier) @keyword.return)
+  (#any-of? @keyword.return "Exit" "Break" "Continue" "exit" "break" "continue"))
+
+; Literals
+
+(number) @number
+
+(string) @string
+
+(nil) @constant.builtin
+
+((identifier) @boolean
+  (#any-of? @boolean "True" "False" "true" "false"))
+
+(comment) @comment
+
+(compiler_directive) @attribute
+
+; Operators and punctuation
+
+[
+  ":="
+  "+="
+  "-="
+  "*="
+  "/="
+  "="
+  "<>"
+  "<"
+  "<="
+  ">"
+  ">="
+  "+"
+  "-"
+  "*"
+  "/"
+  "@"
+  "^"
+  ".."
+] @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+] @punctuation.bracket
+
+[
+  ","
+  ":"
+  ";"
+  "."
+] @punctuation.delimiter
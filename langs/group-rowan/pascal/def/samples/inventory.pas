@@ -0,0 +1,140 @@
+unit Inventory;
+
+{$MODE DELPHI}{$H+}
+
+interface
+
+uses
+  SysUtils, Classes, Generics.Collections;
+
+type
+  EOutOfStock = class(Exception);
+
+  TCategory = (catTool, catPart, catConsumable);
+
+  { A single stock-keeping unit. }
+  TItem = class
+  strict private
+    FName: string;
+    FQuantity: Integer;
+    FCategory: TCategory;
+    procedure SetQuantity(const Value: Integer);
+  public
+    constructor Create(const AName: string; AQuantity: Integer); virtual;
+    function Describe: string;
+    property Name: string read FName;
+    property Quantity: Integer read FQuantity write SetQuantity default 0;
+    property Category: TCategory read FCategory write FCategory;
+  end;
+
+  TItemList = TObjectList<TItem>;
+
+  TStock = class(TObject)
+  private
+    FItems: TItemList;
+    function GetItem(Index: Integer): TItem;
+  public
+    constructor Create;
+    destructor Destroy; override;
+    procedure Take(const AName: string; Count: Integer);
+    property Items[Index: Integer]: TItem read GetItem; default;
+  end;
+
+const
+  MAX_QUANTITY = $FFFF;
+  Separator: string = ' | ';
+
+implementation
+
+resourcestring
+  SOutOfStock = 'Only %d left of ''%s''';
+
+{ TItem }
+
+constructor TItem.Create(const AName: string; AQuantity: Integer);
+begin
+  inherited Create;
+  FName := AName;
+  Quantity := AQuantity;
+end;
+
+procedure TItem.SetQuantity(const Value: Integer);
+begin
+  if (Value < 0) or (Value > MAX_QUANTITY) then
+    raise ERangeError.CreateFmt('Bad quantity: %d', [Value]);
+  FQuantity := Value;
+end;
+
+function TItem.Describe: string;
+const
+  Names: array[TCategory] of string = ('tool', 'part', 'consumable');
+begin
+  Result := Format('%-12s', [FName]) + Separator + Names[FCategory];
+  (* Quantities above a thousand get a marker. *)
+  if FQuantity >= 1000 then
+    Result := Result + #32'*';
+end;
+
+{ TStock }
+
+constructor TStock.Create;
+begin
+  FItems := TItemList.Create(True);
+end;
+
+destructor TStock.Destroy;
+begin
+  FreeAndNil(FItems);
+  inherited;
+end;
+
+function TStock.GetItem(Index: Integer): TItem;
+begin
+  Result := FItems[Index];
+end;
+
+procedure TStock.Take(const AName: string; Count: Integer);
+var
+  Item: TItem;
+  I: Integer;
+begin
+  for I := FItems.Count - 1 downto 0 do
+  begin
+    Item := FItems[I];
+    if not SameText(Item.Name, AName) then
+      Continue;
+    if Item.Quantity < Count then
+      raise EOutOfStock.CreateFmt(SOutOfStock, [Item.Quantity, AName]);
+    Item.Quantity := Item.Quantity - Count;
+    Exit;
+  end;
+  {$IFDEF DEBUG}
+  WriteLn('Unknown item: ', AName);
+  {$ENDIF}
+end;
+
+procedure Report(Stock: TStock);
+var
+  Item: TItem;
+begin
+  try
+    for Item in Stock.FItems do
+      case Item.Category of
+        catTool: WriteLn('T ', Item.Describe);
+        catPart, catConsumable: WriteLn('  ', Item.Describe);
+      else
+        WriteLn('?');
+      end;
+  except
+    on E: EOutOfStock do
+      WriteLn('Stock error: ', E.Message);
+  end;
+end;
+
+initialization
+  Randomize;
+
+finalization
+  WriteLn('Inventory unloaded');
+
+end.
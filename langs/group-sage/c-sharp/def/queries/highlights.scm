@@ -45,11 +45,20 @@
   (string_literal)
   (raw_string_literal)
   (verbatim_string_literal)
-  (interpolated_string_expression)
   (interpolation_start)
   (interpolation_quote)
  ] @string
 
+; Only the text of interpolated strings, so the holes highlight as code
+
+(interpolated_string_expression
+  [
+    (string_content)
+    "\""
+  ] @string)
+
+(interpolation_format_clause) @string.special
+
 (escape_sequence) @string.escape
 
 [
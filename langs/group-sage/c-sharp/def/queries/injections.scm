@@ -0,0 +1,163 @@
+; Regular expressions
+; e.g.: new Regex("^[0-9]+$"), Regex.IsMatch(input, """\b\w+\b""")
+; Verbatim strings are a single token that includes the @" and ", so only
+; regular and raw string contents are injected.
+
+(object_creation_expression
+  type: (identifier) @_type
+  arguments: (argument_list
+    .
+    (argument
+      [
+        (string_literal
+          (string_literal_content) @injection.content)
+        (raw_string_literal
+          (raw_string_content) @injection.content)
+      ]))
+  (#eq? @_type "Regex")
+  (#set! injection.language "regex"))
+
+(invocation_expression
+  function: (member_access_expression
+    expression: (identifier) @_type
+    name: (identifier) @_method)
+  arguments: (argument_list
+    .
+    (argument)
+    .
+    (argument
+      [
+        (string_literal
+          (string_literal_content) @injection.content)
+        (raw_string_literal
+          (raw_string_content) @injection.content)
+      ]))
+  (#eq? @_type "Regex")
+  (#any-of? @_method "IsMatch" "Match" "Matches" "Replace" "Split" "Count" "EnumerateMatches")
+  (#set! injection.language "regex"))
+
+; Raw strings tagged with a language comment, as IDEs do
+; e.g.:
+;   // lang=json
+;   var payload = """{ "id": 1 }""";
+
+((comment) @_pragma
+  .
+  [
+    (local_declaration_statement
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+    (field_declaration
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+  ]
+  (#match? @_pragma "^//\\s*lang(uage)?\\s*=\\s*json\\s*$")
+  (#set! injection.language "json"))
+
+((comment) @_pragma
+  .
+  [
+    (local_declaration_statement
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+    (field_declaration
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+  ]
+  (#match? @_pragma "^//\\s*lang(uage)?\\s*=\\s*xml\\s*$")
+  (#set! injection.language "xml"))
+
+((comment) @_pragma
+  .
+  [
+    (local_declaration_statement
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+    (field_declaration
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+  ]
+  (#match? @_pragma "^//\\s*lang(uage)?\\s*=\\s*sql\\s*$")
+  (#set! injection.language "sql"))
+
+((comment) @_pragma
+  .
+  [
+    (local_declaration_statement
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+    (field_declaration
+      (variable_declaration
+        (variable_declarator
+          (raw_string_literal
+            (raw_string_content) @injection.content))))
+  ]
+  (#match? @_pragma "^//\\s*lang(uage)?\\s*=\\s*regex\\s*$")
+  (#set! injection.language "regex"))
+
+; SQL passed to ADO.NET commands and Dapper
+
+(object_creation_expression
+  type: (identifier) @_type
+  arguments: (argument_list
+    .
+    (argument
+      [
+        (string_literal
+          (string_literal_content) @injection.content)
+        (raw_string_literal
+          (raw_string_content) @injection.content)
+      ]))
+  (#match? @_type "^(Sql|Npgsql|Sqlite|SQLite|MySql|Oracle|Odbc|OleDb|Db)Command$")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|merge|with|create|alter|drop|exec)\\s")
+  (#set! injection.language "sql"))
+
+(assignment_expression
+  left: (member_access_expression
+    name: (identifier) @_property)
+  right: [
+    (string_literal
+      (string_literal_content) @injection.content)
+    (raw_string_literal
+      (raw_string_content) @injection.content)
+  ]
+  (#eq? @_property "CommandText")
+  (#set! injection.language "sql"))
+
+(invocation_expression
+  function: (member_access_expression
+    name: [
+      (identifier) @_method
+      (generic_name
+        (identifier) @_method)
+    ])
+  arguments: (argument_list
+    .
+    (argument
+      [
+        (string_literal
+          (string_literal_content) @injection.content)
+        (raw_string_literal
+          (raw_string_content) @injection.content)
+      ]))
+  (#any-of? @_method
+    "Query" "QueryAsync" "QueryFirst" "QueryFirstAsync" "QueryFirstOrDefault" "QueryFirstOrDefaultAsync"
+    "QuerySingle" "QuerySingleAsync" "QuerySingleOrDefault" "QuerySingleOrDefaultAsync"
+    "QueryMultiple" "QueryMultipleAsync" "Execute" "ExecuteAsync" "ExecuteScalar" "ExecuteScalarAsync"
+    "ExecuteReader" "ExecuteReaderAsync")
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|merge|with|create|alter|drop|exec)\\s")
+  (#set! injection.language "sql"))
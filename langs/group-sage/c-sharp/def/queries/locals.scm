@@ -0,0 +1,49 @@
+; Scopes
+;-------
+
+[
+  (method_declaration)
+  (constructor_declaration)
+  (local_function_statement)
+  (lambda_expression)
+  (anonymous_method_expression)
+  (block)
+  (for_statement)
+  (foreach_statement)
+  (using_statement)
+  (catch_clause)
+] @local.scope
+
+; Definitions
+;------------
+
+(parameter
+  name: (identifier) @local.definition.variable.parameter)
+
+(lambda_expression
+  parameters: (implicit_parameter) @local.definition.variable.parameter)
+
+(variable_declarator
+  name: (identifier) @local.definition.var)
+
+(variable_declarator
+  (tuple_pattern
+    name: (identifier) @local.definition.var))
+
+(foreach_statement
+  left: (identifier) @local.definition.var)
+
+(catch_declaration
+  name: (identifier) @local.definition.var)
+
+(declaration_expression
+  name: (identifier) @local.definition.var)
+
+((local_function_statement
+  name: (identifier) @local.definition.function)
+ (#set! definition.function.scope "parent"))
+
+; References
+;-----------
+
+(identifier) @local.reference
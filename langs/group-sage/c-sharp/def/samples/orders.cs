@@ -0,0 +1,71 @@
+using System;
+using System.Collections.Generic;
+using System.Data;
+using System.Linq;
+using System.Text.RegularExpressions;
+using Dapper;
+
+namespace Shop;
+
+public record Order(int Id, string Customer, decimal Total, DateTime PlacedAt);
+
+public sealed class OrderReport
+{
+    private static readonly Regex OrderCode = new Regex("^ORD-[0-9]{6}$");
+
+    // lang=json
+    private const string SampleOrder = """
+        { "id": 42, "customer": "Ada", "total": 12.5 }
+        """;
+
+    private readonly IDbConnection _connection;
+
+    public OrderReport(IDbConnection connection) => _connection = connection;
+
+    public IEnumerable<Order> Recent(int days)
+    {
+        return _connection.Query<Order>(
+            "SELECT Id, Customer, Total, PlacedAt FROM Orders WHERE PlacedAt > @since",
+            new { since = DateTime.UtcNow.AddDays(-days) });
+    }
+
+    public int Archive(DateTime before)
+    {
+        using var command = _connection.CreateCommand();
+        command.CommandText = "DELETE FROM Orders WHERE PlacedAt < @before";
+        var parameter = command.CreateParameter();
+        parameter.ParameterName = "@before";
+        parameter.Value = before;
+        command.Parameters.Add(parameter);
+        return command.ExecuteNonQuery();
+    }
+
+    public static bool IsOrderCode(string text) =>
+        OrderCode.IsMatch(text) || Regex.IsMatch(text, """^LEGACY-\d+$""");
+
+    public string Summarize(IReadOnlyList<Order> orders)
+    {
+        var total = orders.Sum(order => order.Total);
+        var largest = orders.MaxBy(o => o.Total);
+        return $"{orders.Count} orders, {total:C2} in total, largest {largest?.Customer ?? "none"} " +
+            $"({(largest is null ? 0 : largest.Total / Math.Max(total, 1m)),6:P1} of revenue)";
+    }
+
+    public static string Describe(Order order)
+    {
+        string Age(DateTime placed)
+        {
+            var days = (DateTime.UtcNow - placed).Days;
+            return days switch
+            {
+                0 => "today",
+                1 => "yesterday",
+                _ => $"{days} days ago",
+            };
+        }
+
+        return $$"""
+            {"order": "{{order.Id}}", "placed": "{{Age(order.PlacedAt)}}"}
+            """;
+    }
+}
@@ -0,0 +1,102 @@
+//! C#'s injections, locals and interpolated string highlights.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::assertions::{failed_assertions, parse_assertions};
+use arborium_test_harness::helpers;
+
+fn highlight(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_c_sharp::language(),
+        helpers::queries!(arborium_c_sharp),
+        source,
+    )
+}
+
+fn injected<'a>(source: &'a str, language: &str) -> Vec<&'a str> {
+    helpers::injected_as(&highlight(source), source, language)
+}
+
+fn assert_highlights(source: &str) {
+    let assertions = parse_assertions(source, &["//"]).expect("Invalid assertions");
+    let failures = failed_assertions(source, &highlight(source), &assertions);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_regex_arguments_inject_regex() {
+    let source = "\
+class C {
+    Regex a = new Regex(\"^[0-9]+$\");
+    bool b = Regex.IsMatch(input, \"[a-z]+\");
+    bool c = Regex.IsMatch(\"not a pattern\");
+}
+";
+    assert_eq!(injected(source, "regex"), ["^[0-9]+$", "[a-z]+"]);
+}
+
+#[test]
+fn test_language_comments_tag_raw_strings() {
+    let source = "\
+class C {
+    // lang=json
+    const string Payload = \"\"\"{ \"id\": 1 }\"\"\";
+    const string Untagged = \"\"\"{ \"id\": 2 }\"\"\";
+}
+";
+    let json = injected(source, "json");
+    assert_eq!(json.len(), 1, "{:?}", json);
+    assert!(json[0].contains("\"id\": 1"));
+}
+
+#[test]
+fn test_database_calls_inject_sql() {
+    let source = "\
+class C {
+    void M() {
+        connection.Query<Order>(\"SELECT * FROM Orders\");
+        command.CommandText = \"DELETE FROM Orders\";
+        var cmd = new SqlCommand(\"UPDATE Orders SET Total = 0\", connection);
+        connection.Execute(\"not sql\");
+    }
+}
+";
+    assert_eq!(
+        injected(source, "sql"),
+        [
+            "SELECT * FROM Orders",
+            "DELETE FROM Orders",
+            "UPDATE Orders SET Total = 0"
+        ]
+    );
+}
+
+#[test]
+fn test_interpolation_holes_highlight_as_code() {
+    assert_highlights(
+        "\
+class C {
+    string M(int count) => $\"{count} items, {Math.Max(count, 1):D3} shown\";
+    //                      ^ string
+    //                        ^ !string
+    //                                       ^ !string
+    //                                            ^ function
+    //                                                         ^ string.special
+    //                                                              ^ string
+}
+",
+    );
+}
+
+#[test]
+#[ignore = "the plugin runtime doesn't resolve locals yet"]
+fn test_parameters_highlight_at_use_sites() {
+    assert_highlights(
+        "\
+class C {
+    int Scale(int factor, int[] values) => values.Sum(v => v * factor);
+    //                                     ^ variable.parameter
+    //                                                         ^ variable.parameter
+}
+",
+    );
+}
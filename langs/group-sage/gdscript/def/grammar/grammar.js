@@ -0,0 +1,608 @@
+/**
+ * @file GDScript grammar for tree-sitter
+ * @license MIT
+ *
+ * Targets Godot 4, with Godot 3's `setget`, `onready` and `export` keywords
+ * still accepted. Indentation is tracked by the external scanner.
+ * https://docs.godotengine.org/en/stable/tutorials/scripting/gdscript/gdscript_basics.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  lambda: -1,
+  cast: 1,
+  conditional: 2,
+  or: 3,
+  and: 4,
+  not: 5,
+  in: 6,
+  comparison: 7,
+  bitor: 8,
+  bitxor: 9,
+  bitand: 10,
+  shift: 11,
+  additive: 12,
+  multiplicative: 13,
+  unary: 14,
+  bitnot: 15,
+  power: 16,
+  is: 17,
+  await: 18,
+  call: 19,
+};
+
+module.exports = grammar({
+  name: 'gdscript',
+
+  extras: $ => [/\s|\\\r?\n/, $.comment],
+
+  externals: $ => [
+    $._newline,
+    $._indent,
+    $._dedent,
+
+    // Comments are external so that the scanner runs on every token and can
+    // keep emitting dedents while recovering from errors.
+    $.comment,
+
+    // Lets the scanner avoid dedents between brackets.
+    ')',
+    ']',
+    '}',
+  ],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source: $ => repeat($._statement),
+
+    _statement: $ => choice(
+      $._simple_statements,
+      $._compound_statement,
+    ),
+
+    _simple_statements: $ => seq(
+      $._simple_statement,
+      repeat(seq(';', $._simple_statement)),
+      optional(';'),
+      $._newline,
+    ),
+
+    _simple_statement: $ => choice(
+      $.extends_statement,
+      $.class_name_statement,
+      $.variable_statement,
+      $.const_statement,
+      $.signal_statement,
+      $.annotations,
+      $.expression_statement,
+      $.assignment,
+      $.augmented_assignment,
+      $.return_statement,
+      $.pass_statement,
+      $.break_statement,
+      $.continue_statement,
+      $.breakpoint_statement,
+    ),
+
+    _compound_statement: $ => choice(
+      $.function_definition,
+      $.constructor_definition,
+      $.class_definition,
+      $.enum_definition,
+      $.if_statement,
+      $.for_statement,
+      $.while_statement,
+      $.match_statement,
+    ),
+
+    // Declarations
+
+    extends_statement: $ => seq(
+      'extends',
+      choice($.type, $.string),
+    ),
+
+    class_name_statement: $ => seq(
+      'class_name',
+      field('name', $._name),
+      optional(seq(',', field('icon', $.string))),
+      optional($.extends_statement),
+    ),
+
+    // @export, @export_range(0, 10), @onready
+    annotation: $ => seq(
+      '@',
+      field('name', $.identifier),
+      optional(field('arguments', $.arguments)),
+    ),
+
+    annotations: $ => repeat1($.annotation),
+
+    variable_statement: $ => seq(
+      optional($.annotations),
+      optional(choice('static', 'onready', seq('export', optional($.arguments)))),
+      'var',
+      field('name', $._name),
+      optional($._variable_type),
+      optional(seq('=', field('value', $._expression))),
+      optional(field('setget', choice($.setget, $.property_accessors))),
+    ),
+
+    _variable_type: $ => choice(
+      seq(':', field('type', $.type)),
+      $.inferred_type,
+    ),
+
+    inferred_type: _ => ':=',
+
+    // Godot 3: `var health = 100 setget set_health, get_health`
+    setget: $ => seq(
+      'setget',
+      optional(field('setter', $.identifier)),
+      optional(seq(',', field('getter', $.identifier))),
+    ),
+
+    // Godot 4: an indented block of `set(value):` and `get:` bodies
+    property_accessors: $ => seq(
+      ':',
+      $._indent,
+      repeat1(choice($.set_body, $.get_body)),
+      $._dedent,
+    ),
+
+    set_body: $ => choice(
+      seq('set', '(', field('parameter', $.identifier), ')', ':', field('body', $.body)),
+      seq('set', '=', field('function', $.identifier), optional(',')),
+    ),
+
+    get_body: $ => choice(
+      seq('get', ':', field('body', $.body)),
+      seq('get', '=', field('function', $.identifier), optional(',')),
+    ),
+
+    const_statement: $ => seq(
+      'const',
+      field('name', $._name),
+      optional($._variable_type),
+      '=',
+      field('value', $._expression),
+    ),
+
+    signal_statement: $ => seq(
+      'signal',
+      field('name', $._name),
+      optional(field('parameters', $.parameters)),
+    ),
+
+    enum_definition: $ => seq(
+      'enum',
+      optional(field('name', $._name)),
+      field('body', $.enumerator_list),
+      $._newline,
+    ),
+
+    enumerator_list: $ => seq(
+      '{',
+      optional(seq(commaSep1($.enumerator), optional(','))),
+      '}',
+    ),
+
+    enumerator: $ => seq(
+      field('left', $.identifier),
+      optional(seq('=', field('right', $._expression))),
+    ),
+
+    function_definition: $ => seq(
+      optional($.annotations),
+      optional('static'),
+      'func',
+      field('name', $._name),
+      field('parameters', $.parameters),
+      optional(seq('->', field('return_type', $.type))),
+      ':',
+      field('body', $.body),
+    ),
+
+    constructor_definition: $ => seq(
+      'func',
+      '_init',
+      field('parameters', $.parameters),
+      optional(seq('.', field('super_arguments', $.arguments))),
+      optional(seq('->', field('return_type', $.type))),
+      ':',
+      field('body', $.body),
+    ),
+
+    parameters: $ => seq(
+      '(',
+      optional(seq(commaSep1($._parameter), optional(','))),
+      ')',
+    ),
+
+    _parameter: $ => choice(
+      $.identifier,
+      $.typed_parameter,
+      $.default_parameter,
+      $.typed_default_parameter,
+    ),
+
+    typed_parameter: $ => seq($.identifier, ':', field('type', $.type)),
+
+    default_parameter: $ => seq(
+      $.identifier,
+      choice('=', alias(':=', $.inferred_type)),
+      field('value', $._expression),
+    ),
+
+    typed_default_parameter: $ => seq(
+      $.identifier,
+      ':',
+      field('type', $.type),
+      '=',
+      field('value', $._expression),
+    ),
+
+    class_definition: $ => seq(
+      'class',
+      field('name', $._name),
+      optional(field('extends', $.extends_statement)),
+      ':',
+      field('body', $.class_body),
+    ),
+
+    class_body: $ => choice(
+      $._simple_statements,
+      seq($._indent, repeat($._statement), $._dedent),
+    ),
+
+    // Node2D, Foo.Bar, Array[int], Dictionary[String, int]
+    type: $ => prec.right(seq(
+      $.identifier,
+      repeat(seq('.', $.identifier)),
+      optional(seq('[', commaSep1($.type), ']')),
+    )),
+
+    _name: $ => alias($.identifier, $.name),
+
+    // Control flow
+
+    body: $ => choice(
+      $._simple_statements,
+      seq($._indent, repeat($._statement), $._dedent),
+    ),
+
+    if_statement: $ => seq(
+      'if',
+      field('condition', $._expression),
+      ':',
+      field('body', $.body),
+      repeat(field('alternative', $.elif_clause)),
+      optional(field('alternative', $.else_clause)),
+    ),
+
+    elif_clause: $ => seq(
+      'elif',
+      field('condition', $._expression),
+      ':',
+      field('body', $.body),
+    ),
+
+    else_clause: $ => seq('else', ':', field('body', $.body)),
+
+    for_statement: $ => seq(
+      'for',
+      field('left', $.identifier),
+      optional(seq(':', field('type', $.type))),
+      'in',
+      field('right', $._expression),
+      ':',
+      field('body', $.body),
+    ),
+
+    while_statement: $ => seq(
+      'while',
+      field('condition', $._expression),
+      ':',
+      field('body', $.body),
+    ),
+
+    match_statement: $ => seq(
+      'match',
+      field('value', $._expression),
+      ':',
+      field('body', $.match_body),
+    ),
+
+    match_body: $ => seq($._indent, repeat1($.pattern_section), $._dedent),
+
+    pattern_section: $ => seq(
+      commaSep1($._pattern),
+      optional(seq('when', field('guard', $._expression))),
+      ':',
+      field('body', $.body),
+    ),
+
+    _pattern: $ => choice(
+      $._expression,
+      $.pattern_binding,
+      $.pattern_open_ending,
+    ),
+
+    pattern_binding: $ => seq('var', $.identifier),
+
+    pattern_open_ending: _ => '..',
+
+    // Simple statements
+
+    expression_statement: $ => $._expression,
+
+    assignment: $ => seq(
+      field('left', $._expression),
+      '=',
+      field('right', $._expression),
+    ),
+
+    augmented_assignment: $ => seq(
+      field('left', $._expression),
+      field('operator', choice('+=', '-=', '*=', '/=', '%=', '**=', '&=', '|=', '^=', '<<=', '>>=')),
+      field('right', $._expression),
+    ),
+
+    return_statement: $ => seq('return', optional($._expression)),
+
+    pass_statement: _ => 'pass',
+
+    break_statement: _ => 'break',
+
+    continue_statement: _ => 'continue',
+
+    breakpoint_statement: _ => 'breakpoint',
+
+    // Expressions
+
+    _expression: $ => choice(
+      $._primary_expression,
+      $.not_operator,
+      $.and_operator,
+      $.or_operator,
+      $.binary_operator,
+      $.comparison_operator,
+      $.conditional_expression,
+      $.cast_expression,
+      $.lambda,
+    ),
+
+    _primary_expression: $ => choice(
+      $.identifier,
+      $.integer,
+      $.float,
+      $.string,
+      $.string_name,
+      $.node_path,
+      $.get_node,
+      $.unique_node,
+      $.true,
+      $.false,
+      $.null,
+      $.self,
+      $.super,
+      $.array,
+      $.dictionary,
+      $.attribute,
+      $.subscript,
+      $.call,
+      $.unary_operator,
+      $.await_expression,
+      $.parenthesized_expression,
+    ),
+
+    not_operator: $ => prec(PREC.not, seq(
+      choice('not', '!'),
+      field('argument', $._expression),
+    )),
+
+    and_operator: $ => prec.left(PREC.and, seq(
+      field('left', $._expression),
+      field('operator', choice('and', '&&')),
+      field('right', $._expression),
+    )),
+
+    or_operator: $ => prec.left(PREC.or, seq(
+      field('left', $._expression),
+      field('operator', choice('or', '||')),
+      field('right', $._expression),
+    )),
+
+    binary_operator: $ => {
+      const table = [
+        [prec.left, PREC.in, choice('in', seq('not', 'in'))],
+        [prec.left, PREC.bitor, '|'],
+        [prec.left, PREC.bitxor, '^'],
+        [prec.left, PREC.bitand, '&'],
+        [prec.left, PREC.shift, choice('<<', '>>')],
+        [prec.left, PREC.additive, choice('+', '-')],
+        [prec.left, PREC.multiplicative, choice('*', '/', '%')],
+        [prec.right, PREC.power, '**'],
+        [prec.left, PREC.is, choice('is', seq('is', 'not'))],
+      ];
+      return choice(...table.map(([fn, precedence, operator]) => /** @type {Function} */ (fn)(
+        precedence,
+        seq(
+          field('left', $._expression),
+          field('operator', /** @type {RuleOrLiteral} */ (operator)),
+          field('right', $._expression),
+        ),
+      )));
+    },
+
+    comparison_operator: $ => prec.left(PREC.comparison, seq(
+      field('left', $._expression),
+      field('operator', choice('==', '!=', '<', '<=', '>', '>=')),
+      field('right', $._expression),
+    )),
+
+    unary_operator: $ => choice(
+      prec(PREC.unary, seq(field('operator', choice('-', '+')), field('argument', $._primary_expression))),
+      prec(PREC.bitnot, seq(field('operator', '~'), field('argument', $._primary_expression))),
+    ),
+
+    // value if condition else other
+    conditional_expression: $ => prec.right(PREC.conditional, seq(
+      $._expression,
+      'if',
+      $._expression,
+      'else',
+      $._expression,
+    )),
+
+    cast_expression: $ => prec.left(PREC.cast, seq(
+      field('value', $._expression),
+      'as',
+      field('type', $.type),
+    )),
+
+    await_expression: $ => prec(PREC.await, seq('await', $._primary_expression)),
+
+    // func(body): print(body.name), or an indented block
+    lambda: $ => prec(PREC.lambda, seq(
+      'func',
+      optional(field('name', $._name)),
+      field('parameters', $.parameters),
+      optional(seq('->', field('return_type', $.type))),
+      ':',
+      field('body', choice(
+        alias($._simple_statement, $.body),
+        alias(seq($._indent, repeat($._statement), $._dedent), $.body),
+      )),
+    )),
+
+    attribute: $ => prec(PREC.call, seq(
+      field('object', $._primary_expression),
+      '.',
+      field('attribute', $.identifier),
+    )),
+
+    subscript: $ => prec(PREC.call, seq(
+      field('value', $._primary_expression),
+      '[',
+      field('index', $._expression),
+      ']',
+    )),
+
+    call: $ => prec(PREC.call, seq(
+      field('function', $._primary_expression),
+      field('arguments', $.arguments),
+    )),
+
+    arguments: $ => seq(
+      '(',
+      optional(seq(commaSep1($._expression), optional(','))),
+      ')',
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    array: $ => seq(
+      '[',
+      optional(seq(commaSep1($._pattern), optional(','))),
+      ']',
+    ),
+
+    // {"key": value} and the Lua-style {key = value}
+    dictionary: $ => seq(
+      '{',
+      optional(seq(commaSep1(choice($.pair, $.pattern_open_ending)), optional(','))),
+      '}',
+    ),
+
+    pair: $ => choice(
+      seq(field('key', $._expression), ':', field('value', $._pattern)),
+      seq(field('key', $.identifier), '=', field('value', $._expression)),
+    ),
+
+    // Literals
+
+    identifier: _ => /[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    null: _ => 'null',
+
+    self: _ => 'self',
+
+    super: _ => 'super',
+
+    integer: _ => token(choice(
+      /0[xX][0-9a-fA-F_]+/,
+      /0[bB][01_]+/,
+      /\d[\d_]*/,
+    )),
+
+    float: _ => token(choice(
+      /\d[\d_]*\.[\d_]*([eE][+-]?\d+)?/,
+      /\.\d[\d_]*([eE][+-]?\d+)?/,
+      /\d[\d_]*[eE][+-]?\d+/,
+    )),
+
+    string: $ => choice(
+      seq(
+        '"',
+        repeat(choice(token.immediate(prec(1, /[^"\\\n]+/)), $.escape_sequence)),
+        token.immediate('"'),
+      ),
+      seq(
+        '\'',
+        repeat(choice(token.immediate(prec(1, /[^'\\\n]+/)), $.escape_sequence)),
+        token.immediate('\''),
+      ),
+      token(seq('"""', /([^"\\]|\\.|"[^"]|""[^"])*/, '"""')),
+      token(seq('\'\'\'', /([^'\\]|\\.|'[^']|''[^'])*/, '\'\'\'')),
+      token(seq('r', choice(/"[^"\n]*"/, /'[^'\n]*'/))),
+    ),
+
+    escape_sequence: _ => token.immediate(seq(
+      '\\',
+      choice(
+        /u[0-9a-fA-F]{4}/,
+        /U[0-9a-fA-F]{6}/,
+        /[^uU]/,
+      ),
+    )),
+
+    // &"body_entered"
+    string_name: _ => token(seq('&', choice(/"([^"\\\n]|\\.)*"/, /'([^'\\\n]|\\.)*'/))),
+
+    // ^"Path/To:property"
+    node_path: _ => token(seq('^', choice(/"([^"\\\n]|\\.)*"/, /'([^'\\\n]|\\.)*'/))),
+
+    // $Sprite2D, $UI/HealthBar, $"../Label", $%Unique
+    get_node: _ => token(seq(
+      '$',
+      choice(
+        /"[^"\n]*"/,
+        /'[^'\n]*'/,
+        /%?[a-zA-Z_][a-zA-Z0-9_]*(\/%?[a-zA-Z_][a-zA-Z0-9_]*)*/,
+      ),
+    )),
+
+    // %HealthBar
+    unique_node: _ => token(seq('%', choice(/"[^"\n]*"/, /[a-zA-Z_][a-zA-Z0-9_]*/))),
+
+    comment: _ => token(seq('#', /.*/)),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
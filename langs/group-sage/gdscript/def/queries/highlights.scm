@@ -0,0 +1,223 @@
+; Identifiers
+
+((identifier) @type
+  (#match? @type "^[A-Z][a-zA-Z0-9]*[a-z][a-zA-Z0-9]*$"))
+
+((identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+; Keywords
+
+[
+  "and"
+  "as"
+  "await"
+  "break"
+  "class"
+  "class_name"
+  "const"
+  "continue"
+  "elif"
+  "else"
+  "enum"
+  "export"
+  "extends"
+  "for"
+  "func"
+  "get"
+  "if"
+  "in"
+  "is"
+  "match"
+  "not"
+  "onready"
+  "or"
+  "return"
+  "set"
+  "setget"
+  "signal"
+  "static"
+  "var"
+  "when"
+  "while"
+] @keyword
+
+[
+  (pass_statement)
+  (breakpoint_statement)
+] @keyword
+
+"_init" @constructor
+
+; Annotations
+
+(annotation
+  "@" @attribute
+  name: (identifier) @attribute)
+
+; Types
+
+(type
+  (identifier) @type)
+
+(extends_statement
+  (string) @string.special.path)
+
+(class_name_statement
+  name: (name) @type)
+
+(class_definition
+  name: (name) @type)
+
+(enum_definition
+  name: (name) @type)
+
+(enumerator
+  left: (identifier) @constant)
+
+(inferred_type) @operator
+
+((identifier) @type.builtin
+  (#any-of? @type.builtin
+    "bool" "int" "float" "String" "StringName" "NodePath" "Array" "Dictionary"
+    "Vector2" "Vector2i" "Vector3" "Vector3i" "Vector4" "Vector4i" "Color" "Rect2"
+    "Transform2D" "Transform3D" "Basis" "Quaternion" "Callable" "Signal" "Variant"))
+
+; Functions and signals
+
+(function_definition
+  name: (name) @function)
+
+(lambda
+  name: (name) @function)
+
+(signal_statement
+  name: (name) @function)
+
+(call
+  function: (identifier) @function.call)
+
+(call
+  function: (attribute
+    attribute: (identifier) @function.method.call))
+
+((call
+  function: (identifier) @function.builtin)
+  (#any-of? @function.builtin
+    "preload" "load" "print" "print_debug" "printerr" "push_error" "push_warning"
+    "assert" "range" "len" "str" "typeof" "is_instance_valid" "get_node"))
+
+; Variables and properties
+
+(parameters
+  (identifier) @variable.parameter)
+
+(typed_parameter
+  (identifier) @variable.parameter)
+
+(default_parameter
+  (identifier) @variable.parameter)
+
+(typed_default_parameter
+  (identifier) @variable.parameter)
+
+(set_body
+  parameter: (identifier) @variable.parameter)
+
+(variable_statement
+  name: (name) @variable)
+
+(const_statement
+  name: (name) @constant)
+
+(attribute
+  attribute: (identifier) @property)
+
+(pair
+  key: (identifier) @property)
+
+[
+  (self)
+  (super)
+] @variable.builtin
+
+; Node paths and string names
+
+[
+  (get_node)
+  (unique_node)
+  (node_path)
+] @string.special.path
+
+(string_name) @string.special.symbol
+
+; Literals
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+[
+  (integer)
+  (float)
+] @number
+
+[
+  (true)
+  (false)
+] @boolean
+
+(null) @constant.builtin
+
+(comment) @comment
+
+((comment) @comment.documentation
+  (#match? @comment.documentation "^##"))
+
+; Operators and punctuation
+
+(binary_operator
+  operator: _ @operator)
+
+(comparison_operator
+  operator: _ @operator)
+
+(unary_operator
+  operator: _ @operator)
+
+[
+  "="
+  "+="
+  "-="
+  "*="
+  "/="
+  "%="
+  "**="
+  "&="
+  "|="
+  "^="
+  "<<="
+  ">>="
+  "->"
+  "&&"
+  "||"
+  "!"
+] @operator
+
+[
+  "."
+  ","
+  ":"
+  ";"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(pattern_open_ending) @punctuation.special
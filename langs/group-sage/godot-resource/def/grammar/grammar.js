@@ -0,0 +1,142 @@
+/**
+ * @file Godot resource grammar for tree-sitter
+ * @license MIT
+ *
+ * The text format shared by scenes (.tscn), resources (.tres) and
+ * project.godot: bracketed section headers followed by `key = value`
+ * properties, where values are Variant literals.
+ * https://docs.godotengine.org/en/stable/contributing/development/file_formats/tscn.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'godot_resource',
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    document: $ => seq(
+      repeat($.property),
+      repeat($.section),
+    ),
+
+    // [node name="Player" type="CharacterBody2D" parent="."]
+    section: $ => seq(
+      field('header', $.section_header),
+      repeat($.property),
+    ),
+
+    section_header: $ => seq(
+      '[',
+      field('name', $.identifier),
+      repeat($.attribute),
+      ']',
+    ),
+
+    attribute: $ => seq(
+      field('key', $.identifier),
+      '=',
+      field('value', $._value),
+    ),
+
+    // metadata/_edit_group_ = true
+    property: $ => seq(
+      field('key', $.path),
+      '=',
+      field('value', $._value),
+    ),
+
+    path: _ => /[a-zA-Z0-9_][a-zA-Z0-9_\/:.\-]*/,
+
+    // Values
+
+    _value: $ => choice(
+      $.string,
+      $.string_name,
+      $.node_path,
+      $.integer,
+      $.float,
+      $.true,
+      $.false,
+      $.null,
+      $.array,
+      $.dictionary,
+      $.constructor,
+    ),
+
+    array: $ => seq(
+      '[',
+      optional(seq(commaSep1($._value), optional(','))),
+      ']',
+    ),
+
+    dictionary: $ => seq(
+      '{',
+      optional(seq(commaSep1($.pair), optional(','))),
+      '}',
+    ),
+
+    pair: $ => seq(field('key', $._value), ':', field('value', $._value)),
+
+    // Vector2(0, 1), ExtResource("1_abc"), Array[int]([1, 2])
+    constructor: $ => seq(
+      field('name', $.identifier),
+      optional(seq('[', field('type', $.identifier), ']')),
+      field('arguments', $.arguments),
+    ),
+
+    arguments: $ => seq(
+      '(',
+      optional(seq(commaSep1($._value), optional(','))),
+      ')',
+    ),
+
+    // Literals
+
+    identifier: _ => /[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    true: _ => 'true',
+
+    false: _ => 'false',
+
+    null: _ => 'null',
+
+    integer: _ => /-?\d+/,
+
+    float: _ => token(choice(
+      /-?\d+\.\d*([eE][+-]?\d+)?/,
+      /-?\d+[eE][+-]?\d+/,
+    )),
+
+    // Strings may span lines, e.g. embedded script sources.
+    string: $ => seq(
+      '"',
+      repeat(choice(token.immediate(prec(1, /[^"\\]+/)), $.escape_sequence)),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: _ => token.immediate(/\\./),
+
+    // &"idle"
+    string_name: _ => token(seq('&', /"([^"\\]|\\.)*"/)),
+
+    // ^"UI/HealthBar"
+    node_path: _ => token(seq('^', /"([^"\\]|\\.)*"/)),
+
+    comment: _ => token(seq(';', /.*/)),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
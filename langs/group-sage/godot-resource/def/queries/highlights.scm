@@ -0,0 +1,64 @@
+; Sections
+
+(section_header
+  name: (identifier) @keyword)
+
+(attribute
+  key: (identifier) @property)
+
+(property
+  key: (path) @property)
+
+; Constructors
+
+(constructor
+  name: (identifier) @type)
+
+(constructor
+  type: (identifier) @type)
+
+((constructor
+  name: (identifier) @function.builtin)
+  (#any-of? @function.builtin "ExtResource" "SubResource" "Resource"))
+
+; Values
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(string_name) @string.special.symbol
+
+(node_path) @string.special.path
+
+[
+  (integer)
+  (float)
+] @number
+
+[
+  (true)
+  (false)
+] @boolean
+
+(null) @constant.builtin
+
+(comment) @comment
+
+; Punctuation
+
+[
+  "="
+  ":"
+] @operator
+
+"," @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
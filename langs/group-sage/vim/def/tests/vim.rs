@@ -0,0 +1,45 @@
+//! Vimscript is opt-in, so nothing else in the tree exercises it. Check a
+//! typical vimrc parses cleanly and highlights its commands.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const VIMRC: &str = "\
+set nocompatible
+set expandtab shiftwidth=4
+let mapleader = ' '
+nnoremap <Leader>w :write<CR>
+
+augroup vimrc
+  autocmd!
+  autocmd FileType make setlocal noexpandtab
+augroup END
+
+function! s:Trim() abort
+  if &modifiable
+    call clearmatches()
+  endif
+endfunction
+";
+
+#[test]
+fn test_vimrc_parses_cleanly() {
+    let tree = helpers::parse_tree(arborium_vim::language(), VIMRC);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_vimrc_highlights_commands() {
+    let mut highlighter = helpers::highlighter([(
+        "vim",
+        TestGrammar::new(
+            arborium_vim::language(),
+            arborium_vim::HIGHLIGHTS_QUERY,
+            arborium_vim::INJECTIONS_QUERY,
+        ),
+    )]);
+    let html = highlighter.highlight("vim", VIMRC).unwrap();
+    assert!(html.contains("<a-k>set</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>augroup</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>nnoremap</a-k>"), "{}", html);
+}
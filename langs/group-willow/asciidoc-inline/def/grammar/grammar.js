@@ -0,0 +1,106 @@
+/**
+ * @file AsciiDoc inline grammar for tree-sitter
+ * @license MIT
+ *
+ * Parses the text of an AsciiDoc line, which the block grammar leaves
+ * whole, for its inline markup: strong, emphasis, monospace, highlighted,
+ * superscript and subscript text, passthroughs, attribute references,
+ * cross references, links, inline macros and anchors.
+ *
+ * Constrained (`*word*`) and unconstrained (`**word**`) pairs are both
+ * single tokens. Anything else is plain text.
+ * https://docs.asciidoctor.org/asciidoc/latest/text/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+/**
+ * A constrained and an unconstrained pair of `mark`s around some text.
+ *
+ * @param {string} mark
+ */
+const pair = mark => {
+  const m = mark.replace(/[*^+]/, '\\$&');
+  return token(choice(
+    new RegExp(`${m}${m}([^${m}\\n]|${m}[^${m}\\n])+${m}${m}`),
+    new RegExp(`${m}[^\\s${m}]([^${m}\\n]*[^\\s${m}])?${m}`),
+  ));
+};
+
+module.exports = grammar({
+  name: 'asciidoc_inline',
+
+  extras: _ => [],
+
+  rules: {
+    inline: $ => repeat(choice(
+      $.strong,
+      $.emphasis,
+      $.monospace,
+      $.mark,
+      $.superscript,
+      $.subscript,
+      $.passthrough,
+      $.attribute_reference,
+      $.cross_reference,
+      $.link,
+      $.macro,
+      $.anchor,
+      $._word,
+      $._whitespace,
+      $._punctuation,
+    )),
+
+    // *strong* **strong** _emphasis_ `monospace` #mark#
+    strong: _ => pair('*'),
+
+    emphasis: _ => pair('_'),
+
+    monospace: _ => pair('`'),
+
+    mark: _ => pair('#'),
+
+    // E=mc^2^, H~2~O
+    superscript: _ => /\^[^\s^]+\^/,
+
+    subscript: _ => /~[^\s~]+~/,
+
+    // +{not-an-attribute}+, +++<u>raw</u>+++
+    passthrough: _ => token(choice(
+      /\+\+\+([^+\n]|\+[^+\n])+\+\+\+/,
+      /\+[^\s+]([^+\n]*[^\s+])?\+/,
+    )),
+
+    // {project-name}
+    attribute_reference: _ => /\{[A-Za-z0-9_][A-Za-z0-9_-]*\}/,
+
+    // <<install>>, <<install,Installing>>, xref:guide.adoc#install[Installing]
+    cross_reference: _ => token(choice(
+      /<<[^\s,>][^>\n]*>>/,
+      /xref:[^\s\[]+\[[^\]\n]*\]/,
+    )),
+
+    // https://asciidoctor.org[Asciidoctor], link:guide.html[], mailto:a@b.c[]
+    link: _ => token(choice(
+      /(https?|ftp|irc):\/\/[^\s\[]+(\[[^\]\n]*\])?/,
+      /(link|mailto):[^\s\[]+\[[^\]\n]*\]/,
+    )),
+
+    // kbd:[Ctrl+C], footnote:[A note.], image:logo.png[Logo]
+    macro: _ => /(kbd|btn|menu|footnote|footnoteref|pass|image|icon|anchor|stem|latexmath|asciimath|indexterm|indexterm2):[^\s\[]*\[[^\]\n]*\]/,
+
+    // [[install]], [#install]
+    anchor: _ => token(choice(
+      /\[\[[^\]\n]+\]\]/,
+      /\[#[^\]\n]+\]/,
+    )),
+
+    // Words keep inner underscores, so snake_case isn't emphasis.
+    _word: _ => /[A-Za-z0-9\u00C0-\uFFFF][A-Za-z0-9_\u00C0-\uFFFF]*/,
+
+    _whitespace: _ => /\s+/,
+
+    _punctuation: _ => /[^\sA-Za-z0-9\u00C0-\uFFFF]/,
+  },
+});
@@ -0,0 +1,25 @@
+(strong) @markup.bold
+
+(emphasis) @markup.italic
+
+[
+  (monospace)
+  (passthrough)
+] @markup.raw.inline
+
+(mark) @markup.bold
+
+[
+  (superscript)
+  (subscript)
+] @markup.math
+
+(attribute_reference) @constant.macro
+
+(cross_reference) @markup.link
+
+(link) @markup.link.url
+
+(macro) @function.macro
+
+(anchor) @label
@@ -0,0 +1,18 @@
+; [source,rust] and [,rust] listings, and literal blocks given a source style
+(section_block
+  (element_attr
+    (attr_value) @injection.language)
+  (listing_block
+    (listing_block_body) @injection.content)
+  (#match? @injection.language "^(source)?(%[^,]*)?,"))
+
+(section_block
+  (element_attr
+    (attr_value) @injection.language)
+  (literal_block
+    (literal_block_body) @injection.content)
+  (#match? @injection.language "^source(%[^,]*)?,"))
+
+; Lines are left whole by the block grammar and parsed by the inline one
+((line) @injection.content
+  (#set! injection.language "asciidoc-inline"))
@@ -0,0 +1,133 @@
+//! AsciiDoc source listings inject the language named in their style
+//! attribute, `[source,rust]`, and every line is handed to the internal
+//! inline grammar for its formatting and references.
+
+use arborium_test_harness::arborium_highlight::detect::injection_language_candidates;
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SAMPLE: &str = include_str!("../samples/guide.adoc");
+
+const SOURCE: &str = "\
+Read *this* first.
+
+[source,rust]
+----
+fn main() {}
+----
+
+[,python]
+----
+print(1)
+----
+
+[listing]
+----
+not code
+----
+";
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_asciidoc::language(),
+        helpers::queries!(arborium_asciidoc),
+        source,
+    )
+}
+
+fn highlight(source: &str) -> String {
+    let mut highlighter = helpers::highlighter([
+        (
+            "asciidoc",
+            TestGrammar::new(
+                arborium_asciidoc::language(),
+                arborium_asciidoc::HIGHLIGHTS_QUERY,
+                arborium_asciidoc::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "asciidoc-inline",
+            TestGrammar::new(
+                arborium_asciidoc_inline::language(),
+                arborium_asciidoc_inline::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "python",
+            TestGrammar::new(
+                arborium_python::language(),
+                &arborium_python::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    highlighter.set_injection_resolver(|language: &str| {
+        injection_language_candidates(language)
+            .into_iter()
+            .find(|name| ["asciidoc-inline", "rust", "python"].contains(&name.as_str()))
+    });
+    highlighter.highlight("asciidoc", source).unwrap()
+}
+
+#[test]
+fn test_source_listings_inject_their_style_language() {
+    let listings: Vec<_> = injected(SOURCE)
+        .into_iter()
+        .filter(|(_, language)| language != "asciidoc-inline")
+        .collect();
+    assert_eq!(listings.len(), 2, "{:?}", listings);
+    assert_eq!(listings[0].1, "source,rust");
+    assert!(listings[0].0.starts_with("fn main() {}"), "{:?}", listings);
+    assert_eq!(listings[1].1, ",python");
+    assert!(listings[1].0.starts_with("print(1)"), "{:?}", listings);
+}
+
+#[test]
+fn test_lines_inject_the_inline_grammar() {
+    let injections = injected(SOURCE);
+    assert!(
+        injections.iter().any(|(text, language)| {
+            language == "asciidoc-inline" && text.starts_with("Read *this* first.")
+        }),
+        "{:?}",
+        injections
+    );
+}
+
+#[test]
+fn test_listing_and_inline_captures() {
+    let html = highlight(SOURCE);
+    assert!(html.contains("Read <a-st>*this*</a-st> first."), "{}", html);
+    assert!(
+        html.contains("<a-k>fn</a-k> <a-f>main</a-f>() {}"),
+        "{}",
+        html
+    );
+    assert!(html.contains("not code"), "{}", html);
+}
+
+#[test]
+fn test_sample_captures() {
+    let html = highlight(SAMPLE);
+    assert!(html.contains("<a-st>*calibrated*</a-st>"), "{}", html);
+    assert!(
+        html.contains("<a-tu>&lt;&lt;usage,Usage&gt;&gt;</a-tu>"),
+        "{}",
+        html
+    );
+    // Rust and Python listings
+    assert!(html.contains("<a-k>fn</a-k> <a-f>main</a-f>()"), "{}", html);
+    assert!(
+        html.contains("<a-k>def</a-k> <a-f>daily</a-f>("),
+        "{}",
+        html
+    );
+}
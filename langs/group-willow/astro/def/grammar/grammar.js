@@ -0,0 +1,103 @@
+/**
+ * @file Astro grammar for tree-sitter
+ * @license MIT
+ * @see {@link https://docs.astro.build/en/basics/astro-components/|Astro components}
+ *
+ * An Astro component is HTML with a `---` fenced frontmatter script on top,
+ * `{expression}` holes in text and attribute values, and `<script>` and
+ * `<style>` elements. The script and expressions are left as raw text for
+ * injections.
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const HTML = require('tree-sitter-html/grammar');
+
+module.exports = grammar(HTML, {
+  name: 'astro',
+
+  externals: ($, _) => [
+    $._start_tag_name,
+    $._script_start_tag_name,
+    $._style_start_tag_name,
+    $._end_tag_name,
+    $.erroneous_end_tag_name,
+    '/>',
+    $._implicit_end_tag,
+    $.raw_text,
+    $.comment,
+    $.frontmatter_js_block,
+    $.permissible_text,
+  ],
+
+  extras: $ => [
+    $.comment,
+    /\s+/,
+  ],
+
+  supertypes: $ => [
+    $._node,
+  ],
+
+  rules: {
+    document: $ => seq(
+      optional($.frontmatter),
+      repeat($._node),
+    ),
+
+    // ---
+    // const { title } = Astro.props;
+    // ---
+    // The opening fence outranks text, which could otherwise swallow it.
+    frontmatter: $ => seq(
+      alias(token(prec(1, '---')), '---'),
+      optional($.frontmatter_js_block),
+      '---',
+    ),
+
+    _node: ($, original) => choice(
+      original,
+      $.html_interpolation,
+    ),
+
+    // {items.map((item) => <li>{item}</li>)}
+    html_interpolation: $ => seq(
+      '{',
+      optional($.permissible_text),
+      '}',
+    ),
+
+    // class={styles.card}, {...props}, {title}
+    attribute_interpolation: $ => seq(
+      '{',
+      optional(alias($.permissible_text, $.attribute_js_expr)),
+      '}',
+    ),
+
+    // href=`/posts/${slug}`
+    attribute_backtick_string: _ => /`[^`]*`/,
+
+    attribute: $ => choice(
+      seq(
+        $.attribute_name,
+        optional(seq(
+          '=',
+          choice(
+            $.attribute_value,
+            $.quoted_attribute_value,
+            $.attribute_interpolation,
+            $.attribute_backtick_string,
+          ),
+        )),
+      ),
+      $.attribute_interpolation,
+    ),
+
+    attribute_name: _ => /[^<>{}"'`/=\s]+/,
+
+    attribute_value: _ => /[^<>{}"'`=\s]+/,
+
+    text: _ => /[^<>{}&\s]([^<>{}&]*[^<>{}&\s])?/,
+  },
+});
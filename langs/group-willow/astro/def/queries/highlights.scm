@@ -0,0 +1,26 @@
+; Astro highlights. The HTML base queries are prepended from arborium-html
+; (see arborium.yaml), so only Astro's own nodes are captured here
+
+(frontmatter
+  "---" @punctuation.delimiter)
+
+(frontmatter_js_block) @none
+
+(permissible_text) @none
+
+(attribute_js_expr) @none
+
+[
+  "{"
+  "}"
+] @punctuation.special
+
+(attribute_backtick_string) @string
+
+; client:load, set:html, is:inline, define:vars, transition:name
+((attribute_name) @attribute.builtin
+  (#match? @attribute.builtin "^(client|set|is|define|transition|server):"))
+
+; Components start with a capital letter: <Card>, <Layout.Header>
+((tag_name) @type
+  (#match? @type "^[A-Z]"))
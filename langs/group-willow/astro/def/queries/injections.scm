@@ -0,0 +1,73 @@
+; The frontmatter between the `---` fences is TypeScript
+((frontmatter_js_block) @injection.content
+  (#set! injection.language "typescript"))
+
+; Astro compiles <script> as TypeScript and <style> as CSS, unless a lang
+; attribute says otherwise. `is:inline` scripts are shipped untouched, so
+; they are plain JavaScript
+((script_element
+  (start_tag) @_tag
+  (raw_text) @injection.content)
+ (#not-match? @_tag "\\s(lang\\s*=|is:inline)")
+ (#set! injection.language "typescript"))
+
+((script_element
+  (start_tag) @_tag
+  (raw_text) @injection.content)
+ (#match? @_tag "\\sis:inline")
+ (#not-match? @_tag "\\slang\\s*=")
+ (#set! injection.language "javascript"))
+
+((style_element
+  (start_tag) @_tag
+  (raw_text) @injection.content)
+ (#not-match? @_tag "\\slang\\s*=")
+ (#set! injection.language "css"))
+
+; <script lang="ts">, <style lang="scss">: the value names the language,
+; and aliases like ts and sass resolve to their grammar
+((script_element
+  (start_tag
+    (attribute
+      (attribute_name) @_attr
+      [
+        (attribute_value) @injection.language
+        (quoted_attribute_value
+          (attribute_value) @injection.language)
+      ]))
+  (raw_text) @injection.content)
+ (#eq? @_attr "lang"))
+
+((style_element
+  (start_tag
+    (attribute
+      (attribute_name) @_attr
+      [
+        (attribute_value) @injection.language
+        (quoted_attribute_value
+          (attribute_value) @injection.language)
+      ]))
+  (raw_text) @injection.content)
+ (#eq? @_attr "lang")
+ (#not-any-of? @injection.language "postcss" "less" "stylus"))
+
+; Style languages without a grammar of their own are close enough to SCSS
+((style_element
+  (start_tag
+    (attribute
+      (attribute_name) @_attr
+      (quoted_attribute_value
+        (attribute_value) @_lang)))
+  (raw_text) @injection.content)
+  (#eq? @_attr "lang")
+  (#any-of? @_lang "postcss" "less" "stylus")
+  (#set! injection.language "scss"))
+
+; {expressions} in markup routinely map data to more markup, as in
+; {items.map((item) => <li>{item}</li>)}, so they get TSX. Attribute values
+; are plain expressions and get TypeScript
+((permissible_text) @injection.content
+  (#set! injection.language "tsx"))
+
+((attribute_js_expr) @injection.content
+  (#set! injection.language "typescript"))
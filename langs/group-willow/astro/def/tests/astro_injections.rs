@@ -0,0 +1,90 @@
+//! Astro components inject TypeScript into the frontmatter and `<script>`,
+//! CSS (or the language `lang` names) into `<style>`, and TSX into markup
+//! expressions.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers;
+use arborium_test_harness::invariants::span_violations;
+
+const COMPONENT: &str = "\
+---
+const { items } = Astro.props;
+---
+
+<ul class={listClass}>
+  {items.map((item) => <li>{item}</li>)}
+</ul>
+
+<style lang=\"scss\">
+  $gap: 4px;
+</style>
+";
+
+fn parse(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_astro::language(),
+        helpers::queries!(arborium_astro),
+        source,
+    )
+}
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injections(&parse(source), source)
+}
+
+#[test]
+fn test_frontmatter_is_typescript() {
+    let injections = injected(COMPONENT);
+    let frontmatter: Vec<&str> = injections
+        .iter()
+        .filter(|(_, language)| language == "typescript")
+        .map(|(text, _)| text.trim())
+        .collect();
+    assert!(
+        frontmatter.contains(&"const { items } = Astro.props;"),
+        "{:?}",
+        injections
+    );
+}
+
+#[test]
+fn test_expressions_are_tsx_and_attributes_typescript() {
+    let injections = injected(COMPONENT);
+    assert!(
+        injections.contains(&("items.map((item) => <li>{item}</li>)", "tsx".to_string())),
+        "{:?}",
+        injections
+    );
+    assert!(
+        injections.contains(&("listClass", "typescript".to_string())),
+        "{:?}",
+        injections
+    );
+}
+
+#[test]
+fn test_lang_attribute_names_the_style_language() {
+    let injections = injected(COMPONENT);
+    let style: Vec<&str> = injections
+        .iter()
+        .filter(|(text, _)| text.contains("$gap"))
+        .map(|(_, language)| language.as_str())
+        .collect();
+    assert_eq!(style, ["scss"]);
+}
+
+#[test]
+fn test_scripts_are_typescript_unless_inline() {
+    let source = "<script>\nlet a: number = 1;\n</script>\n<script is:inline>\nlet b = 2;\n</script>\n<style>\np { color: red; }\n</style>\n";
+    let languages: Vec<String> = injected(source).into_iter().map(|(_, l)| l).collect();
+    assert_eq!(languages, ["typescript", "javascript", "css"]);
+}
+
+#[test]
+fn test_html_and_astro_highlights_dont_overlap() {
+    let sample = include_str!("../samples/PostList.astro");
+    for source in [COMPONENT, sample] {
+        let violations = span_violations(source, &parse(source), false);
+        assert!(violations.is_empty(), "{}", violations.join("\n"));
+    }
+}
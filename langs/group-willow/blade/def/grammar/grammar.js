@@ -0,0 +1,116 @@
+/**
+ * @file Blade grammar for tree-sitter
+ * @license MIT
+ *
+ * Laravel's Blade templates: `{{ echo }}`, `{!! raw echo !!}`, `{{-- comments
+ * --}}`, `@directives` with an optional PHP parameter, and `@php` and
+ * `@verbatim` blocks, between runs of content.
+ *
+ * Blade doesn't parse the PHP it embeds, and neither does this grammar: echo
+ * bodies, directive parameters and `@php` blocks are single nodes the
+ * injection query hands to PHP. Blocks like `@if` ... `@endif` are kept flat,
+ * as separate directives, and the content is a hidden token, so the
+ * template's visible children are exactly its tags and everything else is
+ * injected as one combined HTML document.
+ * https://laravel.com/docs/blade
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'blade',
+
+  externals: $ => [
+    $._php_content,
+    $._verbatim_content,
+    $._error_sentinel,
+  ],
+
+  extras: _ => [/\s/],
+
+  rules: {
+    template: $ => repeat($._node),
+
+    _node: $ => choice(
+      $._content,
+      $.echo_statement,
+      $.raw_echo_statement,
+      $.comment,
+      $.directive,
+      $.php_block,
+      $._verbatim,
+    ),
+
+    // Everything between tags. Like Blade, an `@` right after a letter or
+    // digit (an email address) isn't a directive, and `@@if` and `@{{ }}`
+    // escape a directive and an echo.
+    _content: _ => token(prec(-1, choice(
+      /([^{@]|\w@|@@|@\{\{)+/,
+      '{',
+      '@',
+    ))),
+
+    // {{ $user->name }}
+    echo_statement: $ => seq(
+      '{{',
+      optional(alias(/([^}]|\}[^}])+/, $.php_code)),
+      '}}',
+    ),
+
+    // {!! $post->body_html !!}
+    raw_echo_statement: $ => seq(
+      '{!!',
+      optional(alias(/([^!]|![^!]|!![^}])+/, $.php_code)),
+      '!!}',
+    ),
+
+    // @if($user->isAdmin()), @csrf, @endforeach, @can('update', $post)
+    directive: $ => prec.right(seq(
+      field('name', $.directive_name),
+      optional(field('parameter', $.parameter)),
+    )),
+
+    directive_name: _ => /@[a-zA-Z_]\w*(::\w+)?/,
+
+    // The parenthesized PHP after a directive, parentheses included.
+    parameter: $ => seq('(', repeat($._parameter_part), ')'),
+
+    _parameter_part: $ => choice(
+      /[^()'"]+/,
+      $._string,
+      seq('(', repeat($._parameter_part), ')'),
+    ),
+
+    // Strings are skipped whole, so a parenthesis in one doesn't end the
+    // parameter.
+    _string: _ => token(choice(
+      seq('\'', /([^'\\]|\\.)*/, '\''),
+      seq('"', /([^"\\]|\\.)*/, '"'),
+    )),
+
+    // @php ... @endphp, and the inline @php($counter = 0)
+    php_block: $ => choice(
+      seq(
+        alias('@php', $.directive_name),
+        optional(alias($._php_content, $.php_code)),
+        alias('@endphp', $.directive_name),
+      ),
+      seq(
+        alias('@php', $.directive_name),
+        field('parameter', $.parameter),
+      ),
+    ),
+
+    // The body of a verbatim block stays content, so it is still
+    // highlighted as HTML, with its echoes and directives left alone.
+    _verbatim: $ => seq(
+      alias('@verbatim', $.directive_name),
+      optional($._verbatim_content),
+      alias('@endverbatim', $.directive_name),
+    ),
+
+    // {{-- may contain {{ $echoes }} --}}
+    comment: _ => token(seq('{{--', /([^-]|-[^-]|--[^-}])*/, /-*--\}\}/)),
+  },
+});
@@ -0,0 +1,75 @@
+#include "tree_sitter/parser.h"
+
+#include <stdbool.h>
+
+// The bodies of @php and @verbatim blocks run up to their closing directive,
+// which the grammar can't express as a token.
+enum TokenType {
+  PHP_CONTENT,
+  VERBATIM_CONTENT,
+  ERROR_SENTINEL,
+};
+
+void *tree_sitter_blade_external_scanner_create() { return NULL; }
+void tree_sitter_blade_external_scanner_destroy(void *payload) {}
+unsigned tree_sitter_blade_external_scanner_serialize(void *payload, char *buffer) { return 0; }
+void tree_sitter_blade_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {}
+
+static inline void advance(TSLexer *lexer) { lexer->advance(lexer, false); }
+
+// Everything up to, but not including, `end` (`@endphp`), or to the end of
+// the file when the block is never closed.
+static bool scan_until(TSLexer *lexer, const char *end, enum TokenType symbol) {
+  bool has_content = false;
+
+  lexer->result_symbol = symbol;
+  while (!lexer->eof(lexer)) {
+    if (lexer->lookahead != end[0]) {
+      advance(lexer);
+      has_content = true;
+      continue;
+    }
+    lexer->mark_end(lexer);
+    unsigned matched = 0;
+    while (end[matched] != '\0' && lexer->lookahead == end[matched]) {
+      advance(lexer);
+      matched++;
+    }
+    if (end[matched] == '\0') {
+      return has_content;
+    }
+    has_content = true;
+  }
+
+  lexer->mark_end(lexer);
+  return has_content;
+}
+
+// `@php($counter = 0)`, with or without spaces before the parenthesis, is
+// the inline form, whose expression is a directive parameter.
+static bool inline_php_follows(TSLexer *lexer) {
+  while (lexer->lookahead == ' ' || lexer->lookahead == '\t') {
+    advance(lexer);
+  }
+  return lexer->lookahead == '(';
+}
+
+bool tree_sitter_blade_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+  if (valid_symbols[ERROR_SENTINEL]) {
+    return false;
+  }
+
+  if (valid_symbols[PHP_CONTENT]) {
+    lexer->mark_end(lexer);
+    if (inline_php_follows(lexer)) {
+      return false;
+    }
+    return scan_until(lexer, "@endphp", PHP_CONTENT);
+  }
+
+  if (valid_symbols[VERBATIM_CONTENT]) {
+    return scan_until(lexer, "@endverbatim", VERBATIM_CONTENT);
+  }
+
+  return false;
+}
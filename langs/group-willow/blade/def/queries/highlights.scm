@@ -0,0 +1,32 @@
+; Blade tags and comments. The PHP in echoes, directive parameters and @php
+; blocks, and the content between tags, are highlighted by injections (see
+; injections.scm).
+
+(comment) @comment
+
+[
+  "{{"
+  "}}"
+  "{!!"
+  "!!}"
+] @punctuation.special
+
+(directive_name) @keyword
+
+((directive_name) @keyword.conditional
+  (#any-of? @keyword.conditional
+    "@if" "@elseif" "@else" "@endif" "@unless" "@endunless" "@isset"
+    "@endisset" "@empty" "@endempty" "@switch" "@case" "@default"
+    "@endswitch" "@auth" "@endauth" "@guest" "@endguest" "@can" "@elsecan"
+    "@endcan" "@cannot" "@endcannot" "@env" "@endenv" "@production"
+    "@endproduction" "@error" "@enderror"))
+
+((directive_name) @keyword.repeat
+  (#any-of? @keyword.repeat
+    "@for" "@endfor" "@foreach" "@endforeach" "@forelse" "@endforelse"
+    "@while" "@endwhile" "@continue" "@break"))
+
+((directive_name) @keyword.import
+  (#any-of? @keyword.import
+    "@extends" "@include" "@includeIf" "@includeWhen" "@includeUnless"
+    "@includeFirst" "@each" "@use"))
@@ -0,0 +1,17 @@
+; The content between Blade tags, block bodies included, is HTML. It is
+; injected as one combined document, with the tags left out, so an element
+; split across @if branches still parses. The HTML grammar's own injections
+; then highlight <script> and <style> contents.
+((template) @injection.content
+  (#set! injection.language "html")
+  (#set! injection.combined))
+
+; Echoes and @php blocks hold bare PHP, without a <?php tag.
+((php_code) @injection.content
+  (#set! injection.language "php_only"))
+
+; So do directive parameters, parentheses included: `($user->isAdmin())`
+; parses as a parenthesized expression.
+((parameter) @injection.content
+  (#set! injection.language "php_only")
+  (#set! injection.include-children))
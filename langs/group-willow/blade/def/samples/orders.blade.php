@@ -0,0 +1,61 @@
+{{-- resources/views/orders/index.blade.php --}}
+@extends('layouts.app')
+
+@section('title', 'Orders')
+
+@section('content')
+    <x-page-header :title="__('Orders')" class="mb-6">
+        <x-slot:actions>
+            @can('create', App\Models\Order::class)
+                <a href="{{ route('orders.create') }}" class="btn btn-primary">New order</a>
+            @endcan
+        </x-slot>
+    </x-page-header>
+
+    @if (session('status'))
+        <x-alert type="success" :dismissible="true">
+            {{ session('status') }}
+        </x-alert>
+    @endif
+
+    @php
+        $total = $orders->sum(fn ($order) => $order->total);
+        $currency = config('shop.currency', 'EUR');
+    @endphp
+
+    <table class="table">
+        <thead>
+            <tr><th>Number</th><th>Customer</th><th>Total</th><th></th></tr>
+        </thead>
+        <tbody>
+            @forelse ($orders as $order)
+                <tr @class(['table-warning' => $order->isOverdue()])>
+                    <td>#{{ $order->number }}</td>
+                    <td>{{ $order->customer->name ?? 'Guest' }}</td>
+                    <td>{{ Number::currency($order->total, $currency) }}</td>
+                    <td>
+                        <form method="POST" action="{{ route('orders.destroy', $order) }}">
+                            @csrf
+                            @method('DELETE')
+                            <button type="submit" @disabled($order->isShipped())>Cancel</button>
+                        </form>
+                    </td>
+                </tr>
+            @empty
+                <tr><td colspan="4">No orders yet. Questions? Write to support@example.com</td></tr>
+            @endforelse
+        </tbody>
+    </table>
+
+    <p>Total: {!! $formatter->money($total) !!}</p>
+    <p>Escaped for the client-side app: @{{ order.total }}</p>
+
+    {{ $orders->links() }}
+@endsection
+
+@push('scripts')
+    <script>
+        const orderCount = {{ $orders->count() }};
+        window.Shop.init({ currency: @json($currency) });
+    </script>
+@endpush
@@ -0,0 +1,195 @@
+//! Blade templates inject their content as one combined HTML document, and
+//! the PHP in echoes, directive parameters and @php blocks as php_only.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const TEMPLATE: &str = "\
+{{-- Order list --}}
+<ul>
+@foreach ($orders as $order)
+  <li @if ($order->isPaid()) class=\"paid\" @endif>{{ $order->number }}</li>
+@endforeach
+</ul>
+@php
+    $total = $orders->sum('total');
+@endphp
+<p>{!! money($total) !!} &middot; mail billing@example.com</p>
+@verbatim<span>{{ vue }}</span>@endverbatim
+<script>
+  const count = {{ count($orders) }};
+</script>
+";
+
+const SAMPLE: &str = include_str!("../samples/orders.blade.php");
+
+fn parse(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_blade::language(),
+        helpers::queries!(arborium_blade),
+        source,
+    )
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "blade",
+            TestGrammar::new(
+                arborium_blade::language(),
+                arborium_blade::HIGHLIGHTS_QUERY,
+                arborium_blade::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "html",
+            TestGrammar::new(
+                arborium_html::language(),
+                arborium_html::HIGHLIGHTS_QUERY,
+                arborium_html::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "php_only",
+            TestGrammar::new(
+                arborium_php_only::language(),
+                &arborium_php_only::HIGHLIGHTS_QUERY,
+                arborium_php_only::INJECTIONS_QUERY,
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+/// The text of each injection into `language`.
+fn injected<'a>(source: &'a str, result: &Utf8ParseResult, language: &str) -> Vec<&'a str> {
+    helpers::injected_as(result, source, language)
+}
+
+#[test]
+fn test_content_between_tags_is_combined_html() {
+    for source in [TEMPLATE, SAMPLE] {
+        let result = parse(source);
+        for injection in &result.injections {
+            assert!(
+                injection.language == "html" || injection.language == "php_only",
+                "{:?}",
+                injection
+            );
+            assert_eq!(injection.combined, injection.language == "html");
+        }
+        for text in injected(source, &result, "html") {
+            // `@{{` escapes an echo, and @verbatim blocks are left alone
+            let text = text.replace("@{{", "").replace("{{ vue }}", "");
+            assert!(!text.contains("{{") && !text.contains("{!!"), "{:?}", text);
+            assert!(!text.contains("@end"), "{:?}", text);
+        }
+    }
+}
+
+#[test]
+fn test_php_is_injected_as_php_only() {
+    let result = parse(TEMPLATE);
+    let php = injected(TEMPLATE, &result, "php_only");
+
+    // Echoes and raw echoes
+    assert!(
+        php.iter().any(|t| t.trim() == "$order->number"),
+        "{:?}",
+        php
+    );
+    assert!(php.iter().any(|t| t.trim() == "money($total)"), "{:?}", php);
+    // Directive parameters, parentheses included
+    assert!(php.contains(&"($order->isPaid())"), "{:?}", php);
+    // @php blocks
+    assert!(
+        php.iter()
+            .any(|t| t.trim() == "$total = $orders->sum('total');"),
+        "{:?}",
+        php
+    );
+}
+
+#[test]
+fn test_email_address_is_not_a_directive() {
+    let result = parse(TEMPLATE);
+    let at = TEMPLATE.find("@example.com").unwrap() as u32;
+
+    assert!(
+        result.spans.iter().all(|s| s.end <= at || s.start > at),
+        "{:?}",
+        result.spans
+    );
+    assert!(
+        result
+            .injections
+            .iter()
+            .any(|i| i.language == "html" && i.start <= at && i.end > at),
+        "{:?}",
+        result.injections
+    );
+}
+
+#[test]
+fn test_verbatim_block_stays_html() {
+    let result = parse(TEMPLATE);
+    let body = "<span>{{ vue }}</span>";
+    let start = TEMPLATE.find(body).unwrap() as u32;
+    let end = start + body.len() as u32;
+
+    assert!(
+        result
+            .injections
+            .iter()
+            .any(|i| i.language == "html" && i.start <= start && i.end >= end),
+        "{:?}",
+        result.injections
+    );
+    assert!(
+        result
+            .spans
+            .iter()
+            .all(|s| s.end <= start || s.start >= end),
+        "the verbatim block was highlighted as Blade: {:?}",
+        result.spans
+    );
+}
+
+#[test]
+fn test_template_captures() {
+    let html = highlighter().highlight("blade", TEMPLATE).unwrap();
+    assert!(html.contains("<a-c>{{-- Order list --}}</a-c>"), "{}", html);
+    assert!(html.contains("<a-k>@foreach</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>@if</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>@php</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>money</a-f>"), "{}", html);
+    assert!(html.contains("<a-s>&#39;total&#39;</a-s>"), "{}", html);
+}
+
+#[test]
+fn test_element_split_by_tags_highlights() {
+    let html = highlighter().highlight("blade", TEMPLATE).unwrap();
+    // The <li> start tag is cut in three by the @if branch
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    assert!(html.contains("<a-at>class</a-at>"), "{}", html);
+}
+
+#[test]
+fn test_script_injects_javascript_through_html() {
+    let html = highlighter().highlight("blade", TEMPLATE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+
+    let html = highlighter().highlight("blade", SAMPLE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>x-alert</a-tg>"), "{}", html);
+    assert!(html.contains("<a-f>route</a-f>"), "{}", html);
+}
@@ -0,0 +1,26 @@
+; The changed code, in the language of the file it belongs to
+;
+; The language is captured as the new file's path, e.g. `b/src/main.rs`,
+; and the highlighter resolves it from the extension or the file name.
+; Each line is injected on its own, without its `+` or `-` marker. Context
+; lines already start after their leading space.
+
+((block
+  (new_file
+    (filename) @injection.language)
+  (hunks
+    (hunk
+      (changes
+        [
+          (addition)
+          (deletion)
+        ] @injection.content))))
+ (#offset! @injection.content 0 1 0 0))
+
+(block
+  (new_file
+    (filename) @injection.language)
+  (hunks
+    (hunk
+      (changes
+        (context) @injection.content))))
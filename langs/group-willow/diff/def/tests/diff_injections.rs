@@ -0,0 +1,115 @@
+//! Diffs inject each changed line into the language of its file.
+//!
+//! The diff grammar captures the file path as the injected language, and
+//! an injection resolver turns it into a grammar.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::arborium_highlight::detect::detect_from_path;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const RUST_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1a2b3c4..5d6e7f8 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ pub fn greet() -> String {
+-    String::from(\"Hello\")
++    let name = \"World\";
+ }
+";
+
+const PYTHON_DIFF: &str = "\
+diff --git a/app.py b/app.py
+index 1a2b3c4..5d6e7f8 100644
+--- a/app.py
++++ b/app.py
+@@ -1,2 +1,3 @@
+ import os
++def home():
++    return os.environ[\"HOME\"]
+";
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "diff",
+            TestGrammar::new(
+                arborium_diff::language(),
+                arborium_diff::HIGHLIGHTS_QUERY,
+                arborium_diff::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "python",
+            TestGrammar::new(
+                arborium_python::language(),
+                &arborium_python::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    let mut highlighter = helpers::highlighter(grammars);
+    highlighter.set_injection_resolver(|language: &str| {
+        detect_from_path(language, &|ext| match ext {
+            "rs" => Some("rust".to_string()),
+            "py" => Some("python".to_string()),
+            _ => None,
+        })
+    });
+    highlighter
+}
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_diff::language(),
+        helpers::queries!(arborium_diff),
+        source,
+    )
+}
+
+#[test]
+fn test_changed_lines_inject_the_file_path() {
+    let injections = injected(RUST_DIFF);
+    let lines: Vec<&str> = injections.iter().map(|(text, _)| *text).collect();
+    assert_eq!(
+        lines,
+        [
+            "pub fn greet() -> String {",
+            "    String::from(\"Hello\")",
+            "    let name = \"World\";",
+            "}",
+        ]
+    );
+    assert!(injections.iter().all(|(_, path)| path == "b/src/lib.rs"));
+}
+
+#[test]
+fn test_rust_diff_highlights_changed_code() {
+    let html = highlighter().highlight("diff", RUST_DIFF).unwrap();
+    assert!(html.contains("<a-k>fn</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>let</a-k>"), "{}", html);
+    assert!(html.contains("<a-s>&quot;World&quot;</a-s>"), "{}", html);
+}
+
+#[test]
+fn test_python_diff_highlights_changed_code() {
+    let html = highlighter().highlight("diff", PYTHON_DIFF).unwrap();
+    assert!(html.contains("<a-k>def</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>return</a-k>"), "{}", html);
+}
+
+#[test]
+fn test_unresolved_paths_stay_plain() {
+    let diff = RUST_DIFF.replace("lib.rs", "notes.txt");
+    let html = highlighter().highlight("diff", &diff).unwrap();
+    assert!(!html.contains("<a-k>fn</a-k>"), "{}", html);
+}
@@ -0,0 +1,61 @@
+/**
+ * @file Embedded template grammar for tree-sitter
+ * @license MIT
+ *
+ * Templates that embed a host language in `<% %>` tags, as ERB does with
+ * Ruby and EJS with JavaScript: statements (`<% %>`), output (`<%= %>`,
+ * `<%- %>`) and comments (`<%# %>`) between runs of content, with the
+ * trimming variants of both engines (`<%_`, `-%>`, `_%>`, ...).
+ *
+ * The grammar doesn't know which language the code is in; that is decided
+ * by the injections query the template is highlighted with.
+ * https://github.com/ruby/erb
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'embedded_template',
+
+  extras: _ => [],
+
+  rules: {
+    template: $ => repeat(choice(
+      $.content,
+      $.directive,
+      $.output_directive,
+      $.comment_directive,
+    )),
+
+    // Everything between tags; `<%%` is a literal `<%`.
+    content: _ => prec.right(repeat1(choice(/[^<]+|</, '<%%'))),
+
+    // <% if user %>, <%_ items.forEach(item => { _%>
+    directive: $ => seq(
+      choice('<%', '<%_', '<%|'),
+      optional($.code),
+      $._close,
+    ),
+
+    // <%= title %>, <%== raw %>, <%- include('header') %>
+    output_directive: $ => seq(
+      choice('<%=', '<%==', '<%|=', '<%|==', '<%-'),
+      optional($.code),
+      $._close,
+    ),
+
+    // <%# note %>
+    comment_directive: $ => seq(
+      '<%#',
+      optional(alias($.code, $.comment)),
+      '%>',
+    ),
+
+    _close: _ => choice('%>', '-%>', '_%>', '=%>'),
+
+    // Up to the closing tag; `%%>` is a literal `%>`. Trim markers are
+    // single tokens so a closing `-%>` isn't read as code.
+    code: _ => repeat1(choice(/[^%=_-]+|[%=_-]/, '%%>')),
+  },
+});
@@ -0,0 +1,19 @@
+; Template tags and comments. The code inside the tags and the content
+; between them are highlighted by injections (see injections.scm).
+
+(comment_directive) @comment
+
+[
+  "<%"
+  "<%_"
+  "<%|"
+  "<%="
+  "<%=="
+  "<%|="
+  "<%|=="
+  "<%-"
+  "%>"
+  "-%>"
+  "_%>"
+  "=%>"
+] @punctuation.special
@@ -0,0 +1,11 @@
+; EJS: the code in tags is JavaScript and the content between them is
+; HTML, each injected as one combined document.
+((code) @injection.content
+  (#set! injection.language "javascript")
+  (#set! injection.combined)
+  (#set! injection.include-children))
+
+((content) @injection.content
+  (#set! injection.language "html")
+  (#set! injection.combined)
+  (#set! injection.include-children))
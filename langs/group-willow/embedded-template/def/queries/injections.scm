@@ -0,0 +1,12 @@
+; ERB: the code in tags is Ruby and the content between them is HTML. Each
+; is injected as one combined document, so a block opened in one tag and
+; closed in another, or an element split across branches, still parses.
+((code) @injection.content
+  (#set! injection.language "ruby")
+  (#set! injection.combined)
+  (#set! injection.include-children))
+
+((content) @injection.content
+  (#set! injection.language "html")
+  (#set! injection.combined)
+  (#set! injection.include-children))
@@ -0,0 +1,191 @@
+//! ERB and EJS share the embedded-template grammar and differ only in the
+//! injections query they are registered with: both inject their content as
+//! one combined HTML document, and their code as Ruby or JavaScript.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const ERB: &str = "\
+<%# Line items %>
+<ul>
+<% @items.each do |item| -%>
+  <li <% if item.active? %>class=\"active\"<% end %>><%= item.title %></li>
+<% end -%>
+</ul>
+<p>Literal <%%= tag %></p>
+";
+
+const EJS: &str = "\
+<%# Line items %>
+<ul>
+<%_ items.forEach(item => { _%>
+  <li <% if (item.active) { %>class=\"active\"<% } %>><%= item.title %></li>
+<%_ }) _%>
+</ul>
+<script>
+  const total = <%- JSON.stringify(total) %>;
+</script>
+";
+
+const ERB_SAMPLE: &str = include_str!("../samples/erb/show.html.erb");
+
+const EJS_SAMPLE: &str = include_str!("../samples/ejs/dashboard.ejs");
+
+fn parse(injections_query: &str, source: &str) -> Utf8ParseResult {
+    let queries = helpers::Queries {
+        injections: injections_query,
+        ..helpers::queries!(arborium_embedded_template)
+    };
+    helpers::plugin_parse(arborium_embedded_template::language(), queries, source)
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "erb",
+            TestGrammar::new(
+                arborium_embedded_template::language(),
+                arborium_embedded_template::HIGHLIGHTS_QUERY,
+                arborium_embedded_template::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "ejs",
+            TestGrammar::new(
+                arborium_embedded_template::language(),
+                arborium_embedded_template::HIGHLIGHTS_QUERY,
+                arborium_embedded_template::EJS_INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "html",
+            TestGrammar::new(
+                arborium_html::language(),
+                arborium_html::HIGHLIGHTS_QUERY,
+                arborium_html::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "ruby",
+            TestGrammar::new(
+                arborium_ruby::language(),
+                arborium_ruby::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+/// Check every injection of `source` is combined, and is either content
+/// injected as HTML or code injected as `code_language`.
+fn assert_injections(source: &str, result: &Utf8ParseResult, code_language: &str) {
+    let mut languages: Vec<&str> = result
+        .injections
+        .iter()
+        .map(|i| i.language.as_str())
+        .collect();
+    languages.sort();
+    languages.dedup();
+    let mut expected = ["html", code_language];
+    expected.sort();
+    assert_eq!(languages, expected);
+
+    for injection in &result.injections {
+        let text = &source[injection.start as usize..injection.end as usize];
+        assert!(injection.combined);
+        if injection.language == "html" {
+            assert!(!text.replace("<%%", "").contains("<%"), "{:?}", text);
+        } else {
+            assert!(!text.contains("<%") && !text.contains("%>"), "{:?}", text);
+        }
+    }
+}
+
+#[test]
+fn test_erb_injects_ruby_and_html() {
+    for source in [ERB, ERB_SAMPLE] {
+        assert_injections(
+            source,
+            &parse(arborium_embedded_template::INJECTIONS_QUERY, source),
+            "ruby",
+        );
+    }
+}
+
+#[test]
+fn test_ejs_injects_javascript_and_html() {
+    for source in [EJS, EJS_SAMPLE] {
+        assert_injections(
+            source,
+            &parse(arborium_embedded_template::EJS_INJECTIONS_QUERY, source),
+            "javascript",
+        );
+    }
+}
+
+#[test]
+fn test_comment_tag_is_one_comment() {
+    let result = parse(arborium_embedded_template::INJECTIONS_QUERY, ERB);
+    let end = ERB.find('\n').unwrap() as u32;
+
+    assert!(
+        result
+            .spans
+            .iter()
+            .any(|s| s.start == 0 && s.end == end && s.capture == "comment"),
+        "{:?}",
+        result.spans
+    );
+    assert!(
+        result.injections.iter().all(|i| i.start >= end),
+        "the comment was injected: {:?}",
+        result.injections
+    );
+}
+
+#[test]
+fn test_literal_tag_is_content() {
+    let result = parse(arborium_embedded_template::INJECTIONS_QUERY, ERB);
+    let start = ERB.find("<%%=").unwrap() as u32;
+
+    assert!(
+        result
+            .injections
+            .iter()
+            .any(|i| i.language == "html" && i.start <= start && i.end > start),
+        "{:?}",
+        result.injections
+    );
+}
+
+#[test]
+fn test_erb_highlights_ruby_spanning_tags() {
+    let html = highlighter().highlight("erb", ERB).unwrap();
+    // The block opened in one tag is closed in another
+    assert!(html.contains("<a-k>do</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>end</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    assert!(html.contains("<a-at>class</a-at>"), "{}", html);
+}
+
+#[test]
+fn test_ejs_highlights_javascript() {
+    let html = highlighter().highlight("ejs", EJS).unwrap();
+    assert!(html.contains("<a-k>if</a-k>"), "{}", html);
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    // The <script> element is injected through HTML
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+
+    let html = highlighter().highlight("ejs", EJS_SAMPLE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+}
@@ -0,0 +1,203 @@
+/**
+ * @file Handlebars and Mustache grammar for tree-sitter
+ * @license MIT
+ *
+ * Handlebars templates: `{{mustaches}}`, `{{{triple-stashes}}}`, block
+ * helpers (`{{#each}}` ... `{{else}}` ... `{{/each}}`), partials and
+ * comments between runs of content. Mustache templates are the subset
+ * without helpers' arguments.
+ *
+ * Blocks are kept flat, as separate open, else and close tags, and the
+ * content is a hidden token: the template's visible children are exactly
+ * its tags, and the injection query hands everything else, block bodies
+ * included, to HTML as one combined document.
+ * https://handlebarsjs.com/guide/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'handlebars',
+
+  extras: _ => [/\s/],
+
+  word: $ => $.identifier,
+
+  rules: {
+    template: $ => repeat($._node),
+
+    _node: $ => choice(
+      $._content,
+      $.mustache_statement,
+      $.triple_stash,
+      $.block_open,
+      $.else_statement,
+      $.block_close,
+      $.partial_statement,
+      $.comment,
+    ),
+
+    // Everything between tags. `\{{` escapes a mustache, and a lone `{`
+    // or `\` is content too.
+    _content: _ => token(prec(-1, choice(
+      /[^{\\]+/,
+      /\\\{\{/,
+      '\\',
+      '{',
+    ))),
+
+    // {{title}}, {{format-date published "short"}}
+    mustache_statement: $ => seq(
+      choice('{{', '{{~'),
+      $._mustache_body,
+      choice('}}', '~}}'),
+    ),
+
+    // {{{body}}}: output without HTML escaping
+    triple_stash: $ => seq(
+      choice('{{{', '{{~{'),
+      $._mustache_body,
+      choice('}}}', '}~}}'),
+    ),
+
+    // {{#each items as |item index|}}, {{^items}}, {{#> layout}},
+    // {{#*inline "name"}}
+    block_open: $ => seq(
+      choice(
+        '{{#', '{{~#',
+        '{{^', '{{~^',
+        '{{#>', '{{~#>',
+        '{{#*', '{{~#*',
+      ),
+      field('helper', $._expression),
+      repeat(field('argument', $._argument)),
+      optional($.block_params),
+      choice('}}', '~}}'),
+    ),
+
+    // {{else}}, {{else if draft}}, {{^}}
+    else_statement: $ => choice(
+      seq(
+        choice('{{', '{{~'),
+        'else',
+        optional(seq(
+          field('helper', $._expression),
+          repeat(field('argument', $._argument)),
+          optional($.block_params),
+        )),
+        choice('}}', '~}}'),
+      ),
+      seq(
+        choice('{{^', '{{~^'),
+        choice('}}', '~}}'),
+      ),
+    ),
+
+    // {{/each}}
+    block_close: $ => seq(
+      choice('{{/', '{{~/'),
+      field('helper', $._expression),
+      choice('}}', '~}}'),
+    ),
+
+    // {{> user-card user tier="gold"}}, {{> (lookup . "partial")}}
+    partial_statement: $ => seq(
+      choice('{{>', '{{~>'),
+      field('name', $._expression),
+      repeat(field('argument', $._argument)),
+      choice('}}', '~}}'),
+    ),
+
+    block_params: $ => seq('as', '|', repeat1($.identifier), '|'),
+
+    _mustache_body: $ => choice(
+      $._expression,
+      $.helper_invocation,
+    ),
+
+    helper_invocation: $ => seq(
+      field('helper', $._expression),
+      repeat1(field('argument', $._argument)),
+    ),
+
+    _argument: $ => choice(
+      $._expression,
+      $.hash_pair,
+    ),
+
+    // key=value
+    hash_pair: $ => seq(
+      field('key', $.identifier),
+      '=',
+      field('value', $._expression),
+    ),
+
+    _expression: $ => choice(
+      $.identifier,
+      $.path_expression,
+      $.this,
+      // {{.}}, Mustache's current item
+      alias('.', $.this),
+      $.data_name,
+      $.sub_expression,
+      $.string,
+      $.number,
+      $.boolean,
+      $.null,
+      $.undefined,
+    ),
+
+    // (concat first " " last)
+    sub_expression: $ => seq('(', $._mustache_body, ')'),
+
+    // person.name, this.title, ../parent, @root.user, ./name
+    path_expression: $ => prec.left(1, seq(
+      field('head', choice(
+        $.identifier,
+        $.this,
+        $.data_name,
+        alias('..', $.parent),
+        alias('.', $.this),
+      )),
+      repeat1(seq(
+        choice('.', '/'),
+        field('segment', choice(
+          alias($.identifier, $.property_identifier),
+          alias('..', $.parent),
+        )),
+      )),
+    )),
+
+    this: _ => 'this',
+
+    // @index, @key, @first, @root
+    data_name: _ => /@[^\s!"#%&'()*+,.\/;<=>@\[\\\]^`{|}~]+/,
+
+    string: _ => token(choice(
+      seq('"', /([^"\\]|\\.)*/, '"'),
+      seq('\'', /([^'\\]|\\.)*/, '\''),
+    )),
+
+    number: _ => token(prec(1, /-?\d+(\.\d+)?/)),
+
+    boolean: _ => choice('true', 'false'),
+
+    null: _ => 'null',
+
+    undefined: _ => 'undefined',
+
+    // Any run of characters Handlebars allows in an ID, hyphens included
+    // (`format-date`), or a `[literal segment]`.
+    identifier: _ => token(choice(
+      /[^\s!"#%&'()*+,.\/;<=>@\[\\\]^`{|}~]+/,
+      /\[[^\]]*\]/,
+    )),
+
+    // {{! short }}, {{!-- may contain }} --}}
+    comment: _ => token(choice(
+      seq(/\{\{~?!--/, /([^-]|-[^-]|--[^-}~])*/, /-*--~?\}\}/),
+      seq(/\{\{~?!/, /([^}]|\}[^}])*/, /~?\}\}/),
+    )),
+  },
+});
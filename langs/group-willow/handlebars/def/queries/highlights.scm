@@ -0,0 +1,111 @@
+; Handlebars mustaches, blocks, partials and comments. The content between
+; them is highlighted by the combined HTML injection (see injections.scm).
+
+(comment) @comment
+
+[
+  "{{"
+  "{{~"
+  "}}"
+  "~}}"
+  "{{{"
+  "{{~{"
+  "}}}"
+  "}~}}"
+  "{{#"
+  "{{~#"
+  "{{^"
+  "{{~^"
+  "{{#>"
+  "{{~#>"
+  "{{#*"
+  "{{~#*"
+  "{{/"
+  "{{~/"
+  "{{>"
+  "{{~>"
+] @punctuation.special
+
+(identifier) @variable
+
+(property_identifier) @property
+
+[
+  (this)
+  (parent)
+  (data_name)
+] @variable.builtin
+
+(helper_invocation
+  helper: (identifier) @function)
+
+(block_open
+  helper: (identifier) @function)
+
+(block_close
+  helper: (identifier) @function)
+
+(else_statement
+  helper: (identifier) @function)
+
+; The built-in block helpers
+(block_open
+  helper: (identifier) @keyword.conditional
+  (#any-of? @keyword.conditional "if" "unless"))
+
+(block_close
+  helper: (identifier) @keyword.conditional
+  (#any-of? @keyword.conditional "if" "unless"))
+
+(else_statement
+  helper: (identifier) @keyword.conditional
+  (#any-of? @keyword.conditional "if" "unless"))
+
+(block_open
+  helper: (identifier) @keyword.repeat
+  (#eq? @keyword.repeat "each"))
+
+(block_close
+  helper: (identifier) @keyword.repeat
+  (#eq? @keyword.repeat "each"))
+
+(partial_statement
+  name: (identifier) @string.special)
+
+(partial_statement
+  name: (path_expression) @string.special)
+
+(hash_pair
+  key: (identifier) @variable.parameter)
+
+(block_params
+  (identifier) @variable.parameter)
+
+[
+  "else"
+  "as"
+] @keyword
+
+"=" @operator
+
+[
+  "."
+  "/"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "|"
+] @punctuation.bracket
+
+(string) @string
+
+(number) @number
+
+(boolean) @boolean
+
+[
+  (null)
+  (undefined)
+] @constant.builtin
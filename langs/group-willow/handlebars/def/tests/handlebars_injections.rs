@@ -0,0 +1,121 @@
+//! Handlebars templates inject their content, block bodies included, as one
+//! combined HTML document.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const TEMPLATE: &str = "\
+{{!-- Item list --}}
+<ul>
+{{#each items as |item|}}
+  <li {{#if item.active}}class=\"active\"{{/if}}>{{item.title}}</li>
+{{else}}
+  <li>Nothing yet</li>
+{{/each}}
+</ul>
+{{> card title=\"Latest\" user=@root.user}}
+<div>{{{body}}}</div>
+<script>
+  const count = {{items.length}};
+</script>
+";
+
+const SAMPLE: &str = include_str!("../samples/order-confirmation.hbs");
+
+fn parse(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_handlebars::language(),
+        helpers::queries!(arborium_handlebars),
+        source,
+    )
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "handlebars",
+            TestGrammar::new(
+                arborium_handlebars::language(),
+                arborium_handlebars::HIGHLIGHTS_QUERY,
+                arborium_handlebars::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "html",
+            TestGrammar::new(
+                arborium_html::language(),
+                arborium_html::HIGHLIGHTS_QUERY,
+                arborium_html::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+#[test]
+fn test_content_between_tags_is_combined_html() {
+    for source in [TEMPLATE, SAMPLE] {
+        let result = parse(source);
+        assert!(!result.injections.is_empty());
+        for injection in &result.injections {
+            let text = &source[injection.start as usize..injection.end as usize];
+            assert_eq!(injection.language, "html");
+            assert!(injection.combined);
+            // `\{{` is an escaped, literal mustache
+            assert!(!text.replace("\\{{", "").contains("{{"), "{:?}", text);
+        }
+    }
+}
+
+#[test]
+fn test_block_bodies_are_injected() {
+    let result = parse(TEMPLATE);
+    let body = TEMPLATE.find("<li>Nothing yet</li>").unwrap() as u32;
+
+    assert!(
+        result
+            .injections
+            .iter()
+            .any(|i| i.start <= body && i.end > body),
+        "{:?}",
+        result.injections
+    );
+}
+
+#[test]
+fn test_template_captures() {
+    let html = highlighter().highlight("handlebars", TEMPLATE).unwrap();
+    assert!(html.contains("<a-c>{{!-- Item list --}}</a-c>"), "{}", html);
+    assert!(html.contains("<a-k>each</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>if</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>else</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>as</a-k>"), "{}", html);
+    assert!(html.contains("<a-s>&quot;Latest&quot;</a-s>"), "{}", html);
+}
+
+#[test]
+fn test_element_split_by_tags_highlights() {
+    let html = highlighter().highlight("handlebars", TEMPLATE).unwrap();
+    // The <li> start tag is cut in three by the {{#if}} block
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    assert!(html.contains("<a-at>class</a-at>"), "{}", html);
+    assert!(html.contains("<a-tg>div</a-tg>"), "{}", html);
+}
+
+#[test]
+fn test_script_injects_javascript_through_html() {
+    let html = highlighter().highlight("handlebars", TEMPLATE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+
+    let html = highlighter().highlight("handlebars", SAMPLE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+}
@@ -0,0 +1,7 @@
+; The text between template tags is HTML. It is injected as one combined
+; document, with the tags left out, so an element split across
+; {% if %} branches still parses. The HTML grammar's own injections then
+; highlight <script> and <style> contents.
+((source_file) @injection.content
+  (#set! injection.language "html")
+  (#set! injection.combined))
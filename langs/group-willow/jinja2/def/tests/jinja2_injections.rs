@@ -0,0 +1,77 @@
+//! Jinja2 templates inject their text as one combined HTML document.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const TEMPLATE: &str = "\
+<ul>
+{% for item in items %}
+  <li {% if item.active %}class=\"active\"{% endif %}>{{ item.title }}</li>
+{% endfor %}
+</ul>
+<script>
+  const count = {{ items|length }};
+</script>
+";
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "jinja2",
+            TestGrammar::new(
+                arborium_jinja2::language(),
+                arborium_jinja2::HIGHLIGHTS_QUERY,
+                arborium_jinja2::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "html",
+            TestGrammar::new(
+                arborium_html::language(),
+                arborium_html::HIGHLIGHTS_QUERY,
+                arborium_html::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+#[test]
+fn test_text_between_tags_is_combined_html() {
+    let result = helpers::plugin_parse(
+        arborium_jinja2::language(),
+        helpers::queries!(arborium_jinja2),
+        TEMPLATE,
+    );
+
+    assert!(!result.injections.is_empty());
+    for injection in &result.injections {
+        let text = &TEMPLATE[injection.start as usize..injection.end as usize];
+        assert_eq!(injection.language, "html");
+        assert!(injection.combined);
+        assert!(!text.contains("{%") && !text.contains("{{"), "{:?}", text);
+    }
+}
+
+#[test]
+fn test_element_split_by_tags_highlights() {
+    let html = highlighter().highlight("jinja2", TEMPLATE).unwrap();
+    // The <li> start tag is cut in three by the {% if %} branch
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    assert!(html.contains("<a-at>class</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>for</a-k>"), "{}", html);
+}
+
+#[test]
+fn test_script_injects_javascript_through_html() {
+    let html = highlighter().highlight("jinja2", TEMPLATE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+}
@@ -0,0 +1,296 @@
+/**
+ * @file Liquid grammar for tree-sitter
+ * @license MIT
+ *
+ * Liquid templates as used by Shopify themes and Jekyll sites: `{{ output }}`
+ * with filter chains, `{% tags %}`, `{% comment %}` blocks and `{% raw %}`
+ * blocks between runs of content. The content is a hidden token, so the
+ * template's visible children are exactly its tags; the injection query
+ * hands everything else to HTML as one combined document.
+ * https://shopify.github.io/liquid/
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  logical: 1,
+  compare: 2,
+  filter: 3,
+  member: 4,
+};
+
+module.exports = grammar({
+  name: 'liquid',
+
+  externals: $ => [
+    $._block_comment,
+    $._raw_content,
+    $._error_sentinel,
+  ],
+
+  extras: _ => [/\s/],
+
+  word: $ => $.identifier,
+
+  rules: {
+    template: $ => repeat($._node),
+
+    _node: $ => choice(
+      $._content,
+      $.output_directive,
+      $.statement_directive,
+      $.comment,
+      $._raw,
+    ),
+
+    // Everything between tags; a lone `{` that doesn't open one is content
+    // too.
+    _content: _ => token(prec(-1, choice(/[^{]+/, '{'))),
+
+    // {{ product.title | escape }}
+    output_directive: $ => seq(
+      choice('{{', '{{-'),
+      optional($._expression),
+      choice('}}', '-}}'),
+    ),
+
+    // {% if product.available %}
+    statement_directive: $ => seq(
+      choice('{%', '{%-'),
+      $._statement,
+      choice('%}', '-%}'),
+    ),
+
+    // The body of a raw block stays content, so it is still highlighted as
+    // HTML; only its Liquid tags are skipped.
+    _raw: $ => seq(
+      $.raw_directive,
+      optional($._raw_content),
+    ),
+
+    raw_directive: _ => seq(
+      choice('{%', '{%-'),
+      'raw',
+      choice('%}', '-%}'),
+    ),
+
+    _statement: $ => choice(
+      $.if_statement,
+      $.elsif_statement,
+      $.unless_statement,
+      $.case_statement,
+      $.when_statement,
+      $.for_statement,
+      $.tablerow_statement,
+      $.cycle_statement,
+      $.assign_statement,
+      $.capture_statement,
+      $.increment_statement,
+      $.echo_statement,
+      $.include_statement,
+      $.render_statement,
+      $.tag_statement,
+    ),
+
+    if_statement: $ => seq('if', field('condition', $._expression)),
+
+    elsif_statement: $ => seq('elsif', field('condition', $._expression)),
+
+    unless_statement: $ => seq('unless', field('condition', $._expression)),
+
+    case_statement: $ => seq('case', field('value', $._expression)),
+
+    // when 'shirt', 'pants' or 'shoes'
+    when_statement: $ => seq(
+      'when',
+      field('value', $._primary),
+      repeat(seq(choice(',', 'or'), field('value', $._primary))),
+    ),
+
+    // for product in collection.products limit: 4 reversed
+    for_statement: $ => seq(
+      'for',
+      field('variable', $.identifier),
+      'in',
+      field('sequence', $._primary),
+      repeat($.loop_parameter),
+    ),
+
+    tablerow_statement: $ => seq(
+      'tablerow',
+      field('variable', $.identifier),
+      'in',
+      field('sequence', $._primary),
+      repeat($.loop_parameter),
+    ),
+
+    loop_parameter: $ => choice(
+      'reversed',
+      seq(
+        field('name', choice('limit', 'offset', 'cols')),
+        ':',
+        field('value', $._primary),
+      ),
+    ),
+
+    // cycle 'odd', 'even' / cycle 'row': 'odd', 'even'
+    cycle_statement: $ => seq(
+      'cycle',
+      optional(seq(field('group', $.string), ':')),
+      commaSep1(field('value', $._primary)),
+    ),
+
+    assign_statement: $ => seq(
+      'assign',
+      field('variable', $.identifier),
+      '=',
+      field('value', $._expression),
+    ),
+
+    capture_statement: $ => seq('capture', field('variable', $.identifier)),
+
+    increment_statement: $ => seq(
+      choice('increment', 'decrement'),
+      field('variable', $.identifier),
+    ),
+
+    echo_statement: $ => seq('echo', field('value', $._expression)),
+
+    // include 'product-card', product: product
+    include_statement: $ => seq('include', $._include_arguments),
+
+    // render 'product-card' for collection.products as product
+    render_statement: $ => seq('render', $._include_arguments),
+
+    _include_arguments: $ => seq(
+      field('template', $._primary),
+      optional(seq(
+        choice('with', 'for'),
+        field('value', $._primary),
+        optional(seq('as', field('alias', $.identifier))),
+      )),
+      repeat(seq(optional(','), $.argument)),
+    ),
+
+    // endif, else, break, layout 'theme', section 'header', link page.url, ...
+    tag_statement: $ => seq(
+      field('name', $.identifier),
+      repeat(choice($._primary, $.argument, ',')),
+    ),
+
+    _expression: $ => choice(
+      $._primary,
+      $.filter_expression,
+      $.binary_expression,
+    ),
+
+    _primary: $ => choice(
+      $.identifier,
+      $.string,
+      $.number,
+      $.boolean,
+      $.nil,
+      $.empty,
+      $.blank,
+      $.range,
+      $.member_expression,
+      $.subscript_expression,
+    ),
+
+    member_expression: $ => prec(PREC.member, seq(
+      field('object', $._primary),
+      '.',
+      field('property', alias($.identifier, $.property_identifier)),
+    )),
+
+    subscript_expression: $ => prec(PREC.member, seq(
+      field('object', $._primary),
+      '[',
+      field('index', $._expression),
+      ']',
+    )),
+
+    // (1..5), (1..collection.size)
+    range: $ => seq(
+      '(',
+      field('start', $._primary),
+      '..',
+      field('end', $._primary),
+      ')',
+    ),
+
+    // price | times: 1.2 | money_with_currency
+    filter_expression: $ => prec.left(PREC.filter, seq(
+      field('value', $._expression),
+      '|',
+      $.filter,
+    )),
+
+    filter: $ => prec.right(seq(
+      field('name', $.identifier),
+      optional(seq(
+        ':',
+        commaSep1(choice($._primary, $.argument)),
+      )),
+    )),
+
+    // key: value, and Jekyll's key=value
+    argument: $ => seq(
+      field('name', $.identifier),
+      choice(':', '='),
+      field('value', $._primary),
+    ),
+
+    // Comparisons bind tighter than `and`/`or`, which Liquid evaluates
+    // right to left without precedence between them.
+    binary_expression: $ => choice(
+      prec.right(PREC.logical, seq(
+        field('left', $._expression),
+        field('operator', choice('and', 'or')),
+        field('right', $._expression),
+      )),
+      prec.left(PREC.compare, seq(
+        field('left', $._expression),
+        field('operator', choice('==', '!=', '<>', '<', '>', '<=', '>=', 'contains')),
+        field('right', $._expression),
+      )),
+    ),
+
+    // Liquid strings have no escapes.
+    string: _ => token(choice(
+      seq('\'', /[^']*/, '\''),
+      seq('"', /[^"]*/, '"'),
+    )),
+
+    number: _ => /-?\d+(\.\d+)?/,
+
+    boolean: _ => choice('true', 'false'),
+
+    nil: _ => choice('nil', 'null'),
+
+    empty: _ => 'empty',
+
+    blank: _ => 'blank',
+
+    // Liquid names may contain hyphens (`settings.logo-width`) and end in
+    // `?` (`product.available?`).
+    identifier: _ => /[a-zA-Z_\x80-\uffff]([a-zA-Z0-9_\x80-\uffff-]*[a-zA-Z0-9_\x80-\uffff])?\??/,
+
+    // {% comment %} ... {% endcomment %}, {% # note %}
+    comment: $ => choice(
+      $._block_comment,
+      token(seq(/\{%-?[ \t]*#/, /[^%]*%+([^%}][^%]*%+)*/, '}')),
+    ),
+  },
+});
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
@@ -0,0 +1,162 @@
+#include "tree_sitter/parser.h"
+
+#include <stdbool.h>
+#include <string.h>
+#include <wctype.h>
+
+// Liquid's block comments and raw blocks can't be tokenized by the grammar:
+// their bodies run up to a closing tag, and comments nest.
+enum TokenType {
+  BLOCK_COMMENT,
+  RAW_CONTENT,
+  ERROR_SENTINEL,
+};
+
+#define MAX_TAG_NAME 16
+
+void *tree_sitter_liquid_external_scanner_create() { return NULL; }
+void tree_sitter_liquid_external_scanner_destroy(void *payload) {}
+unsigned tree_sitter_liquid_external_scanner_serialize(void *payload, char *buffer) { return 0; }
+void tree_sitter_liquid_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {}
+
+static inline void advance(TSLexer *lexer) { lexer->advance(lexer, false); }
+
+static void skip_whitespace(TSLexer *lexer) {
+  while (iswspace(lexer->lookahead)) {
+    advance(lexer);
+  }
+}
+
+// Consume `{%` or `{%-` and the whitespace after it, with the lexer on `%`.
+static bool scan_tag_open(TSLexer *lexer) {
+  if (lexer->lookahead != '%') {
+    return false;
+  }
+  advance(lexer);
+  if (lexer->lookahead == '-') {
+    advance(lexer);
+  }
+  skip_whitespace(lexer);
+  return true;
+}
+
+// Read a lowercase tag name into `name`. Names too long to be interesting
+// are consumed but left empty.
+static void scan_tag_name(TSLexer *lexer, char name[MAX_TAG_NAME + 1]) {
+  unsigned length = 0;
+  bool too_long = false;
+  while (iswalpha(lexer->lookahead) || lexer->lookahead == '_') {
+    if (length < MAX_TAG_NAME) {
+      name[length++] = (char)lexer->lookahead;
+    } else {
+      too_long = true;
+    }
+    advance(lexer);
+  }
+  name[too_long ? 0 : length] = '\0';
+}
+
+// Consume the rest of a tag, up to and including `%}`.
+static bool scan_tag_close(TSLexer *lexer) {
+  while (!lexer->eof(lexer)) {
+    if (lexer->lookahead == '%') {
+      advance(lexer);
+      if (lexer->lookahead == '}') {
+        advance(lexer);
+        return true;
+      }
+    } else {
+      advance(lexer);
+    }
+  }
+  return false;
+}
+
+// {% comment %} ... {% endcomment %}, with nested comment blocks counted.
+static bool scan_block_comment(TSLexer *lexer) {
+  char name[MAX_TAG_NAME + 1];
+
+  advance(lexer);
+  if (!scan_tag_open(lexer)) {
+    return false;
+  }
+  scan_tag_name(lexer, name);
+  if (strcmp(name, "comment") != 0 || !scan_tag_close(lexer)) {
+    return false;
+  }
+
+  unsigned depth = 1;
+  while (!lexer->eof(lexer)) {
+    if (lexer->lookahead != '{') {
+      advance(lexer);
+      continue;
+    }
+    advance(lexer);
+    if (!scan_tag_open(lexer)) {
+      continue;
+    }
+    scan_tag_name(lexer, name);
+    if (strcmp(name, "comment") == 0) {
+      depth++;
+    } else if (strcmp(name, "endcomment") == 0) {
+      depth--;
+    } else {
+      continue;
+    }
+    if (!scan_tag_close(lexer)) {
+      return false;
+    }
+    if (depth == 0) {
+      lexer->mark_end(lexer);
+      lexer->result_symbol = BLOCK_COMMENT;
+      return true;
+    }
+  }
+
+  // An unterminated comment is left to the grammar, which reports the
+  // `{% comment %}` tag on its own.
+  return false;
+}
+
+// Everything after {% raw %} up to, but not including, {% endraw %}.
+static bool scan_raw_content(TSLexer *lexer) {
+  char name[MAX_TAG_NAME + 1];
+  bool has_content = false;
+
+  lexer->result_symbol = RAW_CONTENT;
+  while (!lexer->eof(lexer)) {
+    if (lexer->lookahead != '{') {
+      advance(lexer);
+      has_content = true;
+      continue;
+    }
+    lexer->mark_end(lexer);
+    advance(lexer);
+    if (scan_tag_open(lexer)) {
+      scan_tag_name(lexer, name);
+      if (strcmp(name, "endraw") == 0) {
+        return has_content;
+      }
+    }
+    has_content = true;
+  }
+
+  lexer->mark_end(lexer);
+  return has_content;
+}
+
+bool tree_sitter_liquid_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+  if (valid_symbols[ERROR_SENTINEL]) {
+    return false;
+  }
+
+  if (valid_symbols[RAW_CONTENT]) {
+    return scan_raw_content(lexer);
+  }
+
+  if (valid_symbols[BLOCK_COMMENT] && lexer->lookahead == '{') {
+    return scan_block_comment(lexer);
+  }
+
+  return false;
+}
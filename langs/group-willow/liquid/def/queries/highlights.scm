@@ -0,0 +1,114 @@
+; Liquid tags, output and comments. The content between them is highlighted
+; by the combined HTML injection (see injections.scm).
+
+(comment) @comment
+
+[
+  "{{"
+  "{{-"
+  "}}"
+  "-}}"
+  "{%"
+  "{%-"
+  "%}"
+  "-%}"
+] @punctuation.special
+
+(identifier) @variable
+
+((identifier) @variable.builtin
+  (#any-of? @variable.builtin "forloop" "tablerowloop"))
+
+(property_identifier) @property
+
+(argument
+  name: (identifier) @variable.parameter)
+
+; Filter arguments are ordinary values: a format string passed to `date`
+; or a separator passed to `join` is a plain string, not an injection.
+(filter
+  name: (identifier) @function)
+
+(tag_statement
+  name: (identifier) @keyword)
+
+(raw_directive
+  "raw" @keyword)
+
+[
+  "if"
+  "elsif"
+  "unless"
+  "case"
+  "when"
+] @keyword.conditional
+
+[
+  "for"
+  "tablerow"
+  "in"
+  "reversed"
+] @keyword.repeat
+
+(loop_parameter
+  name: _ @variable.parameter)
+
+[
+  "include"
+  "render"
+] @keyword.import
+
+[
+  "cycle"
+  "assign"
+  "capture"
+  "increment"
+  "decrement"
+  "echo"
+  "with"
+  "as"
+] @keyword
+
+[
+  "and"
+  "or"
+  "contains"
+] @keyword.operator
+
+[
+  "=="
+  "!="
+  "<>"
+  "<"
+  ">"
+  "<="
+  ">="
+  "="
+  "|"
+  ":"
+  ".."
+] @operator
+
+[
+  "."
+  ","
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+] @punctuation.bracket
+
+(string) @string
+
+(number) @number
+
+(boolean) @boolean
+
+[
+  (nil)
+  (empty)
+  (blank)
+] @constant.builtin
@@ -0,0 +1,7 @@
+; The content between Liquid tags, raw blocks included, is HTML. It is
+; injected as one combined document, with the tags left out, so an element
+; split across {% if %} branches still parses. The HTML grammar's own
+; injections then highlight <script> and <style> contents.
+((template) @injection.content
+  (#set! injection.language "html")
+  (#set! injection.combined))
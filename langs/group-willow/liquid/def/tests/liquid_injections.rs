@@ -0,0 +1,158 @@
+//! Liquid templates inject their content, raw blocks included, as one
+//! combined HTML document.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const TEMPLATE: &str = "\
+{% comment %}Product list{% endcomment %}
+<ul>
+{% for product in collection.products limit: 4 %}
+  <li {% if product.available %}class=\"in-stock\"{% endif %}>{{ product.title | upcase }}</li>
+{% endfor %}
+</ul>
+{% render 'price', product: product %}
+<time>{{ article.published_at | date: \"%b %d, %Y\" }}</time>
+{% raw %}<b>{{ not_liquid }}</b>{% endraw %}
+<script>
+  const count = {{ collection.products_count }};
+</script>
+";
+
+const SAMPLE: &str = include_str!("../samples/collection.liquid");
+
+fn parse(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_liquid::language(),
+        helpers::queries!(arborium_liquid),
+        source,
+    )
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "liquid",
+            TestGrammar::new(
+                arborium_liquid::language(),
+                arborium_liquid::HIGHLIGHTS_QUERY,
+                arborium_liquid::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "html",
+            TestGrammar::new(
+                arborium_html::language(),
+                arborium_html::HIGHLIGHTS_QUERY,
+                arborium_html::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+#[test]
+fn test_content_between_tags_is_combined_html() {
+    for source in [TEMPLATE, SAMPLE] {
+        let result = parse(source);
+        assert!(!result.injections.is_empty());
+        for injection in &result.injections {
+            let text = &source[injection.start as usize..injection.end as usize];
+            assert_eq!(injection.language, "html");
+            assert!(injection.combined);
+            assert!(!text.contains("{%"), "{:?}", text);
+        }
+    }
+}
+
+#[test]
+fn test_raw_block_content_stays_html() {
+    let result = parse(TEMPLATE);
+    let body = "<b>{{ not_liquid }}</b>";
+    let start = TEMPLATE.find(body).unwrap() as u32;
+    let end = start + body.len() as u32;
+
+    assert!(
+        result
+            .injections
+            .iter()
+            .any(|i| i.start <= start && i.end >= end),
+        "{:?}",
+        result.injections
+    );
+    assert!(
+        result
+            .spans
+            .iter()
+            .all(|s| s.end <= start || s.start >= end),
+        "the raw block was highlighted as Liquid: {:?}",
+        result.spans
+    );
+}
+
+#[test]
+fn test_block_comment_is_one_comment() {
+    let result = parse(TEMPLATE);
+    let end = TEMPLATE.find('\n').unwrap() as u32;
+
+    assert!(
+        result
+            .spans
+            .iter()
+            .any(|s| s.start == 0 && s.end == end && s.capture == "comment"),
+        "{:?}",
+        result.spans
+    );
+}
+
+#[test]
+fn test_filter_string_arguments_stay_plain() {
+    let result = parse(TEMPLATE);
+    let start = TEMPLATE.find("\"%b %d, %Y\"").unwrap() as u32;
+    let end = start + "\"%b %d, %Y\"".len() as u32;
+
+    assert!(
+        result
+            .spans
+            .iter()
+            .any(|s| s.start == start && s.end == end && s.capture == "string"),
+        "{:?}",
+        result.spans
+    );
+    assert!(
+        result
+            .injections
+            .iter()
+            .all(|i| i.end <= start || i.start >= end),
+        "a filter argument was injected"
+    );
+}
+
+#[test]
+fn test_element_split_by_tags_highlights() {
+    let html = highlighter().highlight("liquid", TEMPLATE).unwrap();
+    // The <li> start tag is cut in three by the {% if %} branch
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    assert!(html.contains("<a-at>class</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>for</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>render</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>upcase</a-f>"), "{}", html);
+}
+
+#[test]
+fn test_script_injects_javascript_through_html() {
+    let html = highlighter().highlight("liquid", TEMPLATE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+
+    let html = highlighter().highlight("liquid", SAMPLE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+}
@@ -0,0 +1,444 @@
+// This grammar only concerns the inline structure according to the CommonMark Spec
+// (https://spec.commonmark.org/0.30/#inlines)
+// The block grammar (markdown) injects it into every `inline` node.
+
+/// <reference types="tree-sitter-cli/dsl" />
+
+const common = require('../common/common');
+
+// Levels used for dynamic precedence. Links bind tighter than emphasis, and raw html tighter
+// than both, as the spec says.
+const PRECEDENCE_LEVEL_EMPHASIS = 1;
+const PRECEDENCE_LEVEL_LINK = common.PRECEDENCE_LEVEL_LINK;
+const PRECEDENCE_LEVEL_HTML = 100;
+
+const PUNCTUATION_CHARACTERS_REGEX = common.PUNCTUATION_CHARACTERS_REGEX;
+
+// !!!
+// Notice the call to `add_inline_rules` which generates the rules for collections of inlines and
+// emphasis, once for every context they can appear in.
+// !!!
+module.exports = grammar(add_inline_rules({
+    name: 'markdown_inline',
+
+    externals: $ => [
+        // An `$._error` token is never valid and gets emitted to kill invalid parse branches. It
+        // gets emitted when trying to parse the `$._trigger_error` token in `$.link_title`.
+        $._error,
+        $._trigger_error,
+
+        // Opening and closing delimiters for code spans. These are sequences of one or more
+        // backticks. An opening token is only emitted if a closing one of the same length follows.
+        $._code_span_start,
+        $._code_span_close,
+
+        // Opening and closing delimiters for emphasis, one per `*` or `_` of a delimiter run.
+        $._emphasis_open_star,
+        $._emphasis_open_underscore,
+        $._emphasis_close_star,
+        $._emphasis_close_underscore,
+
+        // For emphasis we need to tell the scanner if the last character was whitespace (or the
+        // beginning of the inline) or punctuation. These tokens never actually get emitted, the
+        // scanner only checks whether they are valid.
+        $._last_token_whitespace,
+        $._last_token_punctuation,
+
+        // Opening and closing delimiters for strikethrough, one per `~`.
+        $._strikethrough_open,
+        $._strikethrough_close,
+
+        // Opening and closing delimiters for latex. These are sequences of one or more dollar
+        // signs, emitted under the same rules as code span delimiters.
+        $._latex_span_start,
+        $._latex_span_close,
+
+        // Emitted for an opening delimiter of a code or latex span that has no matching closing
+        // delimiter, which makes it plain text.
+        $._unclosed_span,
+    ],
+    precedences: $ => [
+        [$.hard_line_break, $._whitespace],
+        [$.hard_line_break, $._text_base],
+    ],
+    // More conflicts are defined in `add_inline_rules`
+    conflicts: $ => [
+        [$._closing_tag, $._text_base],
+        [$._open_tag, $._text_base],
+        [$._html_comment, $._text_base],
+        [$._processing_instruction, $._text_base],
+        [$._declaration, $._text_base],
+        [$._cdata_section, $._text_base],
+
+        [$._link_text_non_empty, $._inline_element],
+        [$._link_text_non_empty, $._inline_element_no_star],
+        [$._link_text_non_empty, $._inline_element_no_underscore],
+        [$._link_text_non_empty, $._inline_element_no_tilde],
+        [$._link_text, $._inline_element],
+        [$._link_text, $._inline_element_no_star],
+        [$._link_text, $._inline_element_no_underscore],
+        [$._link_text, $._inline_element_no_tilde],
+
+        [$._image_description, $._image_description_non_empty, $._text_base],
+        [$._image_shortcut_link, $._image_description],
+        [$.shortcut_link, $._link_text],
+        [$.link_destination, $.link_title],
+        [$._link_destination_parenthesis, $.link_title],
+    ],
+    extras: $ => [],
+
+    rules: {
+        inline: $ => seq(optional($._last_token_whitespace), $._inline),
+
+        ...common.rules,
+
+        // A lot of inlines are defined in `add_inline_rules`, including:
+        //
+        // * collections of inlines
+        // * emphasis, strong emphasis and strikethrough
+        //
+        // Some inlines have to be parsed differently depending on the context, e.g. link text may
+        // not contain links, and emphasis opened with `*` can't contain an opening `*`.
+
+        // Code spans. The content is taken literally, so there is no emphasis, links or html in
+        // there.
+        //
+        // https://github.github.com/gfm/#code-spans
+        code_span: $ => seq(
+            alias($._code_span_start, $.code_span_delimiter),
+            repeat(choice($._text_base, '[', ']', $._soft_line_break)),
+            alias($._code_span_close, $.code_span_delimiter),
+        ),
+
+        // Latex spans (`$x$`, `$$x$$`), a common extension used by many markdown renderers.
+        latex_block: $ => seq(
+            alias($._latex_span_start, $.latex_span_delimiter),
+            repeat(choice($._text_base, '[', ']', $._soft_line_break, $.backslash_escape)),
+            alias($._latex_span_close, $.latex_span_delimiter),
+        ),
+
+        // Different kinds of links:
+        // * inline links (e.g. `[link text](link destination)`)
+        // * full reference links (e.g. `[link text][link label]`)
+        // * collapsed reference links (e.g. `[link text][]`)
+        // * shortcut links (e.g. `[link text]`)
+        //
+        // https://github.github.com/gfm/#links
+        _link_text: $ => prec.dynamic(PRECEDENCE_LEVEL_LINK, choice(
+            $._link_text_non_empty,
+            seq('[', ']'),
+        )),
+        _link_text_non_empty: $ => seq('[', alias($._inline_no_link, $.link_text), ']'),
+        shortcut_link: $ => prec.dynamic(PRECEDENCE_LEVEL_LINK, $._link_text_non_empty),
+        full_reference_link: $ => prec.dynamic(2 * PRECEDENCE_LEVEL_LINK, seq(
+            $._link_text,
+            $.link_label,
+        )),
+        collapsed_reference_link: $ => prec.dynamic(PRECEDENCE_LEVEL_LINK, seq(
+            $._link_text,
+            '[',
+            ']',
+        )),
+        inline_link: $ => prec.dynamic(PRECEDENCE_LEVEL_LINK, seq(
+            $._link_text,
+            $._link_target,
+        )),
+        _link_target: $ => seq(
+            '(',
+            repeat(choice($._whitespace, $._soft_line_break)),
+            optional(seq(
+                choice(
+                    seq(
+                        $.link_destination,
+                        optional(seq(
+                            repeat1(choice($._whitespace, $._soft_line_break)),
+                            $.link_title,
+                        )),
+                    ),
+                    $.link_title,
+                ),
+                repeat(choice($._whitespace, $._soft_line_break)),
+            )),
+            ')',
+        ),
+
+        // Images work exactly like links with a '!' added in front.
+        //
+        // https://github.github.com/gfm/#images
+        image: $ => choice(
+            $._image_inline_link,
+            $._image_shortcut_link,
+            $._image_full_reference_link,
+            $._image_collapsed_reference_link,
+        ),
+        _image_inline_link: $ => prec.dynamic(PRECEDENCE_LEVEL_LINK, seq(
+            $._image_description,
+            $._link_target,
+        )),
+        _image_shortcut_link: $ => prec.dynamic(3 * PRECEDENCE_LEVEL_LINK, $._image_description_non_empty),
+        _image_full_reference_link: $ => prec.dynamic(PRECEDENCE_LEVEL_LINK, seq($._image_description, $.link_label)),
+        _image_collapsed_reference_link: $ => prec.dynamic(PRECEDENCE_LEVEL_LINK, seq($._image_description, '[', ']')),
+        _image_description: $ => prec.dynamic(3 * PRECEDENCE_LEVEL_LINK, choice(
+            $._image_description_non_empty,
+            seq('!', '[', prec(1, ']')),
+        )),
+        _image_description_non_empty: $ => seq('!', '[', alias($._inline, $.image_description), prec(1, ']')),
+
+        // Autolinks. Uri autolinks accept schemes of any length, which does not quite match the
+        // spec, but the generated lexer would get a lot bigger otherwise.
+        //
+        // https://github.github.com/gfm/#autolinks
+        uri_autolink: $ => /<[a-zA-Z][a-zA-Z0-9+\.\-][a-zA-Z0-9+\.\-]*:[^ \t\r\n<>]*>/,
+        email_autolink: $ =>
+            /<[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?(\.[a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*>/,
+
+        // Raw html. Like html blocks, it is left to the html grammar, which gets injected into
+        // every `html_tag`.
+        //
+        // https://github.github.com/gfm/#raw-html
+        html_tag: $ => choice(
+            $._open_tag,
+            $._closing_tag,
+            $._html_comment,
+            $._processing_instruction,
+            $._declaration,
+            $._cdata_section,
+        ),
+        _open_tag: $ => prec.dynamic(PRECEDENCE_LEVEL_HTML, seq(
+            '<',
+            $._tag_name,
+            repeat($._attribute),
+            repeat(choice($._whitespace, $._soft_line_break)),
+            optional('/'),
+            '>',
+        )),
+        _closing_tag: $ => prec.dynamic(PRECEDENCE_LEVEL_HTML, seq(
+            '<',
+            '/',
+            $._tag_name,
+            repeat(choice($._whitespace, $._soft_line_break)),
+            '>',
+        )),
+        _tag_name: $ => seq($._word_no_digit, repeat(choice($._word_no_digit, $._digits, '-'))),
+        _attribute: $ => seq(
+            repeat1(choice($._whitespace, $._soft_line_break)),
+            $._attribute_name,
+            optional(seq(
+                repeat(choice($._whitespace, $._soft_line_break)),
+                '=',
+                repeat(choice($._whitespace, $._soft_line_break)),
+                $._attribute_value,
+            )),
+        ),
+        _attribute_name: $ => /[a-zA-Z_:][a-zA-Z0-9_\.:\-]*/,
+        _attribute_value: $ => choice(
+            /[^ \t\r\n"'=<>`]+/,
+            seq("'", repeat(choice($._word, $._whitespace, $._soft_line_break, common.punctuation_without($, ["'"]))), "'"),
+            seq('"', repeat(choice($._word, $._whitespace, $._soft_line_break, common.punctuation_without($, ['"']))), '"'),
+        ),
+        _html_comment: $ => prec.dynamic(PRECEDENCE_LEVEL_HTML, seq(
+            '<!--',
+            repeat(prec.right(choice(
+                $._word,
+                $._whitespace,
+                $._soft_line_break,
+                common.punctuation_without($, ['-']),
+                seq('-', choice(
+                    $._word,
+                    $._whitespace,
+                    $._soft_line_break,
+                    common.punctuation_without($, ['-']),
+                )),
+            ))),
+            '-->',
+        )),
+        _processing_instruction: $ => prec.dynamic(PRECEDENCE_LEVEL_HTML, seq(
+            '<?',
+            repeat(prec.right(choice(
+                $._word,
+                $._whitespace,
+                $._soft_line_break,
+                common.punctuation_without($, []),
+            ))),
+            '?>',
+        )),
+        _declaration: $ => prec.dynamic(PRECEDENCE_LEVEL_HTML, seq(
+            /<![A-Z]+/,
+            choice($._whitespace, $._soft_line_break),
+            repeat(prec.right(choice(
+                $._word,
+                $._whitespace,
+                $._soft_line_break,
+                common.punctuation_without($, ['>']),
+            ))),
+            '>',
+        )),
+        _cdata_section: $ => prec.dynamic(PRECEDENCE_LEVEL_HTML, seq(
+            '<![CDATA[',
+            repeat(prec.right(choice(
+                $._word,
+                $._whitespace,
+                $._soft_line_break,
+                common.punctuation_without($, []),
+            ))),
+            ']]>',
+        )),
+
+        // A hard line break: a backslash or at least two spaces before a line ending.
+        //
+        // https://github.github.com/gfm/#hard-line-breaks
+        hard_line_break: $ => seq(choice('\\', $._whitespace_ge_2), $._soft_line_break),
+
+        // Whitespace is divided into single whitespaces and multiple whitespaces as we need this
+        // information for hard line breaks.
+        _whitespace_ge_2: $ => /\t| [ \t]+/,
+        _whitespace: $ => seq(choice($._whitespace_ge_2, / /), optional($._last_token_whitespace)),
+
+        // Other than whitespace we tokenize into strings of digits, punctuation characters
+        // (handled by `common.punctuation_without`) and strings of any other characters. Underscores
+        // inside a word belong to the word, they can't open or close emphasis anyway.
+        _word: $ => choice($._word_no_digit, $._digits),
+        _word_no_digit: $ => new RegExp('[^' + PUNCTUATION_CHARACTERS_REGEX + ' \\t\\n\\r0-9]+(_+[^' + PUNCTUATION_CHARACTERS_REGEX + ' \\t\\n\\r]+)*'),
+        _digits: $ => /[0-9][0-9_]*/,
+        _soft_line_break: $ => seq($._newline_token, optional($._last_token_whitespace)),
+
+        _inline_base: $ => prec.right(repeat1(choice(
+            $.image,
+            $._soft_line_break,
+            $.backslash_escape,
+            $.hard_line_break,
+            $.uri_autolink,
+            $.email_autolink,
+            $.entity_reference,
+            $.numeric_character_reference,
+            common.EXTENSION_LATEX ? $.latex_block : choice(),
+            $.code_span,
+            $.html_tag,
+            $._text_base,
+            $._unclosed_span,
+        ))),
+        // Brackets are left out as they start and end links. Delimiter characters that the
+        // scanner doesn't claim (like the `*` in `2 * 3`) are plain punctuation.
+        _text_base: $ => choice(
+            $._word,
+            common.punctuation_without($, ['[', ']']),
+            $._whitespace,
+            '<!--',
+            /<![A-Z]+/,
+            '<?',
+            '<![CDATA[',
+        ),
+        _text_inline_no_link: $ => choice(
+            $._text_base,
+            $._emphasis_open_star,
+            $._emphasis_open_underscore,
+            $._unclosed_span,
+        ),
+    },
+}));
+
+// This function adds the rules for collections of inlines and for emphasis. Which inlines are
+// allowed depends on the context: link text may not contain links, and the content of emphasis
+// opened with a `*` may not contain another unmatched opening `*`.
+function add_inline_rules(grammar) {
+    let conflicts = [];
+    for (let link of [true, false]) {
+        let suffix_link = link ? '' : '_no_link';
+        for (let delimiter of [false, 'star', 'underscore', 'tilde']) {
+            let suffix_delimiter = delimiter ? '_no_' + delimiter : '';
+            let suffix = suffix_delimiter + suffix_link;
+            grammar.rules['_inline_element' + suffix] = $ => {
+                let elements = [
+                    $._inline_base,
+                    alias($['_emphasis_star' + suffix_link], $.emphasis),
+                    alias($['_strong_emphasis_star' + suffix_link], $.strong_emphasis),
+                    alias($['_emphasis_underscore' + suffix_link], $.emphasis),
+                    alias($['_strong_emphasis_underscore' + suffix_link], $.strong_emphasis),
+                ];
+                if (common.EXTENSION_STRIKETHROUGH) {
+                    elements.push(alias($['_strikethrough' + suffix_link], $.strikethrough));
+                }
+                // Opening delimiters without a matching closing delimiter are plain text
+                if (delimiter !== 'star') {
+                    elements.push($._emphasis_open_star);
+                }
+                if (delimiter !== 'underscore') {
+                    elements.push($._emphasis_open_underscore);
+                }
+                if (delimiter !== 'tilde') {
+                    elements.push($._strikethrough_open);
+                }
+                if (link) {
+                    elements.push(
+                        $.shortcut_link,
+                        $.full_reference_link,
+                        $.collapsed_reference_link,
+                        $.inline_link,
+                        // Brackets that don't end up being part of a link
+                        seq(choice('[', ']'), optional($._last_token_punctuation)),
+                    );
+                }
+                return choice(...elements);
+            };
+            grammar.rules['_inline' + suffix] = $ => prec.right(repeat1($['_inline_element' + suffix]));
+
+            if (delimiter === false) {
+                grammar.rules['_emphasis_star' + suffix_link] = $ => prec.dynamic(PRECEDENCE_LEVEL_EMPHASIS, seq(
+                    alias($._emphasis_open_star, $.emphasis_delimiter),
+                    optional($._last_token_punctuation),
+                    $['_inline_no_star' + suffix_link],
+                    alias($._emphasis_close_star, $.emphasis_delimiter),
+                ));
+                grammar.rules['_strong_emphasis_star' + suffix_link] = $ => prec.dynamic(2 * PRECEDENCE_LEVEL_EMPHASIS, seq(
+                    alias($._emphasis_open_star, $.emphasis_delimiter),
+                    $['_emphasis_star' + suffix_link],
+                    alias($._emphasis_close_star, $.emphasis_delimiter),
+                ));
+                grammar.rules['_emphasis_underscore' + suffix_link] = $ => prec.dynamic(PRECEDENCE_LEVEL_EMPHASIS, seq(
+                    alias($._emphasis_open_underscore, $.emphasis_delimiter),
+                    optional($._last_token_punctuation),
+                    $['_inline_no_underscore' + suffix_link],
+                    alias($._emphasis_close_underscore, $.emphasis_delimiter),
+                ));
+                grammar.rules['_strong_emphasis_underscore' + suffix_link] = $ => prec.dynamic(2 * PRECEDENCE_LEVEL_EMPHASIS, seq(
+                    alias($._emphasis_open_underscore, $.emphasis_delimiter),
+                    $['_emphasis_underscore' + suffix_link],
+                    alias($._emphasis_close_underscore, $.emphasis_delimiter),
+                ));
+                // GFM strikethrough uses one or two tildes on each side
+                grammar.rules['_strikethrough' + suffix_link] = $ => prec.dynamic(PRECEDENCE_LEVEL_EMPHASIS, choice(
+                    seq(
+                        alias($._strikethrough_open, $.emphasis_delimiter),
+                        optional($._last_token_punctuation),
+                        $['_inline_no_tilde' + suffix_link],
+                        alias($._strikethrough_close, $.emphasis_delimiter),
+                    ),
+                    prec.dynamic(PRECEDENCE_LEVEL_EMPHASIS, seq(
+                        alias($._strikethrough_open, $.emphasis_delimiter),
+                        alias($._strikethrough_open, $.emphasis_delimiter),
+                        optional($._last_token_punctuation),
+                        $['_inline_no_tilde' + suffix_link],
+                        alias($._strikethrough_close, $.emphasis_delimiter),
+                        alias($._strikethrough_close, $.emphasis_delimiter),
+                    )),
+                ));
+
+                // `**a*` could be the start of strong emphasis or an opening `*` followed by
+                // emphasis, only the closing delimiters tell.
+                conflicts.push(['_strong_emphasis_star' + suffix_link, '_inline_element_no_star' + suffix_link]);
+                conflicts.push(['_strong_emphasis_underscore' + suffix_link, '_inline_element_no_underscore' + suffix_link]);
+                conflicts.push(['_strikethrough' + suffix_link, '_inline_element_no_tilde' + suffix_link]);
+            }
+        }
+    }
+
+    let old = grammar.conflicts;
+    grammar.conflicts = $ => {
+        let cs = old($);
+        for (let conflict of conflicts) {
+            cs.push(conflict.map(rule => $[rule]));
+        }
+        return cs;
+    };
+    return grammar;
+}
@@ -0,0 +1,261 @@
+#include "tree_sitter/parser.h"
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// For explanation of the tokens see grammar.js
+typedef enum {
+    ERROR,
+    TRIGGER_ERROR,
+    CODE_SPAN_START,
+    CODE_SPAN_CLOSE,
+    EMPHASIS_OPEN_STAR,
+    EMPHASIS_OPEN_UNDERSCORE,
+    EMPHASIS_CLOSE_STAR,
+    EMPHASIS_CLOSE_UNDERSCORE,
+    LAST_TOKEN_WHITESPACE,
+    LAST_TOKEN_PUNCTUATION,
+    STRIKETHROUGH_OPEN,
+    STRIKETHROUGH_CLOSE,
+    LATEX_SPAN_START,
+    LATEX_SPAN_CLOSE,
+    UNCLOSED_SPAN
+} TokenType;
+
+// Whether the remaining delimiters of the current delimiter run open or close
+static const uint8_t STATE_EMPHASIS_DELIMITER_IS_OPEN = 0x1;
+
+typedef struct {
+    // Bit flags, see the `STATE_*` constants
+    uint8_t state;
+    // Length of the delimiter run that opened the current code span, or 0
+    uint8_t code_span_delimiter_length;
+    // Length of the delimiter run that opened the current latex span, or 0
+    uint8_t latex_span_delimiter_length;
+    // How many delimiters of the current emphasis or strikethrough delimiter
+    // run have yet to be emitted. The decision whether they open or close
+    // is made once, for the first delimiter of the run.
+    uint8_t num_emphasis_delimiters_left;
+} Scanner;
+
+static bool is_punctuation(int32_t chr) {
+    return (chr >= '!' && chr <= '/') || (chr >= ':' && chr <= '@') ||
+           (chr >= '[' && chr <= '`') || (chr >= '{' && chr <= '~');
+}
+
+static bool is_whitespace_or_end(TSLexer *lexer) {
+    return lexer->lookahead == ' ' || lexer->lookahead == '\t' ||
+           lexer->lookahead == '\n' || lexer->lookahead == '\r' ||
+           lexer->eof(lexer);
+}
+
+static unsigned serialize(Scanner *s, char *buffer) {
+    unsigned size = 0;
+    buffer[size++] = (char)s->state;
+    buffer[size++] = (char)s->code_span_delimiter_length;
+    buffer[size++] = (char)s->latex_span_delimiter_length;
+    buffer[size++] = (char)s->num_emphasis_delimiters_left;
+    return size;
+}
+
+static void deserialize(Scanner *s, const char *buffer, unsigned length) {
+    s->state = 0;
+    s->code_span_delimiter_length = 0;
+    s->latex_span_delimiter_length = 0;
+    s->num_emphasis_delimiters_left = 0;
+    if (length == 4) {
+        s->state = (uint8_t)buffer[0];
+        s->code_span_delimiter_length = (uint8_t)buffer[1];
+        s->latex_span_delimiter_length = (uint8_t)buffer[2];
+        s->num_emphasis_delimiters_left = (uint8_t)buffer[3];
+    }
+}
+
+static bool error(TSLexer *lexer) {
+    lexer->result_symbol = ERROR;
+    return true;
+}
+
+// Code spans and latex spans: a run of `delimiter`s opens a span only if a
+// run of the same length follows later on, and then closes it.
+static bool parse_leaf_delimiter(TSLexer *lexer, uint8_t *delimiter_length,
+                                 const bool *valid_symbols,
+                                 const int32_t delimiter,
+                                 const TokenType open_token,
+                                 const TokenType close_token) {
+    uint8_t level = 0;
+    while (lexer->lookahead == delimiter) {
+        lexer->advance(lexer, false);
+        if (level < UINT8_MAX) {
+            level++;
+        }
+    }
+    lexer->mark_end(lexer);
+    if (level == *delimiter_length && valid_symbols[close_token]) {
+        *delimiter_length = 0;
+        lexer->result_symbol = close_token;
+        return true;
+    }
+    if (valid_symbols[open_token]) {
+        // Look ahead for a closing run of the same length. The token itself
+        // ends at the `mark_end` above.
+        uint8_t close_level = 0;
+        while (!lexer->eof(lexer)) {
+            if (lexer->lookahead == delimiter) {
+                if (close_level < UINT8_MAX) {
+                    close_level++;
+                }
+            } else {
+                if (close_level == level) {
+                    break;
+                }
+                close_level = 0;
+            }
+            lexer->advance(lexer, false);
+        }
+        if (close_level == level) {
+            *delimiter_length = level;
+            lexer->result_symbol = open_token;
+            return true;
+        }
+        if (valid_symbols[UNCLOSED_SPAN]) {
+            lexer->result_symbol = UNCLOSED_SPAN;
+            return true;
+        }
+    }
+    return false;
+}
+
+// Emphasis and strikethrough delimiters. Each character of a delimiter run
+// is its own token, so that `***a***` can be strong emphasis around
+// emphasis. Whether the run opens or closes depends on what comes before it
+// (known from `LAST_TOKEN_*` being valid) and after it, following the
+// left- and right-flanking rules of the spec.
+//
+// https://github.github.com/gfm/#left-flanking-delimiter-run
+static bool parse_emphasis_delimiter(Scanner *s, TSLexer *lexer,
+                                     const bool *valid_symbols,
+                                     const int32_t delimiter,
+                                     const TokenType open_token,
+                                     const TokenType close_token) {
+    lexer->advance(lexer, false);
+    // If there are delimiters left, this one belongs to a run we've already
+    // decided on
+    if (s->num_emphasis_delimiters_left > 0) {
+        if ((s->state & STATE_EMPHASIS_DELIMITER_IS_OPEN) &&
+            valid_symbols[open_token]) {
+            s->num_emphasis_delimiters_left--;
+            lexer->result_symbol = open_token;
+            return true;
+        }
+        if (!(s->state & STATE_EMPHASIS_DELIMITER_IS_OPEN) &&
+            valid_symbols[close_token]) {
+            s->num_emphasis_delimiters_left--;
+            lexer->result_symbol = close_token;
+            return true;
+        }
+    }
+    lexer->mark_end(lexer);
+    uint8_t count = 1;
+    while (lexer->lookahead == delimiter) {
+        if (count < UINT8_MAX) {
+            count++;
+        }
+        lexer->advance(lexer, false);
+    }
+    if (!valid_symbols[open_token] && !valid_symbols[close_token]) {
+        return false;
+    }
+    bool last_whitespace = valid_symbols[LAST_TOKEN_WHITESPACE];
+    bool last_punctuation = valid_symbols[LAST_TOKEN_PUNCTUATION];
+    bool next_whitespace = is_whitespace_or_end(lexer);
+    bool next_punctuation = is_punctuation(lexer->lookahead);
+    bool left_flanking =
+        !next_whitespace &&
+        (!next_punctuation || last_whitespace || last_punctuation);
+    bool right_flanking =
+        !last_whitespace &&
+        (!last_punctuation || next_whitespace || next_punctuation);
+    bool can_open = left_flanking;
+    bool can_close = right_flanking;
+    if (delimiter == '_') {
+        // `_` can't open or close emphasis inside a word
+        can_open = left_flanking && (!right_flanking || last_punctuation);
+        can_close = right_flanking && (!left_flanking || next_punctuation);
+    }
+    // Closing delimiters take precedence
+    if (can_close && valid_symbols[close_token]) {
+        s->state &= (uint8_t)~STATE_EMPHASIS_DELIMITER_IS_OPEN;
+        s->num_emphasis_delimiters_left = count - 1;
+        lexer->result_symbol = close_token;
+        return true;
+    }
+    if (can_open && valid_symbols[open_token]) {
+        s->state |= STATE_EMPHASIS_DELIMITER_IS_OPEN;
+        s->num_emphasis_delimiters_left = count - 1;
+        lexer->result_symbol = open_token;
+        return true;
+    }
+    return false;
+}
+
+static bool scan(Scanner *s, TSLexer *lexer, const bool *valid_symbols) {
+    // A normal tree-sitter rule decided that the current branch is invalid
+    // and now "requests" an error to stop the branch
+    if (valid_symbols[TRIGGER_ERROR]) {
+        return error(lexer);
+    }
+
+    switch (lexer->lookahead) {
+        case '`':
+            return parse_leaf_delimiter(lexer, &s->code_span_delimiter_length,
+                                        valid_symbols, '`', CODE_SPAN_START,
+                                        CODE_SPAN_CLOSE);
+        case '$':
+            return parse_leaf_delimiter(lexer, &s->latex_span_delimiter_length,
+                                        valid_symbols, '$', LATEX_SPAN_START,
+                                        LATEX_SPAN_CLOSE);
+        case '*':
+            return parse_emphasis_delimiter(s, lexer, valid_symbols, '*',
+                                            EMPHASIS_OPEN_STAR,
+                                            EMPHASIS_CLOSE_STAR);
+        case '_':
+            return parse_emphasis_delimiter(s, lexer, valid_symbols, '_',
+                                            EMPHASIS_OPEN_UNDERSCORE,
+                                            EMPHASIS_CLOSE_UNDERSCORE);
+        case '~':
+            return parse_emphasis_delimiter(s, lexer, valid_symbols, '~',
+                                            STRIKETHROUGH_OPEN,
+                                            STRIKETHROUGH_CLOSE);
+    }
+    return false;
+}
+
+void *tree_sitter_markdown_inline_external_scanner_create(void) {
+    Scanner *s = (Scanner *)malloc(sizeof(Scanner));
+    deserialize(s, NULL, 0);
+    return s;
+}
+
+bool tree_sitter_markdown_inline_external_scanner_scan(
+    void *payload, TSLexer *lexer, const bool *valid_symbols) {
+    Scanner *scanner = (Scanner *)payload;
+    return scan(scanner, lexer, valid_symbols);
+}
+
+unsigned tree_sitter_markdown_inline_external_scanner_serialize(void *payload,
+                                                                char *buffer) {
+    Scanner *scanner = (Scanner *)payload;
+    return serialize(scanner, buffer);
+}
+
+void tree_sitter_markdown_inline_external_scanner_deserialize(
+    void *payload, const char *buffer, unsigned length) {
+    Scanner *scanner = (Scanner *)payload;
+    deserialize(scanner, buffer, length);
+}
+
+void tree_sitter_markdown_inline_external_scanner_destroy(void *payload) {
+    Scanner *scanner = (Scanner *)payload;
+    free(scanner);
+}
@@ -0,0 +1,54 @@
+;From nvim-treesitter/nvim-treesitter
+[
+  (code_span)
+  (link_title)
+] @text.literal
+
+[
+  (emphasis_delimiter)
+  (code_span_delimiter)
+] @punctuation.delimiter
+
+(emphasis) @text.emphasis
+
+(strong_emphasis) @text.strong
+
+(strikethrough) @text.strikethrough
+
+[
+  (link_destination)
+  (uri_autolink)
+  (email_autolink)
+] @text.uri
+
+[
+  (link_label)
+  (link_text)
+  (image_description)
+] @text.reference
+
+[
+  (backslash_escape)
+  (hard_line_break)
+] @string.escape
+
+[
+  (entity_reference)
+  (numeric_character_reference)
+] @string.special
+
+(image "!" @punctuation.special)
+
+(image ["[" "]" "(" ")"] @punctuation.bracket)
+
+(inline_link ["[" "]" "(" ")"] @punctuation.bracket)
+
+(shortcut_link ["[" "]"] @punctuation.bracket)
+
+(full_reference_link ["[" "]"] @punctuation.bracket)
+
+(collapsed_reference_link ["[" "]"] @punctuation.bracket)
+
+(latex_block) @markup.math
+
+(latex_span_delimiter) @punctuation.delimiter
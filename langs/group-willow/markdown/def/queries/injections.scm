@@ -11,3 +11,9 @@
 
 ((plus_metadata) @injection.content (#set! injection.language "toml"))
 
+; Paragraphs, heading text and table cells are parsed by the inline grammar
+((inline) @injection.content
+  (#set! injection.language "markdown-inline"))
+
+((pipe_table_cell) @injection.content
+  (#set! injection.language "markdown-inline"))
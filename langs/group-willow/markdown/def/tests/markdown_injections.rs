@@ -0,0 +1,91 @@
+//! Markdown block structure comes from the block grammar; paragraphs and
+//! table cells are handed to the internal inline grammar, and fenced code
+//! to the language named in the info string.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SOURCE: &str = "\
+# Notes
+
+Some **strong** and *emphasised* text with `code` and a [link](https://example.com).
+
+| Name | Value |
+| ---- | ----- |
+| `a`  | *one* |
+
+```rust
+fn main() {}
+```
+";
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_markdown::language(),
+        helpers::queries!(arborium_markdown),
+        source,
+    )
+}
+
+#[test]
+fn test_paragraphs_and_cells_inject_markdown_inline() {
+    let injections = injected(SOURCE);
+    let inline: Vec<&str> = injections
+        .iter()
+        .filter(|(_, language)| language == "markdown-inline")
+        .map(|(text, _)| *text)
+        .collect();
+    assert!(
+        inline.iter().any(|text| text.contains("**strong**")),
+        "{:?}",
+        injections
+    );
+    assert!(inline.contains(&"*one*"), "{:?}", injections);
+}
+
+#[test]
+fn test_fences_inject_their_info_string() {
+    let injections = injected(SOURCE);
+    let rust: Vec<_> = injections
+        .iter()
+        .filter(|(_, language)| language == "rust")
+        .collect();
+    assert_eq!(rust.len(), 1, "{:?}", injections);
+    assert!(rust[0].0.contains("fn main() {}"), "{:?}", rust);
+    assert!(!rust[0].0.contains("```"), "{:?}", rust);
+}
+
+#[test]
+fn test_full_pipeline_highlights_inline_markup_and_code() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "markdown",
+            TestGrammar::new(
+                arborium_markdown::language(),
+                arborium_markdown::HIGHLIGHTS_QUERY,
+                arborium_markdown::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "markdown-inline",
+            TestGrammar::new(
+                arborium_markdown_inline::language(),
+                arborium_markdown_inline::HIGHLIGHTS_QUERY,
+                arborium_markdown_inline::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    let html = highlighter.highlight("markdown", SOURCE).unwrap();
+    assert!(html.contains("<a-st>"), "{}", html);
+    assert!(html.contains("<a-em>"), "{}", html);
+    assert!(html.contains("<a-tu>"), "{}", html);
+    assert!(html.contains("<a-tl>"), "{}", html);
+    assert!(html.contains("<a-k>fn</a-k>"), "{}", html);
+}
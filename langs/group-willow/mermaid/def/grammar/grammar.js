@@ -0,0 +1,266 @@
+/**
+ * @file Mermaid grammar for tree-sitter
+ * @license MIT
+ *
+ * Mermaid diagrams as written in fenced code blocks: optional YAML front
+ * matter, `%%{init: ...}%%` directives and `%%` comments, then a header
+ * naming the diagram type.
+ *
+ * Flowcharts (`graph`, `flowchart`) and sequence diagrams are parsed
+ * statement by statement. Every other diagram type, class and state
+ * diagrams included, is read as a stream of words, strings, arrows and
+ * `: labels`, so a dialect this grammar doesn't know still highlights
+ * without errors.
+ * https://mermaid.js.org/intro/syntax-reference.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+/**
+ * @param {RuleOrLiteral} rule
+ * @param {RuleOrLiteral} separator
+ */
+function sepBy(rule, separator) {
+  return seq(repeat(seq(optional(rule), separator)), optional(rule));
+}
+
+/**
+ * @param {Rule} rule
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
+
+module.exports = grammar({
+  name: 'mermaid',
+
+  extras: $ => [/[ \t\r]/, $.comment, $.directive],
+
+  rules: {
+    diagram: $ => seq(
+      repeat($._newline),
+      optional(seq($.front_matter, $._newline)),
+      repeat($._newline),
+      optional(choice($.flowchart, $.sequence_diagram, $.generic_diagram)),
+    ),
+
+    _newline: _ => /\n/,
+
+    comment: _ => /%%[^\n]*/,
+
+    // %%{init: {"theme": "forest"}}%%
+    directive: _ => token(prec(1, /%%\{[^\n]*\}%%/)),
+
+    front_matter: _ => token(seq(
+      '---',
+      /\r?\n/,
+      repeat(/([^-\n][^\n]*|-[^-\n][^\n]*|--[^-\n][^\n]*)?\r?\n/),
+      '---',
+    )),
+
+    _separator: $ => choice($._newline, ';'),
+
+    // Flowcharts
+
+    flowchart: $ => seq(
+      field('type', alias(choice('graph', 'flowchart'), $.diagram_type)),
+      optional(field('direction', $.direction)),
+      sepBy($._flowchart_statement, $._separator),
+    ),
+
+    direction: _ => choice('TB', 'TD', 'BT', 'RL', 'LR'),
+
+    _flowchart_statement: $ => choice(
+      $.node_statement,
+      $.subgraph,
+      $.end,
+      $.direction_statement,
+      $.class_def,
+      $.class_statement,
+      $.style,
+      $.link_style,
+      $.click,
+    ),
+
+    // A --> B & C -- yes --> D
+    node_statement: $ => seq(
+      $._node_group,
+      repeat(seq($.edge, $._node_group)),
+    ),
+
+    _node_group: $ => seq($.node, repeat(seq('&', $.node))),
+
+    node: $ => seq(
+      field('id', $.node_id),
+      optional(field('shape', $.shape)),
+      optional(seq(':::', field('class', $.class_name))),
+    ),
+
+    node_id: _ => /[A-Za-z0-9_]+/,
+
+    class_name: _ => /[A-Za-z0-9_-]+/,
+
+    // [rectangle], (rounded), {diamond}, ((circle)), [(database)], ...
+    shape: $ => choice(
+      seq('[', $._square_label, ']'),
+      seq('(', $._round_label, ')'),
+      seq('{', $._curly_label, '}'),
+      seq('((', $._round_label, '))'),
+      seq('(((', $._round_label, ')))'),
+      seq('[[', $._square_label, ']]'),
+      seq('[(', $._round_label, ')]'),
+      seq('([', $._square_label, '])'),
+      seq('{{', $._curly_label, '}}'),
+      seq('>', $._square_label, ']'),
+      seq(choice('[/', '[\\'), $._slanted_label, choice('/]', '\\]')),
+    ),
+
+    _square_label: $ => choice($.string, alias(/[^\]"\n]+/, $.label)),
+
+    _round_label: $ => choice($.string, alias(/[^)"\n]+/, $.label)),
+
+    _curly_label: $ => choice($.string, alias(/[^}"\n]+/, $.label)),
+
+    _slanted_label: $ => choice($.string, alias(/[^\/\\\]"\n]+/, $.label)),
+
+    // -->, ---, -.->, ==>, ~~~, <-->, --o, --x, with a |label| or a label
+    // inside the arrow, like `-- yes -->`
+    edge: $ => choice(
+      seq(
+        field('arrow', $.arrow),
+        optional(seq(
+          '|',
+          optional(field('label', alias(/[^|\n]+/, $.label))),
+          '|',
+        )),
+      ),
+      seq(
+        field('arrow', alias(choice('--', '==', '-.'), $.arrow)),
+        field('label', alias(
+          /([^-=.\s]|[-=.][^-=.>\s])+([ \t]+([^-=.\s]|[-=.][^-=.>\s])+)*/,
+          $.label,
+        )),
+        field('arrow', $.arrow),
+      ),
+    ),
+
+    arrow: _ => /<?(--+[->ox]|==+[=>ox]|-?\.+-+[>ox]?|~~~+)/,
+
+    subgraph: $ => seq('subgraph', optional(field('title', $.text))),
+
+    end: _ => 'end',
+
+    direction_statement: $ => seq('direction', $.direction),
+
+    class_def: $ => seq(
+      'classDef',
+      field('name', commaSep1($.class_name)),
+      optional(field('styles', $.text)),
+    ),
+
+    class_statement: $ => seq(
+      'class',
+      commaSep1($.node_id),
+      field('class', $.class_name),
+    ),
+
+    style: $ => seq('style', field('id', $.node_id), optional(field('styles', $.text))),
+
+    link_style: $ => seq('linkStyle', optional(field('styles', $.text))),
+
+    click: $ => seq('click', field('id', $.node_id), optional($.text)),
+
+    text: _ => /[^\s;,][^\n;]*/,
+
+    string: _ => /"[^"\n]*"/,
+
+    // Sequence diagrams
+
+    sequence_diagram: $ => seq(
+      field('type', alias('sequenceDiagram', $.diagram_type)),
+      sepBy($._sequence_statement, $._separator),
+    ),
+
+    _sequence_statement: $ => choice(
+      $.participant,
+      $.message,
+      $.note,
+      $.block,
+      $.end,
+      $.activation,
+      $.autonumber,
+    ),
+
+    participant: $ => seq(
+      choice('participant', 'actor'),
+      field('name', $.actor),
+      optional(seq('as', field('alias', $.text))),
+    ),
+
+    actor: _ => /[A-Za-z0-9_]+/,
+
+    // Alice->>+Bob: Hello
+    message: $ => seq(
+      field('from', $.actor),
+      field('arrow', $.message_arrow),
+      optional(field('activation', choice('+', '-'))),
+      field('to', $.actor),
+      ':',
+      optional(field('text', $.message_text)),
+    ),
+
+    message_arrow: _ => /(<<)?--?(>>|>|x|\))/,
+
+    message_text: _ => /[^\n;]+/,
+
+    note: $ => seq(
+      choice('Note', 'note'),
+      choice(seq(choice('left', 'right'), 'of'), 'over'),
+      commaSep1($.actor),
+      ':',
+      optional(field('text', $.message_text)),
+    ),
+
+    // loop Every minute, alt Success, else Failure, ...
+    block: $ => seq(
+      field('kind', choice(
+        'loop', 'alt', 'else', 'opt', 'par', 'and', 'critical', 'option',
+        'break', 'rect', 'box',
+      )),
+      optional(field('label', $.text)),
+    ),
+
+    activation: $ => seq(choice('activate', 'deactivate'), $.actor),
+
+    autonumber: $ => seq('autonumber', optional($.text)),
+
+    // Everything else
+
+    generic_diagram: $ => seq(
+      field('type', $.diagram_type),
+      repeat(choice(
+        $._newline,
+        $.word,
+        $.number,
+        $.string,
+        $.operator,
+        $.description,
+        $._symbol,
+      )),
+    ),
+
+    diagram_type: _ => /[A-Za-z][A-Za-z0-9-]*/,
+
+    word: _ => /[A-Za-z_][A-Za-z0-9_]*/,
+
+    number: _ => /[0-9]+(\.[0-9]+)?/,
+
+    // <|--, *--, ..>, -->, --
+    operator: _ => /[<>|*.=~+-]{2,}/,
+
+    description: $ => seq(':', optional(alias(/[^\n]+/, $.text))),
+
+    _symbol: _ => /[^\sA-Za-z0-9_:]/,
+  },
+});
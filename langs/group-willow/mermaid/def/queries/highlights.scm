@@ -0,0 +1,132 @@
+(comment) @comment
+
+(directive) @keyword.directive
+
+(front_matter) @comment
+
+(diagram_type) @keyword
+
+(direction) @constant.builtin
+
+(string) @string
+
+(label) @string
+
+(text) @string
+
+(message_text) @string
+
+; Flowcharts
+
+(node
+  id: (node_id) @variable)
+
+(class_statement
+  (node_id) @variable)
+
+(style
+  id: (node_id) @variable)
+
+(click
+  id: (node_id) @variable)
+
+(class_name) @type
+
+(arrow) @operator
+
+[
+  "subgraph"
+  "direction"
+  "classDef"
+  "class"
+  "style"
+  "linkStyle"
+  "click"
+] @keyword
+
+(end) @keyword
+
+[
+  "["
+  "]"
+  "("
+  ")"
+  "{"
+  "}"
+  "(("
+  "))"
+  "((("
+  ")))"
+  "[["
+  "]]"
+  "[("
+  ")]"
+  "(["
+  "])"
+  "{{"
+  "}}"
+  ">"
+  "[/"
+  "[\\"
+  "/]"
+  "\\]"
+  "|"
+] @punctuation.bracket
+
+[
+  "&"
+  ":::"
+] @punctuation.delimiter
+
+; Sequence diagrams
+
+(actor) @variable
+
+(message_arrow) @operator
+
+(message
+  activation: _ @operator)
+
+[
+  "participant"
+  "actor"
+  "as"
+  "Note"
+  "note"
+  "left"
+  "right"
+  "of"
+  "over"
+  "activate"
+  "deactivate"
+  "autonumber"
+] @keyword
+
+(block
+  kind: _ @keyword.control)
+
+[
+  ":"
+  ","
+  ";"
+] @punctuation.delimiter
+
+; Other diagram types
+
+(operator) @operator
+
+(number) @number
+
+(description
+  (text) @string)
+
+((word) @keyword
+  (#any-of? @keyword
+    "class" "state" "note" "as" "direction" "end" "namespace"
+    "title" "section" "accTitle" "accDescr" "dateFormat" "axisFormat"
+    "excludes" "includes" "todayMarker" "showData" "commit" "branch"
+    "checkout" "merge" "cherry-pick" "participant" "actor" "loop" "alt"
+    "opt" "par" "rect"))
+
+((word) @constant.builtin
+  (#any-of? @constant.builtin "TB" "TD" "BT" "RL" "LR"))
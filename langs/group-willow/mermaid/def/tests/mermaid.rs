@@ -0,0 +1,93 @@
+//! Flowcharts and sequence diagrams parse statement by statement; every
+//! other diagram type falls back to a flat token stream that still
+//! highlights instead of erroring.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+use arborium_test_harness::tree_sitter::Tree;
+
+const FLOWCHART: &str = include_str!("../samples/checkout.mmd");
+const SEQUENCE: &str = include_str!("../samples/login.mmd");
+const STATE: &str = include_str!("../samples/order.mmd");
+
+fn parse(source: &str) -> Tree {
+    helpers::parse_tree(arborium_mermaid::language(), source)
+}
+
+fn nodes<'a>(source: &'a str, kind: &str) -> Vec<&'a str> {
+    helpers::nodes(arborium_mermaid::language(), source, kind)
+}
+
+fn highlight(source: &str) -> String {
+    let grammars = [(
+        "mermaid",
+        TestGrammar::new(
+            arborium_mermaid::language(),
+            arborium_mermaid::HIGHLIGHTS_QUERY,
+            "",
+        ),
+    )];
+    helpers::highlighter(grammars)
+        .highlight("mermaid", source)
+        .unwrap()
+}
+
+#[test]
+fn test_samples_parse_cleanly() {
+    for sample in [FLOWCHART, SEQUENCE, STATE] {
+        let tree = parse(sample);
+        let root = tree.root_node();
+        assert!(!root.has_error(), "{}", root.to_sexp());
+    }
+}
+
+#[test]
+fn test_flowchart_nodes_and_edges() {
+    let source = "graph TD\n  A[Start] --> B{Ok?}\n  B -- yes --> C((Done))\n  B -.->|no| A\n";
+    assert_eq!(nodes(source, "diagram_type"), ["graph"]);
+    assert_eq!(nodes(source, "direction"), ["TD"]);
+    assert_eq!(nodes(source, "node_id"), ["A", "B", "B", "C", "B", "A"]);
+    assert_eq!(
+        nodes(source, "label"),
+        ["Start", "Ok?", "yes", "Done", "no"]
+    );
+    assert_eq!(nodes(source, "arrow"), ["-->", "--", "-->", "-.->"]);
+}
+
+#[test]
+fn test_sequence_messages() {
+    let source = "sequenceDiagram\n  Alice->>+Bob: Hi; Bob-->>-Alice: Hello\n";
+    assert_eq!(nodes(source, "message").len(), 2);
+    assert_eq!(nodes(source, "actor"), ["Alice", "Bob", "Bob", "Alice"]);
+    assert_eq!(nodes(source, "message_arrow"), ["->>", "-->>"]);
+    assert_eq!(nodes(source, "message_text"), ["Hi", "Hello"]);
+}
+
+#[test]
+fn test_unknown_diagrams_fall_back() {
+    let source = "journey\n  title My working day\n  section Go to work\n    Make tea: 5: Me\n";
+    assert_eq!(nodes(source, "diagram_type"), ["journey"]);
+    assert_eq!(nodes(source, "description").len(), 1);
+
+    let html = highlight(STATE);
+    assert!(html.contains("<a-k>stateDiagram-v2</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>state</a-k>"), "{}", html);
+    assert!(html.contains("<a-o>--&gt;</a-o>"), "{}", html);
+    assert!(html.contains("<a-s>payment captured</a-s>"), "{}", html);
+}
+
+#[test]
+fn test_highlights() {
+    let html = highlight(FLOWCHART);
+    assert!(html.contains("<a-k>flowchart</a-k>"), "{}", html);
+    assert!(html.contains("<a-v>cart</a-v>"), "{}", html);
+    assert!(html.contains("<a-o>-.-&gt;</a-o>"), "{}", html);
+    assert!(html.contains("<a-s>Signed in?</a-s>"), "{}", html);
+    assert!(html.contains("<a-t>failure</a-t>"), "{}", html);
+    assert!(html.contains("<a-c>%% Entry points</a-c>"), "{}", html);
+
+    let html = highlight(SEQUENCE);
+    assert!(html.contains("<a-k>sequenceDiagram</a-k>"), "{}", html);
+    assert!(html.contains("<a-v>Auth</a-v>"), "{}", html);
+    assert!(html.contains("<a-o>-&gt;&gt;</a-o>"), "{}", html);
+    assert!(html.contains("<a-k>loop</a-k>"), "{}", html);
+}
@@ -0,0 +1,226 @@
+/**
+ * @file Org grammar for tree-sitter
+ * @license MIT
+ *
+ * Org documents line by line: headlines with their TODO keyword, priority
+ * and tags, `#+KEY:` settings, blocks, drawers, lists, tables and
+ * paragraphs of inline markup. Source blocks keep their language and
+ * contents apart so the contents can be handed to that language.
+ *
+ * Every line ends in an explicit newline, so whitespace is never an extra.
+ * https://orgmode.org/worg/org-syntax.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Block delimiters and the drawer end are case-insensitive, as in Org.
+const BEGIN = /#\+[bB][eE][gG][iI][nN]_/;
+const END = /#\+[eE][nN][dD]_/;
+const SRC = /[sS][rR][cC]/;
+
+module.exports = grammar({
+  name: 'org',
+
+  extras: _ => [],
+
+  rules: {
+    document: $ => repeat($._element),
+
+    _element: $ => choice(
+      $._blank_line,
+      $.headline,
+      $.keyword,
+      $.src_block,
+      $.block,
+      $.drawer,
+      $.comment,
+      $.list_item,
+      $.table,
+      $.horizontal_rule,
+      $.paragraph,
+    ),
+
+    _blank_line: _ => /[ \t]*\r?\n/,
+
+    _newline: _ => /\r?\n/,
+
+    // * TODO [#A] Title :tag:other:
+    headline: $ => seq(
+      field('stars', $.stars),
+      optional($.todo_keyword),
+      optional($.priority),
+      optional(field('title', $.title)),
+      choice($.tags, $._newline),
+    ),
+
+    stars: _ => /\*+[ \t]+/,
+
+    // The keyword and priority take the spaces after them, so a title
+    // never starts with one.
+    todo_keyword: _ => token(seq(
+      choice('TODO', 'DONE', 'NEXT', 'WAITING', 'HOLD', 'CANCELLED'),
+      /[ \t]+/,
+    )),
+
+    priority: _ => /\[#[A-Z0-9]\][ \t]*/,
+
+    title: $ => repeat1($._inline),
+
+    // The tags end the line, newline included, so that `:a:` elsewhere in
+    // a title stays a word.
+    tags: _ => /[ \t]+:([\w@#%]+:)+[ \t]*\r?\n/,
+
+    // #+TITLE: Notes
+    keyword: $ => seq(
+      field('key', $.keyword_key),
+      optional($._space),
+      optional(field('value', $.keyword_value)),
+      $._newline,
+    ),
+
+    keyword_key: _ => token(prec(1, /#\+[A-Za-z_-]+:/)),
+
+    keyword_value: _ => /[^\s][^\n]*/,
+
+    // #+begin_src rust :results silent
+    src_block: $ => seq(
+      alias(token(prec(2, seq(/[ \t]*/, BEGIN, SRC))), $.block_begin),
+      optional(seq(
+        $._space,
+        field('language', $.language),
+        optional(seq($._space, optional(field('parameters', $.parameters)))),
+      )),
+      $._newline,
+      optional(field('contents', $.contents)),
+      alias(token(prec(2, seq(/[ \t]*/, END, SRC, /[ \t]*(\r?\n)?/))), $.block_end),
+    ),
+
+    language: _ => /[^\s]+/,
+
+    parameters: _ => /[^\s][^\n]*/,
+
+    // Lines are taken as they are until the end line, which takes
+    // precedence over a content line matching all of it.
+    contents: $ => repeat1($._content_line),
+
+    _content_line: _ => /[^\n]*\r?\n/,
+
+    // #+begin_quote, #+begin_example, ...
+    block: $ => seq(
+      alias(token(prec(1, seq(/[ \t]*/, BEGIN))), $.block_begin),
+      field('name', alias(token.immediate(/[A-Za-z]+/), $.block_name)),
+      optional(seq($._space, optional(field('parameters', $.parameters)))),
+      $._newline,
+      optional(field('contents', $.contents)),
+      alias(token(prec(1, seq(/[ \t]*/, END, /[A-Za-z]+[ \t]*(\r?\n)?/))), $.block_end),
+    ),
+
+    // :PROPERTIES:
+    // :ID: 1
+    // :END:
+    drawer: $ => seq(
+      field('name', $.drawer_name),
+      repeat(choice($.property, $._content_line)),
+      alias(token(prec(2, seq(/[ \t]*/, /:[eE][nN][dD]:/, /[ \t]*(\r?\n)?/))), $.drawer_end),
+    ),
+
+    drawer_name: _ => token(prec(1, /[ \t]*:[A-Za-z_-]+:[ \t]*\r?\n/)),
+
+    property: $ => seq(
+      field('name', $.property_name),
+      optional(field('value', $.property_value)),
+      $._newline,
+    ),
+
+    property_name: _ => token(prec(1, /[ \t]*:[A-Za-z_+-]+:/)),
+
+    property_value: _ => /[^\n]+/,
+
+    comment: _ => /[ \t]*#([ \t][^\n]*)?\r?\n/,
+
+    // - item, + item, 1. item, 2) [X] item
+    list_item: $ => seq(
+      field('bullet', $.bullet),
+      optional($.checkbox),
+      repeat($._inline),
+      $._newline,
+    ),
+
+    bullet: _ => /[ \t]*([-+]|\d+[.)])[ \t]+|[ \t]+\*[ \t]+/,
+
+    checkbox: _ => /\[[ xX-]\]/,
+
+    table: $ => prec.right(repeat1(choice($.table_row, $.table_rule))),
+
+    table_row: $ => seq(
+      alias(token(prec(1, /[ \t]*\|/)), '|'),
+      repeat(seq(optional($.table_cell), '|')),
+      optional($.table_cell),
+      $._newline,
+    ),
+
+    table_rule: _ => token(prec(1, /[ \t]*\|-[-+|]*[ \t]*\r?\n/)),
+
+    table_cell: _ => /[^|\n]+/,
+
+    horizontal_rule: _ => /[ \t]*-{5,}[ \t]*\r?\n/,
+
+    paragraph: $ => prec.right(repeat1(seq(repeat1($._inline), $._newline))),
+
+    _inline: $ => choice(
+      $._word,
+      $._space,
+      $.bold,
+      $.italic,
+      $.underline,
+      $.verbatim,
+      $.code,
+      $.strike_through,
+      $.link,
+      $.timestamp,
+      $._marker,
+    ),
+
+    _word: $ => alias(/[^\s*\/_=~+\[<|][^\s]*/, $.word),
+
+    _space: _ => /[ \t]+/,
+
+    // *bold* /italic/ _underline_ =verbatim= ~code~ +strike+
+    bold: _ => /\*[^\s*]([^*\n]*[^\s*])?\*/,
+
+    italic: _ => /\/[^\s\/]([^\/\n]*[^\s\/])?\//,
+
+    underline: _ => /_[^\s_]([^_\n]*[^\s_])?_/,
+
+    verbatim: _ => /=[^\s=]([^=\n]*[^\s=])?=/,
+
+    code: _ => /~[^\s~]([^~\n]*[^\s~])?~/,
+
+    strike_through: _ => /\+[^\s+]([^+\n]*[^\s+])?\+/,
+
+    // [[https://orgmode.org][Org]]
+    link: $ => seq(
+      '[[',
+      field('url', alias($._link_part, $.link_url)),
+      token.immediate(']'),
+      optional(seq(
+        token.immediate('['),
+        field('description', alias($._link_part, $.link_description)),
+        token.immediate(']'),
+      )),
+      token.immediate(']'),
+    ),
+
+    _link_part: _ => token.immediate(/[^\]\n]+/),
+
+    // <2024-05-01 Wed 10:00>, [2024-05-01 Wed]
+    timestamp: _ => choice(
+      /<\d{4}-\d{2}-\d{2}[^>\n]*>/,
+      /\[\d{4}-\d{2}-\d{2}[^\]\n]*\]/,
+    ),
+
+    // A markup character that doesn't open any markup is just text.
+    _marker: $ => alias(/[*\/_=~+\[<|]/, $.word),
+  },
+});
@@ -0,0 +1,139 @@
+; Headlines
+
+(headline
+  (stars) @punctuation.special)
+
+((headline
+  (stars) @_stars
+  title: (title) @markup.heading.1)
+  (#match? @_stars "^\\*[ \t]"))
+
+((headline
+  (stars) @_stars
+  title: (title) @markup.heading.2)
+  (#match? @_stars "^\\*{2}[ \t]"))
+
+((headline
+  (stars) @_stars
+  title: (title) @markup.heading.3)
+  (#match? @_stars "^\\*{3}[ \t]"))
+
+((headline
+  (stars) @_stars
+  title: (title) @markup.heading.4)
+  (#match? @_stars "^\\*{4}[ \t]"))
+
+((headline
+  (stars) @_stars
+  title: (title) @markup.heading.5)
+  (#match? @_stars "^\\*{5}[ \t]"))
+
+((headline
+  (stars) @_stars
+  title: (title) @markup.heading.6)
+  (#match? @_stars "^\\*{6,}[ \t]"))
+
+((todo_keyword) @keyword
+  (#match? @keyword "^(TODO|NEXT|WAITING|HOLD)"))
+
+((todo_keyword) @comment
+  (#match? @comment "^(DONE|CANCELLED)"))
+
+(priority) @attribute
+
+(tags) @tag
+
+; Settings and drawers
+
+(keyword_key) @keyword.directive
+
+(keyword_value) @string
+
+((keyword
+  key: (keyword_key) @_key
+  value: (keyword_value) @markup.heading.1)
+  (#match? @_key "^#\\+[tT][iI][tT][lL][eE]:$"))
+
+(drawer_name) @keyword.directive
+
+(drawer_end) @keyword.directive
+
+(property_name) @property
+
+(property_value) @string
+
+((word) @keyword
+  (#any-of? @keyword "SCHEDULED:" "DEADLINE:" "CLOSED:"))
+
+(comment) @comment
+
+; Blocks
+
+(src_block
+  language: (language) @label)
+
+[
+  (block_begin)
+  (block_end)
+] @keyword.directive
+
+(block
+  name: (block_name) @label)
+
+((block
+  name: (block_name) @_name
+  contents: (contents) @markup.raw.block)
+  (#match? @_name "^([eE][xX][aA][mM][pP][lL][eE]|[vV][eE][rR][bB][aA][tT][iI][mM])$"))
+
+((block
+  name: (block_name) @_name
+  contents: (contents) @markup.quote)
+  (#match? @_name "^[qQ][uU][oO][tT][eE]$"))
+
+(parameters) @attribute
+
+; Lists and tables
+
+(bullet) @markup.list
+
+(checkbox) @markup.list.unchecked
+
+((checkbox) @markup.list.checked
+  (#match? @markup.list.checked "[xX]"))
+
+(table_row
+  "|" @punctuation.special)
+
+(table_rule) @punctuation.special
+
+(horizontal_rule) @punctuation.special
+
+; Inline markup
+
+(bold) @markup.bold
+
+(italic) @markup.italic
+
+(underline) @markup.underline
+
+[
+  (verbatim)
+  (code)
+] @markup.raw.inline
+
+(strike_through) @markup.strikethrough
+
+(link
+  url: (link_url) @markup.link.url)
+
+(link
+  description: (link_description) @markup.link.label)
+
+(link
+  [
+    "[["
+    "]"
+    "["
+  ] @punctuation.bracket)
+
+(timestamp) @string.special
@@ -0,0 +1,10 @@
+; #+begin_src rust ... #+end_src
+(src_block
+  language: (language) @injection.language
+  contents: (contents) @injection.content)
+
+((block
+  name: (block_name) @_name
+  parameters: (parameters) @injection.language
+  contents: (contents) @injection.content)
+  (#match? @_name "^[eE][xX][pP][oO][rR][tT]$"))
@@ -0,0 +1,102 @@
+//! Org source blocks inject the language named after `#+begin_src`, written
+//! the way Org users write it (`C`, `Python`, `emacs-lisp`), so the
+//! highlighter resolves it case-insensitively.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SAMPLE: &str = include_str!("../samples/notes.org");
+
+const SOURCE: &str = "\
+* TODO Measure :perf:
+#+begin_src rust
+fn main() {}
+#+end_src
+
+#+BEGIN_SRC Python :results output
+def speedup(before, after):
+    return before / after
+#+END_SRC
+";
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_org::language(),
+        helpers::queries!(arborium_org),
+        source,
+    )
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = helpers::parse_tree(arborium_org::language(), SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_src_blocks_inject_their_contents() {
+    let injections = injected(SOURCE);
+    assert_eq!(
+        injections,
+        [
+            ("fn main() {}\n", "rust".to_string()),
+            (
+                "def speedup(before, after):\n    return before / after\n",
+                "Python".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_sample_injects_every_block() {
+    let languages: Vec<String> = injected(SAMPLE)
+        .into_iter()
+        .map(|(_, language)| language)
+        .collect();
+    assert_eq!(languages, ["sh", "rust", "python", "C", "emacs-lisp"]);
+}
+
+#[test]
+fn test_injected_captures_land_inside_blocks() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "org",
+            TestGrammar::new(
+                arborium_org::language(),
+                arborium_org::HIGHLIGHTS_QUERY,
+                arborium_org::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "python",
+            TestGrammar::new(
+                arborium_python::language(),
+                &arborium_python::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    highlighter.set_injection_resolver(|language: &str| Some(language.to_lowercase()));
+    let html = highlighter.highlight("org", SOURCE).unwrap();
+    assert!(html.contains("<a-tt>Measure</a-tt>"), "{}", html);
+    assert!(
+        html.contains("\n<a-k>fn</a-k> <a-f>main</a-f>() {}\n"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("\n<a-k>def</a-k> <a-f>speedup</a-f>("),
+        "{}",
+        html
+    );
+    assert!(html.contains("\n    <a-k>return</a-k> "), "{}", html);
+}
@@ -0,0 +1,220 @@
+/**
+ * @file reStructuredText grammar for tree-sitter
+ * @license MIT
+ *
+ * reStructuredText documents line by line: section titles and their
+ * adornments, transitions, explicit markup (directives, comments, targets
+ * and footnotes), field lists, bullet and enumerated lists, block quotes
+ * and paragraphs of inline markup.
+ *
+ * Indentation is what nests in reST. Lines indented under explicit markup
+ * are kept whole as its body, so a `code-block` body can be handed to the
+ * language the directive names, and an admonition's body back to reST.
+ * Every line ends in an explicit newline, so whitespace is never an extra.
+ * https://docutils.sourceforge.io/docs/ref/rst/restructuredtext.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+module.exports = grammar({
+  name: 'rst',
+
+  extras: _ => [],
+
+  rules: {
+    document: $ => repeat($._element),
+
+    _element: $ => choice(
+      $._blank_line,
+      $.section,
+      $.transition,
+      $.directive,
+      $.substitution_definition,
+      $.target,
+      $.footnote,
+      $.comment,
+      $.field,
+      $.list_item,
+      $.block_quote,
+      $.paragraph,
+    ),
+
+    _blank_line: _ => /[ \t]*\r?\n/,
+
+    _newline: _ => /\r?\n/,
+
+    _space: _ => /[ \t]+/,
+
+    // Title
+    // =====
+    //
+    // An underline after a line, or a line after an overline, makes a title
+    // rather than a paragraph line or a transition.
+    section: $ => prec(1, seq(
+      optional(field('overline', $.adornment)),
+      field('title', alias($._text, $.title)),
+      $._newline,
+      field('underline', $.adornment),
+    )),
+
+    // A whole line of one punctuation character, newline included.
+    adornment: _ => token(prec(1,
+      /(={3,}|-{3,}|~{3,}|\^{3,}|\*{3,}|#{3,}|\+{3,}|`{3,}|:{3,}|'{3,}|"{3,}|_{3,}|\.{3,})[ \t]*\r?\n/,
+    )),
+
+    transition: $ => $.adornment,
+
+    // .. code-block:: python
+    //    :linenos:
+    //
+    //    print("hello")
+    directive: $ => seq(
+      $._explicit_marker,
+      $._directive,
+    ),
+
+    _directive: $ => prec.right(seq(
+      field('name', $.directive_name),
+      optional($._space),
+      optional(field('arguments', $.arguments)),
+      $._newline,
+      repeat(field('option', $.option)),
+      optional(field('body', $.body)),
+    )),
+
+    _explicit_marker: _ => /\.\.[ \t]+/,
+
+    // The name takes its `::`, so that `.. note that` stays a comment.
+    directive_name: _ => token(prec(1, /[A-Za-z0-9][A-Za-z0-9_.+-]*(:[A-Za-z0-9_.+-]+)*::/)),
+
+    arguments: _ => /[^\s][^\n]*/,
+
+    option: $ => seq(
+      field('name', $.option_name),
+      optional(field('value', $.option_value)),
+      $._newline,
+    ),
+
+    option_name: _ => token(prec(2, /[ \t]+:[^:\s][^:\n]*:/)),
+
+    option_value: _ => /[^\n]+/,
+
+    // Indented lines, and the blank lines between them, kept whole.
+    body: $ => prec.right(repeat1(choice($._indented_line, $._blank_line))),
+
+    _indented_line: _ => /[ \t]+[^\s][^\n]*\r?\n/,
+
+    // .. |logo| image:: logo.png
+    substitution_definition: $ => seq(
+      $._explicit_marker,
+      field('name', $.substitution_reference),
+      $._space,
+      $._directive,
+    ),
+
+    // .. _docs: https://example.com/docs
+    target: $ => prec.right(seq(
+      $._explicit_marker,
+      field('name', $.target_name),
+      optional($._space),
+      optional(field('link', $.link)),
+      $._newline,
+      optional(field('body', $.body)),
+    )),
+
+    target_name: _ => token(prec(1, /_(`[^`\n]+`|[^:`\s][^:`\n]*|_):/)),
+
+    link: _ => /[^\s][^\n]*/,
+
+    // .. [#] A footnote.
+    footnote: $ => prec.right(seq(
+      $._explicit_marker,
+      field('label', $.footnote_label),
+      repeat($._inline),
+      $._newline,
+      optional(field('body', $.body)),
+    )),
+
+    footnote_label: _ => token(prec(1, /\[(#[\w-]*|\*|\d+|[A-Za-z][\w.-]*)\]/)),
+
+    // .. anything else
+    comment: $ => prec.right(seq(
+      choice(
+        seq($._explicit_marker, optional(/[^\s][^\n]*/), $._newline),
+        /\.\.[ \t]*\r?\n/,
+      ),
+      optional($.body),
+    )),
+
+    // :Author: Ada
+    field: $ => prec.right(seq(
+      field('name', $.field_name),
+      repeat($._inline),
+      $._newline,
+      repeat(seq($._space, $._line)),
+    )),
+
+    field_name: _ => token(prec(1, /:[^:\s`][^:\n`]*:/)),
+
+    // - item, * item, 1. item, #) item, (2) item
+    list_item: $ => prec.right(seq(
+      field('bullet', $.bullet),
+      repeat($._inline),
+      $._newline,
+      repeat(seq($._space, $._line)),
+    )),
+
+    bullet: _ => /[ \t]*([-*+•]|\d+[.)]|#[.)]|\(\d+\))[ \t]+/,
+
+    block_quote: $ => prec.right(repeat1(seq($._space, $._line))),
+
+    paragraph: $ => prec.right(repeat1($._line)),
+
+    // A line never starts with whitespace: an indented one is a block quote
+    // or belongs to the element above.
+    _line: $ => seq($._text, $._newline),
+
+    _text: $ => seq($._inline_start, repeat($._inline)),
+
+    _inline: $ => choice($._space, $._inline_start),
+
+    _inline_start: $ => choice(
+      $._word,
+      $.strong,
+      $.emphasis,
+      $.literal,
+      $.interpreted_text,
+      $.role,
+      $.reference,
+      $.substitution_reference,
+      $.footnote_reference,
+      $._marker,
+    ),
+
+    _word: $ => alias(/[^\s*`:|\[][^\s`]*/, $.word),
+
+    // **strong** *emphasis* ``literal``
+    strong: _ => /\*\*[^\s*]([^*\n]*[^\s*])?\*\*/,
+
+    emphasis: _ => /\*[^\s*]([^*\n]*[^\s*])?\*/,
+
+    literal: _ => /``[^`\n]([^`\n]*[^`\n])?``/,
+
+    // `default role`, :role:`text`
+    interpreted_text: _ => /`[^`\n]+`/,
+
+    role: _ => /:[A-Za-z0-9_.+-]+(:[A-Za-z0-9_.+-]+)*:`[^`\n]+`/,
+
+    // `Python <https://python.org>`_, `Setup`__
+    reference: _ => /`[^`\n]+`__?/,
+
+    substitution_reference: _ => token(prec(1, /\|[^|\s]([^|\n]*[^|\s])?\|_{0,2}/)),
+
+    footnote_reference: _ => /\[(#[\w-]*|\*|\d+|[A-Za-z][\w.-]*)\]_/,
+
+    // A markup character that doesn't open any markup is just text.
+    _marker: $ => alias(/[*`:|\[]/, $.word),
+  },
+});
+
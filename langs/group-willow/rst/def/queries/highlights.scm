@@ -0,0 +1,70 @@
+; Sections
+
+(section
+  title: (title) @markup.heading.1
+  underline: (adornment) @punctuation.special)
+
+(section
+  overline: (adornment) @punctuation.special)
+
+(transition
+  (adornment) @punctuation.special)
+
+; Explicit markup
+
+(directive_name) @function.macro
+
+(directive
+  arguments: (arguments) @string)
+
+(substitution_definition
+  arguments: (arguments) @string)
+
+((directive
+  name: (directive_name) @_name
+  arguments: (arguments) @label)
+  (#match? @_name "^(code-block|code|sourcecode|highlight)::$"))
+
+(option_name) @property
+
+(option_value) @string
+
+(target_name) @label
+
+(target
+  link: (link) @markup.link.url)
+
+(footnote_label) @label
+
+(comment) @comment
+
+; Fields and lists
+
+(field_name) @property
+
+(bullet) @markup.list
+
+; Inline markup
+
+(strong) @markup.bold
+
+(emphasis) @markup.italic
+
+(literal) @markup.raw.inline
+
+(interpreted_text) @markup.italic
+
+(role) @function.macro
+
+[
+  (reference)
+  (footnote_reference)
+] @markup.link
+
+(substitution_reference) @constant.macro
+
+((word) @markup.link
+  (#match? @markup.link "^[A-Za-z0-9][A-Za-z0-9_.-]*__?$"))
+
+((word) @markup.link.url
+  (#match? @markup.link.url "^(https?|ftp)://"))
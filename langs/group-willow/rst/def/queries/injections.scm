@@ -0,0 +1,16 @@
+; .. code-block:: rust
+(directive
+  name: (directive_name) @_name
+  arguments: (arguments) @injection.language
+  body: (body) @injection.content
+  (#match? @_name "^(code-block|code|sourcecode)::$"))
+
+; Admonitions and other containers hold more reST
+((directive
+  name: (directive_name) @_name
+  body: (body) @injection.content)
+  (#any-of? @_name
+    "note::" "tip::" "hint::" "important::" "warning::" "caution::"
+    "danger::" "error::" "attention::" "admonition::" "topic::" "sidebar::"
+    "versionadded::" "versionchanged::" "deprecated::" "seealso::")
+  (#set! injection.language "rst"))
@@ -0,0 +1,92 @@
+//! reST code directives inject the language they name into their indented
+//! body, and admonitions inject their body back into reST. Bodies keep
+//! their indentation, so captures must land on the indented text.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SAMPLE: &str = include_str!("../samples/guide.rst");
+
+const SOURCE: &str = "\
+Usage
+=====
+
+.. code-block:: rust
+   :linenos:
+
+   fn main() {}
+
+.. note::
+
+   Read **this** first.
+";
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_rst::language(),
+        helpers::queries!(arborium_rst),
+        source,
+    )
+}
+
+#[test]
+fn test_sample_parses_cleanly() {
+    let tree = helpers::parse_tree(arborium_rst::language(), SAMPLE);
+    let root = tree.root_node();
+    assert!(!root.has_error(), "{}", root.to_sexp());
+}
+
+#[test]
+fn test_directive_bodies_are_injected() {
+    let injections = injected(SOURCE);
+    assert_eq!(
+        injections,
+        [
+            ("\n   fn main() {}\n\n", "rust".to_string()),
+            ("\n   Read **this** first.\n", "rst".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_sample_injects_every_code_block() {
+    let languages: Vec<String> = injected(SAMPLE)
+        .into_iter()
+        .map(|(_, language)| language)
+        .filter(|language| language != "rst")
+        .collect();
+    assert_eq!(languages, ["bash", "python", "rust", "json"]);
+}
+
+#[test]
+fn test_injected_captures_land_inside_bodies() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "rst",
+            TestGrammar::new(
+                arborium_rst::language(),
+                arborium_rst::HIGHLIGHTS_QUERY,
+                arborium_rst::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    let html = highlighter.highlight("rst", SOURCE).unwrap();
+    assert!(html.contains("<a-tt>Usage</a-tt>"), "{}", html);
+    assert!(
+        html.contains("\n   <a-k>fn</a-k> <a-f>main</a-f>() {}\n"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("Read <a-st>**this**</a-st> first."),
+        "{}",
+        html
+    );
+}
@@ -1,21 +1,5 @@
-; Svelte highlights - includes HTML base queries
-; Based on tree-sitter-svelte with HTML inheritance
-
-; === HTML base queries (inherited) ===
-
-(tag_name) @tag
-(erroneous_end_tag_name) @tag.error
-(doctype) @constant
-(attribute_name) @attribute
-(attribute_value) @string
-(comment) @comment
-
-[
-  "<"
-  ">"
-  "</"
-  "/>"
-] @punctuation.bracket
+; Svelte highlights. The HTML base queries are prepended from arborium-html
+; (see arborium.yaml), so only Svelte's own nodes are captured here
 
 ; === Svelte-specific queries ===
 
@@ -31,10 +15,10 @@
 
 "const" @type.qualifier
 
+; "then" belongs to {#await}, below
 [
   "if"
   "else"
-  "then"
 ] @keyword.conditional
 
 "each" @keyword.repeat
@@ -2,33 +2,49 @@
 ; To modify, edit the source in grammars/tree-sitter-*/queries/
 ; or add extra_injections in grammars/tree-sitter-*/grammar-crate-config.toml
 
-; Default CSS in <style> tags (no lang attribute)
+;; <script> and <style> hold JavaScript and CSS, unless a lang attribute
+; says otherwise
 ((style_element
+  (start_tag) @_tag
   (raw_text) @injection.content)
+ (#not-match? @_tag "\\slang\\s*=")
  (#set! injection.language "css"))
 
-; SCSS/PostCSS/etc when lang attribute is set
-((style_element
+((script_element
+  (start_tag) @_tag
+  (raw_text) @injection.content)
+ (#not-match? @_tag "\\slang\\s*=")
+ (#set! injection.language "javascript"))
+
+; <script lang="ts">, <style lang="scss">: the value names the language,
+; and aliases like ts and sass resolve to their grammar
+((script_element
   (start_tag
     (attribute
       (attribute_name) @_attr
-      (quoted_attribute_value
-        (attribute_value) @_lang)))
+      [
+        (attribute_value) @injection.language
+        (quoted_attribute_value
+          (attribute_value) @injection.language)
+      ]))
   (raw_text) @injection.content)
-  (#eq? @_attr "lang")
-  (#any-of? @_lang "scss" "postcss" "less" "stylus")
-  (#set! injection.language "scss"))
+ (#eq? @_attr "lang"))
 
-; Default JavaScript in <script> tags (no lang attribute)
-((script_element
+((style_element
+  (start_tag
+    (attribute
+      (attribute_name) @_attr
+      [
+        (attribute_value) @injection.language
+        (quoted_attribute_value
+          (attribute_value) @injection.language)
+      ]))
   (raw_text) @injection.content)
-  (#set! injection.language "javascript"))
-
-; JavaScript in Svelte template expressions like {name}, {#if condition}, etc.
-((svelte_raw_text) @injection.content
-  (#set! injection.language "javascript"))
+ (#eq? @_attr "lang")
+ (#not-any-of? @injection.language "postcss" "less" "stylus"))
 
-((script_element
+; Style languages without a grammar of their own are close enough to SCSS
+((style_element
   (start_tag
     (attribute
       (attribute_name) @_attr
@@ -36,7 +52,14 @@
         (attribute_value) @_lang)))
   (raw_text) @injection.content)
   (#eq? @_attr "lang")
-  (#any-of? @_lang "ts" "typescript")
+  (#any-of? @_lang "postcss" "less" "stylus")
+  (#set! injection.language "scss"))
+
+; Expressions in markup: {name}, {#if done}, {#each items as item},
+; {@html body}, attribute values like on:click={handler}. Queries can't see
+; the script's lang from here, so they get TypeScript, which highlights
+; plain JavaScript expressions the same way
+((svelte_raw_text) @injection.content
   (#set! injection.language "typescript"))
 
 ((element
@@ -47,4 +70,4 @@
         (attribute_value) @injection.language)))
   (text) @injection.content)
   (#eq? @_attr "lang")
-  (#eq? @injection.language "pug"))
\ No newline at end of file
+  (#eq? @injection.language "pug"))
@@ -0,0 +1,89 @@
+//! Svelte components inject the language their `lang` attributes name, and
+//! TypeScript into markup expressions.
+
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers;
+use arborium_test_harness::invariants::span_violations;
+
+const COMPONENT: &str = "\
+<script lang=\"ts\">
+  export let items: string[] = [];
+</script>
+
+{#each items as item}
+  <button on:click={() => remove(item)}>{item}</button>
+{/each}
+
+<style lang=\"scss\">
+  $gap: 4px;
+</style>
+";
+
+fn parse(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_svelte::language(),
+        helpers::queries!(arborium_svelte),
+        source,
+    )
+}
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injections(&parse(source), source)
+}
+
+#[test]
+fn test_lang_attribute_names_the_language() {
+    let injections = injected(COMPONENT);
+    let blocks: Vec<&str> = injections
+        .iter()
+        .filter(|(text, _)| text.contains('\n'))
+        .map(|(_, language)| language.as_str())
+        .collect();
+    assert_eq!(blocks, ["ts", "scss"]);
+}
+
+#[test]
+fn test_blocks_without_lang_are_javascript_and_css() {
+    let source = "<script>\nlet a = 1;\n</script>\n<style>\np { color: red; }\n</style>\n";
+    let languages: Vec<String> = injected(source).into_iter().map(|(_, l)| l).collect();
+    assert_eq!(languages, ["javascript", "css"]);
+}
+
+#[test]
+fn test_markup_expressions_are_typescript() {
+    let injections = injected(COMPONENT);
+    let expressions: Vec<&str> = injections
+        .iter()
+        .filter(|(_, language)| language == "typescript")
+        .map(|(text, _)| text.trim())
+        .collect();
+    assert!(expressions.contains(&"items"), "{:?}", expressions);
+    assert!(
+        expressions.contains(&"() => remove(item)"),
+        "{:?}",
+        expressions
+    );
+    assert!(expressions.contains(&"item"), "{:?}", expressions);
+}
+
+#[test]
+fn test_html_and_svelte_highlights_dont_overlap() {
+    let sample = include_str!("../samples/Inbox.svelte");
+    for source in [COMPONENT, sample] {
+        let violations = span_violations(source, &parse(source), false);
+        assert!(violations.is_empty(), "{}", violations.join("\n"));
+    }
+}
+
+#[test]
+fn test_keywords_have_one_capture() {
+    let source = "{#await promise}\n  wait\n{:then value}\n  {value}\n{/await}\n";
+    let result = parse(source);
+    let then: Vec<&str> = result
+        .spans
+        .iter()
+        .filter(|s| &source[s.start as usize..s.end as usize] == "then")
+        .map(|s| s.capture.as_str())
+        .collect();
+    assert_eq!(then, ["keyword.coroutine"]);
+}
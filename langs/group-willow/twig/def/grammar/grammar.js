@@ -0,0 +1,429 @@
+/**
+ * @file Twig grammar for tree-sitter
+ * @license MIT
+ *
+ * Twig 3 templates: `{{ output }}`, `{% tags %}` and `{# comments #}`
+ * between runs of content. The content itself is a hidden token, so the
+ * template's visible children are exactly its tags; the injection query
+ * hands everything else to HTML as one combined document.
+ * https://twig.symfony.com/doc/3.x/templates.html
+ */
+
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+const PREC = {
+  arrow: 1,
+  ternary: 2,
+  or: 10,
+  xor: 12,
+  and: 15,
+  bitwise_or: 16,
+  bitwise_xor: 17,
+  bitwise_and: 18,
+  compare: 20,
+  range: 25,
+  additive: 30,
+  concat: 40,
+  not: 50,
+  multiplicative: 60,
+  test: 100,
+  power: 200,
+  coalesce: 300,
+  unary: 500,
+  filter: 600,
+  call: 700,
+  member: 800,
+};
+
+module.exports = grammar({
+  name: 'twig',
+
+  extras: _ => [/\s/],
+
+  word: $ => $.identifier,
+
+  conflicts: $ => [
+    // `(a)` is a parenthesized expression unless `=>` follows.
+    [$._expression, $.arrow_parameters],
+  ],
+
+  rules: {
+    template: $ => repeat($._node),
+
+    _node: $ => choice(
+      $._content,
+      $.output_directive,
+      $.statement_directive,
+      $.comment,
+    ),
+
+    // Everything between tags; a lone `{` that doesn't open one is content
+    // too.
+    _content: _ => token(prec(-1, choice(/[^{]+/, '{'))),
+
+    // {{ user.name|title }}
+    output_directive: $ => seq(
+      choice('{{', '{{-', '{{~'),
+      optional($._expression),
+      choice('}}', '-}}', '~}}'),
+    ),
+
+    // {% for item in items %}
+    statement_directive: $ => seq(
+      choice('{%', '{%-', '{%~'),
+      $._statement,
+      choice('%}', '-%}', '~%}'),
+    ),
+
+    _statement: $ => choice(
+      $.if_statement,
+      $.elseif_statement,
+      $.for_statement,
+      $.set_statement,
+      $.block_statement,
+      $.extends_statement,
+      $.include_statement,
+      $.embed_statement,
+      $.use_statement,
+      $.import_statement,
+      $.from_statement,
+      $.macro_statement,
+      $.apply_statement,
+      $.with_statement,
+      $.tag_statement,
+    ),
+
+    if_statement: $ => seq('if', field('condition', $._expression)),
+
+    elseif_statement: $ => seq('elseif', field('condition', $._expression)),
+
+    // for key, value in items if value.visible
+    for_statement: $ => seq(
+      'for',
+      field('variable', $.identifier),
+      optional(seq(',', field('variable', $.identifier))),
+      'in',
+      field('sequence', $._expression),
+      optional(seq('if', field('condition', $._expression))),
+    ),
+
+    set_statement: $ => seq(
+      'set',
+      commaSep1(field('variable', $.identifier)),
+      optional(seq('=', commaSep1(field('value', $._expression)))),
+    ),
+
+    block_statement: $ => seq(
+      'block',
+      field('name', $.identifier),
+      optional(field('value', $._expression)),
+    ),
+
+    extends_statement: $ => seq('extends', field('template', $._expression)),
+
+    // include 'card.html.twig' ignore missing with { user } only
+    include_statement: $ => seq('include', $._include_arguments),
+
+    embed_statement: $ => seq('embed', $._include_arguments),
+
+    _include_arguments: $ => seq(
+      field('template', $._expression),
+      optional(seq('ignore', 'missing')),
+      optional(seq('with', field('variables', $._expression))),
+      optional('only'),
+    ),
+
+    use_statement: $ => seq(
+      'use',
+      field('template', $._expression),
+      optional(seq('with', commaSep1($.import_alias))),
+    ),
+
+    import_statement: $ => seq(
+      'import',
+      field('template', $._expression),
+      'as',
+      field('alias', $.identifier),
+    ),
+
+    from_statement: $ => seq(
+      'from',
+      field('template', $._expression),
+      'import',
+      commaSep1(choice($.identifier, $.import_alias)),
+    ),
+
+    import_alias: $ => seq($.identifier, 'as', $.identifier),
+
+    macro_statement: $ => seq(
+      'macro',
+      field('name', $.identifier),
+      field('parameters', $.parameters),
+    ),
+
+    parameters: $ => seq(
+      '(',
+      commaSep(choice($.identifier, $.default_parameter)),
+      optional(','),
+      ')',
+    ),
+
+    default_parameter: $ => seq(
+      field('name', $.identifier),
+      '=',
+      field('value', $._expression),
+    ),
+
+    // apply upper|escape('html')
+    apply_statement: $ => seq('apply', $.filter, repeat(seq('|', $.filter))),
+
+    with_statement: $ => seq(
+      'with',
+      optional(field('variables', $._expression)),
+      optional('only'),
+    ),
+
+    // endfor, else, endblock content, autoescape 'js', deprecated 'msg', ...
+    tag_statement: $ => seq(
+      field('name', $.identifier),
+      optional($._expression),
+    ),
+
+    _expression: $ => choice(
+      $.identifier,
+      $.number,
+      $.string,
+      $.boolean,
+      $.null,
+      $.array,
+      $.hash,
+      $.parenthesized_expression,
+      $.member_expression,
+      $.subscript_expression,
+      $.call_expression,
+      $.filter_expression,
+      $.test_expression,
+      $.unary_expression,
+      $.binary_expression,
+      $.ternary_expression,
+      $.arrow_function,
+    ),
+
+    parenthesized_expression: $ => seq('(', $._expression, ')'),
+
+    member_expression: $ => prec(PREC.member, seq(
+      field('object', $._expression),
+      choice('.', '?.'),
+      field('property', alias($.identifier, $.property_identifier)),
+    )),
+
+    subscript_expression: $ => prec(PREC.member, seq(
+      field('object', $._expression),
+      '[',
+      choice(
+        field('index', $._expression),
+        seq(
+          optional(field('start', $._expression)),
+          ':',
+          optional(field('end', $._expression)),
+        ),
+      ),
+      ']',
+    )),
+
+    call_expression: $ => prec(PREC.call, seq(
+      field('function', $._expression),
+      field('arguments', $.arguments),
+    )),
+
+    arguments: $ => seq(
+      '(',
+      commaSep(choice($._expression, $.named_argument)),
+      optional(','),
+      ')',
+    ),
+
+    named_argument: $ => seq(
+      field('name', $.identifier),
+      choice('=', ':'),
+      field('value', $._expression),
+    ),
+
+    // items|sort|join(', ')
+    filter_expression: $ => prec.left(PREC.filter, seq(
+      field('value', $._expression),
+      '|',
+      $.filter,
+    )),
+
+    filter: $ => prec.right(seq(
+      field('name', $.identifier),
+      optional(field('arguments', $.arguments)),
+    )),
+
+    // loop.index is divisible by(3), user is not defined
+    test_expression: $ => prec.left(PREC.test, seq(
+      field('value', $._expression),
+      'is',
+      optional('not'),
+      $.test,
+    )),
+
+    test: $ => prec.right(seq(
+      field('name', choice(
+        $.identifier,
+        alias('null', $.identifier),
+        alias('none', $.identifier),
+        seq('same', 'as'),
+        seq('divisible', 'by'),
+      )),
+      optional(field('arguments', $.arguments)),
+    )),
+
+    unary_expression: $ => choice(
+      prec(PREC.not, seq(field('operator', 'not'), field('argument', $._expression))),
+      prec(PREC.unary, seq(
+        field('operator', choice('-', '+')),
+        field('argument', $._expression),
+      )),
+    ),
+
+    binary_expression: $ => {
+      const table = [
+        [prec.left, PREC.or, 'or'],
+        [prec.left, PREC.xor, 'xor'],
+        [prec.left, PREC.and, 'and'],
+        [prec.left, PREC.bitwise_or, 'b-or'],
+        [prec.left, PREC.bitwise_xor, 'b-xor'],
+        [prec.left, PREC.bitwise_and, 'b-and'],
+        [prec.left, PREC.compare, choice(
+          '==', '!=', '<', '>', '<=', '>=', '<=>', '===', '!==',
+          'in', seq('not', 'in'),
+          'matches', seq('starts', 'with'), seq('ends', 'with'),
+          seq('has', 'some'), seq('has', 'every'),
+        )],
+        [prec.left, PREC.range, '..'],
+        [prec.left, PREC.additive, choice('+', '-')],
+        [prec.left, PREC.concat, '~'],
+        [prec.left, PREC.multiplicative, choice('*', '/', '//', '%')],
+        [prec.right, PREC.power, '**'],
+        [prec.right, PREC.coalesce, '??'],
+      ];
+
+      return choice(...table.map(([fn, precedence, operator]) =>
+        // @ts-ignore
+        fn(precedence, seq(
+          field('left', $._expression),
+          field('operator', operator),
+          field('right', $._expression),
+        )),
+      ));
+    },
+
+    // a ? b : c, a ?: b, a ? b
+    ternary_expression: $ => prec.right(PREC.ternary, choice(
+      seq(
+        field('condition', $._expression),
+        '?',
+        field('consequence', $._expression),
+        optional(seq(':', field('alternative', $._expression))),
+      ),
+      seq(
+        field('condition', $._expression),
+        '?:',
+        field('alternative', $._expression),
+      ),
+    )),
+
+    // p => p.name, (a, b) => a <=> b
+    arrow_function: $ => prec.right(PREC.arrow, seq(
+      field('parameters', choice($.identifier, $.arrow_parameters)),
+      '=>',
+      field('body', $._expression),
+    )),
+
+    arrow_parameters: $ => seq('(', commaSep($.identifier), ')'),
+
+    array: $ => seq('[', commaSep($._expression), optional(','), ']'),
+
+    // { name: 'x', (key): value, 'quoted': 1, shorthand }
+    hash: $ => seq(
+      '{',
+      commaSep(choice($.pair, alias($.identifier, $.property_identifier))),
+      optional(','),
+      '}',
+    ),
+
+    pair: $ => seq(
+      field('key', choice(
+        alias($.identifier, $.property_identifier),
+        $.string,
+        $.number,
+        $.parenthesized_expression,
+      )),
+      ':',
+      field('value', $._expression),
+    ),
+
+    string: $ => choice(
+      seq(
+        '\'',
+        repeat(choice(
+          token.immediate(prec(1, /[^'\\]+/)),
+          $.escape_sequence,
+        )),
+        '\'',
+      ),
+      seq(
+        '"',
+        repeat(choice(
+          token.immediate(prec(1, /[^"\\#]+/)),
+          token.immediate('#'),
+          $.escape_sequence,
+          $.interpolation,
+        )),
+        '"',
+      ),
+    ),
+
+    // "Hello #{user.name}"
+    interpolation: $ => seq(
+      token.immediate('#{'),
+      $._expression,
+      '}',
+    ),
+
+    escape_sequence: _ => token.immediate(seq('\\', /./)),
+
+    number: _ => /\d+(\.\d+)?([eE][+-]?\d+)?/,
+
+    boolean: _ => choice('true', 'false'),
+
+    null: _ => choice('null', 'none'),
+
+    identifier: _ => /[a-zA-Z_\x80-\uffff][a-zA-Z0-9_\x80-\uffff]*/,
+
+    comment: _ => token(seq('{#', /[^#]*#+([^#}][^#]*#+)*/, '}')),
+  },
+});
+
+/**
+ * Zero or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {ChoiceRule}
+ */
+function commaSep(rule) {
+  return optional(commaSep1(rule));
+}
+
+/**
+ * One or more `rule`s separated by commas.
+ *
+ * @param {RuleOrLiteral} rule
+ * @returns {SeqRule}
+ */
+function commaSep1(rule) {
+  return seq(rule, repeat(seq(',', rule)));
+}
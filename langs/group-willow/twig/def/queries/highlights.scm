@@ -0,0 +1,169 @@
+; Twig tags, output and comments. The content between them is highlighted
+; by the combined HTML injection (see injections.scm).
+
+(comment) @comment
+
+[
+  "{{"
+  "{{-"
+  "{{~"
+  "}}"
+  "-}}"
+  "~}}"
+  "{%"
+  "{%-"
+  "{%~"
+  "%}"
+  "-%}"
+  "~%}"
+] @punctuation.special
+
+(identifier) @variable
+
+((identifier) @variable.builtin
+  (#any-of? @variable.builtin "loop" "_self" "_context" "_charset"))
+
+(property_identifier) @property
+
+(named_argument
+  name: (identifier) @variable.parameter)
+
+(parameters
+  (identifier) @variable.parameter)
+
+(default_parameter
+  name: (identifier) @variable.parameter)
+
+(arrow_function
+  parameters: (identifier) @variable.parameter)
+
+(arrow_parameters
+  (identifier) @variable.parameter)
+
+(call_expression
+  function: (identifier) @function.call)
+
+(call_expression
+  function: (member_expression
+    property: (property_identifier) @function.method))
+
+; Filter arguments are ordinary expressions: a format string passed to
+; |date or |format is a plain string, not an injection.
+(filter
+  name: (identifier) @function)
+
+(test
+  name: _ @function.builtin)
+
+(macro_statement
+  name: (identifier) @function.definition)
+
+(block_statement
+  name: (identifier) @label)
+
+(tag_statement
+  name: (identifier) @keyword)
+
+[
+  "if"
+  "elseif"
+] @keyword.conditional
+
+"for" @keyword.repeat
+
+[
+  "extends"
+  "include"
+  "embed"
+  "use"
+  "import"
+  "from"
+] @keyword.import
+
+"macro" @keyword.function
+
+[
+  "in"
+  "set"
+  "block"
+  "apply"
+  "with"
+  "as"
+  "only"
+  "ignore"
+  "missing"
+] @keyword
+
+[
+  "not"
+  "and"
+  "or"
+  "xor"
+  "b-and"
+  "b-or"
+  "b-xor"
+  "is"
+  "matches"
+  "starts"
+  "ends"
+  "has"
+  "some"
+  "every"
+] @keyword.operator
+
+[
+  "=="
+  "!="
+  "<"
+  ">"
+  "<="
+  ">="
+  "<=>"
+  "==="
+  "!=="
+  ".."
+  "+"
+  "-"
+  "~"
+  "*"
+  "/"
+  "//"
+  "%"
+  "**"
+  "??"
+  "?"
+  "?:"
+  ":"
+  "="
+  "=>"
+  "|"
+] @operator
+
+[
+  "."
+  "?."
+  ","
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(interpolation
+  "#{" @punctuation.special
+  "}" @punctuation.special)
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(number) @number
+
+(boolean) @boolean
+
+(null) @constant.builtin
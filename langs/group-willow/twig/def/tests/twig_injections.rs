@@ -0,0 +1,122 @@
+//! Twig templates inject their content as one combined HTML document.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::arborium_wire::Utf8ParseResult;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const TEMPLATE: &str = "\
+{% extends 'base.html.twig' %}
+{% block content %}
+<ul>
+{% for item in items %}
+  <li {% if item.active %}class=\"active\"{% endif %}>{{ item.title|upper }}</li>
+{% endfor %}
+</ul>
+{% include 'footer.html.twig' with { year: 2024 } only %}
+<time>{{ post.date|date(\"d/m/Y\") }}</time>
+<script>
+  const count = {{ items|length }};
+</script>
+{% endblock %}
+";
+
+const SAMPLE: &str = include_str!("../samples/product-list.html.twig");
+
+fn parse(source: &str) -> Utf8ParseResult {
+    helpers::plugin_parse(
+        arborium_twig::language(),
+        helpers::queries!(arborium_twig),
+        source,
+    )
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "twig",
+            TestGrammar::new(
+                arborium_twig::language(),
+                arborium_twig::HIGHLIGHTS_QUERY,
+                arborium_twig::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "html",
+            TestGrammar::new(
+                arborium_html::language(),
+                arborium_html::HIGHLIGHTS_QUERY,
+                arborium_html::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "javascript",
+            TestGrammar::new(
+                arborium_javascript::language(),
+                arborium_javascript::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    helpers::highlighter(grammars)
+}
+
+#[test]
+fn test_content_between_tags_is_combined_html() {
+    for source in [TEMPLATE, SAMPLE] {
+        let result = parse(source);
+        assert!(!result.injections.is_empty());
+        for injection in &result.injections {
+            let text = &source[injection.start as usize..injection.end as usize];
+            assert_eq!(injection.language, "html");
+            assert!(injection.combined);
+            assert!(
+                !text.contains("{%") && !text.contains("{{") && !text.contains("{#"),
+                "{:?}",
+                text
+            );
+        }
+    }
+}
+
+#[test]
+fn test_filter_string_arguments_stay_plain() {
+    let result = parse(TEMPLATE);
+    let start = TEMPLATE.find("\"d/m/Y\"").unwrap() as u32;
+    let end = start + "\"d/m/Y\"".len() as u32;
+
+    assert!(
+        result
+            .spans
+            .iter()
+            .any(|s| s.start == start && s.end == end && s.capture == "string"),
+        "{:?}",
+        result.spans
+    );
+    assert!(
+        result
+            .injections
+            .iter()
+            .all(|i| i.end <= start || i.start >= end),
+        "a filter argument was injected"
+    );
+}
+
+#[test]
+fn test_element_split_by_tags_highlights() {
+    let html = highlighter().highlight("twig", TEMPLATE).unwrap();
+    // The <li> start tag is cut in three by the {% if %} branch
+    assert!(html.contains("<a-tg>li</a-tg>"), "{}", html);
+    assert!(html.contains("<a-at>class</a-at>"), "{}", html);
+    assert!(html.contains("<a-k>for</a-k>"), "{}", html);
+    assert!(html.contains("<a-k>include</a-k>"), "{}", html);
+    assert!(html.contains("<a-f>upper</a-f>"), "{}", html);
+}
+
+#[test]
+fn test_script_injects_javascript_through_html() {
+    let html = highlighter().highlight("twig", TEMPLATE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+
+    let html = highlighter().highlight("twig", SAMPLE).unwrap();
+    assert!(html.contains("<a-k>const</a-k>"), "{}", html);
+}
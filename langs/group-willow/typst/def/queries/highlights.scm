@@ -69,6 +69,7 @@
 (letter) @constant.character
 (linebreak) @constant.builtin
 
+(math) @markup.math
 (math "$" @operator)
 "#" @operator
 "end" @operator
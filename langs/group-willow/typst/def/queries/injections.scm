@@ -1,7 +1,4 @@
+; ```rust ... ``` -- the tag after the opening fence names the language
 (raw_blck
-  (blob) @injection.shebang @injection.content)
-
-(raw_blck
-  lang: (ident) @_lang
-  (blob) @injection.content
-  (#set-lang-from-info-string! @_lang))
+  lang: (ident) @injection.language
+  (blob) @injection.content)
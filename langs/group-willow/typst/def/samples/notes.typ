@@ -0,0 +1,31 @@
+#set page(paper: "a5", margin: 1.5cm)
+#set heading(numbering: "1.")
+
+= Integer square roots
+
+Newton's method converges quadratically. For $n > 0$, start from
+$x_0 = n$ and iterate
+
+$ x_(k+1) = floor((x_k + floor(n / x_k)) / 2) $
+
+until $x_(k+1) >= x_k$. The answer is then $x_k = floor(sqrt(n))$.
+
+== Implementation
+
+```rust
+fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+```
+
+Each step costs one division, and the loop runs $O(log log n)$ times.
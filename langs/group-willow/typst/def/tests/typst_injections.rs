@@ -0,0 +1,64 @@
+//! Typst raw blocks inject the language named after their opening fence,
+//! and math regions get their own capture.
+
+use arborium_test_harness::helpers::{self, TestGrammar};
+
+const SOURCE: &str = "\
+The loop runs $O(log n)$ times:
+
+$ x_(k+1) = (x_k + n / x_k) / 2 $
+
+```rust
+fn step(x: u64, n: u64) -> u64 {
+    (x + n / x) / 2
+}
+```
+
+```
+no language here
+```
+";
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_typst::language(),
+        helpers::queries!(arborium_typst),
+        source,
+    )
+}
+
+#[test]
+fn test_raw_blocks_inject_their_lang_tag() {
+    let injections = injected(SOURCE);
+    assert_eq!(injections.len(), 1, "{:?}", injections);
+    let (text, language) = &injections[0];
+    assert_eq!(language, "rust");
+    assert!(text.contains("fn step(x: u64"), "{:?}", text);
+    assert!(!text.contains("```"), "{:?}", text);
+}
+
+#[test]
+fn test_raw_block_contents_highlight_as_rust() {
+    let mut highlighter = helpers::highlighter([
+        (
+            "typst",
+            TestGrammar::new(
+                arborium_typst::language(),
+                arborium_typst::HIGHLIGHTS_QUERY,
+                arborium_typst::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "rust",
+            TestGrammar::new(
+                arborium_rust::language(),
+                &arborium_rust::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ]);
+    let html = highlighter.highlight("typst", SOURCE).unwrap();
+    assert!(html.contains("<a-k>fn</a-k>"), "{}", html);
+    assert!(html.contains("<a-t>u64</a-t>"), "{}", html);
+    assert!(html.contains("<a-tl>"), "{}", html);
+}
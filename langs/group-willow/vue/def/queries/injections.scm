@@ -2,29 +2,49 @@
 ; To modify, edit the source in grammars/tree-sitter-*/queries/
 ; or add extra_injections in grammars/tree-sitter-*/grammar-crate-config.toml
 
-; Default CSS in <style> tags (no lang attribute)
+; <script> and <style> hold JavaScript and CSS, unless a lang attribute
+; says otherwise
 ((style_element
+  (start_tag) @_tag
   (raw_text) @injection.content)
+ (#not-match? @_tag "\\slang\\s*=")
  (#set! injection.language "css"))
 
-; Default JavaScript in <script> tags (no lang attribute)
 ((script_element
+  (start_tag) @_tag
   (raw_text) @injection.content)
+ (#not-match? @_tag "\\slang\\s*=")
  (#set! injection.language "javascript"))
 
-; <style lang="css"> or <style lang="scss">
+; <script lang="ts">, <script lang="tsx">, <style lang="scss">: the value
+; names the language, and aliases like ts and sass resolve to their grammar
+((script_element
+  (start_tag
+    (attribute
+      (attribute_name) @_lang
+      [
+        (attribute_value) @injection.language
+        (quoted_attribute_value
+          (attribute_value) @injection.language)
+      ]))
+  (raw_text) @injection.content)
+ (#eq? @_lang "lang"))
+
 ((style_element
   (start_tag
     (attribute
       (attribute_name) @_lang
-      (quoted_attribute_value
-        (attribute_value) @injection.language)))
+      [
+        (attribute_value) @injection.language
+        (quoted_attribute_value
+          (attribute_value) @injection.language)
+      ]))
   (raw_text) @injection.content)
-  (#eq? @_lang "lang")
-  (#any-of? @injection.language "css" "scss"))
+ (#eq? @_lang "lang")
+ (#not-any-of? @injection.language "less" "postcss"))
 
-; TODO: When nvim-treesitter has postcss and less parsers, use @injection.language and @injection.content instead
-; <script lang="scss">
+; TODO: When we have postcss and less grammars, let the pattern above take these
+; <style lang="less">
 (style_element
   (start_tag
     (attribute
@@ -36,42 +56,6 @@
   (#any-of? @_scss "less" "postcss")
   (#set! injection.language "scss"))
 
-; <script lang="js">
-((script_element
-  (start_tag
-    (attribute
-      (attribute_name) @_lang
-      (quoted_attribute_value
-        (attribute_value) @_js)))
-  (raw_text) @injection.content)
-  (#eq? @_lang "lang")
-  (#eq? @_js "js")
-  (#set! injection.language "javascript"))
-
-; <script lang="ts">
-((script_element
-  (start_tag
-    (attribute
-      (attribute_name) @_lang
-      (quoted_attribute_value
-        (attribute_value) @_ts)))
-  (raw_text) @injection.content)
-  (#eq? @_lang "lang")
-  (#eq? @_ts "ts")
-  (#set! injection.language "typescript"))
-
-; <script lang="tsx">
-; <script lang="jsx">
-(script_element
-  (start_tag
-    (attribute
-      (attribute_name) @_attr
-      (quoted_attribute_value
-        (attribute_value) @injection.language)))
-  (#eq? @_attr "lang")
-  (#any-of? @injection.language "tsx" "jsx")
-  (raw_text) @injection.content)
-
 ((interpolation
   (raw_text) @injection.content)
   (#set! injection.language "typescript"))
@@ -81,10 +65,14 @@
     (attribute_value) @injection.content
     (#set! injection.language "typescript")))
 
+; <template lang="pug"> stays plain text until there is a pug grammar
 (template_element
   (start_tag
     (attribute
+      (attribute_name) @_lang
       (quoted_attribute_value
-        (attribute_value) @injection.language)))
+        (attribute_value) @_pug)))
   (text) @injection.content
-  (#eq? @injection.language "pug"))
\ No newline at end of file
+  (#eq? @_lang "lang")
+  (#eq? @_pug "pug")
+  (#set! injection.language "pug"))
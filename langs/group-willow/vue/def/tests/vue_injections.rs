@@ -0,0 +1,104 @@
+//! Vue single-file components inject the language their `lang` attributes
+//! name, and JavaScript and CSS without one.
+
+use arborium_test_harness::arborium_highlight::SyncHighlighter;
+use arborium_test_harness::helpers::{self, TestGrammar, TestProvider};
+
+const SFC: &str = "\
+<template>
+  <p>{{ count }}</p>
+</template>
+
+<script setup lang=\"ts\">
+interface Props { count: number }
+</script>
+
+<style lang=\"scss\" scoped>
+$accent: #42b883;
+p { color: $accent; }
+</style>
+";
+
+fn injected(source: &str) -> Vec<(&str, String)> {
+    helpers::injected(
+        arborium_vue::language(),
+        helpers::queries!(arborium_vue),
+        source,
+    )
+}
+
+/// The languages injected into `<script>` and `<style>` contents.
+fn block_languages(source: &str) -> Vec<String> {
+    injected(source)
+        .into_iter()
+        .filter(|(text, _)| text.contains('\n'))
+        .map(|(_, language)| language)
+        .collect()
+}
+
+fn highlighter() -> SyncHighlighter<TestProvider> {
+    let grammars = [
+        (
+            "vue",
+            TestGrammar::new(
+                arborium_vue::language(),
+                &arborium_vue::HIGHLIGHTS_QUERY,
+                arborium_vue::INJECTIONS_QUERY,
+            ),
+        ),
+        (
+            "typescript",
+            TestGrammar::new(
+                arborium_typescript::language(),
+                &arborium_typescript::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+        (
+            "scss",
+            TestGrammar::new(
+                arborium_scss::language(),
+                &arborium_scss::HIGHLIGHTS_QUERY,
+                "",
+            ),
+        ),
+    ];
+    let mut highlighter = helpers::highlighter(grammars);
+    highlighter.set_injection_resolver(|language: &str| {
+        (language == "ts").then(|| "typescript".to_string())
+    });
+    highlighter
+}
+
+#[test]
+fn test_lang_attribute_names_the_language() {
+    assert_eq!(block_languages(SFC), ["ts", "scss"]);
+}
+
+#[test]
+fn test_blocks_without_lang_are_javascript_and_css() {
+    let source = "<script>\nlet a = 1;\n</script>\n<style>\np { color: red; }\n</style>\n";
+    assert_eq!(block_languages(source), ["javascript", "css"]);
+}
+
+#[test]
+fn test_unknown_style_languages_fall_back_to_scss() {
+    let source = "<style lang=\"less\">\n@accent: red;\n</style>\n";
+    assert_eq!(block_languages(source), ["scss"]);
+}
+
+#[test]
+fn test_pug_templates_stay_plain() {
+    let source = "<template lang=\"pug\">\ndiv.greeting\n  p Hello\n</template>\n";
+    let html = highlighter().highlight("vue", source).unwrap();
+    assert!(html.contains("div.greeting"), "{}", html);
+}
+
+#[test]
+fn test_typescript_and_scss_blocks_highlight() {
+    let html = highlighter().highlight("vue", SFC).unwrap();
+    assert!(html.contains("<a-k>interface</a-k>"), "{}", html);
+    assert!(html.contains("<a-t>number</a-t>"), "{}", html);
+    assert!(html.contains("<a-pr>color</a-pr>"), "{}", html);
+    assert!(!html.contains("<a-k>let</a-k>"), "{}", html);
+}
@@ -33,6 +33,8 @@ export interface Utf8Injection {
   end: number;
   language: string;
   includeChildren: boolean;
+  /** Whether this is one fragment of a combined injection, parsed together with the others */
+  combined: boolean;
 }
 
 /**
@@ -83,6 +85,8 @@ export interface Utf16Injection {
   end: number;
   language: string;
   includeChildren: boolean;
+  /** Whether this is one fragment of a combined injection, parsed together with the others */
+  combined: boolean;
 }
 
 /**
@@ -129,6 +129,7 @@ fn language_icon(grammar: &str) -> &'static str {
         "go" => "\u{e626}",              //
         "ruby" => "\u{e21e}",            //
         "php" => "\u{e73d}",             //
+        "php-only" => "\u{e73d}",        //
         "swift" => "\u{e755}",           //
         "kotlin" => "\u{e634}",          //
         "scala" => "\u{e737}",           //
@@ -152,7 +153,7 @@ fn language_icon(grammar: &str) -> &'static str {
         "json" => "\u{e60b}",                  //
         "yaml" | "yml" => "\u{f481}",          //
         "toml" => "\u{e6b2}",                  //
-        "xml" => "\u{e619}",                   //
+        "xml" | "dtd" => "\u{e619}",           //
         "markdown" => "\u{e609}",              //
         "latex" | "tex" => "\u{e6a4}",         //
 
@@ -170,7 +171,7 @@ fn language_icon(grammar: &str) -> &'static str {
         "meson" => "\u{e615}",             //
 
         // Databases
-        "sql" | "mysql" | "postgresql" => "\u{e706}", //
+        "sql" | "mysql" | "postgresql" | "sqlite" => "\u{e706}", //
 
         // Other
         "git" => "\u{e702}",     //
@@ -398,6 +399,9 @@ pub struct BuildOptions {
     pub output_dir: Option<Utf8PathBuf>,
     pub jobs: usize,
     pub no_fail_fast: bool,
+    /// Embed the queries deflate-compressed (the plugins' `compressed-queries`
+    /// feature)
+    pub compress_queries: bool,
 }
 
 impl Default for BuildOptions {
@@ -408,6 +412,7 @@ impl Default for BuildOptions {
             output_dir: None,
             jobs: 16,
             no_fail_fast: false,
+            compress_queries: false,
         }
     }
 }
@@ -464,7 +469,7 @@ impl PluginGroups {
                 let lang_entry =
                     lang_entry.map_err(|e| report(format!("failed to read entry: {}", e)))?;
                 if lang_entry.path().is_dir() {
-                    grammars.push(lang_entry.file_name().to_string_lossy().to_string());
+                    grammars.push(plugin_grammar_id(&lang_entry.path()));
                 }
             }
 
@@ -483,6 +488,24 @@ impl PluginGroups {
     }
 }
 
+/// The id of the grammar a language directory's plugin is built for: its
+/// first grammar, which for grammars sharing a crate (like `erb` in
+/// `embedded-template`) isn't named after the directory.
+fn plugin_grammar_id(lang_path: &std::path::Path) -> String {
+    let dir_name = lang_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    std::fs::read_to_string(lang_path.join("def/arborium.yaml"))
+        .ok()
+        .and_then(|yaml| {
+            yaml.lines()
+                .find_map(|line| line.trim_start().strip_prefix("- id:"))
+                .map(|id| id.trim().trim_matches(['"', '\'']).to_string())
+        })
+        .unwrap_or(dir_name)
+}
+
 #[derive(Debug, Clone, facet::Facet)]
 #[facet(rename_all = "snake_case")]
 pub struct PluginManifestEntry {
@@ -574,6 +597,7 @@ pub fn build_plugins(repo_root: &Utf8Path, options: &BuildOptions) -> Result<()>
                 &wasm_bindgen,
                 &wasm_opt,
                 &printer,
+                options.compress_queries,
             );
 
             match result {
@@ -827,6 +851,7 @@ fn build_single_plugin(
     wasm_bindgen: &crate::tool::ToolPath,
     wasm_opt: &crate::tool::ToolPath,
     printer: &OutputPrinter,
+    compress_queries: bool,
 ) -> Result<(u64, u64, u64)> {
     printer.print_line(grammar, "Building...", false);
 
@@ -869,7 +894,9 @@ fn build_single_plugin(
     }
 
     // Step 1: Build with cargo +nightly using unstable features
-    // We use -Zbuild-std to rebuild std with optimizations for smaller WASM size
+    // We use -Zbuild-std to rebuild std with optimizations for smaller WASM size.
+    // Panics unwind, using wasm exception handling, so that
+    // `PluginRuntime::guarded` can turn them into errors instead of traps.
 
     // Create a unique artifact directory for this plugin to avoid locking
     let artifact_dir = plugin_source.join("artifact-out");
@@ -884,7 +911,7 @@ fn build_single_plugin(
             "--release",
             "--target",
             "wasm32-unknown-unknown",
-            "-Zbuild-std=std,panic_abort",
+            "-Zbuild-std=std,panic_unwind",
             "-Zunstable-options",
             "-Zbuild-dir-new-layout",
             "-Zbinary-dep-depinfo",
@@ -910,9 +937,12 @@ fn build_single_plugin(
         .env("XROS_DEPLOYMENT_TARGET", "")
         .env(
             "RUSTFLAGS",
-            "-Zunstable-options -Cpanic=immediate-abort -Copt-level=s -Cembed-bitcode=yes -Clto=fat -Ccodegen-units=1 -Cstrip=symbols -Aunexpected_cfgs -Amismatched_lifetime_syntaxes",
+            "-Zunstable-options -Cpanic=unwind -Ctarget-feature=+exception-handling -Cllvm-args=-wasm-use-legacy-eh=false -Copt-level=s -Cembed-bitcode=yes -Clto=fat -Ccodegen-units=1 -Cstrip=symbols -Aunexpected_cfgs -Amismatched_lifetime_syntaxes",
         )
         .current_dir(&plugin_source);
+    if compress_queries {
+        cargo_cmd.args(["--features", "compressed-queries"]);
+    }
 
     let result = run_streaming(cargo_cmd, grammar, printer)?;
 
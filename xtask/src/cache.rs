@@ -190,8 +190,9 @@ impl GrammarCache {
                 .map_err(|_| std::io::Error::other("Non-UTF8 path"))?;
 
             if path.is_dir() {
-                // Recursively hash subdirectories
-                self.hash_dir_recursive(hasher, &path)?;
+                // Recursively hash subdirectories, leaving out the same names
+                // (e.g. the src/ of a grammar generated from a subdirectory)
+                self.hash_dir_except(hasher, &path, exclude)?;
             } else if path.is_file() {
                 self.hash_file(hasher, &path)?;
             }
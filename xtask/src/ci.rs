@@ -516,6 +516,7 @@ echo "Version: $VERSION (release: $IS_RELEASE)""#,
                         Step::run("Test arborium-highlight", "cargo nextest run --manifest-path crates/arborium-highlight/Cargo.toml --all-features --verbose"),
                         Step::run("Build arborium-rustdoc", "cargo build --manifest-path crates/arborium-rustdoc/Cargo.toml --verbose"),
                         Step::run("Test arborium-rustdoc", "cargo test --manifest-path crates/arborium-rustdoc/Cargo.toml --verbose"),
+                        Step::run("Test arborium-test-harness", "cargo nextest run --manifest-path crates/arborium-test-harness/Cargo.toml --verbose --no-tests=pass"),
                         // TypeScript tests for the npm package
                         Step::run("Enable pnpm via corepack", "corepack enable pnpm"),
                         Step::run("Install npm dependencies", "cd packages/arborium && pnpm install --frozen-lockfile"),
@@ -632,6 +633,41 @@ echo "Version: $VERSION (release: $IS_RELEASE)""#,
         }
     }
 
+    // =========================================================================
+    // STAGE 2c: Grammar tests (one job per langs/group-* folder)
+    // =========================================================================
+    // Samples, snapshots, golden files and corpora are checked by each
+    // grammar crate's own tests. `CI` is set, so missing snapshots fail.
+    if let Some(ref groups) = config.plugin_groups {
+        for group in &groups.groups {
+            let display_grammars = group.grammars.join(", ");
+            jobs.insert(
+                format!("test-grammars-{}", group.name),
+                Job::new(runners::UBUNTU_32)
+                    .name(format!("Grammars ({}): {}", group.name, display_grammars))
+                    .container(CONTAINER)
+                    .needs(["generate"])
+                    .steps(
+                        [checkout()]
+                            .into_iter()
+                            .chain(download_generate_output())
+                            .chain([Step::run(
+                                format!("Test {}", display_grammars),
+                                format!(
+                                    "for manifest in langs/group-{}/*/crate/Cargo.toml; do\n  \
+                                     cargo nextest run --manifest-path \"$manifest\" --no-tests=pass\n\
+                                     done",
+                                    group.name
+                                ),
+                            )
+                            // Grammar crates aren't in a workspace; share
+                            // their dependencies' builds
+                            .with_env([("CARGO_TARGET_DIR", "target/grammars")])]),
+                    ),
+            );
+        }
+    }
+
     // =========================================================================
     // STAGE 3: Publish to crates.io (only on release, using trusted publishing)
     // =========================================================================
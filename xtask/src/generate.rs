@@ -32,6 +32,7 @@ use owo_colors::OwoColorize;
 use rayon::prelude::*;
 use rootcause::Report;
 use sailfish::TemplateSimple;
+use std::collections::{HashMap, HashSet};
 use std::process::Stdio;
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -69,6 +70,8 @@ struct CargoTomlTemplate<'a> {
     highlights_prepend_deps: &'a [HighlightDep],
     /// Optional crates for language injections (e.g., JS/CSS for HTML)
     injection_deps: &'a [HighlightDep],
+    /// Grammar crates the hand-written tests use
+    test_deps: &'a [HighlightDep],
     enable_corpus_tests: bool,
 }
 
@@ -78,6 +81,10 @@ struct BuildRsTemplate<'a> {
     generated_disclaimer: &'a str,
     has_scanner: bool,
     c_symbol: &'a str,
+    opt_level: &'a str,
+    /// (grammar/ subdirectory, has scanner) of the grammars with a parser
+    /// of their own
+    own_parsers: Vec<(String, bool)>,
 }
 
 #[derive(TemplateSimple)]
@@ -86,13 +93,45 @@ struct LibRsTemplate<'a> {
     generated_disclaimer: &'a str,
     grammar_id: &'a str,
     c_symbol: &'a str,
+    /// Upstream revision, or a hash of the grammar sources, see [`grammar_commit`]
+    grammar_commit: &'a str,
     highlights_exists: bool,
     injections_exists: bool,
     locals_exists: bool,
+    folds_exists: bool,
+    tags_exists: bool,
     tests_cursed: bool,
     /// Crate names to prepend highlights from, in order
     /// e.g. ["arborium_c"] for C++ inheriting from C
     highlights_prepend: Vec<String>,
+    /// The other grammars sharing this crate with queries of their own
+    shared_queries: Vec<SharedQueries>,
+    /// The other grammars sharing this crate with a parser of their own
+    own_parsers: Vec<OwnParser>,
+}
+
+/// Queries of a grammar sharing its crate with the first one, which it uses
+/// instead of the crate's own.
+struct SharedQueries {
+    grammar_id: String,
+    /// The function returning its language, `language` unless it has a
+    /// parser of its own
+    language_fn: String,
+    /// (const name, query file) of the highlights appended to the crate's
+    /// e.g. ("POSTGRESQL_HIGHLIGHTS_QUERY", "highlights-postgresql.scm")
+    highlights: Option<(String, String)>,
+    /// (const name, query file) of its injections
+    /// e.g. ("EJS_INJECTIONS_QUERY", "injections-ejs.scm")
+    injections: Option<(String, String)>,
+}
+
+/// A grammar sharing its crate with the first one, with a parser of its own
+/// generated from a subdirectory of `grammar/`.
+struct OwnParser {
+    grammar_id: String,
+    c_symbol: String,
+    /// e.g. `interface_language` for `grammar/interface/`
+    language_fn: String,
 }
 
 #[derive(TemplateSimple)]
@@ -135,6 +174,19 @@ struct PluginCargoTomlTemplate<'a> {
 struct PluginLibRsTemplate<'a> {
     grammar_id: &'a str,
     grammar_crate_name_snake: &'a str,
+    /// Comma-separated string literals for the manifest's aliases
+    aliases: &'a str,
+    /// Comma-separated string literals for the manifest's extensions
+    extensions: &'a str,
+    query_hash: &'a str,
+    version: &'a str,
+}
+
+#[derive(TemplateSimple)]
+#[template(path = "plugin_build.stpl.rs")]
+struct PluginBuildRsTemplate<'a> {
+    grammar_id: &'a str,
+    grammar_crate_name_snake: &'a str,
 }
 
 #[derive(TemplateSimple)]
@@ -202,18 +254,39 @@ struct LanguageEntry {
     repo_url: String,
 }
 
+/// User-facing language metadata for the umbrella crate's language list
+#[derive(Debug, Clone)]
+struct LanguageInfoEntry {
+    id: String,
+    /// Rust name of the grammar's crate, e.g. `arborium_rust`
+    crate_name: String,
+    name: String,
+    tag: String,
+    aliases: Vec<String>,
+    /// Extensions shared with another language, which detection resolves
+    /// from the contents
+    contested_extensions: Vec<String>,
+}
+
 // Umbrella crate templates (arborium)
 #[derive(TemplateSimple)]
 #[template(path = "umbrella_lib.stpl.rs")]
 struct UmbrellaLibRsTemplate<'a> {
     /// List of (crate_name, grammar_id) for all grammars
     grammars: &'a [(String, String)],
+    /// List of (crate_name, crate_id, grammar_id, language_fn) for grammars
+    /// sharing another grammar's crate
+    shared_grammars: &'a [(String, String, String, String)],
     /// List of (extension, canonical_id) pairs for detect_language function
     extensions: &'a [(String, String)],
+    /// Metadata for all user-facing languages, sorted by ID
+    language_infos: &'a [LanguageInfoEntry],
     /// List of permissively-licensed grammars (MIT, Apache-2.0, etc.)
     permissive_grammars: &'a [LanguageEntry],
     /// List of GPL-licensed grammars
     gpl_grammars: &'a [LanguageEntry],
+    /// blake3 hash of every grammar's query files, for cache invalidation
+    query_hash: &'a str,
 }
 
 #[derive(TemplateSimple)]
@@ -221,8 +294,9 @@ struct UmbrellaLibRsTemplate<'a> {
 struct UmbrellaStoreTemplate<'a> {
     /// List of (alias, canonical_id) pairs for language normalization
     aliases: &'a [(String, String)],
-    /// List of (feature_name, module_name, grammar_id) for try_lang! macro
-    languages: &'a [(String, String, String)],
+    /// List of (feature_name, module_name, grammar_id, language_fn,
+    /// highlights_const, injections_const) for try_lang! macro
+    languages: &'a [(String, String, String, String, String, String)],
 }
 
 /// Generate crate files for all or a specific grammar.
@@ -584,9 +658,12 @@ fn plan_copy_grammar_sources_inner(
                 continue;
             }
 
-            // Recursively copy subdirectories like include/, common/, rules/
+            // Recursively copy subdirectories like include/, common/, rules/,
+            // and those of grammars with a parser of their own, whose src/
+            // is generated like the top-level one
             let sub_dest = dest_dir.join(&file_name);
-            plan_copy_grammar_sources_inner(plan, &src_path, &sub_dest, mode, false)?;
+            let is_grammar_dir = src_path.join("grammar.js").exists();
+            plan_copy_grammar_sources_inner(plan, &src_path, &sub_dest, mode, is_grammar_dir)?;
         } else if is_grammar_source_file(&file_name) {
             ensure_dest_dir(plan, &mut needs_dest_dir);
 
@@ -660,6 +737,7 @@ fn generate_cargo_toml(
     shared_rel: &str,
     highlights_prepend_deps: &[HighlightDep],
     injection_deps: &[HighlightDep],
+    test_deps: &[HighlightDep],
     enable_corpus_tests: bool,
 ) -> String {
     let grammar = config.grammars.first();
@@ -693,6 +771,7 @@ fn generate_cargo_toml(
         shared_rel,
         highlights_prepend_deps,
         injection_deps,
+        test_deps,
         enable_corpus_tests,
     };
     template
@@ -714,11 +793,21 @@ fn generate_build_rs(crate_name: &str, config: &crate::types::CrateConfig) -> St
                 .unwrap_or(crate_name)
                 .replace('-', "_")
         });
+    let opt_level = grammar
+        .and_then(|g| g.c_opt_level.as_deref())
+        .unwrap_or("z");
+
+    let own_parsers = config
+        .own_parsers()
+        .map(|(g, path)| (path.to_string(), g.has_scanner()))
+        .collect();
 
     let template = BuildRsTemplate {
         generated_disclaimer: &generated_disclaimer("build.stpl.rs"),
         has_scanner,
         c_symbol: &c_symbol,
+        opt_level,
+        own_parsers,
     };
     template
         .render_once()
@@ -730,6 +819,7 @@ fn generate_lib_rs(
     crate_name: &str,
     def_path: &Utf8Path,
     config: &crate::types::CrateConfig,
+    grammar_commit: &str,
     highlights_prepend: Vec<String>,
 ) -> String {
     let grammar = config.grammars.first();
@@ -748,20 +838,128 @@ fn generate_lib_rs(
     let highlights_exists = def_path.join("queries/highlights.scm").exists();
     let injections_exists = def_path.join("queries/injections.scm").exists();
     let locals_exists = def_path.join("queries/locals.scm").exists();
+    let folds_exists = def_path.join("queries/folds.scm").exists();
+    let tags_exists = def_path.join("queries/tags.scm").exists();
+
+    let shared_queries = config
+        .grammars
+        .iter()
+        .skip(1)
+        .map(|g| SharedQueries {
+            grammar_id: g.id().to_string(),
+            language_fn: config.language_fn(g.id()),
+            highlights: g
+                .highlights_append()
+                .map(|file| (highlights_const_name(g.id()), file.to_string())),
+            injections: g
+                .injections_query()
+                .map(|file| (injections_const_name(g.id()), file.to_string())),
+        })
+        .filter(|shared| {
+            shared.highlights.is_some()
+                || shared.injections.is_some()
+                || shared.language_fn != "language"
+        })
+        .collect();
+
+    let own_parsers = config
+        .own_parsers()
+        .map(|(g, _)| OwnParser {
+            grammar_id: g.id().to_string(),
+            c_symbol: g
+                .c_symbol
+                .clone()
+                .unwrap_or_else(|| g.id().replace('-', "_")),
+            language_fn: config.language_fn(g.id()),
+        })
+        .collect();
 
     let template = LibRsTemplate {
         generated_disclaimer: &generated_disclaimer("lib.stpl.rs"),
         grammar_id,
         c_symbol: &c_symbol,
+        grammar_commit,
         highlights_exists,
         injections_exists,
         locals_exists,
+        folds_exists,
+        tags_exists,
         tests_cursed,
         highlights_prepend,
+        shared_queries,
+        own_parsers,
     };
     template.render_once().expect("LibRsTemplate render failed")
 }
 
+/// The value of a grammar crate's `GRAMMAR_COMMIT`: the upstream revision
+/// its grammar was vendored from.
+///
+/// Grammars maintained here (`commit: n/a`), or vendored without recording
+/// a revision, get `src-` and a hash of their grammar sources instead. That
+/// still changes along with the grammar, and can't be mistaken for another
+/// crate's.
+fn grammar_commit(
+    config: &crate::types::CrateConfig,
+    def_path: &Utf8Path,
+) -> Result<String, Report> {
+    let commit = config.commit.trim();
+    if !commit.is_empty() && commit != "n/a" {
+        return Ok(commit.to_string());
+    }
+    let mut hasher = blake3::Hasher::new();
+    hash_grammar_sources(&mut hasher, &def_path.join("grammar"), "")?;
+    Ok(format!("src-{}", &hasher.finalize().to_hex()[..16]))
+}
+
+/// Hash the files under a grammar directory, leaving out what tree-sitter
+/// generates.
+fn hash_grammar_sources(
+    hasher: &mut blake3::Hasher,
+    dir: &Utf8Path,
+    prefix: &str,
+) -> Result<(), Report> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    let mut names: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        // Only the top level has generated directories
+        .filter(|name| !(prefix.is_empty() && matches!(name.as_str(), "src" | "node_modules")))
+        .collect();
+    names.sort();
+    for name in names {
+        let path = dir.join(&name);
+        let relative = format!("{prefix}{name}");
+        if path.is_dir() {
+            hash_grammar_sources(hasher, &path, &format!("{relative}/"))?;
+        } else {
+            hasher.update(format!("{relative}\0").as_bytes());
+            hasher.update(&fs::read(&path)?);
+        }
+    }
+    Ok(())
+}
+
+/// Name of the constant a grammar crate exports a shared grammar's
+/// highlights query as, e.g. `MYSQL_HIGHLIGHTS_QUERY` for `mysql`.
+fn highlights_const_name(grammar_id: &str) -> String {
+    format!(
+        "{}_HIGHLIGHTS_QUERY",
+        grammar_id.replace('-', "_").to_uppercase()
+    )
+}
+
+/// Name of the constant a grammar crate exports a shared grammar's
+/// injections query as, e.g. `EJS_INJECTIONS_QUERY` for `ejs`.
+fn injections_const_name(grammar_id: &str) -> String {
+    format!(
+        "{}_INJECTIONS_QUERY",
+        grammar_id.replace('-', "_").to_uppercase()
+    )
+}
+
 /// Generate tests/corpus.rs content for a grammar crate.
 fn generate_corpus_tests(crate_name: &str, grammar_id: &str) -> String {
     let crate_name_snake = crate_name.replace('-', "_");
@@ -859,18 +1057,53 @@ fn generate_plugin_cargo_toml(grammar_id: &str, grammar_crate_name: &str) -> Str
 }
 
 /// Generate plugin src/lib.rs content.
-fn generate_plugin_lib_rs(grammar_id: &str, grammar_crate_name: &str) -> String {
+fn generate_plugin_lib_rs(
+    grammar_id: &str,
+    grammar_crate_name: &str,
+    aliases: &[String],
+    query_hash: &str,
+    version: &str,
+) -> String {
     let grammar_crate_name_snake = grammar_crate_name.replace('-', "_");
+    let alias_literals = string_literals(aliases.iter().map(String::as_str));
+    // Same as the umbrella crate: the canonical ID and aliases double as
+    // file extensions
+    let extension_literals =
+        string_literals(std::iter::once(grammar_id).chain(aliases.iter().map(String::as_str)));
 
     let template = PluginLibRsTemplate {
         grammar_id,
         grammar_crate_name_snake: &grammar_crate_name_snake,
+        aliases: &alias_literals,
+        extensions: &extension_literals,
+        query_hash,
+        version,
     };
     template
         .render_once()
         .expect("PluginLibRsTemplate render failed")
 }
 
+/// Generate plugin build.rs content.
+fn generate_plugin_build_rs(grammar_id: &str, grammar_crate_name: &str) -> String {
+    let grammar_crate_name_snake = grammar_crate_name.replace('-', "_");
+    let template = PluginBuildRsTemplate {
+        grammar_id,
+        grammar_crate_name_snake: &grammar_crate_name_snake,
+    };
+    template
+        .render_once()
+        .expect("PluginBuildRsTemplate render failed")
+}
+
+/// Render strings as comma-separated Rust string literals.
+fn string_literals<'a>(strings: impl Iterator<Item = &'a str>) -> String {
+    strings
+        .map(|s| format!("{:?}", s))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Generate plugin package.json content.
 fn generate_plugin_package_json(grammar_id: &str, grammar_name: &str, version: &str) -> String {
     let template = PluginPackageJsonTemplate {
@@ -1245,35 +1478,78 @@ fn plan_grammar_generation_with_prepared_temp(
     let def_path = &prepared_temp.crate_state.def_path;
     let crate_path = &prepared_temp.crate_state.crate_path;
 
-    // Use the already prepared temp directory instead of creating a new one
-    let temp_root = &prepared_temp.temp_root;
-    let temp_grammar = &prepared_temp.temp_grammar;
-
-    // Destination is grammar/src/ under the definition path
-    let dest_src_dir = def_path.join("grammar/src");
-    // Also copy to crate/grammar/src/ so the published crate is self-contained.
-    // We must plan this here (not in plan_crate_files_only) because that function
-    // reads from def/grammar/src/ at plan-building time, but those files don't
-    // exist yet in CI (they're gitignored and only created when this plan executes).
-    let crate_grammar_src_dir = crate_path.join("grammar/src");
-
     // Compute cache key
     // Note: compute_cache_key takes (def_path, crates_dir, config) but we only need def_path and config
     // The crates_dir is used for dependency resolution which is already handled in prepared temps
     let crates_dir = repo_root.join("crates");
     let cache_key = cache.compute_cache_key(def_path, &crates_dir, &prepared_temp.config)?;
 
-    if let Some(cached_files) = cache.get(crate_name, &cache_key) {
-        // Cache hit - skip tree-sitter generate, but still plan grammar/src updates
-        let temp_src = temp_root.join("cached_src");
-        cached_files.extract_to(&temp_src)?;
+    let grammar_id = prepared_temp
+        .config
+        .grammars
+        .first()
+        .map(|g| g.id.as_ref())
+        .unwrap_or_else(|| crate_name.strip_prefix("arborium-").unwrap_or(crate_name));
+
+    // Grammars with a parser of their own are generated from their
+    // subdirectory of grammar/, into its src/
+    let mut parsers = vec![(crate_name.clone(), grammar_id, String::new())];
+    for (grammar, path) in prepared_temp.config.own_parsers() {
+        parsers.push((
+            format!("{}/{}", crate_name, path),
+            grammar.id(),
+            format!("{}/", path),
+        ));
+    }
+
+    let mut plan = Plan::for_crate(crate_name);
+    let mut all_hit = true;
+    for (cache_name, grammar_id, subdir) in &parsers {
+        // Destination is grammar/src/ under the definition path.
+        // Also copy to crate/grammar/src/ so the published crate is self-contained.
+        // We must plan this here (not in plan_crate_files_only) because that function
+        // reads from def/grammar/src/ at plan-building time, but those files don't
+        // exist yet in CI (they're gitignored and only created when this plan executes).
+        let dest_src_dir = def_path.join(format!("grammar/{}src", subdir));
+        let crate_grammar_src_dir = crate_path.join(format!("grammar/{}src", subdir));
+
+        let (generated_src, hit) = generate_parser(
+            prepared_temp,
+            cache,
+            cache_name,
+            &cache_key,
+            grammar_id,
+            &prepared_temp.temp_grammar.join(subdir),
+        )?;
+        all_hit &= hit;
+
+        // Plan file updates to both def/grammar/src/ and crate/grammar/src/
+        plan_updates_from_generated(&mut plan, &generated_src, &dest_src_dir, mode)?;
+        plan_updates_from_generated(&mut plan, &generated_src, &crate_grammar_src_dir, mode)?;
+    }
 
-        let mut plan = Plan::for_crate(crate_name);
-        plan_updates_from_generated(&mut plan, &temp_src, &dest_src_dir, mode)?;
-        // Also copy to crate/grammar/src/
-        plan_updates_from_generated(&mut plan, &temp_src, &crate_grammar_src_dir, mode)?;
+    Ok((plan, all_hit))
+}
 
-        return Ok((plan, true)); // true = cache hit
+/// Run tree-sitter generate in `temp_grammar`, unless the cache has its
+/// output already. Returns where the generated files are, and whether they
+/// came from the cache.
+fn generate_parser(
+    prepared_temp: &PreparedTemp,
+    cache: &GrammarCache,
+    cache_name: &str,
+    cache_key: &str,
+    grammar_id: &str,
+    temp_grammar: &Utf8Path,
+) -> Result<(Utf8PathBuf, bool), Report> {
+    if let Some(cached_files) = cache.get(cache_name, cache_key) {
+        // Cache hit - skip tree-sitter generate, but still plan grammar/src updates
+        let temp_src = prepared_temp
+            .temp_root
+            .join("cached_src")
+            .join(cache_name.replace('/', "-"));
+        cached_files.extract_to(&temp_src)?;
+        return Ok((temp_src, true)); // true = cache hit
     }
 
     // Cache miss - run tree-sitter generate in the prepared temp directory
@@ -1284,10 +1560,6 @@ fn plan_grammar_generation_with_prepared_temp(
     // Without this file, tree-sitter CLI 0.25+ falls back to ABI 14 which lacks supertype information.
     let tree_sitter_json_path = temp_grammar.join("tree-sitter.json");
     if !tree_sitter_json_path.exists() {
-        let grammar = prepared_temp.config.grammars.first();
-        let grammar_id = grammar
-            .map(|g| g.id.as_ref())
-            .unwrap_or_else(|| crate_name.strip_prefix("arborium-").unwrap_or(crate_name));
         let tree_sitter_json = format!(
             r#"{{"grammars":[{{"name":"{}","path":"."}}],"metadata":{{"version":"0.0.0"}}}}"#,
             grammar_id
@@ -1311,7 +1583,7 @@ fn plan_grammar_generation_with_prepared_temp(
         let error_lines: Vec<&str> = stderr.lines().take(20).collect();
         return Err(std::io::Error::other(format!(
             "tree-sitter generate failed for {}:\n{}",
-            crate_name,
+            cache_name,
             error_lines.join("\n")
         ))
         .into());
@@ -1321,17 +1593,11 @@ fn plan_grammar_generation_with_prepared_temp(
     let generated_src = temp_grammar.join("src");
 
     // Save to cache for next time
-    if let Err(e) = cache.save(crate_name, &cache_key, &generated_src) {
-        eprintln!("Warning: failed to cache {}: {}", crate_name, e);
+    if let Err(e) = cache.save(cache_name, cache_key, &generated_src) {
+        eprintln!("Warning: failed to cache {}: {}", cache_name, e);
     }
 
-    // Plan file updates to both def/grammar/src/ and crate/grammar/src/
-    let mut plan = Plan::for_crate(crate_name);
-    plan_updates_from_generated(&mut plan, &generated_src, &dest_src_dir, mode)?;
-    // Also copy to crate/grammar/src/
-    plan_updates_from_generated(&mut plan, &generated_src, &crate_grammar_src_dir, mode)?;
-
-    Ok((plan, false)) // false = cache miss
+    Ok((generated_src, false)) // false = cache miss
 }
 
 /// Resolve a crate name to its path relative to another crate's directory.
@@ -1440,19 +1706,38 @@ fn extract_injection_deps(
     from_crate_path: &Utf8Path,
     registry: &PreparedStructures,
 ) -> Vec<HighlightDep> {
-    let mut result = Vec::new();
+    match config.grammars.first().and_then(|g| g.injections.as_ref()) {
+        Some(injections) => resolve_language_deps(injections, from_crate_path, registry),
+        None => Vec::new(),
+    }
+}
 
-    let grammar = match config.grammars.first() {
-        Some(g) => g,
-        None => return result,
-    };
+/// Extract the dev-dependencies the tests in `def/tests/` need, from a
+/// grammar config.
+fn extract_test_deps(
+    config: &crate::types::CrateConfig,
+    from_crate_path: &Utf8Path,
+    registry: &PreparedStructures,
+) -> Vec<HighlightDep> {
+    match config
+        .grammars
+        .first()
+        .and_then(|g| g.test_dependencies.as_ref())
+    {
+        Some(test_deps) => resolve_language_deps(test_deps, from_crate_path, registry),
+        None => Vec::new(),
+    }
+}
 
-    let injections = match &grammar.injections {
-        Some(inj) => inj,
-        None => return result,
-    };
+/// Resolve language IDs to the grammar crates that provide them.
+fn resolve_language_deps(
+    lang_ids: &[String],
+    from_crate_path: &Utf8Path,
+    registry: &PreparedStructures,
+) -> Vec<HighlightDep> {
+    let mut result = Vec::new();
 
-    for lang_id in injections {
+    for lang_id in lang_ids {
         // Convert language ID to crate name (e.g., "javascript" -> "arborium-javascript")
         let crate_name = format!("arborium-{}", lang_id);
 
@@ -1548,7 +1833,13 @@ fn plan_crate_files_only(
             .unwrap_or(&crate_state.name)
     });
     let tests_cursed = grammar.map(|g| g.tests_cursed()).unwrap_or(false);
-    let has_corpus = def_path.join("corpus").exists();
+    // The corpus runner reads the .txt files directly in corpus/; those in
+    // its subdirectories belong to grammars with a parser of their own
+    let has_corpus = fs::read_dir(def_path.join("corpus")).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|e| e.path().extension().is_some_and(|ext| ext == "txt"))
+    });
     let enable_corpus_tests = has_corpus && !tests_cursed;
 
     // crate/ is at langs/group-*/lang/crate/
@@ -1562,6 +1853,9 @@ fn plan_crate_files_only(
     // Extract injection dependencies (optional deps for injected languages)
     let injection_deps = extract_injection_deps(config, crate_path, registry);
 
+    // Extract the grammars the hand-written tests use (dev-dependencies)
+    let test_deps = extract_test_deps(config, crate_path, registry);
+
     // Ensure crate directory exists
     if !crate_path.exists() {
         plan.add(Operation::CreateDir {
@@ -1579,6 +1873,7 @@ fn plan_crate_files_only(
         shared_rel,
         &highlight_prepends.cargo_deps,
         &injection_deps,
+        &test_deps,
         enable_corpus_tests,
     );
 
@@ -1650,6 +1945,7 @@ fn plan_crate_files_only(
         &crate_state.name,
         def_path,
         config,
+        &grammar_commit(config, def_path)?,
         highlight_prepends.lib_prepends,
     );
 
@@ -1735,15 +2031,28 @@ fn plan_crate_files_only(
         plan_copy_grammar_sources(&mut plan, &def_lang_common, &crate_common_dir, mode)?;
     }
 
-    // Copy query files (highlights.scm, injections.scm, locals.scm) into crate/queries/
-    // so that include_str! paths work in the published package.
+    // Copy query files (highlights.scm, injections.scm, locals.scm, folds.scm, tags.scm)
+    // into crate/queries/ so that include_str! paths work in the published package.
     let def_queries_dir = def_path.join("queries");
     let crate_queries_dir = crate_path.join("queries");
 
     if def_queries_dir.exists() {
         let mut queries_found = false;
 
-        for query_name in &["highlights.scm", "injections.scm", "locals.scm"] {
+        // Plus those of grammars sharing the crate, e.g. `injections-ejs.scm`
+        let mut query_names: Vec<&str> = vec![
+            "highlights.scm",
+            "injections.scm",
+            "locals.scm",
+            "folds.scm",
+            "tags.scm",
+        ];
+        for grammar in &config.grammars {
+            query_names.extend(grammar.highlights_append());
+            query_names.extend(grammar.injections_query());
+        }
+
+        for query_name in &query_names {
             let src_query = def_queries_dir.join(query_name);
             if src_query.exists() {
                 if !queries_found {
@@ -1786,6 +2095,15 @@ fn plan_crate_files_only(
             plan_copy_dir_recursive(&mut plan, &def_samples, &crate_samples, mode)?;
         }
 
+        // Copy highlight snapshots, golden HTML, highlight assertion files
+        // and hand-written integration tests if the grammar has them
+        for dir in ["snapshots", "golden", "test", "tests"] {
+            let def_dir = def_path.join(dir);
+            if def_dir.exists() {
+                plan_copy_dir_recursive(&mut plan, &def_dir, &crate_path.join(dir), mode)?;
+            }
+        }
+
         // Copy corpus directory if it exists
         let def_corpus = def_path.join("corpus");
         if def_corpus.exists() {
@@ -1793,6 +2111,27 @@ fn plan_crate_files_only(
             plan_copy_dir_recursive(&mut plan, &def_corpus, &crate_corpus, mode)?;
         }
 
+        // Copy the test harness allowlists and budgets if the grammar has them
+        for file in [
+            "capture-allowlist.txt",
+            "node-kind-allowlist.txt",
+            "injection-allowlist.txt",
+            "timing-budget.txt",
+            "differential-expectations.txt",
+        ] {
+            let def_file = def_path.join(file);
+            if def_file.exists() {
+                let content = fs::read_to_string(&def_file)?;
+                plan_file_update(
+                    &mut plan,
+                    &crate_path.join(file),
+                    content,
+                    &format!("{} for tests", file),
+                    mode,
+                )?;
+            }
+        }
+
         // Copy individual sample files (sample.* at def root)
         for entry in fs::read_dir(def_path)? {
             let entry = entry?;
@@ -1892,7 +2231,15 @@ fn plan_plugin_crate_files(
 
     // Generate npm/src/lib.rs
     let lib_rs_path = npm_path.join("src/lib.rs");
-    let new_lib_rs = generate_plugin_lib_rs(grammar_id, crate_name);
+    let aliases = grammar.aliases.clone().unwrap_or_default();
+    let query_hash = hash_queries(crate_name, &crate_state.def_path)?;
+    let new_lib_rs = generate_plugin_lib_rs(
+        grammar_id,
+        crate_name,
+        &aliases,
+        &query_hash,
+        workspace_version,
+    );
 
     if lib_rs_path.exists() {
         let old_content = fs::read_to_string(&lib_rs_path)?;
@@ -1920,6 +2267,28 @@ fn plan_plugin_crate_files(
         });
     }
 
+    // Generate npm/build.rs
+    let build_rs_path = npm_path.join("build.rs");
+    let new_build_rs = generate_plugin_build_rs(grammar_id, crate_name);
+
+    if build_rs_path.exists() {
+        let old_content = fs::read_to_string(&build_rs_path)?;
+        if old_content != new_build_rs {
+            plan.add(Operation::UpdateFile {
+                path: build_rs_path,
+                old_content: Some(old_content),
+                new_content: new_build_rs,
+                description: "Update plugin build.rs".to_string(),
+            });
+        }
+    } else {
+        plan.add(Operation::CreateFile {
+            path: build_rs_path,
+            content: new_build_rs,
+            description: "Create plugin build.rs".to_string(),
+        });
+    }
+
     // Extract grammar metadata for package.json and README
     let grammar_name = &*grammar.name;
     let grammar_description = grammar
@@ -1999,6 +2368,118 @@ fn plan_plugin_crate_files(
     Ok(plan)
 }
 
+/// Hash the query files of every grammar in the registry.
+///
+/// The umbrella crate embeds this so that render caches are invalidated
+/// whenever any query changes.
+fn hash_bundled_queries(registry: &CrateRegistry) -> Result<String, Report> {
+    let mut crates: Vec<(&str, &Utf8Path)> = registry
+        .configured_crates()
+        .map(|(name, state, _)| (name.as_str(), state.def_path.as_path()))
+        .collect();
+    crates.sort();
+
+    let mut hasher = blake3::Hasher::new();
+    for (name, def_path) in crates {
+        hash_query_files(&mut hasher, name, def_path)?;
+    }
+    Ok(hasher.finalize().to_hex()[..16].to_string())
+}
+
+/// Hash one grammar's query files, for its plugin manifest.
+fn hash_queries(name: &str, def_path: &Utf8Path) -> Result<String, Report> {
+    let mut hasher = blake3::Hasher::new();
+    hash_query_files(&mut hasher, name, def_path)?;
+    Ok(hasher.finalize().to_hex()[..16].to_string())
+}
+
+fn hash_query_files(
+    hasher: &mut blake3::Hasher,
+    name: &str,
+    def_path: &Utf8Path,
+) -> Result<(), Report> {
+    // Every query file, not just the three standard ones: grammars sharing
+    // a crate bring their own injections query (e.g. `injections-ejs.scm`)
+    let queries_dir = def_path.join("queries");
+    if !queries_dir.exists() {
+        return Ok(());
+    }
+    let mut query_names: Vec<String> = fs::read_dir(&queries_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|query_name| query_name.ends_with(".scm"))
+        .collect();
+    query_names.sort();
+    for query_name in query_names {
+        hasher.update(format!("{name}/{query_name}\0").as_bytes());
+        hasher.update(&fs::read(queries_dir.join(&query_name))?);
+    }
+    Ok(())
+}
+
+/// Grammars that share their crate with another grammar, like `erb` and
+/// `ejs` in arborium-embedded-template: (crate_name, crate_id, grammar_id,
+/// language_fn), sorted by grammar id. Their `lang-<grammar_id>` features
+/// enable the crate's `lang-<crate_id>`.
+fn shared_grammars(prepared: &PreparedStructures) -> Vec<(String, String, String, String)> {
+    let mut shared = Vec::new();
+    for pt in &prepared.prepared_temps {
+        let name = &pt.crate_state.name;
+        let crate_id = name.strip_prefix("arborium-").unwrap_or(name);
+        for grammar in &pt.config.grammars {
+            if grammar.id() != crate_id {
+                shared.push((
+                    name.clone(),
+                    crate_id.to_string(),
+                    grammar.id().to_string(),
+                    pt.config.language_fn(grammar.id()),
+                ));
+            }
+        }
+    }
+    shared.sort_by(|a, b| a.2.cmp(&b.2));
+    shared
+}
+
+/// Names of the grammar crates left out of `all-languages`, because they're
+/// slow enough to build that only users who need them should pay for it.
+fn opt_in_crates(prepared: &PreparedStructures) -> HashSet<String> {
+    prepared
+        .prepared_temps
+        .iter()
+        .filter(|pt| pt.config.grammars.iter().any(|g| g.is_opt_in()))
+        .map(|pt| pt.crate_state.name.clone())
+        .collect()
+}
+
+/// The internal grammars each grammar crate injects, by crate name. Internal
+/// grammars only exist to be injected, so the languages that inject them
+/// pull them in.
+fn internal_injections(prepared: &PreparedStructures) -> HashMap<String, Vec<String>> {
+    let internal: HashSet<&str> = prepared
+        .prepared_temps
+        .iter()
+        .flat_map(|pt| &pt.config.grammars)
+        .filter(|g| g.is_internal())
+        .map(|g| g.id.as_str())
+        .collect();
+    prepared
+        .prepared_temps
+        .iter()
+        .map(|pt| {
+            let injected = pt
+                .config
+                .grammars
+                .iter()
+                .flat_map(|g| g.injections.iter().flatten())
+                .filter(|id| internal.contains(id.as_str()))
+                .cloned()
+                .collect();
+            (pt.crate_state.name.clone(), injected)
+        })
+        .collect()
+}
+
 /// Generate the umbrella crate (crates/arborium/Cargo.toml, src/lib.rs, src/provider.rs)
 /// This aggregates all grammar crates as optional dependencies with features.
 fn plan_umbrella_crate(prepared: &PreparedStructures) -> Result<Plan, Report> {
@@ -2018,6 +2499,8 @@ fn plan_umbrella_crate(prepared: &PreparedStructures) -> Result<Plan, Report> {
         })
         .collect();
     grammar_crates.sort_by(|a, b| a.0.cmp(&b.0));
+    let opt_in = opt_in_crates(prepared);
+    let shared_grammars = shared_grammars(prepared);
 
     let version = &prepared.workspace_version;
 
@@ -2050,25 +2533,43 @@ include = [
 [features]
 default = []
 
+# On-disk store for the render cache (arborium::cache::FsStore)
+cache-fs = []
+
 # All languages
 all-languages = [
 "#
     ));
 
     // Add all lang-* features to all-languages
-    for (_name, grammar_id, _) in &grammar_crates {
-        // Skip internal grammars
-        if grammar_id.ends_with("_inline") {
+    for (name, grammar_id, _) in &grammar_crates {
+        // Skip internal grammars, and those you have to ask for by name
+        if grammar_id.ends_with("_inline") || opt_in.contains(name) {
             continue;
         }
         content.push_str(&format!("    \"lang-{}\",\n", grammar_id));
     }
+    for (name, _, grammar_id, _) in &shared_grammars {
+        if !opt_in.contains(name) {
+            content.push_str(&format!("    \"lang-{}\",\n", grammar_id));
+        }
+    }
     content.push_str("]\n\n");
 
     // Individual language features
     content.push_str("# Individual language features\n");
+    let internal_injections = internal_injections(prepared);
     for (name, grammar_id, _) in &grammar_crates {
-        content.push_str(&format!("lang-{} = [\"dep:{}\"]\n", grammar_id, name));
+        let mut deps = vec![format!("\"dep:{}\"", name)];
+        for id in internal_injections.get(name).into_iter().flatten() {
+            deps.push(format!("\"lang-{}\"", id));
+        }
+        content.push_str(&format!("lang-{} = [{}]\n", grammar_id, deps.join(", ")));
+    }
+
+    // Grammars sharing another grammar's crate are enabled through its feature
+    for (_, crate_id, grammar_id, _) in &shared_grammars {
+        content.push_str(&format!("lang-{} = [\"lang-{}\"]\n", grammar_id, crate_id));
     }
 
     // Dependencies section (use full version for all dependencies)
@@ -2078,6 +2579,7 @@ all-languages = [
 arborium-tree-sitter = {{ version = "{version}", path = "../arborium-tree-sitter" }}
 arborium-theme = {{ version = "{version}", path = "../arborium-theme" }}
 arborium-highlight = {{ version = "{version}", path = "../arborium-highlight", features = ["tree-sitter"] }}
+xxhash-rust = {{ version = "0.8", features = ["xxh3"] }}
 
 # Optional grammar dependencies
 "#
@@ -2153,20 +2655,58 @@ dlmalloc = "0.2"
     // Collect aliases and extensions from all grammars in the registry
     let mut aliases: Vec<(String, String)> = Vec::new();
     let mut extensions: Vec<(String, String)> = Vec::new();
-    let mut languages: Vec<(String, String, String)> = Vec::new();
+    let mut languages: Vec<(String, String, String, String, String, String)> = Vec::new();
+    let mut language_infos: Vec<LanguageInfoEntry> = Vec::new();
 
-    for (_state, _config, grammar) in prepared.registry.all_grammars() {
+    for (state, config, grammar) in prepared.registry.all_grammars() {
         let grammar_id = grammar.id().to_string();
 
-        // Skip internal grammars
-        if grammar.is_internal() || grammar_id.ends_with("_inline") {
+        if grammar_id.ends_with("_inline") {
             continue;
         }
 
-        // Build feature name, module name, and grammar ID for try_lang! macro
+        // Build feature name, module name, grammar ID, language function and
+        // queries for try_lang! macro. Grammars sharing a crate use its
+        // module, and the first one's parser and queries unless they have
+        // their own.
+        let crate_id = state.name.strip_prefix("arborium-").unwrap_or(&state.name);
         let feature = format!("lang-{}", grammar_id);
-        let module = format!("lang_{}", grammar_id.replace('-', "_"));
-        languages.push((feature, module, grammar_id.clone()));
+        let module = format!("lang_{}", crate_id.replace('-', "_"));
+        let shares_crate = config
+            .grammars
+            .first()
+            .is_some_and(|g| g.id() != grammar.id());
+        let highlights = match grammar.highlights_append() {
+            Some(_) if shares_crate => highlights_const_name(&grammar_id),
+            _ => "HIGHLIGHTS_QUERY".to_string(),
+        };
+        let injections = match grammar.injections_query() {
+            Some(_) if shares_crate => injections_const_name(&grammar_id),
+            _ => "INJECTIONS_QUERY".to_string(),
+        };
+        let language_fn = config.language_fn(&grammar_id);
+        languages.push((
+            feature,
+            module,
+            grammar_id.clone(),
+            language_fn,
+            highlights,
+            injections,
+        ));
+
+        // Internal grammars can be injected, but aren't languages of their own
+        if grammar.is_internal() {
+            continue;
+        }
+
+        language_infos.push(LanguageInfoEntry {
+            id: grammar_id.clone(),
+            crate_name: state.name.replace('-', "_"),
+            name: grammar.name.clone(),
+            tag: grammar.tag.clone(),
+            aliases: grammar.aliases.clone().unwrap_or_default(),
+            contested_extensions: grammar.contested_extensions().to_vec(),
+        });
 
         // Add canonical ID as an extension (e.g., "rust" -> "rust")
         extensions.push((grammar_id.clone(), grammar_id.clone()));
@@ -2185,6 +2725,7 @@ dlmalloc = "0.2"
     aliases.sort();
     extensions.sort();
     languages.sort();
+    language_infos.sort_by(|a, b| a.id.cmp(&b.id));
 
     // =========================================================================
     // Collect all grammars and separate by license type (for lib.rs and README)
@@ -2237,11 +2778,15 @@ dlmalloc = "0.2"
     // =========================================================================
     // Generate src/lib.rs from template
     // =========================================================================
+    let query_hash = hash_bundled_queries(&prepared.registry)?;
     let lib_rs_content = UmbrellaLibRsTemplate {
         grammars: &grammars_for_lib,
+        shared_grammars: &shared_grammars,
         extensions: &extensions,
+        language_infos: &language_infos,
         permissive_grammars: &permissive_grammars,
         gpl_grammars: &gpl_grammars,
+        query_hash: &query_hash,
     }
     .render_once()
     .expect("UmbrellaLibRsTemplate render failed");
@@ -2345,6 +2890,7 @@ fn plan_shared_crates(prepared: &PreparedStructures, mode: PlanMode) -> Result<P
         "arborium-tree-sitter",
         "arborium-host",
         "arborium-plugin-runtime",
+        "arborium-plugin-host",
         "arborium-wire",
         "arborium-query",
         "arborium-rustdoc",
@@ -2504,6 +3050,7 @@ mod tests {
             HIGHLIGHTS_QUERY,
             INJECTIONS_QUERY,
             LOCALS_QUERY,
+            env!("CARGO_MANIFEST_DIR"),
         );
     }
 }
@@ -2513,6 +3060,86 @@ The harness validates that:
 - The grammar compiles correctly
 - Highlight queries are syntactically valid
 - Injection queries parse without errors
+- Queries only refer to node kinds and fields the grammar has, unless
+  they're listed in the grammar's `node-kind-allowlist.txt`
+- Every highlight capture maps to a theme slot or highlight name, unless
+  it's listed in the grammar's `capture-allowlist.txt`
+- Every sample in `samples/` gets highlighted through the plugin runtime,
+  into spans that are in bounds, on char boundaries, sorted, and named
+- Every language the injections query sets or the samples inject is a
+  grammar id or alias from the repository's `arborium.yaml` files, unless
+  it's listed in the grammar's `injection-allowlist.txt`
+- The plugin runtime and the renderer's `CompiledGrammar` pipeline produce
+  the same highlights and injections for every sample, apart from
+  differences documented in the grammar's `differential-expectations.txt`
+- Samples still highlight the way their snapshots say, for grammars with
+  `def/snapshots/`
+
+Grammars with a `folds.scm` also call `test_folds`, which checks that the
+query compiles against the grammar and captures nothing but `@fold`. Those
+with a `tags.scm` call `test_tags`, which checks that every pattern captures a
+`@name` along with a `@definition.*` or `@reference.*` kind.
+
+## Snapshots
+
+Snapshots list each sample's spans as `start..end capture "text"` lines. When
+they don't match, the test prints which tokens changed capture, appeared or
+disappeared. Run the tests with `ARBORIUM_UPDATE_SNAPSHOTS=1` to accept the
+changes; missing snapshots are recorded automatically, except on CI.
+
+Grammars with `def/golden/` also get each sample rendered to HTML with a
+fixed theme and compared against `golden/<sample>.html`. The renderer output
+must be well-formed, and updates growing a golden file by more than 10% need
+`ARBORIUM_UPDATE_SNAPSHOTS=force`.
+
+## Highlight assertions
+
+Grammars opt into tree-sitter style highlight tests by dropping files into
+`def/test/highlight/`. Comments in them point at the closest line above that
+isn't an assertion, and name the capture expected there:
+
+```rust
+pub fn main() {
+// <- keyword
+//     ^ function
+//  ^ !function
+}
+```
+
+`<-` points at the column the comment starts in, and each `^` at its own
+column. A capture matches itself and its dotted children, and `!` negates
+it. `test_grammar` recognizes the usual comment openers. Use
+`assertions::test_highlight_assertions` with other comment prefixes.
+
+## Timing
+
+Set `ARBORIUM_TIMING=1` to also time highlighting each sample. The median of
+`ARBORIUM_TIMING_RUNS` runs (11 by default) is compared against the grammar's
+`def/timing-budget.txt`, and the test fails when a sample takes more than
+`ARBORIUM_TIMING_FACTOR` (3 by default) times its budget. Run with
+`ARBORIUM_UPDATE_TIMING=1` to re-baseline, and set `ARBORIUM_TIMING_REPORT`
+to a directory to get a JSON report per grammar.
+
+## Incremental edits
+
+`edits::test_incremental_edits` applies random edit sequences to seed
+documents and checks that every incremental parse matches a fresh parse of
+the same text. Failures print the minimized edit sequence.
+
+## Hand-written tests
+
+Tests a grammar needs beyond these live in its `def/tests/`, which is copied
+to the crate's `tests/`. `helpers` has what they share: a highlighter over
+compiled grammars, the plugin runtime's results and injections for a source,
+and the nodes of a parse tree. Other grammars they use go under
+`test_dependencies` in the grammar's `arborium.yaml`, and become path-only
+dev-dependencies:
+
+```yaml
+    test_dependencies:
+      - scss
+      - typescript
+```
 "#
         }
         "arborium-tree-sitter" => {
@@ -2557,6 +3184,15 @@ globalThis.arboriumHost = {
 
 Grammar plugins are WASM plugins loaded on-demand from a CDN.
 
+Alternatively, register plugins by URL and let the host fetch and drive them
+itself through their raw ABI, without their JS glue:
+
+```javascript
+registerGrammarModule("rust", "https://cdn.example/rust/grammar_bg.wasm");
+await highlight("rust", source);       // fetches the plugin on first use
+await highlightNow("rust", source);    // plain text until it's loaded
+```
+
 This crate implements `GrammarProvider` to integrate with `arborium-highlight`,
 ensuring browser and native Rust use the same highlighting logic.
 "#
@@ -2578,8 +3214,44 @@ WASM plugin. This is linked into each grammar plugin.
 - Query execution to produce Span and Injection records
 - Incremental parsing via edit application
 - Cancellation support
+- Panics turned into errors that poison only the affected session
+- Lazy query compilation, optionally from deflate-compressed queries
+  (`compressed-queries` feature)
 
 This is an internal crate used by generated grammar plugins.
+"#
+        }
+        "arborium-plugin-host" => {
+            r#"# arborium-plugin-host
+
+Native host for arborium WASM grammar plugins.
+
+## Purpose
+
+Loads grammar plugins built by `cargo xtask build` with wasmtime and drives
+them from native Rust, without a JavaScript runtime. Plugins export a raw
+ABI next to their wasm-bindgen exports for this (see `arborium_wire::abi`).
+
+## Features
+
+- Wire version check at load time
+- `read_manifest` to tell what a `.wasm` file provides without compiling it
+- Session management (create/free), text updates, incremental edits
+- Parsing to UTF-8 spans and injections
+- Capture names, query sources and query hash of each plugin
+- Per-call time and memory limits, so a runaway parser can't hang the host
+- Each session runs in its own instance, so a trapping plugin only loses
+  the session it was working on
+- `PluginRegistry` to discover plugins in directories and look grammars up
+  by name, alias or extension, next to built-in native grammars
+- `highlight_document` to follow injections across plugins and native
+  grammars
+- Hot reload of rebuilt plugins without losing sessions, with an optional
+  directory watcher (`watch` feature)
+- `ProviderRegistry` and the `SyntaxProvider` trait, to use grammars
+  compiled into the host (`native` feature) and plugins interchangeably
+- On-demand plugin downloads verified against a signed index, with an
+  offline-capable cache (`fetch` and `fetch-http` features)
 "#
         }
         "arborium-wire" => {
@@ -2913,6 +3585,8 @@ fn plan_cli_crate(prepared: &PreparedStructures) -> Result<Plan, Report> {
         })
         .collect();
     grammar_crates.sort_by(|a, b| a.0.cmp(&b.0));
+    let opt_in = opt_in_crates(prepared);
+    let shared_grammars = shared_grammars(prepared);
 
     let version = &prepared.workspace_version;
 
@@ -2945,13 +3619,18 @@ all-languages = [
     ));
 
     // Add all lang-* features to all-languages
-    for (_name, grammar_id) in &grammar_crates {
-        // Skip internal grammars
-        if grammar_id.ends_with("_inline") {
+    for (name, grammar_id) in &grammar_crates {
+        // Skip internal grammars, and those you have to ask for by name
+        if grammar_id.ends_with("_inline") || opt_in.contains(name) {
             continue;
         }
         content.push_str(&format!("    \"lang-{}\",\n", grammar_id));
     }
+    for (name, _, grammar_id, _) in &shared_grammars {
+        if !opt_in.contains(name) {
+            content.push_str(&format!("    \"lang-{}\",\n", grammar_id));
+        }
+    }
     content.push_str("]\n\n");
 
     // Individual language features
@@ -2962,6 +3641,12 @@ all-languages = [
             grammar_id, grammar_id
         ));
     }
+    for (_, _, grammar_id, _) in &shared_grammars {
+        content.push_str(&format!(
+            "lang-{} = [\"arborium/lang-{}\"]\n",
+            grammar_id, grammar_id
+        ));
+    }
 
     // Dependencies section
     content.push_str(&format!(
@@ -241,6 +241,34 @@ fn lint_crate(
             )));
         }
 
+        // Check a highlights overlay of its own exists
+        if let Some(file) = grammar.highlights_append() {
+            if !state.def_path.join("queries").join(file).exists() {
+                diagnostics.push(LintDiagnostic::Error(format!(
+                    "grammar '{gid}': highlights query queries/{file} is missing",
+                )));
+            }
+        }
+
+        // Check an injections query of its own exists
+        if let Some(file) = grammar.injections_query() {
+            if !state.def_path.join("queries").join(file).exists() {
+                diagnostics.push(LintDiagnostic::Error(format!(
+                    "grammar '{gid}': injections query queries/{file} is missing",
+                )));
+            }
+        }
+
+        // Check contested extensions aren't also claimed outright
+        for ext in grammar.contested_extensions() {
+            let aliases = grammar.aliases.as_deref().unwrap_or_default();
+            if ext == gid || aliases.contains(ext) {
+                diagnostics.push(LintDiagnostic::Error(format!(
+                    "grammar '{gid}': contested extension '{ext}' is also an alias",
+                )));
+            }
+        }
+
         // Skip user-facing metadata checks for internal grammars
         if grammar.is_internal() {
             continue;
@@ -125,6 +125,10 @@ enum Command {
         /// Continue building other plugins even if some fail
         #[facet(args::named, default)]
         no_fail_fast: bool,
+
+        /// Embed grammar queries deflate-compressed, for smaller plugins
+        #[facet(args::named, default)]
+        compress_queries: bool,
     },
 
     /// Run grammar tests for a specific language crate
@@ -329,6 +333,7 @@ fn main() {
             jobs,
             dev,
             no_fail_fast,
+            compress_queries,
         } => {
             let repo_root = util::find_repo_root().expect("Could not find repo root");
             let repo_root = camino::Utf8PathBuf::from_path_buf(repo_root).expect("non-UTF8 path");
@@ -351,6 +356,7 @@ fn main() {
                 output_dir: output.map(camino::Utf8PathBuf::from),
                 jobs: jobs.unwrap_or(16),
                 no_fail_fast,
+                compress_queries,
             };
             if let Err(e) = build::build_plugins(&repo_root, &options) {
                 eprintln!("{:?}", e);
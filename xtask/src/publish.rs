@@ -19,7 +19,7 @@
 use camino::{Utf8Path, Utf8PathBuf};
 use owo_colors::OwoColorize;
 use rootcause::Report;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::process::{Command, Stdio};
 
 use crate::tool::Tool;
@@ -40,8 +40,12 @@ const PRE_CRATES: &[&str] = &[
     "crates/arborium-tree-sitter",
     // Depends on theme, tree-sitter
     "crates/arborium-highlight",
-    // Depends on highlight, theme, tree-sitter
+    // Depends on sysroot, tree-sitter, wire
+    "crates/arborium-plugin-runtime",
+    // Depends on highlight, plugin-runtime, theme, tree-sitter, wire
     "crates/arborium-test-harness",
+    // Depends on highlight, wire
+    "crates/arborium-plugin-host",
 ];
 
 /// Crates in the "post" group - must be published after grammar crates.
@@ -1216,8 +1220,9 @@ fn regenerate_umbrella_crate(
 
     // Collect all grammar crates with their paths and versions
     let mut grammar_crates: Vec<(String, String, Utf8PathBuf, String)> = Vec::new();
+    let mut opt_in = HashSet::new();
 
-    for (name, state, _) in registry.configured_crates() {
+    for (name, state, config) in registry.configured_crates() {
         // This is a grammar crate
         let crate_path = state.crate_path.clone();
         let grammar_id = name.strip_prefix("arborium-").unwrap_or(&name).to_string();
@@ -1228,6 +1233,9 @@ fn regenerate_umbrella_crate(
             .cloned()
             .unwrap_or_else(|| workspace_version.clone());
 
+        if config.grammars.iter().any(|g| g.is_opt_in()) {
+            opt_in.insert(grammar_id.clone());
+        }
         grammar_crates.push((name.clone(), grammar_id, crate_path, version));
     }
 
@@ -1269,7 +1277,7 @@ all-languages = [
 
     // Add all lang-* features
     for (_, grammar_id, _, _) in &grammar_crates {
-        if grammar_id.ends_with("_inline") {
+        if grammar_id.ends_with("_inline") || opt_in.contains(grammar_id) {
             continue;
         }
         content.push_str(&format!("    \"lang-{}\",\n", grammar_id));
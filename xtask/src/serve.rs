@@ -139,6 +139,10 @@ structstruck::strike! {
             /// File extension aliases (e.g., ["rs"] for Rust).
             pub aliases: Vec<String>,
 
+            /// Extensions shared with a language that claims them (e.g.,
+            /// ["m"] for Objective-C, which MATLAB claims).
+            pub contested_extensions: Vec<String>,
+
             /// URL to the upstream tree-sitter grammar repository.
             pub grammar_repo: Option<String>,
 
@@ -225,6 +229,7 @@ impl RegistryGrammar {
             link: grammar.link.clone(),
             trivia: grammar.trivia.clone(),
             aliases: grammar.aliases.clone().unwrap_or_default(),
+            contested_extensions: grammar.contested_extensions().to_vec(),
             grammar_repo,
             grammar_license: Some(config.license.to_string()),
             samples,
@@ -559,6 +564,7 @@ fn generate_shared_crate_manifests(repo_root: &Path) -> Result<(), String> {
         "arborium-tree-sitter",
         "arborium-host",
         "arborium-plugin-runtime",
+        "arborium-plugin-host",
         "arborium-wire",
         "arborium-query",
         "arborium-rustdoc",
@@ -1187,6 +1193,17 @@ fn build_language_info_js(registry: &Registry) -> String {
                 .collect();
             js.push_str(&format!("        \"aliases\": [{}],\n", aliases.join(", ")));
         }
+        if !grammar.contested_extensions.is_empty() {
+            let extensions: Vec<String> = grammar
+                .contested_extensions
+                .iter()
+                .map(|e| format!("\"{}\"", escape_for_js(e)))
+                .collect();
+            js.push_str(&format!(
+                "        \"contestedExtensions\": [{}],\n",
+                extensions.join(", ")
+            ));
+        }
         if let Some(ref repo) = grammar.grammar_repo {
             js.push_str(&format!(
                 "        \"grammarRepo\": \"{}\",\n",
@@ -1723,6 +1740,7 @@ mod tests {
                 link: Some("https://www.rust-lang.org/".to_string()),
                 trivia: Some("Originally a personal project".to_string()),
                 aliases: vec!["rs".to_string()],
+                contested_extensions: vec![],
                 grammar_repo: Some("https://github.com/tree-sitter/tree-sitter-rust".to_string()),
                 grammar_license: Some("MIT".to_string()),
                 samples: vec![RegistrySample {
@@ -23,7 +23,10 @@
 //! │   │   │   ├── arborium.yaml
 //! │   │   │   ├── grammar/
 //! │   │   │   ├── queries/
-//! │   │   │   └── samples/
+//! │   │   │   ├── samples/
+//! │   │   │   ├── snapshots/    # Highlight snapshots of the samples (optional)
+//! │   │   │   ├── golden/       # Rendered HTML of the samples (optional)
+//! │   │   │   └── test/highlight/ # Highlight assertion files (optional)
 //! │   │   ├── crate/            # Generated Rust crate
 //! │   │   └── npm/              # Generated WASM package
 //! │   ├── c/
@@ -69,27 +72,30 @@
 //!
 //! # Example `arborium.yaml` (multi-grammar crate)
 //!
+//! Grammars after the first share the crate's parser, unless they have a
+//! `grammar_path`: then their parser is generated from
+//! `def/grammar/<grammar_path>/grammar.js`, and the crate exports it as
+//! `<grammar_path>_language()` next to `language()`.
+//!
 //! ```yaml
-//! repo: https://github.com/tree-sitter-grammars/tree-sitter-xml
-//! commit: 863dbc381f44f6c136a399e684383b977bb2beaa
+//! repo: https://github.com/tree-sitter/tree-sitter-ocaml
+//! commit: 3ef7c00b29e41e3a0c1d18e82ea37c64d72b93fc
 //! license: MIT
-//! authors: ObserverOfTime
 //!
 //! grammars:
-//!   - id: xml
-//!     name: XML
-//!     tag: markup
-//!     tier: 3
+//!   - id: ocaml
+//!     name: OCaml
+//!     tag: code
+//!     tier: 2
 //!     has_scanner: true
-//!     grammar_path: xml
 //!     # ...metadata, samples...
 //!
-//!   - id: dtd
-//!     name: DTD
-//!     tag: markup
+//!   - id: ocaml-interface
+//!     name: OCaml Interface
+//!     tag: code
 //!     tier: 3
 //!     has_scanner: true
-//!     grammar_path: dtd
+//!     grammar_path: interface
 //!     # ...metadata, samples...
 //! ```
 
@@ -131,6 +137,27 @@ pub struct CrateConfig {
     pub grammars: Vec<GrammarConfig>,
 }
 
+impl CrateConfig {
+    /// The grammars after the first with a parser of their own, generated
+    /// from `def/grammar/<grammar_path>/`, along with that path.
+    pub fn own_parsers(&self) -> impl Iterator<Item = (&GrammarConfig, &str)> {
+        self.grammars
+            .iter()
+            .skip(1)
+            .filter_map(|g| Some((g, g.grammar_path.as_deref()?)))
+    }
+
+    /// The name of the function the crate exports a grammar's language as:
+    /// `language`, or e.g. `interface_language` for a grammar with a parser
+    /// of its own in `interface/`.
+    pub fn language_fn(&self, grammar_id: &str) -> String {
+        self.own_parsers()
+            .find(|(g, _)| g.id() == grammar_id)
+            .map(|(_, path)| format!("{}_language", path.replace('-', "_")))
+            .unwrap_or_else(|| "language".to_string())
+    }
+}
+
 // =============================================================================
 // Per-grammar configuration
 // =============================================================================
@@ -164,6 +191,13 @@ pub struct GrammarConfig {
     #[facet(default)]
     pub aliases: Option<Vec<String>>,
 
+    /// File extensions this language shares with another one that claims
+    /// them as an alias, like Objective-C's `.m` (MATLAB's). They're listed
+    /// among the language's extensions but don't map to it: detection picks
+    /// it from the file's contents.
+    #[facet(default)]
+    pub contested_extensions: Option<Vec<String>>,
+
     // =========================================================================
     // Build Configuration
     // =========================================================================
@@ -183,7 +217,9 @@ pub struct GrammarConfig {
     #[facet(default)]
     pub has_scanner: Option<bool>,
 
-    /// Path to the grammar within the repo (for multi-grammar repos).
+    /// Path to the grammar within the repo (for multi-grammar repos). For a
+    /// grammar after the first, its parser is generated from
+    /// `def/grammar/<grammar_path>/` instead of sharing the crate's.
     #[facet(default)]
     pub grammar_path: Option<String>,
 
@@ -191,6 +227,16 @@ pub struct GrammarConfig {
     #[facet(default)]
     pub c_symbol: Option<String>,
 
+    /// Override the C optimization level (default `"z"`). Grammars whose
+    /// generated lexer is one enormous function compile much faster at `"1"`.
+    #[facet(default)]
+    pub c_opt_level: Option<String>,
+
+    /// Leave this grammar out of `all-languages`, so that only users who
+    /// enable its `lang-*` feature pay for compiling it.
+    #[facet(default)]
+    pub opt_in: Option<bool>,
+
     /// Query configuration (highlights inheritance).
     #[facet(default)]
     pub queries: Option<QueriesConfig>,
@@ -204,6 +250,12 @@ pub struct GrammarConfig {
     #[facet(default)]
     pub injections: Option<Vec<String>>,
 
+    /// Other languages the tests in `def/tests/` use (e.g. the hosts a
+    /// grammar gets injected into). These become path-only dev-dependencies,
+    /// which are left out of the published crate.
+    #[facet(default)]
+    pub test_dependencies: Option<Vec<String>>,
+
     // =========================================================================
     // Language Metadata (for demos and documentation)
     // =========================================================================
@@ -251,11 +303,32 @@ impl GrammarConfig {
         self.has_scanner.unwrap_or(false)
     }
 
+    /// Whether this grammar is left out of `all-languages`.
+    pub fn is_opt_in(&self) -> bool {
+        self.opt_in.unwrap_or(false)
+    }
+
     /// Whether tests are cursed (skip test generation).
     pub fn tests_cursed(&self) -> bool {
         self.tests_cursed.unwrap_or(false)
     }
 
+    /// Extensions shared with another language that claims them.
+    pub fn contested_extensions(&self) -> &[String] {
+        self.contested_extensions.as_deref().unwrap_or_default()
+    }
+
+    /// The injections query file overriding `queries/injections.scm`, if any.
+    pub fn injections_query(&self) -> Option<&str> {
+        self.queries.as_ref()?.injections.as_deref()
+    }
+
+    /// The query file appended to the crate's highlights for this grammar,
+    /// if any.
+    pub fn highlights_append(&self) -> Option<&str> {
+        self.queries.as_ref()?.highlights.as_ref()?.append.as_deref()
+    }
+
     /// Whether to generate a WASM plugin for this grammar.
     /// Defaults to true.
     pub fn generate_plugin(&self) -> bool {
@@ -280,6 +353,13 @@ pub struct QueriesConfig {
     /// Highlights query configuration.
     #[facet(default)]
     pub highlights: Option<HighlightsConfig>,
+
+    /// Injections query for this grammar, relative to `queries/`, when it
+    /// shares its crate with another grammar and routes injections
+    /// differently (e.g. `injections-ejs.scm`). Defaults to
+    /// `injections.scm`.
+    #[facet(default)]
+    pub injections: Option<String>,
 }
 
 /// Highlights query configuration.
@@ -288,6 +368,12 @@ pub struct HighlightsConfig {
     /// Queries to prepend from other grammars.
     #[facet(default)]
     pub prepend: Option<Vec<PrependConfig>>,
+
+    /// Query file, relative to `queries/`, appended to `highlights.scm` for
+    /// this grammar when it shares its crate with another, e.g. a SQL
+    /// dialect's `highlights-postgresql.scm`.
+    #[facet(default)]
+    pub append: Option<String>,
 }
 
 /// A reference to another grammar's queries to prepend.
@@ -419,6 +505,12 @@ structstruck::strike! {
 
             /// locals.scm
             pub locals: FileState,
+
+            /// folds.scm
+            pub folds: FileState,
+
+            /// tags.scm
+            pub tags: FileState,
         },
 
         /// Sample files declared in yaml
@@ -644,6 +736,8 @@ impl CrateRegistry {
             files.queries.highlights = Self::read_file_state(&queries_path.join("highlights.scm"));
             files.queries.injections = Self::read_file_state(&queries_path.join("injections.scm"));
             files.queries.locals = Self::read_file_state(&queries_path.join("locals.scm"));
+            files.queries.folds = Self::read_file_state(&queries_path.join("folds.scm"));
+            files.queries.tags = Self::read_file_state(&queries_path.join("tags.scm"));
         }
 
         // Check for samples declared in config (in def/)
@@ -729,6 +823,8 @@ impl CrateRegistry {
             files.queries.highlights = Self::read_file_state(&queries_path.join("highlights.scm"));
             files.queries.injections = Self::read_file_state(&queries_path.join("injections.scm"));
             files.queries.locals = Self::read_file_state(&queries_path.join("locals.scm"));
+            files.queries.folds = Self::read_file_state(&queries_path.join("folds.scm"));
+            files.queries.tags = Self::read_file_state(&queries_path.join("tags.scm"));
         }
 
         // Check for samples declared in config
@@ -1,5 +1,7 @@
 // Arborium Demo - Syntax highlighting via arborium-host (wasm-bindgen)
-// The Rust host handles highlighting with async grammar loading via window.arboriumHost
+// The Rust host fetches grammar plugins on first use and drives them through
+// their raw ABI (registerGrammarModule). window.arboriumHost, which imports a
+// plugin's wasm-bindgen glue, is only used for the tree explorer.
 
 // Build WASI stubs for browser environment (used by grammar plugins)
 function createWasiStubs() {
@@ -50,6 +52,9 @@ let registry = null;
 // The wasm-bindgen host module (arborium_host.js)
 let hostModule = null;
 
+// Grammars fetched as soon as the host is up, since most visitors use them
+const coreLanguages = ['rust', 'javascript', 'python'];
+
 // Cache for loaded grammar plugins
 const grammarCache = {};
 
@@ -138,6 +143,25 @@ function setupArboriumHost() {
             return !!entry || !!grammarCache[language];
         },
 
+        // Map a file extension or alias to a language id (sync). An extension
+        // shared by several languages (`.m`) maps to the one that claims it;
+        // the host checks the contents for the others before asking. If that
+        // one isn't in the demo, any language sharing it will do.
+        languageForExtension(extension) {
+            const entries = Object.entries(languageInfo);
+            for (const [id, info] of entries) {
+                if (id === extension || (info.aliases || []).includes(extension)) {
+                    return id;
+                }
+            }
+            for (const [id, info] of entries) {
+                if ((info.contestedExtensions || []).includes(extension)) {
+                    return id;
+                }
+            }
+            return null;
+        },
+
         // Load a grammar and return a handle (async)
         async loadGrammar(language) {
             const plugin = await loadGrammarPlugin(language);
@@ -169,6 +193,26 @@ function setupArboriumHost() {
             }
         },
 
+        // Return the syntax tree for text using a grammar handle (sync)
+        parseTree(handle, text, maxDepth, maxNodes) {
+            const entry = handleToPlugin.get(handle);
+            if (!entry) throw new Error(`Unknown grammar handle ${handle}`);
+
+            const { plugin, session } = entry;
+            plugin.set_text(session, text);
+            return plugin.parse_tree(session, maxDepth, maxNodes);
+        },
+
+        // Return the nodes covering a byte offset, root first (sync)
+        nodeAt(handle, text, offset) {
+            const entry = handleToPlugin.get(handle);
+            if (!entry) throw new Error(`Unknown grammar handle ${handle}`);
+
+            const { plugin, session } = entry;
+            plugin.set_text(session, text);
+            return plugin.node_at(session, offset);
+        },
+
         // Free a grammar handle and release its session memory
         freeGrammar(handle) {
             const entry = handleToPlugin.get(handle);
@@ -200,9 +244,25 @@ async function loadHost() {
         const module = await import('/pkg/arborium_host.js');
         await module.default('/pkg/arborium_host_bg.wasm');
 
+        // Hand every grammar to the host by URL; each is fetched on first use
+        for (const entry of registry?.entries || []) {
+            const wasmPath = registry.dev_mode ? entry.local_wasm : entry.cdn_wasm;
+            module.registerGrammarModule(entry.language, wasmPath);
+        }
+        for (const language of coreLanguages) {
+            if (registry?.entries?.some(e => e.language === language)) {
+                module.preloadGrammarModule(language);
+            }
+        }
+
         hostModule = {
             highlight: module.highlight,
+            highlightNow: module.highlightNow,
             isLanguageAvailable: module.isLanguageAvailable,
+            parseTree: module.parseTree,
+            detectLanguage: module.detectLanguage,
+            highlightAuto: module.highlightAuto,
+            nodeAt: module.nodeAt,
         };
         return hostModule;
     } catch (e) {
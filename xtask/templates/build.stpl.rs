@@ -7,6 +7,7 @@ fn main() {
     // Layout inside the published crate:
     //   grammar/
     //     scanner.c      (optional, hand-written)
+    //     <name>/        (optional, another parser laid out the same way)
     //     src/
     //       parser.c
     //       grammar.json
@@ -20,6 +21,12 @@ fn main() {
     println!("cargo:rerun-if-changed={}", src_dir.join("parser.c").display());
 <% if has_scanner { %>
     println!("cargo:rerun-if-changed={}", grammar_dir.join("scanner.c").display());
+<% } %>
+<% for (path, scanner) in &own_parsers { %>
+    println!("cargo:rerun-if-changed={}", grammar_dir.join("<%= path %>/src/parser.c").display());
+<% if *scanner { %>
+    println!("cargo:rerun-if-changed={}", grammar_dir.join("<%= path %>/scanner.c").display());
+<% } %>
 <% } %>
 
     let mut build = cc::Build::new();
@@ -28,7 +35,8 @@ fn main() {
         .include(&src_dir)
         .include(&grammar_dir) // for common/ includes like "../common/scanner.h"
         .include(src_dir.join("tree_sitter"))
-        .opt_level_str("z") // optimize aggressively for size
+        .opt_level_str("<%= opt_level %>") // "z" (size) unless the grammar overrides it
+        .debug(false) // debuginfo for generated parse tables is never useful
         .warnings(false)
         .flag_if_supported("-Wno-unused-parameter")
         .flag_if_supported("-Wno-unused-but-set-variable")
@@ -45,6 +53,14 @@ fn main() {
     build.file(src_dir.join("parser.c"));
 <% if has_scanner { %>
     build.file(grammar_dir.join("scanner.c"));
+<% } %>
+<% for (path, scanner) in &own_parsers { %>
+
+    // The parser of a grammar sharing the crate, generated from grammar/<%= path %>/
+    build.file(grammar_dir.join("<%= path %>/src/parser.c"));
+<% if *scanner { %>
+    build.file(grammar_dir.join("<%= path %>/scanner.c"));
+<% } %>
 <% } %>
 
     build.compile("tree_sitter_<%= c_symbol %>");
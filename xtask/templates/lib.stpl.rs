@@ -6,12 +6,33 @@ use tree_sitter_language::LanguageFn;
 
 unsafe extern "C" {
     fn tree_sitter_<%= c_symbol %>() -> *const ();
+<% for parser in &own_parsers { %>
+    fn tree_sitter_<%= parser.c_symbol %>() -> *const ();
+<% } %>
 }
 
 /// Returns the <%= grammar_id %> tree-sitter [`LanguageFn`].
 pub const fn language() -> LanguageFn {
     unsafe { LanguageFn::from_raw(tree_sitter_<%= c_symbol %>) }
 }
+<% for parser in &own_parsers { %>
+
+/// Returns the <%= parser.grammar_id %> tree-sitter [`LanguageFn`], a parser of
+/// its own sharing this crate.
+pub const fn <%= parser.language_fn %>() -> LanguageFn {
+    unsafe { LanguageFn::from_raw(tree_sitter_<%= parser.c_symbol %>) }
+}
+<% } %>
+
+/// Returns the tree-sitter ABI version the <%= grammar_id %> parser was generated for.
+pub fn abi_version() -> usize {
+    // Every ABI version starts `TSLanguage` with the version number
+    unsafe { *tree_sitter_<%= c_symbol %>().cast::<u32>() as usize }
+}
+
+/// The upstream revision the <%= grammar_id %> grammar was vendored from, or
+/// `src-` and a hash of its sources for grammars without one.
+pub const GRAMMAR_COMMIT: &str = "<%= grammar_commit %>";
 
 <% if highlights_exists { %>
 <% if !highlights_prepend.is_empty() { %>
@@ -46,6 +67,25 @@ pub const INJECTIONS_QUERY: &str = include_str!("../queries/injections.scm");
 /// The injections query for <%= grammar_id %> (empty - no injections available).
 pub const INJECTIONS_QUERY: &str = "";
 <% } %>
+<% for shared in &shared_queries { %>
+<% if let Some((const_name, file)) = &shared.highlights { %>
+
+/// The highlights query for <%= shared.grammar_id %>, which shares this grammar:
+/// [`HIGHLIGHTS_QUERY`] followed by its own patterns.
+pub static <%= const_name %>: std::sync::LazyLock<String> = std::sync::LazyLock::new(|| {
+    let mut query = String::new();
+    query.push_str(&HIGHLIGHTS_QUERY);
+    query.push('\n');
+    query.push_str(include_str!("../queries/<%= file %>"));
+    query
+});
+<% } %>
+<% if let Some((const_name, file)) = &shared.injections { %>
+
+/// The injections query for <%= shared.grammar_id %>, which shares this grammar.
+pub const <%= const_name %>: &str = include_str!("../queries/<%= file %>");
+<% } %>
+<% } %>
 
 <% if locals_exists { %>
 /// The locals query for <%= grammar_id %>.
@@ -54,6 +94,41 @@ pub const LOCALS_QUERY: &str = include_str!("../queries/locals.scm");
 /// The locals query for <%= grammar_id %> (empty - no locals available).
 pub const LOCALS_QUERY: &str = "";
 <% } %>
+
+<% if folds_exists { %>
+/// The folds query for <%= grammar_id %>.
+pub const FOLDS_QUERY: &str = include_str!("../queries/folds.scm");
+<% } else { %>
+/// The folds query for <%= grammar_id %> (empty - no folds available).
+pub const FOLDS_QUERY: &str = "";
+<% } %>
+
+<% if tags_exists { %>
+/// The tags query for <%= grammar_id %>.
+pub const TAGS_QUERY: &str = include_str!("../queries/tags.scm");
+<% } else { %>
+/// The tags query for <%= grammar_id %> (empty - no tags available).
+pub const TAGS_QUERY: &str = "";
+<% } %>
+
+/// Returns a hash of [`HIGHLIGHTS_QUERY`], [`INJECTIONS_QUERY`] and
+/// [`LOCALS_QUERY`], computed on first use. It changes whenever any of them
+/// does.
+pub fn queries_hash() -> u64 {
+    static HASH: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+    *HASH.get_or_init(|| {
+        let highlights: &str = &HIGHLIGHTS_QUERY;
+        // FNV-1a, with a zero byte after each query
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for query in [highlights, INJECTIONS_QUERY, LOCALS_QUERY] {
+            for &byte in query.as_bytes().iter().chain(&[0]) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        hash
+    })
+}
 <% if !tests_cursed { %>
 
 #[cfg(test)]
@@ -76,6 +151,49 @@ mod tests {
         );
     }
 
+<% for shared in &shared_queries { %>
+    #[test]
+    fn test_grammar_<%= shared.grammar_id.replace('-', "_") %>() {
+        arborium_test_harness::test_grammar(
+            <%= shared.language_fn %>(),
+            "<%= shared.grammar_id %>",
+<% if let Some((const_name, _)) = &shared.highlights { %>
+            &<%= const_name %>,
+<% } else if !highlights_prepend.is_empty() { %>
+            &HIGHLIGHTS_QUERY,
+<% } else { %>
+            HIGHLIGHTS_QUERY,
+<% } %>
+<% if let Some((const_name, _)) = &shared.injections { %>
+            <%= const_name %>,
+<% } else { %>
+            INJECTIONS_QUERY,
+<% } %>
+            LOCALS_QUERY,
+            env!("CARGO_MANIFEST_DIR"),
+        );
+    }
+
+<% } %>
+<% if folds_exists { %>
+    #[test]
+    fn test_folds() {
+        arborium_test_harness::test_folds(language(), "<%= grammar_id %>", FOLDS_QUERY, env!("CARGO_MANIFEST_DIR"));
+    }
+
+<% } %>
+<% if tags_exists { %>
+    #[test]
+    fn test_tags() {
+        arborium_test_harness::test_tags(language(), "<%= grammar_id %>", TAGS_QUERY, env!("CARGO_MANIFEST_DIR"));
+    }
+
+<% } %>
+    #[test]
+    fn test_metadata() {
+        arborium_test_harness::test_metadata(language(), "<%= grammar_id %>", abi_version(), GRAMMAR_COMMIT);
+    }
+
     #[test]
     fn test_corpus() {
         arborium_test_harness::test_corpus(language(), "<%= grammar_id %>", env!("CARGO_MANIFEST_DIR"));
@@ -0,0 +1,26 @@
+//! Build script for the <%= grammar_id %> grammar plugin.
+
+fn main() {
+    // With `compressed-queries`, the queries are embedded deflated instead
+    // of as text; see `HighlightConfig::deflated`.
+    #[cfg(feature = "compressed-queries")]
+    compress_queries();
+}
+
+#[cfg(feature = "compressed-queries")]
+fn compress_queries() {
+    use <%= grammar_crate_name_snake %> as grammar;
+
+    let out_dir = std::path::PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR not set"));
+    // Use &* to handle both &str constants and LazyLock<String> statics
+    let queries: [(&str, &str); 3] = [
+        ("highlights", &*grammar::HIGHLIGHTS_QUERY),
+        ("injections", grammar::INJECTIONS_QUERY),
+        ("locals", grammar::LOCALS_QUERY),
+    ];
+    for (name, source) in queries {
+        let compressed = miniz_oxide::deflate::compress_to_vec(source.as_bytes(), 10);
+        std::fs::write(out_dir.join(format!("{name}.scm.deflate")), compressed)
+            .expect("failed to write compressed query");
+    }
+}
@@ -1,8 +1,8 @@
 //! <%= grammar_id %> grammar plugin for arborium.
 
 use wasm_bindgen::prelude::*;
-use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
-use arborium_wire::{Utf8ParseResult, Utf16ParseResult};
+use arborium_plugin_runtime::{HighlightConfig, PluginRuntime, TreeLimits};
+use arborium_wire::{QueryKind, Utf8ParseResult, Utf16ParseResult};
 use std::cell::RefCell;
 
 thread_local! {
@@ -13,20 +13,48 @@ fn get_or_init_runtime() -> &'static RefCell<Option<PluginRuntime>> {
     RUNTIME.with(|r| {
         let mut runtime = r.borrow_mut();
         if runtime.is_none() {
+            // Queries compile on first parse, keeping startup cheap
+            #[cfg(not(feature = "compressed-queries"))]
             // Use &* to handle both &str constants and LazyLock<String> statics
-            let config = HighlightConfig::new(
+            let config = HighlightConfig::lazy(
                 <%= grammar_crate_name_snake %>::language(),
                 &*<%= grammar_crate_name_snake %>::HIGHLIGHTS_QUERY,
                 <%= grammar_crate_name_snake %>::INJECTIONS_QUERY,
                 <%= grammar_crate_name_snake %>::LOCALS_QUERY,
-            )
-            .expect("failed to create highlight config");
+            );
+            // Deflated by build.rs
+            #[cfg(feature = "compressed-queries")]
+            let config = HighlightConfig::deflated(
+                <%= grammar_crate_name_snake %>::language(),
+                include_bytes!(concat!(env!("OUT_DIR"), "/highlights.scm.deflate")),
+                include_bytes!(concat!(env!("OUT_DIR"), "/injections.scm.deflate")),
+                include_bytes!(concat!(env!("OUT_DIR"), "/locals.scm.deflate")),
+            );
+            let config = config
+                .with_query_hash("<%= query_hash %>")
+                .with_tags(<%= grammar_crate_name_snake %>::TAGS_QUERY);
             *runtime = Some(PluginRuntime::new(config));
         }
         unsafe { &*(r as *const _) }
     })
 }
 
+// Raw exports for native hosts (see `arborium_wire::abi`)
+arborium_plugin_runtime::export_abi!(get_or_init_runtime());
+
+// Manifest read by native hosts without instantiating the plugin
+arborium_plugin_runtime::export_manifest!(arborium_plugin_runtime::manifest::ManifestFields {
+    wire_version: arborium_wire::WIRE_VERSION,
+    language_id: "<%= grammar_id %>",
+    aliases: &[<%- aliases %>],
+    extensions: &[<%- extensions %>],
+    provides_injections: !<%= grammar_crate_name_snake %>::INJECTIONS_QUERY.is_empty(),
+    provides_locals: !<%= grammar_crate_name_snake %>::LOCALS_QUERY.is_empty(),
+    query_hash: "<%= query_hash %>",
+    grammar_commit: <%= grammar_crate_name_snake %>::GRAMMAR_COMMIT,
+    version: "<%= version %>",
+});
+
 /// Returns the language ID for this grammar plugin.
 #[wasm_bindgen]
 pub fn language_id() -> String {
@@ -40,6 +68,56 @@ pub fn injection_languages() -> Vec<String> {
     vec![]
 }
 
+/// Returns the capture names of the grammar's queries, e.g. for theming.
+#[wasm_bindgen]
+pub fn capture_names() -> Vec<String> {
+    get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .config()
+        .capture_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Compiles the grammar's queries now instead of on the first parse, for
+/// hosts that would rather pay for it upfront.
+#[wasm_bindgen]
+pub fn precompile() -> Result<(), JsValue> {
+    get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .config()
+        .precompile()
+        .map_err(|e| JsValue::from_str(&format!("query error: {}", e.message)))
+}
+
+/// Returns the source of the `highlights`, `injections` or `locals` query
+/// this plugin was built with.
+#[wasm_bindgen]
+pub fn query_source(kind: &str) -> Result<String, JsValue> {
+    let kind = QueryKind::from_name(kind)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown query kind: {}", kind)))?;
+    get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .config()
+        .query_source(kind)
+        .map(|source| source.to_string())
+        .map_err(|e| JsValue::from_str(&format!("query error: {}", e.message)))
+}
+
+/// Returns the hash of the queries this plugin was built with, for cache
+/// invalidation.
+#[wasm_bindgen]
+pub fn query_hash() -> String {
+    "<%= query_hash %>".to_string()
+}
+
 /// Creates a new parser session and returns its ID.
 #[wasm_bindgen]
 pub fn create_session() -> u32 {
@@ -62,12 +140,16 @@ pub fn free_session(session: u32) {
 
 /// Sets the text for a parser session.
 #[wasm_bindgen]
-pub fn set_text(session: u32, text: &str) {
+pub fn set_text(session: u32, text: &str) -> Result<(), JsValue> {
     get_or_init_runtime()
         .borrow_mut()
         .as_mut()
         .expect("runtime not initialized")
-        .set_text(session, text);
+        .guarded(session, |runtime| {
+            runtime.set_text(session, text);
+            Ok(())
+        })
+        .map_err(|e| JsValue::from_str(&format!("parse error: {}", e.message)))
 }
 
 /// Parses the text in a session and returns spans with UTF-8 byte offsets.
@@ -80,7 +162,7 @@ pub fn parse(session: u32) -> Result<JsValue, JsValue> {
         .borrow_mut()
         .as_mut()
         .expect("runtime not initialized")
-        .parse(session);
+        .guarded(session, |runtime| runtime.parse(session));
 
     match result {
         Ok(r) => serde_wasm_bindgen::to_value(&r)
@@ -99,7 +181,65 @@ pub fn parse_utf16(session: u32) -> Result<JsValue, JsValue> {
         .borrow_mut()
         .as_mut()
         .expect("runtime not initialized")
-        .parse_utf16(session);
+        .guarded(session, |runtime| runtime.parse_utf16(session));
+
+    match result {
+        Ok(r) => serde_wasm_bindgen::to_value(&r)
+            .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e))),
+        Err(e) => Err(JsValue::from_str(&format!("parse error: {}", e.message))),
+    }
+}
+
+/// Returns the definitions and references of symbols in a session's text,
+/// e.g. for an outline, with UTF-8 byte offsets.
+///
+/// Empty for grammars without a tags query.
+#[wasm_bindgen]
+pub fn tags(session: u32) -> Result<JsValue, JsValue> {
+    let result = get_or_init_runtime()
+        .borrow_mut()
+        .as_mut()
+        .expect("runtime not initialized")
+        .guarded(session, |runtime| runtime.tags(session));
+
+    match result {
+        Ok(r) => serde_wasm_bindgen::to_value(&r)
+            .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e))),
+        Err(e) => Err(JsValue::from_str(&format!("parse error: {}", e.message))),
+    }
+}
+
+/// Returns the syntax tree of the text in a session, as nested named nodes.
+///
+/// At most `max_nodes` nodes are returned, down to `max_depth` levels below
+/// the root. Offsets are UTF-8 byte offsets.
+#[wasm_bindgen]
+pub fn parse_tree(session: u32, max_depth: u32, max_nodes: u32) -> Result<JsValue, JsValue> {
+    let limits = TreeLimits {
+        max_depth,
+        max_nodes,
+    };
+    let result = get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .syntax_tree(session, &limits);
+
+    match result {
+        Ok(r) => serde_wasm_bindgen::to_value(&r)
+            .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e))),
+        Err(e) => Err(JsValue::from_str(&format!("parse error: {}", e.message))),
+    }
+}
+
+/// Returns the named nodes covering a UTF-8 byte offset, from the root down.
+#[wasm_bindgen]
+pub fn node_at(session: u32, offset: u32) -> Result<JsValue, JsValue> {
+    let result = get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .node_at(session, offset);
 
     match result {
         Ok(r) => serde_wasm_bindgen::to_value(&r)
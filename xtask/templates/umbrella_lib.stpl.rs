@@ -88,9 +88,14 @@
 mod error;
 mod highlighter;
 pub(crate) mod store;
+mod tokens;
 
 // Public modules
 pub mod advanced;
+pub mod cache;
+pub mod detect;
+pub mod lines;
+pub mod lsp;
 
 /// Theme system for ANSI output.
 ///
@@ -103,9 +108,10 @@ pub mod theme {
 pub use error::Error;
 pub use highlighter::{AnsiHighlighter, Highlighter};
 pub use store::GrammarStore;
+pub use tokens::{Token, Tokens, tokens};
 
 // Configuration types (re-exported from arborium-highlight)
-pub use arborium_highlight::HtmlFormat;
+pub use arborium_highlight::{ClassStyle, EscapeMode, HtmlFormat, HtmlOptions};
 
 /// Configuration for highlighting.
 ///
@@ -123,6 +129,11 @@ pub struct Config {
     ///
     /// See [`HtmlFormat`] for options.
     pub html_format: HtmlFormat,
+
+    /// Additional HTML rendering options.
+    ///
+    /// See [`HtmlOptions`] for options.
+    pub html_options: HtmlOptions,
 }
 
 impl Default for Config {
@@ -130,6 +141,7 @@ impl Default for Config {
         Self {
             max_injection_depth: 3,
             html_format: HtmlFormat::default(),
+            html_options: HtmlOptions::default(),
         }
     }
 }
@@ -139,6 +151,7 @@ impl From<Config> for arborium_highlight::HighlightConfig {
         arborium_highlight::HighlightConfig {
             max_injection_depth: config.max_injection_depth,
             html_format: config.html_format,
+            html_options: config.html_options,
         }
     }
 }
@@ -154,6 +167,12 @@ mod wasm;
 // Highlight names constant
 use arborium_theme::highlights;
 
+/// Hash of the bundled grammar queries, computed when this file was generated.
+///
+/// Part of every [`cache`] fingerprint, so cached output is invalidated when
+/// any query changes.
+pub(crate) const QUERY_HASH: &str = "<%= query_hash %>";
+
 /// Standard highlight names used for syntax highlighting.
 ///
 /// These names are used to configure tree-sitter's `HighlightConfiguration`.
@@ -163,7 +182,8 @@ pub const HIGHLIGHT_NAMES: [&str; highlights::COUNT] = highlights::names();
 /// Detect the language from a file path or name.
 ///
 /// Extracts the file extension and maps it to a canonical language identifier.
-/// Returns `None` if the extension is not recognized.
+/// A compound extension wins over its last part, so `welcome.blade.php` is
+/// Blade, not PHP. Returns `None` if the extension is not recognized.
 ///
 /// # Example
 ///
@@ -176,13 +196,27 @@ pub const HIGHLIGHT_NAMES: [&str; highlights::COUNT] = highlights::names();
 /// assert_eq!(detect_language("unknown.xyz"), None);
 /// ```
 pub fn detect_language(path: &str) -> Option<&'static str> {
+    // Compound extensions (`blade.php`) are the last two parts of the name
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let compound = name
+        .rfind('.')
+        .map(|last| name[..last].rfind('.').map_or(name, |dot| &name[dot + 1..]));
+    if let Some(language) = compound.and_then(language_for_extension) {
+        return Some(language);
+    }
+
     // Extract extension from path
     let ext = path
         .rsplit('.')
         .next()
         .filter(|e| !e.contains('/') && !e.contains('\\'))?;
 
-    // Map extension to canonical language ID
+    language_for_extension(ext)
+}
+
+/// Map a file extension (`rs`, `blade.php`) or alias to a canonical
+/// language identifier, without falling back to a shorter extension.
+pub(crate) fn language_for_extension(ext: &str) -> Option<&'static str> {
     Some(match ext.to_lowercase().as_str() {
 <% for (ext, lang) in extensions { %>
         "<%= ext %>" => "<%= lang %>",
@@ -191,6 +225,128 @@ pub fn detect_language(path: &str) -> Option<&'static str> {
     })
 }
 
+/// Metadata about a supported language, for building language pickers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanguageInfo {
+    /// Canonical language identifier (e.g., `"rust"`), as accepted by
+    /// [`Highlighter::highlight`].
+    pub id: &'static str,
+    /// Human-readable display name (e.g., `"Rust"`).
+    pub name: &'static str,
+    /// Alternative names accepted in place of `id` (e.g., `"rs"`).
+    pub aliases: &'static [&'static str],
+    /// File extensions recognized by [`detect_language`] for this language.
+    /// This includes extensions shared with another language, like `.m` for
+    /// Objective-C and MATLAB, which [`detect_language`] maps to the one that
+    /// claims them and [`detect::detect`] resolves from the contents.
+    pub extensions: &'static [&'static str],
+    /// Category used to group languages (e.g., `"code"`, `"markup"`).
+    pub category: &'static str,
+    /// Upstream revision the grammar was vendored from, or `src-` and a
+    /// hash of its sources for grammars maintained here.
+    pub grammar_commit: &'static str,
+}
+
+impl LanguageInfo {
+    /// The tree-sitter ABI version the language's parser was generated for.
+    ///
+    /// Zero if `id` isn't a language enabled via feature flags.
+    pub fn abi_version(&self) -> usize {
+        match self.id {
+<% for info in language_infos { %>
+            #[cfg(feature = "lang-<%= info.id %>")]
+            <%- format!("{:?}", info.id) %> => <%= info.crate_name %>::abi_version(),
+<% } %>
+            _ => 0,
+        }
+    }
+
+    /// A hash of the grammar crate's highlights, injections and locals
+    /// queries, computed on first use.
+    ///
+    /// Zero if `id` isn't a language enabled via feature flags.
+    pub fn queries_hash(&self) -> u64 {
+        match self.id {
+<% for info in language_infos { %>
+            #[cfg(feature = "lang-<%= info.id %>")]
+            <%- format!("{:?}", info.id) %> => <%= info.crate_name %>::queries_hash(),
+<% } %>
+            _ => 0,
+        }
+    }
+
+    /// The named node kinds of the language's grammar, sorted, as its queries
+    /// can refer to them. Hidden kinds (`_expression`) are left out, since
+    /// they never appear in a tree.
+    ///
+    /// Empty if `id` isn't a language enabled via feature flags.
+    pub fn node_kinds(&self) -> Vec<&'static str> {
+        let Some(language) = get_language(self.id) else {
+            return Vec::new();
+        };
+        let mut kinds: Vec<_> = (0..language.node_kind_count() as u16)
+            .filter(|&id| language.node_kind_is_named(id) && language.node_kind_is_visible(id))
+            .filter_map(|id| language.node_kind_for_id(id))
+            .collect();
+        kinds.sort_unstable();
+        kinds.dedup();
+        kinds
+    }
+
+    /// The field names of the language's grammar, sorted.
+    ///
+    /// Empty if `id` isn't a language enabled via feature flags.
+    pub fn field_names(&self) -> Vec<&'static str> {
+        let Some(language) = get_language(self.id) else {
+            return Vec::new();
+        };
+        let mut fields: Vec<_> = (1..=language.field_count() as u16)
+            .filter_map(|id| language.field_name_for_id(id))
+            .collect();
+        fields.sort_unstable();
+        fields
+    }
+}
+
+/// All user-facing languages enabled via feature flags, sorted by ID.
+const LANGUAGES: &[LanguageInfo] = &[
+<% for info in language_infos { %>
+    #[cfg(feature = "lang-<%= info.id %>")]
+    LanguageInfo {
+        id: <%- format!("{:?}", info.id) %>,
+        name: <%- format!("{:?}", info.name) %>,
+        aliases: &[<% for alias in &info.aliases { %><%- format!("{:?}", alias) %>, <% } %>],
+        extensions: &[<%- format!("{:?}", info.id) %>, <% for alias in &info.aliases { %><%- format!("{:?}", alias) %>, <% } %><% for ext in &info.contested_extensions { %><%- format!("{:?}", ext) %>, <% } %>],
+        category: <%- format!("{:?}", info.tag) %>,
+        grammar_commit: <%= info.crate_name %>::GRAMMAR_COMMIT,
+    },
+<% } %>
+];
+
+/// Returns metadata for every language enabled via feature flags.
+///
+/// This is generated from the same registry as the grammar store, so every
+/// `id` listed here can be highlighted.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// for lang in arborium::supported_languages_detailed() {
+///     println!("{} ({}): {:?}", lang.name, lang.id, lang.extensions);
+/// }
+/// ```
+pub fn supported_languages_detailed() -> &'static [LanguageInfo] {
+    LANGUAGES
+}
+
+/// Returns the IDs of every language enabled via feature flags.
+///
+/// See [`supported_languages_detailed`] for display names, aliases and
+/// categories.
+pub fn supported_languages() -> Vec<&'static str> {
+    LANGUAGES.iter().map(|lang| lang.id).collect()
+}
+
 // =============================================================================
 // Language grammar re-exports based on enabled features.
 // Each module provides:
@@ -198,6 +354,10 @@ pub fn detect_language(path: &str) -> Option<&'static str> {
 // - `HIGHLIGHTS_QUERY` - The highlight query string
 // - `INJECTIONS_QUERY` - The injection query string
 // - `LOCALS_QUERY` - The locals query string
+// - `FOLDS_QUERY` - The folds query string
+// - `TAGS_QUERY` - The tags query string
+// - `GRAMMAR_COMMIT`, `abi_version()`, `queries_hash()` - Grammar metadata,
+//   also on [`LanguageInfo`], along with the node kind and field inventory
 // =============================================================================
 
 <% for (crate_name, grammar_id) in grammars { %>
@@ -229,6 +389,49 @@ pub fn get_language(name: &str) -> Option<tree_sitter::Language> {
 <% for (crate_name, grammar_id) in grammars { %>
         #[cfg(feature = "lang-<%= grammar_id %>")]
         "<%= grammar_id %>" => Some(<%= crate_name.replace('-', "_") %>::language().into()),
+<% } %>
+<% for (crate_name, _, grammar_id, language_fn) in shared_grammars { %>
+        #[cfg(feature = "lang-<%= grammar_id %>")]
+        "<%= grammar_id %>" => Some(<%= crate_name.replace('-', "_") %>::<%= language_fn %>().into()),
+<% } %>
+        _ => None,
+    }
+}
+
+/// Returns the folds query for the given language name.
+///
+/// Like [`get_language`], this only knows languages enabled via feature
+/// flags. The query is empty for grammars that don't ship a `folds.scm`;
+/// its `@fold` captures mark the nodes an editor can collapse.
+pub fn get_folds_query(name: &str) -> Option<&'static str> {
+    match name {
+<% for (crate_name, grammar_id) in grammars { %>
+        #[cfg(feature = "lang-<%= grammar_id %>")]
+        "<%= grammar_id %>" => Some(<%= crate_name.replace('-', "_") %>::FOLDS_QUERY),
+<% } %>
+<% for (crate_name, _, grammar_id, _) in shared_grammars { %>
+        #[cfg(feature = "lang-<%= grammar_id %>")]
+        "<%= grammar_id %>" => Some(<%= crate_name.replace('-', "_") %>::FOLDS_QUERY),
+<% } %>
+        _ => None,
+    }
+}
+
+/// Returns the tags query for the given language name.
+///
+/// Like [`get_language`], this only knows languages enabled via feature
+/// flags. The query is empty for grammars that don't ship a `tags.scm`;
+/// its `@definition.*` and `@reference.*` captures, each paired with a
+/// `@name`, make up a file's outline.
+pub fn get_tags_query(name: &str) -> Option<&'static str> {
+    match name {
+<% for (crate_name, grammar_id) in grammars { %>
+        #[cfg(feature = "lang-<%= grammar_id %>")]
+        "<%= grammar_id %>" => Some(<%= crate_name.replace('-', "_") %>::TAGS_QUERY),
+<% } %>
+<% for (crate_name, _, grammar_id, _) in shared_grammars { %>
+        #[cfg(feature = "lang-<%= grammar_id %>")]
+        "<%= grammar_id %>" => Some(<%= crate_name.replace('-', "_") %>::TAGS_QUERY),
 <% } %>
         _ => None,
     }
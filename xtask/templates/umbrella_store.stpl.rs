@@ -99,13 +99,13 @@ impl GrammarStore {
     #[allow(unused_variables)]
     fn compile_grammar(language: &str) -> Option<CompiledGrammar> {
         macro_rules! try_lang {
-            ($feature:literal, $module:ident, $primary:literal) => {
+            ($feature:literal, $module:ident, $primary:literal, $language:ident, $highlights:ident, $injections:ident) => {
                 #[cfg(feature = $feature)]
                 if language == $primary {
                     let config = GrammarConfig {
-                        language: crate::$module::language().into(),
-                        highlights_query: &crate::$module::HIGHLIGHTS_QUERY,
-                        injections_query: crate::$module::INJECTIONS_QUERY,
+                        language: crate::$module::$language().into(),
+                        highlights_query: &crate::$module::$highlights,
+                        injections_query: crate::$module::$injections,
                         locals_query: crate::$module::LOCALS_QUERY,
                     };
                     return CompiledGrammar::new(config).ok();
@@ -114,8 +114,8 @@ impl GrammarStore {
         }
 
         // All languages (generated from arborium.kdl)
-<% for (feature, module, grammar_id) in languages { %>
-        try_lang!("<%= feature %>", <%= module %>, "<%= grammar_id %>");
+<% for (feature, module, grammar_id, language_fn, highlights, injections) in languages { %>
+        try_lang!("<%= feature %>", <%= module %>, "<%= grammar_id %>", <%= language_fn %>, <%= highlights %>, <%= injections %>);
 <% } %>
 
         None